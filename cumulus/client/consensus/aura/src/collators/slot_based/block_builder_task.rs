@@ -0,0 +1,494 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+use codec::{Codec, Encode};
+use cumulus_client_collator::service::ServiceInterface as CollatorServiceInterface;
+use cumulus_client_consensus_common::{
+	self as consensus_common, ParachainBlockImportMarker, ParentSearchParams, PotentialParent,
+};
+use cumulus_client_consensus_proposer::ProposerInterface;
+use cumulus_primitives_aura::AuraUnincludedSegmentApi;
+use cumulus_primitives_core::{
+	relay_chain::{BlockId as RBlockId, Hash as RelayHash},
+	CollectCollationInfo, PersistedValidationData,
+};
+use cumulus_relay_chain_interface::RelayChainInterface;
+use polkadot_primitives::{Id as ParaId, OccupiedCoreAssumption};
+
+use futures::prelude::*;
+use sc_client_api::{backend::AuxStore, BlockBackend, BlockOf};
+use sc_consensus::BlockImport;
+use sc_consensus_aura::standalone as aura_internal;
+use sc_utils::mpsc::TracingUnboundedSender;
+use sp_api::ProvideRuntimeApi;
+use sp_application_crypto::AppPublic;
+use sp_blockchain::HeaderBackend;
+use sp_consensus_aura::{AuraApi, Slot, SlotDuration};
+use sp_core::crypto::Pair;
+use sp_inherents::CreateInherentDataProviders;
+use sp_keystore::KeystorePtr;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, Member};
+use sp_timestamp::Timestamp;
+use std::{
+	convert::TryFrom,
+	sync::Arc,
+	time::{Duration, SystemTime},
+};
+
+use super::CollatorMessage;
+use crate::{
+	collator as collator_util,
+	collators::{can_build_upon, cores_scheduled_for_para, max_ancestry_lookback},
+};
+
+// This is an arbitrary value which is likely guaranteed to exceed any reasonable
+// limit, as it would correspond to 10 non-included blocks.
+//
+// Since we only search for parent blocks which have already been imported,
+// we can guarantee that all imported blocks respect the unincluded segment
+// rules specified by the parachain's runtime and thus will never be too deep.
+const PARENT_SEARCH_DEPTH: usize = 10;
+
+/// Parameters for [`run_block_builder`].
+pub struct BuilderTaskParams<Block: BlockT, BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS> {
+	/// Inherent data providers. Only non-consensus inherent data should be provided, i.e.
+	/// the timestamp, slot, and paras inherents should be omitted, as they are set by this
+	/// collator.
+	pub create_inherent_data_providers: CIDP,
+	/// Used to actually import blocks.
+	pub block_import: BI,
+	/// The underlying para client.
+	pub para_client: Arc<Client>,
+	/// The para client's backend, used to access the database.
+	pub para_backend: Arc<Backend>,
+	/// A handle to the relay-chain client.
+	pub relay_client: RClient,
+	/// A validation code hash provider, used to get the current validation code hash.
+	pub code_hash_provider: CHP,
+	/// The underlying keystore, which should contain Aura consensus keys.
+	pub keystore: KeystorePtr,
+	/// The para's ID.
+	pub para_id: ParaId,
+	/// The underlying block proposer this should call into.
+	pub proposer: Proposer,
+	/// The generic collator service used to plug into this consensus engine.
+	pub collator_service: CS,
+	/// The maximum amount of time to spend authoring each block.
+	pub authoring_duration: Duration,
+	/// Channel to send built blocks to the collation task.
+	pub collator_sender: TracingUnboundedSender<CollatorMessage<Block>>,
+	/// The length of slots in the relay chain.
+	pub relay_chain_slot_duration: Duration,
+	/// Drift every slot by this duration.
+	pub slot_drift: Duration,
+}
+
+/// Information about a parachain slot.
+#[derive(Debug)]
+struct SlotInfo {
+	/// The timestamp at the start of the slot.
+	timestamp: Timestamp,
+	/// The slot number.
+	slot: Slot,
+	/// The duration of the slot.
+	slot_duration: SlotDuration,
+}
+
+/// Manages the timing of parachain slots.
+struct SlotTimer<Block, Client, P> {
+	client: Arc<Client>,
+	drift: Duration,
+	_marker: std::marker::PhantomData<(Block, Box<dyn Fn(P) + Send + Sync + 'static>)>,
+}
+
+/// Returns the duration until the start of the next slot, taking the drift into account, as well
+/// as the timestamp at the start of that slot.
+fn time_until_next_slot(slot_duration: Duration, drift: Duration) -> (Duration, Timestamp) {
+	let now = SystemTime::now()
+		.duration_since(SystemTime::UNIX_EPOCH)
+		.unwrap_or_default()
+		.as_millis()
+		.saturating_sub(drift.as_millis());
+
+	let slot_duration_ms = slot_duration.as_millis().max(1);
+	let next_slot_time = ((now + slot_duration_ms) / slot_duration_ms) * slot_duration_ms;
+	let remaining_millis = next_slot_time - now;
+
+	(Duration::from_millis(remaining_millis as u64), Timestamp::new(next_slot_time as u64))
+}
+
+impl<Block, Client, P> SlotTimer<Block, Client, P>
+where
+	Block: BlockT,
+	Client: ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore + Send + Sync + 'static,
+	Client::Api: AuraApi<Block, P::Public>,
+	P: Pair,
+	P::Public: AppPublic + Member + Codec,
+	P::Signature: TryFrom<Vec<u8>> + Member + Codec,
+{
+	pub fn new_with_drift(client: Arc<Client>, drift: Duration) -> Self {
+		Self { client, drift, _marker: Default::default() }
+	}
+
+	/// Returns a future that resolves at the start of the next parachain slot.
+	///
+	/// The slot duration is read from the runtime at the best parachain block every time, so
+	/// changes to it are picked up.
+	pub async fn wait_until_next_slot(&self) -> Result<SlotInfo, ()> {
+		let best_hash = self.client.info().best_hash;
+		let slot_duration = match aura_internal::slot_duration_at::<P::Public, _, _>(
+			&*self.client,
+			best_hash,
+		) {
+			Ok(slot_duration) => slot_duration,
+			Err(err) => {
+				tracing::error!(target: crate::LOG_TARGET, ?err, "Failed to fetch slot duration from runtime.");
+				return Err(())
+			},
+		};
+
+		let (time_until_next_slot, timestamp) =
+			time_until_next_slot(slot_duration.as_duration(), self.drift);
+		tracing::trace!(
+			target: crate::LOG_TARGET,
+			?time_until_next_slot,
+			?timestamp,
+			"Waiting for the next parachain slot."
+		);
+		futures_timer::Delay::new(time_until_next_slot).await;
+
+		Ok(SlotInfo {
+			slot: Slot::from_timestamp(timestamp, slot_duration),
+			timestamp,
+			slot_duration,
+		})
+	}
+}
+
+/// Run the block builder task of the slot-based collator.
+///
+/// At the start of every parachain slot, this builds a block on top of the best parachain block
+/// that can be built upon at the current best relay chain block, given that we are the author of
+/// the slot. The block is imported, announced and sent to the collation task afterwards.
+pub async fn run_block_builder<Block, P, BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS>(
+	params: BuilderTaskParams<Block, BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS>,
+) where
+	Block: BlockT,
+	Client: ProvideRuntimeApi<Block>
+		+ BlockOf
+		+ AuxStore
+		+ HeaderBackend<Block>
+		+ BlockBackend<Block>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api:
+		AuraApi<Block, P::Public> + CollectCollationInfo<Block> + AuraUnincludedSegmentApi<Block>,
+	Backend: sc_client_api::Backend<Block> + 'static,
+	RClient: RelayChainInterface + Clone + 'static,
+	CIDP: CreateInherentDataProviders<Block, ()> + 'static,
+	CIDP::InherentDataProviders: Send,
+	BI: BlockImport<Block> + ParachainBlockImportMarker + Send + Sync + 'static,
+	Proposer: ProposerInterface<Block> + Send + Sync + 'static,
+	CS: CollatorServiceInterface<Block> + Send + Sync + 'static,
+	CHP: consensus_common::ValidationCodeHashProvider<Block::Hash> + Send + 'static,
+	P: Pair,
+	P::Public: AppPublic + Member + Codec,
+	P::Signature: TryFrom<Vec<u8>> + Member + Codec,
+{
+	let BuilderTaskParams {
+		relay_client,
+		create_inherent_data_providers,
+		para_client,
+		keystore,
+		block_import,
+		para_id,
+		proposer,
+		collator_service,
+		collator_sender,
+		code_hash_provider,
+		authoring_duration,
+		para_backend,
+		relay_chain_slot_duration,
+		slot_drift,
+	} = params;
+
+	let slot_timer = SlotTimer::<_, _, P>::new_with_drift(para_client.clone(), slot_drift);
+
+	let mut collator = {
+		let params = collator_util::Params {
+			create_inherent_data_providers,
+			block_import,
+			relay_client: relay_client.clone(),
+			keystore: keystore.clone(),
+			para_id,
+			proposer,
+			collator_service,
+		};
+
+		collator_util::Collator::<Block, P, _, _, _, _, _>::new(params)
+	};
+
+	loop {
+		// We wait here until the next slot arrives.
+		let para_slot = match slot_timer.wait_until_next_slot().await {
+			Ok(para_slot) => para_slot,
+			Err(()) => return,
+		};
+
+		let relay_parent = match relay_client.best_block_hash().await {
+			Ok(hash) => hash,
+			Err(err) => {
+				tracing::error!(target: crate::LOG_TARGET, ?err, "Unable to fetch best relay chain block hash.");
+				continue
+			},
+		};
+
+		let relay_parent_header = match relay_client.header(RBlockId::hash(relay_parent)).await {
+			Ok(Some(header)) => header,
+			Ok(None) => {
+				tracing::warn!(target: crate::LOG_TARGET, ?relay_parent, "Relay chain header not available.");
+				continue
+			},
+			Err(err) => {
+				tracing::error!(target: crate::LOG_TARGET, ?err, ?relay_parent, "Unable to fetch relay chain header.");
+				continue
+			},
+		};
+
+		let scheduled_cores = cores_scheduled_for_para(relay_parent, para_id, &relay_client).await;
+		if scheduled_cores.is_empty() {
+			tracing::trace!(
+				target: crate::LOG_TARGET,
+				?relay_parent,
+				?para_id,
+				"Para is not scheduled on any core, skipping slot",
+			);
+			continue
+		}
+
+		// Blocks built in consecutive parachain slots are spread over the assigned cores.
+		let core_index = scheduled_cores[(*para_slot.slot % scheduled_cores.len() as u64) as usize];
+
+		let max_pov_size = match relay_client
+			.persisted_validation_data(relay_parent, para_id, OccupiedCoreAssumption::Included)
+			.await
+		{
+			Ok(None) => continue,
+			Ok(Some(pvd)) => pvd.max_pov_size,
+			Err(err) => {
+				tracing::error!(target: crate::LOG_TARGET, ?err, "Failed to gather information from relay-client");
+				continue
+			},
+		};
+
+		let (included_block, parent) = match find_parent::<Block>(
+			relay_parent,
+			para_id,
+			&*para_backend,
+			&relay_client,
+		)
+		.await
+		{
+			Some(value) => value,
+			None => continue,
+		};
+
+		let parent_hash = parent.hash;
+		let parent_header = parent.header;
+
+		if let Some((relay_slot, _)) = consensus_common::relay_slot_and_timestamp(
+			&relay_parent_header,
+			relay_chain_slot_duration,
+		) {
+			tracing::debug!(
+				target: crate::LOG_TARGET,
+				?relay_slot,
+				para_slot = ?para_slot.slot,
+				timestamp = ?para_slot.timestamp,
+				slot_duration = ?para_slot.slot_duration,
+				?relay_chain_slot_duration,
+				"Parachain slot started"
+			);
+		}
+
+		let slot_claim = match can_build_upon::<_, _, P>(
+			para_slot.slot,
+			para_slot.timestamp,
+			parent_hash,
+			included_block,
+			&*para_client,
+			&keystore,
+		)
+		.await
+		{
+			Some(slot) => slot,
+			None => {
+				tracing::debug!(
+					target: crate::LOG_TARGET,
+					?core_index,
+					slot_info = ?para_slot,
+					unincluded_segment_len = parent.depth,
+					relay_parent = %relay_parent,
+					included = ?included_block,
+					parent = ?parent_hash,
+					"Not building block."
+				);
+				continue
+			},
+		};
+
+		tracing::debug!(
+			target: crate::LOG_TARGET,
+			?core_index,
+			slot_info = ?para_slot,
+			unincluded_segment_len = parent.depth,
+			relay_parent = %relay_parent,
+			included = ?included_block,
+			parent = ?parent_hash,
+			"Building block."
+		);
+
+		let validation_data = PersistedValidationData {
+			parent_head: parent_header.encode().into(),
+			relay_parent_number: *relay_parent_header.number(),
+			relay_parent_storage_root: *relay_parent_header.state_root(),
+			max_pov_size,
+		};
+
+		let (parachain_inherent_data, other_inherent_data) = match collator
+			.create_inherent_data(
+				relay_parent,
+				&validation_data,
+				parent_hash,
+				slot_claim.timestamp(),
+			)
+			.await
+		{
+			Err(err) => {
+				tracing::error!(target: crate::LOG_TARGET, ?err);
+				continue
+			},
+			Ok(x) => x,
+		};
+
+		let validation_code_hash = match code_hash_provider.code_hash_at(parent_hash) {
+			None => {
+				tracing::error!(target: crate::LOG_TARGET, ?parent_hash, "Could not fetch validation code hash");
+				continue
+			},
+			Some(v) => v,
+		};
+
+		// Never spend more time authoring than a single parachain slot lasts, otherwise the
+		// next slot would be missed.
+		let authoring_duration = authoring_duration.min(para_slot.slot_duration.as_duration());
+
+		let candidate = match collator
+			.build_block_and_import(
+				&parent_header,
+				&slot_claim,
+				None,
+				(parachain_inherent_data, other_inherent_data),
+				authoring_duration,
+				// Set the block limit to 50% of the maximum PoV size.
+				//
+				// TODO: If we got benchmarking that includes the proof size,
+				// we should be able to use the maximum pov size.
+				(validation_data.max_pov_size / 2) as usize,
+			)
+			.await
+		{
+			Ok(Some(candidate)) => candidate,
+			Ok(None) => {
+				tracing::debug!(target: crate::LOG_TARGET, "No block proposal");
+				continue
+			},
+			Err(err) => {
+				tracing::error!(target: crate::LOG_TARGET, ?err);
+				continue
+			},
+		};
+
+		let new_block_hash = candidate.block.header().hash();
+
+		// Announce the newly built block to our peers.
+		collator.collator_service().announce_block(new_block_hash, None);
+
+		if let Err(err) = collator_sender.unbounded_send(CollatorMessage {
+			relay_parent,
+			parent_header,
+			parachain_candidate: candidate,
+			hash: new_block_hash,
+			validation_code_hash,
+			core_index,
+		}) {
+			tracing::error!(target: crate::LOG_TARGET, ?err, "Unable to send block to collation task.");
+			return
+		}
+	}
+}
+
+/// Finds the block to build on top of at the given relay parent.
+///
+/// Returns the hash of the included block together with the deepest potential parent.
+async fn find_parent<Block>(
+	relay_parent: RelayHash,
+	para_id: ParaId,
+	para_backend: &impl sc_client_api::Backend<Block>,
+	relay_client: &impl RelayChainInterface,
+) -> Option<(Block::Hash, PotentialParent<Block>)>
+where
+	Block: BlockT,
+{
+	let parent_search_params = ParentSearchParams {
+		relay_parent,
+		para_id,
+		ancestry_lookback: max_ancestry_lookback(relay_parent, relay_client).await,
+		max_depth: PARENT_SEARCH_DEPTH,
+		ignore_alternative_branches: true,
+	};
+
+	let potential_parents = cumulus_client_consensus_common::find_potential_parents::<Block>(
+		parent_search_params,
+		para_backend,
+		relay_client,
+	)
+	.await;
+
+	let potential_parents = match potential_parents {
+		Err(e) => {
+			tracing::error!(
+				target: crate::LOG_TARGET,
+				?relay_parent,
+				err = ?e,
+				"Could not fetch potential parents to build upon"
+			);
+
+			return None
+		},
+		Ok(x) => x,
+	};
+
+	let included_block = potential_parents.iter().find(|x| x.depth == 0)?.hash;
+
+	// Choose the longest chain. If it has no space left, building is rejected by
+	// `can_build_upon`.
+	potential_parents
+		.into_iter()
+		.max_by_key(|a| a.depth)
+		.map(|parent| (included_block, parent))
+}
@@ -95,6 +95,13 @@ impl RelayChainInterface for Relaychain {
 		unimplemented!("Not needed for test")
 	}
 
+	async fn availability_cores(
+		&self,
+		_: PHash,
+	) -> RelayChainResult<Vec<relay_chain::CoreState<PHash, relay_chain::BlockNumber>>> {
+		unimplemented!("Not needed for test")
+	}
+
 	async fn best_block_hash(&self) -> RelayChainResult<PHash> {
 		unimplemented!("Not needed for test")
 	}
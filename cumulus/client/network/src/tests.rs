@@ -26,9 +26,10 @@ use futures::{executor::block_on, poll, task::Poll, FutureExt, Stream, StreamExt
 use parking_lot::Mutex;
 use polkadot_node_primitives::{SignedFullStatement, Statement};
 use polkadot_primitives::{
-	CandidateCommitments, CandidateDescriptor, CollatorPair, CommittedCandidateReceipt,
-	Hash as PHash, HeadData, InboundDownwardMessage, InboundHrmpMessage, OccupiedCoreAssumption,
-	PersistedValidationData, SessionIndex, SigningContext, ValidationCodeHash, ValidatorId,
+	BlockNumber, CandidateCommitments, CandidateDescriptor, CollatorPair,
+	CommittedCandidateReceipt, CoreIndex, CoreState, Hash as PHash, HeadData,
+	InboundDownwardMessage, InboundHrmpMessage, OccupiedCoreAssumption, PersistedValidationData,
+	SessionIndex, SigningContext, ValidationCodeHash, ValidatorId,
 };
 use polkadot_test_client::{
 	Client as PClient, ClientBlockImportExt, DefaultTestClientBuilderExt, FullBackend as PBackend,
@@ -82,6 +83,13 @@ impl RelayChainInterface for DummyRelayChainInterface {
 		Ok(self.data.lock().validators.clone())
 	}
 
+	async fn availability_cores(
+		&self,
+		_: PHash,
+	) -> RelayChainResult<Vec<CoreState<PHash, BlockNumber>>> {
+		unimplemented!("Not needed for test")
+	}
+
 	async fn best_block_hash(&self) -> RelayChainResult<PHash> {
 		Ok(self.relay_backend.blockchain().info().best_hash)
 	}
@@ -137,6 +145,7 @@ impl RelayChainInterface for DummyRelayChainInterface {
 					erasure_root: PHash::random(),
 					signature: sp_core::sr25519::Signature([0u8; 64]).into(),
 					validation_code_hash: ValidationCodeHash::from(PHash::random()),
+					core_index: CoreIndex(0),
 				},
 				commitments: CandidateCommitments {
 					upward_messages: Default::default(),
@@ -319,6 +328,7 @@ async fn make_gossip_message_and_header(
 			signature: sp_core::sr25519::Signature([0u8; 64]).into(),
 			para_head: polkadot_parachain_primitives::primitives::HeadData(header.encode()).hash(),
 			validation_code_hash: ValidationCodeHash::from(PHash::random()),
+			core_index: CoreIndex(0),
 		},
 	};
 	let statement = Statement::Seconded(candidate_receipt);
@@ -509,6 +519,7 @@ async fn check_statement_seconded() {
 				erasure_root: PHash::random(),
 				signature: sp_core::sr25519::Signature([0u8; 64]).into(),
 				validation_code_hash: ValidationCodeHash::from(PHash::random()),
+				core_index: CoreIndex(0),
 			},
 		},
 		statement: signed_statement.convert_payload().into(),
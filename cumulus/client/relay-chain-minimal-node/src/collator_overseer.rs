@@ -47,7 +47,6 @@ use sc_network::{NetworkStateInfo, NotificationService};
 use sc_service::TaskManager;
 use sc_utils::mpsc::tracing_unbounded;
 
-use cumulus_primitives_core::relay_chain::{Block, Hash as PHash};
 use cumulus_relay_chain_interface::RelayChainError;
 
 use crate::BlockChainRpcClient;
@@ -57,7 +56,7 @@ pub(crate) struct CollatorOverseerGenArgs<'a> {
 	/// Runtime client generic, providing the `ProvieRuntimeApi` trait besides others.
 	pub runtime_client: Arc<BlockChainRpcClient>,
 	/// Underlying network service implementation.
-	pub network_service: Arc<sc_network::NetworkService<Block, PHash>>,
+	pub network_service: Arc<dyn sc_network::service::traits::NetworkService>,
 	/// Syncing oracle.
 	pub sync_oracle: Box<dyn sp_consensus::SyncOracle + Send>,
 	/// Underlying authority discovery service.
@@ -234,11 +234,11 @@ where
 	block
 		.extrinsics()
 		.iter()
-		// Inherents are at the front of the block and are unsigned.
+		// Inherents are at the front of the block and are bare.
 		//
-		// If `is_signed` is returning `None`, we keep it safe and assume that it is "signed".
-		// We are searching for unsigned transactions anyway.
-		.take_while(|e| !e.is_signed().unwrap_or(true))
+		// If no information is available, we keep it safe and assume that it is not bare.
+		// We are searching for bare extrinsics anyway.
+		.take_while(|e| e.is_bare())
 		.filter_map(|e| e.call().is_sub_type())
 		.find_map(|c| match c {
 			crate::Call::set_validation_data { data: validation_data } => Some(validation_data),
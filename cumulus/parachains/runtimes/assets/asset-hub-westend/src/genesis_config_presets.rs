@@ -0,0 +1,82 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Genesis configs presets for the Asset Hub Westend runtime
+
+use crate::{ExistentialDeposit, SessionKeys};
+use cumulus_primitives_core::ParaId;
+use parachains_common::{
+	genesis_config_helpers::system_parachain_genesis, AccountId, AuraId, Balance,
+};
+use polkadot_runtime_common::genesis_presets::{
+	accounts_with_stashes, testnet_accounts, DevAccount, ALICE, BOB,
+};
+use sp_core::crypto::UncheckedInto;
+use sp_genesis_builder::PresetId;
+use sp_std::{vec, vec::Vec};
+use testnet_parachains_constants::westend::currency::UNITS;
+
+const ASSET_HUB_WESTEND_ED: Balance = ExistentialDeposit::get();
+const PARA_ID: ParaId = ParaId::new(1000);
+
+/// Returns the account id and the aura key of the development `accounts`.
+fn invulnerables(accounts: &[DevAccount]) -> Vec<(AccountId, AuraId)> {
+	accounts.iter().map(|a| (a.account_id(), a.sr25519.unchecked_into())).collect()
+}
+
+/// Helper function to create the runtime `GenesisConfig` patch.
+pub fn asset_hub_westend_genesis(
+	invulnerables: Vec<(AccountId, AuraId)>,
+	endowed_accounts: Vec<AccountId>,
+	endowment: Balance,
+	id: ParaId,
+) -> serde_json::Value {
+	system_parachain_genesis(
+		invulnerables,
+		endowed_accounts,
+		endowment,
+		ASSET_HUB_WESTEND_ED * 16,
+		id,
+		|aura| SessionKeys { aura },
+	)
+}
+
+/// Provides the JSON representation of predefined genesis config for given `id`.
+pub fn get_preset(id: &PresetId) -> Option<Vec<u8>> {
+	let patch = match core::str::from_utf8(id.as_ref()).ok()? {
+		sp_genesis_builder::DEV_RUNTIME_PRESET => asset_hub_westend_genesis(
+			invulnerables(&[ALICE]),
+			accounts_with_stashes(&[ALICE, BOB]),
+			UNITS * 1_000_000,
+			PARA_ID,
+		),
+		sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET => asset_hub_westend_genesis(
+			invulnerables(&[ALICE, BOB]),
+			testnet_accounts(),
+			UNITS * 1_000_000,
+			PARA_ID,
+		),
+		_ => return None,
+	};
+	Some(serde_json::to_vec(&patch).expect("serialization to json is expected to work. qed."))
+}
+
+/// List of supported presets.
+pub fn preset_names() -> Vec<PresetId> {
+	vec![
+		PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET),
+		PresetId::from(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET),
+	]
+}
@@ -52,7 +52,7 @@ use xcm_builder::{
 	FrameTransactionalProcessor, FungibleAdapter, FungiblesAdapter,
 	GlobalConsensusParachainConvertsFor, HashedDescription, IsConcrete, LocalMint,
 	NetworkExportTableItem, NoChecking, NonFungiblesAdapter, ParentAsSuperuser, ParentIsPreset,
-	RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
+	RecordingXcmRouter, RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
 	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, StartsWith,
 	StartsWithExplicitGlobalConsensus, TakeWeightCredit, TrailingSetTopicAsId, UsingComponents,
 	WeightInfoBounds, WithComputedOrigin, WithUniqueTopic, XcmFeeManagerFromComponents,
@@ -666,13 +666,15 @@ type LocalXcmRouter = (
 );
 
 /// The means for routing XCM messages which are not for local execution into the right message
-/// queues.
-pub type XcmRouter = WithUniqueTopic<(
-	LocalXcmRouter,
-	// Router which wraps and sends xcm to BridgeHub to be delivered to the Rococo
-	// GlobalConsensus
-	ToRococoXcmRouter,
-)>;
+/// queues. The sent messages are recorded while dry-running.
+pub type XcmRouter = WithUniqueTopic<
+	RecordingXcmRouter<(
+		LocalXcmRouter,
+		// Router which wraps and sends xcm to BridgeHub to be delivered to the Rococo
+		// GlobalConsensus
+		ToRococoXcmRouter,
+	)>,
+>;
 
 impl pallet_xcm::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
@@ -24,12 +24,21 @@ pub mod matching;
 pub mod runtime_api;
 
 use crate::matching::{LocalLocationPattern, ParentLocation};
-use frame_support::traits::{Equals, EverythingBut};
+use frame_support::{
+	traits::{Equals, EverythingBut, Get},
+	weights::Weight,
+};
 use parachains_common::{AssetIdForTrustBackedAssets, CollectionId, ItemId};
+use sp_std::{marker::PhantomData, vec::Vec};
+use xcm::{
+	latest::{AssetId, Location},
+	VersionedAssetId,
+};
 use xcm_builder::{
 	AsPrefixedGeneralIndex, MatchedConvertedConcreteId, StartsWith, V4V3LocationConverter,
 };
 use xcm_executor::traits::JustTry;
+use xcm_fee_payment_runtime_api::fees::Error as XcmPaymentApiError;
 
 /// `Location` vs `AssetIdForTrustBackedAssets` converter for `TrustBackedAssets`
 pub type AssetIdForTrustBackedAssetsConvert<TrustBackedAssetsPalletLocation> =
@@ -125,6 +134,72 @@ pub type PoolAssetsConvertedConcreteId<PoolAssetsPalletLocation, Balance> =
 		JustTry,
 	>;
 
+/// Adapter for querying the pools of `pallet_asset_conversion`, whose `AssetKind` is the XCM v3
+/// `Location`, with the latest `Location`.
+pub struct PoolAdapter<Runtime>(PhantomData<Runtime>);
+impl<Runtime> PoolAdapter<Runtime>
+where
+	Runtime: pallet_asset_conversion::Config<
+		AssetKind = xcm::v3::Location,
+		PoolId = (xcm::v3::Location, xcm::v3::Location),
+	>,
+{
+	/// Returns the assets which have a pool with `asset`.
+	pub fn get_assets_in_pool_with(asset: &Location) -> Result<Vec<AssetId>, ()> {
+		let asset: xcm::v3::Location = asset.clone().try_into()?;
+		Ok(pallet_asset_conversion::Pools::<Runtime>::iter_keys()
+			.filter_map(|(asset_1, asset_2)| match (asset_1 == asset, asset_2 == asset) {
+				(true, _) => Some(asset_2),
+				(_, true) => Some(asset_1),
+				_ => None,
+			})
+			.filter_map(|other| other.try_into().map(AssetId).ok())
+			.collect())
+	}
+
+	/// Returns the amount of `asset_1` needed to get exactly `amount` of `asset_2` from their
+	/// pool, including the swap fees.
+	///
+	/// Returns `Ok(None)` if there is no such pool or it has no liquidity.
+	pub fn quote_price_tokens_for_exact_tokens(
+		asset_1: Location,
+		asset_2: Location,
+		amount: Runtime::Balance,
+	) -> Result<Option<Runtime::Balance>, ()> {
+		let asset_1: xcm::v3::Location = asset_1.try_into()?;
+		let asset_2: xcm::v3::Location = asset_2.try_into()?;
+		Ok(pallet_asset_conversion::Pallet::<Runtime>::quote_price_tokens_for_exact_tokens(
+			asset_1, asset_2, amount, true,
+		))
+	}
+
+	/// Returns the fee for `weight` in `asset`, as reported by the `XcmPaymentApi`.
+	///
+	/// The fee in any asset other than `NativeToken` is the amount of it the `Trader` swaps for
+	/// exactly the native fee.
+	pub fn query_weight_to_asset_fee<WeightToFee, NativeToken>(
+		weight: Weight,
+		asset: VersionedAssetId,
+	) -> Result<u128, XcmPaymentApiError>
+	where
+		WeightToFee: frame_support::weights::WeightToFee<Balance = Runtime::Balance>,
+		NativeToken: Get<Location>,
+		Runtime::Balance: Into<u128>,
+	{
+		let native_token = NativeToken::get();
+		let native_fee = WeightToFee::weight_to_fee(&weight);
+		let asset_id: AssetId =
+			asset.try_into().map_err(|()| XcmPaymentApiError::VersionedConversionFailed)?;
+		if asset_id.0 == native_token {
+			return Ok(native_fee.into())
+		}
+		Self::quote_price_tokens_for_exact_tokens(asset_id.0, native_token, native_fee)
+			.map_err(|()| XcmPaymentApiError::VersionedConversionFailed)?
+			.map(Into::into)
+			.ok_or(XcmPaymentApiError::AssetNotFound)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -0,0 +1,80 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Genesis configs presets for the Bridge Hub Rococo runtime
+
+use crate::{ExistentialDeposit, SessionKeys};
+use cumulus_primitives_core::ParaId;
+use parachains_common::{
+	genesis_config_helpers::system_parachain_genesis, AccountId, AuraId, Balance,
+};
+use polkadot_runtime_common::genesis_presets::{testnet_accounts, DevAccount, ALICE, BOB};
+use sp_core::crypto::UncheckedInto;
+use sp_genesis_builder::PresetId;
+use sp_std::{vec, vec::Vec};
+
+const BRIDGE_HUB_ROCOCO_ED: Balance = ExistentialDeposit::get();
+const PARA_ID: ParaId = ParaId::new(1013);
+
+/// Returns the account id and the aura key of the development `accounts`.
+fn invulnerables(accounts: &[DevAccount]) -> Vec<(AccountId, AuraId)> {
+	accounts.iter().map(|a| (a.account_id(), a.sr25519.unchecked_into())).collect()
+}
+
+/// Helper function to create the runtime `GenesisConfig` patch.
+fn bridge_hub_rococo_genesis(
+	invulnerables: Vec<(AccountId, AuraId)>,
+	endowed_accounts: Vec<AccountId>,
+	id: ParaId,
+	bridges_pallet_owner: AccountId,
+) -> serde_json::Value {
+	let mut genesis = system_parachain_genesis(
+		invulnerables,
+		endowed_accounts,
+		1u128 << 60,
+		BRIDGE_HUB_ROCOCO_ED * 16,
+		id,
+		|aura| SessionKeys { aura },
+	);
+	genesis["bridgeWestendGrandpa"] =
+		serde_json::json!({ "owner": Some(bridges_pallet_owner.clone()) });
+	genesis["bridgeWestendMessages"] = serde_json::json!({ "owner": Some(bridges_pallet_owner) });
+	genesis["ethereumSystem"] = serde_json::json!({ "paraId": id, "assetHubParaId": 1000 });
+	genesis
+}
+
+/// Provides the JSON representation of predefined genesis config for given `id`.
+pub fn get_preset(id: &PresetId) -> Option<Vec<u8>> {
+	let patch = match core::str::from_utf8(id.as_ref()).ok()? {
+		sp_genesis_builder::DEV_RUNTIME_PRESET |
+		sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET => bridge_hub_rococo_genesis(
+			invulnerables(&[ALICE, BOB]),
+			testnet_accounts(),
+			PARA_ID,
+			BOB.account_id(),
+		),
+		_ => return None,
+	};
+	Some(serde_json::to_vec(&patch).expect("serialization to json is expected to work. qed."))
+}
+
+/// List of supported presets.
+pub fn preset_names() -> Vec<PresetId> {
+	vec![
+		PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET),
+		PresetId::from(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET),
+	]
+}
@@ -14,33 +14,17 @@
 // You should have received a copy of the GNU General Public License
 // along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::chain_spec::{
-	get_account_id_from_seed, get_collator_keys_from_seed, Extensions, GenericChainSpec,
-	SAFE_XCM_VERSION,
-};
-use cumulus_primitives_core::ParaId;
+use crate::chain_spec::{Extensions, GenericChainSpec};
+use asset_hub_rococo_runtime::genesis_config_presets::asset_hub_rococo_genesis;
+use asset_hub_westend_runtime::genesis_config_presets::asset_hub_westend_genesis;
 use hex_literal::hex;
-use parachains_common::{AccountId, AuraId, Balance as AssetHubBalance};
+use parachains_common::Balance as AssetHubBalance;
 use sc_service::ChainType;
-use sp_core::{crypto::UncheckedInto, sr25519};
+use sp_core::crypto::UncheckedInto;
 
 const ASSET_HUB_WESTEND_ED: AssetHubBalance = asset_hub_westend_runtime::ExistentialDeposit::get();
 const ASSET_HUB_ROCOCO_ED: AssetHubBalance = asset_hub_rococo_runtime::ExistentialDeposit::get();
 
-/// Generate the session keys from individual elements.
-///
-/// The input must be a tuple of individual keys (a single arg for now since we have just one key).
-pub fn asset_hub_rococo_session_keys(keys: AuraId) -> asset_hub_rococo_runtime::SessionKeys {
-	asset_hub_rococo_runtime::SessionKeys { aura: keys }
-}
-
-/// Generate the session keys from individual elements.
-///
-/// The input must be a tuple of individual keys (a single arg for now since we have just one key).
-pub fn asset_hub_westend_session_keys(keys: AuraId) -> asset_hub_westend_runtime::SessionKeys {
-	asset_hub_westend_runtime::SessionKeys { aura: keys }
-}
-
 pub fn asset_hub_westend_development_config() -> GenericChainSpec {
 	let mut properties = sc_chain_spec::Properties::new();
 	properties.insert("tokenSymbol".into(), "WND".into());
@@ -54,21 +38,7 @@ pub fn asset_hub_westend_development_config() -> GenericChainSpec {
 	.with_name("Westend Asset Hub Development")
 	.with_id("asset-hub-westend-dev")
 	.with_chain_type(ChainType::Local)
-	.with_genesis_config_patch(asset_hub_westend_genesis(
-		// initial collators.
-		vec![(
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
-			get_collator_keys_from_seed::<AuraId>("Alice"),
-		)],
-		vec![
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
-			get_account_id_from_seed::<sr25519::Public>("Bob"),
-			get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-		],
-		testnet_parachains_constants::westend::currency::UNITS * 1_000_000,
-		1000.into(),
-	))
+	.with_genesis_config_preset_name(sp_genesis_builder::DEV_RUNTIME_PRESET)
 	.with_properties(properties)
 	.build()
 }
@@ -86,35 +56,7 @@ pub fn asset_hub_westend_local_config() -> GenericChainSpec {
 	.with_name("Westend Asset Hub Local")
 	.with_id("asset-hub-westend-local")
 	.with_chain_type(ChainType::Local)
-	.with_genesis_config_patch(asset_hub_westend_genesis(
-		// initial collators.
-		vec![
-			(
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				get_collator_keys_from_seed::<AuraId>("Alice"),
-			),
-			(
-				get_account_id_from_seed::<sr25519::Public>("Bob"),
-				get_collator_keys_from_seed::<AuraId>("Bob"),
-			),
-		],
-		vec![
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
-			get_account_id_from_seed::<sr25519::Public>("Bob"),
-			get_account_id_from_seed::<sr25519::Public>("Charlie"),
-			get_account_id_from_seed::<sr25519::Public>("Dave"),
-			get_account_id_from_seed::<sr25519::Public>("Eve"),
-			get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-			get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-		],
-		testnet_parachains_constants::westend::currency::UNITS * 1_000_000,
-		1000.into(),
-	))
+	.with_genesis_config_preset_name(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET)
 	.with_properties(properties)
 	.build()
 }
@@ -164,86 +106,20 @@ pub fn asset_hub_westend_config() -> GenericChainSpec {
 	.build()
 }
 
-fn asset_hub_westend_genesis(
-	invulnerables: Vec<(AccountId, AuraId)>,
-	endowed_accounts: Vec<AccountId>,
-	endowment: AssetHubBalance,
-	id: ParaId,
-) -> serde_json::Value {
-	serde_json::json!({
-		"balances": {
-			"balances": endowed_accounts
-				.iter()
-				.cloned()
-				.map(|k| (k, endowment))
-				.collect::<Vec<_>>(),
-		},
-		"parachainInfo": {
-			"parachainId": id,
-		},
-		"collatorSelection": {
-			"invulnerables": invulnerables.iter().cloned().map(|(acc, _)| acc).collect::<Vec<_>>(),
-			"candidacyBond": ASSET_HUB_WESTEND_ED * 16,
-		},
-		"session": {
-			"keys": invulnerables
-				.into_iter()
-				.map(|(acc, aura)| {
-					(
-						acc.clone(),                          // account id
-						acc,                                  // validator id
-						asset_hub_westend_session_keys(aura), // session keys
-					)
-				})
-				.collect::<Vec<_>>(),
-		},
-		"polkadotXcm": {
-			"safeXcmVersion": Some(SAFE_XCM_VERSION),
-		},
-	})
-}
-
 pub fn asset_hub_rococo_development_config() -> GenericChainSpec {
 	let mut properties = sc_chain_spec::Properties::new();
 	properties.insert("ss58Format".into(), 42.into());
 	properties.insert("tokenSymbol".into(), "ROC".into());
 	properties.insert("tokenDecimals".into(), 12.into());
-	asset_hub_rococo_like_development_config(
-		properties,
-		"Rococo Asset Hub Development",
-		"asset-hub-rococo-dev",
-		1000,
-	)
-}
 
-fn asset_hub_rococo_like_development_config(
-	properties: sc_chain_spec::Properties,
-	name: &str,
-	chain_id: &str,
-	para_id: u32,
-) -> GenericChainSpec {
 	GenericChainSpec::builder(
 		asset_hub_rococo_runtime::WASM_BINARY.expect("WASM binary was not built, please build it!"),
-		Extensions { relay_chain: "rococo-dev".into(), para_id },
+		Extensions { relay_chain: "rococo-dev".into(), para_id: 1000 },
 	)
-	.with_name(name)
-	.with_id(chain_id)
+	.with_name("Rococo Asset Hub Development")
+	.with_id("asset-hub-rococo-dev")
 	.with_chain_type(ChainType::Local)
-	.with_genesis_config_patch(asset_hub_rococo_genesis(
-		// initial collators.
-		vec![(
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
-			get_collator_keys_from_seed::<AuraId>("Alice"),
-		)],
-		vec![
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
-			get_account_id_from_seed::<sr25519::Public>("Bob"),
-			get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-		],
-		testnet_parachains_constants::rococo::currency::UNITS * 1_000_000,
-		para_id.into(),
-	))
+	.with_genesis_config_preset_name(sp_genesis_builder::DEV_RUNTIME_PRESET)
 	.with_properties(properties)
 	.build()
 }
@@ -253,56 +129,15 @@ pub fn asset_hub_rococo_local_config() -> GenericChainSpec {
 	properties.insert("ss58Format".into(), 42.into());
 	properties.insert("tokenSymbol".into(), "ROC".into());
 	properties.insert("tokenDecimals".into(), 12.into());
-	asset_hub_rococo_like_local_config(
-		properties,
-		"Rococo Asset Hub Local",
-		"asset-hub-rococo-local",
-		1000,
-	)
-}
 
-fn asset_hub_rococo_like_local_config(
-	properties: sc_chain_spec::Properties,
-	name: &str,
-	chain_id: &str,
-	para_id: u32,
-) -> GenericChainSpec {
 	GenericChainSpec::builder(
 		asset_hub_rococo_runtime::WASM_BINARY.expect("WASM binary was not built, please build it!"),
-		Extensions { relay_chain: "rococo-local".into(), para_id },
+		Extensions { relay_chain: "rococo-local".into(), para_id: 1000 },
 	)
-	.with_name(name)
-	.with_id(chain_id)
+	.with_name("Rococo Asset Hub Local")
+	.with_id("asset-hub-rococo-local")
 	.with_chain_type(ChainType::Local)
-	.with_genesis_config_patch(asset_hub_rococo_genesis(
-		// initial collators.
-		vec![
-			(
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				get_collator_keys_from_seed::<AuraId>("Alice"),
-			),
-			(
-				get_account_id_from_seed::<sr25519::Public>("Bob"),
-				get_collator_keys_from_seed::<AuraId>("Bob"),
-			),
-		],
-		vec![
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
-			get_account_id_from_seed::<sr25519::Public>("Bob"),
-			get_account_id_from_seed::<sr25519::Public>("Charlie"),
-			get_account_id_from_seed::<sr25519::Public>("Dave"),
-			get_account_id_from_seed::<sr25519::Public>("Eve"),
-			get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-			get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-		],
-		testnet_parachains_constants::rococo::currency::UNITS * 1_000_000,
-		para_id.into(),
-	))
+	.with_genesis_config_preset_name(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET)
 	.with_properties(properties)
 	.build()
 }
@@ -354,45 +189,3 @@ pub fn asset_hub_rococo_genesis_config() -> GenericChainSpec {
 	.with_properties(properties)
 	.build()
 }
-
-fn asset_hub_rococo_genesis(
-	invulnerables: Vec<(AccountId, AuraId)>,
-	endowed_accounts: Vec<AccountId>,
-	endowment: AssetHubBalance,
-	id: ParaId,
-) -> serde_json::Value {
-	serde_json::json!({
-		"balances": asset_hub_rococo_runtime::BalancesConfig {
-			balances: endowed_accounts
-				.iter()
-				.cloned()
-				.map(|k| (k, endowment))
-				.collect(),
-		},
-		"parachainInfo": asset_hub_rococo_runtime::ParachainInfoConfig {
-			parachain_id: id,
-			..Default::default()
-		},
-		"collatorSelection": asset_hub_rococo_runtime::CollatorSelectionConfig {
-			invulnerables: invulnerables.iter().cloned().map(|(acc, _)| acc).collect(),
-			candidacy_bond: ASSET_HUB_ROCOCO_ED * 16,
-			..Default::default()
-		},
-		"session": asset_hub_rococo_runtime::SessionConfig {
-			keys: invulnerables
-				.into_iter()
-				.map(|(acc, aura)| {
-					(
-						acc.clone(),                         // account id
-						acc,                                 // validator id
-						asset_hub_rococo_session_keys(aura), // session keys
-					)
-				})
-				.collect(),
-		},
-		"polkadotXcm": asset_hub_rococo_runtime::PolkadotXcmConfig {
-			safe_xcm_version: Some(SAFE_XCM_VERSION),
-			..Default::default()
-		}
-	})
-}
@@ -14,11 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::chain_spec::{get_account_id_from_seed, get_collator_keys_from_seed, GenericChainSpec};
+use crate::chain_spec::GenericChainSpec;
 use cumulus_primitives_core::ParaId;
-use parachains_common::Balance as BridgeHubBalance;
 use sc_chain_spec::ChainSpec;
-use sp_core::sr25519;
 use std::str::FromStr;
 
 /// Collects all supported BridgeHub configurations
@@ -75,14 +73,14 @@ impl BridgeHubRuntimeType {
 				"Westend BridgeHub Local",
 				"westend-local",
 				ParaId::new(1002),
-				Some("Bob".to_string()),
+				sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET,
 			))),
 			BridgeHubRuntimeType::WestendDevelopment => Ok(Box::new(westend::local_config(
 				westend::BRIDGE_HUB_WESTEND_DEVELOPMENT,
 				"Westend BridgeHub Development",
 				"westend-dev",
 				ParaId::new(1002),
-				Some("Bob".to_string()),
+				sp_genesis_builder::DEV_RUNTIME_PRESET,
 			))),
 			BridgeHubRuntimeType::Rococo => Ok(Box::new(GenericChainSpec::from_json_bytes(
 				&include_bytes!("../../chain-specs/bridge-hub-rococo.json")[..],
@@ -92,7 +90,7 @@ impl BridgeHubRuntimeType {
 				"Rococo BridgeHub Local",
 				"rococo-local",
 				ParaId::new(1013),
-				Some("Bob".to_string()),
+				sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET,
 				|_| (),
 			))),
 			BridgeHubRuntimeType::RococoDevelopment => Ok(Box::new(rococo::local_config(
@@ -100,7 +98,7 @@ impl BridgeHubRuntimeType {
 				"Rococo BridgeHub Development",
 				"rococo-dev",
 				ParaId::new(1013),
-				Some("Bob".to_string()),
+				sp_genesis_builder::DEV_RUNTIME_PRESET,
 				|_| (),
 			))),
 		}
@@ -122,25 +120,20 @@ fn ensure_id(id: &str) -> Result<&str, String> {
 
 /// Sub-module for Rococo setup
 pub mod rococo {
-	use super::{get_account_id_from_seed, get_collator_keys_from_seed, sr25519, ParaId};
-	use crate::chain_spec::{Extensions, GenericChainSpec, SAFE_XCM_VERSION};
-	use parachains_common::{AccountId, AuraId};
+	use super::ParaId;
+	use crate::chain_spec::{Extensions, GenericChainSpec};
 	use sc_chain_spec::ChainType;
 
-	use super::BridgeHubBalance;
-
 	pub(crate) const BRIDGE_HUB_ROCOCO: &str = "bridge-hub-rococo";
 	pub(crate) const BRIDGE_HUB_ROCOCO_LOCAL: &str = "bridge-hub-rococo-local";
 	pub(crate) const BRIDGE_HUB_ROCOCO_DEVELOPMENT: &str = "bridge-hub-rococo-dev";
-	const BRIDGE_HUB_ROCOCO_ED: BridgeHubBalance =
-		bridge_hub_rococo_runtime::ExistentialDeposit::get();
 
 	pub fn local_config<ModifyProperties: Fn(&mut sc_chain_spec::Properties)>(
 		id: &str,
 		chain_name: &str,
 		relay_chain: &str,
 		para_id: ParaId,
-		bridges_pallet_owner_seed: Option<String>,
+		genesis_preset: &str,
 		modify_props: ModifyProperties,
 	) -> GenericChainSpec {
 		// Rococo defaults
@@ -158,85 +151,10 @@ pub mod rococo {
 		.with_name(chain_name)
 		.with_id(super::ensure_id(id).expect("invalid id"))
 		.with_chain_type(ChainType::Local)
-		.with_genesis_config_patch(genesis(
-			// initial collators.
-			vec![
-				(
-					get_account_id_from_seed::<sr25519::Public>("Alice"),
-					get_collator_keys_from_seed::<AuraId>("Alice"),
-				),
-				(
-					get_account_id_from_seed::<sr25519::Public>("Bob"),
-					get_collator_keys_from_seed::<AuraId>("Bob"),
-				),
-			],
-			vec![
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				get_account_id_from_seed::<sr25519::Public>("Bob"),
-				get_account_id_from_seed::<sr25519::Public>("Charlie"),
-				get_account_id_from_seed::<sr25519::Public>("Dave"),
-				get_account_id_from_seed::<sr25519::Public>("Eve"),
-				get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-				get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-			],
-			para_id,
-			bridges_pallet_owner_seed
-				.as_ref()
-				.map(|seed| get_account_id_from_seed::<sr25519::Public>(seed)),
-		))
+		.with_genesis_config_preset_name(genesis_preset)
 		.with_properties(properties)
 		.build()
 	}
-
-	fn genesis(
-		invulnerables: Vec<(AccountId, AuraId)>,
-		endowed_accounts: Vec<AccountId>,
-		id: ParaId,
-		bridges_pallet_owner: Option<AccountId>,
-	) -> serde_json::Value {
-		serde_json::json!({
-			"balances": {
-				"balances": endowed_accounts.iter().cloned().map(|k| (k, 1u64 << 60)).collect::<Vec<_>>(),
-			},
-			"parachainInfo": {
-				"parachainId": id,
-			},
-			"collatorSelection": {
-				"invulnerables": invulnerables.iter().cloned().map(|(acc, _)| acc).collect::<Vec<_>>(),
-				"candidacyBond": BRIDGE_HUB_ROCOCO_ED * 16,
-			},
-			"session": {
-				"keys": invulnerables
-					.into_iter()
-					.map(|(acc, aura)| {
-						(
-							acc.clone(),                                     // account id
-							acc,                                             // validator id
-							bridge_hub_rococo_runtime::SessionKeys { aura }, // session keys
-						)
-					})
-					.collect::<Vec<_>>(),
-			},
-			"polkadotXcm": {
-				"safeXcmVersion": Some(SAFE_XCM_VERSION),
-			},
-			"bridgeWestendGrandpa": {
-				"owner": bridges_pallet_owner.clone(),
-			},
-			"bridgeWestendMessages": {
-				"owner": bridges_pallet_owner.clone(),
-			},
-			"ethereumSystem": {
-				"paraId": id,
-				"assetHubParaId": 1000
-			}
-		})
-	}
 }
 
 /// Sub-module for Kusama setup
@@ -246,25 +164,20 @@ pub mod kusama {
 
 /// Sub-module for Westend setup.
 pub mod westend {
-	use super::{get_account_id_from_seed, get_collator_keys_from_seed, sr25519, ParaId};
-	use crate::chain_spec::{Extensions, GenericChainSpec, SAFE_XCM_VERSION};
-	use parachains_common::{AccountId, AuraId};
+	use super::ParaId;
+	use crate::chain_spec::{Extensions, GenericChainSpec};
 	use sc_chain_spec::ChainType;
 
-	use super::BridgeHubBalance;
-
 	pub(crate) const BRIDGE_HUB_WESTEND: &str = "bridge-hub-westend";
 	pub(crate) const BRIDGE_HUB_WESTEND_LOCAL: &str = "bridge-hub-westend-local";
 	pub(crate) const BRIDGE_HUB_WESTEND_DEVELOPMENT: &str = "bridge-hub-westend-dev";
-	const BRIDGE_HUB_WESTEND_ED: BridgeHubBalance =
-		bridge_hub_westend_runtime::ExistentialDeposit::get();
 
 	pub fn local_config(
 		id: &str,
 		chain_name: &str,
 		relay_chain: &str,
 		para_id: ParaId,
-		bridges_pallet_owner_seed: Option<String>,
+		genesis_preset: &str,
 	) -> GenericChainSpec {
 		let mut properties = sc_chain_spec::Properties::new();
 		properties.insert("tokenSymbol".into(), "WND".into());
@@ -278,81 +191,10 @@ pub mod westend {
 		.with_name(chain_name)
 		.with_id(super::ensure_id(id).expect("invalid id"))
 		.with_chain_type(ChainType::Local)
-		.with_genesis_config_patch(genesis(
-			// initial collators.
-			vec![
-				(
-					get_account_id_from_seed::<sr25519::Public>("Alice"),
-					get_collator_keys_from_seed::<AuraId>("Alice"),
-				),
-				(
-					get_account_id_from_seed::<sr25519::Public>("Bob"),
-					get_collator_keys_from_seed::<AuraId>("Bob"),
-				),
-			],
-			vec![
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				get_account_id_from_seed::<sr25519::Public>("Bob"),
-				get_account_id_from_seed::<sr25519::Public>("Charlie"),
-				get_account_id_from_seed::<sr25519::Public>("Dave"),
-				get_account_id_from_seed::<sr25519::Public>("Eve"),
-				get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-				get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-			],
-			para_id,
-			bridges_pallet_owner_seed
-				.as_ref()
-				.map(|seed| get_account_id_from_seed::<sr25519::Public>(seed)),
-		))
+		.with_genesis_config_preset_name(genesis_preset)
 		.with_properties(properties)
 		.build()
 	}
-
-	fn genesis(
-		invulnerables: Vec<(AccountId, AuraId)>,
-		endowed_accounts: Vec<AccountId>,
-		id: ParaId,
-		bridges_pallet_owner: Option<AccountId>,
-	) -> serde_json::Value {
-		serde_json::json!({
-			"balances": {
-				"balances": endowed_accounts.iter().cloned().map(|k| (k, 1u64 << 60)).collect::<Vec<_>>(),
-			},
-			"parachainInfo": {
-				"parachainId": id,
-			},
-			"collatorSelection": {
-				"invulnerables": invulnerables.iter().cloned().map(|(acc, _)| acc).collect::<Vec<_>>(),
-				"candidacyBond": BRIDGE_HUB_WESTEND_ED * 16,
-			},
-			"session": {
-				"keys": invulnerables
-					.into_iter()
-					.map(|(acc, aura)| {
-						(
-							acc.clone(),                                      // account id
-							acc,                                              // validator id
-							bridge_hub_westend_runtime::SessionKeys { aura }, // session keys
-						)
-					})
-					.collect::<Vec<_>>(),
-			},
-			"polkadotXcm": {
-				"safeXcmVersion": Some(SAFE_XCM_VERSION),
-			},
-			"bridgeRococoGrandpa": {
-				"owner": bridges_pallet_owner.clone(),
-			},
-			"bridgeRococoMessages":  {
-				"owner": bridges_pallet_owner.clone(),
-			}
-		})
-	}
 }
 
 /// Sub-module for Polkadot setup
@@ -14,24 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::chain_spec::{
-	get_account_id_from_seed, get_collator_keys_from_seed, Extensions, GenericChainSpec,
-	SAFE_XCM_VERSION,
-};
-use cumulus_primitives_core::ParaId;
-use parachains_common::{AccountId, AuraId, Balance as CollectivesBalance};
+use crate::chain_spec::{Extensions, GenericChainSpec};
 use sc_service::ChainType;
-use sp_core::sr25519;
-
-const COLLECTIVES_WESTEND_ED: CollectivesBalance =
-	collectives_westend_runtime::ExistentialDeposit::get();
-
-/// Generate the session keys from individual elements.
-///
-/// The input must be a tuple of individual keys (a single arg for now since we have just one key).
-pub fn collectives_westend_session_keys(keys: AuraId) -> collectives_westend_runtime::SessionKeys {
-	collectives_westend_runtime::SessionKeys { aura: keys }
-}
 
 pub fn collectives_westend_development_config() -> GenericChainSpec {
 	let mut properties = sc_chain_spec::Properties::new();
@@ -42,27 +26,14 @@ pub fn collectives_westend_development_config() -> GenericChainSpec {
 	GenericChainSpec::builder(
 		collectives_westend_runtime::WASM_BINARY
 			.expect("WASM binary was not built, please build it!"),
+		// 1002 avoids a potential collision with Kusama-1001 (Encointer) should there ever
+		// be a collective para on Kusama.
 		Extensions { relay_chain: "westend-dev".into(), para_id: 1002 },
 	)
 	.with_name("Westend Collectives Development")
 	.with_id("collectives_westend_dev")
 	.with_chain_type(ChainType::Local)
-	.with_genesis_config_patch(collectives_westend_genesis(
-		// initial collators.
-		vec![(
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
-			get_collator_keys_from_seed::<AuraId>("Alice"),
-		)],
-		vec![
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
-			get_account_id_from_seed::<sr25519::Public>("Bob"),
-			get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-		],
-		// 1002 avoids a potential collision with Kusama-1001 (Encointer) should there ever
-		// be a collective para on Kusama.
-		1002.into(),
-	))
+	.with_genesis_config_preset_name(sp_genesis_builder::DEV_RUNTIME_PRESET)
 	.with_boot_nodes(Vec::new())
 	.with_properties(properties)
 	.build()
@@ -83,75 +54,8 @@ pub fn collectives_westend_local_config() -> GenericChainSpec {
 	.with_name("Westend Collectives Local")
 	.with_id("collectives_westend_local")
 	.with_chain_type(ChainType::Local)
-	.with_genesis_config_patch(collectives_westend_genesis(
-		// initial collators.
-		vec![
-			(
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				get_collator_keys_from_seed::<AuraId>("Alice"),
-			),
-			(
-				get_account_id_from_seed::<sr25519::Public>("Bob"),
-				get_collator_keys_from_seed::<AuraId>("Bob"),
-			),
-		],
-		vec![
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
-			get_account_id_from_seed::<sr25519::Public>("Bob"),
-			get_account_id_from_seed::<sr25519::Public>("Charlie"),
-			get_account_id_from_seed::<sr25519::Public>("Dave"),
-			get_account_id_from_seed::<sr25519::Public>("Eve"),
-			get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-			get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-		],
-		1002.into(),
-	))
+	.with_genesis_config_preset_name(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET)
 	.with_boot_nodes(Vec::new())
 	.with_properties(properties)
 	.build()
 }
-
-fn collectives_westend_genesis(
-	invulnerables: Vec<(AccountId, AuraId)>,
-	endowed_accounts: Vec<AccountId>,
-	id: ParaId,
-) -> serde_json::Value {
-	serde_json::json!( {
-		"balances": {
-			"balances": endowed_accounts
-				.iter()
-				.cloned()
-				.map(|k| (k, COLLECTIVES_WESTEND_ED * 4096))
-				.collect::<Vec<_>>(),
-		},
-		"parachainInfo": {
-			"parachainId": id,
-		},
-		"collatorSelection": {
-			"invulnerables": invulnerables.iter().cloned().map(|(acc, _)| acc).collect::<Vec<_>>(),
-			"candidacyBond": COLLECTIVES_WESTEND_ED * 16,
-		},
-		"session": {
-			"keys": invulnerables
-				.into_iter()
-				.map(|(acc, aura)| {
-					(
-						acc.clone(),                             // account id
-						acc,                                     // validator id
-						collectives_westend_session_keys(aura), // session keys
-					)
-				})
-				.collect::<Vec<_>>(),
-		},
-		// no need to pass anything to aura, in fact it will panic if we do. Session will take care
-		// of this.
-		"polkadotXcm": {
-			"safeXcmVersion": Some(SAFE_XCM_VERSION),
-		},
-	})
-}
@@ -16,7 +16,6 @@
 
 use crate::chain_spec::GenericChainSpec;
 use cumulus_primitives_core::ParaId;
-use parachains_common::Balance as PeopleBalance;
 use sc_chain_spec::ChainSpec;
 use std::str::FromStr;
 
@@ -60,12 +59,14 @@ impl PeopleRuntimeType {
 				"Rococo People Local",
 				"rococo-local",
 				ParaId::new(1004),
+				sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET,
 			))),
 			PeopleRuntimeType::RococoDevelopment => Ok(Box::new(rococo::local_config(
 				rococo::PEOPLE_ROCOCO_DEVELOPMENT,
 				"Rococo People Development",
 				"rococo-development",
 				ParaId::new(1004),
+				sp_genesis_builder::DEV_RUNTIME_PRESET,
 			))),
 			PeopleRuntimeType::Westend => Ok(Box::new(GenericChainSpec::from_json_bytes(
 				&include_bytes!("../../chain-specs/people-westend.json")[..],
@@ -75,12 +76,14 @@ impl PeopleRuntimeType {
 				"Westend People Local",
 				"westend-local",
 				ParaId::new(1004),
+				sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET,
 			))),
 			PeopleRuntimeType::WestendDevelopment => Ok(Box::new(westend::local_config(
 				westend::PEOPLE_WESTEND_DEVELOPMENT,
 				"Westend People Development",
 				"westend-development",
 				ParaId::new(1004),
+				sp_genesis_builder::DEV_RUNTIME_PRESET,
 			))),
 		}
 	}
@@ -101,25 +104,20 @@ fn ensure_id(id: &str) -> Result<&str, String> {
 
 /// Sub-module for Rococo setup.
 pub mod rococo {
-	use super::{ParaId, PeopleBalance};
-	use crate::chain_spec::{
-		get_account_id_from_seed, get_collator_keys_from_seed, Extensions, GenericChainSpec,
-		SAFE_XCM_VERSION,
-	};
-	use parachains_common::{AccountId, AuraId};
+	use super::ParaId;
+	use crate::chain_spec::{Extensions, GenericChainSpec};
 	use sc_chain_spec::ChainType;
-	use sp_core::sr25519;
 
 	pub(crate) const PEOPLE_ROCOCO: &str = "people-rococo";
 	pub(crate) const PEOPLE_ROCOCO_LOCAL: &str = "people-rococo-local";
 	pub(crate) const PEOPLE_ROCOCO_DEVELOPMENT: &str = "people-rococo-dev";
-	const PEOPLE_ROCOCO_ED: PeopleBalance = people_rococo_runtime::ExistentialDeposit::get();
 
 	pub fn local_config(
 		id: &str,
 		chain_name: &str,
 		relay_chain: &str,
 		para_id: ParaId,
+		genesis_preset: &str,
 	) -> GenericChainSpec {
 		let mut properties = sc_chain_spec::Properties::new();
 		properties.insert("ss58Format".into(), 42.into());
@@ -134,102 +132,28 @@ pub mod rococo {
 		.with_name(chain_name)
 		.with_id(super::ensure_id(id).expect("invalid id"))
 		.with_chain_type(ChainType::Local)
-		.with_genesis_config_patch(genesis(
-			// initial collators.
-			vec![
-				(
-					get_account_id_from_seed::<sr25519::Public>("Alice"),
-					get_collator_keys_from_seed::<AuraId>("Alice"),
-				),
-				(
-					get_account_id_from_seed::<sr25519::Public>("Bob"),
-					get_collator_keys_from_seed::<AuraId>("Bob"),
-				),
-			],
-			vec![
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				get_account_id_from_seed::<sr25519::Public>("Bob"),
-				get_account_id_from_seed::<sr25519::Public>("Charlie"),
-				get_account_id_from_seed::<sr25519::Public>("Dave"),
-				get_account_id_from_seed::<sr25519::Public>("Eve"),
-				get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-				get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-			],
-			para_id,
-		))
+		.with_genesis_config_preset_name(genesis_preset)
 		.with_properties(properties)
 		.build()
 	}
-
-	fn genesis(
-		invulnerables: Vec<(AccountId, AuraId)>,
-		endowed_accounts: Vec<AccountId>,
-		id: ParaId,
-	) -> serde_json::Value {
-		serde_json::json!({
-			"balances": {
-				"balances": endowed_accounts
-					.iter()
-					.cloned()
-					.map(|k| (k, PEOPLE_ROCOCO_ED * 524_288))
-					.collect::<Vec<_>>(),
-			},
-			"parachainInfo": {
-				"parachainId": id,
-			},
-			"collatorSelection": {
-				"invulnerables": invulnerables
-					.iter()
-					.cloned()
-					.map(|(acc, _)| acc)
-					.collect::<Vec<_>>(),
-				"candidacyBond": PEOPLE_ROCOCO_ED * 16,
-			},
-			"session": {
-				"keys": invulnerables
-					.into_iter()
-					.map(|(acc, aura)| {
-						(
-							acc.clone(),                                 // account id
-							acc,                                         // validator id
-							people_rococo_runtime::SessionKeys { aura }, // session keys
-						)
-					})
-					.collect::<Vec<_>>(),
-			},
-			"polkadotXcm": {
-				"safeXcmVersion": Some(SAFE_XCM_VERSION),
-			}
-		})
-	}
 }
 
 /// Sub-module for Westend setup.
 pub mod westend {
-	use super::{ParaId, PeopleBalance};
-	use crate::chain_spec::{
-		get_account_id_from_seed, get_collator_keys_from_seed, Extensions, GenericChainSpec,
-		SAFE_XCM_VERSION,
-	};
-	use parachains_common::{AccountId, AuraId};
+	use super::ParaId;
+	use crate::chain_spec::{Extensions, GenericChainSpec};
 	use sc_chain_spec::ChainType;
-	use sp_core::sr25519;
 
 	pub(crate) const PEOPLE_WESTEND: &str = "people-westend";
 	pub(crate) const PEOPLE_WESTEND_LOCAL: &str = "people-westend-local";
 	pub(crate) const PEOPLE_WESTEND_DEVELOPMENT: &str = "people-westend-dev";
-	const PEOPLE_WESTEND_ED: PeopleBalance = people_westend_runtime::ExistentialDeposit::get();
 
 	pub fn local_config(
 		id: &str,
 		chain_name: &str,
 		relay_chain: &str,
 		para_id: ParaId,
+		genesis_preset: &str,
 	) -> GenericChainSpec {
 		let mut properties = sc_chain_spec::Properties::new();
 		properties.insert("ss58Format".into(), 42.into());
@@ -244,77 +168,8 @@ pub mod westend {
 		.with_name(chain_name)
 		.with_id(super::ensure_id(id).expect("invalid id"))
 		.with_chain_type(ChainType::Local)
-		.with_genesis_config_patch(genesis(
-			// initial collators.
-			vec![
-				(
-					get_account_id_from_seed::<sr25519::Public>("Alice"),
-					get_collator_keys_from_seed::<AuraId>("Alice"),
-				),
-				(
-					get_account_id_from_seed::<sr25519::Public>("Bob"),
-					get_collator_keys_from_seed::<AuraId>("Bob"),
-				),
-			],
-			vec![
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				get_account_id_from_seed::<sr25519::Public>("Bob"),
-				get_account_id_from_seed::<sr25519::Public>("Charlie"),
-				get_account_id_from_seed::<sr25519::Public>("Dave"),
-				get_account_id_from_seed::<sr25519::Public>("Eve"),
-				get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-				get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-			],
-			para_id,
-		))
+		.with_genesis_config_preset_name(genesis_preset)
 		.with_properties(properties)
 		.build()
 	}
-
-	fn genesis(
-		invulnerables: Vec<(AccountId, AuraId)>,
-		endowed_accounts: Vec<AccountId>,
-		id: ParaId,
-	) -> serde_json::Value {
-		serde_json::json!({
-			"balances": {
-				"balances": endowed_accounts
-					.iter()
-					.cloned()
-					.map(|k| (k, PEOPLE_WESTEND_ED * 524_288))
-					.collect::<Vec<_>>(),
-			},
-			"parachainInfo": {
-				"parachainId": id,
-			},
-			"collatorSelection": {
-				"invulnerables": invulnerables
-					.iter()
-					.cloned()
-					.map(|(acc, _)| acc)
-					.collect::<Vec<_>>(),
-				"candidacyBond": PEOPLE_WESTEND_ED * 16,
-			},
-			"session": {
-				"keys": invulnerables
-					.into_iter()
-					.map(|(acc, aura)| {
-						(
-							acc.clone(),                                  // account id
-							acc,                                          // validator id
-							people_westend_runtime::SessionKeys { aura }, // session keys
-						)
-					})
-					.collect::<Vec<_>>(),
-			},
-			"polkadotXcm": {
-				"safeXcmVersion": Some(SAFE_XCM_VERSION),
-			}
-		})
-	}
 }
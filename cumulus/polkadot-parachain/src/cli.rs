@@ -95,6 +95,14 @@ pub struct Cli {
 	#[arg(long)]
 	pub no_hardware_benchmarks: bool,
 
+	/// Use the slot-based collator, which authors blocks on a timer and is able to build
+	/// multiple blocks per relay chain block when the parachain has multiple cores assigned.
+	///
+	/// Only supported by chains running the rococo parachain runtime. Experimental, do not use in
+	/// production.
+	#[arg(long)]
+	pub experimental_use_slot_based: bool,
+
 	/// Relay chain arguments
 	#[arg(raw = true)]
 	pub relaychain_args: Vec<String>,
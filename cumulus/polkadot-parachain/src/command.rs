@@ -21,6 +21,7 @@ use crate::{
 	fake_runtime_api::{
 		asset_hub_polkadot_aura::RuntimeApi as AssetHubPolkadotRuntimeApi, aura::RuntimeApi,
 	},
+	metadata::{self, AuraConsensusId, BlockNumber, Consensus},
 	service::{new_partial, Block},
 };
 use cumulus_primitives_core::ParaId;
@@ -57,6 +58,11 @@ enum Runtime {
 	BridgeHub(chain_spec::bridge_hubs::BridgeHubRuntimeType),
 	Coretime(chain_spec::coretime::CoretimeRuntimeType),
 	People(chain_spec::people::PeopleRuntimeType),
+	/// A runtime that is not known to this node, with its consensus detected from the runtime
+	/// metadata.
+	///
+	/// The node uses a fixed block type, so only runtimes with `u32` block numbers are supported.
+	Omni(Consensus),
 }
 
 trait RuntimeResolver {
@@ -65,7 +71,10 @@ trait RuntimeResolver {
 
 impl RuntimeResolver for dyn ChainSpec {
 	fn runtime(&self) -> Result<Runtime> {
-		Ok(runtime(self.id()))
+		match runtime(self.id()) {
+			Some(runtime) => Ok(runtime),
+			None => omni_runtime(self),
+		}
 	}
 }
 
@@ -82,15 +91,52 @@ impl RuntimeResolver for PathBuf {
 		let chain_spec: EmptyChainSpecWithId =
 			serde_json::from_reader(reader).map_err(|e| sc_cli::Error::Application(Box::new(e)))?;
 
-		Ok(runtime(&chain_spec.id))
+		match runtime(&chain_spec.id) {
+			Some(runtime) => Ok(runtime),
+			None => omni_runtime(&GenericChainSpec::from_json_file(self.clone())?),
+		}
 	}
 }
 
-fn runtime(id: &str) -> Runtime {
+/// Resolves [`Runtime::Omni`] by inspecting the metadata of the runtime in the chain spec.
+///
+/// This builds the genesis state and executes the runtime, so it is resolved only once by every
+/// command.
+fn omni_runtime(chain_spec: &dyn ChainSpec) -> Result<Runtime> {
+	let info = metadata::detect_runtime_info(chain_spec)?;
+
+	// The node uses a fixed `Block` type with `u32` block numbers.
+	if info.block_number != BlockNumber::U32 {
+		return Err(format!(
+			"The runtime of ChainSpec '{}' uses {:?} block numbers, only U32 is supported",
+			chain_spec.id(),
+			info.block_number,
+		)
+		.into())
+	}
+
+	log::info!(
+		"Using the omni node for ChainSpec's id: '{}', detected consensus: {:?}",
+		chain_spec.id(),
+		info.consensus,
+	);
+	Ok(Runtime::Omni(info.consensus))
+}
+
+/// Resolves the [`Runtime`] from the ChainSpec's id. Returns `None` if the id is not known to this
+/// node, in which case the runtime is expected to be run by the omni node.
+fn runtime(id: &str) -> Option<Runtime> {
 	let id = id.replace('_', "-");
 	let (_, id, para_id) = extract_parachain_id(&id);
 
-	if id.starts_with("shell") {
+	let runtime = if id.starts_with("local-testnet") |
+		id.starts_with("staging-testnet") |
+		id.starts_with("tick") |
+		id.starts_with("trick") |
+		id.starts_with("track")
+	{
+		Runtime::Default
+	} else if id.starts_with("shell") {
 		Runtime::Shell
 	} else if id.starts_with("seedling") {
 		Runtime::Seedling
@@ -126,9 +172,11 @@ fn runtime(id: &str) -> Runtime {
 	} else if id.starts_with(chain_spec::people::PeopleRuntimeType::ID_PREFIX) {
 		Runtime::People(id.parse::<chain_spec::people::PeopleRuntimeType>().expect("Invalid value"))
 	} else {
-		log::warn!("No specific runtime was recognized for ChainSpec's id: '{}', so Runtime::default() will be used", id);
-		Runtime::default()
-	}
+		log::info!("No specific runtime was recognized for ChainSpec's id: '{}'", id);
+		return None
+	};
+
+	Some(runtime)
 }
 
 fn load_spec(id: &str) -> std::result::Result<Box<dyn ChainSpec>, String> {
@@ -395,7 +443,8 @@ impl SubstrateCli for RelayChainCli {
 macro_rules! construct_partials {
 	($config:expr, |$partials:ident| $code:expr) => {
 		match $config.chain_spec.runtime()? {
-			Runtime::AssetHubPolkadot => {
+			Runtime::AssetHubPolkadot |
+			Runtime::Omni(Consensus::Aura(AuraConsensusId::Ed25519)) => {
 				let $partials = new_partial::<AssetHubPolkadotRuntimeApi, _>(
 					&$config,
 					crate::service::aura_build_import_queue::<_, AssetHubPolkadotAuraId>,
@@ -409,14 +458,19 @@ macro_rules! construct_partials {
 			Runtime::CollectivesPolkadot |
 			Runtime::CollectivesWestend |
 			Runtime::Coretime(_) |
-			Runtime::People(_) => {
+			Runtime::People(_) |
+			Runtime::Omni(Consensus::Aura(AuraConsensusId::Sr25519)) => {
 				let $partials = new_partial::<RuntimeApi, _>(
 					&$config,
 					crate::service::aura_build_import_queue::<_, AuraId>,
 				)?;
 				$code
 			},
-			Runtime::GluttonWestend | Runtime::Glutton | Runtime::Shell | Runtime::Seedling => {
+			Runtime::GluttonWestend |
+			Runtime::Glutton |
+			Runtime::Shell |
+			Runtime::Seedling |
+			Runtime::Omni(Consensus::Relay) => {
 				let $partials = new_partial::<RuntimeApi, _>(
 					&$config,
 					crate::service::shell_build_import_queue,
@@ -445,7 +499,8 @@ macro_rules! construct_async_run {
 	(|$components:ident, $cli:ident, $cmd:ident, $config:ident| $( $code:tt )* ) => {{
 		let runner = $cli.create_runner($cmd)?;
 		match runner.config().chain_spec.runtime()? {
-			Runtime::AssetHubPolkadot => {
+			Runtime::AssetHubPolkadot |
+			Runtime::Omni(Consensus::Aura(AuraConsensusId::Ed25519)) => {
 				runner.async_run(|$config| {
 					let $components = new_partial::<AssetHubPolkadotRuntimeApi, _>(
 						&$config,
@@ -462,7 +517,8 @@ macro_rules! construct_async_run {
 			Runtime::CollectivesPolkadot |
 			Runtime::CollectivesWestend |
 			Runtime::Coretime(_) |
-			Runtime::People(_) => {
+			Runtime::People(_) |
+			Runtime::Omni(Consensus::Aura(AuraConsensusId::Sr25519)) => {
 				runner.async_run(|$config| {
 					let $components = new_partial::<RuntimeApi, _>(
 						&$config,
@@ -475,7 +531,8 @@ macro_rules! construct_async_run {
 			Runtime::Shell |
 			Runtime::Seedling |
 			Runtime::GluttonWestend |
-			Runtime::Glutton => {
+			Runtime::Glutton |
+			Runtime::Omni(Consensus::Relay) => {
 				runner.async_run(|$config| {
 					let $components = new_partial::<RuntimeApi, _>(
 						&$config,
@@ -806,6 +863,34 @@ pub fn run() -> Result<()> {
 					}
 					.map_err(Into::into),
 
+					Omni(consensus) => match consensus {
+						Consensus::Aura(AuraConsensusId::Sr25519) =>
+							crate::service::start_generic_aura_lookahead_node::<
+								RuntimeApi,
+								AuraId,
+							>(config, polkadot_config, collator_options, id, hwbench)
+							.await
+							.map(|r| r.0),
+						Consensus::Aura(AuraConsensusId::Ed25519) =>
+							crate::service::start_generic_aura_lookahead_node::<
+								AssetHubPolkadotRuntimeApi,
+								AssetHubPolkadotAuraId,
+							>(config, polkadot_config, collator_options, id, hwbench)
+							.await
+							.map(|r| r.0),
+						Consensus::Relay =>
+							crate::service::start_shell_node::<RuntimeApi>(
+								config,
+								polkadot_config,
+								collator_options,
+								id,
+								hwbench,
+							)
+							.await
+							.map(|r| r.0),
+					}
+					.map_err(Into::into),
+
 					Penpal(_) | Default =>
 						crate::service::start_rococo_parachain_node(
 							config,
@@ -813,6 +898,7 @@ pub fn run() -> Result<()> {
 							collator_options,
 							id,
 							hwbench,
+							cli.experimental_use_slot_based,
 						)
 						.await
 						.map(|r| r.0)
@@ -978,6 +1064,7 @@ mod tests {
 	use crate::{
 		chain_spec::{get_account_id_from_seed, get_from_seed},
 		command::{Runtime, RuntimeResolver},
+		metadata::{AuraConsensusId, Consensus},
 	};
 	use sc_chain_spec::{ChainSpec, ChainSpecExtension, ChainSpecGroup, ChainType, Extension};
 	use serde::{Deserialize, Serialize};
@@ -1074,5 +1161,17 @@ mod tests {
 			Box::new(crate::chain_spec::contracts::contracts_rococo_local_config()),
 		);
 		assert_eq!(Runtime::ContractsRococo, path.runtime().unwrap());
+
+		let path = store_configuration(
+			&temp_dir,
+			Box::new(create_default_with_extensions(
+				"unknown-parachain",
+				crate::chain_spec::Extensions { relay_chain: "rococo-local".into(), para_id: 1000 },
+			)),
+		);
+		assert_eq!(
+			Runtime::Omni(Consensus::Aura(AuraConsensusId::Sr25519)),
+			path.runtime().unwrap()
+		);
 	}
 }
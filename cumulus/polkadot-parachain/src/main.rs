@@ -23,6 +23,7 @@ mod chain_spec;
 mod cli;
 mod command;
 mod fake_runtime_api;
+mod metadata;
 mod rpc;
 mod service;
 
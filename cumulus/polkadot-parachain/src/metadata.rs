@@ -0,0 +1,210 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Helpers for inspecting the metadata of an arbitrary parachain runtime.
+//!
+//! The omni node uses them to figure out which consensus and which block number type a runtime
+//! expects, based solely on the runtime code found in the genesis state of its chain spec.
+
+use codec::Decode;
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use sc_chain_spec::ChainSpec;
+use sc_executor::WasmExecutor;
+use scale_info::{form::PortableForm, PortableRegistry, Type, TypeDef, TypeDefPrimitive};
+use sp_core::{
+	storage::well_known_keys::CODE,
+	traits::{CallContext, CodeExecutor, FetchRuntimeCode, RuntimeCode},
+};
+use sp_runtime::BuildStorage;
+use sp_state_machine::BasicExternalities;
+use std::borrow::Cow;
+
+/// The Aura authority id type used by a runtime.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AuraConsensusId {
+	Sr25519,
+	Ed25519,
+}
+
+/// The consensus used by a runtime.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Consensus {
+	/// The runtime is using Aura with the given authority id type.
+	Aura(AuraConsensusId),
+	/// The runtime does not include the Aura pallet and relies on the relay chain for consensus.
+	Relay,
+}
+
+/// The block number type used by a runtime.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BlockNumber {
+	U32,
+	U64,
+}
+
+/// Runtime properties that are detected from the runtime metadata.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct RuntimeInfo {
+	pub consensus: Consensus,
+	pub block_number: BlockNumber,
+}
+
+struct RuntimeCodeFetcher<'a>(&'a [u8]);
+
+impl<'a> FetchRuntimeCode for RuntimeCodeFetcher<'a> {
+	fn fetch_runtime_code(&self) -> Option<Cow<[u8]>> {
+		Some(self.0.into())
+	}
+}
+
+/// Fetches and decodes the metadata of the runtime found in the genesis state of `chain_spec`.
+fn fetch_metadata(chain_spec: &dyn ChainSpec) -> Result<RuntimeMetadata, String> {
+	let storage = chain_spec.build_storage()?;
+	let code = storage.top.get(CODE).ok_or_else(|| {
+		"The genesis state of the chain spec does not contain `:code`".to_string()
+	})?;
+
+	let executor = WasmExecutor::<sp_io::SubstrateHostFunctions>::builder()
+		.with_allow_missing_host_functions(true)
+		.build();
+	let fetcher = RuntimeCodeFetcher(code);
+	let runtime_code = RuntimeCode {
+		heap_pages: None,
+		code_fetcher: &fetcher,
+		hash: sp_core::blake2_256(code).to_vec(),
+	};
+
+	let mut ext = BasicExternalities::new_empty();
+	let call_result = executor
+		.call(&mut ext, &runtime_code, "Metadata_metadata", &[], CallContext::Offchain)
+		.0
+		.map_err(|e| format!("Failed to fetch the runtime metadata: {e}"))?;
+
+	let opaque = Vec::<u8>::decode(&mut &call_result[..])
+		.map_err(|e| format!("Failed to decode the opaque metadata: {e}"))?;
+	let prefixed = RuntimeMetadataPrefixed::decode(&mut &opaque[..])
+		.map_err(|e| format!("Failed to decode the runtime metadata: {e}"))?;
+
+	Ok(prefixed.1)
+}
+
+/// Returns the type registry of `metadata`.
+fn types(metadata: &RuntimeMetadata) -> Result<&PortableRegistry, String> {
+	match metadata {
+		RuntimeMetadata::V14(metadata) => Ok(&metadata.types),
+		RuntimeMetadata::V15(metadata) => Ok(&metadata.types),
+		_ => Err("Unsupported runtime metadata version".into()),
+	}
+}
+
+/// Returns the type of the `entry` storage value of `pallet`, if the runtime declares it.
+fn storage_value_type<'a>(
+	metadata: &'a RuntimeMetadata,
+	pallet: &str,
+	entry: &str,
+) -> Result<Option<&'a Type<PortableForm>>, String> {
+	macro_rules! find_storage_value_type_id {
+		($metadata:expr, $plain:path) => {
+			$metadata
+				.pallets
+				.iter()
+				.find(|p| p.name == pallet)
+				.and_then(|p| p.storage.as_ref())
+				.and_then(|s| s.entries.iter().find(|e| e.name == entry))
+				.and_then(|e| match &e.ty {
+					$plain(ty) => Some(ty.id),
+					_ => None,
+				})
+		};
+	}
+
+	let id = match metadata {
+		RuntimeMetadata::V14(metadata) =>
+			find_storage_value_type_id!(metadata, frame_metadata::v14::StorageEntryType::Plain),
+		RuntimeMetadata::V15(metadata) =>
+			find_storage_value_type_id!(metadata, frame_metadata::v15::StorageEntryType::Plain),
+		_ => return Err("Unsupported runtime metadata version".into()),
+	};
+
+	id.map(|id| {
+		types(metadata)?
+			.resolve(id)
+			.ok_or_else(|| format!("Type of `{pallet}::{entry}` not found"))
+	})
+	.transpose()
+}
+
+/// Detects the block number type from the type of `System::Number`.
+fn block_number(metadata: &RuntimeMetadata) -> Result<BlockNumber, String> {
+	let ty = storage_value_type(metadata, "System", "Number")?
+		.ok_or_else(|| "The runtime does not expose `System::Number`".to_string())?;
+
+	match ty.type_def {
+		TypeDef::Primitive(TypeDefPrimitive::U32) => Ok(BlockNumber::U32),
+		TypeDef::Primitive(TypeDefPrimitive::U64) => Ok(BlockNumber::U64),
+		_ => Err(format!("Unsupported block number type: {:?}", ty.type_def)),
+	}
+}
+
+/// Detects the consensus from the presence and the type of `Aura::Authorities`.
+fn consensus(metadata: &RuntimeMetadata) -> Result<Consensus, String> {
+	let Some(authorities) = storage_value_type(metadata, "Aura", "Authorities")? else {
+		return Ok(Consensus::Relay)
+	};
+
+	// `Aura::Authorities` is a `BoundedVec<AuthorityId, _>`, so the authority id is the first type
+	// parameter. Its path is `sp_consensus_aura::{sr25519,ed25519}::app_{sr25519,ed25519}::Public`.
+	let authority_id_path = authorities
+		.type_params
+		.first()
+		.and_then(|param| param.ty.as_ref())
+		.and_then(|ty| types(metadata).ok()?.resolve(ty.id))
+		.map(|ty| ty.path.segments.clone())
+		.ok_or_else(|| "Could not resolve the Aura authority id type".to_string())?;
+
+	if authority_id_path.iter().any(|s| s == "sr25519") {
+		Ok(Consensus::Aura(AuraConsensusId::Sr25519))
+	} else if authority_id_path.iter().any(|s| s == "ed25519") {
+		Ok(Consensus::Aura(AuraConsensusId::Ed25519))
+	} else {
+		Err(format!("Unsupported Aura authority id type: {}", authority_id_path.join("::")))
+	}
+}
+
+/// Detects the consensus and the block number type of the runtime in `chain_spec`.
+pub fn detect_runtime_info(chain_spec: &dyn ChainSpec) -> Result<RuntimeInfo, String> {
+	let metadata = fetch_metadata(chain_spec)?;
+
+	Ok(RuntimeInfo { consensus: consensus(&metadata)?, block_number: block_number(&metadata)? })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn detect_runtime_info_works() {
+		let chain_spec = crate::chain_spec::rococo_parachain::rococo_parachain_local_config();
+
+		assert_eq!(
+			detect_runtime_info(&chain_spec),
+			Ok(RuntimeInfo {
+				consensus: Consensus::Aura(AuraConsensusId::Sr25519),
+				block_number: BlockNumber::U32,
+			})
+		);
+	}
+}
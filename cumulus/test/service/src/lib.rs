@@ -62,7 +62,9 @@ use polkadot_service::ProvideRuntimeApi;
 use sc_consensus::ImportQueue;
 use sc_network::{
 	config::{FullNetworkConfiguration, TransportConfig},
-	multiaddr, NetworkBlock, NetworkService, NetworkStateInfo,
+	multiaddr,
+	service::traits::NetworkService,
+	NetworkBlock, NetworkStateInfo,
 };
 use sc_service::{
 	config::{
@@ -74,7 +76,7 @@ use sc_service::{
 };
 use sp_arithmetic::traits::SaturatedConversion;
 use sp_blockchain::HeaderBackend;
-use sp_core::{Pair, H256};
+use sp_core::Pair;
 use sp_keyring::Sr25519Keyring;
 use sp_runtime::{codec::Encode, generic};
 use sp_state_machine::BasicExternalities;
@@ -137,7 +139,7 @@ pub type Backend = TFullBackend<Block>;
 pub type ParachainBlockImport = TParachainBlockImport<Block, Arc<Client>, Backend>;
 
 /// Transaction pool type used by the test service
-pub type TransactionPool = Arc<sc_transaction_pool::FullPool<Block, Client>>;
+pub type TransactionPool = Arc<sc_transaction_pool::TransactionPoolHandle<Block, Client>>;
 
 /// Recovery handle that fails regularly to simulate unavailable povs.
 pub struct FailingRecoveryHandle {
@@ -185,7 +187,7 @@ pub type Service = PartialComponents<
 	Backend,
 	(),
 	sc_consensus::import_queue::BasicQueue<Block>,
-	sc_transaction_pool::FullPool<Block, Client>,
+	sc_transaction_pool::TransactionPoolHandle<Block, Client>,
 	ParachainBlockImport,
 >;
 
@@ -226,12 +228,15 @@ pub fn new_partial(
 
 	let registry = config.prometheus_registry();
 
-	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
-		config.transaction_pool.clone(),
-		config.role.is_authority().into(),
-		config.prometheus_registry(),
-		task_manager.spawn_essential_handle(),
-		client.clone(),
+	let transaction_pool = Arc::from(
+		sc_transaction_pool::Builder::new(
+			task_manager.spawn_essential_handle(),
+			client.clone(),
+			config.role.is_authority().into(),
+		)
+		.with_options(config.transaction_pool.clone())
+		.with_prometheus(config.prometheus_registry())
+		.build(),
 	);
 
 	let import_queue = cumulus_client_consensus_relay_chain::import_queue(
@@ -318,7 +323,7 @@ pub async fn start_node_impl<RB>(
 ) -> sc_service::error::Result<(
 	TaskManager,
 	Arc<Client>,
-	Arc<NetworkService<Block, H256>>,
+	Arc<dyn NetworkService>,
 	RpcHandlers,
 	TransactionPool,
 	Arc<Backend>,
@@ -494,7 +499,7 @@ pub struct TestNode {
 	/// Client's instance.
 	pub client: Arc<Client>,
 	/// Node's network.
-	pub network: Arc<NetworkService<Block, H256>>,
+	pub network: Arc<dyn NetworkService>,
 	/// The `MultiaddrWithPeerId` to this node. This is useful if you want to pass it as "boot
 	/// node" to other nodes.
 	pub addr: MultiaddrWithPeerId,
@@ -787,6 +792,9 @@ pub fn node_config(
 		trie_cache_maximum_size: Some(64 * 1024 * 1024),
 		state_pruning: Some(PruningMode::ArchiveAll),
 		blocks_pruning: BlocksPruning::KeepAll,
+		blocks_archive: None,
+		block_timestamp: None,
+		blocks_compaction: false,
 		chain_spec: spec,
 		wasm_method: WasmExecutionMethod::Compiled {
 			instantiation_strategy: sc_executor_wasmtime::InstantiationStrategy::PoolingCopyOnWrite,
@@ -48,8 +48,8 @@ use polkadot_node_subsystem_util::{
 };
 use polkadot_primitives::{
 	collator_signature_payload, CandidateCommitments, CandidateDescriptor, CandidateReceipt,
-	CollatorPair, CoreState, Hash, Id as ParaId, OccupiedCoreAssumption, PersistedValidationData,
-	ValidationCodeHash,
+	CollatorPair, CoreIndex, CoreState, Hash, Id as ParaId, OccupiedCoreAssumption,
+	PersistedValidationData, ValidationCodeHash,
 };
 use sp_core::crypto::Pair;
 use std::sync::Arc;
@@ -358,6 +358,7 @@ async fn handle_new_activations<Context>(
 							validation_data,
 							validation_code_hash,
 							n_validators,
+							core_index: CoreIndex(core_idx as u32),
 						},
 						task_config.key.clone(),
 						&mut task_sender,
@@ -387,6 +388,7 @@ async fn handle_submit_collation<Context>(
 		collation,
 		parent_head,
 		validation_code_hash,
+		core_index,
 		result_sender,
 	} = params;
 
@@ -424,6 +426,7 @@ async fn handle_submit_collation<Context>(
 		validation_data,
 		validation_code_hash,
 		n_validators,
+		core_index,
 	};
 
 	construct_and_distribute_receipt(
@@ -445,6 +448,7 @@ struct PreparedCollation {
 	validation_data: PersistedValidationData,
 	validation_code_hash: ValidationCodeHash,
 	n_validators: usize,
+	core_index: CoreIndex,
 }
 
 /// Takes a prepared collation, along with its context, and produces a candidate receipt
@@ -463,6 +467,7 @@ async fn construct_and_distribute_receipt(
 		validation_data,
 		validation_code_hash,
 		n_validators,
+		core_index,
 	} = collation;
 
 	let persisted_validation_data_hash = validation_data.hash();
@@ -537,6 +542,7 @@ async fn construct_and_distribute_receipt(
 			erasure_root,
 			para_head: commitments.head_data.hash(),
 			validation_code_hash,
+			core_index,
 		},
 	};
 
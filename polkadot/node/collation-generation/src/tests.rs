@@ -386,6 +386,7 @@ fn sends_distribute_collation_message() {
 		erasure_root: dummy_hash(), // this isn't something we're checking right now
 		para_head: test_collation().head_data.hash(),
 		validation_code_hash: expect_validation_code_hash,
+		core_index: CoreIndex(1),
 	};
 
 	assert_eq!(to_collator_protocol.len(), 1);
@@ -550,6 +551,7 @@ fn submit_collation_is_no_op_before_initialization() {
 					collation: test_collation(),
 					parent_head: vec![1, 2, 3].into(),
 					validation_code_hash: Hash::repeat_byte(1).into(),
+					core_index: CoreIndex(0),
 					result_sender: None,
 				}),
 			})
@@ -586,6 +588,7 @@ fn submit_collation_leads_to_distribution() {
 					collation: test_collation(),
 					parent_head: vec![1, 2, 3].into(),
 					validation_code_hash,
+					core_index: CoreIndex(2),
 					result_sender: None,
 				}),
 			})
@@ -622,9 +625,11 @@ fn submit_collation_leads_to_distribution() {
 			AllMessages::CollatorProtocol(CollatorProtocolMessage::DistributeCollation(
 				ccr,
 				parent_head_data_hash,
-				..
+				_pov,
+				_result_sender,
 			)) => {
 				assert_eq!(parent_head_data_hash, parent_head.hash());
+				assert_eq!(ccr.descriptor().core_index, CoreIndex(2));
 				assert_eq!(ccr.descriptor().persisted_validation_data_hash, expected_pvd.hash());
 				assert_eq!(ccr.descriptor().para_head, dummy_head_data().hash());
 				assert_eq!(ccr.descriptor().validation_code_hash, validation_code_hash);
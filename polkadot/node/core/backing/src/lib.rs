@@ -210,6 +210,10 @@ struct PerRelayParentState {
 	parent: Hash,
 	/// The `ParaId` assigned to the local validator at this relay parent.
 	assignment: Option<ParaId>,
+	/// The `CoreIndex` assigned to the local validator at this relay parent.
+	assigned_core: Option<CoreIndex>,
+	/// The para scheduled on or occupying each core at this relay parent.
+	core_paras: HashMap<CoreIndex, ParaId>,
 	/// The candidates that are backed by enough validators in their group, by hash.
 	backed: HashSet<CandidateHash>,
 	/// The table of candidates and statements under this relay-parent.
@@ -382,7 +386,7 @@ struct AttestingData {
 #[derive(Default)]
 struct TableContext {
 	validator: Option<Validator>,
-	groups: HashMap<ParaId, Vec<ValidatorIndex>>,
+	groups: HashMap<CoreIndex, Vec<ValidatorIndex>>,
 	validators: Vec<ValidatorId>,
 	disabled_validators: Vec<ValidatorIndex>,
 }
@@ -404,7 +408,7 @@ impl TableContext {
 impl TableContextTrait for TableContext {
 	type AuthorityId = ValidatorIndex;
 	type Digest = CandidateHash;
-	type GroupId = ParaId;
+	type GroupId = CoreIndex;
 	type Signature = ValidatorSignature;
 	type Candidate = CommittedCandidateReceipt;
 
@@ -412,15 +416,15 @@ impl TableContextTrait for TableContext {
 		candidate.hash()
 	}
 
-	fn candidate_group(candidate: &CommittedCandidateReceipt) -> ParaId {
-		candidate.descriptor().para_id
+	fn candidate_group(candidate: &CommittedCandidateReceipt) -> CoreIndex {
+		candidate.descriptor().core_index
 	}
 
-	fn is_member_of(&self, authority: &ValidatorIndex, group: &ParaId) -> bool {
+	fn is_member_of(&self, authority: &ValidatorIndex, group: &CoreIndex) -> bool {
 		self.groups.get(group).map_or(false, |g| g.iter().any(|a| a == authority))
 	}
 
-	fn get_group_size(&self, group: &ParaId) -> Option<usize> {
+	fn get_group_size(&self, group: &CoreIndex) -> Option<usize> {
 		self.groups.get(group).map(|g| g.len())
 	}
 }
@@ -442,19 +446,19 @@ fn primitive_statement_to_table(s: &SignedFullStatementWithPVD) -> TableSignedSt
 
 fn table_attested_to_backed(
 	attested: TableAttestedCandidate<
-		ParaId,
+		CoreIndex,
 		CommittedCandidateReceipt,
 		ValidatorIndex,
 		ValidatorSignature,
 	>,
 	table_context: &TableContext,
 ) -> Option<BackedCandidate> {
-	let TableAttestedCandidate { candidate, validity_votes, group_id: para_id } = attested;
+	let TableAttestedCandidate { candidate, validity_votes, group_id: core_index } = attested;
 
 	let (ids, validity_votes): (Vec<_>, Vec<ValidityAttestation>) =
 		validity_votes.into_iter().map(|(id, vote)| (id, vote.into())).unzip();
 
-	let group = table_context.groups.get(&para_id)?;
+	let group = table_context.groups.get(&core_index)?;
 
 	let mut validator_indices = BitVec::with_capacity(group.len());
 
@@ -1058,6 +1062,8 @@ async fn construct_per_relay_parent_state<Context>(
 	let mut groups = HashMap::new();
 	let n_cores = cores.len();
 	let mut assignment = None;
+	let mut assigned_core = None;
+	let mut core_paras = HashMap::new();
 
 	for (idx, core) in cores.into_iter().enumerate() {
 		let core_para_id = match core {
@@ -1078,8 +1084,10 @@ async fn construct_per_relay_parent_state<Context>(
 		if let Some(g) = validator_groups.get(group_index.0 as usize) {
 			if validator.as_ref().map_or(false, |v| g.contains(&v.index())) {
 				assignment = Some(core_para_id);
+				assigned_core = Some(core_index);
 			}
-			groups.insert(core_para_id, g.clone());
+			core_paras.insert(core_index, core_para_id);
+			groups.insert(core_index, g.clone());
 		}
 	}
 
@@ -1095,6 +1103,8 @@ async fn construct_per_relay_parent_state<Context>(
 		prospective_parachains_mode: mode,
 		parent,
 		assignment,
+		assigned_core,
+		core_paras,
 		backed: HashSet::new(),
 		table: Table::new(table_config),
 		table_context,
@@ -1542,6 +1552,22 @@ async fn import_statement<Context>(
 	// We should also not accept any candidates which have no valid depths under any of
 	// our active leaves.
 	if let StatementWithPVD::Seconded(candidate, pvd) = statement.payload() {
+		// The candidate must be built for a core its para is scheduled on or occupying.
+		let core_index = candidate.descriptor().core_index;
+		let core_para_id = rp_state.core_paras.get(&core_index).copied();
+		if core_para_id != Some(candidate.descriptor().para_id) {
+			gum::debug!(
+				target: LOG_TARGET,
+				?candidate_hash,
+				para_id = ?candidate.descriptor().para_id,
+				?core_index,
+				?core_para_id,
+				"Not importing statement for a candidate built for a core not assigned to its para",
+			);
+
+			return Ok(None)
+		}
+
 		if !per_candidate.contains_key(&candidate_hash) {
 			if rp_state.prospective_parachains_mode.is_enabled() {
 				let (tx, rx) = oneshot::channel();
@@ -1859,7 +1885,7 @@ async fn maybe_validate_and_import<Context>(
 
 		let candidate_hash = summary.candidate;
 
-		if Some(summary.group_id) != rp_state.assignment {
+		if Some(summary.group_id) != rp_state.assigned_core {
 			return Ok(())
 		}
 		let attesting = match statement.payload() {
@@ -2015,6 +2041,18 @@ async fn handle_second_message<Context>(
 		return Ok(())
 	}
 
+	// Sanity check that candidate is built for our core.
+	if Some(candidate.descriptor().core_index) != rp_state.assigned_core {
+		gum::debug!(
+			target: LOG_TARGET,
+			our_core = ?rp_state.assigned_core,
+			collation_core = ?candidate.descriptor().core_index,
+			"Subsystem asked to second for core outside of our assignment",
+		);
+
+		return Ok(())
+	}
+
 	// If the message is a `CandidateBackingMessage::Second`, sign and dispatch a
 	// Seconded statement only if we have not signed a Valid statement for the requested candidate.
 	//
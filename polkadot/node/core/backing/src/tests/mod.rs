@@ -193,6 +193,7 @@ fn make_erasure_root(test: &TestState, pov: PoV, validation_data: PersistedValid
 #[derive(Default, Clone)]
 struct TestCandidateBuilder {
 	para_id: ParaId,
+	core_index: CoreIndex,
 	head_data: HeadData,
 	pov_hash: Hash,
 	relay_parent: Hash,
@@ -214,6 +215,7 @@ impl TestCandidateBuilder {
 				para_head: self.head_data.hash(),
 				validation_code_hash: ValidationCode(self.validation_code).hash(),
 				persisted_validation_data_hash: self.persisted_validation_data_hash,
+				core_index: self.core_index,
 			},
 			commitments: CandidateCommitments {
 				head_data: self.head_data,
@@ -409,6 +411,7 @@ fn backing_second_works() {
 		let pov_hash = pov.hash();
 		let candidate = TestCandidateBuilder {
 			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(0),
 			relay_parent: test_state.relay_parent,
 			pov_hash,
 			head_data: expected_head_data.clone(),
@@ -493,6 +496,7 @@ fn backing_works() {
 
 		let candidate_a = TestCandidateBuilder {
 			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(0),
 			relay_parent: test_state.relay_parent,
 			pov_hash,
 			head_data: expected_head_data.clone(),
@@ -655,6 +659,7 @@ fn backing_works_while_validation_ongoing() {
 
 		let candidate_a = TestCandidateBuilder {
 			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(0),
 			relay_parent: test_state.relay_parent,
 			pov_hash,
 			head_data: expected_head_data.clone(),
@@ -844,6 +849,7 @@ fn backing_misbehavior_works() {
 
 		let candidate_a = TestCandidateBuilder {
 			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(0),
 			relay_parent: test_state.relay_parent,
 			pov_hash,
 			erasure_root: make_erasure_root(&test_state, pov_a.clone(), pvd_a.clone()),
@@ -1040,6 +1046,7 @@ fn backing_dont_second_invalid() {
 
 		let candidate_a = TestCandidateBuilder {
 			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(0),
 			relay_parent: test_state.relay_parent,
 			pov_hash: pov_hash_a,
 			erasure_root: make_erasure_root(&test_state, pov_block_a.clone(), pvd_a.clone()),
@@ -1051,6 +1058,7 @@ fn backing_dont_second_invalid() {
 
 		let candidate_b = TestCandidateBuilder {
 			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(0),
 			relay_parent: test_state.relay_parent,
 			pov_hash: pov_hash_b,
 			erasure_root: make_erasure_root(&test_state, pov_block_b.clone(), pvd_b.clone()),
@@ -1188,6 +1196,7 @@ fn backing_second_after_first_fails_works() {
 
 		let candidate = TestCandidateBuilder {
 			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(0),
 			relay_parent: test_state.relay_parent,
 			pov_hash,
 			erasure_root: make_erasure_root(&test_state, pov_a.clone(), pvd_a.clone()),
@@ -1279,6 +1288,7 @@ fn backing_second_after_first_fails_works() {
 
 		let candidate_to_second = TestCandidateBuilder {
 			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(0),
 			relay_parent: test_state.relay_parent,
 			pov_hash,
 			erasure_root: make_erasure_root(
@@ -1334,6 +1344,7 @@ fn backing_works_after_failed_validation() {
 
 		let candidate = TestCandidateBuilder {
 			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(0),
 			relay_parent: test_state.relay_parent,
 			pov_hash,
 			erasure_root: make_erasure_root(&test_state, pov_a.clone(), pvd_a.clone()),
@@ -1422,7 +1433,7 @@ fn backing_works_after_failed_validation() {
 fn candidate_backing_reorders_votes() {
 	use sp_core::Encode;
 
-	let para_id = ParaId::from(10);
+	let core_idx = CoreIndex(10);
 	let validators = vec![
 		Sr25519Keyring::Alice,
 		Sr25519Keyring::Bob,
@@ -1436,7 +1447,7 @@ fn candidate_backing_reorders_votes() {
 	let validator_groups = {
 		let mut validator_groups = HashMap::new();
 		validator_groups
-			.insert(para_id, vec![0, 1, 2, 3, 4, 5].into_iter().map(ValidatorIndex).collect());
+			.insert(core_idx, vec![0, 1, 2, 3, 4, 5].into_iter().map(ValidatorIndex).collect());
 		validator_groups
 	};
 
@@ -1466,7 +1477,7 @@ fn candidate_backing_reorders_votes() {
 			(ValidatorIndex(3), fake_attestation(3)),
 			(ValidatorIndex(1), fake_attestation(1)),
 		],
-		group_id: para_id,
+		group_id: core_idx,
 	};
 
 	let backed = table_attested_to_backed(attested, &table_context).unwrap();
@@ -1506,6 +1517,7 @@ fn retry_works() {
 
 		let candidate = TestCandidateBuilder {
 			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(0),
 			relay_parent: test_state.relay_parent,
 			pov_hash,
 			erasure_root: make_erasure_root(&test_state, pov_a.clone(), pvd_a.clone()),
@@ -1687,6 +1699,7 @@ fn observes_backing_even_if_not_validator() {
 
 		let candidate_a = TestCandidateBuilder {
 			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(0),
 			relay_parent: test_state.relay_parent,
 			pov_hash,
 			head_data: expected_head_data.clone(),
@@ -1804,6 +1817,7 @@ fn cannot_second_multiple_candidates_per_parent() {
 		let pov_hash = pov.hash();
 		let candidate_builder = TestCandidateBuilder {
 			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(0),
 			relay_parent: test_state.relay_parent,
 			pov_hash,
 			head_data: expected_head_data.clone(),
@@ -1948,6 +1962,7 @@ fn new_leaf_view_doesnt_clobber_old() {
 		let pov_hash = pov.hash();
 		let candidate = TestCandidateBuilder {
 			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(0),
 			relay_parent: test_state.relay_parent,
 			pov_hash,
 			head_data: expected_head_data.clone(),
@@ -1999,6 +2014,7 @@ fn disabled_validator_doesnt_distribute_statement_on_receiving_second() {
 		let pov_hash = pov.hash();
 		let candidate = TestCandidateBuilder {
 			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(0),
 			relay_parent: test_state.relay_parent,
 			pov_hash,
 			head_data: expected_head_data.clone(),
@@ -2047,6 +2063,7 @@ fn disabled_validator_doesnt_distribute_statement_on_receiving_statement() {
 		let pov_hash = pov.hash();
 		let candidate = TestCandidateBuilder {
 			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(0),
 			relay_parent: test_state.relay_parent,
 			pov_hash,
 			head_data: expected_head_data.clone(),
@@ -2109,6 +2126,7 @@ fn validator_ignores_statements_from_disabled_validators() {
 		let pov_hash = pov.hash();
 		let candidate = TestCandidateBuilder {
 			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(0),
 			relay_parent: test_state.relay_parent,
 			pov_hash,
 			head_data: expected_head_data.clone(),
@@ -2284,3 +2302,115 @@ fn validator_ignores_statements_from_disabled_validators() {
 		virtual_overseer
 	});
 }
+
+// Test that a validator doesn't second a candidate built for a core other than the one its group
+// is assigned to.
+#[test]
+fn backing_doesnt_second_candidate_for_other_core() {
+	let test_state = TestState::default();
+
+	test_harness(test_state.keystore.clone(), |mut virtual_overseer| async move {
+		test_startup(&mut virtual_overseer, &test_state).await;
+
+		let pov = PoV { block_data: BlockData(vec![42, 43, 44]) };
+		let pvd = dummy_pvd();
+		let validation_code = ValidationCode(vec![1, 2, 3]);
+
+		let expected_head_data = test_state.head_data.get(&test_state.chain_ids[0]).unwrap();
+
+		let pov_hash = pov.hash();
+		let candidate = TestCandidateBuilder {
+			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(1),
+			relay_parent: test_state.relay_parent,
+			pov_hash,
+			head_data: expected_head_data.clone(),
+			erasure_root: make_erasure_root(&test_state, pov.clone(), pvd.clone()),
+			persisted_validation_data_hash: pvd.hash(),
+			validation_code: validation_code.0.clone(),
+		}
+		.build();
+
+		let second = CandidateBackingMessage::Second(
+			test_state.relay_parent,
+			candidate.to_plain(),
+			pvd.clone(),
+			pov.clone(),
+		);
+
+		virtual_overseer.send(FromOrchestra::Communication { msg: second }).await;
+
+		// Ensure backing subsystem is not doing any work
+		assert_matches!(virtual_overseer.recv().timeout(Duration::from_secs(1)).await, None);
+
+		virtual_overseer
+			.send(FromOrchestra::Signal(OverseerSignal::ActiveLeaves(
+				ActiveLeavesUpdate::stop_work(test_state.relay_parent),
+			)))
+			.await;
+		virtual_overseer
+	});
+}
+
+// Test that a validator ignores a `Seconded` statement about a candidate built for a core its para
+// is not assigned to.
+#[test]
+fn validator_ignores_seconded_statement_for_core_of_other_para() {
+	let test_state = TestState::default();
+
+	test_harness(test_state.keystore.clone(), |mut virtual_overseer| async move {
+		test_startup(&mut virtual_overseer, &test_state).await;
+
+		let pov = PoV { block_data: BlockData(vec![42, 43, 44]) };
+		let pvd = dummy_pvd();
+		let validation_code = ValidationCode(vec![1, 2, 3]);
+
+		let expected_head_data = test_state.head_data.get(&test_state.chain_ids[0]).unwrap();
+
+		let pov_hash = pov.hash();
+		// Core 1 is assigned to the second para.
+		let candidate = TestCandidateBuilder {
+			para_id: test_state.chain_ids[0],
+			core_index: CoreIndex(1),
+			relay_parent: test_state.relay_parent,
+			pov_hash,
+			head_data: expected_head_data.clone(),
+			erasure_root: make_erasure_root(&test_state, pov.clone(), pvd.clone()),
+			persisted_validation_data_hash: pvd.hash(),
+			validation_code: validation_code.0.clone(),
+		}
+		.build();
+
+		let public2 = Keystore::sr25519_generate_new(
+			&*test_state.keystore,
+			ValidatorId::ID,
+			Some(&test_state.validators[2].to_seed()),
+		)
+		.expect("Insert key into keystore");
+
+		let signed = SignedFullStatementWithPVD::sign(
+			&test_state.keystore,
+			StatementWithPVD::Seconded(candidate.clone(), pvd.clone()),
+			&test_state.signing_context,
+			ValidatorIndex(2),
+			&public2.into(),
+		)
+		.ok()
+		.flatten()
+		.expect("should be signed");
+
+		let statement = CandidateBackingMessage::Statement(test_state.relay_parent, signed.clone());
+
+		virtual_overseer.send(FromOrchestra::Communication { msg: statement }).await;
+
+		// Ensure backing subsystem is not doing any work
+		assert_matches!(virtual_overseer.recv().timeout(Duration::from_secs(1)).await, None);
+
+		virtual_overseer
+			.send(FromOrchestra::Signal(OverseerSignal::ActiveLeaves(
+				ActiveLeavesUpdate::stop_work(test_state.relay_parent),
+			)))
+			.await;
+		virtual_overseer
+	});
+}
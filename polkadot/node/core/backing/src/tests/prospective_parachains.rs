@@ -365,6 +365,7 @@ fn seconding_sanity_check_allowed() {
 		let pov_hash = pov.hash();
 		let candidate = TestCandidateBuilder {
 			para_id,
+			core_index: CoreIndex(0),
 			relay_parent: leaf_a_parent,
 			pov_hash,
 			head_data: expected_head_data.clone(),
@@ -510,6 +511,7 @@ fn seconding_sanity_check_disallowed() {
 		let pov_hash = pov.hash();
 		let candidate = TestCandidateBuilder {
 			para_id,
+			core_index: CoreIndex(0),
 			relay_parent: leaf_a_parent,
 			pov_hash,
 			head_data: expected_head_data.clone(),
@@ -611,6 +613,7 @@ fn seconding_sanity_check_disallowed() {
 		let leaf_a_grandparent = get_parent_hash(leaf_a_parent);
 		let candidate = TestCandidateBuilder {
 			para_id,
+			core_index: CoreIndex(0),
 			relay_parent: leaf_a_grandparent,
 			pov_hash,
 			head_data: expected_head_data.clone(),
@@ -712,6 +715,7 @@ fn prospective_parachains_reject_candidate() {
 		let pov_hash = pov.hash();
 		let candidate = TestCandidateBuilder {
 			para_id,
+			core_index: CoreIndex(0),
 			relay_parent: leaf_a_parent,
 			pov_hash,
 			head_data: expected_head_data.clone(),
@@ -890,6 +894,7 @@ fn second_multiple_candidates_per_relay_parent() {
 		let pov_hash = pov.hash();
 		let candidate_a = TestCandidateBuilder {
 			para_id,
+			core_index: CoreIndex(0),
 			relay_parent: leaf_parent,
 			pov_hash,
 			head_data: expected_head_data.clone(),
@@ -1027,6 +1032,7 @@ fn backing_works() {
 
 		let candidate_a = TestCandidateBuilder {
 			para_id,
+			core_index: CoreIndex(0),
 			relay_parent: leaf_parent,
 			pov_hash,
 			head_data: expected_head_data.clone(),
@@ -1203,6 +1209,7 @@ fn concurrent_dependent_candidates() {
 
 		let candidate_a = TestCandidateBuilder {
 			para_id,
+			core_index: CoreIndex(0),
 			relay_parent: leaf_grandparent,
 			pov_hash: pov_a.hash(),
 			head_data: head_data[1].clone(),
@@ -1213,6 +1220,7 @@ fn concurrent_dependent_candidates() {
 		.build();
 		let candidate_b = TestCandidateBuilder {
 			para_id,
+			core_index: CoreIndex(0),
 			relay_parent: leaf_parent,
 			pov_hash: pov_b.hash(),
 			head_data: head_data[2].clone(),
@@ -1436,6 +1444,7 @@ fn seconding_sanity_check_occupy_same_depth() {
 		let pov_hash = pov.hash();
 		let candidate_a = TestCandidateBuilder {
 			para_id: para_id_a,
+			core_index: CoreIndex(0),
 			relay_parent: leaf_parent,
 			pov_hash,
 			head_data: expected_head_data_a.clone(),
@@ -1446,6 +1455,7 @@ fn seconding_sanity_check_occupy_same_depth() {
 
 		let mut candidate_b = candidate_a.clone();
 		candidate_b.para_id = para_id_b;
+		candidate_b.core_index = CoreIndex(1);
 		candidate_b.head_data = expected_head_data_b.clone();
 		// A rotation happens, test validator is assigned to second para here.
 		candidate_b.relay_parent = leaf_hash;
@@ -1587,6 +1597,7 @@ fn occupied_core_assignment() {
 		let pov_hash = pov.hash();
 		let candidate = TestCandidateBuilder {
 			para_id,
+			core_index: CoreIndex(0),
 			relay_parent: leaf_a_parent,
 			pov_hash,
 			head_data: expected_head_data.clone(),
@@ -135,6 +135,7 @@ fn make_candidate_receipt(relay_parent: Hash) -> CandidateReceipt {
 		signature: dummy_collator_signature(),
 		para_head: zeros,
 		validation_code_hash: zeros.into(),
+		core_index: CoreIndex(0),
 	};
 	CandidateReceipt { descriptor, commitments_hash: zeros }
 }
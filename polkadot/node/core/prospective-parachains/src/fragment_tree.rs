@@ -1059,7 +1059,9 @@ mod tests {
 	use super::*;
 	use assert_matches::assert_matches;
 	use polkadot_node_subsystem_util::inclusion_emulator::InboundHrmpLimitations;
-	use polkadot_primitives::{BlockNumber, CandidateCommitments, CandidateDescriptor, HeadData};
+	use polkadot_primitives::{
+		BlockNumber, CandidateCommitments, CandidateDescriptor, CoreIndex, HeadData,
+	};
 	use polkadot_primitives_test_helpers as test_helpers;
 	use std::iter;
 
@@ -1112,6 +1114,7 @@ mod tests {
 				signature: test_helpers::dummy_collator_signature(),
 				para_head: para_head.hash(),
 				validation_code_hash: Hash::repeat_byte(42).into(),
+				core_index: CoreIndex(0),
 			},
 			commitments: CandidateCommitments {
 				upward_messages: Default::default(),
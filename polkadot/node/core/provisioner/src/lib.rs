@@ -634,6 +634,9 @@ async fn select_candidate_hashes_from_tracked(
 /// Requests backable candidates from Prospective Parachains subsystem
 /// based on core states.
 ///
+/// A para may be scheduled on multiple cores, in which case a chain of as many candidates is
+/// requested for it.
+///
 /// Should be called when prospective parachains are enabled.
 async fn request_backable_candidates(
 	availability_cores: &[CoreState],
@@ -643,14 +646,18 @@ async fn request_backable_candidates(
 ) -> Result<Vec<(CandidateHash, Hash)>, Error> {
 	let block_number = get_block_number_under_construction(relay_parent, sender).await?;
 
-	let mut selected_candidates = Vec::with_capacity(availability_cores.len());
+	// The number of cores each para is scheduled on.
+	let mut scheduled_cores_per_para: BTreeMap<ParaId, u32> = BTreeMap::new();
+	// The candidates of each para which are going to be made available in this block and which
+	// the new candidates should therefore build on top of.
+	let mut required_paths: HashMap<ParaId, Vec<CandidateHash>> = HashMap::new();
 
 	for (core_idx, core) in availability_cores.iter().enumerate() {
-		let (para_id, required_path) = match core {
+		let para_id = match core {
 			CoreState::Scheduled(scheduled_core) => {
 				// The core is free, pick the first eligible candidate from
 				// the fragment tree.
-				(scheduled_core.para_id, Vec::new())
+				scheduled_core.para_id
 			},
 			CoreState::Occupied(occupied_core) => {
 				if bitfields_indicate_availability(core_idx, bitfields, &occupied_core.availability)
@@ -659,10 +666,17 @@ async fn request_backable_candidates(
 						// The candidate occupying the core is available, choose its
 						// child in the fragment tree.
 						//
+						// Candidates of the same para occupying multiple cores are expected to
+						// be chained in the order of their core indices.
+						//
 						// TODO: doesn't work for on-demand parachains. We lean hard on the
 						// assumption that cores are fixed to specific parachains within a session.
 						// https://github.com/paritytech/polkadot/issues/5492
-						(scheduled_core.para_id, vec![occupied_core.candidate_hash])
+						required_paths
+							.entry(scheduled_core.para_id)
+							.or_default()
+							.push(occupied_core.candidate_hash);
+						scheduled_core.para_id
 					} else {
 						continue
 					}
@@ -672,7 +686,7 @@ async fn request_backable_candidates(
 					}
 					if let Some(ref scheduled_core) = occupied_core.next_up_on_time_out {
 						// Candidate's availability timed out, practically same as scheduled.
-						(scheduled_core.para_id, Vec::new())
+						scheduled_core.para_id
 					} else {
 						continue
 					}
@@ -681,19 +695,28 @@ async fn request_backable_candidates(
 			CoreState::Free => continue,
 		};
 
-		let response = get_backable_candidate(relay_parent, para_id, required_path, sender).await?;
+		*scheduled_cores_per_para.entry(para_id).or_insert(0) += 1;
+	}
 
-		match response {
-			Some((hash, relay_parent)) => selected_candidates.push((hash, relay_parent)),
-			None => {
-				gum::debug!(
-					target: LOG_TARGET,
-					leaf_hash = ?relay_parent,
-					core = core_idx,
-					"No backable candidate returned by prospective parachains",
-				);
-			},
+	let mut selected_candidates = Vec::with_capacity(availability_cores.len());
+
+	for (para_id, core_count) in scheduled_cores_per_para {
+		let required_path = required_paths.remove(&para_id).unwrap_or_default();
+		let response =
+			get_backable_candidates(relay_parent, para_id, required_path, core_count, sender)
+				.await?;
+
+		if response.is_empty() {
+			gum::debug!(
+				target: LOG_TARGET,
+				leaf_hash = ?relay_parent,
+				?para_id,
+				"No backable candidate returned by prospective parachains",
+			);
+			continue
 		}
+
+		selected_candidates.extend(response);
 	}
 
 	Ok(selected_candidates)
@@ -737,7 +760,8 @@ async fn select_candidates(
 	gum::trace!(target: LOG_TARGET, leaf_hash=?relay_parent,
 				"Got {} backed candidates", candidates.len());
 
-	// `selected_candidates` is generated in ascending order by core index, and
+	// `selected_candidates` is generated in ascending order by core index (or by para id and
+	// then in dependency order, when prospective parachains are enabled), and
 	// `GetBackedCandidates` _should_ preserve that property, but let's just make sure.
 	//
 	// We can't easily map from `BackedCandidate` to `core_idx`, but we know that every selected
@@ -796,28 +820,27 @@ async fn get_block_number_under_construction(
 	}
 }
 
-/// Requests backable candidate from Prospective Parachains based on
+/// Requests a chain of up to `count` backable candidates from Prospective Parachains based on
 /// the given path in the fragment tree.
-async fn get_backable_candidate(
+async fn get_backable_candidates(
 	relay_parent: Hash,
 	para_id: ParaId,
 	required_path: Vec<CandidateHash>,
+	count: u32,
 	sender: &mut impl overseer::ProvisionerSenderTrait,
-) -> Result<Option<(CandidateHash, Hash)>, Error> {
+) -> Result<Vec<(CandidateHash, Hash)>, Error> {
 	let (tx, rx) = oneshot::channel();
 	sender
 		.send_message(ProspectiveParachainsMessage::GetBackableCandidates(
 			relay_parent,
 			para_id,
-			1, // core count hardcoded to 1, until elastic scaling is implemented and enabled.
+			count,
 			required_path,
 			tx,
 		))
 		.await;
 
-	rx.await
-		.map_err(Error::CanceledBackableCandidate)
-		.map(|res| res.get(0).copied())
+	rx.await.map_err(Error::CanceledBackableCandidate)
 }
 
 /// The availability bitfield for a given core is the transpose
@@ -705,4 +705,109 @@ mod select_candidates {
 			},
 		)
 	}
+
+	#[test]
+	fn request_chain_of_candidates_for_para_on_multiple_cores() {
+		use std::ops::Not;
+
+		let prospective_parachains_mode =
+			ProspectiveParachainsMode::Enabled { max_candidate_depth: 2, allowed_ancestry_len: 0 };
+		let pending_hash = CandidateHash(Hash::repeat_byte(42));
+
+		// Para 1 is scheduled on cores 0 and 2. Para 2 is scheduled on core 3, as well as on core
+		// 1, which is occupied by an available candidate.
+		let mock_cores = vec![
+			CoreState::Scheduled(scheduled_core(1)),
+			build_occupied_core(2, |core| {
+				core.next_up_on_available = Some(scheduled_core(2));
+				core.availability = core.availability.clone().not();
+				core.candidate_hash = pending_hash;
+			}),
+			CoreState::Scheduled(scheduled_core(1)),
+			CoreState::Scheduled(scheduled_core(2)),
+		];
+
+		let expected_backed: Vec<_> = [1, 1, 2, 2]
+			.iter()
+			.enumerate()
+			.map(|(idx, para_id)| {
+				let mut descriptor = dummy_candidate_descriptor(dummy_hash());
+				descriptor.para_id = (*para_id).into();
+				descriptor.relay_parent = Hash::repeat_byte(idx as u8);
+				BackedCandidate {
+					candidate: CommittedCandidateReceipt {
+						descriptor,
+						commitments: Default::default(),
+					},
+					validity_votes: Vec::new(),
+					validator_indices: default_bitvec(MOCK_GROUP_SIZE),
+				}
+			})
+			.collect();
+
+		let backed = expected_backed.clone();
+		let mock_overseer = move |mut receiver: mpsc::UnboundedReceiver<AllMessages>| {
+			async move {
+				while let Some(from_job) = receiver.next().await {
+					match from_job {
+						AllMessages::ChainApi(ChainApiMessage::BlockNumber(_relay_parent, tx)) =>
+							tx.send(Ok(Some(BLOCK_UNDER_PRODUCTION - 1))).unwrap(),
+						AllMessages::ProspectiveParachains(
+							ProspectiveParachainsMessage::GetBackableCandidates(
+								_,
+								para_id,
+								count,
+								required_path,
+								tx,
+							),
+						) => {
+							// A chain of candidates is requested, one for each core.
+							assert_eq!(count, 2);
+
+							let (expected_path, chain) = if para_id == ParaId::from(1) {
+								(Vec::new(), &backed[..2])
+							} else {
+								(vec![pending_hash], &backed[2..])
+							};
+							assert_eq!(required_path, expected_path);
+
+							let _ = tx.send(
+								chain
+									.iter()
+									.map(|c| (c.hash(), c.descriptor().relay_parent))
+									.collect(),
+							);
+						},
+						AllMessages::CandidateBacking(
+							CandidateBackingMessage::GetBackedCandidates(hashes, sender),
+						) => {
+							let expected_hashes: Vec<_> = backed
+								.iter()
+								.map(|c| (c.hash(), c.descriptor().relay_parent))
+								.collect();
+							assert_eq!(hashes, expected_hashes);
+
+							let _ = sender.send(backed.clone());
+						},
+						_ => panic!("Unexpected message: {:?}", from_job),
+					}
+				}
+			}
+		};
+
+		test_harness(mock_overseer, |mut tx: TestSubsystemSender| async move {
+			let result = select_candidates(
+				&mock_cores,
+				&[],
+				&[],
+				prospective_parachains_mode,
+				Default::default(),
+				&mut tx,
+			)
+			.await
+			.unwrap();
+
+			assert_eq!(result, expected_backed);
+		})
+	}
 }
@@ -239,6 +239,7 @@ where
 							signature: collator_signature,
 							para_head: malicious_commitments.head_data.hash(),
 							validation_code_hash,
+							core_index: candidate.descriptor().core_index,
 						},
 						commitments_hash: malicious_commitments.hash(),
 					};
@@ -23,7 +23,7 @@ use sp_keyring::Sr25519Keyring;
 use polkadot_erasure_coding::{branches, obtain_chunks_v1 as obtain_chunks};
 use polkadot_node_primitives::{AvailableData, BlockData, ErasureChunk, PoV, Proof};
 use polkadot_primitives::{
-	CandidateCommitments, CandidateDescriptor, CandidateHash, CommittedCandidateReceipt,
+	CandidateCommitments, CandidateDescriptor, CandidateHash, CommittedCandidateReceipt, CoreIndex,
 	GroupIndex, Hash, HeadData, Id as ParaId, IndexedVec, OccupiedCore, PersistedValidationData,
 	SessionInfo, ValidatorIndex,
 };
@@ -126,6 +126,7 @@ impl TestCandidateBuilder {
 				signature: dummy_collator_signature(),
 				para_head: dummy_hash(),
 				validation_code_hash: dummy_validation_code().hash(),
+				core_index: CoreIndex(0),
 			},
 			commitments: CandidateCommitments { head_data: self.head_data, ..Default::default() },
 		}
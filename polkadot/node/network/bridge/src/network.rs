@@ -25,8 +25,8 @@ use parking_lot::Mutex;
 use parity_scale_codec::Encode;
 
 use sc_network::{
-	config::parse_addr, multiaddr::Multiaddr, types::ProtocolName, IfDisconnected, MessageSink,
-	NetworkPeers, NetworkRequest, NetworkService, OutboundFailure, ReputationChange,
+	config::parse_addr, multiaddr::Multiaddr, service::traits::NetworkService, types::ProtocolName,
+	IfDisconnected, MessageSink, NetworkPeers, NetworkRequest, OutboundFailure, ReputationChange,
 	RequestFailure,
 };
 
@@ -35,7 +35,7 @@ use polkadot_node_network_protocol::{
 	request_response::{OutgoingRequest, Recipient, ReqProtocolNames, Requests},
 	v1 as protocol_v1, v2 as protocol_v2, v3 as protocol_v3, PeerId,
 };
-use polkadot_primitives::{AuthorityDiscoveryId, Block, Hash};
+use polkadot_primitives::AuthorityDiscoveryId;
 
 use crate::{metrics::Metrics, validator_discovery::AuthorityDiscovery, WireMessage};
 
@@ -232,13 +232,13 @@ pub trait Network: Clone + Send + 'static {
 }
 
 #[async_trait]
-impl Network for Arc<NetworkService<Block, Hash>> {
+impl Network for Arc<dyn NetworkService> {
 	async fn set_reserved_peers(
 		&mut self,
 		protocol: ProtocolName,
 		multiaddresses: HashSet<Multiaddr>,
 	) -> Result<(), String> {
-		NetworkService::set_reserved_peers(&**self, protocol, multiaddresses)
+		NetworkPeers::set_reserved_peers(&**self, protocol, multiaddresses)
 	}
 
 	async fn remove_from_peers_set(
@@ -246,15 +246,15 @@ impl Network for Arc<NetworkService<Block, Hash>> {
 		protocol: ProtocolName,
 		peers: Vec<PeerId>,
 	) -> Result<(), String> {
-		NetworkService::remove_peers_from_reserved_set(&**self, protocol, peers)
+		NetworkPeers::remove_peers_from_reserved_set(&**self, protocol, peers)
 	}
 
 	fn report_peer(&self, who: PeerId, rep: ReputationChange) {
-		NetworkService::report_peer(&**self, who, rep);
+		NetworkPeers::report_peer(&**self, who, rep);
 	}
 
 	fn disconnect_peer(&self, who: PeerId, protocol: ProtocolName) {
-		NetworkService::disconnect_peer(&**self, who, protocol);
+		NetworkPeers::disconnect_peer(&**self, who, protocol);
 	}
 
 	async fn start_request<AD: AuthorityDiscovery>(
@@ -289,7 +289,7 @@ impl Network for Arc<NetworkService<Block, Hash>> {
 						Ok(v) => v,
 						Err(_) => continue,
 					};
-					NetworkService::add_known_address(self, peer_id, addr);
+					NetworkPeers::add_known_address(self, peer_id, addr);
 					found_peer_id = Some(peer_id);
 				}
 				found_peer_id
@@ -321,7 +321,7 @@ impl Network for Arc<NetworkService<Block, Hash>> {
 			"Starting request",
 		);
 
-		NetworkService::start_request(
+		NetworkRequest::start_request(
 			self,
 			peer_id,
 			req_protocol_names.get_name(protocol),
@@ -333,7 +333,7 @@ impl Network for Arc<NetworkService<Block, Hash>> {
 	}
 
 	fn peer_role(&self, who: PeerId, handshake: Vec<u8>) -> Option<sc_network::ObservedRole> {
-		NetworkService::peer_role(self, who, handshake)
+		NetworkPeers::peer_role(self, who, handshake)
 	}
 }
 
@@ -204,9 +204,9 @@ struct PeerData {
 
 struct PerRelayParent {
 	prospective_parachains_mode: ProspectiveParachainsMode,
-	/// Validators group responsible for backing candidates built
-	/// on top of this relay parent.
-	validator_group: ValidatorGroup,
+	/// Validators groups responsible for backing candidates built
+	/// on top of this relay parent, per core the candidates were built for.
+	validator_groups: HashMap<CoreIndex, ValidatorGroup>,
 	/// Distributed collations.
 	collations: HashMap<CandidateHash, Collation>,
 }
@@ -215,7 +215,7 @@ impl PerRelayParent {
 	fn new(mode: ProspectiveParachainsMode) -> Self {
 		Self {
 			prospective_parachains_mode: mode,
-			validator_group: ValidatorGroup::default(),
+			validator_groups: HashMap::new(),
 			collations: HashMap::new(),
 		}
 	}
@@ -332,9 +332,9 @@ impl State {
 
 /// Distribute a collation.
 ///
-/// Figure out the core our para is assigned to and the relevant validators.
+/// Figure out the validators assigned to the core the collation was built for.
 /// Issue a connection request to these validators.
-/// If the para is not scheduled or next up on any core, at the relay-parent,
+/// If the para is not scheduled or next up on that core, at the relay-parent,
 /// or the relay-parent isn't in the active-leaves set, we ignore the message
 /// as it must be invalid in that case - although this indicates a logic error
 /// elsewhere in the node.
@@ -350,6 +350,7 @@ async fn distribute_collation<Context>(
 	result_sender: Option<oneshot::Sender<CollationSecondedSignal>>,
 ) -> Result<()> {
 	let candidate_relay_parent = receipt.descriptor.relay_parent;
+	let core_index = receipt.descriptor.core_index;
 	let candidate_hash = receipt.hash();
 
 	let per_relay_parent = match state.per_relay_parent.get_mut(&candidate_relay_parent) {
@@ -394,16 +395,19 @@ async fn distribute_collation<Context>(
 		return Ok(())
 	}
 
-	// Determine which core the para collated-on is assigned to.
-	// If it is not scheduled then ignore the message.
-	let (our_core, num_cores) =
-		match determine_core(ctx.sender(), id, candidate_relay_parent, relay_parent_mode).await? {
-			Some(core) => core,
+	// Check that the core the collation was built for is assigned to the para collated-on.
+	// If it is not then ignore the message.
+	let num_cores =
+		match check_core(ctx.sender(), id, core_index, candidate_relay_parent, relay_parent_mode)
+			.await?
+		{
+			Some(num_cores) => num_cores,
 			None => {
 				gum::warn!(
 					target: LOG_TARGET,
 					para_id = %id,
-					"looks like no core is assigned to {} at {}", id, candidate_relay_parent,
+					core = ?core_index,
+					"looks like core {:?} is not assigned to {} at {}", core_index, id, candidate_relay_parent,
 				);
 
 				return Ok(())
@@ -415,12 +419,13 @@ async fn distribute_collation<Context>(
 	// When prospective parachains are disabled, candidate relay parent here is
 	// guaranteed to be an active leaf.
 	let GroupValidators { validators, session_index, group_index } =
-		determine_our_validators(ctx, runtime, our_core, num_cores, candidate_relay_parent).await?;
+		determine_our_validators(ctx, runtime, core_index, num_cores, candidate_relay_parent)
+			.await?;
 
 	if validators.is_empty() {
 		gum::warn!(
 			target: LOG_TARGET,
-			core = ?our_core,
+			core = ?core_index,
 			"there are no validators assigned to core",
 		);
 
@@ -446,12 +451,13 @@ async fn distribute_collation<Context>(
 		relay_parent_mode = ?relay_parent_mode,
 		?candidate_hash,
 		pov_hash = ?pov.hash(),
-		core = ?our_core,
+		core = ?core_index,
 		current_validators = ?validators,
 		"Accepted collation, connecting to validators."
 	);
 
-	let validators_at_relay_parent = &mut per_relay_parent.validator_group.validators;
+	let validators_at_relay_parent =
+		&mut per_relay_parent.validator_groups.entry(core_index).or_default().validators;
 	if validators_at_relay_parent.is_empty() {
 		*validators_at_relay_parent = validators;
 	}
@@ -506,36 +512,32 @@ async fn distribute_collation<Context>(
 	Ok(())
 }
 
-/// Get the Id of the Core that is assigned to the para being collated on if any
-/// and the total number of cores.
-async fn determine_core(
+/// Check that the given core is assigned to the para being collated on.
+///
+/// Returns the total number of cores if it is.
+async fn check_core(
 	sender: &mut impl overseer::SubsystemSender<RuntimeApiMessage>,
 	para_id: ParaId,
+	core_index: CoreIndex,
 	relay_parent: Hash,
 	relay_parent_mode: ProspectiveParachainsMode,
-) -> Result<Option<(CoreIndex, usize)>> {
+) -> Result<Option<usize>> {
 	let cores = get_availability_cores(sender, relay_parent).await?;
 
-	for (idx, core) in cores.iter().enumerate() {
-		let core_para_id = match core {
-			CoreState::Scheduled(scheduled) => Some(scheduled.para_id),
-			CoreState::Occupied(occupied) =>
-				if relay_parent_mode.is_enabled() {
-					// With async backing we don't care about the core state,
-					// it is only needed for figuring our validators group.
-					Some(occupied.candidate_descriptor.para_id)
-				} else {
-					None
-				},
-			CoreState::Free => None,
-		};
-
-		if core_para_id == Some(para_id) {
-			return Ok(Some(((idx as u32).into(), cores.len())))
-		}
-	}
+	let core_para_id = match cores.get(core_index.0 as usize) {
+		Some(CoreState::Scheduled(scheduled)) => Some(scheduled.para_id),
+		Some(CoreState::Occupied(occupied)) =>
+			if relay_parent_mode.is_enabled() {
+				// With async backing we don't care about the core state,
+				// it is only needed for figuring our validators group.
+				Some(occupied.candidate_descriptor.para_id)
+			} else {
+				None
+			},
+		Some(CoreState::Free) | None => None,
+	};
 
-	Ok(None)
+	Ok((core_para_id == Some(para_id)).then_some(cores.len()))
 }
 
 /// Validators of a particular group index.
@@ -683,10 +685,15 @@ async fn advertise_collation<Context>(
 			}
 		}
 
-		let should_advertise =
-			per_relay_parent
-				.validator_group
-				.should_advertise_to(candidate_hash, peer_ids, &peer);
+		// Only the backing group of the core the collation was built for is advertised to.
+		let validator_group = match per_relay_parent
+			.validator_groups
+			.get_mut(&collation.receipt.descriptor.core_index)
+		{
+			Some(validator_group) => validator_group,
+			None => continue,
+		};
+		let should_advertise = validator_group.should_advertise_to(candidate_hash, peer_ids, &peer);
 
 		match should_advertise {
 			ShouldAdvertiseTo::Yes => {},
@@ -735,9 +742,7 @@ async fn advertise_collation<Context>(
 		))
 		.await;
 
-		per_relay_parent
-			.validator_group
-			.advertised_to_peer(candidate_hash, &peer_ids, peer);
+		validator_group.advertised_to_peer(candidate_hash, &peer_ids, peer);
 
 		advertisement_timeouts.push(ResetInterestTimeout::new(
 			*candidate_hash,
@@ -914,6 +914,57 @@ fn delay_reputation_change() {
 	);
 }
 
+/// Tests that a collation built for a core which is not assigned to the para is not distributed.
+#[test]
+fn collation_for_core_not_assigned_to_para_is_ignored() {
+	let test_state = TestState::default();
+	let local_peer_id = test_state.local_peer_id;
+	let collator_pair = test_state.collator_pair.clone();
+
+	test_harness(
+		local_peer_id,
+		collator_pair,
+		ReputationAggregator::new(|_| true),
+		|mut test_harness| async move {
+			let virtual_overseer = &mut test_harness.virtual_overseer;
+
+			setup_system(virtual_overseer, &test_state).await;
+
+			let pov = PoV { block_data: BlockData(vec![42, 43, 44]) };
+			let mut candidate = TestCandidateBuilder {
+				para_id: test_state.para_id,
+				relay_parent: test_state.relay_parent,
+				pov_hash: pov.hash(),
+				..Default::default()
+			}
+			.build();
+			// The para is scheduled on the first core only.
+			candidate.descriptor.core_index = CoreIndex(1);
+			overseer_send(
+				virtual_overseer,
+				CollatorProtocolMessage::DistributeCollation(candidate, Hash::zero(), pov, None),
+			)
+			.await;
+
+			assert_matches!(
+				overseer_recv(virtual_overseer).await,
+				AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					relay_parent,
+					RuntimeApiRequest::AvailabilityCores(tx)
+				)) => {
+					assert_eq!(relay_parent, test_state.relay_parent);
+					tx.send(Ok(test_state.availability_cores.clone())).unwrap();
+				}
+			);
+
+			// No validators are connected to.
+			assert!(overseer_recv_with_timeout(virtual_overseer, TIMEOUT).await.is_none());
+
+			test_harness
+		},
+	);
+}
+
 /// Tests that collator side works with v2 network protocol
 /// before async backing is enabled.
 #[test]
@@ -207,7 +207,8 @@ pub enum CollatorProtocolMessage {
 	/// This should be sent before any `DistributeCollation` message.
 	CollateOn(ParaId),
 	/// Provide a collation to distribute to validators with an optional result sender.
-	/// The second argument is the parent head-data hash.
+	/// The second argument is the parent head-data hash. The collation is advertised to the
+	/// backing group assigned to the core in the candidate descriptor.
 	///
 	/// The result sender should be informed when at least one parachain validator seconded the
 	/// collation. It is also completely okay to just drop the sender.
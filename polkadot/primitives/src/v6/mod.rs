@@ -491,6 +491,10 @@ pub struct CandidateDescriptor<H = Hash> {
 	pub para_head: Hash,
 	/// The blake2-256 hash of the validation code bytes.
 	pub validation_code_hash: ValidationCodeHash,
+	/// The index of the core the candidate was built for. Backers only back candidates built for
+	/// the core their group is assigned to, and the candidate occupies this core once backed
+	/// on chain.
+	pub core_index: CoreIndex,
 }
 
 impl<H: AsRef<[u8]>> CandidateDescriptor<H> {
@@ -64,9 +64,13 @@ pub mod node_features {
 		/// Tells if tranch0 assignments could be sent in a single certificate.
 		/// Reserved for: `<https://github.com/paritytech/polkadot-sdk/issues/628>`
 		EnableAssignmentsV2 = 0,
+		/// Allows a para to have candidates backed on multiple cores in the same block, each on
+		/// the core in its `CandidateDescriptor::core_index`. This is needed for the elastic
+		/// scaling MVP.
+		ElasticScalingMVP = 1,
 		/// First unassigned feature bit.
 		/// Every time a new feature flag is assigned it should take this value.
 		/// and this should be incremented.
-		FirstUnassigned = 1,
+		FirstUnassigned = 2,
 	}
 }
@@ -24,7 +24,7 @@
 //! contain randomness based data.
 use polkadot_primitives::{
 	CandidateCommitments, CandidateDescriptor, CandidateReceipt, CollatorId, CollatorSignature,
-	CommittedCandidateReceipt, Hash, HeadData, Id as ParaId, PersistedValidationData,
+	CommittedCandidateReceipt, CoreIndex, Hash, HeadData, Id as ParaId, PersistedValidationData,
 	ValidationCode, ValidationCodeHash, ValidatorId,
 };
 pub use rand;
@@ -104,6 +104,7 @@ pub fn dummy_candidate_descriptor_bad_sig(relay_parent: Hash) -> CandidateDescri
 		signature: dummy_collator_signature(),
 		para_head: zeros,
 		validation_code_hash: dummy_validation_code().hash(),
+		core_index: CoreIndex(0),
 	}
 }
 
@@ -226,6 +227,7 @@ pub fn make_valid_candidate_descriptor<H: AsRef<[u8]>>(
 		signature,
 		para_head,
 		validation_code_hash,
+		core_index: CoreIndex(0),
 	};
 
 	assert!(descriptor.check_collator_signature().is_ok());
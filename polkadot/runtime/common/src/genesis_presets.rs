@@ -0,0 +1,189 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Helpers for the genesis config presets of the test networks.
+//!
+//! Keys can not be derived from seeds inside the runtime, so the public keys of the well-known
+//! development accounts are hardcoded. They are the keys of the `//<Name>` and `//<Name>//stash`
+//! derivations of the development phrase, see `sp_keyring`.
+
+use hex_literal::hex;
+use primitives::{AccountId, AsyncBackingParams, BlockNumber, MAX_CODE_SIZE, MAX_POV_SIZE};
+use runtime_parachains::configuration::HostConfiguration;
+use sp_std::vec::Vec;
+
+/// Public keys of a well-known development account.
+#[derive(Clone, Copy, Debug)]
+pub struct DevAccount {
+	/// `sr25519` key of `//<Name>`, which is also the account id.
+	pub sr25519: [u8; 32],
+	/// `sr25519` key of `//<Name>//stash`.
+	pub stash: [u8; 32],
+	/// `ed25519` key of `//<Name>`.
+	pub ed25519: [u8; 32],
+	/// `ecdsa` key of `//<Name>`.
+	pub ecdsa: [u8; 33],
+}
+
+impl DevAccount {
+	/// The account id of `//<Name>`.
+	pub fn account_id(&self) -> AccountId {
+		self.sr25519.into()
+	}
+
+	/// The account id of `//<Name>//stash`.
+	pub fn stash_account_id(&self) -> AccountId {
+		self.stash.into()
+	}
+}
+
+/// `Alice` development account.
+pub const ALICE: DevAccount = DevAccount {
+	sr25519: hex!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"),
+	stash: hex!("be5ddb1579b72e84524fc29e78609e3caf42e85aa118ebfe0b0ad404b5bdd25f"),
+	ed25519: hex!("88dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee"),
+	ecdsa: hex!("020a1091341fe5664bfa1782d5e04779689068c916b04cb365ec3153755684d9a1"),
+};
+
+/// `Bob` development account.
+pub const BOB: DevAccount = DevAccount {
+	sr25519: hex!("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"),
+	stash: hex!("fe65717dad0447d715f660a0a58411de509b42e6efb8375f562f58a554d5860e"),
+	ed25519: hex!("d17c2d7823ebf260fd138f2d7e27d114c0145d968b5ff5006125f2414fadae69"),
+	ecdsa: hex!("0390084fdbf27d2b79d26a4f13f0ccd982cb755a661969143c37cbc49ef5b91f27"),
+};
+
+/// `Charlie` development account.
+pub const CHARLIE: DevAccount = DevAccount {
+	sr25519: hex!("90b5ab205c6974c9ea841be688864633dc9ca8a357843eeacf2314649965fe22"),
+	stash: hex!("1e07379407fecc4b89eb7dbd287c2c781cfb1907a96947a3eb18e4f8e7198625"),
+	ed25519: hex!("439660b36c6c03afafca027b910b4fecf99801834c62a5e6006f27d978de234f"),
+	ecdsa: hex!("0389411795514af1627765eceffcbd002719f031604fadd7d188e2dc585b4e1afb"),
+};
+
+/// `Dave` development account.
+pub const DAVE: DevAccount = DevAccount {
+	sr25519: hex!("306721211d5404bd9da88e0204360a1a9ab8b87c66c1bc2fcdd37f3c2222cc20"),
+	stash: hex!("e860f1b1c7227f7c22602f53f15af80747814dffd839719731ee3bba6edc126c"),
+	ed25519: hex!("5e639b43e0052c47447dac87d6fd2b6ec50bdd4d0f614e4299c665249bbd09d9"),
+	ecdsa: hex!("03bc9d0ca094bd5b8b3225d7651eac5d18c1c04bf8ae8f8b263eebca4e1410ed0c"),
+};
+
+/// `Eve` development account.
+pub const EVE: DevAccount = DevAccount {
+	sr25519: hex!("e659a7a1628cdd93febc04a4e0646ea20e9f5f0ce097d9a05290d4a9e054df4e"),
+	stash: hex!("8ac59e11963af19174d0b94d5d78041c233f55d2e19324665bafdfb62925af2d"),
+	ed25519: hex!("1dfe3e22cc0d45c70779c1095f7489a8ef3cf52d62fbd8c2fa38c9f1723502b5"),
+	ecdsa: hex!("031d10105e323c4afce225208f71a6441ee327a65b9e646e772500c74d31f669aa"),
+};
+
+/// `Ferdie` development account.
+pub const FERDIE: DevAccount = DevAccount {
+	sr25519: hex!("1cbd2d43530a44705ad088af313e18f80b53ef16b36177cd4b77b846f2a5f07c"),
+	stash: hex!("101191192fc877c24d725b337120fa3edc63d227bbc92705db1e2cb65f56981a"),
+	ed25519: hex!("568cb4a574c6d178feb39c27dfc8b3f789e5f5423e19c71633c748b9acf086b5"),
+	ecdsa: hex!("0291f1217d5a04cb83312ee3d88a6e6b33284e053e6ccfc3a90339a0299d12967c"),
+};
+
+/// All the well-known development accounts.
+pub const DEV_ACCOUNTS: [DevAccount; 6] = [ALICE, BOB, CHARLIE, DAVE, EVE, FERDIE];
+
+/// Returns the account ids of `accounts`, followed by the account ids of their stashes.
+pub fn accounts_with_stashes(accounts: &[DevAccount]) -> Vec<AccountId> {
+	accounts
+		.iter()
+		.map(DevAccount::account_id)
+		.chain(accounts.iter().map(DevAccount::stash_account_id))
+		.collect()
+}
+
+/// The accounts endowed on the test networks: all the development accounts and their stashes.
+pub fn testnet_accounts() -> Vec<AccountId> {
+	accounts_with_stashes(&DEV_ACCOUNTS)
+}
+
+/// The parachains host configuration of the test networks.
+pub fn default_parachains_host_configuration() -> HostConfiguration<BlockNumber> {
+	HostConfiguration {
+		validation_upgrade_cooldown: 2u32,
+		validation_upgrade_delay: 2,
+		code_retention_period: 1200,
+		max_code_size: MAX_CODE_SIZE,
+		max_pov_size: MAX_POV_SIZE,
+		max_head_data_size: 32 * 1024,
+		group_rotation_frequency: 20,
+		paras_availability_period: 4,
+		max_upward_queue_count: 8,
+		max_upward_queue_size: 1024 * 1024,
+		max_downward_message_size: 1024 * 1024,
+		max_upward_message_size: 50 * 1024,
+		max_upward_message_num_per_candidate: 5,
+		hrmp_sender_deposit: 0,
+		hrmp_recipient_deposit: 0,
+		hrmp_channel_max_capacity: 8,
+		hrmp_channel_max_total_size: 8 * 1024,
+		hrmp_max_parachain_inbound_channels: 4,
+		hrmp_channel_max_message_size: 1024 * 1024,
+		hrmp_max_parachain_outbound_channels: 4,
+		hrmp_max_message_num_per_candidate: 5,
+		dispute_period: 6,
+		no_show_slots: 2,
+		n_delay_tranches: 25,
+		needed_approvals: 2,
+		relay_vrf_modulo_samples: 2,
+		zeroth_delay_tranche_width: 0,
+		minimum_validation_upgrade_delay: 5,
+		scheduling_lookahead: 2,
+		async_backing_params: AsyncBackingParams {
+			max_candidate_depth: 3,
+			allowed_ancestry_len: 2,
+		},
+		..Default::default()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::{ecdsa, ed25519, sr25519, Pair};
+
+	#[test]
+	fn dev_accounts_match_seeds() {
+		let names = ["Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie"];
+		for (account, name) in DEV_ACCOUNTS.iter().zip(names) {
+			let seed = format!("//{name}");
+			let stash_seed = format!("//{name}//stash");
+			assert_eq!(
+				account.sr25519,
+				sr25519::Pair::from_string(&seed, None).unwrap().public().0
+			);
+			assert_eq!(
+				account.stash,
+				sr25519::Pair::from_string(&stash_seed, None).unwrap().public().0
+			);
+			assert_eq!(
+				account.ed25519,
+				ed25519::Pair::from_string(&seed, None).unwrap().public().0
+			);
+			assert_eq!(account.ecdsa, ecdsa::Pair::from_string(&seed, None).unwrap().public().0);
+		}
+	}
+
+	#[test]
+	fn default_parachains_host_configuration_is_consistent() {
+		default_parachains_host_configuration().panic_if_not_consistent();
+	}
+}
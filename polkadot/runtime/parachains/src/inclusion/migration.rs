@@ -0,0 +1,313 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module that is responsible for migration of storage.
+
+pub use v1::MigrateToV1;
+
+/// The storage layout of the inclusion pallet before the elastic scaling changes, where at most
+/// one candidate could be pending availability per para and candidate descriptors did not carry
+/// a core index.
+pub mod v0 {
+	use crate::inclusion::{Config, Pallet};
+	use bitvec::{order::Lsb0 as BitOrderLsb0, vec::BitVec};
+	use frame_support::{storage_alias, Twox64Concat};
+	use frame_system::pallet_prelude::BlockNumberFor;
+	use parity_scale_codec::{Decode, Encode};
+	use primitives::{
+		CandidateCommitments, CandidateHash, CollatorId, CollatorSignature, CoreIndex, GroupIndex,
+		Hash, Id as ParaId, ValidationCodeHash,
+	};
+	use scale_info::TypeInfo;
+
+	#[derive(Encode, Decode, PartialEq, TypeInfo)]
+	pub struct CandidateDescriptor<H> {
+		pub para_id: ParaId,
+		pub relay_parent: H,
+		pub collator: CollatorId,
+		pub persisted_validation_data_hash: Hash,
+		pub pov_hash: Hash,
+		pub erasure_root: Hash,
+		pub signature: CollatorSignature,
+		pub para_head: Hash,
+		pub validation_code_hash: ValidationCodeHash,
+	}
+
+	#[derive(Encode, Decode, PartialEq, TypeInfo)]
+	pub struct CandidatePendingAvailability<H, N> {
+		pub core: CoreIndex,
+		pub hash: CandidateHash,
+		pub descriptor: CandidateDescriptor<H>,
+		pub availability_votes: BitVec<u8, BitOrderLsb0>,
+		pub backers: BitVec<u8, BitOrderLsb0>,
+		pub relay_parent_number: N,
+		pub backed_in_number: N,
+		pub backing_group: GroupIndex,
+	}
+
+	#[storage_alias]
+	pub type PendingAvailability<T: Config> = StorageMap<
+		Pallet<T>,
+		Twox64Concat,
+		ParaId,
+		CandidatePendingAvailability<<T as frame_system::Config>::Hash, BlockNumberFor<T>>,
+	>;
+
+	#[storage_alias]
+	pub type PendingAvailabilityCommitments<T: Config> =
+		StorageMap<Pallet<T>, Twox64Concat, ParaId, CandidateCommitments>;
+}
+
+mod v1 {
+	use super::v0::{
+		PendingAvailability as V0PendingAvailability,
+		PendingAvailabilityCommitments as V0PendingAvailabilityCommitments,
+	};
+	use crate::{
+		inclusion::{Config, Pallet, PendingAvailability as V1PendingAvailability},
+		scheduler::{self, FreedReason},
+	};
+	use frame_support::{
+		traits::{Get, OnRuntimeUpgrade},
+		weights::Weight,
+	};
+	use sp_std::vec::Vec;
+
+	#[cfg(feature = "try-runtime")]
+	use frame_support::{
+		ensure,
+		traits::{GetStorageVersion, StorageVersion},
+	};
+	#[cfg(feature = "try-runtime")]
+	use parity_scale_codec::{Decode, Encode};
+	#[cfg(feature = "try-runtime")]
+	use primitives::CoreIndex;
+
+	/// Migration to V1
+	pub struct VersionUncheckedMigrateToV1<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for VersionUncheckedMigrateToV1<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			log::trace!(target: crate::inclusion::LOG_TARGET, "Running pre_upgrade() for inclusion MigrateToV1");
+			let occupied_cores =
+				V0PendingAvailability::<T>::iter_values().map(|c| c.core).collect::<Vec<_>>();
+			let commitments_before_upgrade = V0PendingAvailabilityCommitments::<T>::iter().count();
+
+			if occupied_cores.len() != commitments_before_upgrade {
+				log::warn!(
+					target: crate::inclusion::LOG_TARGET,
+					"Number of pending candidates differ from the number of pending commitments. {} vs {}",
+					occupied_cores.len(),
+					commitments_before_upgrade
+				);
+			}
+
+			Ok(occupied_cores.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight: Weight = Weight::zero();
+
+			// The candidates pending availability were backed with descriptors lacking a core
+			// index. Their hashes can't be recomputed from the new descriptor format, so they are
+			// dropped and their cores are freed as if they had timed out.
+			let freed_cores = V0PendingAvailability::<T>::drain()
+				.map(|(_, candidate)| (candidate.core, FreedReason::TimedOut))
+				.collect::<Vec<_>>();
+			weight = weight.saturating_add(
+				T::DbWeight::get().reads_writes(freed_cores.len() as u64, freed_cores.len() as u64),
+			);
+
+			if !freed_cores.is_empty() {
+				log::info!(
+					target: crate::inclusion::LOG_TARGET,
+					"Dropping {} candidates pending availability",
+					freed_cores.len(),
+				);
+
+				<scheduler::Pallet<T>>::free_cores_and_fill_claimqueue(
+					freed_cores,
+					<frame_system::Pallet<T>>::block_number(),
+				);
+				// Availability cores, validator groups, claim queue and session start block.
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(4, 2));
+			}
+
+			// V0PendingAvailabilityCommitments should not contain too many keys so removing
+			// everything at once should be safe
+			let res = V0PendingAvailabilityCommitments::<T>::clear(u32::MAX, None);
+			weight = weight.saturating_add(
+				T::DbWeight::get().reads_writes(res.loops as u64, res.backend as u64),
+			);
+
+			weight
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			log::trace!(target: crate::inclusion::LOG_TARGET, "Running post_upgrade() for inclusion MigrateToV1");
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= StorageVersion::new(1),
+				"Storage version should be >= 1 after the migration"
+			);
+
+			let freed_cores = Vec::<CoreIndex>::decode(&mut &state[..])
+				.map_err(|_| "Failed to decode the cores occupied before the upgrade")?;
+			let availability_cores = <scheduler::Pallet<T>>::availability_cores();
+
+			ensure!(
+				freed_cores.iter().all(|core| availability_cores
+					.get(core.0 as usize)
+					.map_or(true, |core| core.is_free())),
+				"Cores of the dropped candidates should have been freed"
+			);
+			ensure!(
+				V1PendingAvailability::<T>::iter().next().is_none(),
+				"No candidates should be pending availability after the upgrade"
+			);
+			ensure!(
+				V0PendingAvailability::<T>::iter().next().is_none(),
+				"Pending availability candidates storage v0 should have been removed"
+			);
+			ensure!(
+				V0PendingAvailabilityCommitments::<T>::iter().next().is_none(),
+				"Pending availability commitments storage should have been removed"
+			);
+
+			Ok(())
+		}
+	}
+
+	/// Migrate to v1 inclusion module storage.
+	/// - drops the candidates pending availability, whose descriptors lack a core index, and frees
+	///   the cores they occupy
+	/// - removes the `PendingAvailabilityCommitments` map
+	/// - transforms the `PendingAvailability` storage into a map of vectors, allowing multiple
+	///   candidates of the same para to be pending availability at once
+	pub type MigrateToV1<T> = frame_support::migrations::VersionedMigration<
+		0,
+		1,
+		VersionUncheckedMigrateToV1<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{v1::VersionUncheckedMigrateToV1, *};
+	use crate::{
+		inclusion::{PendingAvailability as V1PendingAvailability, *},
+		mock::{new_test_ext, MockGenesisConfig, Test},
+		scheduler::{common::Assignment, AvailabilityCores, CoreOccupied, ParasEntry},
+	};
+	use frame_support::traits::OnRuntimeUpgrade;
+	use primitives::Id as ParaId;
+	use test_helpers::{
+		dummy_candidate_commitments, dummy_collator, dummy_collator_signature, dummy_hash,
+		dummy_validation_code,
+	};
+
+	fn v0_descriptor(para_id: ParaId) -> v0::CandidateDescriptor<primitives::Hash> {
+		v0::CandidateDescriptor {
+			para_id,
+			relay_parent: dummy_hash(),
+			collator: dummy_collator(),
+			persisted_validation_data_hash: dummy_hash(),
+			pov_hash: dummy_hash(),
+			erasure_root: dummy_hash(),
+			signature: dummy_collator_signature(),
+			para_head: dummy_hash(),
+			validation_code_hash: dummy_validation_code().hash(),
+		}
+	}
+
+	#[test]
+	fn migrate_to_v1() {
+		new_test_ext(MockGenesisConfig::default()).execute_with(|| {
+			// No data to migrate.
+			assert_eq!(
+				<VersionUncheckedMigrateToV1<Test> as OnRuntimeUpgrade>::on_runtime_upgrade(),
+				Weight::zero()
+			);
+			assert!(V1PendingAvailability::<Test>::iter().next().is_none());
+
+			AvailabilityCores::<Test>::set(
+				(0..8)
+					.map(|i| {
+						CoreOccupied::Paras(ParasEntry::new(Assignment::Bulk(ParaId::from(i)), 0))
+					})
+					.collect(),
+			);
+
+			for i in 1..5 {
+				v0::PendingAvailability::<Test>::insert(
+					ParaId::from(i),
+					v0::CandidatePendingAvailability {
+						core: CoreIndex(i),
+						descriptor: v0_descriptor(ParaId::from(i)),
+						relay_parent_number: i,
+						hash: CandidateHash(dummy_hash()),
+						availability_votes: Default::default(),
+						backed_in_number: i,
+						backers: Default::default(),
+						backing_group: GroupIndex(i),
+					},
+				);
+				v0::PendingAvailabilityCommitments::<Test>::insert(
+					ParaId::from(i),
+					dummy_candidate_commitments(HeadData(vec![i as _])),
+				);
+			}
+			// add some wrong data also, candidates without commitments or commitments without
+			// candidates.
+			v0::PendingAvailability::<Test>::insert(
+				ParaId::from(6),
+				v0::CandidatePendingAvailability {
+					core: CoreIndex(6),
+					descriptor: v0_descriptor(ParaId::from(6)),
+					relay_parent_number: 6,
+					hash: CandidateHash(dummy_hash()),
+					availability_votes: Default::default(),
+					backed_in_number: 6,
+					backers: Default::default(),
+					backing_group: GroupIndex(6),
+				},
+			);
+			v0::PendingAvailabilityCommitments::<Test>::insert(
+				ParaId::from(7),
+				dummy_candidate_commitments(HeadData(vec![7 as _])),
+			);
+
+			// For tests, db weight is zero.
+			assert_eq!(
+				<VersionUncheckedMigrateToV1<Test> as OnRuntimeUpgrade>::on_runtime_upgrade(),
+				Weight::zero()
+			);
+
+			// The candidates are dropped and the cores they occupied are freed.
+			assert!(V1PendingAvailability::<Test>::iter().next().is_none());
+			let cores = AvailabilityCores::<Test>::get();
+			for i in 0..8 {
+				assert_eq!(cores[i].is_free(), matches!(i, 1..=4 | 6), "core {}", i);
+			}
+
+			assert!(v0::PendingAvailability::<Test>::iter().next().is_none());
+			assert!(v0::PendingAvailabilityCommitments::<Test>::iter().next().is_none());
+		});
+	}
+}
@@ -30,7 +30,7 @@ use bitvec::{order::Lsb0 as BitOrderLsb0, vec::BitVec};
 use frame_support::{
 	defensive,
 	pallet_prelude::*,
-	traits::{Defensive, EnqueueMessage, Footprint, QueueFootprint},
+	traits::{EnqueueMessage, Footprint, QueueFootprint},
 	BoundedSlice,
 };
 use frame_system::pallet_prelude::*;
@@ -48,7 +48,7 @@ use sp_runtime::{traits::One, DispatchError, SaturatedConversion, Saturating};
 #[cfg(feature = "std")]
 use sp_std::fmt;
 use sp_std::{
-	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet, vec_deque::VecDeque},
 	prelude::*,
 };
 
@@ -57,6 +57,8 @@ pub use pallet::*;
 #[cfg(test)]
 pub(crate) mod tests;
 
+pub mod migration;
+
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
@@ -105,6 +107,8 @@ pub struct CandidatePendingAvailability<H, N> {
 	hash: CandidateHash,
 	/// The candidate descriptor.
 	descriptor: CandidateDescriptor<H>,
+	/// The candidate commitments.
+	commitments: CandidateCommitments,
 	/// The received availability votes. One bit per validator.
 	availability_votes: BitVec<u8, BitOrderLsb0>,
 	/// The backers of the candidate pending availability.
@@ -143,6 +147,11 @@ impl<H, N> CandidatePendingAvailability<H, N> {
 		&self.descriptor
 	}
 
+	/// Get the candidate commitments.
+	pub(crate) fn candidate_commitments(&self) -> &CandidateCommitments {
+		&self.commitments
+	}
+
 	/// Get the candidate's relay parent's number.
 	pub(crate) fn relay_parent_number(&self) -> N
 	where
@@ -156,6 +165,7 @@ impl<H, N> CandidatePendingAvailability<H, N> {
 		core: CoreIndex,
 		hash: CandidateHash,
 		descriptor: CandidateDescriptor<H>,
+		commitments: CandidateCommitments,
 		availability_votes: BitVec<u8, BitOrderLsb0>,
 		backers: BitVec<u8, BitOrderLsb0>,
 		relay_parent_number: N,
@@ -166,6 +176,7 @@ impl<H, N> CandidatePendingAvailability<H, N> {
 			core,
 			hash,
 			descriptor,
+			commitments,
 			availability_votes,
 			backers,
 			relay_parent_number,
@@ -256,7 +267,10 @@ pub type MaxUmpMessageLenOf<T> =
 pub mod pallet {
 	use super::*;
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -363,6 +377,8 @@ pub mod pallet {
 		/// either intentionally or as part of a concluded
 		/// invalid dispute.
 		BitfieldReferencesFreedCore,
+		/// The candidate is backed on a different core than the one in its descriptor.
+		CandidateCoreMismatch,
 	}
 
 	/// The latest bitfield for each validator, referred to by their index in the validator set.
@@ -370,20 +386,20 @@ pub mod pallet {
 	pub(crate) type AvailabilityBitfields<T: Config> =
 		StorageMap<_, Twox64Concat, ValidatorIndex, AvailabilityBitfieldRecord<BlockNumberFor<T>>>;
 
-	/// Candidates pending availability by `ParaId`.
+	/// Candidates pending availability by `ParaId`. They form a chain starting from the latest
+	/// included head of the para.
+	///
+	/// Uses a different prefix than the v0 storage item of the same name, so that the migration
+	/// to v1 can read the old entries while writing the new ones.
 	#[pallet::storage]
+	#[pallet::storage_prefix = "V1"]
 	pub(crate) type PendingAvailability<T: Config> = StorageMap<
 		_,
 		Twox64Concat,
 		ParaId,
-		CandidatePendingAvailability<T::Hash, BlockNumberFor<T>>,
+		VecDeque<CandidatePendingAvailability<T::Hash, BlockNumberFor<T>>>,
 	>;
 
-	/// The commitments of candidates pending availability, by `ParaId`.
-	#[pallet::storage]
-	pub(crate) type PendingAvailabilityCommitments<T: Config> =
-		StorageMap<_, Twox64Concat, ParaId, CandidateCommitments>;
-
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {}
 }
@@ -472,7 +488,6 @@ impl<T: Config> Pallet<T> {
 	) {
 		// unlike most drain methods, drained elements are not cleared on `Drop` of the iterator
 		// and require consumption.
-		for _ in <PendingAvailabilityCommitments<T>>::drain() {}
 		for _ in <PendingAvailability<T>>::drain() {}
 		for _ in <AvailabilityBitfields<T>>::drain() {}
 
@@ -495,23 +510,18 @@ impl<T: Config> Pallet<T> {
 	///
 	/// Updates storage items `PendingAvailability` and `AvailabilityBitfields`.
 	///
+	/// A candidate is only enacted once it and all the candidates it builds upon, which are
+	/// pending availability for the same para, are available.
+	///
 	/// Returns a `Vec` of `CandidateHash`es and their respective `AvailabilityCore`s that became
 	/// available, and cores free.
-	pub(crate) fn update_pending_availability_and_get_freed_cores<F>(
-		expected_bits: usize,
+	pub(crate) fn update_pending_availability_and_get_freed_cores(
 		validators: &[ValidatorId],
 		signed_bitfields: SignedAvailabilityBitfields,
-		core_lookup: F,
-	) -> Vec<(CoreIndex, CandidateHash)>
-	where
-		F: Fn(CoreIndex) -> Option<ParaId>,
-	{
-		let mut assigned_paras_record = (0..expected_bits)
-			.map(|bit_index| core_lookup(CoreIndex::from(bit_index as u32)))
-			.map(|opt_para_id| {
-				opt_para_id.map(|para_id| (para_id, PendingAvailability::<T>::get(&para_id)))
-			})
-			.collect::<Vec<_>>();
+	) -> Vec<(CoreIndex, CandidateHash)> {
+		let threshold = availability_threshold(validators.len());
+
+		let mut votes_per_core: BTreeMap<CoreIndex, BTreeSet<ValidatorIndex>> = BTreeMap::new();
 
 		let now = <frame_system::Pallet<T>>::block_number();
 		for (checked_bitfield, validator_index) in
@@ -521,27 +531,8 @@ impl<T: Config> Pallet<T> {
 				(checked_bitfield, validator_idx)
 			}) {
 			for (bit_idx, _) in checked_bitfield.0.iter().enumerate().filter(|(_, is_av)| **is_av) {
-				let pending_availability = if let Some((_, pending_availability)) =
-					assigned_paras_record[bit_idx].as_mut()
-				{
-					pending_availability
-				} else {
-					// For honest validators, this happens in case of unoccupied cores,
-					// which in turn happens in case of a disputed candidate.
-					// A malicious one might include arbitrary indices, but they are represented
-					// by `None` values and will be sorted out in the next if case.
-					continue
-				};
-
-				// defensive check - this is constructed by loading the availability bitfield
-				// record, which is always `Some` if the core is occupied - that's why we're here.
-				let validator_index = validator_index.0 as usize;
-				if let Some(mut bit) =
-					pending_availability.as_mut().and_then(|candidate_pending_availability| {
-						candidate_pending_availability.availability_votes.get_mut(validator_index)
-					}) {
-					*bit = true;
-				}
+				let core_index = CoreIndex(bit_idx as u32);
+				votes_per_core.entry(core_index).or_default().insert(validator_index);
 			}
 
 			let record =
@@ -550,59 +541,84 @@ impl<T: Config> Pallet<T> {
 			<AvailabilityBitfields<T>>::insert(&validator_index, record);
 		}
 
-		let threshold = availability_threshold(validators.len());
+		let mut freed_cores = Vec::new();
 
-		let mut freed_cores = Vec::with_capacity(expected_bits);
-		for (para_id, pending_availability) in assigned_paras_record
-			.into_iter()
-			.flatten()
-			.filter_map(|(id, p)| p.map(|p| (id, p)))
-		{
-			if pending_availability.availability_votes.count_ones() >= threshold {
-				<PendingAvailability<T>>::remove(&para_id);
-				let commitments = match PendingAvailabilityCommitments::<T>::take(&para_id) {
-					Some(commitments) => commitments,
-					None => {
-						log::warn!(
-							target: LOG_TARGET,
-							"Inclusion::process_bitfields: PendingAvailability and PendingAvailabilityCommitments
-							are out of sync, did someone mess with the storage?",
-						);
-						continue
-					},
+		let pending_paraids: Vec<_> = <PendingAvailability<T>>::iter_keys().collect();
+		for paraid in pending_paraids {
+			<PendingAvailability<T>>::mutate(paraid, |maybe_candidates| {
+				let candidates = match maybe_candidates {
+					Some(candidates) => candidates,
+					None => return,
 				};
 
-				let receipt = CommittedCandidateReceipt {
-					descriptor: pending_availability.descriptor,
-					commitments,
-				};
-				let _weight = Self::enact_candidate(
-					pending_availability.relay_parent_number,
-					receipt,
-					pending_availability.backers,
-					pending_availability.availability_votes,
-					pending_availability.core,
-					pending_availability.backing_group,
-				);
+				// The number of candidates at the front of the queue that can be enacted. A
+				// candidate can only be enacted if all of its predecessors are enacted as well.
+				let mut enactable = 0;
+				let mut predecessors_available = true;
+
+				for candidate in candidates.iter_mut() {
+					if let Some(validator_indices) = votes_per_core.remove(&candidate.core) {
+						for validator_index in validator_indices.iter() {
+							// defensive check - this is constructed by loading the availability
+							// bitfield record, which is always `Some` if the core is occupied -
+							// that's why we're here.
+							if let Some(mut bit) =
+								candidate.availability_votes.get_mut(validator_index.0 as usize)
+							{
+								*bit = true;
+							}
+						}
+					}
 
-				freed_cores.push((pending_availability.core, pending_availability.hash));
-			} else {
-				<PendingAvailability<T>>::insert(&para_id, &pending_availability);
-			}
+					// We check the availability of the candidate even if we didn't get any new
+					// votes for its core, as it may have become available in a previous block
+					// without being enacted, because one of its predecessors wasn't available.
+					if predecessors_available &&
+						candidate.availability_votes.count_ones() >= threshold
+					{
+						enactable += 1;
+					} else {
+						predecessors_available = false;
+					}
+				}
+
+				for candidate in candidates.drain(..enactable) {
+					let receipt = CommittedCandidateReceipt {
+						descriptor: candidate.descriptor,
+						commitments: candidate.commitments,
+					};
+					let _weight = Self::enact_candidate(
+						candidate.relay_parent_number,
+						receipt,
+						candidate.backers,
+						candidate.availability_votes,
+						candidate.core,
+						candidate.backing_group,
+					);
+
+					freed_cores.push((candidate.core, candidate.hash));
+				}
+
+				if candidates.is_empty() {
+					*maybe_candidates = None;
+				}
+			});
 		}
 
 		freed_cores
 	}
 
-	/// Process candidates that have been backed. Provide the relay storage root, a set of
-	/// candidates and scheduled cores.
+	/// Process candidates that have been backed. Provide a set of candidates along with their
+	/// scheduled cores, grouped by para.
 	///
-	/// Both should be sorted ascending by core index, and the candidates should be a subset of
-	/// scheduled cores. If these conditions are not met, the execution of the function fails.
+	/// The candidates of each para are expected to form a chain, which builds on top of the
+	/// latest candidate pending availability of the para or, if there is none, on top of the
+	/// currently included head of the para. Candidates are expected to be sanitized already, i.e.
+	/// via `sanitize_backed_candidates`. If these conditions are not met, the execution of the
+	/// function fails.
 	pub(crate) fn process_candidates<GV>(
 		allowed_relay_parents: &AllowedRelayParentsTracker<T::Hash, BlockNumberFor<T>>,
-		candidates: Vec<BackedCandidate<T::Hash>>,
-		scheduled: &BTreeMap<ParaId, CoreIndex>,
+		candidates: &BTreeMap<ParaId, Vec<(BackedCandidate<T::Hash>, CoreIndex)>>,
 		group_validators: GV,
 	) -> Result<ProcessedCandidates<T::Hash>, DispatchError>
 	where
@@ -610,9 +626,7 @@ impl<T: Config> Pallet<T> {
 	{
 		let now = <frame_system::Pallet<T>>::block_number();
 
-		ensure!(candidates.len() <= scheduled.len(), Error::<T>::UnscheduledCandidate);
-
-		if scheduled.is_empty() {
+		if candidates.is_empty() {
 			return Ok(ProcessedCandidates::default())
 		}
 
@@ -622,79 +636,51 @@ impl<T: Config> Pallet<T> {
 		// Collect candidate receipts with backers.
 		let mut candidate_receipt_with_backing_validator_indices =
 			Vec::with_capacity(candidates.len());
+		let mut core_indices = Vec::with_capacity(candidates.len());
 
 		// Do all checks before writing storage.
-		let core_indices_and_backers = {
-			let mut core_indices_and_backers = Vec::with_capacity(candidates.len());
-			let mut last_core = None;
-
-			let mut check_assignment_in_order = |core_idx| -> DispatchResult {
-				ensure!(
-					last_core.map_or(true, |core| core_idx > core),
-					Error::<T>::ScheduledOutOfOrder,
-				);
-
-				last_core = Some(core_idx);
-				Ok(())
-			};
+		let mut new_pending_availability = Vec::with_capacity(candidates.len());
+		for (para_id, para_candidates) in candidates {
+			// The candidates of a para chain on top of each other, starting with the latest head
+			// data and relay parent context of the para.
+			let (mut latest_head_data, mut latest_context) =
+				match Self::para_latest_head_data_and_context(para_id) {
+					Some(latest) => latest,
+					None => {
+						defensive!("Latest included head data for para is missing", para_id);
+						continue
+					},
+				};
 
-			// We combine an outer loop over candidates with an inner loop over the scheduled,
-			// where each iteration of the outer loop picks up at the position
-			// in scheduled just after the past iteration left off.
-			//
-			// If the candidates appear in the same order as they appear in `scheduled`,
-			// then they should always be found. If the end of `scheduled` is reached,
-			// then the candidate was either not scheduled or out-of-order.
-			//
-			// In the meantime, we do certain sanity checks on the candidates and on the scheduled
-			// list.
-			for (candidate_idx, backed_candidate) in candidates.iter().enumerate() {
+			for (candidate_idx, (backed_candidate, core_idx)) in para_candidates.iter().enumerate()
+			{
 				let relay_parent_hash = backed_candidate.descriptor().relay_parent;
-				let para_id = backed_candidate.descriptor().para_id;
 
-				let prev_context = <paras::Pallet<T>>::para_most_recent_context(para_id);
+				ensure!(
+					backed_candidate.descriptor().core_index == *core_idx,
+					Error::<T>::CandidateCoreMismatch,
+				);
 
-				let check_ctx = CandidateCheckContext::<T>::new(prev_context);
+				let check_ctx = CandidateCheckContext::<T>::new(latest_context);
 				let signing_context = SigningContext {
 					parent_hash: relay_parent_hash,
 					session_index: shared::Pallet::<T>::session_index(),
 				};
 
-				let relay_parent_number = match check_ctx.verify_backed_candidate(
+				let relay_parent_number = check_ctx.verify_backed_candidate(
 					&allowed_relay_parents,
 					candidate_idx,
 					backed_candidate,
-				)? {
-					Err(FailedToCreatePVD) => {
-						log::debug!(
-							target: LOG_TARGET,
-							"Failed to create PVD for candidate {}",
-							candidate_idx,
-						);
-						// We don't want to error out here because it will
-						// brick the relay-chain. So we return early without
-						// doing anything.
-						return Ok(ProcessedCandidates::default())
-					},
-					Ok(rpn) => rpn,
-				};
+					latest_head_data.clone(),
+				)?;
 
-				let para_id = backed_candidate.descriptor().para_id;
 				let mut backers = bitvec::bitvec![u8, BitOrderLsb0; 0; validators.len()];
 
-				let core_idx = *scheduled.get(&para_id).ok_or(Error::<T>::UnscheduledCandidate)?;
-				check_assignment_in_order(core_idx)?;
-				ensure!(
-					<PendingAvailability<T>>::get(&para_id).is_none() &&
-						<PendingAvailabilityCommitments<T>>::get(&para_id).is_none(),
-					Error::<T>::CandidateScheduledBeforeParaFree,
-				);
-
 				// The candidate based upon relay parent `N` should be backed by a group
 				// assigned to core at block `N + 1`. Thus, `relay_parent_number + 1`
 				// will always land in the current session.
 				let group_idx = <scheduler::Pallet<T>>::group_assigned_to_core(
-					core_idx,
+					*core_idx,
 					relay_parent_number + One::one(),
 				)
 				.ok_or_else(|| {
@@ -759,22 +745,22 @@ impl<T: Config> Pallet<T> {
 						.push((candidate_receipt, backer_idx_and_attestation));
 				}
 
-				core_indices_and_backers.push((
-					(core_idx, para_id),
+				core_indices.push((*core_idx, *para_id));
+				new_pending_availability.push((
+					backed_candidate,
+					*core_idx,
 					backers,
 					group_idx,
 					relay_parent_number,
 				));
-			}
 
-			core_indices_and_backers
-		};
+				latest_head_data = backed_candidate.candidate.commitments.head_data.clone();
+				latest_context = Some(relay_parent_number);
+			}
+		}
 
 		// one more sweep for actually writing to storage.
-		let core_indices = core_indices_and_backers.iter().map(|(c, ..)| *c).collect();
-		for (candidate, (core, backers, group, relay_parent_number)) in
-			candidates.into_iter().zip(core_indices_and_backers)
-		{
+		for (candidate, core, backers, group, relay_parent_number) in new_pending_availability {
 			let para_id = candidate.descriptor().para_id;
 
 			// initialize all availability votes to 0.
@@ -784,29 +770,27 @@ impl<T: Config> Pallet<T> {
 			Self::deposit_event(Event::<T>::CandidateBacked(
 				candidate.candidate.to_plain(),
 				candidate.candidate.commitments.head_data.clone(),
-				core.0,
+				core,
 				group,
 			));
 
 			let candidate_hash = candidate.candidate.hash();
 
-			let (descriptor, commitments) =
-				(candidate.candidate.descriptor, candidate.candidate.commitments);
-
-			<PendingAvailability<T>>::insert(
-				&para_id,
-				CandidatePendingAvailability {
-					core: core.0,
-					hash: candidate_hash,
-					descriptor,
-					availability_votes,
-					relay_parent_number,
-					backers: backers.to_bitvec(),
-					backed_in_number: now,
-					backing_group: group,
-				},
-			);
-			<PendingAvailabilityCommitments<T>>::insert(&para_id, commitments);
+			<PendingAvailability<T>>::mutate(&para_id, |pending_availability| {
+				pending_availability.get_or_insert_with(VecDeque::new).push_back(
+					CandidatePendingAvailability {
+						core,
+						hash: candidate_hash,
+						descriptor: candidate.candidate.descriptor.clone(),
+						commitments: candidate.candidate.commitments.clone(),
+						availability_votes,
+						relay_parent_number,
+						backers: backers.to_bitvec(),
+						backed_in_number: now,
+						backing_group: group,
+					},
+				);
+			});
 		}
 
 		Ok(ProcessedCandidates::<T::Hash> {
@@ -815,6 +799,24 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// Returns the head data and the relay parent number of the latest candidate of the para,
+	/// which is either the last candidate pending availability or the currently included head.
+	///
+	/// The relay parent number is `None` if the para has no candidate pending availability and
+	/// no candidate was included yet.
+	pub(crate) fn para_latest_head_data_and_context(
+		para_id: &ParaId,
+	) -> Option<(HeadData, Option<BlockNumberFor<T>>)> {
+		match <PendingAvailability<T>>::get(para_id).and_then(|pending| {
+			pending.back().map(|c| (c.commitments.head_data.clone(), c.relay_parent_number))
+		}) {
+			Some((head_data, relay_parent_number)) => Some((head_data, Some(relay_parent_number))),
+			None => <paras::Pallet<T>>::para_head(para_id).map(|head_data| {
+				(head_data, <paras::Pallet<T>>::para_most_recent_context(para_id))
+			}),
+		}
+	}
+
 	/// Run the acceptance criteria checks on the given candidate commitments.
 	pub(crate) fn check_validation_outputs_for_runtime_api(
 		para_id: ParaId,
@@ -1019,110 +1021,161 @@ impl<T: Config> Pallet<T> {
 		weight
 	}
 
-	/// Cleans up all paras pending availability that the predicate returns true for.
+	/// Cleans up all timed out candidates that the predicate returns true for, as well as all
+	/// candidates pending availability of the same para which build on top of them.
 	///
-	/// The predicate accepts the index of the core and the block number the core has been occupied
-	/// since (i.e. the block number the candidate was backed at in this fork of the relay chain).
+	/// The predicate accepts the block number the core has been occupied since (i.e. the block
+	/// number the candidate was backed at in this fork of the relay chain).
 	///
 	/// Returns a vector of cleaned-up core IDs.
 	pub(crate) fn collect_pending(
 		pred: impl Fn(BlockNumberFor<T>) -> AvailabilityTimeoutStatus<BlockNumberFor<T>>,
 	) -> Vec<CoreIndex> {
-		let mut cleaned_up_ids = Vec::new();
 		let mut cleaned_up_cores = Vec::new();
+		let mut timed_out_candidates = Vec::new();
 
-		for (para_id, pending_record) in <PendingAvailability<T>>::iter() {
-			if pred(pending_record.backed_in_number).timed_out {
-				cleaned_up_ids.push(para_id);
-				cleaned_up_cores.push(pending_record.core);
+		for (para_id, candidates) in <PendingAvailability<T>>::iter() {
+			// The first timed out candidate and all of its descendants are removed.
+			if let Some(idx) = candidates.iter().position(|c| pred(c.backed_in_number).timed_out) {
+				timed_out_candidates.push((para_id, idx));
 			}
 		}
 
-		for para_id in cleaned_up_ids {
-			let pending = <PendingAvailability<T>>::take(&para_id);
-			let commitments = <PendingAvailabilityCommitments<T>>::take(&para_id);
-
-			if let (Some(pending), Some(commitments)) = (pending, commitments) {
-				// defensive: this should always be true.
-				let candidate = CandidateReceipt {
-					descriptor: pending.descriptor,
-					commitments_hash: commitments.hash(),
+		for (para_id, idx) in timed_out_candidates {
+			<PendingAvailability<T>>::mutate(&para_id, |maybe_candidates| {
+				let candidates = match maybe_candidates {
+					Some(candidates) => candidates,
+					None => return,
 				};
 
-				Self::deposit_event(Event::<T>::CandidateTimedOut(
-					candidate,
-					commitments.head_data,
-					pending.core,
-				));
-			}
+				for candidate in candidates.drain(idx..) {
+					cleaned_up_cores.push(candidate.core);
+
+					let receipt = CandidateReceipt {
+						descriptor: candidate.descriptor,
+						commitments_hash: candidate.commitments.hash(),
+					};
+
+					Self::deposit_event(Event::<T>::CandidateTimedOut(
+						receipt,
+						candidate.commitments.head_data,
+						candidate.core,
+					));
+				}
+
+				if candidates.is_empty() {
+					*maybe_candidates = None;
+				}
+			});
 		}
 
 		cleaned_up_cores
 	}
 
-	/// Cleans up all paras pending availability that are in the given list of disputed candidates.
+	/// Cleans up all candidates pending availability that are in the given list of disputed
+	/// candidates, as well as all candidates of the same para which build on top of them.
 	///
 	/// Returns a vector of cleaned-up core IDs.
 	pub(crate) fn collect_disputed(disputed: &BTreeSet<CandidateHash>) -> Vec<CoreIndex> {
-		let mut cleaned_up_ids = Vec::new();
 		let mut cleaned_up_cores = Vec::new();
+		let mut disputed_candidates = Vec::new();
 
-		for (para_id, pending_record) in <PendingAvailability<T>>::iter() {
-			if disputed.contains(&pending_record.hash) {
-				cleaned_up_ids.push(para_id);
-				cleaned_up_cores.push(pending_record.core);
+		for (para_id, candidates) in <PendingAvailability<T>>::iter() {
+			// The first disputed candidate and all of its descendants are removed.
+			if let Some(idx) = candidates.iter().position(|c| disputed.contains(&c.hash)) {
+				disputed_candidates.push((para_id, idx));
 			}
 		}
 
-		for para_id in cleaned_up_ids {
-			let _ = <PendingAvailability<T>>::take(&para_id);
-			let _ = <PendingAvailabilityCommitments<T>>::take(&para_id);
+		for (para_id, idx) in disputed_candidates {
+			<PendingAvailability<T>>::mutate(&para_id, |maybe_candidates| {
+				let candidates = match maybe_candidates {
+					Some(candidates) => candidates,
+					None => return,
+				};
+
+				cleaned_up_cores.extend(candidates.drain(idx..).map(|candidate| candidate.core));
+
+				if candidates.is_empty() {
+					*maybe_candidates = None;
+				}
+			});
 		}
 
 		cleaned_up_cores
 	}
 
-	/// Forcibly enact the candidate with the given ID as though it had been deemed available
-	/// by bitfields.
+	/// Forcibly enact all candidates pending availability for the given para, as though they had
+	/// been deemed available by bitfields.
 	///
 	/// Is a no-op if there is no candidate pending availability for this para-id.
 	/// This should generally not be used but it is useful during execution of Runtime APIs,
 	/// where the changes to the state are expected to be discarded directly after.
 	pub(crate) fn force_enact(para: ParaId) {
-		let pending = <PendingAvailability<T>>::take(&para);
-		let commitments = <PendingAvailabilityCommitments<T>>::take(&para);
+		let candidates = <PendingAvailability<T>>::take(&para).unwrap_or_default();
 
-		if let (Some(pending), Some(commitments)) = (pending, commitments) {
-			let candidate =
-				CommittedCandidateReceipt { descriptor: pending.descriptor, commitments };
+		for candidate in candidates {
+			let receipt = CommittedCandidateReceipt {
+				descriptor: candidate.descriptor,
+				commitments: candidate.commitments,
+			};
 
 			Self::enact_candidate(
-				pending.relay_parent_number,
-				candidate,
-				pending.backers,
-				pending.availability_votes,
-				pending.core,
-				pending.backing_group,
+				candidate.relay_parent_number,
+				receipt,
+				candidate.backers,
+				candidate.availability_votes,
+				candidate.core,
+				candidate.backing_group,
 			);
 		}
 	}
 
-	/// Returns the `CommittedCandidateReceipt` pending availability for the para provided, if any.
+	/// Returns the first `CommittedCandidateReceipt` pending availability for the para provided,
+	/// if any.
 	pub(crate) fn candidate_pending_availability(
 		para: ParaId,
 	) -> Option<CommittedCandidateReceipt<T::Hash>> {
+		<PendingAvailability<T>>::get(&para).and_then(|p| {
+			p.get(0).map(|p| CommittedCandidateReceipt {
+				descriptor: p.descriptor.clone(),
+				commitments: p.commitments.clone(),
+			})
+		})
+	}
+
+	/// Returns all the `CommittedCandidateReceipt`s pending availability for the para provided,
+	/// in the order in which they build on top of each other.
+	#[cfg(test)]
+	pub(crate) fn candidates_pending_availability(
+		para: ParaId,
+	) -> Vec<CommittedCandidateReceipt<T::Hash>> {
 		<PendingAvailability<T>>::get(&para)
-			.map(|p| p.descriptor)
-			.and_then(|d| <PendingAvailabilityCommitments<T>>::get(&para).map(move |c| (d, c)))
-			.map(|(d, c)| CommittedCandidateReceipt { descriptor: d, commitments: c })
+			.map(|candidates| {
+				candidates
+					.into_iter()
+					.map(|candidate| CommittedCandidateReceipt {
+						descriptor: candidate.descriptor,
+						commitments: candidate.commitments,
+					})
+					.collect()
+			})
+			.unwrap_or_default()
 	}
 
-	/// Returns the metadata around the candidate pending availability for the
-	/// para provided, if any.
-	pub(crate) fn pending_availability(
+	/// Returns the metadata around the candidate pending availability occupying the supplied
+	/// core, if any.
+	pub(crate) fn pending_availability_with_core(
 		para: ParaId,
+		core: CoreIndex,
 	) -> Option<CandidatePendingAvailability<T::Hash, BlockNumberFor<T>>> {
 		<PendingAvailability<T>>::get(&para)
+			.and_then(|p| p.into_iter().find(|c| c.core_occupied() == core))
+	}
+
+	/// Returns whether the para provided has any candidate pending availability.
+	pub(crate) fn has_pending_availability(para: ParaId) -> bool {
+		<PendingAvailability<T>>::contains_key(&para)
 	}
 }
 
@@ -1173,10 +1226,6 @@ pub(crate) struct CandidateCheckContext<T: Config> {
 	prev_context: Option<BlockNumberFor<T>>,
 }
 
-/// An error indicating that creating Persisted Validation Data failed
-/// while checking a candidate's validity.
-pub(crate) struct FailedToCreatePVD;
-
 impl<T: Config> CandidateCheckContext<T> {
 	pub(crate) fn new(prev_context: Option<BlockNumberFor<T>>) -> Self {
 		Self { config: <configuration::Pallet<T>>::config(), prev_context }
@@ -1186,6 +1235,7 @@ impl<T: Config> CandidateCheckContext<T> {
 	///
 	/// Assures:
 	///  * relay-parent in-bounds
+	///  * the persisted validation data builds on top of the given parent head data
 	///  * collator signature check passes
 	///  * code hash of commitments matches current code hash
 	///  * para head in the descriptor and commitments match
@@ -1196,7 +1246,8 @@ impl<T: Config> CandidateCheckContext<T> {
 		allowed_relay_parents: &AllowedRelayParentsTracker<T::Hash, BlockNumberFor<T>>,
 		candidate_idx: usize,
 		backed_candidate: &BackedCandidate<<T as frame_system::Config>::Hash>,
-	) -> Result<Result<BlockNumberFor<T>, FailedToCreatePVD>, Error<T>> {
+		parent_head_data: HeadData,
+	) -> Result<BlockNumberFor<T>, Error<T>> {
 		let para_id = backed_candidate.descriptor().para_id;
 		let relay_parent = backed_candidate.descriptor().relay_parent;
 
@@ -1209,16 +1260,12 @@ impl<T: Config> CandidateCheckContext<T> {
 		};
 
 		{
-			let persisted_validation_data = match crate::util::make_persisted_validation_data::<T>(
-				para_id,
-				relay_parent_number,
-				relay_parent_storage_root,
-			)
-			.defensive_proof("the para is registered")
-			{
-				Some(l) => l,
-				None => return Ok(Err(FailedToCreatePVD)),
-			};
+			let persisted_validation_data =
+				crate::util::make_persisted_validation_data_with_parent::<T>(
+					relay_parent_number,
+					relay_parent_storage_root,
+					parent_head_data,
+				);
 
 			let expected = persisted_validation_data.hash();
 
@@ -1265,7 +1312,7 @@ impl<T: Config> CandidateCheckContext<T> {
 			);
 			Err(err.strip_into_dispatch_err::<T>())?;
 		};
-		Ok(Ok(relay_parent_number))
+		Ok(relay_parent_number)
 	}
 
 	/// Check the given outputs after candidate validation on whether it passes the acceptance
@@ -273,6 +273,7 @@ pub(crate) struct TestCandidateBuilder {
 	pub(crate) new_validation_code: Option<ValidationCode>,
 	pub(crate) validation_code: ValidationCode,
 	pub(crate) hrmp_watermark: BlockNumber,
+	pub(crate) core_index: CoreIndex,
 }
 
 impl std::default::Default for TestCandidateBuilder {
@@ -288,6 +289,7 @@ impl std::default::Default for TestCandidateBuilder {
 			new_validation_code: None,
 			validation_code: dummy_validation_code(),
 			hrmp_watermark: 0u32.into(),
+			core_index: CoreIndex(0),
 		}
 	}
 }
@@ -305,6 +307,7 @@ impl TestCandidateBuilder {
 				erasure_root: Default::default(),
 				signature: dummy_collator_signature(),
 				collator: dummy_collator(),
+				core_index: self.core_index,
 			},
 			commitments: CandidateCommitments {
 				head_data: self.head_data,
@@ -354,17 +357,13 @@ fn simple_sanitize_bitfields(
 }
 /// Process a set of already sanitized bitfields.
 pub(crate) fn process_bitfields(
-	expected_bits: usize,
 	signed_bitfields: SignedAvailabilityBitfields,
-	core_lookup: impl Fn(CoreIndex) -> Option<ParaId>,
 ) -> Vec<(CoreIndex, CandidateHash)> {
 	let validators = shared::Pallet::<Test>::active_validator_keys();
 
-	ParaInclusion::update_pending_availability_and_get_freed_cores::<_>(
-		expected_bits,
+	ParaInclusion::update_pending_availability_and_get_freed_cores(
 		&validators[..],
 		signed_bitfields,
-		core_lookup,
 	)
 }
 
@@ -385,50 +384,151 @@ fn collect_pending_cleans_up_pending() {
 		let default_candidate = TestCandidateBuilder::default().build();
 		<PendingAvailability<Test>>::insert(
 			chain_a,
-			CandidatePendingAvailability {
+			[CandidatePendingAvailability {
 				core: CoreIndex::from(0),
 				hash: default_candidate.hash(),
 				descriptor: default_candidate.descriptor.clone(),
+				commitments: default_candidate.commitments.clone(),
 				availability_votes: default_availability_votes(),
 				relay_parent_number: 0,
 				backed_in_number: 0,
 				backers: default_backing_bitfield(),
 				backing_group: GroupIndex::from(0),
-			},
-		);
-		PendingAvailabilityCommitments::<Test>::insert(
-			chain_a,
-			default_candidate.commitments.clone(),
+			}]
+			.into_iter()
+			.collect::<VecDeque<_>>(),
 		);
 
 		<PendingAvailability<Test>>::insert(
 			&chain_b,
-			CandidatePendingAvailability {
+			[CandidatePendingAvailability {
 				core: CoreIndex::from(1),
 				hash: default_candidate.hash(),
 				descriptor: default_candidate.descriptor,
+				commitments: default_candidate.commitments,
 				availability_votes: default_availability_votes(),
 				relay_parent_number: 0,
 				backed_in_number: 5,
 				backers: default_backing_bitfield(),
 				backing_group: GroupIndex::from(1),
-			},
+			}]
+			.into_iter()
+			.collect::<VecDeque<_>>(),
 		);
-		PendingAvailabilityCommitments::<Test>::insert(chain_b, default_candidate.commitments);
 
 		run_to_block(5, |_| None);
 
 		assert!(<PendingAvailability<Test>>::get(&chain_a).is_some());
 		assert!(<PendingAvailability<Test>>::get(&chain_b).is_some());
-		assert!(<PendingAvailabilityCommitments<Test>>::get(&chain_a).is_some());
-		assert!(<PendingAvailabilityCommitments<Test>>::get(&chain_b).is_some());
 
 		ParaInclusion::collect_pending(Scheduler::availability_timeout_predicate());
 
 		assert!(<PendingAvailability<Test>>::get(&chain_a).is_none());
 		assert!(<PendingAvailability<Test>>::get(&chain_b).is_some());
-		assert!(<PendingAvailabilityCommitments<Test>>::get(&chain_a).is_none());
-		assert!(<PendingAvailabilityCommitments<Test>>::get(&chain_b).is_some());
+	});
+}
+
+#[test]
+fn collect_pending_cleans_up_descendants_of_timed_out_candidates() {
+	let chain_a = ParaId::from(1_u32);
+
+	let paras = vec![(chain_a, ParaKind::Parachain)];
+	let mut config = genesis_config(paras);
+	config.configuration.config.group_rotation_frequency = 3;
+	new_test_ext(config).execute_with(|| {
+		let candidate = |head: u8| {
+			TestCandidateBuilder {
+				para_id: chain_a,
+				head_data: vec![head].into(),
+				..Default::default()
+			}
+			.build()
+		};
+		let pending = |candidate: CommittedCandidateReceipt, core: u32, backed_in_number| {
+			CandidatePendingAvailability {
+				core: CoreIndex::from(core),
+				hash: candidate.hash(),
+				descriptor: candidate.descriptor,
+				commitments: candidate.commitments,
+				availability_votes: default_availability_votes(),
+				relay_parent_number: 0,
+				backed_in_number,
+				backers: default_backing_bitfield(),
+				backing_group: GroupIndex::from(core),
+			}
+		};
+
+		// The second candidate times out, but the first one doesn't. The third one is a
+		// descendant of the second one and is dropped along with it.
+		<PendingAvailability<Test>>::insert(
+			chain_a,
+			[pending(candidate(1), 0, 5), pending(candidate(2), 1, 0), pending(candidate(3), 2, 5)]
+				.into_iter()
+				.collect::<VecDeque<_>>(),
+		);
+
+		run_to_block(5, |_| None);
+
+		let cleaned_up =
+			ParaInclusion::collect_pending(Scheduler::availability_timeout_predicate());
+		assert_eq!(cleaned_up, vec![CoreIndex::from(1), CoreIndex::from(2)]);
+
+		let remaining = <PendingAvailability<Test>>::get(&chain_a).unwrap();
+		assert_eq!(remaining.len(), 1);
+		assert_eq!(remaining[0].hash, candidate(1).hash());
+	});
+}
+
+#[test]
+fn collect_disputed_cleans_up_descendants_of_disputed_candidates() {
+	let chain_a = ParaId::from(1_u32);
+	let chain_b = ParaId::from(2_u32);
+
+	let paras = vec![(chain_a, ParaKind::Parachain), (chain_b, ParaKind::Parachain)];
+	new_test_ext(genesis_config(paras)).execute_with(|| {
+		let candidate = |para_id: ParaId, head: u8| {
+			TestCandidateBuilder { para_id, head_data: vec![head].into(), ..Default::default() }
+				.build()
+		};
+		let pending =
+			|candidate: CommittedCandidateReceipt, core: u32| CandidatePendingAvailability {
+				core: CoreIndex::from(core),
+				hash: candidate.hash(),
+				descriptor: candidate.descriptor,
+				commitments: candidate.commitments,
+				availability_votes: default_availability_votes(),
+				relay_parent_number: 0,
+				backed_in_number: 0,
+				backers: default_backing_bitfield(),
+				backing_group: GroupIndex::from(core),
+			};
+
+		<PendingAvailability<Test>>::insert(
+			chain_a,
+			[
+				pending(candidate(chain_a, 1), 0),
+				pending(candidate(chain_a, 2), 1),
+				pending(candidate(chain_a, 3), 2),
+			]
+			.into_iter()
+			.collect::<VecDeque<_>>(),
+		);
+		<PendingAvailability<Test>>::insert(
+			chain_b,
+			[pending(candidate(chain_b, 4), 3)].into_iter().collect::<VecDeque<_>>(),
+		);
+
+		let disputed = [candidate(chain_a, 2).hash(), candidate(chain_b, 4).hash()]
+			.into_iter()
+			.collect::<BTreeSet<_>>();
+		let mut cleaned_up = ParaInclusion::collect_disputed(&disputed);
+		cleaned_up.sort();
+		assert_eq!(cleaned_up, vec![CoreIndex::from(1), CoreIndex::from(2), CoreIndex::from(3)]);
+
+		let remaining = <PendingAvailability<Test>>::get(&chain_a).unwrap();
+		assert_eq!(remaining.len(), 1);
+		assert_eq!(remaining[0].hash, candidate(chain_a, 1).hash());
+		assert!(<PendingAvailability<Test>>::get(&chain_b).is_none());
 	});
 }
 
@@ -544,7 +644,7 @@ fn bitfield_checks() {
 			);
 			assert_eq!(checked_bitfields.len(), 1, "No bitfields should have been filtered!");
 
-			let x = process_bitfields(expected_bits(), checked_bitfields, core_lookup);
+			let x = process_bitfields(checked_bitfields);
 			assert!(x.is_empty(), "No core should be freed.");
 		}
 
@@ -565,7 +665,7 @@ fn bitfield_checks() {
 			);
 			assert_eq!(checked_bitfields.len(), 1, "No bitfields should have been filtered!");
 
-			let x = process_bitfields(expected_bits(), checked_bitfields, core_lookup);
+			let x = process_bitfields(checked_bitfields);
 			assert!(x.is_empty(), "No core should be freed.");
 		}
 
@@ -578,18 +678,20 @@ fn bitfield_checks() {
 			let default_candidate = TestCandidateBuilder::default().build();
 			<PendingAvailability<Test>>::insert(
 				chain_a,
-				CandidatePendingAvailability {
+				[CandidatePendingAvailability {
 					core: CoreIndex::from(0),
 					hash: default_candidate.hash(),
 					descriptor: default_candidate.descriptor,
+					commitments: default_candidate.commitments,
 					availability_votes: default_availability_votes(),
 					relay_parent_number: 0,
 					backed_in_number: 0,
 					backers: default_backing_bitfield(),
 					backing_group: GroupIndex::from(0),
-				},
+				}]
+				.into_iter()
+				.collect::<VecDeque<_>>(),
 			);
-			PendingAvailabilityCommitments::<Test>::insert(chain_a, default_candidate.commitments);
 
 			*bare_bitfield.0.get_mut(0).unwrap() = true;
 			let signed = sign_bitfield(
@@ -607,53 +709,10 @@ fn bitfield_checks() {
 			);
 			assert_eq!(checked_bitfields.len(), 1, "No bitfields should have been filtered!");
 
-			let x = process_bitfields(expected_bits(), checked_bitfields, core_lookup);
+			let x = process_bitfields(checked_bitfields);
 			assert!(x.is_empty(), "No core should be freed.");
 
 			<PendingAvailability<Test>>::remove(chain_a);
-			PendingAvailabilityCommitments::<Test>::remove(chain_a);
-		}
-
-		// bitfield signed with pending bit signed, but no commitments.
-		{
-			let mut bare_bitfield = default_bitfield();
-
-			assert_eq!(core_lookup(CoreIndex::from(0)), Some(chain_a));
-
-			let default_candidate = TestCandidateBuilder::default().build();
-			<PendingAvailability<Test>>::insert(
-				chain_a,
-				CandidatePendingAvailability {
-					core: CoreIndex::from(0),
-					hash: default_candidate.hash(),
-					descriptor: default_candidate.descriptor,
-					availability_votes: default_availability_votes(),
-					relay_parent_number: 0,
-					backed_in_number: 0,
-					backers: default_backing_bitfield(),
-					backing_group: GroupIndex::from(0),
-				},
-			);
-
-			*bare_bitfield.0.get_mut(0).unwrap() = true;
-			let signed = sign_bitfield(
-				&keystore,
-				&validators[0],
-				ValidatorIndex(0),
-				bare_bitfield,
-				&signing_context,
-			);
-
-			let checked_bitfields = simple_sanitize_bitfields(
-				vec![signed.into()],
-				DisputedBitfield::zeros(expected_bits()),
-				expected_bits(),
-			);
-			assert_eq!(checked_bitfields.len(), 1, "No bitfields should have been filtered!");
-
-			let x = process_bitfields(expected_bits(), checked_bitfields, core_lookup);
-			// no core is freed
-			assert!(x.is_empty(), "No core should be freed.");
 		}
 	});
 }
@@ -701,13 +760,6 @@ fn supermajority_bitfields_trigger_availability() {
 		let signing_context =
 			SigningContext { parent_hash: System::parent_hash(), session_index: 5 };
 
-		let core_lookup = |core| match core {
-			core if core == CoreIndex::from(0) => Some(chain_a),
-			core if core == CoreIndex::from(1) => Some(chain_b),
-			core if core == CoreIndex::from(2) => Some(thread_a),
-			_ => panic!("Core out of bounds for 2 parachains and 1 parathread core."),
-		};
-
 		let candidate_a = TestCandidateBuilder {
 			para_id: chain_a,
 			head_data: vec![1, 2, 3, 4].into(),
@@ -717,18 +769,20 @@ fn supermajority_bitfields_trigger_availability() {
 
 		<PendingAvailability<Test>>::insert(
 			chain_a,
-			CandidatePendingAvailability {
+			[CandidatePendingAvailability {
 				core: CoreIndex::from(0),
 				hash: candidate_a.hash(),
 				descriptor: candidate_a.clone().descriptor,
+				commitments: candidate_a.clone().commitments,
 				availability_votes: default_availability_votes(),
 				relay_parent_number: 0,
 				backed_in_number: 0,
 				backers: backing_bitfield(&[3, 4]),
 				backing_group: GroupIndex::from(0),
-			},
+			}]
+			.into_iter()
+			.collect::<VecDeque<_>>(),
 		);
-		PendingAvailabilityCommitments::<Test>::insert(chain_a, candidate_a.clone().commitments);
 
 		let candidate_b = TestCandidateBuilder {
 			para_id: chain_b,
@@ -739,18 +793,20 @@ fn supermajority_bitfields_trigger_availability() {
 
 		<PendingAvailability<Test>>::insert(
 			chain_b,
-			CandidatePendingAvailability {
+			[CandidatePendingAvailability {
 				core: CoreIndex::from(1),
 				hash: candidate_b.hash(),
 				descriptor: candidate_b.descriptor,
+				commitments: candidate_b.commitments,
 				availability_votes: default_availability_votes(),
 				relay_parent_number: 0,
 				backed_in_number: 0,
 				backers: backing_bitfield(&[0, 2]),
 				backing_group: GroupIndex::from(1),
-			},
+			}]
+			.into_iter()
+			.collect::<VecDeque<_>>(),
 		);
-		PendingAvailabilityCommitments::<Test>::insert(chain_b, candidate_b.commitments);
 
 		// this bitfield signals that a and b are available.
 		let a_and_b_available = {
@@ -809,15 +865,13 @@ fn supermajority_bitfields_trigger_availability() {
 		assert_eq!(checked_bitfields.len(), old_len, "No bitfields should have been filtered!");
 
 		// only chain A's core is freed.
-		let v = process_bitfields(expected_bits(), checked_bitfields, core_lookup);
+		let v = process_bitfields(checked_bitfields);
 		assert_eq!(vec![(CoreIndex(0), candidate_a.hash())], v);
 
 		// chain A had 4 signing off, which is >= threshold.
 		// chain B has 3 signing off, which is < threshold.
 		assert!(<PendingAvailability<Test>>::get(&chain_a).is_none());
-		assert!(<PendingAvailabilityCommitments<Test>>::get(&chain_a).is_none());
-		assert!(<PendingAvailabilityCommitments<Test>>::get(&chain_b).is_some());
-		assert_eq!(<PendingAvailability<Test>>::get(&chain_b).unwrap().availability_votes, {
+		assert_eq!(<PendingAvailability<Test>>::get(&chain_b).unwrap()[0].availability_votes, {
 			// check that votes from first 3 were tracked.
 
 			let mut votes = default_availability_votes();
@@ -913,100 +967,10 @@ fn candidate_checks() {
 
 		let thread_collator: CollatorId = Sr25519Keyring::Two.public().into();
 		let chain_a_assignment = (chain_a, CoreIndex::from(0));
-
 		let chain_b_assignment = (chain_b, CoreIndex::from(1));
-
 		let thread_a_assignment = (thread_a, CoreIndex::from(2));
 		let allowed_relay_parents = default_allowed_relay_parent_tracker();
 
-		// unscheduled candidate.
-		{
-			let mut candidate = TestCandidateBuilder {
-				para_id: chain_a,
-				relay_parent: System::parent_hash(),
-				pov_hash: Hash::repeat_byte(1),
-				persisted_validation_data_hash: make_vdata_hash(chain_a).unwrap(),
-				hrmp_watermark: RELAY_PARENT_NUM,
-				..Default::default()
-			}
-			.build();
-			collator_sign_candidate(Sr25519Keyring::One, &mut candidate);
-
-			let backed = back_candidate(
-				candidate,
-				&validators,
-				group_validators(GroupIndex::from(0)).unwrap().as_ref(),
-				&keystore,
-				&signing_context,
-				BackingKind::Threshold,
-			);
-
-			assert_noop!(
-				ParaInclusion::process_candidates(
-					&allowed_relay_parents,
-					vec![backed],
-					&[chain_b_assignment].into_iter().collect(),
-					&group_validators,
-				),
-				Error::<Test>::UnscheduledCandidate
-			);
-		}
-
-		// candidates out of order.
-		{
-			let mut candidate_a = TestCandidateBuilder {
-				para_id: chain_a,
-				relay_parent: System::parent_hash(),
-				pov_hash: Hash::repeat_byte(1),
-				persisted_validation_data_hash: make_vdata_hash(chain_a).unwrap(),
-				hrmp_watermark: RELAY_PARENT_NUM,
-				..Default::default()
-			}
-			.build();
-			let mut candidate_b = TestCandidateBuilder {
-				para_id: chain_b,
-				relay_parent: System::parent_hash(),
-				pov_hash: Hash::repeat_byte(2),
-				persisted_validation_data_hash: make_vdata_hash(chain_b).unwrap(),
-				hrmp_watermark: RELAY_PARENT_NUM,
-				..Default::default()
-			}
-			.build();
-
-			collator_sign_candidate(Sr25519Keyring::One, &mut candidate_a);
-
-			collator_sign_candidate(Sr25519Keyring::Two, &mut candidate_b);
-
-			let backed_a = back_candidate(
-				candidate_a,
-				&validators,
-				group_validators(GroupIndex::from(0)).unwrap().as_ref(),
-				&keystore,
-				&signing_context,
-				BackingKind::Threshold,
-			);
-
-			let backed_b = back_candidate(
-				candidate_b,
-				&validators,
-				group_validators(GroupIndex::from(1)).unwrap().as_ref(),
-				&keystore,
-				&signing_context,
-				BackingKind::Threshold,
-			);
-
-			// out-of-order manifests as unscheduled.
-			assert_noop!(
-				ParaInclusion::process_candidates(
-					&allowed_relay_parents,
-					vec![backed_b, backed_a],
-					&[chain_a_assignment, chain_b_assignment].into_iter().collect(),
-					&group_validators,
-				),
-				Error::<Test>::ScheduledOutOfOrder
-			);
-		}
-
 		// candidate not backed.
 		{
 			let mut candidate = TestCandidateBuilder {
@@ -1032,8 +996,9 @@ fn candidate_checks() {
 			assert_noop!(
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
-					vec![backed],
-					&[chain_a_assignment].into_iter().collect(),
+					&[(chain_a_assignment.0, vec![(backed, chain_a_assignment.1)])]
+						.into_iter()
+						.collect(),
 					&group_validators,
 				),
 				Error::<Test>::InsufficientBacking
@@ -1056,6 +1021,7 @@ fn candidate_checks() {
 
 			let mut candidate_b = TestCandidateBuilder {
 				para_id: chain_b,
+				core_index: chain_b_assignment.1,
 				relay_parent: System::parent_hash(),
 				pov_hash: Hash::repeat_byte(2),
 				persisted_validation_data_hash: make_vdata_hash(chain_b).unwrap(),
@@ -1089,8 +1055,12 @@ fn candidate_checks() {
 			assert_noop!(
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
-					vec![backed_b, backed_a],
-					&[chain_a_assignment, chain_b_assignment].into_iter().collect(),
+					&[
+						(chain_a_assignment.0, vec![(backed_a, chain_a_assignment.1)]),
+						(chain_b_assignment.0, vec![(backed_b, chain_b_assignment.1)])
+					]
+					.into_iter()
+					.collect(),
 					&group_validators,
 				),
 				Error::<Test>::DisallowedRelayParent
@@ -1101,6 +1071,7 @@ fn candidate_checks() {
 		{
 			let mut candidate = TestCandidateBuilder {
 				para_id: thread_a,
+				core_index: thread_a_assignment.1,
 				relay_parent: System::parent_hash(),
 				pov_hash: Hash::repeat_byte(1),
 				persisted_validation_data_hash: make_vdata_hash(thread_a).unwrap(),
@@ -1127,15 +1098,16 @@ fn candidate_checks() {
 			assert_noop!(
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
-					vec![backed],
-					&[thread_a_assignment].into_iter().collect(),
+					&[(thread_a_assignment.0, vec![(backed, thread_a_assignment.1)])]
+						.into_iter()
+						.collect(),
 					&group_validators,
 				),
 				Error::<Test>::NotCollatorSigned
 			);
 		}
 
-		// para occupied - reject.
+		// candidate does not build on top of the latest candidate pending availability - reject.
 		{
 			let mut candidate = TestCandidateBuilder {
 				para_id: chain_a,
@@ -1158,42 +1130,48 @@ fn candidate_checks() {
 				BackingKind::Threshold,
 			);
 
-			let candidate = TestCandidateBuilder::default().build();
+			let pending = TestCandidateBuilder {
+				para_id: chain_a,
+				head_data: vec![4, 2].into(),
+				..Default::default()
+			}
+			.build();
 			<PendingAvailability<Test>>::insert(
 				&chain_a,
-				CandidatePendingAvailability {
-					core: CoreIndex::from(0),
-					hash: candidate.hash(),
-					descriptor: candidate.descriptor,
+				[CandidatePendingAvailability {
+					core: CoreIndex::from(1),
+					hash: pending.hash(),
+					descriptor: pending.descriptor,
+					commitments: pending.commitments,
 					availability_votes: default_availability_votes(),
 					relay_parent_number: 3,
 					backed_in_number: 4,
 					backers: default_backing_bitfield(),
-					backing_group: GroupIndex::from(0),
-				},
+					backing_group: GroupIndex::from(1),
+				}]
+				.into_iter()
+				.collect::<VecDeque<_>>(),
 			);
-			<PendingAvailabilityCommitments<Test>>::insert(&chain_a, candidate.commitments);
 
 			assert_noop!(
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
-					vec![backed],
-					&[chain_a_assignment].into_iter().collect(),
+					&[(chain_a, vec![(backed, chain_a_assignment.1)])].into_iter().collect(),
 					&group_validators,
 				),
-				Error::<Test>::CandidateScheduledBeforeParaFree
+				Error::<Test>::ValidationDataHashMismatch
 			);
 
 			<PendingAvailability<Test>>::remove(&chain_a);
-			<PendingAvailabilityCommitments<Test>>::remove(&chain_a);
 		}
 
-		// messed up commitments storage - do not panic - reject.
+		// interfering code upgrade - reject
 		{
 			let mut candidate = TestCandidateBuilder {
 				para_id: chain_a,
 				relay_parent: System::parent_hash(),
 				pov_hash: Hash::repeat_byte(1),
+				new_validation_code: Some(dummy_validation_code()),
 				persisted_validation_data_hash: make_vdata_hash(chain_a).unwrap(),
 				hrmp_watermark: RELAY_PARENT_NUM,
 				..Default::default()
@@ -1202,9 +1180,6 @@ fn candidate_checks() {
 
 			collator_sign_candidate(Sr25519Keyring::One, &mut candidate);
 
-			// this is not supposed to happen
-			<PendingAvailabilityCommitments<Test>>::insert(&chain_a, candidate.commitments.clone());
-
 			let backed = back_candidate(
 				candidate,
 				&validators,
@@ -1214,27 +1189,38 @@ fn candidate_checks() {
 				BackingKind::Threshold,
 			);
 
+			{
+				let cfg = Configuration::config();
+				let expected_at = 10 + cfg.validation_upgrade_delay;
+				assert_eq!(expected_at, 12);
+				Paras::schedule_code_upgrade(
+					chain_a,
+					vec![9, 8, 7, 6, 5, 4, 3, 2, 1].into(),
+					expected_at,
+					&cfg,
+					SetGoAhead::Yes,
+				);
+			}
+
 			assert_noop!(
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
-					vec![backed],
-					&[chain_a_assignment].into_iter().collect(),
+					&[(chain_a_assignment.0, vec![(backed, chain_a_assignment.1)])]
+						.into_iter()
+						.collect(),
 					&group_validators,
 				),
-				Error::<Test>::CandidateScheduledBeforeParaFree
+				Error::<Test>::PrematureCodeUpgrade
 			);
-
-			<PendingAvailabilityCommitments<Test>>::remove(&chain_a);
 		}
 
-		// interfering code upgrade - reject
+		// Bad validation data hash - reject
 		{
 			let mut candidate = TestCandidateBuilder {
 				para_id: chain_a,
 				relay_parent: System::parent_hash(),
 				pov_hash: Hash::repeat_byte(1),
-				new_validation_code: Some(dummy_validation_code()),
-				persisted_validation_data_hash: make_vdata_hash(chain_a).unwrap(),
+				persisted_validation_data_hash: [42u8; 32].into(),
 				hrmp_watermark: RELAY_PARENT_NUM,
 				..Default::default()
 			}
@@ -1251,38 +1237,27 @@ fn candidate_checks() {
 				BackingKind::Threshold,
 			);
 
-			{
-				let cfg = Configuration::config();
-				let expected_at = 10 + cfg.validation_upgrade_delay;
-				assert_eq!(expected_at, 12);
-				Paras::schedule_code_upgrade(
-					chain_a,
-					vec![9, 8, 7, 6, 5, 4, 3, 2, 1].into(),
-					expected_at,
-					&cfg,
-					SetGoAhead::Yes,
-				);
-			}
-
-			assert_noop!(
+			assert_eq!(
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
-					vec![backed],
-					&[chain_a_assignment].into_iter().collect(),
+					&[(chain_a_assignment.0, vec![(backed, chain_a_assignment.1)])]
+						.into_iter()
+						.collect(),
 					&group_validators,
 				),
-				Error::<Test>::PrematureCodeUpgrade
+				Err(Error::<Test>::ValidationDataHashMismatch.into()),
 			);
 		}
 
-		// Bad validation data hash - reject
+		// bad validation code hash
 		{
 			let mut candidate = TestCandidateBuilder {
 				para_id: chain_a,
 				relay_parent: System::parent_hash(),
 				pov_hash: Hash::repeat_byte(1),
-				persisted_validation_data_hash: [42u8; 32].into(),
+				persisted_validation_data_hash: make_vdata_hash(chain_a).unwrap(),
 				hrmp_watermark: RELAY_PARENT_NUM,
+				validation_code: ValidationCode(vec![9, 8, 7, 6, 5, 4, 3, 2, 1]),
 				..Default::default()
 			}
 			.build();
@@ -1298,18 +1273,19 @@ fn candidate_checks() {
 				BackingKind::Threshold,
 			);
 
-			assert_eq!(
+			assert_noop!(
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
-					vec![backed],
-					&[chain_a_assignment].into_iter().collect(),
+					&[(chain_a_assignment.0, vec![(backed, chain_a_assignment.1)])]
+						.into_iter()
+						.collect(),
 					&group_validators,
 				),
-				Err(Error::<Test>::ValidationDataHashMismatch.into()),
+				Error::<Test>::InvalidValidationCodeHash
 			);
 		}
 
-		// bad validation code hash
+		// Para head hash in descriptor doesn't match head data
 		{
 			let mut candidate = TestCandidateBuilder {
 				para_id: chain_a,
@@ -1317,7 +1293,7 @@ fn candidate_checks() {
 				pov_hash: Hash::repeat_byte(1),
 				persisted_validation_data_hash: make_vdata_hash(chain_a).unwrap(),
 				hrmp_watermark: RELAY_PARENT_NUM,
-				validation_code: ValidationCode(vec![9, 8, 7, 6, 5, 4, 3, 2, 1]),
+				para_head_hash: Some(Hash::random()),
 				..Default::default()
 			}
 			.build();
@@ -1336,15 +1312,16 @@ fn candidate_checks() {
 			assert_noop!(
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
-					vec![backed],
-					&[chain_a_assignment].into_iter().collect(),
+					&[(chain_a_assignment.0, vec![(backed, chain_a_assignment.1)])]
+						.into_iter()
+						.collect(),
 					&group_validators,
 				),
-				Error::<Test>::InvalidValidationCodeHash
+				Error::<Test>::ParaHeadMismatch
 			);
 		}
 
-		// Para head hash in descriptor doesn't match head data
+		// Candidate built for a different core than the one it is backed on.
 		{
 			let mut candidate = TestCandidateBuilder {
 				para_id: chain_a,
@@ -1352,7 +1329,7 @@ fn candidate_checks() {
 				pov_hash: Hash::repeat_byte(1),
 				persisted_validation_data_hash: make_vdata_hash(chain_a).unwrap(),
 				hrmp_watermark: RELAY_PARENT_NUM,
-				para_head_hash: Some(Hash::random()),
+				core_index: chain_b_assignment.1,
 				..Default::default()
 			}
 			.build();
@@ -1371,11 +1348,12 @@ fn candidate_checks() {
 			assert_noop!(
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
-					vec![backed],
-					&[chain_a_assignment].into_iter().collect(),
+					&[(chain_a_assignment.0, vec![(backed, chain_a_assignment.1)])]
+						.into_iter()
+						.collect(),
 					&group_validators,
 				),
-				Error::<Test>::ParaHeadMismatch
+				Error::<Test>::CandidateCoreMismatch
 			);
 		}
 	});
@@ -1459,6 +1437,7 @@ fn backing_works() {
 
 		let mut candidate_b = TestCandidateBuilder {
 			para_id: chain_b,
+			core_index: chain_b_assignment.1,
 			relay_parent: System::parent_hash(),
 			pov_hash: Hash::repeat_byte(2),
 			persisted_validation_data_hash: make_vdata_hash(chain_b).unwrap(),
@@ -1470,6 +1449,7 @@ fn backing_works() {
 
 		let mut candidate_c = TestCandidateBuilder {
 			para_id: thread_a,
+			core_index: thread_a_assignment.1,
 			relay_parent: System::parent_hash(),
 			pov_hash: Hash::repeat_byte(3),
 			persisted_validation_data_hash: make_vdata_hash(thread_a).unwrap(),
@@ -1506,13 +1486,17 @@ fn backing_works() {
 			BackingKind::Threshold,
 		);
 
-		let backed_candidates = vec![backed_a.clone(), backed_b.clone(), backed_c];
+		let backed_candidates = vec![
+			(chain_a_assignment, backed_a.clone()),
+			(chain_b_assignment, backed_b.clone()),
+			(thread_a_assignment, backed_c),
+		];
 		let get_backing_group_idx = {
 			// the order defines the group implicitly for this test case
 			let backed_candidates_with_groups = backed_candidates
 				.iter()
 				.enumerate()
-				.map(|(idx, backed_candidate)| (backed_candidate.hash(), GroupIndex(idx as _)))
+				.map(|(idx, (_, backed_candidate))| (backed_candidate.hash(), GroupIndex(idx as _)))
 				.collect::<Vec<_>>();
 
 			move |candidate_hash_x: CandidateHash| -> Option<GroupIndex> {
@@ -1531,9 +1515,10 @@ fn backing_works() {
 			candidate_receipt_with_backing_validator_indices,
 		} = ParaInclusion::process_candidates(
 			&allowed_relay_parents,
-			backed_candidates.clone(),
-			&[chain_a_assignment, chain_b_assignment, thread_a_assignment]
+			&backed_candidates
+				.clone()
 				.into_iter()
+				.map(|((para_id, core), backed)| (para_id, vec![(backed, core)]))
 				.collect(),
 			&group_validators,
 		)
@@ -1554,7 +1539,7 @@ fn backing_works() {
 				CandidateHash,
 				(CandidateReceipt, Vec<(ValidatorIndex, ValidityAttestation)>),
 			>::new();
-			backed_candidates.into_iter().for_each(|backed_candidate| {
+			backed_candidates.into_iter().for_each(|(_, backed_candidate)| {
 				let candidate_receipt_with_backers = intermediate
 					.entry(backed_candidate.hash())
 					.or_insert_with(|| (backed_candidate.receipt(), Vec::new()));
@@ -1605,20 +1590,21 @@ fn backing_works() {
 		};
 		assert_eq!(
 			<PendingAvailability<Test>>::get(&chain_a),
-			Some(CandidatePendingAvailability {
-				core: CoreIndex::from(0),
-				hash: candidate_a.hash(),
-				descriptor: candidate_a.descriptor,
-				availability_votes: default_availability_votes(),
-				relay_parent_number: System::block_number() - 1,
-				backed_in_number: System::block_number(),
-				backers,
-				backing_group: GroupIndex::from(0),
-			})
-		);
-		assert_eq!(
-			<PendingAvailabilityCommitments<Test>>::get(&chain_a),
-			Some(candidate_a.commitments),
+			Some(
+				[CandidatePendingAvailability {
+					core: CoreIndex::from(0),
+					hash: candidate_a.hash(),
+					descriptor: candidate_a.descriptor,
+					commitments: candidate_a.commitments,
+					availability_votes: default_availability_votes(),
+					relay_parent_number: System::block_number() - 1,
+					backed_in_number: System::block_number(),
+					backers,
+					backing_group: GroupIndex::from(0),
+				}]
+				.into_iter()
+				.collect::<VecDeque<_>>()
+			)
 		);
 
 		let backers = {
@@ -1630,39 +1616,211 @@ fn backing_works() {
 		};
 		assert_eq!(
 			<PendingAvailability<Test>>::get(&chain_b),
-			Some(CandidatePendingAvailability {
-				core: CoreIndex::from(1),
-				hash: candidate_b.hash(),
-				descriptor: candidate_b.descriptor,
-				availability_votes: default_availability_votes(),
-				relay_parent_number: System::block_number() - 1,
-				backed_in_number: System::block_number(),
-				backers,
-				backing_group: GroupIndex::from(1),
-			})
+			Some(
+				[CandidatePendingAvailability {
+					core: CoreIndex::from(1),
+					hash: candidate_b.hash(),
+					descriptor: candidate_b.descriptor,
+					commitments: candidate_b.commitments,
+					availability_votes: default_availability_votes(),
+					relay_parent_number: System::block_number() - 1,
+					backed_in_number: System::block_number(),
+					backers,
+					backing_group: GroupIndex::from(1),
+				}]
+				.into_iter()
+				.collect::<VecDeque<_>>()
+			)
 		);
+
 		assert_eq!(
-			<PendingAvailabilityCommitments<Test>>::get(&chain_b),
-			Some(candidate_b.commitments),
+			<PendingAvailability<Test>>::get(&thread_a),
+			Some(
+				[CandidatePendingAvailability {
+					core: CoreIndex::from(2),
+					hash: candidate_c.hash(),
+					descriptor: candidate_c.descriptor,
+					commitments: candidate_c.commitments,
+					availability_votes: default_availability_votes(),
+					relay_parent_number: System::block_number() - 1,
+					backed_in_number: System::block_number(),
+					backers: backing_bitfield(&[4]),
+					backing_group: GroupIndex::from(2),
+				}]
+				.into_iter()
+				.collect::<VecDeque<_>>()
+			)
+		);
+	});
+}
+
+#[test]
+fn backing_works_with_elastic_scaling_mvp() {
+	let chain_a = ParaId::from(1_u32);
+
+	// The block number of the relay-parent for testing.
+	const RELAY_PARENT_NUM: BlockNumber = 4;
+
+	let paras = vec![(chain_a, ParaKind::Parachain)];
+	let validators = vec![
+		Sr25519Keyring::Alice,
+		Sr25519Keyring::Bob,
+		Sr25519Keyring::Charlie,
+		Sr25519Keyring::Dave,
+		Sr25519Keyring::Ferdie,
+	];
+	let keystore: KeystorePtr = Arc::new(LocalKeystore::in_memory());
+	for validator in validators.iter() {
+		Keystore::sr25519_generate_new(
+			&*keystore,
+			PARACHAIN_KEY_TYPE_ID,
+			Some(&validator.to_seed()),
+		)
+		.unwrap();
+	}
+	let validator_public = validator_pubkeys(&validators);
+
+	new_test_ext(genesis_config(paras)).execute_with(|| {
+		shared::Pallet::<Test>::set_active_validators_ascending(validator_public.clone());
+		shared::Pallet::<Test>::set_session_index(5);
+
+		run_to_block(5, |_| None);
+
+		let signing_context =
+			SigningContext { parent_hash: System::parent_hash(), session_index: 5 };
+
+		let group_validators = |group_index: GroupIndex| {
+			match group_index {
+				group_index if group_index == GroupIndex::from(0) => Some(vec![0, 1]),
+				group_index if group_index == GroupIndex::from(1) => Some(vec![2, 3]),
+				group_index if group_index == GroupIndex::from(2) => Some(vec![4]),
+				_ => panic!("Group index out of bounds for 3 cores"),
+			}
+			.map(|vs| vs.into_iter().map(ValidatorIndex).collect::<Vec<_>>())
+		};
+
+		// When processing candidates, we compute the group index from scheduler.
+		let validator_groups = vec![
+			vec![ValidatorIndex(0), ValidatorIndex(1)],
+			vec![ValidatorIndex(2), ValidatorIndex(3)],
+			vec![ValidatorIndex(4)],
+		];
+		Scheduler::set_validator_groups(validator_groups);
+
+		let allowed_relay_parents = default_allowed_relay_parent_tracker();
+
+		// Two candidates of the same para, the second one building on top of the first one.
+		let mut candidate_a_1 = TestCandidateBuilder {
+			para_id: chain_a,
+			relay_parent: System::parent_hash(),
+			pov_hash: Hash::repeat_byte(1),
+			persisted_validation_data_hash: make_vdata_hash(chain_a).unwrap(),
+			head_data: vec![1].into(),
+			hrmp_watermark: RELAY_PARENT_NUM,
+			..Default::default()
+		}
+		.build();
+		collator_sign_candidate(Sr25519Keyring::One, &mut candidate_a_1);
+
+		let mut candidate_a_2 = TestCandidateBuilder {
+			para_id: chain_a,
+			relay_parent: System::parent_hash(),
+			pov_hash: Hash::repeat_byte(2),
+			persisted_validation_data_hash:
+				crate::util::make_persisted_validation_data_with_parent::<Test>(
+					RELAY_PARENT_NUM,
+					Default::default(),
+					vec![1].into(),
+				)
+				.hash(),
+			head_data: vec![2].into(),
+			hrmp_watermark: RELAY_PARENT_NUM,
+			core_index: CoreIndex(1),
+			..Default::default()
+		}
+		.build();
+		collator_sign_candidate(Sr25519Keyring::One, &mut candidate_a_2);
+
+		let backed_a_1 = back_candidate(
+			candidate_a_1.clone(),
+			&validators,
+			group_validators(GroupIndex::from(0)).unwrap().as_ref(),
+			&keystore,
+			&signing_context,
+			BackingKind::Threshold,
+		);
+		let backed_a_2 = back_candidate(
+			candidate_a_2.clone(),
+			&validators,
+			group_validators(GroupIndex::from(1)).unwrap().as_ref(),
+			&keystore,
+			&signing_context,
+			BackingKind::Threshold,
 		);
 
+		let ProcessedCandidates { core_indices: occupied_cores, .. } =
+			ParaInclusion::process_candidates(
+				&allowed_relay_parents,
+				&[(chain_a, vec![(backed_a_1, CoreIndex(0)), (backed_a_2, CoreIndex(1))])]
+					.into_iter()
+					.collect(),
+				&group_validators,
+			)
+			.expect("candidates scheduled, in order, and backed");
+
 		assert_eq!(
-			<PendingAvailability<Test>>::get(&thread_a),
-			Some(CandidatePendingAvailability {
-				core: CoreIndex::from(2),
-				hash: candidate_c.hash(),
-				descriptor: candidate_c.descriptor,
-				availability_votes: default_availability_votes(),
-				relay_parent_number: System::block_number() - 1,
-				backed_in_number: System::block_number(),
-				backers: backing_bitfield(&[4]),
-				backing_group: GroupIndex::from(2),
-			})
+			occupied_cores,
+			vec![(CoreIndex::from(0), chain_a), (CoreIndex::from(1), chain_a)]
 		);
 		assert_eq!(
-			<PendingAvailabilityCommitments<Test>>::get(&thread_a),
-			Some(candidate_c.commitments),
+			ParaInclusion::candidates_pending_availability(chain_a),
+			vec![candidate_a_1.clone(), candidate_a_2.clone()]
 		);
+		assert!(ParaInclusion::pending_availability_with_core(chain_a, CoreIndex(1)).is_some());
+		assert!(ParaInclusion::pending_availability_with_core(chain_a, CoreIndex(2)).is_none());
+
+		let sign_all = |bits: &[usize]| {
+			let mut bare_bitfield = default_bitfield();
+			for bit in bits {
+				*bare_bitfield.0.get_mut(*bit).unwrap() = true;
+			}
+
+			let signed_bitfields = validators
+				.iter()
+				.enumerate()
+				.map(|(i, key)| {
+					sign_bitfield(
+						&keystore,
+						key,
+						ValidatorIndex(i as _),
+						bare_bitfield.clone(),
+						&signing_context,
+					)
+					.into()
+				})
+				.collect::<Vec<_>>();
+
+			simple_sanitize_bitfields(
+				signed_bitfields,
+				DisputedBitfield::zeros(expected_bits()),
+				expected_bits(),
+			)
+		};
+
+		// The second candidate becomes available first. It cannot be enacted before its parent.
+		let freed = process_bitfields(sign_all(&[1]));
+		assert!(freed.is_empty());
+		assert_eq!(<PendingAvailability<Test>>::get(&chain_a).unwrap().len(), 2);
+		assert_eq!(Paras::para_head(&chain_a), Some(Default::default()));
+
+		// Once the first candidate is available, both of them are enacted in order.
+		let freed = process_bitfields(sign_all(&[0]));
+		assert_eq!(
+			freed,
+			vec![(CoreIndex(0), candidate_a_1.hash()), (CoreIndex(1), candidate_a_2.hash())]
+		);
+		assert!(<PendingAvailability<Test>>::get(&chain_a).is_none());
+		assert_eq!(Paras::para_head(&chain_a), Some(vec![2].into()));
 	});
 }
 
@@ -1745,8 +1903,9 @@ fn can_include_candidate_with_ok_code_upgrade() {
 		let ProcessedCandidates { core_indices: occupied_cores, .. } =
 			ParaInclusion::process_candidates(
 				&allowed_relay_parents,
-				vec![backed_a],
-				&[chain_a_assignment].into_iter().collect(),
+				&[(chain_a_assignment.0, vec![(backed_a, chain_a_assignment.1)])]
+					.into_iter()
+					.collect(),
 				&group_validators,
 			)
 			.expect("candidates scheduled, in order, and backed");
@@ -1762,20 +1921,21 @@ fn can_include_candidate_with_ok_code_upgrade() {
 		};
 		assert_eq!(
 			<PendingAvailability<Test>>::get(&chain_a),
-			Some(CandidatePendingAvailability {
-				core: CoreIndex::from(0),
-				hash: candidate_a.hash(),
-				descriptor: candidate_a.descriptor,
-				availability_votes: default_availability_votes(),
-				relay_parent_number: System::block_number() - 1,
-				backed_in_number: System::block_number(),
-				backers,
-				backing_group: GroupIndex::from(0),
-			})
-		);
-		assert_eq!(
-			<PendingAvailabilityCommitments<Test>>::get(&chain_a),
-			Some(candidate_a.commitments),
+			Some(
+				[CandidatePendingAvailability {
+					core: CoreIndex::from(0),
+					hash: candidate_a.hash(),
+					descriptor: candidate_a.descriptor,
+					commitments: candidate_a.commitments,
+					availability_votes: default_availability_votes(),
+					relay_parent_number: System::block_number() - 1,
+					backed_in_number: System::block_number(),
+					backers,
+					backing_group: GroupIndex::from(0),
+				}]
+				.into_iter()
+				.collect::<VecDeque<_>>()
+			)
 		);
 	});
 }
@@ -1895,6 +2055,7 @@ fn check_allowed_relay_parents() {
 
 		let mut candidate_b = TestCandidateBuilder {
 			para_id: chain_b,
+			core_index: chain_b_assignment.1,
 			relay_parent: relay_parent_b.1,
 			pov_hash: Hash::repeat_byte(2),
 			persisted_validation_data_hash: make_vdata_hash_with_block_number(
@@ -1911,6 +2072,7 @@ fn check_allowed_relay_parents() {
 
 		let mut candidate_c = TestCandidateBuilder {
 			para_id: thread_a,
+			core_index: thread_a_assignment.1,
 			relay_parent: relay_parent_c.1,
 			pov_hash: Hash::repeat_byte(3),
 			persisted_validation_data_hash: make_vdata_hash_with_block_number(
@@ -1952,13 +2114,18 @@ fn check_allowed_relay_parents() {
 			BackingKind::Threshold,
 		);
 
-		let backed_candidates = vec![backed_a, backed_b, backed_c];
+		let backed_candidates = vec![
+			(chain_a_assignment, backed_a),
+			(chain_b_assignment, backed_b),
+			(thread_a_assignment, backed_c),
+		];
 
 		ParaInclusion::process_candidates(
 			&allowed_relay_parents,
-			backed_candidates.clone(),
-			&[chain_a_assignment, chain_b_assignment, thread_a_assignment]
+			&backed_candidates
+				.clone()
 				.into_iter()
+				.map(|((para_id, core), backed)| (para_id, vec![(backed, core)]))
 				.collect(),
 			&group_validators,
 		)
@@ -2024,33 +2191,37 @@ fn session_change_wipes() {
 		let candidate = TestCandidateBuilder::default().build();
 		<PendingAvailability<Test>>::insert(
 			&chain_a,
-			CandidatePendingAvailability {
+			[CandidatePendingAvailability {
 				core: CoreIndex::from(0),
 				hash: candidate.hash(),
 				descriptor: candidate.descriptor.clone(),
+				commitments: candidate.commitments.clone(),
 				availability_votes: default_availability_votes(),
 				relay_parent_number: 5,
 				backed_in_number: 6,
 				backers: default_backing_bitfield(),
 				backing_group: GroupIndex::from(0),
-			},
+			}]
+			.into_iter()
+			.collect::<VecDeque<_>>(),
 		);
-		<PendingAvailabilityCommitments<Test>>::insert(&chain_a, candidate.commitments.clone());
 
 		<PendingAvailability<Test>>::insert(
 			&chain_b,
-			CandidatePendingAvailability {
+			[CandidatePendingAvailability {
 				core: CoreIndex::from(1),
 				hash: candidate.hash(),
 				descriptor: candidate.descriptor,
+				commitments: candidate.commitments,
 				availability_votes: default_availability_votes(),
 				relay_parent_number: 6,
 				backed_in_number: 7,
 				backers: default_backing_bitfield(),
 				backing_group: GroupIndex::from(1),
-			},
+			}]
+			.into_iter()
+			.collect::<VecDeque<_>>(),
 		);
-		<PendingAvailabilityCommitments<Test>>::insert(&chain_b, candidate.commitments);
 
 		run_to_block(11, |_| None);
 
@@ -2062,8 +2233,6 @@ fn session_change_wipes() {
 
 		assert!(<PendingAvailability<Test>>::get(&chain_a).is_some());
 		assert!(<PendingAvailability<Test>>::get(&chain_b).is_some());
-		assert!(<PendingAvailabilityCommitments<Test>>::get(&chain_a).is_some());
-		assert!(<PendingAvailabilityCommitments<Test>>::get(&chain_b).is_some());
 
 		run_to_block(12, |n| match n {
 			12 => Some(SessionChangeNotification {
@@ -2085,12 +2254,9 @@ fn session_change_wipes() {
 
 		assert!(<PendingAvailability<Test>>::get(&chain_a).is_none());
 		assert!(<PendingAvailability<Test>>::get(&chain_b).is_none());
-		assert!(<PendingAvailabilityCommitments<Test>>::get(&chain_a).is_none());
-		assert!(<PendingAvailabilityCommitments<Test>>::get(&chain_b).is_none());
 
 		assert!(<AvailabilityBitfields<Test>>::iter().collect::<Vec<_>>().is_empty());
 		assert!(<PendingAvailability<Test>>::iter().collect::<Vec<_>>().is_empty());
-		assert!(<PendingAvailabilityCommitments<Test>>::iter().collect::<Vec<_>>().is_empty());
 	});
 }
 
@@ -2162,11 +2328,6 @@ fn para_upgrade_delay_scheduled_from_inclusion() {
 		]];
 		Scheduler::set_validator_groups(validator_groups);
 
-		let core_lookup = |core| match core {
-			core if core == CoreIndex::from(0) => Some(chain_a),
-			_ => None,
-		};
-
 		let allowed_relay_parents = default_allowed_relay_parent_tracker();
 
 		let chain_a_assignment = (chain_a, CoreIndex::from(0));
@@ -2194,8 +2355,9 @@ fn para_upgrade_delay_scheduled_from_inclusion() {
 		let ProcessedCandidates { core_indices: occupied_cores, .. } =
 			ParaInclusion::process_candidates(
 				&allowed_relay_parents,
-				vec![backed_a],
-				&[chain_a_assignment].into_iter().collect(),
+				&[(chain_a_assignment.0, vec![(backed_a, chain_a_assignment.1)])]
+					.into_iter()
+					.collect(),
 				&group_validators,
 			)
 			.expect("candidates scheduled, in order, and backed");
@@ -2229,11 +2391,10 @@ fn para_upgrade_delay_scheduled_from_inclusion() {
 			expected_bits(),
 		);
 
-		let v = process_bitfields(expected_bits(), checked_bitfields, core_lookup);
+		let v = process_bitfields(checked_bitfields);
 		assert_eq!(vec![(CoreIndex(0), candidate_a.hash())], v);
 
 		assert!(<PendingAvailability<Test>>::get(&chain_a).is_none());
-		assert!(<PendingAvailabilityCommitments<Test>>::get(&chain_a).is_none());
 
 		let active_vote_state = paras::Pallet::<Test>::active_vote_state(&new_validation_code_hash)
 			.expect("prechecking must be initiated");
@@ -28,7 +28,6 @@ use crate::{
 	inclusion::CandidateCheckContext,
 	initializer,
 	metrics::METRICS,
-	paras,
 	scheduler::{self, FreedReason},
 	shared::{self, AllowedRelayParentsTracker},
 	ParaId,
@@ -43,12 +42,12 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 use pallet_babe::{self, ParentBlockRandomness};
 use primitives::{
-	effective_minimum_backing_votes, BackedCandidate, CandidateHash, CandidateReceipt,
-	CheckedDisputeStatementSet, CheckedMultiDisputeStatementSet, CoreIndex, DisputeStatementSet,
-	InherentData as ParachainsInherentData, MultiDisputeStatementSet, ScrapedOnChainVotes,
-	SessionIndex, SignedAvailabilityBitfields, SigningContext, UncheckedSignedAvailabilityBitfield,
-	UncheckedSignedAvailabilityBitfields, ValidatorId, ValidatorIndex, ValidityAttestation,
-	PARACHAINS_INHERENT_IDENTIFIER,
+	effective_minimum_backing_votes, vstaging::node_features::FeatureIndex, BackedCandidate,
+	CandidateHash, CandidateReceipt, CheckedDisputeStatementSet, CheckedMultiDisputeStatementSet,
+	CoreIndex, DisputeStatementSet, InherentData as ParachainsInherentData,
+	MultiDisputeStatementSet, ScrapedOnChainVotes, SessionIndex, SignedAvailabilityBitfields,
+	SigningContext, UncheckedSignedAvailabilityBitfield, UncheckedSignedAvailabilityBitfields,
+	ValidatorId, ValidatorIndex, ValidityAttestation, PARACHAINS_INHERENT_IDENTIFIER,
 };
 use rand::{seq::SliceRandom, SeedableRng};
 
@@ -568,11 +567,9 @@ impl<T: Config> Pallet<T> {
 		// Process new availability bitfields, yielding any availability cores whose
 		// work has now concluded.
 		let freed_concluded =
-			<inclusion::Pallet<T>>::update_pending_availability_and_get_freed_cores::<_>(
-				expected_bits,
+			<inclusion::Pallet<T>>::update_pending_availability_and_get_freed_cores(
 				&validator_public[..],
 				bitfields.clone(),
-				<scheduler::Pallet<T>>::core_para,
 			);
 
 		// Inform the disputes module of all included candidates.
@@ -585,39 +582,42 @@ impl<T: Config> Pallet<T> {
 		let freed = collect_all_freed_cores::<T, _>(freed_concluded.iter().cloned());
 
 		<scheduler::Pallet<T>>::free_cores_and_fill_claimqueue(freed, now);
-		let scheduled = <scheduler::Pallet<T>>::scheduled_paras()
-			.map(|(core_idx, para_id)| (para_id, core_idx))
-			.collect();
+
+		// A para may be scheduled on multiple free cores at once. Cores which are still occupied
+		// cannot be used by new candidates, even if they have claims in the claim queue.
+		let availability_cores = <scheduler::Pallet<T>>::availability_cores();
+		let mut scheduled: BTreeMap<ParaId, BTreeSet<CoreIndex>> = BTreeMap::new();
+		for (core_idx, para_id) in <scheduler::Pallet<T>>::scheduled_paras() {
+			if availability_cores.get(core_idx.0 as usize).map_or(false, |core| core.is_free()) {
+				scheduled.entry(para_id).or_default().insert(core_idx);
+			}
+		}
+
+		let core_index_enabled = configuration::Pallet::<T>::config()
+			.node_features
+			.get(FeatureIndex::ElasticScalingMVP as usize)
+			.map(|b| *b)
+			.unwrap_or(false);
 
 		METRICS.on_candidates_processed_total(backed_candidates.len() as u64);
 
-		let SanitizedBackedCandidates { backed_candidates, votes_from_disabled_were_dropped } =
-			sanitize_backed_candidates::<T, _>(
-				backed_candidates,
-				&allowed_relay_parents,
-				|candidate_idx: usize,
-				 backed_candidate: &BackedCandidate<<T as frame_system::Config>::Hash>|
-				 -> bool {
-					let para_id = backed_candidate.descriptor().para_id;
-					let prev_context = <paras::Pallet<T>>::para_most_recent_context(para_id);
-					let check_ctx = CandidateCheckContext::<T>::new(prev_context);
-
-					// never include a concluded-invalid candidate
-					current_concluded_invalid_disputes.contains(&backed_candidate.hash()) ||
-					// Instead of checking the candidates with code upgrades twice
-					// move the checking up here and skip it in the training wheels fallback.
-					// That way we avoid possible duplicate checks while assuring all
-					// backed candidates fine to pass on.
-					//
-					// NOTE: this is the only place where we check the relay-parent.
-					check_ctx
-						.verify_backed_candidate(&allowed_relay_parents, candidate_idx, backed_candidate)
-						.is_err()
-				},
-				&scheduled,
-			);
+		let SanitizedBackedCandidates {
+			backed_candidates_with_core,
+			votes_from_disabled_were_dropped,
+		} = sanitize_backed_candidates::<T>(
+			backed_candidates,
+			&allowed_relay_parents,
+			&current_concluded_invalid_disputes,
+			scheduled,
+			core_index_enabled,
+		);
 
-		METRICS.on_candidates_sanitized(backed_candidates.len() as u64);
+		METRICS.on_candidates_sanitized(
+			backed_candidates_with_core
+				.values()
+				.map(|candidates| candidates.len() as u64)
+				.sum(),
+		);
 
 		// In `Enter` context (invoked during execution) there should be no backing votes from
 		// disabled validators because they should have been filtered out during inherent data
@@ -632,8 +632,7 @@ impl<T: Config> Pallet<T> {
 			candidate_receipt_with_backing_validator_indices,
 		} = <inclusion::Pallet<T>>::process_candidates(
 			&allowed_relay_parents,
-			backed_candidates.clone(),
-			&scheduled,
+			&backed_candidates_with_core,
 			<scheduler::Pallet<T>>::group_validators,
 		)?;
 		// Note which of the scheduled cores were actually occupied by a backed candidate.
@@ -651,8 +650,15 @@ impl<T: Config> Pallet<T> {
 
 		let bitfields = bitfields.into_iter().map(|v| v.into_unchecked()).collect();
 
-		let processed =
-			ParachainsInherentData { bitfields, backed_candidates, disputes, parent_header };
+		let processed = ParachainsInherentData {
+			bitfields,
+			backed_candidates: backed_candidates_with_core
+				.into_values()
+				.flat_map(|candidates| candidates.into_iter().map(|(candidate, _)| candidate))
+				.collect(),
+			disputes,
+			parent_header,
+		};
 		Ok((processed, Some(all_weight_after).into()))
 	}
 }
@@ -916,76 +922,195 @@ pub(crate) fn sanitize_bitfields<T: crate::inclusion::Config>(
 // Result from `sanitize_backed_candidates`
 #[derive(Debug, PartialEq)]
 struct SanitizedBackedCandidates<Hash> {
-	// Sanitized backed candidates. The `Vec` is sorted according to the occupied core index.
-	backed_candidates: Vec<BackedCandidate<Hash>>,
+	// Sanitized backed candidates along with the assigned core, grouped by para. The candidates
+	// of each para are ordered such that each of them builds on top of the previous one.
+	backed_candidates_with_core: BTreeMap<ParaId, Vec<(BackedCandidate<Hash>, CoreIndex)>>,
 	// Set to true if any votes from disabled validators were dropped from the input.
 	votes_from_disabled_were_dropped: bool,
 }
 
 /// Filter out:
 /// 1. any candidates that have a concluded invalid dispute
-/// 2. all backing votes from disabled validators
-/// 3. any candidates that end up with less than `effective_minimum_backing_votes` backing votes
+/// 2. any candidates of paras which are not scheduled on a free core
+/// 3. any candidates which don't form a chain with the other candidates of the para, starting from
+///    the latest head of the para, or which fail the other backing checks
+/// 4. any candidates that can't be mapped to a scheduled core
+/// 5. all backing votes from disabled validators
+/// 6. any candidates that end up with less than `effective_minimum_backing_votes` backing votes
+///
+/// Whenever a candidate is dropped, all the candidates of the same para building on top of it are
+/// dropped as well.
 ///
 /// `scheduled` follows the same naming scheme as provided in the
 /// guide: Currently `free` but might become `occupied`.
 /// For the filtering here the relevant part is only the current `free`
 /// state.
 ///
-/// `candidate_has_concluded_invalid_dispute` must return `true` if the candidate
-/// is disputed, false otherwise. The passed `usize` is the candidate index.
+/// `concluded_invalid_disputes` contains the candidate hashes of all candidates that have a
+/// concluded invalid dispute.
 ///
-/// Returns struct `SanitizedBackedCandidates` where `backed_candidates` are sorted according to the
-/// occupied core index.
-fn sanitize_backed_candidates<
-	T: crate::inclusion::Config,
-	F: FnMut(usize, &BackedCandidate<T::Hash>) -> bool,
->(
+/// Returns struct `SanitizedBackedCandidates` where `backed_candidates_with_core` are grouped by
+/// para and sorted in the order in which they build on top of each other.
+fn sanitize_backed_candidates<T: crate::inclusion::Config>(
 	mut backed_candidates: Vec<BackedCandidate<T::Hash>>,
 	allowed_relay_parents: &AllowedRelayParentsTracker<T::Hash, BlockNumberFor<T>>,
-	mut candidate_has_concluded_invalid_dispute_or_is_invalid: F,
-	scheduled: &BTreeMap<ParaId, CoreIndex>,
+	concluded_invalid_disputes: &BTreeSet<CandidateHash>,
+	mut scheduled: BTreeMap<ParaId, BTreeSet<CoreIndex>>,
+	core_index_enabled: bool,
 ) -> SanitizedBackedCandidates<T::Hash> {
 	// Remove any candidates that were concluded invalid.
 	// This does not assume sorting.
-	backed_candidates.indexed_retain(move |candidate_idx, backed_candidate| {
-		!candidate_has_concluded_invalid_dispute_or_is_invalid(candidate_idx, backed_candidate)
+	backed_candidates.retain(|backed_candidate| {
+		// never include a concluded-invalid candidate
+		!concluded_invalid_disputes.contains(&backed_candidate.hash())
 	});
 
-	// Assure the backed candidate's `ParaId`'s core is free.
+	// Assure the backed candidate's `ParaId` is scheduled on a free core.
 	// This holds under the assumption that `Scheduler::schedule` is called _before_.
-	// We don't check the relay-parent because this is done in the closure when
-	// constructing the inherent and during actual processing otherwise.
+	// The relative order of the candidates of each para is retained.
+	let mut candidates_per_para: BTreeMap<ParaId, Vec<BackedCandidate<T::Hash>>> = BTreeMap::new();
+	for backed_candidate in backed_candidates {
+		let para_id = backed_candidate.descriptor().para_id;
+		if scheduled.contains_key(&para_id) {
+			candidates_per_para.entry(para_id).or_default().push(backed_candidate);
+		}
+	}
 
-	backed_candidates.retain(|backed_candidate| {
-		let desc = backed_candidate.descriptor();
+	// Check that the candidates of each para form a chain. This is the only place where we check
+	// the relay-parent.
+	filter_unchained_candidates::<T>(&mut candidates_per_para, allowed_relay_parents);
 
-		scheduled.get(&desc.para_id).is_some()
-	});
+	// Map candidates to scheduled cores.
+	let mut backed_candidates_with_core =
+		map_candidates_to_cores::<T>(&mut scheduled, core_index_enabled, candidates_per_para);
 
 	// Filter out backing statements from disabled validators
 	let dropped_disabled = filter_backed_statements_from_disabled_validators::<T>(
-		&mut backed_candidates,
+		&mut backed_candidates_with_core,
 		&allowed_relay_parents,
-		scheduled,
 	);
 
-	// Sort the `Vec` last, once there is a guarantee that these
-	// `BackedCandidates` references the expected relay chain parent,
-	// but more importantly are scheduled for a free core.
-	// This both avoids extra work for obviously invalid candidates,
-	// but also allows this to be done in place.
-	backed_candidates.sort_by(|x, y| {
-		// Never panics, since we filtered all panic arguments out in the previous `fn retain`.
-		scheduled[&x.descriptor().para_id].cmp(&scheduled[&y.descriptor().para_id])
-	});
-
 	SanitizedBackedCandidates {
-		backed_candidates,
+		backed_candidates_with_core,
 		votes_from_disabled_were_dropped: dropped_disabled,
 	}
 }
 
+/// Drop all candidates which don't form a chain starting from the latest head data of their para,
+/// which is either the head of the last candidate pending availability or the included head.
+///
+/// The candidates of a para are expected to be ordered such that each one builds on top of the
+/// previous one. Any candidate failing the checks of `verify_backed_candidate`, including the
+/// check of its persisted validation data against its parent, is dropped along with all the
+/// following candidates of the same para.
+fn filter_unchained_candidates<T: inclusion::Config>(
+	candidates: &mut BTreeMap<ParaId, Vec<BackedCandidate<T::Hash>>>,
+	allowed_relay_parents: &AllowedRelayParentsTracker<T::Hash, BlockNumberFor<T>>,
+) {
+	candidates.retain(|para_id, para_candidates| {
+		let (mut latest_head_data, mut latest_context) =
+			match <inclusion::Pallet<T>>::para_latest_head_data_and_context(para_id) {
+				Some(latest) => latest,
+				None => {
+					log::warn!(
+						target: LOG_TARGET,
+						"Latest included head data for paraid {:?} is None",
+						para_id
+					);
+					return false
+				},
+			};
+
+		let mut chain_len = 0;
+		for (candidate_idx, candidate) in para_candidates.iter().enumerate() {
+			let check_ctx = CandidateCheckContext::<T>::new(latest_context);
+			match check_ctx.verify_backed_candidate(
+				allowed_relay_parents,
+				candidate_idx,
+				candidate,
+				latest_head_data.clone(),
+			) {
+				Ok(relay_parent_number) => {
+					latest_head_data = candidate.candidate.commitments.head_data.clone();
+					latest_context = Some(relay_parent_number);
+					chain_len += 1;
+				},
+				Err(err) => {
+					log::debug!(
+						target: LOG_TARGET,
+						"Dropping candidate {:?} of paraid {:?} and its descendants: {:?}",
+						candidate.hash(),
+						para_id,
+						err,
+					);
+					break
+				},
+			}
+		}
+
+		para_candidates.truncate(chain_len);
+		!para_candidates.is_empty()
+	});
+}
+
+/// Map the candidates of each para to the cores it is scheduled on.
+///
+/// Each candidate is assigned to the core in its descriptor, which must be one of the scheduled
+/// cores of the para that isn't taken by a previous candidate of the same para. Unless the elastic
+/// scaling MVP node feature is enabled, at most one candidate per para is kept. Candidates which
+/// can't be assigned a core are dropped along with all the candidates building on top of them.
+fn map_candidates_to_cores<T: configuration::Config + scheduler::Config + inclusion::Config>(
+	scheduled: &mut BTreeMap<ParaId, BTreeSet<CoreIndex>>,
+	core_index_enabled: bool,
+	candidates: BTreeMap<ParaId, Vec<BackedCandidate<T::Hash>>>,
+) -> BTreeMap<ParaId, Vec<(BackedCandidate<T::Hash>, CoreIndex)>> {
+	let mut backed_candidates_with_core = BTreeMap::new();
+
+	for (para_id, mut backed_candidates) in candidates.into_iter() {
+		let scheduled_cores = match scheduled.get_mut(&para_id) {
+			Some(scheduled_cores) => scheduled_cores,
+			None => continue,
+		};
+
+		if !core_index_enabled && backed_candidates.len() > 1 {
+			log::debug!(
+				target: LOG_TARGET,
+				"Paraid {:?} has {} backed candidates, but the elastic scaling MVP feature is \
+				disabled. Keeping only the first one.",
+				para_id,
+				backed_candidates.len(),
+			);
+			backed_candidates.truncate(1);
+		}
+
+		let mut temp_backed_candidates = Vec::with_capacity(backed_candidates.len());
+
+		// Preserve the order of the candidates, which is the order in which they build on top of
+		// each other.
+		for candidate in backed_candidates {
+			let core_index = candidate.descriptor().core_index;
+			if !scheduled_cores.remove(&core_index) {
+				log::debug!(
+					target: LOG_TARGET,
+					"Candidate {:?} of paraid {:?} is for core {:?}, which is not scheduled for \
+					the para. Dropping it and its descendants.",
+					candidate.hash(),
+					para_id,
+					core_index,
+				);
+				break
+			}
+			temp_backed_candidates.push((candidate, core_index));
+		}
+
+		if !temp_backed_candidates.is_empty() {
+			backed_candidates_with_core.insert(para_id, temp_backed_candidates);
+		}
+	}
+
+	backed_candidates_with_core
+}
+
 /// Derive entropy from babe provided per block randomness.
 ///
 /// In the odd case none is available, uses the `parent_hash` and
@@ -1067,13 +1192,16 @@ fn limit_and_sanitize_disputes<
 	}
 }
 
-// Filters statements from disabled validators in `BackedCandidate`, non-scheduled candidates and
-// few more sanity checks. Returns `true` if at least one statement is removed and `false`
-// otherwise.
+// Filters statements from disabled validators in `BackedCandidate` and does a few more sanity
+// checks. Candidates that end up with insufficient backing are dropped along with all the
+// candidates of the same para building on top of them. Returns `true` if at least one statement
+// is removed and `false` otherwise.
 fn filter_backed_statements_from_disabled_validators<T: shared::Config + scheduler::Config>(
-	backed_candidates: &mut Vec<BackedCandidate<<T as frame_system::Config>::Hash>>,
+	backed_candidates_with_core: &mut BTreeMap<
+		ParaId,
+		Vec<(BackedCandidate<<T as frame_system::Config>::Hash>, CoreIndex)>,
+	>,
 	allowed_relay_parents: &AllowedRelayParentsTracker<T::Hash, BlockNumberFor<T>>,
-	scheduled: &BTreeMap<ParaId, CoreIndex>,
 ) -> bool {
 	let disabled_validators =
 		BTreeSet::<_>::from_iter(shared::Pallet::<T>::disabled_validators().into_iter());
@@ -1083,7 +1211,7 @@ fn filter_backed_statements_from_disabled_validators<T: shared::Config + schedul
 		return false
 	}
 
-	let backed_len_before = backed_candidates.len();
+	let backed_len_before: usize = backed_candidates_with_core.values().map(|c| c.len()).sum();
 
 	// Flag which will be returned. Set to `true` if at least one vote is filtered.
 	let mut filtered = false;
@@ -1094,77 +1222,77 @@ fn filter_backed_statements_from_disabled_validators<T: shared::Config + schedul
 	// the validator group assigned to the parachain. To obtain this group we need:
 	// 1. Core index assigned to the parachain which has produced the candidate
 	// 2. The relay chain block number of the candidate
-	backed_candidates.retain_mut(|bc| {
-		// Get `core_idx` assigned to the `para_id` of the candidate
-		let core_idx = match scheduled.get(&bc.descriptor().para_id) {
-			Some(core_idx) => *core_idx,
-			None => {
-				log::debug!(target: LOG_TARGET, "Can't get core idx of a backed candidate for para id {:?}. Dropping the candidate.", bc.descriptor().para_id);
-				return false
-			}
-		};
+	backed_candidates_with_core.retain(|_, candidates| {
+		let mut valid_len = 0;
+		for (bc, core_idx) in candidates.iter_mut() {
+			// Get relay parent block number of the candidate. We need this to get the group index assigned to this core at this block number
+			let relay_parent_block_number = match allowed_relay_parents
+				.acquire_info(bc.descriptor().relay_parent, None) {
+					Some((_, block_num)) => block_num,
+					None => {
+						log::debug!(target: LOG_TARGET, "Relay parent {:?} for candidate is not in the allowed relay parents. Dropping the candidate.", bc.descriptor().relay_parent);
+						break
+					}
+				};
+
+			// Get the group index for the core
+			let group_idx = match <scheduler::Pallet<T>>::group_assigned_to_core(
+				*core_idx,
+				relay_parent_block_number + One::one(),
+			) {
+				Some(group_idx) => group_idx,
+				None => {
+					log::debug!(target: LOG_TARGET, "Can't get the group index for core idx {:?}. Dropping the candidate.", core_idx);
+					break
+				},
+			};
 
-		// Get relay parent block number of the candidate. We need this to get the group index assigned to this core at this block number
-		let relay_parent_block_number = match allowed_relay_parents
-			.acquire_info(bc.descriptor().relay_parent, None) {
-				Some((_, block_num)) => block_num,
+			// And finally get the validator group for this group index
+			let validator_group = match <scheduler::Pallet<T>>::group_validators(group_idx) {
+				Some(validator_group) => validator_group,
 				None => {
-					log::debug!(target: LOG_TARGET, "Relay parent {:?} for candidate is not in the allowed relay parents. Dropping the candidate.", bc.descriptor().relay_parent);
-					return false
+					log::debug!(target: LOG_TARGET, "Can't get the validators from group {:?}. Dropping the candidate.", group_idx);
+					break
 				}
 			};
 
-		// Get the group index for the core
-		let group_idx = match <scheduler::Pallet<T>>::group_assigned_to_core(
-			core_idx,
-			relay_parent_block_number + One::one(),
-		) {
-			Some(group_idx) => group_idx,
-			None => {
-				log::debug!(target: LOG_TARGET, "Can't get the group index for core idx {:?}. Dropping the candidate.", core_idx);
-				return false
-			},
-		};
-
-		// And finally get the validator group for this group index
-		let validator_group = match <scheduler::Pallet<T>>::group_validators(group_idx) {
-			Some(validator_group) => validator_group,
-			None => {
-				log::debug!(target: LOG_TARGET, "Can't get the validators from group {:?}. Dropping the candidate.", group_idx);
-				return false
+			// Bitmask with the disabled indices within the validator group
+			let disabled_indices = BitVec::<u8, bitvec::order::Lsb0>::from_iter(validator_group.iter().map(|idx| disabled_validators.contains(idx)));
+			// The indices of statements from disabled validators in `BackedCandidate`. We have to drop these.
+			let indices_to_drop = disabled_indices.clone() & &bc.validator_indices;
+			// Apply the bitmask to drop the disabled validator from `validator_indices`
+			bc.validator_indices &= !disabled_indices;
+			// Remove the corresponding votes from `validity_votes`
+			for idx in indices_to_drop.iter_ones().rev() {
+				bc.validity_votes.remove(idx);
 			}
-		};
 
-		// Bitmask with the disabled indices within the validator group
-		let disabled_indices = BitVec::<u8, bitvec::order::Lsb0>::from_iter(validator_group.iter().map(|idx| disabled_validators.contains(idx)));
-		// The indices of statements from disabled validators in `BackedCandidate`. We have to drop these.
-		let indices_to_drop = disabled_indices.clone() & &bc.validator_indices;
-		// Apply the bitmask to drop the disabled validator from `validator_indices`
-		bc.validator_indices &= !disabled_indices;
-		// Remove the corresponding votes from `validity_votes`
-		for idx in indices_to_drop.iter_ones().rev() {
-			bc.validity_votes.remove(idx);
-		}
+			// If at least one statement was dropped we need to return `true`
+			if indices_to_drop.count_ones() > 0 {
+				filtered = true;
+			}
 
-		// If at least one statement was dropped we need to return `true`
-		if indices_to_drop.count_ones() > 0 {
-			filtered = true;
-		}
+			// By filtering votes we might render the candidate invalid and cause a failure in
+			// [`process_candidates`]. To avoid this we have to perform a sanity check here. If there
+			// are not enough backing votes after filtering we will remove the whole candidate,
+			// along with the candidates building on top of it.
+			if bc.validity_votes.len() < effective_minimum_backing_votes(
+				validator_group.len(),
+				minimum_backing_votes
 
-		// By filtering votes we might render the candidate invalid and cause a failure in
-		// [`process_candidates`]. To avoid this we have to perform a sanity check here. If there
-		// are not enough backing votes after filtering we will remove the whole candidate.
-		if bc.validity_votes.len() < effective_minimum_backing_votes(
-			validator_group.len(),
-			minimum_backing_votes
+			) {
+				break
+			}
 
-		) {
-			return false
+			valid_len += 1;
 		}
 
-		true
+		candidates.truncate(valid_len);
+		!candidates.is_empty()
 	});
 
+	let backed_len_after: usize = backed_candidates_with_core.values().map(|c| c.len()).sum();
+
 	// Also return `true` if a whole candidate was dropped from the set
-	filtered || backed_len_before != backed_candidates.len()
+	filtered || backed_len_before != backed_len_after
 }
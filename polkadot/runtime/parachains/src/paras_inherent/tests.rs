@@ -1228,24 +1228,40 @@ mod sanitizers {
 
 	mod candidates {
 		use crate::{
+			inclusion::tests::genesis_config,
 			mock::set_disabled_validators,
+			paras::ParaKind,
 			scheduler::{common::Assignment, ParasEntry},
+			util::make_persisted_validation_data_with_parent,
 		};
+		use primitives::HeadData;
 		use sp_std::collections::vec_deque::VecDeque;
 
 		use super::*;
 
+		const RELAY_PARENT_NUM: u32 = 3;
+
 		// Backed candidates and scheduled parachains used for `sanitize_backed_candidates` testing
 		struct TestData {
 			backed_candidates: Vec<BackedCandidate>,
-			scheduled_paras: BTreeMap<primitives::Id, CoreIndex>,
+			expected_backed_candidates_with_core:
+				BTreeMap<ParaId, Vec<(BackedCandidate, CoreIndex)>>,
+			scheduled_paras: BTreeMap<primitives::Id, BTreeSet<CoreIndex>>,
 		}
 
-		// Generate test data for the candidates and assert that the evnironment is set as expected
-		// (check the comments for details)
-		fn get_test_data() -> TestData {
-			const RELAY_PARENT_NUM: u32 = 3;
+		// Genesis config with the parachains used by the test data registered.
+		fn test_genesis_config() -> MockGenesisConfig {
+			genesis_config(vec![
+				(ParaId::from(1), ParaKind::Parachain),
+				(ParaId::from(2), ParaKind::Parachain),
+			])
+		}
 
+		// Set up the validators, the allowed relay parent and the validator groups used by the
+		// test data. Returns the validators and the keystore holding their keys.
+		fn set_up_validators(
+			validator_groups: Vec<Vec<ValidatorIndex>>,
+		) -> (Vec<Sr25519Keyring>, KeystorePtr) {
 			// Add the relay parent to `shared` pallet. Otherwise some code (e.g. filtering backing
 			// votes) won't behave correctly
 			shared::Pallet::<Test>::add_allowed_relay_parent(
@@ -1255,13 +1271,8 @@ mod sanitizers {
 				1,
 			);
 
-			let header = default_header();
-			let relay_parent = header.hash();
-			let session_index = SessionIndex::from(0_u32);
-
 			let keystore = LocalKeystore::in_memory();
 			let keystore = Arc::new(keystore) as KeystorePtr;
-			let signing_context = SigningContext { parent_hash: relay_parent, session_index };
 
 			let validators = vec![
 				keyring::Sr25519Keyring::Alice,
@@ -1284,24 +1295,86 @@ mod sanitizers {
 				validators.iter().map(|v| v.public().into()).collect::<Vec<ValidatorId>>();
 			shared::Pallet::<Test>::set_active_validators_ascending(validator_ids);
 
-			// Two scheduled parachains - ParaId(1) on CoreIndex(0) and ParaId(2) on CoreIndex(1)
-			let scheduled = (0_usize..2)
-				.into_iter()
-				.map(|idx| (ParaId::from(1_u32 + idx as u32), CoreIndex::from(idx as u32)))
-				.collect::<BTreeMap<_, _>>();
-
 			// Set the validator groups in `scheduler`
-			scheduler::Pallet::<Test>::set_validator_groups(vec![
+			scheduler::Pallet::<Test>::set_validator_groups(validator_groups);
+
+			(validators, keystore)
+		}
+
+		// Build a candidate of `para_id` for `core_index` on top of `parent_head` and back it with
+		// all the validators of `group`.
+		fn make_backed_candidate(
+			para_id: ParaId,
+			parent_head: HeadData,
+			head_data: HeadData,
+			group: &[ValidatorIndex],
+			core_index: CoreIndex,
+			validators: &[Sr25519Keyring],
+			keystore: &KeystorePtr,
+		) -> BackedCandidate {
+			let relay_parent = default_header().hash();
+			let signing_context = SigningContext {
+				parent_hash: relay_parent,
+				session_index: SessionIndex::from(0_u32),
+			};
+
+			let persisted_validation_data_hash =
+				make_persisted_validation_data_with_parent::<Test>(
+					RELAY_PARENT_NUM,
+					Default::default(),
+					parent_head,
+				)
+				.hash();
+
+			let mut candidate = TestCandidateBuilder {
+				para_id,
+				relay_parent,
+				pov_hash: Hash::repeat_byte(head_data.0[0]),
+				persisted_validation_data_hash,
+				head_data,
+				hrmp_watermark: RELAY_PARENT_NUM,
+				core_index,
+				..Default::default()
+			}
+			.build();
+
+			collator_sign_candidate(Sr25519Keyring::One, &mut candidate);
+
+			back_candidate(
+				candidate,
+				validators,
+				group,
+				keystore,
+				&signing_context,
+				BackingKind::Threshold,
+			)
+		}
+
+		// Generate test data for the candidates and assert that the evnironment is set as expected
+		// (check the comments for details)
+		fn get_test_data() -> TestData {
+			let (validators, keystore) = set_up_validators(vec![
 				vec![ValidatorIndex(0), ValidatorIndex(1)],
 				vec![ValidatorIndex(2), ValidatorIndex(3)],
 			]);
 
+			// Two scheduled parachains - ParaId(1) on CoreIndex(0) and ParaId(2) on CoreIndex(1)
+			let scheduled = (0_usize..2)
+				.into_iter()
+				.map(|idx| {
+					(
+						ParaId::from(1_u32 + idx as u32),
+						[CoreIndex::from(idx as u32)].into_iter().collect(),
+					)
+				})
+				.collect::<BTreeMap<_, _>>();
+
 			// Update scheduler's claimqueue with the parachains
 			scheduler::Pallet::<Test>::set_claimqueue(BTreeMap::from([
 				(
 					CoreIndex::from(0),
 					VecDeque::from([ParasEntry::new(
-						Assignment::Pool { para_id: 1.into(), core_index: CoreIndex(1) },
+						Assignment::Pool { para_id: 1.into(), core_index: CoreIndex(0) },
 						RELAY_PARENT_NUM,
 					)]),
 				),
@@ -1314,45 +1387,36 @@ mod sanitizers {
 				),
 			]));
 
-			// Callback used for backing candidates
-			let group_validators = |group_index: GroupIndex| {
-				match group_index {
-					group_index if group_index == GroupIndex::from(0) => Some(vec![0, 1]),
-					group_index if group_index == GroupIndex::from(1) => Some(vec![2, 3]),
-					_ => panic!("Group index out of bounds for 2 parachains and 1 parathread core"),
-				}
-				.map(|m| m.into_iter().map(ValidatorIndex).collect::<Vec<_>>())
-			};
-
-			// Two backed candidates from each parachain
-			let backed_candidates = (0_usize..2)
+			// One backed candidate from each parachain, built on top of the genesis head
+			let backed_candidates = (0_u32..2)
 				.into_iter()
 				.map(|idx0| {
 					let idx1 = idx0 + 1;
-					let mut candidate = TestCandidateBuilder {
-						para_id: ParaId::from(idx1),
-						relay_parent,
-						pov_hash: Hash::repeat_byte(idx1 as u8),
-						persisted_validation_data_hash: [42u8; 32].into(),
-						hrmp_watermark: RELAY_PARENT_NUM,
-						..Default::default()
-					}
-					.build();
-
-					collator_sign_candidate(Sr25519Keyring::One, &mut candidate);
-
-					let backed = back_candidate(
-						candidate,
+					let group =
+						scheduler::Pallet::<Test>::group_validators(GroupIndex(idx0)).unwrap();
+					make_backed_candidate(
+						ParaId::from(idx1),
+						HeadData(vec![]),
+						HeadData(vec![idx1 as u8]),
+						&group,
+						CoreIndex(idx0),
 						&validators,
-						group_validators(GroupIndex::from(idx0 as u32)).unwrap().as_ref(),
 						&keystore,
-						&signing_context,
-						BackingKind::Threshold,
-					);
-					backed
+					)
 				})
 				.collect::<Vec<_>>();
 
+			let expected_backed_candidates_with_core = backed_candidates
+				.iter()
+				.enumerate()
+				.map(|(idx, candidate)| {
+					(
+						candidate.descriptor().para_id,
+						vec![(candidate.clone(), CoreIndex(idx as u32))],
+					)
+				})
+				.collect();
+
 			// State sanity checks
 			assert_eq!(
 				<scheduler::Pallet<Test>>::scheduled_paras().collect::<Vec<_>>(),
@@ -1369,51 +1433,227 @@ mod sanitizers {
 				]
 			);
 
-			TestData { backed_candidates, scheduled_paras: scheduled }
+			TestData {
+				backed_candidates,
+				expected_backed_candidates_with_core,
+				scheduled_paras: scheduled,
+			}
+		}
+
+		// Generate test data with ParaId(1) scheduled on CoreIndex(0) and CoreIndex(1) and
+		// ParaId(2) scheduled on CoreIndex(2). ParaId(1) gets two candidates, the second one
+		// building on top of the first one. The expected output assumes that the elastic scaling
+		// MVP is enabled.
+		fn get_test_data_multiple_cores_per_para() -> TestData {
+			let (validators, keystore) = set_up_validators(vec![
+				vec![ValidatorIndex(0), ValidatorIndex(1)],
+				vec![ValidatorIndex(2), ValidatorIndex(3)],
+				vec![ValidatorIndex(4)],
+			]);
+
+			let scheduled = BTreeMap::from([
+				(ParaId::from(1), [CoreIndex(0), CoreIndex(1)].into_iter().collect()),
+				(ParaId::from(2), [CoreIndex(2)].into_iter().collect()),
+			]);
+
+			let backed_candidate = |para_id: u32, parent_head: u8, head: u8, core: u32| {
+				let group = scheduler::Pallet::<Test>::group_validators(GroupIndex(core)).unwrap();
+				let parent_head =
+					if parent_head == 0 { HeadData(vec![]) } else { HeadData(vec![parent_head]) };
+				make_backed_candidate(
+					ParaId::from(para_id),
+					parent_head,
+					HeadData(vec![head]),
+					&group,
+					CoreIndex(core),
+					&validators,
+					&keystore,
+				)
+			};
+
+			let backed_candidates = vec![
+				backed_candidate(1, 0, 1, 0),
+				backed_candidate(1, 1, 2, 1),
+				backed_candidate(2, 0, 3, 2),
+			];
+
+			let expected_backed_candidates_with_core = BTreeMap::from([
+				(
+					ParaId::from(1),
+					vec![
+						(backed_candidates[0].clone(), CoreIndex(0)),
+						(backed_candidates[1].clone(), CoreIndex(1)),
+					],
+				),
+				(ParaId::from(2), vec![(backed_candidates[2].clone(), CoreIndex(2))]),
+			]);
+
+			TestData {
+				backed_candidates,
+				expected_backed_candidates_with_core,
+				scheduled_paras: scheduled,
+			}
 		}
 
 		#[test]
 		fn happy_path() {
-			new_test_ext(MockGenesisConfig::default()).execute_with(|| {
-				let TestData { backed_candidates, scheduled_paras: scheduled } = get_test_data();
+			for core_index_enabled in [false, true] {
+				new_test_ext(test_genesis_config()).execute_with(|| {
+					let TestData {
+						backed_candidates,
+						expected_backed_candidates_with_core,
+						scheduled_paras: scheduled,
+					} = get_test_data();
+
+					assert_eq!(
+						sanitize_backed_candidates::<Test>(
+							backed_candidates.clone(),
+							&<shared::Pallet<Test>>::allowed_relay_parents(),
+							&BTreeSet::new(),
+							scheduled,
+							core_index_enabled,
+						),
+						SanitizedBackedCandidates {
+							backed_candidates_with_core: expected_backed_candidates_with_core,
+							votes_from_disabled_were_dropped: false
+						}
+					);
+				});
+			}
+		}
 
-				let has_concluded_invalid =
-					|_idx: usize, _backed_candidate: &BackedCandidate| -> bool { false };
+		#[test]
+		fn happy_path_multiple_cores_per_para() {
+			new_test_ext(test_genesis_config()).execute_with(|| {
+				let TestData {
+					backed_candidates,
+					expected_backed_candidates_with_core,
+					scheduled_paras: scheduled,
+				} = get_test_data_multiple_cores_per_para();
 
 				assert_eq!(
-					sanitize_backed_candidates::<Test, _>(
+					sanitize_backed_candidates::<Test>(
 						backed_candidates.clone(),
 						&<shared::Pallet<Test>>::allowed_relay_parents(),
-						has_concluded_invalid,
-						&scheduled
+						&BTreeSet::new(),
+						scheduled,
+						true,
 					),
 					SanitizedBackedCandidates {
-						backed_candidates,
+						backed_candidates_with_core: expected_backed_candidates_with_core,
 						votes_from_disabled_were_dropped: false
 					}
 				);
+			});
+		}
 
-				{}
+		// without the elastic scaling MVP, only the first candidate of a para scheduled on multiple
+		// cores is kept
+		#[test]
+		fn multiple_cores_per_para_without_elastic_scaling() {
+			new_test_ext(test_genesis_config()).execute_with(|| {
+				let TestData {
+					backed_candidates,
+					mut expected_backed_candidates_with_core,
+					scheduled_paras: scheduled,
+				} = get_test_data_multiple_cores_per_para();
+
+				expected_backed_candidates_with_core.get_mut(&ParaId::from(1)).unwrap().pop();
+
+				assert_eq!(
+					sanitize_backed_candidates::<Test>(
+						backed_candidates.clone(),
+						&<shared::Pallet<Test>>::allowed_relay_parents(),
+						&BTreeSet::new(),
+						scheduled,
+						false,
+					),
+					SanitizedBackedCandidates {
+						backed_candidates_with_core: expected_backed_candidates_with_core,
+						votes_from_disabled_were_dropped: false
+					}
+				);
+			});
+		}
+
+		// a candidate with a core index that is not scheduled for its para is dropped, along with
+		// the candidates building on top of it
+		#[test]
+		fn wrong_core_index_is_dropped() {
+			new_test_ext(test_genesis_config()).execute_with(|| {
+				let TestData {
+					mut backed_candidates,
+					mut expected_backed_candidates_with_core,
+					scheduled_paras: scheduled,
+				} = get_test_data_multiple_cores_per_para();
+
+				// Claim the core of ParaId(2) for the second candidate of ParaId(1).
+				backed_candidates[1].candidate.descriptor.core_index = CoreIndex(2);
+
+				expected_backed_candidates_with_core.get_mut(&ParaId::from(1)).unwrap().pop();
+
+				assert_eq!(
+					sanitize_backed_candidates::<Test>(
+						backed_candidates.clone(),
+						&<shared::Pallet<Test>>::allowed_relay_parents(),
+						&BTreeSet::new(),
+						scheduled,
+						true,
+					),
+					SanitizedBackedCandidates {
+						backed_candidates_with_core: expected_backed_candidates_with_core,
+						votes_from_disabled_were_dropped: false
+					}
+				);
+			});
+		}
+
+		// candidates which don't build on top of the latest head of the para are dropped
+		#[test]
+		fn unchained_candidates_are_dropped() {
+			new_test_ext(test_genesis_config()).execute_with(|| {
+				let TestData {
+					mut backed_candidates,
+					mut expected_backed_candidates_with_core,
+					scheduled_paras: scheduled,
+				} = get_test_data_multiple_cores_per_para();
+
+				// The second candidate of ParaId(1) comes first, so neither of them builds on top
+				// of the head of the para.
+				backed_candidates.swap(0, 1);
+				expected_backed_candidates_with_core.remove(&ParaId::from(1));
+
+				assert_eq!(
+					sanitize_backed_candidates::<Test>(
+						backed_candidates.clone(),
+						&<shared::Pallet<Test>>::allowed_relay_parents(),
+						&BTreeSet::new(),
+						scheduled,
+						true,
+					),
+					SanitizedBackedCandidates {
+						backed_candidates_with_core: expected_backed_candidates_with_core,
+						votes_from_disabled_were_dropped: false
+					}
+				);
 			});
 		}
 
 		// nothing is scheduled, so no paraids match, thus all backed candidates are skipped
 		#[test]
 		fn nothing_scheduled() {
-			new_test_ext(MockGenesisConfig::default()).execute_with(|| {
-				let TestData { backed_candidates, scheduled_paras: _ } = get_test_data();
-				let scheduled = &BTreeMap::new();
-				let has_concluded_invalid =
-					|_idx: usize, _backed_candidate: &BackedCandidate| -> bool { false };
+			new_test_ext(test_genesis_config()).execute_with(|| {
+				let TestData { backed_candidates, .. } = get_test_data();
 
 				let SanitizedBackedCandidates {
-					backed_candidates: sanitized_backed_candidates,
+					backed_candidates_with_core: sanitized_backed_candidates,
 					votes_from_disabled_were_dropped,
-				} = sanitize_backed_candidates::<Test, _>(
+				} = sanitize_backed_candidates::<Test>(
 					backed_candidates.clone(),
 					&<shared::Pallet<Test>>::allowed_relay_parents(),
-					has_concluded_invalid,
-					&scheduled,
+					&BTreeSet::new(),
+					BTreeMap::new(),
+					false,
 				);
 
 				assert!(sanitized_backed_candidates.is_empty());
@@ -1424,12 +1664,13 @@ mod sanitizers {
 		// candidates that have concluded as invalid are filtered out
 		#[test]
 		fn invalid_are_filtered_out() {
-			new_test_ext(MockGenesisConfig::default()).execute_with(|| {
-				let TestData { backed_candidates, scheduled_paras: scheduled } = get_test_data();
+			new_test_ext(test_genesis_config()).execute_with(|| {
+				let TestData { backed_candidates, scheduled_paras: scheduled, .. } =
+					get_test_data();
 
 				// mark every second one as concluded invalid
 				let set = {
-					let mut set = std::collections::HashSet::new();
+					let mut set = BTreeSet::new();
 					for (idx, backed_candidate) in backed_candidates.iter().enumerate() {
 						if idx & 0x01 == 0 {
 							set.insert(backed_candidate.hash());
@@ -1437,16 +1678,15 @@ mod sanitizers {
 					}
 					set
 				};
-				let has_concluded_invalid =
-					|_idx: usize, candidate: &BackedCandidate| set.contains(&candidate.hash());
 				let SanitizedBackedCandidates {
-					backed_candidates: sanitized_backed_candidates,
+					backed_candidates_with_core: sanitized_backed_candidates,
 					votes_from_disabled_were_dropped,
-				} = sanitize_backed_candidates::<Test, _>(
+				} = sanitize_backed_candidates::<Test>(
 					backed_candidates.clone(),
 					&<shared::Pallet<Test>>::allowed_relay_parents(),
-					has_concluded_invalid,
-					&scheduled,
+					&set,
+					scheduled,
+					false,
 				);
 
 				assert_eq!(sanitized_backed_candidates.len(), backed_candidates.len() / 2);
@@ -1454,31 +1694,60 @@ mod sanitizers {
 			});
 		}
 
+		// candidates building on top of a candidate that has concluded as invalid are filtered
+		// out as well
+		#[test]
+		fn descendants_of_invalid_are_filtered_out() {
+			new_test_ext(test_genesis_config()).execute_with(|| {
+				let TestData {
+					backed_candidates,
+					mut expected_backed_candidates_with_core,
+					scheduled_paras: scheduled,
+				} = get_test_data_multiple_cores_per_para();
+
+				let set = [backed_candidates[0].hash()].into_iter().collect::<BTreeSet<_>>();
+				expected_backed_candidates_with_core.remove(&ParaId::from(1));
+
+				assert_eq!(
+					sanitize_backed_candidates::<Test>(
+						backed_candidates.clone(),
+						&<shared::Pallet<Test>>::allowed_relay_parents(),
+						&set,
+						scheduled,
+						true,
+					),
+					SanitizedBackedCandidates {
+						backed_candidates_with_core: expected_backed_candidates_with_core,
+						votes_from_disabled_were_dropped: false
+					}
+				);
+			});
+		}
+
 		#[test]
 		fn disabled_non_signing_validator_doesnt_get_filtered() {
-			new_test_ext(MockGenesisConfig::default()).execute_with(|| {
-				let TestData { mut backed_candidates, scheduled_paras } = get_test_data();
+			new_test_ext(test_genesis_config()).execute_with(|| {
+				let TestData { mut expected_backed_candidates_with_core, .. } = get_test_data();
 
 				// Disable Eve
 				set_disabled_validators(vec![4]);
 
-				let before = backed_candidates.clone();
+				let before = expected_backed_candidates_with_core.clone();
 
 				// Eve is disabled but no backing statement is signed by it so nothing should be
 				// filtered
 				assert!(!filter_backed_statements_from_disabled_validators::<Test>(
-					&mut backed_candidates,
+					&mut expected_backed_candidates_with_core,
 					&<shared::Pallet<Test>>::allowed_relay_parents(),
-					&scheduled_paras
 				));
-				assert_eq!(backed_candidates, before);
+				assert_eq!(expected_backed_candidates_with_core, before);
 			});
 		}
 
 		#[test]
 		fn drop_statements_from_disabled_without_dropping_candidate() {
-			new_test_ext(MockGenesisConfig::default()).execute_with(|| {
-				let TestData { mut backed_candidates, scheduled_paras } = get_test_data();
+			new_test_ext(test_genesis_config()).execute_with(|| {
+				let TestData { mut expected_backed_candidates_with_core, .. } = get_test_data();
 
 				// Disable Alice
 				set_disabled_validators(vec![0]);
@@ -1490,62 +1759,90 @@ mod sanitizers {
 				hc.minimum_backing_votes = 1;
 				configuration::Pallet::<Test>::force_set_active_config(hc);
 
+				let first = |candidates: &BTreeMap<_, Vec<(BackedCandidate, _)>>| {
+					candidates.get(&ParaId::from(1)).unwrap()[0].0.clone()
+				};
+
 				// Verify the initial state is as expected
-				assert_eq!(backed_candidates.get(0).unwrap().validity_votes.len(), 2);
-				assert_eq!(
-					backed_candidates.get(0).unwrap().validator_indices.get(0).unwrap(),
-					true
-				);
-				assert_eq!(
-					backed_candidates.get(0).unwrap().validator_indices.get(1).unwrap(),
-					true
-				);
-				let untouched = backed_candidates.get(1).unwrap().clone();
+				let candidate = first(&expected_backed_candidates_with_core);
+				assert_eq!(candidate.validity_votes.len(), 2);
+				assert_eq!(candidate.validator_indices.get(0).unwrap(), true);
+				assert_eq!(candidate.validator_indices.get(1).unwrap(), true);
+				let untouched =
+					expected_backed_candidates_with_core.get(&ParaId::from(2)).unwrap().clone();
 
 				assert!(filter_backed_statements_from_disabled_validators::<Test>(
-					&mut backed_candidates,
+					&mut expected_backed_candidates_with_core,
 					&<shared::Pallet<Test>>::allowed_relay_parents(),
-					&scheduled_paras
 				));
 
 				// there should still be two backed candidates
-				assert_eq!(backed_candidates.len(), 2);
+				assert_eq!(expected_backed_candidates_with_core.len(), 2);
 				// but the first one should have only one validity vote
-				assert_eq!(backed_candidates.get(0).unwrap().validity_votes.len(), 1);
+				let candidate = first(&expected_backed_candidates_with_core);
+				assert_eq!(candidate.validity_votes.len(), 1);
 				// Validator 0 vote should be dropped, validator 1 - retained
+				assert_eq!(candidate.validator_indices.get(0).unwrap(), false);
+				assert_eq!(candidate.validator_indices.get(1).unwrap(), true);
+				// the second candidate shouldn't be modified
 				assert_eq!(
-					backed_candidates.get(0).unwrap().validator_indices.get(0).unwrap(),
-					false
-				);
-				assert_eq!(
-					backed_candidates.get(0).unwrap().validator_indices.get(1).unwrap(),
-					true
+					*expected_backed_candidates_with_core.get(&ParaId::from(2)).unwrap(),
+					untouched
 				);
-				// the second candidate shouldn't be modified
-				assert_eq!(*backed_candidates.get(1).unwrap(), untouched);
 			});
 		}
 
 		#[test]
 		fn drop_candidate_if_all_statements_are_from_disabled() {
-			new_test_ext(MockGenesisConfig::default()).execute_with(|| {
-				let TestData { mut backed_candidates, scheduled_paras } = get_test_data();
+			new_test_ext(test_genesis_config()).execute_with(|| {
+				let TestData { mut expected_backed_candidates_with_core, .. } = get_test_data();
 
 				// Disable Alice and Bob
 				set_disabled_validators(vec![0, 1]);
 
 				// Verify the initial state is as expected
-				assert_eq!(backed_candidates.get(0).unwrap().validity_votes.len(), 2);
-				let untouched = backed_candidates.get(1).unwrap().clone();
+				assert_eq!(
+					expected_backed_candidates_with_core.get(&ParaId::from(1)).unwrap()[0]
+						.0
+						.validity_votes
+						.len(),
+					2
+				);
+				let untouched =
+					expected_backed_candidates_with_core.get(&ParaId::from(2)).unwrap().clone();
+
+				assert!(filter_backed_statements_from_disabled_validators::<Test>(
+					&mut expected_backed_candidates_with_core,
+					&<shared::Pallet<Test>>::allowed_relay_parents(),
+				));
+
+				assert_eq!(expected_backed_candidates_with_core.len(), 1);
+				assert_eq!(
+					*expected_backed_candidates_with_core.get(&ParaId::from(2)).unwrap(),
+					untouched
+				);
+			});
+		}
+
+		// dropping a candidate because of disabled validators drops its descendants as well
+		#[test]
+		fn drop_descendants_of_candidate_with_statements_from_disabled() {
+			new_test_ext(test_genesis_config()).execute_with(|| {
+				let TestData { mut expected_backed_candidates_with_core, .. } =
+					get_test_data_multiple_cores_per_para();
+
+				// Disable Alice and Bob, who back the first candidate of ParaId(1)
+				set_disabled_validators(vec![0, 1]);
 
 				assert!(filter_backed_statements_from_disabled_validators::<Test>(
-					&mut backed_candidates,
+					&mut expected_backed_candidates_with_core,
 					&<shared::Pallet<Test>>::allowed_relay_parents(),
-					&scheduled_paras
 				));
 
-				assert_eq!(backed_candidates.len(), 1);
-				assert_eq!(*backed_candidates.get(0).unwrap(), untouched);
+				assert_eq!(
+					expected_backed_candidates_with_core.keys().collect::<Vec<_>>(),
+					vec![&ParaId::from(2)]
+				);
 			});
 		}
 	}
@@ -92,9 +92,11 @@ pub fn availability_cores<T: initializer::Config>() -> Vec<CoreState<T::Hash, Bl
 		.enumerate()
 		.map(|(i, core)| match core {
 			CoreOccupied::Paras(entry) => {
-				let pending_availability =
-					<inclusion::Pallet<T>>::pending_availability(entry.para_id())
-						.expect("Occupied core always has pending availability; qed");
+				let pending_availability = <inclusion::Pallet<T>>::pending_availability_with_core(
+					entry.para_id(),
+					CoreIndex(i as u32),
+				)
+				.expect("Occupied core always has pending availability; qed");
 
 				let backed_in_number = *pending_availability.backed_in_number();
 
@@ -158,7 +160,7 @@ where
 		},
 		OccupiedCoreAssumption::TimedOut => build(),
 		OccupiedCoreAssumption::Free => {
-			if <inclusion::Pallet<Config>>::pending_availability(para_id).is_some() {
+			if <inclusion::Pallet<Config>>::has_pending_availability(para_id) {
 				None
 			} else {
 				build()
@@ -202,10 +204,12 @@ pub fn assumed_validation_data<T: initializer::Config>(
 	let persisted_validation_data = make_validation_data().or_else(|| {
 		// Try again with force enacting the core. This check only makes sense if
 		// the core is occupied.
-		<inclusion::Pallet<T>>::pending_availability(para_id).and_then(|_| {
+		if <inclusion::Pallet<T>>::has_pending_availability(para_id) {
 			<inclusion::Pallet<T>>::force_enact(para_id);
 			make_validation_data()
-		})
+		} else {
+			None
+		}
 	});
 	// If we were successful, also query current validation code hash.
 	persisted_validation_data.zip(<paras::Pallet<T>>::current_code_hash(&para_id))
@@ -465,27 +469,23 @@ pub fn backing_state<T: initializer::Config>(
 	};
 
 	let pending_availability = {
-		// Note: the API deals with a `Vec` as it is future-proof for cases
-		// where there may be multiple candidates pending availability at a time.
-		// But at the moment only one candidate can be pending availability per
-		// parachain.
+		// Candidates of the same para pending availability are kept in the order in which they
+		// build on top of each other.
 		crate::inclusion::PendingAvailability::<T>::get(&para_id)
-			.and_then(|pending| {
-				let commitments =
-					crate::inclusion::PendingAvailabilityCommitments::<T>::get(&para_id);
-				commitments.map(move |c| (pending, c))
-			})
-			.map(|(pending, commitments)| {
-				CandidatePendingAvailability {
-					candidate_hash: pending.candidate_hash(),
-					descriptor: pending.candidate_descriptor().clone(),
-					commitments,
-					relay_parent_number: pending.relay_parent_number(),
-					max_pov_size: constraints.max_pov_size, // assume always same in session.
-				}
+			.map(|pending_candidates| {
+				pending_candidates
+					.into_iter()
+					.map(|candidate| CandidatePendingAvailability {
+						candidate_hash: candidate.candidate_hash(),
+						descriptor: candidate.candidate_descriptor().clone(),
+						commitments: candidate.candidate_commitments().clone(),
+						relay_parent_number: candidate.relay_parent_number(),
+						// assume always same in session.
+						max_pov_size: constraints.max_pov_size,
+					})
+					.collect()
 			})
-			.into_iter()
-			.collect()
+			.unwrap_or_else(|| vec![])
 	};
 
 	Some(BackingState { constraints, pending_availability })
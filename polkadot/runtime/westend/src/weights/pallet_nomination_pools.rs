@@ -640,4 +640,28 @@ impl<T: frame_system::Config> pallet_nomination_pools::WeightInfo for WeightInfo
 			.saturating_add(T::DbWeight::get().reads(4))
 			.saturating_add(T::DbWeight::get().writes(2))
 	}
+	fn apply_slash() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_745_000 picoseconds.
+		Weight::from_parts(1_860_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+	}
+	fn migrate_delegation() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_782_000 picoseconds.
+		Weight::from_parts(1_903_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+	}
+	fn migrate_pool_to_delegate_stake() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_721_000 picoseconds.
+		Weight::from_parts(1_849_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+	}
 }
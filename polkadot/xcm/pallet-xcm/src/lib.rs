@@ -50,8 +50,8 @@ use sp_runtime::{
 use sp_std::{boxed::Box, marker::PhantomData, prelude::*, result::Result, vec};
 use xcm::{latest::QueryResponseInfo, prelude::*};
 use xcm_builder::{
-	ExecuteController, ExecuteControllerWeightInfo, QueryController, QueryControllerWeightInfo,
-	SendController, SendControllerWeightInfo,
+	record_forwarded_xcms, ExecuteController, ExecuteControllerWeightInfo, QueryController,
+	QueryControllerWeightInfo, SendController, SendControllerWeightInfo,
 };
 use xcm_executor::{
 	traits::{
@@ -61,6 +61,10 @@ use xcm_executor::{
 	},
 	AssetsInHolding,
 };
+use xcm_fee_payment_runtime_api::{
+	dry_run::{CallDryRunEffects, Error as XcmDryRunApiError, XcmDryRunEffects},
+	fees::Error as XcmPaymentApiError,
+};
 
 #[cfg(any(feature = "try-runtime", test))]
 use sp_runtime::TryRuntimeError;
@@ -2292,6 +2296,140 @@ impl<T: Config> Pallet<T> {
 		AccountIdConversion::<T::AccountId>::into_account_truncating(&ID)
 	}
 
+	/// Dry-runs `call` dispatched from `origin`.
+	///
+	/// Returns the result of the dispatch together with the emitted events and the messages
+	/// forwarded through the [`xcm_builder::RecordingXcmRouter`]. The changes are not reverted,
+	/// so this must only be used by the runtime API implementations, which never commit them.
+	pub fn dry_run_call<OriginCaller: Into<<T as Config>::RuntimeOrigin>>(
+		origin: OriginCaller,
+		call: <T as Config>::RuntimeCall,
+	) -> Result<CallDryRunEffects<<T as frame_system::Config>::RuntimeEvent>, XcmDryRunApiError> {
+		frame_system::Pallet::<T>::reset_events();
+		let (execution_result, forwarded_xcms) =
+			record_forwarded_xcms(|| call.dispatch(origin.into()));
+		Ok(CallDryRunEffects {
+			execution_result,
+			emitted_events: Self::emitted_events(),
+			forwarded_xcms: Self::versioned_forwarded_xcms(forwarded_xcms),
+		})
+	}
+
+	/// Dry-runs the XCM program `xcm` as if it was received from `origin_location`.
+	///
+	/// The program is executed by the configured `XcmExecutor` without any weight limit. The same
+	/// caveats as for [`Self::dry_run_call`] apply.
+	pub fn dry_run_xcm(
+		origin_location: VersionedLocation,
+		xcm: VersionedXcm<<T as Config>::RuntimeCall>,
+	) -> Result<XcmDryRunEffects<<T as frame_system::Config>::RuntimeEvent>, XcmDryRunApiError> {
+		let origin_location: Location = origin_location.try_into().map_err(|()| {
+			log::error!(
+				target: "xcm::pallet_xcm::dry_run_xcm",
+				"Location version conversion failed."
+			);
+			XcmDryRunApiError::VersionedConversionFailed
+		})?;
+		let xcm: Xcm<<T as Config>::RuntimeCall> = xcm.try_into().map_err(|()| {
+			log::error!(target: "xcm::pallet_xcm::dry_run_xcm", "Xcm version conversion failed.");
+			XcmDryRunApiError::VersionedConversionFailed
+		})?;
+		let mut hash = xcm.using_encoded(sp_io::hashing::blake2_256);
+		frame_system::Pallet::<T>::reset_events();
+		let (execution_result, forwarded_xcms) = record_forwarded_xcms(|| {
+			T::XcmExecutor::prepare_and_execute(
+				origin_location,
+				xcm,
+				&mut hash,
+				Weight::MAX,
+				Weight::zero(),
+			)
+		});
+		Ok(XcmDryRunEffects {
+			execution_result,
+			emitted_events: Self::emitted_events(),
+			forwarded_xcms: Self::versioned_forwarded_xcms(forwarded_xcms),
+		})
+	}
+
+	/// The events deposited since the last reset, for the dry-run APIs.
+	fn emitted_events() -> Vec<<T as frame_system::Config>::RuntimeEvent> {
+		frame_system::Pallet::<T>::read_events_no_consensus()
+			.map(|record| record.event.clone())
+			.collect()
+	}
+
+	/// Converts the forwarded messages into their versioned form, for the dry-run APIs.
+	fn versioned_forwarded_xcms(
+		forwarded_xcms: Vec<(Location, Vec<Xcm<()>>)>,
+	) -> Vec<(VersionedLocation, Vec<VersionedXcm<()>>)> {
+		forwarded_xcms
+			.into_iter()
+			.map(|(destination, messages)| {
+				(destination.into(), messages.into_iter().map(VersionedXcm::from).collect())
+			})
+			.collect()
+	}
+
+	/// Converts the `asset_ids` accepted as the payment for the execution to the given
+	/// `version`, for the fee payment API.
+	pub fn query_acceptable_payment_assets(
+		version: xcm::Version,
+		asset_ids: Vec<AssetId>,
+	) -> Result<Vec<VersionedAssetId>, XcmPaymentApiError> {
+		asset_ids
+			.into_iter()
+			.map(|asset_id| {
+				VersionedAssetId::from(asset_id)
+					.into_version(version)
+					.map_err(|()| XcmPaymentApiError::UnhandledXcmVersion)
+			})
+			.collect()
+	}
+
+	/// Returns the weight of the execution of `message`, as estimated by the configured
+	/// `Weigher`.
+	pub fn query_xcm_weight(message: VersionedXcm<()>) -> Result<Weight, XcmPaymentApiError> {
+		let message: Xcm<()> = message.try_into().map_err(|()| {
+			log::error!(
+				target: "xcm::pallet_xcm::query_xcm_weight",
+				"Failed to convert versioned message."
+			);
+			XcmPaymentApiError::VersionedConversionFailed
+		})?;
+		T::Weigher::weight(&mut message.into()).map_err(|()| {
+			log::error!(
+				target: "xcm::pallet_xcm::query_xcm_weight",
+				"Error when querying XCM weight."
+			);
+			XcmPaymentApiError::WeightNotComputable
+		})
+	}
+
+	/// Returns the fees charged by the configured `XcmRouter` for delivering `message` to
+	/// `destination`, in the latest XCM version.
+	pub fn query_delivery_fees(
+		destination: VersionedLocation,
+		message: VersionedXcm<()>,
+	) -> Result<VersionedAssets, XcmPaymentApiError> {
+		let destination: Location = destination
+			.try_into()
+			.map_err(|()| XcmPaymentApiError::VersionedConversionFailed)?;
+		let message: Xcm<()> =
+			message.try_into().map_err(|()| XcmPaymentApiError::VersionedConversionFailed)?;
+		let (_, fees) =
+			validate_send::<T::XcmRouter>(destination.clone(), message).map_err(|error| {
+				log::error!(
+					target: "xcm::pallet_xcm::query_delivery_fees",
+					"Error when querying delivery fees to {:?}: {:?}",
+					destination,
+					error,
+				);
+				XcmPaymentApiError::Unroutable
+			})?;
+		Ok(fees.into())
+	}
+
 	/// Create a new expectation of a query response with the querier being here.
 	fn do_new_query(
 		responder: impl Into<Location>,
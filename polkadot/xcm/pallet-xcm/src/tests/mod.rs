@@ -36,6 +36,7 @@ use xcm_executor::{
 	traits::{Properties, QueryHandler, QueryResponseStatus, ShouldExecute},
 	XcmExecutor,
 };
+use xcm_fee_payment_runtime_api::fees::Error as XcmPaymentApiError;
 
 const ALICE: AccountId = AccountId::new([0u8; 32]);
 const BOB: AccountId = AccountId::new([1u8; 32]);
@@ -390,6 +391,114 @@ fn execute_withdraw_to_deposit_works() {
 	});
 }
 
+/// Test dry-running a call which sends an XCM.
+///
+/// Asserts that the sent message is forwarded to its destination along with the emitted events.
+#[test]
+fn dry_run_call_works() {
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let sender: Location = AccountId32 { network: None, id: ALICE.into() }.into();
+		let message = Xcm(vec![ClearOrigin]);
+		let call = RuntimeCall::XcmPallet(crate::Call::send {
+			dest: Box::new(RelayLocation::get().into()),
+			message: Box::new(VersionedXcm::from(message.clone())),
+		});
+
+		let effects = XcmPallet::dry_run_call(RuntimeOrigin::signed(ALICE), call).unwrap();
+		assert_ok!(effects.execution_result);
+		let sent_message =
+			Xcm(vec![DescendOrigin(sender.clone().try_into().unwrap()), ClearOrigin]);
+		assert_eq!(
+			effects.forwarded_xcms,
+			vec![(
+				VersionedLocation::from(RelayLocation::get()),
+				vec![VersionedXcm::from(sent_message.clone())]
+			)]
+		);
+		assert_eq!(
+			effects.emitted_events.last(),
+			Some(&RuntimeEvent::XcmPallet(crate::Event::Sent {
+				origin: sender,
+				destination: RelayLocation::get(),
+				message,
+				message_id: fake_message_hash(&sent_message),
+			}))
+		);
+	});
+}
+
+/// Test dry-running an XCM program which deposits assets to another chain.
+///
+/// Asserts that the execution outcome is returned along with the forwarded message.
+#[test]
+fn dry_run_xcm_works() {
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let origin: Location = AccountId32 { network: None, id: ALICE.into() }.into();
+		let dest: Location = Parachain(OTHER_PARA_ID).into();
+		let message = Xcm::<RuntimeCall>(vec![
+			WithdrawAsset((Here, SEND_AMOUNT).into()),
+			buy_execution((Here, SEND_AMOUNT)),
+			DepositReserveAsset {
+				assets: AllCounted(1).into(),
+				dest: dest.clone(),
+				xcm: Xcm(vec![]),
+			},
+		]);
+
+		let effects = XcmPallet::dry_run_xcm(origin.into(), VersionedXcm::from(message)).unwrap();
+		assert_eq!(effects.execution_result, Outcome::Complete { used: BaseXcmWeight::get() * 3 });
+		assert_eq!(effects.forwarded_xcms.len(), 1);
+		assert_eq!(effects.forwarded_xcms[0].0, VersionedLocation::from(dest));
+		assert_eq!(effects.forwarded_xcms[0].1.len(), 1);
+		assert!(!effects.emitted_events.is_empty());
+	});
+}
+
+#[test]
+fn query_xcm_weight_works() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let message = Xcm::<()>(vec![
+			WithdrawAsset((Here, SEND_AMOUNT).into()),
+			buy_execution((Here, SEND_AMOUNT)),
+			DepositAsset { assets: AllCounted(1).into(), beneficiary: Here.into() },
+		]);
+		assert_eq!(
+			XcmPallet::query_xcm_weight(VersionedXcm::from(message)),
+			Ok(BaseXcmWeight::get() * 3)
+		);
+	});
+}
+
+#[test]
+fn query_delivery_fees_works() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let message = VersionedXcm::from(Xcm::<()>(vec![ClearOrigin]));
+
+		// The paid router charges for the delivery to `Parachain(3000)`.
+		let destination = VersionedLocation::from(Para3000Location::get());
+		assert_eq!(
+			XcmPallet::query_delivery_fees(destination, message.clone()),
+			Ok(VersionedAssets::from(Para3000PaymentAssets::get()))
+		);
+
+		// Other destinations are free.
+		let destination = VersionedLocation::from(Location::from(Parachain(OTHER_PARA_ID)));
+		assert_eq!(
+			XcmPallet::query_delivery_fees(destination, message.clone()),
+			Ok(VersionedAssets::from(Assets::new()))
+		);
+
+		set_send_xcm_artificial_failure(true);
+		let destination = VersionedLocation::from(Location::from(Parachain(OTHER_PARA_ID)));
+		assert_eq!(
+			XcmPallet::query_delivery_fees(destination, message),
+			Err(XcmPaymentApiError::Unroutable)
+		);
+	});
+}
+
 /// Test drop/claim assets.
 #[test]
 fn trapped_assets_can_be_claimed() {
@@ -18,7 +18,7 @@
 
 use frame_system::unique;
 use parity_scale_codec::Encode;
-use sp_std::{marker::PhantomData, result::Result};
+use sp_std::{marker::PhantomData, result::Result, vec, vec::Vec};
 use xcm::prelude::*;
 
 /// Wrapper router which, if the message does not already end with a `SetTopic` instruction,
@@ -104,3 +104,58 @@ impl<Inner: SendXcm, TopicSource: SourceTopic> SendXcm for WithTopicSource<Inner
 		Ok(unique_id)
 	}
 }
+
+environmental::environmental!(recorded_xcms: Vec<(Location, Xcm<()>)>);
+
+/// Executes `f` and returns its result together with the messages delivered through any
+/// [`RecordingXcmRouter`] meanwhile, grouped by their destination in the order of delivery.
+pub fn record_forwarded_xcms<R>(f: impl FnOnce() -> R) -> (R, Vec<(Location, Vec<Xcm<()>>)>) {
+	let mut recorded = Vec::new();
+	let result = recorded_xcms::using(&mut recorded, f);
+
+	let mut forwarded: Vec<(Location, Vec<Xcm<()>>)> = Vec::new();
+	for (destination, message) in recorded {
+		match forwarded.iter_mut().find(|(location, _)| *location == destination) {
+			Some((_, messages)) => messages.push(message),
+			None => forwarded.push((destination, vec![message])),
+		}
+	}
+	(result, forwarded)
+}
+
+/// Wrapper router which records the messages delivered through the `Inner` router while being
+/// executed within [`record_forwarded_xcms`]. Outside of it, the messages are only passed through
+/// to the `Inner` router.
+///
+/// This is meant to capture the messages forwarded by an extrinsic or an XCM program which is
+/// being dry-run, e.g. by a runtime API, without inspecting the queues of every router. Place it
+/// inside of [`WithUniqueTopic`] so that the recorded messages carry the topic which is actually
+/// sent.
+pub struct RecordingXcmRouter<Inner>(PhantomData<Inner>);
+impl<Inner: SendXcm> SendXcm for RecordingXcmRouter<Inner> {
+	type Ticket = (Inner::Ticket, Option<(Location, Xcm<()>)>);
+
+	fn validate(
+		destination: &mut Option<Location>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		// Clone the message only while recording, `Inner` may take the arguments.
+		let is_recording = recorded_xcms::with(|_| ()).is_some();
+		let to_record = match (destination.as_ref(), message.as_ref()) {
+			(Some(destination), Some(message)) if is_recording =>
+				Some((destination.clone(), message.clone())),
+			_ => None,
+		};
+		let (ticket, assets) = Inner::validate(destination, message)?;
+		Ok(((ticket, to_record), assets))
+	}
+
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		let (ticket, to_record) = ticket;
+		let hash = Inner::deliver(ticket)?;
+		if let Some(delivered) = to_record {
+			recorded_xcms::with(|recorded| recorded.push(delivered));
+		}
+		Ok(hash)
+	}
+}
@@ -0,0 +1,54 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+
+type Router = RecordingXcmRouter<TestMessageSender>;
+
+#[test]
+fn recording_router_records_only_while_recording() {
+	let message = Xcm::<()>(vec![ClearOrigin]);
+
+	send_xcm::<Router>(Parent.into(), message.clone()).unwrap();
+	let ((), recorded) = record_forwarded_xcms(|| ());
+	assert!(recorded.is_empty());
+
+	let (result, recorded) = record_forwarded_xcms(|| {
+		send_xcm::<Router>(Parent.into(), message.clone()).unwrap();
+		send_xcm::<Router>(Parachain(1).into(), message.clone()).unwrap();
+		send_xcm::<Router>(Parent.into(), Xcm(vec![ClearTopic])).unwrap();
+		42
+	});
+	assert_eq!(result, 42);
+	assert_eq!(
+		recorded,
+		vec![
+			(Parent.into(), vec![message.clone(), Xcm(vec![ClearTopic])]),
+			(Parachain(1).into(), vec![message]),
+		]
+	);
+	// The messages are delivered through the inner router regardless of the recording.
+	assert_eq!(sent_xcm().len(), 4);
+}
+
+#[test]
+fn recording_router_does_not_record_failed_deliveries() {
+	let (result, recorded) = record_forwarded_xcms(|| {
+		send_xcm::<RecordingXcmRouter<()>>(Parent.into(), Xcm(vec![ClearOrigin]))
+	});
+	assert_eq!(result, Err(SendError::NotApplicable));
+	assert!(recorded.is_empty());
+}
@@ -0,0 +1,90 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for dry-running the extrinsics and the XCM programs.
+//!
+//! The runtime API calls are executed on top of the state of the given block and the changes are
+//! discarded afterwards, so the effects returned by the API are never committed.
+
+use codec::{Decode, Encode};
+use frame_support::pallet_prelude::DispatchResultWithPostInfo;
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+use xcm::prelude::*;
+
+/// Effects of dry-running an extrinsic.
+#[derive(Encode, Decode, Debug, TypeInfo)]
+pub struct CallDryRunEffects<Event> {
+	/// The result of executing the extrinsic.
+	pub execution_result: DispatchResultWithPostInfo,
+	/// The list of events fired by the extrinsic.
+	pub emitted_events: Vec<Event>,
+	/// The list of XCMs that were queued for sending, grouped by their destination.
+	pub forwarded_xcms: Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>,
+}
+
+/// Effects of dry-running an XCM program.
+#[derive(Encode, Decode, Debug, TypeInfo)]
+pub struct XcmDryRunEffects<Event> {
+	/// The outcome of the XCM program execution.
+	pub execution_result: Outcome,
+	/// The list of events fired by the XCM program execution.
+	pub emitted_events: Vec<Event>,
+	/// The list of XCMs that were queued for sending, grouped by their destination.
+	pub forwarded_xcms: Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// API for dry-running the extrinsics and the XCM programs.
+	///
+	/// Both calls return the events emitted during the execution and the XCMs forwarded to other
+	/// locations, which can be used to estimate the execution and the delivery fees.
+	///
+	/// The fees are not part of the result. The fees withdrawn by the execution only show up in
+	/// the emitted events, while the fees of the forwarded XCMs have to be queried separately with
+	/// the [`XcmPaymentApi`](crate::fees::XcmPaymentApi), i.e. `query_xcm_weight` and
+	/// `query_weight_to_asset_fee` for the execution on the destination and `query_delivery_fees`
+	/// for their delivery.
+	///
+	/// Extrinsics or XCMs might fail when executed, this doesn't mean the result of these calls
+	/// will be an `Err`. In those cases, there might still be a valid result, with the execution
+	/// error inside it. The only reasons why these calls might return an error are listed in the
+	/// [`Error`] enum.
+	pub trait DryRunApi<Call, Event, OriginCaller>
+	where
+		Call: Encode,
+		Event: Decode,
+		OriginCaller: Encode,
+	{
+		/// Dry run the call `call` dispatched from `origin`.
+		fn dry_run_call(origin: OriginCaller, call: Call) -> Result<CallDryRunEffects<Event>, Error>;
+
+		/// Dry run the XCM program `xcm` as if it was received from `origin_location`.
+		fn dry_run_xcm(origin_location: VersionedLocation, xcm: VersionedXcm<Call>) -> Result<XcmDryRunEffects<Event>, Error>;
+	}
+}
+
+/// The possible errors of the dry-run API.
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub enum Error {
+	/// An API call is unsupported.
+	#[codec(index = 0)]
+	Unimplemented,
+
+	/// Converting a versioned data structure from one version to another failed.
+	#[codec(index = 1)]
+	VersionedConversionFailed,
+}
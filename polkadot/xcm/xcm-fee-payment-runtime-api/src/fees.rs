@@ -0,0 +1,156 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for estimating the fees of the XCM programs.
+
+use codec::{Decode, Encode};
+use frame_support::traits::Get;
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+use sp_weights::Weight;
+use xcm::{
+	latest::{AssetId, Location},
+	Version, VersionedAssetId, VersionedAssets, VersionedLocation, VersionedXcm,
+};
+
+sp_api::decl_runtime_apis! {
+	/// A trait of XCM payment API.
+	///
+	/// API provides functionality for obtaining:
+	///
+	/// * the weight required to execute an XCM message,
+	/// * a list of acceptable `AssetId`s for message execution payment,
+	/// * the cost of the weight in the specified acceptable `AssetId`,
+	/// * the fees for an XCM message delivery.
+	///
+	/// To determine the execution weight of the calls required for
+	/// [`xcm::latest::Instruction::Transact`] instruction, `TransactionPaymentCallApi` can be used.
+	pub trait XcmPaymentApi {
+		/// Returns a list of acceptable payment assets.
+		///
+		/// # Arguments
+		///
+		/// * `xcm_version`: Version.
+		fn query_acceptable_payment_assets(xcm_version: Version) -> Result<Vec<VersionedAssetId>, Error>;
+
+		/// Returns a weight needed to execute a XCM.
+		///
+		/// # Arguments
+		///
+		/// * `message`: `VersionedXcm`.
+		fn query_xcm_weight(message: VersionedXcm<()>) -> Result<Weight, Error>;
+
+		/// Converts a weight into a fee for the specified `AssetId`.
+		///
+		/// # Arguments
+		///
+		/// * `weight`: convertible `Weight`.
+		/// * `asset`: `VersionedAssetId`.
+		fn query_weight_to_asset_fee(weight: Weight, asset: VersionedAssetId) -> Result<u128, Error>;
+
+		/// Get delivery fees for sending a specific `message` to a `destination`.
+		/// These always come in a specific asset, defined by the chain.
+		///
+		/// # Arguments
+		/// * `message`: The message that'll be sent, necessary because most delivery fees are based
+		///   on the size of the message.
+		/// * `destination`: The destination to send the message to. Different destinations may use
+		///   different senders that charge different fees.
+		fn query_delivery_fees(destination: VersionedLocation, message: VersionedXcm<()>) -> Result<VersionedAssets, Error>;
+	}
+}
+
+/// The possible errors of the fee payment API.
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub enum Error {
+	/// An API part is unsupported.
+	#[codec(index = 0)]
+	Unimplemented,
+
+	/// Converting a versioned data structure from one version to another failed.
+	#[codec(index = 1)]
+	VersionedConversionFailed,
+
+	/// XCM message weight calculation failed.
+	#[codec(index = 2)]
+	WeightNotComputable,
+
+	/// XCM version not able to be handled.
+	#[codec(index = 3)]
+	UnhandledXcmVersion,
+
+	/// The given asset is not handled as a fee asset.
+	#[codec(index = 4)]
+	AssetNotFound,
+
+	/// Destination is known to be unroutable.
+	#[codec(index = 5)]
+	Unroutable,
+}
+
+/// Implementation of [`XcmPaymentApi::query_weight_to_asset_fee`] for chains whose `Trader` only
+/// accepts their native token, located at `NativeLocation`.
+///
+/// The fee is computed with `WeightToFee`. Any other asset results in [`Error::AssetNotFound`].
+pub fn query_weight_to_native_asset_fee<WeightToFee, NativeLocation>(
+	weight: Weight,
+	asset: VersionedAssetId,
+) -> Result<u128, Error>
+where
+	WeightToFee: frame_support::weights::WeightToFee,
+	WeightToFee::Balance: Into<u128>,
+	NativeLocation: Get<Location>,
+{
+	let asset_id: AssetId = asset.try_into().map_err(|()| Error::VersionedConversionFailed)?;
+	if asset_id.0 == NativeLocation::get() {
+		Ok(WeightToFee::weight_to_fee(&weight).into())
+	} else {
+		Err(Error::AssetNotFound)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{parameter_types, weights::IdentityFee};
+	use xcm::latest::Junction::Parachain;
+
+	parameter_types! {
+		pub NativeLocation: Location = Location::parent();
+	}
+
+	type Fee = IdentityFee<u128>;
+
+	#[test]
+	fn native_asset_fee_works() {
+		let weight = Weight::from_parts(1_000, 0);
+		let native = VersionedAssetId::from(AssetId(Location::parent()));
+		assert_eq!(
+			query_weight_to_native_asset_fee::<Fee, NativeLocation>(weight, native),
+			Ok(1_000)
+		);
+	}
+
+	#[test]
+	fn other_assets_are_not_found() {
+		let weight = Weight::from_parts(1_000, 0);
+		let other = VersionedAssetId::from(AssetId(Location::new(1, [Parachain(1000)])));
+		assert_eq!(
+			query_weight_to_native_asset_fee::<Fee, NativeLocation>(weight, other),
+			Err(Error::AssetNotFound),
+		);
+	}
+}
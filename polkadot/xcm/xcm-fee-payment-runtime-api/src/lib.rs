@@ -0,0 +1,32 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime APIs for the XCM programs and the extrinsics sending them.
+//!
+//! They are meant to be used by wallets and UIs to find out the effects of an extrinsic or an XCM
+//! program before actually submitting it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Dry-run API.
+/// Given an extrinsic or an XCM program, it returns the outcome of its execution together with
+/// the emitted events and the forwarded XCMs, without committing any changes.
+pub mod dry_run;
+
+/// Fee estimation API.
+/// Given an XCM program, it returns the weight needed for its execution and the fees for that
+/// weight in any of the acceptable assets, as well as the fees for delivering it to a destination.
+pub mod fees;
@@ -57,10 +57,13 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		role: Role::Authority,
 		tokio_handle: tokio_handle.clone(),
 		transaction_pool: TransactionPoolOptions {
-			ready: PoolLimit { count: 100_000, total_bytes: 100 * 1024 * 1024 },
-			future: PoolLimit { count: 100_000, total_bytes: 100 * 1024 * 1024 },
-			reject_future_transactions: false,
-			ban_time: Duration::from_secs(30 * 60),
+			options: sc_transaction_pool::Options {
+				ready: PoolLimit { count: 100_000, total_bytes: 100 * 1024 * 1024 },
+				future: PoolLimit { count: 100_000, total_bytes: 100 * 1024 * 1024 },
+				reject_future_transactions: false,
+				ban_time: Duration::from_secs(30 * 60),
+			},
+			pool_type: Default::default(),
 		},
 		network: network_config,
 		keystore: KeystoreConfig::InMemory,
@@ -68,6 +71,9 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		trie_cache_maximum_size: Some(64 * 1024 * 1024),
 		state_pruning: Some(PruningMode::ArchiveAll),
 		blocks_pruning: BlocksPruning::KeepAll,
+		blocks_archive: None,
+		block_timestamp: None,
+		blocks_compaction: false,
 		chain_spec: spec,
 		wasm_method: Default::default(),
 		rpc_addr: None,
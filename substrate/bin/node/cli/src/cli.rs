@@ -91,9 +91,15 @@ pub enum Subcommand {
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Export a snapshot of the state of the latest finalized block.
+	ExportStateSnapshot(sc_cli::ExportStateSnapshotCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
+	/// Start a fresh node from a state snapshot.
+	ImportStateSnapshot(sc_cli::ImportStateSnapshotCmd),
+
 	/// Remove the whole chain.
 	PurgeChain(sc_cli::PurgeChainCmd),
 
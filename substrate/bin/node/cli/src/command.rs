@@ -196,6 +196,22 @@ pub fn run() -> Result<()> {
 				Ok((cmd.run(client, config.chain_spec), task_manager))
 			})
 		},
+		Some(Subcommand::ExportStateSnapshot(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				let PartialComponents { client, backend, task_manager, other, .. } =
+					new_partial(&config, None)?;
+				let (_, import_setup, _, _, _, _) = other;
+				let warp_sync = Arc::new(grandpa::warp_proof::NetworkProvider::new(
+					backend,
+					import_setup.1.shared_authority_set().clone(),
+					Vec::default(),
+				));
+				let genesis_authorities =
+					grandpa::GenesisAuthoritySetProvider::<Block>::get(&client)?;
+				Ok((cmd.run(client, warp_sync, genesis_authorities), task_manager))
+			})
+		},
 		Some(Subcommand::ImportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
@@ -204,6 +220,21 @@ pub fn run() -> Result<()> {
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		},
+		Some(Subcommand::ImportStateSnapshot(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				let PartialComponents {
+					client, backend, task_manager, import_queue, other, ..
+				} = new_partial(&config, None)?;
+				let (_, import_setup, _, _, _, _) = other;
+				let warp_sync = Arc::new(grandpa::warp_proof::NetworkProvider::new(
+					backend,
+					import_setup.1.shared_authority_set().clone(),
+					Vec::default(),
+				));
+				Ok((cmd.run(client, import_queue, warp_sync), task_manager))
+			})
+		},
 		Some(Subcommand::PurgeChain(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run(config.database))
@@ -25,7 +25,7 @@ use node_primitives::{AccountId, Balance, Nonce};
 use sp_core::{ecdsa, ed25519, sr25519};
 use sp_crypto_hashing::blake2_256;
 use sp_keyring::AccountKeyring;
-use sp_runtime::generic::Era;
+use sp_runtime::generic::{Era, ExtrinsicFormat, Preamble};
 
 /// Alice's account id.
 pub fn alice() -> AccountId {
@@ -92,8 +92,8 @@ pub fn sign(
 	tx_version: u32,
 	genesis_hash: [u8; 32],
 ) -> UncheckedExtrinsic {
-	match xt.signed {
-		Some((signed, extra)) => {
+	match xt.format {
+		ExtrinsicFormat::Signed(signed, extra) => {
 			let payload =
 				(xt.function, extra.clone(), spec_version, tx_version, genesis_hash, genesis_hash);
 			let key = AccountKeyring::from_account_id(&signed).unwrap();
@@ -108,10 +108,13 @@ pub fn sign(
 					})
 					.into();
 			UncheckedExtrinsic {
-				signature: Some((sp_runtime::MultiAddress::Id(signed), signature, extra)),
+				preamble: Preamble::Signed(sp_runtime::MultiAddress::Id(signed), signature, extra),
 				function: payload.0,
 			}
 		},
-		None => UncheckedExtrinsic { signature: None, function: xt.function },
+		ExtrinsicFormat::Bare =>
+			UncheckedExtrinsic { preamble: Preamble::Bare, function: xt.function },
+		ExtrinsicFormat::General(extra) =>
+			UncheckedExtrinsic { preamble: Preamble::General(extra), function: xt.function },
 	}
 }
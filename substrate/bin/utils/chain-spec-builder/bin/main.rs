@@ -17,8 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use chain_spec_builder::{
-	generate_chain_spec_for_runtime, ChainSpecBuilder, ChainSpecBuilderCmd, ConvertToRawCmd,
-	UpdateCodeCmd, VerifyCmd,
+	apply_patch_to_chain_spec, generate_chain_spec_for_runtime, list_presets_for_runtime,
+	verify_chain_spec, ChainSpecBuilder, ChainSpecBuilderCmd, ConvertToRawCmd, UpdateCodeCmd,
 };
 use clap::Parser;
 use sc_chain_spec::{update_code_in_json_chain_spec, GenericChainSpec};
@@ -66,10 +66,17 @@ fn main() -> Result<(), String> {
 				.map_err(|e| format!("Conversion to pretty failed: {e}"))?;
 			fs::write(chain_spec_path, chain_spec_json).map_err(|err| err.to_string())?;
 		},
-		ChainSpecBuilderCmd::Verify(VerifyCmd { ref input_chain_spec }) => {
-			let chain_spec = GenericChainSpec::<()>::from_json_file(input_chain_spec.clone())?;
-			let _ = serde_json::from_str::<serde_json::Value>(&chain_spec.as_json(true)?)
-				.map_err(|e| format!("Conversion to json failed: {e}"))?;
+		ChainSpecBuilderCmd::Verify(cmd) => {
+			let state_root = verify_chain_spec(&cmd)?;
+			println!("Genesis state root: {state_root:?}");
+		},
+		ChainSpecBuilderCmd::ApplyPatch(cmd) => {
+			let chain_spec_json = apply_patch_to_chain_spec(&cmd)?;
+			fs::write(chain_spec_path, chain_spec_json).map_err(|err| err.to_string())?;
+		},
+		ChainSpecBuilderCmd::ListPresets(cmd) => {
+			let preset_names = list_presets_for_runtime(&cmd)?;
+			println!("{}", preset_names.join("\n"));
 		},
 	};
 	Ok(())
@@ -62,6 +62,19 @@
 //!
 //! _Note_: [`GenesisBuilder::build_config`][sp-genesis-builder-build] runtime function is called.
 //!
+//! ##### Generate raw storage chain spec using the named preset provided by the runtime.
+//!
+//! List the names of the genesis config presets provided by the runtime and build the chain spec
+//! using one of them:
+//!
+//! ```bash
+//! chain-spec-builder list-presets -r runtime.wasm
+//! chain-spec-builder create -s -r runtime.wasm named-preset development
+//! ```
+//!
+//! _Note:_ [`GenesisBuilder::get_preset`][sp-genesis-builder-get-preset] and
+//! [`GenesisBuilder::build_config`][sp-genesis-builder-build] runtime functions are called.
+//!
 //! ##### Generate human readable chain spec using provided genesis config patch.
 //! ```bash
 //! chain-spec-builder create -r runtime.wasm patch patch.json
@@ -75,19 +88,45 @@
 //!
 //! ##### Extra tools.
 //! The `chain-spec-builder` provides also some extra utilities: [`VerifyCmd`], [`ConvertToRawCmd`],
-//! [`UpdateCodeCmd`].
+//! [`UpdateCodeCmd`], [`ApplyPatchCmd`], [`ListPresetsCmd`].
+//!
+//! ##### Patch the genesis config of an existing chain spec.
+//!
+//! Apply the provided `patch.json` to the runtime genesis config of `chain_spec.json`. The patched
+//! chain spec is verified by building its genesis state before it is written:
+//!
+//! ```bash
+//! chain-spec-builder -c patched_chain_spec.json apply-patch chain_spec.json patch.json
+//! ```
+//!
+//! _Note:_ [`GenesisBuilder::build_config`][sp-genesis-builder-build] runtime function is called.
+//!
+//! ##### Verify the genesis state root of a chain spec.
+//!
+//! Build the genesis state of `chain_spec.json` with the embedded runtime and check that its root
+//! matches the expected one:
+//!
+//! ```bash
+//! chain-spec-builder verify --state-root 0x1234..cdef chain_spec.json
+//! ```
 //!
 //! [`sc-chain-spec`]: ../sc_chain_spec/index.html
 //! [`node-cli`]: ../node_cli/index.html
 //! [`sp-genesis-builder`]: ../sp_genesis_builder/index.html
 //! [sp-genesis-builder-create]: ../sp_genesis_builder/trait.GenesisBuilder.html#method.create_default_config
 //! [sp-genesis-builder-build]: ../sp_genesis_builder/trait.GenesisBuilder.html#method.build_config
+//! [sp-genesis-builder-get-preset]: ../sp_genesis_builder/trait.GenesisBuilder.html#method.get_preset
 
 use std::{fs, path::PathBuf};
 
 use clap::{Parser, Subcommand};
-use sc_chain_spec::{GenericChainSpec, GenesisConfigBuilderRuntimeCaller};
+use sc_chain_spec::{
+	construct_genesis_state_root, update_runtime_genesis_in_json_chain_spec, GenericChainSpec,
+	GenesisConfigBuilderRuntimeCaller,
+};
 use serde_json::Value;
+use sp_core::H256;
+use sp_runtime::{traits::BlakeTwo256, BuildStorage};
 
 /// A utility to easily create a chain spec definition.
 #[derive(Debug, Parser)]
@@ -107,6 +146,8 @@ pub enum ChainSpecBuilderCmd {
 	Verify(VerifyCmd),
 	UpdateCode(UpdateCodeCmd),
 	ConvertToRaw(ConvertToRawCmd),
+	ApplyPatch(ApplyPatchCmd),
+	ListPresets(ListPresetsCmd),
 }
 
 /// Create a new chain spec by interacting with the provided runtime wasm blob.
@@ -137,6 +178,7 @@ enum GenesisBuildAction {
 	Patch(PatchCmd),
 	Full(FullCmd),
 	Default(DefaultCmd),
+	NamedPreset(NamedPresetCmd),
 }
 
 /// Patches the runtime's default genesis config with provided patch.
@@ -163,6 +205,21 @@ struct DefaultCmd {
 	default_config_path: Option<PathBuf>,
 }
 
+/// Uses the named genesis config preset provided by the runtime in ChainSpec.
+#[derive(Parser, Debug, Clone)]
+struct NamedPresetCmd {
+	/// The name of the genesis config preset.
+	preset_name: String,
+}
+
+/// Lists the names of the genesis config presets provided by the runtime.
+#[derive(Parser, Debug, Clone)]
+pub struct ListPresetsCmd {
+	/// The path to runtime wasm blob.
+	#[arg(long, short)]
+	pub runtime_wasm_path: PathBuf,
+}
+
 /// Updates the code in the provided input chain spec.
 ///
 /// The code field of the chain spec will be updated with the runtime provided in the
@@ -175,6 +232,19 @@ pub struct UpdateCodeCmd {
 	pub runtime_wasm_path: PathBuf,
 }
 
+/// Applies the provided patch to the runtime genesis config of the input chain spec.
+///
+/// The patch is merged into the genesis config patch or into the full genesis config, whichever is
+/// stored in the chain spec. The raw format is not supported. The genesis state of the patched
+/// chain spec is built with the embedded runtime, so any invalid fields will be reported.
+#[derive(Parser, Debug, Clone)]
+pub struct ApplyPatchCmd {
+	/// Chain spec to be patched.
+	pub input_chain_spec: PathBuf,
+	/// The path to the runtime genesis config patch.
+	pub patch_path: PathBuf,
+}
+
 /// Converts the given chain spec into the raw format.
 #[derive(Parser, Debug, Clone)]
 pub struct ConvertToRawCmd {
@@ -184,13 +254,19 @@ pub struct ConvertToRawCmd {
 
 /// Verifies the provided input chain spec.
 ///
-/// Silently checks if given input chain spec can be converted to raw. It allows to check if all
-/// RuntimeGenesisConfig fiels are properly initialized and if the json does not contain invalid
-/// fields.
+/// Checks if given input chain spec can be converted to raw and prints the root of its genesis
+/// state. It allows to check if all RuntimeGenesisConfig fields are properly initialized and if
+/// the json does not contain invalid fields.
+///
+/// If the expected state root is provided, the root of the genesis state built by the embedded
+/// runtime is also compared against it.
 #[derive(Parser, Debug, Clone)]
 pub struct VerifyCmd {
 	/// Chain spec to be verified.
 	pub input_chain_spec: PathBuf,
+	/// The expected root of the genesis state.
+	#[arg(long)]
+	pub state_root: Option<H256>,
 }
 
 /// Processes `CreateCmd` and returns JSON version of `ChainSpec`.
@@ -230,6 +306,8 @@ pub fn generate_chain_spec_for_runtime(cmd: &CreateCmd) -> Result<String, String
 			});
 			builder.with_genesis_config(default_config)
 		},
+		GenesisBuildAction::NamedPreset(NamedPresetCmd { ref preset_name }) =>
+			builder.with_genesis_config_preset_name(preset_name),
 	};
 
 	let chain_spec = builder.build();
@@ -244,3 +322,63 @@ pub fn generate_chain_spec_for_runtime(cmd: &CreateCmd) -> Result<String, String
 		(false, false) => chain_spec.as_json(false),
 	}
 }
+
+/// Processes `ApplyPatchCmd` and returns JSON version of the patched `ChainSpec`.
+pub fn apply_patch_to_chain_spec(cmd: &ApplyPatchCmd) -> Result<String, String> {
+	let ApplyPatchCmd { ref input_chain_spec, ref patch_path } = *cmd;
+
+	let chain_spec = fs::read(input_chain_spec.as_path())
+		.map_err(|e| format!("chain spec file {input_chain_spec:?} shall be readable: {e}"))?;
+	let mut chain_spec_json = serde_json::from_slice::<Value>(&chain_spec[..]).map_err(|e| {
+		format!("chain spec file {input_chain_spec:?} shall contain a valid json: {e}")
+	})?;
+
+	let patch = fs::read(patch_path.as_path())
+		.map_err(|e| format!("patch file {patch_path:?} shall be readable: {e}"))?;
+	let patch = serde_json::from_slice::<Value>(&patch[..])
+		.map_err(|e| format!("patch file {patch_path:?} shall contain a valid json: {e}"))?;
+
+	if !update_runtime_genesis_in_json_chain_spec(&mut chain_spec_json, patch) {
+		return Err(format!(
+			"chain spec file {input_chain_spec:?} shall contain the runtime genesis config"
+		))
+	}
+
+	let chain_spec_json = serde_json::to_vec(&chain_spec_json)
+		.map_err(|e| format!("Conversion to json failed: {e}"))?;
+	let chain_spec = GenericChainSpec::<()>::from_json_bytes(chain_spec_json)?;
+	chain_spec.as_json(true)?;
+	chain_spec.as_json(false)
+}
+
+/// Processes `VerifyCmd` and returns the root of the genesis state of the `ChainSpec`.
+///
+/// The root is computed with the `BlakeTwo256` hasher, which is used by all the Substrate based
+/// chains.
+pub fn verify_chain_spec(cmd: &VerifyCmd) -> Result<H256, String> {
+	let chain_spec = GenericChainSpec::<()>::from_json_file(cmd.input_chain_spec.clone())?;
+	let storage = chain_spec.build_storage()?;
+
+	let executor = sc_executor::WasmExecutor::<sp_io::SubstrateHostFunctions>::builder()
+		.with_allow_missing_host_functions(true)
+		.build();
+	let state_root = construct_genesis_state_root::<BlakeTwo256, _>(storage, &executor)
+		.map_err(|e| format!("Genesis state root computation failed: {e}"))?;
+
+	match cmd.state_root {
+		Some(expected) if expected != state_root =>
+			Err(format!("Genesis state root mismatch: expected {expected:?}, got {state_root:?}")),
+		_ => Ok(state_root),
+	}
+}
+
+/// Processes `ListPresetsCmd` and returns the names of the presets provided by the runtime.
+pub fn list_presets_for_runtime(cmd: &ListPresetsCmd) -> Result<Vec<String>, String> {
+	let code = fs::read(cmd.runtime_wasm_path.as_path())
+		.map_err(|e| format!("wasm blob shall be readable {e}"))?;
+	let caller: GenesisConfigBuilderRuntimeCaller =
+		GenesisConfigBuilderRuntimeCaller::new(&code[..]);
+	caller
+		.preset_names()
+		.map_err(|e| format!("getting the preset names from runtime should work: {e}"))
+}
@@ -0,0 +1,151 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use sc_chain_spec::{ChainType, GenericChainSpec};
+use serde_json::{json, Value};
+use sp_core::H256;
+use staging_chain_spec_builder::{
+	apply_patch_to_chain_spec, verify_chain_spec, ApplyPatchCmd, VerifyCmd,
+};
+use std::{fs, path::PathBuf};
+use tempfile::TempDir;
+
+/// Genesis config patch which `substrate-test-runtime` can build the genesis state from.
+fn genesis_patch() -> Value {
+	json!({
+		"babe": {
+			"epochConfig": {
+				"c": [1, 4],
+				"allowed_slots": "PrimaryAndSecondaryPlainSlots"
+			}
+		},
+		"substrateTest": {"authorities": []}
+	})
+}
+
+/// Writes a plain chain spec of `substrate-test-runtime` built from `patch` and returns its path.
+fn write_chain_spec(dir: &TempDir, name: &str, patch: Value) -> PathBuf {
+	let chain_spec = GenericChainSpec::<()>::builder(
+		substrate_test_runtime::wasm_binary_unwrap(),
+		Default::default(),
+	)
+	.with_name("TestName")
+	.with_id("test_id")
+	.with_chain_type(ChainType::Local)
+	.with_genesis_config_patch(patch)
+	.build();
+
+	let path = dir.path().join(name);
+	fs::write(&path, chain_spec.as_json(false).unwrap()).unwrap();
+	path
+}
+
+fn write_patch(dir: &TempDir, content: &str) -> PathBuf {
+	let path = dir.path().join("patch.json");
+	fs::write(&path, content).unwrap();
+	path
+}
+
+#[test]
+fn apply_patch_works() {
+	let dir = TempDir::new().unwrap();
+	let input_chain_spec = write_chain_spec(&dir, "chain_spec.json", genesis_patch());
+	let patch_path = write_patch(&dir, r#"{"babe": {"epochConfig": {"c": [7, 10]}}}"#);
+
+	let output =
+		apply_patch_to_chain_spec(&ApplyPatchCmd { input_chain_spec, patch_path }).unwrap();
+
+	let output = serde_json::from_str::<Value>(&output).unwrap();
+	assert_eq!(
+		output["genesis"]["runtimeGenesis"]["patch"],
+		json!({
+			"babe": {
+				"epochConfig": {
+					"c": [7, 10],
+					"allowed_slots": "PrimaryAndSecondaryPlainSlots"
+				}
+			},
+			"substrateTest": {"authorities": []}
+		})
+	);
+}
+
+#[test]
+fn apply_patch_fails_for_invalid_patch() {
+	let dir = TempDir::new().unwrap();
+	let input_chain_spec = write_chain_spec(&dir, "chain_spec.json", genesis_patch());
+
+	// Not a json.
+	let patch_path = write_patch(&dir, "{ invalid");
+	let err = apply_patch_to_chain_spec(&ApplyPatchCmd {
+		input_chain_spec: input_chain_spec.clone(),
+		patch_path,
+	})
+	.unwrap_err();
+	assert!(err.contains("shall contain a valid json"), "{err}");
+
+	// Unknown field of the runtime genesis config, rejected when building the genesis state.
+	let patch_path = write_patch(&dir, r#"{"babe": {"invalidField": 1}}"#);
+	assert!(apply_patch_to_chain_spec(&ApplyPatchCmd { input_chain_spec, patch_path }).is_err());
+}
+
+#[test]
+fn apply_patch_fails_for_raw_chain_spec() {
+	let dir = TempDir::new().unwrap();
+	let plain = write_chain_spec(&dir, "chain_spec.json", genesis_patch());
+	let raw = GenericChainSpec::<()>::from_json_file(plain).unwrap().as_json(true).unwrap();
+	let input_chain_spec = dir.path().join("raw_chain_spec.json");
+	fs::write(&input_chain_spec, raw).unwrap();
+	let patch_path = write_patch(&dir, "{}");
+
+	let err =
+		apply_patch_to_chain_spec(&ApplyPatchCmd { input_chain_spec, patch_path }).unwrap_err();
+	assert!(err.contains("shall contain the runtime genesis config"), "{err}");
+}
+
+#[test]
+fn verify_works() {
+	let dir = TempDir::new().unwrap();
+	let input_chain_spec = write_chain_spec(&dir, "chain_spec.json", genesis_patch());
+
+	let state_root = verify_chain_spec(&VerifyCmd {
+		input_chain_spec: input_chain_spec.clone(),
+		state_root: None,
+	})
+	.unwrap();
+	assert_eq!(
+		verify_chain_spec(&VerifyCmd {
+			input_chain_spec: input_chain_spec.clone(),
+			state_root: Some(state_root)
+		}),
+		Ok(state_root)
+	);
+
+	let err = verify_chain_spec(&VerifyCmd { input_chain_spec, state_root: Some(H256::zero()) })
+		.unwrap_err();
+	assert!(err.contains("Genesis state root mismatch"), "{err}");
+}
+
+#[test]
+fn verify_fails_for_invalid_genesis_config() {
+	let dir = TempDir::new().unwrap();
+	let input_chain_spec =
+		write_chain_spec(&dir, "chain_spec.json", json!({"invalidPallet": {"value": 1}}));
+
+	assert!(verify_chain_spec(&VerifyCmd { input_chain_spec, state_root: None }).is_err());
+}
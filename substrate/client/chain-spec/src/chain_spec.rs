@@ -45,6 +45,7 @@ use std::{
 enum GenesisBuildAction {
 	Patch(json::Value),
 	Full(json::Value),
+	NamedPreset(String),
 }
 
 #[allow(deprecated)]
@@ -72,7 +73,7 @@ impl<G> Clone for GenesisSource<G> {
 }
 
 impl<G: RuntimeGenesis> GenesisSource<G> {
-	fn resolve(&self) -> Result<Genesis<G>, String> {
+	fn resolve<EHF: HostFunctions>(&self) -> Result<Genesis<G>, String> {
 		/// helper container for deserializing genesis from the JSON file (ChainSpec JSON file is
 		/// also supported here)
 		#[derive(Serialize, Deserialize)]
@@ -118,6 +119,13 @@ impl<G: RuntimeGenesis> GenesisSource<G> {
 					json_blob: RuntimeGenesisConfigJson::Patch(patch.clone()),
 					code: code.clone(),
 				})),
+			Self::GenesisBuilderApi(GenesisBuildAction::NamedPreset(name), code) => {
+				let patch = RuntimeCaller::<EHF>::new(&code[..]).get_named_preset(name)?;
+				Ok(Genesis::RuntimeGenesis(RuntimeGenesisInner {
+					json_blob: RuntimeGenesisConfigJson::Patch(patch),
+					code: code.clone(),
+				}))
+			},
 		}
 	}
 }
@@ -127,7 +135,7 @@ where
 	EHF: HostFunctions,
 {
 	fn assimilate_storage(&self, storage: &mut Storage) -> Result<(), String> {
-		match self.genesis.resolve()? {
+		match self.genesis.resolve::<EHF>()? {
 			#[allow(deprecated)]
 			Genesis::Runtime(runtime_genesis_config) => {
 				runtime_genesis_config.assimilate_storage(storage)?;
@@ -431,6 +439,14 @@ impl<G, E, EHF> ChainSpecBuilder<G, E, EHF> {
 		self
 	}
 
+	/// Sets the name of runtime's GenesisConfig preset.
+	///
+	/// The preset, which is the patch of the default GenesisConfig, is provided by the runtime.
+	pub fn with_genesis_config_preset_name(mut self, name: &str) -> Self {
+		self.genesis_build_action = GenesisBuildAction::NamedPreset(name.to_string());
+		self
+	}
+
 	/// Builds a [`ChainSpec`] instance using the provided settings.
 	pub fn build(self) -> ChainSpec<G, E, EHF> {
 		let client_spec = ClientSpec {
@@ -635,7 +651,7 @@ where
 	EHF: HostFunctions,
 {
 	fn json_container(&self, raw: bool) -> Result<ChainSpecJsonContainer<G, E>, String> {
-		let raw_genesis = match (raw, self.genesis.resolve()?) {
+		let raw_genesis = match (raw, self.genesis.resolve::<EHF>()?) {
 			(
 				true,
 				Genesis::RuntimeGenesis(RuntimeGenesisInner {
@@ -838,6 +854,34 @@ pub fn update_code_in_json_chain_spec(chain_spec: &mut json::Value, code: &[u8])
 	}
 }
 
+/// This function applies the given patch to the runtime genesis config in given chain spec.
+///
+/// Only the `Genesis<G>::RuntimeGenesis` format is supported. The patch is merged into the
+/// runtime genesis config patch or into the full runtime genesis config, depending on which of
+/// them is stored in the chain spec. The merging rules are the same as for
+/// [`GenesisConfigBuilderRuntimeCaller::get_storage_for_patch`](RuntimeCaller::get_storage_for_patch).
+///
+/// If update was successful `true` is returned, otherwise `false`. Chain spec JSON is modified in
+/// place.
+pub fn update_runtime_genesis_in_json_chain_spec(
+	chain_spec: &mut json::Value,
+	patch: json::Value,
+) -> bool {
+	let mut patch_path = json_path!["genesis", "runtimeGenesis", "patch"];
+	let mut config_path = json_path!["genesis", "runtimeGenesis", "config"];
+
+	let key = if json_contains_path(&chain_spec, &mut patch_path) {
+		"patch"
+	} else if json_contains_path(&chain_spec, &mut config_path) {
+		"config"
+	} else {
+		return false
+	};
+
+	crate::json_patch::merge(chain_spec, json::json!({"genesis":{"runtimeGenesis":{key: patch}}}));
+	true
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1056,6 +1100,44 @@ mod tests {
 		assert_eq!(actual_raw, expected_raw);
 	}
 
+	#[test]
+	fn generate_chain_spec_with_named_preset_works() {
+		let output = ChainSpec::<()>::builder(
+			substrate_test_runtime::wasm_binary_unwrap().into(),
+			Default::default(),
+		)
+		.with_name("TestName")
+		.with_id("test_id")
+		.with_chain_type(ChainType::Local)
+		.with_genesis_config_preset_name("local_testnet")
+		.build();
+
+		let actual = from_str::<Value>(&output.as_json(false).unwrap()).unwrap();
+		assert_eq!(
+			actual["genesis"]["runtimeGenesis"]["patch"]["substrateTest"]["authorities"],
+			json!([
+				AccountKeyring::Alice.public().to_ss58check(),
+				AccountKeyring::Bob.public().to_ss58check()
+			])
+		);
+		assert!(output.as_json(true).is_ok());
+	}
+
+	#[test]
+	fn generate_chain_spec_with_unknown_named_preset_fails() {
+		let output = ChainSpec::<()>::builder(
+			substrate_test_runtime::wasm_binary_unwrap().into(),
+			Default::default(),
+		)
+		.with_name("TestName")
+		.with_id("test_id")
+		.with_chain_type(ChainType::Local)
+		.with_genesis_config_preset_name("unknown")
+		.build();
+
+		assert!(output.as_json(false).is_err());
+	}
+
 	#[test]
 	fn generate_chain_spec_with_full_config_works() {
 		let j = include_str!("../../../test-utils/runtime/res/default_genesis_config.json");
@@ -1230,6 +1312,92 @@ mod tests {
 		));
 	}
 
+	#[test]
+	fn update_runtime_genesis_works_with_runtime_genesis_config() {
+		let j = include_str!("../../../test-utils/runtime/res/default_genesis_config.json");
+		let chain_spec = ChainSpec::<()>::builder(
+			substrate_test_runtime::wasm_binary_unwrap().into(),
+			Default::default(),
+		)
+		.with_name("TestName")
+		.with_id("test_id")
+		.with_chain_type(ChainType::Local)
+		.with_genesis_config(from_str(j).unwrap())
+		.build();
+
+		let mut chain_spec_json = from_str::<Value>(&chain_spec.as_json(false).unwrap()).unwrap();
+		assert!(update_runtime_genesis_in_json_chain_spec(
+			&mut chain_spec_json,
+			json!({"babe": {"epochConfig": {"c": [7, 10]}}}),
+		));
+
+		assert!(json_eval_value_at_key(
+			&chain_spec_json,
+			&mut json_path!["genesis", "runtimeGenesis", "config", "babe", "epochConfig", "c"],
+			&|v| { *v == json!([7, 10]) }
+		));
+		assert!(json_eval_value_at_key(
+			&chain_spec_json,
+			&mut json_path![
+				"genesis",
+				"runtimeGenesis",
+				"config",
+				"babe",
+				"epochConfig",
+				"allowed_slots"
+			],
+			&|v| { *v == "PrimaryAndSecondaryPlainSlots" }
+		));
+
+		let chain_spec =
+			ChainSpec::<()>::from_json_bytes(serde_json::to_vec(&chain_spec_json).unwrap())
+				.unwrap();
+		assert!(chain_spec.as_json(true).is_ok());
+	}
+
+	#[test]
+	fn update_runtime_genesis_works_with_runtime_genesis_patch() {
+		let chain_spec = ChainSpec::<()>::builder(
+			substrate_test_runtime::wasm_binary_unwrap().into(),
+			Default::default(),
+		)
+		.with_name("TestName")
+		.with_id("test_id")
+		.with_chain_type(ChainType::Local)
+		.with_genesis_config_patch(json!({"substrateTest": {"authorities": []}}))
+		.build();
+
+		let mut chain_spec_json = from_str::<Value>(&chain_spec.as_json(false).unwrap()).unwrap();
+		assert!(update_runtime_genesis_in_json_chain_spec(
+			&mut chain_spec_json,
+			json!({"babe": {"epochConfig": {"c": [7, 10]}}}),
+		));
+
+		assert_eq!(
+			chain_spec_json["genesis"]["runtimeGenesis"]["patch"],
+			json!({
+				"babe": {"epochConfig": {"c": [7, 10]}},
+				"substrateTest": {"authorities": []}
+			})
+		);
+	}
+
+	#[test]
+	fn update_runtime_genesis_fails_for_raw() {
+		let chain_spec = ChainSpec::<()>::builder(
+			substrate_test_runtime::wasm_binary_unwrap().into(),
+			Default::default(),
+		)
+		.with_name("TestName")
+		.with_id("test_id")
+		.with_chain_type(ChainType::Local)
+		.with_genesis_config_patch(json!({}))
+		.build();
+
+		let mut chain_spec_json = from_str::<Value>(&chain_spec.as_json(true).unwrap()).unwrap();
+		assert!(!update_runtime_genesis_in_json_chain_spec(&mut chain_spec_json, json!({})));
+	}
+
 	#[test]
 	fn generate_from_genesis_is_still_supported() {
 		#[allow(deprecated)]
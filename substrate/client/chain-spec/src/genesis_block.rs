@@ -82,6 +82,22 @@ pub fn construct_genesis_block<Block: BlockT>(
 	)
 }
 
+/// Returns the state root of the given genesis storage.
+///
+/// The state version is read from the runtime code contained in the genesis storage.
+pub fn construct_genesis_state_root<H, E>(
+	storage: Storage,
+	executor: &E,
+) -> sp_blockchain::Result<H::Output>
+where
+	H: HashT,
+	E: RuntimeVersionOf,
+{
+	let state_version = resolve_state_version_from_wasm(&storage, executor)?;
+	let backend = sp_state_machine::InMemoryBackend::<H>::from((storage, state_version));
+	Ok(*backend.root())
+}
+
 /// Trait for building the genesis block.
 pub trait BuildGenesisBlock<Block: BlockT> {
 	/// The import operation used to import the genesis block into the backend.
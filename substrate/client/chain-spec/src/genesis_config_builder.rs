@@ -135,6 +135,42 @@ where
 		crate::json_patch::merge(&mut config, patch);
 		self.get_storage_for_config(config)
 	}
+
+	/// Returns the names of the `GenesisConfig` presets provided by the `runtime`.
+	///
+	/// Calls [`GenesisBuilder::preset_names`](sp_genesis_builder::GenesisBuilder::preset_names) in
+	/// the `runtime`.
+	pub fn preset_names(&self) -> core::result::Result<Vec<String>, String> {
+		let mut t = BasicExternalities::new_empty();
+		let call_result = self
+			.call(&mut t, "GenesisBuilder_preset_names", &[])
+			.map_err(|e| format!("wasm call error {e}"))?;
+		Vec::<String>::decode(&mut &call_result[..]).map_err(|e| format!("scale codec error: {e}"))
+	}
+
+	/// Returns the named `GenesisConfig` preset provided by the `runtime`.
+	///
+	/// The preset is the patch of the default `GenesisConfig`. Calls
+	/// [`GenesisBuilder::get_preset`](sp_genesis_builder::GenesisBuilder::get_preset) in the
+	/// `runtime`.
+	pub fn get_named_preset(&self, id: &str) -> core::result::Result<Value, String> {
+		let mut t = BasicExternalities::new_empty();
+		let call_result = self
+			.call(&mut t, "GenesisBuilder_get_preset", &id.encode())
+			.map_err(|e| format!("wasm call error {e}"))?;
+		let named_preset = Option::<Vec<u8>>::decode(&mut &call_result[..])
+			.map_err(|e| format!("scale codec error: {e}"))?
+			.ok_or_else(|| format!("The preset with name {id} is not available."))?;
+		from_slice(&named_preset[..]).map_err(|e| format!("preset {id} shall be a valid json: {e}"))
+	}
+
+	/// Creates the genesis state by patching the default `GenesisConfig` with the named preset and
+	/// applying it.
+	///
+	/// See [`Self::get_named_preset`] and [`Self::get_storage_for_patch`].
+	pub fn get_storage_for_named_preset(&self, id: &str) -> core::result::Result<Storage, String> {
+		self.get_storage_for_patch(self.get_named_preset(id)?)
+	}
 }
 
 #[cfg(test)]
@@ -192,4 +228,31 @@ mod tests {
 			}
 		);
 	}
+
+	#[test]
+	fn preset_names_works() {
+		let preset_names =
+			<GenesisConfigBuilderRuntimeCaller>::new(substrate_test_runtime::wasm_binary_unwrap())
+				.preset_names()
+				.unwrap();
+		assert_eq!(preset_names, vec!["development".to_string(), "local_testnet".to_string()]);
+	}
+
+	#[test]
+	fn get_named_preset_works() {
+		let caller =
+			<GenesisConfigBuilderRuntimeCaller>::new(substrate_test_runtime::wasm_binary_unwrap());
+
+		let preset = caller.get_named_preset("development").unwrap();
+		assert_eq!(
+			preset["substrateTest"],
+			json!({"authorities": ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]})
+		);
+		assert!(caller.get_storage_for_named_preset("development").is_ok());
+
+		assert_eq!(
+			caller.get_named_preset("unknown"),
+			Err("The preset with name unknown is not available.".to_string())
+		);
+	}
 }
@@ -189,6 +189,11 @@
 //! Consequently, the runtime must support the [`sp_genesis_builder::GenesisBuilder`] API to
 //! utilize either `patch` or `full` formats.
 //!
+//! The runtime may also provide a set of named presets of the genesis config, see
+//! [`sp_genesis_builder::GenesisBuilder::get_preset`]. A chain spec built with
+//! [`ChainSpecBuilder::with_genesis_config_preset_name`] stores the preset obtained from the
+//! runtime as the `patch`.
+//!
 //! This entire process is encapsulated within the implementation of the [`BuildStorage`] trait,
 //! which can be accessed through the [`ChainSpec::as_storage_builder`] method. There is an
 //! intermediate internal helper that facilitates this interaction,
@@ -329,13 +334,13 @@ mod json_patch;
 
 pub use self::{
 	chain_spec::{
-		update_code_in_json_chain_spec, ChainSpec as GenericChainSpec, ChainSpecBuilder,
-		NoExtension,
+		update_code_in_json_chain_spec, update_runtime_genesis_in_json_chain_spec,
+		ChainSpec as GenericChainSpec, ChainSpecBuilder, NoExtension,
 	},
 	extension::{get_extension, get_extension_mut, Extension, Fork, Forks, GetExtension, Group},
 	genesis_block::{
-		construct_genesis_block, resolve_state_version_from_wasm, BuildGenesisBlock,
-		GenesisBlockBuilder,
+		construct_genesis_block, construct_genesis_state_root, resolve_state_version_from_wasm,
+		BuildGenesisBlock, GenesisBlockBuilder,
 	},
 	genesis_config_builder::GenesisConfigBuilderRuntimeCaller,
 	json_patch::merge as json_merge,
@@ -296,3 +296,42 @@ impl Into<sc_network::config::SyncMode> for SyncMode {
 		}
 	}
 }
+
+/// Networking backend to use.
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
+#[value(rename_all = "kebab-case")]
+pub enum NetworkBackendType {
+	/// Use `rust-libp2p`.
+	Libp2p,
+	/// Use `litep2p`.
+	Litep2p,
+}
+
+impl Into<sc_network::config::NetworkBackendType> for NetworkBackendType {
+	fn into(self) -> sc_network::config::NetworkBackendType {
+		match self {
+			NetworkBackendType::Libp2p => sc_network::config::NetworkBackendType::Libp2p,
+			NetworkBackendType::Litep2p => sc_network::config::NetworkBackendType::Litep2p,
+		}
+	}
+}
+
+/// Type of the transaction pool.
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
+#[value(rename_all = "kebab-case")]
+pub enum TransactionPoolType {
+	/// Uses the single-state transaction pool, validating the transactions against the best
+	/// block only.
+	SingleState,
+	/// Uses the fork-aware transaction pool, maintaining a view for every fork.
+	ForkAware,
+}
+
+impl Into<sc_service::TransactionPoolType> for TransactionPoolType {
+	fn into(self) -> sc_service::TransactionPoolType {
+		match self {
+			TransactionPoolType::SingleState => sc_service::TransactionPoolType::SingleState,
+			TransactionPoolType::ForkAware => sc_service::TransactionPoolType::ForkAware,
+		}
+	}
+}
@@ -0,0 +1,90 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{DatabaseParams, PruningParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use sc_client_api::{BlockBackend, HeaderBackend, StorageProvider};
+use sc_network_sync::strategy::warp::{AuthorityList, WarpSyncProvider};
+use sc_service::chain_ops::export_state_snapshot;
+use sp_runtime::traits::Block as BlockT;
+use std::{fmt::Debug, fs, io, path::PathBuf, sync::Arc};
+
+/// The `export-state-snapshot` command used to export a snapshot of the state of the latest
+/// finalized block, along with the proofs of its finality.
+#[derive(Debug, Clone, Parser)]
+pub struct ExportStateSnapshotCmd {
+	/// Output file name or stdout if unspecified.
+	#[arg()]
+	pub output: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ExportStateSnapshotCmd {
+	/// Run the `export-state-snapshot` command.
+	///
+	/// The finality proofs are generated by `warp_sync_provider` and verified against
+	/// `genesis_authorities`.
+	pub async fn run<B, BA, C>(
+		&self,
+		client: Arc<C>,
+		warp_sync_provider: Arc<dyn WarpSyncProvider<B>>,
+		genesis_authorities: AuthorityList,
+	) -> error::Result<()>
+	where
+		B: BlockT,
+		BA: sc_client_api::backend::Backend<B>,
+		C: HeaderBackend<B> + BlockBackend<B> + StorageProvider<B, BA>,
+	{
+		let file: Box<dyn io::Write> = match &self.output {
+			Some(filename) => Box::new(io::BufWriter::new(fs::File::create(filename)?)),
+			None => Box::new(io::stdout()),
+		};
+
+		export_state_snapshot(client, &*warp_sync_provider, genesis_authorities, file)?;
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ExportStateSnapshotCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
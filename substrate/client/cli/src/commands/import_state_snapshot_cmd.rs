@@ -0,0 +1,88 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{ImportParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use sc_client_api::HeaderBackend;
+use sc_network_sync::strategy::warp::WarpSyncProvider;
+use sc_service::chain_ops::import_state_snapshot;
+use sp_runtime::traits::Block as BlockT;
+use std::{
+	fmt::Debug,
+	fs,
+	io::{self, Read},
+	path::PathBuf,
+	sync::Arc,
+};
+
+/// The `import-state-snapshot` command used to start a fresh node from a state snapshot.
+#[derive(Debug, Parser)]
+pub struct ImportStateSnapshotCmd {
+	/// Input file or stdin if unspecified.
+	#[arg()]
+	pub input: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub import_params: ImportParams,
+}
+
+impl ImportStateSnapshotCmd {
+	/// Run the `import-state-snapshot` command.
+	///
+	/// The finality proofs of the snapshot are verified by `warp_sync_provider`, starting from
+	/// the genesis authorities.
+	pub async fn run<B, C, IQ>(
+		&self,
+		client: Arc<C>,
+		import_queue: IQ,
+		warp_sync_provider: Arc<dyn WarpSyncProvider<B>>,
+	) -> error::Result<()>
+	where
+		C: HeaderBackend<B> + Send + Sync + 'static,
+		B: BlockT,
+		IQ: sc_service::ImportQueue<B> + 'static,
+	{
+		let file: Box<dyn Read + Send> = match &self.input {
+			Some(filename) => Box::new(io::BufReader::new(fs::File::open(filename)?)),
+			None => Box::new(io::stdin()),
+		};
+
+		import_state_snapshot(client, import_queue, &*warp_sync_provider, file)
+			.await
+			.map_err(Into::into)
+	}
+}
+
+impl CliConfiguration for ImportStateSnapshotCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn import_params(&self) -> Option<&ImportParams> {
+		Some(&self.import_params)
+	}
+}
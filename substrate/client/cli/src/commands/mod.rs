@@ -23,9 +23,11 @@ mod chain_info_cmd;
 mod check_block_cmd;
 mod export_blocks_cmd;
 mod export_state_cmd;
+mod export_state_snapshot_cmd;
 mod generate;
 mod generate_node_key;
 mod import_blocks_cmd;
+mod import_state_snapshot_cmd;
 mod insert_key;
 mod inspect_key;
 mod inspect_node_key;
@@ -41,9 +43,11 @@ mod verify;
 
 pub use self::{
 	build_spec_cmd::BuildSpecCmd, chain_info_cmd::ChainInfoCmd, check_block_cmd::CheckBlockCmd,
-	export_blocks_cmd::ExportBlocksCmd, export_state_cmd::ExportStateCmd, generate::GenerateCmd,
+	export_blocks_cmd::ExportBlocksCmd, export_state_cmd::ExportStateCmd,
+	export_state_snapshot_cmd::ExportStateSnapshotCmd, generate::GenerateCmd,
 	generate_node_key::GenerateNodeKeyCmd, import_blocks_cmd::ImportBlocksCmd,
-	insert_key::InsertKeyCmd, inspect_key::InspectKeyCmd, inspect_node_key::InspectNodeKeyCmd,
-	key::KeySubcommand, purge_chain_cmd::PurgeChainCmd, revert_cmd::RevertCmd, run_cmd::RunCmd,
-	sign::SignCmd, vanity::VanityCmd, verify::VerifyCmd,
+	import_state_snapshot_cmd::ImportStateSnapshotCmd, insert_key::InsertKeyCmd,
+	inspect_key::InspectKeyCmd, inspect_node_key::InspectNodeKeyCmd, key::KeySubcommand,
+	purge_chain_cmd::PurgeChainCmd, revert_cmd::RevertCmd, run_cmd::RunCmd, sign::SignCmd,
+	vanity::VanityCmd, verify::VerifyCmd,
 };
@@ -26,9 +26,10 @@ use log::warn;
 use names::{Generator, Name};
 use sc_service::{
 	config::{
-		BasePath, Configuration, DatabaseSource, KeystoreConfig, NetworkConfiguration,
-		NodeKeyConfig, OffchainWorkerConfig, OutputFormat, PrometheusConfig, PruningMode, Role,
-		RpcMethods, TelemetryEndpoints, TransactionPoolOptions, WasmExecutionMethod,
+		BasePath, BlockTimestampExtractor, BlocksArchiveSettings, Configuration, DatabaseSource,
+		KeystoreConfig, NetworkConfiguration, NodeKeyConfig, OffchainWorkerConfig, OutputFormat,
+		PrometheusConfig, PruningMode, Role, RpcMethods, TelemetryEndpoints,
+		TransactionPoolOptions, WasmExecutionMethod,
 	},
 	BlocksPruning, ChainSpec, TracingReceiver,
 };
@@ -41,6 +42,9 @@ pub(crate) const NODE_NAME_MAX_LENGTH: usize = 64;
 /// Default sub directory to store network config.
 pub(crate) const DEFAULT_NETWORK_CONFIG_PATH: &str = "network";
 
+/// The default directory of the block archive.
+pub(crate) const DEFAULT_BLOCKS_ARCHIVE_PATH: &str = "blocks_archive";
+
 /// The recommended open file descriptor limit to be configured for the process.
 const RECOMMENDED_OPEN_FILE_DESCRIPTOR_LIMIT: u64 = 10_000;
 
@@ -264,6 +268,37 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			.unwrap_or_else(|| Ok(BlocksPruning::KeepFinalized))
 	}
 
+	/// Get the block archive settings.
+	///
+	/// By default the depth is retrieved from `blocks_archive` if it is available. Otherwise the
+	/// block archive is disabled.
+	fn blocks_archive(&self, config_dir: &PathBuf) -> Result<Option<BlocksArchiveSettings>> {
+		Ok(self
+			.pruning_params()
+			.map(|x| x.blocks_archive())
+			.transpose()?
+			.flatten()
+			.map(|depth| BlocksArchiveSettings {
+				path: config_dir.join(DEFAULT_BLOCKS_ARCHIVE_PATH),
+				depth,
+			}))
+	}
+
+	/// Get the extractor of the timestamp of the imported blocks, used to prune blocks by age.
+	///
+	/// By default the timestamp is read from the `Timestamp` pallet.
+	fn block_timestamp(&self) -> Result<Option<BlockTimestampExtractor>> {
+		Ok(Some(BlockTimestampExtractor::timestamp_pallet()))
+	}
+
+	/// Whether the database should be periodically compacted after pruning blocks.
+	///
+	/// By default this is retrieved from `blocks_compaction` if it is available. Otherwise its
+	/// `false`.
+	fn blocks_compaction(&self) -> Result<bool> {
+		self.pruning_params().map(|x| x.blocks_compaction()).unwrap_or(Ok(false))
+	}
+
 	/// Get the chain ID (string).
 	///
 	/// By default this is retrieved from `SharedParams`.
@@ -476,6 +511,7 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		let keystore = self.keystore_config(&config_dir)?;
 		let telemetry_endpoints = self.telemetry_endpoints(&chain_spec)?;
 		let runtime_cache_size = self.runtime_cache_size()?;
+		let blocks_archive = self.blocks_archive(&config_dir)?;
 
 		Ok(Configuration {
 			impl_name: C::impl_name(),
@@ -498,6 +534,9 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			trie_cache_maximum_size: self.trie_cache_maximum_size()?,
 			state_pruning: self.state_pruning()?,
 			blocks_pruning: self.blocks_pruning()?,
+			blocks_archive,
+			block_timestamp: self.block_timestamp()?,
+			blocks_compaction: self.blocks_compaction()?,
 			wasm_method: self.wasm_method()?,
 			wasm_runtime_overrides: self.wasm_runtime_overrides(),
 			rpc_addr: self.rpc_addr(DCV::rpc_listen_port())?,
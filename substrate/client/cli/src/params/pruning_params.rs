@@ -19,6 +19,7 @@
 use crate::error;
 use clap::Args;
 use sc_service::{BlocksPruning, PruningMode};
+use std::time::Duration;
 
 /// Parameters to define the pruning mode
 #[derive(Debug, Clone, Args)]
@@ -47,13 +48,31 @@ pub struct PruningParams {
 	///  - 'archive-canonical' Keep only finalized blocks.
 	///  - number
 	///  Keep the last `number` of finalized blocks.
+	///  - 'number'd
+	///  Keep the finalized blocks of the last `number` of days. Headers are always kept. The age
+	///  of a block is read from the `Timestamp` pallet, unless the node reads it differently.
 	#[arg(
 		alias = "keep-blocks",
 		long,
 		value_name = "PRUNING_MODE",
 		default_value = "archive-canonical"
 	)]
-	pub blocks_pruning: DatabasePruningMode,
+	pub blocks_pruning: BlocksPruningMode,
+
+	/// Move the bodies and justifications of finalized blocks older than the given number of
+	/// blocks out of the database into flat files.
+	///
+	/// The archived blocks are still served as if they were in the database. Requires an
+	/// archive blocks pruning mode.
+	#[arg(long, value_name = "DEPTH")]
+	pub blocks_archive: Option<u32>,
+
+	/// Compact the database every hour to reclaim the space of the pruned blocks.
+	///
+	/// Compaction can take a while on large databases. Has no effect with an archive blocks
+	/// pruning mode.
+	#[arg(long)]
+	pub blocks_compaction: bool,
 }
 
 impl PruningParams {
@@ -66,6 +85,16 @@ impl PruningParams {
 	pub fn blocks_pruning(&self) -> error::Result<BlocksPruning> {
 		Ok(self.blocks_pruning.into())
 	}
+
+	/// Get the depth of the block archive from the parameters
+	pub fn blocks_archive(&self) -> error::Result<Option<u32>> {
+		Ok(self.blocks_archive)
+	}
+
+	/// Whether the database should be compacted after pruning blocks
+	pub fn blocks_compaction(&self) -> error::Result<bool> {
+		Ok(self.blocks_compaction)
+	}
 }
 
 /// Specifies the pruning mode of the database.
@@ -117,3 +146,61 @@ impl Into<BlocksPruning> for DatabasePruningMode {
 		}
 	}
 }
+
+/// Specifies the pruning mode of block bodies.
+///
+/// Unlike the state, block bodies can also be pruned by age.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlocksPruningMode {
+	/// Prune the bodies like the state would be pruned.
+	Database(DatabasePruningMode),
+	/// Keep only the bodies of the finalized blocks of the last number of days.
+	Days(u32),
+}
+
+impl std::str::FromStr for BlocksPruningMode {
+	type Err = String;
+
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		match input.strip_suffix('d') {
+			Some(days) => days
+				.parse()
+				.map_err(|_| "Invalid pruning mode specified".to_string())
+				.map(Self::Days),
+			None => input.parse().map(Self::Database),
+		}
+	}
+}
+
+impl Into<BlocksPruning> for BlocksPruningMode {
+	fn into(self) -> BlocksPruning {
+		match self {
+			BlocksPruningMode::Database(mode) => mode.into(),
+			BlocksPruningMode::Days(days) =>
+				BlocksPruning::Age(Duration::from_secs(u64::from(days) * 24 * 60 * 60)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn blocks_pruning_mode_is_parsed() {
+		assert_eq!(
+			"archive".parse::<BlocksPruningMode>(),
+			Ok(BlocksPruningMode::Database(DatabasePruningMode::Archive))
+		);
+		assert_eq!(
+			"256".parse::<BlocksPruningMode>(),
+			Ok(BlocksPruningMode::Database(DatabasePruningMode::Custom(256)))
+		);
+		assert_eq!("30d".parse::<BlocksPruningMode>(), Ok(BlocksPruningMode::Days(30)));
+		assert!("d".parse::<BlocksPruningMode>().is_err());
+		assert!("archived".parse::<BlocksPruningMode>().is_err());
+
+		let pruning: BlocksPruning = BlocksPruningMode::Days(2).into();
+		assert_eq!(pruning, BlocksPruning::Age(Duration::from_secs(2 * 24 * 60 * 60)));
+	}
+}
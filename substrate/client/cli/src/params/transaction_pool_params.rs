@@ -16,6 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::arg_enums::TransactionPoolType;
 use clap::Args;
 use sc_service::config::TransactionPoolOptions;
 
@@ -35,30 +36,27 @@ pub struct TransactionPoolParams {
 	/// If it is considered invalid. Defaults to 1800s.
 	#[arg(long, value_name = "SECONDS")]
 	pub tx_ban_seconds: Option<u64>,
+
+	/// The type of the transaction pool.
+	#[arg(
+		long,
+		value_name = "TYPE",
+		value_enum,
+		ignore_case = true,
+		default_value_t = TransactionPoolType::SingleState,
+	)]
+	pub pool_type: TransactionPoolType,
 }
 
 impl TransactionPoolParams {
 	/// Fill the given `PoolConfiguration` by looking at the cli parameters.
 	pub fn transaction_pool(&self, is_dev: bool) -> TransactionPoolOptions {
-		let mut opts = TransactionPoolOptions::default();
-
-		// ready queue
-		opts.ready.count = self.pool_limit;
-		opts.ready.total_bytes = self.pool_kbytes * 1024;
-
-		// future queue
-		let factor = 10;
-		opts.future.count = self.pool_limit / factor;
-		opts.future.total_bytes = self.pool_kbytes * 1024 / factor;
-
-		opts.ban_time = if let Some(ban_seconds) = self.tx_ban_seconds {
-			std::time::Duration::from_secs(ban_seconds)
-		} else if is_dev {
-			std::time::Duration::from_secs(0)
-		} else {
-			std::time::Duration::from_secs(30 * 60)
-		};
-
-		opts
+		TransactionPoolOptions::new_with_params(
+			self.pool_limit,
+			self.pool_kbytes * 1024,
+			self.tx_ban_seconds,
+			self.pool_type.into(),
+			is_dev,
+		)
 	}
 }
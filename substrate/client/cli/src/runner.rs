@@ -251,6 +251,9 @@ mod tests {
 				trie_cache_maximum_size: None,
 				state_pruning: None,
 				blocks_pruning: sc_client_db::BlocksPruning::KeepAll,
+				blocks_archive: None,
+				block_timestamp: None,
+				blocks_compaction: false,
 				chain_spec: Box::new(
 					GenericChainSpec::<()>::builder(Default::default(), NoExtension::None)
 						.with_name("test")
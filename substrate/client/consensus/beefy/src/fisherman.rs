@@ -0,0 +1,259 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Detection and reporting of BEEFY votes cast for blocks that are not part of the canonical
+//! chain (fork voting) or for blocks that have not been produced yet (future block voting).
+
+use crate::{error::Error, worker::BeefyWorker, LOG_TARGET};
+use codec::Codec;
+use log::debug;
+use sc_client_api::Backend;
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_consensus::SyncOracle;
+use sp_consensus_beefy::{
+	check_commitment_signature,
+	ecdsa_crypto::{AuthorityId, Signature},
+	BeefyApi, BeefySignatureHasher, ForkVotingProof, FutureBlockVotingProof, PayloadProvider,
+	SignedCommitment, ValidatorSet, VoteMessage,
+};
+use sp_core::offchain::{storage::OffchainDb, OffchainDbExt};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block, NumberFor},
+};
+
+/// The first `BeefyApi` version that supports fork voting and future block voting reports.
+const FORK_VOTING_REPORTS_API_VERSION: u32 = 4;
+
+impl<B, BE, P, R, S> BeefyWorker<B, BE, P, R, S>
+where
+	B: Block + Codec,
+	BE: Backend<B> + 'static,
+	P: PayloadProvider<B>,
+	S: SyncOracle,
+	R: ProvideRuntimeApi<B>,
+	R::Api: BeefyApi<B, AuthorityId>,
+{
+	/// Check if `vote` was cast for a block that is not part of the canonical chain, or for a
+	/// block that has not been produced yet, and report the offender if that's the case.
+	pub(crate) fn check_vote(
+		&self,
+		vote: VoteMessage<NumberFor<B>, AuthorityId, Signature>,
+		validator_set: &ValidatorSet<AuthorityId>,
+	) -> Result<(), Error> {
+		let number = vote.commitment.block_number;
+		let blockchain = self.base.backend.blockchain();
+
+		if number > blockchain.info().best_number {
+			return self.report_future_block_voting(FutureBlockVotingProof { vote }, validator_set)
+		}
+
+		let canonical_hash = blockchain
+			.expect_block_hash_from_id(&BlockId::Number(number))
+			.map_err(|e| Error::Backend(e.to_string()))?;
+		let canonical_header = blockchain
+			.expect_header(canonical_hash)
+			.map_err(|e| Error::Backend(e.to_string()))?;
+		match self.payload_provider.payload(&canonical_header) {
+			Some(canonical_payload) if canonical_payload != vote.commitment.payload =>
+				self.report_fork_voting(vote, canonical_hash, validator_set),
+			_ => Ok(()),
+		}
+	}
+
+	/// Check the votes aggregated in `signed_commitment` for fork voting or future block voting
+	/// and report the offenders, if any.
+	pub(crate) fn check_signed_commitment(
+		&self,
+		signed_commitment: SignedCommitment<NumberFor<B>, Signature>,
+		validator_set: &ValidatorSet<AuthorityId>,
+	) -> Result<(), Error> {
+		if signed_commitment.commitment.validator_set_id != validator_set.id() {
+			// We can't tell who signed the commitment.
+			return Ok(())
+		}
+
+		let SignedCommitment { commitment, signatures } = signed_commitment;
+		for (id, signature) in validator_set.validators().iter().zip(signatures.into_iter()) {
+			if let Some(signature) = signature {
+				let vote =
+					VoteMessage { commitment: commitment.clone(), id: id.clone(), signature };
+				self.check_vote(vote, validator_set)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Whether the offender is known and not ourselves, and the vote signature is valid.
+	fn should_report(
+		&self,
+		vote: &VoteMessage<NumberFor<B>, AuthorityId, Signature>,
+		validator_set: &ValidatorSet<AuthorityId>,
+	) -> bool {
+		if !validator_set.validators().contains(&vote.id) {
+			debug!(target: LOG_TARGET, "🥩 Skip report for vote of unknown authority {:?}", vote);
+			return false
+		}
+		if !check_commitment_signature::<_, _, BeefySignatureHasher>(
+			&vote.commitment,
+			&vote.id,
+			&vote.signature,
+		) {
+			debug!(target: LOG_TARGET, "🥩 Skip report for badly signed vote {:?}", vote);
+			return false
+		}
+		if let Some(local_id) = self.base.key_store.authority_id(validator_set.validators()) {
+			if vote.id == local_id {
+				debug!(target: LOG_TARGET, "🥩 Skip equivocation report for own equivocation");
+				return false
+			}
+		}
+
+		true
+	}
+
+	/// Whether the runtime at `at` is able to process fork voting and future block voting
+	/// reports.
+	fn runtime_supports_reports(&self, at: B::Hash) -> Result<bool, Error> {
+		self.base
+			.runtime
+			.runtime_api()
+			.has_api_with::<dyn BeefyApi<B, AuthorityId>, _>(at, |version| {
+				version >= FORK_VOTING_REPORTS_API_VERSION
+			})
+			.map_err(Error::RuntimeApi)
+	}
+
+	/// Report the given fork voting equivocation to the BEEFY runtime module. The ancestry proof
+	/// is generated at the best block, proving that the canonical block at
+	/// `vote.commitment.block_number` is `canonical_hash`.
+	fn report_fork_voting(
+		&self,
+		vote: VoteMessage<NumberFor<B>, AuthorityId, Signature>,
+		canonical_hash: B::Hash,
+		validator_set: &ValidatorSet<AuthorityId>,
+	) -> Result<(), Error> {
+		if !self.should_report(&vote, validator_set) {
+			return Ok(())
+		}
+
+		let blockchain = self.base.backend.blockchain();
+		let best_block_hash = blockchain.info().best_hash;
+		if !self.runtime_supports_reports(best_block_hash)? {
+			debug!(target: LOG_TARGET, "🥩 Runtime doesn't support fork voting reports.");
+			return Ok(())
+		}
+
+		let mut runtime_api = self.base.runtime.runtime_api();
+		// the ancestry proof is built using the MMR nodes stored in the offchain db.
+		if let Some(offchain_storage) = self.base.backend.offchain_storage() {
+			runtime_api.register_extension(OffchainDbExt::new(OffchainDb::new(offchain_storage)));
+		}
+
+		let number = vote.commitment.block_number;
+		let ancestry_proof = match runtime_api
+			.generate_ancestry_proof(best_block_hash, number, None)
+			.map_err(Error::RuntimeApi)?
+		{
+			Some(proof) => proof,
+			None => {
+				debug!(
+					target: LOG_TARGET,
+					"🥩 Couldn't generate ancestry proof for block #{:?}.", number
+				);
+				return Ok(())
+			},
+		};
+		let best_header = blockchain
+			.expect_header(best_block_hash)
+			.map_err(|e| Error::Backend(e.to_string()))?;
+
+		// generate key ownership proof at the canonical block
+		let key_owner_proof = match runtime_api
+			.generate_key_ownership_proof(canonical_hash, validator_set.id(), vote.id.clone())
+			.map_err(Error::RuntimeApi)?
+		{
+			Some(proof) => proof,
+			None => {
+				debug!(
+					target: LOG_TARGET,
+					"🥩 Fork voting offender not part of the authority set."
+				);
+				return Ok(())
+			},
+		};
+
+		let proof = ForkVotingProof { vote, ancestry_proof, header: best_header };
+		debug!(target: LOG_TARGET, "🥩 Reporting fork voting equivocation: {:?}", proof);
+		runtime_api
+			.submit_report_fork_voting_unsigned_extrinsic(best_block_hash, proof, key_owner_proof)
+			.map_err(Error::RuntimeApi)?;
+
+		Ok(())
+	}
+
+	/// Report the given future block voting equivocation to the BEEFY runtime module.
+	fn report_future_block_voting(
+		&self,
+		proof: FutureBlockVotingProof<NumberFor<B>, AuthorityId>,
+		validator_set: &ValidatorSet<AuthorityId>,
+	) -> Result<(), Error> {
+		if !self.should_report(&proof.vote, validator_set) {
+			return Ok(())
+		}
+
+		let best_block_hash = self.base.backend.blockchain().info().best_hash;
+		if !self.runtime_supports_reports(best_block_hash)? {
+			debug!(target: LOG_TARGET, "🥩 Runtime doesn't support future block voting reports.");
+			return Ok(())
+		}
+
+		let runtime_api = self.base.runtime.runtime_api();
+		// generate key ownership proof at the best block
+		let key_owner_proof = match runtime_api
+			.generate_key_ownership_proof(
+				best_block_hash,
+				validator_set.id(),
+				proof.vote.id.clone(),
+			)
+			.map_err(Error::RuntimeApi)?
+		{
+			Some(proof) => proof,
+			None => {
+				debug!(
+					target: LOG_TARGET,
+					"🥩 Future block voting offender not part of the authority set."
+				);
+				return Ok(())
+			},
+		};
+
+		debug!(target: LOG_TARGET, "🥩 Reporting future block voting equivocation: {:?}", proof);
+		runtime_api
+			.submit_report_future_block_voting_unsigned_extrinsic(
+				best_block_hash,
+				proof,
+				key_owner_proof,
+			)
+			.map_err(Error::RuntimeApi)?;
+
+		Ok(())
+	}
+}
@@ -54,6 +54,7 @@ use std::{collections::BTreeMap, marker::PhantomData, sync::Arc, time::Duration}
 
 mod aux_schema;
 mod error;
+mod fisherman;
 mod keystore;
 mod metrics;
 mod round;
@@ -217,7 +218,7 @@ pub async fn start_beefy_gadget<B, BE, C, N, P, R, S>(
 	beefy_params: BeefyParams<B, BE, C, N, P, R, S>,
 ) where
 	B: Block,
-	BE: Backend<B>,
+	BE: Backend<B> + 'static,
 	C: Client<B, BE> + BlockBackend<B>,
 	P: PayloadProvider<B> + Clone,
 	R: ProvideRuntimeApi<B>,
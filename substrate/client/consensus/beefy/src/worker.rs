@@ -597,7 +597,7 @@ pub(crate) struct BeefyWorker<B: Block, BE, P, RuntimeApi, S> {
 impl<B, BE, P, R, S> BeefyWorker<B, BE, P, R, S>
 where
 	B: Block + Codec,
-	BE: Backend<B>,
+	BE: Backend<B> + 'static,
 	P: PayloadProvider<B>,
 	S: SyncOracle,
 	R: ProvideRuntimeApi<B>,
@@ -695,6 +695,12 @@ where
 		vote: VoteMessage<NumberFor<B>, AuthorityId, Signature>,
 	) -> Result<(), Error> {
 		let block_num = vote.commitment.block_number;
+		// Check if the vote is for a non-canonical or future block, and report it if so.
+		if let Ok(rounds) = self.persisted_state.voting_oracle.active_rounds() {
+			if let Err(err) = self.check_vote(vote.clone(), rounds.validator_set()) {
+				debug!(target: LOG_TARGET, "🥩 Failed to check vote {:?}: {:?}", vote, err);
+			}
+		}
 		match self.voting_oracle().triage_round(block_num)? {
 			RoundAction::Process =>
 				if let Some(finality_proof) = self.handle_vote(vote)? {
@@ -723,6 +729,18 @@ where
 			VersionedFinalityProof::V1(ref sc) => sc,
 		};
 		let block_num = signed_commitment.commitment.block_number;
+		// Check if the justification is for a non-canonical or future block, and report the
+		// signers if so.
+		if let Ok(rounds) = self.persisted_state.voting_oracle.active_rounds() {
+			if let Err(err) =
+				self.check_signed_commitment(signed_commitment.clone(), rounds.validator_set())
+			{
+				debug!(
+					target: LOG_TARGET,
+					"🥩 Failed to check justification for round {:?}: {:?}", block_num, err
+				);
+			}
+		}
 		match self.voting_oracle().triage_round(block_num)? {
 			RoundAction::Process => {
 				debug!(target: LOG_TARGET, "🥩 Process justification for round: {:?}.", block_num);
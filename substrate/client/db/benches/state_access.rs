@@ -122,6 +122,8 @@ fn create_backend(config: BenchmarkConfig, temp_dir: &TempDir) -> Backend<Block>
 		state_pruning: Some(PruningMode::ArchiveAll),
 		source: DatabaseSource::ParityDb { path },
 		blocks_pruning: BlocksPruning::KeepAll,
+		blocks_archive: None,
+		block_timestamp: None,
 	};
 
 	Backend::new(settings, 100).expect("Creates backend")
@@ -0,0 +1,371 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Flat file archive of finalized block bodies and justifications.
+//!
+//! Records are appended to segment files that are never rewritten. The index file starts with the
+//! number of the first archived block, followed by one fixed-size entry per block number pointing
+//! into the segments. An entry with zero length means nothing was archived for that number.
+//!
+//! Records are written before the database transaction removing the data is committed. A record
+//! archived twice, e.g. after a crash, simply has its index entry overwritten.
+
+use codec::{Decode, Encode};
+use parking_lot::Mutex;
+use sp_blockchain::{Error as ClientError, Result as ClientResult};
+use std::{
+	fs::{self, File, OpenOptions},
+	io::{self, Read, Seek, SeekFrom, Write},
+	marker::PhantomData,
+	path::{Path, PathBuf},
+};
+
+const LOG_TARGET: &str = "db::archive";
+
+/// Name of the index file.
+const INDEX_FILE: &str = "index";
+
+/// Size of the index file header.
+const INDEX_HEADER_SIZE: u64 = 8;
+
+/// Size of an index entry: segment (`u32`), offset (`u64`) and length (`u32`).
+const INDEX_ENTRY_SIZE: u64 = 16;
+
+/// A new segment is started once the current one grows beyond this size.
+const MAX_SEGMENT_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Settings of the block archive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlocksArchiveSettings {
+	/// Directory of the archive files.
+	pub path: PathBuf,
+	/// Number of recent finalized blocks whose bodies and justifications are kept in the
+	/// database.
+	pub depth: u32,
+}
+
+/// Archived block body, as it was stored in the database.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub(crate) enum ArchivedBody {
+	/// Encoded body from the `BODY` column.
+	Plain(Vec<u8>),
+	/// Encoded body from the `BODY_INDEX` column. Indexed transactions stay in the database.
+	Indexed(Vec<u8>),
+}
+
+/// Archived block data.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub(crate) struct ArchivedBlock<H> {
+	/// Hash of the block.
+	pub hash: H,
+	/// Body of the block.
+	pub body: Option<ArchivedBody>,
+	/// Encoded justifications of the block.
+	pub justifications: Option<Vec<u8>>,
+}
+
+/// Location of a record in the segments.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct IndexEntry {
+	segment: u32,
+	offset: u64,
+	len: u32,
+}
+
+impl IndexEntry {
+	fn to_bytes(self) -> [u8; INDEX_ENTRY_SIZE as usize] {
+		let mut bytes = [0u8; INDEX_ENTRY_SIZE as usize];
+		bytes[..4].copy_from_slice(&self.segment.to_le_bytes());
+		bytes[4..12].copy_from_slice(&self.offset.to_le_bytes());
+		bytes[12..].copy_from_slice(&self.len.to_le_bytes());
+		bytes
+	}
+
+	fn from_bytes(bytes: &[u8; INDEX_ENTRY_SIZE as usize]) -> Self {
+		let mut segment = [0u8; 4];
+		let mut offset = [0u8; 8];
+		let mut len = [0u8; 4];
+		segment.copy_from_slice(&bytes[..4]);
+		offset.copy_from_slice(&bytes[4..12]);
+		len.copy_from_slice(&bytes[12..]);
+		IndexEntry {
+			segment: u32::from_le_bytes(segment),
+			offset: u64::from_le_bytes(offset),
+			len: u32::from_le_bytes(len),
+		}
+	}
+}
+
+struct ArchiveFiles {
+	index: File,
+	/// Number of the first archived block, if any.
+	first: Option<u64>,
+	/// Number of entries in the index.
+	entries: u64,
+	/// Current segment.
+	segment: File,
+	segment_index: u32,
+	segment_len: u64,
+}
+
+/// Append-only archive of finalized block bodies and justifications.
+pub(crate) struct BlockArchive<H> {
+	path: PathBuf,
+	depth: u32,
+	files: Mutex<ArchiveFiles>,
+	_phantom: PhantomData<H>,
+}
+
+fn segment_path(path: &Path, index: u32) -> PathBuf {
+	path.join(format!("segment_{:08}", index))
+}
+
+fn open_segment(path: &Path, index: u32) -> io::Result<File> {
+	OpenOptions::new().create(true).append(true).open(segment_path(path, index))
+}
+
+impl<H: Encode + Decode + PartialEq> BlockArchive<H> {
+	/// Open the archive at `settings.path`, creating it if it doesn't exist.
+	pub fn open(settings: &BlocksArchiveSettings) -> io::Result<Self> {
+		let path = settings.path.clone();
+		fs::create_dir_all(&path)?;
+
+		let mut index = OpenOptions::new()
+			.create(true)
+			.read(true)
+			.write(true)
+			.open(path.join(INDEX_FILE))?;
+		let index_len = index.metadata()?.len();
+		let (first, entries) = if index_len < INDEX_HEADER_SIZE {
+			index.set_len(0)?;
+			(None, 0)
+		} else {
+			let mut first = [0u8; INDEX_HEADER_SIZE as usize];
+			index.seek(SeekFrom::Start(0))?;
+			index.read_exact(&mut first)?;
+			// Drop a partially written entry.
+			let entries = (index_len - INDEX_HEADER_SIZE) / INDEX_ENTRY_SIZE;
+			index.set_len(INDEX_HEADER_SIZE + entries * INDEX_ENTRY_SIZE)?;
+			(Some(u64::from_le_bytes(first)), entries)
+		};
+
+		let mut segment_index = 0;
+		while segment_path(&path, segment_index + 1).exists() {
+			segment_index += 1;
+		}
+		let segment = open_segment(&path, segment_index)?;
+		let segment_len = segment.metadata()?.len();
+
+		log::debug!(
+			target: LOG_TARGET,
+			"Opened block archive at {:?}: {} entries from #{:?}, {} segments",
+			path,
+			entries,
+			first,
+			segment_index + 1,
+		);
+
+		Ok(BlockArchive {
+			path,
+			depth: settings.depth,
+			files: Mutex::new(ArchiveFiles {
+				index,
+				first,
+				entries,
+				segment,
+				segment_index,
+				segment_len,
+			}),
+			_phantom: PhantomData,
+		})
+	}
+
+	/// Number of recent finalized blocks that are kept in the database.
+	pub fn depth(&self) -> u32 {
+		self.depth
+	}
+
+	/// Append `block` to the archive as the block `number`.
+	///
+	/// Returns `false` if `number` precedes the first archived block, in which case nothing is
+	/// written and the data should stay in the database.
+	pub fn append(&self, number: u64, block: &ArchivedBlock<H>) -> io::Result<bool> {
+		let mut files = self.files.lock();
+		let first = match files.first {
+			Some(first) if number < first => return Ok(false),
+			Some(first) => first,
+			None => {
+				files.index.seek(SeekFrom::Start(0))?;
+				files.index.write_all(&number.to_le_bytes())?;
+				files.first = Some(number);
+				number
+			},
+		};
+
+		if files.segment_len >= MAX_SEGMENT_SIZE {
+			let segment_index = files.segment_index + 1;
+			files.segment = open_segment(&self.path, segment_index)?;
+			files.segment_index = segment_index;
+			files.segment_len = 0;
+		}
+
+		let record = block.encode();
+		let entry = IndexEntry {
+			segment: files.segment_index,
+			offset: files.segment_len,
+			len: u32::try_from(record.len())
+				.map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Record too large"))?,
+		};
+		files.segment.write_all(&record)?;
+		files.segment.sync_data()?;
+		files.segment_len += record.len() as u64;
+
+		// Numbers skipped since the last archived block get empty entries.
+		let position = number - first;
+		let mut entries = Vec::new();
+		for _ in files.entries.min(position)..position {
+			entries.extend_from_slice(&IndexEntry::default().to_bytes());
+		}
+		entries.extend_from_slice(&entry.to_bytes());
+		let start = files.entries.min(position);
+		files
+			.index
+			.seek(SeekFrom::Start(INDEX_HEADER_SIZE + start * INDEX_ENTRY_SIZE))?;
+		files.index.write_all(&entries)?;
+		files.index.sync_data()?;
+		files.entries = files.entries.max(position + 1);
+
+		Ok(true)
+	}
+
+	/// Read the block `number` from the archive, if it has the given `hash`.
+	pub fn get(&self, number: u64, hash: &H) -> ClientResult<Option<ArchivedBlock<H>>> {
+		let read = || -> io::Result<Option<Vec<u8>>> {
+			let mut files = self.files.lock();
+			let position = match files.first {
+				Some(first) if number >= first && number - first < files.entries => number - first,
+				_ => return Ok(None),
+			};
+
+			let mut entry = [0u8; INDEX_ENTRY_SIZE as usize];
+			files
+				.index
+				.seek(SeekFrom::Start(INDEX_HEADER_SIZE + position * INDEX_ENTRY_SIZE))?;
+			files.index.read_exact(&mut entry)?;
+			let entry = IndexEntry::from_bytes(&entry);
+			if entry.len == 0 {
+				return Ok(None)
+			}
+
+			let mut segment = File::open(segment_path(&self.path, entry.segment))?;
+			segment.seek(SeekFrom::Start(entry.offset))?;
+			let mut record = vec![0u8; entry.len as usize];
+			segment.read_exact(&mut record)?;
+			Ok(Some(record))
+		};
+
+		let record = match read() {
+			Ok(Some(record)) => record,
+			Ok(None) => return Ok(None),
+			Err(e) =>
+				return Err(ClientError::Backend(format!("Error reading block archive: {}", e))),
+		};
+		let block = ArchivedBlock::<H>::decode(&mut &record[..]).map_err(|e| {
+			ClientError::Backend(format!("Error decoding archived block #{}: {}", number, e))
+		})?;
+
+		// The entry may belong to a different block after a revert.
+		Ok((block.hash == *hash).then_some(block))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn block(hash: u64, body: &[u8]) -> ArchivedBlock<u64> {
+		ArchivedBlock {
+			hash,
+			body: Some(ArchivedBody::Plain(body.to_vec())),
+			justifications: Some(vec![hash as u8]),
+		}
+	}
+
+	fn open(path: &Path) -> BlockArchive<u64> {
+		BlockArchive::open(&BlocksArchiveSettings { path: path.into(), depth: 1 }).unwrap()
+	}
+
+	#[test]
+	fn archived_blocks_are_read_back() {
+		let dir = tempfile::tempdir().unwrap();
+		let archive = open(dir.path());
+
+		assert!(archive.append(10, &block(10, b"ten")).unwrap());
+		assert!(archive.append(11, &block(11, b"eleven")).unwrap());
+		// Numbers between archived blocks are empty.
+		assert!(archive.append(14, &block(14, b"fourteen")).unwrap());
+
+		assert_eq!(archive.get(10, &10).unwrap(), Some(block(10, b"ten")));
+		assert_eq!(archive.get(11, &11).unwrap(), Some(block(11, b"eleven")));
+		assert_eq!(archive.get(12, &12).unwrap(), None);
+		assert_eq!(archive.get(14, &14).unwrap(), Some(block(14, b"fourteen")));
+		assert_eq!(archive.get(15, &15).unwrap(), None);
+
+		// Blocks preceding the first archived one are not archived.
+		assert!(!archive.append(9, &block(9, b"nine")).unwrap());
+		assert_eq!(archive.get(9, &9).unwrap(), None);
+
+		// The archive survives reopening.
+		drop(archive);
+		let archive = open(dir.path());
+		assert_eq!(archive.get(11, &11).unwrap(), Some(block(11, b"eleven")));
+		assert!(archive.append(15, &block(15, b"fifteen")).unwrap());
+		assert_eq!(archive.get(15, &15).unwrap(), Some(block(15, b"fifteen")));
+	}
+
+	#[test]
+	fn rearchived_block_replaces_previous_record() {
+		let dir = tempfile::tempdir().unwrap();
+		let archive = open(dir.path());
+
+		assert!(archive.append(1, &block(1, b"one")).unwrap());
+		assert!(archive.append(2, &block(2, b"two")).unwrap());
+		assert!(archive.append(1, &block(100, b"other")).unwrap());
+
+		assert_eq!(archive.get(1, &1).unwrap(), None);
+		assert_eq!(archive.get(1, &100).unwrap(), Some(block(100, b"other")));
+		assert_eq!(archive.get(2, &2).unwrap(), Some(block(2, b"two")));
+	}
+
+	#[test]
+	fn partially_written_index_entry_is_dropped() {
+		let dir = tempfile::tempdir().unwrap();
+		let archive = open(dir.path());
+		assert!(archive.append(1, &block(1, b"one")).unwrap());
+		drop(archive);
+
+		let mut index = OpenOptions::new().append(true).open(dir.path().join(INDEX_FILE)).unwrap();
+		index.write_all(&[1, 2, 3]).unwrap();
+		drop(index);
+
+		let archive = open(dir.path());
+		assert_eq!(archive.get(1, &1).unwrap(), Some(block(1, b"one")));
+		assert!(archive.append(2, &block(2, b"two")).unwrap());
+		assert_eq!(archive.get(2, &2).unwrap(), Some(block(2, b"two")));
+	}
+}
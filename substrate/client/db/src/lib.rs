@@ -32,6 +32,7 @@ pub mod offchain;
 
 pub mod bench;
 
+mod block_archive;
 mod children;
 mod parity_db;
 mod pinned_blocks_cache;
@@ -48,10 +49,15 @@ use std::{
 	collections::{HashMap, HashSet},
 	io,
 	path::{Path, PathBuf},
-	sync::Arc,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	time::Duration,
 };
 
 use crate::{
+	block_archive::{ArchivedBlock, ArchivedBody, BlockArchive},
 	pinned_blocks_cache::PinnedBlocksCache,
 	record_stats_state::RecordStatsState,
 	stats::StateUsageStats,
@@ -97,9 +103,14 @@ pub use sc_state_db::PruningMode;
 pub use sp_database::Database;
 
 pub use bench::BenchmarkingState;
+pub use block_archive::BlocksArchiveSettings;
 
 const CACHE_HEADERS: usize = 8;
 
+/// Maximum number of blocks whose timestamp is read, and of blocks pruned, by age when finalizing
+/// a block.
+const MAX_AGE_PRUNED_BLOCKS: u32 = 512;
+
 /// DB-backed patricia trie state, transaction type is an overlay of changes to commit.
 pub type DbState<H> = sp_state_machine::TrieBackend<Arc<dyn sp_state_machine::Storage<H>>, H>;
 
@@ -127,6 +138,79 @@ enum DbExtrinsic<B: BlockT> {
 	Full(B::Extrinsic),
 }
 
+/// Extracts the timestamp of a block, in milliseconds, from the storage changes of its import.
+///
+/// The database does not know where the runtime stores the timestamp of a block, so it has to be
+/// provided to prune blocks by age.
+#[derive(Clone)]
+pub struct BlockTimestampExtractor(Arc<dyn Fn(&StorageCollection) -> Option<u64> + Send + Sync>);
+
+impl BlockTimestampExtractor {
+	/// Create a new extractor from the given function.
+	pub fn new(
+		extract: impl Fn(&StorageCollection) -> Option<u64> + Send + Sync + 'static,
+	) -> Self {
+		Self(Arc::new(extract))
+	}
+
+	/// Read the timestamp from the SCALE encoded `u64` storage value at `key`.
+	///
+	/// This is how the timestamp pallet stores the timestamp set by the timestamp inherent.
+	pub fn from_storage_value(key: Vec<u8>) -> Self {
+		Self::new(move |storage_updates| {
+			storage_updates
+				.iter()
+				.find(|(k, _)| *k == key)
+				.and_then(|(_, value)| value.as_ref())
+				.and_then(|value| u64::decode(&mut &value[..]).ok())
+		})
+	}
+
+	/// Read the timestamp set by the timestamp pallet, assuming it is named `Timestamp` in the
+	/// runtime.
+	pub fn timestamp_pallet() -> Self {
+		Self::from_storage_value(
+			[sp_crypto_hashing::twox_128(b"Timestamp"), sp_crypto_hashing::twox_128(b"Now")]
+				.concat(),
+		)
+	}
+
+	fn extract(&self, storage_updates: &StorageCollection) -> Option<u64> {
+		(self.0)(storage_updates)
+	}
+}
+
+impl std::fmt::Debug for BlockTimestampExtractor {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("BlockTimestampExtractor")
+	}
+}
+
+/// Key of the timestamp of the block with the given lookup key in the `META` column.
+fn block_timestamp_key(lookup_key: &[u8]) -> Vec<u8> {
+	[&meta_keys::BLOCK_TIMESTAMP_PREFIX[..], lookup_key].concat()
+}
+
+/// Progress of pruning blocks by age.
+#[derive(Debug, Clone, Copy, Encode, Decode)]
+struct AgePruningCursor<N> {
+	/// First block that was not pruned yet.
+	next_pruned: N,
+	/// Every block below this one is old enough to be pruned.
+	prunable_until: N,
+	/// First block whose timestamp was not read yet.
+	///
+	/// The blocks from `prunable_until` up to this one have no known timestamp.
+	next_scanned: N,
+}
+
+impl<N: One> AgePruningCursor<N> {
+	// Genesis is never executed, so it has no timestamp and is not pruned.
+	fn new() -> Self {
+		Self { next_pruned: One::one(), prunable_until: One::one(), next_scanned: One::one() }
+	}
+}
+
 /// A reference tracking state.
 ///
 /// It makes sure that the hash we are using stays pinned in storage
@@ -310,6 +394,15 @@ pub struct DatabaseSettings {
 	///
 	/// NOTE: only finalized blocks are subject for removal!
 	pub blocks_pruning: BlocksPruning,
+	/// Flat file archive of finalized block bodies and justifications.
+	///
+	/// If `None` is given, bodies and justifications are kept in the database. Requires an
+	/// archive blocks pruning mode.
+	pub blocks_archive: Option<BlocksArchiveSettings>,
+	/// Extracts the timestamp of the imported blocks.
+	///
+	/// Required by [`BlocksPruning::Age`].
+	pub block_timestamp: Option<BlockTimestampExtractor>,
 }
 
 /// Block pruning settings.
@@ -321,6 +414,12 @@ pub enum BlocksPruning {
 	KeepFinalized,
 	/// Keep N recent finalized blocks.
 	Some(u32),
+	/// Keep the finalized blocks whose timestamp is within the given age of the timestamp of the
+	/// last finalized block.
+	///
+	/// Block timestamps are read with [`DatabaseSettings::block_timestamp`]. A block with no known
+	/// timestamp, e.g. imported without being executed, is pruned once a later block is.
+	Age(Duration),
 }
 
 impl BlocksPruning {
@@ -328,7 +427,7 @@ impl BlocksPruning {
 	pub fn is_archive(&self) -> bool {
 		match *self {
 			BlocksPruning::KeepAll | BlocksPruning::KeepFinalized => true,
-			BlocksPruning::Some(_) => false,
+			BlocksPruning::Some(_) | BlocksPruning::Age(_) => false,
 		}
 	}
 }
@@ -485,10 +584,14 @@ pub struct BlockchainDb<Block: BlockT> {
 	header_metadata_cache: Arc<HeaderMetadataCache<Block>>,
 	header_cache: Mutex<LinkedHashMap<Block::Hash, Option<Block::Header>>>,
 	pinned_blocks_cache: Arc<RwLock<PinnedBlocksCache<Block>>>,
+	archive: Option<BlockArchive<Block::Hash>>,
 }
 
 impl<Block: BlockT> BlockchainDb<Block> {
-	fn new(db: Arc<dyn Database<DbHash>>) -> ClientResult<Self> {
+	fn new(
+		db: Arc<dyn Database<DbHash>>,
+		archive: Option<BlockArchive<Block::Hash>>,
+	) -> ClientResult<Self> {
 		let meta = read_meta::<Block>(&*db, columns::HEADER)?;
 		let leaves = LeafSet::read_from_db(&*db, columns::META, meta_keys::LEAF_PREFIX)?;
 		Ok(BlockchainDb {
@@ -498,6 +601,7 @@ impl<Block: BlockT> BlockchainDb<Block> {
 			header_metadata_cache: Arc::new(HeaderMetadataCache::default()),
 			header_cache: Default::default(),
 			pinned_blocks_cache: Arc::new(RwLock::new(PinnedBlocksCache::new())),
+			archive,
 		})
 	}
 
@@ -583,13 +687,27 @@ impl<Block: BlockT> BlockchainDb<Block> {
 		self.pinned_blocks_cache.write().unpin(hash);
 	}
 
+	/// Read the block from the flat file archive, if it was archived.
+	fn archived(&self, hash: Block::Hash) -> ClientResult<Option<ArchivedBlock<Block::Hash>>> {
+		let Some(archive) = &self.archive else { return Ok(None) };
+		match self.header_metadata(hash) {
+			Ok(header) => archive.get(header.number.saturated_into(), &hash),
+			Err(sp_blockchain::Error::UnknownBlock(_)) => Ok(None),
+			Err(e) => Err(e),
+		}
+	}
+
 	fn justifications_uncached(&self, hash: Block::Hash) -> ClientResult<Option<Justifications>> {
-		match read_db(
+		let justifications = match read_db(
 			&*self.db,
 			columns::KEY_LOOKUP,
 			columns::JUSTIFICATIONS,
 			BlockId::<Block>::Hash(hash),
 		)? {
+			Some(justifications) => Some(justifications),
+			None => self.archived(hash)?.and_then(|block| block.justifications),
+		};
+		match justifications {
 			Some(justifications) => match Decode::decode(&mut &justifications[..]) {
 				Ok(justifications) => Ok(Some(justifications)),
 				Err(err) =>
@@ -606,15 +724,7 @@ impl<Block: BlockT> BlockchainDb<Block> {
 		if let Some(body) =
 			read_db(&*self.db, columns::KEY_LOOKUP, columns::BODY, BlockId::Hash::<Block>(hash))?
 		{
-			// Plain body
-			match Decode::decode(&mut &body[..]) {
-				Ok(body) => return Ok(Some(body)),
-				Err(err) =>
-					return Err(sp_blockchain::Error::Backend(format!(
-						"Error decoding body: {}",
-						err
-					))),
-			}
+			return self.decode_body(ArchivedBody::Plain(body)).map(Some)
 		}
 
 		if let Some(index) = read_db(
@@ -623,48 +733,66 @@ impl<Block: BlockT> BlockchainDb<Block> {
 			columns::BODY_INDEX,
 			BlockId::Hash::<Block>(hash),
 		)? {
-			match Vec::<DbExtrinsic<Block>>::decode(&mut &index[..]) {
-				Ok(index) => {
-					let mut body = Vec::new();
-					for ex in index {
-						match ex {
-							DbExtrinsic::Indexed { hash, header } => {
-								match self.db.get(columns::TRANSACTION, hash.as_ref()) {
-									Some(t) => {
-										let mut input =
-											utils::join_input(header.as_ref(), t.as_ref());
-										let ex = Block::Extrinsic::decode(&mut input).map_err(
-											|err| {
-												sp_blockchain::Error::Backend(format!(
-													"Error decoding indexed extrinsic: {}",
-													err
-												))
-											},
-										)?;
-										body.push(ex);
-									},
-									None =>
-										return Err(sp_blockchain::Error::Backend(format!(
-											"Missing indexed transaction {:?}",
-											hash
-										))),
-								};
-							},
-							DbExtrinsic::Full(ex) => {
-								body.push(ex);
-							},
+			return self.decode_body(ArchivedBody::Indexed(index)).map(Some)
+		}
+
+		match self.archived(hash)?.and_then(|block| block.body) {
+			Some(body) => self.decode_body(body).map(Some),
+			None => Ok(None),
+		}
+	}
+
+	fn decode_body(&self, body: ArchivedBody) -> ClientResult<Vec<Block::Extrinsic>> {
+		match body {
+			ArchivedBody::Plain(body) => {
+				// Plain body
+				match Decode::decode(&mut &body[..]) {
+					Ok(body) => Ok(body),
+					Err(err) =>
+						Err(sp_blockchain::Error::Backend(format!("Error decoding body: {}", err))),
+				}
+			},
+			ArchivedBody::Indexed(index) =>
+				match Vec::<DbExtrinsic<Block>>::decode(&mut &index[..]) {
+					Ok(index) => {
+						let mut body = Vec::new();
+						for ex in index {
+							match ex {
+								DbExtrinsic::Indexed { hash, header } => {
+									match self.db.get(columns::TRANSACTION, hash.as_ref()) {
+										Some(t) => {
+											let mut input =
+												utils::join_input(header.as_ref(), t.as_ref());
+											let ex = Block::Extrinsic::decode(&mut input).map_err(
+												|err| {
+													sp_blockchain::Error::Backend(format!(
+														"Error decoding indexed extrinsic: {}",
+														err
+													))
+												},
+											)?;
+											body.push(ex);
+										},
+										None =>
+											return Err(sp_blockchain::Error::Backend(format!(
+												"Missing indexed transaction {:?}",
+												hash
+											))),
+									};
+								},
+								DbExtrinsic::Full(ex) => {
+									body.push(ex);
+								},
+							}
 						}
-					}
-					return Ok(Some(body))
-				},
-				Err(err) =>
-					return Err(sp_blockchain::Error::Backend(format!(
+						Ok(body)
+					},
+					Err(err) => Err(sp_blockchain::Error::Backend(format!(
 						"Error decoding body list: {}",
 						err
 					))),
-			}
+				},
 		}
-		Ok(None)
 	}
 }
 
@@ -780,7 +908,10 @@ impl<Block: BlockT> sc_client_api::blockchain::Backend<Block> for BlockchainDb<B
 			BlockId::<Block>::Hash(hash),
 		)? {
 			Some(body) => body,
-			None => return Ok(None),
+			None => match self.archived(hash)?.and_then(|block| block.body) {
+				Some(ArchivedBody::Indexed(body)) => body,
+				_ => return Ok(None),
+			},
 		};
 		match Vec::<DbExtrinsic<Block>>::decode(&mut &body[..]) {
 			Ok(index) => {
@@ -1126,6 +1257,12 @@ pub struct Backend<Block: BlockT> {
 	import_lock: Arc<RwLock<()>>,
 	is_archive: bool,
 	blocks_pruning: BlocksPruning,
+	block_timestamp: Option<BlockTimestampExtractor>,
+	age_pruning_cursor: Mutex<Option<AgePruningCursor<NumberFor<Block>>>>,
+	// the cursor written by the transaction being built. It replaces `age_pruning_cursor` once
+	// the transaction is committed.
+	pending_age_pruning_cursor: Mutex<Option<AgePruningCursor<NumberFor<Block>>>>,
+	blocks_pruned_since_compaction: AtomicU64,
 	io_stats: FrozenForDuration<(kvdb::IoStats, StateUsageInfo)>,
 	state_usage: Arc<StateUsageStats>,
 	genesis_state: RwLock<Option<Arc<DbGenesisStorage<Block>>>>,
@@ -1155,6 +1292,29 @@ impl<Block: BlockT> Backend<Block> {
 		Self::from_database(db as Arc<_>, canonicalization_delay, &db_config, needs_init)
 	}
 
+	/// Compact the database columns of block bodies and justifications, if blocks were pruned
+	/// since the last compaction.
+	///
+	/// Returns whether the database was compacted.
+	pub fn compact_pruned_blocks(&self) -> ClientResult<bool> {
+		if self.blocks_pruned_since_compaction.swap(0, Ordering::Relaxed) == 0 {
+			return Ok(false)
+		}
+
+		for column in [
+			columns::BODY,
+			columns::BODY_INDEX,
+			columns::JUSTIFICATIONS,
+			columns::TRANSACTION,
+			columns::META,
+		] {
+			self.storage.db.compact(column).map_err(|e| {
+				sp_blockchain::Error::Backend(format!("Error compacting database: {}", e))
+			})?;
+		}
+		Ok(true)
+	}
+
 	/// Reset the shared trie cache.
 	pub fn reset_trie_cache(&self) {
 		if let Some(cache) = &self.shared_trie_cache {
@@ -1178,7 +1338,7 @@ impl<Block: BlockT> Backend<Block> {
 		let db = sp_database::as_database(db);
 		let state_pruning = match blocks_pruning {
 			BlocksPruning::KeepAll => PruningMode::ArchiveAll,
-			BlocksPruning::KeepFinalized => PruningMode::ArchiveCanonical,
+			BlocksPruning::KeepFinalized | BlocksPruning::Age(_) => PruningMode::ArchiveCanonical,
 			BlocksPruning::Some(n) => PruningMode::blocks_pruning(n),
 		};
 		let db_setting = DatabaseSettings {
@@ -1186,6 +1346,8 @@ impl<Block: BlockT> Backend<Block> {
 			state_pruning: Some(state_pruning),
 			source: DatabaseSource::Custom { db, require_create_flag: true },
 			blocks_pruning,
+			blocks_archive: None,
+			block_timestamp: None,
 		};
 
 		Self::new(db_setting, canonicalization_delay).expect("failed to create test-db")
@@ -1232,7 +1394,24 @@ impl<Block: BlockT> Backend<Block> {
 
 		let state_pruning_used = state_db.pruning_mode();
 		let is_archive_pruning = state_pruning_used.is_archive();
-		let blockchain = BlockchainDb::new(db.clone())?;
+		let archive = match &config.blocks_archive {
+			Some(_) if !config.blocks_pruning.is_archive() =>
+				return Err(sp_blockchain::Error::Backend(
+					"The block archive requires an archive blocks pruning mode".into(),
+				)),
+			Some(settings) => Some(BlockArchive::open(settings).map_err(|e| {
+				sp_blockchain::Error::Backend(format!("Error opening block archive: {}", e))
+			})?),
+			None => None,
+		};
+		if matches!(config.blocks_pruning, BlocksPruning::Age(_)) &&
+			config.block_timestamp.is_none()
+		{
+			return Err(sp_blockchain::Error::Backend(
+				"Pruning blocks by age requires a block timestamp extractor".into(),
+			))
+		}
+		let blockchain = BlockchainDb::new(db.clone(), archive)?;
 
 		let storage_db =
 			StorageDb { db: db.clone(), state_db, prefix_keys: !db.supports_ref_counting() };
@@ -1249,6 +1428,10 @@ impl<Block: BlockT> Backend<Block> {
 			io_stats: FrozenForDuration::new(std::time::Duration::from_secs(1)),
 			state_usage: Arc::new(StateUsageStats::new()),
 			blocks_pruning: config.blocks_pruning,
+			block_timestamp: config.block_timestamp.clone(),
+			age_pruning_cursor: Mutex::new(None),
+			pending_age_pruning_cursor: Mutex::new(None),
+			blocks_pruned_since_compaction: AtomicU64::new(0),
 			genesis_state: RwLock::new(None),
 			shared_trie_cache: config.trie_cache_maximum_size.map(|maximum_size| {
 				SharedTrieCache::new(sp_trie::cache::CacheSize::new(maximum_size))
@@ -1388,6 +1571,7 @@ impl<Block: BlockT> Backend<Block> {
 			transaction,
 			header,
 			hash,
+			None,
 			with_state,
 			current_transaction_justifications,
 		)?;
@@ -1505,6 +1689,18 @@ impl<Block: BlockT> Backend<Block> {
 			utils::insert_hash_to_key_mapping(&mut transaction, columns::KEY_LOOKUP, number, hash)?;
 
 			transaction.set_from_vec(columns::HEADER, &lookup_key, pending_block.header.encode());
+			let timestamp = match (self.blocks_pruning, &self.block_timestamp) {
+				(BlocksPruning::Age(_), Some(block_timestamp)) =>
+					block_timestamp.extract(&operation.storage_updates),
+				_ => None,
+			};
+			if let Some(timestamp) = timestamp {
+				transaction.set_from_vec(
+					columns::META,
+					&block_timestamp_key(&lookup_key),
+					timestamp.encode(),
+				);
+			}
 			if let Some(body) = pending_block.body {
 				// If we have any index operations we save block in the new format with indexed
 				// extrinsic headers Otherwise we save the body as a single blob.
@@ -1653,6 +1849,7 @@ impl<Block: BlockT> Backend<Block> {
 					&mut transaction,
 					header,
 					hash,
+					timestamp,
 					operation.commit_state,
 					&mut current_transaction_justifications,
 				)?;
@@ -1764,6 +1961,8 @@ impl<Block: BlockT> Backend<Block> {
 		// Apply all in-memory state changes.
 		// Code beyond this point can't fail.
 
+		self.apply_age_pruning_cursor(true);
+
 		if let Some((header, hash)) = imported {
 			trace!(target: "db", "DB Commit done {:?}", hash);
 			let header_metadata = CachedHeaderMetadata::from(&header);
@@ -1781,12 +1980,14 @@ impl<Block: BlockT> Backend<Block> {
 
 	// write stuff to a transaction after a new block is finalized.
 	// this canonicalizes finalized blocks. Fails if called with a block which
-	// was not a child of the last finalized block.
+	// was not a child of the last finalized block. `f_timestamp` is the timestamp of a block
+	// imported in the same transaction, which is not in the db yet.
 	fn note_finalized(
 		&self,
 		transaction: &mut Transaction<DbHash>,
 		f_header: &Block::Header,
 		f_hash: Block::Hash,
+		f_timestamp: Option<u64>,
 		with_state: bool,
 		current_transaction_justifications: &mut HashMap<Block::Hash, Justification>,
 	) -> ClientResult<()> {
@@ -1818,6 +2019,7 @@ impl<Block: BlockT> Backend<Block> {
 			transaction,
 			f_num,
 			f_hash,
+			f_timestamp,
 			&new_displaced,
 			current_transaction_justifications,
 		)?;
@@ -1830,11 +2032,18 @@ impl<Block: BlockT> Backend<Block> {
 		transaction: &mut Transaction<DbHash>,
 		finalized_number: NumberFor<Block>,
 		finalized_hash: Block::Hash,
+		finalized_timestamp: Option<u64>,
 		displaced: &FinalizationOutcome<Block::Hash, NumberFor<Block>>,
 		current_transaction_justifications: &mut HashMap<Block::Hash, Justification>,
 	) -> ClientResult<()> {
 		match self.blocks_pruning {
-			BlocksPruning::KeepAll => {},
+			BlocksPruning::KeepAll => {
+				self.archive_block(
+					transaction,
+					finalized_number,
+					current_transaction_justifications,
+				)?;
+			},
 			BlocksPruning::Some(blocks_pruning) => {
 				// Always keep the last finalized block
 				let keep = std::cmp::max(blocks_pruning, 1);
@@ -1861,12 +2070,181 @@ impl<Block: BlockT> Backend<Block> {
 				self.prune_displaced_branches(transaction, finalized_hash, displaced)?;
 			},
 			BlocksPruning::KeepFinalized => {
+				self.archive_block(
+					transaction,
+					finalized_number,
+					current_transaction_justifications,
+				)?;
+				self.prune_displaced_branches(transaction, finalized_hash, displaced)?;
+			},
+			BlocksPruning::Age(max_age) => {
+				self.prune_blocks_by_age(
+					transaction,
+					finalized_number,
+					finalized_hash,
+					finalized_timestamp,
+					max_age,
+					current_transaction_justifications,
+				)?;
 				self.prune_displaced_branches(transaction, finalized_hash, displaced)?;
 			},
 		}
 		Ok(())
 	}
 
+	// prunes the finalized blocks older than `max_age`, resuming from the first block that was
+	// not pruned yet.
+	fn prune_blocks_by_age(
+		&self,
+		transaction: &mut Transaction<DbHash>,
+		finalized_number: NumberFor<Block>,
+		finalized_hash: Block::Hash,
+		finalized_timestamp: Option<u64>,
+		max_age: Duration,
+		current_transaction_justifications: &mut HashMap<Block::Hash, Justification>,
+	) -> ClientResult<()> {
+		let finalized_timestamp = match finalized_timestamp {
+			Some(timestamp) => Some(timestamp),
+			None => self.stored_block_timestamp(&utils::number_and_hash_to_lookup_key(
+				finalized_number,
+				finalized_hash,
+			)?)?,
+		};
+		let Some(finalized_timestamp) = finalized_timestamp else { return Ok(()) };
+		let min_timestamp =
+			finalized_timestamp.saturating_sub(max_age.as_millis().saturated_into());
+
+		let mut pending_cursor = self.pending_age_pruning_cursor.lock();
+		let mut cursor = match *pending_cursor {
+			Some(cursor) => cursor,
+			None => match *self.age_pruning_cursor.lock() {
+				Some(cursor) => cursor,
+				None => match self.storage.db.get(columns::META, meta_keys::AGE_PRUNING_CURSOR) {
+					Some(cursor) => Decode::decode(&mut &cursor[..]).map_err(|err| {
+						sp_blockchain::Error::Backend(format!(
+							"Error decoding age pruning cursor: {}",
+							err
+						))
+					})?,
+					None => AgePruningCursor::new(),
+				},
+			},
+		};
+
+		// Find the last block that is old enough to be pruned. Timestamps increase with the block
+		// number, so the blocks before it are old enough as well, even if their timestamp is
+		// unknown. Always keep the last finalized block.
+		let mut scanned = 0;
+		while cursor.next_scanned < finalized_number && scanned < MAX_AGE_PRUNED_BLOCKS {
+			if let Some(hash) = self.blockchain.hash(cursor.next_scanned)? {
+				let lookup_key = utils::number_and_hash_to_lookup_key(cursor.next_scanned, hash)?;
+				match self.stored_block_timestamp(&lookup_key)? {
+					Some(timestamp) if timestamp < min_timestamp =>
+						cursor.prunable_until = cursor.next_scanned + One::one(),
+					Some(_) => break,
+					None => {},
+				}
+			}
+			cursor.next_scanned += One::one();
+			scanned += 1;
+		}
+
+		let mut pruned = 0;
+		while cursor.next_pruned < cursor.prunable_until && pruned < MAX_AGE_PRUNED_BLOCKS {
+			if let Some(hash) = self.blockchain.hash(cursor.next_pruned)? {
+				// Before we prune a block, check if it is pinned
+				self.blockchain.insert_persisted_body_if_pinned(hash)?;
+				if let Some(justification) = current_transaction_justifications.remove(&hash) {
+					self.blockchain.insert_justifications_if_pinned(hash, justification);
+				} else {
+					self.blockchain.insert_persisted_justifications_if_pinned(hash)?;
+				}
+
+				self.prune_block(transaction, BlockId::<Block>::hash(hash))?;
+				pruned += 1;
+			}
+			cursor.next_pruned += One::one();
+		}
+
+		// The cursor is kept in memory as well, since blocks finalized in the same transaction
+		// must not be pruned twice. It only becomes the current cursor once the transaction is
+		// committed, see `apply_age_pruning_cursor`.
+		transaction.set_from_vec(columns::META, meta_keys::AGE_PRUNING_CURSOR, cursor.encode());
+		*pending_cursor = Some(cursor);
+		Ok(())
+	}
+
+	// makes the age pruning cursor of the last transaction the current one if the transaction
+	// was committed, or discards it otherwise.
+	fn apply_age_pruning_cursor(&self, committed: bool) {
+		let pending_cursor = self.pending_age_pruning_cursor.lock().take();
+		if let (true, Some(cursor)) = (committed, pending_cursor) {
+			*self.age_pruning_cursor.lock() = Some(cursor);
+		}
+	}
+
+	fn stored_block_timestamp(&self, lookup_key: &[u8]) -> ClientResult<Option<u64>> {
+		match self.storage.db.get(columns::META, &block_timestamp_key(lookup_key)) {
+			Some(timestamp) => match u64::decode(&mut &timestamp[..]) {
+				Ok(timestamp) => Ok(Some(timestamp)),
+				Err(err) => Err(sp_blockchain::Error::Backend(format!(
+					"Error decoding block timestamp: {}",
+					err
+				))),
+			},
+			None => Ok(None),
+		}
+	}
+
+	// moves the body and justifications of the finalized block that went out of the archive
+	// depth from the database to the block archive.
+	fn archive_block(
+		&self,
+		transaction: &mut Transaction<DbHash>,
+		finalized_number: NumberFor<Block>,
+		current_transaction_justifications: &HashMap<Block::Hash, Justification>,
+	) -> ClientResult<()> {
+		let Some(archive) = &self.blockchain.archive else { return Ok(()) };
+
+		// Always keep the last finalized block
+		let keep = std::cmp::max(archive.depth(), 1);
+		if finalized_number < keep.into() {
+			return Ok(())
+		}
+		let number = finalized_number.saturating_sub(keep.into());
+		let Some(hash) = self.blockchain.hash(number)? else { return Ok(()) };
+		let id = BlockId::<Block>::number(number);
+
+		let body = match read_db(&*self.storage.db, columns::KEY_LOOKUP, columns::BODY, id)? {
+			Some(body) => Some(ArchivedBody::Plain(body)),
+			None => read_db(&*self.storage.db, columns::KEY_LOOKUP, columns::BODY_INDEX, id)?
+				.map(ArchivedBody::Indexed),
+		};
+		// If the block was finalized in this transaction, its justification is not in the db yet.
+		let justifications = match current_transaction_justifications.get(&hash) {
+			Some(justification) => Some(Justifications::from(justification.clone()).encode()),
+			None => read_db(&*self.storage.db, columns::KEY_LOOKUP, columns::JUSTIFICATIONS, id)?,
+		};
+		if body.is_none() && justifications.is_none() {
+			return Ok(())
+		}
+
+		let block = ArchivedBlock { hash, body, justifications };
+		let archived = archive.append(number.saturated_into(), &block).map_err(|e| {
+			sp_blockchain::Error::Backend(format!("Error writing block archive: {}", e))
+		})?;
+		if !archived {
+			return Ok(())
+		}
+
+		debug!(target: "db", "Archived block #{}", id);
+		// Indexed transactions are still referenced by the archived body.
+		for column in [columns::BODY, columns::BODY_INDEX, columns::JUSTIFICATIONS] {
+			utils::remove_from_db(transaction, &*self.storage.db, columns::KEY_LOOKUP, column, id)?;
+		}
+		Ok(())
+	}
+
 	fn prune_displaced_branches(
 		&self,
 		transaction: &mut Transaction<DbHash>,
@@ -1896,6 +2274,14 @@ impl<Block: BlockT> Backend<Block> {
 		id: BlockId<Block>,
 	) -> ClientResult<()> {
 		debug!(target: "db", "Removing block #{}", id);
+		self.blocks_pruned_since_compaction.fetch_add(1, Ordering::Relaxed);
+		if let BlocksPruning::Age(_) = self.blocks_pruning {
+			if let Some(lookup_key) =
+				utils::block_id_to_lookup_key(&*self.storage.db, columns::KEY_LOOKUP, id)?
+			{
+				transaction.remove(columns::META, &block_timestamp_key(&lookup_key));
+			}
+		}
 		utils::remove_from_db(
 			transaction,
 			&*self.storage.db,
@@ -2104,6 +2490,7 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 		self.state_usage.merge_sm(usage);
 
 		if let Err(e) = self.try_commit_operation(operation) {
+			self.apply_age_pruning_cursor(false);
 			let state_meta_db = StateMetaDb(self.storage.db.clone());
 			self.storage
 				.state_db
@@ -2126,17 +2513,22 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 		let header = self.blockchain.expect_header(hash)?;
 
 		let mut current_transaction_justifications = HashMap::new();
-		let m = self.finalize_block_with_transaction(
-			&mut transaction,
-			hash,
-			&header,
-			None,
-			justification,
-			&mut current_transaction_justifications,
-		)?;
+		let result = self
+			.finalize_block_with_transaction(
+				&mut transaction,
+				hash,
+				&header,
+				None,
+				justification,
+				&mut current_transaction_justifications,
+			)
+			.and_then(|m| {
+				self.storage.db.commit(transaction)?;
+				Ok(m)
+			});
 
-		self.storage.db.commit(transaction)?;
-		self.blockchain.update_meta(m);
+		self.apply_age_pruning_cursor(result.is_ok());
+		self.blockchain.update_meta(result?);
 		Ok(())
 	}
 
@@ -2708,6 +3100,8 @@ pub(crate) mod tests {
 				state_pruning: Some(PruningMode::blocks_pruning(1)),
 				source: DatabaseSource::Custom { db: backing, require_create_flag: false },
 				blocks_pruning: BlocksPruning::KeepFinalized,
+				blocks_archive: None,
+				block_timestamp: None,
 			},
 			0,
 		)
@@ -3446,6 +3840,316 @@ pub(crate) mod tests {
 		}
 	}
 
+	#[test]
+	fn archive_blocks_on_finalize() {
+		let archive_dir = tempfile::tempdir().unwrap();
+		let db = sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS));
+		let backend = Backend::<Block>::new(
+			DatabaseSettings {
+				trie_cache_maximum_size: Some(16 * 1024 * 1024),
+				state_pruning: Some(PruningMode::ArchiveCanonical),
+				source: DatabaseSource::Custom { db: db.clone(), require_create_flag: true },
+				blocks_pruning: BlocksPruning::KeepFinalized,
+				blocks_archive: Some(BlocksArchiveSettings {
+					path: archive_dir.path().into(),
+					depth: 2,
+				}),
+				block_timestamp: None,
+			},
+			0,
+		)
+		.unwrap();
+
+		let mut blocks = Vec::new();
+		let mut prev_hash = Default::default();
+		for i in 0..5 {
+			let hash = insert_block(
+				&backend,
+				i,
+				prev_hash,
+				None,
+				Default::default(),
+				vec![i.into()],
+				None,
+			)
+			.unwrap();
+			blocks.push(hash);
+			prev_hash = hash;
+		}
+
+		{
+			let mut op = backend.begin_operation().unwrap();
+			backend.begin_state_operation(&mut op, blocks[4]).unwrap();
+			for i in 1..5 {
+				op.mark_finalized(blocks[i], Some((CONS0_ENGINE_ID, vec![i as u8]))).unwrap();
+			}
+			backend.commit_operation(op).unwrap();
+		}
+
+		// Blocks out of the archive depth are no longer in the database.
+		for i in 0..3 {
+			let id = BlockId::<Block>::hash(blocks[i]);
+			assert!(read_db(&*db, columns::KEY_LOOKUP, columns::BODY, id).unwrap().is_none());
+			assert!(read_db(&*db, columns::KEY_LOOKUP, columns::JUSTIFICATIONS, id)
+				.unwrap()
+				.is_none());
+		}
+		let id = BlockId::<Block>::hash(blocks[3]);
+		assert!(read_db(&*db, columns::KEY_LOOKUP, columns::BODY, id).unwrap().is_some());
+
+		// But are still served by the blockchain.
+		let bc = backend.blockchain();
+		for i in 0..5 {
+			assert_eq!(Some(vec![(i as u64).into()]), bc.body(blocks[i]).unwrap());
+		}
+		assert_eq!(None, bc.justifications(blocks[0]).unwrap());
+		for i in 1..5 {
+			assert_eq!(
+				Some(Justifications::from((CONS0_ENGINE_ID, vec![i as u8]))),
+				bc.justifications(blocks[i]).unwrap()
+			);
+		}
+	}
+
+	#[test]
+	fn block_archive_requires_archive_blocks_pruning() {
+		let archive_dir = tempfile::tempdir().unwrap();
+		let db = sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS));
+		let result = Backend::<Block>::new(
+			DatabaseSettings {
+				trie_cache_maximum_size: None,
+				state_pruning: Some(PruningMode::blocks_pruning(2)),
+				source: DatabaseSource::Custom { db, require_create_flag: true },
+				blocks_pruning: BlocksPruning::Some(2),
+				blocks_archive: Some(BlocksArchiveSettings {
+					path: archive_dir.path().into(),
+					depth: 2,
+				}),
+				block_timestamp: None,
+			},
+			0,
+		);
+		assert!(result.is_err());
+	}
+
+	fn timestamp_key() -> Vec<u8> {
+		[sp_crypto_hashing::twox_128(b"Timestamp"), sp_crypto_hashing::twox_128(b"Now")].concat()
+	}
+
+	fn new_age_pruning_backend(max_age: Duration) -> Backend<Block> {
+		let db = sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS));
+		Backend::<Block>::new(
+			DatabaseSettings {
+				trie_cache_maximum_size: Some(16 * 1024 * 1024),
+				state_pruning: Some(PruningMode::ArchiveCanonical),
+				source: DatabaseSource::Custom { db, require_create_flag: true },
+				blocks_pruning: BlocksPruning::Age(max_age),
+				blocks_archive: None,
+				block_timestamp: Some(BlockTimestampExtractor::timestamp_pallet()),
+			},
+			0,
+		)
+		.unwrap()
+	}
+
+	fn insert_block_with_timestamp(
+		backend: &Backend<Block>,
+		number: u64,
+		parent_hash: H256,
+		timestamp: Option<u64>,
+		new_state: NewBlockState,
+	) -> H256 {
+		let mut op = backend.begin_operation().unwrap();
+		backend.begin_state_operation(&mut op, parent_hash).unwrap();
+		let (root, overlay) = op.old_state.storage_root(
+			vec![(parent_hash.as_ref(), Some(parent_hash.as_ref()))].into_iter(),
+			StateVersion::V1,
+		);
+		op.update_db_storage(overlay).unwrap();
+		if let Some(timestamp) = timestamp {
+			op.update_storage(vec![(timestamp_key(), Some(timestamp.encode()))], vec![])
+				.unwrap();
+		}
+		let header = Header {
+			number,
+			parent_hash,
+			state_root: root.into(),
+			digest: Default::default(),
+			extrinsics_root: Default::default(),
+		};
+		op.set_block_data(header.clone(), Some(vec![number.into()]), None, None, new_state)
+			.unwrap();
+		backend.commit_operation(op).unwrap();
+		header.hash()
+	}
+
+	#[test]
+	fn prune_blocks_by_age_on_finalize() {
+		let backend = new_age_pruning_backend(Duration::from_secs(60));
+
+		// One block every 20 seconds. Genesis is not executed, so it has no timestamp.
+		let mut blocks = Vec::new();
+		let mut prev_hash = Default::default();
+		for i in 0..10u64 {
+			let timestamp = (i != 0).then_some(i * 20_000);
+			prev_hash =
+				insert_block_with_timestamp(&backend, i, prev_hash, timestamp, NewBlockState::Best);
+			blocks.push(prev_hash);
+		}
+
+		{
+			let mut op = backend.begin_operation().unwrap();
+			backend.begin_state_operation(&mut op, blocks[9]).unwrap();
+			for i in 1..10 {
+				op.mark_finalized(blocks[i], None).unwrap();
+			}
+			backend.commit_operation(op).unwrap();
+		}
+
+		// Blocks more than a minute older than the last finalized block are pruned.
+		let bc = backend.blockchain();
+		assert_eq!(Some(vec![0u64.into()]), bc.body(blocks[0]).unwrap());
+		for i in 1..6 {
+			assert_eq!(None, bc.body(blocks[i]).unwrap());
+			assert!(bc.header(blocks[i]).unwrap().is_some());
+		}
+		for i in 6..10 {
+			assert_eq!(Some(vec![(i as u64).into()]), bc.body(blocks[i]).unwrap());
+		}
+
+		// Pruning resumes where it stopped, using the timestamp of a block that is imported and
+		// finalized at once.
+		insert_block_with_timestamp(&backend, 10, blocks[9], Some(300_000), NewBlockState::Final);
+		for i in 6..10 {
+			assert_eq!(None, bc.body(blocks[i]).unwrap());
+		}
+
+		assert!(backend.compact_pruned_blocks().unwrap());
+		assert!(!backend.compact_pruned_blocks().unwrap());
+	}
+
+	#[test]
+	fn prune_blocks_by_age_after_failed_commit() {
+		let backend = new_age_pruning_backend(Duration::from_secs(60));
+
+		let mut blocks = Vec::new();
+		let mut prev_hash = Default::default();
+		for i in 0..10u64 {
+			let timestamp = (i != 0).then_some(i * 20_000);
+			prev_hash =
+				insert_block_with_timestamp(&backend, i, prev_hash, timestamp, NewBlockState::Best);
+			blocks.push(prev_hash);
+		}
+
+		// The operation fails after the blocks were pruned in its transaction.
+		{
+			let mut op = backend.begin_operation().unwrap();
+			backend.begin_state_operation(&mut op, blocks[9]).unwrap();
+			for i in 1..10 {
+				op.mark_finalized(blocks[i], None).unwrap();
+			}
+			op.mark_head(H256::random()).unwrap();
+			assert!(backend.commit_operation(op).is_err());
+		}
+		let bc = backend.blockchain();
+		for i in 1..10 {
+			assert_eq!(Some(vec![(i as u64).into()]), bc.body(blocks[i]).unwrap());
+		}
+
+		// Finalizing the blocks again prunes them.
+		{
+			let mut op = backend.begin_operation().unwrap();
+			backend.begin_state_operation(&mut op, blocks[9]).unwrap();
+			for i in 1..10 {
+				op.mark_finalized(blocks[i], None).unwrap();
+			}
+			backend.commit_operation(op).unwrap();
+		}
+		for i in 1..6 {
+			assert_eq!(None, bc.body(blocks[i]).unwrap());
+		}
+		for i in 6..10 {
+			assert_eq!(Some(vec![(i as u64).into()]), bc.body(blocks[i]).unwrap());
+		}
+	}
+
+	#[test]
+	fn prune_blocks_by_age_with_unknown_timestamps() {
+		let backend = new_age_pruning_backend(Duration::from_secs(60));
+
+		// The timestamps of genesis and of blocks 3 and 6 are unknown.
+		let mut blocks = Vec::new();
+		let mut prev_hash = Default::default();
+		for i in 0..10u64 {
+			let timestamp = (![0, 3, 6].contains(&i)).then_some(i * 20_000);
+			prev_hash =
+				insert_block_with_timestamp(&backend, i, prev_hash, timestamp, NewBlockState::Best);
+			blocks.push(prev_hash);
+		}
+		backend.finalize_block(blocks[9], None).unwrap();
+
+		// Block 3 is pruned because block 4 is old enough, block 6 is kept because block 7 is not.
+		let bc = backend.blockchain();
+		assert_eq!(Some(vec![0u64.into()]), bc.body(blocks[0]).unwrap());
+		for i in 1..6 {
+			assert_eq!(None, bc.body(blocks[i]).unwrap());
+		}
+		for i in 6..10 {
+			assert_eq!(Some(vec![(i as u64).into()]), bc.body(blocks[i]).unwrap());
+		}
+	}
+
+	#[test]
+	fn prune_blocks_by_age_after_warp_sync() {
+		let backend = new_age_pruning_backend(Duration::from_secs(60));
+
+		// The blocks up to the warp sync target at block 5 were not executed, so they have no
+		// timestamp.
+		let mut blocks = Vec::new();
+		let mut prev_hash = Default::default();
+		for i in 0..10u64 {
+			let timestamp = (i > 5).then_some(i * 20_000);
+			prev_hash =
+				insert_block_with_timestamp(&backend, i, prev_hash, timestamp, NewBlockState::Best);
+			blocks.push(prev_hash);
+		}
+
+		// Nothing is known to be old enough yet.
+		backend.finalize_block(blocks[8], None).unwrap();
+		let bc = backend.blockchain();
+		for i in 0..10 {
+			assert_eq!(Some(vec![(i as u64).into()]), bc.body(blocks[i]).unwrap());
+		}
+
+		// The unexecuted blocks are pruned once the first executed block is old enough.
+		backend.finalize_block(blocks[9], None).unwrap();
+		insert_block_with_timestamp(&backend, 10, blocks[9], Some(200_000), NewBlockState::Final);
+		assert_eq!(Some(vec![0u64.into()]), bc.body(blocks[0]).unwrap());
+		for i in 1..7 {
+			assert_eq!(None, bc.body(blocks[i]).unwrap());
+		}
+		for i in 7..10 {
+			assert_eq!(Some(vec![(i as u64).into()]), bc.body(blocks[i]).unwrap());
+		}
+	}
+
+	#[test]
+	fn prune_blocks_by_age_requires_block_timestamp() {
+		let db = sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS));
+		let result = Backend::<Block>::new(
+			DatabaseSettings {
+				trie_cache_maximum_size: None,
+				state_pruning: Some(PruningMode::ArchiveCanonical),
+				source: DatabaseSource::Custom { db, require_create_flag: true },
+				blocks_pruning: BlocksPruning::Age(Duration::from_secs(60)),
+				blocks_archive: None,
+				block_timestamp: None,
+			},
+			0,
+		);
+		assert!(result.is_err());
+	}
+
 	#[test]
 	fn prune_blocks_on_finalize_with_fork() {
 		sp_tracing::try_init_simple();
@@ -56,6 +56,10 @@ pub mod meta_keys {
 	pub const LEAF_PREFIX: &[u8; 4] = b"leaf";
 	/// Children prefix list key.
 	pub const CHILDREN_PREFIX: &[u8; 8] = b"children";
+	/// Block timestamp prefix key.
+	pub const BLOCK_TIMESTAMP_PREFIX: &[u8; 9] = b"timestamp";
+	/// Progress of pruning blocks by age.
+	pub const AGE_PRUNING_CURSOR: &[u8; 10] = b"age_pruned";
 }
 
 /// Database metadata.
@@ -340,7 +344,7 @@ fn open_kvdb_rocksdb<Block: BlockT>(
 	let db = kvdb_rocksdb::Database::open(&db_config, path)?;
 	// write database version only after the database is succesfully opened
 	crate::upgrade::update_version(path)?;
-	Ok(sp_database::as_database(db))
+	Ok(sp_database::as_database_with_compaction(db, kvdb_rocksdb::Database::force_compact))
 }
 
 #[cfg(not(any(feature = "rocksdb", test)))]
@@ -258,6 +258,18 @@ pub enum TransportConfig {
 	MemoryOnly,
 }
 
+/// Networking backend driving the node's peer-to-peer connections.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NetworkBackendType {
+	/// Use the `rust-libp2p` based backend, see [`NetworkWorker`](crate::NetworkWorker).
+	#[default]
+	Libp2p,
+
+	/// Use the `litep2p` based backend, see
+	/// [`Litep2pNetworkBackend`](crate::litep2p::Litep2pNetworkBackend).
+	Litep2p,
+}
+
 /// The policy for connections to non-reserved peers.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum NonReservedPeerMode {
@@ -655,6 +667,11 @@ pub struct NetworkConfiguration {
 	/// a modification of the way the implementation works. Different nodes with different
 	/// configured values remain compatible with each other.
 	pub yamux_window_size: Option<u32>,
+
+	/// Networking backend used for P2P communication.
+	///
+	/// Both backends speak the same wire protocols and can be mixed on one network.
+	pub network_backend: NetworkBackendType,
 }
 
 impl NetworkConfiguration {
@@ -687,6 +704,7 @@ impl NetworkConfiguration {
 				.expect("value is a constant; constant is non-zero; qed."),
 			yamux_window_size: None,
 			ipfs_server: false,
+			network_backend: NetworkBackendType::default(),
 		}
 	}
 
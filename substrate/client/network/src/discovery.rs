@@ -932,7 +932,7 @@ impl NetworkBehaviour for DiscoveryBehaviour {
 }
 
 /// Legacy (fallback) Kademlia protocol name based on `protocol_id`.
-fn legacy_kademlia_protocol_name(id: &ProtocolId) -> Vec<u8> {
+pub(crate) fn legacy_kademlia_protocol_name(id: &ProtocolId) -> Vec<u8> {
 	let mut v = vec![b'/'];
 	v.extend_from_slice(id.as_ref().as_bytes());
 	v.extend_from_slice(b"/kad");
@@ -940,7 +940,10 @@ fn legacy_kademlia_protocol_name(id: &ProtocolId) -> Vec<u8> {
 }
 
 /// Kademlia protocol name based on `genesis_hash` and `fork_id`.
-fn kademlia_protocol_name<Hash: AsRef<[u8]>>(genesis_hash: Hash, fork_id: Option<&str>) -> Vec<u8> {
+pub(crate) fn kademlia_protocol_name<Hash: AsRef<[u8]>>(
+	genesis_hash: Hash,
+	fork_id: Option<&str>,
+) -> Vec<u8> {
 	let genesis_hash_hex = bytes2hex("", genesis_hash.as_ref());
 	if let Some(fork_id) = fork_id {
 		format!("/{}/{}/kad", genesis_hash_hex, fork_id).as_bytes().into()
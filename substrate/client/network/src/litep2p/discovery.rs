@@ -0,0 +1,347 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Discovery for the `litep2p` backend.
+//!
+//! Combines Kademlia, Identify, Ping and, optionally, mDNS. Kademlia uses the same protocol
+//! names as [`DiscoveryBehaviour`](crate::discovery::DiscoveryBehaviour) so both backends
+//! share the DHT.
+
+use crate::{
+	config::{NetworkConfiguration, ProtocolId, TransportConfig},
+	discovery::{kademlia_protocol_name, legacy_kademlia_protocol_name},
+};
+
+use futures::{stream::BoxStream, FutureExt, StreamExt};
+use futures_timer::Delay;
+use libp2p::Multiaddr;
+use litep2p::{
+	protocol::{
+		libp2p::{
+			identify::{Config as IdentifyConfig, IdentifyEvent},
+			kademlia::{
+				Config as KademliaConfig, ConfigBuilder as KademliaConfigBuilder, KademliaEvent,
+				KademliaHandle, QueryId, Quorum, Record, RecordKey,
+			},
+			ping::{Config as PingConfig, PingEvent},
+		},
+		mdns::{Config as MdnsConfig, MdnsEvent},
+	},
+	types::protocol::ProtocolName,
+	PeerId,
+};
+
+use std::{cmp, collections::HashMap, time::Duration};
+
+/// Logging target for the file.
+const LOG_TARGET: &str = "sub-libp2p::discovery";
+
+/// Maximum delay between two random walks.
+const MAX_RANDOM_WALK_DELAY: Duration = Duration::from_secs(60);
+
+/// Interval between two mDNS queries.
+const MDNS_QUERY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Event emitted by [`Discovery`].
+#[derive(Debug)]
+pub(crate) enum DiscoveryEvent {
+	/// Peers and their addresses were discovered through a Kademlia query.
+	Discovered {
+		/// Discovered peers.
+		peers: Vec<(PeerId, Vec<Multiaddr>)>,
+	},
+
+	/// Peers were added to the Kademlia routing table.
+	RoutingTableUpdate {
+		/// Peers added to the routing table.
+		peers: Vec<PeerId>,
+	},
+
+	/// Addresses were discovered on the local network through mDNS.
+	MdnsDiscovered {
+		/// Discovered addresses.
+		addresses: Vec<Multiaddr>,
+	},
+
+	/// Peer was identified.
+	Identified {
+		/// Peer ID.
+		peer: PeerId,
+
+		/// Listen addresses of the peer.
+		listen_addresses: Vec<Multiaddr>,
+
+		/// Address of the local node as observed by the peer.
+		observed_address: Multiaddr,
+
+		/// Agent version of the peer, if known.
+		agent_version: Option<String>,
+	},
+
+	/// Ping was measured.
+	Ping {
+		/// Peer ID.
+		peer: PeerId,
+
+		/// Round-trip time.
+		rtt: Duration,
+	},
+
+	/// Record was found from the DHT.
+	GetRecordSuccess {
+		/// Query ID.
+		query_id: QueryId,
+
+		/// Found record.
+		record: Record,
+	},
+
+	/// Record was stored in the DHT.
+	PutRecordSuccess {
+		/// Query ID.
+		query_id: QueryId,
+	},
+
+	/// Query failed.
+	QueryFailed {
+		/// Query ID.
+		query_id: QueryId,
+	},
+}
+
+/// Configurations of the protocols used by [`Discovery`], installed into `litep2p`.
+pub(crate) struct DiscoveryConfigs {
+	/// Ping configuration.
+	pub(crate) ping: PingConfig,
+
+	/// Identify configuration.
+	pub(crate) identify: IdentifyConfig,
+
+	/// Kademlia configuration.
+	pub(crate) kademlia: KademliaConfig,
+
+	/// mDNS configuration, if enabled.
+	pub(crate) mdns: Option<MdnsConfig>,
+}
+
+/// Peer discovery for the `litep2p` backend.
+pub(crate) struct Discovery {
+	/// Kademlia handle.
+	kademlia_handle: KademliaHandle,
+
+	/// Identify events.
+	identify_event_stream: BoxStream<'static, IdentifyEvent>,
+
+	/// Ping events.
+	ping_event_stream: BoxStream<'static, PingEvent>,
+
+	/// mDNS events, if enabled.
+	mdns_event_stream: Option<BoxStream<'static, MdnsEvent>>,
+
+	/// Timer for the next random walk, `None` if random walks are disabled.
+	next_random_walk: Option<Delay>,
+
+	/// Delay before the random walk after the next one.
+	random_walk_delay: Duration,
+
+	/// Whether non-global addresses are added to the DHT.
+	allow_non_global_addresses: bool,
+}
+
+impl Discovery {
+	/// Create new [`Discovery`] and the configurations of the protocols it uses.
+	pub(crate) fn new<Hash: AsRef<[u8]>>(
+		config: &NetworkConfiguration,
+		genesis_hash: Hash,
+		fork_id: Option<&str>,
+		protocol_id: &ProtocolId,
+		known_peers: HashMap<PeerId, Vec<Multiaddr>>,
+	) -> (Self, DiscoveryConfigs) {
+		let (ping, ping_event_stream) = PingConfig::default();
+		let (identify, identify_event_stream) = IdentifyConfig::new(
+			"/substrate/1.0".to_string(),
+			Some(config.client_version.clone()),
+			config.public_addresses.clone(),
+		);
+
+		let protocol_names = [
+			kademlia_protocol_name(genesis_hash, fork_id),
+			legacy_kademlia_protocol_name(protocol_id),
+		]
+		.into_iter()
+		.map(|name| {
+			ProtocolName::from(String::from_utf8(name).expect("protocol names are utf-8; qed"))
+		})
+		.collect();
+
+		let (kademlia, kademlia_handle) = KademliaConfigBuilder::new()
+			.with_known_peers(known_peers)
+			.with_protocol_names(protocol_names)
+			.with_replication_factor(config.kademlia_replication_factor.get())
+			.build();
+
+		let (mdns, mdns_event_stream) = match config.transport {
+			TransportConfig::Normal { enable_mdns: true, .. } => {
+				let (mdns, mdns_event_stream) = MdnsConfig::new(MDNS_QUERY_INTERVAL);
+				(Some(mdns), Some(mdns_event_stream.boxed()))
+			},
+			_ => (None, None),
+		};
+
+		let allow_non_global_addresses = match config.transport {
+			TransportConfig::MemoryOnly => true,
+			TransportConfig::Normal { allow_private_ip, .. } =>
+				allow_private_ip || config.allow_non_globals_in_dht,
+		};
+
+		(
+			Self {
+				kademlia_handle,
+				identify_event_stream: identify_event_stream.boxed(),
+				ping_event_stream: ping_event_stream.boxed(),
+				mdns_event_stream,
+				next_random_walk: config
+					.enable_dht_random_walk
+					.then(|| Delay::new(Duration::from_secs(1))),
+				random_walk_delay: Duration::from_secs(1),
+				allow_non_global_addresses,
+			},
+			DiscoveryConfigs { ping, identify, kademlia, mdns },
+		)
+	}
+
+	/// Add known address of `peer` to Kademlia.
+	pub(crate) async fn add_known_peer(&mut self, peer: PeerId, addresses: Vec<Multiaddr>) {
+		let addresses = addresses
+			.into_iter()
+			.filter(|address| self.allow_non_global_addresses || is_global(address))
+			.collect::<Vec<_>>();
+
+		if !addresses.is_empty() {
+			self.kademlia_handle.add_known_peer(peer, addresses).await;
+		}
+	}
+
+	/// Start getting a value from the DHT.
+	pub(crate) async fn get_value(&mut self, key: RecordKey) -> QueryId {
+		self.kademlia_handle.get_record(key, Quorum::One).await
+	}
+
+	/// Start putting a value into the DHT.
+	pub(crate) async fn put_value(&mut self, key: RecordKey, value: Vec<u8>) -> QueryId {
+		self.kademlia_handle.put_record(Record::new(key, value)).await
+	}
+
+	/// Start a random walk, looking for a random peer to discover new ones on the way.
+	async fn random_walk(&mut self) {
+		let query_id = self.kademlia_handle.find_node(PeerId::random()).await;
+
+		log::trace!(target: LOG_TARGET, "start random walk, query {query_id:?}");
+
+		self.next_random_walk = Some(Delay::new(self.random_walk_delay));
+		self.random_walk_delay = cmp::min(self.random_walk_delay * 2, MAX_RANDOM_WALK_DELAY);
+	}
+
+	/// Handle an event emitted by Kademlia.
+	fn on_kademlia_event(&mut self, event: KademliaEvent) -> Option<DiscoveryEvent> {
+		match event {
+			KademliaEvent::FindNodeSuccess { peers, .. } => {
+				log::trace!(target: LOG_TARGET, "random walk found {} peers", peers.len());
+				Some(DiscoveryEvent::Discovered { peers })
+			},
+			KademliaEvent::RoutingTableUpdate { peers } =>
+				Some(DiscoveryEvent::RoutingTableUpdate { peers }),
+			KademliaEvent::GetRecordSuccess { query_id, record } =>
+				Some(DiscoveryEvent::GetRecordSuccess { query_id, record }),
+			KademliaEvent::PutRecordSucess { query_id, .. } =>
+				Some(DiscoveryEvent::PutRecordSuccess { query_id }),
+			KademliaEvent::QueryFailed { query_id } =>
+				Some(DiscoveryEvent::QueryFailed { query_id }),
+		}
+	}
+
+	/// Poll the next event from the discovery protocols.
+	pub(crate) async fn next_event(&mut self) -> Option<DiscoveryEvent> {
+		loop {
+			let next_random_walk = &mut self.next_random_walk;
+			let random_walk = async move {
+				match next_random_walk.as_mut() {
+					Some(delay) => delay.await,
+					None => futures::future::pending().await,
+				}
+			};
+			let mdns_event_stream = &mut self.mdns_event_stream;
+			let mdns = async move {
+				match mdns_event_stream.as_mut() {
+					Some(stream) => stream.next().await,
+					None => futures::future::pending().await,
+				}
+			};
+
+			tokio::select! {
+				_ = random_walk.fuse() => {
+					self.random_walk().await;
+				},
+				event = self.kademlia_handle.next() => match event {
+					Some(event) => if let Some(event) = self.on_kademlia_event(event) {
+						return Some(event)
+					},
+					None => return None,
+				},
+				event = self.identify_event_stream.next() => match event {
+					Some(IdentifyEvent::PeerIdentified {
+						peer,
+						listen_addresses,
+						observed_address,
+						user_agent,
+						..
+					}) => return Some(DiscoveryEvent::Identified {
+						peer,
+						listen_addresses,
+						observed_address,
+						agent_version: user_agent,
+					}),
+					None => return None,
+				},
+				event = self.ping_event_stream.next() => match event {
+					Some(PingEvent::Ping { peer, ping }) =>
+						return Some(DiscoveryEvent::Ping { peer, rtt: ping }),
+					None => return None,
+				},
+				event = mdns.fuse() => match event {
+					Some(MdnsEvent::Discovered(addresses)) =>
+						return Some(DiscoveryEvent::MdnsDiscovered { addresses }),
+					None => self.mdns_event_stream = None,
+				},
+			}
+		}
+	}
+}
+
+/// Check if `address` can be considered globally reachable.
+fn is_global(address: &Multiaddr) -> bool {
+	use libp2p::multiaddr::Protocol;
+
+	match address.iter().next() {
+		Some(Protocol::Ip4(ip)) =>
+			!(ip.is_private() || ip.is_loopback() || ip.is_link_local() || ip.is_unspecified()),
+		Some(Protocol::Ip6(ip)) => !(ip.is_loopback() || ip.is_unspecified()),
+		Some(Protocol::Dns(_)) | Some(Protocol::Dns4(_)) | Some(Protocol::Dns6(_)) => true,
+		_ => false,
+	}
+}
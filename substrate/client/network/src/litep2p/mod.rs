@@ -0,0 +1,720 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! `litep2p`-based network backend.
+//!
+//! The backend speaks the same wire protocols as the `rust-libp2p` based
+//! [`NetworkWorker`](crate::NetworkWorker) and reuses the backend-agnostic parts of
+//! `sc-network`: [`PeerStore`](crate::peer_store::PeerStore) for reputations,
+//! [`ProtocolController`] for slot allocation and
+//! [`NotificationService`](crate::service::traits::NotificationService) /
+//! [`IncomingRequest`](crate::request_responses::IncomingRequest) for the installed protocols.
+//! Nodes running either backend can therefore be mixed on the same network.
+
+use crate::{
+	config::{
+		FullNetworkConfiguration, MultiaddrWithPeerId, NetworkConfiguration, Params,
+		TransportConfig,
+	},
+	error::Error,
+	event::{DhtEvent, Event},
+	litep2p::{
+		discovery::{Discovery, DiscoveryEvent},
+		service::NetworkServiceCommand,
+		shim::{
+			notification::NotificationProtocol,
+			request_response::{OutboundRequestSenders, RequestResponseProtocol},
+		},
+	},
+	network_state::{
+		Endpoint as NetworkStateEndpoint, NetworkState,
+		NotConnectedPeer as NetworkStateNotConnectedPeer, Peer as NetworkStatePeer,
+		PeerEndpoint as NetworkStatePeerEndpoint,
+	},
+	peer_store::{PeerStoreHandle, PeerStoreProvider},
+	protocol::metrics as notification_metrics,
+	protocol_controller::{ProtoSetConfig, ProtocolController, SetId},
+	service::{
+		out_events,
+		traits::{NetworkBackend, NetworkService, NetworkStateInfo, NetworkStatus},
+	},
+	types::ProtocolName,
+	KademliaKey,
+};
+
+use codec::Encode;
+use futures::StreamExt;
+use libp2p::{identity::Keypair, multiaddr::Protocol, Multiaddr, PeerId};
+use litep2p::{
+	config::ConfigBuilder,
+	crypto::ed25519::{Keypair as Litep2pKeypair, SecretKey},
+	executor::Executor,
+	protocol::{
+		libp2p::kademlia::{QueryId, RecordKey},
+		notification::ConfigBuilder as NotificationConfigBuilder,
+		request_response::ConfigBuilder as RequestResponseConfigBuilder,
+	},
+	transport::{
+		tcp::config::Config as TcpTransportConfig,
+		websocket::config::Config as WebSocketTransportConfig, Endpoint,
+	},
+	types::protocol::ProtocolName as Litep2pProtocolName,
+	Litep2p, Litep2pEvent,
+};
+use log::{debug, info, trace, warn};
+use parking_lot::Mutex;
+use sc_network_common::{role::Roles, ExHashT};
+use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver};
+use sp_runtime::traits::Block as BlockT;
+
+use std::{
+	collections::{HashMap, HashSet},
+	future::Future,
+	io, iter,
+	pin::Pin,
+	sync::{atomic::AtomicUsize, Arc},
+	time::{Duration, Instant},
+};
+
+mod discovery;
+mod service;
+mod shim;
+
+pub use service::Litep2pNetworkService;
+
+/// Logging target for the file.
+const LOG_TARGET: &str = "sub-libp2p";
+
+/// Number of distinct peers that must observe the same address before it's considered external.
+const MIN_ADDRESS_CONFIRMATIONS: usize = 2;
+
+/// Convert `libp2p` [`PeerId`] to `litep2p` peer ID.
+pub(crate) fn to_litep2p_peer_id(peer: &PeerId) -> litep2p::PeerId {
+	litep2p::PeerId::from_bytes(&peer.to_bytes()).expect("`PeerId`s share the encoding; qed")
+}
+
+/// Convert `litep2p` peer ID to `libp2p` [`PeerId`].
+pub(crate) fn from_litep2p_peer_id(peer: &litep2p::PeerId) -> PeerId {
+	PeerId::from_bytes(&peer.to_bytes()).expect("`PeerId`s share the encoding; qed")
+}
+
+/// Executor for spawning the background tasks of `litep2p`.
+struct Litep2pExecutor {
+	/// Executor given to the backend in [`Params`].
+	executor: Mutex<Box<dyn Fn(Pin<Box<dyn Future<Output = ()> + Send>>) + Send>>,
+}
+
+impl Executor for Litep2pExecutor {
+	fn run(&self, future: Pin<Box<dyn Future<Output = ()> + Send>>) {
+		(self.executor.lock())(future)
+	}
+
+	fn run_with_name(&self, _: &'static str, future: Pin<Box<dyn Future<Output = ()> + Send>>) {
+		(self.executor.lock())(future)
+	}
+}
+
+/// Information about a peer, collected for [`NetworkState`].
+#[derive(Debug, Default)]
+struct PeerInfo {
+	/// Number of open connections to the peer.
+	num_connections: usize,
+
+	/// Endpoint of the latest connection.
+	endpoint: Option<NetworkStatePeerEndpoint>,
+
+	/// Agent version reported by Identify.
+	version_string: Option<String>,
+
+	/// Latest ping time.
+	latest_ping_time: Option<Duration>,
+
+	/// Known addresses of the peer.
+	known_addresses: HashSet<Multiaddr>,
+}
+
+/// Networking backend for `litep2p`.
+pub struct Litep2pNetworkBackend {
+	/// `litep2p` instance.
+	litep2p: Litep2p,
+
+	/// Peer discovery.
+	discovery: Discovery,
+
+	/// Commands from [`Litep2pNetworkService`].
+	cmd_rx: TracingUnboundedReceiver<NetworkServiceCommand>,
+
+	/// Handle to the network service.
+	network_service: Arc<Litep2pNetworkService>,
+
+	/// Listen addresses, shared with the network service.
+	listen_addresses: Arc<Mutex<HashSet<Multiaddr>>>,
+
+	/// External addresses, shared with the network service.
+	external_addresses: Arc<Mutex<HashSet<Multiaddr>>>,
+
+	/// Addresses of the local node observed by remote peers which haven't been confirmed yet.
+	observed_addresses: HashMap<Multiaddr, HashSet<PeerId>>,
+
+	/// Information about known peers.
+	peers: HashMap<PeerId, PeerInfo>,
+
+	/// Pending `GET_VALUE` queries.
+	pending_get_values: HashMap<QueryId, (KademliaKey, Instant)>,
+
+	/// Pending `PUT_VALUE` queries.
+	pending_put_values: HashMap<QueryId, (KademliaKey, Instant)>,
+
+	/// Senders for events that happen on the network.
+	event_streams: out_events::OutChannels,
+
+	/// Peer store handle.
+	peer_store_handle: PeerStoreHandle,
+}
+
+impl Litep2pNetworkBackend {
+	/// Convert the `NodeKeyConfig` keypair into a `litep2p` keypair.
+	fn litep2p_keypair(keypair: &Keypair) -> Result<Litep2pKeypair, Error> {
+		let secret = keypair
+			.clone()
+			.try_into_ed25519()
+			.map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "node key must be ed25519"))?
+			.secret();
+		let secret = SecretKey::from_bytes(&mut secret.as_ref().to_vec())
+			.map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error.to_string()))?;
+
+		Ok(Litep2pKeypair::from(secret))
+	}
+
+	/// Build the transport configurations from the listen addresses of the node.
+	fn transport_configs(
+		config: &NetworkConfiguration,
+	) -> Result<(TcpTransportConfig, WebSocketTransportConfig), Error> {
+		if let TransportConfig::MemoryOnly = config.transport {
+			return Err(io::Error::new(
+				io::ErrorKind::Unsupported,
+				"`litep2p` backend doesn't support the memory transport",
+			)
+			.into())
+		}
+
+		let (websocket, tcp): (Vec<_>, Vec<_>) =
+			config.listen_addresses.iter().cloned().partition(|address| {
+				address
+					.iter()
+					.any(|protocol| matches!(protocol, Protocol::Ws(_) | Protocol::Wss(_)))
+			});
+
+		let mut yamux_config = litep2p::yamux::Config::default();
+		if let Some(window_size) = config.yamux_window_size {
+			yamux_config.set_receive_window(window_size);
+		}
+
+		Ok((
+			TcpTransportConfig {
+				listen_addresses: tcp,
+				yamux_config: yamux_config.clone(),
+				..Default::default()
+			},
+			WebSocketTransportConfig {
+				listen_addresses: websocket,
+				yamux_config,
+				..Default::default()
+			},
+		))
+	}
+
+	/// Add known addresses of `peer`.
+	async fn add_known_addresses(&mut self, peer: PeerId, addresses: Vec<Multiaddr>) {
+		if peer == self.network_service.local_peer_id() || addresses.is_empty() {
+			return
+		}
+
+		self.litep2p
+			.add_known_address(to_litep2p_peer_id(&peer), addresses.clone().into_iter());
+		self.discovery
+			.add_known_peer(to_litep2p_peer_id(&peer), addresses.clone())
+			.await;
+		self.peer_store_handle.add_known_peer(peer);
+		self.peers.entry(peer).or_default().known_addresses.extend(addresses);
+	}
+
+	/// Build the current [`NetworkState`].
+	fn network_state(&self) -> NetworkState {
+		let mut connected_peers = HashMap::new();
+		let mut not_connected_peers = HashMap::new();
+
+		for (peer, info) in &self.peers {
+			match (info.num_connections, &info.endpoint) {
+				(0, _) | (_, None) => {
+					not_connected_peers.insert(
+						peer.to_base58(),
+						NetworkStateNotConnectedPeer {
+							known_addresses: info.known_addresses.clone(),
+							version_string: info.version_string.clone(),
+							latest_ping_time: info.latest_ping_time,
+						},
+					);
+				},
+				(_, Some(endpoint)) => {
+					connected_peers.insert(
+						peer.to_base58(),
+						NetworkStatePeer {
+							endpoint: endpoint.clone(),
+							version_string: info.version_string.clone(),
+							latest_ping_time: info.latest_ping_time,
+							known_addresses: info.known_addresses.clone(),
+						},
+					);
+				},
+			}
+		}
+
+		NetworkState {
+			peer_id: self.network_service.local_peer_id().to_base58(),
+			listened_addresses: self.listen_addresses.lock().clone(),
+			external_addresses: self.external_addresses.lock().clone(),
+			connected_peers,
+			not_connected_peers,
+			peerset: serde_json::json!(
+				"Unimplemented. See https://github.com/paritytech/substrate/issues/14160."
+			),
+		}
+	}
+
+	/// Handle a command from [`Litep2pNetworkService`].
+	async fn on_command(&mut self, command: NetworkServiceCommand) {
+		match command {
+			NetworkServiceCommand::GetValue { key } => {
+				let query_id = self.discovery.get_value(RecordKey::new(&key.to_vec())).await;
+				self.pending_get_values.insert(query_id, (key, Instant::now()));
+			},
+			NetworkServiceCommand::PutValue { key, value } => {
+				let query_id = self.discovery.put_value(RecordKey::new(&key.to_vec()), value).await;
+				self.pending_put_values.insert(query_id, (key, Instant::now()));
+			},
+			NetworkServiceCommand::EventStream { tx } => self.event_streams.push(tx),
+			NetworkServiceCommand::Status { tx } => {
+				let _ = tx.send(NetworkStatus {
+					num_connected_peers: self
+						.peers
+						.values()
+						.filter(|info| info.num_connections > 0)
+						.count(),
+					total_bytes_inbound: self.litep2p.bandwidth_sink().inbound() as u64,
+					total_bytes_outbound: self.litep2p.bandwidth_sink().outbound() as u64,
+				});
+			},
+			NetworkServiceCommand::NetworkState { tx } => {
+				let _ = tx.send(self.network_state());
+			},
+			NetworkServiceCommand::AddKnownAddress { peer, mut address } => {
+				if matches!(address.iter().last(), Some(Protocol::P2p(_))) {
+					address.pop();
+				}
+				self.add_known_addresses(peer, vec![address]).await;
+			},
+		}
+	}
+
+	/// Record an address of the local node observed by `peer`.
+	fn on_observed_address(&mut self, peer: PeerId, address: Multiaddr) {
+		let observers = self.observed_addresses.entry(address.clone()).or_default();
+		observers.insert(peer);
+
+		if observers.len() >= MIN_ADDRESS_CONFIRMATIONS &&
+			self.external_addresses.lock().insert(address.clone())
+		{
+			info!(target: LOG_TARGET, "🔍 Discovered new external address for our node: {address}");
+			self.observed_addresses.remove(&address);
+		}
+	}
+
+	/// Handle an event emitted by [`Discovery`].
+	async fn on_discovery_event(&mut self, event: DiscoveryEvent) {
+		match event {
+			DiscoveryEvent::Discovered { peers } =>
+				for (peer, addresses) in peers {
+					self.add_known_addresses(from_litep2p_peer_id(&peer), addresses).await;
+				},
+			DiscoveryEvent::RoutingTableUpdate { peers } =>
+				for peer in peers {
+					self.peer_store_handle.add_known_peer(from_litep2p_peer_id(&peer));
+				},
+			DiscoveryEvent::MdnsDiscovered { addresses } =>
+				for mut address in addresses {
+					let Some(Protocol::P2p(multihash)) = address.pop() else { continue };
+					let Ok(peer) = PeerId::from_multihash(multihash) else { continue };
+
+					self.add_known_addresses(peer, vec![address]).await;
+				},
+			DiscoveryEvent::Identified {
+				peer,
+				listen_addresses,
+				observed_address,
+				agent_version,
+				..
+			} => {
+				let peer = from_litep2p_peer_id(&peer);
+
+				self.peers.entry(peer).or_default().version_string = agent_version;
+				self.on_observed_address(peer, observed_address);
+				self.add_known_addresses(peer, listen_addresses).await;
+			},
+			DiscoveryEvent::Ping { peer, rtt } => {
+				self.peers.entry(from_litep2p_peer_id(&peer)).or_default().latest_ping_time =
+					Some(rtt);
+			},
+			DiscoveryEvent::GetRecordSuccess { query_id, record } => {
+				if let Some((key, started)) = self.pending_get_values.remove(&query_id) {
+					trace!(target: LOG_TARGET, "`GET_VALUE` for {key:?} took {:?}", started.elapsed());
+
+					self.event_streams.send(Event::Dht(DhtEvent::ValueFound(vec![(
+						KademliaKey::new(&record.key.to_vec()),
+						record.value,
+					)])));
+				}
+			},
+			DiscoveryEvent::PutRecordSuccess { query_id } => {
+				if let Some((key, started)) = self.pending_put_values.remove(&query_id) {
+					trace!(target: LOG_TARGET, "`PUT_VALUE` for {key:?} took {:?}", started.elapsed());
+
+					self.event_streams.send(Event::Dht(DhtEvent::ValuePut(key)));
+				}
+			},
+			DiscoveryEvent::QueryFailed { query_id } => {
+				if let Some((key, _)) = self.pending_get_values.remove(&query_id) {
+					self.event_streams.send(Event::Dht(DhtEvent::ValueNotFound(key)));
+				} else if let Some((key, _)) = self.pending_put_values.remove(&query_id) {
+					self.event_streams.send(Event::Dht(DhtEvent::ValuePutFailed(key)));
+				}
+			},
+		}
+	}
+
+	/// Handle an event emitted by `litep2p`.
+	fn on_litep2p_event(&mut self, event: Litep2pEvent) {
+		match event {
+			Litep2pEvent::ConnectionEstablished { peer, endpoint } => {
+				let info = self.peers.entry(from_litep2p_peer_id(&peer)).or_default();
+
+				info.num_connections += 1;
+				info.endpoint = Some(match endpoint {
+					Endpoint::Dialer { address, .. } =>
+						NetworkStatePeerEndpoint::Dialing(address, NetworkStateEndpoint::Dialer),
+					Endpoint::Listener { address, .. } => NetworkStatePeerEndpoint::Listening {
+						local_addr: Multiaddr::empty(),
+						send_back_addr: address,
+					},
+				});
+			},
+			Litep2pEvent::ConnectionClosed { peer, .. } => {
+				let peer = from_litep2p_peer_id(&peer);
+				let Some(info) = self.peers.get_mut(&peer) else { return };
+
+				info.num_connections = info.num_connections.saturating_sub(1);
+				if info.num_connections == 0 {
+					info.endpoint = None;
+					self.peer_store_handle.report_disconnect(peer);
+				}
+			},
+			Litep2pEvent::DialFailure { address, error } => {
+				debug!(target: LOG_TARGET, "failed to dial {address}: {error:?}");
+			},
+		}
+	}
+}
+
+#[async_trait::async_trait]
+impl<B: BlockT + 'static, H: ExHashT> NetworkBackend<B, H> for Litep2pNetworkBackend {
+	fn new(params: Params<B>) -> Result<Self, Error> {
+		let FullNetworkConfiguration {
+			notification_protocols,
+			request_response_protocols,
+			mut network_config,
+		} = params.network_config;
+
+		// Private and public keys configuration.
+		let keypair = network_config.node_key.clone().into_keypair()?;
+		let local_peer_id = keypair.public().to_peer_id();
+		let litep2p_keypair = Self::litep2p_keypair(&keypair)?;
+
+		network_config.boot_nodes.retain(|boot_node| boot_node.peer_id != local_peer_id);
+		network_config
+			.default_peers_set
+			.reserved_nodes
+			.retain(|reserved_node| reserved_node.peer_id != local_peer_id);
+
+		info!(target: LOG_TARGET, "🏷  Local node identity is: {}", local_peer_id.to_base58());
+		info!(target: LOG_TARGET, "Running litep2p network backend");
+
+		let (tcp_config, websocket_config) = Self::transport_configs(&network_config)?;
+		let mut config_builder = ConfigBuilder::new()
+			.with_keypair(litep2p_keypair)
+			.with_tcp(tcp_config)
+			.with_websocket(websocket_config);
+
+		let known_addresses: Vec<MultiaddrWithPeerId> = network_config
+			.boot_nodes
+			.iter()
+			.chain(network_config.default_peers_set.reserved_nodes.iter())
+			.chain(
+				notification_protocols
+					.iter()
+					.flat_map(|protocol| protocol.set_config().reserved_nodes.iter()),
+			)
+			.cloned()
+			.collect();
+
+		// Protocol controllers. The first set is always the block announcement protocol.
+		let sets = iter::once(&network_config.default_peers_set)
+			.chain(notification_protocols.iter().map(|protocol| protocol.set_config()));
+		let (protocol_handles, from_controllers): (Vec<_>, Vec<_>) = sets
+			.enumerate()
+			.map(|(set_id, set_config)| {
+				let (to_notifications, from_controller) =
+					tracing_unbounded("mpsc_protocol_controller_to_litep2p", 10_000);
+				let (handle, controller) = ProtocolController::new(
+					SetId::from(set_id),
+					ProtoSetConfig {
+						in_peers: set_config.in_peers,
+						out_peers: set_config.out_peers,
+						reserved_nodes: set_config
+							.reserved_nodes
+							.iter()
+							.map(|node| node.peer_id)
+							.collect(),
+						reserved_only: set_config.non_reserved_mode.is_reserved_only(),
+					},
+					to_notifications,
+					Box::new(params.peer_store.clone()),
+				);
+				(params.executor)(Box::pin(controller.run()));
+
+				(handle, from_controller)
+			})
+			.unzip();
+
+		// Notification protocols.
+		let roles = Roles::from(&params.role);
+		let metrics = params
+			.metrics_registry
+			.as_ref()
+			.and_then(|registry| notification_metrics::register(registry).ok());
+		let num_sync_peers = Arc::new(AtomicUsize::new(0));
+		let notification_protocol_ids: HashMap<ProtocolName, SetId> =
+			iter::once(&params.block_announce_config)
+				.chain(notification_protocols.iter())
+				.enumerate()
+				.map(|(index, protocol)| (protocol.protocol_name().clone(), SetId::from(index)))
+				.collect();
+
+		let notification_protocols = iter::once(params.block_announce_config)
+			.chain(notification_protocols)
+			.zip(protocol_handles.iter().cloned().zip(from_controllers))
+			.enumerate();
+		for (set_id, (protocol, (controller, from_controller))) in notification_protocols {
+			let protocol_name = protocol.protocol_name().clone();
+			let handshake = protocol
+				.handshake()
+				.as_ref()
+				.map_or_else(|| roles.encode(), |handshake| handshake.to_vec());
+			let (config, handle) = NotificationConfigBuilder::new(Litep2pProtocolName::from(
+				protocol_name.to_string(),
+			))
+			.with_max_size(protocol.max_notification_size() as usize)
+			.with_handshake(handshake)
+			.with_fallback_names(
+				protocol
+					.fallback_names()
+					.map(|name| Litep2pProtocolName::from(name.to_string()))
+					.collect(),
+			)
+			.with_auto_accept_inbound(true)
+			.build();
+			config_builder = config_builder.with_notification_protocol(config);
+
+			let (mut protocol_handle, command_stream) = protocol.take_protocol_handle().split();
+			protocol_handle.set_metrics(metrics.clone());
+
+			let num_peers =
+				if set_id == 0 { num_sync_peers.clone() } else { Arc::new(AtomicUsize::new(0)) };
+			let protocol = NotificationProtocol::new(
+				protocol_name,
+				handle,
+				protocol_handle,
+				command_stream,
+				controller,
+				from_controller,
+				params.peer_store.clone(),
+				num_peers,
+			);
+			(params.executor)(Box::pin(protocol.run()));
+		}
+
+		// Request-response protocols.
+		let mut request_senders = HashMap::new();
+		let mut request_receivers = Vec::new();
+		for config in request_response_protocols {
+			if request_senders.contains_key(&config.name) {
+				return Err(Error::DuplicateRequestResponseProtocol { protocol: config.name })
+			}
+
+			let (tx, rx) = tracing_unbounded("mpsc_litep2p_request_response", 10_000);
+			request_senders.insert(config.name.clone(), tx);
+			request_receivers.push((config, rx));
+		}
+		let request_senders: OutboundRequestSenders = Arc::new(request_senders);
+
+		for (config, request_rx) in request_receivers {
+			let (litep2p_config, handle) = RequestResponseConfigBuilder::new(
+				Litep2pProtocolName::from(config.name.to_string()),
+			)
+			.with_max_size(std::cmp::max(config.max_request_size, config.max_response_size) as usize)
+			.with_fallback_names(
+				config
+					.fallback_names
+					.iter()
+					.map(|name| Litep2pProtocolName::from(name.to_string()))
+					.collect(),
+			)
+			.with_timeout(config.request_timeout)
+			.build();
+			config_builder = config_builder.with_request_response_protocol(litep2p_config);
+
+			let protocol = RequestResponseProtocol::new(
+				config.name,
+				handle,
+				config.inbound_queue,
+				request_rx,
+				request_senders.clone(),
+				params.peer_store.clone(),
+			);
+			(params.executor)(Box::pin(protocol.run()));
+		}
+
+		// Discovery.
+		let mut known_peers = HashMap::<litep2p::PeerId, Vec<Multiaddr>>::new();
+		for node in &known_addresses {
+			known_peers
+				.entry(to_litep2p_peer_id(&node.peer_id))
+				.or_default()
+				.push(node.multiaddr.clone());
+		}
+		let (discovery, discovery_configs) = Discovery::new(
+			&network_config,
+			params.genesis_hash,
+			params.fork_id.as_deref(),
+			&params.protocol_id,
+			known_peers.clone(),
+		);
+		config_builder = config_builder
+			.with_libp2p_ping(discovery_configs.ping)
+			.with_libp2p_identify(discovery_configs.identify)
+			.with_libp2p_kademlia(discovery_configs.kademlia);
+		if let Some(mdns) = discovery_configs.mdns {
+			config_builder = config_builder.with_mdns(mdns);
+		}
+
+		let executor = Arc::new(Litep2pExecutor { executor: Mutex::new(params.executor) });
+		let mut litep2p = Litep2p::new(config_builder.with_executor(executor).build())?;
+
+		for (peer, addresses) in known_peers {
+			litep2p.add_known_address(peer, addresses.into_iter());
+		}
+
+		// `litep2p` reports its listen addresses with the local peer ID, `sc-network` without it.
+		let listen_addresses = Arc::new(Mutex::new(
+			litep2p
+				.listen_addresses()
+				.map(|address| {
+					address
+						.iter()
+						.filter(|protocol| !matches!(protocol, Protocol::P2p(_)))
+						.collect()
+				})
+				.collect::<HashSet<Multiaddr>>(),
+		));
+		let external_addresses = Arc::new(Mutex::new(
+			network_config.public_addresses.iter().cloned().collect::<HashSet<_>>(),
+		));
+		for address in listen_addresses.lock().iter() {
+			info!(target: LOG_TARGET, "📞 Listening on {address}");
+		}
+
+		let (cmd_tx, cmd_rx) = tracing_unbounded("mpsc_network_worker", 100_000);
+		let network_service = Arc::new(Litep2pNetworkService::new(
+			local_peer_id,
+			keypair,
+			cmd_tx,
+			params.peer_store.clone(),
+			protocol_handles,
+			notification_protocol_ids,
+			request_senders,
+			listen_addresses.clone(),
+			external_addresses.clone(),
+			num_sync_peers,
+		));
+
+		let mut peers = HashMap::<PeerId, PeerInfo>::new();
+		for node in known_addresses {
+			peers.entry(node.peer_id).or_default().known_addresses.insert(node.multiaddr);
+		}
+
+		Ok(Self {
+			litep2p,
+			discovery,
+			cmd_rx,
+			network_service,
+			listen_addresses,
+			external_addresses,
+			observed_addresses: HashMap::new(),
+			peers,
+			pending_get_values: HashMap::new(),
+			pending_put_values: HashMap::new(),
+			event_streams: out_events::OutChannels::new(params.metrics_registry.as_ref())?,
+			peer_store_handle: params.peer_store,
+		})
+	}
+
+	fn network_service(&self) -> Arc<dyn NetworkService> {
+		self.network_service.clone()
+	}
+
+	async fn run(mut self) {
+		loop {
+			tokio::select! {
+				command = self.cmd_rx.next() => match command {
+					Some(command) => self.on_command(command).await,
+					None => return,
+				},
+				event = self.discovery.next_event() => match event {
+					Some(event) => self.on_discovery_event(event).await,
+					None => return,
+				},
+				event = self.litep2p.next_event() => match event {
+					Some(event) => self.on_litep2p_event(event),
+					None => {
+						warn!(target: LOG_TARGET, "`litep2p` has terminated, shutting down the backend");
+						return
+					},
+				},
+			}
+		}
+	}
+}
@@ -0,0 +1,452 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! `NetworkService` implementation for the `litep2p` backend.
+
+use crate::{
+	config::MultiaddrWithPeerId,
+	event::Event,
+	litep2p::shim::request_response::{OutboundRequest, OutboundRequestSenders},
+	network_state::NetworkState,
+	peer_store::{PeerStoreHandle, PeerStoreProvider},
+	protocol_controller::{ProtocolHandle, SetId},
+	request_responses::{IfDisconnected, RequestFailure},
+	service::{
+		out_events,
+		signature::{Signature, SigningError},
+		traits::{
+			NetworkDHTProvider, NetworkEventStream, NetworkNotification, NetworkPeers,
+			NetworkRequest, NetworkSigner, NetworkStateInfo, NetworkStatus, NetworkStatusProvider,
+			NotificationSender as NotificationSenderT, NotificationSenderError,
+		},
+	},
+	types::ProtocolName,
+	KademliaKey, Keypair, OutboundFailure, ReputationChange,
+};
+
+use codec::DecodeAll;
+use futures::{channel::oneshot, stream::BoxStream};
+use libp2p::{multiaddr, Multiaddr, PeerId};
+use parking_lot::Mutex;
+use sc_network_common::role::{ObservedRole, Roles};
+use sc_utils::mpsc::TracingUnboundedSender;
+
+use std::{
+	collections::{HashMap, HashSet},
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	},
+};
+
+/// Logging target for the file.
+const LOG_TARGET: &str = "sub-libp2p";
+
+/// Commands sent by [`Litep2pNetworkService`] to
+/// [`Litep2pNetworkBackend`](super::Litep2pNetworkBackend).
+pub(crate) enum NetworkServiceCommand {
+	/// Get value from the DHT.
+	GetValue {
+		/// Record key.
+		key: KademliaKey,
+	},
+
+	/// Put value into the DHT.
+	PutValue {
+		/// Record key.
+		key: KademliaKey,
+
+		/// Record value.
+		value: Vec<u8>,
+	},
+
+	/// Subscribe to network events.
+	EventStream {
+		/// Sender for the events.
+		tx: out_events::Sender,
+	},
+
+	/// Query network status.
+	Status {
+		/// `oneshot::Sender` for sending the status.
+		tx: oneshot::Sender<NetworkStatus>,
+	},
+
+	/// Query network state.
+	NetworkState {
+		/// `oneshot::Sender` for sending the state.
+		tx: oneshot::Sender<NetworkState>,
+	},
+
+	/// Add known address for peer.
+	AddKnownAddress {
+		/// Peer ID.
+		peer: PeerId,
+
+		/// Address of the peer.
+		address: Multiaddr,
+	},
+}
+
+/// `NetworkService` for the `litep2p` backend.
+pub struct Litep2pNetworkService {
+	/// Local peer ID.
+	local_peer_id: PeerId,
+
+	/// Local identity, used for signing.
+	keypair: Keypair,
+
+	/// TX channel for sending commands to the backend.
+	cmd_tx: TracingUnboundedSender<NetworkServiceCommand>,
+
+	/// Handle to `PeerStore`.
+	peer_store_handle: PeerStoreHandle,
+
+	/// `ProtocolController` handles of the notification protocols, indexed by [`SetId`].
+	protocol_handles: Vec<ProtocolHandle>,
+
+	/// Notification protocol name to [`SetId`] mapping.
+	notification_protocol_ids: HashMap<ProtocolName, SetId>,
+
+	/// Senders for outbound requests of the request-response protocols.
+	request_senders: OutboundRequestSenders,
+
+	/// Listen addresses, updated by the backend.
+	listen_addresses: Arc<Mutex<HashSet<Multiaddr>>>,
+
+	/// External addresses, updated by the backend.
+	external_addresses: Arc<Mutex<HashSet<Multiaddr>>>,
+
+	/// Number of peers connected over the block announcement protocol.
+	num_sync_peers: Arc<AtomicUsize>,
+}
+
+impl Litep2pNetworkService {
+	/// Create new [`Litep2pNetworkService`].
+	pub(crate) fn new(
+		local_peer_id: PeerId,
+		keypair: Keypair,
+		cmd_tx: TracingUnboundedSender<NetworkServiceCommand>,
+		peer_store_handle: PeerStoreHandle,
+		protocol_handles: Vec<ProtocolHandle>,
+		notification_protocol_ids: HashMap<ProtocolName, SetId>,
+		request_senders: OutboundRequestSenders,
+		listen_addresses: Arc<Mutex<HashSet<Multiaddr>>>,
+		external_addresses: Arc<Mutex<HashSet<Multiaddr>>>,
+		num_sync_peers: Arc<AtomicUsize>,
+	) -> Self {
+		Self {
+			local_peer_id,
+			keypair,
+			cmd_tx,
+			peer_store_handle,
+			protocol_handles,
+			notification_protocol_ids,
+			request_senders,
+			listen_addresses,
+			external_addresses,
+			num_sync_peers,
+		}
+	}
+
+	/// Get the `ProtocolController` handle of `protocol`.
+	fn protocol_handle(&self, protocol: &ProtocolName) -> Result<&ProtocolHandle, String> {
+		self.notification_protocol_ids
+			.get(protocol)
+			.map(|set_id| &self.protocol_handles[usize::from(*set_id)])
+			.ok_or_else(|| format!("Unknown protocol: {protocol}"))
+	}
+
+	/// Extract `PeerId` from each `Multiaddr` and register the addresses with the backend.
+	///
+	/// Returns an `Err` if one of the given addresses is invalid or contains an
+	/// invalid peer ID (which includes the local peer ID).
+	fn add_addresses(&self, peers: HashSet<Multiaddr>) -> Result<HashSet<PeerId>, String> {
+		peers
+			.into_iter()
+			.map(|mut address| {
+				let peer = match address.pop() {
+					Some(multiaddr::Protocol::P2p(key)) => PeerId::from_multihash(key)
+						.map_err(|_| "Invalid PeerId format".to_string())?,
+					_ => return Err("Missing PeerId from address".to_string()),
+				};
+
+				// Make sure the local peer ID is never added to the PSM.
+				if peer == self.local_peer_id {
+					return Err("Local peer ID cannot be added as a reserved peer.".to_string())
+				}
+
+				if !address.is_empty() {
+					self.add_known_address(peer, address);
+				}
+
+				Ok(peer)
+			})
+			.collect()
+	}
+}
+
+impl NetworkSigner for Litep2pNetworkService {
+	fn sign_with_local_identity(&self, msg: Vec<u8>) -> Result<Signature, SigningError> {
+		Signature::sign_message(msg, &self.keypair)
+	}
+}
+
+impl NetworkDHTProvider for Litep2pNetworkService {
+	fn get_value(&self, key: &KademliaKey) {
+		let _ = self.cmd_tx.unbounded_send(NetworkServiceCommand::GetValue { key: key.clone() });
+	}
+
+	fn put_value(&self, key: KademliaKey, value: Vec<u8>) {
+		let _ = self.cmd_tx.unbounded_send(NetworkServiceCommand::PutValue { key, value });
+	}
+}
+
+#[async_trait::async_trait]
+impl NetworkStatusProvider for Litep2pNetworkService {
+	async fn status(&self) -> Result<NetworkStatus, ()> {
+		let (tx, rx) = oneshot::channel();
+		self.cmd_tx
+			.unbounded_send(NetworkServiceCommand::Status { tx })
+			.map_err(|_| ())?;
+
+		rx.await.map_err(|_| ())
+	}
+
+	async fn network_state(&self) -> Result<NetworkState, ()> {
+		let (tx, rx) = oneshot::channel();
+		self.cmd_tx
+			.unbounded_send(NetworkServiceCommand::NetworkState { tx })
+			.map_err(|_| ())?;
+
+		rx.await.map_err(|_| ())
+	}
+
+	async fn reserved_peers(&self) -> Result<Vec<PeerId>, ()> {
+		let (tx, rx) = oneshot::channel();
+		self.protocol_handles[0].reserved_peers(tx);
+
+		// The channel can only be closed if `ProtocolController` no longer exists.
+		rx.await.map_err(|_| ())
+	}
+}
+
+impl NetworkPeers for Litep2pNetworkService {
+	fn set_authorized_peers(&self, peers: HashSet<PeerId>) {
+		self.protocol_handles[0].set_reserved_peers(peers);
+	}
+
+	fn set_authorized_only(&self, reserved_only: bool) {
+		self.protocol_handles[0].set_reserved_only(reserved_only);
+	}
+
+	fn add_known_address(&self, peer: PeerId, address: Multiaddr) {
+		let _ = self
+			.cmd_tx
+			.unbounded_send(NetworkServiceCommand::AddKnownAddress { peer, address });
+	}
+
+	fn report_peer(&self, peer: PeerId, cost_benefit: ReputationChange) {
+		self.peer_store_handle.clone().report_peer(peer, cost_benefit);
+	}
+
+	fn peer_reputation(&self, peer_id: &PeerId) -> i32 {
+		self.peer_store_handle.peer_reputation(peer_id)
+	}
+
+	fn disconnect_peer(&self, peer: PeerId, protocol: ProtocolName) {
+		match self.protocol_handle(&protocol) {
+			Ok(handle) => handle.disconnect_peer(peer),
+			Err(error) => log::warn!(target: LOG_TARGET, "disconnect_peer(): {error}"),
+		}
+	}
+
+	fn accept_unreserved_peers(&self) {
+		self.protocol_handles[0].set_reserved_only(false);
+	}
+
+	fn deny_unreserved_peers(&self) {
+		self.protocol_handles[0].set_reserved_only(true);
+	}
+
+	fn add_reserved_peer(&self, peer: MultiaddrWithPeerId) -> Result<(), String> {
+		// Make sure the local peer ID is never added as a reserved peer.
+		if peer.peer_id == self.local_peer_id {
+			return Err("Local peer ID cannot be added as a reserved peer.".to_string())
+		}
+
+		self.add_known_address(peer.peer_id, peer.multiaddr);
+		self.protocol_handles[0].add_reserved_peer(peer.peer_id);
+		Ok(())
+	}
+
+	fn remove_reserved_peer(&self, peer: PeerId) {
+		self.protocol_handles[0].remove_reserved_peer(peer);
+	}
+
+	fn set_reserved_peers(
+		&self,
+		protocol: ProtocolName,
+		peers: HashSet<Multiaddr>,
+	) -> Result<(), String> {
+		let handle = self.protocol_handle(&protocol)?;
+		let peers = self.add_addresses(peers)?;
+
+		handle.set_reserved_peers(peers);
+		Ok(())
+	}
+
+	fn add_peers_to_reserved_set(
+		&self,
+		protocol: ProtocolName,
+		peers: HashSet<Multiaddr>,
+	) -> Result<(), String> {
+		let handle = self.protocol_handle(&protocol)?;
+
+		for peer in self.add_addresses(peers)? {
+			handle.add_reserved_peer(peer);
+		}
+		Ok(())
+	}
+
+	fn remove_peers_from_reserved_set(
+		&self,
+		protocol: ProtocolName,
+		peers: Vec<PeerId>,
+	) -> Result<(), String> {
+		let handle = self.protocol_handle(&protocol)?;
+
+		for peer in peers {
+			handle.remove_reserved_peer(peer);
+		}
+		Ok(())
+	}
+
+	fn sync_num_connected(&self) -> usize {
+		self.num_sync_peers.load(Ordering::Relaxed)
+	}
+
+	fn peer_role(&self, peer: PeerId, handshake: Vec<u8>) -> Option<ObservedRole> {
+		match Roles::decode_all(&mut &handshake[..]) {
+			Ok(role) => Some(role.into()),
+			Err(_) => {
+				log::debug!(target: LOG_TARGET, "handshake doesn't contain peer role: {handshake:?}");
+				self.peer_store_handle.peer_role(&peer)
+			},
+		}
+	}
+}
+
+impl NetworkEventStream for Litep2pNetworkService {
+	fn event_stream(&self, stream_name: &'static str) -> BoxStream<'static, Event> {
+		let (tx, rx) = out_events::channel(stream_name, 100_000);
+		let _ = self.cmd_tx.unbounded_send(NetworkServiceCommand::EventStream { tx });
+		Box::pin(rx)
+	}
+}
+
+impl NetworkStateInfo for Litep2pNetworkService {
+	fn external_addresses(&self) -> Vec<Multiaddr> {
+		self.external_addresses.lock().iter().cloned().collect()
+	}
+
+	fn listen_addresses(&self) -> Vec<Multiaddr> {
+		self.listen_addresses.lock().iter().cloned().collect()
+	}
+
+	fn local_peer_id(&self) -> PeerId {
+		self.local_peer_id
+	}
+}
+
+// Notifications are sent through the `NotificationService` of each protocol. The protocols are
+// owned by their handlers, so calls made through the network service are rejected instead.
+impl NetworkNotification for Litep2pNetworkService {
+	fn write_notification(&self, peer: PeerId, protocol: ProtocolName, _: Vec<u8>) {
+		log::warn!(
+			target: LOG_TARGET,
+			"dropping notification to {peer:?} over {protocol}: use `NotificationService` instead",
+		);
+	}
+
+	fn notification_sender(
+		&self,
+		_: PeerId,
+		protocol: ProtocolName,
+	) -> Result<Box<dyn NotificationSenderT>, NotificationSenderError> {
+		log::warn!(
+			target: LOG_TARGET,
+			"no notification sender for {protocol}: use `NotificationService` instead",
+		);
+		Err(NotificationSenderError::BadProtocol)
+	}
+
+	fn set_notification_handshake(&self, protocol: ProtocolName, _: Vec<u8>) {
+		log::warn!(
+			target: LOG_TARGET,
+			"ignoring handshake update for {protocol}: use `NotificationService` instead",
+		);
+	}
+}
+
+#[async_trait::async_trait]
+impl NetworkRequest for Litep2pNetworkService {
+	async fn request(
+		&self,
+		target: PeerId,
+		protocol: ProtocolName,
+		request: Vec<u8>,
+		fallback_request: Option<(Vec<u8>, ProtocolName)>,
+		connect: IfDisconnected,
+	) -> Result<(Vec<u8>, ProtocolName), RequestFailure> {
+		let (tx, rx) = oneshot::channel();
+
+		self.start_request(target, protocol, request, fallback_request, tx, connect);
+
+		match rx.await {
+			Ok(v) => v,
+			// The channel can only be closed if the request-response protocol no longer exists.
+			Err(_) => Err(RequestFailure::Network(OutboundFailure::ConnectionClosed)),
+		}
+	}
+
+	fn start_request(
+		&self,
+		peer: PeerId,
+		protocol: ProtocolName,
+		request: Vec<u8>,
+		fallback_request: Option<(Vec<u8>, ProtocolName)>,
+		sender: oneshot::Sender<Result<(Vec<u8>, ProtocolName), RequestFailure>>,
+		connect: IfDisconnected,
+	) {
+		match self.request_senders.get(&protocol) {
+			Some(tx) => {
+				let _ = tx.unbounded_send(OutboundRequest {
+					peer,
+					request,
+					fallback_request,
+					sender,
+					connect,
+				});
+			},
+			None => {
+				let _ = sender.send(Err(RequestFailure::UnknownProtocol));
+			},
+		}
+	}
+}
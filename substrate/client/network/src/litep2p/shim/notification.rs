@@ -0,0 +1,350 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Shim for `litep2p::NotificationHandle` which exposes it to the installed protocols through
+//! the same [`NotificationService`](crate::service::traits::NotificationService) the
+//! `libp2p` backend uses.
+//!
+//! Opening and closing substreams is driven by the
+//! [`ProtocolController`](crate::protocol_controller::ProtocolController) of the protocol,
+//! exactly like `Notifications` does it for the `libp2p` backend.
+
+use crate::{
+	litep2p::{from_litep2p_peer_id, to_litep2p_peer_id},
+	peer_store::{PeerStoreHandle, PeerStoreProvider},
+	protocol::{
+		NotificationCommand, NotificationsSink, NotificationsSinkMessage, ProtocolHandle,
+		ValidationCallResult,
+	},
+	protocol_controller::{self, IncomingIndex},
+	service::traits::{Direction, ValidationResult},
+	types::ProtocolName,
+};
+
+use futures::{
+	future::BoxFuture,
+	stream::{BoxStream, FuturesUnordered, SelectAll},
+	FutureExt, StreamExt,
+};
+use libp2p::PeerId;
+use litep2p::protocol::notification::{
+	Direction as Litep2pDirection, NotificationEvent, NotificationHandle,
+	ValidationResult as Litep2pValidationResult,
+};
+use sc_utils::mpsc::TracingUnboundedReceiver;
+
+use std::{
+	collections::{HashMap, HashSet},
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	},
+};
+
+/// Logging target for the file.
+const LOG_TARGET: &str = "sub-libp2p::notification";
+
+/// Message received from the sink of a connected peer.
+type SinkMessage = (PeerId, NotificationsSinkMessage);
+
+/// Wrapper over `litep2p`'s notification protocol handle.
+pub(crate) struct NotificationProtocol {
+	/// Protocol name.
+	protocol: ProtocolName,
+
+	/// `litep2p` notification handle.
+	handle: NotificationHandle,
+
+	/// Handle for reporting events to the installed protocol.
+	protocol_handle: ProtocolHandle,
+
+	/// Commands sent by the installed protocol.
+	command_stream: Box<dyn futures::Stream<Item = NotificationCommand> + Send + Unpin>,
+
+	/// Handle to the `ProtocolController` of the protocol.
+	controller: protocol_controller::ProtocolHandle,
+
+	/// Instructions from the `ProtocolController` of the protocol.
+	from_controller: TracingUnboundedReceiver<protocol_controller::Message>,
+
+	/// Peer store handle.
+	peer_store_handle: PeerStoreHandle,
+
+	/// Inbound substreams waiting for the installed protocol to validate them.
+	pending_validations:
+		FuturesUnordered<BoxFuture<'static, (PeerId, Result<ValidationResult, ()>)>>,
+
+	/// Inbound substreams accepted by the protocol and waiting for the `ProtocolController`.
+	pending_inbound: HashMap<IncomingIndex, PeerId>,
+
+	/// Next index allocated for an inbound substream.
+	next_index: u64,
+
+	/// Peers with an open substream.
+	peers: HashSet<PeerId>,
+
+	/// Messages written by the installed protocol into the [`NotificationsSink`] of a peer.
+	sinks: SelectAll<BoxStream<'static, SinkMessage>>,
+
+	/// Number of peers with an open substream, shared with the network service.
+	num_peers: Arc<AtomicUsize>,
+}
+
+impl NotificationProtocol {
+	/// Create new [`NotificationProtocol`].
+	pub(crate) fn new(
+		protocol: ProtocolName,
+		handle: NotificationHandle,
+		protocol_handle: ProtocolHandle,
+		command_stream: Box<dyn futures::Stream<Item = NotificationCommand> + Send + Unpin>,
+		controller: protocol_controller::ProtocolHandle,
+		from_controller: TracingUnboundedReceiver<protocol_controller::Message>,
+		peer_store_handle: PeerStoreHandle,
+		num_peers: Arc<AtomicUsize>,
+	) -> Self {
+		Self {
+			protocol,
+			handle,
+			protocol_handle,
+			command_stream,
+			controller,
+			from_controller,
+			peer_store_handle,
+			pending_validations: FuturesUnordered::new(),
+			pending_inbound: HashMap::new(),
+			next_index: 0u64,
+			peers: HashSet::new(),
+			sinks: SelectAll::new(),
+			num_peers,
+		}
+	}
+
+	/// Handle inbound substream which must be validated by the installed protocol.
+	fn on_validate_substream(&mut self, peer: PeerId, handshake: Vec<u8>) {
+		if self.peer_store_handle.is_banned(&peer) {
+			log::trace!(target: LOG_TARGET, "{}: rejecting banned peer {peer}", self.protocol);
+			self.handle
+				.send_validation_result(to_litep2p_peer_id(&peer), Litep2pValidationResult::Reject);
+			return
+		}
+
+		match self.protocol_handle.report_incoming_substream(peer, handshake) {
+			Ok(ValidationCallResult::WaitForValidation(rx)) => self
+				.pending_validations
+				.push(async move { (peer, rx.await.map_err(|_| ())) }.boxed()),
+			Ok(ValidationCallResult::Delegated) => self.on_protocol_validated(peer),
+			Err(()) => self
+				.handle
+				.send_validation_result(to_litep2p_peer_id(&peer), Litep2pValidationResult::Reject),
+		}
+	}
+
+	/// Installed protocol accepted the substream, ask `ProtocolController` for a slot.
+	fn on_protocol_validated(&mut self, peer: PeerId) {
+		let index = IncomingIndex(self.next_index);
+		self.next_index += 1;
+
+		self.pending_inbound.insert(index, peer);
+		self.controller.incoming_connection(peer, index);
+	}
+
+	/// Substream was opened with `peer`.
+	fn on_substream_opened(
+		&mut self,
+		peer: PeerId,
+		direction: Litep2pDirection,
+		handshake: Vec<u8>,
+		fallback: Option<ProtocolName>,
+	) {
+		let direction = match direction {
+			Litep2pDirection::Inbound => Direction::Inbound,
+			Litep2pDirection::Outbound => Direction::Outbound,
+		};
+		let (sink, async_rx, sync_rx) = NotificationsSink::new(peer);
+
+		self.sinks.push(async_rx.map(move |message| (peer, message)).boxed());
+		self.sinks.push(sync_rx.map(move |message| (peer, message)).boxed());
+
+		if self.peers.insert(peer) {
+			self.num_peers.fetch_add(1, Ordering::Relaxed);
+		}
+		let _ = self
+			.protocol_handle
+			.report_substream_opened(peer, direction, handshake, fallback, sink);
+	}
+
+	/// Substream to `peer` was closed.
+	fn on_substream_closed(&mut self, peer: PeerId) {
+		if !self.peers.remove(&peer) {
+			return
+		}
+
+		self.num_peers.fetch_sub(1, Ordering::Relaxed);
+		self.controller.dropped(peer);
+		let _ = self.protocol_handle.report_substream_closed(peer);
+	}
+
+	/// Handle a message sent by the installed protocol through the [`NotificationsSink`].
+	async fn on_sink_message(&mut self, peer: PeerId, message: NotificationsSinkMessage) {
+		if !self.peers.contains(&peer) {
+			return
+		}
+
+		match message {
+			NotificationsSinkMessage::Notification { message } => {
+				if let Err(error) =
+					self.handle.send_sync_notification(to_litep2p_peer_id(&peer), message)
+				{
+					log::debug!(
+						target: LOG_TARGET,
+						"{}: failed to send notification to {peer}: {error:?}",
+						self.protocol,
+					);
+				}
+			},
+			NotificationsSinkMessage::ForceClose => {
+				self.handle.close_substream(to_litep2p_peer_id(&peer)).await;
+			},
+		}
+	}
+
+	/// Handle an instruction from the `ProtocolController`.
+	async fn on_controller_message(&mut self, message: protocol_controller::Message) {
+		match message {
+			protocol_controller::Message::Connect { peer_id, .. } => {
+				if let Err(error) = self.handle.open_substream(to_litep2p_peer_id(&peer_id)).await {
+					log::trace!(
+						target: LOG_TARGET,
+						"{}: failed to open substream to {peer_id}: {error:?}",
+						self.protocol,
+					);
+					self.controller.dropped(peer_id);
+				}
+			},
+			protocol_controller::Message::Drop { peer_id, .. } => {
+				self.handle.close_substream(to_litep2p_peer_id(&peer_id)).await;
+			},
+			protocol_controller::Message::Accept(index) => {
+				if let Some(peer) = self.pending_inbound.remove(&index) {
+					self.handle.send_validation_result(
+						to_litep2p_peer_id(&peer),
+						Litep2pValidationResult::Accept,
+					);
+				}
+			},
+			protocol_controller::Message::Reject(index) => {
+				if let Some(peer) = self.pending_inbound.remove(&index) {
+					self.handle.send_validation_result(
+						to_litep2p_peer_id(&peer),
+						Litep2pValidationResult::Reject,
+					);
+				}
+			},
+		}
+	}
+
+	/// Handle a command from the installed protocol.
+	async fn on_command(&mut self, command: NotificationCommand) {
+		match command {
+			NotificationCommand::OpenSubstream(peer) => {
+				let _ = self.handle.open_substream(to_litep2p_peer_id(&peer)).await;
+			},
+			NotificationCommand::CloseSubstream(peer) => {
+				self.handle.close_substream(to_litep2p_peer_id(&peer)).await;
+			},
+			NotificationCommand::SetHandshake(handshake) => {
+				self.handle.set_handshake(handshake);
+			},
+		}
+	}
+
+	/// Handle an event emitted by `litep2p`.
+	async fn on_litep2p_event(&mut self, event: NotificationEvent) {
+		match event {
+			NotificationEvent::ValidateSubstream { peer, handshake, .. } => {
+				self.on_validate_substream(from_litep2p_peer_id(&peer), handshake);
+			},
+			NotificationEvent::NotificationStreamOpened {
+				peer,
+				direction,
+				handshake,
+				fallback,
+				..
+			} => {
+				self.on_substream_opened(
+					from_litep2p_peer_id(&peer),
+					direction,
+					handshake,
+					fallback.map(|protocol| ProtocolName::from(protocol.to_string())),
+				);
+			},
+			NotificationEvent::NotificationStreamClosed { peer } => {
+				self.on_substream_closed(from_litep2p_peer_id(&peer));
+			},
+			NotificationEvent::NotificationStreamOpenFailure { peer, error } => {
+				let peer = from_litep2p_peer_id(&peer);
+
+				log::trace!(
+					target: LOG_TARGET,
+					"{}: failed to open substream to {peer}: {error:?}",
+					self.protocol,
+				);
+				self.controller.dropped(peer);
+			},
+			NotificationEvent::NotificationReceived { peer, notification } => {
+				let _ = self
+					.protocol_handle
+					.report_notification_received(from_litep2p_peer_id(&peer), notification.into());
+			},
+		}
+	}
+
+	/// Run the event loop of the notification protocol.
+	pub(crate) async fn run(mut self) {
+		loop {
+			tokio::select! {
+				event = self.handle.next() => match event {
+					Some(event) => self.on_litep2p_event(event).await,
+					None => return,
+				},
+				message = self.from_controller.next() => match message {
+					Some(message) => self.on_controller_message(message).await,
+					None => return,
+				},
+				command = self.command_stream.next() => match command {
+					Some(command) => self.on_command(command).await,
+					None => return,
+				},
+				Some((peer, result)) = self.pending_validations.next(),
+					if !self.pending_validations.is_empty() =>
+				{
+					match result {
+						Ok(ValidationResult::Accept) => self.on_protocol_validated(peer),
+						Ok(ValidationResult::Reject) | Err(()) => self.handle.send_validation_result(
+							to_litep2p_peer_id(&peer),
+							Litep2pValidationResult::Reject,
+						),
+					}
+				},
+				Some((peer, message)) = self.sinks.next(), if !self.sinks.is_empty() => {
+					self.on_sink_message(peer, message).await;
+				},
+			}
+		}
+	}
+}
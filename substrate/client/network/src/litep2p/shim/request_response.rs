@@ -0,0 +1,301 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Shim for `litep2p::RequestResponseHandle` which forwards inbound requests to the
+//! `inbound_queue` of [`ProtocolConfig`](crate::request_responses::ProtocolConfig) and
+//! answers outbound requests made through [`NetworkRequest`](crate::NetworkRequest).
+
+use crate::{
+	litep2p::{from_litep2p_peer_id, to_litep2p_peer_id},
+	peer_store::{PeerStoreHandle, PeerStoreProvider},
+	request_responses::{IncomingRequest, OutgoingResponse, RequestFailure},
+	service::OutboundFailure,
+	types::ProtocolName,
+	IfDisconnected,
+};
+
+use futures::{
+	channel::oneshot, future::BoxFuture, stream::FuturesUnordered, FutureExt, StreamExt,
+};
+use libp2p::PeerId;
+use litep2p::{
+	protocol::request_response::{
+		DialOptions, RequestResponseError, RequestResponseEvent, RequestResponseHandle,
+	},
+	types::RequestId,
+};
+use sc_utils::mpsc::{TracingUnboundedReceiver, TracingUnboundedSender};
+
+use std::{collections::HashMap, sync::Arc};
+
+/// Logging target for the file.
+const LOG_TARGET: &str = "sub-libp2p::request-response";
+
+/// Outbound request sent by the network service to the protocol.
+pub(crate) struct OutboundRequest {
+	/// Peer ID of the remote.
+	pub(crate) peer: PeerId,
+
+	/// Request payload.
+	pub(crate) request: Vec<u8>,
+
+	/// Request to send over the fallback protocol if the remote doesn't support the
+	/// main protocol.
+	pub(crate) fallback_request: Option<(Vec<u8>, ProtocolName)>,
+
+	/// Channel for sending the response back to the caller.
+	pub(crate) sender: oneshot::Sender<Result<(Vec<u8>, ProtocolName), RequestFailure>>,
+
+	/// Whether the remote should be dialed if it's not connected.
+	pub(crate) connect: IfDisconnected,
+}
+
+/// Senders for outbound requests, indexed by protocol name.
+pub(crate) type OutboundRequestSenders =
+	Arc<HashMap<ProtocolName, TracingUnboundedSender<OutboundRequest>>>;
+
+/// Pending outbound request.
+struct PendingRequest {
+	/// Channel for sending the response back to the caller.
+	sender: oneshot::Sender<Result<(Vec<u8>, ProtocolName), RequestFailure>>,
+
+	/// Fallback request, if any.
+	fallback_request: Option<(Vec<u8>, ProtocolName)>,
+
+	/// Peer ID of the remote.
+	peer: PeerId,
+
+	/// Whether the remote should be dialed if it's not connected.
+	connect: IfDisconnected,
+}
+
+/// Response for an inbound request, as produced by the handler of the protocol.
+type PendingResponse = (PeerId, RequestId, Result<OutgoingResponse, oneshot::Canceled>);
+
+/// Wrapper over `litep2p`'s request-response protocol handle.
+pub(crate) struct RequestResponseProtocol {
+	/// Protocol name.
+	protocol: ProtocolName,
+
+	/// `litep2p` request-response handle.
+	handle: RequestResponseHandle,
+
+	/// Queue where inbound requests are forwarded, `None` if they are not answered.
+	inbound_queue: Option<async_channel::Sender<IncomingRequest>>,
+
+	/// Outbound requests sent by the network service.
+	request_rx: TracingUnboundedReceiver<OutboundRequest>,
+
+	/// Senders of all request-response protocols, used for fallback requests.
+	senders: OutboundRequestSenders,
+
+	/// Pending outbound requests.
+	pending_outbound: HashMap<RequestId, PendingRequest>,
+
+	/// Pending responses to inbound requests.
+	pending_inbound: FuturesUnordered<BoxFuture<'static, PendingResponse>>,
+
+	/// Peer store handle.
+	peer_store_handle: PeerStoreHandle,
+}
+
+impl RequestResponseProtocol {
+	/// Create new [`RequestResponseProtocol`].
+	pub(crate) fn new(
+		protocol: ProtocolName,
+		handle: RequestResponseHandle,
+		inbound_queue: Option<async_channel::Sender<IncomingRequest>>,
+		request_rx: TracingUnboundedReceiver<OutboundRequest>,
+		senders: OutboundRequestSenders,
+		peer_store_handle: PeerStoreHandle,
+	) -> Self {
+		Self {
+			protocol,
+			handle,
+			inbound_queue,
+			request_rx,
+			senders,
+			pending_outbound: HashMap::new(),
+			pending_inbound: FuturesUnordered::new(),
+			peer_store_handle,
+		}
+	}
+
+	/// Send `request` to `peer`.
+	async fn on_send_request(&mut self, request: OutboundRequest) {
+		let OutboundRequest { peer, request, fallback_request, sender, connect } = request;
+		let dial_options = match connect {
+			IfDisconnected::TryConnect => DialOptions::Dial,
+			IfDisconnected::ImmediateError => DialOptions::Reject,
+		};
+
+		match self.handle.send_request(to_litep2p_peer_id(&peer), request, dial_options).await {
+			Ok(request_id) => {
+				self.pending_outbound
+					.insert(request_id, PendingRequest { sender, fallback_request, peer, connect });
+			},
+			Err(error) => {
+				log::debug!(
+					target: LOG_TARGET,
+					"{}: failed to send request to {peer}: {error:?}",
+					self.protocol,
+				);
+				let _ = sender.send(Err(RequestFailure::Refused));
+			},
+		}
+	}
+
+	/// Handle inbound request from `peer`.
+	fn on_inbound_request(&mut self, peer: PeerId, request_id: RequestId, request: Vec<u8>) {
+		if self.peer_store_handle.is_banned(&peer) {
+			log::trace!(target: LOG_TARGET, "{}: rejecting banned peer {peer}", self.protocol);
+			self.handle.reject_request(request_id);
+			return
+		}
+
+		let Some(inbound_queue) = &self.inbound_queue else {
+			self.handle.reject_request(request_id);
+			return
+		};
+
+		let (tx, rx) = oneshot::channel();
+		match inbound_queue.try_send(IncomingRequest {
+			peer,
+			payload: request,
+			pending_response: tx,
+		}) {
+			Ok(()) =>
+				self.pending_inbound.push(async move { (peer, request_id, rx.await) }.boxed()),
+			Err(_) => {
+				log::debug!(
+					target: LOG_TARGET,
+					"{}: inbound queue full, rejecting request from {peer}",
+					self.protocol,
+				);
+				self.handle.reject_request(request_id);
+			},
+		}
+	}
+
+	/// Handle response produced by the protocol for an inbound request.
+	fn on_inbound_response(
+		&mut self,
+		peer: PeerId,
+		request_id: RequestId,
+		response: Result<OutgoingResponse, oneshot::Canceled>,
+	) {
+		let Ok(OutgoingResponse { result, reputation_changes, sent_feedback }) = response else {
+			self.handle.reject_request(request_id);
+			return
+		};
+
+		for change in reputation_changes {
+			self.peer_store_handle.report_peer(peer, change);
+		}
+
+		match result {
+			Ok(payload) => {
+				self.handle.send_response(request_id, payload);
+
+				if let Some(sent_feedback) = sent_feedback {
+					let _ = sent_feedback.send(());
+				}
+			},
+			Err(()) => self.handle.reject_request(request_id),
+		}
+	}
+
+	/// Handle failure of an outbound request.
+	fn on_request_failed(&mut self, request_id: RequestId, error: RequestResponseError) {
+		let Some(PendingRequest { sender, fallback_request, peer, connect }) =
+			self.pending_outbound.remove(&request_id)
+		else {
+			return
+		};
+
+		// retry over the fallback protocol if the remote doesn't support this one
+		if let (RequestResponseError::UnsupportedProtocol, Some((request, protocol))) =
+			(&error, fallback_request)
+		{
+			if let Some(fallback) = self.senders.get(&protocol) {
+				let _ = fallback.unbounded_send(OutboundRequest {
+					peer,
+					request,
+					fallback_request: None,
+					sender,
+					connect,
+				});
+				return
+			}
+
+			let _ = sender.send(Err(RequestFailure::UnknownProtocol));
+			return
+		}
+
+		let error = match error {
+			RequestResponseError::NotConnected => RequestFailure::NotConnected,
+			RequestResponseError::Rejected => RequestFailure::Refused,
+			RequestResponseError::Canceled => RequestFailure::Obsolete,
+			RequestResponseError::Timeout => RequestFailure::Network(OutboundFailure::Timeout),
+			RequestResponseError::UnsupportedProtocol =>
+				RequestFailure::Network(OutboundFailure::UnsupportedProtocols),
+			_ => RequestFailure::Network(OutboundFailure::ConnectionClosed),
+		};
+		let _ = sender.send(Err(error));
+	}
+
+	/// Handle an event emitted by `litep2p`.
+	fn on_litep2p_event(&mut self, event: RequestResponseEvent) {
+		match event {
+			RequestResponseEvent::RequestReceived { peer, request_id, request, .. } => {
+				self.on_inbound_request(from_litep2p_peer_id(&peer), request_id, request);
+			},
+			RequestResponseEvent::ResponseReceived { request_id, response, .. } => {
+				if let Some(PendingRequest { sender, .. }) =
+					self.pending_outbound.remove(&request_id)
+				{
+					let _ = sender.send(Ok((response, self.protocol.clone())));
+				}
+			},
+			RequestResponseEvent::RequestFailed { request_id, error, .. } => {
+				self.on_request_failed(request_id, error);
+			},
+		}
+	}
+
+	/// Run the event loop of the request-response protocol.
+	pub(crate) async fn run(mut self) {
+		loop {
+			tokio::select! {
+				event = self.handle.next() => match event {
+					Some(event) => self.on_litep2p_event(event),
+					None => return,
+				},
+				request = self.request_rx.next() => match request {
+					Some(request) => self.on_send_request(request).await,
+					None => return,
+				},
+				Some((peer, request_id, response)) = self.pending_inbound.next(),
+					if !self.pending_inbound.is_empty() =>
+				{
+					self.on_inbound_response(peer, request_id, response);
+				},
+			}
+		}
+	}
+}
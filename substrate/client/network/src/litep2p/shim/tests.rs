@@ -0,0 +1,485 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the shims, run against two `litep2p` instances connected over TCP.
+
+use super::{
+	notification::NotificationProtocol,
+	request_response::{OutboundRequest, OutboundRequestSenders, RequestResponseProtocol},
+};
+use crate::{
+	litep2p::from_litep2p_peer_id,
+	peer_store::{PeerStore, PeerStoreHandle, PeerStoreProvider},
+	protocol::notification_service,
+	protocol_controller::{ProtoSetConfig, ProtocolController, SetId},
+	request_responses::{IncomingRequest, OutgoingResponse, RequestFailure},
+	service::traits::{Direction, NotificationEvent, NotificationService, ValidationResult},
+	types::ProtocolName,
+	IfDisconnected, ReputationChange,
+};
+
+use assert_matches::assert_matches;
+use futures::channel::oneshot;
+use libp2p::PeerId;
+use litep2p::{
+	config::ConfigBuilder,
+	crypto::ed25519::Keypair,
+	protocol::{
+		notification::{ConfigBuilder as NotificationConfigBuilder, NotificationHandle},
+		request_response::{ConfigBuilder as RequestResponseConfigBuilder, RequestResponseHandle},
+	},
+	transport::tcp::config::Config as TcpTransportConfig,
+	types::protocol::ProtocolName as Litep2pProtocolName,
+	Litep2p, Litep2pEvent,
+};
+use sc_utils::mpsc::tracing_unbounded;
+
+use std::{
+	collections::{HashMap, HashSet},
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+const NOTIFICATION_PROTOCOL: &str = "/notif/1";
+const HANDSHAKE: [u8; 4] = [1, 3, 3, 7];
+
+/// Build a `litep2p` instance listening on a local TCP port.
+fn litep2p(configure: impl FnOnce(ConfigBuilder) -> ConfigBuilder) -> Litep2p {
+	let config =
+		ConfigBuilder::new()
+			.with_keypair(Keypair::generate())
+			.with_tcp(TcpTransportConfig {
+				listen_addresses: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+				..Default::default()
+			});
+
+	Litep2p::new(configure(config).build()).unwrap()
+}
+
+/// Connect `litep2p1` to `litep2p2` and drive both instances in the background.
+///
+/// Returns the peer IDs of the instances.
+async fn connect(mut litep2p1: Litep2p, mut litep2p2: Litep2p) -> (PeerId, PeerId) {
+	let peer1 = from_litep2p_peer_id(litep2p1.local_peer_id());
+	let peer2 = from_litep2p_peer_id(litep2p2.local_peer_id());
+
+	let address = litep2p2.listen_addresses().next().unwrap().clone();
+	litep2p1.dial_address(address).await.unwrap();
+
+	let (mut connected1, mut connected2) = (false, false);
+	while !connected1 || !connected2 {
+		tokio::select! {
+			event = litep2p1.next_event() =>
+				if let Some(Litep2pEvent::ConnectionEstablished { .. }) = event {
+					connected1 = true;
+				},
+			event = litep2p2.next_event() =>
+				if let Some(Litep2pEvent::ConnectionEstablished { .. }) = event {
+					connected2 = true;
+				},
+		}
+	}
+
+	run(litep2p1);
+	run(litep2p2);
+
+	(peer1, peer2)
+}
+
+/// Drive `litep2p` in the background.
+fn run(mut litep2p: Litep2p) {
+	tokio::spawn(async move { while litep2p.next_event().await.is_some() {} });
+}
+
+/// Create a peer store and drive it in the background.
+fn peer_store() -> PeerStoreHandle {
+	let peer_store = PeerStore::new(Vec::new());
+	let handle = peer_store.handle();
+	tokio::spawn(peer_store.run());
+
+	handle
+}
+
+/// `litep2p` configuration of the notification protocol.
+fn notification_config() -> (litep2p::protocol::notification::Config, NotificationHandle) {
+	NotificationConfigBuilder::new(Litep2pProtocolName::from(NOTIFICATION_PROTOCOL))
+		.with_max_size(1024)
+		.with_handshake(HANDSHAKE.to_vec())
+		.with_auto_accept_inbound(true)
+		.build()
+}
+
+/// Run the notification shim over `handle`, returning the service of the installed protocol and
+/// the number of peers with an open substream.
+fn notification_protocol(
+	handle: NotificationHandle,
+	reserved_nodes: HashSet<PeerId>,
+	peer_store: PeerStoreHandle,
+) -> (Box<dyn NotificationService>, Arc<AtomicUsize>) {
+	let (protocol_handle_pair, service) = notification_service(NOTIFICATION_PROTOCOL.into());
+	let (protocol_handle, command_stream) = protocol_handle_pair.split();
+
+	let (to_notifications, from_controller) = tracing_unbounded("mpsc_test_controller", 10_000);
+	let (controller_handle, controller) = ProtocolController::new(
+		SetId::from(0usize),
+		ProtoSetConfig { in_peers: 1, out_peers: 1, reserved_nodes, reserved_only: false },
+		to_notifications,
+		Box::new(peer_store.clone()),
+	);
+	tokio::spawn(controller.run());
+
+	let num_peers = Arc::new(AtomicUsize::new(0));
+	let protocol = NotificationProtocol::new(
+		NOTIFICATION_PROTOCOL.into(),
+		handle,
+		protocol_handle,
+		command_stream,
+		controller_handle,
+		from_controller,
+		peer_store,
+		num_peers.clone(),
+	);
+	tokio::spawn(protocol.run());
+
+	(service, num_peers)
+}
+
+/// Connect two nodes running the notification shim, where the first node has the second one as
+/// reserved node and thus opens a substream to it.
+///
+/// If `ban_peer1` is set, the second node bans the first one. The peer store of the second node is
+/// returned along with the services.
+async fn notification_nodes(
+	ban_peer1: bool,
+) -> (
+	PeerId,
+	Box<dyn NotificationService>,
+	Arc<AtomicUsize>,
+	PeerId,
+	Box<dyn NotificationService>,
+	PeerStoreHandle,
+) {
+	let (config1, handle1) = notification_config();
+	let (config2, handle2) = notification_config();
+	let litep2p1 = litep2p(|builder| builder.with_notification_protocol(config1));
+	let litep2p2 = litep2p(|builder| builder.with_notification_protocol(config2));
+	let (peer1, peer2) = connect(litep2p1, litep2p2).await;
+
+	let mut peer_store2 = peer_store();
+	if ban_peer1 {
+		peer_store2.report_peer(peer1, ReputationChange::new_fatal("banned"));
+	}
+
+	let (service1, num_peers1) =
+		notification_protocol(handle1, HashSet::from([peer2]), peer_store());
+	let (service2, _) = notification_protocol(handle2, HashSet::new(), peer_store2.clone());
+
+	(peer1, service1, num_peers1, peer2, service2, peer_store2)
+}
+
+/// Accept the inbound substream from `peer` and wait until it's opened.
+async fn accept_substream(service: &mut Box<dyn NotificationService>, peer: PeerId) {
+	match service.next_event().await {
+		Some(NotificationEvent::ValidateInboundSubstream {
+			peer: remote,
+			handshake,
+			result_tx,
+		}) => {
+			assert_eq!(remote, peer);
+			assert_eq!(handshake, HANDSHAKE.to_vec());
+			result_tx.send(ValidationResult::Accept).unwrap();
+		},
+		event => panic!("unexpected event: {event:?}"),
+	}
+
+	match service.next_event().await {
+		Some(NotificationEvent::NotificationStreamOpened { peer: remote, direction, .. }) => {
+			assert_eq!(remote, peer);
+			assert_eq!(direction, Direction::Inbound);
+		},
+		event => panic!("unexpected event: {event:?}"),
+	}
+}
+
+#[tokio::test]
+async fn notification_substream_opened_to_reserved_node() {
+	let (peer1, mut service1, num_peers1, peer2, mut service2, _) = notification_nodes(false).await;
+
+	accept_substream(&mut service2, peer1).await;
+	match service1.next_event().await {
+		Some(NotificationEvent::NotificationStreamOpened {
+			peer,
+			direction,
+			handshake,
+			negotiated_fallback,
+		}) => {
+			assert_eq!(peer, peer2);
+			assert_eq!(direction, Direction::Outbound);
+			assert_eq!(handshake, HANDSHAKE.to_vec());
+			assert_eq!(negotiated_fallback, None);
+		},
+		event => panic!("unexpected event: {event:?}"),
+	}
+	assert_eq!(num_peers1.load(Ordering::Relaxed), 1);
+
+	// notifications are sent through the sink of the peer.
+	service1.send_sync_notification(&peer2, vec![1, 2, 3]);
+	match service2.next_event().await {
+		Some(NotificationEvent::NotificationReceived { peer, notification }) => {
+			assert_eq!(peer, peer1);
+			assert_eq!(notification, vec![1, 2, 3]);
+		},
+		event => panic!("unexpected event: {event:?}"),
+	}
+}
+
+#[tokio::test]
+async fn notification_substream_closed_by_remote() {
+	let (peer1, mut service1, num_peers1, peer2, mut service2, mut peer_store2) =
+		notification_nodes(false).await;
+
+	accept_substream(&mut service2, peer1).await;
+	assert!(matches!(
+		service1.next_event().await,
+		Some(NotificationEvent::NotificationStreamOpened { .. })
+	));
+
+	// banning the peer makes the protocol controller of the second node disconnect it.
+	peer_store2.report_peer(peer1, ReputationChange::new_fatal("banned"));
+	match service1.next_event().await {
+		Some(NotificationEvent::NotificationStreamClosed { peer }) => assert_eq!(peer, peer2),
+		event => panic!("unexpected event: {event:?}"),
+	}
+	assert_eq!(num_peers1.load(Ordering::Relaxed), 0);
+}
+
+#[tokio::test]
+async fn notification_substream_rejected_by_protocol() {
+	let (peer1, mut service1, num_peers1, _peer2, mut service2, _) =
+		notification_nodes(false).await;
+
+	match service2.next_event().await {
+		Some(NotificationEvent::ValidateInboundSubstream { peer, result_tx, .. }) => {
+			assert_eq!(peer, peer1);
+			result_tx.send(ValidationResult::Reject).unwrap();
+		},
+		event => panic!("unexpected event: {event:?}"),
+	}
+
+	assert!(tokio::time::timeout(Duration::from_secs(2), service1.next_event())
+		.await
+		.is_err());
+	assert_eq!(num_peers1.load(Ordering::Relaxed), 0);
+}
+
+#[tokio::test]
+async fn notification_substream_from_banned_peer_rejected() {
+	let (_peer1, mut service1, num_peers1, _peer2, mut service2, _) =
+		notification_nodes(true).await;
+
+	// the installed protocol isn't asked to validate the substream.
+	assert!(tokio::time::timeout(Duration::from_secs(2), service2.next_event())
+		.await
+		.is_err());
+	assert!(tokio::time::timeout(Duration::from_secs(1), service1.next_event())
+		.await
+		.is_err());
+	assert_eq!(num_peers1.load(Ordering::Relaxed), 0);
+}
+
+/// `litep2p` configuration of a request-response protocol.
+fn request_response_config(
+	protocol: &str,
+) -> (litep2p::protocol::request_response::Config, RequestResponseHandle) {
+	RequestResponseConfigBuilder::new(Litep2pProtocolName::from(protocol.to_string()))
+		.with_max_size(1024)
+		.with_timeout(Duration::from_secs(5))
+		.build()
+}
+
+/// Run the request-response shims over `protocols`, returning the senders for outbound requests.
+fn request_response_protocols(
+	protocols: Vec<(RequestResponseHandle, &str, Option<async_channel::Sender<IncomingRequest>>)>,
+	peer_store: PeerStoreHandle,
+) -> OutboundRequestSenders {
+	let (senders, receivers): (HashMap<_, _>, Vec<_>) = protocols
+		.into_iter()
+		.map(|(handle, protocol, inbound_queue)| {
+			let protocol = ProtocolName::from(protocol.to_string());
+			let (tx, rx) = tracing_unbounded("mpsc_test_request_response", 10_000);
+			((protocol.clone(), tx), (protocol, handle, inbound_queue, rx))
+		})
+		.unzip();
+	let senders = Arc::new(senders);
+
+	for (protocol, handle, inbound_queue, request_rx) in receivers {
+		let protocol = RequestResponseProtocol::new(
+			protocol,
+			handle,
+			inbound_queue,
+			request_rx,
+			senders.clone(),
+			peer_store.clone(),
+		);
+		tokio::spawn(protocol.run());
+	}
+
+	senders
+}
+
+/// Answer every inbound request with `response`.
+fn respond_with(
+	inbound_rx: async_channel::Receiver<IncomingRequest>,
+	response: Vec<u8>,
+	reputation_changes: Vec<ReputationChange>,
+	sent_feedback: Option<oneshot::Sender<()>>,
+) {
+	tokio::spawn(async move {
+		let mut sent_feedback = sent_feedback;
+		while let Ok(IncomingRequest { pending_response, .. }) = inbound_rx.recv().await {
+			let _ = pending_response.send(OutgoingResponse {
+				result: Ok(response.clone()),
+				reputation_changes: reputation_changes.clone(),
+				sent_feedback: sent_feedback.take(),
+			});
+		}
+	});
+}
+
+/// Send `request` over `protocol` and wait for the response.
+async fn send_request(
+	senders: &OutboundRequestSenders,
+	protocol: &str,
+	peer: PeerId,
+	request: Vec<u8>,
+	fallback_request: Option<(Vec<u8>, ProtocolName)>,
+	connect: IfDisconnected,
+) -> Result<(Vec<u8>, ProtocolName), RequestFailure> {
+	let (sender, rx) = oneshot::channel();
+	senders[&ProtocolName::from(protocol.to_string())]
+		.unbounded_send(OutboundRequest { peer, request, fallback_request, sender, connect })
+		.unwrap();
+
+	rx.await.unwrap()
+}
+
+#[tokio::test]
+async fn request_answered_by_remote() {
+	let (config1, handle1) = request_response_config("/req/1");
+	let (config2, handle2) = request_response_config("/req/1");
+	let litep2p1 = litep2p(|builder| builder.with_request_response_protocol(config1));
+	let litep2p2 = litep2p(|builder| builder.with_request_response_protocol(config2));
+	let (peer1, peer2) = connect(litep2p1, litep2p2).await;
+
+	let peer_store2 = peer_store();
+	let (inbound_tx, inbound_rx) = async_channel::bounded(16);
+	let (feedback_tx, feedback_rx) = oneshot::channel();
+	respond_with(
+		inbound_rx,
+		b"pong".to_vec(),
+		vec![ReputationChange::new(-10, "test")],
+		Some(feedback_tx),
+	);
+	request_response_protocols(vec![(handle2, "/req/1", Some(inbound_tx))], peer_store2.clone());
+	let senders = request_response_protocols(vec![(handle1, "/req/1", None)], peer_store());
+
+	let response =
+		send_request(&senders, "/req/1", peer2, b"ping".to_vec(), None, IfDisconnected::TryConnect)
+			.await;
+	assert_matches!(response, Ok((response, protocol)) if response == b"pong" && protocol == "/req/1".into());
+
+	// the feedback is sent and the reputation changes are applied once the response is sent.
+	feedback_rx.await.unwrap();
+	assert!(peer_store2.peer_reputation(&peer1) < 0);
+}
+
+#[tokio::test]
+async fn request_from_banned_peer_refused() {
+	let (config1, handle1) = request_response_config("/req/1");
+	let (config2, handle2) = request_response_config("/req/1");
+	let litep2p1 = litep2p(|builder| builder.with_request_response_protocol(config1));
+	let litep2p2 = litep2p(|builder| builder.with_request_response_protocol(config2));
+	let (peer1, peer2) = connect(litep2p1, litep2p2).await;
+
+	let mut peer_store2 = peer_store();
+	peer_store2.report_peer(peer1, ReputationChange::new_fatal("banned"));
+	let (inbound_tx, inbound_rx) = async_channel::bounded(16);
+	respond_with(inbound_rx, b"pong".to_vec(), Vec::new(), None);
+	request_response_protocols(vec![(handle2, "/req/1", Some(inbound_tx))], peer_store2);
+	let senders = request_response_protocols(vec![(handle1, "/req/1", None)], peer_store());
+
+	let response =
+		send_request(&senders, "/req/1", peer2, b"ping".to_vec(), None, IfDisconnected::TryConnect)
+			.await;
+	assert_matches!(response, Err(RequestFailure::Refused));
+}
+
+#[tokio::test]
+async fn request_to_disconnected_peer_fails() {
+	let (config, handle) = request_response_config("/req/1");
+	run(litep2p(|builder| builder.with_request_response_protocol(config)));
+	let senders = request_response_protocols(vec![(handle, "/req/1", None)], peer_store());
+
+	let response = send_request(
+		&senders,
+		"/req/1",
+		PeerId::random(),
+		b"ping".to_vec(),
+		None,
+		IfDisconnected::ImmediateError,
+	)
+	.await;
+	assert_matches!(response, Err(RequestFailure::NotConnected));
+}
+
+#[tokio::test]
+async fn request_sent_over_fallback_protocol() {
+	let (config1, handle1) = request_response_config("/req/2");
+	let (fallback_config1, fallback_handle1) = request_response_config("/req/1");
+	let (config2, handle2) = request_response_config("/req/1");
+	let litep2p1 = litep2p(|builder| {
+		builder
+			.with_request_response_protocol(config1)
+			.with_request_response_protocol(fallback_config1)
+	});
+	let litep2p2 = litep2p(|builder| builder.with_request_response_protocol(config2));
+	let (_peer1, peer2) = connect(litep2p1, litep2p2).await;
+
+	// the remote only supports the fallback protocol.
+	let (inbound_tx, inbound_rx) = async_channel::bounded(16);
+	respond_with(inbound_rx, b"pong".to_vec(), Vec::new(), None);
+	request_response_protocols(vec![(handle2, "/req/1", Some(inbound_tx))], peer_store());
+	let senders = request_response_protocols(
+		vec![(handle1, "/req/2", None), (fallback_handle1, "/req/1", None)],
+		peer_store(),
+	);
+
+	let response = send_request(
+		&senders,
+		"/req/2",
+		peer2,
+		b"ping".to_vec(),
+		Some((b"ping".to_vec(), ProtocolName::from("/req/1"))),
+		IfDisconnected::TryConnect,
+	)
+	.await;
+	assert_matches!(response, Ok((response, protocol)) if response == b"pong" && protocol == "/req/1".into());
+}
@@ -43,9 +43,11 @@ use sp_runtime::traits::Block as BlockT;
 
 use std::{collections::HashSet, iter, task::Poll};
 
-use notifications::{metrics, Notifications, NotificationsOut};
+use notifications::{Notifications, NotificationsOut};
 
-pub(crate) use notifications::ProtocolHandle;
+pub(crate) use notifications::{
+	metrics, NotificationCommand, NotificationsSinkMessage, ProtocolHandle, ValidationCallResult,
+};
 
 pub use notifications::{
 	notification_service, NotificationsSink, NotifsHandlerError, ProtocolHandlePair, Ready,
@@ -43,8 +43,9 @@ use crate::{
 	service::{
 		signature::{Signature, SigningError},
 		traits::{
-			NetworkDHTProvider, NetworkEventStream, NetworkNotification, NetworkPeers,
-			NetworkRequest, NetworkSigner, NetworkStateInfo, NetworkStatus, NetworkStatusProvider,
+			NetworkBackend, NetworkDHTProvider, NetworkEventStream, NetworkNotification,
+			NetworkPeers, NetworkRequest, NetworkService as NetworkServiceT, NetworkSigner,
+			NetworkStateInfo, NetworkStatus, NetworkStatusProvider,
 			NotificationSender as NotificationSenderT, NotificationSenderError,
 			NotificationSenderReady as NotificationSenderReadyT,
 		},
@@ -101,7 +102,7 @@ pub use libp2p::identity::{DecodingError, Keypair, PublicKey};
 pub use protocol::NotificationsSink;
 
 mod metrics;
-mod out_events;
+pub(crate) mod out_events;
 
 pub mod signature;
 pub mod traits;
@@ -711,38 +712,6 @@ where
 }
 
 impl<B: BlockT + 'static, H: ExHashT> NetworkService<B, H> {
-	/// Get network state.
-	///
-	/// **Note**: Use this only for debugging. This API is unstable. There are warnings literally
-	/// everywhere about this. Please don't use this function to retrieve actual information.
-	///
-	/// Returns an error if the `NetworkWorker` is no longer running.
-	pub async fn network_state(&self) -> Result<NetworkState, ()> {
-		let (tx, rx) = oneshot::channel();
-
-		let _ = self
-			.to_worker
-			.unbounded_send(ServiceToWorkerMsg::NetworkState { pending_response: tx });
-
-		match rx.await {
-			Ok(v) => v.map_err(|_| ()),
-			// The channel can only be closed if the network worker no longer exists.
-			Err(_) => Err(()),
-		}
-	}
-
-	/// Get the list of reserved peers.
-	///
-	/// Returns an error if the `NetworkWorker` is no longer running.
-	pub async fn reserved_peers(&self) -> Result<Vec<PeerId>, ()> {
-		let (tx, rx) = oneshot::channel();
-
-		self.sync_protocol_handle.reserved_peers(tx);
-
-		// The channel can only be closed if `ProtocolController` no longer exists.
-		rx.await.map_err(|_| ())
-	}
-
 	/// Utility function to extract `PeerId` from each `Multiaddr` for peer set updates.
 	///
 	/// Returns an `Err` if one of the given addresses is invalid or contains an
@@ -798,8 +767,8 @@ where
 	B: sp_runtime::traits::Block,
 	H: ExHashT,
 {
-	fn sign_with_local_identity(&self, msg: impl AsRef<[u8]>) -> Result<Signature, SigningError> {
-		Signature::sign_message(msg.as_ref(), &self.local_identity)
+	fn sign_with_local_identity(&self, msg: Vec<u8>) -> Result<Signature, SigningError> {
+		Signature::sign_message(msg, &self.local_identity)
 	}
 }
 
@@ -844,6 +813,29 @@ where
 			Err(_) => Err(()),
 		}
 	}
+
+	async fn network_state(&self) -> Result<NetworkState, ()> {
+		let (tx, rx) = oneshot::channel();
+
+		let _ = self
+			.to_worker
+			.unbounded_send(ServiceToWorkerMsg::NetworkState { pending_response: tx });
+
+		match rx.await {
+			Ok(v) => v.map_err(|_| ()),
+			// The channel can only be closed if the network worker no longer exists.
+			Err(_) => Err(()),
+		}
+	}
+
+	async fn reserved_peers(&self) -> Result<Vec<PeerId>, ()> {
+		let (tx, rx) = oneshot::channel();
+
+		self.sync_protocol_handle.reserved_peers(tx);
+
+		// The channel can only be closed if `ProtocolController` no longer exists.
+		rx.await.map_err(|_| ())
+	}
 }
 
 impl<B, H> NetworkPeers for NetworkService<B, H>
@@ -1214,6 +1206,25 @@ where
 	_block: PhantomData<B>,
 }
 
+#[async_trait::async_trait]
+impl<B, H> NetworkBackend<B, H> for NetworkWorker<B, H>
+where
+	B: BlockT + 'static,
+	H: ExHashT,
+{
+	fn new(params: Params<B>) -> Result<Self, Error> {
+		NetworkWorker::new(params)
+	}
+
+	fn network_service(&self) -> Arc<dyn NetworkServiceT> {
+		self.service.clone()
+	}
+
+	async fn run(self) {
+		NetworkWorker::run(self).await
+	}
+}
+
 impl<B, H> NetworkWorker<B, H>
 where
 	B: BlockT + 'static,
@@ -21,9 +21,10 @@
 //! Traits defined by `sc-network`.
 
 use crate::{
-	config::MultiaddrWithPeerId,
+	config::{MultiaddrWithPeerId, Params},
 	error,
 	event::Event,
+	network_state::NetworkState,
 	request_responses::{IfDisconnected, RequestFailure},
 	service::signature::Signature,
 	types::ProtocolName,
@@ -33,16 +34,69 @@ use crate::{
 use futures::{channel::oneshot, Stream};
 use libp2p::{Multiaddr, PeerId};
 
-use sc_network_common::role::ObservedRole;
+use sc_network_common::{role::ObservedRole, ExHashT};
+use sp_runtime::traits::Block as BlockT;
 
 use std::{collections::HashSet, fmt::Debug, future::Future, pin::Pin, sync::Arc};
 
 pub use libp2p::{identity::SigningError, kad::record::Key as KademliaKey};
 
+/// Networking backend.
+///
+/// Implemented by the libp2p-based [`NetworkWorker`](crate::NetworkWorker) and by the
+/// litep2p-based [`Litep2pNetworkBackend`](crate::litep2p::Litep2pNetworkBackend). Both speak
+/// the same wire protocols, so nodes running different backends interoperate.
+#[async_trait::async_trait]
+pub trait NetworkBackend<B: BlockT + 'static, H: ExHashT>: Send + 'static {
+	/// Create new `NetworkBackend`.
+	fn new(params: Params<B>) -> Result<Self, error::Error>
+	where
+		Self: Sized;
+
+	/// Get handle to [`NetworkService`] of the `NetworkBackend`.
+	fn network_service(&self) -> Arc<dyn NetworkService>;
+
+	/// Start the event loop of the `NetworkBackend`.
+	///
+	/// The future only resolves once the backend has shut down.
+	async fn run(self);
+}
+
+/// Object-safe view over every service exposed by a [`NetworkBackend`].
+pub trait NetworkService:
+	NetworkSigner
+	+ NetworkDHTProvider
+	+ NetworkStatusProvider
+	+ NetworkPeers
+	+ NetworkEventStream
+	+ NetworkStateInfo
+	+ NetworkNotification
+	+ NetworkRequest
+	+ Send
+	+ Sync
+	+ 'static
+{
+}
+
+impl<T> NetworkService for T where
+	T: NetworkSigner
+		+ NetworkDHTProvider
+		+ NetworkStatusProvider
+		+ NetworkPeers
+		+ NetworkEventStream
+		+ NetworkStateInfo
+		+ NetworkNotification
+		+ NetworkRequest
+		+ Send
+		+ Sync
+		+ 'static
+{
+}
+
 /// Signer with network identity
 pub trait NetworkSigner {
 	/// Signs the message with the `KeyPair` that defines the local [`PeerId`].
-	fn sign_with_local_identity(&self, msg: impl AsRef<[u8]>) -> Result<Signature, SigningError>;
+	fn sign_with_local_identity(&self, msg: Vec<u8>) -> Result<Signature, SigningError>;
 }
 
 impl<T> NetworkSigner for Arc<T>
@@ -50,7 +104,7 @@ where
 	T: ?Sized,
 	T: NetworkSigner,
 {
-	fn sign_with_local_identity(&self, msg: impl AsRef<[u8]>) -> Result<Signature, SigningError> {
+	fn sign_with_local_identity(&self, msg: Vec<u8>) -> Result<Signature, SigningError> {
 		T::sign_with_local_identity(self, msg)
 	}
 }
@@ -117,6 +171,19 @@ pub trait NetworkStatusProvider {
 	///
 	/// Returns an error if the `NetworkWorker` is no longer running.
 	async fn status(&self) -> Result<NetworkStatus, ()>;
+
+	/// Get the network state.
+	///
+	/// **Note**: Use this only for debugging. This API is unstable. There are warnings literally
+	/// everywhere about this. Please don't use this function to retrieve actual information.
+	///
+	/// Returns an error if the `NetworkWorker` is no longer running.
+	async fn network_state(&self) -> Result<NetworkState, ()>;
+
+	/// Get the list of reserved peers of the block announcement protocol.
+	///
+	/// Returns an error if the `NetworkWorker` is no longer running.
+	async fn reserved_peers(&self) -> Result<Vec<PeerId>, ()>;
 }
 
 // Manual implementation to avoid extra boxing here
@@ -134,6 +201,26 @@ where
 	{
 		T::status(self)
 	}
+
+	fn network_state<'life0, 'async_trait>(
+		&'life0 self,
+	) -> Pin<Box<dyn Future<Output = Result<NetworkState, ()>> + Send + 'async_trait>>
+	where
+		'life0: 'async_trait,
+		Self: 'async_trait,
+	{
+		T::network_state(self)
+	}
+
+	fn reserved_peers<'life0, 'async_trait>(
+		&'life0 self,
+	) -> Pin<Box<dyn Future<Output = Result<Vec<PeerId>, ()>> + Send + 'async_trait>>
+	where
+		'life0: 'async_trait,
+		Self: 'async_trait,
+	{
+		T::reserved_peers(self)
+	}
 }
 
 /// Provides low-level API for manipulating network peers.
@@ -459,8 +459,7 @@ where
 
 			if !to_send.is_empty() {
 				log::trace!(target: LOG_TARGET, "Sending {} statements to {}", to_send.len(), who);
-				self.network
-					.write_notification(*who, self.protocol_name.clone(), to_send.encode());
+				self.notification_service.send_sync_notification(who, to_send.encode());
 			}
 		}
 
@@ -56,7 +56,7 @@ use prost::Message;
 use schnellru::{ByLength, LruMap};
 use tokio::time::{Interval, MissedTickBehavior};
 
-use sc_client_api::{BlockBackend, HeaderBackend, ProofProvider};
+use sc_client_api::{backend::AuxStore, BlockBackend, HeaderBackend, ProofProvider};
 use sc_consensus::{import_queue::ImportQueueService, IncomingBlock};
 use sc_network::{
 	config::{
@@ -324,6 +324,7 @@ where
 		+ BlockBackend<B>
 		+ HeaderMetadata<B, Error = sp_blockchain::Error>
 		+ ProofProvider<B>
+		+ AuxStore
 		+ Send
 		+ Sync
 		+ 'static,
@@ -32,7 +32,7 @@ use chain_sync::{ChainSync, ChainSyncAction, ChainSyncMode};
 use libp2p::PeerId;
 use log::{debug, error, info};
 use prometheus_endpoint::Registry;
-use sc_client_api::{BlockBackend, ProofProvider};
+use sc_client_api::{backend::AuxStore, BlockBackend, ProofProvider};
 use sc_consensus::{BlockImportError, BlockImportStatus, IncomingBlock};
 use sc_network_common::sync::{
 	message::{BlockAnnounce, BlockData, BlockRequest},
@@ -172,6 +172,8 @@ pub struct SyncingStrategy<B: BlockT, Client> {
 	/// Connected peers and their best blocks used to seed a new strategy when switching to it in
 	/// [`SyncingStrategy::proceed_to_next`].
 	peer_best_blocks: HashMap<PeerId, (B::Hash, NumberFor<B>)>,
+	/// Warp sync configuration kept to restart warp sync if a resumed state sync fails.
+	warp_sync_config: Option<WarpSyncConfig<B>>,
 }
 
 impl<B: BlockT, Client> SyncingStrategy<B, Client>
@@ -181,6 +183,7 @@ where
 		+ BlockBackend<B>
 		+ HeaderMetadata<B, Error = sp_blockchain::Error>
 		+ ProofProvider<B>
+		+ AuxStore
 		+ Send
 		+ Sync
 		+ 'static,
@@ -194,6 +197,22 @@ where
 		if let SyncMode::Warp = config.mode {
 			let warp_sync_config = warp_sync_config
 				.expect("Warp sync configuration must be supplied in warp sync mode.");
+			// Continue downloading the state interrupted by a restart instead of warp syncing to
+			// a new target.
+			if let WarpSyncConfig::WithProvider(_) = warp_sync_config {
+				if let Some(state) = StateStrategy::resume(client.clone(), std::iter::empty()) {
+					info!(target: LOG_TARGET, "Resuming interrupted state sync.");
+					return Ok(Self {
+						config,
+						client,
+						warp: None,
+						state: Some(state),
+						chain_sync: None,
+						peer_best_blocks: Default::default(),
+						warp_sync_config: Some(warp_sync_config),
+					})
+				}
+			}
 			let warp_sync = WarpSync::new(client.clone(), warp_sync_config);
 			Ok(Self {
 				config,
@@ -202,6 +221,7 @@ where
 				state: None,
 				chain_sync: None,
 				peer_best_blocks: Default::default(),
+				warp_sync_config: None,
 			})
 		} else {
 			let chain_sync = ChainSync::new(
@@ -219,6 +239,7 @@ where
 				state: None,
 				chain_sync: Some(chain_sync),
 				peer_best_blocks: Default::default(),
+				warp_sync_config: None,
 			})
 		}
 	}
@@ -557,8 +578,19 @@ where
 				},
 			}
 		} else if let Some(state) = &self.state {
+			let warp_sync_config = self.warp_sync_config.take();
 			if state.is_succeded() {
 				info!(target: LOG_TARGET, "State sync is complete, continuing with block sync.");
+			} else if let Some(warp_sync_config) = warp_sync_config {
+				error!(target: LOG_TARGET, "Resumed state sync failed. Restarting warp sync.");
+				let mut warp = WarpSync::new(self.client.clone(), warp_sync_config);
+				self.peer_best_blocks.iter().for_each(|(peer_id, (best_hash, best_number))| {
+					warp.add_peer(*peer_id, *best_hash, *best_number)
+				});
+
+				self.state = None;
+				self.warp = Some(warp);
+				return Ok(())
 			} else {
 				error!(target: LOG_TARGET, "State sync failed. Falling back to full sync.");
 			}
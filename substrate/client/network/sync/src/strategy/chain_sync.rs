@@ -44,7 +44,7 @@ use codec::Encode;
 use libp2p::PeerId;
 use log::{debug, error, info, trace, warn};
 use prometheus_endpoint::{register, Gauge, GaugeVec, Opts, PrometheusError, Registry, U64};
-use sc_client_api::{BlockBackend, ProofProvider};
+use sc_client_api::{backend::AuxStore, BlockBackend, ProofProvider};
 use sc_consensus::{BlockImportError, BlockImportStatus, IncomingBlock};
 use sc_network_common::sync::message::{
 	BlockAnnounce, BlockAttributes, BlockData, BlockRequest, BlockResponse, Direction, FromBlock,
@@ -343,8 +343,8 @@ pub(crate) enum PeerSyncState<B: BlockT> {
 	DownloadingStale(B::Hash),
 	/// Downloading justification for given block hash.
 	DownloadingJustification(B::Hash),
-	/// Downloading state.
-	DownloadingState,
+	/// Downloading state of the key range with the given index.
+	DownloadingState(usize),
 	/// Actively downloading block history after warp sync.
 	DownloadingGap(NumberFor<B>),
 }
@@ -362,6 +362,7 @@ where
 		+ BlockBackend<B>
 		+ HeaderMetadata<B, Error = sp_blockchain::Error>
 		+ ProofProvider<B>
+		+ AuxStore
 		+ Send
 		+ Sync
 		+ 'static,
@@ -894,7 +895,7 @@ where
 					},
 					PeerSyncState::Available |
 					PeerSyncState::DownloadingJustification(..) |
-					PeerSyncState::DownloadingState => Vec::new(),
+					PeerSyncState::DownloadingState(_) => Vec::new(),
 				}
 			} else {
 				// When request.is_none() this is a block announcement. Just accept blocks.
@@ -1326,7 +1327,7 @@ where
 				PeerSyncState::DownloadingNew(_) |
 				PeerSyncState::DownloadingStale(_) |
 				PeerSyncState::DownloadingGap(_) |
-				PeerSyncState::DownloadingState => {
+				PeerSyncState::DownloadingState(_) => {
 					// Cancel a request first, as `add_peer` may generate a new request.
 					self.actions.push(ChainSyncAction::CancelRequest { peer_id });
 					self.add_peer(peer_id, peer_sync.best_hash, peer_sync.best_number);
@@ -1639,33 +1640,43 @@ where
 			.collect()
 	}
 
-	/// Get a state request scheduled by sync to be sent out (if any).
-	fn state_request(&mut self) -> Option<(PeerId, OpaqueStateRequest)> {
+	/// Get the state requests scheduled by sync to be sent out, one for every key range not being
+	/// downloaded yet.
+	fn state_requests(&mut self) -> Vec<(PeerId, OpaqueStateRequest)> {
 		if self.allowed_requests.is_empty() {
-			return None
+			return Vec::new()
 		}
-		if self.state_sync.is_some() &&
-			self.peers.iter().any(|(_, peer)| peer.state == PeerSyncState::DownloadingState)
-		{
-			// Only one pending state request is allowed.
-			return None
+		let Some(sync) = &self.state_sync else { return Vec::new() };
+		if sync.is_complete() {
+			return Vec::new()
 		}
-		if let Some(sync) = &self.state_sync {
-			if sync.is_complete() {
-				return None
-			}
 
-			for (id, peer) in self.peers.iter_mut() {
-				if peer.state.is_available() && peer.common_number >= sync.target_number() {
-					peer.state = PeerSyncState::DownloadingState;
-					let request = sync.next_request();
-					trace!(target: LOG_TARGET, "New StateRequest for {}: {:?}", id, request);
-					self.allowed_requests.clear();
-					return Some((*id, OpaqueStateRequest(Box::new(request))))
-				}
+		let in_flight = self
+			.peers
+			.values()
+			.filter_map(|peer| match peer.state {
+				PeerSyncState::DownloadingState(range) => Some(range),
+				_ => None,
+			})
+			.collect::<HashSet<_>>();
+		let mut pending_ranges =
+			sync.pending_ranges().into_iter().filter(|range| !in_flight.contains(range));
+
+		let mut requests = Vec::new();
+		for (id, peer) in self.peers.iter_mut() {
+			if !peer.state.is_available() || peer.common_number < sync.target_number() {
+				continue
 			}
+			let Some(range) = pending_ranges.next() else { break };
+			peer.state = PeerSyncState::DownloadingState(range);
+			let request = sync.next_request(range);
+			trace!(target: LOG_TARGET, "New StateRequest for {id} for range {range}: {request:?}");
+			requests.push((*id, OpaqueStateRequest(Box::new(request))));
+		}
+		if !requests.is_empty() {
+			self.allowed_requests.clear();
 		}
-		None
+		requests
 	}
 
 	#[must_use]
@@ -1683,12 +1694,15 @@ where
 			BadPeer(*peer_id, rep::BAD_RESPONSE)
 		})?;
 
-		if let Some(peer) = self.peers.get_mut(peer_id) {
-			if let PeerSyncState::DownloadingState = peer.state {
-				peer.state = PeerSyncState::Available;
-				self.allowed_requests.set_all();
-			}
-		}
+		let Some(range) = self.peers.get_mut(peer_id).and_then(|peer| {
+			let PeerSyncState::DownloadingState(range) = peer.state else { return None };
+			peer.state = PeerSyncState::Available;
+			self.allowed_requests.set_all();
+			Some(range)
+		}) else {
+			debug!(target: LOG_TARGET, "Ignored unexpected state response from {peer_id}");
+			return Err(BadPeer(*peer_id, rep::NOT_REQUESTED))
+		};
 		let import_result = if let Some(sync) = &mut self.state_sync {
 			debug!(
 				target: LOG_TARGET,
@@ -1697,7 +1711,7 @@ where
 				response.entries.len(),
 				response.proof.len(),
 			);
-			sync.import(*response)
+			sync.import(range, *response)
 		} else {
 			debug!(target: LOG_TARGET, "Ignored obsolete state response from {peer_id}");
 			return Err(BadPeer(*peer_id, rep::NOT_REQUESTED))
@@ -1881,11 +1895,11 @@ where
 			.map(|(peer_id, request)| ChainSyncAction::SendBlockRequest { peer_id, request });
 		self.actions.extend(justification_requests);
 
-		let state_request = self
-			.state_request()
+		let state_requests = self
+			.state_requests()
 			.into_iter()
 			.map(|(peer_id, request)| ChainSyncAction::SendStateRequest { peer_id, request });
-		self.actions.extend(state_request);
+		self.actions.extend(state_requests);
 
 		std::mem::take(&mut self.actions).into_iter()
 	}
@@ -995,3 +995,36 @@ fn request_across_forks() {
 		assert!(sync.is_known(&block.header.parent_hash()));
 	}
 }
+
+#[test]
+fn state_ranges_are_requested_from_peers_in_parallel() {
+	let client = Arc::new(TestClientBuilder::new().build());
+	let mode = ChainSyncMode::LightState { skip_proofs: false, storage_chain_mode: false };
+	let mut sync = ChainSync::new(mode, client.clone(), 1, 64, None, std::iter::empty()).unwrap();
+
+	let info = client.info();
+	let peers = [PeerId::random(), PeerId::random(), PeerId::random()];
+	for peer_id in peers {
+		sync.add_peer(peer_id, info.genesis_hash, 0);
+	}
+	let genesis_header = client.header(info.genesis_hash).unwrap().unwrap();
+	sync.state_sync = Some(StateSync::new(client.clone(), genesis_header, None, None, false));
+	sync.allowed_requests.set_all();
+
+	// every peer downloads another key range
+	let requests = sync.state_requests();
+	assert_eq!(requests.len(), peers.len());
+	let ranges = sync
+		.peers
+		.values()
+		.filter_map(|peer| match peer.state {
+			PeerSyncState::DownloadingState(range) => Some(range),
+			_ => None,
+		})
+		.collect::<HashSet<_>>();
+	assert_eq!(ranges.len(), peers.len());
+
+	// no more requests are scheduled while all peers are busy
+	sync.allowed_requests.set_all();
+	assert!(sync.state_requests().is_empty());
+}
@@ -20,13 +20,13 @@
 
 use crate::{
 	schema::v1::StateResponse,
-	strategy::state_sync::{ImportResult, StateSync, StateSyncProvider},
+	strategy::state_sync::{aux_schema, ImportResult, StateSync, StateSyncProvider},
 	types::{BadPeer, OpaqueStateRequest, OpaqueStateResponse, SyncState, SyncStatus},
 	LOG_TARGET,
 };
 use libp2p::PeerId;
-use log::{debug, error, trace};
-use sc_client_api::ProofProvider;
+use log::{debug, error, trace, warn};
+use sc_client_api::{backend::AuxStore, ProofProvider};
 use sc_consensus::{BlockImportError, BlockImportStatus, IncomingBlock};
 use sc_network_common::sync::message::BlockAnnounce;
 use sp_consensus::BlockOrigin;
@@ -34,7 +34,10 @@ use sp_runtime::{
 	traits::{Block as BlockT, Header, NumberFor},
 	Justifications, SaturatedConversion,
 };
-use std::{collections::HashMap, sync::Arc};
+use std::{
+	collections::{HashMap, HashSet},
+	sync::Arc,
+};
 
 mod rep {
 	use sc_network::ReputationChange as Rep;
@@ -46,6 +49,12 @@ mod rep {
 	pub const BAD_STATE: Rep = Rep::new(-(1 << 29), "Bad state");
 }
 
+/// Number of consecutive failed state requests after which a resumed state sync gives up.
+///
+/// The target block of a resumed download may be pruned by the peers in the meantime, in which
+/// case the sync is restarted from a fresh target.
+const MAX_RESUMED_FAILURES: u32 = 16;
+
 /// Action that should be performed on [`StateStrategy`]'s behalf.
 pub enum StateStrategyAction<B: BlockT> {
 	/// Send state request to peer.
@@ -60,7 +69,8 @@ pub enum StateStrategyAction<B: BlockT> {
 
 enum PeerState {
 	Available,
-	DownloadingState,
+	/// Downloading the key range with the given index.
+	DownloadingState(usize),
 }
 
 impl PeerState {
@@ -80,6 +90,10 @@ pub struct StateStrategy<B: BlockT> {
 	peers: HashMap<PeerId, Peer<B>>,
 	actions: Vec<StateStrategyAction<B>>,
 	succeded: bool,
+	/// Whether the state sync resumes a download interrupted by a restart.
+	resumed: bool,
+	/// Number of state requests failed in a row.
+	failures: u32,
 }
 
 impl<B: BlockT> StateStrategy<B> {
@@ -93,7 +107,7 @@ impl<B: BlockT> StateStrategy<B> {
 		initial_peers: impl Iterator<Item = (PeerId, NumberFor<B>)>,
 	) -> Self
 	where
-		Client: ProofProvider<B> + Send + Sync + 'static,
+		Client: ProofProvider<B> + AuxStore + Send + Sync + 'static,
 	{
 		let peers = initial_peers
 			.map(|(peer_id, best_number)| {
@@ -111,9 +125,41 @@ impl<B: BlockT> StateStrategy<B> {
 			peers,
 			actions: Vec::new(),
 			succeded: false,
+			resumed: false,
+			failures: 0,
 		}
 	}
 
+	/// Resume the state sync interrupted by a restart, as persisted in the aux-db.
+	///
+	/// Returns `None` if there is no state sync to resume.
+	pub fn resume<Client>(
+		client: Arc<Client>,
+		initial_peers: impl Iterator<Item = (PeerId, NumberFor<B>)>,
+	) -> Option<Self>
+	where
+		Client: ProofProvider<B> + AuxStore + Send + Sync + 'static,
+	{
+		let target = match aux_schema::load_target::<B, _>(&*client) {
+			Ok(target) => target?,
+			Err(e) => {
+				warn!(target: LOG_TARGET, "Failed to load state sync target: {e}");
+				return None
+			},
+		};
+
+		let mut state_strategy = Self::new(
+			client,
+			target.header,
+			target.body,
+			target.justifications,
+			false,
+			initial_peers,
+		);
+		state_strategy.resumed = true;
+		Some(state_strategy)
+	}
+
 	// Create a new instance with a custom state sync provider.
 	// Used in tests.
 	#[cfg(test)]
@@ -130,6 +176,8 @@ impl<B: BlockT> StateStrategy<B> {
 				.collect(),
 			actions: Vec::new(),
 			succeded: false,
+			resumed: false,
+			failures: 0,
 		}
 	}
 
@@ -140,7 +188,27 @@ impl<B: BlockT> StateStrategy<B> {
 
 	/// Notify that a peer has disconnected.
 	pub fn remove_peer(&mut self, peer_id: &PeerId) {
-		self.peers.remove(peer_id);
+		if let Some(Peer { state: PeerState::DownloadingState(_), .. }) = self.peers.remove(peer_id)
+		{
+			// The peer is disconnected when a request to it fails.
+			self.on_request_failed();
+		}
+	}
+
+	/// Account a failed state request.
+	fn on_request_failed(&mut self) {
+		self.failures += 1;
+
+		if self.resumed && self.failures >= MAX_RESUMED_FAILURES {
+			warn!(
+				target: LOG_TARGET,
+				"Resumed state sync of #{} failed {} times in a row, giving up.",
+				self.state_sync.target_number(),
+				self.failures,
+			);
+			self.resumed = false;
+			self.actions.push(StateStrategyAction::Finished);
+		}
 	}
 
 	/// Submit a validated block announcement.
@@ -176,9 +244,15 @@ impl<B: BlockT> StateStrategy<B> {
 		peer_id: PeerId,
 		response: OpaqueStateResponse,
 	) -> Result<(), BadPeer> {
-		if let Some(peer) = self.peers.get_mut(&peer_id) {
-			peer.state = PeerState::Available;
-		}
+		let Some(range) = self.peers.get_mut(&peer_id).and_then(|peer| {
+			match std::mem::replace(&mut peer.state, PeerState::Available) {
+				PeerState::DownloadingState(range) => Some(range),
+				PeerState::Available => None,
+			}
+		}) else {
+			debug!(target: LOG_TARGET, "Unexpected state response from {peer_id}.");
+			return Ok(())
+		};
 
 		let response: Box<StateResponse> = response.0.downcast().map_err(|_error| {
 			error!(
@@ -198,7 +272,7 @@ impl<B: BlockT> StateStrategy<B> {
 			response.proof.len(),
 		);
 
-		match self.state_sync.import(*response) {
+		match self.state_sync.import(range, *response) {
 			ImportResult::Import(hash, header, state, body, justifications) => {
 				let origin = BlockOrigin::NetworkInitialSync;
 				let block = IncomingBlock {
@@ -214,13 +288,18 @@ impl<B: BlockT> StateStrategy<B> {
 					state: Some(state),
 				};
 				debug!(target: LOG_TARGET, "State download is complete. Import is queued");
+				self.failures = 0;
 				self.actions
 					.push(StateStrategyAction::ImportBlocks { origin, blocks: vec![block] });
 				Ok(())
 			},
-			ImportResult::Continue => Ok(()),
+			ImportResult::Continue => {
+				self.failures = 0;
+				Ok(())
+			},
 			ImportResult::BadResponse => {
 				debug!(target: LOG_TARGET, "Bad state data received from {peer_id}");
+				self.on_request_failed();
 				Err(BadPeer(peer_id, rep::BAD_STATE))
 			},
 		}
@@ -265,29 +344,40 @@ impl<B: BlockT> StateStrategy<B> {
 		}
 	}
 
-	/// Produce state request.
-	fn state_request(&mut self) -> Option<(PeerId, OpaqueStateRequest)> {
+	/// Produce state requests, one for every key range not being downloaded yet.
+	fn state_requests(&mut self) -> Vec<(PeerId, OpaqueStateRequest)> {
 		if self.state_sync.is_complete() {
-			return None
+			return Vec::new()
 		}
 
-		if self
+		let in_flight = self
 			.peers
 			.values()
-			.any(|peer| matches!(peer.state, PeerState::DownloadingState))
-		{
-			// Only one state request at a time is possible.
-			return None
-		}
+			.filter_map(|peer| match peer.state {
+				PeerState::DownloadingState(range) => Some(range),
+				PeerState::Available => None,
+			})
+			.collect::<HashSet<_>>();
 
-		let peer_id =
-			self.schedule_next_peer(PeerState::DownloadingState, self.state_sync.target_number())?;
-		let request = self.state_sync.next_request();
-		trace!(
-			target: LOG_TARGET,
-			"New state request to {peer_id}: {request:?}.",
-		);
-		Some((peer_id, OpaqueStateRequest(Box::new(request))))
+		let mut requests = Vec::new();
+		for range in self.state_sync.pending_ranges() {
+			if in_flight.contains(&range) {
+				continue
+			}
+			let Some(peer_id) = self.schedule_next_peer(
+				PeerState::DownloadingState(range),
+				self.state_sync.target_number(),
+			) else {
+				break
+			};
+			let request = self.state_sync.next_request(range);
+			trace!(
+				target: LOG_TARGET,
+				"New state request to {peer_id} for range {range}: {request:?}.",
+			);
+			requests.push((peer_id, OpaqueStateRequest(Box::new(request))));
+		}
+		requests
 	}
 
 	fn schedule_next_peer(
@@ -333,11 +423,11 @@ impl<B: BlockT> StateStrategy<B> {
 	/// Get actions that should be performed by the owner on [`WarpSync`]'s behalf
 	#[must_use]
 	pub fn actions(&mut self) -> impl Iterator<Item = StateStrategyAction<B>> {
-		let state_request = self
-			.state_request()
+		let state_requests = self
+			.state_requests()
 			.into_iter()
 			.map(|(peer_id, request)| StateStrategyAction::SendStateRequest { peer_id, request });
-		self.actions.extend(state_request);
+		self.actions.extend(state_requests);
 
 		std::mem::take(&mut self.actions).into_iter()
 	}
@@ -354,13 +444,17 @@ mod test {
 	use super::*;
 	use crate::{
 		schema::v1::{StateRequest, StateResponse},
-		strategy::state_sync::{ImportResult, StateSyncProgress, StateSyncProvider},
+		strategy::state_sync::{
+			ImportResult, StateSyncProgress, StateSyncProvider, STATE_SYNC_RANGES,
+		},
 	};
-	use codec::Decode;
+	use codec::{Decode, Encode};
 	use sc_block_builder::BlockBuilderBuilder;
 	use sc_client_api::KeyValueStates;
 	use sc_consensus::{ImportedAux, ImportedState};
+	use sp_blockchain::HeaderBackend;
 	use sp_runtime::traits::Zero;
+	use std::collections::BTreeMap;
 	use substrate_test_runtime_client::{
 		runtime::{Block, Hash},
 		BlockBuilderExt, DefaultTestClientBuilderExt, TestClientBuilder, TestClientBuilderExt,
@@ -370,8 +464,9 @@ mod test {
 		pub StateSync<B: BlockT> {}
 
 		impl<B: BlockT> StateSyncProvider<B> for StateSync<B> {
-			fn import(&mut self, response: StateResponse) -> ImportResult<B>;
-			fn next_request(&self) -> StateRequest;
+			fn import(&mut self, range: usize, response: StateResponse) -> ImportResult<B>;
+			fn next_request(&self, range: usize) -> StateRequest;
+			fn pending_ranges(&self) -> Vec<usize>;
 			fn is_complete(&self) -> bool;
 			fn target_number(&self) -> NumberFor<B>;
 			fn target_hash(&self) -> B::Hash;
@@ -396,7 +491,7 @@ mod test {
 			StateStrategy::new(client, target_header, None, None, false, std::iter::empty());
 
 		assert!(state_strategy
-			.schedule_next_peer(PeerState::DownloadingState, Zero::zero())
+			.schedule_next_peer(PeerState::DownloadingState(0), Zero::zero())
 			.is_none());
 	}
 
@@ -428,7 +523,7 @@ mod test {
 			);
 
 			let peer_id =
-				state_strategy.schedule_next_peer(PeerState::DownloadingState, Zero::zero());
+				state_strategy.schedule_next_peer(PeerState::DownloadingState(0), Zero::zero());
 			assert!(*peers.get(&peer_id.unwrap()).unwrap() >= 6);
 		}
 	}
@@ -460,7 +555,7 @@ mod test {
 				initial_peers,
 			);
 
-			let peer_id = state_strategy.schedule_next_peer(PeerState::DownloadingState, 10);
+			let peer_id = state_strategy.schedule_next_peer(PeerState::DownloadingState(0), 10);
 			assert!(*peers.get(&peer_id.unwrap()).unwrap() == 10);
 		}
 	}
@@ -488,7 +583,7 @@ mod test {
 			initial_peers,
 		);
 
-		let (_peer_id, mut opaque_request) = state_strategy.state_request().unwrap();
+		let (_peer_id, mut opaque_request) = state_strategy.state_requests().pop().unwrap();
 		let request: &mut StateRequest = opaque_request.0.downcast_mut().unwrap();
 		let hash = Hash::decode(&mut &*request.block).unwrap();
 
@@ -496,7 +591,7 @@ mod test {
 	}
 
 	#[test]
-	fn no_parallel_state_requests() {
+	fn parallel_state_requests_cover_disjoint_ranges() {
 		let client = Arc::new(TestClientBuilder::new().set_no_genesis().build());
 		let target_block = BlockBuilderBuilder::new(&*client)
 			.on_parent_block(client.chain_info().best_hash)
@@ -518,23 +613,129 @@ mod test {
 			initial_peers,
 		);
 
-		// First request is sent.
-		assert!(state_strategy.state_request().is_some());
+		// Every peer synced to at least the median gets a request for a distinct range.
+		let requests = state_strategy.state_requests();
+		assert_eq!(requests.len(), 5);
+		let starts = requests
+			.into_iter()
+			.map(|(_peer_id, mut opaque_request)| {
+				let request: &mut StateRequest = opaque_request.0.downcast_mut().unwrap();
+				std::mem::take(&mut request.start)
+			})
+			.collect::<HashSet<_>>();
+		assert_eq!(starts.len(), 5);
+
+		// No request is sent for the ranges in flight.
+		assert!(state_strategy.state_requests().is_empty());
+	}
+
+	fn serve_state_request(
+		client: &substrate_test_runtime_client::TestClient,
+		request: OpaqueStateRequest,
+	) -> OpaqueStateResponse {
+		let request: Box<StateRequest> = request.0.downcast().unwrap();
+		let hash = Hash::decode(&mut &*request.block).unwrap();
+		let (proof, _count) = client
+			.read_proof_collection(hash, request.start.as_slice(), usize::MAX)
+			.unwrap();
+		OpaqueStateResponse(Box::new(StateResponse { entries: Vec::new(), proof: proof.encode() }))
+	}
+
+	#[test]
+	fn interrupted_state_download_is_resumed() {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let target_header = client.header(client.chain_info().genesis_hash).unwrap().unwrap();
+		let peers = (0..STATE_SYNC_RANGES).map(|_| (PeerId::random(), 1)).collect::<Vec<_>>();
+
+		let mut state_strategy = StateStrategy::new(
+			client.clone(),
+			target_header.clone(),
+			None,
+			None,
+			false,
+			peers.clone().into_iter(),
+		);
+		let requests = state_strategy.state_requests();
+		assert_eq!(requests.len(), STATE_SYNC_RANGES);
 
-		// No parallel request is sent.
-		assert!(state_strategy.state_request().is_none());
+		// Download half of the ranges and "restart".
+		for (peer_id, request) in requests.into_iter().take(STATE_SYNC_RANGES / 2) {
+			state_strategy.on_state_response(peer_id, serve_state_request(&client, request));
+		}
+		assert!(state_strategy.actions.is_empty());
+		drop(state_strategy);
+
+		let mut state_strategy =
+			StateStrategy::<Block>::resume(client.clone(), peers.into_iter()).unwrap();
+		assert_eq!(state_strategy.state_sync.target_hash(), target_header.hash());
+		assert_eq!(state_strategy.state_sync.pending_ranges().len(), STATE_SYNC_RANGES / 2);
+
+		// Only the remaining ranges are requested.
+		let requests = state_strategy.state_requests();
+		assert_eq!(requests.len(), STATE_SYNC_RANGES / 2);
+		for (peer_id, request) in requests {
+			state_strategy.on_state_response(peer_id, serve_state_request(&client, request));
+		}
+
+		// The downloaded state matches the state downloaded in one go.
+		let (proof, _count) =
+			client.read_proof_collection(target_header.hash(), &[], usize::MAX).unwrap();
+		let (expected, _completed) =
+			client.verify_range_proof(*target_header.state_root(), proof, &[]).unwrap();
+		let expected = expected.0[0].key_values.iter().cloned().collect::<BTreeMap<_, _>>();
+
+		assert_eq!(state_strategy.actions.len(), 1);
+		let StateStrategyAction::ImportBlocks { blocks, .. } = state_strategy.actions.remove(0)
+		else {
+			panic!("State is imported once all ranges are downloaded.")
+		};
+		let state = blocks[0].state.as_ref().unwrap();
+		let top = state.state.0.iter().find(|level| level.state_root.is_empty()).unwrap();
+		let imported = top.key_values.iter().cloned().collect::<BTreeMap<_, _>>();
+		assert_eq!(imported, expected);
+
+		// Nothing is left to resume.
+		assert!(StateStrategy::<Block>::resume(client, std::iter::empty()).is_none());
+	}
+
+	#[test]
+	fn resumed_state_sync_gives_up_after_repeated_failures() {
+		let mut state_sync_provider = MockStateSync::<Block>::new();
+		state_sync_provider
+			.expect_import()
+			.times(MAX_RESUMED_FAILURES as usize)
+			.returning(|_, _| ImportResult::BadResponse);
+		state_sync_provider.expect_target_number().return_const(1u64);
+		let peers = (0..MAX_RESUMED_FAILURES).map(|_| (PeerId::random(), 10)).collect::<Vec<_>>();
+		let mut state_strategy =
+			StateStrategy::new_with_provider(Box::new(state_sync_provider), peers.iter().cloned());
+		state_strategy.resumed = true;
+
+		for (peer_id, _) in peers {
+			state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState(0);
+			let dummy_response = OpaqueStateResponse(Box::new(StateResponse::default()));
+			state_strategy.on_state_response(peer_id, dummy_response);
+		}
+
+		// All peers are dropped and the strategy finishes without success.
+		assert_eq!(state_strategy.actions.len(), MAX_RESUMED_FAILURES as usize + 1);
+		assert!(state_strategy
+			.actions
+			.iter()
+			.any(|action| matches!(action, StateStrategyAction::Finished)));
+		assert!(!state_strategy.is_succeded());
 	}
 
 	#[test]
 	fn received_state_response_makes_peer_available_again() {
 		let mut state_sync_provider = MockStateSync::<Block>::new();
-		state_sync_provider.expect_import().return_once(|_| ImportResult::Continue);
+		state_sync_provider.expect_import().return_once(|_, _| ImportResult::Continue);
 		let peer_id = PeerId::random();
 		let initial_peers = std::iter::once((peer_id, 10));
 		let mut state_strategy =
 			StateStrategy::new_with_provider(Box::new(state_sync_provider), initial_peers);
 		// Manually set the peer's state.
-		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState;
+		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState(0);
 
 		let dummy_response = OpaqueStateResponse(Box::new(StateResponse::default()));
 		state_strategy.on_state_response(peer_id, dummy_response);
@@ -546,13 +747,15 @@ mod test {
 	fn bad_state_response_drops_peer() {
 		let mut state_sync_provider = MockStateSync::<Block>::new();
 		// Provider says that state response is bad.
-		state_sync_provider.expect_import().return_once(|_| ImportResult::BadResponse);
+		state_sync_provider
+			.expect_import()
+			.return_once(|_, _| ImportResult::BadResponse);
 		let peer_id = PeerId::random();
 		let initial_peers = std::iter::once((peer_id, 10));
 		let mut state_strategy =
 			StateStrategy::new_with_provider(Box::new(state_sync_provider), initial_peers);
 		// Manually set the peer's state.
-		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState;
+		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState(0);
 		let dummy_response = OpaqueStateResponse(Box::new(StateResponse::default()));
 		// Receiving response drops the peer.
 		assert!(matches!(
@@ -565,13 +768,13 @@ mod test {
 	fn partial_state_response_doesnt_generate_actions() {
 		let mut state_sync_provider = MockStateSync::<Block>::new();
 		// Sync provider says that the response is partial.
-		state_sync_provider.expect_import().return_once(|_| ImportResult::Continue);
+		state_sync_provider.expect_import().return_once(|_, _| ImportResult::Continue);
 		let peer_id = PeerId::random();
 		let initial_peers = std::iter::once((peer_id, 10));
 		let mut state_strategy =
 			StateStrategy::new_with_provider(Box::new(state_sync_provider), initial_peers);
 		// Manually set the peer's state .
-		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState;
+		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState(0);
 
 		let dummy_response = OpaqueStateResponse(Box::new(StateResponse::default()));
 		state_strategy.on_state_response(peer_id, dummy_response);
@@ -606,7 +809,7 @@ mod test {
 			body.clone(),
 			justifications.clone(),
 		);
-		state_sync_provider.expect_import().return_once(move |_| import);
+		state_sync_provider.expect_import().return_once(move |_, _| import);
 
 		// Reference values to check against.
 		let expected_origin = BlockOrigin::NetworkInitialSync;
@@ -630,7 +833,7 @@ mod test {
 		let mut state_strategy =
 			StateStrategy::new_with_provider(Box::new(state_sync_provider), initial_peers);
 		// Manually set the peer's state .
-		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState;
+		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState(0);
 
 		// Receive response.
 		let dummy_response = OpaqueStateResponse(Box::new(StateResponse::default()));
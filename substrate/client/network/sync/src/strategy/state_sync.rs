@@ -23,8 +23,8 @@ use crate::{
 	LOG_TARGET,
 };
 use codec::{Decode, Encode};
-use log::debug;
-use sc_client_api::{CompactProof, ProofProvider};
+use log::{debug, info, warn};
+use sc_client_api::{backend::AuxStore, CompactProof, ProofProvider};
 use sc_consensus::ImportedState;
 use smallvec::SmallVec;
 use sp_core::storage::well_known_keys;
@@ -34,12 +34,48 @@ use sp_runtime::{
 };
 use std::{collections::HashMap, fmt, sync::Arc};
 
+pub(crate) mod aux_schema;
+
+/// Number of disjoint key ranges the state is split into for download.
+pub(crate) const STATE_SYNC_RANGES: usize = 16;
+
+/// Number of distinct first key bytes covered by a single range.
+const RANGE_WIDTH: usize = 256 / STATE_SYNC_RANGES;
+
+/// Cursor the download of `range` starts after.
+///
+/// Start keys are exclusive, so range `i` covers the keys above `[i * RANGE_WIDTH]` and up to
+/// and including the start key of range `i + 1`.
+fn range_start(range: usize) -> Vec<Vec<u8>> {
+	if range == 0 {
+		Vec::new()
+	} else {
+		vec![vec![(range * RANGE_WIDTH) as u8]]
+	}
+}
+
+/// Last top trie key covered by `range`, `None` for the last range.
+fn range_end(range: usize) -> Option<Vec<u8>> {
+	(range + 1 < STATE_SYNC_RANGES).then(|| vec![((range + 1) * RANGE_WIDTH) as u8])
+}
+
+/// Range containing every child trie root key.
+///
+/// All child storage keys share the `:child_storage:` prefix and therefore fall into the
+/// same range. Child trie content is only kept for responses of that range, which makes the
+/// range download child tries sequentially as before.
+fn child_tries_range() -> usize {
+	well_known_keys::CHILD_STORAGE_KEY_PREFIX[0] as usize / RANGE_WIDTH
+}
+
 /// Generic state sync provider. Used for mocking in tests.
 pub trait StateSyncProvider<B: BlockT>: Send + Sync {
-	/// Validate and import a state response.
-	fn import(&mut self, response: StateResponse) -> ImportResult<B>;
-	/// Produce next state request.
-	fn next_request(&self) -> StateRequest;
+	/// Validate and import a state response for key range `range`.
+	fn import(&mut self, range: usize, response: StateResponse) -> ImportResult<B>;
+	/// Produce next state request for key range `range`.
+	fn next_request(&self, range: usize) -> StateRequest;
+	/// Key ranges that are not fully downloaded yet.
+	fn pending_ranges(&self) -> Vec<usize>;
 	/// Check if the state is complete.
 	fn is_complete(&self) -> bool;
 	/// Returns target block number.
@@ -91,13 +127,17 @@ pub enum ImportResult<B: BlockT> {
 
 /// State sync state machine. Accumulates partial state data until it
 /// is ready to be imported.
+///
+/// The state is downloaded in [`STATE_SYNC_RANGES`] disjoint key ranges which can be requested
+/// from different peers in parallel. Every verified chunk is also written to the aux-db, so an
+/// interrupted download of the same target block resumes where it stopped.
 pub struct StateSync<B: BlockT, Client> {
 	target_block: B::Hash,
 	target_header: B::Header,
 	target_root: B::Hash,
 	target_body: Option<Vec<B::Extrinsic>>,
 	target_justifications: Option<Justifications>,
-	last_key: SmallVec<[Vec<u8>; 2]>,
+	ranges: Vec<aux_schema::RangeProgress>,
 	state: HashMap<Vec<u8>, (Vec<(Vec<u8>, Vec<u8>)>, Vec<Vec<u8>>)>,
 	complete: bool,
 	client: Arc<Client>,
@@ -108,9 +148,12 @@ pub struct StateSync<B: BlockT, Client> {
 impl<B, Client> StateSync<B, Client>
 where
 	B: BlockT,
-	Client: ProofProvider<B> + Send + Sync + 'static,
+	Client: ProofProvider<B> + AuxStore + Send + Sync + 'static,
 {
 	///  Create a new instance.
+	///
+	/// Resumes the download persisted in the aux-db if it was for the same target block,
+	/// otherwise discards it.
 	pub fn new(
 		client: Arc<Client>,
 		target_header: B::Header,
@@ -118,29 +161,156 @@ where
 		target_justifications: Option<Justifications>,
 		skip_proof: bool,
 	) -> Self {
-		Self {
+		let mut state_sync = Self {
 			client,
 			target_block: target_header.hash(),
 			target_root: *target_header.state_root(),
 			target_header,
 			target_body,
 			target_justifications,
-			last_key: SmallVec::default(),
+			ranges: (0..STATE_SYNC_RANGES)
+				.map(|range| aux_schema::RangeProgress {
+					cursor: range_start(range),
+					..Default::default()
+				})
+				.collect(),
 			state: HashMap::default(),
 			complete: false,
 			imported_bytes: 0,
 			skip_proof,
+		};
+
+		match aux_schema::load_progress::<B::Hash, _>(&*state_sync.client) {
+			Ok(Some(progress))
+				if progress.target == state_sync.target_block &&
+					progress.ranges.len() == STATE_SYNC_RANGES =>
+			{
+				if state_sync.resume(&progress) {
+					info!(
+						target: LOG_TARGET,
+						"Resuming state sync of #{} ({}) at {}%.",
+						state_sync.target_number(),
+						state_sync.target_block,
+						state_sync.progress().percentage,
+					);
+					return state_sync
+				}
+				state_sync.state.clear();
+				state_sync.clear_persisted(&progress);
+			},
+			Ok(Some(progress)) => state_sync.clear_persisted(&progress),
+			Ok(None) => {},
+			Err(e) => warn!(target: LOG_TARGET, "Failed to load state sync progress: {e}"),
 		}
+
+		let target = aux_schema::StateSyncTarget::<B> {
+			header: state_sync.target_header.clone(),
+			body: state_sync.target_body.clone(),
+			justifications: state_sync.target_justifications.clone(),
+		};
+		if let Err(e) =
+			aux_schema::write_target(&*state_sync.client, &target, &state_sync.persisted_progress())
+		{
+			warn!(target: LOG_TARGET, "Failed to persist state sync target: {e}");
+		}
+
+		state_sync
+	}
+
+	/// Replay the chunks persisted for `progress`. Returns `false` if some of them are missing.
+	fn resume(&mut self, progress: &aux_schema::Progress<B::Hash>) -> bool {
+		for (range, range_progress) in progress.ranges.iter().enumerate() {
+			for index in 0..range_progress.chunks {
+				match aux_schema::load_chunk(&*self.client, range as u32, index) {
+					Ok(Some(chunk)) => self.apply_chunk(chunk),
+					Ok(None) => {
+						debug!(
+							target: LOG_TARGET,
+							"Missing state sync chunk {index} of range {range}, restarting download.",
+						);
+						return false
+					},
+					Err(e) => {
+						warn!(target: LOG_TARGET, "Failed to load state sync chunk: {e}");
+						return false
+					},
+				}
+			}
+		}
+
+		self.ranges = progress.ranges.clone();
+		self.imported_bytes = progress.imported_bytes;
+		true
+	}
+
+	/// Progress to persist in the aux-db.
+	fn persisted_progress(&self) -> aux_schema::Progress<B::Hash> {
+		aux_schema::Progress {
+			target: self.target_block,
+			ranges: self.ranges.clone(),
+			imported_bytes: self.imported_bytes,
+		}
+	}
+
+	/// Remove the download tracked by `progress` from the aux-db.
+	fn clear_persisted(&self, progress: &aux_schema::Progress<B::Hash>) {
+		if let Err(e) = aux_schema::clear(&*self.client, progress) {
+			warn!(target: LOG_TARGET, "Failed to clear state sync progress: {e}");
+		}
+	}
+
+	/// Merge verified state into the accumulated state.
+	fn apply_chunk(&mut self, chunk: aux_schema::Chunk) {
+		for (state_root, key_values, parent_storage_keys) in chunk {
+			let entry = self.state.entry(state_root).or_default();
+			entry.1.extend(parent_storage_keys);
+			if entry.0.len() > 0 && entry.1.len() > 1 {
+				// Already imported child trie with same root.
+			} else {
+				entry.0.extend(key_values);
+			}
+		}
+	}
+
+	/// Split the top trie `key_values` of `range` into the chunk entries for the top trie and
+	/// the child trie roots it references, dropping keys past the end of the range.
+	fn top_trie_chunk(
+		range: usize,
+		key_values: impl IntoIterator<Item = (Vec<u8>, Vec<u8>)>,
+	) -> aux_schema::Chunk {
+		let end = range_end(range);
+		let mut child_roots = Vec::new();
+		let key_values = key_values
+			.into_iter()
+			.filter(|(key, _)| end.as_ref().map_or(true, |end| key <= end))
+			.filter_map(|(key, value)| {
+				// Skip all child key root (will be recalculated on import).
+				if well_known_keys::is_child_storage_key(key.as_slice()) {
+					child_roots.push((value, Vec::new(), vec![key]));
+					None
+				} else {
+					Some((key, value))
+				}
+			})
+			.collect();
+
+		let mut chunk = vec![(Vec::new(), key_values, Vec::new())];
+		chunk.extend(child_roots);
+		chunk
 	}
 }
 
 impl<B, Client> StateSyncProvider<B> for StateSync<B, Client>
 where
 	B: BlockT,
-	Client: ProofProvider<B> + Send + Sync + 'static,
+	Client: ProofProvider<B> + AuxStore + Send + Sync + 'static,
 {
-	///  Validate and import a state response.
-	fn import(&mut self, response: StateResponse) -> ImportResult<B> {
+	///  Validate and import a state response for key range `range`.
+	fn import(&mut self, range: usize, response: StateResponse) -> ImportResult<B> {
+		if self.ranges.get(range).map_or(true, |progress| progress.complete) {
+			debug!(target: LOG_TARGET, "State response for completed range {range}");
+			return ImportResult::Continue
+		}
 		if response.entries.is_empty() && response.proof.is_empty() {
 			debug!(target: LOG_TARGET, "Bad state response");
 			return ImportResult::BadResponse
@@ -149,6 +319,9 @@ where
 			debug!(target: LOG_TARGET, "Missing proof");
 			return ImportResult::BadResponse
 		}
+		let mut cursor = SmallVec::<[Vec<u8>; 2]>::from_vec(self.ranges[range].cursor.clone());
+		let with_child_tries = range == child_tries_range();
+		let mut chunk = aux_schema::Chunk::new();
 		let complete = if !self.skip_proof {
 			debug!(target: LOG_TARGET, "Importing state from {} trie nodes", response.proof.len());
 			let proof_size = response.proof.len() as u64;
@@ -159,65 +332,30 @@ where
 					return ImportResult::BadResponse
 				},
 			};
-			let (values, completed) = match self.client.verify_range_proof(
-				self.target_root,
-				proof,
-				self.last_key.as_slice(),
-			) {
-				Err(e) => {
-					debug!(
-						target: LOG_TARGET,
-						"StateResponse failed proof verification: {}",
-						e,
-					);
-					return ImportResult::BadResponse
-				},
-				Ok(values) => values,
-			};
+			let (values, completed) =
+				match self.client.verify_range_proof(self.target_root, proof, cursor.as_slice()) {
+					Err(e) => {
+						debug!(
+							target: LOG_TARGET,
+							"StateResponse failed proof verification: {}",
+							e,
+						);
+						return ImportResult::BadResponse
+					},
+					Ok(values) => values,
+				};
 			debug!(target: LOG_TARGET, "Imported with {} keys", values.len());
 
 			let complete = completed == 0;
-			if !complete && !values.update_last_key(completed, &mut self.last_key) {
+			if !complete && !values.update_last_key(completed, &mut cursor) {
 				debug!(target: LOG_TARGET, "Error updating key cursor, depth: {}", completed);
 			};
 
 			for values in values.0 {
-				let key_values = if values.state_root.is_empty() {
-					// Read child trie roots.
-					values
-						.key_values
-						.into_iter()
-						.filter(|key_value| {
-							if well_known_keys::is_child_storage_key(key_value.0.as_slice()) {
-								self.state
-									.entry(key_value.1.clone())
-									.or_default()
-									.1
-									.push(key_value.0.clone());
-								false
-							} else {
-								true
-							}
-						})
-						.collect()
-				} else {
-					values.key_values
-				};
-				let entry = self.state.entry(values.state_root).or_default();
-				if entry.0.len() > 0 && entry.1.len() > 1 {
-					// Already imported child_trie with same root.
-					// Warning this will not work with parallel download.
-				} else if entry.0.is_empty() {
-					for (key, _value) in key_values.iter() {
-						self.imported_bytes += key.len() as u64;
-					}
-
-					entry.0 = key_values;
-				} else {
-					for (key, value) in key_values {
-						self.imported_bytes += key.len() as u64;
-						entry.0.push((key, value))
-					}
+				if values.state_root.is_empty() {
+					chunk.extend(Self::top_trie_chunk(range, values.key_values));
+				} else if with_child_tries {
+					chunk.push((values.state_root, values.key_values, Vec::new()));
 				}
 			}
 			self.imported_bytes += proof_size;
@@ -228,11 +366,11 @@ where
 			// the parent cursor stays valid.
 			// Empty parent trie content only happens when all the response content
 			// is part of a single child trie.
-			if self.last_key.len() == 2 && response.entries[0].entries.is_empty() {
+			if cursor.len() == 2 && response.entries[0].entries.is_empty() {
 				// Do not remove the parent trie position.
-				self.last_key.pop();
+				cursor.pop();
 			} else {
-				self.last_key.clear();
+				cursor.clear();
 			}
 			for state in response.entries {
 				debug!(
@@ -244,33 +382,38 @@ where
 
 				if !state.complete {
 					if let Some(e) = state.entries.last() {
-						self.last_key.push(e.key.clone());
+						cursor.push(e.key.clone());
 					}
 					complete = false;
 				}
-				let is_top = state.state_root.is_empty();
-				let entry = self.state.entry(state.state_root).or_default();
-				if entry.0.len() > 0 && entry.1.len() > 1 {
-					// Already imported child trie with same root.
-				} else {
-					let mut child_roots = Vec::new();
-					for StateEntry { key, value } in state.entries {
-						// Skip all child key root (will be recalculated on import).
-						if is_top && well_known_keys::is_child_storage_key(key.as_slice()) {
-							child_roots.push((value, key));
-						} else {
-							self.imported_bytes += key.len() as u64;
-							entry.0.push((key, value))
-						}
-					}
-					for (root, storage_key) in child_roots {
-						self.state.entry(root).or_default().1.push(storage_key);
-					}
+				let key_values =
+					state.entries.into_iter().map(|StateEntry { key, value }| (key, value));
+				if state.state_root.is_empty() {
+					chunk.extend(Self::top_trie_chunk(range, key_values));
+				} else if with_child_tries {
+					chunk.push((state.state_root, key_values.collect(), Vec::new()));
 				}
 			}
 			complete
 		};
-		if complete {
+		// Responses run past the end of the range, the range is done once the cursor does.
+		let complete = complete ||
+			match (cursor.first(), range_end(range)) {
+				(Some(last), Some(end)) => *last >= end,
+				_ => false,
+			};
+
+		self.imported_bytes += chunk
+			.iter()
+			.flat_map(|(_, key_values, _)| key_values)
+			.map(|(key, _)| key.len() as u64)
+			.sum::<u64>();
+		self.ranges[range].cursor = cursor.into_vec();
+		self.ranges[range].complete = complete;
+
+		if self.ranges.iter().all(|progress| progress.complete) {
+			self.apply_chunk(chunk);
+			self.clear_persisted(&self.persisted_progress());
 			self.complete = true;
 			ImportResult::Import(
 				self.target_block,
@@ -283,19 +426,39 @@ where
 				self.target_justifications.clone(),
 			)
 		} else {
+			let index = self.ranges[range].chunks;
+			self.ranges[range].chunks += 1;
+			if let Err(e) = aux_schema::write_chunk(
+				&*self.client,
+				range as u32,
+				index,
+				&chunk,
+				&self.persisted_progress(),
+			) {
+				warn!(target: LOG_TARGET, "Failed to persist state sync chunk: {e}");
+			}
+			self.apply_chunk(chunk);
 			ImportResult::Continue
 		}
 	}
 
-	/// Produce next state request.
-	fn next_request(&self) -> StateRequest {
+	/// Produce next state request for key range `range`.
+	fn next_request(&self, range: usize) -> StateRequest {
 		StateRequest {
 			block: self.target_block.encode(),
-			start: self.last_key.clone().into_vec(),
+			start: self.ranges[range].cursor.clone(),
 			no_proof: self.skip_proof,
 		}
 	}
 
+	/// Key ranges that are not fully downloaded yet.
+	fn pending_ranges(&self) -> Vec<usize> {
+		if self.complete {
+			return Vec::new()
+		}
+		(0..self.ranges.len()).filter(|range| !self.ranges[*range].complete).collect()
+	}
+
 	/// Check if the state is complete.
 	fn is_complete(&self) -> bool {
 		self.complete
@@ -313,8 +476,21 @@ where
 
 	/// Returns state sync estimated progress.
 	fn progress(&self) -> StateSyncProgress {
-		let cursor = *self.last_key.get(0).and_then(|last| last.get(0)).unwrap_or(&0u8);
-		let percent_done = cursor as u32 * 100 / 256;
+		let done =
+			self.ranges
+				.iter()
+				.enumerate()
+				.map(|(range, progress)| {
+					if progress.complete {
+						return RANGE_WIDTH
+					}
+					let start = range * RANGE_WIDTH;
+					progress.cursor.first().and_then(|last| last.first()).map_or(0, |cursor| {
+						(*cursor as usize).saturating_sub(start).min(RANGE_WIDTH)
+					})
+				})
+				.sum::<usize>();
+		let percent_done = (done * 100 / 256) as u32;
 		StateSyncProgress {
 			percentage: percent_done,
 			size: self.imported_bytes,
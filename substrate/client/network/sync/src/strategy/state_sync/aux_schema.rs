@@ -0,0 +1,151 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Schema for the state sync progress in the aux-db.
+
+use codec::{Decode, Encode};
+use sc_client_api::backend::AuxStore;
+use sp_blockchain::{Error as ClientError, Result as ClientResult};
+use sp_runtime::{traits::Block as BlockT, Justifications};
+
+/// Key of the block whose state is being downloaded.
+const TARGET_KEY: &[u8] = b"state_sync_target";
+
+/// Key of the download progress.
+const PROGRESS_KEY: &[u8] = b"state_sync_progress";
+
+/// Key of the `index`-th chunk downloaded for `range`.
+fn chunk_key(range: u32, index: u32) -> Vec<u8> {
+	(b"state_sync_chunk", range, index).encode()
+}
+
+/// Block whose state is being downloaded.
+#[derive(Encode, Decode)]
+pub(crate) struct StateSyncTarget<B: BlockT> {
+	/// Header of the block.
+	pub header: B::Header,
+	/// Body of the block, if known.
+	pub body: Option<Vec<B::Extrinsic>>,
+	/// Justifications of the block, if known.
+	pub justifications: Option<Justifications>,
+}
+
+/// Download progress of a key range.
+#[derive(Encode, Decode, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct RangeProgress {
+	/// Cursor the next request for the range starts after.
+	pub cursor: Vec<Vec<u8>>,
+	/// Number of chunks stored for the range.
+	pub chunks: u32,
+	/// Whether the whole range was downloaded.
+	pub complete: bool,
+}
+
+/// Download progress of the state of the target block.
+#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Progress<H> {
+	/// Hash of the target block.
+	pub target: H,
+	/// Progress of every key range.
+	pub ranges: Vec<RangeProgress>,
+	/// Bytes downloaded so far.
+	pub imported_bytes: u64,
+}
+
+/// Verified state received in one response, as `(state_root, key_values, parent_storage_keys)`
+/// for every trie it touches.
+pub(crate) type Chunk = Vec<(Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>, Vec<Vec<u8>>)>;
+
+fn load_decode<A: AuxStore, T: Decode>(backend: &A, key: &[u8]) -> ClientResult<Option<T>> {
+	match backend.get_aux(key)? {
+		None => Ok(None),
+		Some(t) => T::decode(&mut &t[..]).map(Some).map_err(|e| {
+			ClientError::Backend(format!("State sync DB is corrupted. Decode error: {e}"))
+		}),
+	}
+}
+
+/// Load the target of an interrupted state sync.
+pub(crate) fn load_target<B: BlockT, A: AuxStore>(
+	backend: &A,
+) -> ClientResult<Option<StateSyncTarget<B>>> {
+	load_decode(backend, TARGET_KEY)
+}
+
+/// Load the progress of an interrupted state sync.
+pub(crate) fn load_progress<H: Decode, A: AuxStore>(
+	backend: &A,
+) -> ClientResult<Option<Progress<H>>> {
+	load_decode(backend, PROGRESS_KEY)
+}
+
+/// Load the `index`-th chunk downloaded for `range`.
+pub(crate) fn load_chunk<A: AuxStore>(
+	backend: &A,
+	range: u32,
+	index: u32,
+) -> ClientResult<Option<Chunk>> {
+	load_decode(backend, &chunk_key(range, index))
+}
+
+/// Start tracking the state sync of `target`.
+pub(crate) fn write_target<B: BlockT, A: AuxStore>(
+	backend: &A,
+	target: &StateSyncTarget<B>,
+	progress: &Progress<B::Hash>,
+) -> ClientResult<()> {
+	backend.insert_aux(
+		&[(TARGET_KEY, target.encode().as_slice()), (PROGRESS_KEY, progress.encode().as_slice())],
+		&[],
+	)
+}
+
+/// Store the `index`-th chunk downloaded for `range` along with the updated progress.
+pub(crate) fn write_chunk<H: Encode, A: AuxStore>(
+	backend: &A,
+	range: u32,
+	index: u32,
+	chunk: &Chunk,
+	progress: &Progress<H>,
+) -> ClientResult<()> {
+	backend.insert_aux(
+		&[
+			(chunk_key(range, index).as_slice(), chunk.encode().as_slice()),
+			(PROGRESS_KEY, progress.encode().as_slice()),
+		],
+		&[],
+	)
+}
+
+/// Remove everything stored for the state sync tracked by `progress`.
+pub(crate) fn clear<H, A: AuxStore>(backend: &A, progress: &Progress<H>) -> ClientResult<()> {
+	let chunk_keys = progress
+		.ranges
+		.iter()
+		.enumerate()
+		.flat_map(|(range, progress)| {
+			(0..progress.chunks).map(move |index| chunk_key(range as u32, index))
+		})
+		.collect::<Vec<_>>();
+	let keys = [TARGET_KEY, PROGRESS_KEY]
+		.into_iter()
+		.chain(chunk_keys.iter().map(Vec::as_slice))
+		.collect::<Vec<_>>();
+
+	backend.insert_aux(&[], &keys)
+}
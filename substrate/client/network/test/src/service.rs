@@ -19,14 +19,16 @@
 use futures::prelude::*;
 use libp2p::{Multiaddr, PeerId};
 
+use sc_block_builder::BlockBuilderBuilder;
 use sc_consensus::{ImportQueue, Link};
 use sc_network::{
 	config::{self, FullNetworkConfiguration, MultiaddrWithPeerId, ProtocolId, TransportConfig},
 	event::Event,
+	litep2p::Litep2pNetworkBackend,
 	peer_store::PeerStore,
-	service::traits::{NotificationEvent, ValidationResult},
-	NetworkEventStream, NetworkPeers, NetworkService, NetworkStateInfo, NetworkWorker,
-	NotificationService,
+	service::traits::{NetworkService as NetworkServiceT, NotificationEvent, ValidationResult},
+	NetworkBackend, NetworkEventStream, NetworkPeers, NetworkService, NetworkStateInfo,
+	NetworkWorker, NotificationService,
 };
 use sc_network_common::role::Roles;
 use sc_network_light::light_client_requests::handler::LightClientRequestHandler;
@@ -37,10 +39,11 @@ use sc_network_sync::{
 	state_request_handler::StateRequestHandler,
 };
 use sp_blockchain::HeaderBackend;
+use sp_consensus::BlockOrigin;
 use sp_runtime::traits::{Block as BlockT, Zero};
 use substrate_test_runtime_client::{
 	runtime::{Block as TestBlock, Hash as TestHash},
-	TestClientBuilder, TestClientBuilderExt as _,
+	ClientBlockImportExt as _, TestClientBuilder, TestClientBuilderExt as _,
 };
 
 use std::{sync::Arc, time::Duration};
@@ -97,6 +100,11 @@ impl TestNetworkBuilder {
 		}
 	}
 
+	pub fn with_client(mut self, client: Arc<substrate_test_runtime_client::TestClient>) -> Self {
+		self.client = Some(client);
+		self
+	}
+
 	pub fn with_config(mut self, config: config::NetworkConfiguration) -> Self {
 		self.config = Some(config);
 		self
@@ -117,7 +125,15 @@ impl TestNetworkBuilder {
 		self
 	}
 
-	pub fn build(mut self) -> (TestNetwork, Option<Box<dyn NotificationService>>) {
+	pub fn build(self) -> (TestNetwork, Option<Box<dyn NotificationService>>) {
+		let (worker, handle) = self.build_backend::<TestNetworkWorker>();
+		(TestNetwork::new(worker), handle)
+	}
+
+	/// Build the network with the backend `N`, which must be driven by the caller.
+	pub fn build_backend<N: NetworkBackend<TestBlock, TestHash>>(
+		mut self,
+	) -> (N, Option<Box<dyn NotificationService>>) {
 		let client = self.client.as_mut().map_or(
 			Arc::new(TestClientBuilder::with_default_backend().build_with_longest_chain().0),
 			|v| v.clone(),
@@ -236,10 +252,7 @@ impl TestNetworkBuilder {
 
 		let genesis_hash =
 			client.hash(Zero::zero()).ok().flatten().expect("Genesis block exists; qed");
-		let worker = NetworkWorker::<
-			substrate_test_runtime_client::runtime::Block,
-			substrate_test_runtime_client::runtime::Hash,
-		>::new(config::Params::<substrate_test_runtime_client::runtime::Block> {
+		let backend = N::new(config::Params::<substrate_test_runtime_client::runtime::Block> {
 			block_announce_config,
 			role: config::Role::Full,
 			executor: Box::new(|f| {
@@ -254,9 +267,9 @@ impl TestNetworkBuilder {
 		})
 		.unwrap();
 
-		let service = worker.service().clone();
+		let service = backend.network_service();
 		tokio::spawn(async move {
-			let _ = chain_sync_network_provider.run(service).await;
+			let _ = chain_sync_network_provider.run(Arc::new(service)).await;
 		});
 		tokio::spawn(async move {
 			loop {
@@ -270,7 +283,7 @@ impl TestNetworkBuilder {
 		});
 		tokio::spawn(engine.run());
 
-		(TestNetwork::new(worker), handle)
+		(backend, handle)
 	}
 }
 
@@ -816,3 +829,159 @@ async fn ensure_public_addresses_consistent_with_transport_not_memory() {
 		.0
 		.start_network();
 }
+
+/// Network configuration listening on a local TCP port, with `reserved_nodes` as reserved nodes of
+/// the block announcement protocol.
+fn tcp_config(reserved_nodes: Vec<MultiaddrWithPeerId>) -> config::NetworkConfiguration {
+	config::NetworkConfiguration {
+		listen_addresses: vec![config::build_multiaddr![Ip4([127, 0, 0, 1]), Tcp(0_u16)]],
+		default_peers_set: config::SetConfig { reserved_nodes, ..Default::default() },
+		..config::NetworkConfiguration::new("test-node", "test-client", Default::default(), None)
+	}
+}
+
+/// Start `backend`, returning its service once it listens on an address.
+async fn start_backend<N: NetworkBackend<TestBlock, TestHash>>(
+	backend: N,
+) -> (Arc<dyn NetworkServiceT>, MultiaddrWithPeerId) {
+	let service = backend.network_service();
+	tokio::spawn(backend.run());
+
+	loop {
+		if let Some(multiaddr) = service.listen_addresses().into_iter().next() {
+			let peer_id = service.local_peer_id();
+			return (service, MultiaddrWithPeerId { multiaddr, peer_id })
+		}
+		tokio::time::sleep(Duration::from_millis(100)).await;
+	}
+}
+
+/// Accept the substream opened by `peer` and wait until it's open.
+async fn accept_substream(handle: &mut Box<dyn NotificationService>, peer: PeerId) {
+	loop {
+		match handle.next_event().await.unwrap() {
+			NotificationEvent::ValidateInboundSubstream { result_tx, .. } => {
+				result_tx.send(ValidationResult::Accept).unwrap();
+			},
+			NotificationEvent::NotificationStreamOpened { peer: remote, .. } => {
+				assert_eq!(remote, peer);
+				return
+			},
+			event => panic!("unexpected event: {event:?}"),
+		}
+	}
+}
+
+/// Wait for the next notification, returning its sender and content.
+async fn notification_received(handle: &mut Box<dyn NotificationService>) -> (PeerId, Vec<u8>) {
+	match tokio::time::timeout(Duration::from_secs(30), handle.next_event())
+		.await
+		.unwrap()
+	{
+		Some(NotificationEvent::NotificationReceived { peer, notification }) =>
+			(peer, notification),
+		event => panic!("unexpected event: {event:?}"),
+	}
+}
+
+/// Connect a node running the backend `N1` to a node running the backend `N2` and exchange
+/// notifications between them.
+async fn notifications_between_backends<N1, N2>()
+where
+	N1: NetworkBackend<TestBlock, TestHash>,
+	N2: NetworkBackend<TestBlock, TestHash>,
+{
+	let (network1, handle1) = TestNetworkBuilder::new()
+		.with_config(tcp_config(Vec::new()))
+		.build_backend::<N1>();
+	let (node1, address1) = start_backend(network1).await;
+
+	let (network2, handle2) = TestNetworkBuilder::new()
+		.with_config(tcp_config(Vec::new()))
+		.with_set_config(config::SetConfig {
+			reserved_nodes: vec![address1.clone()],
+			..Default::default()
+		})
+		.build_backend::<N2>();
+	let (node2, _) = start_backend(network2).await;
+	let (mut handle1, mut handle2) = (handle1.unwrap(), handle2.unwrap());
+
+	let (peer1, peer2) = (node1.local_peer_id(), node2.local_peer_id());
+	tokio::time::timeout(Duration::from_secs(30), async {
+		futures::join!(accept_substream(&mut handle1, peer2), accept_substream(&mut handle2, peer1))
+	})
+	.await
+	.unwrap();
+
+	handle1.send_sync_notification(&peer2, b"hello from node1".to_vec());
+	handle2.send_sync_notification(&peer1, b"hello from node2".to_vec());
+
+	assert_eq!(notification_received(&mut handle2).await, (peer1, b"hello from node1".to_vec()));
+	assert_eq!(notification_received(&mut handle1).await, (peer2, b"hello from node2".to_vec()));
+}
+
+#[tokio::test]
+async fn litep2p_exchanges_notifications_with_libp2p() {
+	notifications_between_backends::<Litep2pNetworkBackend, TestNetworkWorker>().await;
+	notifications_between_backends::<TestNetworkWorker, Litep2pNetworkBackend>().await;
+}
+
+#[tokio::test]
+async fn litep2p_exchanges_notifications_with_litep2p() {
+	notifications_between_backends::<Litep2pNetworkBackend, Litep2pNetworkBackend>().await;
+}
+
+/// Connect a node running the backend `N1` that has a chain of `blocks` blocks to a node running
+/// the backend `N2` and wait until the latter has synced the chain.
+async fn sync_between_backends<N1, N2>(blocks: u64)
+where
+	N1: NetworkBackend<TestBlock, TestHash>,
+	N2: NetworkBackend<TestBlock, TestHash>,
+{
+	let mut client1 =
+		Arc::new(TestClientBuilder::with_default_backend().build_with_longest_chain().0);
+	for _ in 0..blocks {
+		let info = client1.info();
+		let block = BlockBuilderBuilder::new(&*client1)
+			.on_parent_block(info.best_hash)
+			.with_parent_block_number(info.best_number)
+			.build()
+			.unwrap()
+			.build()
+			.unwrap()
+			.block;
+		client1.import(BlockOrigin::Own, block).await.unwrap();
+	}
+	let best_hash = client1.info().best_hash;
+
+	let (network1, _) = TestNetworkBuilder::new()
+		.with_client(client1)
+		.with_config(tcp_config(Vec::new()))
+		.build_backend::<N1>();
+	let (_, address1) = start_backend(network1).await;
+
+	let client2 = Arc::new(TestClientBuilder::with_default_backend().build_with_longest_chain().0);
+	let (network2, _) = TestNetworkBuilder::new()
+		.with_client(client2.clone())
+		.with_config(tcp_config(vec![address1]))
+		.build_backend::<N2>();
+	let _ = start_backend(network2).await;
+
+	tokio::time::timeout(Duration::from_secs(60), async {
+		while client2.info().best_hash != best_hash {
+			tokio::time::sleep(Duration::from_millis(100)).await;
+		}
+	})
+	.await
+	.expect("chain is synced");
+}
+
+#[tokio::test]
+async fn litep2p_syncs_from_libp2p() {
+	sync_between_backends::<TestNetworkWorker, Litep2pNetworkBackend>(10).await;
+}
+
+#[tokio::test]
+async fn libp2p_syncs_from_litep2p() {
+	sync_between_backends::<Litep2pNetworkBackend, TestNetworkWorker>(10).await;
+}
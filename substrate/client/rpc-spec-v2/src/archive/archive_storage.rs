@@ -18,14 +18,22 @@
 
 //! Implementation of the `archive_storage` method.
 
-use std::sync::Arc;
+use std::{cmp::Ordering, iter::Peekable, sync::Arc};
 
 use sc_client_api::{Backend, ChildInfo, StorageKey, StorageProvider};
 use sp_runtime::traits::Block as BlockT;
+use tokio::sync::mpsc;
 
-use crate::common::{
-	events::{ArchiveStorageResult, PaginatedStorageQuery, StorageQueryType},
-	storage::{IterQueryType, QueryIter, Storage},
+use crate::{
+	common::{
+		events::{
+			ArchiveStorageDiffEvent, ArchiveStorageDiffOperationType, ArchiveStorageDiffResult,
+			ArchiveStorageDiffType, ArchiveStorageResult, PaginatedStorageQuery, StorageQueryType,
+			StorageResult,
+		},
+		storage::{IterQueryType, QueryIter, QueryResult, Storage},
+	},
+	hex_string,
 };
 
 /// Generates the events of the `archive_storage` method.
@@ -127,3 +135,306 @@ where
 		ArchiveStorageResult::ok(storage_results, discarded_items)
 	}
 }
+
+/// Parsed item of the `archive_storageDiff` method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffDetails {
+	/// The key prefix of the query.
+	pub key: StorageKey,
+	/// The type of the values reported.
+	pub return_type: ArchiveStorageDiffType,
+	/// The child trie key, if any.
+	pub child_trie_key: Option<StorageKey>,
+}
+
+impl DiffDetails {
+	/// Returns `true` if all the keys reported by `other` are also reported by `self`.
+	fn covers(&self, other: &DiffDetails) -> bool {
+		self.return_type == other.return_type &&
+			self.child_trie_key == other.child_trie_key &&
+			other.key.0.starts_with(&self.key.0)
+	}
+}
+
+/// Removes the items that are already covered by other items.
+///
+/// This ensures a key is reported at most once per return type and child trie.
+pub fn deduplicate_diff_items(items: Vec<DiffDetails>) -> Vec<DiffDetails> {
+	let mut result: Vec<DiffDetails> = Vec::with_capacity(items.len());
+
+	for item in items {
+		if result.iter().any(|existing| existing.covers(&item)) {
+			continue
+		}
+
+		result.retain(|existing| !item.covers(existing));
+		result.push(item);
+	}
+
+	result
+}
+
+/// The maximum number of keys compared by the `archive_storageDiff` method before the
+/// generated events are sent.
+const STORAGE_DIFF_BATCH: usize = 64;
+
+/// Generates the events of the `archive_storageDiff` method.
+pub struct ArchiveStorageDiff<Client, Block, BE> {
+	/// Storage client.
+	client: Storage<Client, Block, BE>,
+}
+
+impl<Client, Block, BE> ArchiveStorageDiff<Client, Block, BE> {
+	/// Constructs a new [`ArchiveStorageDiff`].
+	pub fn new(client: Arc<Client>) -> Self {
+		Self { client: Storage::new(client) }
+	}
+}
+
+impl<Client, Block, BE> ArchiveStorageDiff<Client, Block, BE>
+where
+	Block: BlockT + 'static,
+	BE: Backend<Block> + 'static,
+	Client: StorageProvider<Block, BE> + 'static,
+{
+	/// Report the differences between the `previous_hash` and the `hash` blocks.
+	///
+	/// Events are sent through the provided channel. The last event is either
+	/// [`ArchiveStorageDiffEvent::StorageDiffDone`] or
+	/// [`ArchiveStorageDiffEvent::StorageDiffError`].
+	///
+	/// The database is read synchronously, this must be spawned as a blocking task.
+	pub async fn handle_trie_queries(
+		&self,
+		hash: Block::Hash,
+		previous_hash: Block::Hash,
+		items: Vec<DiffDetails>,
+		tx: mpsc::Sender<ArchiveStorageDiffEvent>,
+	) {
+		for item in deduplicate_diff_items(items) {
+			match self.handle_item(hash, previous_hash, item, &tx).await {
+				Ok(true) => continue,
+				// The receiver has been dropped, no need to continue.
+				Ok(false) => return,
+				Err(error) => {
+					let _ = tx.send(ArchiveStorageDiffEvent::err(error)).await;
+					return
+				},
+			}
+		}
+
+		let _ = tx.send(ArchiveStorageDiffEvent::StorageDiffDone).await;
+	}
+
+	/// Report the differences of a single item.
+	///
+	/// Returns `false` if the receiver of the events has been dropped.
+	async fn handle_item(
+		&self,
+		hash: Block::Hash,
+		previous_hash: Block::Hash,
+		item: DiffDetails,
+		tx: &mpsc::Sender<ArchiveStorageDiffEvent>,
+	) -> Result<bool, String> {
+		let mut start_key = None;
+
+		loop {
+			// The storage iterators are not kept across the await points, each batch resumes
+			// the iteration after the last compared key.
+			let (events, last_key) =
+				self.diff_batch(hash, previous_hash, &item, start_key.as_ref())?;
+
+			for event in events {
+				if tx.send(event).await.is_err() {
+					return Ok(false)
+				}
+			}
+
+			match last_key {
+				Some(key) => start_key = Some(key),
+				None => return Ok(true),
+			}
+		}
+	}
+
+	/// Compare at most [`STORAGE_DIFF_BATCH`] keys of the item, starting after `start_key`.
+	///
+	/// Returns the generated events and the last compared key, or `None` if all the keys
+	/// of the item have been compared.
+	fn diff_batch(
+		&self,
+		hash: Block::Hash,
+		previous_hash: Block::Hash,
+		item: &DiffDetails,
+		start_key: Option<&StorageKey>,
+	) -> Result<(Vec<ArchiveStorageDiffEvent>, Option<StorageKey>), String> {
+		let child_info = item.child_trie_key.as_ref().map(|key| ChildInfo::new_default(&key.0));
+		let child_trie_key = item.child_trie_key.as_ref().map(|key| hex_string(&key.0));
+
+		let mut keys_iter = self
+			.client
+			.raw_keys_iter(hash, &item.key, start_key, child_info.as_ref())?
+			.peekable();
+		let mut previous_keys_iter = self
+			.client
+			.raw_keys_iter(previous_hash, &item.key, start_key, child_info.as_ref())?
+			.peekable();
+
+		let mut events = Vec::new();
+		let mut last_key = None;
+		for _ in 0..STORAGE_DIFF_BATCH {
+			let Some((key, operation_type)) =
+				next_diff_key(&mut keys_iter, &mut previous_keys_iter)
+			else {
+				return Ok((events, None))
+			};
+			last_key = Some(key.clone());
+
+			let result = match operation_type {
+				ArchiveStorageDiffOperationType::Added =>
+					self.query(hash, &key, item.return_type, child_info.as_ref())?,
+				ArchiveStorageDiffOperationType::Deleted =>
+					self.query(previous_hash, &key, item.return_type, child_info.as_ref())?,
+				ArchiveStorageDiffOperationType::Modified => {
+					// Compare the hashes to avoid fetching the values of unchanged keys.
+					let current = self.client.query_hash(hash, &key, child_info.as_ref())?;
+					let previous =
+						self.client.query_hash(previous_hash, &key, child_info.as_ref())?;
+					if current == previous {
+						continue
+					}
+
+					match item.return_type {
+						ArchiveStorageDiffType::Hash => current,
+						ArchiveStorageDiffType::Value =>
+							self.client.query_value(hash, &key, child_info.as_ref())?,
+					}
+				},
+			};
+
+			let Some(StorageResult { key, result }) = result else { continue };
+
+			events.push(ArchiveStorageDiffEvent::StorageDiff(ArchiveStorageDiffResult {
+				key,
+				result,
+				operation_type,
+				child_trie_key: child_trie_key.clone(),
+			}));
+		}
+
+		Ok((events, last_key))
+	}
+
+	/// Fetch the value or the hash of the provided key.
+	fn query(
+		&self,
+		hash: Block::Hash,
+		key: &StorageKey,
+		return_type: ArchiveStorageDiffType,
+		child_info: Option<&ChildInfo>,
+	) -> QueryResult {
+		match return_type {
+			ArchiveStorageDiffType::Value => self.client.query_value(hash, key, child_info),
+			ArchiveStorageDiffType::Hash => self.client.query_hash(hash, key, child_info),
+		}
+	}
+}
+
+/// Advance the two sorted key iterators and return the next key that may be part of the diff.
+///
+/// Keys only present in the first iterator are added, keys only present in the second
+/// iterator are deleted. Keys present in both iterators are reported as modified and
+/// their values must be compared by the caller.
+fn next_diff_key<I, P>(
+	keys_iter: &mut Peekable<I>,
+	previous_keys_iter: &mut Peekable<P>,
+) -> Option<(StorageKey, ArchiveStorageDiffOperationType)>
+where
+	I: Iterator<Item = StorageKey>,
+	P: Iterator<Item = StorageKey>,
+{
+	let ordering = match (keys_iter.peek(), previous_keys_iter.peek()) {
+		(None, None) => return None,
+		(Some(_), None) => Ordering::Less,
+		(None, Some(_)) => Ordering::Greater,
+		(Some(key), Some(previous_key)) => key.0.cmp(&previous_key.0),
+	};
+
+	match ordering {
+		Ordering::Less => keys_iter.next().map(|key| (key, ArchiveStorageDiffOperationType::Added)),
+		Ordering::Greater => previous_keys_iter
+			.next()
+			.map(|key| (key, ArchiveStorageDiffOperationType::Deleted)),
+		Ordering::Equal => {
+			previous_keys_iter.next();
+			keys_iter.next().map(|key| (key, ArchiveStorageDiffOperationType::Modified))
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn item(key: &[u8], return_type: ArchiveStorageDiffType) -> DiffDetails {
+		DiffDetails { key: StorageKey(key.to_vec()), return_type, child_trie_key: None }
+	}
+
+	#[test]
+	fn dedup_diff_items() {
+		let items = vec![
+			item(b"ab", ArchiveStorageDiffType::Value),
+			item(b"a", ArchiveStorageDiffType::Value),
+			item(b"abc", ArchiveStorageDiffType::Hash),
+			item(b"b", ArchiveStorageDiffType::Value),
+			item(b"a", ArchiveStorageDiffType::Value),
+		];
+
+		assert_eq!(
+			deduplicate_diff_items(items),
+			vec![
+				item(b"a", ArchiveStorageDiffType::Value),
+				item(b"abc", ArchiveStorageDiffType::Hash),
+				item(b"b", ArchiveStorageDiffType::Value),
+			]
+		);
+
+		// Child tries are deduplicated independently.
+		let child = DiffDetails {
+			key: StorageKey(b"ab".to_vec()),
+			return_type: ArchiveStorageDiffType::Value,
+			child_trie_key: Some(StorageKey(b"child".to_vec())),
+		};
+		let items = vec![item(b"a", ArchiveStorageDiffType::Value), child.clone()];
+		assert_eq!(
+			deduplicate_diff_items(items),
+			vec![item(b"a", ArchiveStorageDiffType::Value), child]
+		);
+	}
+
+	#[test]
+	fn diff_keys_are_merged() {
+		let keys = |keys: &[&[u8]]| {
+			keys.iter().map(|key| StorageKey(key.to_vec())).collect::<Vec<_>>().into_iter()
+		};
+
+		let mut current = keys(&[b"a", b"c", b"d"]).peekable();
+		let mut previous = keys(&[b"b", b"c", b"e"]).peekable();
+
+		let mut result = Vec::new();
+		while let Some((key, ty)) = next_diff_key(&mut current, &mut previous) {
+			result.push((key.0, ty));
+		}
+
+		assert_eq!(
+			result,
+			vec![
+				(b"a".to_vec(), ArchiveStorageDiffOperationType::Added),
+				(b"b".to_vec(), ArchiveStorageDiffOperationType::Deleted),
+				(b"c".to_vec(), ArchiveStorageDiffOperationType::Modified),
+				(b"d".to_vec(), ArchiveStorageDiffOperationType::Added),
+				(b"e".to_vec(), ArchiveStorageDiffOperationType::Deleted),
+			]
+		);
+	}
+}
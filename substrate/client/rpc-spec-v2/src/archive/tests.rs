@@ -18,8 +18,9 @@
 
 use crate::{
 	common::events::{
-		ArchiveStorageMethodOk, ArchiveStorageResult, PaginatedStorageQuery, StorageQueryType,
-		StorageResultType,
+		ArchiveStorageDiffEvent, ArchiveStorageDiffItem, ArchiveStorageDiffOperationType,
+		ArchiveStorageDiffResult, ArchiveStorageDiffType, ArchiveStorageMethodOk,
+		ArchiveStorageResult, PaginatedStorageQuery, StorageQueryType, StorageResultType,
 	},
 	hex_string, MethodResult,
 };
@@ -32,13 +33,14 @@ use super::{
 use assert_matches::assert_matches;
 use codec::{Decode, Encode};
 use jsonrpsee::{
-	core::EmptyServerParams as EmptyParams, rpc_params, MethodsError as Error, RpcModule,
+	core::{server::Subscription as RpcSubscription, EmptyServerParams as EmptyParams},
+	rpc_params, MethodsError as Error, RpcModule,
 };
 use sc_block_builder::BlockBuilderBuilder;
 use sc_client_api::ChildInfo;
 use sp_blockchain::HeaderBackend;
 use sp_consensus::BlockOrigin;
-use sp_core::{Blake2Hasher, Hasher};
+use sp_core::{testing::TaskExecutor, Blake2Hasher, Hasher};
 use sp_runtime::{
 	traits::{Block as BlockT, Header as HeaderT},
 	SaturatedConversion,
@@ -78,6 +80,7 @@ fn setup_api(
 		client.clone(),
 		backend,
 		CHAIN_GENESIS,
+		Arc::new(TaskExecutor::default()),
 		ArchiveConfig { max_descendant_responses, max_queried_items },
 	)
 	.into_rpc();
@@ -85,6 +88,15 @@ fn setup_api(
 	(client, api)
 }
 
+async fn get_next_event<T: serde::de::DeserializeOwned>(sub: &mut RpcSubscription) -> T {
+	let (event, _sub_id) = tokio::time::timeout(std::time::Duration::from_secs(60), sub.next())
+		.await
+		.unwrap()
+		.unwrap()
+		.unwrap();
+	event
+}
+
 #[tokio::test]
 async fn archive_genesis() {
 	let (_client, api) = setup_api(MAX_PAGINATION_LIMIT, MAX_QUERIED_LIMIT);
@@ -838,3 +850,272 @@ async fn archive_storage_discarded_items() {
 		_ => panic!("Unexpected result"),
 	};
 }
+
+#[tokio::test]
+async fn archive_storage_diff_main_trie() {
+	let (mut client, api) = setup_api(MAX_PAGINATION_LIMIT, MAX_QUERIED_LIMIT);
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap();
+	builder.push_storage_change(b":A".to_vec(), Some(b"B".to_vec())).unwrap();
+	builder.push_storage_change(b":AA".to_vec(), Some(b"BB".to_vec())).unwrap();
+	let prev_block = builder.build().unwrap().block;
+	let prev_hash = format!("{:?}", prev_block.header.hash());
+	client.import(BlockOrigin::Own, prev_block.clone()).await.unwrap();
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(prev_block.hash())
+		.with_parent_block_number(1)
+		.build()
+		.unwrap();
+	builder.push_storage_change(b":A".to_vec(), Some(b"11".to_vec())).unwrap();
+	builder.push_storage_change(b":AA".to_vec(), None).unwrap();
+	builder.push_storage_change(b":AAA".to_vec(), Some(b"222".to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	// Search for items in the main trie:
+	// - values of keys under ":A"
+	// - hashes of keys under ":AA"
+	let items = vec![
+		ArchiveStorageDiffItem::<String> {
+			key: hex_string(b":A"),
+			return_type: ArchiveStorageDiffType::Value,
+			child_trie_key: None,
+		},
+		ArchiveStorageDiffItem::<String> {
+			key: hex_string(b":AA"),
+			return_type: ArchiveStorageDiffType::Hash,
+			child_trie_key: None,
+		},
+	];
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
+			rpc_params![&block_hash, items.clone(), &prev_hash],
+		)
+		.await
+		.unwrap();
+
+	let event = get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await;
+	assert_eq!(
+		ArchiveStorageDiffEvent::StorageDiff(ArchiveStorageDiffResult {
+			key: hex_string(b":A"),
+			result: StorageResultType::Value(hex_string(b"11")),
+			operation_type: ArchiveStorageDiffOperationType::Modified,
+			child_trie_key: None,
+		}),
+		event,
+	);
+
+	let event = get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await;
+	assert_eq!(
+		ArchiveStorageDiffEvent::StorageDiff(ArchiveStorageDiffResult {
+			key: hex_string(b":AA"),
+			result: StorageResultType::Value(hex_string(b"BB")),
+			operation_type: ArchiveStorageDiffOperationType::Deleted,
+			child_trie_key: None,
+		}),
+		event,
+	);
+
+	let event = get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await;
+	assert_eq!(
+		ArchiveStorageDiffEvent::StorageDiff(ArchiveStorageDiffResult {
+			key: hex_string(b":AAA"),
+			result: StorageResultType::Value(hex_string(b"222")),
+			operation_type: ArchiveStorageDiffOperationType::Added,
+			child_trie_key: None,
+		}),
+		event,
+	);
+
+	// Hashes of the ":AA" prefix.
+	let event = get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await;
+	assert_eq!(
+		ArchiveStorageDiffEvent::StorageDiff(ArchiveStorageDiffResult {
+			key: hex_string(b":AA"),
+			result: StorageResultType::Hash(format!("{:?}", Blake2Hasher::hash(b"BB"))),
+			operation_type: ArchiveStorageDiffOperationType::Deleted,
+			child_trie_key: None,
+		}),
+		event,
+	);
+
+	let event = get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await;
+	assert_eq!(
+		ArchiveStorageDiffEvent::StorageDiff(ArchiveStorageDiffResult {
+			key: hex_string(b":AAA"),
+			result: StorageResultType::Hash(format!("{:?}", Blake2Hasher::hash(b"222"))),
+			operation_type: ArchiveStorageDiffOperationType::Added,
+			child_trie_key: None,
+		}),
+		event,
+	);
+
+	let event = get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await;
+	assert_eq!(ArchiveStorageDiffEvent::StorageDiffDone, event);
+
+	// The parent block is used when the previous hash is not provided.
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
+			rpc_params![&block_hash, items, Option::<String>::None],
+		)
+		.await
+		.unwrap();
+	for _ in 0..5 {
+		let event = get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await;
+		assert_matches!(event, ArchiveStorageDiffEvent::StorageDiff(_));
+	}
+	let event = get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await;
+	assert_eq!(ArchiveStorageDiffEvent::StorageDiffDone, event);
+}
+
+#[tokio::test]
+async fn archive_storage_diff_many_keys() {
+	let (mut client, api) = setup_api(MAX_PAGINATION_LIMIT, MAX_QUERIED_LIMIT);
+	let keys: Vec<_> = (0..100u8).map(|i| format!(":B{:03}", i).into_bytes()).collect();
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap();
+	for key in &keys {
+		builder.push_storage_change(key.clone(), Some(b"0".to_vec())).unwrap();
+	}
+	let prev_block = builder.build().unwrap().block;
+	let prev_hash = format!("{:?}", prev_block.header.hash());
+	client.import(BlockOrigin::Own, prev_block.clone()).await.unwrap();
+
+	// Modify every other key, the diff spans more keys than are compared at once.
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(prev_block.hash())
+		.with_parent_block_number(1)
+		.build()
+		.unwrap();
+	for key in keys.iter().step_by(2) {
+		builder.push_storage_change(key.clone(), Some(b"1".to_vec())).unwrap();
+	}
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let items = vec![ArchiveStorageDiffItem::<String> {
+		key: hex_string(b":B"),
+		return_type: ArchiveStorageDiffType::Value,
+		child_trie_key: None,
+	}];
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
+			rpc_params![&block_hash, items, &prev_hash],
+		)
+		.await
+		.unwrap();
+
+	for key in keys.iter().step_by(2) {
+		let event = get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await;
+		assert_eq!(
+			ArchiveStorageDiffEvent::StorageDiff(ArchiveStorageDiffResult {
+				key: hex_string(key),
+				result: StorageResultType::Value(hex_string(b"1")),
+				operation_type: ArchiveStorageDiffOperationType::Modified,
+				child_trie_key: None,
+			}),
+			event,
+		);
+	}
+	let event = get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await;
+	assert_eq!(ArchiveStorageDiffEvent::StorageDiffDone, event);
+}
+
+#[tokio::test]
+async fn archive_storage_diff_no_changes() {
+	let (mut client, api) = setup_api(MAX_PAGINATION_LIMIT, MAX_QUERIED_LIMIT);
+
+	let block = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap()
+		.build()
+		.unwrap()
+		.block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	// The child trie is not modified by the block.
+	let items = vec![ArchiveStorageDiffItem::<String> {
+		key: "0x".into(),
+		return_type: ArchiveStorageDiffType::Value,
+		child_trie_key: Some(hex_string(&CHILD_STORAGE_KEY)),
+	}];
+	let mut sub = api
+		.subscribe_unbounded("archive_unstable_storageDiff", rpc_params![&block_hash, items])
+		.await
+		.unwrap();
+
+	let event = get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await;
+	assert_eq!(ArchiveStorageDiffEvent::StorageDiffDone, event);
+}
+
+#[tokio::test]
+async fn archive_storage_diff_invalid_params() {
+	let (mut client, api) = setup_api(MAX_PAGINATION_LIMIT, 1);
+
+	let block = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap()
+		.build()
+		.unwrap()
+		.block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	// Invalid hex-encoded key.
+	let items = vec![ArchiveStorageDiffItem::<String> {
+		key: "0xInvalidParam".into(),
+		return_type: ArchiveStorageDiffType::Value,
+		child_trie_key: None,
+	}];
+	let mut sub = api
+		.subscribe_unbounded("archive_unstable_storageDiff", rpc_params![&block_hash, items])
+		.await
+		.unwrap();
+	let event = get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await;
+	assert_matches!(event, ArchiveStorageDiffEvent::StorageDiffError(_));
+
+	// Too many items.
+	let item = ArchiveStorageDiffItem::<String> {
+		key: hex_string(b":A"),
+		return_type: ArchiveStorageDiffType::Value,
+		child_trie_key: None,
+	};
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
+			rpc_params![&block_hash, vec![item.clone(), item]],
+		)
+		.await
+		.unwrap();
+	let event = get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await;
+	assert_matches!(event, ArchiveStorageDiffEvent::StorageDiffError(_));
+
+	// Unknown block without a previous hash.
+	let invalid_hash = hex_string(&INVALID_HASH);
+	let items: Vec<ArchiveStorageDiffItem<String>> = vec![];
+	let mut sub = api
+		.subscribe_unbounded("archive_unstable_storageDiff", rpc_params![&invalid_hash, items])
+		.await
+		.unwrap();
+	let event = get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await;
+	assert_matches!(event, ArchiveStorageDiffEvent::StorageDiffError(_));
+}
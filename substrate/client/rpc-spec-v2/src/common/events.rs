@@ -143,6 +143,102 @@ pub struct ArchiveStorageMethodErr {
 	pub error: String,
 }
 
+/// The type of the archive storage difference query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArchiveStorageDiffType {
+	/// The result is provided as value of the key.
+	Value,
+	/// The result is provided as hash of the value of the key.
+	Hash,
+}
+
+/// The storage item to query for the difference between two blocks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveStorageDiffItem<Key> {
+	/// The provided key.
+	///
+	/// All the descendants of this key are reported.
+	pub key: Key,
+	/// The type of the storage query.
+	pub return_type: ArchiveStorageDiffType,
+	/// The child trie key if provided.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	pub child_trie_key: Option<Key>,
+}
+
+/// The operation type of a storage difference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArchiveStorageDiffOperationType {
+	/// The key is present in the block, but not in the previous block.
+	Added,
+	/// The key is present in both blocks, but the value differs.
+	Modified,
+	/// The key is present in the previous block, but not in the block.
+	Deleted,
+}
+
+/// A storage difference reported by the `archive_storageDiff` method.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveStorageDiffResult {
+	/// The hex-encoded key of the result.
+	pub key: String,
+	/// The result of the query.
+	///
+	/// For deleted keys this contains the value (or hash) from the previous block.
+	#[serde(flatten)]
+	pub result: StorageResultType,
+	/// The operation type.
+	#[serde(rename = "type")]
+	pub operation_type: ArchiveStorageDiffOperationType,
+	/// The hex-encoded child trie key if the difference was found in a child trie.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	pub child_trie_key: Option<String>,
+}
+
+/// The error of the `archive_storageDiff` method.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveStorageDiffError {
+	/// Reported error.
+	pub error: String,
+}
+
+/// The events generated by the `archive_storageDiff` method.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "event")]
+pub enum ArchiveStorageDiffEvent {
+	/// A storage difference between the two blocks.
+	StorageDiff(ArchiveStorageDiffResult),
+	/// The method encountered an error and no further events are generated.
+	StorageDiffError(ArchiveStorageDiffError),
+	/// All the differences have been reported.
+	StorageDiffDone,
+}
+
+impl ArchiveStorageDiffEvent {
+	/// Create a new `ArchiveStorageDiffEvent::StorageDiffError` event.
+	pub fn err(error: String) -> Self {
+		Self::StorageDiffError(ArchiveStorageDiffError { error })
+	}
+
+	/// Checks if the event is a `StorageDiffDone` event.
+	pub fn is_done(&self) -> bool {
+		matches!(self, Self::StorageDiffDone)
+	}
+
+	/// Checks if the event is a `StorageDiffError` event.
+	pub fn is_err(&self) -> bool {
+		matches!(self, Self::StorageDiffError(_))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -270,4 +366,52 @@ mod tests {
 		let dec: PaginatedStorageQuery<&str> = serde_json::from_str(exp).unwrap();
 		assert_eq!(dec, item);
 	}
+
+	#[test]
+	fn archive_storage_diff_events() {
+		let item = ArchiveStorageDiffItem {
+			key: "0x1",
+			return_type: ArchiveStorageDiffType::Value,
+			child_trie_key: None,
+		};
+		// Encode
+		let ser = serde_json::to_string(&item).unwrap();
+		let exp = r#"{"key":"0x1","returnType":"value"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageDiffItem<&str> = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, item);
+
+		let event = ArchiveStorageDiffEvent::StorageDiff(ArchiveStorageDiffResult {
+			key: "0x1".into(),
+			result: StorageResultType::Hash("0x2".into()),
+			operation_type: ArchiveStorageDiffOperationType::Modified,
+			child_trie_key: Some("0x3".into()),
+		});
+		// Encode
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"storageDiff","key":"0x1","hash":"0x2","type":"modified","childTrieKey":"0x3"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageDiffEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, event);
+
+		let event = ArchiveStorageDiffEvent::StorageDiffDone;
+		// Encode
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"storageDiffDone"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageDiffEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, event);
+
+		let event = ArchiveStorageDiffEvent::err("reason".into());
+		// Encode
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"storageDiffError","error":"reason"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageDiffEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, event);
+	}
 }
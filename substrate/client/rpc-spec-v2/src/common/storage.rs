@@ -146,6 +146,30 @@ where
 			.unwrap_or_else(|error| QueryResult::Err(error.to_string()))
 	}
 
+	/// Iterate over all the keys that start with the provided key.
+	///
+	/// The iteration starts after the `pagination_start_key`, if provided.
+	pub fn raw_keys_iter(
+		&self,
+		hash: Block::Hash,
+		key: &StorageKey,
+		pagination_start_key: Option<&StorageKey>,
+		child_key: Option<&ChildInfo>,
+	) -> Result<impl Iterator<Item = StorageKey>, String> {
+		let keys_iter = if let Some(child_key) = child_key {
+			self.client.child_storage_keys(
+				hash,
+				child_key.to_owned(),
+				Some(key),
+				pagination_start_key,
+			)
+		} else {
+			self.client.storage_keys(hash, Some(key), pagination_start_key)
+		};
+
+		keys_iter.map_err(|err| err.to_string())
+	}
+
 	/// Iterate over at most the provided number of keys.
 	///
 	/// Returns the storage result with a potential next key to resume iteration.
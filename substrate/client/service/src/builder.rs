@@ -27,11 +27,11 @@ use crate::{
 };
 use futures::{channel::oneshot, future::ready, FutureExt, StreamExt};
 use jsonrpsee::RpcModule;
-use log::info;
+use log::{debug, info, warn};
 use prometheus_endpoint::Registry;
 use sc_chain_spec::get_extension;
 use sc_client_api::{
-	execution_extensions::ExecutionExtensions, proof_provider::ProofProvider, BadBlocks,
+	execution_extensions::ExecutionExtensions, proof_provider::ProofProvider, AuxStore, BadBlocks,
 	BlockBackend, BlockchainEvents, ExecutorProvider, ForkBlocks, StorageProvider, UsageProvider,
 };
 use sc_client_db::{Backend, DatabaseSettings};
@@ -42,9 +42,11 @@ use sc_executor::{
 };
 use sc_keystore::LocalKeystore;
 use sc_network::{
-	config::{FullNetworkConfiguration, SyncMode},
+	config::{FullNetworkConfiguration, NetworkBackendType, SyncMode},
+	litep2p::Litep2pNetworkBackend,
 	peer_store::PeerStore,
-	NetworkService, NetworkStateInfo, NetworkStatusProvider,
+	service::traits::NetworkService,
+	NetworkBackend, NetworkStateInfo, NetworkWorker,
 };
 use sc_network_bitswap::BitswapRequestHandler;
 use sc_network_common::role::Roles;
@@ -77,7 +79,11 @@ use sp_consensus::block_validation::{
 use sp_core::traits::{CodeExecutor, SpawnNamed};
 use sp_keystore::KeystorePtr;
 use sp_runtime::traits::{Block as BlockT, BlockIdTo, NumberFor, Zero};
-use std::{str::FromStr, sync::Arc, time::SystemTime};
+use std::{
+	str::FromStr,
+	sync::Arc,
+	time::{Duration, SystemTime},
+};
 
 /// Full client type.
 pub type TFullClient<TBl, TRtApi, TExec> =
@@ -86,6 +92,9 @@ pub type TFullClient<TBl, TRtApi, TExec> =
 /// Full client backend type.
 pub type TFullBackend<TBl> = Backend<TBl>;
 
+/// Interval at which the database is compacted if blocks were pruned.
+const DB_COMPACTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
 /// Full client call executor type.
 pub type TFullCallExecutor<TBl, TExec> = crate::client::LocalCallExecutor<TBl, Backend<TBl>, TExec>;
 
@@ -255,9 +264,31 @@ where
 		client
 	};
 
+	if config.blocks_compaction && !config.blocks_pruning.is_archive() {
+		task_manager.spawn_handle().spawn(
+			"db-compaction",
+			Some("db"),
+			compact_pruned_blocks(backend.clone()),
+		);
+	}
+
 	Ok((client, backend, keystore_container, task_manager))
 }
 
+/// Periodically compact the database to reclaim the space of the pruned blocks.
+async fn compact_pruned_blocks<TBl: BlockT>(backend: Arc<TFullBackend<TBl>>) {
+	loop {
+		futures_timer::Delay::new(DB_COMPACTION_INTERVAL).await;
+		let backend = backend.clone();
+		match tokio::task::spawn_blocking(move || backend.compact_pruned_blocks()).await {
+			Ok(Ok(true)) => debug!(target: "db", "Compacted the database after pruning blocks"),
+			Ok(Ok(false)) => {},
+			Ok(Err(e)) => warn!(target: "db", "Failed to compact the database: {}", e),
+			Err(e) => warn!(target: "db", "Database compaction task failed: {}", e),
+		}
+	}
+}
+
 /// Creates a [`NativeElseWasmExecutor`] according to [`Configuration`].
 pub fn new_native_or_wasm_executor<D: NativeExecutionDispatch>(
 	config: &Configuration,
@@ -340,19 +371,6 @@ where
 	)
 }
 
-/// Shared network instance implementing a set of mandatory traits.
-pub trait SpawnTaskNetwork<Block: BlockT>:
-	NetworkStateInfo + NetworkStatusProvider + Send + Sync + 'static
-{
-}
-
-impl<T, Block> SpawnTaskNetwork<Block> for T
-where
-	Block: BlockT,
-	T: NetworkStateInfo + NetworkStatusProvider + Send + Sync + 'static,
-{
-}
-
 /// Parameters to pass into `build`.
 pub struct SpawnTasksParams<'a, TBl: BlockT, TCl, TExPool, TRpc, Backend> {
 	/// The service configuration.
@@ -371,7 +389,7 @@ pub struct SpawnTasksParams<'a, TBl: BlockT, TCl, TExPool, TRpc, Backend> {
 	pub rpc_builder:
 		Box<dyn Fn(DenyUnsafe, SubscriptionTaskExecutor) -> Result<RpcModule<TRpc>, Error>>,
 	/// A shared network instance.
-	pub network: Arc<dyn SpawnTaskNetwork<TBl>>,
+	pub network: Arc<dyn NetworkService>,
 	/// A Sender for RPC requests.
 	pub system_rpc_tx: TracingUnboundedSender<sc_rpc::system::Request<TBl>>,
 	/// Controller for transactions handlers
@@ -682,6 +700,7 @@ where
 			client.clone(),
 			backend.clone(),
 			genesis_hash,
+			task_executor.clone(),
 			// Defaults to sensible limits for the `Archive`.
 			sc_rpc_spec_v2::archive::ArchiveConfig::default(),
 		)
@@ -752,7 +771,7 @@ pub fn build_network<TBl, TExPool, TImpQu, TCl>(
 	params: BuildNetworkParams<TBl, TExPool, TImpQu, TCl>,
 ) -> Result<
 	(
-		Arc<NetworkService<TBl, <TBl as BlockT>::Hash>>,
+		Arc<dyn NetworkService>,
 		TracingUnboundedSender<sc_rpc::system::Request<TBl>>,
 		sc_network_transactions::TransactionsHandlerController<<TBl as BlockT>::Hash>,
 		NetworkStarter,
@@ -768,6 +787,7 @@ where
 		+ BlockBackend<TBl>
 		+ BlockIdTo<TBl, Error = sp_blockchain::Error>
 		+ ProofProvider<TBl>
+		+ AuxStore
 		+ HeaderBackend<TBl>
 		+ BlockchainEvents<TBl>
 		+ 'static,
@@ -951,8 +971,40 @@ where
 	};
 
 	let has_bootnodes = !network_params.network_config.network_config.boot_nodes.is_empty();
-	let network_mut = sc_network::NetworkWorker::new(network_params)?;
-	let network = network_mut.service().clone();
+	let (network, network_future) = match config.network.network_backend {
+		NetworkBackendType::Libp2p => {
+			let network_mut = NetworkWorker::<TBl, <TBl as BlockT>::Hash>::new(network_params)?;
+			let network = network_mut.network_service();
+			let future = build_network_future(
+				network_mut,
+				client.clone(),
+				sync_service.clone(),
+				config.announce_block,
+			)
+			.boxed();
+
+			(network, future)
+		},
+		NetworkBackendType::Litep2p => {
+			let network_mut = <Litep2pNetworkBackend as NetworkBackend<
+				TBl,
+				<TBl as BlockT>::Hash,
+			>>::new(network_params)?;
+			let network = <Litep2pNetworkBackend as NetworkBackend<
+				TBl,
+				<TBl as BlockT>::Hash,
+			>>::network_service(&network_mut);
+			let future = build_network_future::<_, _, <TBl as BlockT>::Hash, _>(
+				network_mut,
+				client.clone(),
+				sync_service.clone(),
+				config.announce_block,
+			)
+			.boxed();
+
+			(network, future)
+		},
+	};
 
 	let (tx_handler, tx_handler_controller) = transactions_handler_proto.build(
 		network.clone(),
@@ -965,7 +1017,7 @@ where
 	spawn_handle.spawn_blocking(
 		"chain-sync-network-service-provider",
 		Some("networking"),
-		chain_sync_network_provider.run(network.clone()),
+		chain_sync_network_provider.run(Arc::new(network.clone())),
 	);
 	spawn_handle.spawn("import-queue", None, import_queue.run(Box::new(sync_service_import_queue)));
 	spawn_handle.spawn_blocking("syncing", None, engine.run());
@@ -976,7 +1028,7 @@ where
 		Some("networking"),
 		build_system_rpc_future(
 			config.role.clone(),
-			network_mut.service().clone(),
+			network.clone(),
 			sync_service.clone(),
 			client.clone(),
 			system_rpc_rx,
@@ -984,9 +1036,6 @@ where
 		),
 	);
 
-	let future =
-		build_network_future(network_mut, client, sync_service.clone(), config.announce_block);
-
 	// TODO: Normally, one is supposed to pass a list of notifications protocols supported by the
 	// node through the `NetworkConfiguration` struct. But because this function doesn't know in
 	// advance which components, such as GrandPa or Polkadot, will be plugged on top of the
@@ -1020,7 +1069,7 @@ where
 			return
 		}
 
-		future.await
+		network_future.await
 	});
 
 	Ok((
@@ -0,0 +1,498 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! State snapshots.
+//!
+//! A snapshot holds the full state of a finalized block, split into chunks that each carry the
+//! hash of their content, along with the warp sync proofs that finalize the block starting from
+//! genesis. Importing a snapshot verifies the proofs against the genesis authorities and the
+//! state against the state root of the block, so a fresh node can start from it instead of
+//! downloading the state from the network.
+
+use crate::error::Error;
+use codec::{Decode, Encode, IoReader as CodecIoReader};
+use futures::{future, prelude::*};
+use log::info;
+use sc_client_api::{BlockBackend, HeaderBackend, KeyValueStates, StorageProvider};
+use sc_consensus::{
+	import_queue::{BlockImportError, BlockImportStatus, ImportQueue, IncomingBlock, Link},
+	ImportedState,
+};
+use sc_network_sync::strategy::warp::{
+	AuthorityList, EncodedProof, VerificationResult, WarpSyncProvider,
+};
+use sp_consensus::BlockOrigin;
+use sp_core::{
+	hashing::blake2_256,
+	storage::{well_known_keys, ChildInfo},
+};
+use sp_runtime::{
+	traits::{Block as BlockT, Header, NumberFor, Zero},
+	Justifications,
+};
+use sp_state_machine::KeyValueStorageLevel;
+use std::{
+	collections::BTreeMap,
+	io::{Read, Write},
+	pin::Pin,
+	sync::Arc,
+	task::Poll,
+};
+
+/// Magic bytes the snapshot file starts with.
+const SNAPSHOT_MAGIC: [u8; 8] = *b"snapshot";
+
+/// Version of the snapshot format.
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// Size in bytes of the key-value pairs after which a chunk is written.
+const CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Block the snapshot is taken at.
+#[derive(Encode, Decode)]
+struct SnapshotHeader<B: BlockT> {
+	/// Header of the block.
+	header: B::Header,
+	/// Body of the block.
+	body: Option<Vec<B::Extrinsic>>,
+	/// Justifications of the block.
+	justifications: Option<Justifications>,
+	/// Encoded warp sync proofs finalizing the block, starting from genesis.
+	finality_proofs: Vec<Vec<u8>>,
+}
+
+/// Child trie the key-value pairs of a chunk belong to.
+#[derive(Encode, Decode, Clone, Debug, PartialEq)]
+struct ChildTrie {
+	/// Prefixed storage key of the child trie.
+	storage_key: Vec<u8>,
+	/// Root of the child trie.
+	root: Vec<u8>,
+}
+
+/// Key-value pairs of a single trie.
+#[derive(Encode, Decode, Clone, Debug, Default, PartialEq)]
+struct StateChunk {
+	/// Child trie of the pairs, `None` for the top trie.
+	child_trie: Option<ChildTrie>,
+	/// Key-value pairs.
+	key_values: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+/// Record following the snapshot header.
+#[derive(Encode, Decode)]
+enum Record {
+	/// Encoded [`StateChunk`] along with its hash.
+	Chunk { data: Vec<u8>, hash: [u8; 32] },
+	/// End of the snapshot, with the number of chunks written.
+	End { chunks: u32 },
+}
+
+/// Writes the records of a snapshot.
+struct RecordWriter<W> {
+	output: W,
+	chunks: u32,
+}
+
+impl<W: Write> RecordWriter<W> {
+	fn write_chunk(&mut self, chunk: &StateChunk) -> Result<(), Error> {
+		let data = chunk.encode();
+		let hash = blake2_256(&data);
+		self.output.write_all(&Record::Chunk { data, hash }.encode())?;
+		self.chunks += 1;
+		Ok(())
+	}
+
+	fn finish(mut self) -> Result<(), Error> {
+		self.output.write_all(&Record::End { chunks: self.chunks }.encode())?;
+		self.output.flush()?;
+		Ok(())
+	}
+}
+
+/// Splits the key-value pairs of a trie into chunks.
+struct ChunkedTrie {
+	chunk: StateChunk,
+	size: usize,
+}
+
+impl ChunkedTrie {
+	fn new(child_trie: Option<ChildTrie>) -> Self {
+		Self { chunk: StateChunk { child_trie, key_values: Vec::new() }, size: 0 }
+	}
+
+	fn push<W: Write>(
+		&mut self,
+		key: Vec<u8>,
+		value: Vec<u8>,
+		writer: &mut RecordWriter<W>,
+	) -> Result<(), Error> {
+		self.size += key.len() + value.len();
+		self.chunk.key_values.push((key, value));
+		if self.size >= CHUNK_SIZE {
+			self.flush(writer)?;
+		}
+		Ok(())
+	}
+
+	fn flush<W: Write>(&mut self, writer: &mut RecordWriter<W>) -> Result<(), Error> {
+		if !self.chunk.key_values.is_empty() {
+			writer.write_chunk(&self.chunk)?;
+			self.chunk.key_values.clear();
+			self.size = 0;
+		}
+		Ok(())
+	}
+}
+
+/// Export a snapshot of the state of the last block finalized by the warp sync proofs of
+/// `warp_sync_provider`.
+///
+/// The proofs are verified starting from `genesis_authorities`. Returns the hash of the block
+/// the snapshot is taken at.
+pub fn export_state_snapshot<B, BA, C>(
+	client: Arc<C>,
+	warp_sync_provider: &dyn WarpSyncProvider<B>,
+	genesis_authorities: AuthorityList,
+	output: impl Write,
+) -> Result<B::Hash, Error>
+where
+	B: BlockT,
+	BA: sc_client_api::backend::Backend<B>,
+	C: HeaderBackend<B> + BlockBackend<B> + StorageProvider<B, BA>,
+{
+	let mut finality_proofs = Vec::new();
+	let mut begin = client.info().genesis_hash;
+	let mut set_id = 0;
+	let mut authorities = genesis_authorities;
+	let header = loop {
+		let proof = warp_sync_provider
+			.generate(begin)
+			.map_err(|e| Error::Other(format!("Failed to generate finality proof: {e}")))?;
+		let result = warp_sync_provider
+			.verify(&proof, set_id, authorities)
+			.map_err(|e| Error::Other(format!("Failed to verify finality proof: {e}")))?;
+		finality_proofs.push(proof.0);
+
+		match result {
+			VerificationResult::Partial(next_set_id, next_authorities, hash) => {
+				set_id = next_set_id;
+				authorities = next_authorities;
+				begin = hash;
+			},
+			VerificationResult::Complete(_, _, header) => break header,
+		}
+	};
+	let hash = header.hash();
+	info!("Exporting state snapshot at #{} ({})...", header.number(), hash);
+
+	let mut writer = RecordWriter { output, chunks: 0 };
+	writer.output.write_all(&(SNAPSHOT_MAGIC, SNAPSHOT_VERSION).encode())?;
+	let snapshot_header = SnapshotHeader::<B> {
+		header,
+		body: client.block_body(hash)?,
+		justifications: client.justifications(hash)?,
+		finality_proofs,
+	};
+	writer.output.write_all(&snapshot_header.encode())?;
+
+	let mut top = ChunkedTrie::new(None);
+	for (key, value) in client.storage_pairs(hash, None, None)? {
+		// Child tries are written separately, their roots are recalculated on import.
+		if !key.0.starts_with(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+			top.push(key.0, value.0, &mut writer)?;
+			continue
+		}
+
+		let child_info = ChildInfo::new_default(
+			&key.0[well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX.len()..],
+		);
+		let mut child = ChunkedTrie::new(Some(ChildTrie { storage_key: key.0, root: value.0 }));
+		for child_key in client.child_storage_keys(hash, child_info.clone(), None, None)? {
+			if let Some(child_value) = client.child_storage(hash, &child_info, &child_key)? {
+				child.push(child_key.0, child_value.0, &mut writer)?;
+			}
+		}
+		child.flush(&mut writer)?;
+	}
+	top.flush(&mut writer)?;
+
+	info!("Exported {} state chunks.", writer.chunks);
+	writer.finish()?;
+	Ok(hash)
+}
+
+/// Verify that `finality_proofs` finalize the block `hash`, starting from the genesis
+/// authorities known to `warp_sync_provider`.
+fn verify_finality_proofs<B: BlockT>(
+	warp_sync_provider: &dyn WarpSyncProvider<B>,
+	finality_proofs: Vec<Vec<u8>>,
+	hash: B::Hash,
+) -> Result<(), Error> {
+	let mut set_id = 0;
+	let mut authorities = warp_sync_provider.current_authorities();
+	for proof in finality_proofs {
+		match warp_sync_provider
+			.verify(&EncodedProof(proof), set_id, authorities)
+			.map_err(|e| Error::Other(format!("Invalid finality proof: {e}")))?
+		{
+			VerificationResult::Partial(next_set_id, next_authorities, _) => {
+				set_id = next_set_id;
+				authorities = next_authorities;
+			},
+			VerificationResult::Complete(_, _, header) if header.hash() == hash => return Ok(()),
+			VerificationResult::Complete(_, _, header) =>
+				return Err(Error::Other(format!(
+					"Finality proofs finalize #{} ({}) instead of the snapshot block",
+					header.number(),
+					header.hash(),
+				))),
+		}
+	}
+
+	Err(Error::Other("Finality proofs do not finalize the snapshot block".into()))
+}
+
+/// Read the state chunks of a snapshot, verifying their hashes.
+fn read_state(reader: &mut impl codec::Input) -> Result<KeyValueStates, Error> {
+	let mut top = Vec::new();
+	let mut children = BTreeMap::<Vec<u8>, KeyValueStorageLevel>::new();
+	let mut chunks = 0u32;
+
+	loop {
+		let record = Record::decode(reader)
+			.map_err(|e| Error::Other(format!("Failed to read state chunk #{chunks}: {e}")))?;
+		let (data, hash) = match record {
+			Record::Chunk { data, hash } => (data, hash),
+			Record::End { chunks: expected } if expected == chunks => break,
+			Record::End { chunks: expected } =>
+				return Err(Error::Other(format!(
+					"Snapshot has {chunks} state chunks, expected {expected}"
+				))),
+		};
+		if blake2_256(&data) != hash {
+			return Err(Error::Other(format!("State chunk #{chunks} is corrupted")))
+		}
+		let chunk = StateChunk::decode(&mut &data[..])
+			.map_err(|e| Error::Other(format!("Failed to decode state chunk #{chunks}: {e}")))?;
+
+		match chunk.child_trie {
+			None => top.extend(chunk.key_values),
+			Some(ChildTrie { storage_key, root }) => children
+				.entry(storage_key.clone())
+				.or_insert_with(|| KeyValueStorageLevel {
+					state_root: root,
+					parent_storage_keys: vec![storage_key],
+					key_values: Vec::new(),
+				})
+				.key_values
+				.extend(chunk.key_values),
+		}
+		chunks += 1;
+	}
+
+	let top = KeyValueStorageLevel {
+		state_root: Vec::new(),
+		parent_storage_keys: Vec::new(),
+		key_values: top,
+	};
+	Ok(KeyValueStates(std::iter::once(top).chain(children.into_values()).collect()))
+}
+
+/// Read a snapshot and turn it into a block to import with its state.
+fn read_snapshot<B: BlockT>(
+	warp_sync_provider: &dyn WarpSyncProvider<B>,
+	input: impl Read,
+) -> Result<IncomingBlock<B>, Error> {
+	let mut reader = CodecIoReader(input);
+	let (magic, version) = <([u8; 8], u32)>::decode(&mut reader)
+		.map_err(|e| Error::Other(format!("Failed to read snapshot header: {e}")))?;
+	if magic != SNAPSHOT_MAGIC {
+		return Err(Error::Other("Input is not a state snapshot".into()))
+	}
+	if version != SNAPSHOT_VERSION {
+		return Err(Error::Other(format!("Unsupported state snapshot version {version}")))
+	}
+	let SnapshotHeader::<B> { header, body, justifications, finality_proofs } =
+		Decode::decode(&mut reader)
+			.map_err(|e| Error::Other(format!("Failed to read snapshot header: {e}")))?;
+	let hash = header.hash();
+
+	verify_finality_proofs(warp_sync_provider, finality_proofs, hash)?;
+	info!("Verified finality of state snapshot at #{} ({}).", header.number(), hash);
+
+	let state = read_state(&mut reader)?;
+	Ok(IncomingBlock {
+		hash,
+		header: Some(header),
+		body,
+		indexed_body: None,
+		justifications,
+		origin: None,
+		allow_missing_state: true,
+		import_existing: true,
+		skip_execution: true,
+		state: Some(ImportedState { block: hash, state }),
+	})
+}
+
+/// Import a state snapshot into a fresh database.
+///
+/// The block of the snapshot is imported with its state through `import_queue`, the same way
+/// as the target block of warp sync. Its state root is checked on import.
+pub fn import_state_snapshot<B, IQ, C>(
+	client: Arc<C>,
+	mut import_queue: IQ,
+	warp_sync_provider: &dyn WarpSyncProvider<B>,
+	input: impl Read,
+) -> Pin<Box<dyn Future<Output = Result<(), Error>> + Send>>
+where
+	C: HeaderBackend<B> + Send + Sync + 'static,
+	B: BlockT,
+	IQ: ImportQueue<B> + 'static,
+{
+	struct WaitLink<B: BlockT> {
+		hash: B::Hash,
+		result: Option<Result<(), BlockImportError>>,
+	}
+
+	impl<B: BlockT> Link<B> for WaitLink<B> {
+		fn blocks_processed(
+			&mut self,
+			_imported: usize,
+			_num_expected_blocks: usize,
+			results: Vec<(Result<BlockImportStatus<NumberFor<B>>, BlockImportError>, B::Hash)>,
+		) {
+			for (result, hash) in results {
+				if hash == self.hash {
+					self.result = Some(result.map(|_| ()));
+				}
+			}
+		}
+	}
+
+	if !client.info().finalized_number.is_zero() {
+		return future::ready(Err(Error::Other(
+			"State snapshots can only be imported into an empty database".into(),
+		)))
+		.boxed()
+	}
+
+	let block = match read_snapshot(warp_sync_provider, input) {
+		Ok(block) => block,
+		Err(e) => return future::ready(Err(e)).boxed(),
+	};
+	let hash = block.hash;
+	info!("Importing state snapshot at {}...", hash);
+	import_queue
+		.service_ref()
+		.import_blocks(BlockOrigin::NetworkInitialSync, vec![block]);
+
+	let mut link = WaitLink::<B> { hash, result: None };
+	future::poll_fn(move |cx| {
+		import_queue.poll_actions(cx, &mut link);
+
+		match link.result.take() {
+			Some(Ok(())) => {
+				let info = client.info();
+				info!(
+					"🎉 Imported state snapshot. Finalized: #{} ({})",
+					info.finalized_number, info.finalized_hash,
+				);
+				Poll::Ready(Ok(()))
+			},
+			Some(Err(e)) =>
+				Poll::Ready(Err(Error::Other(format!("Failed to import state snapshot: {e}")))),
+			None => Poll::Pending,
+		}
+	})
+	.boxed()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn write_records(chunks: &[StateChunk]) -> Vec<u8> {
+		let mut output = Vec::new();
+		let mut writer = RecordWriter { output: &mut output, chunks: 0 };
+		for chunk in chunks {
+			writer.write_chunk(chunk).unwrap();
+		}
+		writer.finish().unwrap();
+		output
+	}
+
+	fn test_chunks() -> Vec<StateChunk> {
+		let child_trie = ChildTrie {
+			storage_key: [well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX, b"child"].concat(),
+			root: vec![1; 32],
+		};
+		vec![
+			StateChunk { child_trie: None, key_values: vec![(vec![1], vec![2])] },
+			StateChunk {
+				child_trie: Some(child_trie.clone()),
+				key_values: vec![(vec![3], vec![4])],
+			},
+			StateChunk { child_trie: None, key_values: vec![(vec![5], vec![6])] },
+			StateChunk { child_trie: Some(child_trie), key_values: vec![(vec![7], vec![8])] },
+		]
+	}
+
+	#[test]
+	fn state_chunks_are_read_back() {
+		let output = write_records(&test_chunks());
+
+		let state = read_state(&mut &output[..]).unwrap();
+
+		assert_eq!(state.0.len(), 2);
+		assert!(state.0[0].state_root.is_empty());
+		assert_eq!(state.0[0].key_values, vec![(vec![1], vec![2]), (vec![5], vec![6])]);
+		assert_eq!(state.0[1].state_root, vec![1; 32]);
+		assert_eq!(
+			state.0[1].parent_storage_keys,
+			vec![[well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX, b"child"].concat()],
+		);
+		assert_eq!(state.0[1].key_values, vec![(vec![3], vec![4]), (vec![7], vec![8])]);
+	}
+
+	#[test]
+	fn corrupted_state_chunk_is_rejected() {
+		let mut output = write_records(&test_chunks());
+		// Flip a byte of the first key.
+		let position = output.iter().position(|byte| *byte == 1).unwrap();
+		output[position] = 9;
+
+		assert!(read_state(&mut &output[..]).is_err());
+	}
+
+	#[test]
+	fn truncated_snapshot_is_rejected() {
+		let output = write_records(&test_chunks());
+
+		// Missing end record.
+		let chunks_len = output.len() - Record::End { chunks: 4 }.encoded_size();
+		assert!(read_state(&mut &output[..chunks_len]).is_err());
+
+		// Missing chunk.
+		let mut records = write_records(&test_chunks()[..3]);
+		records.truncate(records.len() - Record::End { chunks: 3 }.encoded_size());
+		records.extend(Record::End { chunks: 4 }.encode());
+		assert!(read_state(&mut &records[..]).is_err());
+	}
+}
@@ -20,7 +20,10 @@
 
 use prometheus_endpoint::Registry;
 use sc_chain_spec::ChainSpec;
-pub use sc_client_db::{BlocksPruning, Database, DatabaseSource, PruningMode};
+pub use sc_client_db::{
+	BlockTimestampExtractor, BlocksArchiveSettings, BlocksPruning, Database, DatabaseSource,
+	PruningMode,
+};
 pub use sc_executor::{WasmExecutionMethod, WasmtimeInstantiationStrategy};
 pub use sc_informant::OutputFormat;
 pub use sc_network::{
@@ -34,7 +37,7 @@ pub use sc_network::{
 	Multiaddr,
 };
 pub use sc_telemetry::TelemetryEndpoints;
-pub use sc_transaction_pool::Options as TransactionPoolOptions;
+pub use sc_transaction_pool::{TransactionPoolOptions, TransactionPoolType};
 use sp_core::crypto::SecretString;
 use std::{
 	io, iter,
@@ -72,6 +75,14 @@ pub struct Configuration {
 	///
 	/// NOTE: only finalized blocks are subject for removal!
 	pub blocks_pruning: BlocksPruning,
+	/// Flat file archive of finalized block bodies and justifications.
+	pub blocks_archive: Option<BlocksArchiveSettings>,
+	/// Extracts the timestamp of the imported blocks.
+	///
+	/// Required by [`BlocksPruning::Age`].
+	pub block_timestamp: Option<BlockTimestampExtractor>,
+	/// Periodically compact the database to reclaim the space of the pruned blocks.
+	pub blocks_compaction: bool,
 	/// Chain configuration.
 	pub chain_spec: Box<dyn ChainSpec>,
 	/// Wasm execution method.
@@ -243,6 +254,8 @@ impl Configuration {
 			state_pruning: self.state_pruning.clone(),
 			source: self.database.clone(),
 			blocks_pruning: self.blocks_pruning,
+			blocks_archive: self.blocks_archive.clone(),
+			block_timestamp: self.block_timestamp.clone(),
 		}
 	}
 }
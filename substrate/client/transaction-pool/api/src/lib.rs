@@ -26,7 +26,7 @@ use codec::Codec;
 use futures::{Future, Stream};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use sp_core::offchain::TransactionPoolExt;
-use sp_runtime::traits::{Block as BlockT, Member, NumberFor};
+use sp_runtime::traits::{Block as BlockT, Member};
 use std::{collections::HashMap, hash::Hash, marker::PhantomData, pin::Pin, sync::Arc};
 
 const LOG_TARGET: &str = "txpool::api";
@@ -282,10 +282,9 @@ pub trait TransactionPool: Send + Sync {
 	/// Get an iterator for ready transactions ordered by priority.
 	///
 	/// Guarantees to return only when transaction pool got updated at `at` block.
-	/// Guarantees to return immediately when `None` is passed.
 	fn ready_at(
 		&self,
-		at: NumberFor<Self::Block>,
+		at: <Self::Block as BlockT>::Hash,
 	) -> Pin<
 		Box<
 			dyn Future<
@@ -345,6 +344,7 @@ impl<T> ReadyTransactions for std::iter::Empty<T> {
 }
 
 /// Events that the transaction pool listens for.
+#[derive(Debug)]
 pub enum ChainEvent<B: BlockT> {
 	/// New best block have been added to the chain.
 	NewBestBlock {
@@ -355,6 +355,11 @@ pub enum ChainEvent<B: BlockT> {
 		/// If `None`, no re-org happened on import.
 		tree_route: Option<Arc<sp_blockchain::TreeRoute<B>>>,
 	},
+	/// New block that is not the new best block has been added to the chain.
+	NewBlock {
+		/// Hash of the block.
+		hash: B::Hash,
+	},
 	/// An existing block has been finalized.
 	Finalized {
 		/// Hash of just finalized block.
@@ -368,7 +373,9 @@ impl<B: BlockT> ChainEvent<B> {
 	/// Returns the block hash associated to the event.
 	pub fn hash(&self) -> B::Hash {
 		match self {
-			Self::NewBestBlock { hash, .. } | Self::Finalized { hash, .. } => *hash,
+			Self::NewBestBlock { hash, .. } |
+			Self::NewBlock { hash } |
+			Self::Finalized { hash, .. } => *hash,
 		}
 	}
 
@@ -0,0 +1,348 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Utility for building the transaction pool selected in the node configuration.
+
+use crate::{
+	error,
+	fork_aware_txpool::ForkAwareTxPoolFull,
+	graph::{self, base_pool::Transaction, IsValidator},
+	FullPool,
+};
+use async_trait::async_trait;
+use prometheus_endpoint::Registry as PrometheusRegistry;
+use sc_transaction_pool_api::{
+	ChainEvent, ImportNotificationStream, LocalTransactionFor, LocalTransactionPool,
+	MaintainedTransactionPool, PoolFuture, PoolStatus, ReadyTransactions, TransactionFor,
+	TransactionPool, TransactionSource, TransactionStatusStreamFor, TxHash,
+};
+use sp_core::traits::SpawnEssentialNamed;
+use sp_runtime::traits::Block as BlockT;
+use std::{collections::HashMap, future::Future, marker::PhantomData, pin::Pin, sync::Arc};
+
+/// The type of the transaction pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransactionPoolType {
+	/// Single-state transaction pool, validating the transactions against the best block only.
+	#[default]
+	SingleState,
+	/// Fork-aware transaction pool, maintaining a view for every tip of the non-finalized forks.
+	ForkAware,
+}
+
+/// Configuration of the transaction pool.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionPoolOptions {
+	/// The options of the pool queues.
+	pub options: graph::Options,
+	/// The type of the transaction pool.
+	pub pool_type: TransactionPoolType,
+}
+
+impl TransactionPoolOptions {
+	/// Creates the transaction pool options from the given parameters.
+	pub fn new_with_params(
+		pool_limit: usize,
+		pool_bytes: usize,
+		tx_ban_seconds: Option<u64>,
+		pool_type: TransactionPoolType,
+		is_dev: bool,
+	) -> Self {
+		let mut options = graph::Options::default();
+
+		// ready queue
+		options.ready.count = pool_limit;
+		options.ready.total_bytes = pool_bytes;
+
+		// future queue
+		let factor = 10;
+		options.future.count = pool_limit / factor;
+		options.future.total_bytes = pool_bytes / factor;
+
+		options.ban_time = if let Some(ban_seconds) = tx_ban_seconds {
+			std::time::Duration::from_secs(ban_seconds)
+		} else if is_dev {
+			std::time::Duration::from_secs(0)
+		} else {
+			std::time::Duration::from_secs(30 * 60)
+		};
+
+		Self { options, pool_type }
+	}
+}
+
+/// The transaction stored in the transaction pool for a full node.
+type FullPoolTransaction<Block> =
+	Transaction<<Block as BlockT>::Hash, <Block as BlockT>::Extrinsic>;
+
+/// The transaction pool for a full node, regardless of its type.
+pub trait FullClientTransactionPool<Block, Client>:
+	MaintainedTransactionPool<
+		Block = Block,
+		Hash = Block::Hash,
+		InPoolTransaction = FullPoolTransaction<Block>,
+		Error = error::Error,
+	> + LocalTransactionPool<Block = Block, Hash = Block::Hash, Error = error::Error>
+where
+	Block: BlockT,
+	Client: 'static,
+{
+}
+
+impl<Block, Client, P> FullClientTransactionPool<Block, Client> for P
+where
+	Block: BlockT,
+	Client: 'static,
+	P: MaintainedTransactionPool<
+			Block = Block,
+			Hash = Block::Hash,
+			InPoolTransaction = FullPoolTransaction<Block>,
+			Error = error::Error,
+		> + LocalTransactionPool<Block = Block, Hash = Block::Hash, Error = error::Error>,
+{
+}
+
+/// The handle to the transaction pool for a full node, built by the [`Builder`].
+///
+/// Depending on the configuration, the handle wraps either [`crate::BasicPool`] or
+/// [`crate::ForkAwareTxPool`].
+pub struct TransactionPoolHandle<Block, Client>
+where
+	Block: BlockT,
+	Client: 'static,
+{
+	pool: Arc<dyn FullClientTransactionPool<Block, Client>>,
+}
+
+impl<Block, Client> TransactionPoolHandle<Block, Client>
+where
+	Block: BlockT,
+	Client: 'static,
+{
+	/// Creates the handle wrapping the given transaction pool.
+	pub fn new(pool: Arc<dyn FullClientTransactionPool<Block, Client>>) -> Self {
+		Self { pool }
+	}
+}
+
+impl<Block, Client> TransactionPool for TransactionPoolHandle<Block, Client>
+where
+	Block: BlockT,
+	Client: 'static,
+{
+	type Block = Block;
+	type Hash = Block::Hash;
+	type InPoolTransaction = FullPoolTransaction<Block>;
+	type Error = error::Error;
+
+	fn submit_at(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xts: Vec<TransactionFor<Self>>,
+	) -> PoolFuture<Vec<Result<TxHash<Self>, Self::Error>>, Self::Error> {
+		self.pool.submit_at(at, source, xts)
+	}
+
+	fn submit_one(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> PoolFuture<TxHash<Self>, Self::Error> {
+		self.pool.submit_one(at, source, xt)
+	}
+
+	fn submit_and_watch(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> PoolFuture<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
+		self.pool.submit_and_watch(at, source, xt)
+	}
+
+	fn ready_at(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+	) -> Pin<
+		Box<
+			dyn Future<
+					Output = Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send>,
+				> + Send,
+		>,
+	> {
+		self.pool.ready_at(at)
+	}
+
+	fn ready(&self) -> Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send> {
+		self.pool.ready()
+	}
+
+	fn remove_invalid(&self, hashes: &[TxHash<Self>]) -> Vec<Arc<Self::InPoolTransaction>> {
+		self.pool.remove_invalid(hashes)
+	}
+
+	fn futures(&self) -> Vec<Self::InPoolTransaction> {
+		self.pool.futures()
+	}
+
+	fn status(&self) -> PoolStatus {
+		self.pool.status()
+	}
+
+	fn import_notification_stream(&self) -> ImportNotificationStream<TxHash<Self>> {
+		self.pool.import_notification_stream()
+	}
+
+	fn on_broadcasted(&self, propagations: HashMap<TxHash<Self>, Vec<String>>) {
+		self.pool.on_broadcasted(propagations)
+	}
+
+	fn hash_of(&self, xt: &TransactionFor<Self>) -> TxHash<Self> {
+		self.pool.hash_of(xt)
+	}
+
+	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>> {
+		self.pool.ready_transaction(hash)
+	}
+}
+
+#[async_trait]
+impl<Block, Client> MaintainedTransactionPool for TransactionPoolHandle<Block, Client>
+where
+	Block: BlockT,
+	Client: 'static,
+{
+	async fn maintain(&self, event: ChainEvent<Self::Block>) {
+		self.pool.maintain(event).await
+	}
+}
+
+impl<Block, Client> LocalTransactionPool for TransactionPoolHandle<Block, Client>
+where
+	Block: BlockT,
+	Client: 'static,
+{
+	type Block = Block;
+	type Hash = Block::Hash;
+	type Error = error::Error;
+
+	fn submit_local(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		xt: LocalTransactionFor<Self>,
+	) -> Result<Self::Hash, Self::Error> {
+		self.pool.submit_local(at, xt)
+	}
+}
+
+/// Builder of the transaction pool for a full node.
+///
+/// The type of the built pool is selected by [`TransactionPoolOptions::pool_type`].
+pub struct Builder<'a, Block, Client> {
+	options: TransactionPoolOptions,
+	is_validator: IsValidator,
+	prometheus: Option<&'a PrometheusRegistry>,
+	client: Arc<Client>,
+	spawner: Box<dyn SpawnEssentialNamed>,
+	_phantom: PhantomData<Block>,
+}
+
+impl<'a, Block, Client> Builder<'a, Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sc_client_api::ExecutorProvider<Block>
+		+ sc_client_api::UsageProvider<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	/// Creates the new builder with the default options.
+	pub fn new(
+		spawner: impl SpawnEssentialNamed + 'static,
+		client: Arc<Client>,
+		is_validator: IsValidator,
+	) -> Self {
+		Self {
+			options: Default::default(),
+			is_validator,
+			prometheus: None,
+			client,
+			spawner: Box::new(spawner),
+			_phantom: Default::default(),
+		}
+	}
+
+	/// Sets the options of the transaction pool.
+	pub fn with_options(mut self, options: TransactionPoolOptions) -> Self {
+		self.options = options;
+		self
+	}
+
+	/// Sets the prometheus registry used to report the metrics.
+	pub fn with_prometheus(mut self, prometheus: Option<&'a PrometheusRegistry>) -> Self {
+		self.prometheus = prometheus;
+		self
+	}
+
+	/// Builds the transaction pool of the configured type.
+	pub fn build(self) -> TransactionPoolHandle<Block, Client> {
+		let pool: Arc<dyn FullClientTransactionPool<Block, Client>> = match self.options.pool_type {
+			TransactionPoolType::SingleState => FullPool::<Block, Client>::new_full(
+				self.options.options,
+				self.is_validator,
+				self.prometheus,
+				self.spawner,
+				self.client,
+			),
+			TransactionPoolType::ForkAware => ForkAwareTxPoolFull::<Block, Client>::new_full(
+				self.options.options,
+				self.is_validator,
+				self.prometheus,
+				self.spawner,
+				self.client,
+			),
+		};
+		TransactionPoolHandle::new(pool)
+	}
+}
+
+impl<Block, Client> From<Arc<FullPool<Block, Client>>> for TransactionPoolHandle<Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	fn from(pool: Arc<FullPool<Block, Client>>) -> Self {
+		Self::new(pool)
+	}
+}
@@ -178,6 +178,7 @@ where
 	pub fn force_update(&mut self, event: &ChainEvent<Block>) {
 		match event {
 			ChainEvent::NewBestBlock { hash, .. } => self.recent_best_block = *hash,
+			ChainEvent::NewBlock { .. } => {},
 			ChainEvent::Finalized { hash, .. } => self.recent_finalized_block = *hash,
 		};
 		log::debug!(
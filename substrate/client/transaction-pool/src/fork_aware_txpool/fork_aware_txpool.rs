@@ -0,0 +1,866 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate fork-aware transaction pool implementation.
+
+use super::{
+	multi_view_listener::MultiViewListener,
+	tx_mem_pool::{TxInMemPool, TxMemPool},
+	view::View,
+	view_store::ViewStore,
+};
+use crate::{
+	api::FullChainApi,
+	error,
+	graph::{self, ExtrinsicHash, IsValidator, ValidatedTransaction},
+	metrics::MetricsLink as PrometheusMetrics,
+	PolledIterator, ReadyIteratorFor, LOG_TARGET,
+};
+use async_trait::async_trait;
+use futures::{
+	channel::{
+		mpsc::{channel, Sender},
+		oneshot,
+	},
+	future,
+	prelude::*,
+};
+use parking_lot::Mutex;
+use prometheus_endpoint::Registry as PrometheusRegistry;
+use sc_transaction_pool_api::{
+	error::{Error as TxPoolError, IntoPoolError},
+	ChainEvent, ImportNotificationStream, MaintainedTransactionPool, PoolFuture, PoolStatus,
+	TransactionFor, TransactionPool, TransactionSource, TransactionStatusStreamFor, TxHash,
+};
+use sp_blockchain::{HashAndNumber, TreeRoute};
+use sp_core::traits::SpawnEssentialNamed;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor},
+};
+use std::{
+	collections::{HashMap, HashSet},
+	pin::Pin,
+	sync::Arc,
+};
+
+/// The pollers waiting for the ready transactions at the given blocks.
+struct ReadyPoll<T, Block: BlockT> {
+	pollers: HashMap<Block::Hash, Vec<oneshot::Sender<T>>>,
+}
+
+impl<T, Block: BlockT> ReadyPoll<T, Block> {
+	fn new() -> Self {
+		Self { pollers: Default::default() }
+	}
+
+	/// Sends the ready transactions to the pollers waiting at the given block.
+	fn trigger(&mut self, at: Block::Hash, iterator_factory: impl Fn() -> T) {
+		let Some(pollers) = self.pollers.remove(&at) else { return };
+		log::debug!(target: LOG_TARGET, "Sending ready signal at block {:?}", at);
+		for poller in pollers {
+			let _ = poller.send(iterator_factory());
+		}
+	}
+
+	/// Adds the poller waiting for the ready transactions at the given block.
+	fn add(&mut self, at: Block::Hash) -> oneshot::Receiver<T> {
+		let (sender, receiver) = oneshot::channel();
+		self.pollers.entry(at).or_default().push(sender);
+		receiver
+	}
+
+	/// Returns the blocks at which pollers are waiting.
+	fn blocks(&self) -> Vec<Block::Hash> {
+		self.pollers.keys().copied().collect()
+	}
+
+	/// Drops the pollers at the given blocks.
+	fn remove(&mut self, blocks: &[Block::Hash]) {
+		for at in blocks {
+			self.pollers.remove(at);
+		}
+	}
+}
+
+/// Fork aware transaction pool for a full node.
+pub type ForkAwareTxPoolFull<Block, Client> = ForkAwareTxPool<FullChainApi<Client, Block>, Block>;
+
+/// Fork aware implementation of the transaction pool.
+///
+/// The pool maintains a separate [`View`] for every tip of the tree of the non-finalized blocks.
+/// Every view contains the transactions validated against the state of its block, so the
+/// transactions are not lost when the chain switches between the forks.
+pub struct ForkAwareTxPool<ChainApi, Block>
+where
+	Block: BlockT,
+	ChainApi: graph::ChainApi<Block = Block> + 'static,
+{
+	/// The reference to the chain api.
+	api: Arc<ChainApi>,
+	/// All the transactions submitted to the pool that were not yet finalized.
+	mempool: Arc<TxMemPool<ChainApi>>,
+	/// The views maintained for the tips of the forks.
+	view_store: Arc<ViewStore<ChainApi>>,
+	/// Merges the events of the watched transactions reported by all the views.
+	listener: Arc<MultiViewListener<ChainApi>>,
+	/// The pollers waiting for the ready transactions at the given block.
+	ready_poll: Arc<Mutex<ReadyPoll<ReadyIteratorFor<ChainApi>, Block>>>,
+	/// The prometheus metrics.
+	metrics: PrometheusMetrics,
+	/// The options of the pools of the views.
+	options: graph::Options,
+	/// Is the node a validator.
+	is_validator: Arc<IsValidator>,
+	/// The most recently notified best block.
+	best_block: Mutex<Block::Hash>,
+	/// The most recently notified finalized block.
+	finalized_block: Mutex<Block::Hash>,
+	/// The hashes of the transactions included in the non-finalized blocks.
+	included_transactions:
+		Mutex<HashMap<Block::Hash, (NumberFor<Block>, Vec<ExtrinsicHash<ChainApi>>)>>,
+	/// The sinks of the import notification streams.
+	import_notification_sinks: Arc<Mutex<Vec<Sender<ExtrinsicHash<ChainApi>>>>>,
+}
+
+impl<ChainApi, Block> ForkAwareTxPool<ChainApi, Block>
+where
+	Block: BlockT,
+	ChainApi: graph::ChainApi<Block = Block> + 'static,
+{
+	/// Create new fork aware transaction pool with provided api, for tests.
+	pub fn new_test(
+		pool_api: Arc<ChainApi>,
+		best_block_hash: Block::Hash,
+		finalized_hash: Block::Hash,
+	) -> Self {
+		Self::new(
+			Default::default(),
+			true.into(),
+			pool_api,
+			Default::default(),
+			best_block_hash,
+			finalized_hash,
+		)
+	}
+
+	/// Create new fork aware transaction pool with provided api and options.
+	fn new(
+		options: graph::Options,
+		is_validator: IsValidator,
+		pool_api: Arc<ChainApi>,
+		metrics: PrometheusMetrics,
+		best_block_hash: Block::Hash,
+		finalized_hash: Block::Hash,
+	) -> Self {
+		let best_block_number = pool_api
+			.block_id_to_number(&BlockId::Hash(best_block_hash))
+			.ok()
+			.flatten()
+			.unwrap_or_default();
+
+		let pool = Self {
+			mempool: Arc::new(TxMemPool::new(pool_api.clone(), &options)),
+			api: pool_api,
+			view_store: Arc::new(ViewStore::new()),
+			listener: Arc::new(MultiViewListener::new()),
+			ready_poll: Arc::new(Mutex::new(ReadyPoll::new())),
+			metrics,
+			options,
+			is_validator: Arc::new(is_validator),
+			best_block: Mutex::new(best_block_hash),
+			finalized_block: Mutex::new(finalized_hash),
+			included_transactions: Default::default(),
+			import_notification_sinks: Default::default(),
+		};
+
+		// The pool is empty, so the initial view does not need to be populated.
+		let view = View::new(
+			pool.api.clone(),
+			HashAndNumber { hash: best_block_hash, number: best_block_number },
+			pool.options.clone(),
+			pool.view_is_validator(),
+		);
+		pool.view_store.insert_view(Arc::new(view));
+		pool.view_store.set_most_recent(best_block_hash);
+
+		pool
+	}
+
+	/// Get access to the underlying api.
+	pub fn api(&self) -> &ChainApi {
+		&self.api
+	}
+
+	/// Returns the number of the views maintained by the pool.
+	pub fn views_count(&self) -> usize {
+		self.view_store.views().len()
+	}
+
+	/// Returns the number of the transactions in the memory pool.
+	pub fn mempool_len(&self) -> usize {
+		self.mempool.len()
+	}
+
+	/// Returns the status of the view at the given block, if it exists.
+	pub fn view_status_at(&self, at: Block::Hash) -> Option<PoolStatus> {
+		self.view_store.get_view_at(&at).map(|view| view.status())
+	}
+
+	fn view_is_validator(&self) -> IsValidator {
+		let is_validator = self.is_validator.clone();
+		IsValidator::from(
+			Box::new(move || is_validator.is_validator()) as Box<dyn Fn() -> bool + Send + Sync>
+		)
+	}
+
+	fn block_number(&self, hash: Block::Hash) -> Option<NumberFor<Block>> {
+		match self.api.block_id_to_number(&BlockId::Hash(hash)) {
+			Ok(Some(number)) => Some(number),
+			Ok(None) => {
+				log::debug!(target: LOG_TARGET, "Could not find the number of {:?}.", hash);
+				None
+			},
+			Err(e) => {
+				log::debug!(target: LOG_TARGET, "Error retrieving the number of {:?}: {}", hash, e);
+				None
+			},
+		}
+	}
+
+	fn tree_route(&self, from: Block::Hash, to: Block::Hash) -> Option<TreeRoute<Block>> {
+		self.api
+			.tree_route(from, to)
+			.map_err(|e| {
+				log::debug!(
+					target: LOG_TARGET,
+					"Error occurred while computing tree_route from {from:?} to {to:?}: {e}"
+				)
+			})
+			.ok()
+	}
+
+	/// Returns `true` if `ancestor` is an ancestor of (or equal to) `block`.
+	fn is_ancestor(&self, ancestor: Block::Hash, block: Block::Hash) -> bool {
+		ancestor == block ||
+			self.tree_route(ancestor, block)
+				.map_or(false, |tree_route| tree_route.retracted().is_empty())
+	}
+
+	/// Returns the hashes of the transactions included in the given block.
+	async fn block_transactions(&self, at: &HashAndNumber<Block>) -> Vec<ExtrinsicHash<ChainApi>> {
+		if let Some((_, hashes)) = self.included_transactions.lock().get(&at.hash) {
+			return hashes.clone()
+		}
+
+		let hashes = self
+			.api
+			.block_body(at.hash)
+			.await
+			.unwrap_or_else(|e| {
+				log::warn!(target: LOG_TARGET, "Error retrieving the body of {:?}: {}", at.hash, e);
+				None
+			})
+			.unwrap_or_default()
+			.iter()
+			.map(|tx| self.api.hash_and_length(tx).0)
+			.collect::<Vec<_>>();
+
+		self.included_transactions.lock().insert(at.hash, (at.number, hashes.clone()));
+		hashes
+	}
+
+	/// Returns the hashes of the transactions included in the non-finalized blocks of the chain
+	/// ending at the given block.
+	async fn transactions_included_on_chain(
+		&self,
+		at: Block::Hash,
+	) -> HashSet<ExtrinsicHash<ChainApi>> {
+		let finalized = *self.finalized_block.lock();
+		let Some(tree_route) = self.tree_route(finalized, at) else { return Default::default() };
+
+		let mut included = HashSet::new();
+		for block in tree_route.enacted() {
+			included.extend(self.block_transactions(block).await);
+		}
+		included
+	}
+
+	/// Reports the inclusion of the transactions for the blocks enacted and retracted when the
+	/// best block changes.
+	async fn report_inclusion(&self, from: Block::Hash, to: Block::Hash) {
+		let Some(tree_route) = self.tree_route(from, to) else { return };
+
+		for block in tree_route.retracted().iter().rev() {
+			for tx_hash in self.block_transactions(block).await {
+				self.listener.retracted(tx_hash, block.hash);
+			}
+		}
+
+		for block in tree_route.enacted() {
+			for (index, tx_hash) in self.block_transactions(block).await.into_iter().enumerate() {
+				self.listener.in_block(tx_hash, block.hash, index);
+			}
+		}
+	}
+
+	/// Creates the view at the given block and populates it with the transactions from the
+	/// memory pool which are not included on its chain.
+	///
+	/// Returns the hashes of the transactions that are invalid in the new view.
+	async fn create_view(&self, at: HashAndNumber<Block>) -> Vec<ExtrinsicHash<ChainApi>> {
+		let view = Arc::new(View::new(
+			self.api.clone(),
+			at.clone(),
+			self.options.clone(),
+			self.view_is_validator(),
+		));
+
+		let included = self.transactions_included_on_chain(at.hash).await;
+		let xts = self
+			.mempool
+			.clone_transactions()
+			.into_iter()
+			.filter(|(hash, _)| !included.contains(hash))
+			.collect::<Vec<_>>();
+		let mut invalid = self.submit_to_view(&view, &xts).await;
+
+		self.view_store.insert_view(view.clone());
+
+		// The transactions submitted while the view was populated were only submitted to the
+		// views registered at that time.
+		let submitted = xts.iter().map(|(hash, _)| *hash).collect::<HashSet<_>>();
+		let missed = self
+			.mempool
+			.clone_transactions()
+			.into_iter()
+			.filter(|(hash, _)| !included.contains(hash) && !submitted.contains(hash))
+			.collect::<Vec<_>>();
+		invalid.extend(self.submit_to_view(&view, &missed).await);
+
+		log::debug!(
+			target: LOG_TARGET,
+			"[{:?}] created view: submitted {}, included on chain {}",
+			at.hash,
+			xts.len() + missed.len(),
+			included.len(),
+		);
+
+		invalid
+	}
+
+	/// Submits the transactions to the view, watching the watched ones.
+	///
+	/// Returns the hashes of the transactions that are invalid in the view.
+	async fn submit_to_view(
+		&self,
+		view: &View<ChainApi>,
+		xts: &[(ExtrinsicHash<ChainApi>, TxInMemPool<ChainApi>)],
+	) -> Vec<ExtrinsicHash<ChainApi>> {
+		for (tx_hash, tx) in xts {
+			if tx.watched {
+				let stream = view.create_watcher(*tx_hash).into_stream();
+				self.listener.add_view_watcher_for_tx(*tx_hash, view.at.hash, stream);
+			}
+		}
+
+		let results = view.submit_many(xts.iter().map(|(_, tx)| (tx.source, tx.tx.clone()))).await;
+
+		xts.iter()
+			.zip(results)
+			.filter_map(|((tx_hash, _), result)| {
+				match result.map_err(IntoPoolError::into_pool_error) {
+					Err(Ok(TxPoolError::InvalidTransaction(_))) => Some(*tx_hash),
+					_ => None,
+				}
+			})
+			.collect()
+	}
+
+	/// Invalidates the transactions that are not valid in any view.
+	///
+	/// The transactions included in any of the non-finalized blocks are kept, as they may still
+	/// become finalized.
+	fn invalidate_transactions(&self, candidates: Vec<ExtrinsicHash<ChainApi>>) {
+		let invalid = {
+			let included_transactions = self.included_transactions.lock();
+			candidates
+				.into_iter()
+				.filter(|tx_hash| {
+					!self.view_store.is_imported(tx_hash) &&
+						!included_transactions
+							.values()
+							.any(|(_, hashes)| hashes.contains(tx_hash))
+				})
+				.collect::<Vec<_>>()
+		};
+
+		if invalid.is_empty() {
+			return
+		}
+
+		log::debug!(target: LOG_TARGET, "invalidated transactions: {:?}", invalid);
+		self.metrics
+			.report(|metrics| metrics.validations_invalid.inc_by(invalid.len() as u64));
+		self.mempool.remove_transactions(&invalid);
+		self.listener.invalidate_transactions(&invalid);
+	}
+
+	/// Creates the view at the newly imported block, which becomes a new tip of its fork.
+	async fn handle_new_block(&self, at: HashAndNumber<Block>) {
+		if self.view_store.contains(&at.hash) {
+			return
+		}
+
+		let invalid = self.create_view(at.clone()).await;
+
+		// The views at the ancestors of the new block are no longer needed, except the view at the
+		// best block which is still used by the block authoring and the RPC.
+		let best_block = *self.best_block.lock();
+		self.view_store.retain(|view| {
+			view.at.hash == at.hash ||
+				view.at.hash == best_block ||
+				!self.is_ancestor(view.at.hash, at.hash)
+		});
+
+		self.invalidate_transactions(invalid);
+
+		if let Some(view) = self.view_store.get_view_at(&at.hash) {
+			self.ready_poll
+				.lock()
+				.trigger(at.hash, || Box::new(view.pool.validated_pool().ready()));
+		}
+	}
+
+	async fn handle_new_best_block(&self, at: HashAndNumber<Block>) {
+		let previous_best = std::mem::replace(&mut *self.best_block.lock(), at.hash);
+		self.report_inclusion(previous_best, at.hash).await;
+
+		self.handle_new_block(at.clone()).await;
+		self.view_store.set_most_recent(at.hash);
+
+		// The view at an ancestor is kept only while it is at the best block.
+		if previous_best != at.hash && self.is_ancestor(previous_best, at.hash) {
+			self.view_store.retain(|view| view.at.hash != previous_best);
+		}
+	}
+
+	async fn handle_finalized(&self, finalized: Block::Hash, tree_route: &[Block::Hash]) {
+		let Some(finalized_number) = self.block_number(finalized) else { return };
+		*self.finalized_block.lock() = finalized;
+
+		let mut finalized_transactions = Vec::new();
+		for hash in tree_route.iter().chain(std::iter::once(&finalized)) {
+			let Some(number) = self.block_number(*hash) else { continue };
+			let block = HashAndNumber { hash: *hash, number };
+			for (index, tx_hash) in self.block_transactions(&block).await.into_iter().enumerate() {
+				self.listener.finalized(tx_hash, *hash, index);
+				finalized_transactions.push(tx_hash);
+			}
+		}
+
+		log::trace!(
+			target: LOG_TARGET,
+			"[{:?}] finalized transactions: {:?}",
+			finalized,
+			finalized_transactions
+		);
+		self.mempool.remove_transactions(&finalized_transactions);
+
+		// The views that are not descendants of the finalized block will never be used.
+		self.view_store.retain(|view| self.is_ancestor(finalized, view.at.hash));
+		if self.view_store.is_empty() {
+			let invalid = self
+				.create_view(HashAndNumber { hash: finalized, number: finalized_number })
+				.await;
+			self.view_store.set_most_recent(finalized);
+			self.invalidate_transactions(invalid);
+		}
+		// The ancestry is checked without holding the lock, as it reads the database.
+		let polled = self.ready_poll.lock().blocks();
+		let stale = polled
+			.into_iter()
+			.filter(|at| !self.is_ancestor(finalized, *at))
+			.collect::<Vec<_>>();
+		self.ready_poll.lock().remove(&stale);
+
+		self.included_transactions
+			.lock()
+			.retain(|_, (number, _)| *number > finalized_number);
+	}
+}
+
+/// Notifies the sinks about the newly imported transaction.
+fn notify_import<Hash: std::fmt::Debug + Copy>(sinks: &Mutex<Vec<Sender<Hash>>>, hash: Hash) {
+	sinks.lock().retain_mut(|sink| match sink.try_send(hash) {
+		Ok(()) => true,
+		Err(e) =>
+			if e.is_full() {
+				log::warn!(
+					target: LOG_TARGET,
+					"[{:?}] Trying to notify an import but the channel is full",
+					hash,
+				);
+				true
+			} else {
+				false
+			},
+	});
+}
+
+impl<ChainApi, Block> TransactionPool for ForkAwareTxPool<ChainApi, Block>
+where
+	Block: BlockT,
+	ChainApi: 'static + graph::ChainApi<Block = Block>,
+{
+	type Block = ChainApi::Block;
+	type Hash = ExtrinsicHash<ChainApi>;
+	type InPoolTransaction = graph::base_pool::Transaction<TxHash<Self>, TransactionFor<Self>>;
+	type Error = ChainApi::Error;
+
+	fn submit_at(
+		&self,
+		_: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xts: Vec<TransactionFor<Self>>,
+	) -> PoolFuture<Vec<Result<TxHash<Self>, Self::Error>>, Self::Error> {
+		self.metrics
+			.report(|metrics| metrics.submitted_transactions.inc_by(xts.len() as u64));
+
+		let mempool = self.mempool.clone();
+		let view_store = self.view_store.clone();
+		let import_notification_sinks = self.import_notification_sinks.clone();
+
+		let mempool_results = mempool.push_unwatched(source, &xts);
+		let to_be_submitted = mempool_results
+			.iter()
+			.zip(xts)
+			.filter_map(|(result, xt)| result.as_ref().ok().map(|hash| (*hash, xt)))
+			.collect::<Vec<_>>();
+
+		async move {
+			let mut submission_results =
+				view_store.submit_at(source, to_be_submitted).await.into_iter();
+
+			let results = mempool_results
+				.into_iter()
+				.map(|result| {
+					result.and_then(|tx_hash| {
+						let result = submission_results
+							.next()
+							.expect("Every transaction accepted into mempool was submitted; qed");
+						// The transaction is rejected by all the views.
+						if result.is_err() {
+							mempool.remove_transactions(&[tx_hash]);
+						}
+						result
+					})
+				})
+				.collect::<Vec<_>>();
+
+			for tx_hash in results.iter().filter_map(|result| result.as_ref().ok()) {
+				notify_import(&import_notification_sinks, *tx_hash);
+			}
+
+			Ok(results)
+		}
+		.boxed()
+	}
+
+	fn submit_one(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> PoolFuture<TxHash<Self>, Self::Error> {
+		let result = self.submit_at(at, source, vec![xt]);
+
+		async move { result.await?.pop().expect("One extrinsic passed; one result returned; qed") }
+			.boxed()
+	}
+
+	fn submit_and_watch(
+		&self,
+		_: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> PoolFuture<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
+		self.metrics.report(|metrics| metrics.submitted_transactions.inc());
+
+		let tx_hash = match self.mempool.push_watched(source, xt.clone()) {
+			Ok(tx_hash) => tx_hash,
+			Err(e) => return future::ready(Err(e)).boxed(),
+		};
+
+		let Some(external_watcher) = self.listener.create_external_watcher_for_tx(tx_hash) else {
+			self.mempool.remove_transactions(&[tx_hash]);
+			return future::ready(Err(TxPoolError::AlreadyImported(Box::new(tx_hash)).into()))
+				.boxed()
+		};
+
+		for view in self.view_store.views() {
+			let stream = view.create_watcher(tx_hash).into_stream();
+			self.listener.add_view_watcher_for_tx(tx_hash, view.at.hash, stream);
+		}
+
+		let mempool = self.mempool.clone();
+		let view_store = self.view_store.clone();
+		let listener = self.listener.clone();
+		let import_notification_sinks = self.import_notification_sinks.clone();
+
+		async move {
+			let result = view_store
+				.submit_at(source, vec![(tx_hash, xt)])
+				.await
+				.pop()
+				.expect("One extrinsic passed; one result returned; qed");
+
+			match result {
+				Ok(_) => {
+					notify_import(&import_notification_sinks, tx_hash);
+					Ok(external_watcher)
+				},
+				Err(e) => {
+					mempool.remove_transactions(&[tx_hash]);
+					listener.remove_watcher(&tx_hash);
+					Err(e)
+				},
+			}
+		}
+		.boxed()
+	}
+
+	fn remove_invalid(&self, hashes: &[TxHash<Self>]) -> Vec<Arc<Self::InPoolTransaction>> {
+		let removed = self.view_store.remove_invalid(hashes);
+		self.mempool.remove_transactions(hashes);
+		self.listener.invalidate_transactions(hashes);
+		self.metrics
+			.report(|metrics| metrics.validations_invalid.inc_by(hashes.len() as u64));
+
+		removed
+	}
+
+	fn status(&self) -> PoolStatus {
+		self.view_store
+			.most_recent_view()
+			.map(|view| view.status())
+			.unwrap_or(PoolStatus { ready: 0, ready_bytes: 0, future: 0, future_bytes: 0 })
+	}
+
+	fn import_notification_stream(&self) -> ImportNotificationStream<TxHash<Self>> {
+		const CHANNEL_BUFFER_SIZE: usize = 1024;
+
+		let (sink, stream) = channel(CHANNEL_BUFFER_SIZE);
+		self.import_notification_sinks.lock().push(sink);
+		stream
+	}
+
+	fn hash_of(&self, xt: &TransactionFor<Self>) -> TxHash<Self> {
+		self.api.hash_and_length(xt).0
+	}
+
+	fn on_broadcasted(&self, propagations: HashMap<TxHash<Self>, Vec<String>>) {
+		for (tx_hash, peers) in propagations {
+			self.listener.broadcasted(tx_hash, peers);
+		}
+	}
+
+	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>> {
+		self.view_store
+			.most_recent_view()
+			.and_then(|view| view.pool.validated_pool().ready_by_hash(hash))
+	}
+
+	fn ready_at(&self, at: <Self::Block as BlockT>::Hash) -> PolledIterator<ChainApi> {
+		// If there are no transactions in the pool, it is fine to return early.
+		if self.mempool.len() == 0 {
+			return async { Box::new(std::iter::empty()) as Box<_> }.boxed()
+		}
+
+		// The lock is held while checking the view, so the view cannot be created between the
+		// check and adding the poller.
+		let mut ready_poll = self.ready_poll.lock();
+		if let Some(view) = self.view_store.get_view_at(&at) {
+			log::trace!(target: LOG_TARGET, "Transaction pool already processed block {:?}", at);
+			let iterator: ReadyIteratorFor<ChainApi> = Box::new(view.pool.validated_pool().ready());
+			return async move { iterator }.boxed()
+		}
+
+		ready_poll
+			.add(at)
+			.map(|received| {
+				received.unwrap_or_else(|e| {
+					log::warn!("Error receiving pending set: {:?}", e);
+					Box::new(std::iter::empty())
+				})
+			})
+			.boxed()
+	}
+
+	fn ready(&self) -> ReadyIteratorFor<ChainApi> {
+		match self.view_store.most_recent_view() {
+			Some(view) => Box::new(view.pool.validated_pool().ready()),
+			None => Box::new(std::iter::empty()),
+		}
+	}
+
+	fn futures(&self) -> Vec<Self::InPoolTransaction> {
+		self.view_store
+			.most_recent_view()
+			.map(|view| view.pool.validated_pool().pool.read().futures().cloned().collect())
+			.unwrap_or_default()
+	}
+}
+
+#[async_trait]
+impl<ChainApi, Block> MaintainedTransactionPool for ForkAwareTxPool<ChainApi, Block>
+where
+	Block: BlockT,
+	ChainApi: 'static + graph::ChainApi<Block = Block>,
+{
+	async fn maintain(&self, event: ChainEvent<Self::Block>) {
+		log::trace!(target: LOG_TARGET, "maintain: {:?}", event);
+		match event {
+			ChainEvent::NewBestBlock { hash, .. } => {
+				let Some(number) = self.block_number(hash) else { return };
+				self.handle_new_best_block(HashAndNumber { hash, number }).await;
+			},
+			ChainEvent::NewBlock { hash } => {
+				let Some(number) = self.block_number(hash) else { return };
+				self.handle_new_block(HashAndNumber { hash, number }).await;
+			},
+			ChainEvent::Finalized { hash, tree_route } => {
+				self.handle_finalized(hash, &tree_route).await;
+			},
+		}
+	}
+}
+
+impl<Block, Client> ForkAwareTxPoolFull<Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sc_client_api::ExecutorProvider<Block>
+		+ sc_client_api::UsageProvider<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	/// Create new fork aware transaction pool for a full node with the provided api.
+	pub fn new_full(
+		options: graph::Options,
+		is_validator: IsValidator,
+		prometheus: Option<&PrometheusRegistry>,
+		spawner: impl SpawnEssentialNamed,
+		client: Arc<Client>,
+	) -> Arc<Self> {
+		let pool_api = Arc::new(FullChainApi::new(client.clone(), prometheus, &spawner));
+		Arc::new(Self::new(
+			options,
+			is_validator,
+			pool_api,
+			PrometheusMetrics::new(prometheus),
+			client.usage_info().chain.best_hash,
+			client.usage_info().chain.finalized_hash,
+		))
+	}
+}
+
+impl<Block, Client> sc_transaction_pool_api::LocalTransactionPool
+	for ForkAwareTxPoolFull<Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>,
+	Client: Send + Sync + 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	type Block = Block;
+	type Hash = ExtrinsicHash<FullChainApi<Client, Block>>;
+	type Error = <FullChainApi<Client, Block> as graph::ChainApi>::Error;
+
+	fn submit_local(
+		&self,
+		_: Block::Hash,
+		xt: sc_transaction_pool_api::LocalTransactionFor<Self>,
+	) -> Result<Self::Hash, Self::Error> {
+		use graph::ChainApi as _;
+		use sp_runtime::{
+			traits::SaturatedConversion, transaction_validity::TransactionValidityError,
+		};
+
+		let (hash, bytes) = self.api.hash_and_length(&xt);
+		self.mempool.push_unwatched(TransactionSource::Local, &[xt.clone()]).remove(0)?;
+
+		// The transaction is valid if it is valid in any view, otherwise the error reported by
+		// the most recent view is returned.
+		let most_recent_view = self.view_store.most_recent_view().map(|view| view.at.hash);
+		let mut result = None;
+		for view in self.view_store.views() {
+			let validity = self
+				.api
+				.validate_transaction_blocking(view.at.hash, TransactionSource::Local, xt.clone())
+				.and_then(|validity| {
+					validity.map_err(|e| {
+						error::Error::Pool(match e {
+							TransactionValidityError::Invalid(i) =>
+								TxPoolError::InvalidTransaction(i),
+							TransactionValidityError::Unknown(u) =>
+								TxPoolError::UnknownTransaction(u),
+						})
+					})
+				});
+
+			let view_result = validity.and_then(|validity| {
+				let validated = ValidatedTransaction::valid_at(
+					view.at.number.saturated_into::<u64>(),
+					hash,
+					TransactionSource::Local,
+					xt.clone(),
+					bytes,
+					validity,
+				);
+				view.pool.validated_pool().submit(vec![validated]).remove(0)
+			});
+
+			let replace = match (&result, &view_result) {
+				(Some(Ok(_)), _) => false,
+				(_, Ok(_)) | (None, Err(_)) => true,
+				(Some(Err(_)), Err(_)) => Some(view.at.hash) == most_recent_view,
+			};
+			if replace {
+				result = Some(view_result);
+			}
+		}
+
+		let result = result.unwrap_or(Ok(hash));
+		match result {
+			Ok(hash) => notify_import(&self.import_notification_sinks, hash),
+			Err(_) => self.mempool.remove_transactions(&[hash]),
+		}
+		result
+	}
+}
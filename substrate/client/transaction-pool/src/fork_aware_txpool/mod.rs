@@ -0,0 +1,42 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate fork-aware transaction pool.
+//!
+//! The single-state pool keeps the transactions validated against the best block only. When the
+//! node switches to another fork, the transactions are revalidated and may be dropped. The
+//! fork-aware pool instead maintains a view for every tip of the tree of the non-finalized blocks:
+//!
+//! - the memory pool ([`tx_mem_pool::TxMemPool`]) keeps all the submitted transactions until they
+//!   are finalized or found invalid in every view,
+//! - every view ([`view::View`]) keeps the transactions validated at its block,
+//! - the view store ([`view_store::ViewStore`]) keeps the views of the forks,
+//! - the listener ([`multi_view_listener::MultiViewListener`]) merges the events reported by all
+//!   the views into a single stream per watched transaction.
+//!
+//! A new view is created for every new best block and populated with the transactions from the
+//! memory pool that are not included on its chain. The views at the ancestors of the new best
+//! block are removed, as well as the views not descending from the finalized block.
+
+mod fork_aware_txpool;
+mod multi_view_listener;
+mod tx_mem_pool;
+mod view;
+mod view_store;
+
+pub use fork_aware_txpool::{ForkAwareTxPool, ForkAwareTxPoolFull};
@@ -0,0 +1,307 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Multi view listener.
+//!
+//! Every view of the fork-aware pool reports the events of the transactions it contains through
+//! its own watcher. The [`MultiViewListener`] merges the streams of all the views into a single
+//! external stream per watched transaction and extends it with the events that are only known
+//! at the pool level (inclusion in blocks, finalization, invalidation).
+
+use crate::{
+	graph::{BlockHash, ExtrinsicHash},
+	LOG_TARGET,
+};
+use futures::{stream::SelectAll, Stream, StreamExt};
+use log::trace;
+use parking_lot::RwLock;
+use sc_transaction_pool_api::{TransactionStatus, TransactionStatusStream, TxIndex};
+use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
+use std::{
+	collections::{HashMap, HashSet},
+	pin::Pin,
+};
+
+use super::view::ViewStream;
+
+/// The stream of the transaction events reported to the external watcher.
+pub(super) type TxStatusStream<ChainApi> =
+	Pin<Box<TransactionStatusStream<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>>>;
+
+/// The commands sent to the task driving the external watcher of a transaction.
+enum ControllerCommand<ChainApi: crate::graph::ChainApi> {
+	/// A new view has been created for the transaction.
+	AddViewStream(BlockHash<ChainApi>, ViewStream<ChainApi>),
+	/// The transaction was included in the block.
+	InBlock(BlockHash<ChainApi>, TxIndex),
+	/// The block containing the transaction was retracted.
+	Retracted(BlockHash<ChainApi>),
+	/// The block containing the transaction was finalized.
+	Finalized(BlockHash<ChainApi>, TxIndex),
+	/// The transaction was broadcast to the given peers.
+	Broadcasted(Vec<String>),
+	/// The transaction is invalid in every view.
+	Invalid,
+}
+
+/// The state of the stream of a single watched transaction.
+struct ExternalWatcherContext<ChainApi: crate::graph::ChainApi> {
+	/// The hash of the watched transaction.
+	tx_hash: ExtrinsicHash<ChainApi>,
+	/// The merged streams of the views.
+	view_streams: SelectAll<ViewStream<ChainApi>>,
+	/// The commands sent by the pool.
+	command_receiver: TracingUnboundedReceiver<ControllerCommand<ChainApi>>,
+	/// The views in which the transaction is ready.
+	ready_views: HashSet<BlockHash<ChainApi>>,
+	/// The views in which the transaction is in the future queue.
+	future_views: HashSet<BlockHash<ChainApi>>,
+	/// The last status reported by the views, used to avoid duplicated events.
+	last_view_status: Option<TransactionStatus<ExtrinsicHash<ChainApi>, ExtrinsicHash<ChainApi>>>,
+	/// The stream was terminated with a final event.
+	terminated: bool,
+}
+
+impl<ChainApi: crate::graph::ChainApi> ExternalWatcherContext<ChainApi> {
+	/// Handles the event reported by the given view.
+	///
+	/// Returns the status that shall be sent to the external watcher, if any.
+	fn handle_view_event(
+		&mut self,
+		view_hash: BlockHash<ChainApi>,
+		status: Option<TransactionStatus<ExtrinsicHash<ChainApi>, ExtrinsicHash<ChainApi>>>,
+	) -> Option<TransactionStatus<ExtrinsicHash<ChainApi>, ExtrinsicHash<ChainApi>>> {
+		trace!(
+			target: LOG_TARGET,
+			"[{:?}] view {:?} reported: {:?}",
+			self.tx_hash,
+			view_hash,
+			status
+		);
+
+		let status = match status {
+			Some(TransactionStatus::Ready) => {
+				self.future_views.remove(&view_hash);
+				self.ready_views.insert(view_hash);
+				TransactionStatus::Ready
+			},
+			Some(TransactionStatus::Future) => {
+				self.future_views.insert(view_hash);
+				self.ready_views.remove(&view_hash);
+				if !self.ready_views.is_empty() {
+					return None
+				}
+				TransactionStatus::Future
+			},
+			// The remaining events of the view are handled at the pool level: the transaction
+			// is not removed from the pool until it is dropped from all the views.
+			_ => {
+				self.ready_views.remove(&view_hash);
+				self.future_views.remove(&view_hash);
+				return None
+			},
+		};
+
+		if self.last_view_status.as_ref() == Some(&status) {
+			return None
+		}
+		self.last_view_status = Some(status.clone());
+		Some(status)
+	}
+
+	/// Handles the command sent by the pool.
+	///
+	/// Returns the status that shall be sent to the external watcher, if any.
+	fn handle_command(
+		&mut self,
+		command: ControllerCommand<ChainApi>,
+	) -> Option<TransactionStatus<ExtrinsicHash<ChainApi>, ExtrinsicHash<ChainApi>>> {
+		let status = match command {
+			ControllerCommand::AddViewStream(view_hash, stream) => {
+				trace!(target: LOG_TARGET, "[{:?}] added view {:?}", self.tx_hash, view_hash);
+				self.view_streams.push(stream);
+				return None
+			},
+			ControllerCommand::InBlock(block, index) => TransactionStatus::InBlock((block, index)),
+			ControllerCommand::Retracted(block) => TransactionStatus::Retracted(block),
+			ControllerCommand::Finalized(block, index) =>
+				TransactionStatus::Finalized((block, index)),
+			ControllerCommand::Broadcasted(peers) => TransactionStatus::Broadcast(peers),
+			ControllerCommand::Invalid => TransactionStatus::Invalid,
+		};
+
+		// The views may report the transaction as ready again once it was retracted.
+		if !matches!(status, TransactionStatus::Broadcast(_)) {
+			self.last_view_status = None;
+		}
+		self.terminated = status.is_final();
+		Some(status)
+	}
+}
+
+/// Merges the events of the transactions reported by all the views.
+pub(super) struct MultiViewListener<ChainApi: crate::graph::ChainApi> {
+	/// The senders of the commands for each watched transaction.
+	controllers: RwLock<
+		HashMap<ExtrinsicHash<ChainApi>, TracingUnboundedSender<ControllerCommand<ChainApi>>>,
+	>,
+}
+
+impl<ChainApi> MultiViewListener<ChainApi>
+where
+	ChainApi: crate::graph::ChainApi + 'static,
+{
+	/// Creates a new [`MultiViewListener`].
+	pub(super) fn new() -> Self {
+		Self { controllers: Default::default() }
+	}
+
+	/// Creates the external watcher of the given transaction.
+	///
+	/// Returns `None` if the transaction is already watched.
+	pub(super) fn create_external_watcher_for_tx(
+		&self,
+		tx_hash: ExtrinsicHash<ChainApi>,
+	) -> Option<TxStatusStream<ChainApi>> {
+		let mut controllers = self.controllers.write();
+		if controllers.contains_key(&tx_hash) {
+			return None
+		}
+
+		trace!(target: LOG_TARGET, "[{:?}] create_external_watcher_for_tx", tx_hash);
+
+		let (command_sender, command_receiver) =
+			tracing_unbounded("mpsc_txpool_multi_view_listener", 100_000);
+		controllers.insert(tx_hash, command_sender);
+
+		let context = ExternalWatcherContext {
+			tx_hash,
+			view_streams: SelectAll::new(),
+			command_receiver,
+			ready_views: Default::default(),
+			future_views: Default::default(),
+			last_view_status: None,
+			terminated: false,
+		};
+
+		let stream = futures::stream::unfold(context, |mut ctx| async move {
+			if ctx.terminated {
+				return None
+			}
+
+			loop {
+				// The events of the views are processed first, so the initial status of the
+				// transaction is reported before the events generated by the block import.
+				futures::select_biased! {
+					(view_hash, status) = ctx.view_streams.select_next_some() => {
+						if let Some(status) = ctx.handle_view_event(view_hash, status) {
+							return Some((status, ctx))
+						}
+					},
+					command = ctx.command_receiver.next() => {
+						// The pool dropped the controller, no more events will be reported.
+						let command = command?;
+						if let Some(status) = ctx.handle_command(command) {
+							return Some((status, ctx))
+						}
+					},
+				}
+			}
+		});
+
+		Some(stream.boxed())
+	}
+
+	/// Adds the stream of the events reported by a view to the external watcher.
+	pub(super) fn add_view_watcher_for_tx(
+		&self,
+		tx_hash: ExtrinsicHash<ChainApi>,
+		view_hash: BlockHash<ChainApi>,
+		stream: impl Stream<Item = TransactionStatus<ExtrinsicHash<ChainApi>, ExtrinsicHash<ChainApi>>>
+			+ Send
+			+ 'static,
+	) {
+		// Every view stream is terminated with `None`, so the context can stop tracking the
+		// view once it is removed from the pool.
+		let stream = stream
+			.map(Some)
+			.chain(futures::stream::once(async { None }))
+			.map(move |status| (view_hash, status))
+			.boxed();
+		self.send(tx_hash, ControllerCommand::AddViewStream(view_hash, stream));
+	}
+
+	/// The transaction was included in the given block.
+	pub(super) fn in_block(
+		&self,
+		tx_hash: ExtrinsicHash<ChainApi>,
+		block: BlockHash<ChainApi>,
+		index: TxIndex,
+	) {
+		self.send(tx_hash, ControllerCommand::InBlock(block, index));
+	}
+
+	/// The block including the transaction was retracted.
+	pub(super) fn retracted(&self, tx_hash: ExtrinsicHash<ChainApi>, block: BlockHash<ChainApi>) {
+		self.send(tx_hash, ControllerCommand::Retracted(block));
+	}
+
+	/// The block including the transaction was finalized.
+	///
+	/// This is the last event reported for the transaction.
+	pub(super) fn finalized(
+		&self,
+		tx_hash: ExtrinsicHash<ChainApi>,
+		block: BlockHash<ChainApi>,
+		index: TxIndex,
+	) {
+		self.send(tx_hash, ControllerCommand::Finalized(block, index));
+		self.controllers.write().remove(&tx_hash);
+	}
+
+	/// The transaction was broadcast to the given peers.
+	pub(super) fn broadcasted(&self, tx_hash: ExtrinsicHash<ChainApi>, peers: Vec<String>) {
+		self.send(tx_hash, ControllerCommand::Broadcasted(peers));
+	}
+
+	/// The transactions are invalid.
+	///
+	/// This is the last event reported for the transactions.
+	pub(super) fn invalidate_transactions(&self, tx_hashes: &[ExtrinsicHash<ChainApi>]) {
+		for tx_hash in tx_hashes {
+			self.send(*tx_hash, ControllerCommand::Invalid);
+			self.controllers.write().remove(tx_hash);
+		}
+	}
+
+	/// Stops watching the transaction without reporting any final event.
+	pub(super) fn remove_watcher(&self, tx_hash: &ExtrinsicHash<ChainApi>) {
+		self.controllers.write().remove(tx_hash);
+	}
+
+	fn send(&self, tx_hash: ExtrinsicHash<ChainApi>, command: ControllerCommand<ChainApi>) {
+		let mut controllers = self.controllers.write();
+		if let Some(sender) = controllers.get(&tx_hash) {
+			if sender.unbounded_send(command).is_err() {
+				// The external watcher was dropped.
+				trace!(target: LOG_TARGET, "[{:?}] external watcher dropped", tx_hash);
+				controllers.remove(&tx_hash);
+			}
+		}
+	}
+}
@@ -0,0 +1,150 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Transaction memory pool.
+//!
+//! The memory pool keeps all the transactions submitted to the fork-aware pool, regardless of
+//! their validity in any particular view. The transactions are used to populate the views
+//! created for the newly imported blocks.
+
+use crate::{
+	graph::{self, ExtrinsicFor, ExtrinsicHash},
+	LOG_TARGET,
+};
+use parking_lot::RwLock;
+use sc_transaction_pool_api::{error::Error as TxPoolError, TransactionSource};
+use std::{
+	collections::HashMap,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	},
+};
+
+/// The transaction kept in the memory pool.
+pub(super) struct TxInMemPool<ChainApi: graph::ChainApi> {
+	/// Is the transaction watched by the external watcher.
+	pub(super) watched: bool,
+	/// The transaction.
+	pub(super) tx: ExtrinsicFor<ChainApi>,
+	/// The source of the transaction.
+	pub(super) source: TransactionSource,
+	/// The length of the encoded transaction.
+	pub(super) bytes: usize,
+}
+
+impl<ChainApi: graph::ChainApi> Clone for TxInMemPool<ChainApi> {
+	fn clone(&self) -> Self {
+		Self { watched: self.watched, tx: self.tx.clone(), source: self.source, bytes: self.bytes }
+	}
+}
+
+/// The pool of the transactions that were submitted but not yet finalized.
+pub(super) struct TxMemPool<ChainApi: graph::ChainApi> {
+	api: Arc<ChainApi>,
+	/// The transactions, indexed by their hash.
+	transactions: RwLock<HashMap<ExtrinsicHash<ChainApi>, TxInMemPool<ChainApi>>>,
+	/// The total size of the transactions, updated while holding the lock of `transactions`.
+	total_bytes: AtomicUsize,
+	/// The maximal number of the transactions.
+	max_count: usize,
+	/// The maximal total size of the transactions.
+	max_bytes: usize,
+}
+
+impl<ChainApi> TxMemPool<ChainApi>
+where
+	ChainApi: graph::ChainApi + 'static,
+{
+	/// Creates a new memory pool with the limits taken from the pool options.
+	pub(super) fn new(api: Arc<ChainApi>, options: &graph::Options) -> Self {
+		Self {
+			api,
+			transactions: Default::default(),
+			total_bytes: Default::default(),
+			max_count: options.ready.count + options.future.count,
+			max_bytes: options.ready.total_bytes + options.future.total_bytes,
+		}
+	}
+
+	/// Returns the number of the transactions in the memory pool.
+	pub(super) fn len(&self) -> usize {
+		self.transactions.read().len()
+	}
+
+	/// Returns a copy of all the transactions in the memory pool.
+	pub(super) fn clone_transactions(
+		&self,
+	) -> HashMap<ExtrinsicHash<ChainApi>, TxInMemPool<ChainApi>> {
+		self.transactions.read().clone()
+	}
+
+	/// Inserts the unwatched transactions.
+	///
+	/// Returns the result of the insertion for every transaction.
+	pub(super) fn push_unwatched(
+		&self,
+		source: TransactionSource,
+		xts: &[ExtrinsicFor<ChainApi>],
+	) -> Vec<Result<ExtrinsicHash<ChainApi>, ChainApi::Error>> {
+		xts.iter().map(|xt| self.push(false, source, xt.clone())).collect()
+	}
+
+	/// Inserts the watched transaction.
+	pub(super) fn push_watched(
+		&self,
+		source: TransactionSource,
+		xt: ExtrinsicFor<ChainApi>,
+	) -> Result<ExtrinsicHash<ChainApi>, ChainApi::Error> {
+		self.push(true, source, xt)
+	}
+
+	fn push(
+		&self,
+		watched: bool,
+		source: TransactionSource,
+		tx: ExtrinsicFor<ChainApi>,
+	) -> Result<ExtrinsicHash<ChainApi>, ChainApi::Error> {
+		let (hash, bytes) = self.api.hash_and_length(&tx);
+		let mut transactions = self.transactions.write();
+
+		if transactions.contains_key(&hash) {
+			return Err(TxPoolError::AlreadyImported(Box::new(hash)).into())
+		}
+
+		let total_bytes = self.total_bytes.load(Ordering::Relaxed);
+		if transactions.len() >= self.max_count || total_bytes + bytes > self.max_bytes {
+			log::debug!(target: LOG_TARGET, "[{:?}] mempool is full", hash);
+			return Err(TxPoolError::ImmediatelyDropped.into())
+		}
+
+		transactions.insert(hash, TxInMemPool { watched, tx, source, bytes });
+		self.total_bytes.store(total_bytes + bytes, Ordering::Relaxed);
+		Ok(hash)
+	}
+
+	/// Removes the transactions from the memory pool.
+	pub(super) fn remove_transactions(&self, hashes: &[ExtrinsicHash<ChainApi>]) {
+		let mut transactions = self.transactions.write();
+		for hash in hashes {
+			if let Some(tx) = transactions.remove(hash) {
+				self.total_bytes.fetch_sub(tx.bytes, Ordering::Relaxed);
+			}
+		}
+	}
+}
@@ -0,0 +1,93 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Transaction pool view.
+//!
+//! The view represents the state of the transaction pool at the given block. It contains the
+//! transactions that were validated against the state of that block.
+
+use crate::{
+	graph::{self, BlockHash, ExtrinsicFor, ExtrinsicHash, IsValidator},
+	LOG_TARGET,
+};
+use futures::{future::join_all, stream::BoxStream};
+use sc_transaction_pool_api::{TransactionSource, TransactionStatus};
+use sp_blockchain::HashAndNumber;
+use std::sync::Arc;
+
+/// The stream of the transaction events reported by a single view.
+///
+/// The stream is terminated with `None` when the view is removed.
+pub(super) type ViewStream<ChainApi> = BoxStream<
+	'static,
+	(
+		BlockHash<ChainApi>,
+		Option<TransactionStatus<ExtrinsicHash<ChainApi>, ExtrinsicHash<ChainApi>>>,
+	),
+>;
+
+/// The state of the transaction pool at the given block.
+pub(super) struct View<ChainApi: graph::ChainApi> {
+	/// The pool containing the transactions validated at the view's block.
+	pub(super) pool: graph::Pool<ChainApi>,
+	/// The block at which the view was created.
+	pub(super) at: HashAndNumber<ChainApi::Block>,
+}
+
+impl<ChainApi> View<ChainApi>
+where
+	ChainApi: graph::ChainApi + 'static,
+{
+	/// Creates a new empty view at the given block.
+	pub(super) fn new(
+		api: Arc<ChainApi>,
+		at: HashAndNumber<ChainApi::Block>,
+		options: graph::Options,
+		is_validator: IsValidator,
+	) -> Self {
+		Self { pool: graph::Pool::new(options, is_validator, api), at }
+	}
+
+	/// Validates and imports the given transactions into the view.
+	pub(super) async fn submit_many(
+		&self,
+		xts: impl IntoIterator<Item = (TransactionSource, ExtrinsicFor<ChainApi>)>,
+	) -> Vec<Result<ExtrinsicHash<ChainApi>, ChainApi::Error>> {
+		log::trace!(target: LOG_TARGET, "[{:?}] view::submit_many", self.at.hash);
+		join_all(
+			xts.into_iter()
+				.map(|(source, xt)| self.pool.submit_one(self.at.hash, source, xt)),
+		)
+		.await
+	}
+
+	/// Creates the watcher of the given transaction.
+	///
+	/// The transaction shall be submitted to the view after the watcher is created.
+	pub(super) fn create_watcher(
+		&self,
+		tx_hash: ExtrinsicHash<ChainApi>,
+	) -> graph::watcher::Watcher<ExtrinsicHash<ChainApi>, ExtrinsicHash<ChainApi>> {
+		self.pool.validated_pool().create_watcher(tx_hash)
+	}
+
+	/// Returns the status of the view's pool.
+	pub(super) fn status(&self) -> sc_transaction_pool_api::PoolStatus {
+		self.pool.validated_pool().status()
+	}
+}
@@ -0,0 +1,179 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The store of the views maintained by the fork-aware transaction pool.
+
+use super::view::View;
+use crate::{
+	graph::{self, base_pool::Transaction, BlockHash, ExtrinsicFor, ExtrinsicHash},
+	LOG_TARGET,
+};
+use futures::future::join_all;
+use parking_lot::RwLock;
+use sc_transaction_pool_api::TransactionSource;
+use std::{collections::HashMap, sync::Arc};
+
+/// The collection of the views, one per every leaf of the tree of the non-finalized blocks.
+pub(super) struct ViewStore<ChainApi: graph::ChainApi> {
+	/// The views, indexed by the block hash.
+	views: RwLock<HashMap<BlockHash<ChainApi>, Arc<View<ChainApi>>>>,
+	/// The view at the most recently notified best block.
+	most_recent_view: RwLock<Option<BlockHash<ChainApi>>>,
+}
+
+impl<ChainApi> ViewStore<ChainApi>
+where
+	ChainApi: graph::ChainApi + 'static,
+{
+	/// Creates a new empty view store.
+	pub(super) fn new() -> Self {
+		Self { views: Default::default(), most_recent_view: Default::default() }
+	}
+
+	/// Returns `true` if there are no views.
+	pub(super) fn is_empty(&self) -> bool {
+		self.views.read().is_empty()
+	}
+
+	/// Returns `true` if the store contains the view at the given block.
+	pub(super) fn contains(&self, at: &BlockHash<ChainApi>) -> bool {
+		self.views.read().contains_key(at)
+	}
+
+	/// Returns the view at the given block.
+	pub(super) fn get_view_at(&self, at: &BlockHash<ChainApi>) -> Option<Arc<View<ChainApi>>> {
+		self.views.read().get(at).cloned()
+	}
+
+	/// Returns all the views.
+	pub(super) fn views(&self) -> Vec<Arc<View<ChainApi>>> {
+		self.views.read().values().cloned().collect()
+	}
+
+	/// Returns the view at the most recently notified best block.
+	pub(super) fn most_recent_view(&self) -> Option<Arc<View<ChainApi>>> {
+		let most_recent_view = (*self.most_recent_view.read())?;
+		self.get_view_at(&most_recent_view)
+	}
+
+	/// Inserts the view.
+	pub(super) fn insert_view(&self, view: Arc<View<ChainApi>>) {
+		self.views.write().insert(view.at.hash, view);
+	}
+
+	/// Makes the view at the given block the most recent one.
+	pub(super) fn set_most_recent(&self, at: BlockHash<ChainApi>) {
+		*self.most_recent_view.write() = Some(at);
+	}
+
+	/// Removes the views for which the given predicate returns `false`.
+	///
+	/// The predicate is evaluated without holding the lock on the views, so it may read the
+	/// database. Returns the removed views.
+	pub(super) fn retain(
+		&self,
+		mut predicate: impl FnMut(&View<ChainApi>) -> bool,
+	) -> Vec<Arc<View<ChainApi>>> {
+		let to_remove = self
+			.views()
+			.into_iter()
+			.filter(|view| !predicate(view))
+			.map(|view| view.at.hash)
+			.collect::<Vec<_>>();
+		let removed = {
+			let mut views = self.views.write();
+			to_remove.iter().filter_map(|at| views.remove(at)).collect::<Vec<_>>()
+		};
+
+		let mut most_recent_view = self.most_recent_view.write();
+		if most_recent_view.map_or(false, |at| !self.views.read().contains_key(&at)) {
+			*most_recent_view = None;
+		}
+
+		log::trace!(
+			target: LOG_TARGET,
+			"view_store::retain removed: {:?}",
+			removed.iter().map(|view| view.at.hash).collect::<Vec<_>>()
+		);
+		removed
+	}
+
+	/// Submits the transactions to all the views.
+	///
+	/// The transaction is considered valid if it was imported into at least one view. Otherwise
+	/// the error reported by the most recent view is returned. If there are no views, the
+	/// transactions are accepted as they will be validated once the view is created.
+	pub(super) async fn submit_at(
+		&self,
+		source: TransactionSource,
+		xts: Vec<(ExtrinsicHash<ChainApi>, ExtrinsicFor<ChainApi>)>,
+	) -> Vec<Result<ExtrinsicHash<ChainApi>, ChainApi::Error>> {
+		let views = self.views();
+		let views_results = join_all(views.iter().map(|view| {
+			let xts = xts.iter().map(|(_, xt)| (source, xt.clone()));
+			view.submit_many(xts)
+		}))
+		.await;
+
+		let most_recent_view = *self.most_recent_view.read();
+		let mut results = xts.iter().map(|_| None).collect::<Vec<_>>();
+		for (view, view_results) in views.iter().zip(views_results) {
+			let is_most_recent = Some(view.at.hash) == most_recent_view;
+			for (result, view_result) in results.iter_mut().zip(view_results) {
+				let replace = match (&*result, &view_result) {
+					(Some(Ok(_)), _) => false,
+					(_, Ok(_)) | (None, Err(_)) => true,
+					(Some(Err(_)), Err(_)) => is_most_recent,
+				};
+				if replace {
+					*result = Some(view_result);
+				}
+			}
+		}
+
+		results
+			.into_iter()
+			.zip(xts)
+			.map(|(result, (hash, _))| result.unwrap_or(Ok(hash)))
+			.collect()
+	}
+
+	/// Returns `true` if the transaction is imported into any of the views.
+	pub(super) fn is_imported(&self, hash: &ExtrinsicHash<ChainApi>) -> bool {
+		self.views
+			.read()
+			.values()
+			.any(|view| view.pool.validated_pool().pool.read().is_imported(hash))
+	}
+
+	/// Removes the given transactions from all the views.
+	///
+	/// Returns the removed transactions.
+	pub(super) fn remove_invalid(
+		&self,
+		hashes: &[ExtrinsicHash<ChainApi>],
+	) -> Vec<Arc<Transaction<ExtrinsicHash<ChainApi>, ExtrinsicFor<ChainApi>>>> {
+		let mut removed = HashMap::new();
+		for view in self.views() {
+			for tx in view.pool.validated_pool().remove_invalid(hashes) {
+				removed.insert(tx.hash, tx);
+			}
+		}
+		removed.into_values().collect()
+	}
+}
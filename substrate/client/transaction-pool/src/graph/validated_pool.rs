@@ -100,6 +100,13 @@ impl From<Box<dyn Fn() -> bool + Send + Sync>> for IsValidator {
 	}
 }
 
+impl IsValidator {
+	/// Returns `true` if the node is a validator.
+	pub(crate) fn is_validator(&self) -> bool {
+		(self.0)()
+	}
+}
+
 /// Pool that deals with validated transactions.
 pub struct ValidatedPool<B: ChainApi> {
 	api: Arc<B>,
@@ -294,6 +301,16 @@ impl<B: ChainApi> ValidatedPool<B> {
 		}
 	}
 
+	/// Creates a new watcher for the given extrinsic.
+	///
+	/// The watcher receives the events of a transaction that is submitted later on.
+	pub fn create_watcher(
+		&self,
+		tx_hash: ExtrinsicHash<B>,
+	) -> Watcher<ExtrinsicHash<B>, ExtrinsicHash<B>> {
+		self.listener.write().create_watcher(tx_hash)
+	}
+
 	/// Resubmits revalidated transactions back to the pool.
 	///
 	/// Removes and then submits passed transactions and all dependent transactions.
@@ -23,8 +23,10 @@
 #![warn(unused_extern_crates)]
 
 mod api;
+mod builder;
 mod enactment_state;
 pub mod error;
+mod fork_aware_txpool;
 mod graph;
 mod metrics;
 mod revalidation;
@@ -33,7 +35,12 @@ mod tests;
 
 pub use crate::api::FullChainApi;
 use async_trait::async_trait;
+pub use builder::{
+	Builder, FullClientTransactionPool, TransactionPoolHandle, TransactionPoolOptions,
+	TransactionPoolType,
+};
 use enactment_state::{EnactmentAction, EnactmentState};
+pub use fork_aware_txpool::{ForkAwareTxPool, ForkAwareTxPoolFull};
 use futures::{
 	channel::oneshot,
 	future::{self, ready},
@@ -330,7 +337,7 @@ where
 		self.pool.validated_pool().ready_by_hash(hash)
 	}
 
-	fn ready_at(&self, at: NumberFor<Self::Block>) -> PolledIterator<PoolApi> {
+	fn ready_at(&self, at: <Self::Block as BlockT>::Hash) -> PolledIterator<PoolApi> {
 		let status = self.status();
 		// If there are no transactions in the pool, it is fine to return early.
 		//
@@ -340,6 +347,15 @@ where
 			return async { Box::new(std::iter::empty()) as Box<_> }.boxed()
 		}
 
+		// The pool is maintained at the best chain only, so the blocks are told apart by number.
+		let at = match self.api.block_id_to_number(&BlockId::Hash(at)) {
+			Ok(Some(number)) => number,
+			_ => {
+				log::debug!(target: LOG_TARGET, "Could not find the number of {:?}.", at);
+				return async { Box::new(std::iter::empty()) as Box<_> }.boxed()
+			},
+		};
+
 		if self.ready_poll.lock().updated_at() >= at {
 			log::trace!(target: LOG_TARGET, "Transaction pool already processed block  #{}", at);
 			let iterator: ReadyIteratorFor<PoolApi> = Box::new(self.pool.validated_pool().ready());
@@ -658,7 +674,7 @@ where
 					})
 					.unwrap_or_default()
 					.into_iter()
-					.filter(|tx| tx.is_signed().unwrap_or(true));
+					.filter(|tx| !tx.is_bare());
 
 				let mut resubmitted_to_report = 0;
 
@@ -727,6 +743,11 @@ where
 	PoolApi: 'static + graph::ChainApi<Block = Block>,
 {
 	async fn maintain(&self, event: ChainEvent<Self::Block>) {
+		// The pool is maintained at the best chain only.
+		if let ChainEvent::NewBlock { .. } = event {
+			return
+		}
+
 		let prev_finalized_block = self.enactment_state.lock().recent_finalized_block();
 		let compute_tree_route = |from, to| -> Result<TreeRoute<Block>, String> {
 			match self.api.tree_route(from, to) {
@@ -784,10 +805,7 @@ where
 	Client: sc_client_api::BlockchainEvents<Block>,
 	Pool: MaintainedTransactionPool<Block = Block>,
 {
-	let import_stream = client
-		.import_notification_stream()
-		.filter_map(|n| ready(n.try_into().ok()))
-		.fuse();
+	let import_stream = client.import_notification_stream().map(Into::into).fuse();
 	let finality_stream = client.finality_notification_stream().map(Into::into).fuse();
 
 	futures::stream::select(import_stream, finality_stream)
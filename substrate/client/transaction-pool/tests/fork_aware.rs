@@ -0,0 +1,243 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the fork-aware transaction pool.
+
+use futures::{
+	executor::{block_on, block_on_stream},
+	FutureExt,
+};
+use sc_transaction_pool::ForkAwareTxPool;
+use sc_transaction_pool_api::{
+	ChainEvent, MaintainedTransactionPool, TransactionPool, TransactionStatus,
+};
+use sp_runtime::transaction_validity::TransactionSource;
+use std::sync::Arc;
+use substrate_test_runtime_client::{
+	runtime::{Block, Hash, Header},
+	AccountKeyring::*,
+};
+use substrate_test_runtime_transaction_pool::{uxt, TestApi};
+
+const SOURCE: TransactionSource = TransactionSource::External;
+
+fn pool() -> (ForkAwareTxPool<TestApi, Block>, Arc<TestApi>, Hash) {
+	let api = Arc::new(TestApi::with_alice_nonce(200));
+	let genesis_hash = api.expect_hash_from_number(0);
+	(ForkAwareTxPool::new_test(api.clone(), genesis_hash, genesis_hash), api, genesis_hash)
+}
+
+fn new_best_block_event(header: &Header) -> ChainEvent<Block> {
+	ChainEvent::NewBestBlock { hash: header.hash(), tree_route: None }
+}
+
+fn new_block_event(header: &Header) -> ChainEvent<Block> {
+	ChainEvent::NewBlock { hash: header.hash() }
+}
+
+fn finalized_block_event(header: &Header) -> ChainEvent<Block> {
+	ChainEvent::Finalized { hash: header.hash(), tree_route: Arc::from(vec![]) }
+}
+
+#[test]
+fn fatp_submitted_transactions_are_ready() {
+	let (pool, _, genesis) = pool();
+
+	block_on(pool.submit_one(genesis, SOURCE, uxt(Alice, 200))).unwrap();
+	block_on(pool.submit_one(genesis, SOURCE, uxt(Alice, 201))).unwrap();
+
+	assert_eq!(pool.status().ready, 2);
+	assert_eq!(pool.mempool_len(), 2);
+}
+
+#[test]
+fn fatp_rejects_duplicated_transactions() {
+	let (pool, _, genesis) = pool();
+
+	block_on(pool.submit_one(genesis, SOURCE, uxt(Alice, 200))).unwrap();
+	assert!(block_on(pool.submit_one(genesis, SOURCE, uxt(Alice, 200))).is_err());
+	assert_eq!(pool.mempool_len(), 1);
+}
+
+#[test]
+fn fatp_reports_in_block_and_finalized() {
+	let (pool, api, genesis) = pool();
+	let xt = uxt(Alice, 200);
+
+	let watcher = block_on(pool.submit_and_watch(genesis, SOURCE, xt.clone())).unwrap();
+
+	let header = api.push_block(1, vec![xt], true);
+	block_on(pool.maintain(new_best_block_event(&header)));
+	assert_eq!(pool.status().ready, 0);
+	assert_eq!(pool.mempool_len(), 1);
+
+	block_on(pool.maintain(finalized_block_event(&header)));
+	assert_eq!(pool.mempool_len(), 0);
+
+	assert_eq!(
+		block_on_stream(watcher).collect::<Vec<_>>(),
+		vec![
+			TransactionStatus::Ready,
+			TransactionStatus::InBlock((header.hash(), 0)),
+			TransactionStatus::Finalized((header.hash(), 0)),
+		]
+	);
+}
+
+#[test]
+fn fatp_keeps_transactions_retracted_by_fork_switch() {
+	let (pool, api, genesis) = pool();
+	let xt = uxt(Alice, 200);
+
+	let watcher = block_on(pool.submit_and_watch(genesis, SOURCE, xt.clone())).unwrap();
+
+	let header_a = api.push_block(1, vec![xt], true);
+	block_on(pool.maintain(new_best_block_event(&header_a)));
+	assert_eq!(pool.status().ready, 0);
+
+	let header_b = api.push_block(1, vec![], true);
+	block_on(pool.maintain(new_best_block_event(&header_b)));
+	assert_eq!(pool.status().ready, 1);
+	assert_eq!(pool.views_count(), 2);
+	assert_eq!(pool.view_status_at(header_a.hash()).unwrap().ready, 0);
+
+	assert_eq!(
+		block_on_stream(watcher).take(4).collect::<Vec<_>>(),
+		vec![
+			TransactionStatus::Ready,
+			TransactionStatus::InBlock((header_a.hash(), 0)),
+			TransactionStatus::Retracted(header_a.hash()),
+			TransactionStatus::Ready,
+		]
+	);
+}
+
+#[test]
+fn fatp_switching_back_to_existing_view_reports_in_block() {
+	let (pool, api, genesis) = pool();
+	let xt = uxt(Alice, 200);
+
+	let watcher = block_on(pool.submit_and_watch(genesis, SOURCE, xt.clone())).unwrap();
+
+	let header_a = api.push_block(1, vec![xt], true);
+	let header_b = api.push_block(1, vec![], true);
+	block_on(pool.maintain(new_best_block_event(&header_a)));
+	block_on(pool.maintain(new_best_block_event(&header_b)));
+	block_on(pool.maintain(new_best_block_event(&header_a)));
+	assert_eq!(pool.status().ready, 0);
+
+	assert_eq!(
+		block_on_stream(watcher).take(5).collect::<Vec<_>>(),
+		vec![
+			TransactionStatus::Ready,
+			TransactionStatus::InBlock((header_a.hash(), 0)),
+			TransactionStatus::Retracted(header_a.hash()),
+			TransactionStatus::Ready,
+			TransactionStatus::InBlock((header_a.hash(), 0)),
+		]
+	);
+}
+
+#[test]
+fn fatp_finalization_removes_stale_views() {
+	let (pool, api, genesis) = pool();
+
+	block_on(pool.submit_one(genesis, SOURCE, uxt(Alice, 200))).unwrap();
+
+	let header_a = api.push_block(1, vec![], true);
+	let header_b = api.push_block(1, vec![], true);
+	block_on(pool.maintain(new_best_block_event(&header_a)));
+	block_on(pool.maintain(new_best_block_event(&header_b)));
+	assert_eq!(pool.views_count(), 2);
+
+	block_on(pool.maintain(finalized_block_event(&header_b)));
+	assert_eq!(pool.views_count(), 1);
+	assert!(pool.view_status_at(header_a.hash()).is_none());
+	assert_eq!(pool.status().ready, 1);
+}
+
+#[test]
+fn fatp_transactions_invalid_in_all_views_are_invalidated() {
+	let (pool, api, genesis) = pool();
+	let xt = uxt(Alice, 200);
+
+	let watcher = block_on(pool.submit_and_watch(genesis, SOURCE, xt.clone())).unwrap();
+
+	api.add_invalid(&xt);
+	let header = api.push_block(1, vec![], true);
+	block_on(pool.maintain(new_best_block_event(&header)));
+
+	assert_eq!(pool.status().ready, 0);
+	assert_eq!(pool.mempool_len(), 0);
+	assert_eq!(
+		block_on_stream(watcher).collect::<Vec<_>>(),
+		vec![TransactionStatus::Ready, TransactionStatus::Invalid]
+	);
+}
+
+#[test]
+fn fatp_import_notification_is_sent_once() {
+	let (pool, api, genesis) = pool();
+	let import_stream = pool.import_notification_stream();
+	let xt = uxt(Alice, 200);
+
+	let hash = block_on(pool.submit_one(genesis, SOURCE, xt)).unwrap();
+	let header = api.push_block(1, vec![], true);
+	block_on(pool.maintain(new_best_block_event(&header)));
+	drop(pool);
+
+	assert_eq!(block_on_stream(import_stream).collect::<Vec<_>>(), vec![hash]);
+}
+
+#[test]
+fn fatp_new_block_creates_view_for_non_best_fork() {
+	let (pool, api, genesis) = pool();
+	let xt = uxt(Alice, 200);
+
+	block_on(pool.submit_one(genesis, SOURCE, xt.clone())).unwrap();
+
+	let header_a = api.push_block(1, vec![xt], true);
+	let header_b = api.push_block(1, vec![], true);
+	block_on(pool.maintain(new_best_block_event(&header_a)));
+	block_on(pool.maintain(new_block_event(&header_b)));
+
+	assert_eq!(pool.views_count(), 2);
+	assert_eq!(pool.status().ready, 0);
+	assert_eq!(pool.view_status_at(header_b.hash()).unwrap().ready, 1);
+}
+
+#[test]
+fn fatp_ready_at_distinguishes_forks_at_the_same_height() {
+	let (pool, api, genesis) = pool();
+	let xt = uxt(Alice, 200);
+
+	block_on(pool.submit_one(genesis, SOURCE, xt.clone())).unwrap();
+
+	let header_a = api.push_block(1, vec![xt], true);
+	let header_b = api.push_block(1, vec![], true);
+	block_on(pool.maintain(new_best_block_event(&header_a)));
+
+	let ready_a = pool.ready_at(header_a.hash()).now_or_never().unwrap();
+	assert_eq!(ready_a.count(), 0);
+
+	let mut ready_b = pool.ready_at(header_b.hash());
+	assert!((&mut ready_b).now_or_never().is_none());
+
+	block_on(pool.maintain(new_block_event(&header_b)));
+	assert_eq!(ready_b.now_or_never().unwrap().count(), 1);
+}
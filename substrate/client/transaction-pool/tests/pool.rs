@@ -929,8 +929,9 @@ fn ready_set_should_not_resolve_before_block_update() {
 	let xt1 = uxt(Alice, 209);
 	block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, xt1.clone()))
 		.expect("1. Imported");
+	let header = api.push_block(1, vec![], true);
 
-	assert!(pool.ready_at(1).now_or_never().is_none());
+	assert!(pool.ready_at(header.hash()).now_or_never().is_none());
 }
 
 #[test]
@@ -942,9 +943,10 @@ fn ready_set_should_resolve_after_block_update() {
 
 	block_on(pool.submit_one(api.expect_hash_from_number(1), SOURCE, xt1.clone()))
 		.expect("1. Imported");
+	let hash = header.hash();
 	block_on(pool.maintain(block_event(header)));
 
-	assert!(pool.ready_at(1).now_or_never().is_some());
+	assert!(pool.ready_at(hash).now_or_never().is_some());
 }
 
 #[test]
@@ -960,7 +962,7 @@ fn ready_set_should_eventually_resolve_when_block_update_arrives() {
 	let noop_waker = futures::task::noop_waker();
 	let mut context = futures::task::Context::from_waker(&noop_waker);
 
-	let mut ready_set_future = pool.ready_at(1);
+	let mut ready_set_future = pool.ready_at(header.hash());
 	if ready_set_future.poll_unpin(&mut context).is_ready() {
 		panic!("Ready set should not be ready before block update!");
 	}
@@ -71,7 +71,7 @@ where
 		};
 
 		let voters =
-			<pallet_staking::Pallet<Runtime> as ElectionDataProvider>::electing_voters(bounds)
+			<pallet_staking::Pallet<Runtime> as ElectionDataProvider>::electing_voters(bounds, 0)
 				.unwrap();
 
 		let mut voters_nominator_only = voters
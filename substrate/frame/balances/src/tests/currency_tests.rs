@@ -24,8 +24,8 @@ use frame_support::{
 		BalanceStatus::{Free, Reserved},
 		Currency,
 		ExistenceRequirement::{self, AllowDeath, KeepAlive},
-		Hooks, LockIdentifier, LockableCurrency, NamedReservableCurrency, ReservableCurrency,
-		WithdrawReasons,
+		Hooks, InspectLockableCurrency, LockIdentifier, LockableCurrency, NamedReservableCurrency,
+		ReservableCurrency, WithdrawReasons,
 	},
 	StorageNoopGuard,
 };
@@ -155,6 +155,23 @@ fn lock_removal_should_work() {
 		});
 }
 
+#[test]
+fn balance_locked_should_work() {
+	ExtBuilder::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build_and_execute_with(|| {
+			assert_eq!(Balances::balance_locked(ID_1, &1), 0);
+			Balances::set_lock(ID_1, &1, 5, WithdrawReasons::all());
+			Balances::set_lock(ID_2, &1, 7, WithdrawReasons::all());
+			assert_eq!(Balances::balance_locked(ID_1, &1), 5);
+			assert_eq!(Balances::balance_locked(ID_2, &1), 7);
+			Balances::remove_lock(ID_1, &1);
+			assert_eq!(Balances::balance_locked(ID_1, &1), 0);
+			assert_eq!(Balances::balance_locked(ID_2, &1), 7);
+		});
+}
+
 #[test]
 fn lock_replacement_should_work() {
 	ExtBuilder::default()
@@ -240,22 +257,22 @@ fn lock_should_work_reserve() {
 				TokenError::Frozen
 			);
 			assert_noop!(Balances::reserve(&1, 1), Error::<Test>::LiquidityRestrictions,);
-			assert!(<ChargeTransactionPayment<Test> as SignedExtension>::pre_dispatch(
-				ChargeTransactionPayment::from(1),
-				&1,
-				CALL,
-				&info_from_weight(Weight::from_parts(1, 0)),
-				1,
-			)
-			.is_err());
-			assert!(<ChargeTransactionPayment<Test> as SignedExtension>::pre_dispatch(
-				ChargeTransactionPayment::from(0),
-				&1,
-				CALL,
-				&info_from_weight(Weight::from_parts(1, 0)),
-				1,
-			)
-			.is_err());
+			assert!(ChargeTransactionPayment::<Test>::from(1)
+				.validate_and_prepare(
+					Some(1).into(),
+					CALL,
+					&info_from_weight(Weight::from_parts(1, 0)),
+					1,
+				)
+				.is_err());
+			assert!(ChargeTransactionPayment::<Test>::from(0)
+				.validate_and_prepare(
+					Some(1).into(),
+					CALL,
+					&info_from_weight(Weight::from_parts(1, 0)),
+					1,
+				)
+				.is_err());
 		});
 }
 
@@ -271,22 +288,22 @@ fn lock_should_work_tx_fee() {
 				TokenError::Frozen
 			);
 			assert_noop!(Balances::reserve(&1, 1), Error::<Test>::LiquidityRestrictions,);
-			assert!(<ChargeTransactionPayment<Test> as SignedExtension>::pre_dispatch(
-				ChargeTransactionPayment::from(1),
-				&1,
-				CALL,
-				&info_from_weight(Weight::from_parts(1, 0)),
-				1,
-			)
-			.is_err());
-			assert!(<ChargeTransactionPayment<Test> as SignedExtension>::pre_dispatch(
-				ChargeTransactionPayment::from(0),
-				&1,
-				CALL,
-				&info_from_weight(Weight::from_parts(1, 0)),
-				1,
-			)
-			.is_err());
+			assert!(ChargeTransactionPayment::<Test>::from(1)
+				.validate_and_prepare(
+					Some(1).into(),
+					CALL,
+					&info_from_weight(Weight::from_parts(1, 0)),
+					1,
+				)
+				.is_err());
+			assert!(ChargeTransactionPayment::<Test>::from(0)
+				.validate_and_prepare(
+					Some(1).into(),
+					CALL,
+					&info_from_weight(Weight::from_parts(1, 0)),
+					1,
+				)
+				.is_err());
 		});
 }
 
@@ -992,7 +1009,7 @@ fn slash_consumed_slash_full_works() {
 	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
 		Balances::make_free_balance_be(&1, 1_000);
 		assert_ok!(System::inc_consumers(&1)); // <-- Reference counter added here is enough for all tests
-									   // Slashed completed in full
+										 // Slashed completed in full
 		assert_eq!(Balances::slash(&1, 900), (NegativeImbalance::new(900), 0));
 		// Account is still alive
 		assert!(System::account_exists(&1));
@@ -1004,7 +1021,7 @@ fn slash_consumed_slash_over_works() {
 	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
 		Balances::make_free_balance_be(&1, 1_000);
 		assert_ok!(System::inc_consumers(&1)); // <-- Reference counter added here is enough for all tests
-									   // Slashed completed in full
+										 // Slashed completed in full
 		assert_eq!(Balances::slash(&1, 1_000), (NegativeImbalance::new(900), 100));
 		// Account is still alive
 		assert!(System::account_exists(&1));
@@ -1016,7 +1033,7 @@ fn slash_consumed_slash_partial_works() {
 	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
 		Balances::make_free_balance_be(&1, 1_000);
 		assert_ok!(System::inc_consumers(&1)); // <-- Reference counter added here is enough for all tests
-									   // Slashed completed in full
+										 // Slashed completed in full
 		assert_eq!(Balances::slash(&1, 800), (NegativeImbalance::new(800), 0));
 		// Account is still alive
 		assert!(System::account_exists(&1));
@@ -33,18 +33,27 @@
 //!
 //! and thanks to versioning can be easily updated in the future.
 
-use sp_runtime::traits::{Convert, Member};
+use sp_runtime::{
+	generic::OpaqueDigestItemId,
+	traits::{Convert, Header, Member},
+};
 use sp_std::prelude::*;
 
 use codec::Decode;
-use pallet_mmr::{LeafDataProvider, ParentNumberAndHash};
+use pallet_mmr::{primitives::AncestryProof, LeafDataProvider, ParentNumberAndHash};
 use sp_consensus_beefy::{
+	known_payloads,
 	mmr::{BeefyAuthoritySet, BeefyDataProvider, BeefyNextAuthoritySet, MmrLeaf, MmrLeafVersion},
+	AncestryHelper, AncestryHelperWeightInfo, Commitment, ConsensusLog,
 	ValidatorSet as BeefyValidatorSet,
 };
 
-use frame_support::{crypto::ecdsa::ECDSAExt, traits::Get};
-use frame_system::pallet_prelude::BlockNumberFor;
+use frame_support::{
+	crypto::ecdsa::ECDSAExt,
+	traits::Get,
+	weights::{constants::WEIGHT_REF_TIME_PER_MICROS, Weight},
+};
+use frame_system::pallet_prelude::{BlockNumberFor, HeaderFor};
 
 pub use pallet::*;
 
@@ -174,6 +183,118 @@ where
 	}
 }
 
+impl<T: Config> AncestryHelper<HeaderFor<T>> for Pallet<T>
+where
+	T: pallet_mmr::Config<Hashing = sp_consensus_beefy::MmrHashing>,
+{
+	type Proof = AncestryProof<MerkleRootOf<T>>;
+	type ValidationContext = MerkleRootOf<T>;
+
+	fn generate_proof(
+		prev_block_number: BlockNumberFor<T>,
+		best_known_block_number: Option<BlockNumberFor<T>>,
+	) -> Option<Self::Proof> {
+		pallet_mmr::Pallet::<T>::generate_ancestry_proof(prev_block_number, best_known_block_number)
+			.map_err(|e| {
+				log::error!(
+					target: "runtime::beefy",
+					"Failed to generate ancestry proof for block {:?} at {:?}: {:?}",
+					prev_block_number,
+					best_known_block_number,
+					e
+				);
+				e
+			})
+			.ok()
+	}
+
+	fn extract_validation_context(header: HeaderFor<T>) -> Option<Self::ValidationContext> {
+		// Check if the provided header is canonical.
+		let expected_hash = frame_system::Pallet::<T>::block_hash(*header.number());
+		if expected_hash != header.hash() {
+			return None
+		}
+
+		// Extract the MMR root from the header digest
+		header.digest().convert_first(|l| {
+			l.try_to(OpaqueDigestItemId::Consensus(&sp_consensus_beefy::BEEFY_ENGINE_ID))
+				.and_then(|log: ConsensusLog<<T as pallet_beefy::Config>::BeefyId>| match log {
+					ConsensusLog::MmrRoot(mmr_root) => Some(mmr_root),
+					_ => None,
+				})
+		})
+	}
+
+	fn is_non_canonical(
+		commitment: &Commitment<BlockNumberFor<T>>,
+		proof: Self::Proof,
+		context: Self::ValidationContext,
+	) -> bool {
+		// The proof must be generated for the block that the commitment is pointing to,
+		// otherwise it can't tell us anything about the commitment.
+		let commitment_leaf_count =
+			match pallet_mmr::Pallet::<T>::block_num_to_leaf_count(commitment.block_number) {
+				Ok(commitment_leaf_count) => commitment_leaf_count,
+				Err(_) => {
+					// We can't prove that the commitment is non-canonical if the
+					// `commitment.block_number` is invalid.
+					return false
+				},
+			};
+		if commitment_leaf_count != proof.prev_leaf_count {
+			return false
+		}
+
+		// Verify the ancestry proof against the canonical MMR root, obtaining the canonical
+		// MMR root at `commitment.block_number`.
+		let canonical_mmr_root = context;
+		let canonical_prev_root =
+			match pallet_mmr::Pallet::<T>::verify_ancestry_proof(canonical_mmr_root, proof) {
+				Ok(canonical_prev_root) => canonical_prev_root,
+				Err(_) => {
+					// Can't prove that the commitment is non-canonical if the proof
+					// is invalid.
+					return false
+				},
+			};
+
+		// If the commitment doesn't contain an MMR root (or contains an invalid one), or if the
+		// MMR root is different from the canonical one, the commitment is non-canonical.
+		match commitment.payload.get_decoded::<MerkleRootOf<T>>(&known_payloads::MMR_ROOT_ID) {
+			Some(commitment_root) => canonical_prev_root != commitment_root,
+			None => true,
+		}
+	}
+}
+
+impl<T: Config> AncestryHelperWeightInfo<HeaderFor<T>> for Pallet<T>
+where
+	T: pallet_mmr::Config<Hashing = sp_consensus_beefy::MmrHashing>,
+{
+	fn extract_validation_context() -> Weight {
+		// reading the canonical block hash and decoding the header digest
+		T::DbWeight::get()
+			.reads(1)
+			.saturating_add(Weight::from_parts(10u64 * WEIGHT_REF_TIME_PER_MICROS, 0))
+	}
+
+	fn is_non_canonical(proof: &<Self as AncestryHelper<HeaderFor<T>>>::Proof) -> Weight {
+		// the number of hashes computed while verifying the ancestry proof
+		let hashes_count = proof.prev_peaks.len().saturating_add(proof.items.len()) as u64;
+
+		// converting the block number to a leaf count
+		T::DbWeight::get()
+			.reads(2)
+			// verifying the ancestry proof
+			.saturating_add(
+				Weight::from_parts(5u64 * WEIGHT_REF_TIME_PER_MICROS, 0)
+					.saturating_mul(hashes_count.saturating_add(1)),
+			)
+			// decoding and comparing the commitment MMR root
+			.saturating_add(Weight::from_parts(5u64 * WEIGHT_REF_TIME_PER_MICROS, 0))
+	}
+}
+
 impl<T: Config> Pallet<T> {
 	/// Return the currently active BEEFY authority set proof.
 	pub fn authority_set_proof() -> BeefyAuthoritySet<MerkleRootOf<T>> {
@@ -19,15 +19,23 @@ use std::vec;
 
 use codec::{Decode, Encode};
 use sp_consensus_beefy::{
+	known_payloads,
 	mmr::{BeefyNextAuthoritySet, MmrLeafVersion},
-	ValidatorSet,
+	AncestryHelper, Commitment, Payload, ValidatorSet,
 };
 
-use sp_core::H256;
+use sp_core::{
+	offchain::{testing::TestOffchainExt, OffchainDbExt, OffchainWorkerExt},
+	H256,
+};
 use sp_io::TestExternalities;
-use sp_runtime::{traits::Keccak256, DigestItem};
+use sp_runtime::{
+	traits::{Header as HeaderT, Keccak256},
+	DigestItem,
+};
 
 use frame_support::traits::OnInitialize;
+use frame_system::pallet_prelude::HeaderFor;
 
 use crate::mock::*;
 
@@ -43,6 +51,12 @@ pub fn beefy_log(log: ConsensusLog<BeefyId>) -> DigestItem {
 	DigestItem::Consensus(BEEFY_ENGINE_ID, log.encode())
 }
 
+fn register_offchain_ext(ext: &mut TestExternalities) {
+	let (offchain, _offchain_state) = TestOffchainExt::with_offchain_db(ext.offchain_db());
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+}
+
 fn read_mmr_leaf(ext: &mut TestExternalities, key: Vec<u8>) -> MmrLeaf {
 	type Node = pallet_mmr::primitives::DataOrHash<Keccak256, MmrLeaf>;
 	ext.persist_offchain_overlay();
@@ -207,3 +221,99 @@ fn should_update_authorities() {
 		assert_eq!(want, next_auth_set.keyset_commitment);
 	});
 }
+
+#[test]
+fn extract_validation_context_should_work_correctly() {
+	let mut ext = new_test_ext(vec![1, 2]);
+
+	ext.execute_with(|| {
+		System::initialize(&1, &H256::repeat_byte(1), &Default::default());
+		init_block(1);
+		let mmr_root = Mmr::mmr_root();
+		let header = System::finalize();
+
+		// The header is not part of the canonical chain yet.
+		assert_eq!(
+			<BeefyMmr as AncestryHelper<HeaderFor<Test>>>::extract_validation_context(
+				header.clone()
+			),
+			None
+		);
+
+		// Once the header is canonical, the MMR root can be extracted from its digest.
+		frame_system::BlockHash::<Test>::insert(1, header.hash());
+		assert_eq!(
+			<BeefyMmr as AncestryHelper<HeaderFor<Test>>>::extract_validation_context(
+				header.clone()
+			),
+			Some(mmr_root)
+		);
+
+		// A different header at the same height isn't canonical.
+		let mut fork_header = header;
+		fork_header.digest = Default::default();
+		assert_eq!(
+			<BeefyMmr as AncestryHelper<HeaderFor<Test>>>::extract_validation_context(fork_header),
+			None
+		);
+	});
+}
+
+#[test]
+fn is_non_canonical_should_work_correctly() {
+	let mut ext = new_test_ext(vec![1, 2]);
+
+	let mut prev_roots = vec![];
+	ext.execute_with(|| {
+		for block_num in 1..=50 {
+			System::initialize(
+				&block_num,
+				&H256::repeat_byte(block_num as u8),
+				&Default::default(),
+			);
+			init_block(block_num);
+			prev_roots.push(Mmr::mmr_root())
+		}
+	});
+	ext.persist_offchain_overlay();
+	register_offchain_ext(&mut ext);
+
+	ext.execute_with(|| {
+		let mmr_root = Mmr::mmr_root();
+		let valid_proof =
+			<BeefyMmr as AncestryHelper<HeaderFor<Test>>>::generate_proof(25, None).unwrap();
+		let mut invalid_proof = valid_proof.clone();
+		invalid_proof.items.push((300, Default::default()));
+
+		let commitment = |block_number: u64, payload: Payload| Commitment {
+			payload,
+			block_number,
+			validator_set_id: 0,
+		};
+		let is_non_canonical = |commitment: &Commitment<u64>, proof| {
+			<BeefyMmr as AncestryHelper<HeaderFor<Test>>>::is_non_canonical(
+				commitment, proof, mmr_root,
+			)
+		};
+
+		// A commitment that contains the canonical MMR root is canonical.
+		let canonical_payload =
+			Payload::from_single_entry(known_payloads::MMR_ROOT_ID, prev_roots[24].encode());
+		assert!(!is_non_canonical(&commitment(25, canonical_payload.clone()), valid_proof.clone()));
+
+		// A commitment that contains a different MMR root is non-canonical.
+		let fork_payload =
+			Payload::from_single_entry(known_payloads::MMR_ROOT_ID, H256::repeat_byte(0).encode());
+		assert!(is_non_canonical(&commitment(25, fork_payload.clone()), valid_proof.clone()));
+
+		// A commitment without an MMR root is non-canonical.
+		let empty_payload = Payload::from_single_entry([0, 0], vec![]);
+		assert!(is_non_canonical(&commitment(25, empty_payload), valid_proof.clone()));
+
+		// Nothing can be proved using an invalid proof.
+		assert!(!is_non_canonical(&commitment(25, fork_payload.clone()), invalid_proof));
+
+		// Nothing can be proved using a proof generated for a different block.
+		assert!(!is_non_canonical(&commitment(24, fork_payload), valid_proof));
+	});
+}
@@ -17,8 +17,8 @@
 
 //! An opt-in utility module for reporting equivocations.
 //!
-//! This module defines an offence type for BEEFY equivocations
-//! and some utility traits to wire together:
+//! This module defines offence types for BEEFY equivocations (double voting, fork voting and
+//! future block voting) and some utility traits to wire together:
 //! - a key ownership proof system (e.g. to prove that a given authority was part of a session);
 //! - a system for reporting offences;
 //! - a system for signing and submitting transactions;
@@ -36,9 +36,12 @@
 
 use codec::{self as codec, Decode, Encode};
 use frame_support::traits::{Get, KeyOwnerProofSystem};
-use frame_system::pallet_prelude::BlockNumberFor;
+use frame_system::pallet_prelude::{BlockNumberFor, HeaderFor};
 use log::{error, info};
-use sp_consensus_beefy::{EquivocationProof, ValidatorSetId, KEY_TYPE as BEEFY_KEY_TYPE};
+use sp_consensus_beefy::{
+	check_commitment_signature, AncestryHelper, EquivocationProof, ForkVotingProof,
+	FutureBlockVotingProof, ValidatorSetId, KEY_TYPE as BEEFY_KEY_TYPE,
+};
 use sp_runtime::{
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
@@ -65,52 +68,77 @@ pub struct TimeSlot<N: Copy + Clone + PartialOrd + Ord + Eq + PartialEq + Encode
 	pub round: N,
 }
 
-/// BEEFY equivocation offence report.
-pub struct EquivocationOffence<Offender, N>
-where
-	N: Copy + Clone + PartialOrd + Ord + Eq + PartialEq + Encode + Decode,
-{
-	/// Time slot at which this incident happened.
-	pub time_slot: TimeSlot<N>,
-	/// The session index in which the incident happened.
-	pub session_index: SessionIndex,
-	/// The size of the validator set at the time of the offence.
-	pub validator_set_count: u32,
-	/// The authority which produced this equivocation.
-	pub offender: Offender,
-}
+macro_rules! decl_beefy_offence {
+	($(#[$attr:meta])* $name:ident, $id:expr) => {
+		$(#[$attr])*
+		pub struct $name<Offender, N>
+		where
+			N: Copy + Clone + PartialOrd + Ord + Eq + PartialEq + Encode + Decode,
+		{
+			/// Time slot at which this incident happened.
+			pub time_slot: TimeSlot<N>,
+			/// The session index in which the incident happened.
+			pub session_index: SessionIndex,
+			/// The size of the validator set at the time of the offence.
+			pub validator_set_count: u32,
+			/// The authority which produced this equivocation.
+			pub offender: Offender,
+		}
 
-impl<Offender: Clone, N> Offence<Offender> for EquivocationOffence<Offender, N>
-where
-	N: Copy + Clone + PartialOrd + Ord + Eq + PartialEq + Encode + Decode,
-{
-	const ID: Kind = *b"beefy:equivocati";
-	type TimeSlot = TimeSlot<N>;
+		impl<Offender: Clone, N> Offence<Offender> for $name<Offender, N>
+		where
+			N: Copy + Clone + PartialOrd + Ord + Eq + PartialEq + Encode + Decode,
+		{
+			const ID: Kind = *$id;
+			type TimeSlot = TimeSlot<N>;
 
-	fn offenders(&self) -> Vec<Offender> {
-		vec![self.offender.clone()]
-	}
+			fn offenders(&self) -> Vec<Offender> {
+				vec![self.offender.clone()]
+			}
 
-	fn session_index(&self) -> SessionIndex {
-		self.session_index
-	}
+			fn session_index(&self) -> SessionIndex {
+				self.session_index
+			}
 
-	fn validator_set_count(&self) -> u32 {
-		self.validator_set_count
-	}
+			fn validator_set_count(&self) -> u32 {
+				self.validator_set_count
+			}
 
-	fn time_slot(&self) -> Self::TimeSlot {
-		self.time_slot
-	}
+			fn time_slot(&self) -> Self::TimeSlot {
+				self.time_slot
+			}
 
-	// The formula is min((3k / n)^2, 1)
-	// where k = offenders_number and n = validators_number
-	fn slash_fraction(&self, offenders_count: u32) -> Perbill {
-		// Perbill type domain is [0, 1] by definition
-		Perbill::from_rational(3 * offenders_count, self.validator_set_count).square()
-	}
+			// The formula is min((3k / n)^2, 1)
+			// where k = offenders_number and n = validators_number
+			fn slash_fraction(&self, offenders_count: u32) -> Perbill {
+				// Perbill type domain is [0, 1] by definition
+				Perbill::from_rational(3 * offenders_count, self.validator_set_count).square()
+			}
+		}
+	};
 }
 
+decl_beefy_offence!(
+	/// BEEFY equivocation offence report: an authority voted for different payloads in the same
+	/// round.
+	EquivocationOffence,
+	b"beefy:equivocati"
+);
+
+decl_beefy_offence!(
+	/// BEEFY fork voting offence report: an authority voted for a block that is not part of the
+	/// canonical chain.
+	ForkVotingOffence,
+	b"beefy:forkvoting"
+);
+
+decl_beefy_offence!(
+	/// BEEFY future block voting offence report: an authority voted for a block that has not
+	/// been produced yet.
+	FutureBlockVotingOffence,
+	b"beefy:futurevote"
+);
+
 /// BEEFY equivocation offence report system.
 ///
 /// This type implements `OffenceReportSystem` such that:
@@ -122,24 +150,164 @@ where
 pub struct EquivocationReportSystem<T, R, P, L>(sp_std::marker::PhantomData<(T, R, P, L)>);
 
 /// Equivocation evidence convenience alias.
-pub type EquivocationEvidenceFor<T> = (
-	EquivocationProof<
-		BlockNumberFor<T>,
-		<T as Config>::BeefyId,
-		<<T as Config>::BeefyId as RuntimeAppPublic>::Signature,
-	>,
-	<T as Config>::KeyOwnerProof,
-);
+pub enum EquivocationEvidenceFor<T: Config> {
+	/// Proof that an authority voted for different payloads in the same round.
+	DoubleVotingProof(
+		EquivocationProof<
+			BlockNumberFor<T>,
+			T::BeefyId,
+			<T::BeefyId as RuntimeAppPublic>::Signature,
+		>,
+		T::KeyOwnerProof,
+	),
+	/// Proof that an authority voted for a block that is not part of the canonical chain.
+	ForkVotingProof(
+		ForkVotingProof<
+			HeaderFor<T>,
+			T::BeefyId,
+			<T::AncestryHelper as AncestryHelper<HeaderFor<T>>>::Proof,
+		>,
+		T::KeyOwnerProof,
+	),
+	/// Proof that an authority voted for a block that has not been produced yet.
+	FutureBlockVotingProof(FutureBlockVotingProof<BlockNumberFor<T>, T::BeefyId>, T::KeyOwnerProof),
+}
+
+impl<T: Config> EquivocationEvidenceFor<T> {
+	/// Returns the authority id of the equivocator.
+	fn offender_id(&self) -> &T::BeefyId {
+		match self {
+			EquivocationEvidenceFor::DoubleVotingProof(equivocation_proof, _) =>
+				equivocation_proof.offender_id(),
+			EquivocationEvidenceFor::ForkVotingProof(equivocation_proof, _) =>
+				&equivocation_proof.vote.id,
+			EquivocationEvidenceFor::FutureBlockVotingProof(equivocation_proof, _) =>
+				&equivocation_proof.vote.id,
+		}
+	}
+
+	/// Returns the round number at which the equivocation occurred.
+	fn round_number(&self) -> &BlockNumberFor<T> {
+		match self {
+			EquivocationEvidenceFor::DoubleVotingProof(equivocation_proof, _) =>
+				equivocation_proof.round_number(),
+			EquivocationEvidenceFor::ForkVotingProof(equivocation_proof, _) =>
+				&equivocation_proof.vote.commitment.block_number,
+			EquivocationEvidenceFor::FutureBlockVotingProof(equivocation_proof, _) =>
+				&equivocation_proof.vote.commitment.block_number,
+		}
+	}
+
+	/// Returns the set id at which the equivocation occurred.
+	fn set_id(&self) -> ValidatorSetId {
+		match self {
+			EquivocationEvidenceFor::DoubleVotingProof(equivocation_proof, _) =>
+				equivocation_proof.set_id(),
+			EquivocationEvidenceFor::ForkVotingProof(equivocation_proof, _) =>
+				equivocation_proof.vote.commitment.validator_set_id,
+			EquivocationEvidenceFor::FutureBlockVotingProof(equivocation_proof, _) =>
+				equivocation_proof.vote.commitment.validator_set_id,
+		}
+	}
+
+	/// Returns the key ownership proof attached to the equivocation report.
+	fn key_owner_proof(&self) -> &T::KeyOwnerProof {
+		match self {
+			EquivocationEvidenceFor::DoubleVotingProof(_, key_owner_proof) => key_owner_proof,
+			EquivocationEvidenceFor::ForkVotingProof(_, key_owner_proof) => key_owner_proof,
+			EquivocationEvidenceFor::FutureBlockVotingProof(_, key_owner_proof) => key_owner_proof,
+		}
+	}
+
+	/// Returns the tag prefix used when validating unsigned reports of this kind.
+	fn tag_prefix(&self) -> &'static str {
+		match self {
+			EquivocationEvidenceFor::DoubleVotingProof(..) => "BeefyEquivocation",
+			EquivocationEvidenceFor::ForkVotingProof(..) => "BeefyForkVoting",
+			EquivocationEvidenceFor::FutureBlockVotingProof(..) => "BeefyFutureBlockVoting",
+		}
+	}
+
+	/// Validates the equivocation proof, making sure that the signature of the equivocated
+	/// vote(s) is valid and that the reported misbehavior actually happened.
+	fn check_equivocation_proof(&self) -> Result<(), Error<T>> {
+		match self {
+			EquivocationEvidenceFor::DoubleVotingProof(equivocation_proof, _) => {
+				// Validate equivocation proof (check votes are different and signatures are
+				// valid).
+				if !sp_consensus_beefy::check_equivocation_proof(equivocation_proof) {
+					return Err(Error::<T>::InvalidEquivocationProof)
+				}
+
+				Ok(())
+			},
+			EquivocationEvidenceFor::ForkVotingProof(equivocation_proof, _) => {
+				let ForkVotingProof { vote, ancestry_proof, header } = equivocation_proof;
+
+				// Check that the header is part of the canonical chain and extract the info
+				// needed for validating the ancestry proof.
+				let validation_context =
+					<T::AncestryHelper as AncestryHelper<HeaderFor<T>>>::extract_validation_context(
+						header.clone(),
+					)
+					.ok_or(Error::<T>::InvalidForkVotingProof)?;
+
+				// Check that the commitment is pointing to a block that is not part of the
+				// canonical chain.
+				let is_non_canonical =
+					<T::AncestryHelper as AncestryHelper<HeaderFor<T>>>::is_non_canonical(
+						&vote.commitment,
+						ancestry_proof.clone(),
+						validation_context,
+					);
+				if !is_non_canonical {
+					return Err(Error::<T>::InvalidForkVotingProof)
+				}
+
+				if !check_commitment_signature(&vote.commitment, &vote.id, &vote.signature) {
+					return Err(Error::<T>::InvalidForkVotingProof)
+				}
+
+				Ok(())
+			},
+			EquivocationEvidenceFor::FutureBlockVotingProof(equivocation_proof, _) => {
+				let FutureBlockVotingProof { vote } = equivocation_proof;
+
+				// Check that the commitment is pointing to a block that has not been produced
+				// yet.
+				let is_future_block =
+					vote.commitment.block_number >= frame_system::Pallet::<T>::block_number();
+				if !is_future_block {
+					return Err(Error::<T>::InvalidFutureBlockVotingProof)
+				}
+
+				if !check_commitment_signature(&vote.commitment, &vote.id, &vote.signature) {
+					return Err(Error::<T>::InvalidFutureBlockVotingProof)
+				}
+
+				Ok(())
+			},
+		}
+	}
+}
 
 impl<T, R, P, L> OffenceReportSystem<Option<T::AccountId>, EquivocationEvidenceFor<T>>
 	for EquivocationReportSystem<T, R, P, L>
 where
 	T: Config + pallet_authorship::Config + frame_system::offchain::SendTransactionTypes<Call<T>>,
 	R: ReportOffence<
-		T::AccountId,
-		P::IdentificationTuple,
-		EquivocationOffence<P::IdentificationTuple, BlockNumberFor<T>>,
-	>,
+			T::AccountId,
+			P::IdentificationTuple,
+			EquivocationOffence<P::IdentificationTuple, BlockNumberFor<T>>,
+		> + ReportOffence<
+			T::AccountId,
+			P::IdentificationTuple,
+			ForkVotingOffence<P::IdentificationTuple, BlockNumberFor<T>>,
+		> + ReportOffence<
+			T::AccountId,
+			P::IdentificationTuple,
+			FutureBlockVotingOffence<P::IdentificationTuple, BlockNumberFor<T>>,
+		>,
 	P: KeyOwnerProofSystem<(KeyTypeId, T::BeefyId), Proof = T::KeyOwnerProof>,
 	P::IdentificationTuple: Clone,
 	L: Get<u64>,
@@ -148,11 +316,25 @@ where
 
 	fn publish_evidence(evidence: EquivocationEvidenceFor<T>) -> Result<(), ()> {
 		use frame_system::offchain::SubmitTransaction;
-		let (equivocation_proof, key_owner_proof) = evidence;
 
-		let call = Call::report_equivocation_unsigned {
-			equivocation_proof: Box::new(equivocation_proof),
-			key_owner_proof,
+		let call = match evidence {
+			EquivocationEvidenceFor::DoubleVotingProof(equivocation_proof, key_owner_proof) =>
+				Call::report_equivocation_unsigned {
+					equivocation_proof: Box::new(equivocation_proof),
+					key_owner_proof,
+				},
+			EquivocationEvidenceFor::ForkVotingProof(equivocation_proof, key_owner_proof) =>
+				Call::report_fork_voting_unsigned {
+					equivocation_proof: Box::new(equivocation_proof),
+					key_owner_proof,
+				},
+			EquivocationEvidenceFor::FutureBlockVotingProof(
+				equivocation_proof,
+				key_owner_proof,
+			) => Call::report_future_block_voting_unsigned {
+				equivocation_proof: Box::new(equivocation_proof),
+				key_owner_proof,
+			},
 		};
 
 		let res = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into());
@@ -166,19 +348,35 @@ where
 	fn check_evidence(
 		evidence: EquivocationEvidenceFor<T>,
 	) -> Result<(), TransactionValidityError> {
-		let (equivocation_proof, key_owner_proof) = evidence;
-
 		// Check the membership proof to extract the offender's id
-		let key = (BEEFY_KEY_TYPE, equivocation_proof.offender_id().clone());
-		let offender = P::check_proof(key, key_owner_proof).ok_or(InvalidTransaction::BadProof)?;
+		let key = (BEEFY_KEY_TYPE, evidence.offender_id().clone());
+		let offender = P::check_proof(key, evidence.key_owner_proof().clone())
+			.ok_or(InvalidTransaction::BadProof)?;
 
 		// Check if the offence has already been reported, and if so then we can discard the report.
-		let time_slot = TimeSlot {
-			set_id: equivocation_proof.set_id(),
-			round: *equivocation_proof.round_number(),
+		let time_slot = TimeSlot { set_id: evidence.set_id(), round: *evidence.round_number() };
+		let is_known_offence = match evidence {
+			EquivocationEvidenceFor::DoubleVotingProof(..) =>
+				<R as ReportOffence<
+					T::AccountId,
+					P::IdentificationTuple,
+					EquivocationOffence<P::IdentificationTuple, BlockNumberFor<T>>,
+				>>::is_known_offence(&[offender], &time_slot),
+			EquivocationEvidenceFor::ForkVotingProof(..) =>
+				<R as ReportOffence<
+					T::AccountId,
+					P::IdentificationTuple,
+					ForkVotingOffence<P::IdentificationTuple, BlockNumberFor<T>>,
+				>>::is_known_offence(&[offender], &time_slot),
+			EquivocationEvidenceFor::FutureBlockVotingProof(..) =>
+				<R as ReportOffence<
+					T::AccountId,
+					P::IdentificationTuple,
+					FutureBlockVotingOffence<P::IdentificationTuple, BlockNumberFor<T>>,
+				>>::is_known_offence(&[offender], &time_slot),
 		};
 
-		if R::is_known_offence(&[offender], &time_slot) {
+		if is_known_offence {
 			Err(InvalidTransaction::Stale.into())
 		} else {
 			Ok(())
@@ -189,16 +387,16 @@ where
 		reporter: Option<T::AccountId>,
 		evidence: EquivocationEvidenceFor<T>,
 	) -> Result<(), DispatchError> {
-		let (equivocation_proof, key_owner_proof) = evidence;
 		let reporter = reporter.or_else(|| <pallet_authorship::Pallet<T>>::author());
-		let offender = equivocation_proof.offender_id().clone();
+		let offender = evidence.offender_id().clone();
 
 		// We check the equivocation within the context of its set id (and
 		// associated session) and round. We also need to know the validator
 		// set count at the time of the offence since it is required to calculate
 		// the slash amount.
-		let set_id = equivocation_proof.set_id();
-		let round = *equivocation_proof.round_number();
+		let set_id = evidence.set_id();
+		let round = *evidence.round_number();
+		let key_owner_proof = evidence.key_owner_proof().clone();
 		let session_index = key_owner_proof.session();
 		let validator_set_count = key_owner_proof.validator_count();
 
@@ -206,10 +404,8 @@ where
 		let offender = P::check_proof((BEEFY_KEY_TYPE, offender), key_owner_proof)
 			.ok_or(Error::<T>::InvalidKeyOwnershipProof)?;
 
-		// Validate equivocation proof (check votes are different and signatures are valid).
-		if !sp_consensus_beefy::check_equivocation_proof(&equivocation_proof) {
-			return Err(Error::<T>::InvalidEquivocationProof.into())
-		}
+		// Validate the equivocation proof.
+		evidence.check_equivocation_proof()?;
 
 		// Check that the session id for the membership proof is within the
 		// bounds of the set id reported in the equivocation.
@@ -219,69 +415,100 @@ where
 			return Err(Error::<T>::InvalidEquivocationProof.into())
 		}
 
-		let offence = EquivocationOffence {
-			time_slot: TimeSlot { set_id, round },
-			session_index,
-			validator_set_count,
-			offender,
-		};
-
-		R::report_offence(reporter.into_iter().collect(), offence)
-			.map_err(|_| Error::<T>::DuplicateOffenceReport)?;
+		let time_slot = TimeSlot { set_id, round };
+		let reporters = reporter.into_iter().collect();
+		match evidence {
+			EquivocationEvidenceFor::DoubleVotingProof(..) => R::report_offence(
+				reporters,
+				EquivocationOffence { time_slot, session_index, validator_set_count, offender },
+			),
+			EquivocationEvidenceFor::ForkVotingProof(..) => R::report_offence(
+				reporters,
+				ForkVotingOffence { time_slot, session_index, validator_set_count, offender },
+			),
+			EquivocationEvidenceFor::FutureBlockVotingProof(..) => R::report_offence(
+				reporters,
+				FutureBlockVotingOffence {
+					time_slot,
+					session_index,
+					validator_set_count,
+					offender,
+				},
+			),
+		}
+		.map_err(|_| Error::<T>::DuplicateOffenceReport)?;
 
 		Ok(())
 	}
 }
 
 /// Methods for the `ValidateUnsigned` implementation:
-/// It restricts calls to `report_equivocation_unsigned` to local calls (i.e. extrinsics generated
-/// on this node) or that already in a block. This guarantees that only block authors can include
-/// unsigned equivocation reports.
+/// It restricts calls to `report_equivocation_unsigned`, `report_fork_voting_unsigned` and
+/// `report_future_block_voting_unsigned` to local calls (i.e. extrinsics generated on this node)
+/// or that already in a block. This guarantees that only block authors can include unsigned
+/// equivocation reports.
 impl<T: Config> Pallet<T> {
+	fn equivocation_evidence_for(call: &Call<T>) -> Option<EquivocationEvidenceFor<T>> {
+		match call {
+			Call::report_equivocation_unsigned { equivocation_proof, key_owner_proof } =>
+				Some(EquivocationEvidenceFor::DoubleVotingProof(
+					*equivocation_proof.clone(),
+					key_owner_proof.clone(),
+				)),
+			Call::report_fork_voting_unsigned { equivocation_proof, key_owner_proof } =>
+				Some(EquivocationEvidenceFor::ForkVotingProof(
+					*equivocation_proof.clone(),
+					key_owner_proof.clone(),
+				)),
+			Call::report_future_block_voting_unsigned { equivocation_proof, key_owner_proof } =>
+				Some(EquivocationEvidenceFor::FutureBlockVotingProof(
+					*equivocation_proof.clone(),
+					key_owner_proof.clone(),
+				)),
+			_ => None,
+		}
+	}
+
 	pub fn validate_unsigned(source: TransactionSource, call: &Call<T>) -> TransactionValidity {
-		if let Call::report_equivocation_unsigned { equivocation_proof, key_owner_proof } = call {
-			// discard equivocation report not coming from the local node
-			match source {
-				TransactionSource::Local | TransactionSource::InBlock => { /* allowed */ },
-				_ => {
-					log::warn!(
-						target: LOG_TARGET,
-						"rejecting unsigned report equivocation transaction because it is not local/in-block."
-					);
-					return InvalidTransaction::Call.into()
-				},
-			}
+		let Some(evidence) = Self::equivocation_evidence_for(call) else {
+			return InvalidTransaction::Call.into()
+		};
 
-			let evidence = (*equivocation_proof.clone(), key_owner_proof.clone());
-			T::EquivocationReportSystem::check_evidence(evidence)?;
-
-			let longevity =
-				<T::EquivocationReportSystem as OffenceReportSystem<_, _>>::Longevity::get();
-
-			ValidTransaction::with_tag_prefix("BeefyEquivocation")
-				// We assign the maximum priority for any equivocation report.
-				.priority(TransactionPriority::MAX)
-				// Only one equivocation report for the same offender at the same slot.
-				.and_provides((
-					equivocation_proof.offender_id().clone(),
-					equivocation_proof.set_id(),
-					*equivocation_proof.round_number(),
-				))
-				.longevity(longevity)
-				// We don't propagate this. This can never be included on a remote node.
-				.propagate(false)
-				.build()
-		} else {
-			InvalidTransaction::Call.into()
+		// discard equivocation report not coming from the local node
+		match source {
+			TransactionSource::Local | TransactionSource::InBlock => { /* allowed */ },
+			_ => {
+				log::warn!(
+					target: LOG_TARGET,
+					"rejecting unsigned report equivocation transaction because it is not local/in-block."
+				);
+				return InvalidTransaction::Call.into()
+			},
 		}
+
+		let tag_prefix = evidence.tag_prefix();
+		let tag = (evidence.offender_id().clone(), evidence.set_id(), *evidence.round_number());
+		T::EquivocationReportSystem::check_evidence(evidence)?;
+
+		let longevity =
+			<T::EquivocationReportSystem as OffenceReportSystem<_, _>>::Longevity::get();
+
+		ValidTransaction::with_tag_prefix(tag_prefix)
+			// We assign the maximum priority for any equivocation report.
+			.priority(TransactionPriority::MAX)
+			// Only one equivocation report for the same offender at the same slot.
+			.and_provides(tag)
+			.longevity(longevity)
+			// We don't propagate this. This can never be included on a remote node.
+			.propagate(false)
+			.build()
 	}
 
 	pub fn pre_dispatch(call: &Call<T>) -> Result<(), TransactionValidityError> {
-		if let Call::report_equivocation_unsigned { equivocation_proof, key_owner_proof } = call {
-			let evidence = (*equivocation_proof.clone(), key_owner_proof.clone());
-			T::EquivocationReportSystem::check_evidence(evidence)
-		} else {
-			Err(InvalidTransaction::Call.into())
-		}
+		let Some(evidence) = Self::equivocation_evidence_for(call) else {
+			return Err(InvalidTransaction::Call.into())
+		};
+
+		T::EquivocationReportSystem::check_evidence(evidence)
 	}
 }
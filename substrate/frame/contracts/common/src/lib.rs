@@ -0,0 +1,31 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Definitions shared by `pallet-contracts` and `pallet-revive`.
+//!
+//! Both pallets run contracts on different virtual machines but share the same economic model.
+//! This crate hosts the parts that are independent of the virtual machine:
+//!
+//! - The [`primitives`] which are exposed to clients through the runtime APIs of both pallets.
+//! - The [`storage_meter`] which meters and charges the storage deposit of a call stack.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod primitives;
+pub mod storage_meter;
+
+pub use primitives::*;
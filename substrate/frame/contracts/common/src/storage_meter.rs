@@ -0,0 +1,931 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains functions to meter the storage deposit.
+//!
+//! The pallets connect the metering to their storage and currency by implementing [`Config`] and
+//! [`Ext`].
+
+use crate::StorageDeposit as Deposit;
+use frame_support::{
+	CloneNoBound, DefaultNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, Saturating, Zero},
+	DispatchError, FixedPointNumber, FixedPointOperand, FixedU128,
+};
+use sp_std::{fmt::Debug, marker::PhantomData, vec::Vec};
+
+/// Deposit that uses the balance type of the [`Config`].
+pub type DepositOf<C> = Deposit<<C as Config>::Balance>;
+
+/// The storage consumption of a contract and the deposit that was paid for it.
+///
+/// It is borrowed from the contract info of a pallet in order to apply a [`Diff`].
+pub struct StorageUsage<'a, Balance> {
+	/// How many bytes of storage are accumulated in this contract's child trie.
+	pub bytes: &'a mut u32,
+	/// How many items of storage are accumulated in this contract's child trie.
+	pub items: &'a mut u32,
+	/// This records to how much deposit the accumulated `bytes` amount to.
+	pub byte_deposit: &'a mut Balance,
+	/// This records to how much deposit the accumulated `items` amount to.
+	pub item_deposit: &'a mut Balance,
+}
+
+/// The pallet specific types and functions the storage meter relies on.
+pub trait Config {
+	/// The account id of the origin and the contracts.
+	type AccountId: Clone + Eq + Debug;
+	/// The balance in which the storage deposit is paid.
+	type Balance: AtLeast32BitUnsigned + FixedPointOperand + Copy + Default + Debug;
+	/// The origin of a call stack.
+	type Origin;
+	/// The information a pallet stores about a contract.
+	type ContractInfo;
+	/// The information a pallet stores about uploaded code.
+	type CodeInfo;
+
+	/// Returns the account of a signed `origin` or `None` if it is the root origin.
+	fn origin_account(origin: &Self::Origin) -> Option<&Self::AccountId>;
+
+	/// The deposit limit which is used for calls by the root origin.
+	fn default_deposit_limit() -> Self::Balance;
+
+	/// The amount of balance a caller has to pay for each byte of storage.
+	fn deposit_per_byte() -> Self::Balance;
+
+	/// The amount of balance a caller has to pay for each storage item.
+	fn deposit_per_item() -> Self::Balance;
+
+	/// The existential deposit of the native currency.
+	fn min_balance() -> Self::Balance;
+
+	/// The error that is returned when the deposit exceeds the limit of the meter.
+	fn deposit_limit_exhausted() -> DispatchError;
+
+	/// Borrows the storage consumption and the deposit of a contract.
+	fn storage_usage(info: &mut Self::ContractInfo) -> StorageUsage<'_, Self::Balance>;
+
+	/// The deposit of a contract that is refunded when the contract is terminated.
+	fn total_deposit(info: &Self::ContractInfo) -> Self::Balance;
+
+	/// Sets and returns the base deposit of a contract that is about to be instantiated.
+	///
+	/// The base deposit includes the existential deposit of the contract's account.
+	fn update_base_deposit(
+		info: &mut Self::ContractInfo,
+		code_info: &Self::CodeInfo,
+	) -> Self::Balance;
+
+	/// Brings the account of a new `contract` into existence.
+	///
+	/// The existential deposit is transferred from `origin` and the account must not be reaped as
+	/// long as the contract exists.
+	fn create_contract_account(
+		origin: &Self::AccountId,
+		contract: &Self::AccountId,
+	) -> Result<(), DispatchError>;
+}
+
+/// A trait that allows to decouple the metering from the charging of balance.
+///
+/// This mostly exists for testing so that the charging can be mocked.
+pub trait Ext<C: Config> {
+	/// This checks whether `origin` is able to afford the storage deposit limit.
+	///
+	/// It is necessary to do this check beforehand so that the charge won't fail later on.
+	///
+	/// `origin`: The origin of the call stack from which is responsible for putting down a deposit.
+	/// `limit`: The limit with which the meter was constructed.
+	/// `min_leftover`: How much `free_balance` in addition to the existential deposit (ed) should
+	/// be left inside the `origin` account.
+	///
+	/// Returns the limit that should be used by the meter. If origin can't afford the `limit`
+	/// it returns `Err`.
+	fn check_limit(
+		origin: &C::AccountId,
+		limit: Option<C::Balance>,
+		min_leftover: C::Balance,
+	) -> Result<C::Balance, DispatchError>;
+	/// This is called to inform the implementer that some balance should be charged due to
+	/// some interaction of the `origin` with a `contract`.
+	///
+	/// The balance transfer can either flow from `origin` to `contract` or the other way
+	/// around depending on whether `amount` constitutes a `Charge` or a `Refund`.
+	/// It should be used in combination with `check_limit` to check that no more balance than this
+	/// limit is ever charged.
+	fn charge(
+		origin: &C::AccountId,
+		contract: &C::AccountId,
+		amount: &DepositOf<C>,
+		state: &ContractState<C>,
+	) -> Result<(), DispatchError>;
+}
+
+/// Used to implement a type state pattern for the meter.
+///
+/// It is sealed and cannot be implemented outside of this module.
+pub trait State: private::Sealed {}
+
+/// State parameter that constitutes a meter that is in its root state.
+#[derive(Default, Debug)]
+pub struct Root;
+
+/// State parameter that constitutes a meter that is in its nested state.
+/// Its value indicates whether the nested meter has its own limit.
+#[derive(DefaultNoBound, RuntimeDebugNoBound)]
+pub enum Nested {
+	#[default]
+	DerivedLimit,
+	OwnLimit,
+}
+
+impl State for Root {}
+impl State for Nested {}
+
+/// A type that allows the metering of consumed or freed storage of a single contract call stack.
+#[derive(DefaultNoBound, RuntimeDebugNoBound)]
+pub struct RawMeter<C: Config, E, S: State + Default + Debug> {
+	/// The limit of how much balance this meter is allowed to consume.
+	limit: C::Balance,
+	/// The amount of balance that was used in this meter and all of its already absorbed children.
+	total_deposit: DepositOf<C>,
+	/// The amount of storage changes that were recorded in this meter alone.
+	own_contribution: Contribution<C>,
+	/// List of charges that should be applied at the end of a contract stack execution.
+	///
+	/// We only have one charge per contract hence the size of this vector is
+	/// limited by the maximum call depth.
+	charges: Vec<Charge<C>>,
+	/// We store the nested state to determine if it has a special limit for sub-call.
+	nested: S,
+	/// Type parameter only used in impls.
+	_phantom: PhantomData<E>,
+}
+
+/// This type is used to describe a storage change when charging from the meter.
+#[derive(Default, RuntimeDebugNoBound)]
+pub struct Diff {
+	/// How many bytes were added to storage.
+	pub bytes_added: u32,
+	/// How many bytes were removed from storage.
+	pub bytes_removed: u32,
+	/// How many storage items were added to storage.
+	pub items_added: u32,
+	/// How many storage items were removed from storage.
+	pub items_removed: u32,
+}
+
+impl Diff {
+	/// Calculate how much of a charge or refund results from applying the diff and store it
+	/// in the passed `info` if any.
+	///
+	/// # Note
+	///
+	/// In case `None` is passed for `info` only charges are calculated. This is because refunds
+	/// are calculated pro rata of the existing storage within a contract and hence need extract
+	/// this information from the passed `info`.
+	pub fn update_contract<C: Config>(&self, info: Option<&mut C::ContractInfo>) -> DepositOf<C> {
+		let per_byte = C::deposit_per_byte();
+		let per_item = C::deposit_per_item();
+		let bytes_added = self.bytes_added.saturating_sub(self.bytes_removed);
+		let items_added = self.items_added.saturating_sub(self.items_removed);
+		let mut bytes_deposit = Deposit::Charge(per_byte.saturating_mul((bytes_added).into()));
+		let mut items_deposit = Deposit::Charge(per_item.saturating_mul((items_added).into()));
+
+		// Without any contract info we can only calculate diffs which add storage
+		let info = if let Some(info) = info {
+			C::storage_usage(info)
+		} else {
+			debug_assert_eq!(self.bytes_removed, 0);
+			debug_assert_eq!(self.items_removed, 0);
+			return bytes_deposit.saturating_add(&items_deposit)
+		};
+
+		// Refunds are calculated pro rata based on the accumulated storage within the contract
+		let bytes_removed = self.bytes_removed.saturating_sub(self.bytes_added);
+		let items_removed = self.items_removed.saturating_sub(self.items_added);
+		let ratio = FixedU128::checked_from_rational(bytes_removed, *info.bytes)
+			.unwrap_or_default()
+			.min(FixedU128::from_u32(1));
+		bytes_deposit = bytes_deposit
+			.saturating_add(&Deposit::Refund(ratio.saturating_mul_int(*info.byte_deposit)));
+		let ratio = FixedU128::checked_from_rational(items_removed, *info.items)
+			.unwrap_or_default()
+			.min(FixedU128::from_u32(1));
+		items_deposit = items_deposit
+			.saturating_add(&Deposit::Refund(ratio.saturating_mul_int(*info.item_deposit)));
+
+		// We need to update the contract info structure with the new deposits
+		*info.bytes = info.bytes.saturating_add(bytes_added).saturating_sub(bytes_removed);
+		*info.items = info.items.saturating_add(items_added).saturating_sub(items_removed);
+		match &bytes_deposit {
+			Deposit::Charge(amount) =>
+				*info.byte_deposit = info.byte_deposit.saturating_add(*amount),
+			Deposit::Refund(amount) =>
+				*info.byte_deposit = info.byte_deposit.saturating_sub(*amount),
+		}
+		match &items_deposit {
+			Deposit::Charge(amount) =>
+				*info.item_deposit = info.item_deposit.saturating_add(*amount),
+			Deposit::Refund(amount) =>
+				*info.item_deposit = info.item_deposit.saturating_sub(*amount),
+		}
+
+		bytes_deposit.saturating_add(&items_deposit)
+	}
+}
+
+impl Diff {
+	fn saturating_add(&self, rhs: &Self) -> Self {
+		Self {
+			bytes_added: self.bytes_added.saturating_add(rhs.bytes_added),
+			bytes_removed: self.bytes_removed.saturating_add(rhs.bytes_removed),
+			items_added: self.items_added.saturating_add(rhs.items_added),
+			items_removed: self.items_removed.saturating_add(rhs.items_removed),
+		}
+	}
+}
+
+/// The state of a contract.
+///
+/// In case of termination the beneficiary is indicated.
+#[derive(RuntimeDebugNoBound, CloneNoBound, PartialEqNoBound, EqNoBound)]
+pub enum ContractState<C: Config> {
+	Alive,
+	Terminated { beneficiary: C::AccountId },
+}
+
+/// Records information to charge or refund a plain account.
+///
+/// All the charges are deferred to the end of a whole call stack. Reason is that by doing
+/// this we can do all the refunds before doing any charge. This way a plain account can use
+/// more deposit than it has balance as along as it is covered by a refund. This
+/// essentially makes the order of storage changes irrelevant with regard to the deposit system.
+/// The only exception is when a special (tougher) deposit limit is specified for a cross-contract
+/// call. In that case the limit is enforced once the call is returned, rolling it back if
+/// exhausted.
+#[derive(RuntimeDebugNoBound, CloneNoBound)]
+struct Charge<C: Config> {
+	contract: C::AccountId,
+	amount: DepositOf<C>,
+	state: ContractState<C>,
+}
+
+/// Records the storage changes of a storage meter.
+#[derive(RuntimeDebugNoBound)]
+enum Contribution<C: Config> {
+	/// The contract the meter belongs to is alive and accumulates changes using a [`Diff`].
+	Alive(Diff),
+	/// The meter was checked against its limit using [`RawMeter::enforce_limit`] at the end of
+	/// its execution. In this process the [`Diff`] was converted into a [`Deposit`].
+	Checked(DepositOf<C>),
+	/// The contract was terminated. In this process the [`Diff`] was converted into a [`Deposit`]
+	/// in order to calculate the refund. Upon termination the `reducible_balance` in the
+	/// contract's account is transferred to the [`beneficiary`].
+	Terminated { deposit: DepositOf<C>, beneficiary: C::AccountId },
+}
+
+impl<C: Config> Contribution<C> {
+	/// See [`Diff::update_contract`].
+	fn update_contract(&self, info: Option<&mut C::ContractInfo>) -> DepositOf<C> {
+		match self {
+			Self::Alive(diff) => diff.update_contract::<C>(info),
+			Self::Terminated { deposit, beneficiary: _ } | Self::Checked(deposit) =>
+				deposit.clone(),
+		}
+	}
+}
+
+impl<C: Config> Default for Contribution<C> {
+	fn default() -> Self {
+		Self::Alive(Default::default())
+	}
+}
+
+/// Functions that apply to all states.
+impl<C, E, S> RawMeter<C, E, S>
+where
+	C: Config,
+	E: Ext<C>,
+	S: State + Default + Debug,
+{
+	/// Create a new child that has its `limit`.
+	/// Passing `0` as the limit is interpreted as to take whatever is remaining from its parent.
+	///
+	/// This is called whenever a new subcall is initiated in order to track the storage
+	/// usage for this sub call separately. This is necessary because we want to exchange balance
+	/// with the current contract we are interacting with.
+	pub fn nested(&self, limit: C::Balance) -> RawMeter<C, E, Nested> {
+		debug_assert!(matches!(self.contract_state(), ContractState::Alive));
+		// If a special limit is specified higher than it is available,
+		// we want to enforce the lesser limit to the nested meter, to fail in the sub-call.
+		let limit = self.available().min(limit);
+		if limit.is_zero() {
+			RawMeter { limit: self.available(), ..Default::default() }
+		} else {
+			RawMeter { limit, nested: Nested::OwnLimit, ..Default::default() }
+		}
+	}
+
+	/// Absorb a child that was spawned to handle a sub call.
+	///
+	/// This should be called whenever a sub call comes to its end and it is **not** reverted.
+	/// This does the actual balance transfer from/to `origin` and `contract` based on the
+	/// overall storage consumption of the call. It also updates the supplied contract info.
+	///
+	/// In case a contract reverted the child meter should just be dropped in order to revert
+	/// any changes it recorded.
+	///
+	/// # Parameters
+	///
+	/// - `absorbed`: The child storage meter that should be absorbed.
+	/// - `origin`: The origin that spawned the original root meter.
+	/// - `contract`: The contract's account that this sub call belongs to.
+	/// - `info`: The info of the contract in question. `None` if the contract was terminated.
+	pub fn absorb(
+		&mut self,
+		absorbed: RawMeter<C, E, Nested>,
+		contract: &C::AccountId,
+		info: Option<&mut C::ContractInfo>,
+	) {
+		let own_deposit = absorbed.own_contribution.update_contract(info);
+		self.total_deposit = self
+			.total_deposit
+			.saturating_add(&absorbed.total_deposit)
+			.saturating_add(&own_deposit);
+		self.charges.extend_from_slice(&absorbed.charges);
+		if !own_deposit.is_zero() {
+			self.charges.push(Charge {
+				contract: contract.clone(),
+				amount: own_deposit,
+				state: absorbed.contract_state(),
+			});
+		}
+	}
+
+	/// The amount of balance that is still available from the original `limit`.
+	fn available(&self) -> C::Balance {
+		self.total_deposit.available(&self.limit)
+	}
+
+	/// Returns the state of the currently executed contract.
+	fn contract_state(&self) -> ContractState<C> {
+		match &self.own_contribution {
+			Contribution::Terminated { deposit: _, beneficiary } =>
+				ContractState::Terminated { beneficiary: beneficiary.clone() },
+			_ => ContractState::Alive,
+		}
+	}
+}
+
+/// Functions that only apply to the root state.
+impl<C, E> RawMeter<C, E, Root>
+where
+	C: Config,
+	E: Ext<C>,
+{
+	/// Create new storage meter for the specified `origin` and `limit`.
+	///
+	/// This tries to [`Ext::check_limit`] on `origin` and fails if this is not possible.
+	pub fn new(
+		origin: &C::Origin,
+		limit: Option<C::Balance>,
+		min_leftover: C::Balance,
+	) -> Result<Self, DispatchError> {
+		// Check the limit only if the origin is not root.
+		match C::origin_account(origin) {
+			None => Ok(Self {
+				limit: limit.unwrap_or(C::default_deposit_limit()),
+				..Default::default()
+			}),
+			Some(o) => {
+				let limit = E::check_limit(o, limit, min_leftover)?;
+				Ok(Self { limit, ..Default::default() })
+			},
+		}
+	}
+
+	/// The total amount of deposit that should change hands as result of the execution
+	/// that this meter was passed into. This will also perform all the charges accumulated
+	/// in the whole contract stack.
+	///
+	/// This drops the root meter in order to make sure it is only called when the whole
+	/// execution did finish.
+	pub fn try_into_deposit(self, origin: &C::Origin) -> Result<DepositOf<C>, DispatchError> {
+		// Only refund or charge deposit if the origin is not root.
+		let Some(origin) = C::origin_account(origin) else {
+			return Ok(Deposit::Charge(Zero::zero()))
+		};
+		for charge in self.charges.iter().filter(|c| matches!(c.amount, Deposit::Refund(_))) {
+			E::charge(origin, &charge.contract, &charge.amount, &charge.state)?;
+		}
+		for charge in self.charges.iter().filter(|c| matches!(c.amount, Deposit::Charge(_))) {
+			E::charge(origin, &charge.contract, &charge.amount, &charge.state)?;
+		}
+		Ok(self.total_deposit)
+	}
+}
+
+/// Functions that only apply to the nested state.
+impl<C, E> RawMeter<C, E, Nested>
+where
+	C: Config,
+	E: Ext<C>,
+{
+	/// Charges `diff` from the meter.
+	pub fn charge(&mut self, diff: &Diff) {
+		match &mut self.own_contribution {
+			Contribution::Alive(own) => *own = own.saturating_add(diff),
+			_ => panic!("Charge is never called after termination; qed"),
+		};
+	}
+
+	/// Adds a deposit charge.
+	///
+	/// Use this method instead of [`Self::charge`] when the charge is not the result of a storage
+	/// change. This is the case when a `delegate_dependency` is added or removed, when the
+	/// `code_hash` is updated or when the base deposit of a contract is put down on instantiation.
+	/// [`Self::charge`] cannot be used here because we keep track of the deposit charge separately
+	/// from the storage charge.
+	pub fn charge_deposit(&mut self, contract: C::AccountId, amount: DepositOf<C>) {
+		self.total_deposit = self.total_deposit.saturating_add(&amount);
+		self.charges.push(Charge { contract, amount, state: ContractState::Alive });
+	}
+
+	/// Charges from `origin` a storage deposit for contract instantiation.
+	///
+	/// This immediately transfers the balance in order to create the account.
+	pub fn charge_instantiate(
+		&mut self,
+		origin: &C::AccountId,
+		contract: &C::AccountId,
+		contract_info: &mut C::ContractInfo,
+		code_info: &C::CodeInfo,
+	) -> Result<DepositOf<C>, DispatchError> {
+		debug_assert!(matches!(self.contract_state(), ContractState::Alive));
+		let ed = C::min_balance();
+
+		let deposit = C::update_base_deposit(contract_info, code_info);
+		if deposit > self.limit {
+			return Err(C::deposit_limit_exhausted())
+		}
+
+		let deposit = Deposit::Charge(deposit);
+
+		// We do not increase `own_contribution` because this will be charged later when the
+		// contract execution does conclude and hence would lead to a double charge.
+		self.total_deposit = Deposit::Charge(ed);
+
+		// We need to make sure that the contract's account exists.
+		C::create_contract_account(origin, contract)?;
+
+		self.charge_deposit(contract.clone(), deposit.saturating_sub(&Deposit::Charge(ed)));
+
+		Ok(deposit)
+	}
+
+	/// Call to tell the meter that the currently executing contract was terminated.
+	///
+	/// This will manipulate the meter so that all storage deposit accumulated in
+	/// `contract_info` will be refunded to the `origin` of the meter. And the free
+	/// (`reducible_balance`) will be sent to the `beneficiary`.
+	pub fn terminate(&mut self, info: &C::ContractInfo, beneficiary: C::AccountId) {
+		debug_assert!(matches!(self.contract_state(), ContractState::Alive));
+		self.own_contribution = Contribution::Terminated {
+			deposit: Deposit::Refund(C::total_deposit(info)),
+			beneficiary,
+		};
+	}
+
+	/// [`Self::charge`] does not enforce the storage limit since we want to do this check as late
+	/// as possible to allow later refunds to offset earlier charges.
+	///
+	/// # Note
+	///
+	/// We normally need to call this **once** for every call stack and not for every cross contract
+	/// call. However, if a dedicated limit is specified for a sub-call, this needs to be called
+	/// once the sub-call has returned. For this, the [`Self::enforce_subcall_limit`] wrapper is
+	/// used.
+	pub fn enforce_limit(
+		&mut self,
+		info: Option<&mut C::ContractInfo>,
+	) -> Result<(), DispatchError> {
+		let deposit = self.own_contribution.update_contract(info);
+		let total_deposit = self.total_deposit.saturating_add(&deposit);
+		// We don't want to override a `Terminated` with a `Checked`.
+		if matches!(self.contract_state(), ContractState::Alive) {
+			self.own_contribution = Contribution::Checked(deposit);
+		}
+		if let Deposit::Charge(amount) = total_deposit {
+			if amount > self.limit {
+				return Err(C::deposit_limit_exhausted())
+			}
+		}
+		Ok(())
+	}
+
+	/// This is a wrapper around [`Self::enforce_limit`] to use on the exit from a sub-call to
+	/// enforce its special limit if needed.
+	pub fn enforce_subcall_limit(
+		&mut self,
+		info: Option<&mut C::ContractInfo>,
+	) -> Result<(), DispatchError> {
+		match self.nested {
+			Nested::OwnLimit => self.enforce_limit(info),
+			Nested::DerivedLimit => Ok(()),
+		}
+	}
+}
+
+mod private {
+	pub trait Sealed {}
+	impl Sealed for super::Root {}
+	impl Sealed for super::Nested {}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::parameter_types;
+	use pretty_assertions::assert_eq;
+
+	const ALICE: u64 = 1;
+	const BOB: u64 = 2;
+	const CHARLIE: u64 = 3;
+
+	/// Prices storage like the test runtimes of the pallets do.
+	enum Test {}
+
+	enum Origin {
+		Root,
+		Signed(u64),
+	}
+
+	impl Origin {
+		fn from_account_id(account_id: u64) -> Self {
+			Self::Signed(account_id)
+		}
+	}
+
+	#[derive(Default)]
+	struct ContractInfo {
+		storage_bytes: u32,
+		storage_items: u32,
+		storage_byte_deposit: u64,
+		storage_item_deposit: u64,
+		storage_base_deposit: u64,
+	}
+
+	impl ContractInfo {
+		fn extra_deposit(&self) -> u64 {
+			self.storage_byte_deposit.saturating_add(self.storage_item_deposit)
+		}
+	}
+
+	impl Config for Test {
+		type AccountId = u64;
+		type Balance = u64;
+		type Origin = Origin;
+		type ContractInfo = ContractInfo;
+		type CodeInfo = ();
+
+		fn origin_account(origin: &Origin) -> Option<&u64> {
+			match origin {
+				Origin::Root => None,
+				Origin::Signed(account_id) => Some(account_id),
+			}
+		}
+
+		fn default_deposit_limit() -> u64 {
+			10_000_000
+		}
+
+		fn deposit_per_byte() -> u64 {
+			1
+		}
+
+		fn deposit_per_item() -> u64 {
+			2
+		}
+
+		fn min_balance() -> u64 {
+			1
+		}
+
+		fn deposit_limit_exhausted() -> DispatchError {
+			DispatchError::Other("StorageDepositLimitExhausted")
+		}
+
+		fn storage_usage(info: &mut ContractInfo) -> StorageUsage<'_, u64> {
+			StorageUsage {
+				bytes: &mut info.storage_bytes,
+				items: &mut info.storage_items,
+				byte_deposit: &mut info.storage_byte_deposit,
+				item_deposit: &mut info.storage_item_deposit,
+			}
+		}
+
+		fn total_deposit(info: &ContractInfo) -> u64 {
+			info.extra_deposit()
+				.saturating_add(info.storage_base_deposit)
+				.saturating_sub(Self::min_balance())
+		}
+
+		fn update_base_deposit(info: &mut ContractInfo, _code_info: &()) -> u64 {
+			info.storage_base_deposit = Self::min_balance();
+			info.storage_base_deposit
+		}
+
+		fn create_contract_account(_origin: &u64, _contract: &u64) -> Result<(), DispatchError> {
+			Ok(())
+		}
+	}
+
+	type TestMeter = RawMeter<Test, TestExt, Root>;
+
+	parameter_types! {
+		static TestExtTestValue: TestExt = Default::default();
+	}
+
+	#[derive(Debug, PartialEq, Eq, Clone)]
+	struct LimitCheck {
+		origin: u64,
+		limit: u64,
+		min_leftover: u64,
+	}
+
+	#[derive(Debug, PartialEq, Eq, Clone)]
+	struct Charge {
+		origin: u64,
+		contract: u64,
+		amount: DepositOf<Test>,
+		state: ContractState<Test>,
+	}
+
+	#[derive(Default, Debug, PartialEq, Eq, Clone)]
+	pub struct TestExt {
+		limit_checks: Vec<LimitCheck>,
+		charges: Vec<Charge>,
+	}
+
+	impl TestExt {
+		fn clear(&mut self) {
+			self.limit_checks.clear();
+			self.charges.clear();
+		}
+	}
+
+	impl Ext<Test> for TestExt {
+		fn check_limit(
+			origin: &u64,
+			limit: Option<u64>,
+			min_leftover: u64,
+		) -> Result<u64, DispatchError> {
+			let limit = limit.unwrap_or(42);
+			TestExtTestValue::mutate(|ext| {
+				ext.limit_checks.push(LimitCheck { origin: *origin, limit, min_leftover })
+			});
+			Ok(limit)
+		}
+
+		fn charge(
+			origin: &u64,
+			contract: &u64,
+			amount: &DepositOf<Test>,
+			state: &ContractState<Test>,
+		) -> Result<(), DispatchError> {
+			TestExtTestValue::mutate(|ext| {
+				ext.charges.push(Charge {
+					origin: *origin,
+					contract: *contract,
+					amount: amount.clone(),
+					state: state.clone(),
+				})
+			});
+			Ok(())
+		}
+	}
+
+	fn clear_ext() {
+		TestExtTestValue::mutate(|ext| ext.clear())
+	}
+
+	struct ChargingTestCase {
+		origin: Origin,
+		deposit: DepositOf<Test>,
+		expected: TestExt,
+	}
+
+	#[test]
+	fn new_reserves_balance_works() {
+		clear_ext();
+
+		TestMeter::new(&Origin::from_account_id(ALICE), Some(1_000), 0).unwrap();
+
+		assert_eq!(
+			TestExtTestValue::get(),
+			TestExt {
+				limit_checks: vec![LimitCheck { origin: ALICE, limit: 1_000, min_leftover: 0 }],
+				..Default::default()
+			}
+		)
+	}
+
+	#[test]
+	fn empty_charge_works() {
+		clear_ext();
+
+		let mut meter = TestMeter::new(&Origin::from_account_id(ALICE), Some(1_000), 0).unwrap();
+		assert_eq!(meter.available(), 1_000);
+
+		// an empty charge does not create a `Charge` entry
+		let mut nested0 = meter.nested(Zero::zero());
+		nested0.charge(&Default::default());
+		meter.absorb(nested0, &BOB, None);
+
+		assert_eq!(
+			TestExtTestValue::get(),
+			TestExt {
+				limit_checks: vec![LimitCheck { origin: ALICE, limit: 1_000, min_leftover: 0 }],
+				..Default::default()
+			}
+		)
+	}
+
+	#[test]
+	fn charging_works() {
+		let test_cases = vec![
+			ChargingTestCase {
+				origin: Origin::from_account_id(ALICE),
+				deposit: Deposit::Refund(28),
+				expected: TestExt {
+					limit_checks: vec![LimitCheck { origin: ALICE, limit: 100, min_leftover: 0 }],
+					charges: vec![
+						Charge {
+							origin: ALICE,
+							contract: CHARLIE,
+							amount: Deposit::Refund(10),
+							state: ContractState::Alive,
+						},
+						Charge {
+							origin: ALICE,
+							contract: CHARLIE,
+							amount: Deposit::Refund(20),
+							state: ContractState::Alive,
+						},
+						Charge {
+							origin: ALICE,
+							contract: BOB,
+							amount: Deposit::Charge(2),
+							state: ContractState::Alive,
+						},
+					],
+				},
+			},
+			ChargingTestCase {
+				origin: Origin::Root,
+				deposit: Deposit::Charge(0),
+				expected: TestExt { limit_checks: vec![], charges: vec![] },
+			},
+		];
+
+		for test_case in test_cases {
+			clear_ext();
+
+			let mut meter = TestMeter::new(&test_case.origin, Some(100), 0).unwrap();
+			assert_eq!(meter.available(), 100);
+
+			let mut nested0_info = ContractInfo {
+				storage_bytes: 100,
+				storage_items: 5,
+				storage_byte_deposit: 100,
+				storage_item_deposit: 10,
+				..Default::default()
+			};
+			let mut nested0 = meter.nested(Zero::zero());
+			nested0.charge(&Diff {
+				bytes_added: 108,
+				bytes_removed: 5,
+				items_added: 1,
+				items_removed: 2,
+			});
+			nested0.charge(&Diff { bytes_removed: 99, ..Default::default() });
+
+			let mut nested1_info = ContractInfo {
+				storage_bytes: 100,
+				storage_items: 10,
+				storage_byte_deposit: 100,
+				storage_item_deposit: 20,
+				..Default::default()
+			};
+			let mut nested1 = nested0.nested(Zero::zero());
+			nested1.charge(&Diff { items_removed: 5, ..Default::default() });
+			nested0.absorb(nested1, &CHARLIE, Some(&mut nested1_info));
+
+			let mut nested2_info = ContractInfo {
+				storage_bytes: 100,
+				storage_items: 7,
+				storage_byte_deposit: 100,
+				storage_item_deposit: 20,
+				..Default::default()
+			};
+			let mut nested2 = nested0.nested(Zero::zero());
+			nested2.charge(&Diff { items_removed: 7, ..Default::default() });
+			nested0.absorb(nested2, &CHARLIE, Some(&mut nested2_info));
+
+			nested0.enforce_limit(Some(&mut nested0_info)).unwrap();
+			meter.absorb(nested0, &BOB, Some(&mut nested0_info));
+
+			assert_eq!(meter.try_into_deposit(&test_case.origin).unwrap(), test_case.deposit);
+
+			assert_eq!(nested0_info.extra_deposit(), 112);
+			assert_eq!(nested1_info.extra_deposit(), 110);
+			assert_eq!(nested2_info.extra_deposit(), 100);
+
+			assert_eq!(TestExtTestValue::get(), test_case.expected)
+		}
+	}
+
+	#[test]
+	fn termination_works() {
+		let test_cases = vec![
+			ChargingTestCase {
+				origin: Origin::from_account_id(ALICE),
+				deposit: Deposit::Refund(107),
+				expected: TestExt {
+					limit_checks: vec![LimitCheck { origin: ALICE, limit: 1_000, min_leftover: 0 }],
+					charges: vec![
+						Charge {
+							origin: ALICE,
+							contract: CHARLIE,
+							amount: Deposit::Refund(119),
+							state: ContractState::Terminated { beneficiary: CHARLIE },
+						},
+						Charge {
+							origin: ALICE,
+							contract: BOB,
+							amount: Deposit::Charge(12),
+							state: ContractState::Alive,
+						},
+					],
+				},
+			},
+			ChargingTestCase {
+				origin: Origin::Root,
+				deposit: Deposit::Charge(0),
+				expected: TestExt { limit_checks: vec![], charges: vec![] },
+			},
+		];
+
+		for test_case in test_cases {
+			clear_ext();
+
+			let mut meter = TestMeter::new(&test_case.origin, Some(1_000), 0).unwrap();
+			assert_eq!(meter.available(), 1_000);
+
+			let mut nested0 = meter.nested(Zero::zero());
+			nested0.charge(&Diff {
+				bytes_added: 5,
+				bytes_removed: 1,
+				items_added: 3,
+				items_removed: 1,
+			});
+			nested0.charge(&Diff { items_added: 2, ..Default::default() });
+
+			let mut nested1_info = ContractInfo {
+				storage_bytes: 100,
+				storage_items: 10,
+				storage_byte_deposit: 100,
+				storage_item_deposit: 20,
+				..Default::default()
+			};
+			let mut nested1 = nested0.nested(Zero::zero());
+			nested1.charge(&Diff { items_removed: 5, ..Default::default() });
+			nested1.charge(&Diff { bytes_added: 20, ..Default::default() });
+			nested1.terminate(&nested1_info, CHARLIE);
+			nested0.enforce_limit(Some(&mut nested1_info)).unwrap();
+			nested0.absorb(nested1, &CHARLIE, None);
+
+			meter.absorb(nested0, &BOB, None);
+			assert_eq!(meter.try_into_deposit(&test_case.origin).unwrap(), test_case.deposit);
+
+			assert_eq!(TestExtTestValue::get(), test_case.expected)
+		}
+	}
+}
@@ -15,11 +15,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::{
+	exec::AccountIdOf, BalanceOf, CallTrace, CallTraceOf, CallType, Config, StorageAccess,
+	TracedEvent, LOG_TARGET,
+};
 pub use crate::{
 	exec::{ExecResult, ExportedFunction},
 	primitives::ExecReturnValue,
 };
-use crate::{Config, LOG_TARGET};
+use frame_support::{weights::Weight, DefaultNoBound};
+use sp_std::prelude::*;
 
 /// Umbrella trait for all interfaces that serves for debugging.
 pub trait Debugger<T: Config>: Tracing<T> + CallInterceptor<T> {}
@@ -110,3 +115,78 @@ impl<T: Config> CallInterceptor<T> for () {
 		None
 	}
 }
+
+/// Collects a [`CallTrace`] while a call stack is executing.
+///
+/// It is passed down the call stack next to the debug buffer and only ever set when executing
+/// as an RPC. It keeps the inputs, outputs and storage accesses of all frames in memory.
+#[derive(DefaultNoBound)]
+pub(crate) struct CallTracer<T: Config> {
+	/// The traces of the frames which are currently executing. The last one is the top frame.
+	stack: Vec<CallTraceOf<T>>,
+	/// The trace of the first frame once it finished executing.
+	trace: Option<CallTraceOf<T>>,
+}
+
+impl<T: Config> CallTracer<T> {
+	/// Called when a new frame starts executing.
+	pub fn enter(
+		&mut self,
+		call_type: CallType,
+		from: Option<AccountIdOf<T>>,
+		to: AccountIdOf<T>,
+		value: BalanceOf<T>,
+		input: &[u8],
+	) {
+		self.stack.push(CallTrace {
+			call_type,
+			from,
+			to,
+			value,
+			input: input.to_vec(),
+			output: Vec::new(),
+			error: None,
+			reverted: false,
+			gas_used: Weight::zero(),
+			storage: Vec::new(),
+			events: Vec::new(),
+			calls: Vec::new(),
+		});
+	}
+
+	/// Called when the top frame finished executing.
+	pub fn exit(&mut self, output: &ExecResult, gas_used: Weight) {
+		let Some(mut trace) = self.stack.pop() else { return };
+		match output {
+			Ok(output) => {
+				trace.output = output.data.clone();
+				trace.reverted = output.did_revert();
+			},
+			Err(err) => trace.error = Some(err.error),
+		}
+		trace.gas_used = gas_used;
+		match self.stack.last_mut() {
+			Some(parent) => parent.calls.push(trace),
+			None => self.trace = Some(trace),
+		}
+	}
+
+	/// Record a storage access of the top frame.
+	pub fn storage_access(&mut self, access: StorageAccess) {
+		if let Some(trace) = self.stack.last_mut() {
+			trace.storage.push(access);
+		}
+	}
+
+	/// Record an event emitted by the contract of the top frame.
+	pub fn event(&mut self, topics: Vec<T::Hash>, data: Vec<u8>) {
+		if let Some(trace) = self.stack.last_mut() {
+			trace.events.push(TracedEvent { topics, data });
+		}
+	}
+
+	/// The trace of the first frame or `None` if it was never entered.
+	pub fn into_trace(self) -> Option<CallTraceOf<T>> {
+		self.trace
+	}
+}
@@ -16,9 +16,9 @@
 // limitations under the License.
 
 use crate::{
-	debug::{CallInterceptor, CallSpan, Tracing},
+	debug::{CallInterceptor, CallSpan, CallTracer, Tracing},
 	gas::GasMeter,
-	primitives::{ExecReturnValue, StorageDeposit},
+	primitives::{CallType, ExecReturnValue, StorageAccess, StorageDeposit},
 	storage::{self, meter::Diff, WriteOutcome},
 	BalanceOf, CodeHash, CodeInfo, CodeInfoOf, Config, ContractInfo, ContractInfoOf,
 	DebugBufferVec, Determinism, Error, Event, Nonce, Origin, Pallet as Contracts, Schedule,
@@ -465,6 +465,8 @@ pub struct Stack<'a, T: Config, E> {
 	/// All the bytes added to this field should be valid UTF-8. The buffer has no defined
 	/// structure and is intended to be shown to users as-is for debugging purposes.
 	debug_message: Option<&'a mut DebugBufferVec<T>>,
+	/// Collects a structured trace of all frames of this call stack.
+	tracer: Option<&'a mut CallTracer<T>>,
 	/// The determinism requirement of this call stack.
 	determinism: Determinism,
 	/// No executable is held by the struct but influences its behaviour.
@@ -659,8 +661,8 @@ where
 	///
 	/// # Note
 	///
-	/// `debug_message` should only ever be set to `Some` when executing as an RPC because
-	/// it adds allocations and could be abused to drive the runtime into an OOM panic.
+	/// `debug_message` and `tracer` should only ever be set to `Some` when executing as an RPC
+	/// because they add allocations and could be abused to drive the runtime into an OOM panic.
 	///
 	/// # Return Value
 	///
@@ -674,6 +676,7 @@ where
 		value: BalanceOf<T>,
 		input_data: Vec<u8>,
 		debug_message: Option<&'a mut DebugBufferVec<T>>,
+		tracer: Option<&'a mut CallTracer<T>>,
 		determinism: Determinism,
 	) -> Result<ExecReturnValue, ExecError> {
 		let (mut stack, executable) = Self::new(
@@ -684,6 +687,7 @@ where
 			schedule,
 			value,
 			debug_message,
+			tracer,
 			determinism,
 		)?;
 		stack.run(executable, input_data)
@@ -693,8 +697,8 @@ where
 	///
 	/// # Note
 	///
-	/// `debug_message` should only ever be set to `Some` when executing as an RPC because
-	/// it adds allocations and could be abused to drive the runtime into an OOM panic.
+	/// `debug_message` and `tracer` should only ever be set to `Some` when executing as an RPC
+	/// because they add allocations and could be abused to drive the runtime into an OOM panic.
 	///
 	/// # Return Value
 	///
@@ -709,6 +713,7 @@ where
 		input_data: Vec<u8>,
 		salt: &[u8],
 		debug_message: Option<&'a mut DebugBufferVec<T>>,
+		tracer: Option<&'a mut CallTracer<T>>,
 	) -> Result<(T::AccountId, ExecReturnValue), ExecError> {
 		let (mut stack, executable) = Self::new(
 			FrameArgs::Instantiate {
@@ -724,6 +729,7 @@ where
 			schedule,
 			value,
 			debug_message,
+			tracer,
 			Determinism::Enforced,
 		)?;
 		let account_id = stack.top_frame().account_id.clone();
@@ -739,6 +745,7 @@ where
 		schedule: &'a Schedule<T>,
 		value: BalanceOf<T>,
 		debug_message: Option<&'a mut DebugBufferVec<T>>,
+		tracer: Option<&'a mut CallTracer<T>>,
 		determinism: Determinism,
 	) -> Result<(Self, E), ExecError> {
 		let (first_frame, executable, nonce) = Self::new_frame(
@@ -762,6 +769,7 @@ where
 			first_frame,
 			frames: Default::default(),
 			debug_message,
+			tracer,
 			determinism,
 			_phantom: Default::default(),
 		};
@@ -889,6 +897,21 @@ where
 		let entry_point = frame.entry_point;
 		let delegated_code_hash =
 			if frame.delegate_caller.is_some() { Some(*executable.code_hash()) } else { None };
+
+		if self.tracer.is_some() {
+			let call_type = match (entry_point, delegated_code_hash) {
+				(ExportedFunction::Constructor, _) => CallType::Instantiate,
+				(ExportedFunction::Call, Some(_)) => CallType::DelegateCall,
+				(ExportedFunction::Call, None) => CallType::Call,
+			};
+			let from = self.caller().account_id().ok().cloned();
+			let frame = self.top_frame();
+			let (to, value) = (frame.account_id.clone(), frame.value_transferred);
+			if let Some(tracer) = self.tracer.as_mut() {
+				tracer.enter(call_type, from, to, value, &input_data);
+			}
+		}
+
 		let do_transaction = || {
 			// We need to charge the storage deposit before the initial transfer so that
 			// it can create the account in case the initial transfer is < ed.
@@ -1009,6 +1032,11 @@ where
 			Err(error) => (false, Err(error.into())),
 		};
 
+		let gas_used = self.top_frame().nested_gas.gas_consumed();
+		if let Some(tracer) = self.tracer.as_mut() {
+			tracer.exit(&output, gas_used);
+		}
+
 		self.pop_frame(success);
 		output
 	}
@@ -1312,10 +1340,16 @@ where
 	}
 
 	fn get_storage(&mut self, key: &Key<T>) -> Option<Vec<u8>> {
+		if let Some(tracer) = self.tracer.as_mut() {
+			tracer.storage_access(StorageAccess::Read { key: key.to_vec() });
+		}
 		self.top_frame_mut().contract_info().read(key)
 	}
 
 	fn get_storage_size(&mut self, key: &Key<T>) -> Option<u32> {
+		if let Some(tracer) = self.tracer.as_mut() {
+			tracer.storage_access(StorageAccess::Read { key: key.to_vec() });
+		}
 		self.top_frame_mut().contract_info().size(key.into())
 	}
 
@@ -1325,6 +1359,9 @@ where
 		value: Option<Vec<u8>>,
 		take_old: bool,
 	) -> Result<WriteOutcome, DispatchError> {
+		if let Some(tracer) = self.tracer.as_mut() {
+			tracer.storage_access(StorageAccess::Write { key: key.to_vec(), value: value.clone() });
+		}
 		let frame = self.top_frame_mut();
 		frame.contract_info.get(&frame.account_id).write(
 			key.into(),
@@ -1395,6 +1432,9 @@ where
 	}
 
 	fn deposit_event(&mut self, topics: Vec<T::Hash>, data: Vec<u8>) {
+		if let Some(tracer) = self.tracer.as_mut() {
+			tracer.event(topics.clone(), data.clone());
+		}
 		Contracts::<Self::T>::deposit_event(
 			topics,
 			Event::ContractEmitted { contract: self.top_frame().account_id.clone(), data },
@@ -1778,6 +1818,7 @@ mod tests {
 					value,
 					vec![],
 					None,
+					None,
 					Determinism::Enforced,
 				),
 				Ok(_)
@@ -1834,6 +1875,7 @@ mod tests {
 				value,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced,
 			)
 			.unwrap();
@@ -1878,6 +1920,7 @@ mod tests {
 				value,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced,
 			)
 			.unwrap();
@@ -1916,6 +1959,7 @@ mod tests {
 				55,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced,
 			)
 			.unwrap();
@@ -1970,6 +2014,7 @@ mod tests {
 				0,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced,
 			);
 
@@ -2005,6 +2050,7 @@ mod tests {
 				0,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced,
 			);
 
@@ -2038,6 +2084,7 @@ mod tests {
 				0,
 				vec![1, 2, 3, 4],
 				None,
+				None,
 				Determinism::Enforced,
 			);
 			assert_matches!(result, Ok(_));
@@ -2076,6 +2123,7 @@ mod tests {
 					vec![1, 2, 3, 4],
 					&[],
 					None,
+					None,
 				);
 				assert_matches!(result, Ok(_));
 			});
@@ -2125,6 +2173,7 @@ mod tests {
 				value,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced,
 			);
 
@@ -2181,6 +2230,7 @@ mod tests {
 				0,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced,
 			);
 
@@ -2217,6 +2267,7 @@ mod tests {
 				0,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced,
 			);
 			assert_matches!(result, Ok(_));
@@ -2249,6 +2300,7 @@ mod tests {
 				0,
 				vec![0],
 				None,
+				None,
 				Determinism::Enforced,
 			);
 			assert_matches!(result, Ok(_));
@@ -2279,6 +2331,7 @@ mod tests {
 				0,
 				vec![0],
 				None,
+				None,
 				Determinism::Enforced,
 			);
 			assert_matches!(result, Ok(_));
@@ -2318,6 +2371,7 @@ mod tests {
 				0,
 				vec![0],
 				None,
+				None,
 				Determinism::Enforced,
 			);
 			assert_matches!(result, Ok(_));
@@ -2348,6 +2402,7 @@ mod tests {
 				0,
 				vec![0],
 				None,
+				None,
 				Determinism::Enforced,
 			);
 			assert_matches!(result, Ok(_));
@@ -2378,6 +2433,7 @@ mod tests {
 				1,
 				vec![0],
 				None,
+				None,
 				Determinism::Enforced,
 			);
 			assert_matches!(result, Err(_));
@@ -2417,6 +2473,7 @@ mod tests {
 				0,
 				vec![0],
 				None,
+				None,
 				Determinism::Enforced,
 			);
 			assert_matches!(result, Ok(_));
@@ -2459,6 +2516,7 @@ mod tests {
 				0,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced,
 			);
 
@@ -2489,6 +2547,7 @@ mod tests {
 					vec![],
 					&[],
 					None,
+					None,
 				),
 				Err(_)
 			);
@@ -2530,6 +2589,7 @@ mod tests {
 						vec![],
 						&[],
 						None,
+						None,
 					),
 					Ok((address, ref output)) if output.data == vec![80, 65, 83, 83] => address
 				);
@@ -2585,6 +2645,7 @@ mod tests {
 						vec![],
 						&[],
 						None,
+						None,
 					),
 					Ok((address, ref output)) if output.data == vec![70, 65, 73, 76] => address
 				);
@@ -2649,6 +2710,7 @@ mod tests {
 						min_balance * 10,
 						vec![],
 						None,
+						None,
 						Determinism::Enforced,
 					),
 					Ok(_)
@@ -2724,6 +2786,7 @@ mod tests {
 						0,
 						vec![],
 						None,
+						None,
 						Determinism::Enforced,
 					),
 					Ok(_)
@@ -2770,6 +2833,7 @@ mod tests {
 						vec![],
 						&[],
 						None,
+						None,
 					),
 					Err(Error::<Test>::TerminatedInConstructor.into())
 				);
@@ -2834,6 +2898,7 @@ mod tests {
 				0,
 				vec![0],
 				None,
+				None,
 				Determinism::Enforced,
 			);
 			assert_matches!(result, Ok(_));
@@ -2874,6 +2939,7 @@ mod tests {
 					vec![],
 					&[],
 					None,
+					None,
 				);
 				assert_matches!(result, Ok(_));
 			});
@@ -2907,6 +2973,7 @@ mod tests {
 				0,
 				vec![],
 				Some(&mut debug_buffer),
+				None,
 				Determinism::Enforced,
 			)
 			.unwrap();
@@ -2943,6 +3010,7 @@ mod tests {
 				0,
 				vec![],
 				Some(&mut debug_buffer),
+				None,
 				Determinism::Enforced,
 			);
 			assert!(result.is_err());
@@ -2982,6 +3050,7 @@ mod tests {
 				0,
 				vec![],
 				Some(&mut debug_buf_after),
+				None,
 				Determinism::Enforced,
 			)
 			.unwrap();
@@ -3017,6 +3086,7 @@ mod tests {
 				0,
 				CHARLIE.encode(),
 				None,
+				None,
 				Determinism::Enforced
 			));
 
@@ -3031,6 +3101,7 @@ mod tests {
 					0,
 					BOB.encode(),
 					None,
+					None,
 					Determinism::Enforced
 				)
 				.map_err(|e| e.error),
@@ -3074,6 +3145,7 @@ mod tests {
 					0,
 					vec![0],
 					None,
+					None,
 					Determinism::Enforced
 				)
 				.map_err(|e| e.error),
@@ -3111,6 +3183,7 @@ mod tests {
 				0,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced,
 			)
 			.unwrap();
@@ -3198,6 +3271,7 @@ mod tests {
 				0,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced,
 			)
 			.unwrap();
@@ -3308,6 +3382,7 @@ mod tests {
 					vec![],
 					&[],
 					None,
+					None,
 				)
 				.ok();
 				assert_eq!(<Nonce<Test>>::get(), 0);
@@ -3322,6 +3397,7 @@ mod tests {
 					vec![],
 					&[],
 					None,
+					None,
 				));
 				assert_eq!(<Nonce<Test>>::get(), 1);
 
@@ -3335,6 +3411,7 @@ mod tests {
 					vec![],
 					&[],
 					None,
+					None,
 				));
 				assert_eq!(<Nonce<Test>>::get(), 2);
 
@@ -3348,6 +3425,7 @@ mod tests {
 					vec![],
 					&[],
 					None,
+					None,
 				));
 				assert_eq!(<Nonce<Test>>::get(), 4);
 			});
@@ -3416,6 +3494,7 @@ mod tests {
 				0,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced
 			));
 		});
@@ -3544,6 +3623,7 @@ mod tests {
 				0,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced
 			));
 		});
@@ -3584,6 +3664,7 @@ mod tests {
 				0,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced
 			));
 		});
@@ -3624,6 +3705,7 @@ mod tests {
 				0,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced
 			));
 		});
@@ -3681,6 +3763,7 @@ mod tests {
 				0,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced
 			));
 		});
@@ -3738,6 +3821,7 @@ mod tests {
 				0,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced
 			));
 		});
@@ -3774,6 +3858,7 @@ mod tests {
 				0,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced,
 			);
 			assert_matches!(result, Ok(_));
@@ -3841,6 +3926,7 @@ mod tests {
 					0,
 					vec![],
 					None,
+					None,
 					Determinism::Enforced
 				));
 			});
@@ -3873,6 +3959,7 @@ mod tests {
 				0,
 				vec![],
 				None,
+				None,
 				Determinism::Enforced,
 			);
 			assert_matches!(result, Ok(_));
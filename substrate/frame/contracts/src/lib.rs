@@ -106,6 +106,7 @@ pub mod weights;
 #[cfg(test)]
 mod tests;
 use crate::{
+	debug::CallTracer,
 	exec::{
 		AccountIdOf, ErrorOrigin, ExecError, Executable, Ext, Key, MomentOf, Stack as ExecStack,
 	},
@@ -162,6 +163,7 @@ type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup
 type DebugBufferVec<T> = BoundedVec<u8, <T as Config>::MaxDebugBufferLen>;
 type EventRecordOf<T> =
 	EventRecord<<T as frame_system::Config>::RuntimeEvent, <T as frame_system::Config>::Hash>;
+type CallTraceOf<T> = CallTrace<AccountIdOf<T>, BalanceOf<T>, <T as frame_system::Config>::Hash>;
 
 /// The old weight type.
 ///
@@ -715,6 +717,7 @@ pub mod pallet {
 				gas_limit: gas_limit.into(),
 				storage_deposit_limit: storage_deposit_limit.map(Into::into),
 				debug_message: None,
+				tracer: None,
 			};
 			let dest = T::Lookup::lookup(dest)?;
 			let mut output =
@@ -791,6 +794,7 @@ pub mod pallet {
 				gas_limit,
 				storage_deposit_limit,
 				debug_message: None,
+				tracer: None,
 			};
 
 			let mut output =
@@ -835,6 +839,7 @@ pub mod pallet {
 				gas_limit,
 				storage_deposit_limit: storage_deposit_limit.map(Into::into),
 				debug_message: None,
+				tracer: None,
 			};
 			let mut output = InstantiateInput::<T> { code: WasmCode::CodeHash(code_hash), salt }
 				.run_guarded(common);
@@ -1165,6 +1170,7 @@ struct CommonInput<'a, T: Config> {
 	gas_limit: Weight,
 	storage_deposit_limit: Option<BalanceOf<T>>,
 	debug_message: Option<&'a mut DebugBufferVec<T>>,
+	tracer: Option<&'a mut CallTracer<T>>,
 }
 
 /// Input specific to a call into contract.
@@ -1308,7 +1314,7 @@ impl<T: Config> Invokable<T> for CallInput<T> {
 		mut gas_meter: GasMeter<T>,
 	) -> InternalOutput<T, Self::Output> {
 		let CallInput { dest, determinism } = self;
-		let CommonInput { origin, value, data, debug_message, .. } = common;
+		let CommonInput { origin, value, data, debug_message, tracer, .. } = common;
 		let mut storage_meter =
 			match StorageMeter::new(&origin, common.storage_deposit_limit, common.value) {
 				Ok(meter) => meter,
@@ -1329,6 +1335,7 @@ impl<T: Config> Invokable<T> for CallInput<T> {
 			value,
 			data.clone(),
 			debug_message,
+			tracer,
 			determinism,
 		);
 
@@ -1370,7 +1377,7 @@ impl<T: Config> Invokable<T> for InstantiateInput<T> {
 			let contract_origin = Origin::from_account_id(origin.clone());
 			let mut storage_meter =
 				StorageMeter::new(&contract_origin, common.storage_deposit_limit, common.value)?;
-			let CommonInput { value, data, debug_message, .. } = common;
+			let CommonInput { value, data, debug_message, tracer, .. } = common;
 			let result = ExecStack::<T, WasmBlob<T>>::run_instantiate(
 				origin.clone(),
 				executable,
@@ -1381,6 +1388,7 @@ impl<T: Config> Invokable<T> for InstantiateInput<T> {
 				data.clone(),
 				&salt,
 				debug_message,
+				tracer,
 			);
 
 			storage_deposit = storage_meter.try_into_deposit(&contract_origin)?;
@@ -1435,6 +1443,149 @@ impl<T: Config> Pallet<T> {
 		debug: DebugInfo,
 		collect_events: CollectEvents,
 		determinism: Determinism,
+	) -> ContractExecResult<BalanceOf<T>, EventRecordOf<T>> {
+		Self::internal_bare_call(
+			origin,
+			dest,
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			data,
+			debug,
+			collect_events,
+			determinism,
+			None,
+		)
+	}
+
+	/// Perform a call to a specified contract and collect a trace of all nested calls.
+	///
+	/// This function is similar to [`Self::bare_call`] but additionally returns a [`CallTrace`]
+	/// which records the input, output, gas usage, storage accesses and emitted events of every
+	/// frame. The trace is `None` if the call stack was never entered, e.g. because `dest` is not
+	/// a contract.
+	///
+	/// # Note
+	///
+	/// This should only ever be used when executing as an RPC because it adds allocations and
+	/// could be abused to drive the runtime into an OOM panic.
+	pub fn bare_call_traced(
+		origin: T::AccountId,
+		dest: T::AccountId,
+		value: BalanceOf<T>,
+		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
+		data: Vec<u8>,
+		debug: DebugInfo,
+		collect_events: CollectEvents,
+		determinism: Determinism,
+	) -> ContractTracedExecResult<T::AccountId, BalanceOf<T>, EventRecordOf<T>, T::Hash> {
+		let mut tracer = CallTracer::default();
+		let result = Self::internal_bare_call(
+			origin,
+			dest,
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			data,
+			debug,
+			collect_events,
+			determinism,
+			Some(&mut tracer),
+		);
+		(result, tracer.into_trace())
+	}
+
+	/// Instantiate a new contract.
+	///
+	/// This function is similar to [`Self::instantiate`], but doesn't perform any address lookups
+	/// and better suitable for calling directly from Rust.
+	///
+	/// It returns the execution result, account id and the amount of used weight.
+	///
+	/// # Note
+	///
+	/// If `debug` is set to `DebugInfo::UnsafeDebug` it returns additional human readable debugging
+	/// information.
+	///
+	/// If `collect_events` is set to `CollectEvents::UnsafeCollect` it collects all the Events
+	/// emitted in the block so far.
+	pub fn bare_instantiate(
+		origin: T::AccountId,
+		value: BalanceOf<T>,
+		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
+		code: Code<CodeHash<T>>,
+		data: Vec<u8>,
+		salt: Vec<u8>,
+		debug: DebugInfo,
+		collect_events: CollectEvents,
+	) -> ContractInstantiateResult<T::AccountId, BalanceOf<T>, EventRecordOf<T>> {
+		Self::internal_bare_instantiate(
+			origin,
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			code,
+			data,
+			salt,
+			debug,
+			collect_events,
+			None,
+		)
+	}
+
+	/// Instantiate a new contract and collect a trace of all nested calls.
+	///
+	/// This function is similar to [`Self::bare_instantiate`] but additionally returns a
+	/// [`CallTrace`] whose first frame is the constructor of the new contract. Together with
+	/// the returned gas and storage deposit this allows to debug and estimate deployments which
+	/// interact with other contracts. The trace is `None` if the constructor was never entered,
+	/// e.g. because the code could not be uploaded.
+	///
+	/// # Note
+	///
+	/// This should only ever be used when executing as an RPC because it adds allocations and
+	/// could be abused to drive the runtime into an OOM panic.
+	pub fn bare_instantiate_traced(
+		origin: T::AccountId,
+		value: BalanceOf<T>,
+		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
+		code: Code<CodeHash<T>>,
+		data: Vec<u8>,
+		salt: Vec<u8>,
+		debug: DebugInfo,
+		collect_events: CollectEvents,
+	) -> ContractTracedInstantiateResult<T::AccountId, BalanceOf<T>, EventRecordOf<T>, T::Hash> {
+		let mut tracer = CallTracer::default();
+		let result = Self::internal_bare_instantiate(
+			origin,
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			code,
+			data,
+			salt,
+			debug,
+			collect_events,
+			Some(&mut tracer),
+		);
+		(result, tracer.into_trace())
+	}
+
+	/// Shared implementation of [`Self::bare_call`] and [`Self::bare_call_traced`].
+	fn internal_bare_call(
+		origin: T::AccountId,
+		dest: T::AccountId,
+		value: BalanceOf<T>,
+		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
+		data: Vec<u8>,
+		debug: DebugInfo,
+		collect_events: CollectEvents,
+		determinism: Determinism,
+		tracer: Option<&mut CallTracer<T>>,
 	) -> ContractExecResult<BalanceOf<T>, EventRecordOf<T>> {
 		ensure_no_migration_in_progress!();
 
@@ -1451,6 +1602,7 @@ impl<T: Config> Pallet<T> {
 			gas_limit,
 			storage_deposit_limit,
 			debug_message: debug_message.as_mut(),
+			tracer,
 		};
 		let output = CallInput::<T> { dest, determinism }.run_guarded(common);
 		let events = if matches!(collect_events, CollectEvents::UnsafeCollect) {
@@ -1469,21 +1621,9 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	/// Instantiate a new contract.
-	///
-	/// This function is similar to [`Self::instantiate`], but doesn't perform any address lookups
-	/// and better suitable for calling directly from Rust.
-	///
-	/// It returns the execution result, account id and the amount of used weight.
-	///
-	/// # Note
-	///
-	/// If `debug` is set to `DebugInfo::UnsafeDebug` it returns additional human readable debugging
-	/// information.
-	///
-	/// If `collect_events` is set to `CollectEvents::UnsafeCollect` it collects all the Events
-	/// emitted in the block so far.
-	pub fn bare_instantiate(
+	/// Shared implementation of [`Self::bare_instantiate`] and
+	/// [`Self::bare_instantiate_traced`].
+	fn internal_bare_instantiate(
 		origin: T::AccountId,
 		value: BalanceOf<T>,
 		gas_limit: Weight,
@@ -1493,6 +1633,7 @@ impl<T: Config> Pallet<T> {
 		salt: Vec<u8>,
 		debug: DebugInfo,
 		collect_events: CollectEvents,
+		tracer: Option<&mut CallTracer<T>>,
 	) -> ContractInstantiateResult<T::AccountId, BalanceOf<T>, EventRecordOf<T>> {
 		ensure_no_migration_in_progress!();
 
@@ -1547,6 +1688,7 @@ impl<T: Config> Pallet<T> {
 			gas_limit,
 			storage_deposit_limit,
 			debug_message: debug_message.as_mut(),
+			tracer,
 		};
 
 		let output = InstantiateInput::<T> { code, salt }.run_guarded(common);
@@ -1672,7 +1814,7 @@ impl<T: Config> Pallet<T> {
 
 sp_api::decl_runtime_apis! {
 	/// The API used to dry-run contract interactions.
-	#[api_version(2)]
+	#[api_version(3)]
 	pub trait ContractsApi<AccountId, Balance, BlockNumber, Hash, EventRecord> where
 		AccountId: Codec,
 		Balance: Codec,
@@ -1724,5 +1866,33 @@ sp_api::decl_runtime_apis! {
 			address: AccountId,
 			key: Vec<u8>,
 		) -> GetStorageResult;
+
+		/// Perform a call like [`Self::call`] and additionally return a trace of all nested calls.
+		///
+		/// See [`crate::Pallet::bare_call_traced`].
+		#[api_version(3)]
+		fn trace_call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: Option<Weight>,
+			storage_deposit_limit: Option<Balance>,
+			input_data: Vec<u8>,
+		) -> ContractTracedExecResult<AccountId, Balance, EventRecord, Hash>;
+
+		/// Instantiate a new contract like [`Self::instantiate`] and additionally return a trace
+		/// of all nested calls.
+		///
+		/// See [`crate::Pallet::bare_instantiate_traced`].
+		#[api_version(3)]
+		fn trace_instantiate(
+			origin: AccountId,
+			value: Balance,
+			gas_limit: Option<Weight>,
+			storage_deposit_limit: Option<Balance>,
+			code: Code<Hash>,
+			data: Vec<u8>,
+			salt: Vec<u8>,
+		) -> ContractTracedInstantiateResult<AccountId, Balance, EventRecord, Hash>;
 	}
 }
@@ -19,71 +19,14 @@
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::weights::Weight;
-use pallet_contracts_uapi::ReturnFlags;
 use scale_info::TypeInfo;
-use sp_runtime::{
-	traits::{Saturating, Zero},
-	DispatchError, RuntimeDebug,
-};
+use sp_runtime::{DispatchError, RuntimeDebug};
 use sp_std::prelude::*;
 
-/// Result type of a `bare_call` or `bare_instantiate` call as well as `ContractsApi::call` and
-/// `ContractsApi::instantiate`.
-///
-/// It contains the execution result together with some auxiliary information.
-///
-/// #Note
-///
-/// It has been extended to include `events` at the end of the struct while not bumping the
-/// `ContractsApi` version. Therefore when SCALE decoding a `ContractResult` its trailing data
-/// should be ignored to avoid any potential compatibility issues.
-#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
-pub struct ContractResult<R, Balance, EventRecord> {
-	/// How much weight was consumed during execution.
-	pub gas_consumed: Weight,
-	/// How much weight is required as gas limit in order to execute this call.
-	///
-	/// This value should be used to determine the weight limit for on-chain execution.
-	///
-	/// # Note
-	///
-	/// This can only different from [`Self::gas_consumed`] when weight pre charging
-	/// is used. Currently, only `seal_call_runtime` makes use of pre charging.
-	/// Additionally, any `seal_call` or `seal_instantiate` makes use of pre-charging
-	/// when a non-zero `gas_limit` argument is supplied.
-	pub gas_required: Weight,
-	/// How much balance was paid by the origin into the contract's deposit account in order to
-	/// pay for storage.
-	///
-	/// The storage deposit is never actually charged from the origin in case of [`Self::result`]
-	/// is `Err`. This is because on error all storage changes are rolled back including the
-	/// payment of the deposit.
-	pub storage_deposit: StorageDeposit<Balance>,
-	/// An optional debug message. This message is only filled when explicitly requested
-	/// by the code that calls into the contract. Otherwise it is empty.
-	///
-	/// The contained bytes are valid UTF-8. This is not declared as `String` because
-	/// this type is not allowed within the runtime.
-	///
-	/// Clients should not make any assumptions about the format of the buffer.
-	/// They should just display it as-is. It is **not** only a collection of log lines
-	/// provided by a contract but a formatted buffer with different sections.
-	///
-	/// # Note
-	///
-	/// The debug message is never generated during on-chain execution. It is reserved for
-	/// RPC calls.
-	pub debug_message: Vec<u8>,
-	/// The execution result of the wasm code.
-	pub result: R,
-	/// The events that were emitted during execution. It is an option as event collection is
-	/// optional.
-	pub events: Option<Vec<EventRecord>>,
-}
-
-/// Result type of a `bare_call` call as well as `ContractsApi::call`.
-pub type ContractExecResult<Balance, EventRecord> =
-	ContractResult<Result<ExecReturnValue, DispatchError>, Balance, EventRecord>;
+pub use pallet_contracts_common::{
+	Code, CodeUploadReturnValue, ContractExecResult, ContractResult, ExecReturnValue,
+	StorageDeposit,
+};
 
 /// Result type of a `bare_instantiate` call as well as `ContractsApi::instantiate`.
 pub type ContractInstantiateResult<AccountId, Balance, EventRecord> =
@@ -107,22 +50,6 @@ pub enum ContractAccessError {
 	MigrationInProgress,
 }
 
-/// Output of a contract call or instantiation which ran to completion.
-#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
-pub struct ExecReturnValue {
-	/// Flags passed along by `seal_return`. Empty when `seal_return` was never called.
-	pub flags: ReturnFlags,
-	/// Buffer passed along by `seal_return`. Empty when `seal_return` was never called.
-	pub data: Vec<u8>,
-}
-
-impl ExecReturnValue {
-	/// The contract did revert all storage changes.
-	pub fn did_revert(&self) -> bool {
-		self.flags.contains(ReturnFlags::REVERT)
-	}
-}
-
 /// The result of a successful contract instantiation.
 #[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct InstantiateReturnValue<AccountId> {
@@ -132,121 +59,73 @@ pub struct InstantiateReturnValue<AccountId> {
 	pub account_id: AccountId,
 }
 
-/// The result of successfully uploading a contract.
-#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, RuntimeDebug, TypeInfo)]
-pub struct CodeUploadReturnValue<CodeHash, Balance> {
-	/// The key under which the new code is stored.
-	pub code_hash: CodeHash,
-	/// The deposit that was reserved at the caller. Is zero when the code already existed.
-	pub deposit: Balance,
+/// Result type of a `bare_call_traced` call as well as `ContractsApi::trace_call`.
+pub type ContractTracedExecResult<AccountId, Balance, EventRecord, Hash> =
+	(ContractExecResult<Balance, EventRecord>, Option<CallTrace<AccountId, Balance, Hash>>);
+
+/// Result type of a `bare_instantiate_traced` call as well as `ContractsApi::trace_instantiate`.
+pub type ContractTracedInstantiateResult<AccountId, Balance, EventRecord, Hash> = (
+	ContractInstantiateResult<AccountId, Balance, EventRecord>,
+	Option<CallTrace<AccountId, Balance, Hash>>,
+);
+
+/// The kind of frame that was recorded in a [`CallTrace`].
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum CallType {
+	/// A regular call into a contract.
+	Call,
+	/// A call that executed foreign code in the context of the calling contract.
+	DelegateCall,
+	/// The instantiation of a new contract.
+	Instantiate,
 }
 
-/// Reference to an existing code hash or a new wasm module.
+/// A storage access of a contract that was recorded in a [`CallTrace`].
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
-pub enum Code<Hash> {
-	/// A wasm module as raw bytes.
-	Upload(Vec<u8>),
-	/// The code hash of an on-chain wasm blob.
-	Existing(Hash),
+pub enum StorageAccess {
+	/// The value or the size of the value under `key` was read.
+	Read { key: Vec<u8> },
+	/// `value` was written under `key`. `None` means that the key was cleared.
+	Write { key: Vec<u8>, value: Option<Vec<u8>> },
 }
 
-/// The amount of balance that was either charged or refunded in order to pay for storage.
-#[derive(
-	Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, MaxEncodedLen, RuntimeDebug, TypeInfo,
-)]
-pub enum StorageDeposit<Balance> {
-	/// The transaction reduced storage consumption.
-	///
-	/// This means that the specified amount of balance was transferred from the involved
-	/// deposit accounts to the origin.
-	Refund(Balance),
-	/// The transaction increased storage consumption.
-	///
-	/// This means that the specified amount of balance was transferred from the origin
-	/// to the involved deposit accounts.
-	Charge(Balance),
-}
-
-impl<Balance: Zero> Default for StorageDeposit<Balance> {
-	fn default() -> Self {
-		Self::Charge(Zero::zero())
-	}
-}
-
-impl<Balance: Zero + Copy> StorageDeposit<Balance> {
-	/// Returns how much balance is charged or `0` in case of a refund.
-	pub fn charge_or_zero(&self) -> Balance {
-		match self {
-			Self::Charge(amount) => *amount,
-			Self::Refund(_) => Zero::zero(),
-		}
-	}
-
-	pub fn is_zero(&self) -> bool {
-		match self {
-			Self::Charge(amount) => amount.is_zero(),
-			Self::Refund(amount) => amount.is_zero(),
-		}
-	}
+/// An event emitted by a contract that was recorded in a [`CallTrace`].
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct TracedEvent<Hash> {
+	/// The topics the event was indexed with.
+	pub topics: Vec<Hash>,
+	/// The data supplied by the contract.
+	pub data: Vec<u8>,
 }
 
-impl<Balance> StorageDeposit<Balance>
-where
-	Balance: Saturating + Ord + Copy,
-{
-	/// This is essentially a saturating signed add.
-	pub fn saturating_add(&self, rhs: &Self) -> Self {
-		use StorageDeposit::*;
-		match (self, rhs) {
-			(Charge(lhs), Charge(rhs)) => Charge(lhs.saturating_add(*rhs)),
-			(Refund(lhs), Refund(rhs)) => Refund(lhs.saturating_add(*rhs)),
-			(Charge(lhs), Refund(rhs)) =>
-				if lhs >= rhs {
-					Charge(lhs.saturating_sub(*rhs))
-				} else {
-					Refund(rhs.saturating_sub(*lhs))
-				},
-			(Refund(lhs), Charge(rhs)) =>
-				if lhs > rhs {
-					Refund(lhs.saturating_sub(*rhs))
-				} else {
-					Charge(rhs.saturating_sub(*lhs))
-				},
-		}
-	}
-
-	/// This is essentially a saturating signed sub.
-	pub fn saturating_sub(&self, rhs: &Self) -> Self {
-		use StorageDeposit::*;
-		match (self, rhs) {
-			(Charge(lhs), Refund(rhs)) => Charge(lhs.saturating_add(*rhs)),
-			(Refund(lhs), Charge(rhs)) => Refund(lhs.saturating_add(*rhs)),
-			(Charge(lhs), Charge(rhs)) =>
-				if lhs >= rhs {
-					Charge(lhs.saturating_sub(*rhs))
-				} else {
-					Refund(rhs.saturating_sub(*lhs))
-				},
-			(Refund(lhs), Refund(rhs)) =>
-				if lhs > rhs {
-					Refund(lhs.saturating_sub(*rhs))
-				} else {
-					Charge(rhs.saturating_sub(*lhs))
-				},
-		}
-	}
-
-	/// If the amount of deposit (this type) is constrained by a `limit` this calculates how
-	/// much balance (if any) is still available from this limit.
-	///
-	/// # Note
-	///
-	/// In case of a refund the return value can be larger than `limit`.
-	pub fn available(&self, limit: &Balance) -> Balance {
-		use StorageDeposit::*;
-		match self {
-			Charge(amount) => limit.saturating_sub(*amount),
-			Refund(amount) => limit.saturating_add(*amount),
-		}
-	}
+/// The trace of a contract call or instantiation including all of its nested calls.
+///
+/// It is only ever collected when dry-running a call through one of the tracing RPCs. The
+/// structure follows the call tracer of geth so that it can be converted easily.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct CallTrace<AccountId, Balance, Hash> {
+	/// The kind of the traced frame.
+	pub call_type: CallType,
+	/// The caller of the frame. `None` if it was called by the root origin.
+	pub from: Option<AccountId>,
+	/// The contract that was executed.
+	pub to: AccountId,
+	/// The balance transferred to the contract.
+	pub value: Balance,
+	/// The input data passed to the contract.
+	pub input: Vec<u8>,
+	/// The data returned by the contract. Empty if the execution failed.
+	pub output: Vec<u8>,
+	/// Set if the execution failed. The changes of the frame were rolled back in this case.
+	pub error: Option<DispatchError>,
+	/// Whether the contract reverted its changes by returning the `REVERT` flag.
+	pub reverted: bool,
+	/// The gas consumed by the frame and all of its nested frames.
+	pub gas_used: Weight,
+	/// The storage accesses of the frame in the order they happened.
+	pub storage: Vec<StorageAccess>,
+	/// The events emitted by the contract of the frame.
+	pub events: Vec<TracedEvent<Hash>>,
+	/// The frames that were created by this frame in the order they were created.
+	pub calls: Vec<CallTrace<AccountId, Balance, Hash>>,
 }
@@ -211,7 +211,7 @@ impl<T: Config> ContractInfo<T> {
 		let ed = Pallet::<T>::min_balance();
 		let info_deposit =
 			Diff { bytes_added: self.encoded_size() as u32, items_added: 1, ..Default::default() }
-				.update_contract::<T>(None)
+				.update_contract::<Pallet<T>>(None)
 				.charge_or_zero();
 
 		// Instantiating the contract prevents its code to be deleted, therefore the base deposit
@@ -19,7 +19,7 @@ use super::*;
 use crate::{
 	debug::{CallInterceptor, CallSpan, ExecResult, ExportedFunction, Tracing},
 	primitives::ExecReturnValue,
-	AccountIdOf,
+	AccountIdOf, CallType, StorageAccess, TracedEvent,
 };
 use frame_support::traits::Currency;
 use pretty_assertions::assert_eq;
@@ -227,3 +227,103 @@ fn call_interception_works() {
 		);
 	});
 }
+
+#[test]
+fn call_tracer_works() {
+	let (wasm_caller, _) = compile_module::<Test>("call").unwrap();
+	let (wasm_callee, _) = compile_module::<Test>("store_call").unwrap();
+	let (wasm_event, _) = compile_module::<Test>("event_and_return_on_deploy").unwrap();
+
+	ExtBuilder::default().existential_deposit(200).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+		let (result, trace) = Contracts::bare_instantiate_traced(
+			ALICE,
+			0,
+			GAS_LIMIT,
+			None,
+			Code::Upload(wasm_event),
+			vec![],
+			vec![],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		);
+		let addr_event = result.result.unwrap().account_id;
+		let trace = trace.unwrap();
+		assert_eq!(trace.call_type, CallType::Instantiate);
+		assert_eq!(trace.from, Some(ALICE));
+		assert_eq!(trace.to, addr_event);
+		assert_eq!(trace.output, vec![1, 2, 3, 4]);
+		assert_eq!(trace.events, vec![TracedEvent { topics: vec![], data: vec![1, 2, 3, 4] }]);
+		assert!(trace.calls.is_empty());
+
+		let deploy = |wasm| {
+			Contracts::bare_instantiate(
+				ALICE,
+				0,
+				GAS_LIMIT,
+				None,
+				Code::Upload(wasm),
+				vec![],
+				vec![],
+				DebugInfo::Skip,
+				CollectEvents::Skip,
+			)
+			.result
+			.unwrap()
+			.account_id
+		};
+		let addr_caller = deploy(wasm_caller);
+		let addr_callee = deploy(wasm_callee);
+
+		let main_args = (100u32, &addr_callee).encode();
+		let inner_args = 100u32.encode();
+		let (result, trace) = Contracts::bare_call_traced(
+			ALICE,
+			addr_caller.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			main_args.clone(),
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+			Determinism::Enforced,
+		);
+		assert_ok!(result.result);
+		let trace = trace.unwrap();
+		assert_eq!(trace.call_type, CallType::Call);
+		assert_eq!(trace.from, Some(ALICE));
+		assert_eq!(trace.to, addr_caller);
+		assert_eq!(trace.input, main_args);
+		assert_eq!(trace.error, None);
+		assert!(trace.storage.is_empty());
+		assert_eq!(trace.calls.len(), 1);
+
+		let nested = &trace.calls[0];
+		assert_eq!(nested.call_type, CallType::Call);
+		assert_eq!(nested.from, Some(addr_caller));
+		assert_eq!(nested.to, addr_callee);
+		assert_eq!(nested.input, inner_args);
+		let mut key = vec![0u8; 32];
+		key[0] = 1;
+		assert_eq!(nested.storage, vec![StorageAccess::Write { key, value: Some(vec![0u8; 100]) }]);
+		assert!(nested.gas_used.ref_time() > 0);
+		assert!(nested.gas_used.all_lte(trace.gas_used));
+		assert!(trace.gas_used.all_lte(result.gas_consumed));
+
+		// Calling an account without a contract never enters the call stack.
+		let (result, trace) = Contracts::bare_call_traced(
+			ALICE,
+			BOB,
+			0,
+			GAS_LIMIT,
+			None,
+			vec![],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+			Determinism::Enforced,
+		);
+		assert_err!(result.result, <Error<Test>>::ContractNotFound);
+		assert_eq!(trace, None);
+	});
+}
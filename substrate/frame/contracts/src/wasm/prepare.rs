@@ -26,7 +26,7 @@ use crate::{
 		runtime::AllowDeprecatedInterface, CodeInfo, Determinism, Environment, WasmBlob,
 		BYTES_PER_PAGE,
 	},
-	AccountIdOf, CodeVec, Config, Error, Schedule, LOG_TARGET,
+	AccountIdOf, CodeVec, Config, Error, Pallet, Schedule, LOG_TARGET,
 };
 use codec::MaxEncodedLen;
 use sp_runtime::{traits::Hash, DispatchError};
@@ -288,7 +288,7 @@ where
 	let code_len = code.len() as u32;
 	let bytes_added = code_len.saturating_add(<CodeInfo<T>>::max_encoded_len() as u32);
 	let deposit = Diff { bytes_added, items_added: 2, ..Default::default() }
-		.update_contract::<T>(None)
+		.update_contract::<Pallet<T>>(None)
 		.charge_or_zero();
 	let code_info = CodeInfo { owner, deposit, determinism, refcount: 0, code_len };
 	let code_hash = T::Hashing::hash(&code);
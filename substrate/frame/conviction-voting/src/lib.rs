@@ -32,7 +32,7 @@ use frame_support::{
 	ensure,
 	traits::{
 		fungible, Currency, Get, LockIdentifier, LockableCurrency, PollStatus, Polling,
-		ReservableCurrency, WithdrawReasons,
+		ReservableCurrency, VotingPowerProvider, WithdrawReasons,
 	},
 };
 use frame_system::pallet_prelude::BlockNumberFor;
@@ -136,6 +136,13 @@ pub mod pallet {
 		/// those successful voters are locked into the consequences that their votes entail.
 		#[pallet::constant]
 		type VoteLockingPeriod: Get<BlockNumberFor<Self>>;
+
+		/// Voting power of accounts that is not part of their own balance, such as stake in a
+		/// nomination pool.
+		///
+		/// Funds held in the account of the voter, e.g. by the staking system, can always be used
+		/// to vote. Use `()` if there is no other source of voting power.
+		type VotingPower: VotingPowerProvider<Self::AccountId, BalanceOf<Self, I>>;
 	}
 
 	/// All voting for a particular voter in a particular voting class. We store the balance for the
@@ -213,7 +220,11 @@ pub mod pallet {
 		///
 		/// Weight: `O(R)` where R is the number of polls the voter has voted on.
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::vote_new().max(T::WeightInfo::vote_existing()))]
+		#[pallet::weight(
+			T::WeightInfo::vote_new()
+				.max(T::WeightInfo::vote_existing())
+				.saturating_add(T::VotingPower::weight())
+		)]
 		pub fn vote(
 			origin: OriginFor<T>,
 			#[pallet::compact] poll_index: PollIndexOf<T, I>,
@@ -249,7 +260,7 @@ pub mod pallet {
 		// NOTE: weight must cover an incorrect voting of origin with max votes, this is ensure
 		// because a valid delegation cover decoding a direct voting with max votes.
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::delegate(T::MaxVotes::get()))]
+		#[pallet::weight(T::WeightInfo::delegate(T::MaxVotes::get()).saturating_add(T::VotingPower::weight()))]
 		pub fn delegate(
 			origin: OriginFor<T>,
 			class: ClassOf<T, I>,
@@ -261,7 +272,7 @@ pub mod pallet {
 			let to = T::Lookup::lookup(to)?;
 			let votes = Self::try_delegate(who, class, to, conviction, balance)?;
 
-			Ok(Some(T::WeightInfo::delegate(votes)).into())
+			Ok(Some(T::WeightInfo::delegate(votes).saturating_add(T::VotingPower::weight())).into())
 		}
 
 		/// Undelegate the voting power of the sending account for a particular class of polls.
@@ -301,7 +312,7 @@ pub mod pallet {
 		///
 		/// Weight: `O(R)` with R number of vote of target.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::unlock())]
+		#[pallet::weight(T::WeightInfo::unlock().saturating_add(T::VotingPower::weight()))]
 		pub fn unlock(
 			origin: OriginFor<T>,
 			class: ClassOf<T, I>,
@@ -387,16 +398,21 @@ pub mod pallet {
 }
 
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// The balance `who` can vote with.
+	///
+	/// This is the total balance of the account, including held funds such as stake, and the
+	/// voting power provided by [`Config::VotingPower`].
+	pub fn voting_balance(who: &T::AccountId) -> BalanceOf<T, I> {
+		T::Currency::total_balance(who).saturating_add(T::VotingPower::voting_power(who))
+	}
+
 	/// Actually enact a vote, if legit.
 	fn try_vote(
 		who: &T::AccountId,
 		poll_index: PollIndexOf<T, I>,
 		vote: AccountVote<BalanceOf<T, I>>,
 	) -> DispatchResult {
-		ensure!(
-			vote.balance() <= T::Currency::total_balance(who),
-			Error::<T, I>::InsufficientFunds
-		);
+		ensure!(vote.balance() <= Self::voting_balance(who), Error::<T, I>::InsufficientFunds);
 		T::Polls::try_access_poll(poll_index, |poll_status| {
 			let (tally, class) = poll_status.ensure_ongoing().ok_or(Error::<T, I>::NotOngoing)?;
 			VotingFor::<T, I>::try_mutate(who, &class, |voting| {
@@ -556,7 +572,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	) -> Result<u32, DispatchError> {
 		ensure!(who != target, Error::<T, I>::Nonsense);
 		T::Polls::classes().binary_search(&class).map_err(|_| Error::<T, I>::BadClass)?;
-		ensure!(balance <= T::Currency::total_balance(&who), Error::<T, I>::InsufficientFunds);
+		ensure!(balance <= Self::voting_balance(&who), Error::<T, I>::InsufficientFunds);
 		let votes =
 			VotingFor::<T, I>::try_mutate(&who, &class, |voting| -> Result<u32, DispatchError> {
 				let old = sp_std::mem::replace(
@@ -630,7 +646,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	}
 
 	fn extend_lock(who: &T::AccountId, class: &ClassOf<T, I>, amount: BalanceOf<T, I>) {
-		ClassLocksFor::<T, I>::mutate(who, |locks| {
+		let lock_needed = ClassLocksFor::<T, I>::mutate(who, |locks| {
 			match locks.iter().position(|x| &x.0 == class) {
 				Some(i) => locks[i].1 = locks[i].1.max(amount),
 				None => {
@@ -643,6 +659,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 					);
 				},
 			}
+			locks.iter().map(|x| x.1).max().unwrap_or(Zero::zero())
 		});
 		T::Currency::extend_lock(
 			CONVICTION_VOTING_ID,
@@ -650,6 +667,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			amount,
 			WithdrawReasons::except(WithdrawReasons::RESERVE),
 		);
+		Self::set_voting_power_lock(who, lock_needed);
 	}
 
 	/// Rejig the lock on an account. It will never get more stringent (since that would indicate
@@ -682,5 +700,13 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				WithdrawReasons::except(WithdrawReasons::RESERVE),
 			);
 		}
+		Self::set_voting_power_lock(who, lock_needed);
+	}
+
+	/// Lock the part of `lock_needed` that is not covered by the balance of `who` with
+	/// [`Config::VotingPower`].
+	fn set_voting_power_lock(who: &T::AccountId, lock_needed: BalanceOf<T, I>) {
+		let uncovered = lock_needed.saturating_sub(T::Currency::total_balance(who));
+		T::VotingPower::set_lock(who, uncovered);
 	}
 }
@@ -22,6 +22,7 @@ use std::collections::BTreeMap;
 use frame_support::{
 	assert_noop, assert_ok, derive_impl, parameter_types,
 	traits::{ConstU32, ConstU64, Contains, Polling, VoteTally},
+	weights::Weight,
 };
 use sp_core::H256;
 use sp_runtime::{
@@ -189,6 +190,31 @@ impl Config for Test {
 	type WeightInfo = ();
 	type MaxTurnout = frame_support::traits::TotalIssuanceOf<Balances, Self::AccountId>;
 	type Polls = TestPolls;
+	type VotingPower = PooledStake;
+}
+
+parameter_types! {
+	pub static PooledStakeOf: BTreeMap<u64, u64> = BTreeMap::new();
+	pub static PooledStakeLocks: BTreeMap<u64, u64> = BTreeMap::new();
+}
+
+/// Voting power of stake that is kept outside of the account of the voter, e.g. in a pool.
+pub struct PooledStake;
+impl VotingPowerProvider<u64, u64> for PooledStake {
+	fn voting_power(who: &u64) -> u64 {
+		PooledStakeOf::get().get(who).copied().unwrap_or_default()
+	}
+
+	fn set_lock(who: &u64, amount: u64) {
+		PooledStakeLocks::mutate(|locks| match amount {
+			0 => locks.remove(who),
+			_ => locks.insert(*who, amount),
+		});
+	}
+
+	fn weight() -> Weight {
+		Weight::zero()
+	}
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -774,6 +800,79 @@ fn lock_aggregation_over_different_classes_with_casting_works() {
 	});
 }
 
+#[test]
+fn held_balance_can_be_used_to_vote() {
+	new_test_ext().execute_with(|| {
+		// most of the balance of 1 is held, e.g. by the staking system.
+		assert_ok!(Balances::reserve(&1, 8));
+		assert_eq!(Voting::voting_balance(&1), 10);
+
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 3, aye(10, 1)));
+		assert_eq!(tally(3), Tally::from_parts(10, 0, 10));
+
+		// the lock covers the held funds once they are released.
+		Balances::unreserve(&1, 8);
+		assert_eq!(Balances::usable_balance(1), 0);
+
+		assert_ok!(Voting::remove_vote(RuntimeOrigin::signed(1), None, 3));
+		assert_ok!(Voting::unlock(RuntimeOrigin::signed(1), class(3), 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
+
+#[test]
+fn external_voting_power_can_be_used_to_vote() {
+	new_test_ext().execute_with(|| {
+		// 1 has 10 in their account and 20 staked in a pool.
+		PooledStakeOf::mutate(|stake| stake.insert(1, 20));
+		assert_eq!(Voting::voting_balance(&1), 30);
+
+		assert_noop!(
+			Voting::vote(RuntimeOrigin::signed(1), 3, aye(31, 1)),
+			Error::<Test>::InsufficientFunds
+		);
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 3, aye(30, 1)));
+		assert_eq!(tally(3), Tally::from_parts(30, 0, 30));
+
+		// the voting power can also be delegated.
+		PooledStakeOf::mutate(|stake| stake.insert(2, 10));
+		assert_noop!(
+			Voting::delegate(RuntimeOrigin::signed(2), class(3), 3, Conviction::Locked1x, 31),
+			Error::<Test>::InsufficientFunds
+		);
+		assert_ok!(Voting::delegate(
+			RuntimeOrigin::signed(2),
+			class(3),
+			3,
+			Conviction::Locked1x,
+			30
+		));
+
+		// the free balance of the voter is locked as usual, the rest by the provider.
+		assert_eq!(Balances::usable_balance(1), 0);
+		assert_eq!(PooledStakeLocks::get().get(&1), Some(&20));
+		assert_eq!(PooledStakeLocks::get().get(&2), Some(&10));
+	});
+}
+
+#[test]
+fn external_voting_power_lock_is_removed_on_unlock() {
+	new_test_ext().execute_with(|| {
+		PooledStakeOf::mutate(|stake| stake.insert(1, 20));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 3, aye(15, 0)));
+		assert_eq!(PooledStakeLocks::get().get(&1), Some(&5));
+
+		// a bigger vote extends the lock.
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 3, aye(25, 0)));
+		assert_eq!(PooledStakeLocks::get().get(&1), Some(&15));
+
+		assert_ok!(Voting::remove_vote(RuntimeOrigin::signed(1), None, 3));
+		assert_ok!(Voting::unlock(RuntimeOrigin::signed(1), class(3), 1));
+		assert_eq!(PooledStakeLocks::get().get(&1), None);
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
+
 #[test]
 fn errors_with_vote_work() {
 	new_test_ext().execute_with(|| {
@@ -0,0 +1,497 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for pallet-delegated-staking.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok, traits::fungible::InspectHold};
+use pallet_staking::Error as StakingError;
+use sp_staking::{DelegationInterface, DelegationMigrator, Stake, StakerStatus};
+
+#[test]
+fn create_an_agent_with_first_delegator() {
+	ExtBuilder::default().build_and_execute(|| {
+		let agent: AccountId = 200;
+		let reward_account: AccountId = 201;
+		let delegator: AccountId = 202;
+
+		// set intention to accept delegation.
+		fund(&agent, 1000);
+		assert_ok!(DelegatedStaking::register_agent(
+			RawOrigin::Signed(agent).into(),
+			reward_account
+		));
+
+		// delegate to this account
+		fund(&delegator, 1000);
+		assert_ok!(DelegatedStaking::delegate_to_agent(
+			RawOrigin::Signed(delegator).into(),
+			agent,
+			100
+		));
+
+		// verify
+		assert!(DelegatedStaking::is_agent(&agent));
+		assert_eq!(DelegatedStaking::stakeable_balance(&agent), 100);
+		assert_eq!(
+			Balances::balance_on_hold(&HoldReason::StakingDelegation.into(), &delegator),
+			100
+		);
+		assert_eq!(DelegatedStaking::held_balance_of(&delegator), 100);
+
+		// the delegated funds are bonded as a virtual stake of the agent.
+		assert!(eq_stake(agent, 100, 100));
+		assert!(<Staking as StakingInterface>::is_virtual_staker(&agent));
+		assert_eq!(
+			pallet_staking::Payee::<T>::get(&agent),
+			Some(pallet_staking::RewardDestination::Account(reward_account))
+		);
+
+		assert_eq!(
+			events_since_last_call(),
+			vec![Event::Delegated { agent, delegator, amount: 100 }]
+		);
+	});
+}
+
+#[test]
+fn cannot_become_agent() {
+	ExtBuilder::default().build_and_execute(|| {
+		// cannot set reward account same as agent account
+		assert_noop!(
+			DelegatedStaking::register_agent(RawOrigin::Signed(100).into(), 100),
+			Error::<T>::InvalidRewardDestination
+		);
+
+		// an existing validator cannot become agent
+		assert_noop!(
+			DelegatedStaking::register_agent(
+				RawOrigin::Signed(mock::GENESIS_VALIDATOR).into(),
+				100
+			),
+			Error::<T>::AlreadyStaking
+		);
+
+		// an existing direct staker to `CoreStaking` cannot become an agent.
+		assert_noop!(
+			DelegatedStaking::register_agent(
+				RawOrigin::Signed(mock::GENESIS_NOMINATOR_ONE).into(),
+				100
+			),
+			Error::<T>::AlreadyStaking
+		);
+		assert_noop!(
+			DelegatedStaking::register_agent(
+				RawOrigin::Signed(mock::GENESIS_NOMINATOR_TWO).into(),
+				100
+			),
+			Error::<T>::AlreadyStaking
+		);
+
+		// an existing agent cannot register again.
+		assert_ok!(DelegatedStaking::register_agent(RawOrigin::Signed(300).into(), 301));
+		assert_noop!(
+			DelegatedStaking::register_agent(RawOrigin::Signed(300).into(), 302),
+			Error::<T>::NotAllowed
+		);
+	});
+}
+
+#[test]
+fn delegate_restrictions() {
+	ExtBuilder::default().build_and_execute(|| {
+		let agent_one = 200;
+		let delegator_one = 210;
+		fund(&agent_one, 100);
+		assert_ok!(DelegatedStaking::register_agent(
+			RawOrigin::Signed(agent_one).into(),
+			agent_one + 1
+		));
+		fund(&delegator_one, 200);
+		assert_ok!(DelegatedStaking::delegate_to_agent(
+			RawOrigin::Signed(delegator_one).into(),
+			agent_one,
+			100
+		));
+
+		let agent_two = 300;
+		let delegator_two = 310;
+		fund(&agent_two, 100);
+		assert_ok!(DelegatedStaking::register_agent(
+			RawOrigin::Signed(agent_two).into(),
+			agent_two + 1
+		));
+		fund(&delegator_two, 200);
+		assert_ok!(DelegatedStaking::delegate_to_agent(
+			RawOrigin::Signed(delegator_two).into(),
+			agent_two,
+			100
+		));
+
+		// agent one tries to delegate to agent 2
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(RawOrigin::Signed(agent_one).into(), agent_two, 10),
+			Error::<T>::InvalidDelegation
+		);
+
+		// delegator one tries to delegate to agent 2 as well (it already delegates to agent 1)
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(
+				RawOrigin::Signed(delegator_one).into(),
+				agent_two,
+				10
+			),
+			Error::<T>::InvalidDelegation
+		);
+
+		// cannot delegate to non agents.
+		let non_agent = 201;
+		// give it some funds
+		fund(&non_agent, 200);
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(
+				RawOrigin::Signed(delegator_one).into(),
+				non_agent,
+				10
+			),
+			Error::<T>::InvalidDelegation
+		);
+
+		// cannot delegate to a delegator
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(
+				RawOrigin::Signed(non_agent).into(),
+				delegator_one,
+				10
+			),
+			Error::<T>::NotAgent
+		);
+
+		// delegator cannot delegate to self
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(
+				RawOrigin::Signed(delegator_one).into(),
+				delegator_one,
+				10
+			),
+			Error::<T>::InvalidDelegation
+		);
+
+		// agent cannot delegate to self
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(RawOrigin::Signed(agent_one).into(), agent_one, 10),
+			Error::<T>::InvalidDelegation
+		);
+	});
+}
+
+#[test]
+fn delegate_and_release_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		let agent: AccountId = 200;
+		let reward_acc: AccountId = 201;
+
+		// delegators 300..305 delegate 100, 110, .., 150 each.
+		let delegators: Vec<AccountId> = (300..306).collect();
+		let total_delegated =
+			setup_delegation_stake(agent, reward_acc, delegators.clone(), 100, 10);
+		assert_eq!(total_delegated, 750);
+		assert!(eq_stake(agent, 750, 750));
+
+		// nothing to release before unbonding.
+		assert_noop!(
+			DelegatedStaking::release_delegation(RawOrigin::Signed(agent).into(), 300, 100, 0),
+			Error::<T>::NotEnoughFunds
+		);
+
+		// unbond some funds.
+		assert_ok!(<DelegatedStaking as StakingInterface>::unbond(&agent, 250));
+		assert!(eq_stake(agent, 750, 500));
+
+		// funds are not withdrawable before the bonding duration.
+		start_era(1);
+		assert_noop!(
+			DelegatedStaking::release_delegation(RawOrigin::Signed(agent).into(), 300, 100, 0),
+			Error::<T>::NotEnoughFunds
+		);
+
+		// after the bonding duration, delegators can be paid out.
+		start_era(1 + BondingDuration::get());
+		assert_ok!(DelegatedStaking::release_delegation(
+			RawOrigin::Signed(agent).into(),
+			300,
+			100,
+			0
+		));
+		// delegator 300 has nothing left delegated.
+		assert!(!DelegatedStaking::is_delegator(&300));
+		assert_eq!(held_balance(&300), 0);
+
+		// the rest of the withdrawn funds are unclaimed withdrawals.
+		let agent_ledger = get_agent(&agent).ledger;
+		assert_eq!(agent_ledger.total_delegated, 650);
+		assert_eq!(agent_ledger.unclaimed_withdrawals, 150);
+		assert!(eq_stake(agent, 500, 500));
+		assert_eq!(get_agent(&agent).total_unbonded(), 150);
+
+		// only the delegated amount can be released.
+		assert_noop!(
+			DelegatedStaking::release_delegation(RawOrigin::Signed(agent).into(), 301, 120, 0),
+			Error::<T>::NotEnoughFunds
+		);
+		// only the agent of the delegation can release it.
+		assert_noop!(
+			DelegatedStaking::release_delegation(RawOrigin::Signed(300).into(), 301, 10, 0),
+			Error::<T>::NotAgent
+		);
+
+		assert_ok!(DelegatedStaking::release_delegation(
+			RawOrigin::Signed(agent).into(),
+			305,
+			150,
+			0
+		));
+		assert_eq!(held_balance(&305), 0);
+		assert_eq!(get_agent(&agent).ledger.unclaimed_withdrawals, 0);
+
+		// only the withdrawn amount can be released.
+		assert_noop!(
+			DelegatedStaking::release_delegation(RawOrigin::Signed(agent).into(), 304, 140, 0),
+			Error::<T>::NotEnoughFunds
+		);
+	});
+}
+
+#[test]
+fn agent_is_killed_after_last_release() {
+	ExtBuilder::default().build_and_execute(|| {
+		let agent: AccountId = 200;
+		setup_delegation_stake(agent, 201, vec![300, 301], 100, 0);
+
+		assert_ok!(<DelegatedStaking as StakingInterface>::chill(&agent));
+		assert_ok!(<DelegatedStaking as StakingInterface>::unbond(&agent, 200));
+		start_era(BondingDuration::get());
+
+		assert_ok!(DelegatedStaking::release_delegation(
+			RawOrigin::Signed(agent).into(),
+			300,
+			100,
+			0
+		));
+		// the stake is killed in core staking.
+		assert!(Staking::status(&agent).is_err());
+		assert!(!<Staking as StakingInterface>::is_virtual_staker(&agent));
+
+		assert_ok!(DelegatedStaking::release_delegation(
+			RawOrigin::Signed(agent).into(),
+			301,
+			100,
+			0
+		));
+		// nothing left, agent is killed.
+		assert!(!DelegatedStaking::is_agent(&agent));
+		assert_eq!(Delegators::<T>::count(), 0);
+	});
+}
+
+#[test]
+fn bond_extra_requires_delegated_funds() {
+	ExtBuilder::default().build_and_execute(|| {
+		let agent: AccountId = 200;
+		setup_delegation_stake(agent, 201, vec![300], 100, 0);
+
+		// agent's own funds can't be staked.
+		assert_noop!(
+			<DelegatedStaking as StakingInterface>::bond_extra(&agent, 10),
+			Error::<T>::NotEnoughFunds
+		);
+
+		// delegating more increases the stake.
+		fund(&301, 100);
+		assert_ok!(DelegatedStaking::delegate_to_agent(RawOrigin::Signed(301).into(), agent, 50));
+		assert!(eq_stake(agent, 150, 150));
+	});
+}
+
+#[test]
+fn pending_slash_is_applied_lazily() {
+	ExtBuilder::default().build_and_execute(|| {
+		let agent: AccountId = 200;
+		let reporter: AccountId = 400;
+		setup_delegation_stake(agent, 201, vec![300, 301], 500, 0);
+		assert_ok!(<DelegatedStaking as StakingInterface>::nominate(
+			&agent,
+			vec![GENESIS_VALIDATOR]
+		));
+
+		// nothing to slash yet.
+		assert!(!DelegatedStaking::has_pending_slash(&agent));
+		assert_noop!(
+			<DelegatedStaking as DelegationInterface>::delegator_slash(&agent, &300, 10, None),
+			Error::<T>::NothingToSlash
+		);
+
+		// slash the agent in core staking.
+		pallet_staking::slashing::do_slash::<T>(
+			&agent,
+			100,
+			&mut Default::default(),
+			&mut Default::default(),
+			0,
+		);
+
+		// the slash is only recorded for the agent, funds of delegators are untouched.
+		assert!(eq_stake(agent, 900, 900));
+		assert!(DelegatedStaking::has_pending_slash(&agent));
+		assert_eq!(get_agent(&agent).ledger.pending_slash, 100);
+		assert_eq!(DelegatedStaking::agent_balance(&agent), 900);
+		assert_eq!(held_balance(&300), 500);
+
+		// a slash bigger than the delegation can't be applied.
+		assert_noop!(
+			<DelegatedStaking as DelegationInterface>::delegator_slash(&agent, &300, 501, None),
+			Error::<T>::NotEnoughFunds
+		);
+
+		// apply the slash to delegator 300 and reward the reporter.
+		let issuance = Balances::total_issuance();
+		assert_ok!(<DelegatedStaking as DelegationInterface>::delegator_slash(
+			&agent,
+			&300,
+			60,
+			Some(reporter)
+		));
+		assert_eq!(held_balance(&300), 440);
+		assert_eq!(DelegatedStaking::delegator_balance(&300), 440);
+		assert_eq!(get_agent(&agent).ledger.pending_slash, 40);
+		// 10% of the slash goes to the reporter, the rest is burned.
+		assert_eq!(Balances::free_balance(reporter), 6);
+		assert_eq!(Balances::total_issuance(), issuance - 54);
+
+		// apply the rest to delegator 301.
+		assert_ok!(<DelegatedStaking as DelegationInterface>::delegator_slash(
+			&agent, &301, 40, None
+		));
+		assert_eq!(held_balance(&301), 460);
+		assert!(!DelegatedStaking::has_pending_slash(&agent));
+		assert_eq!(get_agent(&agent).ledger.total_delegated, 900);
+	});
+}
+
+#[test]
+fn migrate_nominator_to_agent() {
+	ExtBuilder::default().build_and_execute(|| {
+		let agent = GENESIS_NOMINATOR_TWO;
+		let reward_acc = 201;
+		let proxy_delegator = DelegatedStaking::generate_proxy_delegator(&agent);
+
+		// the nominator has 2000 funds, 200 of which are staked.
+		assert_eq!(Balances::total_balance(&agent), 2000);
+		assert_eq!(Staking::stake(&agent).unwrap(), Stake { total: 200, active: 200 });
+
+		// cannot migrate with the stash as the reward account.
+		assert_noop!(
+			DelegatedStaking::migrate_nominator_to_agent(&agent, &agent),
+			Error::<T>::InvalidRewardDestination
+		);
+		// only direct stakers can migrate.
+		assert_noop!(
+			DelegatedStaking::migrate_nominator_to_agent(&300, &reward_acc),
+			Error::<T>::NotAllowed
+		);
+
+		assert_ok!(DelegatedStaking::migrate_nominator_to_agent(&agent, &reward_acc));
+
+		// all the funds are moved to the proxy delegator and delegated to the agent.
+		assert_eq!(Balances::total_balance(&agent), 0);
+		assert_eq!(held_balance(&proxy_delegator), 2000);
+		assert!(<Staking as StakingInterface>::is_virtual_staker(&agent));
+		assert_eq!(
+			Staking::status(&agent).unwrap(),
+			StakerStatus::Nominator(vec![GENESIS_VALIDATOR])
+		);
+		// the free funds of the nominator are unclaimed withdrawals.
+		let agent_ledger = get_agent(&agent).ledger;
+		assert_eq!(agent_ledger.total_delegated, 2000);
+		assert_eq!(agent_ledger.unclaimed_withdrawals, 1800);
+		assert!(eq_stake(agent, 200, 200));
+
+		// migrate the delegation to real delegators.
+		assert_noop!(
+			<DelegatedStaking as DelegationMigrator>::migrate_delegation(&agent, &300, 2001),
+			Error::<T>::NotEnoughFunds
+		);
+		assert_noop!(
+			<DelegatedStaking as DelegationMigrator>::migrate_delegation(
+				&agent,
+				&GENESIS_NOMINATOR_ONE,
+				100
+			),
+			Error::<T>::AlreadyStaking
+		);
+		for delegator in 300..305 {
+			// delegators are existing accounts.
+			fund(&delegator, ExistentialDeposit::get());
+			assert_ok!(<DelegatedStaking as DelegationMigrator>::migrate_delegation(
+				&agent, &delegator, 400
+			));
+			assert_eq!(held_balance(&delegator), 400);
+			assert_eq!(DelegatedStaking::delegator_balance(&delegator), 400);
+		}
+
+		// the proxy delegator is fully drained.
+		assert_eq!(held_balance(&proxy_delegator), 0);
+		assert!(!DelegatedStaking::is_delegator(&proxy_delegator));
+		// the agent ledger is unaffected.
+		assert_eq!(get_agent(&agent).ledger.total_delegated, 2000);
+
+		// a migrated delegator is like any other delegator.
+		assert_noop!(
+			<DelegatedStaking as DelegationMigrator>::migrate_delegation(&agent, &300, 100),
+			Error::<T>::NotAllowed
+		);
+		assert_ok!(DelegatedStaking::release_delegation(
+			RawOrigin::Signed(agent).into(),
+			300,
+			400,
+			0
+		));
+		assert_eq!(Balances::free_balance(300), 400 + ExistentialDeposit::get());
+	});
+}
+
+#[test]
+fn virtual_agent_cannot_use_direct_staking() {
+	ExtBuilder::default().build_and_execute(|| {
+		let agent: AccountId = 200;
+		setup_delegation_stake(agent, 201, vec![300], 100, 0);
+
+		assert_noop!(
+			Staking::bond_extra(RawOrigin::Signed(agent).into(), 10),
+			StakingError::<T>::VirtualStakerNotAllowed
+		);
+		assert_noop!(
+			Staking::reap_stash(RawOrigin::Signed(300).into(), agent, 0),
+			StakingError::<T>::VirtualStakerNotAllowed
+		);
+		assert_noop!(
+			<DelegatedStaking as StakingInterface>::update_payee(&agent, &agent),
+			Error::<T>::InvalidRewardDestination
+		);
+	});
+}
@@ -33,6 +33,11 @@ use sp_runtime::InnerOf;
 
 const SEED: u32 = 999;
 
+/// The most significant page, which all of the benchmarks operate on.
+fn msp<T: Config>() -> PageIndex {
+	<MultiPhase<T> as ElectionProviderBase>::msp()
+}
+
 /// Creates a **valid** solution with exactly the given size.
 ///
 /// The snapshot is also created internally.
@@ -110,12 +115,15 @@ fn solution_with_size<T: Config>(
 	assert_eq!(all_voters.len() as u32, size.voters);
 	assert_eq!(winners.len() as u32, desired_targets);
 
-	<SnapshotMetadata<T>>::put(SolutionOrSnapshotSize {
-		voters: all_voters.len() as u32,
-		targets: targets.len() as u32,
-	});
+	<SnapshotMetadata<T>>::insert(
+		msp::<T>(),
+		SolutionOrSnapshotSize { voters: all_voters.len() as u32, targets: targets.len() as u32 },
+	);
 	<DesiredTargets<T>>::put(desired_targets);
-	<Snapshot<T>>::put(RoundSnapshot { voters: all_voters.clone(), targets: targets.clone() });
+	<Snapshot<T>>::insert(
+		msp::<T>(),
+		RoundSnapshot { voters: all_voters.clone(), targets: targets.clone() },
+	);
 
 	// write the snapshot to staking or whoever is the data provider, in case it is needed further
 	// down the road.
@@ -153,7 +161,7 @@ fn solution_with_size<T: Config>(
 		score.minimal_stake > 0,
 		"score is zero, this probably means that the stakes are not set."
 	);
-	Ok(RawSolution { solution, score, round })
+	Ok(RawSolution { solution, score, round, page: msp::<T>() })
 }
 
 fn set_up_data_provider<T: Config>(v: u32, t: u32) {
@@ -200,23 +208,23 @@ frame_benchmarking::benchmarks! {
 	}
 
 	on_initialize_open_signed {
-		assert!(<MultiPhase<T>>::snapshot().is_none());
+		assert!(<MultiPhase<T>>::snapshot(msp::<T>()).is_none());
 		assert!(<MultiPhase<T>>::current_phase().is_off());
 	}: {
 		<MultiPhase<T>>::phase_transition(Phase::Signed);
 	} verify {
-		assert!(<MultiPhase<T>>::snapshot().is_none());
+		assert!(<MultiPhase<T>>::snapshot(msp::<T>()).is_none());
 		assert!(<MultiPhase<T>>::current_phase().is_signed());
 	}
 
 	on_initialize_open_unsigned {
-		assert!(<MultiPhase<T>>::snapshot().is_none());
+		assert!(<MultiPhase<T>>::snapshot(msp::<T>()).is_none());
 		assert!(<MultiPhase<T>>::current_phase().is_off());
 	}: {
 		let now = frame_system::Pallet::<T>::block_number();
 		<MultiPhase<T>>::phase_transition(Phase::Unsigned((true, now)));
 	} verify {
-		assert!(<MultiPhase<T>>::snapshot().is_none());
+		assert!(<MultiPhase<T>>::snapshot(msp::<T>()).is_none());
 		assert!(<MultiPhase<T>>::current_phase().is_unsigned());
 	}
 
@@ -234,6 +242,7 @@ frame_benchmarking::benchmarks! {
 		assert_eq!(T::Currency::free_balance(&receiver), T::Currency::minimum_balance());
 	}: {
 		<MultiPhase<T>>::finalize_signed_phase_accept_solution(
+			msp::<T>(),
 			ready,
 			&receiver,
 			deposit,
@@ -272,19 +281,20 @@ frame_benchmarking::benchmarks! {
 		// we don't directly need the data-provider to be populated, but it is just easy to use it.
 		set_up_data_provider::<T>(v, t);
 		// default bounds are unbounded.
-		let targets = T::DataProvider::electable_targets(DataProviderBounds::default())?;
-		let voters = T::DataProvider::electing_voters(DataProviderBounds::default())?;
+		let targets = T::DataProvider::electable_targets(DataProviderBounds::default(), msp::<T>())?;
+		let voters = T::DataProvider::electing_voters(DataProviderBounds::default(), msp::<T>())?;
 		let desired_targets = T::DataProvider::desired_targets()?;
-		assert!(<MultiPhase<T>>::snapshot().is_none());
+		assert!(<MultiPhase<T>>::snapshot(msp::<T>()).is_none());
 	}: {
-		<MultiPhase::<T>>::create_snapshot_internal(targets, voters, desired_targets)
+		<MultiPhase::<T>>::create_snapshot_internal(msp::<T>(), targets, voters, desired_targets)
 	} verify {
-		assert!(<MultiPhase<T>>::snapshot().is_some());
-		assert_eq!(<MultiPhase<T>>::snapshot_metadata().ok_or("metadata missing")?.voters, v);
-		assert_eq!(<MultiPhase<T>>::snapshot_metadata().ok_or("metadata missing")?.targets, t);
+		assert!(<MultiPhase<T>>::snapshot(msp::<T>()).is_some());
+		assert_eq!(<MultiPhase<T>>::snapshot_metadata(msp::<T>()).ok_or("metadata missing")?.voters, v);
+		assert_eq!(<MultiPhase<T>>::snapshot_metadata(msp::<T>()).ok_or("metadata missing")?.targets, t);
 	}
 
-	// a call to `<Pallet as ElectionProvider>::elect` where we only return the queued solution.
+	// a call to `<Pallet as ElectionProvider>::elect` where we only return the queued solution of
+	// the most significant page.
 	elect_queued {
 		// number of assignments, i.e. solution.len(). This means the active nominators, thus must be
 		// a subset of `v`.
@@ -304,20 +314,22 @@ frame_benchmarking::benchmarks! {
 				.map_err(<&str>::from)?;
 		<CurrentPhase<T>>::put(Phase::Signed);
 		// assume a queued solution is stored, regardless of where it comes from.
-		<QueuedSolution<T>>::put(ready_solution);
+		<QueuedSolution<T>>::insert(msp::<T>(), ready_solution);
 
 		// these are set by the `solution_with_size` function.
 		assert!(<DesiredTargets<T>>::get().is_some());
-		assert!(<Snapshot<T>>::get().is_some());
-		assert!(<SnapshotMetadata<T>>::get().is_some());
+		assert!(<Snapshot<T>>::contains_key(msp::<T>()));
+		assert!(<SnapshotMetadata<T>>::contains_key(msp::<T>()));
 	}: {
-		assert_ok!(<MultiPhase<T> as ElectionProvider>::elect());
+		assert_ok!(<MultiPhase<T> as ElectionProvider>::elect(msp::<T>()));
 	} verify {
-		assert!(<MultiPhase<T>>::queued_solution().is_none());
-		assert!(<DesiredTargets<T>>::get().is_none());
-		assert!(<Snapshot<T>>::get().is_none());
-		assert!(<SnapshotMetadata<T>>::get().is_none());
-		assert_eq!(<CurrentPhase<T>>::get(), <Phase<frame_system::pallet_prelude::BlockNumberFor::<T>>>::Off);
+		assert!(<MultiPhase<T>>::queued_solution(msp::<T>()).is_none());
+		if msp::<T>() == <MultiPhase<T> as ElectionProviderBase>::lsp() {
+			assert!(<DesiredTargets<T>>::get().is_none());
+			assert!(!<Snapshot<T>>::contains_key(msp::<T>()));
+			assert!(!<SnapshotMetadata<T>>::contains_key(msp::<T>()));
+			assert_eq!(<CurrentPhase<T>>::get(), <Phase<frame_system::pallet_prelude::BlockNumberFor::<T>>>::Off);
+		}
 	}
 
 	submit {
@@ -326,12 +338,13 @@ frame_benchmarking::benchmarks! {
 		<MultiPhase<T>>::phase_transition(Phase::Signed);
 		<Round<T>>::put(1);
 
-		let mut signed_submissions = SignedSubmissions::<T>::get();
+		let mut signed_submissions = SignedSubmissions::<T>::get(msp::<T>());
 
 		// Insert `max` submissions
 		for i in 0..(T::SignedMaxSubmissions::get() - 1) {
 			let raw_solution = RawSolution {
 				score: ElectionScore { minimal_stake: 10_000_000u128 + (i as u128), ..Default::default() },
+				page: msp::<T>(),
 				..Default::default()
 			};
 			let signed_submission = SignedSubmission {
@@ -347,19 +360,20 @@ frame_benchmarking::benchmarks! {
 		// this score will eject the weakest one.
 		let solution = RawSolution {
 			score: ElectionScore { minimal_stake: 10_000_000u128 + 1, ..Default::default() },
+			page: msp::<T>(),
 			..Default::default()
 		};
 
 		let caller = frame_benchmarking::whitelisted_caller();
 		let deposit = MultiPhase::<T>::deposit_for(
 			&solution,
-			MultiPhase::<T>::snapshot_metadata().unwrap_or_default(),
+			MultiPhase::<T>::snapshot_metadata(msp::<T>()).unwrap_or_default(),
 		);
 		T::Currency::make_free_balance_be(&caller,  T::Currency::minimum_balance() * 1000u32.into() + deposit);
 
 	}: _(RawOrigin::Signed(caller), Box::new(solution))
 	verify {
-		assert!(<MultiPhase<T>>::signed_submissions().len() as u32 == T::SignedMaxSubmissions::get());
+		assert!(<MultiPhase<T>>::signed_submissions(msp::<T>()).len() as u32 == T::SignedMaxSubmissions::get());
 	}
 
 	submit_unsigned {
@@ -379,11 +393,11 @@ frame_benchmarking::benchmarks! {
 		let witness = SolutionOrSnapshotSize { voters: v, targets: t };
 		let raw_solution = solution_with_size::<T>(witness, a, d)?;
 
-		assert!(<MultiPhase<T>>::queued_solution().is_none());
+		assert!(<MultiPhase<T>>::queued_solution(msp::<T>()).is_none());
 		<CurrentPhase<T>>::put(Phase::Unsigned((true, 1u32.into())));
 	}: _(RawOrigin::None, Box::new(raw_solution), witness)
 	verify {
-		assert!(<MultiPhase<T>>::queued_solution().is_some());
+		assert!(<MultiPhase<T>>::queued_solution(msp::<T>()).is_some());
 	}
 
 	// This is checking a valid solution. The worse case is indeed a valid solution.
@@ -449,13 +463,13 @@ frame_benchmarking::benchmarks! {
 		let t = T::BenchmarkingConfig::MAXIMUM_TARGETS;
 
 		set_up_data_provider::<T>(v, t);
-		assert!(<MultiPhase<T>>::snapshot().is_none());
+		assert!(<MultiPhase<T>>::snapshot(msp::<T>()).is_none());
 	}: {
-		<MultiPhase::<T>>::create_snapshot().map_err(|_| "could not create snapshot")?;
+		<MultiPhase::<T>>::create_snapshot_page(msp::<T>()).map_err(|_| "could not create snapshot")?;
 	} verify {
-		assert!(<MultiPhase<T>>::snapshot().is_some());
-		assert_eq!(<MultiPhase<T>>::snapshot_metadata().ok_or("snapshot missing")?.voters, v);
-		assert_eq!(<MultiPhase<T>>::snapshot_metadata().ok_or("snapshot missing")?.targets, t);
+		assert!(<MultiPhase<T>>::snapshot(msp::<T>()).is_some());
+		assert_eq!(<MultiPhase<T>>::snapshot_metadata(msp::<T>()).ok_or("snapshot missing")?.voters, v);
+		assert_eq!(<MultiPhase<T>>::snapshot_metadata(msp::<T>()).ok_or("snapshot missing")?.targets, t);
 	}
 
 	#[extra]
@@ -480,7 +494,7 @@ frame_benchmarking::benchmarks! {
 		// assignments
 		let witness = SolutionOrSnapshotSize { voters: v, targets: t };
 		let RawSolution { solution, .. } = solution_with_size::<T>(witness, a, d)?;
-		let RoundSnapshot { voters, targets } = MultiPhase::<T>::snapshot().ok_or("snapshot missing")?;
+		let RoundSnapshot { voters, targets } = MultiPhase::<T>::snapshot(msp::<T>()).ok_or("snapshot missing")?;
 		let voter_at = helpers::voter_at_fn::<T::MinerConfig>(&voters);
 		let target_at = helpers::target_at_fn::<T::MinerConfig>(&targets);
 		let mut assignments = solution.into_assignment(voter_at, target_at).expect("solution generated by `solution_with_size` must be valid.");
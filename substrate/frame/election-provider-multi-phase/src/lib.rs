@@ -46,6 +46,37 @@
 //! have length zero, then the pallet essentially runs only the fallback strategy, denoted by
 //! [`Config::Fallback`].
 //!
+//! ### Multi-page elections
+//!
+//! If [`Config::Pages`] is more than one, the election is performed over multiple pages. The
+//! snapshot is then fetched from the data provider one page per block, starting from the most
+//! significant page, in the blocks right before the signed phase ([`Phase::Snapshot`]):
+//!
+//! ```ignore
+//!                 <--T::Pages - 1-->                                          elect(msp..=lsp)
+//!                 +                 +   <--T::SignedPhase-->  +  <--T::UnsignedPhase-->   +
+//!   +---------------------------------------------------------------------------------------+
+//!    Phase::Off   + Phase::Snapshot +       Phase::Signed     +      Phase::Unsigned      +
+//! ```
+//!
+//! Each page of the snapshot contains its own voters, and all of the targets. Solutions are
+//! submitted, queued and verified per page, each [`RawSolution`] referring to a single page. The
+//! solution of the most significant page elects exactly [`DesiredTargets`] winners, while the
+//! solutions of the other pages may only back a subset of them.
+//!
+//! At the end of the signed phase, only the signed submissions of the most significant page are
+//! verified. The submissions of each of the remaining pages are verified in the subsequent blocks,
+//! one page per block. Unsigned solutions of a page are only accepted once its signed submissions
+//! are verified.
+//!
+//! [`ElectionProvider::elect`] must then be called once per page, from the most significant page
+//! down to page `0`. The winners of the most significant page are stored in [`ElectedWinners`],
+//! and the remaining pages are trimmed to only contain backings of these winners. The round only
+//! ends once the least significant page is handed out. If no solution is queued for a page other
+//! than the most significant one, the election fails, unless the winners were computed by
+//! [`Config::Fallback`] or in the emergency phase: these compute the whole election at once, hence
+//! the remaining pages are handed out empty.
+//!
 //! ### Signed Phase
 //!
 //! In the signed phase, solutions (of type [`RawSolution`]) are submitted and queued on chain. A
@@ -128,9 +159,9 @@
 //! proceeds to the [`Phase::Emergency`]. During this phase, any solution can be submitted from
 //! [`Config::ForceOrigin`], without any checking, via [`Pallet::set_emergency_election_result`]
 //! transaction. Hence, `[`Config::ForceOrigin`]` should only be set to a trusted origin, such as
-//! the council or root. Once submitted, the forced solution is kept in [`QueuedSolution`] until the
-//! next call to `T::ElectionProvider::elect`, where it is returned and [`Phase`] goes back to
-//! `Off`.
+//! the council or root. Once submitted, the forced solution is kept in [`QueuedSolution`] of the
+//! most significant page until the next call to `T::ElectionProvider::elect`, where it is returned
+//! and [`Phase`] goes back to `Off` once the remaining pages are handed out.
 //!
 //! This implies that the user of this pallet (i.e. a staking pallet) should re-try calling
 //! `T::ElectionProvider::elect` in case of error, until `OK(_)` is returned.
@@ -158,8 +189,9 @@
 //! is as follows:
 //!
 //! 0. **all** of the used indices must be correct.
-//! 1. present *exactly* correct number of winners.
-//! 2. any assignment is checked to match with [`RoundSnapshot::voters`].
+//! 1. present *exactly* correct number of winners for the most significant page, or a subset of the
+//!    winners of the most significant page for any other page.
+//! 2. any assignment is checked to match with [`RoundSnapshot::voters`] of its page.
 //! 3. the claimed score is valid, based on the fixed point arithmetic accuracy.
 //!
 //! ## Accuracy
@@ -233,14 +265,14 @@ use codec::{Decode, Encode};
 use frame_election_provider_support::{
 	bounds::{CountBound, ElectionBounds, ElectionBoundsBuilder, SizeBound},
 	BoundedSupportsOf, DataProviderBounds, ElectionDataProvider, ElectionProvider,
-	ElectionProviderBase, InstantElectionProvider, NposSolution,
+	ElectionProviderBase, InstantElectionProvider, NposSolution, PageIndex,
 };
 use frame_support::{
 	dispatch::DispatchClass,
 	ensure,
 	traits::{Currency, DefensiveResult, Get, OnUnbalanced, ReservableCurrency},
 	weights::Weight,
-	DefaultNoBound, EqNoBound, PartialEqNoBound,
+	BoundedVec, DefaultNoBound, EqNoBound, PartialEqNoBound,
 };
 use frame_system::{ensure_none, offchain::SendTransactionTypes, pallet_prelude::BlockNumberFor};
 use scale_info::TypeInfo;
@@ -256,7 +288,7 @@ use sp_runtime::{
 	},
 	DispatchError, ModuleError, PerThing, Perbill, RuntimeDebug, SaturatedConversion,
 };
-use sp_std::prelude::*;
+use sp_std::{collections::btree_set::BTreeSet, prelude::*};
 
 #[cfg(feature = "try-runtime")]
 use sp_runtime::TryRuntimeError;
@@ -336,6 +368,13 @@ pub enum Phase<Bn> {
 	/// After that, the only way to leave this phase is through a successful
 	/// `T::ElectionProvider::elect`.
 	Emergency,
+	/// The snapshot is being created over multiple blocks. The inner value is the index of the
+	/// next page that should be fetched from the data provider.
+	///
+	/// This phase is only ever used if [`Config::Pages`] is more than one. The most significant
+	/// page is fetched when transitioning out of [`Phase::Off`], and each subsequent block fetches
+	/// one more page, until the least significant page is fetched and the signed phase starts.
+	Snapshot(PageIndex),
 }
 
 impl<Bn> Default for Phase<Bn> {
@@ -374,6 +413,11 @@ impl<Bn: PartialEq + Eq> Phase<Bn> {
 	pub fn is_off(&self) -> bool {
 		matches!(self, Phase::Off)
 	}
+
+	/// Whether the phase is creating a multi-page snapshot or not.
+	pub fn is_snapshot(&self) -> bool {
+		matches!(self, Phase::Snapshot(_))
+	}
 }
 
 /// The type of `Computation` that provided this election data.
@@ -411,12 +455,14 @@ pub struct RawSolution<S> {
 	pub score: ElectionScore,
 	/// The round at which this solution should be submitted.
 	pub round: u32,
+	/// The page of the snapshot that this solution is referring to.
+	pub page: PageIndex,
 }
 
 impl<C: Default> Default for RawSolution<C> {
 	fn default() -> Self {
 		// Round 0 is always invalid, only set this to 1.
-		Self { round: 1, solution: Default::default(), score: Default::default() }
+		Self { round: 1, solution: Default::default(), score: Default::default(), page: 0 }
 	}
 }
 
@@ -450,10 +496,11 @@ where
 	pub compute: ElectionCompute,
 }
 
-/// A snapshot of all the data that is needed for en entire round. They are provided by
-/// [`ElectionDataProvider`] and are kept around until the round is finished.
+/// A snapshot of all the data that is needed for a single page of an entire round. They are
+/// provided by [`ElectionDataProvider`] and are kept around until the round is finished.
 ///
-/// These are stored together because they are often accessed together.
+/// These are stored together because they are often accessed together. Each page contains its
+/// own voters and all of the targets, such that any page can be solved independently.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, Default, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 pub struct RoundSnapshot<AccountId, DataProvider> {
@@ -465,7 +512,7 @@ pub struct RoundSnapshot<AccountId, DataProvider> {
 
 /// Encodes the length of a solution or a snapshot.
 ///
-/// This is stored automatically on-chain, and it contains the **size of the entire snapshot**.
+/// This is stored automatically on-chain, and it contains the **size of an entire snapshot page**.
 /// This is also used in dispatchables as weight witness data and should **only contain the size of
 /// the presented solution**, not the entire snapshot.
 #[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, Debug, Default, TypeInfo)]
@@ -556,6 +603,10 @@ pub enum FeasibilityError {
 	///
 	/// Should never happen under correct configurations.
 	BoundedConversionFailed,
+	/// The page of the solution does not exist.
+	InvalidPage,
+	/// A winner of a non-most-significant page is not a winner of the most significant page.
+	InvalidWinner,
 }
 
 impl From<sp_npos_elections::Error> for FeasibilityError {
@@ -663,11 +714,24 @@ pub mod pallet {
 		/// size.
 		type SignedDepositBase: Convert<usize, BalanceOf<Self>>;
 
-		/// The maximum number of electing voters and electable targets to put in the snapshot.
-		/// At the moment, snapshots are only over a single block, but once multi-block elections
-		/// are introduced they will take place over multiple blocks.
+		/// The maximum number of electing voters and electable targets to put in each page of the
+		/// snapshot.
+		///
+		/// If [`Config::Pages`] is more than one, the snapshot is created over multiple blocks,
+		/// and these bounds are applied to each page separately.
 		type ElectionBounds: Get<ElectionBounds>;
 
+		/// The number of pages of the election.
+		///
+		/// The snapshot is created over `Pages` blocks, one page per block, right before the
+		/// signed phase. Solutions are submitted and verified per page, and
+		/// [`ElectionProvider::elect`] is expected to be called once per page, from the most
+		/// significant page down to page `0`.
+		///
+		/// Must be at least `1`. If set to `1`, the election is performed over a single page.
+		#[pallet::constant]
+		type Pages: Get<PageIndex>;
+
 		/// Handler for the slashed deposits.
 		type SlashHandler: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
@@ -745,6 +809,9 @@ pub mod pallet {
 			let signed_deadline = T::SignedPhase::get() + T::UnsignedPhase::get();
 			let unsigned_deadline = T::UnsignedPhase::get();
 
+			// all but the least significant page are fetched before the signed phase starts.
+			let snapshot_blocks: BlockNumberFor<T> = Self::msp().into();
+
 			let remaining = next_election - now;
 			let current_phase = Self::current_phase();
 
@@ -753,23 +820,59 @@ pub mod pallet {
 				"current phase {:?}, next election {:?}, metadata: {:?}",
 				current_phase,
 				next_election,
-				Self::snapshot_metadata()
+				Self::snapshot_metadata(Self::msp())
 			);
 			match current_phase {
-				Phase::Off if remaining <= signed_deadline && remaining > unsigned_deadline => {
+				Phase::Off
+					if remaining <= signed_deadline + snapshot_blocks &&
+						remaining > unsigned_deadline =>
+				{
 					// NOTE: if signed-phase length is zero, second part of the if-condition fails.
-					match Self::create_snapshot() {
-						Ok(_) => {
-							Self::phase_transition(Phase::Signed);
-							T::WeightInfo::on_initialize_open_signed()
-						},
-						Err(why) => {
-							// Not much we can do about this at this point.
-							log!(warn, "failed to open signed phase due to {:?}", why);
-							T::WeightInfo::on_initialize_nothing()
-						},
+					if T::Pages::get() > 1 {
+						match Self::create_snapshot_page(Self::msp()) {
+							Ok(_) => {
+								Self::phase_transition(Phase::Snapshot(Self::msp() - 1));
+								T::WeightInfo::on_initialize_open_signed()
+							},
+							Err(why) => {
+								log!(warn, "failed to start the paged snapshot due to {:?}", why);
+								T::WeightInfo::on_initialize_nothing()
+							},
+						}
+					} else {
+						match Self::create_snapshot() {
+							Ok(_) => {
+								Self::phase_transition(Phase::Signed);
+								T::WeightInfo::on_initialize_open_signed()
+							},
+							Err(why) => {
+								// Not much we can do about this at this point.
+								log!(warn, "failed to open signed phase due to {:?}", why);
+								T::WeightInfo::on_initialize_nothing()
+							},
+						}
 					}
 				},
+				Phase::Snapshot(page) => match Self::create_snapshot_page(page) {
+					Ok(_) if page > Self::lsp() => {
+						Self::phase_transition(Phase::Snapshot(page - 1));
+						T::WeightInfo::on_initialize_open_signed()
+					},
+					Ok(_) if remaining > unsigned_deadline => {
+						Self::phase_transition(Phase::Signed);
+						T::WeightInfo::on_initialize_open_signed()
+					},
+					Ok(_) => {
+						// we are already late, skip the signed phase altogether.
+						Self::phase_transition(Phase::Unsigned((true, now)));
+						T::WeightInfo::on_initialize_open_unsigned()
+					},
+					Err(why) => {
+						// try again in the next block.
+						log!(warn, "failed to create snapshot page {} due to {:?}", page, why);
+						T::WeightInfo::on_initialize_nothing()
+					},
+				},
 				Phase::Signed | Phase::Off
 					if remaining <= unsigned_deadline && remaining > Zero::zero() =>
 				{
@@ -785,7 +888,10 @@ pub mod pallet {
 						//     is a guard against the case that `elect` is called prematurely. This
 						//     adds a small amount of overhead, but that is unfortunately
 						//     unavoidable.
-						let _ = Self::finalize_signed_phase();
+						//   - if there are multiple pages, only the signed submissions of the most
+						//     significant page are processed here, and the rest are processed in
+						//     the subsequent blocks, one page per block.
+						let _ = Self::finalize_signed_phase(Self::msp());
 						// In the future we can consider disabling the unsigned phase if the signed
 						// phase completes successfully, but for now we're enabling it
 						// unconditionally as a defensive measure.
@@ -812,6 +918,14 @@ pub mod pallet {
 						T::WeightInfo::on_initialize_open_unsigned()
 					}
 				},
+				Phase::Unsigned((true, opened))
+					if now > opened && now - opened <= snapshot_blocks =>
+				{
+					// process the signed submissions of the remaining pages, one page per block.
+					let page = Self::msp().saturating_sub((now - opened).saturated_into());
+					let (weight, _) = Self::finalize_signed_phase_internal(page);
+					weight.saturating_add(T::WeightInfo::on_initialize_nothing())
+				},
 				_ => T::WeightInfo::on_initialize_nothing(),
 			}
 		}
@@ -922,8 +1036,9 @@ pub mod pallet {
 			Self::unsigned_pre_dispatch_checks(&raw_solution).expect(error_message);
 
 			// Ensure witness was correct.
+			let page = raw_solution.page;
 			let SolutionOrSnapshotSize { voters, targets } =
-				Self::snapshot_metadata().expect(error_message);
+				Self::snapshot_metadata(page).expect(error_message);
 
 			// NOTE: we are asserting, not `ensure`ing -- we want to panic here.
 			assert!(voters as u32 == witness.voters, "{}", error_message);
@@ -933,9 +1048,9 @@ pub mod pallet {
 				.expect(error_message);
 
 			// Store the newly received solution.
-			log!(info, "queued unsigned solution with score {:?}", ready.score);
-			let ejected_a_solution = <QueuedSolution<T>>::exists();
-			<QueuedSolution<T>>::put(ready);
+			log!(info, "queued unsigned solution with score {:?} for page {}", ready.score, page);
+			let ejected_a_solution = <QueuedSolution<T>>::contains_key(page);
+			<QueuedSolution<T>>::insert(page, ready);
 			Self::deposit_event(Event::SolutionStored {
 				compute: ElectionCompute::Unsigned,
 				origin: None,
@@ -964,6 +1079,9 @@ pub mod pallet {
 		/// Set a solution in the queue, to be handed out to the client of this pallet in the next
 		/// call to `ElectionProvider::elect`.
 		///
+		/// The solution is stored in the most significant page, and thus must contain all of the
+		/// winners and their full backing.
+		///
 		/// This can only be set by `T::ForceOrigin`, and only when the phase is `Emergency`.
 		///
 		/// The solution is not checked for any feasibility and is assumed to be trustworthy, as any
@@ -992,10 +1110,10 @@ pub mod pallet {
 			Self::deposit_event(Event::SolutionStored {
 				compute: ElectionCompute::Emergency,
 				origin: None,
-				prev_ejected: QueuedSolution::<T>::exists(),
+				prev_ejected: QueuedSolution::<T>::contains_key(Self::msp()),
 			});
 
-			<QueuedSolution<T>>::put(solution);
+			<QueuedSolution<T>>::insert(Self::msp(), solution);
 			Ok(())
 		}
 
@@ -1019,13 +1137,15 @@ pub mod pallet {
 			// ensure solution is timely.
 			ensure!(Self::current_phase().is_signed(), Error::<T>::PreDispatchEarlySubmission);
 			ensure!(raw_solution.round == Self::round(), Error::<T>::PreDispatchDifferentRound);
+			ensure!(raw_solution.page <= Self::msp(), Error::<T>::PreDispatchInvalidPage);
 
 			// NOTE: this is the only case where having separate snapshot would have been better
 			// because could do just decode_len. But we can create abstractions to do this.
 
 			// build size. Note: this is not needed for weight calc, thus not input.
 			// unlikely to ever return an error: if phase is signed, snapshot will exist.
-			let size = Self::snapshot_metadata().ok_or(Error::<T>::MissingSnapshotMetadata)?;
+			let size = Self::snapshot_metadata(raw_solution.page)
+				.ok_or(Error::<T>::MissingSnapshotMetadata)?;
 
 			ensure!(
 				Self::solution_weight_of(&raw_solution, size).all_lt(T::SignedMaxWeight::get()),
@@ -1039,6 +1159,7 @@ pub mod pallet {
 				T::EstimateCallFee::estimate_call_fee(&call, None::<Weight>.into())
 			};
 
+			let page = raw_solution.page;
 			let submission = SignedSubmission {
 				who: who.clone(),
 				deposit,
@@ -1048,7 +1169,7 @@ pub mod pallet {
 
 			// insert the submission if the queue has space or it's better than the weakest
 			// eject the weakest if the queue was full
-			let mut signed_submissions = Self::signed_submissions();
+			let mut signed_submissions = Self::signed_submissions(page);
 			let maybe_removed = match signed_submissions.insert(submission) {
 				// it's an error if we failed to insert a submission: this indicates the queue was
 				// full but our solution had insufficient score to eject any solution
@@ -1079,7 +1200,8 @@ pub mod pallet {
 		/// Trigger the governance fallback.
 		///
 		/// This can only be called when [`Phase::Emergency`] is enabled, as an alternative to
-		/// calling [`Call::set_emergency_election_result`].
+		/// calling [`Call::set_emergency_election_result`]. The result is stored in the most
+		/// significant page.
 		#[pallet::call_index(4)]
 		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
 		pub fn governance_fallback(
@@ -1120,10 +1242,10 @@ pub mod pallet {
 			Self::deposit_event(Event::SolutionStored {
 				compute: ElectionCompute::Fallback,
 				origin: None,
-				prev_ejected: QueuedSolution::<T>::exists(),
+				prev_ejected: QueuedSolution::<T>::contains_key(Self::msp()),
 			});
 
-			<QueuedSolution<T>>::put(solution);
+			<QueuedSolution<T>>::insert(Self::msp(), solution);
 			Ok(())
 		}
 	}
@@ -1194,6 +1316,9 @@ pub mod pallet {
 		TooManyWinners,
 		/// Sumission was prepared for a different round.
 		PreDispatchDifferentRound,
+		/// Submission was prepared for a page that does not exist, or is not open for submissions
+		/// yet.
+		PreDispatchInvalidPage,
 	}
 
 	#[pallet::validate_unsigned]
@@ -1221,8 +1346,8 @@ pub mod pallet {
 							.saturating_add(raw_solution.score.minimal_stake.saturated_into()),
 					)
 					// Used to deduplicate unsigned solutions: each validator should produce one
-					// solution per round at most, and solutions are not propagate.
-					.and_provides(raw_solution.round)
+					// solution per round and page at most, and solutions are not propagate.
+					.and_provides((raw_solution.round, raw_solution.page))
 					// Transaction should stay in the pool for the duration of the unsigned phase.
 					.longevity(T::UnsignedPhase::get().saturated_into::<u64>())
 					// We don't propagate this. This can never be validated at a remote node.
@@ -1264,41 +1389,50 @@ pub mod pallet {
 	#[pallet::getter(fn current_phase)]
 	pub type CurrentPhase<T: Config> = StorageValue<_, Phase<BlockNumberFor<T>>, ValueQuery>;
 
-	/// Current best solution, signed or unsigned, queued to be returned upon `elect`.
-	///
-	/// Always sorted by score.
+	/// Current best solution of each page, signed or unsigned, queued to be returned upon `elect`.
 	#[pallet::storage]
 	#[pallet::getter(fn queued_solution)]
 	pub type QueuedSolution<T: Config> =
-		StorageValue<_, ReadySolution<T::AccountId, T::MaxWinners>>;
+		StorageMap<_, Twox64Concat, PageIndex, ReadySolution<T::AccountId, T::MaxWinners>>;
+
+	/// The winners of the most significant page, once it has been handed out by `elect`, along
+	/// with how they were computed.
+	///
+	/// All of the subsequent pages are trimmed to only contain backings of these winners.
+	#[pallet::storage]
+	pub type ElectedWinners<T: Config> =
+		StorageValue<_, (BoundedVec<T::AccountId, T::MaxWinners>, ElectionCompute), OptionQuery>;
 
-	/// Snapshot data of the round.
+	/// Snapshot data of each page of the round.
 	///
-	/// This is created at the beginning of the signed phase and cleared upon calling `elect`.
+	/// This is created right before the beginning of the signed phase and cleared once the last
+	/// page is handed out by `elect`.
 	/// Note: This storage type must only be mutated through [`SnapshotWrapper`].
 	#[pallet::storage]
 	#[pallet::getter(fn snapshot)]
-	pub type Snapshot<T: Config> = StorageValue<_, RoundSnapshot<T::AccountId, VoterOf<T>>>;
+	pub type Snapshot<T: Config> =
+		StorageMap<_, Twox64Concat, PageIndex, RoundSnapshot<T::AccountId, VoterOf<T>>>;
 
 	/// Desired number of targets to elect for this round.
 	///
-	/// Only exists when [`Snapshot`] is present.
+	/// Only exists when at least one page of the [`Snapshot`] is present.
 	/// Note: This storage type must only be mutated through [`SnapshotWrapper`].
 	#[pallet::storage]
 	#[pallet::getter(fn desired_targets)]
 	pub type DesiredTargets<T> = StorageValue<_, u32>;
 
-	/// The metadata of the [`RoundSnapshot`]
+	/// The metadata of each page of the [`RoundSnapshot`]
 	///
-	/// Only exists when [`Snapshot`] is present.
+	/// Only exists when the corresponding page of the [`Snapshot`] is present.
 	/// Note: This storage type must only be mutated through [`SnapshotWrapper`].
 	#[pallet::storage]
 	#[pallet::getter(fn snapshot_metadata)]
-	pub type SnapshotMetadata<T: Config> = StorageValue<_, SolutionOrSnapshotSize>;
+	pub type SnapshotMetadata<T: Config> =
+		StorageMap<_, Twox64Concat, PageIndex, SolutionOrSnapshotSize>;
 
 	// The following storage items collectively comprise `SignedSubmissions<T>`, and should never be
-	// accessed independently. Instead, get `Self::signed_submissions()`, modify it as desired, and
-	// then do `signed_submissions.put()` when you're done with it.
+	// accessed independently. Instead, get `Self::signed_submissions(page)`, modify it as desired,
+	// and then do `signed_submissions.put()` when you're done with it.
 
 	/// The next index to be assigned to an incoming signed submission.
 	///
@@ -1312,15 +1446,15 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type SignedSubmissionNextIndex<T: Config> = StorageValue<_, u32, ValueQuery>;
 
-	/// A sorted, bounded vector of `(score, block_number, index)`, where each `index` points to a
-	/// value in `SignedSubmissions`.
+	/// A sorted, bounded vector of `(score, block_number, index)` for each page, where each `index`
+	/// points to a value in `SignedSubmissions`.
 	///
 	/// We never need to process more than a single signed submission at a time. Signed submissions
 	/// can be quite large, so we're willing to pay the cost of multiple database accesses to access
 	/// them one at a time instead of reading and decoding all of them at once.
 	#[pallet::storage]
 	pub type SignedSubmissionIndices<T: Config> =
-		StorageValue<_, SubmissionIndicesOf<T>, ValueQuery>;
+		StorageMap<_, Twox64Concat, PageIndex, SubmissionIndicesOf<T>, ValueQuery>;
 
 	/// Unchecked, signed solutions.
 	///
@@ -1346,7 +1480,8 @@ pub mod pallet {
 	/// The current storage version.
 	///
 	/// v1: https://github.com/paritytech/substrate/pull/12237/
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+	/// v2: paged snapshot, signed submissions and queued solutions.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
@@ -1359,31 +1494,35 @@ pub mod pallet {
 pub struct SnapshotWrapper<T>(sp_std::marker::PhantomData<T>);
 
 impl<T: Config> SnapshotWrapper<T> {
-	/// Kill all snapshot related storage items at the same time.
+	/// Kill all snapshot related storage items of all pages at the same time.
 	pub fn kill() {
-		<Snapshot<T>>::kill();
-		<SnapshotMetadata<T>>::kill();
+		let _ = <Snapshot<T>>::clear(u32::MAX, None);
+		let _ = <SnapshotMetadata<T>>::clear(u32::MAX, None);
 		<DesiredTargets<T>>::kill();
 	}
-	/// Set all snapshot related storage items at the same time.
-	pub fn set(metadata: SolutionOrSnapshotSize, desired_targets: u32, buffer: &[u8]) {
-		<SnapshotMetadata<T>>::put(metadata);
+	/// Set all snapshot related storage items of the given page at the same time.
+	pub fn set(
+		page: PageIndex,
+		metadata: SolutionOrSnapshotSize,
+		desired_targets: u32,
+		buffer: &[u8],
+	) {
+		<SnapshotMetadata<T>>::insert(page, metadata);
 		<DesiredTargets<T>>::put(desired_targets);
-		sp_io::storage::set(&<Snapshot<T>>::hashed_key(), &buffer);
+		sp_io::storage::set(&<Snapshot<T>>::hashed_key_for(page), &buffer);
 	}
 
-	/// Check if all of the storage items exist at the same time or all of the storage items do not
-	/// exist.
+	/// Check if all of the storage items of each page exist at the same time or all of the storage
+	/// items do not exist.
 	#[cfg(feature = "try-runtime")]
 	pub fn is_consistent() -> bool {
-		let snapshots = [
-			<Snapshot<T>>::exists(),
-			<SnapshotMetadata<T>>::exists(),
-			<DesiredTargets<T>>::exists(),
-		];
+		let pages_consistent = (0..T::Pages::get()).all(|page| {
+			<Snapshot<T>>::contains_key(page) == <SnapshotMetadata<T>>::contains_key(page)
+		});
 
-		// All should either exist or not exist
-		snapshots.iter().skip(1).all(|v| snapshots[0] == *v)
+		// `DesiredTargets` should exist iff any of the pages exists.
+		pages_consistent &&
+			<DesiredTargets<T>>::exists() == <Snapshot<T>>::iter_keys().next().is_some()
 	}
 }
 
@@ -1430,13 +1569,14 @@ impl<T: Config> Pallet<T> {
 	///
 	/// Extracted for easier weight calculation.
 	fn create_snapshot_internal(
+		page: PageIndex,
 		targets: Vec<T::AccountId>,
 		voters: Vec<VoterOf<T>>,
 		desired_targets: u32,
 	) {
 		let metadata =
 			SolutionOrSnapshotSize { voters: voters.len() as u32, targets: targets.len() as u32 };
-		log!(info, "creating a snapshot with metadata {:?}", metadata);
+		log!(info, "creating snapshot page {} with metadata {:?}", page, metadata);
 
 		// instead of using storage APIs, we do a manual encoding into a fixed-size buffer.
 		// `encoded_size` encodes it without storing it anywhere, this should not cause any
@@ -1452,17 +1592,18 @@ impl<T: Config> Pallet<T> {
 		// buffer should have not re-allocated since.
 		debug_assert!(buffer.len() == size && size == buffer.capacity());
 
-		SnapshotWrapper::<T>::set(metadata, desired_targets, &buffer);
+		SnapshotWrapper::<T>::set(page, metadata, desired_targets, &buffer);
 	}
 
 	/// Parts of [`create_snapshot`] that happen outside of this pallet.
 	///
 	/// Extracted for easier weight calculation.
 	fn create_snapshot_external(
+		page: PageIndex,
 	) -> Result<(Vec<T::AccountId>, Vec<VoterOf<T>>, u32), ElectionError<T>> {
 		let election_bounds = T::ElectionBounds::get();
 
-		let targets = T::DataProvider::electable_targets(election_bounds.targets)
+		let targets = T::DataProvider::electable_targets(election_bounds.targets, page)
 			.and_then(|t| {
 				election_bounds.ensure_targets_limits(
 					CountBound(t.len() as u32),
@@ -1472,7 +1613,7 @@ impl<T: Config> Pallet<T> {
 			})
 			.map_err(ElectionError::DataProvider)?;
 
-		let voters = T::DataProvider::electing_voters(election_bounds.voters)
+		let voters = T::DataProvider::electing_voters(election_bounds.voters, page)
 			.and_then(|v| {
 				election_bounds.ensure_voters_limits(
 					CountBound(v.len() as u32),
@@ -1501,28 +1642,39 @@ impl<T: Config> Pallet<T> {
 		Ok((targets, voters, desired_targets))
 	}
 
-	/// Creates the snapshot. Writes new data to:
+	/// Creates a single page of the snapshot. Writes new data to:
 	///
-	/// 1. [`SnapshotMetadata`]
-	/// 2. [`RoundSnapshot`]
+	/// 1. [`SnapshotMetadata`] of the page.
+	/// 2. [`RoundSnapshot`] of the page.
 	/// 3. [`DesiredTargets`]
 	///
 	/// Returns `Ok(())` if operation is okay.
 	///
 	/// This is a *self-weighing* function, it will register its own extra weight as
 	/// [`DispatchClass::Mandatory`] with the system pallet.
-	pub fn create_snapshot() -> Result<(), ElectionError<T>> {
+	pub fn create_snapshot_page(page: PageIndex) -> Result<(), ElectionError<T>> {
 		// this is self-weighing itself..
-		let (targets, voters, desired_targets) = Self::create_snapshot_external()?;
+		let (targets, voters, desired_targets) = Self::create_snapshot_external(page)?;
 
 		// ..therefore we only measure the weight of this and add it.
 		let internal_weight =
 			T::WeightInfo::create_snapshot_internal(voters.len() as u32, targets.len() as u32);
-		Self::create_snapshot_internal(targets, voters, desired_targets);
+		Self::create_snapshot_internal(page, targets, voters, desired_targets);
 		Self::register_weight(internal_weight);
 		Ok(())
 	}
 
+	/// Creates all pages of the snapshot at once, from the most significant page to the least
+	/// significant one.
+	///
+	/// See [`Self::create_snapshot_page`].
+	pub fn create_snapshot() -> Result<(), ElectionError<T>> {
+		for page in (Self::lsp()..=Self::msp()).rev() {
+			Self::create_snapshot_page(page)?;
+		}
+		Ok(())
+	}
+
 	/// Register some amount of weight directly with the system pallet.
 	///
 	/// This is always mandatory weight.
@@ -1534,32 +1686,76 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Checks the feasibility of a solution.
+	///
+	/// On top of the checks of [`Miner::feasibility_check`], this ensures that:
+	///
+	/// - the solution of the most significant page elects exactly `desired_targets` winners.
+	/// - the solution of any other page only backs the winners of the most significant page, if
+	///   they are already known.
 	pub fn feasibility_check(
 		raw_solution: RawSolution<SolutionOf<T::MinerConfig>>,
 		compute: ElectionCompute,
 	) -> Result<ReadySolution<T::AccountId, T::MaxWinners>, FeasibilityError> {
+		let page = raw_solution.page;
+		ensure!(page <= Self::msp(), FeasibilityError::InvalidPage);
+
 		let desired_targets =
 			Self::desired_targets().ok_or(FeasibilityError::SnapshotUnavailable)?;
 
-		let snapshot = Self::snapshot().ok_or(FeasibilityError::SnapshotUnavailable)?;
+		let snapshot = Self::snapshot(page).ok_or(FeasibilityError::SnapshotUnavailable)?;
 		let round = Self::round();
-		let minimum_untrusted_score = Self::minimum_untrusted_score();
 
-		Miner::<T::MinerConfig>::feasibility_check(
+		let is_msp = page == Self::msp();
+		// the minimum untrusted score is meant for an entire election, which only the most
+		// significant page represents.
+		let minimum_untrusted_score = if is_msp { Self::minimum_untrusted_score() } else { None };
+		if is_msp {
+			ensure!(
+				raw_solution.solution.unique_targets().len() as u32 == desired_targets,
+				FeasibilityError::WrongWinnerCount
+			);
+		}
+
+		let ready = Miner::<T::MinerConfig>::feasibility_check(
 			raw_solution,
 			compute,
 			desired_targets,
 			snapshot,
 			round,
 			minimum_untrusted_score,
-		)
+		)?;
+
+		if !is_msp {
+			if let Some(winners) = Self::msp_winners() {
+				ensure!(
+					ready.supports.iter().all(|(winner, _)| winners.contains(winner)),
+					FeasibilityError::InvalidWinner
+				);
+			}
+		}
+
+		Ok(ready)
+	}
+
+	/// The winners of the most significant page, if known.
+	///
+	/// These are either the winners that have already been handed out by `elect`, or the winners
+	/// of the currently queued solution of the most significant page.
+	pub(crate) fn msp_winners() -> Option<BTreeSet<T::AccountId>> {
+		<ElectedWinners<T>>::get()
+			.map(|(winners, _)| winners.into_iter().collect())
+			.or_else(|| {
+				<QueuedSolution<T>>::get(Self::msp())
+					.map(|ready| ready.supports.into_iter().map(|(winner, _)| winner).collect())
+			})
 	}
 
-	/// Perform the tasks to be done after a new `elect` has been triggered:
+	/// Perform the tasks to be done after the last page has been handed out by `elect`:
 	///
 	/// 1. Increment round.
 	/// 2. Change phase to [`Phase::Off`]
 	/// 3. Clear all snapshot data.
+	/// 4. Clear all queued solutions and the elected winners.
 	fn rotate_round() {
 		// Inc round.
 		<Round<T>>::mutate(|r| *r += 1);
@@ -1569,19 +1765,27 @@ impl<T: Config> Pallet<T> {
 
 		// Kill snapshot and relevant metadata (everything created by [`SnapshotMetadata::set`]).
 		SnapshotWrapper::<T>::kill();
+
+		// Any leftover solution is stale by now.
+		let _ = <QueuedSolution<T>>::clear(u32::MAX, None);
+		<ElectedWinners<T>>::kill();
 	}
 
-	fn do_elect() -> Result<BoundedSupportsOf<Self>, ElectionError<T>> {
-		// We have to unconditionally try finalizing the signed phase here. There are only two
-		// possibilities:
+	fn do_elect(page: PageIndex) -> Result<BoundedSupportsOf<Self>, ElectionError<T>> {
+		// We have to unconditionally try finalizing the signed phase of this page here. There are
+		// only two possibilities:
 		//
 		// - signed phase was open, in which case this is essential for correct functioning of the
 		//   system
 		// - signed phase was complete or not started, in which case finalization is idempotent and
 		//   inexpensive (1 read of an empty vector).
-		let _ = Self::finalize_signed_phase();
+		let _ = Self::finalize_signed_phase(page);
 
-		<QueuedSolution<T>>::take()
+		if page != Self::msp() {
+			return Self::do_elect_trimmed(page)
+		}
+
+		<QueuedSolution<T>>::take(page)
 			.ok_or(ElectionError::<T>::NothingQueued)
 			.or_else(|_| {
 				// default data provider bounds are unbounded. calling `instant_elect` with
@@ -1605,6 +1809,15 @@ impl<T: Config> Pallet<T> {
 				if Self::round() != 1 {
 					log!(info, "Finalized election round with compute {:?}.", compute);
 				}
+				if Self::msp() != Self::lsp() {
+					// the remaining pages can only back these winners.
+					<ElectedWinners<T>>::put((
+						BoundedVec::truncate_from(
+							supports.iter().map(|(winner, _)| winner.clone()).collect::<Vec<_>>(),
+						),
+						compute,
+					));
+				}
 				supports
 			})
 			.map_err(|err| {
@@ -1616,6 +1829,39 @@ impl<T: Config> Pallet<T> {
 			})
 	}
 
+	/// Hand out the queued solution of a page other than the most significant one, trimmed to only
+	/// contain the backings of [`ElectedWinners`].
+	///
+	/// If no solution is queued for this page, an empty set of supports is returned only if the
+	/// winners were computed by [`Config::Fallback`] or in the emergency phase, as these already
+	/// account for the whole election. Otherwise, the backings of the voters of this page would be
+	/// lost and the election fails with [`ElectionError::NothingQueued`].
+	fn do_elect_trimmed(page: PageIndex) -> Result<BoundedSupportsOf<Self>, ElectionError<T>> {
+		let (winners, elected_compute) = <ElectedWinners<T>>::get()
+			.map(|(w, compute)| (w.into_iter().collect::<BTreeSet<_>>(), Some(compute)))
+			.unwrap_or_default();
+
+		match <QueuedSolution<T>>::take(page) {
+			Some(ReadySolution { mut supports, score, compute }) => {
+				Self::deposit_event(Event::ElectionFinalized { compute, score });
+				log!(info, "Finalized page {} with compute {:?}.", page, compute);
+				supports.retain(|(winner, _)| winners.contains(winner));
+				Ok(supports)
+			},
+			None => match elected_compute {
+				Some(ElectionCompute::Fallback) | Some(ElectionCompute::Emergency) => {
+					log!(info, "no solution queued for page {}, handing out an empty page.", page);
+					Ok(Default::default())
+				},
+				_ => {
+					Self::deposit_event(Event::ElectionFailed);
+					log!(warn, "no solution queued for page {}.", page);
+					Err(ElectionError::<T>::NothingQueued)
+				},
+			},
+		}
+	}
+
 	/// record the weight of the given `supports`.
 	fn weigh_supports(supports: &Supports<T::AccountId>) {
 		let active_voters = supports
@@ -1636,8 +1882,9 @@ impl<T: Config> Pallet<T> {
 	}
 
 	// [`Snapshot`] state check. Invariants:
-	// - [`DesiredTargets`] exists if and only if [`Snapshot`] is present.
-	// - [`SnapshotMetadata`] exist if and only if [`Snapshot`] is present.
+	// - [`DesiredTargets`] exists if and only if any page of the [`Snapshot`] is present.
+	// - [`SnapshotMetadata`] of a page exist if and only if that page of the [`Snapshot`] is
+	//   present.
 	fn try_state_snapshot() -> Result<(), TryRuntimeError> {
 		if SnapshotWrapper::<T>::is_consistent() {
 			Ok(())
@@ -1647,34 +1894,39 @@ impl<T: Config> Pallet<T> {
 	}
 
 	// [`SignedSubmissionsMap`] state check. Invariants:
-	// - All [`SignedSubmissionIndices`] are present in [`SignedSubmissionsMap`], and no more;
+	// - All [`SignedSubmissionIndices`] of all pages are present in [`SignedSubmissionsMap`], and
+	//   no more;
 	// - [`SignedSubmissionNextIndex`] is not present in [`SignedSubmissionsMap`];
-	// - [`SignedSubmissionIndices`] is sorted by election score.
+	// - [`SignedSubmissionIndices`] of each page is sorted by election score.
 	fn try_state_signed_submissions_map() -> Result<(), TryRuntimeError> {
-		let mut last_score: ElectionScore = Default::default();
-		let indices = <SignedSubmissionIndices<T>>::get();
-
-		for (i, indice) in indices.iter().enumerate() {
-			let submission = <SignedSubmissionsMap<T>>::get(indice.2);
-			if submission.is_none() {
-				return Err(
-					"All signed submissions indices must be part of the submissions map".into()
-				)
-			}
-
-			if i == 0 {
-				last_score = indice.0
-			} else {
-				if last_score.strict_threshold_better(indice.0, Perbill::zero()) {
+		let mut total_indices = 0;
+		for (_page, indices) in <SignedSubmissionIndices<T>>::iter() {
+			let mut last_score: ElectionScore = Default::default();
+			total_indices += indices.len();
+
+			for (i, indice) in indices.iter().enumerate() {
+				let submission = <SignedSubmissionsMap<T>>::get(indice.2);
+				if submission.is_none() {
 					return Err(
-						"Signed submission indices vector must be ordered by election score".into()
+						"All signed submissions indices must be part of the submissions map".into()
 					)
 				}
-				last_score = indice.0;
+
+				if i == 0 {
+					last_score = indice.0
+				} else {
+					if last_score.strict_threshold_better(indice.0, Perbill::zero()) {
+						return Err(
+							"Signed submission indices vector must be ordered by election score"
+								.into(),
+						)
+					}
+					last_score = indice.0;
+				}
 			}
 		}
 
-		if <SignedSubmissionsMap<T>>::iter().nth(indices.len()).is_some() {
+		if <SignedSubmissionsMap<T>>::iter().nth(total_indices).is_some() {
 			return Err(
 				"Signed submissions map length should be the same as the indices vec length".into()
 			)
@@ -1695,12 +1947,12 @@ impl<T: Config> Pallet<T> {
 	}
 
 	// [`Phase::Off`] state check. Invariants:
-	// - If phase is `Phase::Off`, [`Snapshot`] must be none.
+	// - If phase is `Phase::Off`, no page of the [`Snapshot`] must exist.
 	fn try_state_phase_off() -> Result<(), TryRuntimeError> {
 		match Self::current_phase().is_off() {
 			false => Ok(()),
 			true =>
-				if <Snapshot<T>>::get().is_some() {
+				if <Snapshot<T>>::iter_keys().next().is_some() {
 					Err("Snapshot must be none when in Phase::Off".into())
 				} else {
 					Ok(())
@@ -1715,6 +1967,7 @@ impl<T: Config> ElectionProviderBase for Pallet<T> {
 	type Error = ElectionError<T>;
 	type MaxWinners = T::MaxWinners;
 	type DataProvider = T::DataProvider;
+	type Pages = T::Pages;
 }
 
 impl<T: Config> ElectionProvider for Pallet<T> {
@@ -1725,12 +1978,15 @@ impl<T: Config> ElectionProvider for Pallet<T> {
 		}
 	}
 
-	fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error> {
-		match Self::do_elect() {
+	fn elect(page: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		match Self::do_elect(page) {
 			Ok(supports) => {
-				// All went okay, record the weight, put sign to be Off, clean snapshot, etc.
+				// All went okay, record the weight. If this was the last page, put sign to be
+				// Off, clean snapshot, etc.
 				Self::weigh_supports(&supports);
-				Self::rotate_round();
+				if page == Self::lsp() {
+					Self::rotate_round();
+				}
 				Ok(supports)
 			},
 			Err(why) => {
@@ -1817,6 +2073,23 @@ mod feasibility_check {
 		})
 	}
 
+	#[test]
+	fn invalid_page_fails() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(<EpochLength>::get() - <SignedPhase>::get() - <UnsignedPhase>::get());
+			assert!(MultiPhase::current_phase().is_signed());
+
+			// there is only a single page.
+			let mut raw = raw_solution();
+			raw.page = 1;
+
+			assert_noop!(
+				MultiPhase::feasibility_check(raw, COMPUTE),
+				FeasibilityError::InvalidPage,
+			);
+		})
+	}
+
 	#[test]
 	fn less_than_desired_targets_fails() {
 		ExtBuilder::default().desired_targets(8).build_and_execute(|| {
@@ -1847,7 +2120,7 @@ mod feasibility_check {
 			assert!(MultiPhase::current_phase().is_signed());
 
 			let mut raw = raw_solution();
-			assert_eq!(MultiPhase::snapshot().unwrap().targets.len(), 4);
+			assert_eq!(MultiPhase::snapshot(0).unwrap().targets.len(), 4);
 			// ----------------------------------------------------^^ valid range is [0..3].
 
 			// Swap all votes from 3 to 4. This will ensure that the number of unique winners will
@@ -1881,7 +2154,7 @@ mod feasibility_check {
 			assert!(MultiPhase::current_phase().is_signed());
 
 			let mut solution = raw_solution();
-			assert_eq!(MultiPhase::snapshot().unwrap().voters.len(), 8);
+			assert_eq!(MultiPhase::snapshot(0).unwrap().voters.len(), 8);
 			// ----------------------------------------------------^^ valid range is [0..7].
 
 			// Check that there is an index 7 in votes1, and flip to 8.
@@ -1908,7 +2181,7 @@ mod feasibility_check {
 			assert!(MultiPhase::current_phase().is_signed());
 
 			let mut solution = raw_solution();
-			assert_eq!(MultiPhase::snapshot().unwrap().voters.len(), 8);
+			assert_eq!(MultiPhase::snapshot(0).unwrap().voters.len(), 8);
 			// ----------------------------------------------------^^ valid range is [0..7].
 
 			// First, check that voter at index 7 (40) actually voted for 3 (40) -- this is self
@@ -1937,7 +2210,7 @@ mod feasibility_check {
 			assert!(MultiPhase::current_phase().is_signed());
 
 			let mut solution = raw_solution();
-			assert_eq!(MultiPhase::snapshot().unwrap().voters.len(), 8);
+			assert_eq!(MultiPhase::snapshot(0).unwrap().voters.len(), 8);
 
 			// Simply faff with the score.
 			solution.score.minimal_stake += 1;
@@ -1964,6 +2237,147 @@ mod tests {
 	use frame_support::{assert_noop, assert_ok};
 	use sp_npos_elections::{BalancingConfig, Support};
 
+	#[test]
+	fn paged_snapshot_is_created_over_multiple_blocks() {
+		ExtBuilder::default().pages(3).build_and_execute(|| {
+			// 0 ------- 13 ------- 14 ------- 15 ------- 25 ------- 30
+			//           |          |          |          |          |
+			//        Snapshot(1) Snapshot(0) Signed     Unsigned   Elect
+
+			roll_to(12);
+			assert_eq!(MultiPhase::current_phase(), Phase::Off);
+
+			// the most significant page is fetched first.
+			roll_to(13);
+			assert_eq!(MultiPhase::current_phase(), Phase::Snapshot(1));
+			assert!(MultiPhase::snapshot(2).is_some());
+			assert!(MultiPhase::snapshot(1).is_none());
+			assert!(MultiPhase::snapshot(0).is_none());
+
+			roll_to(14);
+			assert_eq!(MultiPhase::current_phase(), Phase::Snapshot(0));
+			assert!(MultiPhase::snapshot(1).is_some());
+			assert!(MultiPhase::snapshot(0).is_none());
+
+			// the signed phase still starts at the same block.
+			roll_to(15);
+			assert_eq!(MultiPhase::current_phase(), Phase::Signed);
+			assert!(MultiPhase::snapshot(0).is_some());
+
+			// voters are split between the pages, while all pages contain all targets.
+			assert_eq!(
+				MultiPhase::snapshot_metadata(2).unwrap(),
+				SolutionOrSnapshotSize { voters: 3, targets: 4 }
+			);
+			assert_eq!(
+				MultiPhase::snapshot_metadata(1).unwrap(),
+				SolutionOrSnapshotSize { voters: 3, targets: 4 }
+			);
+			assert_eq!(
+				MultiPhase::snapshot_metadata(0).unwrap(),
+				SolutionOrSnapshotSize { voters: 2, targets: 4 }
+			);
+
+			assert_eq!(
+				multi_phase_events(),
+				vec![
+					Event::PhaseTransitioned { from: Phase::Off, to: Phase::Snapshot(1), round: 1 },
+					Event::PhaseTransitioned {
+						from: Phase::Snapshot(1),
+						to: Phase::Snapshot(0),
+						round: 1
+					},
+					Event::PhaseTransitioned {
+						from: Phase::Snapshot(0),
+						to: Phase::Signed,
+						round: 1
+					},
+				]
+			);
+		})
+	}
+
+	#[test]
+	fn paged_unsigned_election_works() {
+		ExtBuilder::default().pages(2).build_and_execute(|| {
+			roll_to_unsigned();
+			assert!(MultiPhase::current_phase().is_unsigned());
+
+			// the most significant page defines the winners, the rest of the pages back them.
+			for page in [1, 0] {
+				let (raw_solution, witness, _) = MultiPhase::mine_solution(page).unwrap();
+				assert_eq!(raw_solution.page, page);
+				assert_ok!(MultiPhase::submit_unsigned(
+					RuntimeOrigin::none(),
+					Box::new(raw_solution),
+					witness
+				));
+			}
+			assert!(MultiPhase::queued_solution(1).is_some());
+			assert!(MultiPhase::queued_solution(0).is_some());
+
+			let msp = MultiPhase::elect(1).unwrap();
+			assert_eq!(msp.len(), 2);
+			// the election is not over yet.
+			assert!(MultiPhase::current_phase().is_unsigned());
+			assert_eq!(MultiPhase::round(), 1);
+
+			let lsp = MultiPhase::elect(0).unwrap();
+			assert!(!lsp.is_empty());
+			assert!(lsp.iter().all(|(winner, _)| msp.iter().any(|(w, _)| w == winner)));
+
+			// now it is.
+			assert!(MultiPhase::current_phase().is_off());
+			assert_eq!(MultiPhase::round(), 2);
+			assert!(MultiPhase::snapshot(1).is_none());
+			assert!(MultiPhase::snapshot(0).is_none());
+			assert!(ElectedWinners::<Runtime>::get().is_none());
+		})
+	}
+
+	#[test]
+	fn paged_elect_hands_out_empty_pages_after_fallback() {
+		ExtBuilder::default().pages(2).build_and_execute(|| {
+			roll_to_signed();
+			assert!(MultiPhase::current_phase().is_signed());
+
+			// nothing is queued: the most significant page falls back.
+			let msp = MultiPhase::elect(1).unwrap();
+			assert_eq!(msp.len(), 2);
+			let (winners, compute) = ElectedWinners::<Runtime>::get().unwrap();
+			assert_eq!(winners.into_inner(), msp.iter().map(|(w, _)| *w).collect::<Vec<_>>());
+			assert_eq!(compute, ElectionCompute::Fallback);
+
+			// the fallback computed the whole election, the rest of the pages are empty.
+			assert!(MultiPhase::elect(0).unwrap().is_empty());
+			assert!(MultiPhase::current_phase().is_off());
+			assert_eq!(MultiPhase::round(), 2);
+		})
+	}
+
+	#[test]
+	fn paged_elect_fails_without_solution_for_a_page() {
+		ExtBuilder::default().pages(2).build_and_execute(|| {
+			roll_to_unsigned();
+			assert!(MultiPhase::current_phase().is_unsigned());
+
+			// only the most significant page is solved.
+			let (raw_solution, witness, _) = MultiPhase::mine_solution(1).unwrap();
+			assert_ok!(MultiPhase::submit_unsigned(
+				RuntimeOrigin::none(),
+				Box::new(raw_solution),
+				witness
+			));
+			assert_eq!(MultiPhase::elect(1).unwrap().len(), 2);
+			assert_eq!(ElectedWinners::<Runtime>::get().unwrap().1, ElectionCompute::Unsigned);
+
+			// the backings of the voters of the least significant page would be lost.
+			assert_eq!(MultiPhase::elect(0).unwrap_err(), ElectionError::NothingQueued);
+			assert!(MultiPhase::current_phase().is_emergency());
+			assert_eq!(MultiPhase::round(), 1);
+		})
+	}
+
 	#[test]
 	fn phase_rotation_works() {
 		ExtBuilder::default().build_and_execute(|| {
@@ -1977,7 +2391,7 @@ mod tests {
 
 			roll_to(4);
 			assert_eq!(MultiPhase::current_phase(), Phase::Off);
-			assert!(MultiPhase::snapshot().is_none());
+			assert!(MultiPhase::snapshot(0).is_none());
 			assert_eq!(MultiPhase::round(), 1);
 
 			roll_to_signed();
@@ -1986,12 +2400,12 @@ mod tests {
 				multi_phase_events(),
 				vec![Event::PhaseTransitioned { from: Phase::Off, to: Phase::Signed, round: 1 }]
 			);
-			assert!(MultiPhase::snapshot().is_some());
+			assert!(MultiPhase::snapshot(0).is_some());
 			assert_eq!(MultiPhase::round(), 1);
 
 			roll_to(24);
 			assert_eq!(MultiPhase::current_phase(), Phase::Signed);
-			assert!(MultiPhase::snapshot().is_some());
+			assert!(MultiPhase::snapshot(0).is_some());
 			assert_eq!(MultiPhase::round(), 1);
 
 			roll_to_unsigned();
@@ -2007,25 +2421,25 @@ mod tests {
 					},
 				],
 			);
-			assert!(MultiPhase::snapshot().is_some());
+			assert!(MultiPhase::snapshot(0).is_some());
 
 			roll_to(29);
 			assert_eq!(MultiPhase::current_phase(), Phase::Unsigned((true, 25)));
-			assert!(MultiPhase::snapshot().is_some());
+			assert!(MultiPhase::snapshot(0).is_some());
 
 			roll_to(30);
 			assert_eq!(MultiPhase::current_phase(), Phase::Unsigned((true, 25)));
-			assert!(MultiPhase::snapshot().is_some());
+			assert!(MultiPhase::snapshot(0).is_some());
 
 			// We close when upstream tells us to elect.
 			roll_to(32);
 			assert_eq!(MultiPhase::current_phase(), Phase::Unsigned((true, 25)));
-			assert!(MultiPhase::snapshot().is_some());
+			assert!(MultiPhase::snapshot(0).is_some());
 
-			assert_ok!(MultiPhase::elect());
+			assert_ok!(MultiPhase::elect(0));
 
 			assert!(MultiPhase::current_phase().is_off());
-			assert!(MultiPhase::snapshot().is_none());
+			assert!(MultiPhase::snapshot(0).is_none());
 			assert_eq!(MultiPhase::round(), 2);
 
 			roll_to(44);
@@ -2081,15 +2495,15 @@ mod tests {
 
 			roll_to(20);
 			assert!(MultiPhase::current_phase().is_unsigned_open_at(20));
-			assert!(MultiPhase::snapshot().is_some());
+			assert!(MultiPhase::snapshot(0).is_some());
 
 			roll_to(30);
 			assert!(MultiPhase::current_phase().is_unsigned_open_at(20));
 
-			assert_ok!(MultiPhase::elect());
+			assert_ok!(MultiPhase::elect(0));
 
 			assert!(MultiPhase::current_phase().is_off());
-			assert!(MultiPhase::snapshot().is_none());
+			assert!(MultiPhase::snapshot(0).is_none());
 
 			assert_eq!(
 				multi_phase_events(),
@@ -2128,15 +2542,15 @@ mod tests {
 
 			roll_to_signed();
 			assert!(MultiPhase::current_phase().is_signed());
-			assert!(MultiPhase::snapshot().is_some());
+			assert!(MultiPhase::snapshot(0).is_some());
 
 			roll_to(30);
 			assert!(MultiPhase::current_phase().is_signed());
 
-			assert_ok!(MultiPhase::elect());
+			assert_ok!(MultiPhase::elect(0));
 
 			assert!(MultiPhase::current_phase().is_off());
-			assert!(MultiPhase::snapshot().is_none());
+			assert!(MultiPhase::snapshot(0).is_none());
 
 			assert_eq!(
 				multi_phase_events(),
@@ -2172,7 +2586,7 @@ mod tests {
 			assert!(MultiPhase::current_phase().is_off());
 
 			// This module is now only capable of doing on-chain backup.
-			assert_ok!(MultiPhase::elect());
+			assert_ok!(MultiPhase::elect(0));
 
 			assert!(MultiPhase::current_phase().is_off());
 
@@ -2208,7 +2622,7 @@ mod tests {
 			assert_eq!(MultiPhase::round(), 1);
 
 			// An unexpected call to elect.
-			assert_ok!(MultiPhase::elect());
+			assert_ok!(MultiPhase::elect(0));
 
 			// We surely can't have any feasible solutions. This will cause an on-chain election.
 			assert_eq!(
@@ -2224,11 +2638,11 @@ mod tests {
 			);
 			// All storage items must be cleared.
 			assert_eq!(MultiPhase::round(), 2);
-			assert!(MultiPhase::snapshot().is_none());
-			assert!(MultiPhase::snapshot_metadata().is_none());
+			assert!(MultiPhase::snapshot(0).is_none());
+			assert!(MultiPhase::snapshot_metadata(0).is_none());
 			assert!(MultiPhase::desired_targets().is_none());
-			assert!(MultiPhase::queued_solution().is_none());
-			assert!(MultiPhase::signed_submissions().is_empty());
+			assert!(MultiPhase::queued_solution(0).is_none());
+			assert!(MultiPhase::signed_submissions(0).is_empty());
 		})
 	}
 
@@ -2259,15 +2673,15 @@ mod tests {
 			}
 
 			// an unexpected call to elect.
-			assert_ok!(MultiPhase::elect());
+			assert_ok!(MultiPhase::elect(0));
 
 			// all storage items must be cleared.
 			assert_eq!(MultiPhase::round(), 2);
-			assert!(MultiPhase::snapshot().is_none());
-			assert!(MultiPhase::snapshot_metadata().is_none());
+			assert!(MultiPhase::snapshot(0).is_none());
+			assert!(MultiPhase::snapshot_metadata(0).is_none());
 			assert!(MultiPhase::desired_targets().is_none());
-			assert!(MultiPhase::queued_solution().is_none());
-			assert!(MultiPhase::signed_submissions().is_empty());
+			assert!(MultiPhase::queued_solution(0).is_none());
+			assert!(MultiPhase::signed_submissions(0).is_empty());
 
 			assert_eq!(
 				multi_phase_events(),
@@ -2330,7 +2744,7 @@ mod tests {
 			));
 
 			roll_to(30);
-			assert_ok!(MultiPhase::elect());
+			assert_ok!(MultiPhase::elect(0));
 
 			assert_eq!(
 				multi_phase_events(),
@@ -2372,22 +2786,22 @@ mod tests {
 			assert!(MultiPhase::current_phase().is_unsigned());
 
 			// ensure we have snapshots in place.
-			assert!(MultiPhase::snapshot().is_some());
+			assert!(MultiPhase::snapshot(0).is_some());
 			assert_eq!(MultiPhase::desired_targets().unwrap(), 2);
 
 			// mine seq_phragmen solution with 2 iters.
-			let (solution, witness, _) = MultiPhase::mine_solution().unwrap();
+			let (solution, witness, _) = MultiPhase::mine_solution(0).unwrap();
 
 			// ensure this solution is valid.
-			assert!(MultiPhase::queued_solution().is_none());
+			assert!(MultiPhase::queued_solution(0).is_none());
 			assert_ok!(MultiPhase::submit_unsigned(
 				crate::mock::RuntimeOrigin::none(),
 				Box::new(solution),
 				witness
 			));
-			assert!(MultiPhase::queued_solution().is_some());
+			assert!(MultiPhase::queued_solution(0).is_some());
 
-			assert_ok!(MultiPhase::elect());
+			assert_ok!(MultiPhase::elect(0));
 
 			assert_eq!(
 				multi_phase_events(),
@@ -2428,8 +2842,8 @@ mod tests {
 			assert_eq!(MultiPhase::current_phase(), Phase::Unsigned((true, 25)));
 
 			// Zilch solutions thus far, but we get a result.
-			assert!(MultiPhase::queued_solution().is_none());
-			let supports = MultiPhase::elect().unwrap();
+			assert!(MultiPhase::queued_solution(0).is_none());
+			let supports = MultiPhase::elect(0).unwrap();
 
 			assert_eq!(
 				supports,
@@ -2470,12 +2884,12 @@ mod tests {
 			assert_eq!(MultiPhase::current_phase(), Phase::Unsigned((true, 25)));
 
 			// Zilch solutions thus far.
-			assert!(MultiPhase::queued_solution().is_none());
-			assert_eq!(MultiPhase::elect().unwrap_err(), ElectionError::Fallback("NoFallback."));
+			assert!(MultiPhase::queued_solution(0).is_none());
+			assert_eq!(MultiPhase::elect(0).unwrap_err(), ElectionError::Fallback("NoFallback."));
 			// phase is now emergency.
 			assert_eq!(MultiPhase::current_phase(), Phase::Emergency);
 			// snapshot is still there until election finalizes.
-			assert!(MultiPhase::snapshot().is_some());
+			assert!(MultiPhase::snapshot(0).is_some());
 
 			assert_eq!(
 				multi_phase_events(),
@@ -2504,13 +2918,13 @@ mod tests {
 			assert_eq!(MultiPhase::current_phase(), Phase::Unsigned((true, 25)));
 
 			// Zilch solutions thus far.
-			assert!(MultiPhase::queued_solution().is_none());
-			assert_eq!(MultiPhase::elect().unwrap_err(), ElectionError::Fallback("NoFallback."));
+			assert!(MultiPhase::queued_solution(0).is_none());
+			assert_eq!(MultiPhase::elect(0).unwrap_err(), ElectionError::Fallback("NoFallback."));
 
 			// phase is now emergency.
 			assert_eq!(MultiPhase::current_phase(), Phase::Emergency);
-			assert!(MultiPhase::queued_solution().is_none());
-			assert!(MultiPhase::snapshot().is_some());
+			assert!(MultiPhase::queued_solution(0).is_none());
+			assert!(MultiPhase::snapshot(0).is_some());
 
 			// no single account can trigger this
 			assert_noop!(
@@ -2521,9 +2935,9 @@ mod tests {
 			// only root can
 			assert_ok!(MultiPhase::governance_fallback(RuntimeOrigin::root(), None, None));
 			// something is queued now
-			assert!(MultiPhase::queued_solution().is_some());
+			assert!(MultiPhase::queued_solution(0).is_some());
 			// next election call with fix everything.;
-			assert!(MultiPhase::elect().is_ok());
+			assert!(MultiPhase::elect(0).is_ok());
 			assert_eq!(MultiPhase::current_phase(), Phase::Off);
 
 			assert_eq!(
@@ -2575,7 +2989,7 @@ mod tests {
 			assert_eq!(MultiPhase::current_phase(), Phase::Off);
 
 			// On-chain backup works though.
-			let supports = MultiPhase::elect().unwrap();
+			let supports = MultiPhase::elect(0).unwrap();
 			assert!(supports.len() > 0);
 
 			assert_eq!(
@@ -2614,7 +3028,7 @@ mod tests {
 			assert_eq!(MultiPhase::current_phase(), Phase::Off);
 
 			roll_to(29);
-			let err = MultiPhase::elect().unwrap_err();
+			let err = MultiPhase::elect(0).unwrap_err();
 			assert_eq!(err, ElectionError::Fallback("NoFallback."));
 			assert_eq!(MultiPhase::current_phase(), Phase::Emergency);
 
@@ -2643,7 +3057,7 @@ mod tests {
 			assert_eq!(MultiPhase::current_phase(), Phase::Signed);
 
 			assert_eq!(
-				MultiPhase::snapshot_metadata().unwrap(),
+				MultiPhase::snapshot_metadata(0).unwrap(),
 				SolutionOrSnapshotSize { voters: 2, targets: 4 }
 			);
 		})
@@ -2658,7 +3072,7 @@ mod tests {
 			// set the solution balancing to get the desired score.
 			crate::mock::Balancing::set(Some(BalancingConfig { iterations: 2, tolerance: 0 }));
 
-			let (solution, _, _) = MultiPhase::mine_solution().unwrap();
+			let (solution, _, _) = MultiPhase::mine_solution(0).unwrap();
 			// Default solution's score.
 			assert!(matches!(solution.score, ElectionScore { minimal_stake: 50, .. }));
 
@@ -17,7 +17,7 @@
 
 pub mod v1 {
 	use frame_support::{
-		storage::unhashed,
+		storage::{unhashed, StoragePrefixedMap},
 		traits::{Defensive, GetStorageVersion, OnRuntimeUpgrade},
 		BoundedVec,
 	};
@@ -38,14 +38,15 @@ pub mod v1 {
 			);
 
 			if current == 1 && onchain == 0 {
-				if SignedSubmissionIndices::<T>::exists() {
+				// `SignedSubmissionIndices` was a plain value until the pallet was paged, living at
+				// the prefix of the current map.
+				let key = SignedSubmissionIndices::<T>::final_prefix();
+				if unhashed::exists(&key) {
 					// This needs to be tested at a both a block height where this value exists, and
 					// when it doesn't.
 					let now = frame_system::Pallet::<T>::block_number();
-					let map = unhashed::get::<BTreeMap<ElectionScore, u32>>(
-						&SignedSubmissionIndices::<T>::hashed_key(),
-					)
-					.defensive_unwrap_or_default();
+					let map = unhashed::get::<BTreeMap<ElectionScore, u32>>(&key)
+						.defensive_unwrap_or_default();
 					let vector = map
 						.into_iter()
 						.map(|(score, index)| (score, now, index))
@@ -59,8 +60,8 @@ pub mod v1 {
 					);
 
 					// defensive-only, assuming a constant `SignedMaxSubmissions`.
-					let bounded = BoundedVec::<_, _>::truncate_from(vector);
-					SignedSubmissionIndices::<T>::put(bounded);
+					let bounded = BoundedVec::<_, T::SignedMaxSubmissions>::truncate_from(vector);
+					unhashed::put(&key, &bounded);
 
 					log!(info, "SignedSubmissionIndices existed and got migrated");
 				} else {
@@ -76,3 +77,197 @@ pub mod v1 {
 		}
 	}
 }
+
+pub mod v2 {
+	use codec::Decode;
+	use frame_support::{
+		storage::{unhashed, StoragePrefixedMap},
+		traits::{GetStorageVersion, OnRuntimeUpgrade},
+	};
+	use sp_std::vec::Vec;
+
+	use crate::*;
+
+	#[cfg(feature = "try-runtime")]
+	use sp_runtime::traits::Saturating;
+	#[cfg(feature = "try-runtime")]
+	use sp_std::collections::btree_map::BTreeMap;
+
+	/// The submitter and the deposit of each pending signed submission.
+	///
+	/// Only the first two fields of a signed submission are needed to refund it, and they have not
+	/// changed.
+	fn pending_deposits<T: Config>() -> Vec<(T::AccountId, BalanceOf<T>)> {
+		SignedSubmissionsMap::<T>::iter_keys()
+			.filter_map(|index| {
+				let raw = unhashed::get_raw(&SignedSubmissionsMap::<T>::hashed_key_for(index))?;
+				<(T::AccountId, BalanceOf<T>)>::decode(&mut &raw[..]).ok()
+			})
+			.collect()
+	}
+
+	/// Migrate the pallet to paged elections.
+	///
+	/// The snapshot, its metadata, the queued solution and the signed submission indices used to
+	/// be plain values, and are now maps keyed by the page index. Any ongoing election is aborted:
+	/// the deposits of the pending signed submissions are returned, the old values are removed and
+	/// the phase is set back to [`Phase::Off`], such that a new election starts from scratch.
+	pub struct MigrateToV2<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let current = Pallet::<T>::current_storage_version();
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			log!(
+				info,
+				"Running migration with current storage version {:?} / onchain {:?}",
+				current,
+				onchain
+			);
+
+			if current == 2 && onchain == 1 {
+				let refunds = pending_deposits::<T>();
+				let refunded = refunds.len() as u64;
+				for (who, deposit) in refunds {
+					let _remaining = T::Currency::unreserve(&who, deposit);
+				}
+				let _ = SignedSubmissionsMap::<T>::clear(u32::MAX, None);
+				SignedSubmissionNextIndex::<T>::kill();
+
+				// The old values live at the prefix of the new maps.
+				for prefix in [
+					Snapshot::<T>::final_prefix(),
+					SnapshotMetadata::<T>::final_prefix(),
+					QueuedSolution::<T>::final_prefix(),
+					SignedSubmissionIndices::<T>::final_prefix(),
+				] {
+					unhashed::kill(&prefix);
+				}
+				DesiredTargets::<T>::kill();
+				CurrentPhase::<T>::put(Phase::Off);
+
+				current.put::<Pallet<T>>();
+				log!(info, "Migrated to paged elections, refunded {} submissions.", refunded);
+				T::DbWeight::get().reads_writes(refunded * 2 + 2, refunded * 2 + 8)
+			} else {
+				log!(info, "Migration did not execute. This probably should be removed");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 1,
+				"Expected v1 before upgrading to v2"
+			);
+
+			// the reserved balance each submitter is expected to have after the refunds.
+			let mut expected = BTreeMap::<T::AccountId, BalanceOf<T>>::new();
+			for (who, deposit) in pending_deposits::<T>() {
+				let reserved = expected
+					.entry(who.clone())
+					.or_insert_with(|| T::Currency::reserved_balance(&who));
+				*reserved = reserved.saturating_sub(deposit);
+			}
+			Ok(expected.into_iter().collect::<Vec<_>>().encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			frame_support::ensure!(Pallet::<T>::on_chain_storage_version() == 2, "v2 not applied");
+
+			let expected = <Vec<(T::AccountId, BalanceOf<T>)>>::decode(&mut &state[..])
+				.map_err(|_| "Failed to decode the pre-upgrade state")?;
+			for (who, reserved) in expected {
+				frame_support::ensure!(
+					T::Currency::reserved_balance(&who) == reserved,
+					"the deposit of a signed submission was not refunded"
+				);
+			}
+
+			frame_support::ensure!(
+				SignedSubmissionsMap::<T>::iter_keys().next().is_none(),
+				"signed submissions left behind"
+			);
+			frame_support::ensure!(
+				SignedSubmissionNextIndex::<T>::get() == 0,
+				"signed submission index left behind"
+			);
+			for prefix in [
+				Snapshot::<T>::final_prefix(),
+				SnapshotMetadata::<T>::final_prefix(),
+				QueuedSolution::<T>::final_prefix(),
+				SignedSubmissionIndices::<T>::final_prefix(),
+			] {
+				frame_support::ensure!(
+					unhashed::get_raw(&prefix).is_none(),
+					"plain value of a paged map left behind"
+				);
+			}
+			frame_support::ensure!(
+				DesiredTargets::<T>::get().is_none(),
+				"desired targets left behind"
+			);
+			frame_support::ensure!(CurrentPhase::<T>::get().is_off(), "election not aborted");
+
+			Ok(())
+		}
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::*;
+		use crate::{
+			mock::{balances, ExtBuilder, MultiPhase, Runtime},
+			signed::SignedSubmission,
+		};
+		use frame_support::{assert_ok, traits::StorageVersion};
+
+		#[test]
+		fn migrate_to_v2_aborts_the_ongoing_election() {
+			ExtBuilder::default().build_and_execute(|| {
+				StorageVersion::new(1).put::<MultiPhase>();
+
+				// the old plain values, and a pending signed submission.
+				unhashed::put(&Snapshot::<Runtime>::final_prefix(), &(vec![1u64], vec![10u64]));
+				unhashed::put(&SnapshotMetadata::<Runtime>::final_prefix(), &(1u32, 1u32));
+				unhashed::put(&QueuedSolution::<Runtime>::final_prefix(), &0u32);
+				unhashed::put(&SignedSubmissionIndices::<Runtime>::final_prefix(), &vec![0u32]);
+				DesiredTargets::<Runtime>::put(2);
+				CurrentPhase::<Runtime>::put(Phase::Signed);
+				assert_ok!(<Runtime as Config>::Currency::reserve(&99, 5));
+				SignedSubmissionsMap::<Runtime>::insert(
+					0,
+					SignedSubmission {
+						who: 99,
+						deposit: 5,
+						raw_solution: Default::default(),
+						call_fee: 1,
+					},
+				);
+				SignedSubmissionNextIndex::<Runtime>::put(1);
+				assert_eq!(balances(&99), (95, 5));
+
+				#[cfg(feature = "try-runtime")]
+				let state = MigrateToV2::<Runtime>::pre_upgrade().unwrap();
+				MigrateToV2::<Runtime>::on_runtime_upgrade();
+				#[cfg(feature = "try-runtime")]
+				assert_ok!(MigrateToV2::<Runtime>::post_upgrade(state));
+
+				assert_eq!(MultiPhase::on_chain_storage_version(), 2);
+				assert_eq!(balances(&99), (100, 0));
+				assert_eq!(SignedSubmissionsMap::<Runtime>::iter_keys().count(), 0);
+				assert_eq!(SignedSubmissionNextIndex::<Runtime>::get(), 0);
+				assert!(unhashed::get_raw(&Snapshot::<Runtime>::final_prefix()).is_none());
+				assert!(unhashed::get_raw(&QueuedSolution::<Runtime>::final_prefix()).is_none());
+				assert!(DesiredTargets::<Runtime>::get().is_none());
+				assert!(CurrentPhase::<Runtime>::get().is_off());
+
+				// running it again is a no-op.
+				MigrateToV2::<Runtime>::on_runtime_upgrade();
+				assert_eq!(balances(&99), (100, 0));
+			})
+		}
+	}
+}
@@ -19,7 +19,7 @@ use super::*;
 use crate::{self as multi_phase, signed::GeometricDepositBase, unsigned::MinerConfig};
 use frame_election_provider_support::{
 	bounds::{DataProviderBounds, ElectionBounds},
-	data_provider, onchain, ElectionDataProvider, NposSolution, SequentialPhragmen,
+	data_provider, onchain, ElectionDataProvider, NposSolution, PageIndex, SequentialPhragmen,
 };
 pub use frame_support::derive_impl;
 use frame_support::{
@@ -116,7 +116,14 @@ pub fn roll_to_round(n: u32) {
 
 	while MultiPhase::round() != n {
 		roll_to_signed();
-		frame_support::assert_ok!(MultiPhase::elect());
+		elect_all_pages();
+	}
+}
+
+/// Call `elect` for all of the pages, from the most significant to the least significant one.
+pub fn elect_all_pages() {
+	for page in (MultiPhase::lsp()..=MultiPhase::msp()).rev() {
+		frame_support::assert_ok!(MultiPhase::elect(page));
 	}
 }
 
@@ -136,7 +143,7 @@ pub struct TrimHelpers {
 ///
 /// Assignments are pre-sorted in reverse order of stake.
 pub fn trim_helpers() -> TrimHelpers {
-	let RoundSnapshot { voters, targets } = MultiPhase::snapshot().unwrap();
+	let RoundSnapshot { voters, targets } = MultiPhase::snapshot(MultiPhase::msp()).unwrap();
 	let stakes: std::collections::HashMap<_, _> =
 		voters.iter().map(|(id, stake, _)| (*id, *stake)).collect();
 
@@ -172,11 +179,12 @@ pub fn trim_helpers() -> TrimHelpers {
 	TrimHelpers { voters, assignments, encoded_size_of, voter_index: Box::new(voter_index) }
 }
 
-/// Spit out a verifiable raw solution.
+/// Spit out a verifiable raw solution for the most significant page.
 ///
 /// This is a good example of what an offchain miner would do.
 pub fn raw_solution() -> RawSolution<SolutionOf<Runtime>> {
-	let RoundSnapshot { voters, targets } = MultiPhase::snapshot().unwrap();
+	let page = MultiPhase::msp();
+	let RoundSnapshot { voters, targets } = MultiPhase::snapshot(page).unwrap();
 	let desired_targets = MultiPhase::desired_targets().unwrap();
 
 	let ElectionResult::<_, SolutionAccuracyOf<Runtime>> { winners: _, assignments } =
@@ -196,11 +204,11 @@ pub fn raw_solution() -> RawSolution<SolutionOf<Runtime>> {
 		<SolutionOf<Runtime>>::from_assignment(&assignments, &voter_index, &target_index).unwrap();
 
 	let round = MultiPhase::round();
-	RawSolution { solution, score, round }
+	RawSolution { solution, score, round, page }
 }
 
 pub fn witness() -> SolutionOrSnapshotSize {
-	MultiPhase::snapshot()
+	MultiPhase::snapshot(MultiPhase::msp())
 		.map(|snap| SolutionOrSnapshotSize {
 			voters: snap.voters.len() as u32,
 			targets: snap.targets.len() as u32,
@@ -284,6 +292,7 @@ parameter_types! {
 	];
 
 	pub static DesiredTargets: u32 = 2;
+	pub static Pages: PageIndex = 1;
 	pub static SignedPhase: BlockNumber = 10;
 	pub static UnsignedPhase: BlockNumber = 5;
 	pub static SignedMaxSubmissions: u32 = 5;
@@ -332,6 +341,7 @@ impl ElectionProviderBase for MockFallback {
 	type Error = &'static str;
 	type DataProvider = StakingMock;
 	type MaxWinners = MaxWinners;
+	type Pages = ConstU32<1>;
 }
 
 impl InstantElectionProvider for MockFallback {
@@ -419,6 +429,7 @@ impl crate::Config for Runtime {
 	type MinerConfig = Self;
 	type Solver = SequentialPhragmen<AccountId, SolutionAccuracyOf<Runtime>, Balancing>;
 	type ElectionBounds = ElectionsBounds;
+	type Pages = Pages;
 }
 
 impl Convert<usize, BalanceOf<Runtime>> for Runtime {
@@ -458,7 +469,10 @@ impl ElectionDataProvider for StakingMock {
 	type AccountId = AccountId;
 	type MaxVotesPerVoter = MaxNominations;
 
-	fn electable_targets(bounds: DataProviderBounds) -> data_provider::Result<Vec<AccountId>> {
+	fn electable_targets(
+		bounds: DataProviderBounds,
+		_page: PageIndex,
+	) -> data_provider::Result<Vec<AccountId>> {
 		let targets = Targets::get();
 
 		if !DataProviderAllowBadData::get() &&
@@ -470,8 +484,23 @@ impl ElectionDataProvider for StakingMock {
 		Ok(targets)
 	}
 
-	fn electing_voters(bounds: DataProviderBounds) -> data_provider::Result<Vec<VoterOf<Runtime>>> {
-		let mut voters = Voters::get();
+	fn electing_voters(
+		bounds: DataProviderBounds,
+		page: PageIndex,
+	) -> data_provider::Result<Vec<VoterOf<Runtime>>> {
+		// split the voters evenly between the pages, the most significant page getting the first
+		// chunk of them.
+		let pages = Pages::get();
+		if page >= pages {
+			return Ok(vec![])
+		}
+		let all_voters = Voters::get();
+		let per_page = (all_voters.len() + pages as usize - 1) / pages as usize;
+		let mut voters = all_voters
+			.into_iter()
+			.skip((pages - 1 - page) as usize * per_page)
+			.take(per_page)
+			.collect::<Vec<_>>();
 
 		if !DataProviderAllowBadData::get() {
 			if let Some(max_len) = bounds.count {
@@ -556,6 +585,10 @@ impl ExtBuilder {
 		<DesiredTargets>::set(t);
 		self
 	}
+	pub fn pages(self, pages: PageIndex) -> Self {
+		<Pages>::set(pages);
+		self
+	}
 	pub fn add_voter(
 		self,
 		who: AccountId,
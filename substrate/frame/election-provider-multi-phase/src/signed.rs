@@ -25,7 +25,7 @@ use crate::{
 	SolutionOf, SolutionOrSnapshotSize, Weight, WeightInfo,
 };
 use codec::{Decode, Encode, HasCompact};
-use frame_election_provider_support::NposSolution;
+use frame_election_provider_support::{NposSolution, PageIndex};
 use frame_support::traits::{
 	defensive_prelude::*, Currency, Get, OnUnbalanced, ReservableCurrency,
 };
@@ -118,11 +118,12 @@ pub enum InsertResult<T: Config> {
 	InsertedEjecting(SignedSubmissionOf<T>),
 }
 
-/// Mask type which pretends to be a set of `SignedSubmissionOf<T>`, while in fact delegating to the
-/// actual implementations in `SignedSubmissionIndices<T>`, `SignedSubmissionsMap<T>`, and
-/// `SignedSubmissionNextIndex<T>`.
+/// Mask type which pretends to be a set of `SignedSubmissionOf<T>` of a single page, while in fact
+/// delegating to the actual implementations in `SignedSubmissionIndices<T>`,
+/// `SignedSubmissionsMap<T>`, and `SignedSubmissionNextIndex<T>`.
 #[cfg_attr(feature = "std", derive(frame_support::DebugNoBound))]
 pub struct SignedSubmissions<T: Config> {
+	page: PageIndex,
 	indices: SubmissionIndicesOf<T>,
 	next_idx: u32,
 	insertion_overlay: BTreeMap<u32, SignedSubmissionOf<T>>,
@@ -140,10 +141,11 @@ impl<T: Config> SignedSubmissions<T> {
 		self.indices.len()
 	}
 
-	/// Get the signed submissions from storage.
-	pub fn get() -> Self {
+	/// Get the signed submissions of the given page from storage.
+	pub fn get(page: PageIndex) -> Self {
 		let submissions = SignedSubmissions {
-			indices: SignedSubmissionIndices::<T>::get(),
+			page,
+			indices: SignedSubmissionIndices::<T>::get(page),
 			next_idx: SignedSubmissionNextIndex::<T>::get(),
 			insertion_overlay: BTreeMap::new(),
 			deletion_overlay: BTreeSet::new(),
@@ -177,7 +179,11 @@ impl<T: Config> SignedSubmissions<T> {
 			.max()
 			.map_or(true, |max_idx| self.next_idx > max_idx,));
 
-		SignedSubmissionIndices::<T>::put(self.indices);
+		if self.indices.is_empty() {
+			SignedSubmissionIndices::<T>::remove(self.page);
+		} else {
+			SignedSubmissionIndices::<T>::insert(self.page, self.indices);
+		}
 		SignedSubmissionNextIndex::<T>::put(self.next_idx);
 		for key in self.deletion_overlay {
 			self.insertion_overlay.remove(&key);
@@ -263,22 +269,21 @@ impl<T: Config> SignedSubmissions<T> {
 	///
 	/// Note that, like `put`, this function consumes `Self` and modifies storage.
 	fn drain_submitted_order(mut self) -> impl Iterator<Item = SignedSubmissionOf<T>> {
-		let mut keys = SignedSubmissionsMap::<T>::iter_keys()
-			.filter(|k| {
-				if self.deletion_overlay.contains(k) {
-					// Remove submissions that should be deleted.
-					SignedSubmissionsMap::<T>::remove(k);
-					false
-				} else {
-					true
-				}
-			})
-			.chain(self.insertion_overlay.keys().copied())
-			.collect::<Vec<_>>();
+		// Remove submissions that should be deleted.
+		for k in sp_std::mem::take(&mut self.deletion_overlay) {
+			SignedSubmissionsMap::<T>::remove(k);
+		}
+
+		// all the remaining submissions of this page are tracked in `self.indices`.
+		let mut keys = self.indices.iter().map(|(_, _, index)| *index).collect::<Vec<_>>();
 		keys.sort();
 
-		SignedSubmissionIndices::<T>::kill();
-		SignedSubmissionNextIndex::<T>::kill();
+		SignedSubmissionIndices::<T>::remove(self.page);
+		// the next index is shared between all pages, keep it around until all of them are
+		// drained.
+		if SignedSubmissionIndices::<T>::iter_keys().next().is_none() {
+			SignedSubmissionNextIndex::<T>::kill();
+		}
 
 		keys.into_iter().filter_map(move |index| {
 			SignedSubmissionsMap::<T>::take(index).or_else(|| self.insertion_overlay.remove(&index))
@@ -290,8 +295,8 @@ impl<T: Config> SignedSubmissions<T> {
 	///
 	/// Note that if you hold an instance of `SignedSubmissions`, this function does _not_
 	/// track its current length. This only decodes what is currently stored in memory.
-	pub fn decode_len() -> Option<usize> {
-		SignedSubmissionIndices::<T>::decode_len()
+	pub fn decode_len(page: PageIndex) -> Option<usize> {
+		SignedSubmissionIndices::<T>::decode_len(page)
 	}
 
 	/// Insert a new signed submission into the set.
@@ -377,35 +382,36 @@ where
 }
 
 impl<T: Config> Pallet<T> {
-	/// `Self` accessor for `SignedSubmission<T>`.
-	pub fn signed_submissions() -> SignedSubmissions<T> {
-		SignedSubmissions::<T>::get()
+	/// `Self` accessor for `SignedSubmission<T>` of the given page.
+	pub fn signed_submissions(page: PageIndex) -> SignedSubmissions<T> {
+		SignedSubmissions::<T>::get(page)
 	}
 
-	/// Finish the signed phase. Process the signed submissions from best to worse until a valid one
-	/// is found, rewarding the best one and slashing the invalid ones along the way.
+	/// Finish the signed phase of the given page. Process the signed submissions of this page from
+	/// best to worse until a valid one is found, rewarding the best one and slashing the invalid
+	/// ones along the way.
 	///
 	/// Returns true if we have a good solution in the signed phase.
 	///
-	/// This drains the [`SignedSubmissions`], potentially storing the best valid one in
-	/// [`QueuedSolution`].
+	/// This drains the [`SignedSubmissions`] of the page, potentially storing the best valid one
+	/// in [`QueuedSolution`].
 	///
 	/// This is a *self-weighing* function, it automatically registers its weight internally when
 	/// being called.
-	pub fn finalize_signed_phase() -> bool {
-		let (weight, found_solution) = Self::finalize_signed_phase_internal();
+	pub fn finalize_signed_phase(page: PageIndex) -> bool {
+		let (weight, found_solution) = Self::finalize_signed_phase_internal(page);
 		Self::register_weight(weight);
 		found_solution
 	}
 
 	/// The guts of [`finalized_signed_phase`], that does everything except registering its weight.
-	pub(crate) fn finalize_signed_phase_internal() -> (Weight, bool) {
-		let mut all_submissions = Self::signed_submissions();
+	pub(crate) fn finalize_signed_phase_internal(page: PageIndex) -> (Weight, bool) {
+		let mut all_submissions = Self::signed_submissions(page);
 		let mut found_solution = false;
 		let mut weight = T::DbWeight::get().reads(1);
 
 		let SolutionOrSnapshotSize { voters, targets } =
-			Self::snapshot_metadata().unwrap_or_default();
+			Self::snapshot_metadata(page).unwrap_or_default();
 
 		while let Some(best) = all_submissions.pop_last() {
 			log!(
@@ -427,6 +433,7 @@ impl<T: Config> Pallet<T> {
 			match Self::feasibility_check(raw_solution, ElectionCompute::Signed) {
 				Ok(ready_solution) => {
 					Self::finalize_signed_phase_accept_solution(
+						page,
 						ready_solution,
 						&who,
 						deposit,
@@ -470,13 +477,16 @@ impl<T: Config> Pallet<T> {
 			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 2));
 		}
 
-		debug_assert!(!SignedSubmissionIndices::<T>::exists());
-		debug_assert!(!SignedSubmissionNextIndex::<T>::exists());
-		debug_assert!(SignedSubmissionsMap::<T>::iter().next().is_none());
+		debug_assert!(!SignedSubmissionIndices::<T>::contains_key(page));
+		if SignedSubmissionIndices::<T>::iter_keys().next().is_none() {
+			debug_assert!(!SignedSubmissionNextIndex::<T>::exists());
+			debug_assert!(SignedSubmissionsMap::<T>::iter().next().is_none());
+		}
 
 		log!(
 			debug,
-			"closed signed phase, found solution? {}, discarded {}",
+			"closed signed phase of page {}, found solution? {}, discarded {}",
+			page,
 			found_solution,
 			discarded
 		);
@@ -489,13 +499,14 @@ impl<T: Config> Pallet<T> {
 	///
 	/// Infallible
 	pub fn finalize_signed_phase_accept_solution(
+		page: PageIndex,
 		ready_solution: ReadySolution<T::AccountId, T::MaxWinners>,
 		who: &T::AccountId,
 		deposit: BalanceOf<T>,
 		call_fee: BalanceOf<T>,
 	) {
 		// write this ready solution.
-		<QueuedSolution<T>>::put(ready_solution);
+		<QueuedSolution<T>>::insert(page, ready_solution);
 
 		let reward = T::SignedRewardBase::get();
 		// emit reward event
@@ -555,7 +566,7 @@ impl<T: Config> Pallet<T> {
 		let weight_deposit = T::SignedDepositWeight::get()
 			.saturating_mul(feasibility_weight.ref_time().saturated_into());
 
-		T::SignedDepositBase::convert(Self::signed_submissions().len())
+		T::SignedDepositBase::convert(Self::signed_submissions(raw_solution.page).len())
 			.saturating_add(len_deposit)
 			.saturating_add(weight_deposit)
 	}
@@ -605,6 +616,45 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn cannot_submit_for_invalid_page() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to_signed();
+			assert_eq!(MultiPhase::current_phase(), Phase::Signed);
+
+			// there is only a single page.
+			let mut solution = raw_solution();
+			solution.page = 1;
+
+			assert_noop!(
+				MultiPhase::submit(RuntimeOrigin::signed(99), Box::new(solution)),
+				Error::<Runtime>::PreDispatchInvalidPage,
+			);
+		})
+	}
+
+	#[test]
+	fn signed_submissions_are_per_page() {
+		ExtBuilder::default().pages(2).build_and_execute(|| {
+			roll_to_signed();
+			assert_eq!(MultiPhase::current_phase(), Phase::Signed);
+
+			let solution = raw_solution();
+			assert_eq!(solution.page, 1);
+			assert_ok!(MultiPhase::submit(RuntimeOrigin::signed(99), Box::new(solution)));
+
+			assert_eq!(MultiPhase::signed_submissions(1).len(), 1);
+			assert!(MultiPhase::signed_submissions(0).is_empty());
+
+			// only the most significant page is processed when the unsigned phase opens.
+			roll_to_unsigned();
+			assert!(MultiPhase::signed_submissions(1).is_empty());
+			assert!(MultiPhase::queued_solution(1).is_some());
+			assert!(MultiPhase::queued_solution(0).is_none());
+			assert_eq!(balances(&99), (100 + 7 + 8, 0));
+		})
+	}
+
 	#[test]
 	fn cannot_submit_too_early() {
 		ExtBuilder::default().build_and_execute(|| {
@@ -621,9 +671,7 @@ mod tests {
 			);
 
 			// make sure invariants hold true and post-test try state checks to pass.
-			<crate::Snapshot<Runtime>>::kill();
-			<crate::SnapshotMetadata<Runtime>>::kill();
-			<crate::DesiredTargets<Runtime>>::kill();
+			crate::SnapshotWrapper::<Runtime>::kill();
 		})
 	}
 
@@ -687,7 +735,7 @@ mod tests {
 			assert_ok!(MultiPhase::submit(RuntimeOrigin::signed(99), Box::new(solution)));
 
 			assert_eq!(balances(&99), (95, 5));
-			assert_eq!(MultiPhase::signed_submissions().iter().next().unwrap().deposit, 5);
+			assert_eq!(MultiPhase::signed_submissions(0).iter().next().unwrap().deposit, 5);
 
 			assert_eq!(
 				multi_phase_events(),
@@ -715,7 +763,7 @@ mod tests {
 			assert_ok!(MultiPhase::submit(RuntimeOrigin::signed(99), Box::new(solution)));
 			assert_eq!(balances(&99), (95, 5));
 
-			assert!(MultiPhase::finalize_signed_phase());
+			assert!(MultiPhase::finalize_signed_phase(0));
 			assert_eq!(balances(&99), (100 + 7 + 8, 0));
 
 			assert_eq!(
@@ -749,7 +797,7 @@ mod tests {
 			assert_eq!(balances(&99), (95, 5));
 
 			// no good solution was stored.
-			assert!(!MultiPhase::finalize_signed_phase());
+			assert!(!MultiPhase::finalize_signed_phase(0));
 			// and the bond is gone.
 			assert_eq!(balances(&99), (95, 0));
 
@@ -788,7 +836,7 @@ mod tests {
 			assert_eq!(balances(&999), (95, 5));
 
 			// _some_ good solution was stored.
-			assert!(MultiPhase::finalize_signed_phase());
+			assert!(MultiPhase::finalize_signed_phase(0));
 
 			// 99 is rewarded.
 			assert_eq!(balances(&99), (100 + 7 + 8, 0));
@@ -911,7 +959,7 @@ mod tests {
 				assert_eq!(balances(&account), (95, 5));
 			}
 
-			assert_ok!(MultiPhase::do_elect());
+			assert_ok!(MultiPhase::do_elect(0));
 
 			for s in 0..SignedMaxSubmissions::get() {
 				let account = 99 + s as u64;
@@ -1053,7 +1101,7 @@ mod tests {
 			}
 
 			assert_eq!(
-				MultiPhase::signed_submissions()
+				MultiPhase::signed_submissions(0)
 					.iter()
 					.map(|s| s.raw_solution.score.minimal_stake)
 					.collect::<Vec<_>>(),
@@ -1069,7 +1117,7 @@ mod tests {
 
 			// the one with score 5 was rejected, the new one inserted.
 			assert_eq!(
-				MultiPhase::signed_submissions()
+				MultiPhase::signed_submissions(0)
 					.iter()
 					.map(|s| s.raw_solution.score.minimal_stake)
 					.collect::<Vec<_>>(),
@@ -1103,7 +1151,7 @@ mod tests {
 			assert_ok!(MultiPhase::submit(RuntimeOrigin::signed(99), Box::new(solution)));
 
 			assert_eq!(
-				MultiPhase::signed_submissions()
+				MultiPhase::signed_submissions(0)
 					.iter()
 					.map(|s| s.raw_solution.score.minimal_stake)
 					.collect::<Vec<_>>(),
@@ -1119,7 +1167,7 @@ mod tests {
 
 			// the one with score 5 was rejected, the new one inserted.
 			assert_eq!(
-				MultiPhase::signed_submissions()
+				MultiPhase::signed_submissions(0)
 					.iter()
 					.map(|s| s.raw_solution.score.minimal_stake)
 					.collect::<Vec<_>>(),
@@ -1175,7 +1223,7 @@ mod tests {
 			}
 
 			assert_eq!(
-				MultiPhase::signed_submissions()
+				MultiPhase::signed_submissions(0)
 					.iter()
 					.map(|s| s.raw_solution.score.minimal_stake)
 					.collect::<Vec<_>>(),
@@ -1208,7 +1256,7 @@ mod tests {
 			assert_ok!(MultiPhase::submit(RuntimeOrigin::signed(99), Box::new(solution)));
 
 			assert_eq!(
-				MultiPhase::signed_submissions()
+				MultiPhase::signed_submissions(0)
 					.iter()
 					.map(|s| (s.who, s.raw_solution.score.minimal_stake,))
 					.collect::<Vec<_>>(),
@@ -1223,7 +1271,7 @@ mod tests {
 			assert_ok!(MultiPhase::submit(RuntimeOrigin::signed(999), Box::new(solution)));
 
 			assert_eq!(
-				MultiPhase::signed_submissions()
+				MultiPhase::signed_submissions(0)
 					.iter()
 					.map(|s| (s.who, s.raw_solution.score.minimal_stake,))
 					.collect::<Vec<_>>(),
@@ -1237,7 +1285,7 @@ mod tests {
 			assert_ok!(MultiPhase::submit(RuntimeOrigin::signed(9999), Box::new(solution)));
 
 			assert_eq!(
-				MultiPhase::signed_submissions()
+				MultiPhase::signed_submissions(0)
 					.iter()
 					.map(|s| (s.who, s.raw_solution.score.minimal_stake,))
 					.collect::<Vec<_>>(),
@@ -1263,7 +1311,7 @@ mod tests {
 			}
 
 			assert_eq!(
-				MultiPhase::signed_submissions()
+				MultiPhase::signed_submissions(0)
 					.iter()
 					.map(|s| (s.who, s.raw_solution.score.minimal_stake))
 					.collect::<Vec<_>>(),
@@ -1334,12 +1382,12 @@ mod tests {
 			assert_ok!(MultiPhase::submit(RuntimeOrigin::signed(9999), Box::new(solution_9999)));
 
 			assert_eq!(
-				MultiPhase::signed_submissions().iter().map(|x| x.who).collect::<Vec<_>>(),
+				MultiPhase::signed_submissions(0).iter().map(|x| x.who).collect::<Vec<_>>(),
 				vec![9999, 99, 999]
 			);
 
 			// _some_ good solution was stored.
-			assert!(MultiPhase::finalize_signed_phase());
+			assert!(MultiPhase::finalize_signed_phase(0));
 
 			// 99 is rewarded.
 			assert_eq!(balances(&99), (100 + 7 + 8, 0));
@@ -1382,7 +1430,7 @@ mod tests {
 				roll_to_signed();
 				assert!(MultiPhase::current_phase().is_signed());
 
-				let (raw, witness, _) = MultiPhase::mine_solution().unwrap();
+				let (raw, witness, _) = MultiPhase::mine_solution(0).unwrap();
 				let solution_weight = <Runtime as MinerConfig>::solution_weight(
 					witness.voters,
 					witness.targets,
@@ -1470,8 +1518,8 @@ mod tests {
 			for block_number in 0..25 {
 				roll_to(block_number);
 
-				assert_eq!(SignedSubmissions::<Runtime>::decode_len().unwrap_or_default(), 0);
-				assert_storage_noop!(MultiPhase::finalize_signed_phase_internal());
+				assert_eq!(SignedSubmissions::<Runtime>::decode_len(0).unwrap_or_default(), 0);
+				assert_storage_noop!(MultiPhase::finalize_signed_phase_internal(0));
 			}
 		})
 	}
@@ -1488,10 +1536,10 @@ mod tests {
 			assert_ok!(MultiPhase::submit(RuntimeOrigin::signed(99), Box::new(solution)));
 
 			// _some_ good solution was stored.
-			assert!(MultiPhase::finalize_signed_phase());
+			assert!(MultiPhase::finalize_signed_phase(0));
 
 			// calling it again doesn't change anything
-			assert_storage_noop!(MultiPhase::finalize_signed_phase());
+			assert_storage_noop!(MultiPhase::finalize_signed_phase(0));
 
 			assert_eq!(
 				multi_phase_events(),
@@ -18,11 +18,14 @@
 //! The unsigned phase, and its miner.
 
 use crate::{
-	helpers, Call, Config, ElectionCompute, Error, FeasibilityError, Pallet, RawSolution,
-	ReadySolution, RoundSnapshot, SolutionAccuracyOf, SolutionOf, SolutionOrSnapshotSize, Weight,
+	helpers, Call, Config, ElectionCompute, Error, FeasibilityError, Pallet, QueuedSolution,
+	RawSolution, ReadySolution, RoundSnapshot, SignedSubmissions, SolutionAccuracyOf, SolutionOf,
+	SolutionOrSnapshotSize, Weight,
 };
 use codec::Encode;
-use frame_election_provider_support::{NposSolution, NposSolver, PerThing128, VoteWeight};
+use frame_election_provider_support::{
+	ElectionProviderBase, NposSolution, NposSolver, PageIndex, PerThing128, VoteWeight,
+};
 use frame_support::{
 	dispatch::DispatchResult,
 	ensure,
@@ -179,17 +182,34 @@ fn ocw_solution_exists<T: Config>() -> bool {
 }
 
 impl<T: Config> Pallet<T> {
-	/// Mine a new npos solution.
+	/// Mine a new npos solution for the given page.
+	///
+	/// If the page is not the most significant one and the winners of the most significant page
+	/// are already known, the votes of the voters are restricted to those winners.
 	///
 	/// The Npos Solver type, `S`, must have the same AccountId and Error type as the
 	/// [`crate::Config::Solver`] in order to create a unified return type.
-	pub fn mine_solution() -> Result<
+	pub fn mine_solution(
+		page: PageIndex,
+	) -> Result<
 		(RawSolution<SolutionOf<T::MinerConfig>>, SolutionOrSnapshotSize, TrimmingStatus),
 		MinerError,
 	> {
 		let RoundSnapshot { voters, targets } =
-			Self::snapshot().ok_or(MinerError::SnapshotUnAvailable)?;
+			Self::snapshot(page).ok_or(MinerError::SnapshotUnAvailable)?;
 		let desired_targets = Self::desired_targets().ok_or(MinerError::SnapshotUnAvailable)?;
+
+		let voters = match Self::msp_winners() {
+			Some(winners) if page != Self::msp() => voters
+				.into_iter()
+				.map(|(who, stake, mut votes)| {
+					votes.retain(|target| winners.contains(target));
+					(who, stake, votes)
+				})
+				.collect(),
+			_ => voters,
+		};
+
 		let (solution, score, size, is_trimmed) =
 			Miner::<T::MinerConfig>::mine_solution_with_snapshot::<T::Solver>(
 				voters,
@@ -197,7 +217,22 @@ impl<T: Config> Pallet<T> {
 				desired_targets,
 			)?;
 		let round = Self::round();
-		Ok((RawSolution { solution, score, round }, size, is_trimmed))
+		Ok((RawSolution { solution, score, round, page }, size, is_trimmed))
+	}
+
+	/// The page that the offchain worker should mine a solution for.
+	///
+	/// This is the most significant page that has no queued solution and no pending signed
+	/// submissions. If there is no such page, the most significant page is returned, in the hope
+	/// of improving its queued solution.
+	pub fn ocw_page() -> PageIndex {
+		(Self::lsp()..=Self::msp())
+			.rev()
+			.find(|page| {
+				!<QueuedSolution<T>>::contains_key(page) &&
+					SignedSubmissions::<T>::decode_len(*page).unwrap_or_default() == 0
+			})
+			.unwrap_or(Self::msp())
 	}
 
 	/// Attempt to restore a solution from cache. Otherwise, compute it fresh. Either way, submit
@@ -205,10 +240,15 @@ impl<T: Config> Pallet<T> {
 	pub fn restore_or_compute_then_maybe_submit() -> Result<(), MinerError> {
 		log!(debug, "miner attempting to restore or compute an unsigned solution.");
 
+		let page = Self::ocw_page();
 		let call = restore_solution::<T>()
 			.and_then(|call| {
 				// ensure the cached call is still current before submitting
 				if let Call::submit_unsigned { raw_solution, .. } = &call {
+					// the cached call is for another page, that we are done with.
+					if raw_solution.page != page {
+						return Err(MinerError::NoStoredSolution)
+					}
 					// prevent errors arising from state changes in a forkful chain
 					Self::basic_checks(raw_solution, "restored")?;
 					Ok(call)
@@ -224,7 +264,7 @@ impl<T: Config> Pallet<T> {
 						// if not present or cache invalidated due to feasibility, regenerate.
 						// note that failing `Feasibility` can only mean that the solution was
 						// computed over a snapshot that has changed due to a fork.
-						let call = Self::mine_checked_call()?;
+						let call = Self::mine_checked_call(page)?;
 						save_solution(&call)?;
 						Ok(call)
 					},
@@ -246,26 +286,28 @@ impl<T: Config> Pallet<T> {
 		Self::submit_call(call)
 	}
 
-	/// Mine a new solution, cache it, and submit it back to the chain as an unsigned transaction.
+	/// Mine a new solution for [`Self::ocw_page`], cache it, and submit it back to the chain as an
+	/// unsigned transaction.
 	pub fn mine_check_save_submit() -> Result<(), MinerError> {
 		log!(debug, "miner attempting to compute an unsigned solution.");
 
-		let call = Self::mine_checked_call()?;
+		let call = Self::mine_checked_call(Self::ocw_page())?;
 		save_solution(&call)?;
 		Self::submit_call(call)
 	}
 
-	/// Mine a new solution as a call. Performs all checks.
-	pub fn mine_checked_call() -> Result<Call<T>, MinerError> {
+	/// Mine a new solution for the given page as a call. Performs all checks.
+	pub fn mine_checked_call(page: PageIndex) -> Result<Call<T>, MinerError> {
 		// get the solution, with a load of checks to ensure if submitted, IT IS ABSOLUTELY VALID.
-		let (raw_solution, witness, _) = Self::mine_and_check()?;
+		let (raw_solution, witness, _) = Self::mine_and_check(page)?;
 
 		let score = raw_solution.score;
 		let call: Call<T> = Call::submit_unsigned { raw_solution: Box::new(raw_solution), witness };
 
 		log!(
 			debug,
-			"mined a solution with score {:?} and size {}",
+			"mined a solution for page {} with score {:?} and size {}",
+			page,
 			score,
 			call.using_encoded(|b| b.len())
 		);
@@ -302,17 +344,19 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	/// Mine a new npos solution, with all the relevant checks to make sure that it will be accepted
-	/// to the chain.
+	/// Mine a new npos solution for the given page, with all the relevant checks to make sure that
+	/// it will be accepted to the chain.
 	///
 	/// If you want an unchecked solution, use [`Pallet::mine_solution`].
 	/// If you want a checked solution and submit it at the same time, use
 	/// [`Pallet::mine_check_save_submit`].
-	pub fn mine_and_check() -> Result<
+	pub fn mine_and_check(
+		page: PageIndex,
+	) -> Result<
 		(RawSolution<SolutionOf<T::MinerConfig>>, SolutionOrSnapshotSize, TrimmingStatus),
 		MinerError,
 	> {
-		let (raw_solution, witness, is_trimmed) = Self::mine_solution()?;
+		let (raw_solution, witness, is_trimmed) = Self::mine_solution(page)?;
 		Self::basic_checks(&raw_solution, "mined")?;
 		Ok((raw_solution, witness, is_trimmed))
 	}
@@ -375,16 +419,30 @@ impl<T: Config> Pallet<T> {
 		// ensure round is current
 		ensure!(Self::round() == raw_solution.round, Error::<T>::OcwCallWrongEra);
 
-		// ensure correct number of winners.
+		// ensure the page exists, and its signed submissions have already been processed.
+		let page = raw_solution.page;
 		ensure!(
-			Self::desired_targets().unwrap_or_default() ==
-				raw_solution.solution.unique_targets().len() as u32,
+			page <= Self::msp() &&
+				SignedSubmissions::<T>::decode_len(page).unwrap_or_default() == 0,
+			Error::<T>::PreDispatchInvalidPage,
+		);
+
+		// ensure correct number of winners: the most significant page must elect exactly
+		// `desired_targets`, the other pages can only back a subset of them.
+		let desired_targets = Self::desired_targets().unwrap_or_default();
+		let winners = raw_solution.solution.unique_targets().len() as u32;
+		ensure!(
+			if page == Self::msp() {
+				winners == desired_targets
+			} else {
+				winners <= desired_targets
+			},
 			Error::<T>::PreDispatchWrongWinnerCount,
 		);
 
 		// ensure score is being improved. Panic henceforth.
 		ensure!(
-			Self::queued_solution()
+			Self::queued_solution(page)
 				.map_or(true, |q: ReadySolution<_, _>| raw_solution.score > q.score),
 			Error::<T>::PreDispatchWeakSubmission,
 		);
@@ -740,7 +798,7 @@ impl<T: MinerConfig> Miner<T> {
 		current_round: u32,
 		minimum_untrusted_score: Option<ElectionScore>,
 	) -> Result<ReadySolution<T::AccountId, T::MaxWinners>, FeasibilityError> {
-		let RawSolution { solution, score, round } = raw_solution;
+		let RawSolution { solution, score, round, page: _ } = raw_solution;
 		let RoundSnapshot { voters: snapshot_voters, targets: snapshot_targets } = snapshot;
 
 		// First, check round.
@@ -749,7 +807,9 @@ impl<T: MinerConfig> Miner<T> {
 		// Winners are not directly encoded in the solution.
 		let winners = solution.unique_targets();
 
-		ensure!(winners.len() as u32 == desired_targets, FeasibilityError::WrongWinnerCount);
+		// Any page can elect at most `desired_targets`. The pallet further ensures that the most
+		// significant page elects exactly `desired_targets`.
+		ensure!(winners.len() as u32 <= desired_targets, FeasibilityError::WrongWinnerCount);
 		// Fail early if targets requested by data provider exceed maximum winners supported.
 		ensure!(desired_targets <= T::MaxWinners::get(), FeasibilityError::TooManyDesiredTargets);
 
@@ -1129,7 +1189,7 @@ mod tests {
 				score: ElectionScore { minimal_stake: 10, ..Default::default() },
 				..Default::default()
 			};
-			<QueuedSolution<Runtime>>::put(ready);
+			<QueuedSolution<Runtime>>::insert(0, ready);
 
 			// won't work anymore.
 			assert!(matches!(
@@ -1260,20 +1320,20 @@ mod tests {
 			assert!(MultiPhase::current_phase().is_unsigned());
 
 			// ensure we have snapshots in place.
-			assert!(MultiPhase::snapshot().is_some());
+			assert!(MultiPhase::snapshot(0).is_some());
 			assert_eq!(MultiPhase::desired_targets().unwrap(), 2);
 
 			// mine seq_phragmen solution with 2 iters.
-			let (solution, witness, _) = MultiPhase::mine_solution().unwrap();
+			let (solution, witness, _) = MultiPhase::mine_solution(0).unwrap();
 
 			// ensure this solution is valid.
-			assert!(MultiPhase::queued_solution().is_none());
+			assert!(MultiPhase::queued_solution(0).is_none());
 			assert_ok!(MultiPhase::submit_unsigned(
 				RuntimeOrigin::none(),
 				Box::new(solution),
 				witness
 			));
-			assert!(MultiPhase::queued_solution().is_some());
+			assert!(MultiPhase::queued_solution(0).is_some());
 			assert_eq!(
 				multi_phase_events(),
 				vec![
@@ -1302,7 +1362,7 @@ mod tests {
 				roll_to_unsigned();
 				assert!(MultiPhase::current_phase().is_unsigned());
 
-				let (raw, witness, t) = MultiPhase::mine_solution().unwrap();
+				let (raw, witness, t) = MultiPhase::mine_solution(0).unwrap();
 				let solution_weight = <Runtime as MinerConfig>::solution_weight(
 					witness.voters,
 					witness.targets,
@@ -1317,7 +1377,7 @@ mod tests {
 				// now reduce the max weight
 				<MinerMaxWeight>::set(Weight::from_parts(25, u64::MAX));
 
-				let (raw, witness, t) = MultiPhase::mine_solution().unwrap();
+				let (raw, witness, t) = MultiPhase::mine_solution(0).unwrap();
 				let solution_weight = <Runtime as MinerConfig>::solution_weight(
 					witness.voters,
 					witness.targets,
@@ -1339,7 +1399,7 @@ mod tests {
 			assert!(MultiPhase::current_phase().is_unsigned());
 
 			// Force the number of winners to be bigger to fail
-			let (mut solution, _, _) = MultiPhase::mine_solution().unwrap();
+			let (mut solution, _, _) = MultiPhase::mine_solution(0).unwrap();
 			solution.solution.votes1[0].1 = 4;
 
 			assert_eq!(
@@ -1378,7 +1438,7 @@ mod tests {
 					],
 				};
 
-				let RoundSnapshot { voters, targets } = MultiPhase::snapshot().unwrap();
+				let RoundSnapshot { voters, targets } = MultiPhase::snapshot(0).unwrap();
 				let desired_targets = MultiPhase::desired_targets().unwrap();
 
 				let (raw, score, witness, _) =
@@ -1389,14 +1449,15 @@ mod tests {
 						desired_targets,
 					)
 					.unwrap();
-				let solution = RawSolution { solution: raw, score, round: MultiPhase::round() };
+				let solution =
+					RawSolution { solution: raw, score, round: MultiPhase::round(), page: 0 };
 				assert_ok!(MultiPhase::unsigned_pre_dispatch_checks(&solution));
 				assert_ok!(MultiPhase::submit_unsigned(
 					RuntimeOrigin::none(),
 					Box::new(solution),
 					witness
 				));
-				assert_eq!(MultiPhase::queued_solution().unwrap().score.minimal_stake, 12);
+				assert_eq!(MultiPhase::queued_solution(0).unwrap().score.minimal_stake, 12);
 
 				// trial 1: a solution who's minimal stake is 10, i.e. worse than the first solution
 				// of 12.
@@ -1414,7 +1475,8 @@ mod tests {
 					desired_targets,
 				)
 				.unwrap();
-				let solution = RawSolution { solution: raw, score, round: MultiPhase::round() };
+				let solution =
+					RawSolution { solution: raw, score, round: MultiPhase::round(), page: 0 };
 				// 10 is not better than 12
 				assert_eq!(solution.score.minimal_stake, 10);
 				// submitting this will actually panic.
@@ -1444,7 +1506,8 @@ mod tests {
 					desired_targets,
 				)
 				.unwrap();
-				let solution = RawSolution { solution: raw, score, round: MultiPhase::round() };
+				let solution =
+					RawSolution { solution: raw, score, round: MultiPhase::round(), page: 0 };
 				// 12 is not better than 12. We need score of atleast 13 to be accepted.
 				assert_eq!(solution.score.minimal_stake, 12);
 				// submitting this will panic.
@@ -1471,7 +1534,8 @@ mod tests {
 						desired_targets,
 					)
 					.unwrap();
-				let solution = RawSolution { solution: raw, score, round: MultiPhase::round() };
+				let solution =
+					RawSolution { solution: raw, score, round: MultiPhase::round(), page: 0 };
 				assert_eq!(solution.score.minimal_stake, 13);
 
 				// this should work
@@ -1504,7 +1568,8 @@ mod tests {
 						desired_targets,
 					)
 					.unwrap();
-				let solution = RawSolution { solution: raw, score, round: MultiPhase::round() };
+				let solution =
+					RawSolution { solution: raw, score, round: MultiPhase::round(), page: 0 };
 				assert_eq!(solution.score.minimal_stake, 17);
 
 				// and it is fine
@@ -1667,7 +1732,7 @@ mod tests {
 
 			// after an election, the solution is not cleared
 			// we don't actually care about the result of the election
-			let _ = MultiPhase::do_elect();
+			let _ = MultiPhase::do_elect(0);
 			MultiPhase::offchain_worker(block_plus(1));
 			assert!(ocw_solution_exists::<Runtime>(), "elections does not clear the ocw cache");
 
@@ -1997,14 +2062,14 @@ mod tests {
 			roll_to_unsigned();
 
 			// how long would the default solution be?
-			let solution = MultiPhase::mine_solution().unwrap();
+			let solution = MultiPhase::mine_solution(0).unwrap();
 			let max_length = <Runtime as MinerConfig>::MaxLength::get();
 			let solution_size = solution.0.solution.encoded_size();
 			assert!(solution_size <= max_length as usize);
 
 			// now set the max size to less than the actual size and regenerate
 			<Runtime as MinerConfig>::MaxLength::set(solution_size as u32 - 1);
-			let solution = MultiPhase::mine_solution().unwrap();
+			let solution = MultiPhase::mine_solution(0).unwrap();
 			let max_length = <Runtime as MinerConfig>::MaxLength::get();
 			let solution_size = solution.0.solution.encoded_size();
 			assert!(solution_size <= max_length as usize);
@@ -213,6 +213,7 @@ impl pallet_election_provider_multi_phase::Config for Runtime {
 	type ElectionBounds = ElectionBounds;
 	type BenchmarkingConfig = NoopElectionProviderBenchmarkConfig;
 	type WeightInfo = ();
+	type Pages = ConstU32<1>;
 }
 
 impl MinerConfig for Runtime {
@@ -275,7 +276,7 @@ impl pallet_nomination_pools::Config for Runtime {
 	type RewardCounter = sp_runtime::FixedU128;
 	type BalanceToU256 = BalanceToU256;
 	type U256ToBalance = U256ToBalance;
-	type Staking = Staking;
+	type StakeAdapter = pallet_nomination_pools::adapter::TransferStake<Self, Staking>;
 	type PostUnbondingPoolsWindow = ConstU32<2>;
 	type PalletId = PoolsPalletId;
 	type MaxMetadataLen = ConstU32<256>;
@@ -291,7 +292,9 @@ parameter_types! {
 const MAX_QUOTA_NOMINATIONS: u32 = 16;
 
 impl pallet_staking::Config for Runtime {
+	type OldCurrency = Balances;
 	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type CurrencyBalance = Balance;
 	type UnixTime = Timestamp;
 	type CurrencyToVote = ();
@@ -504,8 +507,8 @@ impl Default for BalancesExtBuilder {
 			(90, 100),
 			(100, 100),
 			(200, 100),
-			// stashes
-			(11, 1000),
+			// stashes, which need an existential deposit on top of their stake.
+			(11, 1001),
 			(21, 2000),
 			(31, 3000),
 			(41, 4000),
@@ -846,14 +849,14 @@ pub fn roll_to_epm_off() {
 	}
 }
 
-// Queue a solution based on the current snapshot.
+// Queue a solution based on the current snapshot of the single page of the election.
 pub(crate) fn try_queue_solution(when: ElectionCompute) -> Result<(), String> {
-	let raw_solution = ElectionProviderMultiPhase::mine_solution()
+	let raw_solution = ElectionProviderMultiPhase::mine_solution(0)
 		.map_err(|e| format!("error mining solution: {:?}", e))?;
 
 	ElectionProviderMultiPhase::feasibility_check(raw_solution.0, when)
 		.map(|ready| {
-			QueuedSolution::<Runtime>::put(ready);
+			QueuedSolution::<Runtime>::insert(0, ready);
 		})
 		.map_err(|e| format!("error in solution feasibility: {:?}", e))
 }
@@ -931,9 +934,8 @@ pub(crate) fn set_minimum_election_score(
 	.map_err(|_| ())
 }
 
-pub(crate) fn locked_amount_for(account_id: AccountId) -> Balance {
-	let lock = pallet_balances::Locks::<Runtime>::get(account_id);
-	lock[0].amount
+pub(crate) fn staked_amount_for(account_id: AccountId) -> Balance {
+	pallet_staking::asset::staked::<Runtime>(&account_id)
 }
 
 pub(crate) fn staking_events() -> Vec<pallet_staking::Event<Runtime>> {
@@ -56,8 +56,9 @@
 //!
 //! To accommodate both type of elections in one trait, the traits lean toward **stateful
 //! election**, as it is more general than the stateless. This is why [`ElectionProvider::elect`]
-//! has no parameters. All value and type parameter must be provided by the [`ElectionDataProvider`]
-//! trait, even if the election happens immediately.
+//! only receives the page that is being requested (see the multi-page election section below). All
+//! value and type parameter must be provided by the [`ElectionDataProvider`] trait, even if the
+//! election happens immediately.
 //!
 //! ## Election Data
 //!
@@ -76,6 +77,27 @@
 //! Nonetheless, an [`ElectionProvider`] shan't rely on this and should preferably provide some
 //! means of fallback election as well, in case the `elect` was called immaturely early.
 //!
+//! ## Multi-page election
+//!
+//! Both the election data and the election result can be split into a number of pages, which is
+//! defined by [`ElectionProviderBase::Pages`]. This allows the data to be prepared and the result
+//! to be consumed over several blocks, instead of having to fit the whole election into a single
+//! one.
+//!
+//! Pages are processed from the most significant page ([`ElectionProviderBase::msp`], i.e.
+//! `Pages - 1`) down to the least significant page ([`ElectionProviderBase::lsp`], i.e. `0`):
+//!
+//! - [`ElectionDataProvider::electing_voters`] and [`ElectionDataProvider::electable_targets`]
+//!   receive the page that is being requested. A data provider that keeps an ordered list of voters
+//!   is expected to return the voters with the most stake in the most significant page, and to
+//!   continue from where the previous page stopped in the following ones.
+//! - [`ElectionProvider::elect`] is called once per page by the receiver of the election, starting
+//!   from the most significant page. The election is finished once the least significant page has
+//!   been requested.
+//!
+//! An election provider with a single page (i.e. `Pages = 1`) behaves exactly like a non-paged
+//! one: all the data is fetched and returned in page `0`.
+//!
 //! ## Example
 //!
 //! ```rust
@@ -109,12 +131,14 @@
 //!         fn desired_targets() -> data_provider::Result<u32> {
 //!             Ok(1)
 //!         }
-//!         fn electing_voters(bounds: DataProviderBounds)
+//!         fn electing_voters(bounds: DataProviderBounds, _page: PageIndex)
 //!           -> data_provider::Result<Vec<VoterOf<Self>>>
 //!         {
 //!             Ok(Default::default())
 //!         }
-//!         fn electable_targets(bounds: DataProviderBounds) -> data_provider::Result<Vec<AccountId>> {
+//!         fn electable_targets(bounds: DataProviderBounds, _page: PageIndex)
+//!           -> data_provider::Result<Vec<AccountId>>
+//!         {
 //!             Ok(vec![10, 20, 30])
 //!         }
 //!         fn next_election_prediction(now: BlockNumber) -> BlockNumber {
@@ -139,13 +163,13 @@
 //!         type Error = &'static str;
 //!         type DataProvider = T::DataProvider;
 //!         type MaxWinners = ConstU32<{ u32::MAX }>;
-//!
+//!         type Pages = ConstU32<1>;
 //!     }
 //!
 //!     impl<T: Config> ElectionProvider for GenericElectionProvider<T> {
 //!         fn ongoing() -> bool { false }
-//!         fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error> {
-//!             Self::DataProvider::electable_targets(DataProviderBounds::default())
+//!         fn elect(page: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error> {
+//!             Self::DataProvider::electable_targets(DataProviderBounds::default(), page)
 //!                 .map_err(|_| "failed to elect")
 //!                 .map(|t| bounded_vec![(t[0], Support::default())])
 //!         }
@@ -177,6 +201,7 @@ pub mod bounds;
 pub mod onchain;
 pub mod traits;
 
+use frame_support::traits::ConstU32;
 use sp_runtime::{
 	traits::{Bounded, Saturating, Zero},
 	RuntimeDebug,
@@ -273,6 +298,11 @@ pub type IndexAssignmentOf<C> = IndexAssignment<
 	<C as NposSolution>::Accuracy,
 >;
 
+/// The index of a page of an election.
+///
+/// See [Multi-page election](crate#multi-page-election) for more information.
+pub type PageIndex = u32;
+
 /// Types that are used by the data provider trait.
 pub mod data_provider {
 	/// Alias for the result type of the election data provider.
@@ -293,18 +323,29 @@ pub trait ElectionDataProvider {
 	/// All possible targets for the election, i.e. the targets that could become elected, thus
 	/// "electable".
 	///
+	/// The `bounds` apply to the requested `page` only.
+	///
 	/// This should be implemented as a self-weighing function. The implementor should register its
 	/// appropriate weight at the end of execution with the system pallet directly.
-	fn electable_targets(bounds: DataProviderBounds)
-		-> data_provider::Result<Vec<Self::AccountId>>;
+	fn electable_targets(
+		bounds: DataProviderBounds,
+		page: PageIndex,
+	) -> data_provider::Result<Vec<Self::AccountId>>;
 
-	/// All the voters that participate in the election, thus "electing".
+	/// All the voters that participate in the election associated with the given `page`, thus
+	/// "electing".
+	///
+	/// The `bounds` apply to the requested `page` only. Pages are requested from the most
+	/// significant one down to page `0`, which is always the last page of a snapshot.
 	///
 	/// Note that if a notion of self-vote exists, it should be represented here.
 	///
 	/// This should be implemented as a self-weighing function. The implementor should register its
 	/// appropriate weight at the end of execution with the system pallet directly.
-	fn electing_voters(bounds: DataProviderBounds) -> data_provider::Result<Vec<VoterOf<Self>>>;
+	fn electing_voters(
+		bounds: DataProviderBounds,
+		page: PageIndex,
+	) -> data_provider::Result<Vec<VoterOf<Self>>>;
 
 	/// The number of targets to elect.
 	///
@@ -387,6 +428,21 @@ pub trait ElectionProviderBase {
 		BlockNumber = Self::BlockNumber,
 	>;
 
+	/// The number of pages that the election data and result are split into.
+	///
+	/// Must be at least `1`.
+	type Pages: Get<PageIndex>;
+
+	/// The most significant page of the election, i.e. the first page to be processed.
+	fn msp() -> PageIndex {
+		Self::Pages::get().saturating_sub(1)
+	}
+
+	/// The least significant page of the election, i.e. the last page to be processed.
+	fn lsp() -> PageIndex {
+		Zero::zero()
+	}
+
 	/// checked call to `Self::DataProvider::desired_targets()` ensuring the value never exceeds
 	/// [`Self::MaxWinners`].
 	fn desired_targets_checked() -> data_provider::Result<u32> {
@@ -405,16 +461,20 @@ pub trait ElectionProviderBase {
 /// It must always use [`ElectionProviderBase::DataProvider`] to fetch the data it needs.
 ///
 /// This election provider that could function asynchronously. This implies that this election might
-/// needs data ahead of time (ergo, receives no arguments to `elect`), and might be `ongoing` at
-/// times.
+/// needs data ahead of time (ergo, receives only the requested page as argument to `elect`), and
+/// might be `ongoing` at times.
 pub trait ElectionProvider: ElectionProviderBase {
 	/// Indicate if this election provider is currently ongoing an asynchronous election or not.
 	fn ongoing() -> bool;
 
-	/// Performs the election. This should be implemented as a self-weighing function. The
-	/// implementor should register its appropriate weight at the end of execution with the
-	/// system pallet directly.
-	fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error>;
+	/// Performs the election and returns the result of the given `page`.
+	///
+	/// Pages are requested from [`ElectionProviderBase::msp`] down to
+	/// [`ElectionProviderBase::lsp`], and each page is only requested once per election.
+	///
+	/// This should be implemented as a self-weighing function. The implementor should register
+	/// its appropriate weight at the end of execution with the system pallet directly.
+	fn elect(page: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error>;
 }
 
 /// A (almost) marker trait that signifies an election provider as working synchronously. i.e. being
@@ -423,6 +483,8 @@ pub trait ElectionProvider: ElectionProviderBase {
 /// This must still use the same data provider as with [`ElectionProviderBase::DataProvider`].
 /// However, it can optionally overwrite the amount of voters and targets that are fetched from the
 /// data provider at runtime via `forced_input_voters_bound` and `forced_input_target_bound`.
+///
+/// An instant election always happens over a single page, i.e. page `0` of the data provider.
 pub trait InstantElectionProvider: ElectionProviderBase {
 	fn instant_elect(
 		forced_input_voters_bound: DataProviderBounds,
@@ -444,6 +506,7 @@ where
 	type Error = &'static str;
 	type MaxWinners = MaxWinners;
 	type DataProvider = DataProvider;
+	type Pages = ConstU32<1>;
 }
 
 impl<AccountId, BlockNumber, DataProvider, MaxWinners> ElectionProvider
@@ -456,7 +519,7 @@ where
 		false
 	}
 
-	fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error> {
+	fn elect(_: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error> {
 		Err("`NoElection` cannot do anything.")
 	}
 }
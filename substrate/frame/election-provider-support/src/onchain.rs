@@ -22,9 +22,12 @@
 use crate::{
 	bounds::{DataProviderBounds, ElectionBounds, ElectionBoundsBuilder},
 	BoundedSupportsOf, Debug, ElectionDataProvider, ElectionProvider, ElectionProviderBase,
-	InstantElectionProvider, NposSolver, WeightInfo,
+	InstantElectionProvider, NposSolver, PageIndex, WeightInfo,
+};
+use frame_support::{
+	dispatch::DispatchClass,
+	traits::{ConstU32, Get},
 };
-use frame_support::{dispatch::DispatchClass, traits::Get};
 use sp_npos_elections::{
 	assignment_ratio_to_staked_normalized, to_supports, BoundedSupports, ElectionResult, VoteWeight,
 };
@@ -40,6 +43,8 @@ pub enum Error {
 	/// Configurational error caused by `desired_targets` requested by data provider exceeding
 	/// `MaxWinners`.
 	TooManyWinners,
+	/// The requested page does not exist, as the on-chain election only has a single page.
+	InvalidPage,
 }
 
 impl From<sp_npos_elections::Error> for Error {
@@ -54,6 +59,8 @@ impl From<sp_npos_elections::Error> for Error {
 ///
 /// This type has some utilities to make it safe. Nonetheless, it should be used with utmost care. A
 /// thoughtful value must be set as [`Config::Bounds`] to ensure the size of the input is sensible.
+///
+/// The election always happens over a single page, i.e. the whole election is computed at once.
 pub struct OnChainExecution<T: Config>(PhantomData<T>);
 
 #[deprecated(note = "use OnChainExecution, which is bounded by default")]
@@ -98,9 +105,10 @@ pub type OnChainBoundedSupportsOf<E> = BoundedSupports<
 
 fn elect_with_input_bounds<T: Config>(
 	bounds: ElectionBounds,
+	page: PageIndex,
 ) -> Result<OnChainBoundedSupportsOf<T>, Error> {
-	let (voters, targets) = T::DataProvider::electing_voters(bounds.voters)
-		.and_then(|voters| Ok((voters, T::DataProvider::electable_targets(bounds.targets)?)))
+	let (voters, targets) = T::DataProvider::electing_voters(bounds.voters, page)
+		.and_then(|voters| Ok((voters, T::DataProvider::electable_targets(bounds.targets, page)?)))
 		.map_err(Error::DataProvider)?;
 
 	let desired_targets = T::DataProvider::desired_targets().map_err(Error::DataProvider)?;
@@ -151,6 +159,7 @@ impl<T: Config> ElectionProviderBase for OnChainExecution<T> {
 	type Error = Error;
 	type MaxWinners = T::MaxWinners;
 	type DataProvider = T::DataProvider;
+	type Pages = ConstU32<1>;
 }
 
 impl<T: Config> InstantElectionProvider for OnChainExecution<T> {
@@ -163,7 +172,7 @@ impl<T: Config> InstantElectionProvider for OnChainExecution<T> {
 			.targets_or_lower(forced_input_targets_bounds)
 			.build();
 
-		elect_with_input_bounds::<T>(elections_bounds)
+		elect_with_input_bounds::<T>(elections_bounds, Self::lsp())
 	}
 }
 
@@ -172,9 +181,13 @@ impl<T: Config> ElectionProvider for OnChainExecution<T> {
 		false
 	}
 
-	fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error> {
+	fn elect(page: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		if page != Self::lsp() {
+			return Err(Error::InvalidPage)
+		}
+
 		let election_bounds = ElectionBoundsBuilder::from(T::Bounds::get()).build();
-		elect_with_input_bounds::<T>(election_bounds)
+		elect_with_input_bounds::<T>(election_bounds, page)
 	}
 }
 
@@ -265,7 +278,10 @@ mod tests {
 			type AccountId = AccountId;
 			type BlockNumber = BlockNumber;
 			type MaxVotesPerVoter = ConstU32<2>;
-			fn electing_voters(_: DataProviderBounds) -> data_provider::Result<Vec<VoterOf<Self>>> {
+			fn electing_voters(
+				_: DataProviderBounds,
+				_: PageIndex,
+			) -> data_provider::Result<Vec<VoterOf<Self>>> {
 				Ok(vec![
 					(1, 10, bounded_vec![10, 20]),
 					(2, 20, bounded_vec![30, 20]),
@@ -273,7 +289,10 @@ mod tests {
 				])
 			}
 
-			fn electable_targets(_: DataProviderBounds) -> data_provider::Result<Vec<AccountId>> {
+			fn electable_targets(
+				_: DataProviderBounds,
+				_: PageIndex,
+			) -> data_provider::Result<Vec<AccountId>> {
 				Ok(vec![10, 20, 30])
 			}
 
@@ -291,7 +310,7 @@ mod tests {
 	fn onchain_seq_phragmen_works() {
 		sp_io::TestExternalities::new_empty().execute_with(|| {
 			assert_eq!(
-				<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect().unwrap(),
+				<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect(0).unwrap(),
 				vec![
 					(10, Support { total: 25, voters: vec![(1, 10), (3, 15)] }),
 					(30, Support { total: 35, voters: vec![(2, 20), (3, 15)] })
@@ -308,17 +327,28 @@ mod tests {
 			MaxWinners::set(9);
 
 			assert_noop!(
-				<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect(),
+				<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect(0),
 				Error::TooManyWinners,
 			);
 		})
 	}
 
+	#[test]
+	fn only_single_page_is_supported() {
+		sp_io::TestExternalities::new_empty().execute_with(|| {
+			assert_eq!(<OnChainExecution::<PhragmenParams> as ElectionProviderBase>::msp(), 0);
+			assert_noop!(
+				<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect(1),
+				Error::InvalidPage,
+			);
+		})
+	}
+
 	#[test]
 	fn onchain_phragmms_works() {
 		sp_io::TestExternalities::new_empty().execute_with(|| {
 			assert_eq!(
-				<OnChainExecution::<PhragMMSParams> as ElectionProvider>::elect().unwrap(),
+				<OnChainExecution::<PhragMMSParams> as ElectionProvider>::elect(0).unwrap(),
 				vec![
 					(10, Support { total: 25, voters: vec![(1, 10), (3, 15)] }),
 					(30, Support { total: 35, voters: vec![(2, 20), (3, 15)] })
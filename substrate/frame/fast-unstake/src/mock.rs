@@ -75,7 +75,7 @@ impl pallet_balances::Config for Runtime {
 	type WeightInfo = ();
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
-	type RuntimeHoldReason = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type RuntimeFreezeReason = ();
 }
 
@@ -105,19 +105,24 @@ impl frame_election_provider_support::ElectionProviderBase for MockElection {
 	type MaxWinners = MaxWinners;
 	type DataProvider = Staking;
 	type Error = ();
+	type Pages = ConstU32<1>;
 }
 
 impl frame_election_provider_support::ElectionProvider for MockElection {
 	fn ongoing() -> bool {
 		Ongoing::get()
 	}
-	fn elect() -> Result<frame_election_provider_support::BoundedSupportsOf<Self>, Self::Error> {
+	fn elect(
+		_page: frame_election_provider_support::PageIndex,
+	) -> Result<frame_election_provider_support::BoundedSupportsOf<Self>, Self::Error> {
 		Err(())
 	}
 }
 
 impl pallet_staking::Config for Runtime {
+	type OldCurrency = Balances;
 	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type CurrencyBalance = Balance;
 	type UnixTime = pallet_timestamp::Pallet<Self>;
 	type CurrencyToVote = ();
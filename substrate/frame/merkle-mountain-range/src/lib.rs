@@ -259,6 +259,22 @@ where
 	}
 }
 
+/// Stateless ancestry proof verification.
+///
+/// This function can be used to verify that the MMR described by the given
+/// [primitives::AncestryProof] (`ancestry_proof`) is an ancestor of the MMR with the known root
+/// hash `root`. Returns the root hash of the ancestor MMR if the proof is valid.
+pub fn verify_ancestry_proof<H, L>(
+	root: H::Output,
+	ancestry_proof: primitives::AncestryProof<H::Output>,
+) -> Result<H::Output, primitives::Error>
+where
+	H: traits::Hash,
+	L: primitives::FullLeaf,
+{
+	mmr::verify_ancestry_proof::<H, L>(root, ancestry_proof)
+}
+
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// Build offchain key from `parent_hash` of block that originally added node `pos` to MMR.
 	///
@@ -307,6 +323,14 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		utils::block_num_to_leaf_index::<HeaderFor<T>>(block_num, first_mmr_block)
 	}
 
+	/// Convert a block number into the number of leaves that the MMR had at that block.
+	pub fn block_num_to_leaf_count(block_num: BlockNumberFor<T>) -> Result<LeafIndex, Error> {
+		let leaf_index = Self::block_num_to_leaf_index(block_num)?;
+		leaf_index.checked_add(1).ok_or_else(|| {
+			Error::InvalidNumericOp.log_debug("The number of leaves couldn't be computed.")
+		})
+	}
+
 	/// Generate an MMR proof for the given `block_numbers`.
 	/// If `best_known_block_number = Some(n)`, this generates a historical proof for
 	/// the chain with head at height `n`.
@@ -339,6 +363,43 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		mmr.generate_proof(leaf_indices)
 	}
 
+	/// Generate a proof that the MMR at `prev_block_number` is an ancestor of the MMR at
+	/// `best_known_block_number`.
+	/// If `best_known_block_number` is `None`, the MMR at the current block height is used.
+	///
+	/// Note this method can only be used from an off-chain context
+	/// (Offchain Worker or Runtime API call), since it requires
+	/// all the nodes to be present.
+	pub fn generate_ancestry_proof(
+		prev_block_number: BlockNumberFor<T>,
+		best_known_block_number: Option<BlockNumberFor<T>>,
+	) -> Result<primitives::AncestryProof<HashOf<T, I>>, Error> {
+		// check whether best_known_block_number provided, else use current best block
+		let best_known_block_number =
+			best_known_block_number.unwrap_or_else(|| <frame_system::Pallet<T>>::block_number());
+
+		let leaf_count = Self::block_num_to_leaf_count(best_known_block_number)?;
+		let prev_leaf_count = Self::block_num_to_leaf_count(prev_block_number)?;
+		if prev_leaf_count > leaf_count {
+			return Err(Error::InvalidBestKnownBlock
+				.log_debug("The previous block is higher than the best known block."))
+		}
+
+		let mmr: ModuleMmr<mmr::storage::OffchainStorage, T, I> = mmr::Mmr::new(leaf_count);
+		mmr.generate_ancestry_proof(prev_leaf_count)
+	}
+
+	/// Verify that the MMR described by `ancestry_proof` is an ancestor of the MMR with the given
+	/// `root`, returning the root of the ancestor MMR.
+	///
+	/// This method is safe to use within the runtime code.
+	pub fn verify_ancestry_proof(
+		root: HashOf<T, I>,
+		ancestry_proof: primitives::AncestryProof<HashOf<T, I>>,
+	) -> Result<HashOf<T, I>, Error> {
+		verify_ancestry_proof::<HashingOf<T, I>, LeafOf<T, I>>(root, ancestry_proof)
+	}
+
 	/// Return the on-chain MMR root hash.
 	pub fn mmr_root() -> HashOf<T, I> {
 		Self::mmr_root_hash()
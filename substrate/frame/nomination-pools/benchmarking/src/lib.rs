@@ -29,22 +29,24 @@ use frame_support::{
 	assert_ok, ensure,
 	traits::{
 		fungible::{Inspect, Mutate, Unbalanced},
-		Get,
+		tokens::Preservation,
+		Get, Imbalance,
 	},
 };
 use frame_system::RawOrigin as RuntimeOrigin;
 use pallet_nomination_pools::{
+	adapter::{StakeStrategy, StakeStrategyType},
 	BalanceOf, BondExtra, BondedPoolInner, BondedPools, ClaimPermission, ClaimPermissions,
 	Commission, CommissionChangeRate, CommissionClaimPermission, ConfigOp, GlobalMaxCommission,
 	MaxPoolMembers, MaxPoolMembersPerPool, MaxPools, Metadata, MinCreateBond, MinJoinBond,
-	Pallet as Pools, PoolMembers, PoolRoles, PoolState, RewardPools, SubPoolsStorage,
+	Pallet as Pools, PoolId, PoolMembers, PoolRoles, PoolState, RewardPools, SubPoolsStorage,
 };
 use pallet_staking::MaxNominationsOf;
 use sp_runtime::{
-	traits::{Bounded, StaticLookup, Zero},
+	traits::{Bounded, StaticLookup, UniqueSaturatedInto, Zero},
 	Perbill,
 };
-use sp_staking::{EraIndex, StakingInterface};
+use sp_staking::{EraIndex, StakingInterface, StakingUnchecked};
 use sp_std::{vec, vec::Vec};
 // `frame_benchmarking::benchmarks!` macro needs this
 use pallet_nomination_pools::Call;
@@ -119,6 +121,45 @@ fn vote_to_balance<T: pallet_nomination_pools::Config>(
 	vote.try_into().map_err(|_| "could not convert u64 to Balance")
 }
 
+/// Moves the pool `pool_id` back to [`StakeStrategyType::Transfer`], so that its migration to
+/// delegation based staking can be benchmarked.
+fn migrate_to_transfer_stake<T: Config>(pool_id: PoolId) {
+	if T::StakeAdapter::strategy_type() == StakeStrategyType::Transfer {
+		// the pool already uses the transfer strategy.
+		return
+	}
+	let pool_account = Pools::<T>::create_bonded_account(pool_id);
+	// drop the agent and release the funds delegated by the members.
+	T::StakeAdapter::remove_as_agent(&pool_account);
+
+	// transfer the funds of all members to the pool account.
+	PoolMembers::<T>::iter()
+		.filter(|(_, member)| member.pool_id == pool_id)
+		.for_each(|(member_account, member)| {
+			CurrencyOf::<T>::transfer(
+				&member_account,
+				&pool_account,
+				member.total_balance(),
+				Preservation::Preserve,
+			)
+			.expect("member should have enough balance to transfer");
+		});
+
+	// the pool account needs to keep the existential deposit free to hold its stake.
+	let _ = CurrencyOf::<T>::mint_into(&pool_account, CurrencyOf::<T>::minimum_balance());
+
+	pallet_staking::Pallet::<T>::migrate_to_direct_staker(&pool_account);
+}
+
+/// Asserts `value` only if the runtime uses [`StakeStrategyType::Delegate`].
+///
+/// The calls benchmarked with it are not supported by the transfer strategy.
+fn assert_if_delegate<T: pallet_nomination_pools::Config>(value: bool) {
+	if T::StakeAdapter::strategy_type() == StakeStrategyType::Delegate {
+		assert!(value);
+	}
+}
+
 #[allow(unused)]
 struct ListScenario<T: pallet_nomination_pools::Config> {
 	/// Stash/Controller that is expected to be moved.
@@ -157,7 +198,7 @@ impl<T: Config> ListScenario<T> {
 		let (pool_creator1, pool_origin1) =
 			create_pool_account::<T>(USER_SEED + 1, origin_weight, Some(Perbill::from_percent(50)));
 
-		T::Staking::nominate(
+		T::StakeAdapter::nominate(
 			&pool_origin1,
 			// NOTE: these don't really need to be validators.
 			vec![account("random_validator", 0, USER_SEED)],
@@ -166,7 +207,7 @@ impl<T: Config> ListScenario<T> {
 		let (_, pool_origin2) =
 			create_pool_account::<T>(USER_SEED + 2, origin_weight, Some(Perbill::from_percent(50)));
 
-		T::Staking::nominate(
+		T::StakeAdapter::nominate(
 			&pool_origin2,
 			vec![account("random_validator", 0, USER_SEED)].clone(),
 		)?;
@@ -184,7 +225,7 @@ impl<T: Config> ListScenario<T> {
 		let (_, pool_dest1) =
 			create_pool_account::<T>(USER_SEED + 3, dest_weight, Some(Perbill::from_percent(50)));
 
-		T::Staking::nominate(&pool_dest1, vec![account("random_validator", 0, USER_SEED)])?;
+		T::StakeAdapter::nominate(&pool_dest1, vec![account("random_validator", 0, USER_SEED)])?;
 
 		let weight_of = pallet_staking::Pallet::<T>::weight_of_fn();
 		assert_eq!(vote_to_balance::<T>(weight_of(&pool_origin1)).unwrap(), origin_weight);
@@ -210,11 +251,12 @@ impl<T: Config> ListScenario<T> {
 		self.origin1_member = Some(joiner.clone());
 		CurrencyOf::<T>::set_balance(&joiner, amount * 2u32.into());
 
-		let original_bonded = T::Staking::active_stake(&self.origin1).unwrap();
+		let original_bonded = T::StakeAdapter::active_stake(&self.origin1);
 
 		// Unbond `amount` from the underlying pool account so when the member joins
 		// we will maintain `current_bonded`.
-		T::Staking::unbond(&self.origin1, amount).expect("the pool was created in `Self::new`.");
+		T::StakeAdapter::unbond(&self.origin1, amount)
+			.expect("the pool was created in `Self::new`.");
 
 		// Account pool points for the unbonded balance.
 		BondedPools::<T>::mutate(&1, |maybe_pool| {
@@ -243,7 +285,7 @@ frame_benchmarking::benchmarks! {
 		// setup the worst case list scenario.
 		let scenario = ListScenario::<T>::new(origin_weight, true)?;
 		assert_eq!(
-			T::Staking::active_stake(&scenario.origin1).unwrap(),
+			T::StakeAdapter::active_stake(&scenario.origin1),
 			origin_weight
 		);
 
@@ -258,7 +300,7 @@ frame_benchmarking::benchmarks! {
 	verify {
 		assert_eq!(CurrencyOf::<T>::balance(&joiner), joiner_free - max_additional);
 		assert_eq!(
-			T::Staking::active_stake(&scenario.origin1).unwrap(),
+			T::StakeAdapter::active_stake(&scenario.origin1),
 			scenario.dest_weight
 		);
 	}
@@ -273,7 +315,7 @@ frame_benchmarking::benchmarks! {
 	}: bond_extra(RuntimeOrigin::Signed(scenario.creator1.clone()), BondExtra::FreeBalance(extra))
 	verify {
 		assert!(
-			T::Staking::active_stake(&scenario.origin1).unwrap() >=
+			T::StakeAdapter::active_stake(&scenario.origin1) >=
 			scenario.dest_weight
 		);
 	}
@@ -297,7 +339,7 @@ frame_benchmarking::benchmarks! {
 	verify {
 		 // commission of 50% deducted here.
 		assert!(
-			T::Staking::active_stake(&scenario.origin1).unwrap() >=
+			T::StakeAdapter::active_stake(&scenario.origin1) >=
 			scenario.dest_weight / 2u32.into()
 		);
 	}
@@ -351,7 +393,7 @@ frame_benchmarking::benchmarks! {
 		whitelist_account!(member_id);
 	}: _(RuntimeOrigin::Signed(member_id.clone()), member_id_lookup, all_points)
 	verify {
-		let bonded_after = T::Staking::active_stake(&scenario.origin1).unwrap();
+		let bonded_after = T::StakeAdapter::active_stake(&scenario.origin1);
 		// We at least went down to the destination bag
 		assert!(bonded_after <= scenario.dest_weight);
 		let member = PoolMembers::<T>::get(
@@ -360,7 +402,7 @@ frame_benchmarking::benchmarks! {
 		.unwrap();
 		assert_eq!(
 			member.unbonding_eras.keys().cloned().collect::<Vec<_>>(),
-			vec![0 + T::Staking::bonding_duration()]
+			vec![0 + T::StakeAdapter::bonding_duration()]
 		);
 		assert_eq!(
 			member.unbonding_eras.values().cloned().collect::<Vec<_>>(),
@@ -382,7 +424,7 @@ frame_benchmarking::benchmarks! {
 
 		// Sanity check join worked
 		assert_eq!(
-			T::Staking::active_stake(&pool_account).unwrap(),
+			T::StakeAdapter::active_stake(&pool_account),
 			min_create_bond + min_join_bond
 		);
 		assert_eq!(CurrencyOf::<T>::balance(&joiner), min_join_bond);
@@ -392,7 +434,7 @@ frame_benchmarking::benchmarks! {
 
 		// Sanity check that unbond worked
 		assert_eq!(
-			T::Staking::active_stake(&pool_account).unwrap(),
+			T::StakeAdapter::active_stake(&pool_account),
 			min_create_bond
 		);
 		assert_eq!(pallet_staking::Ledger::<T>::get(&pool_account).unwrap().unlocking.len(), 1);
@@ -425,7 +467,7 @@ frame_benchmarking::benchmarks! {
 
 		// Sanity check join worked
 		assert_eq!(
-			T::Staking::active_stake(&pool_account).unwrap(),
+			T::StakeAdapter::active_stake(&pool_account),
 			min_create_bond + min_join_bond
 		);
 		assert_eq!(CurrencyOf::<T>::balance(&joiner), min_join_bond);
@@ -436,7 +478,7 @@ frame_benchmarking::benchmarks! {
 
 		// Sanity check that unbond worked
 		assert_eq!(
-			T::Staking::active_stake(&pool_account).unwrap(),
+			T::StakeAdapter::active_stake(&pool_account),
 			min_create_bond
 		);
 		assert_eq!(pallet_staking::Ledger::<T>::get(&pool_account).unwrap().unlocking.len(), 1);
@@ -482,11 +524,11 @@ frame_benchmarking::benchmarks! {
 
 		// Sanity check that unbond worked
 		assert_eq!(
-			T::Staking::active_stake(&pool_account).unwrap(),
+			T::StakeAdapter::active_stake(&pool_account),
 			Zero::zero()
 		);
 		assert_eq!(
-			CurrencyOf::<T>::balance(&pool_account),
+			T::StakeAdapter::total_balance(&pool_account),
 			min_create_bond
 		);
 		assert_eq!(pallet_staking::Ledger::<T>::get(&pool_account).unwrap().unlocking.len(), 1);
@@ -527,8 +569,12 @@ frame_benchmarking::benchmarks! {
 		let depositor: T::AccountId = account("depositor", USER_SEED, 0);
 		let depositor_lookup = T::Lookup::unlookup(depositor.clone());
 
-		// Give the depositor some balance to bond
-		CurrencyOf::<T>::set_balance(&depositor, min_create_bond * 2u32.into());
+		// Give the depositor some balance to bond. It also needs to transfer the minimum balance to
+		// the reward account, so give it some additional minimum balance.
+		CurrencyOf::<T>::set_balance(
+			&depositor,
+			min_create_bond + CurrencyOf::<T>::minimum_balance() * 2u32.into()
+		);
 
 		// Make sure no Pools exist at a pre-condition for our verify checks
 		assert_eq!(RewardPools::<T>::count(), 0);
@@ -562,8 +608,8 @@ frame_benchmarking::benchmarks! {
 			}
 		);
 		assert_eq!(
-			T::Staking::active_stake(&Pools::<T>::create_bonded_account(1)),
-			Ok(min_create_bond)
+			T::StakeAdapter::active_stake(&Pools::<T>::create_bonded_account(1)),
+			min_create_bond
 		);
 	}
 
@@ -602,8 +648,8 @@ frame_benchmarking::benchmarks! {
 			}
 		);
 		assert_eq!(
-			T::Staking::active_stake(&Pools::<T>::create_bonded_account(1)),
-			Ok(min_create_bond)
+			T::StakeAdapter::active_stake(&Pools::<T>::create_bonded_account(1)),
+			min_create_bond
 		);
 	}
 
@@ -687,13 +733,19 @@ frame_benchmarking::benchmarks! {
 			.map(|i| account("stash", USER_SEED, i))
 			.collect();
 
-		assert_ok!(T::Staking::nominate(&pool_account, validators));
-		assert!(T::Staking::nominations(&Pools::<T>::create_bonded_account(1)).is_some());
+		assert_ok!(T::StakeAdapter::nominate(&pool_account, validators));
+		assert!(<T::StakeAdapter as StakeStrategy>::CoreStaking::nominations(
+			&Pools::<T>::create_bonded_account(1)
+		)
+		.is_some());
 
 		whitelist_account!(depositor);
 	}:_(RuntimeOrigin::Signed(depositor.clone()), 1)
 	verify {
-		assert!(T::Staking::nominations(&Pools::<T>::create_bonded_account(1)).is_none());
+		assert!(<T::StakeAdapter as StakeStrategy>::CoreStaking::nominations(
+			&Pools::<T>::create_bonded_account(1)
+		)
+		.is_none());
 	}
 
 	set_commission {
@@ -792,7 +844,7 @@ frame_benchmarking::benchmarks! {
 
 		// Sanity check join worked
 		assert_eq!(
-			T::Staking::active_stake(&pool_account).unwrap(),
+			T::StakeAdapter::active_stake(&pool_account),
 			min_create_bond + min_join_bond
 		);
 	}:_(RuntimeOrigin::Signed(joiner.clone()), ClaimPermission::PermissionlessAll)
@@ -844,6 +896,119 @@ frame_benchmarking::benchmarks! {
 		assert!(&Pools::<T>::check_ed_imbalance().is_ok());
 	}
 
+	apply_slash {
+		// a pending slash is only left to be applied by members with the delegate strategy, with
+		// the transfer strategy the call is rejected.
+		let deposit_amount =
+			Pools::<T>::depositor_min_bond() * T::MaxUnbonding::get().into() * 4u32.into();
+		let (depositor, pool_account) = create_pool_account::<T>(0, deposit_amount, None);
+		let depositor_lookup = T::Lookup::unlookup(depositor.clone());
+		assert_if_delegate::<T>(
+			T::StakeAdapter::member_delegation_balance(&depositor) == deposit_amount
+		);
+
+		// slash the pool by half.
+		let slash_amount = deposit_amount / 2u32.into();
+		let staking_slash_amount: u128 = slash_amount.unique_saturated_into();
+		pallet_staking::slashing::do_slash::<T>(
+			&pool_account,
+			staking_slash_amount.unique_saturated_into(),
+			&mut Zero::zero(),
+			&mut pallet_staking::NegativeImbalanceOf::<T>::zero(),
+			EraIndex::zero(),
+		);
+		assert_eq!(
+			PoolMembers::<T>::get(&depositor).unwrap().total_balance(),
+			deposit_amount - slash_amount
+		);
+
+		// fill the unbonding pools of the member for the worst case.
+		for i in 1..(T::MaxUnbonding::get() + 1) {
+			pallet_staking::CurrentEra::<T>::put(i);
+			assert_ok!(Pools::<T>::unbond(
+				RuntimeOrigin::Signed(depositor.clone()).into(),
+				depositor_lookup.clone(),
+				Pools::<T>::depositor_min_bond(),
+			));
+		}
+		pallet_staking::CurrentEra::<T>::put(T::MaxUnbonding::get() + 2);
+
+		let slash_reporter =
+			create_funded_user_with_balance::<T>("slasher", 0, CurrencyOf::<T>::minimum_balance());
+		whitelist_account!(depositor);
+	}: {
+		assert_if_delegate::<T>(
+			Pools::<T>::apply_slash(
+				RuntimeOrigin::Signed(slash_reporter.clone()).into(),
+				depositor_lookup.clone(),
+			)
+			.is_ok()
+		);
+	}
+	verify {
+		assert_if_delegate::<T>(
+			T::StakeAdapter::member_delegation_balance(&depositor) == deposit_amount - slash_amount
+		);
+	}
+
+	migrate_delegation {
+		let deposit_amount = Pools::<T>::depositor_min_bond() * 2u32.into();
+		let (depositor, pool_account) = create_pool_account::<T>(0, deposit_amount, None);
+		let depositor_lookup = T::Lookup::unlookup(depositor.clone());
+
+		// move the pool back to the transfer strategy, then migrate it to delegation again
+		// without migrating the delegation of its members.
+		migrate_to_transfer_stake::<T>(1);
+		assert_eq!(T::StakeAdapter::pool_strategy(&pool_account), StakeStrategyType::Transfer);
+		assert_if_delegate::<T>(
+			Pools::<T>::migrate_pool_to_delegate_stake(
+				RuntimeOrigin::Signed(depositor.clone()).into(),
+				1u32.into(),
+			)
+			.is_ok()
+		);
+		assert!(T::StakeAdapter::member_delegation_balance(&depositor).is_zero());
+
+		whitelist_account!(depositor);
+	}: {
+		assert_if_delegate::<T>(
+			Pools::<T>::migrate_delegation(
+				RuntimeOrigin::Signed(depositor.clone()).into(),
+				depositor_lookup.clone(),
+			)
+			.is_ok()
+		);
+	}
+	verify {
+		assert_if_delegate::<T>(
+			T::StakeAdapter::member_delegation_balance(&depositor) == deposit_amount
+		);
+	}
+
+	migrate_pool_to_delegate_stake {
+		let deposit_amount = Pools::<T>::depositor_min_bond() * 2u32.into();
+		let (depositor, pool_account) = create_pool_account::<T>(0, deposit_amount, None);
+
+		// move the pool back to the transfer strategy.
+		migrate_to_transfer_stake::<T>(1);
+		assert_eq!(T::StakeAdapter::pool_strategy(&pool_account), StakeStrategyType::Transfer);
+
+		whitelist_account!(depositor);
+	}: {
+		assert_if_delegate::<T>(
+			Pools::<T>::migrate_pool_to_delegate_stake(
+				RuntimeOrigin::Signed(depositor.clone()).into(),
+				1u32.into(),
+			)
+			.is_ok()
+		);
+	}
+	verify {
+		assert_if_delegate::<T>(
+			T::StakeAdapter::pool_strategy(&pool_account) == StakeStrategyType::Delegate
+		);
+	}
+
 	impl_benchmark_test_suite!(
 		Pallet,
 		crate::mock::new_test_ext(),
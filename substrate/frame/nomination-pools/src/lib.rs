@@ -343,9 +343,11 @@
 //!
 //! ### Limitations
 //!
-//! * PoolMembers cannot vote with their staked funds because they are transferred into the pools
-//!   account. In the future this can be overcome by allowing the members to vote with their bonded
-//!   funds via vote splitting.
+//! * PoolMembers cannot vote with their staked funds out of the box because they are transferred
+//!   into the pools account, unless the pallet is configured with the [`adapter::DelegateStake`]
+//!   strategy where the funds stay held in the member accounts. Otherwise, the pallet can be used
+//!   as the [`VotingPowerProvider`] of a voting pallet, such that members can vote with their share
+//!   of the pool. While such votes lock the share, the member cannot withdraw it.
 //! * PoolMembers cannot quickly transfer to another pool if they do no like nominations, instead
 //!   they must wait for the unbonding duration.
 
@@ -359,7 +361,7 @@ use frame_support::{
 	traits::{
 		fungible::{Inspect, InspectFreeze, Mutate, MutateFreeze},
 		tokens::{Fortitude, Preservation},
-		Defensive, DefensiveOption, DefensiveResult, DefensiveSaturating, Get,
+		Defensive, DefensiveOption, DefensiveResult, DefensiveSaturating, Get, VotingPowerProvider,
 	},
 	DefaultNoBound, PalletError,
 };
@@ -397,12 +399,15 @@ pub mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod adapter;
 pub mod migration;
 pub mod weights;
 
 pub use pallet::*;
 pub use weights::WeightInfo;
 
+use adapter::{StakeStrategy, StakeStrategyType};
+
 /// The balance type used by the currency system.
 pub type BalanceOf<T> =
 	<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
@@ -425,7 +430,7 @@ pub enum ConfigOp<T: Codec + Debug> {
 }
 
 /// The type of bonding that can happen to a pool.
-enum BondType {
+pub enum BondType {
 	/// Someone is bonding into the pool upon creation.
 	Create,
 	/// Someone is adding more funds later to this pool.
@@ -545,9 +550,15 @@ impl<T: Config> PoolMember<T> {
 
 	/// Total balance of the member, both active and unbonding.
 	/// Doesn't mutate state.
-	#[cfg(any(feature = "try-runtime", feature = "fuzzing", test, debug_assertions))]
-	fn total_balance(&self) -> BalanceOf<T> {
-		let pool = BondedPool::<T>::get(self.pool_id).unwrap();
+	pub fn total_balance(&self) -> BalanceOf<T> {
+		let pool = match BondedPool::<T>::get(self.pool_id) {
+			Some(pool) => pool,
+			None => {
+				// this internal function is always called with a valid pool id.
+				defensive!("pool should exist; qed");
+				return Zero::zero()
+			},
+		};
 		let active_balance = pool.points_to_balance(self.active_points());
 
 		let sub_pools = match SubPoolsStorage::<T>::get(self.pool_id) {
@@ -991,8 +1002,7 @@ impl<T: Config> BondedPool<T> {
 	///
 	/// This is often used for bonding and issuing new funds into the pool.
 	fn balance_to_point(&self, new_funds: BalanceOf<T>) -> BalanceOf<T> {
-		let bonded_balance =
-			T::Staking::active_stake(&self.bonded_account()).unwrap_or(Zero::zero());
+		let bonded_balance = T::StakeAdapter::active_stake(&self.bonded_account());
 		Pallet::<T>::balance_to_point(bonded_balance, self.points, new_funds)
 	}
 
@@ -1000,8 +1010,7 @@ impl<T: Config> BondedPool<T> {
 	///
 	/// This is often used for unbonding.
 	fn points_to_balance(&self, points: BalanceOf<T>) -> BalanceOf<T> {
-		let bonded_balance =
-			T::Staking::active_stake(&self.bonded_account()).unwrap_or(Zero::zero());
+		let bonded_balance = T::StakeAdapter::active_stake(&self.bonded_account());
 		Pallet::<T>::point_to_balance(bonded_balance, self.points, points)
 	}
 
@@ -1050,14 +1059,7 @@ impl<T: Config> BondedPool<T> {
 
 	/// The pools balance that is transferable provided it is expendable by staking pallet.
 	fn transferable_balance(&self) -> BalanceOf<T> {
-		let account = self.bonded_account();
-		// Note on why we can't use `Currency::reducible_balance`: Since pooled account has a
-		// provider (staking pallet), the account can not be set expendable by
-		// `pallet-nomination-pool`. This means reducible balance always returns balance preserving
-		// ED in the account. What we want though is transferable balance given the account can be
-		// dusted.
-		T::Currency::balance(&account)
-			.saturating_sub(T::Staking::active_stake(&account).unwrap_or_default())
+		T::StakeAdapter::transferable_balance(&self.bonded_account())
 	}
 
 	fn is_root(&self, who: &T::AccountId) -> bool {
@@ -1123,8 +1125,7 @@ impl<T: Config> BondedPool<T> {
 	fn ok_to_be_open(&self) -> Result<(), DispatchError> {
 		ensure!(!self.is_destroying(), Error::<T>::CanNotChangeState);
 
-		let bonded_balance =
-			T::Staking::active_stake(&self.bonded_account()).unwrap_or(Zero::zero());
+		let bonded_balance = T::StakeAdapter::active_stake(&self.bonded_account());
 		ensure!(!bonded_balance.is_zero(), Error::<T>::OverflowRisk);
 
 		let points_to_balance_ratio_floor = self
@@ -1243,8 +1244,9 @@ impl<T: Config> BondedPool<T> {
 	/// Bond exactly `amount` from `who`'s funds into this pool. Increases the [`TotalValueLocked`]
 	/// by `amount`.
 	///
-	/// If the bond is [`BondType::Create`], [`Staking::bond`] is called, and `who` is allowed to be
-	/// killed. Otherwise, [`Staking::bond_extra`] is called and `who` cannot be killed.
+	/// If the bond is [`BondType::Create`], [`StakingInterface::bond`] is called, and `who` is
+	/// allowed to be killed. Otherwise, [`StakingInterface::bond_extra`] is called and `who`
+	/// cannot be killed. How the funds of `who` are moved depends on [`Config::StakeAdapter`].
 	///
 	/// Returns `Ok(points_issues)`, `Err` otherwise.
 	fn try_bond_funds(
@@ -1255,26 +1257,21 @@ impl<T: Config> BondedPool<T> {
 	) -> Result<BalanceOf<T>, DispatchError> {
 		// Cache the value
 		let bonded_account = self.bonded_account();
-		T::Currency::transfer(
-			who,
-			&bonded_account,
-			amount,
-			match ty {
-				BondType::Create => Preservation::Expendable,
-				BondType::Later => Preservation::Preserve,
-			},
-		)?;
+
 		// We must calculate the points issued *before* we bond who's funds, else points:balance
 		// ratio will be wrong.
 		let points_issued = self.issue(amount);
 
-		match ty {
-			BondType::Create => T::Staking::bond(&bonded_account, amount, &self.reward_account())?,
-			// The pool should always be created in such a way its in a state to bond extra, but if
-			// the active balance is slashed below the minimum bonded or the account cannot be
-			// found, we exit early.
-			BondType::Later => T::Staking::bond_extra(&bonded_account, amount)?,
+		if let BondType::Create = ty {
+			// The bonded account is kept alive by the pool, such that all of its funds can be
+			// held by the staking pallet without leaving an existential deposit free.
+			frame_system::Pallet::<T>::inc_providers(&bonded_account);
 		}
+
+		// The pool should always be created in such a way its in a state to bond extra, but if
+		// the active balance is slashed below the minimum bonded or the account cannot be found,
+		// we exit early.
+		T::StakeAdapter::pledge_bond(who, &bonded_account, &self.reward_account(), amount, ty)?;
 		TotalValueLocked::<T>::mutate(|tvl| {
 			tvl.saturating_accrue(amount);
 		});
@@ -1565,7 +1562,7 @@ impl<T: Config> Get<u32> for TotalUnbondingPools<T> {
 		// NOTE: this may be dangerous in the scenario bonding_duration gets decreased because
 		// we would no longer be able to decode `BoundedBTreeMap::<EraIndex, UnbondPool<T>,
 		// TotalUnbondingPools<T>>`, which uses `TotalUnbondingPools` as the bound
-		T::Staking::bonding_duration() + T::PostUnbondingPoolsWindow::get()
+		T::StakeAdapter::bonding_duration() + T::PostUnbondingPoolsWindow::get()
 	}
 }
 
@@ -1577,7 +1574,7 @@ pub mod pallet {
 	use sp_runtime::Perbill;
 
 	/// The current storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(8);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(9);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -1641,8 +1638,14 @@ pub mod pallet {
 		/// Infallible method for converting `U256` to `Currency::Balance`.
 		type U256ToBalance: Convert<U256, BalanceOf<Self>>;
 
-		/// The interface for nominating.
-		type Staking: StakingInterface<Balance = BalanceOf<Self>, AccountId = Self::AccountId>;
+		/// The adapter used to stake the funds of pool members.
+		///
+		/// See [`adapter::TransferStake`] and [`adapter::DelegateStake`] for the supported
+		/// strategies.
+		type StakeAdapter: adapter::StakeStrategy<
+			AccountId = Self::AccountId,
+			Balance = BalanceOf<Self>,
+		>;
 
 		/// The amount of eras a `SubPools::with_era` pool can exist before it gets merged into the
 		/// `SubPools::no_era` pool. In other words, this is the amount of eras a member will be
@@ -1743,6 +1746,13 @@ pub mod pallet {
 	pub type ClaimPermissions<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, ClaimPermission, ValueQuery>;
 
+	/// The part of the voting power of a pool member that is locked by a voting pallet.
+	///
+	/// See [`VotingPowerProvider::set_lock`].
+	#[pallet::storage]
+	pub type VotingPowerLocks<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub min_join_bond: BalanceOf<T>,
@@ -1943,6 +1953,16 @@ pub mod pallet {
 		BondExtraRestricted,
 		/// No imbalance in the ED deposit for the pool.
 		NothingToAdjust,
+		/// No slash pending that can be applied to the member.
+		NothingToSlash,
+		/// The pool or member delegation has already migrated to delegate stake.
+		AlreadyMigrated,
+		/// The pool or member delegation has not migrated yet to delegate stake.
+		NotMigrated,
+		/// This call is not allowed in the current state of the pallet.
+		NotSupported,
+		/// The funds of the member cannot be withdrawn since they are locked by a vote.
+		VotingPowerLocked,
 	}
 
 	#[derive(Encode, Decode, PartialEq, TypeInfo, PalletError, RuntimeDebug)]
@@ -1958,6 +1978,8 @@ pub mod pallet {
 		/// The bonded account should only be killed by the staking system when the depositor is
 		/// withdrawing
 		BondedStashKilledPrematurely,
+		/// The delegation feature is unsupported.
+		DelegationUnsupported,
 	}
 
 	impl<T> From<DefensiveError> for Error<T> {
@@ -2134,12 +2156,12 @@ pub mod pallet {
 				&mut reward_pool,
 			)?;
 
-			let current_era = T::Staking::current_era();
-			let unbond_era = T::Staking::bonding_duration().saturating_add(current_era);
+			let current_era = T::StakeAdapter::current_era();
+			let unbond_era = T::StakeAdapter::bonding_duration().saturating_add(current_era);
 
 			// Unbond in the actual underlying nominator.
 			let unbonding_balance = bonded_pool.dissolve(unbonding_points);
-			T::Staking::unbond(&bonded_pool.bonded_account(), unbonding_balance)?;
+			T::StakeAdapter::unbond(&bonded_pool.bonded_account(), unbonding_balance)?;
 
 			// Note that we lazily create the unbonding pools here if they don't already exist
 			let mut sub_pools = SubPoolsStorage::<T>::get(member.pool_id)
@@ -2202,7 +2224,7 @@ pub mod pallet {
 			// For now we only allow a pool to withdraw unbonded if its not destroying. If the pool
 			// is destroying then `withdraw_unbonded` can be used.
 			ensure!(pool.state != PoolState::Destroying, Error::<T>::NotDestroying);
-			T::Staking::withdraw_unbonded(pool.bonded_account(), num_slashing_spans)?;
+			T::StakeAdapter::withdraw_unbonded(&pool.bonded_account(), num_slashing_spans)?;
 
 			Ok(())
 		}
@@ -2239,7 +2261,7 @@ pub mod pallet {
 			let member_account = T::Lookup::lookup(member_account)?;
 			let mut member =
 				PoolMembers::<T>::get(&member_account).ok_or(Error::<T>::PoolMemberNotFound)?;
-			let current_era = T::Staking::current_era();
+			let current_era = T::StakeAdapter::current_era();
 
 			let bonded_pool = BondedPool::<T>::get(member.pool_id)
 				.defensive_ok_or::<Error<T>>(DefensiveError::PoolNotFound.into())?;
@@ -2247,6 +2269,12 @@ pub mod pallet {
 				SubPoolsStorage::<T>::get(member.pool_id).ok_or(Error::<T>::SubPoolsNotFound)?;
 
 			bonded_pool.ok_to_withdraw_unbonded_with(&caller, &member_account)?;
+			let voting_power = Self::voting_power(&member_account);
+
+			// a pending slash of the member must be applied before their funds are released.
+			if !Self::member_pending_slash(&member_account, &member)?.is_zero() {
+				Self::do_apply_slash(&member_account, None)?;
+			}
 
 			// NOTE: must do this after we have done the `ok_to_withdraw_unbonded_other_with` check.
 			let withdrawn_points = member.withdraw_unlocked(current_era);
@@ -2254,8 +2282,10 @@ pub mod pallet {
 
 			// Before calculating the `balance_to_unbond`, we call withdraw unbonded to ensure the
 			// `transferrable_balance` is correct.
-			let stash_killed =
-				T::Staking::withdraw_unbonded(bonded_pool.bonded_account(), num_slashing_spans)?;
+			let stash_killed = T::StakeAdapter::withdraw_unbonded(
+				&bonded_pool.bonded_account(),
+				num_slashing_spans,
+			)?;
 
 			// defensive-only: the depositor puts enough funds into the stash so that it will only
 			// be destroyed when they are leaving.
@@ -2290,11 +2320,11 @@ pub mod pallet {
 				// order to ensure members can leave the pool and it can be destroyed.
 				.min(bonded_pool.transferable_balance());
 
-			T::Currency::transfer(
-				&bonded_pool.bonded_account(),
+			T::StakeAdapter::member_withdraw(
 				&member_account,
+				&bonded_pool.bonded_account(),
 				balance_to_unbond,
-				Preservation::Expendable,
+				num_slashing_spans,
 			)
 			.defensive()?;
 
@@ -2331,6 +2361,13 @@ pub mod pallet {
 				Some(T::WeightInfo::withdraw_unbonded_update(num_slashing_spans))
 			};
 
+			// the voting power of the member must not drop below what is locked by their votes.
+			let voting_power_lock = VotingPowerLocks::<T>::get(&member_account).min(voting_power);
+			ensure!(
+				Self::voting_power(&member_account) >= voting_power_lock,
+				Error::<T>::VotingPowerLocked
+			);
+
 			Ok(post_info_weight.into())
 		}
 
@@ -2411,7 +2448,7 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			let bonded_pool = BondedPool::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
 			ensure!(bonded_pool.can_nominate(&who), Error::<T>::NotNominator);
-			T::Staking::nominate(&bonded_pool.bonded_account(), validators)
+			T::StakeAdapter::nominate(&bonded_pool.bonded_account(), validators)
 		}
 
 		/// Set a new state for the pool.
@@ -2584,7 +2621,7 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			let bonded_pool = BondedPool::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
 			ensure!(bonded_pool.can_nominate(&who), Error::<T>::NotNominator);
-			T::Staking::chill(&bonded_pool.bonded_account())
+			T::StakeAdapter::chill(&bonded_pool.bonded_account())
 		}
 
 		/// `origin` bonds funds from `extra` for some pool member `member` into their respective
@@ -2784,6 +2821,132 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Apply a pending slash on a member.
+		///
+		/// Fails unless [`crate::pallet::Config::StakeAdapter`] is of strategy type:
+		/// [`adapter::StakeStrategyType::Delegate`].
+		///
+		/// This call can be dispatched permissionlessly (i.e. by any account). If the member has
+		/// slash to be applied, caller may be rewarded with the part of the slash.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::apply_slash())]
+		pub fn apply_slash(
+			origin: OriginFor<T>,
+			member_account: AccountIdLookupOf<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure!(
+				T::StakeAdapter::strategy_type() == StakeStrategyType::Delegate,
+				Error::<T>::NotSupported
+			);
+
+			let who = ensure_signed(origin)?;
+			let member_account = T::Lookup::lookup(member_account)?;
+			Self::do_apply_slash(&member_account, Some(who))?;
+
+			// If successful, refund the fees.
+			Ok(Pays::No.into())
+		}
+
+		/// Migrates delegated funds from the pool account to the `member_account`.
+		///
+		/// Fails unless [`crate::pallet::Config::StakeAdapter`] is of strategy type:
+		/// [`adapter::StakeStrategyType::Delegate`].
+		///
+		/// This is a permission-less call and refunds any fee if claim is successful.
+		///
+		/// If the pool has migrated to delegation based staking, the staked tokens of pool members
+		/// can be moved and held in their own account. See [`adapter::DelegateStake`]
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::migrate_delegation())]
+		pub fn migrate_delegation(
+			origin: OriginFor<T>,
+			member_account: AccountIdLookupOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let _caller = ensure_signed(origin)?;
+
+			ensure!(
+				T::StakeAdapter::strategy_type() == StakeStrategyType::Delegate,
+				Error::<T>::NotSupported
+			);
+
+			let member_account = T::Lookup::lookup(member_account)?;
+			let member =
+				PoolMembers::<T>::get(&member_account).ok_or(Error::<T>::PoolMemberNotFound)?;
+
+			// ensure pool is migrated.
+			let pool_account = Self::create_bonded_account(member.pool_id);
+			ensure!(
+				T::StakeAdapter::pool_strategy(&pool_account) == StakeStrategyType::Delegate,
+				Error::<T>::NotMigrated
+			);
+
+			// ensure member has not migrated already.
+			let delegation = T::StakeAdapter::member_delegation_balance(&member_account);
+			ensure!(delegation.is_zero(), Error::<T>::AlreadyMigrated);
+
+			let pool_contribution = member.total_balance();
+			ensure!(pool_contribution >= MinJoinBond::<T>::get(), Error::<T>::MinimumBondNotMet);
+
+			T::StakeAdapter::migrate_delegation(&pool_account, &member_account, pool_contribution)?;
+
+			// if successful, we refund the fee.
+			Ok(Pays::No.into())
+		}
+
+		/// Migrate pool from [`adapter::StakeStrategyType::Transfer`] to
+		/// [`adapter::StakeStrategyType::Delegate`].
+		///
+		/// Fails unless [`crate::pallet::Config::StakeAdapter`] is of strategy type:
+		/// [`adapter::StakeStrategyType::Delegate`].
+		///
+		/// This call can be dispatched permissionlessly, and refunds any fee if successful.
+		///
+		/// If the pool has already migrated to delegation based staking, this call will fail.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::migrate_pool_to_delegate_stake())]
+		pub fn migrate_pool_to_delegate_stake(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+		) -> DispatchResultWithPostInfo {
+			// gate this call to be called only if `DelegateStake` strategy is used.
+			ensure!(
+				T::StakeAdapter::strategy_type() == StakeStrategyType::Delegate,
+				Error::<T>::NotSupported
+			);
+
+			let _caller = ensure_signed(origin)?;
+			// ensure pool exists.
+			let bonded_pool = BondedPool::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			ensure!(
+				T::StakeAdapter::pool_strategy(&bonded_pool.bonded_account()) ==
+					StakeStrategyType::Transfer,
+				Error::<T>::AlreadyMigrated
+			);
+
+			Self::migrate_to_delegate_stake(pool_id)?;
+			Ok(Pays::No.into())
+		}
+	}
+
+	#[pallet::view_functions]
+	impl<T: Config> Pallet<T> {
+		/// Returns the pending rewards for the member that the `AccountId` was given for.
+		///
+		/// Returns `None` if `who` is not a pool member or if the rewards could not be computed.
+		pub fn pending_rewards(who: T::AccountId) -> Option<BalanceOf<T>> {
+			Self::api_pending_rewards(who)
+		}
+
+		/// Returns the equivalent balance of `points` for a given pool.
+		pub fn points_to_balance(pool_id: PoolId, points: BalanceOf<T>) -> BalanceOf<T> {
+			Self::api_points_to_balance(pool_id, points)
+		}
+
+		/// Returns the equivalent points of `new_funds` for a given pool.
+		pub fn balance_to_points(pool_id: PoolId, new_funds: BalanceOf<T>) -> BalanceOf<T> {
+			Self::api_balance_to_points(pool_id, new_funds)
+		}
 	}
 
 	#[pallet::hooks]
@@ -2799,7 +2962,7 @@ pub mod pallet {
 				"Minimum points to balance ratio must be greater than 0"
 			);
 			assert!(
-				T::Staking::bonding_duration() < TotalUnbondingPools::<T>::get(),
+				T::StakeAdapter::bonding_duration() < TotalUnbondingPools::<T>::get(),
 				"There must be more unbonding pools then the bonding duration /
 				so a slash can be applied to relevant unboding pools. (We assume /
 				the bonding duration > slash deffer duration.",
@@ -2817,7 +2980,7 @@ impl<T: Config> Pallet<T> {
 	/// It is essentially `max { MinNominatorBond, MinCreateBond, MinJoinBond }`, where the former
 	/// is coming from the staking pallet and the latter two are configured in this pallet.
 	pub fn depositor_min_bond() -> BalanceOf<T> {
-		T::Staking::minimum_nominator_bond()
+		T::StakeAdapter::minimum_nominator_bond()
 			.max(MinCreateBond::<T>::get())
 			.max(MinJoinBond::<T>::get())
 			.max(T::Currency::minimum_balance())
@@ -2853,7 +3016,7 @@ impl<T: Config> Pallet<T> {
 			"bonded account of dissolving pool should have no consumers"
 		);
 		defensive_assert!(
-			T::Staking::total_stake(&bonded_account).unwrap_or_default() == Zero::zero(),
+			T::StakeAdapter::total_stake(&bonded_account) == Zero::zero(),
 			"dissolving pool should not have any stake in the staking pallet"
 		);
 
@@ -2882,6 +3045,8 @@ impl<T: Config> Pallet<T> {
 		// NOTE: Defensively force set balance to zero.
 		T::Currency::set_balance(&reward_account, Zero::zero());
 		T::Currency::set_balance(&bonded_pool.bonded_account(), Zero::zero());
+		// Release the provider reference taken when the pool was created.
+		let _ = frame_system::Pallet::<T>::dec_providers(&bonded_pool.bonded_account()).defensive();
 
 		Self::deposit_event(Event::<T>::Destroyed { pool_id: bonded_pool.id });
 		// Remove bonded pool metadata.
@@ -2890,6 +3055,62 @@ impl<T: Config> Pallet<T> {
 		bonded_pool.remove();
 	}
 
+	/// Migrate the bonded account of the pool `id` to a delegated agent of the staking system.
+	///
+	/// The pool reward account becomes the reward destination of the migrated pool.
+	fn migrate_to_delegate_stake(id: PoolId) -> DispatchResult {
+		T::StakeAdapter::migrate_nominator_to_agent(
+			&Self::create_bonded_account(id),
+			&Self::create_reward_account(id),
+		)
+	}
+
+	/// Apply the pending slash of `member_account`, if any, rewarding `reporter` with a part of
+	/// the slash.
+	fn do_apply_slash(
+		member_account: &T::AccountId,
+		reporter: Option<T::AccountId>,
+	) -> DispatchResult {
+		let member = PoolMembers::<T>::get(member_account).ok_or(Error::<T>::PoolMemberNotFound)?;
+
+		let pending_slash = Self::member_pending_slash(member_account, &member)?;
+
+		// if nothing to slash, return error.
+		ensure!(!pending_slash.is_zero(), Error::<T>::NothingToSlash);
+
+		T::StakeAdapter::member_slash(
+			member_account,
+			&Self::create_bonded_account(member.pool_id),
+			pending_slash,
+			reporter,
+		)
+	}
+
+	/// Pending slash for a member.
+	///
+	/// This is the difference between the funds the member has delegated to the pool and their
+	/// share of the funds of the pool. It is always zero for pools using the transfer strategy.
+	fn member_pending_slash(
+		member_account: &T::AccountId,
+		member: &PoolMember<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let pool_account = Self::create_bonded_account(member.pool_id);
+
+		// if pool doesn't have any pending slash, it implies the member also does not have any.
+		if !T::StakeAdapter::has_pending_slash(&pool_account) {
+			return Ok(Zero::zero())
+		}
+
+		// this is their actual held balance that may or may not have been slashed.
+		let actual_balance = T::StakeAdapter::member_delegation_balance(member_account);
+
+		// this is their balance in the pool
+		let expected_balance = member.total_balance();
+
+		// return the amount to be slashed.
+		Ok(actual_balance.saturating_sub(expected_balance))
+	}
+
 	/// Create the main, bonded account of a pool with the given id.
 	pub fn create_bonded_account(id: PoolId) -> T::AccountId {
 		T::PalletId::get().into_sub_account_truncating((AccountType::Bonded, id))
@@ -3426,8 +3647,7 @@ impl<T: Config> Pallet<T> {
 				pool is being destroyed and the depositor is the last member",
 			);
 
-			expected_tvl +=
-				T::Staking::total_stake(&bonded_pool.bonded_account()).unwrap_or_default();
+			expected_tvl += T::StakeAdapter::total_stake(&bonded_pool.bonded_account());
 
 			Ok(())
 		})?;
@@ -3456,8 +3676,8 @@ impl<T: Config> Pallet<T> {
 			let subs = SubPoolsStorage::<T>::get(pool_id).unwrap_or_default();
 
 			let sum_unbonding_balance = subs.sum_unbonding_balance();
-			let bonded_balance = T::Staking::active_stake(&pool_account).unwrap_or_default();
-			let total_balance = T::Currency::total_balance(&pool_account);
+			let bonded_balance = T::StakeAdapter::active_stake(&pool_account);
+			let total_balance = T::StakeAdapter::total_balance(&pool_account);
 
 			assert!(
 				total_balance >= bonded_balance + sum_unbonding_balance,
@@ -3560,8 +3780,7 @@ impl<T: Config> Pallet<T> {
 	/// If the pool ID does not exist, returns 0 ratio balance to points. Used by runtime API.
 	pub fn api_balance_to_points(pool_id: PoolId, new_funds: BalanceOf<T>) -> BalanceOf<T> {
 		if let Some(pool) = BondedPool::<T>::get(pool_id) {
-			let bonded_balance =
-				T::Staking::active_stake(&pool.bonded_account()).unwrap_or(Zero::zero());
+			let bonded_balance = T::StakeAdapter::active_stake(&pool.bonded_account());
 			Pallet::<T>::balance_to_point(bonded_balance, pool.points, new_funds)
 		} else {
 			Zero::zero()
@@ -3569,6 +3788,36 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+impl<T: Config> VotingPowerProvider<T::AccountId, BalanceOf<T>> for Pallet<T> {
+	/// The balance of a pool member, both active and unbonding, that is kept in the pool account.
+	///
+	/// Funds of members that are delegated to the pool stay in their own account and are thus not
+	/// accounted for here.
+	fn voting_power(who: &T::AccountId) -> BalanceOf<T> {
+		PoolMembers::<T>::get(who)
+			.map(|member| {
+				member
+					.total_balance()
+					.saturating_sub(T::StakeAdapter::member_delegation_balance(who))
+			})
+			.unwrap_or_default()
+	}
+
+	/// Prevents the member from withdrawing their unbonded funds below `amount`.
+	fn set_lock(who: &T::AccountId, amount: BalanceOf<T>) {
+		if amount.is_zero() {
+			VotingPowerLocks::<T>::remove(who);
+		} else {
+			VotingPowerLocks::<T>::insert(who, amount);
+		}
+	}
+
+	fn weight() -> Weight {
+		// `PoolMembers`, the delegation of the member and `VotingPowerLocks`.
+		T::DbWeight::get().reads_writes(3, 1)
+	}
+}
+
 impl<T: Config> sp_staking::OnStakingUpdate<T::AccountId, BalanceOf<T>> for Pallet<T> {
 	/// Reduces the balances of the [`SubPools`], that belong to the pool involved in the
 	/// slash, to the amount that is defined in the `slashed_unlocking` field of
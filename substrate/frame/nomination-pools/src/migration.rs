@@ -27,6 +27,15 @@ use sp_runtime::TryRuntimeError;
 pub mod versioned {
 	use super::*;
 
+	/// v9: Adds a provider reference to the bonded account of every existing pool.
+	pub type V8ToV9<T> = frame_support::migrations::VersionedMigration<
+		8,
+		9,
+		v9::VersionUncheckedMigrateV8ToV9<T>,
+		crate::pallet::Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+
 	/// v8: Adds commission claim permissions to `BondedPools`.
 	pub type V7ToV8<T> = frame_support::migrations::VersionedMigration<
 		7,
@@ -109,6 +118,55 @@ pub mod unversioned {
 	}
 }
 
+pub mod v9 {
+	use super::*;
+
+	/// Gives the bonded account of every existing pool the provider reference that newly created
+	/// pools take, which is released when the pool is dissolved.
+	pub struct VersionUncheckedMigrateV8ToV9<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for VersionUncheckedMigrateV8ToV9<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let providers = BondedPools::<T>::iter_keys()
+				.map(|id| {
+					let bonded_account = Pallet::<T>::create_bonded_account(id);
+					(id, frame_system::Pallet::<T>::providers(&bonded_account))
+				})
+				.collect::<BTreeMap<PoolId, u32>>();
+			Ok(providers.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let mut migrated = 0u64;
+			for id in BondedPools::<T>::iter_keys() {
+				let bonded_account = Pallet::<T>::create_bonded_account(id);
+				frame_system::Pallet::<T>::inc_providers(&bonded_account);
+				migrated.saturating_inc();
+			}
+
+			log!(info, "added a provider to the bonded account of {} pools", migrated);
+
+			// reads: migrated * (BondedPools + System::Account)
+			// writes: migrated * System::Account
+			T::DbWeight::get().reads_writes(migrated.saturating_mul(2), migrated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(data: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let providers = BTreeMap::<PoolId, u32>::decode(&mut &data[..])
+				.map_err(|_| "failed to decode the providers of the bonded accounts")?;
+			for (id, before) in providers {
+				let bonded_account = Pallet::<T>::create_bonded_account(id);
+				ensure!(
+					frame_system::Pallet::<T>::providers(&bonded_account) == before + 1,
+					"the provider of the bonded account has not been added."
+				);
+			}
+			Ok(())
+		}
+	}
+}
+
 pub mod v8 {
 	use super::{v7::V7BondedPoolInner, *};
 
@@ -1024,8 +1082,9 @@ mod helpers {
 	pub(crate) fn calculate_tvl_by_total_stake<T: Config>() -> BalanceOf<T> {
 		BondedPools::<T>::iter()
 			.map(|(id, inner)| {
-				T::Staking::total_stake(&BondedPool { id, inner: inner.clone() }.bonded_account())
-					.unwrap_or_default()
+				T::StakeAdapter::total_stake(
+					&BondedPool { id, inner: inner.clone() }.bonded_account(),
+				)
 			})
 			.reduce(|acc, total_balance| acc + total_balance)
 			.unwrap_or_default()
@@ -7338,3 +7338,130 @@ mod slash {
 		});
 	}
 }
+
+mod delegate_stake {
+	use super::*;
+
+	#[test]
+	fn delegation_calls_are_not_supported_with_transfer_stake() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_eq!(
+				<Runtime as Config>::StakeAdapter::strategy_type(),
+				StakeStrategyType::Transfer
+			);
+
+			assert_noop!(
+				Pools::apply_slash(RuntimeOrigin::signed(11), 10),
+				Error::<Runtime>::NotSupported
+			);
+			assert_noop!(
+				Pools::migrate_delegation(RuntimeOrigin::signed(11), 10),
+				Error::<Runtime>::NotSupported
+			);
+			assert_noop!(
+				Pools::migrate_pool_to_delegate_stake(RuntimeOrigin::signed(11), 1),
+				Error::<Runtime>::NotSupported
+			);
+		});
+	}
+
+	#[test]
+	fn members_of_transfer_stake_pools_have_no_pending_slash() {
+		ExtBuilder::default().add_members(vec![(20, 20)]).build_and_execute(|| {
+			// the pool is slashed.
+			StakingMock::slash_by(1, 15);
+
+			// the slash is applied greedily, so nothing is pending for the members.
+			let member = PoolMembers::<Runtime>::get(20).unwrap();
+			assert_eq!(Pools::member_pending_slash(&20, &member), Ok(0));
+			assert_noop!(Pools::do_apply_slash(&20, None), Error::<Runtime>::NothingToSlash);
+		});
+	}
+}
+
+mod migrations {
+	use super::*;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	#[test]
+	fn v9_adds_the_provider_of_existing_pools() {
+		ExtBuilder::default().build_and_execute(|| {
+			let bonded_account = Pools::create_bonded_account(1);
+			let providers = System::providers(&bonded_account);
+
+			// the pool was created before the bonded account was given its own provider.
+			assert_ok!(System::dec_providers(&bonded_account));
+			StorageVersion::new(8).put::<Pools>();
+
+			crate::migration::versioned::V8ToV9::<Runtime>::on_runtime_upgrade();
+
+			assert_eq!(System::providers(&bonded_account), providers);
+			assert_eq!(Pools::on_chain_storage_version(), 9);
+		});
+	}
+}
+
+mod voting_power {
+	use super::*;
+
+	#[test]
+	fn members_can_vote_with_their_share_of_the_pool() {
+		ExtBuilder::default().add_members(vec![(20, 20)]).build_and_execute(|| {
+			assert_eq!(Pools::voting_power(&10), 10);
+			assert_eq!(Pools::voting_power(&20), 20);
+			// not a member.
+			assert_eq!(Pools::voting_power(&30), 0);
+
+			// the pool is slashed in half.
+			StakingMock::slash_by(1, 15);
+			assert_eq!(Pools::voting_power(&10), 5);
+			assert_eq!(Pools::voting_power(&20), 10);
+
+			// unbonding funds still count as voting power.
+			assert_ok!(Pools::unbond(RuntimeOrigin::signed(20), 20, 20));
+			assert_eq!(Pools::voting_power(&20), 10);
+		});
+	}
+
+	#[test]
+	fn locked_voting_power_cannot_be_withdrawn() {
+		ExtBuilder::default().add_members(vec![(20, 20)]).build_and_execute(|| {
+			<Pools as VotingPowerProvider<_, _>>::set_lock(&20, 15);
+			assert_eq!(VotingPowerLocks::<Runtime>::get(20), 15);
+
+			assert_ok!(Pools::unbond(RuntimeOrigin::signed(20), 20, 10));
+			CurrentEra::set(3);
+			// withdrawing would leave 10 of voting power.
+			assert_noop!(
+				Pools::withdraw_unbonded(RuntimeOrigin::signed(20), 20, 0),
+				Error::<Runtime>::VotingPowerLocked
+			);
+
+			// the lock is reduced, e.g. the vote is removed.
+			<Pools as VotingPowerProvider<_, _>>::set_lock(&20, 10);
+			assert_ok!(Pools::withdraw_unbonded(RuntimeOrigin::signed(20), 20, 0));
+			assert_eq!(Pools::voting_power(&20), 10);
+
+			<Pools as VotingPowerProvider<_, _>>::set_lock(&20, 0);
+			assert!(!VotingPowerLocks::<Runtime>::contains_key(20));
+		});
+	}
+
+	#[test]
+	fn slashed_voting_power_stays_locked() {
+		ExtBuilder::default().add_members(vec![(20, 20)]).build_and_execute(|| {
+			<Pools as VotingPowerProvider<_, _>>::set_lock(&20, 20);
+
+			// the pool is slashed in half, so the member is left with 10 of voting power.
+			StakingMock::slash_by(1, 15);
+			assert_ok!(Pools::unbond(RuntimeOrigin::signed(20), 20, 20));
+			CurrentEra::set(3);
+
+			// what is left of the locked funds can still not be withdrawn.
+			assert_noop!(
+				Pools::withdraw_unbonded(RuntimeOrigin::signed(20), 20, 0),
+				Error::<Runtime>::VotingPowerLocked
+			);
+		});
+	}
+}
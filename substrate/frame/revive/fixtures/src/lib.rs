@@ -0,0 +1,230 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fixtures for testing `pallet-revive`.
+//!
+//! The fixtures are PolkaVM programs located under the `data/` directory. They are written in the
+//! text format of the `polkavm-common` assembler, except that syscalls are called by their name,
+//! e.g. `ecalli seal_return`. The called syscalls become the imports of the program.
+//!
+//! Without the `std` feature only the programs used by the benchmarks in [`bench`] are available.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+use polkavm_common::{
+	assembler,
+	program::{self, ProgramParts},
+	writer::Writer,
+};
+
+#[cfg(feature = "std")]
+fn fixtures_root_dir() -> std::path::PathBuf {
+	std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data")
+}
+
+/// Load a given PolkaVM program represented by a .polkavm file and returns its blob.
+///
+/// The fixture files are located under the `data/` directory.
+#[cfg(feature = "std")]
+pub fn compile_module(fixture_name: &str) -> anyhow::Result<Vec<u8>> {
+	let fixture_path = fixtures_root_dir().join(format!("{fixture_name}.polkavm"));
+	assemble(&std::fs::read_to_string(fixture_path)?).map_err(anyhow::Error::msg)
+}
+
+/// Assemble the PolkaVM program `source` into a blob.
+pub fn assemble(source: &str) -> Result<Vec<u8>, String> {
+	let mut imports = Vec::new();
+	let source = source
+		.lines()
+		.map(|line| match line.trim().strip_prefix("ecalli ") {
+			Some(symbol) if symbol.parse::<u32>().is_err() => {
+				let index =
+					imports.iter().position(|import| *import == symbol).unwrap_or_else(|| {
+						imports.push(symbol);
+						imports.len() - 1
+					});
+				format!("ecalli {index}")
+			},
+			_ => line.to_string(),
+		})
+		.collect::<Vec<_>>()
+		.join("\n");
+
+	let blob = assembler::assemble(&source)?;
+	with_imports(blob, &imports)
+}
+
+/// Add an imports section with the given `imports` to `blob`.
+///
+/// The assembler has no notion of imports, hence the blob is decomposed and written anew.
+fn with_imports(blob: Vec<u8>, imports: &[&str]) -> Result<Vec<u8>, String> {
+	let parts = ProgramParts::from_bytes(blob.into()).map_err(|err| err.to_string())?;
+
+	fn push_section(writer: &mut Writer, section: u8, contents: &[u8]) {
+		if !contents.is_empty() {
+			writer.push_byte(section);
+			writer.push_bytes_with_length(contents);
+		}
+	}
+
+	let mut memory_config = Vec::new();
+	let mut writer = Writer::new(&mut memory_config);
+	if parts.ro_data_size > 0 || parts.rw_data_size > 0 || parts.stack_size > 0 {
+		writer.push_varint(parts.ro_data_size);
+		writer.push_varint(parts.rw_data_size);
+		writer.push_varint(parts.stack_size);
+	}
+
+	let mut import_section = Vec::new();
+	let mut writer = Writer::new(&mut import_section);
+	if !imports.is_empty() {
+		writer.push_varint(imports.len() as u32);
+		let mut offset = 0u32;
+		for import in imports {
+			writer.push_u32(offset);
+			offset += import.len() as u32;
+		}
+		for import in imports {
+			writer.push_raw_bytes(import.as_bytes());
+		}
+	}
+
+	let mut output = Vec::new();
+	let mut writer = Writer::new(&mut output);
+	writer.push_raw_bytes(&program::BLOB_MAGIC);
+	writer.push_byte(program::BLOB_VERSION_V1);
+	push_section(&mut writer, program::SECTION_MEMORY_CONFIG, &memory_config);
+	push_section(&mut writer, program::SECTION_RO_DATA, &parts.ro_data);
+	push_section(&mut writer, program::SECTION_RW_DATA, &parts.rw_data);
+	push_section(&mut writer, program::SECTION_IMPORTS, &import_section);
+	push_section(&mut writer, program::SECTION_EXPORTS, &parts.exports);
+	push_section(&mut writer, program::SECTION_CODE_AND_JUMP_TABLE, &parts.code_and_jump_table);
+	writer.push_byte(program::SECTION_END_OF_FILE);
+
+	Ok(output)
+}
+
+/// Programs used by the benchmarks of `pallet-revive`.
+///
+/// They are assembled from sources embedded in this crate so that they are also available
+/// within the runtime.
+pub mod bench {
+	use super::*;
+
+	/// A program which does nothing.
+	pub fn dummy() -> Vec<u8> {
+		assemble(include_str!("../data/dummy.polkavm")).expect("The fixture is valid; qed")
+	}
+
+	/// A program whose blob is roughly `len` bytes large but never smaller than [`dummy`].
+	///
+	/// Both entry points return right away. The remainder of the blob consists of unreachable
+	/// `trap` instructions.
+	pub fn sized(len: u32) -> Vec<u8> {
+		// Eight `trap` instructions take up nine bytes. The rest of the blob is less than 64 bytes.
+		let traps = len.saturating_sub(64) as usize * 8 / 9;
+		let mut source = String::from("pub @deploy:\n\tret\n\npub @call:\n\tret\n");
+		for _ in 0..traps {
+			source.push_str("\ttrap\n");
+		}
+		assemble(&source).expect("The generated program is valid; qed")
+	}
+
+	/// A program whose `call` entry point executes roughly `r` instructions.
+	///
+	/// It copies a word within its stack in a loop of four instructions.
+	pub fn instr(r: u32) -> Vec<u8> {
+		let source = format!(
+			"%stack_size = 4096
+
+pub @deploy:
+	ret
+
+pub @call:
+	sp = sp - 8
+	a0 = {}
+	jump @done if a0 == 0
+@loop:
+	a1 = u32 [sp]
+	u32 [sp + 4] = a1
+	a0 = a0 - 1
+	jump @loop if a0 != 0
+@done:
+	sp = sp + 8
+	ret
+",
+			r / 4,
+		);
+		assemble(&source).expect("The generated program is valid; qed")
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use polkavm_common::program::ProgramBlob;
+
+	#[test]
+	fn syscalls_become_imports() {
+		let blob = super::compile_module("dummy").unwrap();
+		let program = ProgramBlob::parse(blob.into()).unwrap();
+		assert_eq!(program.imports().len(), 0);
+		assert_eq!(
+			program
+				.exports()
+				.map(|export| export.symbol().as_bytes().to_vec())
+				.collect::<Vec<_>>(),
+			vec![b"call".to_vec(), b"deploy".to_vec()],
+		);
+
+		let blob = super::compile_module("storage").unwrap();
+		let program = ProgramBlob::parse(blob.into()).unwrap();
+		assert_eq!(
+			program
+				.imports()
+				.iter()
+				.map(|import| import.unwrap().as_bytes().to_vec())
+				.collect::<Vec<_>>(),
+			[&b"input"[..], b"set_storage", b"get_storage", b"seal_return"].map(<[u8]>::to_vec),
+		);
+	}
+
+	#[test]
+	fn sized_programs_stay_within_len() {
+		use super::bench::{dummy, sized};
+
+		assert_eq!(sized(0).len(), dummy().len());
+		for len in [1024, 64 * 1024, 256 * 1024] {
+			let blob = sized(len);
+			assert!(blob.len() <= len as usize, "{} > {len}", blob.len());
+			assert!(blob.len() > len as usize - 128, "{} too small for {len}", blob.len());
+			ProgramBlob::parse(blob.into()).unwrap();
+		}
+	}
+
+	#[test]
+	fn instr_program_is_valid() {
+		let program = ProgramBlob::parse(super::bench::instr(5000).into()).unwrap();
+		assert_eq!(program.imports().len(), 0);
+	}
+}
@@ -0,0 +1,214 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Index from Ethereum transaction hashes to the blocks which include them.
+//!
+//! `eth_getTransactionReceipt` only knows the hash of a transaction. [`TransactionIndexer`]
+//! records the block of every Ethereum transaction in the aux storage of the client while
+//! blocks are imported so that the receipt can be read from that block. The blocks imported
+//! while the indexer wasn't running are indexed when it is started.
+
+use std::{future::Future, marker::PhantomData, sync::Arc};
+
+use codec::{Codec, Decode};
+use futures::StreamExt;
+use sc_client_api::{AuxStore, BlockchainEvents};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor, One, Zero};
+
+use crate::ReviveRuntimeApi;
+
+const LOG_TARGET: &str = "revive-eth-rpc";
+
+/// Prefix of the aux storage keys mapping a transaction hash to the hash of its block.
+const TRANSACTION_BLOCK_PREFIX: &[u8] = b"revive_eth_transaction_block";
+
+/// Aux storage key of the hash of the last best block the indexer processed.
+const LAST_INDEXED_BLOCK_KEY: &[u8] = b"revive_eth_last_indexed_block";
+
+fn transaction_block_key(transaction_hash: &H256) -> Vec<u8> {
+	[TRANSACTION_BLOCK_PREFIX, transaction_hash.as_bytes()].concat()
+}
+
+/// Returns the hash of the block which was indexed for the transaction with `transaction_hash`.
+///
+/// The block is not necessarily part of the canonical chain. Callers need to check that
+/// themselves.
+pub fn transaction_block<C: AuxStore>(
+	client: &C,
+	transaction_hash: &H256,
+) -> sp_blockchain::Result<Option<H256>> {
+	client
+		.get_aux(&transaction_block_key(transaction_hash))?
+		.map(|block_hash| {
+			H256::decode(&mut &block_hash[..]).map_err(|e| {
+				sp_blockchain::Error::Backend(format!("Invalid transaction index entry: {e}"))
+			})
+		})
+		.transpose()
+}
+
+/// Indexes the Ethereum transactions of every block that is imported.
+///
+/// Only blocks which become part of the best chain are indexed. When the best chain is
+/// re-organized the blocks which became canonical are indexed again. This way a transaction
+/// included on more than one fork always refers to the block on the best chain.
+///
+/// The indexer remembers the last best block it processed. When it is started it first indexes
+/// the blocks of the best chain imported since then, or all of them the first time it runs.
+pub struct TransactionIndexer<C, Block, AccountId, Balance, EventRecord> {
+	client: Arc<C>,
+	_marker: PhantomData<(Block, AccountId, Balance, EventRecord)>,
+}
+
+impl<C, Block, AccountId, Balance, EventRecord>
+	TransactionIndexer<C, Block, AccountId, Balance, EventRecord>
+where
+	Block: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<Block> + BlockchainEvents<Block> + HeaderBackend<Block> + AuxStore,
+	C::Api: ReviveRuntimeApi<Block, AccountId, Balance, EventRecord>,
+	AccountId: Codec,
+	Balance: Codec,
+	EventRecord: Codec,
+{
+	/// Create the indexer which runs until the client stops importing blocks.
+	///
+	/// The best chain imported so far is indexed before the notifications of new blocks are
+	/// processed.
+	pub fn start(client: Arc<C>) -> impl Future<Output = ()> {
+		// Subscribe first so that no block is missed while catching up.
+		let mut notifications = client.every_import_notification_stream();
+		let indexer = Self { client, _marker: Default::default() };
+		async move {
+			indexer.catch_up();
+			while let Some(notification) = notifications.next().await {
+				if !notification.is_new_best {
+					continue
+				}
+				if let Some(tree_route) = &notification.tree_route {
+					for block in tree_route.enacted() {
+						indexer.index_block(block.hash);
+					}
+				}
+				indexer.index_block(notification.hash);
+				indexer.set_last_indexed_block(notification.hash);
+			}
+		}
+	}
+
+	/// Index the blocks of the best chain which were imported since the last indexed block.
+	fn catch_up(&self) {
+		let info = self.client.info();
+		let first = match self.last_indexed_block().and_then(|hash| self.canonical_ancestor(hash)) {
+			Ok(Some(number)) => number + One::one(),
+			Ok(None) => Zero::zero(),
+			Err(e) => {
+				log::warn!(target: LOG_TARGET, "Unable to find the last indexed block: {e}");
+				return
+			},
+		};
+		if first > info.best_number {
+			return
+		}
+		log::debug!(
+			target: LOG_TARGET,
+			"Indexing blocks {first} to {} imported before the indexer was started",
+			info.best_number,
+		);
+
+		let mut number = first;
+		while number <= info.best_number {
+			match self.client.hash(number) {
+				Ok(Some(hash)) => self.index_block(hash),
+				Ok(None) => (),
+				Err(e) => {
+					log::warn!(target: LOG_TARGET, "Unable to fetch hash of block {number}: {e}");
+					return
+				},
+			}
+			number += One::one();
+		}
+		self.set_last_indexed_block(info.best_hash);
+	}
+
+	/// Returns the number of the latest block of the best chain which is an ancestor of `hash`
+	/// or `hash` itself.
+	///
+	/// Returns `None` if the block or one of its ancestors is unknown.
+	fn canonical_ancestor(
+		&self,
+		mut hash: H256,
+	) -> sp_blockchain::Result<Option<NumberFor<Block>>> {
+		loop {
+			let Some(header) = self.client.header(hash)? else { return Ok(None) };
+			if self.client.hash(*header.number())? == Some(hash) {
+				return Ok(Some(*header.number()))
+			}
+			hash = *header.parent_hash();
+		}
+	}
+
+	/// The hash of the last best block which was indexed.
+	fn last_indexed_block(&self) -> sp_blockchain::Result<Option<H256>> {
+		self.client
+			.get_aux(LAST_INDEXED_BLOCK_KEY)?
+			.map(|hash| {
+				H256::decode(&mut &hash[..]).map_err(|e| {
+					sp_blockchain::Error::Backend(format!("Invalid last indexed block: {e}"))
+				})
+			})
+			.transpose()
+	}
+
+	fn set_last_indexed_block(&self, hash: H256) {
+		if let Err(e) = self.client.insert_aux(&[(LAST_INDEXED_BLOCK_KEY, hash.as_bytes())], &[]) {
+			log::warn!(target: LOG_TARGET, "Unable to store the last indexed block {hash:?}: {e}");
+		}
+	}
+
+	/// Point the transactions of the block with `hash` to that block.
+	fn index_block(&self, hash: H256) {
+		let api = self.client.runtime_api();
+		match api.has_api::<dyn ReviveRuntimeApi<Block, AccountId, Balance, EventRecord>>(hash) {
+			Ok(true) => (),
+			Ok(false) => return,
+			Err(e) => {
+				log::warn!(target: LOG_TARGET, "Unable to query runtime of block {hash:?}: {e}");
+				return
+			},
+		}
+		let receipts = match api.eth_receipts(hash) {
+			Ok(receipts) if receipts.is_empty() => return,
+			Ok(receipts) => receipts,
+			Err(e) => {
+				log::warn!(target: LOG_TARGET, "Unable to fetch receipts of block {hash:?}: {e}");
+				return
+			},
+		};
+
+		let keys: Vec<_> = receipts
+			.iter()
+			.map(|receipt| transaction_block_key(&receipt.transaction_hash))
+			.collect();
+		let insert: Vec<_> = keys.iter().map(|key| (&key[..], hash.as_bytes())).collect();
+		if let Err(e) = self.client.insert_aux(&insert, &[]) {
+			log::warn!(target: LOG_TARGET, "Unable to index transactions of block {hash:?}: {e}");
+		}
+	}
+}
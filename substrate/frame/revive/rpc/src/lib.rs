@@ -0,0 +1,539 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ethereum JSON-RPC interface for the revive pallet.
+//!
+//! This serves the subset of the `eth_*` methods which wallets and libraries like ethers.js
+//! need in order to interact with contracts. Transactions submitted through
+//! `eth_sendRawTransaction` are wrapped into unsigned `eth_transact` extrinsics by the runtime
+//! and submitted to the transaction pool.
+//!
+//! Receipts are found through the index kept by [`TransactionIndexer`] which needs to be spawned
+//! alongside the RPC server. `eth_getLogs` is limited to [`MAX_LOG_BLOCK_RANGE`] blocks per
+//! request.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::{Codec, Encode};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{ErrorCode, ErrorObject, ErrorObjectOwned},
+};
+use pallet_revive::{
+	evm::{self, TransactionSigned, GAS_PRICE},
+	EthContractResult, EthReceipt,
+};
+use sc_client_api::AuxStore;
+use sc_transaction_pool_api::{InPoolTransaction, TransactionPool, TransactionSource};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H160, H256, U256};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor, SaturatedConversion};
+
+mod index;
+mod types;
+
+#[cfg(test)]
+mod tests;
+
+pub use index::{transaction_block, TransactionIndexer};
+pub use pallet_revive::ReviveApi as ReviveRuntimeApi;
+pub use types::*;
+
+/// How many blocks a single `eth_getLogs` request is allowed to search.
+pub const MAX_LOG_BLOCK_RANGE: u64 = 1024;
+
+/// Ethereum RPC methods.
+#[rpc(client, server)]
+pub trait EthApi {
+	/// Returns the EIP-155 chain id.
+	#[method(name = "eth_chainId")]
+	fn chain_id(&self) -> RpcResult<U256>;
+
+	/// Returns the chain id as a decimal string.
+	#[method(name = "net_version")]
+	fn net_version(&self) -> RpcResult<String>;
+
+	/// Returns the number of the best block.
+	#[method(name = "eth_blockNumber")]
+	fn block_number(&self) -> RpcResult<U256>;
+
+	/// Returns the price of one unit of gas.
+	#[method(name = "eth_gasPrice")]
+	fn gas_price(&self) -> RpcResult<U256>;
+
+	/// Returns the tip EIP-1559 transactions should pay per unit of gas.
+	///
+	/// Always zero as the price of gas is fixed.
+	#[method(name = "eth_maxPriorityFeePerGas")]
+	fn max_priority_fee_per_gas(&self) -> RpcResult<U256>;
+
+	/// Returns the balance of `address`.
+	#[method(name = "eth_getBalance")]
+	fn get_balance(&self, address: H160, block: Option<BlockNumberOrTag>) -> RpcResult<U256>;
+
+	/// Returns the nonce of `address`.
+	///
+	/// The transactions in the pool are taken into account for the `pending` block.
+	#[method(name = "eth_getTransactionCount")]
+	fn get_transaction_count(
+		&self,
+		address: H160,
+		block: Option<BlockNumberOrTag>,
+	) -> RpcResult<U256>;
+
+	/// Dry run a call or contract creation and return its output.
+	#[method(name = "eth_call")]
+	fn call(&self, request: CallRequest, block: Option<BlockNumberOrTag>) -> RpcResult<Bytes>;
+
+	/// Returns the gas needed to pay for the fee and storage deposit of a transaction.
+	#[method(name = "eth_estimateGas")]
+	fn estimate_gas(
+		&self,
+		request: CallRequest,
+		block: Option<BlockNumberOrTag>,
+	) -> RpcResult<U256>;
+
+	/// Submit a signed transaction and return its hash.
+	#[method(name = "eth_sendRawTransaction")]
+	async fn send_raw_transaction(&self, transaction: Bytes) -> RpcResult<H256>;
+
+	/// Returns the receipt of a transaction included in the best chain.
+	#[method(name = "eth_getTransactionReceipt")]
+	fn get_transaction_receipt(&self, transaction_hash: H256) -> RpcResult<Option<ReceiptInfo>>;
+
+	/// Returns the logs matching `filter`.
+	#[method(name = "eth_getLogs")]
+	fn get_logs(&self, filter: Filter) -> RpcResult<Vec<Log>>;
+
+	/// Returns a block.
+	///
+	/// Only the hashes of the transactions are returned regardless of `hydrated_transactions`.
+	#[method(name = "eth_getBlockByNumber")]
+	fn get_block_by_number(
+		&self,
+		block: BlockNumberOrTag,
+		hydrated_transactions: bool,
+	) -> RpcResult<Option<BlockInfo>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The transaction was not decodable.
+	DecodeError,
+	/// The contract reverted.
+	ExecutionReverted,
+	/// The transaction pool rejected the transaction.
+	PoolError,
+	/// The requested block does not exist.
+	UnknownBlock,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+			// The code used by Ethereum clients for reverted executions.
+			Error::ExecutionReverted => 3,
+			Error::PoolError => 4,
+			Error::UnknownBlock => 5,
+		}
+	}
+}
+
+/// Provides the Ethereum RPC methods on top of [`ReviveRuntimeApi`].
+pub struct EthRpc<C, P, Block, AccountId, Balance, EventRecord> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	/// Shared reference to the transaction pool.
+	pool: Arc<P>,
+	_marker: PhantomData<(Block, AccountId, Balance, EventRecord)>,
+}
+
+impl<C, P, Block, AccountId, Balance, EventRecord>
+	EthRpc<C, P, Block, AccountId, Balance, EventRecord>
+{
+	/// Creates a new instance of the Ethereum RPC helper.
+	pub fn new(client: Arc<C>, pool: Arc<P>) -> Self {
+		Self { client, pool, _marker: Default::default() }
+	}
+}
+
+impl<C, P, Block, AccountId, Balance, EventRecord>
+	EthRpc<C, P, Block, AccountId, Balance, EventRecord>
+where
+	Block: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore,
+	C::Api: ReviveRuntimeApi<Block, AccountId, Balance, EventRecord>,
+	P: TransactionPool<Block = Block>,
+	AccountId: Codec,
+	Balance: Codec + Copy + Into<U256>,
+	EventRecord: Codec,
+{
+	/// Returns the hash of `block` or `None` if there is no such block.
+	fn block_hash(&self, block: Option<BlockNumberOrTag>) -> RpcResult<Option<H256>> {
+		let info = self.client.info();
+		let number = match block.unwrap_or_default() {
+			BlockNumberOrTag::Tag(BlockTag::Earliest) => return Ok(Some(info.genesis_hash)),
+			BlockNumberOrTag::Tag(BlockTag::Finalized) => return Ok(Some(info.finalized_hash)),
+			BlockNumberOrTag::Tag(_) => return Ok(Some(info.best_hash)),
+			BlockNumberOrTag::Number(number) => number,
+		};
+		let Ok(number) = u64::try_from(number) else { return Ok(None) };
+		self.client.hash(number.saturated_into()).map_err(runtime_error)
+	}
+
+	/// Returns the hash of `block` or an error if there is no such block.
+	fn resolve_block(&self, block: Option<BlockNumberOrTag>) -> RpcResult<H256> {
+		self.block_hash(block)?.ok_or_else(|| {
+			ErrorObject::owned(Error::UnknownBlock.into(), "Unknown block.", None::<()>)
+		})
+	}
+
+	/// Returns the number of the block with `hash`.
+	fn block_number_of(&self, hash: H256) -> RpcResult<NumberFor<Block>> {
+		self.client.number(hash).map_err(runtime_error)?.ok_or_else(|| {
+			ErrorObject::owned(Error::UnknownBlock.into(), "Unknown block.", None::<()>)
+		})
+	}
+
+	/// Returns the receipts of all Ethereum transactions in the block with `hash`.
+	fn receipts(&self, hash: H256) -> RpcResult<Vec<ReceiptInfo>> {
+		let block_number = number_to_u256::<Block>(self.block_number_of(hash)?);
+		let receipts = self.client.runtime_api().eth_receipts(hash).map_err(runtime_error)?;
+		Ok(receipt_infos(hash, block_number, receipts))
+	}
+
+	/// Dry run `request` on top of the block with `hash`.
+	fn dry_run(&self, hash: H256, request: CallRequest) -> RpcResult<EthContractResult<Balance>> {
+		self.client
+			.runtime_api()
+			.eth_dry_run(
+				hash,
+				request.from.unwrap_or_default(),
+				request.to,
+				request.value.unwrap_or_default(),
+				request.input.map(|input| input.0).unwrap_or_default(),
+				None,
+			)
+			.map_err(runtime_error)
+	}
+
+	/// Increments `nonce` for every ready transaction of `address` in the pool.
+	///
+	/// This follows the chain of `provides` tags in the same way `system_accountNextIndex` does.
+	fn pending_nonce(&self, address: H160, mut nonce: U256) -> U256 {
+		let mut current_tag = (evm::TAG_PREFIX, (address, nonce)).encode();
+		for tx in self.pool.ready() {
+			if tx.provides().first() == Some(&current_tag) {
+				nonce = nonce.saturating_add(U256::one());
+				current_tag = (evm::TAG_PREFIX, (address, nonce)).encode();
+			}
+		}
+		nonce
+	}
+}
+
+#[async_trait]
+impl<C, P, Block, AccountId, Balance, EventRecord> EthApiServer
+	for EthRpc<C, P, Block, AccountId, Balance, EventRecord>
+where
+	Block: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore + Send + Sync + 'static,
+	C::Api: ReviveRuntimeApi<Block, AccountId, Balance, EventRecord>,
+	P: TransactionPool<Block = Block> + 'static,
+	AccountId: Codec + Send + Sync + 'static,
+	Balance: Codec + Copy + Into<U256> + Send + Sync + 'static,
+	EventRecord: Codec + Send + Sync + 'static,
+{
+	fn chain_id(&self) -> RpcResult<U256> {
+		let best_hash = self.client.info().best_hash;
+		let chain_id = self.client.runtime_api().chain_id(best_hash).map_err(runtime_error)?;
+		Ok(chain_id.into())
+	}
+
+	fn net_version(&self) -> RpcResult<String> {
+		Ok(self.chain_id()?.to_string())
+	}
+
+	fn block_number(&self) -> RpcResult<U256> {
+		Ok(number_to_u256::<Block>(self.client.info().best_number))
+	}
+
+	fn gas_price(&self) -> RpcResult<U256> {
+		Ok(GAS_PRICE.into())
+	}
+
+	fn max_priority_fee_per_gas(&self) -> RpcResult<U256> {
+		Ok(U256::zero())
+	}
+
+	fn get_balance(&self, address: H160, block: Option<BlockNumberOrTag>) -> RpcResult<U256> {
+		let hash = self.resolve_block(block)?;
+		self.client.runtime_api().balance(hash, address).map_err(runtime_error)
+	}
+
+	fn get_transaction_count(
+		&self,
+		address: H160,
+		block: Option<BlockNumberOrTag>,
+	) -> RpcResult<U256> {
+		let hash = self.resolve_block(block)?;
+		let nonce = self.client.runtime_api().nonce(hash, address).map_err(runtime_error)?;
+		if block == Some(BlockNumberOrTag::Tag(BlockTag::Pending)) {
+			Ok(self.pending_nonce(address, nonce))
+		} else {
+			Ok(nonce)
+		}
+	}
+
+	fn call(&self, request: CallRequest, block: Option<BlockNumberOrTag>) -> RpcResult<Bytes> {
+		let hash = self.resolve_block(block)?;
+		let retval = self.dry_run(hash, request)?.result.map_err(execution_error)?;
+		if retval.did_revert() {
+			return Err(reverted_error(retval.data))
+		}
+		Ok(retval.data.into())
+	}
+
+	fn estimate_gas(
+		&self,
+		request: CallRequest,
+		block: Option<BlockNumberOrTag>,
+	) -> RpcResult<U256> {
+		let hash = self.resolve_block(block)?;
+		let result = self.dry_run(hash, request)?;
+		let retval = result.result.map_err(execution_error)?;
+		if retval.did_revert() {
+			return Err(reverted_error(retval.data))
+		}
+		Ok(fee_to_gas(result.fee.into().saturating_add(result.storage_deposit.into())))
+	}
+
+	async fn send_raw_transaction(&self, transaction: Bytes) -> RpcResult<H256> {
+		let payload = transaction.0;
+		let tx = TransactionSigned::decode(&payload).map_err(|e| {
+			ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to decode transaction.",
+				Some(e.to_string()),
+			)
+		})?;
+		let from = tx.recover_eth_address().ok_or_else(|| {
+			ErrorObject::owned(Error::DecodeError.into(), "Invalid signature.", None::<()>)
+		})?;
+		let transaction_hash = evm::transaction_hash(&payload);
+
+		// The limits of the execution are derived from the transaction by the runtime. It is up
+		// to the pool to reject it if the signer didn't authorize enough gas.
+		let best_hash = self.client.info().best_hash;
+		let xt = self
+			.client
+			.runtime_api()
+			.eth_transact(best_hash, payload)
+			.map_err(runtime_error)?;
+
+		self.pool
+			.submit_one(best_hash, TransactionSource::External, xt)
+			.await
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::PoolError.into(),
+					"Unable to submit transaction.",
+					Some(e.to_string()),
+				)
+			})?;
+		log::debug!(target: "rpc", "Submitted eth transaction {:?} from {:?}", transaction_hash, from);
+		Ok(transaction_hash)
+	}
+
+	fn get_transaction_receipt(&self, transaction_hash: H256) -> RpcResult<Option<ReceiptInfo>> {
+		let Some(hash) =
+			transaction_block(&*self.client, &transaction_hash).map_err(runtime_error)?
+		else {
+			return Ok(None)
+		};
+		// The block might have been retracted without the transaction being included again.
+		let Some(number) = self.client.number(hash).map_err(runtime_error)? else {
+			return Ok(None)
+		};
+		if self.client.hash(number).map_err(runtime_error)? != Some(hash) {
+			return Ok(None)
+		}
+		Ok(self
+			.receipts(hash)?
+			.into_iter()
+			.find(|receipt| receipt.transaction_hash == transaction_hash))
+	}
+
+	fn get_logs(&self, filter: Filter) -> RpcResult<Vec<Log>> {
+		let hashes = match filter.block_hash {
+			Some(hash) => vec![hash],
+			None => {
+				let from: u64 =
+					self.block_number_of(self.resolve_block(filter.from_block)?)?.saturated_into();
+				let to: u64 =
+					self.block_number_of(self.resolve_block(filter.to_block)?)?.saturated_into();
+				if to.saturating_sub(from) >= MAX_LOG_BLOCK_RANGE {
+					return Err(ErrorObject::owned(
+						ErrorCode::InvalidParams.code(),
+						"Block range too large.",
+						Some(format!("At most {MAX_LOG_BLOCK_RANGE} blocks can be searched.")),
+					))
+				}
+				let mut hashes = Vec::new();
+				for number in from..=to {
+					if let Some(hash) =
+						self.client.hash(number.saturated_into()).map_err(runtime_error)?
+					{
+						hashes.push(hash);
+					}
+				}
+				hashes
+			},
+		};
+
+		let mut logs = Vec::new();
+		for hash in hashes {
+			for receipt in self.receipts(hash)? {
+				logs.extend(
+					receipt
+						.logs
+						.into_iter()
+						.filter(|log| filter.matches(&log.address, &log.topics)),
+				);
+			}
+		}
+		Ok(logs)
+	}
+
+	fn get_block_by_number(
+		&self,
+		block: BlockNumberOrTag,
+		_hydrated_transactions: bool,
+	) -> RpcResult<Option<BlockInfo>> {
+		let Some(hash) = self.block_hash(Some(block))? else { return Ok(None) };
+		let Some(header) = self.client.header(hash).map_err(runtime_error)? else {
+			return Ok(None)
+		};
+		let receipts = self.receipts(hash)?;
+
+		let mut logs_bloom = [0u8; 256];
+		for receipt in &receipts {
+			for (bloom, receipt_bloom) in logs_bloom.iter_mut().zip(receipt.logs_bloom.iter()) {
+				*bloom |= receipt_bloom;
+			}
+		}
+		Ok(Some(BlockInfo {
+			hash,
+			parent_hash: *header.parent_hash(),
+			number: number_to_u256::<Block>(*header.number()),
+			state_root: *header.state_root(),
+			transactions_root: *header.extrinsics_root(),
+			logs_bloom: logs_bloom.to_vec().into(),
+			gas_used: receipts.last().map_or(U256::zero(), |receipt| receipt.cumulative_gas_used),
+			gas_limit: U256::zero(),
+			base_fee_per_gas: GAS_PRICE.into(),
+			timestamp: U256::zero(),
+			difficulty: U256::zero(),
+			nonce: U256::zero(),
+			miner: H160::zero(),
+			extra_data: Bytes(Vec::new()),
+			transactions: receipts.into_iter().map(|receipt| receipt.transaction_hash).collect(),
+		}))
+	}
+}
+
+/// Convert the receipts returned by the runtime into their JSON representation.
+fn receipt_infos<Balance: Into<U256>>(
+	block_hash: H256,
+	block_number: U256,
+	receipts: Vec<EthReceipt<Balance>>,
+) -> Vec<ReceiptInfo> {
+	let mut cumulative_gas_used = U256::zero();
+	let mut log_index = U256::zero();
+	receipts
+		.into_iter()
+		.enumerate()
+		.map(|(transaction_index, receipt)| {
+			let gas_used = fee_to_gas(receipt.fee.into());
+			cumulative_gas_used = cumulative_gas_used.saturating_add(gas_used);
+			let logs_bloom = logs_bloom(&receipt.logs);
+			let logs = receipt
+				.logs
+				.into_iter()
+				.map(|log| {
+					let log = Log {
+						address: log.address,
+						topics: log.topics,
+						data: log.data.into(),
+						block_hash,
+						block_number,
+						transaction_hash: receipt.transaction_hash,
+						transaction_index: transaction_index.into(),
+						log_index,
+						removed: false,
+					};
+					log_index = log_index.saturating_add(U256::one());
+					log
+				})
+				.collect();
+			ReceiptInfo {
+				transaction_hash: receipt.transaction_hash,
+				transaction_index: transaction_index.into(),
+				block_hash,
+				block_number,
+				from: receipt.from,
+				to: receipt.to,
+				cumulative_gas_used,
+				gas_used,
+				effective_gas_price: GAS_PRICE.into(),
+				contract_address: receipt.contract_address,
+				logs,
+				logs_bloom,
+				status: if receipt.success { U256::one() } else { U256::zero() },
+				transaction_type: receipt.transaction_type.into(),
+			}
+		})
+		.collect()
+}
+
+/// The amount of gas which pays for `fee` at [`GAS_PRICE`].
+fn fee_to_gas(fee: U256) -> U256 {
+	let gas_price = U256::from(GAS_PRICE);
+	fee.saturating_add(gas_price - 1) / gas_price
+}
+
+fn number_to_u256<Block: BlockT>(number: NumberFor<Block>) -> U256 {
+	number.saturated_into::<u64>().into()
+}
+
+fn runtime_error(e: impl std::fmt::Display) -> ErrorObjectOwned {
+	ErrorObject::owned(Error::RuntimeError.into(), "Unable to query runtime.", Some(e.to_string()))
+}
+
+fn execution_error(e: impl std::fmt::Debug) -> ErrorObjectOwned {
+	ErrorObject::owned(Error::RuntimeError.into(), "Execution failed.", Some(format!("{e:?}")))
+}
+
+fn reverted_error(data: Vec<u8>) -> ErrorObjectOwned {
+	ErrorObject::owned(Error::ExecutionReverted.into(), "execution reverted", Some(Bytes(data)))
+}
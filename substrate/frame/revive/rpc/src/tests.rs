@@ -0,0 +1,518 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use jsonrpsee::{core::EmptyServerParams as EmptyParams, MethodsError as RpcError, RpcModule};
+use pallet_contracts_uapi::ReturnFlags;
+use pallet_revive::{
+	Code, CodeUploadResult, ContractExecResult, ContractInstantiateResult, EthLog, ExecReturnValue,
+	GetStorageResult,
+};
+use parking_lot::Mutex;
+use sc_block_builder::BlockBuilderBuilder;
+use sc_client_api::{
+	BlockchainEvents, FinalityNotifications, ImportNotifications, StorageEventStream, StorageKey,
+};
+use sc_transaction_pool::{BasicPool, FullChainApi};
+use sp_api::{ApiRef, ProvideRuntimeApi};
+use sp_blockchain::{BlockStatus, Info};
+use sp_consensus::BlockOrigin;
+use sp_core::{ecdsa, Pair};
+use sp_weights::Weight;
+use std::{collections::HashMap, time::Duration};
+use substrate_test_runtime_client::{
+	runtime::{Block, BlockNumber, Extrinsic, ExtrinsicBuilder, Header, Transfer},
+	AccountKeyring, Backend, BlockBuilderExt, Client, ClientBlockImportExt,
+	DefaultTestClientBuilderExt, TestClientBuilder, TestClientBuilderExt,
+};
+
+const CHAIN_ID: u64 = 42;
+
+type TestPool = BasicPool<FullChainApi<Client<Backend>, Block>, Block>;
+type TestRpc = EthRpc<MockClient, TestPool, Block, u64, u64, ()>;
+
+#[derive(Default)]
+struct MockRuntimeApiData {
+	/// The receipts returned by `eth_receipts` for each block.
+	receipts: HashMap<H256, Vec<EthReceipt<u64>>>,
+	/// The payloads passed to `eth_transact`.
+	transacted: Vec<Vec<u8>>,
+}
+
+#[derive(Clone)]
+struct MockRuntimeApi {
+	data: Arc<Mutex<MockRuntimeApiData>>,
+}
+
+sp_api::mock_impl_runtime_apis! {
+	impl ReviveRuntimeApi<Block, u64, u64, ()> for MockRuntimeApi {
+		fn call(
+			_origin: u64,
+			_dest: H160,
+			_value: u64,
+			_gas_limit: Option<Weight>,
+			_storage_deposit_limit: Option<u64>,
+			_input_data: Vec<u8>,
+		) -> ContractExecResult<u64, ()> {
+			unimplemented!()
+		}
+
+		fn instantiate(
+			_origin: u64,
+			_value: u64,
+			_gas_limit: Option<Weight>,
+			_storage_deposit_limit: Option<u64>,
+			_code: Code,
+			_data: Vec<u8>,
+			_salt: Option<[u8; 32]>,
+		) -> ContractInstantiateResult<u64, ()> {
+			unimplemented!()
+		}
+
+		fn upload_code(
+			_origin: u64,
+			_code: Vec<u8>,
+			_storage_deposit_limit: Option<u64>,
+		) -> CodeUploadResult<u64> {
+			unimplemented!()
+		}
+
+		fn get_storage(_address: H160, _key: [u8; 32]) -> GetStorageResult {
+			unimplemented!()
+		}
+
+		fn chain_id() -> u64 {
+			CHAIN_ID
+		}
+
+		fn balance(_address: H160) -> U256 {
+			U256::zero()
+		}
+
+		fn nonce(_address: H160) -> U256 {
+			U256::zero()
+		}
+
+		fn eth_dry_run(
+			_from: H160,
+			_to: Option<H160>,
+			_value: U256,
+			input: Vec<u8>,
+			_gas_limit: Option<Weight>,
+		) -> EthContractResult<u64> {
+			EthContractResult {
+				fee: 10,
+				gas_required: Weight::from_parts(100, 10),
+				storage_deposit: 5,
+				result: Ok(ExecReturnValue { flags: ReturnFlags::empty(), data: input }),
+			}
+		}
+
+		fn eth_transact(&self, payload: Vec<u8>) -> Extrinsic {
+			self.data.lock().transacted.push(payload);
+			let transfer = Transfer {
+				from: AccountKeyring::Alice.into(),
+				to: AccountKeyring::Bob.into(),
+				amount: 1,
+				nonce: 0,
+			};
+			ExtrinsicBuilder::new_transfer(transfer).build()
+		}
+
+		#[advanced]
+		fn eth_receipts(
+			&self,
+			at: <Block as BlockT>::Hash,
+		) -> Result<Vec<EthReceipt<u64>>, sp_api::ApiError> {
+			Ok(self.data.lock().receipts.get(&at).cloned().unwrap_or_default())
+		}
+	}
+}
+
+/// A test client which answers the runtime API calls with [`MockRuntimeApi`].
+struct MockClient {
+	client: Arc<Client<Backend>>,
+	data: Arc<Mutex<MockRuntimeApiData>>,
+}
+
+impl MockClient {
+	fn new() -> Arc<Self> {
+		Arc::new(Self {
+			client: Arc::new(TestClientBuilder::new().build()),
+			data: Default::default(),
+		})
+	}
+
+	/// Import a block on top of `parent` which includes the transactions of `receipts`.
+	///
+	/// `name` makes sure that the block has a different hash than its siblings.
+	async fn import_block(
+		&self,
+		parent: H256,
+		name: &[u8],
+		receipts: Vec<EthReceipt<u64>>,
+	) -> H256 {
+		let mut builder = BlockBuilderBuilder::new(&*self.client)
+			.on_parent_block(parent)
+			.fetch_parent_block_number(&*self.client)
+			.unwrap()
+			.build()
+			.unwrap();
+		builder.push_storage_change(b"name".to_vec(), Some(name.to_vec())).unwrap();
+		let block = builder.build().unwrap().block;
+		let hash = block.hash();
+		self.data.lock().receipts.insert(hash, receipts);
+		self.client.clone().import(BlockOrigin::Own, block).await.unwrap();
+		hash
+	}
+
+	fn genesis_hash(&self) -> H256 {
+		self.client.info().genesis_hash
+	}
+}
+
+impl HeaderBackend<Block> for MockClient {
+	fn header(&self, hash: H256) -> sp_blockchain::Result<Option<Header>> {
+		self.client.header(hash)
+	}
+
+	fn info(&self) -> Info<Block> {
+		self.client.info()
+	}
+
+	fn status(&self, hash: H256) -> sp_blockchain::Result<BlockStatus> {
+		self.client.status(hash)
+	}
+
+	fn number(&self, hash: H256) -> sp_blockchain::Result<Option<BlockNumber>> {
+		self.client.number(hash)
+	}
+
+	fn hash(&self, number: BlockNumber) -> sp_blockchain::Result<Option<H256>> {
+		self.client.hash(number)
+	}
+}
+
+impl AuxStore for MockClient {
+	fn insert_aux<
+		'a,
+		'b: 'a,
+		'c: 'a,
+		I: IntoIterator<Item = &'a (&'c [u8], &'c [u8])>,
+		D: IntoIterator<Item = &'a &'b [u8]>,
+	>(
+		&self,
+		insert: I,
+		delete: D,
+	) -> sp_blockchain::Result<()> {
+		self.client.insert_aux(insert, delete)
+	}
+
+	fn get_aux(&self, key: &[u8]) -> sp_blockchain::Result<Option<Vec<u8>>> {
+		self.client.get_aux(key)
+	}
+}
+
+impl BlockchainEvents<Block> for MockClient {
+	fn import_notification_stream(&self) -> ImportNotifications<Block> {
+		self.client.import_notification_stream()
+	}
+
+	fn every_import_notification_stream(&self) -> ImportNotifications<Block> {
+		self.client.every_import_notification_stream()
+	}
+
+	fn finality_notification_stream(&self) -> FinalityNotifications<Block> {
+		self.client.finality_notification_stream()
+	}
+
+	fn storage_changes_notification_stream(
+		&self,
+		filter_keys: Option<&[StorageKey]>,
+		child_filter_keys: Option<&[(StorageKey, Option<Vec<StorageKey>>)]>,
+	) -> sp_blockchain::Result<StorageEventStream<H256>> {
+		self.client.storage_changes_notification_stream(filter_keys, child_filter_keys)
+	}
+}
+
+impl ProvideRuntimeApi<Block> for MockClient {
+	type Api = MockRuntimeApi;
+
+	fn runtime_api(&self) -> ApiRef<'_, Self::Api> {
+		MockRuntimeApi { data: self.data.clone() }.into()
+	}
+}
+
+/// Start the indexer and the RPC server on top of a fresh chain.
+fn setup() -> (Arc<MockClient>, Arc<TestPool>, RpcModule<TestRpc>) {
+	let client = MockClient::new();
+	tokio::spawn(TransactionIndexer::<_, Block, u64, u64, ()>::start(client.clone()));
+	let spawner = sp_core::testing::TaskExecutor::new();
+	let pool =
+		BasicPool::new_full(Default::default(), true.into(), None, spawner, client.client.clone());
+	let rpc = EthRpc::new(client.clone(), pool.clone()).into_rpc();
+	(client, pool, rpc)
+}
+
+/// Give the indexer the chance to process the notifications of all imported blocks.
+async fn wait_for_indexer() {
+	tokio::time::sleep(Duration::from_millis(100)).await;
+}
+
+fn receipt(transaction_hash: H256, fee: u64, logs: Vec<EthLog>) -> EthReceipt<u64> {
+	EthReceipt {
+		transaction_hash,
+		extrinsic_index: 1,
+		from: H160([1; 20]),
+		to: Some(H160([2; 20])),
+		contract_address: None,
+		fee,
+		success: true,
+		transaction_type: evm::TYPE_LEGACY,
+		logs,
+	}
+}
+
+fn log(address: H160, topics: Vec<H256>) -> EthLog {
+	EthLog { address, topics, data: vec![] }
+}
+
+/// A transaction signed by the key derived from `seed`.
+fn signed_transaction(seed: u8, chain_id: u64) -> evm::TransactionLegacySigned {
+	let unsigned = evm::TransactionLegacyUnsigned {
+		nonce: 0.into(),
+		gas_price: GAS_PRICE.into(),
+		gas: 1_000.into(),
+		to: Some(H160([2; 20])),
+		value: 1.into(),
+		input: vec![1, 2, 3],
+		chain_id: Some(chain_id.into()),
+	};
+	let signature = ecdsa::Pair::from_seed(&[seed; 32]).sign_prehashed(&unsigned.signing_hash().0);
+	unsigned.with_signature(signature.into())
+}
+
+#[test]
+fn receipt_infos_are_indexed_within_the_block() {
+	let logs = |n| vec![log(H160([2; 20]), vec![]); n];
+	let receipts = vec![receipt(H256([1; 32]), 10, logs(2)), receipt(H256([2; 32]), 5, logs(1))];
+	let infos = receipt_infos(H256([9; 32]), 7.into(), receipts);
+
+	assert_eq!(infos.len(), 2);
+	assert_eq!(infos[0].cumulative_gas_used, fee_to_gas(10.into()));
+	assert_eq!(infos[1].cumulative_gas_used, fee_to_gas(10.into()) + fee_to_gas(5.into()));
+	assert_eq!(infos[1].transaction_index, U256::one());
+	assert_eq!(
+		infos
+			.iter()
+			.flat_map(|info| info.logs.iter().map(|log| log.log_index))
+			.collect::<Vec<_>>(),
+		vec![U256::from(0), U256::from(1), U256::from(2)],
+	);
+	assert_eq!(infos[1].logs[0].transaction_hash, H256([2; 32]));
+	assert_eq!(infos[1].logs[0].block_number, U256::from(7));
+}
+
+#[tokio::test]
+async fn receipts_are_found_through_the_index() {
+	let (client, _, rpc) = setup();
+	let transaction_hash = H256([1; 32]);
+
+	let block_1 = client
+		.import_block(client.genesis_hash(), b"1", vec![receipt(transaction_hash, 10, vec![])])
+		.await;
+	let mut parent = block_1;
+	for name in 2u32..10 {
+		parent = client.import_block(parent, &name.encode(), vec![]).await;
+	}
+	wait_for_indexer().await;
+
+	assert_eq!(transaction_block(&*client, &transaction_hash).unwrap(), Some(block_1));
+	let receipt: Option<ReceiptInfo> =
+		rpc.call("eth_getTransactionReceipt", [transaction_hash]).await.unwrap();
+	let receipt = receipt.unwrap();
+	assert_eq!(receipt.block_hash, block_1);
+	assert_eq!(receipt.block_number, U256::one());
+	assert_eq!(receipt.gas_used, fee_to_gas(10.into()));
+
+	let receipt: Option<ReceiptInfo> =
+		rpc.call("eth_getTransactionReceipt", [H256([2; 32])]).await.unwrap();
+	assert_eq!(receipt, None);
+}
+
+#[tokio::test]
+async fn blocks_imported_before_start_are_indexed() {
+	let client = MockClient::new();
+	let indexer = || TransactionIndexer::<_, Block, u64, u64, ()>::start(client.clone());
+	let (first, second) = (H256([1; 32]), H256([2; 32]));
+
+	let block_1 = client
+		.import_block(client.genesis_hash(), b"1", vec![receipt(first, 10, vec![])])
+		.await;
+	let task = tokio::spawn(indexer());
+	wait_for_indexer().await;
+	assert_eq!(transaction_block(&*client, &first).unwrap(), Some(block_1));
+
+	// the blocks imported while the indexer is stopped are indexed on restart
+	task.abort();
+	let block_2 = client.import_block(block_1, b"2", vec![receipt(second, 10, vec![])]).await;
+	wait_for_indexer().await;
+	assert_eq!(transaction_block(&*client, &second).unwrap(), None);
+	tokio::spawn(indexer());
+	wait_for_indexer().await;
+	assert_eq!(transaction_block(&*client, &second).unwrap(), Some(block_2));
+}
+
+#[tokio::test]
+async fn receipts_follow_the_best_chain() {
+	let (client, _, rpc) = setup();
+	let genesis_hash = client.genesis_hash();
+	let (included_on_both, retracted) = (H256([1; 32]), H256([2; 32]));
+
+	let a1 = client
+		.import_block(
+			genesis_hash,
+			b"a1",
+			vec![receipt(included_on_both, 10, vec![]), receipt(retracted, 10, vec![])],
+		)
+		.await;
+	wait_for_indexer().await;
+	assert_eq!(transaction_block(&*client, &retracted).unwrap(), Some(a1));
+
+	// `b1` only becomes part of the best chain once `b2` is imported.
+	let b1 = client
+		.import_block(genesis_hash, b"b1", vec![receipt(included_on_both, 20, vec![])])
+		.await;
+	wait_for_indexer().await;
+	assert_eq!(transaction_block(&*client, &included_on_both).unwrap(), Some(a1));
+
+	let b2 = client.import_block(b1, b"b2", vec![]).await;
+	wait_for_indexer().await;
+	assert_eq!(client.info().best_hash, b2);
+
+	let receipt: Option<ReceiptInfo> =
+		rpc.call("eth_getTransactionReceipt", [included_on_both]).await.unwrap();
+	let receipt = receipt.unwrap();
+	assert_eq!(receipt.block_hash, b1);
+	assert_eq!(receipt.gas_used, fee_to_gas(20.into()));
+
+	let receipt: Option<ReceiptInfo> =
+		rpc.call("eth_getTransactionReceipt", [retracted]).await.unwrap();
+	assert_eq!(receipt, None);
+}
+
+#[tokio::test]
+async fn logs_are_filtered() {
+	let (client, _, rpc) = setup();
+	let (contract, other_contract) = (H160([2; 20]), H160([3; 20]));
+	let topic = H256([4; 32]);
+
+	let receipts = vec![
+		receipt(H256([1; 32]), 10, vec![log(contract, vec![topic]), log(other_contract, vec![])]),
+		receipt(H256([2; 32]), 10, vec![log(contract, vec![])]),
+	];
+	let block = client.import_block(client.genesis_hash(), b"1", receipts).await;
+
+	let filter = Filter { address: Some(ValueOrList::Value(contract)), ..Default::default() };
+	let logs: Vec<Log> = rpc.call("eth_getLogs", [filter]).await.unwrap();
+	assert_eq!(logs.len(), 2);
+	assert!(logs.iter().all(|log| log.address == contract && log.block_hash == block));
+	assert_eq!(logs[1].log_index, U256::from(2));
+
+	let filter = Filter {
+		address: Some(ValueOrList::Value(contract)),
+		topics: Some(vec![Some(ValueOrList::Value(topic))]),
+		..Default::default()
+	};
+	let logs: Vec<Log> = rpc.call("eth_getLogs", [filter]).await.unwrap();
+	assert_eq!(logs.len(), 1);
+	assert_eq!(logs[0].transaction_hash, H256([1; 32]));
+
+	let filter = Filter {
+		from_block: Some(BlockNumberOrTag::Tag(BlockTag::Earliest)),
+		to_block: Some(BlockNumberOrTag::Number(U256::from(MAX_LOG_BLOCK_RANGE))),
+		..Default::default()
+	};
+	assert!(matches!(
+		rpc.call::<_, Vec<Log>>("eth_getLogs", [filter]).await,
+		Err(RpcError::JsonRpc(err)) if err.code() == i32::from(Error::UnknownBlock)
+	));
+}
+
+#[tokio::test]
+async fn dry_runs_use_the_runtime_api() {
+	let (_, _, rpc) = setup();
+
+	let chain_id: U256 = rpc.call("eth_chainId", EmptyParams::new()).await.unwrap();
+	assert_eq!(chain_id, CHAIN_ID.into());
+
+	let request = CallRequest { input: Some(vec![1, 2, 3].into()), ..Default::default() };
+	let output: Bytes = rpc.call("eth_call", [request.clone()]).await.unwrap();
+	assert_eq!(output.0, vec![1, 2, 3]);
+
+	let gas: U256 = rpc.call("eth_estimateGas", [request]).await.unwrap();
+	assert_eq!(gas, fee_to_gas(15.into()));
+}
+
+#[tokio::test]
+async fn raw_transactions_are_submitted_to_the_pool() {
+	let (client, pool, rpc) = setup();
+	let payload = signed_transaction(1, CHAIN_ID).rlp_bytes();
+
+	let hash: H256 = rpc.call("eth_sendRawTransaction", [Bytes(payload.clone())]).await.unwrap();
+	assert_eq!(hash, evm::transaction_hash(&payload));
+	assert_eq!(client.data.lock().transacted, vec![payload]);
+	assert_eq!(pool.status().ready, 1);
+
+	let mut invalid = signed_transaction(1, CHAIN_ID);
+	invalid.s = U256::zero();
+	assert!(matches!(
+		rpc.call::<_, H256>("eth_sendRawTransaction", [Bytes(invalid.rlp_bytes())]).await,
+		Err(RpcError::JsonRpc(err)) if err.code() == i32::from(Error::DecodeError)
+	));
+	assert!(matches!(
+		rpc.call::<_, H256>("eth_sendRawTransaction", [Bytes(vec![1, 2, 3])]).await,
+		Err(RpcError::JsonRpc(err)) if err.code() == i32::from(Error::DecodeError)
+	));
+	assert_eq!(client.data.lock().transacted.len(), 1);
+}
+
+#[tokio::test]
+async fn eip1559_transactions_are_submitted_to_the_pool() {
+	let (client, pool, rpc) = setup();
+	let unsigned = evm::Transaction1559Unsigned {
+		chain_id: CHAIN_ID.into(),
+		nonce: 0.into(),
+		max_priority_fee_per_gas: 0.into(),
+		max_fee_per_gas: GAS_PRICE.into(),
+		gas: 1_000.into(),
+		to: Some(H160([2; 20])),
+		value: 1.into(),
+		input: vec![1, 2, 3],
+		access_list: vec![],
+	};
+	let signature = ecdsa::Pair::from_seed(&[1; 32]).sign_prehashed(&unsigned.signing_hash().0);
+	let payload = unsigned.with_signature(signature.into()).rlp_bytes();
+
+	let tip: U256 = rpc.call("eth_maxPriorityFeePerGas", EmptyParams::new()).await.unwrap();
+	assert_eq!(tip, U256::zero());
+	let hash: H256 = rpc.call("eth_sendRawTransaction", [Bytes(payload.clone())]).await.unwrap();
+	assert_eq!(hash, evm::transaction_hash(&payload));
+	assert_eq!(client.data.lock().transacted, vec![payload]);
+	assert_eq!(pool.status().ready, 1);
+}
@@ -0,0 +1,305 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The JSON types of the Ethereum RPC interface.
+
+use pallet_revive::EthLog;
+use serde::{Deserialize, Serialize};
+use sp_core::{Bytes, H160, H256, U256};
+use sp_crypto_hashing::keccak_256;
+
+/// The number of bytes of a logs bloom filter.
+const BLOOM_SIZE: usize = 256;
+
+/// A named block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockTag {
+	/// The genesis block.
+	Earliest,
+	/// The best block.
+	Latest,
+	/// The best block including the transactions in the pool.
+	Pending,
+	/// Treated like [`Self::Latest`].
+	Safe,
+	/// The last finalized block.
+	Finalized,
+}
+
+/// A block referenced by its number or by a [`BlockTag`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BlockNumberOrTag {
+	/// The block with this number.
+	Number(U256),
+	/// The block with this name.
+	Tag(BlockTag),
+}
+
+impl Default for BlockNumberOrTag {
+	fn default() -> Self {
+		Self::Tag(BlockTag::Latest)
+	}
+}
+
+/// Either a single value or a list of values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ValueOrList<T> {
+	/// A single value.
+	Value(T),
+	/// A list of values.
+	List(Vec<T>),
+}
+
+impl<T: PartialEq> ValueOrList<T> {
+	/// Whether `value` is contained.
+	pub fn contains(&self, value: &T) -> bool {
+		match self {
+			Self::Value(v) => v == value,
+			Self::List(list) => list.contains(value),
+		}
+	}
+}
+
+/// The arguments of `eth_call` and `eth_estimateGas`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallRequest {
+	/// The caller. Defaults to the zero address.
+	pub from: Option<H160>,
+	/// The called contract or `None` to dry run a contract creation.
+	pub to: Option<H160>,
+	/// Ignored as the gas is always estimated.
+	pub gas: Option<U256>,
+	/// Ignored as the gas price is fixed.
+	pub gas_price: Option<U256>,
+	/// The value to transfer.
+	pub value: Option<U256>,
+	/// The call data or the code of the contract to create.
+	#[serde(alias = "data")]
+	pub input: Option<Bytes>,
+}
+
+/// The arguments of `eth_getLogs`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Filter {
+	/// The first block to search. Defaults to the latest block.
+	pub from_block: Option<BlockNumberOrTag>,
+	/// The last block to search. Defaults to the latest block.
+	pub to_block: Option<BlockNumberOrTag>,
+	/// Only search this block. Can't be combined with `from_block` and `to_block`.
+	pub block_hash: Option<H256>,
+	/// Only return logs emitted by one of those contracts.
+	pub address: Option<ValueOrList<H160>>,
+	/// Only return logs whose topic at the same position is one of those topics.
+	///
+	/// A `null` entry matches any topic.
+	pub topics: Option<Vec<Option<ValueOrList<H256>>>>,
+}
+
+impl Filter {
+	/// Whether a log emitted by `address` with `topics` passes this filter.
+	///
+	/// Only the `address` and `topics` filters are checked.
+	pub fn matches(&self, address: &H160, topics: &[H256]) -> bool {
+		if let Some(filter) = &self.address {
+			if !filter.contains(address) {
+				return false
+			}
+		}
+		self.topics.iter().flatten().enumerate().all(|(i, filter)| match filter {
+			Some(filter) => topics.get(i).map_or(false, |topic| filter.contains(topic)),
+			None => true,
+		})
+	}
+}
+
+/// A log as returned by `eth_getLogs` and as part of a [`ReceiptInfo`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+	/// The contract that emitted the log.
+	pub address: H160,
+	/// The topics used to index the log.
+	pub topics: Vec<H256>,
+	/// The data supplied by the contract.
+	pub data: Bytes,
+	/// The hash of the block that contains the log.
+	pub block_hash: H256,
+	/// The number of the block that contains the log.
+	pub block_number: U256,
+	/// The hash of the transaction that emitted the log.
+	pub transaction_hash: H256,
+	/// The index of the transaction within the block.
+	pub transaction_index: U256,
+	/// The index of the log within the block.
+	pub log_index: U256,
+	/// Always `false` as only logs of canonical blocks are returned.
+	pub removed: bool,
+}
+
+/// The receipt of a transaction as returned by `eth_getTransactionReceipt`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptInfo {
+	/// The hash of the transaction.
+	pub transaction_hash: H256,
+	/// The index of the transaction within the block.
+	pub transaction_index: U256,
+	/// The hash of the block that contains the transaction.
+	pub block_hash: H256,
+	/// The number of the block that contains the transaction.
+	pub block_number: U256,
+	/// The signer of the transaction.
+	pub from: H160,
+	/// The called contract or `None` if a contract was created.
+	pub to: Option<H160>,
+	/// The gas used by this and all previous transactions of the block.
+	pub cumulative_gas_used: U256,
+	/// The gas used by this transaction.
+	pub gas_used: U256,
+	/// The price that was paid per unit of gas.
+	pub effective_gas_price: U256,
+	/// The address of the created contract if any.
+	pub contract_address: Option<H160>,
+	/// The logs emitted during execution.
+	pub logs: Vec<Log>,
+	/// The bloom filter of [`Self::logs`].
+	pub logs_bloom: Bytes,
+	/// `1` if the execution succeeded and `0` otherwise.
+	pub status: U256,
+	/// Always `0` as only legacy transactions are supported.
+	#[serde(rename = "type")]
+	pub transaction_type: U256,
+}
+
+/// A block as returned by `eth_getBlockByNumber`.
+///
+/// Fields which have no meaning on this chain are set to zero.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockInfo {
+	/// The hash of the block.
+	pub hash: H256,
+	/// The hash of the parent block.
+	pub parent_hash: H256,
+	/// The number of the block.
+	pub number: U256,
+	/// The state root of the block.
+	pub state_root: H256,
+	/// The extrinsics root of the block.
+	pub transactions_root: H256,
+	/// The bloom filter of all logs of the block.
+	pub logs_bloom: Bytes,
+	/// The gas used by all transactions of the block.
+	pub gas_used: U256,
+	/// Always zero.
+	pub gas_limit: U256,
+	/// Always [`pallet_revive::evm::GAS_PRICE`] as the price of gas is fixed.
+	///
+	/// Its presence tells wallets that EIP-1559 transactions are supported.
+	pub base_fee_per_gas: U256,
+	/// Always zero.
+	pub timestamp: U256,
+	/// Always zero.
+	pub difficulty: U256,
+	/// Always zero.
+	pub nonce: U256,
+	/// Always the zero address.
+	pub miner: H160,
+	/// Always empty.
+	pub extra_data: Bytes,
+	/// The hashes of all Ethereum transactions in the block.
+	pub transactions: Vec<H256>,
+}
+
+/// Compute the bloom filter of `logs` as defined by the Ethereum yellow paper.
+pub fn logs_bloom<'a>(logs: impl IntoIterator<Item = &'a EthLog>) -> Bytes {
+	let mut bloom = [0u8; BLOOM_SIZE];
+	let mut accrue = |input: &[u8]| {
+		let hash = keccak_256(input);
+		for i in [0, 2, 4] {
+			let bit = (usize::from(hash[i]) << 8 | usize::from(hash[i + 1])) & 0x7ff;
+			bloom[BLOOM_SIZE - 1 - bit / 8] |= 1 << (bit % 8);
+		}
+	};
+	for log in logs {
+		accrue(log.address.as_bytes());
+		for topic in &log.topics {
+			accrue(topic.as_bytes());
+		}
+	}
+	bloom.to_vec().into()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn block_number_or_tag_deserializes() {
+		let block: BlockNumberOrTag = serde_json::from_str(r#""0x10""#).unwrap();
+		assert_eq!(block, BlockNumberOrTag::Number(16.into()));
+		let block: BlockNumberOrTag = serde_json::from_str(r#""pending""#).unwrap();
+		assert_eq!(block, BlockNumberOrTag::Tag(BlockTag::Pending));
+	}
+
+	#[test]
+	fn call_request_accepts_data_alias() {
+		let request: CallRequest = serde_json::from_str(
+			r#"{"to":"0x0101010101010101010101010101010101010101","data":"0x1234"}"#,
+		)
+		.unwrap();
+		assert_eq!(request.to, Some(H160([1; 20])));
+		assert_eq!(request.input, Some(Bytes(vec![0x12, 0x34])));
+	}
+
+	#[test]
+	fn filter_matches_address_and_topics() {
+		let address = H160([1; 20]);
+		let topics = [H256([1; 32]), H256([2; 32])];
+		assert!(Filter::default().matches(&address, &topics));
+
+		let filter = Filter {
+			address: Some(ValueOrList::List(vec![H160([2; 20]), H160([1; 20])])),
+			topics: Some(vec![None, Some(ValueOrList::Value(H256([2; 32])))]),
+			..Default::default()
+		};
+		assert!(filter.matches(&address, &topics));
+
+		let filter = Filter { address: Some(ValueOrList::Value(H160([2; 20]))), ..filter };
+		assert!(!filter.matches(&address, &topics));
+
+		let filter = Filter {
+			topics: Some(vec![None, None, Some(ValueOrList::Value(H256([3; 32])))]),
+			..Default::default()
+		};
+		assert!(!filter.matches(&address, &topics));
+	}
+
+	#[test]
+	fn logs_bloom_sets_three_bits_per_item() {
+		let log = EthLog { address: H160([1; 20]), topics: vec![], data: vec![] };
+		let bloom = logs_bloom([&log]);
+		let bits: u32 = bloom.0.iter().map(|byte| byte.count_ones()).sum();
+		assert!(bits > 0 && bits <= 3);
+		assert_eq!(logs_bloom([]), Bytes(vec![0; BLOOM_SIZE]));
+	}
+}
@@ -0,0 +1,327 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Functions that deal with address derivation and the mapping between native accounts and
+//! Ethereum style addresses.
+
+use crate::{BalanceOf, Config, Error, HoldReason, OriginalAccount};
+use codec::MaxEncodedLen;
+use frame_support::{
+	ensure,
+	traits::{fungible::MutateHold, tokens::Precision, Get},
+};
+use sp_core::{H160, H256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::{traits::Saturating, AccountId32, DispatchResult};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// Map between the native chain account id `T` and an Ethereum [`H160`].
+///
+/// This trait exists only to emulate specialization for different concrete
+/// native account ids. **Not** to make the mapping user configurable. Hence
+/// the trait is `Sealed` and only one mandatory implementor [`AccountId32Mapper`]
+/// exists.
+///
+/// Please note that we assume that the native account is at least 20 bytes and
+/// only implement this type for a `T` where this is the case. Luckily, this is the
+/// case for all existing runtimes as of right now. Reasoning is that this will allow
+/// us to reverse an address -> account_id mapping by just stripping the prefix.
+///
+/// We require the mapping to be reversible. Since we are potentially dealing with types of
+/// different sizes one direction of the mapping is necessarily lossy. This requires the mapping to
+/// make use of the [`OriginalAccount`] storage item to reverse the mapping.
+pub trait AddressMapper<T: Config>: private::Sealed {
+	/// Convert an account id to an ethereum address.
+	fn to_address(account_id: &T::AccountId) -> H160;
+
+	/// Convert an ethereum address to a native account id.
+	fn to_account_id(address: &H160) -> T::AccountId;
+
+	/// Same as [`Self::to_account_id`] but always returns the fallback account.
+	///
+	/// This skips the query into [`OriginalAccount`] and always returns the stateless
+	/// fallback account. This is useful when we know for a fact that the `address`
+	/// in question is originally a `H160`. This is usually only the case when we
+	/// generated a new contract address.
+	fn to_fallback_account_id(address: &H160) -> T::AccountId;
+
+	/// Create a stateful mapping for `account_id`
+	///
+	/// This will enable `to_account_id` to map back to the original
+	/// `account_id` instead of the fallback account id.
+	fn map(account_id: &T::AccountId) -> DispatchResult;
+
+	/// Remove the mapping in order to reclaim the deposit.
+	///
+	/// There is no reason why one would unmap their `account_id` except
+	/// for reclaiming the deposit.
+	fn unmap(account_id: &T::AccountId) -> DispatchResult;
+
+	/// Returns true if the `account_id` is usable as an origin.
+	///
+	/// This means either the `account_id` doesn't require a stateful mapping
+	/// or a stateful mapping exists.
+	fn is_mapped(account_id: &T::AccountId) -> bool;
+}
+
+mod private {
+	pub trait Sealed {}
+	impl<T> Sealed for super::AccountId32Mapper<T> {}
+}
+
+/// The mapper to be used if the account id is `AccountId32`.
+///
+/// It converts between addresses by either hash then truncate the last 12 bytes or
+/// suffixing them. To recover the original account id of a hashed and truncated account id we use
+/// [`OriginalAccount`] and fall back to suffixing the address with `0xEE` if no account was found.
+/// This means contracts and plain wallets controlled by an `secp256k1` always have a `0xEE`
+/// suffixed account.
+pub struct AccountId32Mapper<T>(PhantomData<T>);
+
+impl<T> AddressMapper<T> for AccountId32Mapper<T>
+where
+	T: Config<AccountId = AccountId32>,
+{
+	fn to_address(account_id: &AccountId32) -> H160 {
+		let account_bytes: &[u8; 32] = account_id.as_ref();
+		if is_eth_derived(account_id) {
+			// this was originally an eth address
+			// we just strip the 0xEE suffix to get the original address
+			H160::from_slice(&account_bytes[..20])
+		} else {
+			// this is an (ed|sr)25519 derived address
+			// avoid truncating the public key by hashing it first
+			let account_hash = keccak_256(account_bytes);
+			H160::from_slice(&account_hash[12..])
+		}
+	}
+
+	fn to_account_id(address: &H160) -> AccountId32 {
+		<OriginalAccount<T>>::get(address).unwrap_or_else(|| Self::to_fallback_account_id(address))
+	}
+
+	fn to_fallback_account_id(address: &H160) -> AccountId32 {
+		let mut account_id = AccountId32::new([0xEE; 32]);
+		let account_bytes: &mut [u8; 32] = account_id.as_mut();
+		account_bytes[..20].copy_from_slice(address.as_bytes());
+		account_id
+	}
+
+	fn map(account_id: &T::AccountId) -> DispatchResult {
+		ensure!(!Self::is_mapped(account_id), <Error<T>>::AccountAlreadyMapped);
+
+		// each mapping entry stores the address (20 bytes) and the account id (32 bytes)
+		let deposit = mapping_deposit::<T>();
+		T::Currency::hold(&HoldReason::AddressMapping.into(), account_id, deposit)?;
+
+		<OriginalAccount<T>>::insert(Self::to_address(account_id), account_id);
+		Ok(())
+	}
+
+	fn unmap(account_id: &T::AccountId) -> DispatchResult {
+		// will do nothing if address is not mapped so no check required
+		<OriginalAccount<T>>::remove(Self::to_address(account_id));
+		T::Currency::release_all(
+			&HoldReason::AddressMapping.into(),
+			account_id,
+			Precision::BestEffort,
+		)?;
+		Ok(())
+	}
+
+	fn is_mapped(account_id: &T::AccountId) -> bool {
+		is_eth_derived(account_id) ||
+			<OriginalAccount<T>>::contains_key(Self::to_address(account_id))
+	}
+}
+
+/// Returns true if the passed account id is controlled by an eth key.
+///
+/// This is a stateless check that just compares the last 12 bytes. Please note that it is
+/// theoretically possible to create an ed25519 keypair that passed this filter. However,
+/// this can't be used for an attack. It also won't happen by accident since everybody is using
+/// sr25519 where this is not a valid public key.
+fn is_eth_derived(account_id: &AccountId32) -> bool {
+	let account_bytes: &[u8; 32] = account_id.as_ref();
+	account_bytes[20..] == [0xEE; 12]
+}
+
+/// The deposit that is held from an account for the lifetime of its mapping.
+fn mapping_deposit<T: Config>() -> BalanceOf<T> {
+	let bytes = H160::max_encoded_len().saturating_add(T::AccountId::max_encoded_len()) as u32;
+	T::DepositPerByte::get()
+		.saturating_mul(bytes.into())
+		.saturating_add(T::DepositPerItem::get())
+}
+
+/// Determine the address of a contract using CREATE semantics.
+///
+/// This is the same formula Ethereum uses: `keccak256(rlp([deployer, nonce]))[12..]`.
+pub fn create1(deployer: &H160, nonce: u64) -> H160 {
+	// RLP encoding of the nonce: a single byte for small values, a length prefixed big endian
+	// integer without leading zeroes otherwise.
+	let nonce_bytes = nonce.to_be_bytes();
+	let leading_zeroes = nonce_bytes.iter().take_while(|b| **b == 0).count();
+	let mut encoded_nonce = Vec::with_capacity(9);
+	match nonce {
+		0 => encoded_nonce.push(0x80),
+		1..=0x7f => encoded_nonce.push(nonce as u8),
+		_ => {
+			encoded_nonce.push(0x80 + (8 - leading_zeroes) as u8);
+			encoded_nonce.extend_from_slice(&nonce_bytes[leading_zeroes..]);
+		},
+	}
+
+	// The payload is always shorter than 56 bytes which means the list is prefixed by a single
+	// byte containing its length.
+	let payload_len = 1 + 20 + encoded_nonce.len();
+	let mut list = Vec::with_capacity(1 + payload_len);
+	list.push(0xc0 + payload_len as u8);
+	list.push(0x80 + 20);
+	list.extend_from_slice(deployer.as_bytes());
+	list.extend_from_slice(&encoded_nonce);
+
+	let hash = keccak_256(&list);
+	H160::from_slice(&hash[12..])
+}
+
+/// Determine the address of a contract using the CREATE2 semantics.
+///
+/// The init code of Ethereum corresponds to the code *and* the constructor input here. Hence the
+/// formula is `keccak256(0xff ++ deployer ++ salt ++ keccak256(code ++ input))[12..]`.
+pub fn create2(deployer: &H160, code: &[u8], input_data: &[u8], salt: &[u8; 32]) -> H160 {
+	let init_code_hash = {
+		let init_code: Vec<u8> = code.iter().chain(input_data).cloned().collect();
+		keccak_256(&init_code)
+	};
+	let mut bytes = [0; 85];
+	bytes[0] = 0xff;
+	bytes[1..21].copy_from_slice(deployer.as_bytes());
+	bytes[21..53].copy_from_slice(salt);
+	bytes[53..85].copy_from_slice(&init_code_hash);
+	let hash = keccak_256(&bytes);
+	H160::from_slice(&hash[12..])
+}
+
+/// The hash of a code blob as used as key in the code storage.
+pub fn code_hash(code: &[u8]) -> H256 {
+	H256(keccak_256(code))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		tests::{ExtBuilder, Test, ALICE, ALICE_ADDR},
+		AddressMapper, Error,
+	};
+	use frame_support::{
+		assert_err,
+		traits::fungible::{InspectHold, Mutate},
+	};
+	use pretty_assertions::assert_eq;
+	use sp_core::{hex2array, H160};
+
+	#[test]
+	fn create1_works() {
+		let deployer = H160(hex2array!("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0"));
+		assert_eq!(
+			create1(&deployer, 0),
+			H160(hex2array!("cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"))
+		);
+		assert_eq!(
+			create1(&deployer, 1),
+			H160(hex2array!("343c43a37d37dff08ae8c4a11544c718abb4fcf8"))
+		);
+		assert_eq!(
+			create1(&deployer, 2),
+			H160(hex2array!("f778b86fa74e846c4f0a1fbd1335fe81c00a0c91"))
+		);
+		assert_eq!(
+			create1(&deployer, 3),
+			H160(hex2array!("fffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c"))
+		);
+		assert_eq!(
+			create1(&deployer, 0x80),
+			H160(hex2array!("08e190dcb7b73f5fcdabb43e102215c83659a76d"))
+		);
+		assert_eq!(
+			create1(&deployer, u64::MAX),
+			H160(hex2array!("9bc924993b60399df164c3763a964301d3db95ca"))
+		);
+	}
+
+	#[test]
+	fn create2_works() {
+		// first example from EIP-1014
+		assert_eq!(
+			create2(&H160::zero(), &[], &[0x00], &[0; 32]),
+			H160(hex2array!("4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38"))
+		);
+	}
+
+	#[test]
+	fn fallback_map_works() {
+		// eth derived accounts don't need a stateful mapping
+		assert!(<Test as Config>::AddressMapper::is_mapped(&ALICE));
+		assert_eq!(ALICE, <Test as Config>::AddressMapper::to_fallback_account_id(&ALICE_ADDR));
+		assert_eq!(ALICE_ADDR, <Test as Config>::AddressMapper::to_address(&ALICE));
+	}
+
+	#[test]
+	fn map_works() {
+		ExtBuilder::default().build().execute_with(|| {
+			let account_id = AccountId32::new([0x11; 32]);
+			let address = <Test as Config>::AddressMapper::to_address(&account_id);
+			<Test as Config>::Currency::set_balance(&account_id, 100_000);
+
+			// without a mapping the fallback account is returned
+			assert!(!<Test as Config>::AddressMapper::is_mapped(&account_id));
+			assert_eq!(
+				<Test as Config>::AddressMapper::to_account_id(&address),
+				<Test as Config>::AddressMapper::to_fallback_account_id(&address)
+			);
+
+			// with a mapping the original account is recovered and a deposit is held
+			<Test as Config>::AddressMapper::map(&account_id).unwrap();
+			assert!(<Test as Config>::AddressMapper::is_mapped(&account_id));
+			assert_eq!(<Test as Config>::AddressMapper::to_account_id(&address), account_id);
+			assert_eq!(
+				<Test as Config>::Currency::balance_on_hold(
+					&HoldReason::AddressMapping.into(),
+					&account_id
+				),
+				mapping_deposit::<Test>(),
+			);
+			assert_err!(
+				<Test as Config>::AddressMapper::map(&account_id),
+				<Error<Test>>::AccountAlreadyMapped,
+			);
+
+			// unmapping releases the deposit
+			<Test as Config>::AddressMapper::unmap(&account_id).unwrap();
+			assert!(!<Test as Config>::AddressMapper::is_mapped(&account_id));
+			assert_eq!(
+				<Test as Config>::Currency::balance_on_hold(
+					&HoldReason::AddressMapping.into(),
+					&account_id
+				),
+				0,
+			);
+		});
+	}
+}
@@ -0,0 +1,143 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The environment for benchmarking syscalls without executing a contract.
+
+use super::{Contract, Module};
+use crate::{
+	exec::Stack,
+	gas::GasMeter,
+	storage::meter::Meter,
+	vm::{PolkaVmBlob, Runtime, TrapReason},
+	BalanceOf, Config, ContractInfoOf, DebugBuffer, Origin,
+};
+use codec::{Encode, HasCompact};
+use core::fmt::Debug;
+use frame_benchmarking::benchmarking;
+use frame_support::weights::Weight;
+use scale_info::TypeInfo;
+use sp_runtime::traits::Zero;
+use sp_std::prelude::*;
+
+type StackExt<'a, T> = Stack<'a, T, PolkaVmBlob<T>>;
+
+/// A call into a contract whose frame is set up but never executed.
+///
+/// The syscall benchmarks invoke the [`Runtime`] on this frame directly. This keeps the cost of
+/// executing a contract out of their results. The frame is built within the measured block as it
+/// borrows from the setup. Its storage accesses are whitelisted.
+pub struct CallSetup<T: Config> {
+	contract: Contract<T>,
+	origin: Origin<T>,
+	gas_meter: GasMeter<T>,
+	storage_meter: Meter<T>,
+	debug_message: Option<DebugBuffer>,
+	data: Vec<u8>,
+}
+
+impl<T> Default for CallSetup<T>
+where
+	T: Config,
+	<BalanceOf<T> as HasCompact>::Type: Clone + Eq + PartialEq + Debug + TypeInfo + Encode,
+{
+	fn default() -> Self {
+		Self::new(Module::dummy())
+	}
+}
+
+impl<T> CallSetup<T>
+where
+	T: Config,
+	<BalanceOf<T> as HasCompact>::Type: Clone + Eq + PartialEq + Debug + TypeInfo + Encode,
+{
+	/// Set up a call into a new contract instantiated from `module`.
+	pub fn new(module: Module) -> Self {
+		let contract = Contract::<T>::new(module, vec![]).expect("Instantiation succeeds; qed");
+		let origin = Origin::from_account_id(contract.caller.clone());
+		let storage_meter =
+			Meter::new(&origin, None, Zero::zero()).expect("The caller is funded; qed");
+
+		// The account and the info of the contract are already accounted for by the `call`
+		// benchmark.
+		benchmarking::add_to_whitelist(
+			frame_system::Account::<T>::hashed_key_for(&contract.account_id).into(),
+		);
+		benchmarking::add_to_whitelist(
+			ContractInfoOf::<T>::hashed_key_for(&contract.address).into(),
+		);
+
+		Self {
+			contract,
+			origin,
+			gas_meter: GasMeter::new(Weight::MAX),
+			storage_meter,
+			debug_message: None,
+			data: vec![],
+		}
+	}
+
+	/// Set the balance of the called contract.
+	pub fn set_balance(&mut self, balance: BalanceOf<T>) {
+		self.contract.set_balance(balance);
+	}
+
+	/// Set the input data of the call.
+	pub fn set_data(&mut self, data: Vec<u8>) {
+		self.data = data;
+	}
+
+	/// Collect the debug messages of the call.
+	pub fn enable_debug_message(&mut self) {
+		self.debug_message = Some(Default::default());
+	}
+
+	/// The debug messages emitted by the call if they are collected.
+	pub fn debug_message(&self) -> Option<DebugBuffer> {
+		self.debug_message.clone()
+	}
+
+	/// The input data of the call.
+	pub fn data(&self) -> Vec<u8> {
+		self.data.clone()
+	}
+
+	/// The called contract.
+	pub fn contract(&self) -> Contract<T> {
+		self.contract.clone()
+	}
+
+	/// Invoke the syscall `name` with `args` on a new frame using `memory` as contract memory.
+	///
+	/// The input data is moved into the frame so it is not copied while measuring.
+	pub fn syscall(
+		&mut self,
+		memory: &mut [u8],
+		name: &[u8],
+		args: [u32; 6],
+	) -> Result<Option<u32>, TrapReason> {
+		let input = core::mem::take(&mut self.data);
+		let (mut ext, _) = StackExt::bench_new_call(
+			self.contract.address,
+			self.origin.clone(),
+			&mut self.gas_meter,
+			&mut self.storage_meter,
+			Zero::zero(),
+			self.debug_message.as_mut(),
+		);
+		Runtime::new(&mut ext, input).handle_ecall(memory, name, args)
+	}
+}
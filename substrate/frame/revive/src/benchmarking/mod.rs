@@ -0,0 +1,1158 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the revive pallet.
+//!
+//! The extrinsics are benchmarked by dispatching them. The syscalls are benchmarked by invoking
+//! the [`crate::vm::Runtime`] directly with a plain buffer as contract memory. See [`CallSetup`].
+
+#![cfg(feature = "runtime-benchmarks")]
+
+mod call_builder;
+
+use self::call_builder::CallSetup;
+use crate::{
+	address::{code_hash, create2},
+	evm::{self, TransactionLegacyUnsigned},
+	exec::Key,
+	limits,
+	vm::{balance_to_u256, TrapReason, SENTINEL},
+	Pallet as Revive, *,
+};
+use codec::{Decode, Encode};
+use frame_benchmarking::v2::*;
+use frame_support::{
+	crypto::ecdsa::ECDSAExt,
+	traits::{
+		fungible::{Inspect, Mutate},
+		tokens::{Fortitude, Preservation},
+	},
+};
+use frame_system::RawOrigin;
+use pallet_contracts_uapi::{CallFlags, ReturnErrorCode};
+use pallet_revive_fixtures::bench as fixtures;
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::traits::{Bounded, Hash, SaturatedConversion};
+use sp_std::prelude::*;
+
+/// How many instructions the `instr` benchmark executes at most.
+const INSTR_BENCHMARK_RUNS: u32 = 5000;
+
+/// The key type used to generate the keys signing the benchmark inputs.
+const KEY_TYPE: KeyTypeId = KeyTypeId(*b"code");
+
+/// A PolkaVM blob together with its code hash.
+struct Module {
+	code: Vec<u8>,
+	hash: H256,
+}
+
+impl Module {
+	fn new(code: Vec<u8>) -> Self {
+		let hash = code_hash(&code);
+		Self { code, hash }
+	}
+
+	/// A program which does nothing.
+	fn dummy() -> Self {
+		Self::new(fixtures::dummy())
+	}
+
+	/// A program of roughly `len` bytes which does nothing.
+	fn sized(len: u32) -> Self {
+		Self::new(fixtures::sized(len))
+	}
+
+	/// A program which executes roughly `r` instructions when called.
+	fn instr(r: u32) -> Self {
+		Self::new(fixtures::instr(r))
+	}
+}
+
+/// An instantiated and deployed contract.
+struct Contract<T: Config> {
+	caller: T::AccountId,
+	account_id: T::AccountId,
+	address: H160,
+}
+
+impl<T: Config> Clone for Contract<T> {
+	fn clone(&self) -> Self {
+		Self {
+			caller: self.caller.clone(),
+			account_id: self.account_id.clone(),
+			address: self.address,
+		}
+	}
+}
+
+impl<T> Contract<T>
+where
+	T: Config,
+	<BalanceOf<T> as HasCompact>::Type: Clone + Eq + PartialEq + Debug + TypeInfo + Encode,
+{
+	/// Create new contract and use a default account id as instantiator.
+	fn new(module: Module, data: Vec<u8>) -> Result<Contract<T>, &'static str> {
+		Self::with_index(0, module, data)
+	}
+
+	/// Create new contract and use an account id derived from the supplied index as instantiator.
+	fn with_index(index: u32, module: Module, data: Vec<u8>) -> Result<Contract<T>, &'static str> {
+		Self::with_caller(account("instantiator", index, 0), module, data)
+	}
+
+	/// Create new contract and use the supplied `caller` as instantiator.
+	fn with_caller(
+		caller: T::AccountId,
+		module: Module,
+		data: Vec<u8>,
+	) -> Result<Contract<T>, &'static str> {
+		T::Currency::set_balance(&caller, caller_funding::<T>());
+		if !T::AddressMapper::is_mapped(&caller) {
+			T::AddressMapper::map(&caller)?;
+		}
+
+		let outcome = Revive::<T>::bare_instantiate(
+			caller.clone(),
+			Pallet::<T>::min_balance(),
+			Weight::MAX,
+			None,
+			Code::Upload(module.code),
+			data,
+			Some([0xff; 32]),
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		);
+		let address = outcome.result?.addr;
+		let account_id = T::AddressMapper::to_fallback_account_id(&address);
+
+		Ok(Contract { caller, account_id, address })
+	}
+
+	/// Create a new contract with the supplied storage item count and size each.
+	fn with_storage(code: Module, stor_num: u32, stor_size: u32) -> Result<Self, &'static str> {
+		let contract = Contract::<T>::new(code, vec![])?;
+		let storage_items = (0..stor_num)
+			.map(|i| {
+				let hash = T::Hashing::hash_of(&i)
+					.as_ref()
+					.try_into()
+					.map_err(|_| "Hash too big for storage key")?;
+				Ok((hash, vec![42u8; stor_size as usize]))
+			})
+			.collect::<Result<Vec<_>, &'static str>>()?;
+		contract.store(&storage_items)?;
+		Ok(contract)
+	}
+
+	/// Store the supplied storage items into this contracts storage.
+	fn store(&self, items: &Vec<([u8; 32], Vec<u8>)>) -> Result<(), &'static str> {
+		let info = self.info()?;
+		for item in items {
+			info.write(&Key::Fix(item.0), Some(item.1.clone()), None, false)
+				.map_err(|_| "Failed to write storage to restoration dest")?;
+		}
+		<ContractInfoOf<T>>::insert(&self.address, info);
+		Ok(())
+	}
+
+	/// Get the `ContractInfo` of the `address` or an error if it no longer exists.
+	fn address_info(address: &H160) -> Result<ContractInfo<T>, &'static str> {
+		ContractInfoOf::<T>::get(address).ok_or("Expected contract to exist at this point.")
+	}
+
+	/// Get the `ContractInfo` of this contract or an error if it no longer exists.
+	fn info(&self) -> Result<ContractInfo<T>, &'static str> {
+		Self::address_info(&self.address)
+	}
+
+	/// Set the balance of the contract to the supplied amount.
+	fn set_balance(&self, balance: BalanceOf<T>) {
+		T::Currency::set_balance(&self.account_id, balance);
+	}
+
+	/// Returns `true` iff all storage entries related to code storage exist.
+	fn code_exists(hash: &H256) -> bool {
+		<PristineCode<T>>::contains_key(hash) && <CodeInfoOf<T>>::contains_key(hash)
+	}
+
+	/// Returns `true` iff no storage entry related to code storage exist.
+	fn code_removed(hash: &H256) -> bool {
+		!<PristineCode<T>>::contains_key(hash) && !<CodeInfoOf<T>>::contains_key(hash)
+	}
+}
+
+/// The funding that each account that either calls or instantiates contracts is funded with.
+fn caller_funding<T: Config>() -> BalanceOf<T> {
+	// Minting can overflow, so we can't abuse of the funding. This value happens to be big enough,
+	// but not too big to make the total supply overflow.
+	BalanceOf::<T>::max_value() / 10_000u32.into()
+}
+
+/// A mapped account which is funded with [`caller_funding`].
+fn funded_caller<T: Config>() -> Result<T::AccountId, BenchmarkError> {
+	let caller = whitelisted_caller();
+	T::Currency::set_balance(&caller, caller_funding::<T>());
+	if !T::AddressMapper::is_mapped(&caller) {
+		T::AddressMapper::map(&caller)?;
+	}
+	Ok(caller)
+}
+
+/// The little endian encoding of `value` as it is read by the syscalls.
+fn u256_bytes(value: U256) -> [u8; 32] {
+	let mut bytes = [0u8; 32];
+	value.to_little_endian(&mut bytes);
+	bytes
+}
+
+/// The largest storage key a contract can use.
+fn max_storage_key() -> Key {
+	Key::try_from_var(vec![0xff; limits::STORAGE_KEY_BYTES as usize])
+		.expect("The key is within the limit; qed")
+}
+
+#[benchmarks(
+	where
+		<BalanceOf<T> as HasCompact>::Type: Clone + Eq + PartialEq + Debug + TypeInfo + Encode,
+)]
+mod benchmarks {
+	use super::*;
+
+	// The base weight consumed on processing contracts deletion queue.
+	#[benchmark]
+	fn on_process_deletion_queue_batch() {
+		#[block]
+		{
+			ContractInfo::<T>::process_deletion_queue_batch(Weight::MAX);
+		}
+	}
+
+	#[benchmark(skip_meta)]
+	fn on_initialize_per_trie_key(k: Linear<0, 1024>) -> Result<(), BenchmarkError> {
+		let instance = Contract::<T>::with_storage(Module::dummy(), k, limits::PAYLOAD_BYTES)?;
+		instance.info()?.queue_trie_for_deletion();
+
+		#[block]
+		{
+			ContractInfo::<T>::process_deletion_queue_batch(Weight::MAX);
+		}
+
+		Ok(())
+	}
+
+	// This benchmarks the overhead of loading a code of size `c` byte from storage and into
+	// the execution engine. This does **not** include the actual execution for which the gas
+	// meter is responsible. The code consists of unreachable instructions which are loaded but
+	// never executed.
+	// The results are supposed to be used as `call_with_code_per_byte(c) -
+	// call_with_code_per_byte(0)`.
+	#[benchmark]
+	fn call_with_code_per_byte(
+		c: Linear<0, { limits::code::BLOB_BYTES }>,
+	) -> Result<(), BenchmarkError> {
+		let instance = Contract::<T>::with_caller(whitelisted_caller(), Module::sized(c), vec![])?;
+		let value = Pallet::<T>::min_balance();
+		let origin = RawOrigin::Signed(instance.caller.clone());
+
+		#[extrinsic_call]
+		call(origin, instance.address, value, Weight::MAX, None, vec![]);
+
+		Ok(())
+	}
+
+	// `c`: Size of the code in bytes.
+	// `i`: Size of the input in bytes.
+	#[benchmark]
+	fn instantiate_with_code(
+		c: Linear<0, { limits::code::BLOB_BYTES }>,
+		i: Linear<0, { limits::code::BLOB_BYTES }>,
+	) -> Result<(), BenchmarkError> {
+		let input = vec![42u8; i as usize];
+		let salt = [42u8; 32];
+		let value = Pallet::<T>::min_balance();
+		let caller = funded_caller::<T>()?;
+		let origin = RawOrigin::Signed(caller.clone());
+		let Module { code, hash } = Module::sized(c);
+		let deployer = T::AddressMapper::to_address(&caller);
+		let address = create2(&deployer, &code, &input, &salt);
+		let account_id = T::AddressMapper::to_fallback_account_id(&address);
+
+		#[extrinsic_call]
+		_(origin, value, Weight::MAX, None, code, input, Some(salt));
+
+		assert_eq!(Contract::<T>::address_info(&address)?.code_hash, hash);
+		assert!(T::Currency::balance(&account_id) >= value);
+		Ok(())
+	}
+
+	// `i`: Size of the input in bytes.
+	#[benchmark]
+	fn instantiate(i: Linear<0, { limits::code::BLOB_BYTES }>) -> Result<(), BenchmarkError> {
+		let input = vec![42u8; i as usize];
+		let salt = [42u8; 32];
+		let value = Pallet::<T>::min_balance();
+		let caller = funded_caller::<T>()?;
+		let origin = RawOrigin::Signed(caller.clone());
+		let Module { code, hash } = Module::dummy();
+		let deployer = T::AddressMapper::to_address(&caller);
+		let address = create2(&deployer, &code, &input, &salt);
+		let account_id = T::AddressMapper::to_fallback_account_id(&address);
+		Revive::<T>::bare_upload_code(caller, code, None)?;
+
+		#[extrinsic_call]
+		_(origin, value, Weight::MAX, None, hash, input, Some(salt));
+
+		assert_eq!(Contract::<T>::address_info(&address)?.code_hash, hash);
+		assert!(T::Currency::balance(&account_id) >= value);
+		Ok(())
+	}
+
+	// We just call a dummy contract to measure the overhead of the call extrinsic.
+	// The size of the data has no influence on the costs of this extrinsic as long as the contract
+	// won't call `seal_input` in its constructor to copy the data to contract memory.
+	// The dummy contract used here does not do this. The costs for the data copy is billed as
+	// part of `seal_input`.
+	#[benchmark]
+	fn call() -> Result<(), BenchmarkError> {
+		let data = vec![42u8; 1024];
+		let instance = Contract::<T>::with_caller(whitelisted_caller(), Module::dummy(), vec![])?;
+		let value = Pallet::<T>::min_balance();
+		let origin = RawOrigin::Signed(instance.caller.clone());
+		let before = T::Currency::balance(&instance.account_id);
+
+		#[extrinsic_call]
+		_(origin, instance.address, value, Weight::MAX, None, data);
+
+		assert_eq!(T::Currency::balance(&instance.account_id), before + value);
+		Ok(())
+	}
+
+	// `c`: Size of the code in bytes.
+	#[benchmark]
+	fn upload_code(c: Linear<0, { limits::code::BLOB_BYTES }>) -> Result<(), BenchmarkError> {
+		let caller = funded_caller::<T>()?;
+		let origin = RawOrigin::Signed(caller);
+		let Module { code, hash } = Module::sized(c);
+
+		#[extrinsic_call]
+		_(origin, code, None);
+
+		assert!(Contract::<T>::code_exists(&hash));
+		Ok(())
+	}
+
+	// Removing code does not depend on the size of the contract because all the information
+	// needed to verify the removal claim (refcount, owner) is stored in a separate storage
+	// item (`CodeInfoOf`).
+	#[benchmark]
+	fn remove_code() -> Result<(), BenchmarkError> {
+		let caller = funded_caller::<T>()?;
+		let origin = RawOrigin::Signed(caller.clone());
+		let Module { code, hash } = Module::dummy();
+		let uploaded = Revive::<T>::bare_upload_code(caller, code, None)?;
+		assert_eq!(uploaded.code_hash, hash);
+		assert!(Contract::<T>::code_exists(&hash));
+
+		#[extrinsic_call]
+		_(origin, hash);
+
+		assert!(Contract::<T>::code_removed(&hash));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn map_account() {
+		let caller = whitelisted_caller();
+		T::Currency::set_balance(&caller, caller_funding::<T>());
+		let origin = RawOrigin::Signed(caller.clone());
+		assert!(!T::AddressMapper::is_mapped(&caller));
+
+		#[extrinsic_call]
+		_(origin);
+
+		assert!(T::AddressMapper::is_mapped(&caller));
+	}
+
+	#[benchmark]
+	fn unmap_account() -> Result<(), BenchmarkError> {
+		let caller = funded_caller::<T>()?;
+		let origin = RawOrigin::Signed(caller.clone());
+		assert!(T::AddressMapper::is_mapped(&caller));
+
+		#[extrinsic_call]
+		_(origin);
+
+		assert!(!T::AddressMapper::is_mapped(&caller));
+		Ok(())
+	}
+
+	// `p`: Size of the RLP encoded transaction in bytes.
+	//
+	// The transaction calls a dummy contract. Its execution is paid from the gas limit. What is
+	// measured here is the decoding and verification of the transaction, the derivation of its
+	// gas limit as well as the overhead of the call.
+	#[benchmark]
+	fn eth_transact(p: Linear<0, { limits::code::BLOB_BYTES }>) -> Result<(), BenchmarkError> {
+		let instance = Contract::<T>::new(Module::dummy(), vec![])?;
+		let value = Pallet::<T>::min_balance();
+		let tx = TransactionLegacyUnsigned {
+			nonce: 0u32.into(),
+			gas_price: evm::GAS_PRICE.into(),
+			gas: balance_to_u256::<T>(caller_funding::<T>() / 2u32.into()),
+			to: Some(instance.address),
+			value: balance_to_u256::<T>(value),
+			input: vec![42u8; (p as usize).saturating_sub(evm::TRANSACTION_MAX_OVERHEAD)],
+			chain_id: Some(T::ChainId::get().into()),
+		};
+		let signer = sp_io::crypto::ecdsa_generate(KEY_TYPE, None);
+		let signature =
+			sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &signer, &tx.signing_hash().0)
+				.expect("Generates signature");
+		let tx = tx.with_signature(signature.0);
+		let from = tx.recover_eth_address().expect("The signature is valid");
+		let account_id = T::AddressMapper::to_account_id(&from);
+		T::Currency::set_balance(&account_id, caller_funding::<T>());
+		let payload = tx.rlp_bytes();
+		let before = T::Currency::balance(&instance.account_id);
+
+		#[extrinsic_call]
+		_(RawOrigin::None, payload);
+
+		assert_eq!(frame_system::Pallet::<T>::account_nonce(&account_id), 1u32.into());
+		assert_eq!(T::Currency::balance(&instance.account_id), before + value);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn seal_caller() {
+		let mut setup = CallSetup::<T>::default();
+		let caller = setup.contract().caller;
+		let mut memory = vec![0u8; 20];
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"caller", [0; 6]);
+			assert_eq!(result.unwrap(), None);
+		}
+
+		assert_eq!(&memory[..], T::AddressMapper::to_address(&caller).as_bytes());
+	}
+
+	#[benchmark]
+	fn seal_is_contract() -> Result<(), BenchmarkError> {
+		let callee = Contract::<T>::with_index(1, Module::dummy(), vec![])?;
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = callee.address.as_bytes().to_vec();
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"is_contract", [0; 6]);
+			assert_eq!(result.unwrap(), Some(1));
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn seal_code_hash() -> Result<(), BenchmarkError> {
+		let callee = Contract::<T>::with_index(1, Module::dummy(), vec![])?;
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = callee.address.as_bytes().to_vec();
+		memory.resize(20 + 32, 0);
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"code_hash", [0, 20, 0, 0, 0, 0]);
+			assert_eq!(result.unwrap(), Some(ReturnErrorCode::Success.into()));
+		}
+
+		assert_eq!(&memory[20..], callee.info()?.code_hash.as_bytes());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn seal_own_code_hash() -> Result<(), BenchmarkError> {
+		let mut setup = CallSetup::<T>::default();
+		let code_hash = setup.contract().info()?.code_hash;
+		let mut memory = vec![0u8; 32];
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"own_code_hash", [0; 6]);
+			assert_eq!(result.unwrap(), None);
+		}
+
+		assert_eq!(&memory[..], code_hash.as_bytes());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn seal_caller_is_origin() {
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = vec![];
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"caller_is_origin", [0; 6]);
+			assert_eq!(result.unwrap(), Some(1));
+		}
+	}
+
+	#[benchmark]
+	fn seal_caller_is_root() {
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = vec![];
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"caller_is_root", [0; 6]);
+			assert_eq!(result.unwrap(), Some(0));
+		}
+	}
+
+	#[benchmark]
+	fn seal_address() {
+		let mut setup = CallSetup::<T>::default();
+		let address = setup.contract().address;
+		let mut memory = vec![0u8; 20];
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"address", [0; 6]);
+			assert_eq!(result.unwrap(), None);
+		}
+
+		assert_eq!(&memory[..], address.as_bytes());
+	}
+
+	#[benchmark]
+	fn seal_gas_left() {
+		let mut setup = CallSetup::<T>::default();
+		// The output buffer is preceded by its length. It fits the largest encoded weight.
+		let len = Weight::MAX.encode().len() as u32;
+		let mut memory = len.to_le_bytes().to_vec();
+		memory.resize(4 + len as usize, 0);
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"gas_left", [4, 0, 0, 0, 0, 0]);
+			assert_eq!(result.unwrap(), None);
+		}
+
+		let len = u32::from_le_bytes(memory[..4].try_into().unwrap()) as usize;
+		assert!(Weight::decode(&mut &memory[4..4 + len]).is_ok());
+	}
+
+	#[benchmark]
+	fn seal_balance() {
+		let mut setup = CallSetup::<T>::default();
+		let balance = T::Currency::reducible_balance(
+			&setup.contract().account_id,
+			Preservation::Preserve,
+			Fortitude::Polite,
+		);
+		let mut memory = vec![0u8; 32];
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"balance", [0; 6]);
+			assert_eq!(result.unwrap(), None);
+		}
+
+		assert_eq!(U256::from_little_endian(&memory), balance_to_u256::<T>(balance));
+	}
+
+	#[benchmark]
+	fn seal_value_transferred() {
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = vec![0u8; 32];
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"value_transferred", [0; 6]);
+			assert_eq!(result.unwrap(), None);
+		}
+
+		assert_eq!(U256::from_little_endian(&memory), U256::zero());
+	}
+
+	#[benchmark]
+	fn seal_minimum_balance() {
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = vec![0u8; 32];
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"minimum_balance", [0; 6]);
+			assert_eq!(result.unwrap(), None);
+		}
+
+		assert_eq!(
+			U256::from_little_endian(&memory),
+			balance_to_u256::<T>(Pallet::<T>::min_balance())
+		);
+	}
+
+	#[benchmark]
+	fn seal_block_number() {
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = vec![0u8; 32];
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"block_number", [0; 6]);
+			assert_eq!(result.unwrap(), None);
+		}
+
+		let block_number: u128 = frame_system::Pallet::<T>::block_number().saturated_into();
+		assert_eq!(U256::from_little_endian(&memory), block_number.into());
+	}
+
+	#[benchmark]
+	fn seal_now() {
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = vec![0u8; 32];
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"now", [0; 6]);
+			assert_eq!(result.unwrap(), None);
+		}
+
+		let now: u128 = T::Time::now().saturated_into();
+		assert_eq!(U256::from_little_endian(&memory), now.into());
+	}
+
+	#[benchmark]
+	fn seal_chain_id() {
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = vec![0u8; 32];
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"chain_id", [0; 6]);
+			assert_eq!(result.unwrap(), None);
+		}
+
+		assert_eq!(U256::from_little_endian(&memory), T::ChainId::get().into());
+	}
+
+	// `n`: Size of the input in bytes.
+	#[benchmark]
+	fn seal_input(n: Linear<0, { limits::code::BLOB_BYTES - 4 }>) {
+		let mut setup = CallSetup::<T>::default();
+		setup.set_data(vec![42u8; n as usize]);
+		let input = setup.data();
+		// The output buffer is preceded by its length.
+		let mut memory = n.to_le_bytes().to_vec();
+		memory.resize(4 + n as usize, 0);
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"input", [4, 0, 0, 0, 0, 0]);
+			assert_eq!(result.unwrap(), None);
+		}
+
+		assert_eq!(&memory[4..], &input[..]);
+	}
+
+	// `n`: Size of the returned data in bytes.
+	#[benchmark]
+	fn seal_return(n: Linear<0, { limits::code::BLOB_BYTES - 4 }>) {
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = vec![42u8; n as usize];
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"seal_return", [0, 0, n, 0, 0, 0]);
+			assert!(matches!(result, Err(TrapReason::Return(_))));
+		}
+	}
+
+	#[benchmark]
+	fn seal_terminate() -> Result<(), BenchmarkError> {
+		let beneficiary =
+			T::AddressMapper::to_address(&account::<T::AccountId>("beneficiary", 0, 0));
+		let mut setup = CallSetup::<T>::default();
+		let contract = setup.contract();
+		let mut memory = beneficiary.as_bytes().to_vec();
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"terminate", [0; 6]);
+			assert!(matches!(result, Err(TrapReason::Termination)));
+		}
+
+		assert!(Contract::<T>::address_info(&contract.address).is_err());
+		Ok(())
+	}
+
+	// `t`: Number of topics.
+	// `n`: Size of the event data in bytes.
+	#[benchmark]
+	fn seal_deposit_event(
+		t: Linear<0, { limits::NUM_EVENT_TOPICS }>,
+		n: Linear<0, { limits::PAYLOAD_BYTES }>,
+	) {
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = (0..t)
+			.flat_map(|i| H256::repeat_byte(i as u8).to_fixed_bytes())
+			.collect::<Vec<_>>();
+		memory.resize(memory.len() + n as usize, 42);
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"deposit_event", [0, t, t * 32, n, 0, 0]);
+			assert_eq!(result.unwrap(), None);
+		}
+	}
+
+	// `i`: Size of the debug message in bytes.
+	#[benchmark]
+	fn seal_debug_message(i: Linear<0, { limits::code::BLOB_BYTES }>) {
+		let mut setup = CallSetup::<T>::default();
+		setup.enable_debug_message();
+		let mut memory = vec![b'A'; i as usize];
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"debug_message", [0, i, 0, 0, 0, 0]);
+			assert_eq!(result.unwrap(), Some(ReturnErrorCode::Success.into()));
+		}
+
+		assert_eq!(setup.debug_message().unwrap().len() as u32, i);
+	}
+
+	// `n`: Size of the new value in bytes.
+	// `o`: Size of the old value in bytes.
+	#[benchmark(skip_meta)]
+	fn seal_set_storage(
+		n: Linear<0, { limits::PAYLOAD_BYTES }>,
+		o: Linear<0, { limits::PAYLOAD_BYTES }>,
+	) -> Result<(), BenchmarkError> {
+		let key = max_storage_key();
+		let value = vec![1u8; n as usize];
+		let mut setup = CallSetup::<T>::default();
+		let info = setup.contract().info()?;
+		info.write(&key, Some(vec![42u8; o as usize]), None, false)
+			.map_err(|_| "Failed to write to storage during setup.")?;
+		let key_len = key.to_vec().len() as u32;
+		let mut memory = key.to_vec();
+		memory.extend_from_slice(&value);
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"set_storage", [0, key_len, key_len, n, 0, 0]);
+			assert_eq!(result.unwrap(), Some(o));
+		}
+
+		assert_eq!(info.read(&key).unwrap(), value);
+		Ok(())
+	}
+
+	// `n`: Size of the cleared value in bytes.
+	#[benchmark(skip_meta)]
+	fn seal_clear_storage(n: Linear<0, { limits::PAYLOAD_BYTES }>) -> Result<(), BenchmarkError> {
+		let key = max_storage_key();
+		let mut setup = CallSetup::<T>::default();
+		let info = setup.contract().info()?;
+		info.write(&key, Some(vec![42u8; n as usize]), None, false)
+			.map_err(|_| "Failed to write to storage during setup.")?;
+		let key_len = key.to_vec().len() as u32;
+		let mut memory = key.to_vec();
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"clear_storage", [0, key_len, 0, 0, 0, 0]);
+			assert_eq!(result.unwrap(), Some(n));
+		}
+
+		assert!(info.read(&key).is_none());
+		Ok(())
+	}
+
+	// `n`: Size of the read value in bytes.
+	#[benchmark(skip_meta)]
+	fn seal_get_storage(n: Linear<0, { limits::PAYLOAD_BYTES }>) -> Result<(), BenchmarkError> {
+		let key = max_storage_key();
+		let value = vec![42u8; n as usize];
+		let mut setup = CallSetup::<T>::default();
+		let info = setup.contract().info()?;
+		info.write(&key, Some(value.clone()), None, false)
+			.map_err(|_| "Failed to write to storage during setup.")?;
+		let key_len = key.to_vec().len() as u32;
+		// The output buffer is preceded by its length.
+		let mut memory = key.to_vec();
+		memory.extend_from_slice(&n.to_le_bytes());
+		memory.resize(memory.len() + n as usize, 0);
+		let out_ptr = key_len + 4;
+
+		#[block]
+		{
+			let result =
+				setup.syscall(&mut memory, b"get_storage", [0, key_len, out_ptr, key_len, 0, 0]);
+			assert_eq!(result.unwrap(), Some(ReturnErrorCode::Success.into()));
+		}
+
+		assert_eq!(&memory[out_ptr as usize..], &value[..]);
+		Ok(())
+	}
+
+	// `n`: Size of the checked value in bytes.
+	#[benchmark(skip_meta)]
+	fn seal_contains_storage(
+		n: Linear<0, { limits::PAYLOAD_BYTES }>,
+	) -> Result<(), BenchmarkError> {
+		let key = max_storage_key();
+		let mut setup = CallSetup::<T>::default();
+		let info = setup.contract().info()?;
+		info.write(&key, Some(vec![42u8; n as usize]), None, false)
+			.map_err(|_| "Failed to write to storage during setup.")?;
+		let key_len = key.to_vec().len() as u32;
+		let mut memory = key.to_vec();
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"contains_storage", [0, key_len, 0, 0, 0, 0]);
+			assert_eq!(result.unwrap(), Some(n));
+		}
+
+		assert!(info.read(&key).is_some());
+		Ok(())
+	}
+
+	// `n`: Size of the taken value in bytes.
+	#[benchmark(skip_meta)]
+	fn seal_take_storage(n: Linear<0, { limits::PAYLOAD_BYTES }>) -> Result<(), BenchmarkError> {
+		let key = max_storage_key();
+		let value = vec![42u8; n as usize];
+		let mut setup = CallSetup::<T>::default();
+		let info = setup.contract().info()?;
+		info.write(&key, Some(value.clone()), None, false)
+			.map_err(|_| "Failed to write to storage during setup.")?;
+		let key_len = key.to_vec().len() as u32;
+		// The output buffer is preceded by its length.
+		let mut memory = key.to_vec();
+		memory.extend_from_slice(&n.to_le_bytes());
+		memory.resize(memory.len() + n as usize, 0);
+		let out_ptr = key_len + 4;
+
+		#[block]
+		{
+			let result =
+				setup.syscall(&mut memory, b"take_storage", [0, key_len, out_ptr, key_len, 0, 0]);
+			assert_eq!(result.unwrap(), Some(ReturnErrorCode::Success.into()));
+		}
+
+		assert_eq!(&memory[out_ptr as usize..], &value[..]);
+		assert!(info.read(&key).is_none());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn seal_transfer() {
+		let recipient = account::<T::AccountId>("recipient", 0, 0);
+		let value = Pallet::<T>::min_balance();
+		let mut setup = CallSetup::<T>::default();
+		setup.set_balance(caller_funding::<T>());
+		let to = T::AddressMapper::to_address(&recipient);
+		let mut memory = to.as_bytes().to_vec();
+		memory.extend_from_slice(&u256_bytes(balance_to_u256::<T>(value)));
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"transfer", [0, 20, 0, 0, 0, 0]);
+			assert_eq!(result.unwrap(), Some(ReturnErrorCode::Success.into()));
+		}
+
+		assert_eq!(T::Currency::balance(&T::AddressMapper::to_account_id(&to)), value);
+	}
+
+	// `t`: Whether a value is transferred.
+	// `i`: Size of the input in bytes which is cloned from the input of the caller.
+	#[benchmark]
+	fn seal_call(
+		t: Linear<0, 1>,
+		i: Linear<0, { limits::code::BLOB_BYTES }>,
+	) -> Result<(), BenchmarkError> {
+		let callee = Contract::<T>::with_index(1, Module::dummy(), vec![])?;
+		let value = if t == 1 { Pallet::<T>::min_balance() } else { Zero::zero() };
+		let mut setup = CallSetup::<T>::default();
+		setup.set_balance(caller_funding::<T>());
+		setup.set_data(vec![42u8; i as usize]);
+		let before = T::Currency::balance(&callee.account_id);
+
+		let mut memory = callee.address.as_bytes().to_vec();
+		memory.extend_from_slice(&u256_bytes(balance_to_u256::<T>(value)));
+		let args_ptr = memory.len() as u32;
+		let (flags, callee_ptr, value_ptr) = (CallFlags::CLONE_INPUT.bits(), 0u32, 20u32);
+		memory.extend_from_slice(
+			&(
+				flags,
+				callee_ptr,
+				u64::MAX,
+				u64::MAX,
+				SENTINEL,
+				value_ptr,
+				0u32,
+				0u32,
+				SENTINEL,
+				0u32,
+			)
+				.encode(),
+		);
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"call", [args_ptr, 0, 0, 0, 0, 0]);
+			assert_eq!(result.unwrap(), Some(ReturnErrorCode::Success.into()));
+		}
+
+		assert_eq!(T::Currency::balance(&callee.account_id), before + value);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn seal_delegate_call() -> Result<(), BenchmarkError> {
+		let hash = Contract::<T>::with_index(1, Module::dummy(), vec![])?.info()?.code_hash;
+		let mut setup = CallSetup::<T>::default();
+
+		let mut memory = hash.as_bytes().to_vec();
+		let args_ptr = memory.len() as u32;
+		memory.extend_from_slice(&(0u32, 0u32, 0u32, 0u32, SENTINEL, 0u32).encode());
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"delegate_call", [args_ptr, 0, 0, 0, 0, 0]);
+			assert_eq!(result.unwrap(), Some(ReturnErrorCode::Success.into()));
+		}
+
+		Ok(())
+	}
+
+	// `t`: Whether a value is transferred.
+	// `i`: Size of the input in bytes.
+	#[benchmark]
+	fn seal_instantiate(
+		t: Linear<0, 1>,
+		i: Linear<0, { limits::code::BLOB_BYTES - 256 }>,
+	) -> Result<(), BenchmarkError> {
+		let Module { code, hash } = Module::dummy();
+		let input = vec![42u8; i as usize];
+		let salt = [42u8; 32];
+		let value = if t == 1 { Pallet::<T>::min_balance() } else { Zero::zero() };
+		let mut setup = CallSetup::<T>::default();
+		setup.set_balance(caller_funding::<T>());
+		let deployer = setup.contract().address;
+		let address = create2(&deployer, &code, &input, &salt);
+
+		// code hash, value, salt, the returned address and the input followed by the arguments
+		let (hash_ptr, value_ptr, salt_ptr, address_ptr, input_ptr): (u32, u32, u32, u32, u32) =
+			(0, 32, 64, 96, 116);
+		let mut memory = hash.as_bytes().to_vec();
+		memory.extend_from_slice(&u256_bytes(balance_to_u256::<T>(value)));
+		memory.extend_from_slice(&salt);
+		memory.extend_from_slice(&[0u8; 20]);
+		memory.extend_from_slice(&input);
+		let args_ptr = memory.len() as u32;
+		memory.extend_from_slice(
+			&(
+				hash_ptr,
+				u64::MAX,
+				u64::MAX,
+				SENTINEL,
+				value_ptr,
+				input_ptr,
+				i,
+				address_ptr,
+				SENTINEL,
+				0u32,
+				salt_ptr,
+			)
+				.encode(),
+		);
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"instantiate", [args_ptr, 0, 0, 0, 0, 0]);
+			assert_eq!(result.unwrap(), Some(ReturnErrorCode::Success.into()));
+		}
+
+		assert_eq!(&memory[address_ptr as usize..input_ptr as usize], address.as_bytes());
+		assert_eq!(Contract::<T>::address_info(&address)?.code_hash, hash);
+		Ok(())
+	}
+
+	// `n`: Size of the input in bytes.
+	#[benchmark]
+	fn seal_hash_sha2_256(n: Linear<0, { limits::code::BLOB_BYTES }>) {
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = vec![42u8; n as usize];
+		memory.resize(n as usize + 32, 0);
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"hash_sha2_256", [0, n, n, 0, 0, 0]);
+			assert_eq!(result.unwrap(), None);
+		}
+
+		assert_eq!(
+			&memory[n as usize..],
+			sp_io::hashing::sha2_256(&memory[..n as usize]).as_slice()
+		);
+	}
+
+	// `n`: Size of the input in bytes.
+	#[benchmark]
+	fn seal_hash_keccak_256(n: Linear<0, { limits::code::BLOB_BYTES }>) {
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = vec![42u8; n as usize];
+		memory.resize(n as usize + 32, 0);
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"hash_keccak_256", [0, n, n, 0, 0, 0]);
+			assert_eq!(result.unwrap(), None);
+		}
+
+		assert_eq!(
+			&memory[n as usize..],
+			sp_io::hashing::keccak_256(&memory[..n as usize]).as_slice()
+		);
+	}
+
+	// `n`: Size of the input in bytes.
+	#[benchmark]
+	fn seal_hash_blake2_256(n: Linear<0, { limits::code::BLOB_BYTES }>) {
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = vec![42u8; n as usize];
+		memory.resize(n as usize + 32, 0);
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"hash_blake2_256", [0, n, n, 0, 0, 0]);
+			assert_eq!(result.unwrap(), None);
+		}
+
+		assert_eq!(
+			&memory[n as usize..],
+			sp_io::hashing::blake2_256(&memory[..n as usize]).as_slice()
+		);
+	}
+
+	// `n`: Size of the input in bytes.
+	#[benchmark]
+	fn seal_hash_blake2_128(n: Linear<0, { limits::code::BLOB_BYTES }>) {
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = vec![42u8; n as usize];
+		memory.resize(n as usize + 16, 0);
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"hash_blake2_128", [0, n, n, 0, 0, 0]);
+			assert_eq!(result.unwrap(), None);
+		}
+
+		assert_eq!(
+			&memory[n as usize..],
+			sp_io::hashing::blake2_128(&memory[..n as usize]).as_slice()
+		);
+	}
+
+	#[benchmark]
+	fn seal_ecdsa_recover() {
+		let message_hash = sp_io::hashing::blake2_256("Hello world".as_bytes());
+		let pub_key = sp_io::crypto::ecdsa_generate(KEY_TYPE, None);
+		let signature = sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &pub_key, &message_hash)
+			.expect("Generates signature");
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = signature.0.to_vec();
+		memory.extend_from_slice(&message_hash);
+		memory.resize(memory.len() + 33, 0);
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"ecdsa_recover", [0, 65, 97, 0, 0, 0]);
+			assert_eq!(result.unwrap(), Some(ReturnErrorCode::Success.into()));
+		}
+
+		assert_eq!(&memory[97..], &pub_key.0[..]);
+	}
+
+	#[benchmark]
+	fn seal_ecdsa_to_eth_address() {
+		let pub_key = sp_io::crypto::ecdsa_generate(KEY_TYPE, None);
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = pub_key.0.to_vec();
+		memory.resize(memory.len() + 20, 0);
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"ecdsa_to_eth_address", [0, 33, 0, 0, 0, 0]);
+			assert_eq!(result.unwrap(), Some(ReturnErrorCode::Success.into()));
+		}
+
+		assert_eq!(&memory[33..], &pub_key.to_eth_address().unwrap()[..]);
+	}
+
+	#[benchmark]
+	fn seal_reentrance_count() {
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = vec![];
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"reentrance_count", [0; 6]);
+			assert_eq!(result.unwrap(), Some(0));
+		}
+	}
+
+	#[benchmark]
+	fn seal_account_reentrance_count() -> Result<(), BenchmarkError> {
+		let other = Contract::<T>::with_index(1, Module::dummy(), vec![])?;
+		let mut setup = CallSetup::<T>::default();
+		let mut memory = other.address.as_bytes().to_vec();
+
+		#[block]
+		{
+			let result = setup.syscall(&mut memory, b"account_reentrance_count", [0; 6]);
+			assert_eq!(result.unwrap(), Some(0));
+		}
+
+		Ok(())
+	}
+
+	// We call a contract which executes a loop of `r` instructions. The per instruction weight
+	// is supposed to be used as `instr(1) - instr(0)`.
+	#[benchmark]
+	fn instr(r: Linear<0, INSTR_BENCHMARK_RUNS>) -> Result<(), BenchmarkError> {
+		let instance = Contract::<T>::with_caller(whitelisted_caller(), Module::instr(r), vec![])?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+
+		#[extrinsic_call]
+		call(origin, instance.address, Zero::zero(), Weight::MAX, None, vec![]);
+
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(
+		Revive,
+		crate::tests::ExtBuilder::default().build(),
+		crate::tests::Test,
+	);
+}
@@ -0,0 +1,726 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding and signature recovery of Ethereum transactions.
+//!
+//! Ethereum wallets submit transactions as RLP encoded and signed byte blobs. Those bytes are
+//! passed verbatim to [`crate::Pallet::eth_transact`] which uses the types in this module to
+//! find out who signed them and what they are supposed to do.
+//!
+//! Legacy (type 0) and [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559) (type 2) transactions
+//! are supported. [`TransactionSigned`] decodes either of them. Legacy transactions must be replay
+//! protected as described in [EIP-155](https://eips.ethereum.org/EIPS/eip-155).
+
+use rlp::{DecoderError, Rlp, RlpStream};
+use sp_core::{H160, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Half of the order of the secp256k1 curve.
+///
+/// Signatures with an `s` above this value are rejected as described in
+/// [EIP-2](https://eips.ethereum.org/EIPS/eip-2) so that transactions can't be made malleable.
+const SECP256K1N_HALF: U256 =
+	U256([0xdfe92f46681b20a0, 0x5d576e7357a4501d, 0xffffffffffffffff, 0x7fffffffffffffff]);
+
+/// The price of one unit of gas in the native currency.
+///
+/// Fees are derived from the consumed weight. Ethereum tooling expects them to be expressed
+/// as `gas * gas_price`. Fixing the price to `1` makes `gas` equal to the fee.
+pub const GAS_PRICE: u64 = 1;
+
+/// The prefix of the transaction pool tags used by [`crate::Pallet::eth_transact`].
+///
+/// Every transaction provides the tag `(TAG_PREFIX, (signer, nonce))` and requires the one of
+/// the previous nonce unless its nonce is the current account nonce.
+pub const TAG_PREFIX: &str = "ReviveEthTransact";
+
+/// The type reported for legacy transactions, which have no type byte.
+pub const TYPE_LEGACY: u8 = 0;
+
+/// The type byte which prefixes the payload of an EIP-1559 transaction.
+pub const TYPE_EIP1559: u8 = 2;
+
+/// An upper bound of the bytes a transaction without an access list needs on top of its input.
+///
+/// This is the size of an EIP-1559 transaction, which is larger than a legacy one: The type
+/// byte, the list and input headers of 9 bytes each at most, the eight 256 bit fields of 33
+/// bytes each, `to` with 21 bytes and a byte each for the empty access list and the y parity.
+pub const TRANSACTION_MAX_OVERHEAD: usize = 1 + 9 + 9 + 8 * 33 + 21 + 1 + 1;
+
+/// The fields of a legacy transaction that are covered by its signature.
+#[derive(Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct TransactionLegacyUnsigned {
+	/// The nonce of the signer. Needs to be equal to the account nonce of the signer.
+	pub nonce: U256,
+	/// The price per unit of gas the signer is willing to pay.
+	pub gas_price: U256,
+	/// The maximum amount of gas the signer is willing to pay for.
+	pub gas: U256,
+	/// The called contract or `None` if a new contract should be instantiated.
+	pub to: Option<H160>,
+	/// The value that is transferred to `to`.
+	pub value: U256,
+	/// The call data or the code of the contract to create.
+	pub input: Vec<u8>,
+	/// The chain this transaction is meant for or `None` if it isn't replay protected.
+	pub chain_id: Option<U256>,
+}
+
+impl TransactionLegacyUnsigned {
+	/// The RLP encoding of the transaction as it is hashed for signing.
+	pub fn rlp_bytes(&self) -> Vec<u8> {
+		let mut s = RlpStream::new_list(if self.chain_id.is_some() { 9 } else { 6 });
+		self.append_fields(&mut s);
+		if let Some(chain_id) = &self.chain_id {
+			append_u256(&mut s, chain_id);
+			s.append_empty_data();
+			s.append_empty_data();
+		}
+		s.out().to_vec()
+	}
+
+	/// The hash which is signed by the sender of the transaction.
+	pub fn signing_hash(&self) -> H256 {
+		H256(keccak_256(&self.rlp_bytes()))
+	}
+
+	/// Attach a signature created over [`Self::signing_hash`].
+	///
+	/// The last byte of `signature` is the recovery id which needs to be either `0` or `1`.
+	pub fn with_signature(self, signature: [u8; 65]) -> TransactionLegacySigned {
+		let recovery_id = U256::from(signature[64]);
+		let v = match &self.chain_id {
+			Some(chain_id) => *chain_id * 2 + 35 + recovery_id,
+			None => recovery_id + 27,
+		};
+		TransactionLegacySigned {
+			transaction_legacy_unsigned: self,
+			r: U256::from_big_endian(&signature[..32]),
+			s: U256::from_big_endian(&signature[32..64]),
+			v,
+		}
+	}
+
+	fn append_fields(&self, s: &mut RlpStream) {
+		append_u256(s, &self.nonce);
+		append_u256(s, &self.gas_price);
+		append_u256(s, &self.gas);
+		append_to(s, &self.to);
+		append_u256(s, &self.value);
+		s.append(&self.input);
+	}
+}
+
+/// A legacy transaction together with its signature.
+#[derive(Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct TransactionLegacySigned {
+	/// The signed part of the transaction.
+	pub transaction_legacy_unsigned: TransactionLegacyUnsigned,
+	/// The `r` value of the signature.
+	pub r: U256,
+	/// The `s` value of the signature.
+	pub s: U256,
+	/// The recovery id combined with the chain id.
+	pub v: U256,
+}
+
+impl TransactionLegacySigned {
+	/// Decode a transaction from the bytes a wallet submitted.
+	///
+	/// The chain id is extracted from `v`. Trailing bytes are not allowed.
+	pub fn decode(payload: &[u8]) -> Result<Self, DecoderError> {
+		let rlp = Rlp::new(payload);
+		if rlp.payload_info()?.total() != payload.len() {
+			return Err(DecoderError::RlpInconsistentLengthAndData)
+		}
+		if rlp.item_count()? != 9 {
+			return Err(DecoderError::RlpIncorrectListLen)
+		}
+
+		let v = decode_u256(&rlp, 6)?;
+		let chain_id = if v >= U256::from(35) {
+			Some((v - 35) / 2)
+		} else if v == U256::from(27) || v == U256::from(28) {
+			None
+		} else {
+			return Err(DecoderError::Custom("invalid v"))
+		};
+
+		Ok(Self {
+			transaction_legacy_unsigned: TransactionLegacyUnsigned {
+				nonce: decode_u256(&rlp, 0)?,
+				gas_price: decode_u256(&rlp, 1)?,
+				gas: decode_u256(&rlp, 2)?,
+				to: decode_to(&rlp, 3)?,
+				value: decode_u256(&rlp, 4)?,
+				input: rlp.val_at(5)?,
+				chain_id,
+			},
+			r: decode_u256(&rlp, 7)?,
+			s: decode_u256(&rlp, 8)?,
+			v,
+		})
+	}
+
+	/// The RLP encoding of the signed transaction.
+	///
+	/// This is the inverse of [`Self::decode`].
+	pub fn rlp_bytes(&self) -> Vec<u8> {
+		let mut s = RlpStream::new_list(9);
+		self.transaction_legacy_unsigned.append_fields(&mut s);
+		append_u256(&mut s, &self.v);
+		append_u256(&mut s, &self.r);
+		append_u256(&mut s, &self.s);
+		s.out().to_vec()
+	}
+
+	/// The recovery id encoded in `v`.
+	///
+	/// Returns `None` if `v` is not consistent with the chain id.
+	pub fn recovery_id(&self) -> Option<u8> {
+		let offset = match &self.transaction_legacy_unsigned.chain_id {
+			Some(chain_id) => chain_id.checked_mul(2.into())?.checked_add(35.into())?,
+			None => 27.into(),
+		};
+		match self.v.checked_sub(offset)?.low_u64() {
+			id @ (0 | 1) => Some(id as u8),
+			_ => None,
+		}
+	}
+
+	/// The signature in the format expected by `secp256k1_ecdsa_recover`.
+	pub fn signature(&self) -> Option<[u8; 65]> {
+		signature(&self.r, &self.s, self.recovery_id()?)
+	}
+
+	/// Recover the address of the account that signed this transaction.
+	///
+	/// Returns `None` if the signature is invalid.
+	pub fn recover_eth_address(&self) -> Option<H160> {
+		recover_eth_address(&self.signature()?, &self.transaction_legacy_unsigned.signing_hash())
+	}
+}
+
+/// An entry of the access list of an EIP-1559 transaction.
+#[derive(Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct AccessListEntry {
+	/// The accessed account.
+	pub address: H160,
+	/// The accessed storage keys of `address`.
+	pub storage_keys: Vec<H256>,
+}
+
+/// The fields of an EIP-1559 transaction that are covered by its signature.
+#[derive(Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct Transaction1559Unsigned {
+	/// The chain this transaction is meant for.
+	pub chain_id: U256,
+	/// The nonce of the signer. Needs to be equal to the account nonce of the signer.
+	pub nonce: U256,
+	/// The part of `max_fee_per_gas` the signer is willing to pay as a tip.
+	pub max_priority_fee_per_gas: U256,
+	/// The maximum price per unit of gas the signer is willing to pay.
+	pub max_fee_per_gas: U256,
+	/// The maximum amount of gas the signer is willing to pay for.
+	pub gas: U256,
+	/// The called contract or `None` if a new contract should be instantiated.
+	pub to: Option<H160>,
+	/// The value that is transferred to `to`.
+	pub value: U256,
+	/// The call data or the code of the contract to create.
+	pub input: Vec<u8>,
+	/// The accounts and storage keys the transaction is going to access.
+	///
+	/// It is covered by the signature but has no effect on the execution.
+	pub access_list: Vec<AccessListEntry>,
+}
+
+impl Transaction1559Unsigned {
+	/// The typed encoding of the transaction as it is hashed for signing.
+	pub fn rlp_bytes(&self) -> Vec<u8> {
+		let mut s = RlpStream::new_list(9);
+		self.append_fields(&mut s);
+		[&[TYPE_EIP1559][..], &s.out()].concat()
+	}
+
+	/// The hash which is signed by the sender of the transaction.
+	pub fn signing_hash(&self) -> H256 {
+		H256(keccak_256(&self.rlp_bytes()))
+	}
+
+	/// Attach a signature created over [`Self::signing_hash`].
+	///
+	/// The last byte of `signature` is the recovery id which needs to be either `0` or `1`.
+	pub fn with_signature(self, signature: [u8; 65]) -> Transaction1559Signed {
+		Transaction1559Signed {
+			transaction_1559_unsigned: self,
+			r: U256::from_big_endian(&signature[..32]),
+			s: U256::from_big_endian(&signature[32..64]),
+			y_parity: U256::from(signature[64]),
+		}
+	}
+
+	fn append_fields(&self, s: &mut RlpStream) {
+		append_u256(s, &self.chain_id);
+		append_u256(s, &self.nonce);
+		append_u256(s, &self.max_priority_fee_per_gas);
+		append_u256(s, &self.max_fee_per_gas);
+		append_u256(s, &self.gas);
+		append_to(s, &self.to);
+		append_u256(s, &self.value);
+		s.append(&self.input);
+		s.begin_list(self.access_list.len());
+		for entry in &self.access_list {
+			s.begin_list(2);
+			s.append(&entry.address.as_bytes().to_vec());
+			s.begin_list(entry.storage_keys.len());
+			for key in &entry.storage_keys {
+				s.append(&key.as_bytes().to_vec());
+			}
+		}
+	}
+}
+
+/// An EIP-1559 transaction together with its signature.
+#[derive(Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct Transaction1559Signed {
+	/// The signed part of the transaction.
+	pub transaction_1559_unsigned: Transaction1559Unsigned,
+	/// The `r` value of the signature.
+	pub r: U256,
+	/// The `s` value of the signature.
+	pub s: U256,
+	/// The recovery id of the signature.
+	pub y_parity: U256,
+}
+
+impl Transaction1559Signed {
+	/// Decode a transaction from the bytes a wallet submitted.
+	///
+	/// The payload needs to start with [`TYPE_EIP1559`]. Trailing bytes are not allowed.
+	pub fn decode(payload: &[u8]) -> Result<Self, DecoderError> {
+		let Some((&TYPE_EIP1559, payload)) = payload.split_first() else {
+			return Err(DecoderError::Custom("invalid transaction type"))
+		};
+		let rlp = Rlp::new(payload);
+		if rlp.payload_info()?.total() != payload.len() {
+			return Err(DecoderError::RlpInconsistentLengthAndData)
+		}
+		if rlp.item_count()? != 12 {
+			return Err(DecoderError::RlpIncorrectListLen)
+		}
+
+		let access_list = rlp.at(8)?;
+		if !access_list.is_list() {
+			return Err(DecoderError::RlpExpectedToBeList)
+		}
+		let access_list = access_list
+			.iter()
+			.map(|entry| {
+				if entry.item_count()? != 2 {
+					return Err(DecoderError::RlpIncorrectListLen)
+				}
+				let storage_keys = entry.at(1)?;
+				if !storage_keys.is_list() {
+					return Err(DecoderError::RlpExpectedToBeList)
+				}
+				Ok(AccessListEntry {
+					address: H160::from_slice(&decode_fixed(&entry.at(0)?, 20)?),
+					storage_keys: storage_keys
+						.iter()
+						.map(|key| decode_fixed(&key, 32).map(|key| H256::from_slice(&key)))
+						.collect::<Result<_, _>>()?,
+				})
+			})
+			.collect::<Result<_, _>>()?;
+		let y_parity = decode_u256(&rlp, 9)?;
+		if y_parity > U256::one() {
+			return Err(DecoderError::Custom("invalid y parity"))
+		}
+
+		Ok(Self {
+			transaction_1559_unsigned: Transaction1559Unsigned {
+				chain_id: decode_u256(&rlp, 0)?,
+				nonce: decode_u256(&rlp, 1)?,
+				max_priority_fee_per_gas: decode_u256(&rlp, 2)?,
+				max_fee_per_gas: decode_u256(&rlp, 3)?,
+				gas: decode_u256(&rlp, 4)?,
+				to: decode_to(&rlp, 5)?,
+				value: decode_u256(&rlp, 6)?,
+				input: rlp.val_at(7)?,
+				access_list,
+			},
+			r: decode_u256(&rlp, 10)?,
+			s: decode_u256(&rlp, 11)?,
+			y_parity,
+		})
+	}
+
+	/// The typed encoding of the signed transaction.
+	///
+	/// This is the inverse of [`Self::decode`].
+	pub fn rlp_bytes(&self) -> Vec<u8> {
+		let mut s = RlpStream::new_list(12);
+		self.transaction_1559_unsigned.append_fields(&mut s);
+		append_u256(&mut s, &self.y_parity);
+		append_u256(&mut s, &self.r);
+		append_u256(&mut s, &self.s);
+		[&[TYPE_EIP1559][..], &s.out()].concat()
+	}
+
+	/// The signature in the format expected by `secp256k1_ecdsa_recover`.
+	pub fn signature(&self) -> Option<[u8; 65]> {
+		if self.y_parity > U256::one() {
+			return None
+		}
+		signature(&self.r, &self.s, self.y_parity.low_u64() as u8)
+	}
+
+	/// Recover the address of the account that signed this transaction.
+	///
+	/// Returns `None` if the signature is invalid.
+	pub fn recover_eth_address(&self) -> Option<H160> {
+		recover_eth_address(&self.signature()?, &self.transaction_1559_unsigned.signing_hash())
+	}
+}
+
+/// A signed transaction of any of the supported types.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum TransactionSigned {
+	/// A legacy transaction.
+	TransactionLegacySigned(TransactionLegacySigned),
+	/// An EIP-1559 transaction.
+	Transaction1559Signed(Transaction1559Signed),
+}
+
+impl From<TransactionLegacySigned> for TransactionSigned {
+	fn from(tx: TransactionLegacySigned) -> Self {
+		Self::TransactionLegacySigned(tx)
+	}
+}
+
+impl From<Transaction1559Signed> for TransactionSigned {
+	fn from(tx: Transaction1559Signed) -> Self {
+		Self::Transaction1559Signed(tx)
+	}
+}
+
+impl TransactionSigned {
+	/// Decode a transaction from the bytes a wallet submitted.
+	///
+	/// Typed transactions start with their type byte, legacy transactions with the header of an
+	/// RLP list.
+	pub fn decode(payload: &[u8]) -> Result<Self, DecoderError> {
+		match payload.first() {
+			Some(&TYPE_EIP1559) => Transaction1559Signed::decode(payload).map(Into::into),
+			Some(byte) if *byte >= 0xc0 => TransactionLegacySigned::decode(payload).map(Into::into),
+			_ => Err(DecoderError::Custom("unsupported transaction type")),
+		}
+	}
+
+	/// The type of the transaction as reported in its receipt.
+	pub fn transaction_type(&self) -> u8 {
+		match self {
+			Self::TransactionLegacySigned(_) => TYPE_LEGACY,
+			Self::Transaction1559Signed(_) => TYPE_EIP1559,
+		}
+	}
+
+	/// The encoding of the signed transaction.
+	///
+	/// This is the inverse of [`Self::decode`].
+	pub fn rlp_bytes(&self) -> Vec<u8> {
+		match self {
+			Self::TransactionLegacySigned(tx) => tx.rlp_bytes(),
+			Self::Transaction1559Signed(tx) => tx.rlp_bytes(),
+		}
+	}
+
+	/// Recover the address of the account that signed this transaction.
+	///
+	/// Returns `None` if the signature is invalid.
+	pub fn recover_eth_address(&self) -> Option<H160> {
+		match self {
+			Self::TransactionLegacySigned(tx) => tx.recover_eth_address(),
+			Self::Transaction1559Signed(tx) => tx.recover_eth_address(),
+		}
+	}
+
+	/// The chain this transaction is meant for or `None` if it isn't replay protected.
+	pub fn chain_id(&self) -> Option<U256> {
+		match self {
+			Self::TransactionLegacySigned(tx) => tx.transaction_legacy_unsigned.chain_id,
+			Self::Transaction1559Signed(tx) => Some(tx.transaction_1559_unsigned.chain_id),
+		}
+	}
+
+	/// The nonce of the signer.
+	pub fn nonce(&self) -> U256 {
+		match self {
+			Self::TransactionLegacySigned(tx) => tx.transaction_legacy_unsigned.nonce,
+			Self::Transaction1559Signed(tx) => tx.transaction_1559_unsigned.nonce,
+		}
+	}
+
+	/// The maximum amount of gas the signer is willing to pay for.
+	pub fn gas(&self) -> U256 {
+		match self {
+			Self::TransactionLegacySigned(tx) => tx.transaction_legacy_unsigned.gas,
+			Self::Transaction1559Signed(tx) => tx.transaction_1559_unsigned.gas,
+		}
+	}
+
+	/// The maximum price per unit of gas the signer is willing to pay.
+	///
+	/// This is the `gas_price` of legacy transactions and the `max_fee_per_gas` of EIP-1559
+	/// transactions.
+	pub fn max_fee_per_gas(&self) -> U256 {
+		match self {
+			Self::TransactionLegacySigned(tx) => tx.transaction_legacy_unsigned.gas_price,
+			Self::Transaction1559Signed(tx) => tx.transaction_1559_unsigned.max_fee_per_gas,
+		}
+	}
+
+	/// The called contract or `None` if a new contract should be instantiated.
+	pub fn to(&self) -> Option<H160> {
+		match self {
+			Self::TransactionLegacySigned(tx) => tx.transaction_legacy_unsigned.to,
+			Self::Transaction1559Signed(tx) => tx.transaction_1559_unsigned.to,
+		}
+	}
+
+	/// The value that is transferred to `to`.
+	pub fn value(&self) -> U256 {
+		match self {
+			Self::TransactionLegacySigned(tx) => tx.transaction_legacy_unsigned.value,
+			Self::Transaction1559Signed(tx) => tx.transaction_1559_unsigned.value,
+		}
+	}
+
+	/// Consume the transaction and return its input.
+	pub fn into_input(self) -> Vec<u8> {
+		match self {
+			Self::TransactionLegacySigned(tx) => tx.transaction_legacy_unsigned.input,
+			Self::Transaction1559Signed(tx) => tx.transaction_1559_unsigned.input,
+		}
+	}
+}
+
+/// The hash under which Ethereum tooling refers to the transaction contained in `payload`.
+pub fn transaction_hash(payload: &[u8]) -> H256 {
+	H256(keccak_256(payload))
+}
+
+/// Build the signature in the format expected by `secp256k1_ecdsa_recover`.
+///
+/// Returns `None` if `r` or `s` are out of range.
+fn signature(r: &U256, s: &U256, recovery_id: u8) -> Option<[u8; 65]> {
+	if r.is_zero() || s.is_zero() || *s > SECP256K1N_HALF {
+		return None
+	}
+	let mut signature = [0u8; 65];
+	r.to_big_endian(&mut signature[..32]);
+	s.to_big_endian(&mut signature[32..64]);
+	signature[64] = recovery_id;
+	Some(signature)
+}
+
+/// Recover the address of the account that created `signature` over `msg`.
+fn recover_eth_address(signature: &[u8; 65], msg: &H256) -> Option<H160> {
+	let pubkey = sp_io::crypto::secp256k1_ecdsa_recover(signature, &msg.0).ok()?;
+	Some(H160::from_slice(&keccak_256(&pubkey)[12..]))
+}
+
+/// Append `to` as the empty string if a contract is created.
+fn append_to(s: &mut RlpStream, to: &Option<H160>) {
+	match to {
+		Some(to) => s.append(&to.as_bytes().to_vec()),
+		None => s.append_empty_data(),
+	};
+}
+
+/// Decode the item at `index` which was encoded by [`append_to`].
+fn decode_to(rlp: &Rlp, index: usize) -> Result<Option<H160>, DecoderError> {
+	let to: Vec<u8> = rlp.val_at(index)?;
+	match to.len() {
+		0 => Ok(None),
+		20 => Ok(Some(H160::from_slice(&to))),
+		_ => Err(DecoderError::RlpInvalidLength),
+	}
+}
+
+/// Decode `rlp` as a string of exactly `len` bytes.
+fn decode_fixed(rlp: &Rlp, len: usize) -> Result<Vec<u8>, DecoderError> {
+	let bytes: Vec<u8> = rlp.as_val()?;
+	if bytes.len() != len {
+		return Err(DecoderError::RlpInvalidLength)
+	}
+	Ok(bytes)
+}
+
+/// Append `value` as big endian bytes without leading zeros.
+fn append_u256(s: &mut RlpStream, value: &U256) {
+	let mut bytes = [0u8; 32];
+	value.to_big_endian(&mut bytes);
+	let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+	s.append(&bytes[start..].to_vec());
+}
+
+/// Decode the item at `index` which was encoded by [`append_u256`].
+fn decode_u256(rlp: &Rlp, index: usize) -> Result<U256, DecoderError> {
+	let bytes: Vec<u8> = rlp.val_at(index)?;
+	if bytes.len() > 32 {
+		return Err(DecoderError::RlpIsTooBig)
+	}
+	if bytes.first() == Some(&0) {
+		return Err(DecoderError::RlpDataLenWithZeroPrefix)
+	}
+	Ok(U256::from_big_endian(&bytes))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use hex_literal::hex;
+	use sp_core::{ecdsa, Pair};
+
+	// The example transaction from EIP-155.
+	const SIGNED: [u8; 110] = hex!("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83");
+
+	#[test]
+	fn decode_eip155_example_works() {
+		let tx = TransactionLegacySigned::decode(&SIGNED).unwrap();
+		let unsigned = &tx.transaction_legacy_unsigned;
+		assert_eq!(unsigned.nonce, 9.into());
+		assert_eq!(unsigned.gas_price, 20_000_000_000u64.into());
+		assert_eq!(unsigned.gas, 21_000.into());
+		assert_eq!(unsigned.to, Some(H160([0x35; 20])));
+		assert_eq!(unsigned.value, 1_000_000_000_000_000_000u64.into());
+		assert!(unsigned.input.is_empty());
+		assert_eq!(unsigned.chain_id, Some(1.into()));
+		assert_eq!(tx.recovery_id(), Some(0));
+		assert_eq!(
+			unsigned.signing_hash(),
+			H256(hex!("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"))
+		);
+		assert_eq!(tx.rlp_bytes(), SIGNED.to_vec());
+	}
+
+	#[test]
+	fn recover_eth_address_works() {
+		let tx = TransactionLegacySigned::decode(&SIGNED).unwrap();
+		assert_eq!(
+			tx.recover_eth_address(),
+			Some(H160(hex!("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f")))
+		);
+	}
+
+	#[test]
+	fn with_signature_roundtrips() {
+		let tx = TransactionLegacySigned::decode(&SIGNED).unwrap();
+		let signature = tx.signature().unwrap();
+		let resigned = tx.transaction_legacy_unsigned.clone().with_signature(signature);
+		assert_eq!(resigned, tx);
+	}
+
+	#[test]
+	fn malformed_transactions_are_rejected() {
+		// trailing bytes
+		let mut payload = SIGNED.to_vec();
+		payload.push(0);
+		assert!(TransactionLegacySigned::decode(&payload).is_err());
+
+		// `v` which neither encodes a chain id nor a plain recovery id
+		let mut tx = TransactionLegacySigned::decode(&SIGNED).unwrap();
+		tx.v = 30.into();
+		assert!(TransactionLegacySigned::decode(&tx.rlp_bytes()).is_err());
+
+		// high `s` values are malleable
+		let mut tx = TransactionLegacySigned::decode(&SIGNED).unwrap();
+		tx.s = SECP256K1N_HALF + 1;
+		assert_eq!(tx.recover_eth_address(), None);
+	}
+
+	fn eip1559_transaction() -> Transaction1559Unsigned {
+		Transaction1559Unsigned {
+			chain_id: 1.into(),
+			nonce: 9.into(),
+			max_priority_fee_per_gas: 1_000_000_000u64.into(),
+			max_fee_per_gas: 20_000_000_000u64.into(),
+			gas: 21_000.into(),
+			to: Some(H160([0x35; 20])),
+			value: 1_000_000_000_000_000_000u64.into(),
+			input: vec![1, 2, 3],
+			access_list: vec![AccessListEntry {
+				address: H160([0x36; 20]),
+				storage_keys: vec![H256([1; 32]), H256([2; 32])],
+			}],
+		}
+	}
+
+	#[test]
+	fn eip1559_transaction_roundtrips() {
+		let pair = ecdsa::Pair::from_seed(&[1; 32]);
+		let unsigned = eip1559_transaction();
+		let tx = unsigned
+			.clone()
+			.with_signature(pair.sign_prehashed(&unsigned.signing_hash().0).0);
+		let payload = tx.rlp_bytes();
+		assert_eq!(payload[0], TYPE_EIP1559);
+
+		let decoded = TransactionSigned::decode(&payload).unwrap();
+		assert_eq!(decoded, TransactionSigned::Transaction1559Signed(tx));
+		assert_eq!(decoded.rlp_bytes(), payload);
+		assert_eq!(decoded.chain_id(), Some(1.into()));
+		assert_eq!(decoded.max_fee_per_gas(), 20_000_000_000u64.into());
+		assert_eq!(decoded.into_input(), vec![1, 2, 3]);
+	}
+
+	#[test]
+	fn eip1559_and_legacy_signers_match() {
+		let pair = ecdsa::Pair::from_seed(&[1; 32]);
+		let unsigned = eip1559_transaction();
+		let tx = unsigned
+			.clone()
+			.with_signature(pair.sign_prehashed(&unsigned.signing_hash().0).0);
+
+		let legacy = TransactionLegacySigned::decode(&SIGNED).unwrap().transaction_legacy_unsigned;
+		let legacy = legacy.clone().with_signature(pair.sign_prehashed(&legacy.signing_hash().0).0);
+
+		assert!(tx.recover_eth_address().is_some());
+		assert_eq!(tx.recover_eth_address(), legacy.recover_eth_address());
+	}
+
+	#[test]
+	fn unsupported_transactions_are_rejected() {
+		// EIP-2930 transactions
+		assert!(TransactionSigned::decode(&[1, 0xc0]).is_err());
+		assert!(TransactionSigned::decode(&[]).is_err());
+
+		// a y parity which is not a recovery id
+		let mut tx = eip1559_transaction().with_signature([1; 65]);
+		tx.y_parity = 2.into();
+		assert!(Transaction1559Signed::decode(&tx.rlp_bytes()).is_err());
+
+		// trailing bytes
+		let mut payload = eip1559_transaction().with_signature([1; 65]).rlp_bytes();
+		payload.push(0);
+		assert!(TransactionSigned::decode(&payload).is_err());
+	}
+}
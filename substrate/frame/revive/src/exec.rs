@@ -0,0 +1,2047 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	address::{self, AddressMapper},
+	gas::GasMeter,
+	limits,
+	primitives::ExecReturnValue,
+	storage::{self, WriteOutcome},
+	vm::CodeInfo,
+	AccountIdOf, BalanceOf, CodeInfoOf, Config, ContractInfo, ContractInfoOf, DebugBuffer, Error,
+	Event, Nonce, Origin, Pallet as Contracts, LOG_TARGET,
+};
+use frame_support::{
+	crypto::ecdsa::ECDSAExt,
+	dispatch::DispatchResult,
+	storage::{with_transaction, TransactionOutcome},
+	traits::{
+		fungible::{Inspect, Mutate},
+		tokens::{Fortitude, Preservation},
+		Time,
+	},
+	weights::Weight,
+	Blake2_128Concat, BoundedVec, StorageHasher,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_core::{ecdsa::Public as ECDSAPublic, ConstU32, H160, H256};
+use sp_io::{crypto::secp256k1_ecdsa_recover_compressed, hashing::blake2_256};
+use sp_runtime::{traits::Zero, DispatchError};
+use sp_std::{fmt::Debug, marker::PhantomData, mem, prelude::*, vec::Vec};
+
+pub type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
+pub type ExecResult = Result<ExecReturnValue, ExecError>;
+
+/// Type for variable sized storage key. Used for transparent hashing.
+type VarSizedKey = BoundedVec<u8, ConstU32<{ limits::STORAGE_KEY_BYTES }>>;
+
+/// Combined key type for both fixed and variable sized storage keys.
+pub enum Key {
+	/// Variant for fixed sized keys.
+	Fix([u8; 32]),
+	/// Variant for variable sized keys.
+	Var(VarSizedKey),
+}
+
+impl Key {
+	/// Copies self into a new vec.
+	pub fn to_vec(&self) -> Vec<u8> {
+		match self {
+			Key::Fix(v) => v.to_vec(),
+			Key::Var(v) => v.to_vec(),
+		}
+	}
+
+	pub fn hash(&self) -> Vec<u8> {
+		match self {
+			Key::Fix(v) => blake2_256(v.as_slice()).to_vec(),
+			Key::Var(v) => Blake2_128Concat::hash(v.as_slice()),
+		}
+	}
+
+	pub fn try_from_fix(v: Vec<u8>) -> Result<Self, Vec<u8>> {
+		<[u8; 32]>::try_from(v).map(Self::Fix)
+	}
+
+	pub fn try_from_var(v: Vec<u8>) -> Result<Self, Vec<u8>> {
+		VarSizedKey::try_from(v).map(Self::Var)
+	}
+}
+
+/// Origin of the error.
+///
+/// Call or instantiate both called into other contracts and pass through errors happening
+/// in those to the caller. This enum is for the caller to distinguish whether the error
+/// happened during the execution of the callee or in the current execution context.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, codec::Decode, codec::Encode)]
+pub enum ErrorOrigin {
+	/// Caller error origin.
+	///
+	/// The error happened in the current execution context rather than in the one
+	/// of the contract that is called into.
+	Caller,
+	/// The error happened during execution of the called contract.
+	Callee,
+}
+
+/// Error returned by contract execution.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, codec::Decode, codec::Encode)]
+pub struct ExecError {
+	/// The reason why the execution failed.
+	pub error: DispatchError,
+	/// Origin of the error.
+	pub origin: ErrorOrigin,
+}
+
+impl<T: Into<DispatchError>> From<T> for ExecError {
+	fn from(error: T) -> Self {
+		Self { error: error.into(), origin: ErrorOrigin::Caller }
+	}
+}
+
+/// An interface that provides access to the external environment in which the
+/// smart-contract is executed.
+///
+/// This interface is specialized to an account of the executing code, so all
+/// operations are implicitly performed on that account.
+///
+/// # Note
+///
+/// This trait is sealed and cannot be implemented by downstream crates.
+pub trait Ext: sealing::Sealed {
+	type T: Config;
+
+	/// Call (possibly transferring some amount of funds) into the specified account.
+	fn call(
+		&mut self,
+		gas_limit: Weight,
+		deposit_limit: BalanceOf<Self::T>,
+		to: &H160,
+		value: BalanceOf<Self::T>,
+		input_data: Vec<u8>,
+		allows_reentry: bool,
+	) -> Result<ExecReturnValue, ExecError>;
+
+	/// Execute code in the current frame.
+	fn delegate_call(
+		&mut self,
+		code: H256,
+		input_data: Vec<u8>,
+	) -> Result<ExecReturnValue, ExecError>;
+
+	/// Instantiate a contract from the given code.
+	///
+	/// The newly created account will be associated with `code`. `value` specifies the amount of
+	/// value transferred from the caller to the newly created account.
+	///
+	/// The address of the new contract is derived using CREATE2 semantics if a `salt` is supplied
+	/// and using CREATE semantics with the nonce of the current contract otherwise.
+	fn instantiate(
+		&mut self,
+		gas_limit: Weight,
+		deposit_limit: BalanceOf<Self::T>,
+		code: H256,
+		value: BalanceOf<Self::T>,
+		input_data: Vec<u8>,
+		salt: Option<&[u8; 32]>,
+	) -> Result<(H160, ExecReturnValue), ExecError>;
+
+	/// Transfer all funds to `beneficiary` and delete the contract.
+	///
+	/// Since this function removes the self contract eagerly, if succeeded, no further actions
+	/// should be performed on this `Ext` instance.
+	///
+	/// This function will fail if the same contract is present on the contract
+	/// call stack.
+	fn terminate(&mut self, beneficiary: &H160) -> Result<(), DispatchError>;
+
+	/// Transfer some amount of funds into the specified account.
+	fn transfer(&mut self, to: &H160, value: BalanceOf<Self::T>) -> DispatchResult;
+
+	/// Returns the storage entry of the executing account by the given `key`.
+	///
+	/// Returns `None` if the `key` wasn't previously set by `set_storage` or
+	/// was deleted.
+	fn get_storage(&mut self, key: &Key) -> Option<Vec<u8>>;
+
+	/// Returns `Some(len)` (in bytes) if a storage item exists at `key`.
+	///
+	/// Returns `None` if the `key` wasn't previously set by `set_storage` or
+	/// was deleted.
+	fn get_storage_size(&mut self, key: &Key) -> Option<u32>;
+
+	/// Sets the storage entry by the given key to the specified value. If `value` is `None` then
+	/// the storage entry is deleted.
+	fn set_storage(
+		&mut self,
+		key: &Key,
+		value: Option<Vec<u8>>,
+		take_old: bool,
+	) -> Result<WriteOutcome, DispatchError>;
+
+	/// Returns the caller.
+	fn caller(&self) -> Origin<Self::T>;
+
+	/// Check if a contract lives at the specified `address`.
+	fn is_contract(&self, address: &H160) -> bool;
+
+	/// Returns the code hash of the contract for the given `address`.
+	///
+	/// Returns `None` if the `address` does not belong to a contract.
+	fn code_hash(&self, address: &H160) -> Option<H256>;
+
+	/// Returns the code hash of the contract being executed.
+	fn own_code_hash(&mut self) -> &H256;
+
+	/// Check if the caller of the current contract is the origin of the whole call stack.
+	///
+	/// This can be checked with `is_contract(self.caller())` as well.
+	/// However, this function does not require any storage lookup and therefore uses less weight.
+	fn caller_is_origin(&self) -> bool;
+
+	/// Check if the caller is origin, and this origin is root.
+	fn caller_is_root(&self) -> bool;
+
+	/// Returns a reference to the account id of the current contract.
+	fn account_id(&self) -> &AccountIdOf<Self::T>;
+
+	/// Returns the [`H160`] address of the current contract.
+	fn address(&self) -> H160 {
+		<Self::T as Config>::AddressMapper::to_address(self.account_id())
+	}
+
+	/// Returns the balance of the current contract.
+	///
+	/// The `value_transferred` is already added.
+	fn balance(&self) -> BalanceOf<Self::T>;
+
+	/// Returns the value transferred along with this call.
+	fn value_transferred(&self) -> BalanceOf<Self::T>;
+
+	/// Returns a reference to the timestamp of the current block
+	fn now(&self) -> &MomentOf<Self::T>;
+
+	/// Returns the minimum balance that is required for creating an account.
+	fn minimum_balance(&self) -> BalanceOf<Self::T>;
+
+	/// Deposit an event with the given topics.
+	///
+	/// There should not be any duplicates in `topics`.
+	fn deposit_event(&mut self, topics: Vec<H256>, data: Vec<u8>);
+
+	/// Returns the current block number.
+	fn block_number(&self) -> BlockNumberFor<Self::T>;
+
+	/// Returns the maximum allowed size of a storage item.
+	fn max_value_size(&self) -> u32;
+
+	/// Get an immutable reference to the nested gas meter.
+	fn gas_meter(&self) -> &GasMeter<Self::T>;
+
+	/// Get a mutable reference to the nested gas meter.
+	fn gas_meter_mut(&mut self) -> &mut GasMeter<Self::T>;
+
+	/// Append a string to the debug buffer.
+	///
+	/// It is added as-is without any additional new line.
+	///
+	/// This is a no-op if debug message recording is disabled which is always the case
+	/// when the code is executing on-chain.
+	///
+	/// Returns `true` if debug message recording is enabled. Otherwise `false` is returned.
+	fn append_debug_buffer(&mut self, msg: &str) -> bool;
+
+	/// Recovers ECDSA compressed public key based on signature and message hash.
+	fn ecdsa_recover(&self, signature: &[u8; 65], message_hash: &[u8; 32]) -> Result<[u8; 33], ()>;
+
+	/// Returns Ethereum address from the ECDSA compressed public key.
+	fn ecdsa_to_eth_address(&self, pk: &[u8; 33]) -> Result<[u8; 20], ()>;
+
+	/// Returns the number of times the currently executing contract exists on the call stack in
+	/// addition to the calling instance. A value of 0 means no reentrancy.
+	fn reentrance_count(&self) -> u32;
+
+	/// Returns the number of times the specified contract exists on the call stack. Delegated calls
+	/// are not calculated as separate entrance.
+	/// A value of 0 means it does not exist on the call stack.
+	fn account_reentrance_count(&self, address: &H160) -> u32;
+
+	/// Returns a nonce that is incremented for every instantiated contract.
+	fn nonce(&mut self) -> u64;
+
+	/// Increment the reference count of a of a stored code by one.
+	///
+	/// # Errors
+	///
+	/// [`Error::CodeNotFound`] is returned if no stored code found having the specified
+	/// `code_hash`.
+	fn increment_refcount(code_hash: H256) -> Result<(), DispatchError>;
+
+	/// Decrement the reference count of a stored code by one.
+	///
+	/// # Note
+	///
+	/// A contract whose reference count dropped to zero isn't automatically removed. A
+	/// `remove_code` transaction must be submitted by the original uploader to do so.
+	fn decrement_refcount(code_hash: H256);
+}
+
+/// Describes the different functions that can be exported by an [`Executable`].
+#[derive(
+	Copy,
+	Clone,
+	PartialEq,
+	Eq,
+	sp_core::RuntimeDebug,
+	codec::Decode,
+	codec::Encode,
+	codec::MaxEncodedLen,
+	scale_info::TypeInfo,
+)]
+pub enum ExportedFunction {
+	/// The constructor function which is executed on deployment of a contract.
+	Constructor,
+	/// The function which is executed when a contract is called.
+	Call,
+}
+
+impl ExportedFunction {
+	/// The name of the export as it appears in the PolkaVM blob.
+	pub fn identifier(&self) -> &str {
+		match self {
+			Self::Constructor => "deploy",
+			Self::Call => "call",
+		}
+	}
+}
+
+/// A trait that represents something that can be executed.
+///
+/// In the on-chain environment this would be represented by a PolkaVM blob. This trait exists in
+/// order to be able to mock the PolkaVM logic for testing.
+pub trait Executable<T: Config>: Sized {
+	/// Load the executable from storage.
+	///
+	/// # Note
+	/// Charges size base load weight from the gas meter.
+	fn from_storage(code_hash: H256, gas_meter: &mut GasMeter<T>) -> Result<Self, DispatchError>;
+
+	/// Execute the specified exported function and return the result.
+	///
+	/// When the specified function is `Constructor` the executable is stored and its
+	/// refcount incremented.
+	///
+	/// # Note
+	///
+	/// This functions expects to be executed in a storage transaction that rolls back
+	/// all of its emitted storage changes.
+	fn execute<E: Ext<T = T>>(
+		self,
+		ext: &mut E,
+		function: &ExportedFunction,
+		input_data: Vec<u8>,
+	) -> ExecResult;
+
+	/// The code info of the executable.
+	fn code_info(&self) -> &CodeInfo<T>;
+
+	/// The raw code of the executable.
+	///
+	/// Used to derive the address of contracts created with CREATE2 semantics.
+	fn code(&self) -> &[u8];
+
+	/// The code hash of the executable.
+	fn code_hash(&self) -> &H256;
+}
+
+/// The complete call stack of a contract execution.
+///
+/// The call stack is initiated by either a signed origin or one of the contract RPC calls.
+/// This type implements `Ext` and by that exposes the business logic of contract execution to
+/// the runtime module which interfaces with the contract (the PolkaVM blob) itself.
+pub struct Stack<'a, T: Config, E> {
+	/// The origin that initiated the call stack. It could either be a Signed plain account that
+	/// holds an account id or Root.
+	///
+	/// # Note
+	///
+	/// Please note that it is possible that the id of a Signed origin belongs to a contract rather
+	/// than a plain account when being called through one of the contract RPCs where the
+	/// client can freely choose the origin. This usually makes no sense but is still possible.
+	origin: Origin<T>,
+	/// The gas meter where costs are charged to.
+	gas_meter: &'a mut GasMeter<T>,
+	/// The storage meter makes sure that the storage deposit limit is obeyed.
+	storage_meter: &'a mut storage::meter::Meter<T>,
+	/// The timestamp at the point of call stack instantiation.
+	timestamp: MomentOf<T>,
+	/// The block number at the time of call stack instantiation.
+	block_number: BlockNumberFor<T>,
+	/// The nonce is cached here when accessed. It is written back when the call stack
+	/// finishes executing. Please refer to [`Nonce`] to a description of
+	/// the nonce itself.
+	nonce: Option<u64>,
+	/// The actual call stack. One entry per nested contract called/instantiated.
+	/// This does **not** include the [`Self::first_frame`].
+	frames: Vec<Frame<T>>,
+	/// Statically guarantee that each call stack has at least one frame.
+	first_frame: Frame<T>,
+	/// A text buffer used to output human readable information.
+	///
+	/// All the bytes added to this field should be valid UTF-8. The buffer has no defined
+	/// structure and is intended to be shown to users as-is for debugging purposes.
+	debug_message: Option<&'a mut DebugBuffer>,
+	/// No executable is held by the struct but influences its behaviour.
+	_phantom: PhantomData<E>,
+}
+
+/// Represents one entry in the call stack.
+///
+/// For each nested contract call or instantiate one frame is created. It holds specific
+/// information for the said call and caches the in-storage `ContractInfo` data structure.
+pub struct Frame<T: Config> {
+	/// The address of the executing contract.
+	address: H160,
+	/// The account id of the executing contract.
+	account_id: T::AccountId,
+	/// The cached in-storage data of the contract.
+	contract_info: CachedContract<T>,
+	/// The amount of balance transferred by the caller as part of the call.
+	value_transferred: BalanceOf<T>,
+	/// Determines whether this is a call or instantiate frame.
+	entry_point: ExportedFunction,
+	/// The gas meter capped to the supplied gas limit.
+	nested_gas: GasMeter<T>,
+	/// The storage meter for the individual call.
+	nested_storage: storage::meter::NestedMeter<T>,
+	/// If `false` the contract enabled its defense against reentrance attacks.
+	allows_reentry: bool,
+	/// The caller of the currently executing frame which was spawned by `delegate_call`.
+	delegate_caller: Option<Origin<T>>,
+}
+
+/// Used in a delegate call frame arguments in order to override the executable and caller.
+struct DelegatedCall<T: Config, E> {
+	/// The executable which is run instead of the contracts own `executable`.
+	executable: E,
+	/// The caller of the contract.
+	caller: Origin<T>,
+}
+
+/// Parameter passed in when creating a new `Frame`.
+///
+/// It determines whether the new frame is for a call or an instantiate.
+enum FrameArgs<'a, T: Config, E> {
+	Call {
+		/// The address of the contract that is to be called.
+		dest: H160,
+		/// If `None` the contract info needs to be reloaded from storage.
+		cached_info: Option<ContractInfo<T>>,
+		/// This frame was created by `delegate_call` and hence uses different code than
+		/// what is stored at [`Self::Call::dest`]. Its caller ([`DelegatedCall::caller`]) is the
+		/// account which called the caller contract
+		delegated_call: Option<DelegatedCall<T, E>>,
+	},
+	Instantiate {
+		/// The contract or signed origin which instantiates the new contract.
+		sender: T::AccountId,
+		/// The nonce of `sender` used to derive the address when no `salt` is supplied.
+		deployer_nonce: u64,
+		/// The nonce that should be used to derive a new trie id for the contract.
+		nonce: u64,
+		/// The executable whose `deploy` function is run.
+		executable: E,
+		/// A salt used in the contract address derivation of the new contract.
+		salt: Option<&'a [u8; 32]>,
+		/// The input data is used in the contract address derivation of the new contract.
+		input_data: &'a [u8],
+	},
+}
+
+/// Describes the different states of a contract as contained in a `Frame`.
+enum CachedContract<T: Config> {
+	/// The cached contract is up to date with the in-storage value.
+	Cached(ContractInfo<T>),
+	/// A recursive call into the same contract did write to the contract info.
+	///
+	/// In this case the cached contract is stale and needs to be reloaded from storage.
+	Invalidated,
+	/// The current contract executed `terminate` and removed the contract.
+	///
+	/// In this case a reload is neither allowed nor possible. Please note that recursive
+	/// calls cannot remove a contract as this is checked and denied.
+	Terminated,
+}
+
+impl<T: Config> CachedContract<T> {
+	/// Return `Some(ContractInfo)` if the contract is in cached state. `None` otherwise.
+	fn into_contract(self) -> Option<ContractInfo<T>> {
+		if let CachedContract::Cached(contract) = self {
+			Some(contract)
+		} else {
+			None
+		}
+	}
+
+	/// Return `Some(&mut ContractInfo)` if the contract is in cached state. `None` otherwise.
+	fn as_contract(&mut self) -> Option<&mut ContractInfo<T>> {
+		if let CachedContract::Cached(contract) = self {
+			Some(contract)
+		} else {
+			None
+		}
+	}
+}
+
+impl<T: Config> Frame<T> {
+	/// Return the `contract_info` of the current contract.
+	fn contract_info(&mut self) -> &mut ContractInfo<T> {
+		self.contract_info.get(&self.address)
+	}
+
+	/// Terminate and return the `contract_info` of the current contract.
+	///
+	/// # Note
+	///
+	/// Under no circumstances the contract is allowed to access the `contract_info` after
+	/// a call to this function. This would constitute a programming error in the exec module.
+	fn terminate(&mut self) -> ContractInfo<T> {
+		self.contract_info.terminate(&self.address)
+	}
+}
+
+/// Extract the contract info after loading it from storage.
+///
+/// This assumes that `load` was executed before calling this macro.
+macro_rules! get_cached_or_panic_after_load {
+	($c:expr) => {{
+		if let CachedContract::Cached(contract) = $c {
+			contract
+		} else {
+			panic!(
+				"It is impossible to remove a contract that is on the call stack;\
+				See implementations of terminate;\
+				Therefore fetching a contract will never fail while using an address
+				that is currently active on the call stack;\
+				qed"
+			);
+		}
+	}};
+}
+
+/// Same as [`Stack::top_frame`].
+///
+/// We need this access as a macro because sometimes hiding the lifetimes behind
+/// a function won't work out.
+macro_rules! top_frame {
+	($stack:expr) => {
+		$stack.frames.last().unwrap_or(&$stack.first_frame)
+	};
+}
+
+/// Same as [`Stack::top_frame_mut`].
+///
+/// We need this access as a macro because sometimes hiding the lifetimes behind
+/// a function won't work out.
+macro_rules! top_frame_mut {
+	($stack:expr) => {
+		$stack.frames.last_mut().unwrap_or(&mut $stack.first_frame)
+	};
+}
+
+impl<T: Config> CachedContract<T> {
+	/// Load the `contract_info` from storage if necessary.
+	fn load(&mut self, address: &H160) {
+		if let CachedContract::Invalidated = self {
+			let contract = <ContractInfoOf<T>>::get(address);
+			if let Some(contract) = contract {
+				*self = CachedContract::Cached(contract);
+			}
+		}
+	}
+
+	/// Return the cached contract_info.
+	fn get(&mut self, address: &H160) -> &mut ContractInfo<T> {
+		self.load(address);
+		get_cached_or_panic_after_load!(self)
+	}
+
+	/// Terminate and return the contract info.
+	fn terminate(&mut self, address: &H160) -> ContractInfo<T> {
+		self.load(address);
+		get_cached_or_panic_after_load!(mem::replace(self, Self::Terminated))
+	}
+}
+
+impl<'a, T, E> Stack<'a, T, E>
+where
+	T: Config,
+	E: Executable<T>,
+{
+	/// Create and run a new call stack by calling into `dest`.
+	///
+	/// # Note
+	///
+	/// `debug_message` should only ever be set to `Some` when executing as an RPC because
+	/// it adds allocations and could be abused to drive the runtime into an OOM panic.
+	pub fn run_call(
+		origin: Origin<T>,
+		dest: H160,
+		gas_meter: &'a mut GasMeter<T>,
+		storage_meter: &'a mut storage::meter::Meter<T>,
+		value: BalanceOf<T>,
+		input_data: Vec<u8>,
+		debug_message: Option<&'a mut DebugBuffer>,
+	) -> Result<ExecReturnValue, ExecError> {
+		let (mut stack, executable) = Self::new(
+			FrameArgs::Call { dest, cached_info: None, delegated_call: None },
+			origin,
+			gas_meter,
+			storage_meter,
+			value,
+			debug_message,
+		)?;
+		stack.run(executable, input_data)
+	}
+
+	/// Create and run a new call stack by instantiating a new contract.
+	///
+	/// # Note
+	///
+	/// `debug_message` should only ever be set to `Some` when executing as an RPC because
+	/// it adds allocations and could be abused to drive the runtime into an OOM panic.
+	///
+	/// The nonce of `origin` was already incremented by the transaction that triggered this
+	/// instantiation. Hence the nonce used to derive the address of the new contract is the one
+	/// before the increment, which is consistent with how Ethereum derives contract addresses.
+	pub fn run_instantiate(
+		origin: T::AccountId,
+		executable: E,
+		gas_meter: &'a mut GasMeter<T>,
+		storage_meter: &'a mut storage::meter::Meter<T>,
+		value: BalanceOf<T>,
+		input_data: Vec<u8>,
+		salt: Option<&[u8; 32]>,
+		debug_message: Option<&'a mut DebugBuffer>,
+	) -> Result<(H160, ExecReturnValue), ExecError> {
+		let deployer_nonce = account_nonce::<T>(&origin).saturating_sub(1);
+		let (mut stack, executable) = Self::new(
+			FrameArgs::Instantiate {
+				sender: origin.clone(),
+				deployer_nonce,
+				nonce: <Nonce<T>>::get().wrapping_add(1),
+				executable,
+				salt,
+				input_data: input_data.as_ref(),
+			},
+			Origin::from_account_id(origin),
+			gas_meter,
+			storage_meter,
+			value,
+			debug_message,
+		)?;
+		let address = stack.top_frame().address;
+		stack.run(executable, input_data).map(|ret| (address, ret))
+	}
+
+	/// Create a call stack for calling into `dest` without executing the contract.
+	///
+	/// This allows the benchmarks to invoke syscalls in the context of a contract.
+	#[cfg(feature = "runtime-benchmarks")]
+	pub fn bench_new_call(
+		dest: H160,
+		origin: Origin<T>,
+		gas_meter: &'a mut GasMeter<T>,
+		storage_meter: &'a mut storage::meter::Meter<T>,
+		value: BalanceOf<T>,
+		debug_message: Option<&'a mut DebugBuffer>,
+	) -> (Self, E) {
+		Self::new(
+			FrameArgs::Call { dest, cached_info: None, delegated_call: None },
+			origin,
+			gas_meter,
+			storage_meter,
+			value,
+			debug_message,
+		)
+		.expect("The contract exists; qed")
+	}
+
+	/// Create a new call stack.
+	fn new(
+		args: FrameArgs<T, E>,
+		origin: Origin<T>,
+		gas_meter: &'a mut GasMeter<T>,
+		storage_meter: &'a mut storage::meter::Meter<T>,
+		value: BalanceOf<T>,
+		debug_message: Option<&'a mut DebugBuffer>,
+	) -> Result<(Self, E), ExecError> {
+		let (first_frame, executable, nonce) = Self::new_frame(
+			args,
+			value,
+			gas_meter,
+			Weight::zero(),
+			storage_meter,
+			BalanceOf::<T>::zero(),
+		)?;
+
+		let stack = Self {
+			origin,
+			gas_meter,
+			storage_meter,
+			timestamp: T::Time::now(),
+			block_number: <frame_system::Pallet<T>>::block_number(),
+			nonce,
+			first_frame,
+			frames: Default::default(),
+			debug_message,
+			_phantom: Default::default(),
+		};
+
+		Ok((stack, executable))
+	}
+
+	/// Construct a new frame.
+	///
+	/// This does not take `self` because when constructing the first frame `self` is
+	/// not initialized, yet.
+	fn new_frame<S: storage::meter::State + Default + Debug>(
+		frame_args: FrameArgs<T, E>,
+		value_transferred: BalanceOf<T>,
+		gas_meter: &mut GasMeter<T>,
+		gas_limit: Weight,
+		storage_meter: &mut storage::meter::GenericMeter<T, S>,
+		deposit_limit: BalanceOf<T>,
+	) -> Result<(Frame<T>, E, Option<u64>), ExecError> {
+		let (address, contract_info, executable, delegate_caller, entry_point, nonce) =
+			match frame_args {
+				FrameArgs::Call { dest, cached_info, delegated_call } => {
+					let contract = if let Some(contract) = cached_info {
+						contract
+					} else {
+						<ContractInfoOf<T>>::get(&dest).ok_or(<Error<T>>::ContractNotFound)?
+					};
+
+					let (executable, delegate_caller) =
+						if let Some(DelegatedCall { executable, caller }) = delegated_call {
+							(executable, Some(caller))
+						} else {
+							(E::from_storage(contract.code_hash, gas_meter)?, None)
+						};
+
+					(dest, contract, executable, delegate_caller, ExportedFunction::Call, None)
+				},
+				FrameArgs::Instantiate {
+					sender,
+					deployer_nonce,
+					nonce,
+					executable,
+					salt,
+					input_data,
+				} => {
+					let deployer = T::AddressMapper::to_address(&sender);
+					let address = if let Some(salt) = salt {
+						address::create2(&deployer, executable.code(), input_data, salt)
+					} else {
+						address::create1(&deployer, deployer_nonce)
+					};
+					let contract = ContractInfo::new(&address, nonce, *executable.code_hash())?;
+					(
+						address,
+						contract,
+						executable,
+						None,
+						ExportedFunction::Constructor,
+						Some(nonce),
+					)
+				},
+			};
+
+		let frame = Frame {
+			delegate_caller,
+			value_transferred,
+			contract_info: CachedContract::Cached(contract_info),
+			account_id: T::AddressMapper::to_fallback_account_id(&address),
+			address,
+			entry_point,
+			nested_gas: gas_meter.nested(gas_limit),
+			nested_storage: storage_meter.nested(deposit_limit),
+			allows_reentry: true,
+		};
+
+		Ok((frame, executable, nonce))
+	}
+
+	/// Create a subsequent nested frame.
+	fn push_frame(
+		&mut self,
+		frame_args: FrameArgs<T, E>,
+		value_transferred: BalanceOf<T>,
+		gas_limit: Weight,
+		deposit_limit: BalanceOf<T>,
+	) -> Result<E, ExecError> {
+		if self.frames.len() as u32 == limits::CALL_STACK_DEPTH {
+			return Err(Error::<T>::MaxCallDepthReached.into())
+		}
+
+		// We need to make sure that changes made to the contract info are not discarded.
+		// We do not store on instantiate because we do not allow to call into a contract
+		// from its own constructor.
+		let frame = self.top_frame();
+		if let (CachedContract::Cached(contract), ExportedFunction::Call) =
+			(&frame.contract_info, frame.entry_point)
+		{
+			<ContractInfoOf<T>>::insert(frame.address, contract.clone());
+		}
+
+		let frame = top_frame_mut!(self);
+		let nested_gas = &mut frame.nested_gas;
+		let nested_storage = &mut frame.nested_storage;
+		let (frame, executable, _) = Self::new_frame(
+			frame_args,
+			value_transferred,
+			nested_gas,
+			gas_limit,
+			nested_storage,
+			deposit_limit,
+		)?;
+		self.frames.push(frame);
+		Ok(executable)
+	}
+
+	/// Run the current (top) frame.
+	///
+	/// This can be either a call or an instantiate.
+	fn run(&mut self, executable: E, input_data: Vec<u8>) -> Result<ExecReturnValue, ExecError> {
+		let frame = self.top_frame();
+		let entry_point = frame.entry_point;
+		let delegated_code_hash =
+			if frame.delegate_caller.is_some() { Some(*executable.code_hash()) } else { None };
+		let do_transaction = || {
+			// We need to charge the storage deposit before the initial transfer so that
+			// it can create the account in case the initial transfer is < ed.
+			if entry_point == ExportedFunction::Constructor {
+				// Root origin can't be used to instantiate a contract, so it is safe to assume that
+				// if we reached this point the origin has an associated account.
+				let origin = &self.origin.account_id()?;
+				let frame = top_frame_mut!(self);
+				frame.nested_storage.charge_instantiate(
+					origin,
+					&frame.account_id,
+					frame.contract_info.get(&frame.address),
+					executable.code_info(),
+				)?;
+			}
+
+			// Every non delegate call or instantiate also optionally transfers the balance.
+			self.initial_transfer()?;
+
+			let output = executable
+				.execute(self, &entry_point, input_data)
+				.map_err(|e| ExecError { error: e.error, origin: ErrorOrigin::Callee })?;
+
+			// Avoid useless work that would be reverted anyways.
+			if output.did_revert() {
+				return Ok(output)
+			}
+
+			// Storage limit is normally enforced as late as possible (when the last frame returns)
+			// so that the ordering of storage accesses does not matter.
+			// (However, if a special limit was set for a sub-call, it should be enforced right
+			// after the sub-call returned. See below for this case of enforcement).
+			if self.frames.is_empty() {
+				let frame = &mut self.first_frame;
+				frame.contract_info.load(&frame.address);
+				let contract = frame.contract_info.as_contract();
+				frame.nested_storage.enforce_limit(contract)?;
+			}
+
+			let frame = self.top_frame();
+			let address = frame.address;
+			match (entry_point, delegated_code_hash) {
+				(ExportedFunction::Constructor, _) => {
+					// It is not allowed to terminate a contract inside its constructor.
+					if matches!(frame.contract_info, CachedContract::Terminated) {
+						return Err(Error::<T>::TerminatedInConstructor.into())
+					}
+
+					// If a special limit was set for the sub-call, we enforce it here.
+					// This is needed because contract constructor might write to storage.
+					// The sub-call will be rolled back in case the limit is exhausted.
+					let frame = self.top_frame_mut();
+					let contract = frame.contract_info.as_contract();
+					frame.nested_storage.enforce_subcall_limit(contract)?;
+
+					let caller = T::AddressMapper::to_address(self.caller().account_id()?);
+
+					// Deposit an instantiation event.
+					Contracts::<T>::deposit_event(Event::Instantiated {
+						deployer: caller,
+						contract: address,
+					});
+				},
+				(ExportedFunction::Call, Some(code_hash)) => {
+					Contracts::<T>::deposit_event(Event::DelegateCalled {
+						contract: address,
+						code_hash,
+					});
+				},
+				(ExportedFunction::Call, None) => {
+					// If a special limit was set for the sub-call, we enforce it here.
+					// The sub-call will be rolled back in case the limit is exhausted.
+					let frame = self.top_frame_mut();
+					let contract = frame.contract_info.as_contract();
+					frame.nested_storage.enforce_subcall_limit(contract)?;
+
+					let caller = self.caller();
+					Contracts::<T>::deposit_event(Event::Called { caller, contract: address });
+				},
+			}
+
+			Ok(output)
+		};
+
+		// All changes performed by the contract are executed under a storage transaction.
+		// This allows for roll back on error. Changes to the cached contract_info are
+		// committed or rolled back when popping the frame.
+		//
+		// `with_transactional` may return an error caused by a limit in the
+		// transactional storage depth.
+		let transaction_outcome =
+			with_transaction(|| -> TransactionOutcome<Result<_, DispatchError>> {
+				let output = do_transaction();
+				match &output {
+					Ok(result) if !result.did_revert() =>
+						TransactionOutcome::Commit(Ok((true, output))),
+					_ => TransactionOutcome::Rollback(Ok((false, output))),
+				}
+			});
+
+		let (success, output) = match transaction_outcome {
+			// `with_transactional` executed successfully, and we have the expected output.
+			Ok((success, output)) => (success, output),
+			// `with_transactional` returned an error, and we propagate that error and note no state
+			// has changed.
+			Err(error) => (false, Err(error.into())),
+		};
+
+		self.pop_frame(success);
+		output
+	}
+
+	/// Remove the current (top) frame from the stack.
+	///
+	/// This is called after running the current frame. It commits cached values to storage
+	/// and invalidates all stale references to it that might exist further down the call stack.
+	fn pop_frame(&mut self, persist: bool) {
+		// Revert changes to the nonce in case of a failed instantiation.
+		if !persist && self.top_frame().entry_point == ExportedFunction::Constructor {
+			self.nonce.as_mut().map(|c| *c = c.wrapping_sub(1));
+		}
+
+		// Pop the current frame from the stack and return it in case it needs to interact
+		// with duplicates that might exist on the stack.
+		// A `None` means that we are returning from the `first_frame`.
+		let frame = self.frames.pop();
+
+		// Both branches do essentially the same with the exception. The difference is that
+		// the else branch does consume the hardcoded `first_frame`.
+		if let Some(mut frame) = frame {
+			let address = frame.address;
+			let prev = top_frame_mut!(self);
+
+			prev.nested_gas.absorb_nested(frame.nested_gas);
+
+			// Only gas counter changes are persisted in case of a failure.
+			if !persist {
+				return
+			}
+
+			// Record the storage meter changes of the nested call into the parent meter.
+			// If the dropped frame's contract wasn't terminated we update the deposit counter
+			// in its contract info. The load is necessary to pull it from storage in case
+			// it was invalidated.
+			frame.contract_info.load(&address);
+			let mut contract = frame.contract_info.into_contract();
+			prev.nested_storage
+				.absorb(frame.nested_storage, &frame.account_id, contract.as_mut());
+
+			// In case the contract wasn't terminated we need to persist changes made to it.
+			if let Some(contract) = contract {
+				// optimization: Predecessor is the same contract.
+				// We can just copy the contract into the predecessor without a storage write.
+				// This is possible when there is no other contract in-between that could
+				// trigger a rollback.
+				if prev.address == address {
+					prev.contract_info = CachedContract::Cached(contract);
+					return
+				}
+
+				// Predecessor is a different contract: We persist the info and invalidate the first
+				// stale cache we find. This triggers a reload from storage on next use. We skip(1)
+				// because that case is already handled by the optimization above. Only the first
+				// cache needs to be invalidated because that one will invalidate the next cache
+				// when it is popped from the stack.
+				<ContractInfoOf<T>>::insert(address, contract);
+				if let Some(c) = self.frames_mut().skip(1).find(|f| f.address == address) {
+					c.contract_info = CachedContract::Invalidated;
+				}
+			}
+		} else {
+			if let Some((msg, false)) = self.debug_message.as_ref().map(|m| (m, m.is_empty())) {
+				log::debug!(
+					target: LOG_TARGET,
+					"Execution finished with debug buffer: {}",
+					core::str::from_utf8(msg).unwrap_or("<Invalid UTF8>"),
+				);
+			}
+			self.gas_meter.absorb_nested(mem::take(&mut self.first_frame.nested_gas));
+			if !persist {
+				return
+			}
+			let mut contract = self.first_frame.contract_info.as_contract();
+			self.storage_meter.absorb(
+				mem::take(&mut self.first_frame.nested_storage),
+				&self.first_frame.account_id,
+				contract.as_deref_mut(),
+			);
+			if let Some(contract) = contract {
+				<ContractInfoOf<T>>::insert(self.first_frame.address, contract);
+			}
+			if let Some(nonce) = self.nonce {
+				<Nonce<T>>::set(nonce);
+			}
+		}
+	}
+
+	/// Transfer some funds from `from` to `to`.
+	fn transfer(
+		preservation: Preservation,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		value: BalanceOf<T>,
+	) -> DispatchResult {
+		if !value.is_zero() && from != to {
+			T::Currency::transfer(from, to, value, preservation)
+				.map_err(|_| Error::<T>::TransferFailed)?;
+		}
+		Ok(())
+	}
+
+	// The transfer as performed by a call or instantiate.
+	fn initial_transfer(&self) -> DispatchResult {
+		let frame = self.top_frame();
+
+		// If it is a delegate call, then we've already transferred tokens in the
+		// last non-delegate frame.
+		if frame.delegate_caller.is_some() {
+			return Ok(())
+		}
+
+		let value = frame.value_transferred;
+
+		// Get the account id from the caller.
+		// If the caller is root there is no account to transfer from, and therefore we can't take
+		// any `value` other than 0.
+		let caller = match self.caller() {
+			Origin::Signed(caller) => caller,
+			Origin::Root if value.is_zero() => return Ok(()),
+			Origin::Root => return DispatchError::RootNotAllowed.into(),
+		};
+		Self::transfer(Preservation::Preserve, &caller, &frame.account_id, value)
+	}
+
+	/// Reference to the current (top) frame.
+	fn top_frame(&self) -> &Frame<T> {
+		top_frame!(self)
+	}
+
+	/// Mutable reference to the current (top) frame.
+	fn top_frame_mut(&mut self) -> &mut Frame<T> {
+		top_frame_mut!(self)
+	}
+
+	/// Iterator over all frames.
+	///
+	/// The iterator starts with the top frame and ends with the root frame.
+	fn frames(&self) -> impl Iterator<Item = &Frame<T>> {
+		sp_std::iter::once(&self.first_frame).chain(&self.frames).rev()
+	}
+
+	/// Same as `frames` but with a mutable reference as iterator item.
+	fn frames_mut(&mut self) -> impl Iterator<Item = &mut Frame<T>> {
+		sp_std::iter::once(&mut self.first_frame).chain(&mut self.frames).rev()
+	}
+
+	/// Returns whether the current contract is on the stack multiple times.
+	fn is_recursive(&self) -> bool {
+		let address = &self.top_frame().address;
+		self.frames().skip(1).any(|f| &f.address == address)
+	}
+
+	/// Returns whether the specified contract allows to be reentered right now.
+	fn allows_reentry(&self, address: &H160) -> bool {
+		!self.frames().any(|f| &f.address == address && !f.allows_reentry)
+	}
+
+	/// Increments and returns the next nonce. Pulls it from storage if it isn't in cache.
+	fn next_nonce(&mut self) -> u64 {
+		let next = self.nonce().wrapping_add(1);
+		self.nonce = Some(next);
+		next
+	}
+}
+
+impl<'a, T, E> Ext for Stack<'a, T, E>
+where
+	T: Config,
+	E: Executable<T>,
+{
+	type T = T;
+
+	fn call(
+		&mut self,
+		gas_limit: Weight,
+		deposit_limit: BalanceOf<T>,
+		to: &H160,
+		value: BalanceOf<T>,
+		input_data: Vec<u8>,
+		allows_reentry: bool,
+	) -> Result<ExecReturnValue, ExecError> {
+		// Before pushing the new frame: Protect the caller contract against reentrancy attacks.
+		// It is important to do this before calling `allows_reentry` so that a direct recursion
+		// is caught by it.
+		self.top_frame_mut().allows_reentry = allows_reentry;
+
+		let try_call = || {
+			if !self.allows_reentry(to) {
+				return Err(<Error<T>>::ReentranceDenied.into())
+			}
+			// We ignore instantiate frames in our search for a cached contract.
+			// Otherwise it would be possible to recursively call a contract from its own
+			// constructor: We disallow calling not fully constructed contracts.
+			let cached_info = self
+				.frames()
+				.find(|f| f.entry_point == ExportedFunction::Call && &f.address == to)
+				.and_then(|f| match &f.contract_info {
+					CachedContract::Cached(contract) => Some(contract.clone()),
+					_ => None,
+				});
+			let executable = self.push_frame(
+				FrameArgs::Call { dest: *to, cached_info, delegated_call: None },
+				value,
+				gas_limit,
+				deposit_limit,
+			)?;
+			self.run(executable, input_data)
+		};
+
+		// We need to make sure to reset `allows_reentry` even on failure.
+		let result = try_call();
+
+		// Protection is on a per call basis.
+		self.top_frame_mut().allows_reentry = true;
+
+		result
+	}
+
+	fn delegate_call(
+		&mut self,
+		code_hash: H256,
+		input_data: Vec<u8>,
+	) -> Result<ExecReturnValue, ExecError> {
+		let executable = E::from_storage(code_hash, self.gas_meter_mut())?;
+		let top_frame = self.top_frame_mut();
+		let contract_info = top_frame.contract_info().clone();
+		let address = top_frame.address;
+		let value = top_frame.value_transferred;
+		let executable = self.push_frame(
+			FrameArgs::Call {
+				dest: address,
+				cached_info: Some(contract_info),
+				delegated_call: Some(DelegatedCall { executable, caller: self.caller().clone() }),
+			},
+			value,
+			Weight::zero(),
+			BalanceOf::<T>::zero(),
+		)?;
+		self.run(executable, input_data)
+	}
+
+	fn instantiate(
+		&mut self,
+		gas_limit: Weight,
+		deposit_limit: BalanceOf<Self::T>,
+		code_hash: H256,
+		value: BalanceOf<T>,
+		input_data: Vec<u8>,
+		salt: Option<&[u8; 32]>,
+	) -> Result<(H160, ExecReturnValue), ExecError> {
+		let executable = E::from_storage(code_hash, self.gas_meter_mut())?;
+		let nonce = self.next_nonce();
+		let sender = self.top_frame().account_id.clone();
+		// Like on Ethereum every instantiation increments the nonce of the deploying contract.
+		// The increment is rolled back together with the rest of the frame on failure.
+		let deployer_nonce = account_nonce::<T>(&sender);
+		frame_system::Pallet::<T>::inc_account_nonce(&sender);
+		let executable = self.push_frame(
+			FrameArgs::Instantiate {
+				sender,
+				deployer_nonce,
+				nonce,
+				executable,
+				salt,
+				input_data: input_data.as_ref(),
+			},
+			value,
+			gas_limit,
+			deposit_limit,
+		)?;
+		let address = self.top_frame().address;
+		self.run(executable, input_data).map(|ret| (address, ret))
+	}
+
+	fn terminate(&mut self, beneficiary: &H160) -> Result<(), DispatchError> {
+		if self.is_recursive() {
+			return Err(Error::<T>::TerminatedWhileReentrant.into())
+		}
+		let frame = self.top_frame_mut();
+		let info = frame.terminate();
+		let beneficiary_account = T::AddressMapper::to_account_id(beneficiary);
+		frame.nested_storage.terminate(&info, beneficiary_account);
+
+		info.queue_trie_for_deletion();
+		ContractInfoOf::<T>::remove(&frame.address);
+		Self::decrement_refcount(info.code_hash);
+
+		Contracts::<T>::deposit_event(Event::Terminated {
+			contract: frame.address,
+			beneficiary: *beneficiary,
+		});
+		Ok(())
+	}
+
+	fn transfer(&mut self, to: &H160, value: BalanceOf<T>) -> DispatchResult {
+		Self::transfer(
+			Preservation::Preserve,
+			&self.top_frame().account_id,
+			&T::AddressMapper::to_account_id(to),
+			value,
+		)
+	}
+
+	fn get_storage(&mut self, key: &Key) -> Option<Vec<u8>> {
+		self.top_frame_mut().contract_info().read(key)
+	}
+
+	fn get_storage_size(&mut self, key: &Key) -> Option<u32> {
+		self.top_frame_mut().contract_info().size(key)
+	}
+
+	fn set_storage(
+		&mut self,
+		key: &Key,
+		value: Option<Vec<u8>>,
+		take_old: bool,
+	) -> Result<WriteOutcome, DispatchError> {
+		let frame = self.top_frame_mut();
+		frame.contract_info.get(&frame.address).write(
+			key,
+			value,
+			Some(&mut frame.nested_storage),
+			take_old,
+		)
+	}
+
+	fn account_id(&self) -> &T::AccountId {
+		&self.top_frame().account_id
+	}
+
+	fn address(&self) -> H160 {
+		self.top_frame().address
+	}
+
+	fn caller(&self) -> Origin<T> {
+		if let Some(caller) = &self.top_frame().delegate_caller {
+			caller.clone()
+		} else {
+			self.frames()
+				.nth(1)
+				.map(|f| Origin::from_account_id(f.account_id.clone()))
+				.unwrap_or(self.origin.clone())
+		}
+	}
+
+	fn is_contract(&self, address: &H160) -> bool {
+		ContractInfoOf::<T>::contains_key(address)
+	}
+
+	fn code_hash(&self, address: &H160) -> Option<H256> {
+		<ContractInfoOf<T>>::get(address).map(|contract| contract.code_hash)
+	}
+
+	fn own_code_hash(&mut self) -> &H256 {
+		&self.top_frame_mut().contract_info().code_hash
+	}
+
+	fn caller_is_origin(&self) -> bool {
+		self.origin == self.caller()
+	}
+
+	fn caller_is_root(&self) -> bool {
+		// if the caller isn't origin, then it can't be root.
+		self.caller_is_origin() && self.origin == Origin::Root
+	}
+
+	fn balance(&self) -> BalanceOf<T> {
+		T::Currency::reducible_balance(
+			&self.top_frame().account_id,
+			Preservation::Preserve,
+			Fortitude::Polite,
+		)
+	}
+
+	fn value_transferred(&self) -> BalanceOf<T> {
+		self.top_frame().value_transferred
+	}
+
+	fn now(&self) -> &MomentOf<T> {
+		&self.timestamp
+	}
+
+	fn minimum_balance(&self) -> BalanceOf<T> {
+		T::Currency::minimum_balance()
+	}
+
+	fn deposit_event(&mut self, topics: Vec<H256>, data: Vec<u8>) {
+		Contracts::<Self::T>::deposit_event(Event::ContractEmitted {
+			contract: self.top_frame().address,
+			data,
+			topics,
+		});
+	}
+
+	fn block_number(&self) -> BlockNumberFor<T> {
+		self.block_number
+	}
+
+	fn max_value_size(&self) -> u32 {
+		limits::PAYLOAD_BYTES
+	}
+
+	fn gas_meter(&self) -> &GasMeter<Self::T> {
+		&self.top_frame().nested_gas
+	}
+
+	fn gas_meter_mut(&mut self) -> &mut GasMeter<Self::T> {
+		&mut self.top_frame_mut().nested_gas
+	}
+
+	fn append_debug_buffer(&mut self, msg: &str) -> bool {
+		if let Some(buffer) = &mut self.debug_message {
+			buffer
+				.try_extend(&mut msg.bytes())
+				.map_err(|_| {
+					log::debug!(
+						target: LOG_TARGET,
+						"Debug buffer (of {} bytes) exhausted!",
+						limits::DEBUG_BUFFER_BYTES,
+					)
+				})
+				.ok();
+			true
+		} else {
+			false
+		}
+	}
+
+	fn ecdsa_recover(&self, signature: &[u8; 65], message_hash: &[u8; 32]) -> Result<[u8; 33], ()> {
+		secp256k1_ecdsa_recover_compressed(signature, message_hash).map_err(|_| ())
+	}
+
+	fn ecdsa_to_eth_address(&self, pk: &[u8; 33]) -> Result<[u8; 20], ()> {
+		ECDSAPublic(*pk).to_eth_address()
+	}
+
+	fn reentrance_count(&self) -> u32 {
+		let address = self.top_frame().address;
+		self.account_reentrance_count(&address).saturating_sub(1)
+	}
+
+	fn account_reentrance_count(&self, address: &H160) -> u32 {
+		self.frames()
+			.filter(|f| f.delegate_caller.is_none() && &f.address == address)
+			.count() as u32
+	}
+
+	fn nonce(&mut self) -> u64 {
+		if let Some(current) = self.nonce {
+			current
+		} else {
+			let current = <Nonce<T>>::get();
+			self.nonce = Some(current);
+			current
+		}
+	}
+
+	fn increment_refcount(code_hash: H256) -> Result<(), DispatchError> {
+		<CodeInfoOf<Self::T>>::mutate(code_hash, |existing| -> Result<(), DispatchError> {
+			if let Some(info) = existing {
+				*info.refcount_mut() = info.refcount().saturating_add(1);
+				Ok(())
+			} else {
+				Err(Error::<T>::CodeNotFound.into())
+			}
+		})
+	}
+
+	fn decrement_refcount(code_hash: H256) {
+		<CodeInfoOf<T>>::mutate(code_hash, |existing| {
+			if let Some(info) = existing {
+				*info.refcount_mut() = info.refcount().saturating_sub(1);
+			}
+		});
+	}
+}
+
+/// The current nonce of `account` as a `u64`.
+fn account_nonce<T: Config>(account: &T::AccountId) -> u64 {
+	use sp_runtime::SaturatedConversion;
+	frame_system::Pallet::<T>::account_nonce(account).saturated_into()
+}
+
+mod sealing {
+	use super::*;
+
+	pub trait Sealed {}
+
+	impl<'a, T: Config, E> Sealed for Stack<'a, T, E> {}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		exec::ExportedFunction::*,
+		gas::GasMeter,
+		tests::{
+			test_utils::{get_balance, place_contract, set_balance},
+			ExtBuilder, RuntimeEvent as MetaEvent, Test, ALICE, ALICE_ADDR, BOB, BOB_ADDR,
+			CHARLIE_ADDR, GAS_LIMIT,
+		},
+		Error,
+	};
+	use assert_matches::assert_matches;
+	use frame_support::{assert_err, assert_ok, parameter_types};
+	use pallet_contracts_uapi::ReturnFlags;
+	use pretty_assertions::assert_eq;
+	use std::{collections::hash_map::HashMap, rc::Rc};
+
+	type System = frame_system::Pallet<Test>;
+
+	type MockStack<'a> = Stack<'a, Test, MockExecutable>;
+
+	parameter_types! {
+		static Loader: MockLoader = MockLoader::default();
+	}
+
+	fn events() -> Vec<Event<Test>> {
+		System::events()
+			.into_iter()
+			.filter_map(|meta| match meta.event {
+				MetaEvent::Revive(contract_event) => Some(contract_event),
+				_ => None,
+			})
+			.collect()
+	}
+
+	struct MockCtx<'a> {
+		ext: &'a mut MockStack<'a>,
+		input_data: Vec<u8>,
+	}
+
+	#[derive(Clone)]
+	struct MockExecutable {
+		func: Rc<dyn for<'a> Fn(MockCtx<'a>, &Self) -> ExecResult + 'static>,
+		func_type: ExportedFunction,
+		code_hash: H256,
+		code_info: CodeInfo<Test>,
+	}
+
+	#[derive(Default, Clone)]
+	pub struct MockLoader {
+		map: HashMap<H256, MockExecutable>,
+		counter: u64,
+	}
+
+	impl MockLoader {
+		fn code_hashes() -> Vec<H256> {
+			Loader::get().map.keys().copied().collect()
+		}
+
+		fn insert(
+			func_type: ExportedFunction,
+			f: impl Fn(MockCtx, &MockExecutable) -> ExecResult + 'static,
+		) -> H256 {
+			Loader::mutate(|loader| {
+				// Generate code hashes as monotonically increasing values.
+				let hash = H256::from_low_u64_be(loader.counter);
+				loader.counter += 1;
+				loader.map.insert(
+					hash,
+					MockExecutable {
+						func: Rc::new(f),
+						func_type,
+						code_hash: hash,
+						code_info: CodeInfo::<Test>::new(ALICE),
+					},
+				);
+				hash
+			})
+		}
+	}
+
+	impl Executable<Test> for MockExecutable {
+		fn from_storage(
+			code_hash: H256,
+			_gas_meter: &mut GasMeter<Test>,
+		) -> Result<Self, DispatchError> {
+			Loader::mutate(|loader| {
+				loader.map.get(&code_hash).cloned().ok_or(Error::<Test>::CodeNotFound.into())
+			})
+		}
+
+		fn execute<E: Ext<T = Test>>(
+			self,
+			ext: &mut E,
+			function: &ExportedFunction,
+			input_data: Vec<u8>,
+		) -> ExecResult {
+			if let &Constructor = function {
+				E::increment_refcount(self.code_hash).unwrap();
+			}
+			// # Safety
+			//
+			// We know that we **always** call execute with a `MockStack` in this test.
+			//
+			// # Note
+			//
+			// The transmute is necessary because `execute` has to be generic over all
+			// `E: Ext`. However, `MockExecutable` can't be generic over `E` as it would
+			// constitute a cycle.
+			let ext = unsafe { mem::transmute(ext) };
+			if function == &self.func_type {
+				(self.func)(MockCtx { ext, input_data }, &self)
+			} else {
+				exec_success()
+			}
+		}
+
+		fn code(&self) -> &[u8] {
+			self.code_hash.as_bytes()
+		}
+
+		fn code_hash(&self) -> &H256 {
+			&self.code_hash
+		}
+
+		fn code_info(&self) -> &CodeInfo<Test> {
+			&self.code_info
+		}
+	}
+
+	fn exec_success() -> ExecResult {
+		Ok(ExecReturnValue { flags: ReturnFlags::empty(), data: Vec::new() })
+	}
+
+	fn exec_trapped() -> ExecResult {
+		Err(ExecError { error: <Error<Test>>::ContractTrapped.into(), origin: ErrorOrigin::Callee })
+	}
+
+	fn run_call(dest: H160, value: u64, input_data: Vec<u8>) -> ExecResult {
+		let origin = Origin::from_account_id(ALICE);
+		let mut storage_meter = storage::meter::Meter::new(&origin, Some(0), value).unwrap();
+		MockStack::run_call(
+			origin,
+			dest,
+			&mut GasMeter::<Test>::new(GAS_LIMIT),
+			&mut storage_meter,
+			value,
+			input_data,
+			None,
+		)
+	}
+
+	#[test]
+	fn it_works() {
+		parameter_types! {
+			static TestData: Vec<usize> = vec![0];
+		}
+
+		let exec_ch = MockLoader::insert(Call, |_ctx, _executable| {
+			TestData::mutate(|data| data.push(1));
+			exec_success()
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			place_contract(&BOB_ADDR, exec_ch);
+			assert_matches!(run_call(BOB_ADDR, 0, vec![]), Ok(_));
+		});
+
+		assert_eq!(TestData::get(), vec![0, 1]);
+	}
+
+	#[test]
+	fn transfer_works() {
+		// This test verifies that a contract is able to transfer
+		// some funds to another account.
+		ExtBuilder::default().build().execute_with(|| {
+			set_balance(&ALICE, 100);
+			set_balance(&BOB, 0);
+
+			MockStack::transfer(Preservation::Preserve, &ALICE, &BOB, 55).unwrap();
+
+			assert_eq!(get_balance(&ALICE), 45);
+			assert_eq!(get_balance(&BOB), 55);
+		});
+	}
+
+	#[test]
+	fn changes_are_reverted_on_failing_call() {
+		// This test verifies that changes are reverted on a call which fails (or equally, returns
+		// a non-zero status code).
+		let return_ch = MockLoader::insert(Call, |_, _| {
+			Ok(ExecReturnValue { flags: ReturnFlags::REVERT, data: Vec::new() })
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			place_contract(&BOB_ADDR, return_ch);
+			set_balance(&ALICE, 100);
+			let balance = get_balance(&BOB);
+
+			let output = run_call(BOB_ADDR, 55, vec![]).unwrap();
+
+			assert!(output.did_revert());
+			assert_eq!(get_balance(&ALICE), 100);
+			assert_eq!(get_balance(&BOB), balance);
+		});
+	}
+
+	#[test]
+	fn balance_too_low() {
+		// This test verifies that a contract can't send value if it's
+		// balance is too low.
+		ExtBuilder::default().build().execute_with(|| {
+			set_balance(&ALICE, 0);
+
+			let result = MockStack::transfer(Preservation::Preserve, &ALICE, &BOB, 100);
+
+			assert_eq!(result, Err(Error::<Test>::TransferFailed.into()));
+			assert_eq!(get_balance(&ALICE), 0);
+			assert_eq!(get_balance(&BOB), 0);
+		});
+	}
+
+	#[test]
+	fn output_is_returned_on_success() {
+		// Verifies that if a contract returns data with a successful exit status, this data
+		// is returned from the execution context.
+		let return_ch = MockLoader::insert(Call, |_, _| {
+			Ok(ExecReturnValue { flags: ReturnFlags::empty(), data: vec![1, 2, 3, 4] })
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			place_contract(&BOB_ADDR, return_ch);
+
+			let output = run_call(BOB_ADDR, 0, vec![]).unwrap();
+			assert!(!output.did_revert());
+			assert_eq!(output.data, vec![1, 2, 3, 4]);
+		});
+	}
+
+	#[test]
+	fn input_data_to_call() {
+		let input_data_ch = MockLoader::insert(Call, |ctx, _| {
+			assert_eq!(ctx.input_data, &[1, 2, 3, 4]);
+			exec_success()
+		});
+
+		// This one tests passing the input data into a contract via call.
+		ExtBuilder::default().build().execute_with(|| {
+			place_contract(&BOB_ADDR, input_data_ch);
+			assert_matches!(run_call(BOB_ADDR, 0, vec![1, 2, 3, 4]), Ok(_));
+		});
+	}
+
+	#[test]
+	fn max_depth() {
+		// This test verifies that when we reach the maximal depth creation of an
+		// yet another context fails.
+		parameter_types! {
+			static ReachedBottom: bool = false;
+		}
+		let recurse_ch = MockLoader::insert(Call, |ctx, _| {
+			// Try to call into yourself.
+			let r = ctx.ext.call(Weight::zero(), 0, &BOB_ADDR, 0, vec![], true);
+
+			ReachedBottom::mutate(|reached_bottom| {
+				if !*reached_bottom {
+					// We are first time here, it means we just reached bottom.
+					// Verify that we've got proper error and set `reached_bottom`.
+					assert_eq!(r, Err(Error::<Test>::MaxCallDepthReached.into()));
+					*reached_bottom = true;
+				} else {
+					// We just unwinding stack here.
+					assert_matches!(r, Ok(_));
+				}
+			});
+
+			exec_success()
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			set_balance(&BOB, 1);
+			place_contract(&BOB_ADDR, recurse_ch);
+			assert_matches!(run_call(BOB_ADDR, 0, vec![]), Ok(_));
+		});
+	}
+
+	#[test]
+	fn caller_returns_proper_values() {
+		parameter_types! {
+			static WitnessedCallerBob: Option<AccountIdOf<Test>> = None;
+			static WitnessedCallerCharlie: Option<AccountIdOf<Test>> = None;
+		}
+
+		let bob_ch = MockLoader::insert(Call, |ctx, _| {
+			// Record the caller for bob.
+			WitnessedCallerBob::mutate(|caller| {
+				*caller = Some(ctx.ext.caller().account_id().unwrap().clone())
+			});
+
+			// Call into CHARLIE contract.
+			assert_matches!(ctx.ext.call(Weight::zero(), 0, &CHARLIE_ADDR, 0, vec![], true), Ok(_));
+			exec_success()
+		});
+		let charlie_ch = MockLoader::insert(Call, |ctx, _| {
+			// Record the caller for charlie.
+			WitnessedCallerCharlie::mutate(|caller| {
+				*caller = Some(ctx.ext.caller().account_id().unwrap().clone())
+			});
+			exec_success()
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			place_contract(&BOB_ADDR, bob_ch);
+			place_contract(&CHARLIE_ADDR, charlie_ch);
+			assert_matches!(run_call(BOB_ADDR, 0, vec![]), Ok(_));
+		});
+
+		assert_eq!(WitnessedCallerBob::get(), Some(ALICE));
+		assert_eq!(WitnessedCallerCharlie::get(), Some(BOB));
+	}
+
+	#[test]
+	fn address_returns_proper_values() {
+		let bob_ch = MockLoader::insert(Call, |ctx, _| {
+			assert_eq!(ctx.ext.address(), BOB_ADDR);
+			assert_eq!(ctx.ext.account_id(), &BOB);
+			assert!(ctx.ext.is_contract(&BOB_ADDR));
+			assert!(!ctx.ext.is_contract(&ALICE_ADDR));
+			assert!(ctx.ext.code_hash(&ALICE_ADDR).is_none());
+			exec_success()
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			place_contract(&BOB_ADDR, bob_ch);
+			assert_matches!(run_call(BOB_ADDR, 0, vec![]), Ok(_));
+		});
+	}
+
+	#[test]
+	fn instantiation_derives_address_from_nonce() {
+		let dummy_ch = MockLoader::insert(Call, |_, _| exec_success());
+
+		ExtBuilder::default()
+			.with_code_hashes(MockLoader::code_hashes())
+			.build()
+			.execute_with(|| {
+				let min_balance = <Test as Config>::Currency::minimum_balance();
+				let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
+				let executable = MockExecutable::from_storage(dummy_ch, &mut gas_meter).unwrap();
+				set_balance(&ALICE, min_balance * 1000);
+				// The transaction which triggers the instantiation already bumped the nonce.
+				System::inc_account_nonce(&ALICE);
+				let origin = Origin::from_account_id(ALICE);
+				let mut storage_meter =
+					storage::meter::Meter::new(&origin, Some(min_balance * 100), min_balance)
+						.unwrap();
+
+				let (address, _) = MockStack::run_instantiate(
+					ALICE,
+					executable,
+					&mut gas_meter,
+					&mut storage_meter,
+					min_balance,
+					vec![],
+					None,
+					None,
+				)
+				.unwrap();
+
+				assert_eq!(address, address::create1(&ALICE_ADDR, 0));
+				assert_eq!(ContractInfoOf::<Test>::get(address).unwrap().code_hash, dummy_ch);
+				assert_eq!(
+					&events(),
+					&[Event::Instantiated { deployer: ALICE_ADDR, contract: address }]
+				);
+			});
+	}
+
+	#[test]
+	fn instantiation_with_salt_derives_address_from_code() {
+		let dummy_ch = MockLoader::insert(Call, |_, _| exec_success());
+
+		ExtBuilder::default()
+			.with_code_hashes(MockLoader::code_hashes())
+			.build()
+			.execute_with(|| {
+				let min_balance = <Test as Config>::Currency::minimum_balance();
+				let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
+				let executable = MockExecutable::from_storage(dummy_ch, &mut gas_meter).unwrap();
+				let code = executable.code().to_vec();
+				set_balance(&ALICE, min_balance * 1000);
+				let origin = Origin::from_account_id(ALICE);
+				let mut storage_meter =
+					storage::meter::Meter::new(&origin, Some(min_balance * 100), min_balance)
+						.unwrap();
+
+				let (address, _) = MockStack::run_instantiate(
+					ALICE,
+					executable,
+					&mut gas_meter,
+					&mut storage_meter,
+					min_balance,
+					vec![1, 2, 3],
+					Some(&[42; 32]),
+					None,
+				)
+				.unwrap();
+
+				assert_eq!(address, address::create2(&ALICE_ADDR, &code, &[1, 2, 3], &[42; 32]));
+			});
+	}
+
+	#[test]
+	fn instantiation_traps() {
+		let dummy_ch = MockLoader::insert(Constructor, |_, _| exec_trapped());
+
+		ExtBuilder::default()
+			.with_code_hashes(MockLoader::code_hashes())
+			.build()
+			.execute_with(|| {
+				let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
+				let executable = MockExecutable::from_storage(dummy_ch, &mut gas_meter).unwrap();
+				set_balance(&ALICE, 1000);
+				let origin = Origin::from_account_id(ALICE);
+				let mut storage_meter = storage::meter::Meter::new(&origin, Some(100), 0).unwrap();
+
+				let result = MockStack::run_instantiate(
+					ALICE,
+					executable,
+					&mut gas_meter,
+					&mut storage_meter,
+					0,
+					vec![],
+					None,
+					None,
+				);
+				assert_matches!(result, Err(ExecError { origin: ErrorOrigin::Callee, .. }));
+				assert_eq!(&events(), &[]);
+			});
+	}
+
+	#[test]
+	fn nested_instantiation_increments_contract_nonce() {
+		parameter_types! {
+			static Instantiated: Vec<H160> = vec![];
+		}
+		let dummy_ch = MockLoader::insert(Call, |_, _| exec_success());
+		let instantiator_ch = MockLoader::insert(Call, {
+			move |ctx, _| {
+				for _ in 0..2 {
+					let (address, _) =
+						ctx.ext.instantiate(Weight::zero(), 0, dummy_ch, 0, vec![], None).unwrap();
+					Instantiated::mutate(|i| i.push(address));
+				}
+				exec_success()
+			}
+		});
+
+		ExtBuilder::default()
+			.with_code_hashes(MockLoader::code_hashes())
+			.build()
+			.execute_with(|| {
+				set_balance(&ALICE, 1_000_000);
+				place_contract(&BOB_ADDR, instantiator_ch);
+				let origin = Origin::from_account_id(ALICE);
+				let mut storage_meter =
+					storage::meter::Meter::new(&origin, Some(10_000), 0).unwrap();
+
+				assert_ok!(MockStack::run_call(
+					origin,
+					BOB_ADDR,
+					&mut GasMeter::<Test>::new(GAS_LIMIT),
+					&mut storage_meter,
+					0,
+					vec![],
+					None,
+				));
+
+				assert_eq!(
+					Instantiated::get(),
+					vec![address::create1(&BOB_ADDR, 0), address::create1(&BOB_ADDR, 1)]
+				);
+				assert_eq!(System::account_nonce(&BOB), 2);
+			});
+	}
+
+	#[test]
+	fn termination_from_instantiate_fails() {
+		let terminate_ch = MockLoader::insert(Constructor, |ctx, _| {
+			ctx.ext.terminate(&ALICE_ADDR).unwrap();
+			exec_success()
+		});
+
+		ExtBuilder::default()
+			.with_code_hashes(MockLoader::code_hashes())
+			.build()
+			.execute_with(|| {
+				let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
+				let executable =
+					MockExecutable::from_storage(terminate_ch, &mut gas_meter).unwrap();
+				set_balance(&ALICE, 10_000);
+				let origin = Origin::from_account_id(ALICE);
+				let mut storage_meter =
+					storage::meter::Meter::new(&origin, Some(1_000), 100).unwrap();
+
+				assert_err!(
+					MockStack::run_instantiate(
+						ALICE,
+						executable,
+						&mut gas_meter,
+						&mut storage_meter,
+						100,
+						vec![],
+						None,
+						None,
+					)
+					.map(|_| ()),
+					Error::<Test>::TerminatedInConstructor
+				);
+			});
+	}
+
+	#[test]
+	fn recursive_call_during_constructor_fails() {
+		let code = MockLoader::insert(Constructor, |ctx, _| {
+			let address = ctx.ext.address();
+			assert_matches!(
+				ctx.ext.call(Weight::zero(), 0, &address, 0, vec![], true),
+				Err(ExecError { error, .. }) if error == <Error<Test>>::ContractNotFound.into()
+			);
+			exec_success()
+		});
+
+		ExtBuilder::default()
+			.with_code_hashes(MockLoader::code_hashes())
+			.build()
+			.execute_with(|| {
+				let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
+				let executable = MockExecutable::from_storage(code, &mut gas_meter).unwrap();
+				set_balance(&ALICE, 10_000);
+				let origin = Origin::from_account_id(ALICE);
+				let mut storage_meter =
+					storage::meter::Meter::new(&origin, Some(1_000), 100).unwrap();
+
+				assert_ok!(MockStack::run_instantiate(
+					ALICE,
+					executable,
+					&mut gas_meter,
+					&mut storage_meter,
+					100,
+					vec![],
+					None,
+					None,
+				));
+			});
+	}
+
+	#[test]
+	fn reentrance_is_denied_when_disallowed() {
+		let code_bob = MockLoader::insert(Call, |ctx, _| {
+			if ctx.input_data[0] == 0 {
+				let address = ctx.ext.address();
+				assert_matches!(
+					ctx.ext.call(Weight::zero(), 0, &address, 0, vec![1], false),
+					Err(ExecError { error, .. }) if error == <Error<Test>>::ReentranceDenied.into()
+				);
+			}
+			exec_success()
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			place_contract(&BOB_ADDR, code_bob);
+			assert_matches!(run_call(BOB_ADDR, 0, vec![0]), Ok(_));
+		});
+	}
+
+	#[test]
+	fn set_storage_works() {
+		let code_hash = MockLoader::insert(Call, |ctx, _| {
+			let key = Key::Fix([1; 32]);
+			assert_eq!(
+				ctx.ext.set_storage(&key, Some(vec![1, 2, 3]), false),
+				Ok(WriteOutcome::New)
+			);
+			assert_eq!(ctx.ext.get_storage(&key), Some(vec![1, 2, 3]));
+			assert_eq!(ctx.ext.get_storage_size(&key), Some(3));
+			assert_eq!(
+				ctx.ext.set_storage(&key, None, true),
+				Ok(WriteOutcome::Taken(vec![1, 2, 3]))
+			);
+			assert_eq!(ctx.ext.get_storage(&key), None);
+			exec_success()
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			set_balance(&ALICE, 1000);
+			place_contract(&BOB_ADDR, code_hash);
+			let origin = Origin::from_account_id(ALICE);
+			let mut storage_meter = storage::meter::Meter::new(&origin, Some(100), 0).unwrap();
+			assert_ok!(MockStack::run_call(
+				origin,
+				BOB_ADDR,
+				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut storage_meter,
+				0,
+				vec![],
+				None,
+			));
+		});
+	}
+}
@@ -0,0 +1,1574 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Revive Pallet
+//!
+//! The Revive module provides functionality for the runtime to deploy and execute PolkaVM
+//! smart-contracts. Contracts are addressed by 20 byte Ethereum style addresses.
+//!
+//! - [`Config`]
+//! - [`Call`]
+//!
+//! ## Overview
+//!
+//! This module is a sibling of `pallet-contracts`. Instead of Wasm it executes RISC-V code
+//! compiled to a PolkaVM program blob. Balances, block numbers and timestamps are exposed to
+//! contracts as 256 bit integers and accounts as [`H160`] addresses so that code written for
+//! Ethereum can be ported with little effort.
+//!
+//! Native accounts are mapped to addresses by the [`Config::AddressMapper`]. Accounts which are
+//! not derived from an Ethereum key need to call [`Pallet::map_account`] before they are able to
+//! interact with contracts. This stores the mapping so that the original account can be
+//! recovered from its address.
+//!
+//! The smart-contract code is stored once, and later retrievable via its hash. This means that
+//! multiple smart-contracts can be instantiated from the same hash, without replicating the code
+//! each time. Contract addresses are derived like on Ethereum: from the deployer and its nonce,
+//! or from the deployer, the code and a salt if a salt is supplied.
+//!
+//! ### Weight
+//!
+//! Senders must specify a [`Weight`] limit with every call, as all instructions invoked by the
+//! smart-contract require weight. Unused weight is refunded after the call, regardless of the
+//! execution outcome.
+//!
+//! If the weight limit is reached, then all calls and state changes (including balance transfers)
+//! are only reverted at the current call's contract level. For example, if contract A calls B and B
+//! runs out of gas mid-call, then all of B's calls are reverted. Assuming correct error handling by
+//! contract A, A's other calls and state changes still persist.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable functions
+//!
+//! * [`Pallet::instantiate_with_code`] - Deploys a new contract from the supplied PolkaVM blob,
+//!   optionally transferring some balance.
+//! * [`Pallet::instantiate`] - The same as `instantiate_with_code` but instead of uploading new
+//!   code an existing `code_hash` is supplied.
+//! * [`Pallet::call`] - Makes a call to an address, optionally transferring some balance.
+//! * [`Pallet::upload_code`] - Uploads new code without instantiating a contract from it.
+//! * [`Pallet::remove_code`] - Removes the stored code and refunds the deposit to its owner. Only
+//!   allowed to code owner.
+//! * [`Pallet::map_account`] - Registers the caller so that its address can be mapped back to its
+//!   account.
+//! * [`Pallet::unmap_account`] - Removes the mapping and releases its deposit.
+//! * [`Pallet::eth_transact`] - Executes a transaction that was signed by an Ethereum wallet.
+//!
+//! ### Ethereum transactions
+//!
+//! Transactions signed by Ethereum wallets are submitted as unsigned [`Pallet::eth_transact`]
+//! extrinsics. The signer is recovered from the signature contained in the transaction and
+//! checked against the [`Config::ChainId`] and its account nonce before the transaction is
+//! admitted to the pool. As the extrinsic is unsigned it doesn't pay the usual transaction fee.
+//! Instead, the weight it consumed is converted via [`Config::WeightPrice`] and burned from the
+//! signer. The [`evm`] module contains the types to decode and verify those transactions.
+//!
+//! The limits of the execution are derived from the transaction. Its signer authorizes paying
+//! `gas` times the gas price for the fee and the storage deposit together. The gas limit is the
+//! largest weight whose fee fits into that amount.
+//!
+//! Balances are exposed to Ethereum tooling without any scaling. Chains which want wallets to
+//! display correct amounts should use 18 decimals for their native currency.
+
+#![allow(rustdoc::private_intra_doc_links)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod address;
+mod benchmarking;
+pub mod evm;
+mod exec;
+mod gas;
+mod primitives;
+pub use primitives::*;
+
+mod storage;
+mod vm;
+
+pub mod limits;
+pub mod weights;
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+	evm::TransactionSigned,
+	exec::{ExecError, Executable, Key, Stack as ExecStack},
+	gas::GasMeter,
+	storage::{meter::Meter as StorageMeter, ContractInfo, DeletionQueueManager},
+	vm::{balance_to_u256, u256_to_balance, CodeInfo, PolkaVmBlob},
+};
+use codec::{Codec, Decode, Encode, HasCompact, MaxEncodedLen};
+use frame_support::{
+	dispatch::{DispatchClass, DispatchResult, Pays, PostDispatchInfo, RawOrigin},
+	ensure,
+	storage::{with_transaction, TransactionOutcome},
+	traits::{
+		fungible::{Inspect, Mutate, MutateHold},
+		tokens::{Fortitude, Precision, Preservation},
+		ConstU32, EnsureOrigin, Get, Time,
+	},
+	weights::Weight,
+	BoundedVec, RuntimeDebugNoBound,
+};
+use frame_system::{
+	ensure_none, ensure_signed,
+	pallet_prelude::{BlockNumberFor, OriginFor},
+	EventRecord, Pallet as System, Phase,
+};
+use scale_info::TypeInfo;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{
+	traits::{BadOrigin, Block as BlockT, Convert, SaturatedConversion, Saturating, Zero},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+	DispatchError, Perbill, RuntimeDebug,
+};
+use sp_std::{fmt::Debug, prelude::*};
+
+pub use crate::{
+	address::{create1, create2, AccountId32Mapper, AddressMapper},
+	pallet::*,
+};
+pub use weights::WeightInfo;
+
+type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+type TrieId = BoundedVec<u8, ConstU32<128>>;
+type BalanceOf<T> =
+	<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+type CodeVec = BoundedVec<u8, ConstU32<{ limits::code::BLOB_BYTES }>>;
+type EventRecordOf<T> =
+	EventRecord<<T as frame_system::Config>::RuntimeEvent, <T as frame_system::Config>::Hash>;
+type DebugBuffer = BoundedVec<u8, ConstU32<{ limits::DEBUG_BUFFER_BYTES }>>;
+
+/// The target that is used for the log output emitted by this crate.
+///
+/// Hence you can use this target to selectively increase the log level for this crate.
+///
+/// Example: `RUST_LOG=runtime::revive=debug my_code --dev`
+const LOG_TARGET: &str = "runtime::revive";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use sp_runtime::Perbill;
+
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
+	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The time implementation used to supply timestamps to contracts through `now`.
+		type Time: Time;
+
+		/// The fungible in which fees are paid and contract balances are held.
+		type Currency: Inspect<Self::AccountId>
+			+ Mutate<Self::AccountId>
+			+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>
+			+ TryInto<Event<Self>>;
+
+		/// Overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// Describes the weights of the dispatchables of this module and is also used to
+		/// charge the syscalls invoked by contracts.
+		type WeightInfo: WeightInfo;
+
+		/// The amount of balance a caller has to pay for each byte of storage.
+		///
+		/// # Note
+		///
+		/// Changing this value for an existing chain might need a storage migration.
+		#[pallet::constant]
+		type DepositPerByte: Get<BalanceOf<Self>>;
+
+		/// Fallback value to limit the storage deposit if it's not being set by the caller.
+		#[pallet::constant]
+		type DefaultDepositLimit: Get<BalanceOf<Self>>;
+
+		/// The amount of balance a caller has to pay for each storage item.
+		///
+		/// # Note
+		///
+		/// Changing this value for an existing chain might need a storage migration.
+		#[pallet::constant]
+		type DepositPerItem: Get<BalanceOf<Self>>;
+
+		/// The percentage of the storage deposit that should be held for using a code hash.
+		/// Instantiating a contract protects the code from being removed. In order to prevent
+		/// abuse these actions are protected with a percentage of the code deposit.
+		#[pallet::constant]
+		type CodeHashLockupDepositPercent: Get<Perbill>;
+
+		/// Maps native accounts to the [`H160`] addresses used by contracts and back.
+		///
+		/// Use [`AccountId32Mapper`] if the runtime uses `AccountId32` as its account id.
+		type AddressMapper: AddressMapper<Self>;
+
+		/// Origin allowed to upload code.
+		///
+		/// By default, it is safe to set this to `EnsureSigned`, allowing anyone to upload contract
+		/// code.
+		type UploadOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+		/// Origin allowed to instantiate code.
+		///
+		/// # Note
+		///
+		/// This is not enforced when a contract instantiates another contract. The
+		/// [`Self::UploadOrigin`] should make sure that no code is deployed that does unwanted
+		/// instantiations.
+		///
+		/// By default, it is safe to set this to `EnsureSigned`, allowing anyone to instantiate
+		/// contract code.
+		type InstantiateOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+		/// The EIP-155 chain id exposed to contracts through the `chain_id` syscall.
+		///
+		/// This should be unique among all chains which use Ethereum style addresses.
+		#[pallet::constant]
+		type ChainId: Get<u64>;
+
+		/// Converts the weight consumed by [`Pallet::eth_transact`] into the fee which is burned
+		/// from the signer of the transaction.
+		///
+		/// Ethereum transactions are submitted as unsigned extrinsics and hence don't pay the
+		/// regular transaction fee. This should return the same fee as the runtime's
+		/// `WeightToFee` in order to not make them cheaper than regular extrinsics.
+		///
+		/// The gas limit of a transaction is derived by searching for the largest weight whose
+		/// price fits into what the signer authorized. The price hence needs to grow with the
+		/// weight.
+		type WeightPrice: Convert<Weight, BalanceOf<Self>>;
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_block: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			ContractInfo::<T>::process_deletion_queue_batch(remaining_weight)
+				.saturating_add(T::WeightInfo::on_process_deletion_queue_batch())
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T>
+	where
+		<BalanceOf<T> as HasCompact>::Type: Clone + Eq + PartialEq + Debug + TypeInfo + Encode,
+	{
+		/// Makes a call to an account, optionally transferring some balance.
+		///
+		/// # Parameters
+		///
+		/// * `dest`: Address of the contract to call.
+		/// * `value`: The balance to transfer from the `origin` to `dest`.
+		/// * `gas_limit`: The gas limit enforced when executing the constructor.
+		/// * `storage_deposit_limit`: The maximum amount of balance that can be charged from the
+		///   caller to pay for the storage consumed.
+		/// * `data`: The input data to pass to the contract.
+		///
+		/// * If the account is a smart-contract account, the associated code will be
+		/// executed and any value will be transferred.
+		/// * If the account is a regular account, any value will be transferred.
+		/// * If no account exists and the call value is not less than `existential_deposit`,
+		/// a regular account will be created and any value will be transferred.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::call().saturating_add(*gas_limit))]
+		pub fn call(
+			origin: OriginFor<T>,
+			dest: H160,
+			#[pallet::compact] value: BalanceOf<T>,
+			gas_limit: Weight,
+			storage_deposit_limit: Option<<BalanceOf<T> as codec::HasCompact>::Type>,
+			data: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let origin = Origin::from_runtime_origin(origin)?;
+			let mut output = Self::internal_call(
+				origin,
+				dest,
+				value,
+				gas_limit,
+				storage_deposit_limit.map(Into::into),
+				data,
+				None,
+			);
+			if let Ok(retval) = &output.result {
+				if retval.did_revert() {
+					output.result = Err(<Error<T>>::ContractReverted.into());
+				}
+			}
+			output.gas_meter.into_dispatch_result(output.result, T::WeightInfo::call())
+		}
+
+		/// Instantiates a new contract from the supplied `code` optionally transferring
+		/// some balance.
+		///
+		/// This dispatchable has the same effect as calling [`Self::upload_code`] +
+		/// [`Self::instantiate`]. Bundling them together provides efficiency gains. Please
+		/// also check the documentation of [`Self::upload_code`].
+		///
+		/// # Parameters
+		///
+		/// * `value`: The balance to transfer from the `origin` to the newly created contract.
+		/// * `gas_limit`: The gas limit enforced when executing the constructor.
+		/// * `storage_deposit_limit`: The maximum amount of balance that can be charged/reserved
+		///   from the caller to pay for the storage consumed.
+		/// * `code`: The contract code to deploy in raw bytes.
+		/// * `data`: The input data to pass to the contract constructor.
+		/// * `salt`: Used for the address derivation. If `Some` is supplied then `CREATE2`
+		/// 	semantics are used. If `None` then `CREATE1` is used.
+		///
+		/// Instantiation is executed as follows:
+		///
+		/// - The supplied `code` is deployed, and a `code_hash` is created for that code.
+		/// - If the `code_hash` already exists on the chain the underlying `code` will be shared.
+		/// - The destination address is computed based on the sender, the salt and its nonce.
+		/// - The smart-contract account is created at the computed address.
+		/// - The `value` is transferred to the new account.
+		/// - The `deploy` function is executed in the context of the newly-created account.
+		#[pallet::call_index(1)]
+		#[pallet::weight(
+			T::WeightInfo::instantiate_with_code(code.len() as u32, data.len() as u32)
+			.saturating_add(*gas_limit)
+		)]
+		pub fn instantiate_with_code(
+			origin: OriginFor<T>,
+			#[pallet::compact] value: BalanceOf<T>,
+			gas_limit: Weight,
+			storage_deposit_limit: Option<<BalanceOf<T> as codec::HasCompact>::Type>,
+			code: Vec<u8>,
+			data: Vec<u8>,
+			salt: Option<[u8; 32]>,
+		) -> DispatchResultWithPostInfo {
+			T::InstantiateOrigin::ensure_origin(origin.clone())?;
+			let origin = T::UploadOrigin::ensure_origin(origin)?;
+			Self::ensure_is_mapped(&origin)?;
+			let code_len = code.len() as u32;
+			let data_len = data.len() as u32;
+
+			let (module, upload_deposit) = Self::try_upload_code(
+				origin.clone(),
+				code,
+				storage_deposit_limit.clone().map(Into::into),
+			)?;
+
+			// Reduces the storage deposit limit by the amount that was reserved for the upload.
+			let storage_deposit_limit =
+				storage_deposit_limit.map(|limit| limit.into().saturating_sub(upload_deposit));
+
+			let mut output = Self::internal_instantiate(
+				origin,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				Ok(module),
+				data,
+				salt,
+				None,
+			);
+			if let Ok(retval) = &output.result {
+				if retval.1.did_revert() {
+					output.result = Err(<Error<T>>::ContractReverted.into());
+				}
+			}
+
+			output.gas_meter.into_dispatch_result(
+				output.result.map(|(_address, output)| output),
+				T::WeightInfo::instantiate_with_code(code_len, data_len),
+			)
+		}
+
+		/// Instantiates a contract from a previously deployed PolkaVM blob.
+		///
+		/// This function is identical to [`Self::instantiate_with_code`] but without the
+		/// code deployment step. Instead, the `code_hash` of an on-chain deployed blob
+		/// must be supplied.
+		#[pallet::call_index(2)]
+		#[pallet::weight(
+			T::WeightInfo::instantiate(data.len() as u32).saturating_add(*gas_limit)
+		)]
+		pub fn instantiate(
+			origin: OriginFor<T>,
+			#[pallet::compact] value: BalanceOf<T>,
+			gas_limit: Weight,
+			storage_deposit_limit: Option<<BalanceOf<T> as codec::HasCompact>::Type>,
+			code_hash: H256,
+			data: Vec<u8>,
+			salt: Option<[u8; 32]>,
+		) -> DispatchResultWithPostInfo {
+			let origin = T::InstantiateOrigin::ensure_origin(origin)?;
+			Self::ensure_is_mapped(&origin)?;
+			let data_len = data.len() as u32;
+			let mut output = Self::internal_instantiate(
+				origin,
+				value,
+				gas_limit,
+				storage_deposit_limit.map(Into::into),
+				Err(code_hash),
+				data,
+				salt,
+				None,
+			);
+			if let Ok(retval) = &output.result {
+				if retval.1.did_revert() {
+					output.result = Err(<Error<T>>::ContractReverted.into());
+				}
+			}
+			output.gas_meter.into_dispatch_result(
+				output.result.map(|(_address, output)| output),
+				T::WeightInfo::instantiate(data_len),
+			)
+		}
+
+		/// Upload new `code` without instantiating a contract from it.
+		///
+		/// If the code does not already exist a deposit is reserved from the caller
+		/// and unreserved only when [`Self::remove_code`] is called. The size of the reserve
+		/// depends on the size of the supplied `code`.
+		///
+		/// # Note
+		///
+		/// Anyone can instantiate a contract from any uploaded code and thus prevent its removal.
+		/// To avoid this situation a constructor could employ access control so that it can
+		/// only be instantiated by permissioned entities. The same is true when uploading
+		/// through [`Self::instantiate_with_code`].
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::upload_code(code.len() as u32))]
+		pub fn upload_code(
+			origin: OriginFor<T>,
+			code: Vec<u8>,
+			storage_deposit_limit: Option<<BalanceOf<T> as codec::HasCompact>::Type>,
+		) -> DispatchResult {
+			let origin = T::UploadOrigin::ensure_origin(origin)?;
+			Self::bare_upload_code(origin, code, storage_deposit_limit.map(Into::into)).map(|_| ())
+		}
+
+		/// Remove the code stored under `code_hash` and refund the deposit to its owner.
+		///
+		/// A code can only be removed by its original uploader (its owner) and only if it is
+		/// not used by any contract.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::remove_code())]
+		pub fn remove_code(origin: OriginFor<T>, code_hash: H256) -> DispatchResultWithPostInfo {
+			let origin = ensure_signed(origin)?;
+			<PolkaVmBlob<T>>::remove(&origin, code_hash)?;
+			// we waive the fee because removing unused code is beneficial
+			Ok(Pays::No.into())
+		}
+
+		/// Register the callers account id so that it can be used in contract interactions.
+		///
+		/// This will error if the origin is already mapped or is a eth native `Address20`. It will
+		/// take a deposit that can be released by calling [`Self::unmap_account`].
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::map_account())]
+		pub fn map_account(origin: OriginFor<T>) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			T::AddressMapper::map(&origin)
+		}
+
+		/// Unregister the callers account id in order to free the deposit.
+		///
+		/// There is no reason to ever call this function other than freeing up the deposit.
+		/// This is only useful when the account should no longer be used.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::unmap_account())]
+		pub fn unmap_account(origin: OriginFor<T>) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			T::AddressMapper::unmap(&origin)
+		}
+
+		/// Execute a transaction which was signed by an Ethereum wallet.
+		///
+		/// # Parameters
+		///
+		/// * `payload`: The RLP encoded and signed transaction as submitted by the wallet. See
+		///   [`evm::TransactionSigned`].
+		///
+		/// This extrinsic is submitted unsigned. Its validity is established by recovering the
+		/// signer of `payload`, which is then used as the origin.
+		///
+		/// The signer authorizes paying `gas` times `gas_price`, or `max_fee_per_gas` for
+		/// EIP-1559 transactions, for the fee and the storage deposit together. The transaction
+		/// is executed with the largest gas limit whose fee fits into that amount, and it fails
+		/// if the fee and the storage deposit it consumed exceed it.
+		///
+		/// If `to` is not set a new contract is created with the input of the transaction as
+		/// its code. Constructor arguments are not supported in this case.
+		///
+		/// Just like on Ethereum, a failing transaction still increments the nonce and pays a
+		/// fee. This is why this extrinsic never returns an error once the transaction was
+		/// found valid. The outcome is reported through [`Event::EthTransactionExecuted`].
+		#[pallet::call_index(7)]
+		#[pallet::weight(Pallet::<T>::eth_transact_weight(payload))]
+		pub fn eth_transact(origin: OriginFor<T>, payload: Vec<u8>) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			let checked = Self::check_eth_transact(&payload)
+				.map_err(|_| <Error<T>>::EthTransactionInvalid)?;
+			ensure!(checked.tx.nonce() == checked.account_nonce, <Error<T>>::EthTransactionInvalid);
+			System::<T>::inc_account_nonce(&checked.account_id);
+
+			let CheckedEthTransact { tx, from, account_id, value, max_cost, gas_limit, .. } =
+				checked;
+			let to = tx.to();
+			let transaction_type = tx.transaction_type();
+			let base_weight = T::WeightInfo::eth_transact(payload.len() as u32);
+			// Only the changes of the execution are rolled back on failure. The nonce and the fee
+			// are kept just like on Ethereum.
+			let (success, contract_address, gas_consumed) = with_transaction(|| {
+				let (success, contract_address, gas_consumed, storage_deposit) =
+					Self::execute_eth_transact(account_id.clone(), tx, value, gas_limit, max_cost);
+				// The fee can't exceed `max_cost` because of the gas limit. The storage deposit is
+				// paid from what is left.
+				let cost = T::WeightPrice::convert(base_weight.saturating_add(gas_consumed))
+					.saturating_add(storage_deposit);
+				if success && cost <= max_cost {
+					TransactionOutcome::Commit(Ok::<_, DispatchError>((
+						true,
+						contract_address,
+						gas_consumed,
+					)))
+				} else {
+					TransactionOutcome::Rollback(Ok((false, None, gas_consumed)))
+				}
+			})?;
+
+			let actual_weight = base_weight.saturating_add(gas_consumed);
+			// `BestEffort` never fails because of missing funds. The balance was checked
+			// during validation anyways.
+			let fee = T::Currency::burn_from(
+				&account_id,
+				T::WeightPrice::convert(actual_weight),
+				Precision::BestEffort,
+				Fortitude::Polite,
+			)
+			.unwrap_or_default();
+
+			Self::deposit_event(Event::EthTransactionExecuted {
+				transaction_hash: evm::transaction_hash(&payload),
+				from,
+				to,
+				contract_address,
+				fee,
+				success,
+				transaction_type,
+			});
+
+			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::No })
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T>
+	where
+		<BalanceOf<T> as HasCompact>::Type: Clone + Eq + PartialEq + Debug + TypeInfo + Encode,
+	{
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::eth_transact { payload } = call else {
+				return InvalidTransaction::Call.into()
+			};
+			let checked = Self::check_eth_transact(payload)?;
+
+			let nonce = checked.tx.nonce();
+			let mut builder = ValidTransaction::with_tag_prefix(evm::TAG_PREFIX)
+				.and_provides((checked.from, nonce));
+			if nonce > checked.account_nonce {
+				builder = builder.and_requires((checked.from, nonce - 1));
+			}
+			builder.build()
+		}
+
+		fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+			let Call::eth_transact { payload } = call else {
+				return Err(InvalidTransaction::Call.into())
+			};
+			let checked = Self::check_eth_transact(payload)?;
+			ensure!(checked.tx.nonce() == checked.account_nonce, InvalidTransaction::Future);
+			Ok(())
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Contract deployed by deployer at the specified address.
+		Instantiated { deployer: H160, contract: H160 },
+
+		/// Contract has been removed.
+		///
+		/// # Note
+		///
+		/// The only way for a contract to be removed and emitting this event is by calling
+		/// `terminate`.
+		Terminated {
+			/// The contract that was terminated.
+			contract: H160,
+			/// The account that received the contracts remaining balance
+			beneficiary: H160,
+		},
+
+		/// Code with the specified hash has been stored.
+		CodeStored { code_hash: H256 },
+
+		/// A custom event emitted by the contract.
+		ContractEmitted {
+			/// The contract that emitted the event.
+			contract: H160,
+			/// Data supplied by the contract. Metadata generated during contract compilation
+			/// is needed to decode it.
+			data: Vec<u8>,
+			/// A list of topics used to index the event.
+			/// Number of topics is capped by [`limits::NUM_EVENT_TOPICS`].
+			topics: Vec<H256>,
+		},
+
+		/// A code with the specified hash was removed.
+		CodeRemoved { code_hash: H256 },
+
+		/// A contract was called either by a plain account or another contract.
+		///
+		/// # Note
+		///
+		/// Please keep in mind that like all events this is only emitted for successful
+		/// calls. This is because on failure all storage changes including events are
+		/// rolled back.
+		Called {
+			/// The caller of the `contract`.
+			caller: Origin<T>,
+			/// The contract that was called.
+			contract: H160,
+		},
+
+		/// A contract delegate called a code hash.
+		///
+		/// # Note
+		///
+		/// Please keep in mind that like all events this is only emitted for successful
+		/// calls. This is because on failure all storage changes including events are
+		/// rolled back.
+		DelegateCalled {
+			/// The contract that performed the delegate call and hence in whose context
+			/// the `code_hash` is executed.
+			contract: H160,
+			/// The code hash that was delegate called.
+			code_hash: H256,
+		},
+
+		/// A transaction submitted through [`Pallet::eth_transact`] was executed.
+		///
+		/// # Note
+		///
+		/// Unlike the other events this one is also emitted for failed executions. Only the
+		/// changes made by the execution are rolled back in this case. The nonce increment
+		/// and the fee are kept.
+		EthTransactionExecuted {
+			/// The hash under which Ethereum tooling refers to the transaction.
+			transaction_hash: H256,
+			/// The signer of the transaction.
+			from: H160,
+			/// The called contract or `None` if a contract was created.
+			to: Option<H160>,
+			/// The address of the created contract if any.
+			contract_address: Option<H160>,
+			/// The fee that was burned from the signer.
+			fee: BalanceOf<T>,
+			/// Whether the execution succeeded and its changes were kept.
+			success: bool,
+			/// The type of the transaction. See [`evm::TransactionSigned::transaction_type`].
+			transaction_type: u8,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Invalid combination of flags supplied to `call` or `delegate_call`.
+		InvalidCallFlags,
+		/// The executed contract exhausted its gas limit.
+		OutOfGas,
+		/// The output buffer supplied to a contract API call was too small.
+		OutputBufferTooSmall,
+		/// Performing the requested transfer failed. Probably because there isn't enough
+		/// free balance in the sender's account.
+		TransferFailed,
+		/// Performing a call was denied because the calling depth reached the limit
+		/// of what is specified in [`limits::CALL_STACK_DEPTH`].
+		MaxCallDepthReached,
+		/// No contract was found at the specified address.
+		ContractNotFound,
+		/// The code supplied to `instantiate_with_code` exceeds the limit specified in
+		/// [`limits::code::BLOB_BYTES`].
+		CodeTooLarge,
+		/// No code could be found at the supplied code hash.
+		CodeNotFound,
+		/// A buffer outside of sandbox memory was passed to a contract API function.
+		OutOfBounds,
+		/// Input passed to a contract API function failed to decode as expected type.
+		DecodingFailed,
+		/// Contract trapped during execution.
+		ContractTrapped,
+		/// The size defined in [`limits::PAYLOAD_BYTES`] was exceeded.
+		ValueTooLarge,
+		/// Termination of a contract is not allowed while the contract is already
+		/// on the call stack. Can be triggered by `terminate`.
+		TerminatedWhileReentrant,
+		/// `call` forwarded this contracts input. It therefore is no longer available.
+		InputForwarded,
+		/// The amount of topics passed to `deposit_event` exceeds the limit.
+		TooManyTopics,
+		/// A contract with the same address already exists.
+		DuplicateContract,
+		/// A contract self destructed in its constructor.
+		///
+		/// This can be triggered by a call to `terminate`.
+		TerminatedInConstructor,
+		/// A call tried to invoke a contract that is flagged as non-reentrant.
+		ReentranceDenied,
+		/// Origin doesn't have enough balance to pay the required storage deposits.
+		StorageDepositNotEnoughFunds,
+		/// More storage was created than allowed by the storage deposit limit.
+		StorageDepositLimitExhausted,
+		/// Code removal was denied because the code is still in use by at least one contract.
+		CodeInUse,
+		/// The contract ran to completion but decided to revert its storage changes.
+		/// Please note that this error is only returned from extrinsics. When called directly
+		/// or via RPC an `Ok` will be returned. In this case the caller needs to inspect the flags
+		/// to determine whether a reversion has taken place.
+		ContractReverted,
+		/// The contract's code was found to be invalid during validation.
+		///
+		/// The most likely cause of this is that an API was used which is not supported by the
+		/// node, the blob exceeds one of the limits in [`limits::code`] or the blob is not a
+		/// valid PolkaVM program.
+		///
+		/// A more detailed error can be found on the node console if debug messages are enabled
+		/// by supplying `-lruntime::revive=debug`.
+		CodeRejected,
+		/// The contract tried to invoke a syscall which does not exist.
+		InvalidSyscall,
+		/// A 256 bit value supplied by a contract does not fit into the balance type.
+		BalanceConversionFailed,
+		/// Tried to map an account that is already mapped.
+		AccountAlreadyMapped,
+		/// An `AccountID32` account tried to interact with the pallet without having a mapping.
+		///
+		/// Call [`Pallet::map_account`] in order to create a mapping for the account.
+		AccountUnmapped,
+		/// The payload passed to [`Pallet::eth_transact`] is not a valid transaction.
+		EthTransactionInvalid,
+	}
+
+	/// A reason for the pallet revive placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// The Pallet has reserved it for storing code on-chain.
+		CodeUploadDepositReserve,
+		/// The Pallet has reserved it for storage deposit.
+		StorageDepositReserve,
+		/// Deposit for creating an address mapping in [`OriginalAccount`].
+		AddressMapping,
+	}
+
+	/// A mapping from a contract's code hash to its code.
+	#[pallet::storage]
+	pub(crate) type PristineCode<T: Config> = StorageMap<_, Identity, H256, CodeVec>;
+
+	/// A mapping from a contract's code hash to its code info.
+	#[pallet::storage]
+	pub(crate) type CodeInfoOf<T: Config> = StorageMap<_, Identity, H256, CodeInfo<T>>;
+
+	/// This is a **monotonic** counter incremented on contract instantiation.
+	///
+	/// This is used in order to generate unique trie ids for contracts.
+	/// The trie id of a new contract is calculated from hash(address, nonce).
+	/// The nonce is required because otherwise the following sequence would lead to
+	/// a possible collision of storage:
+	///
+	/// 1. Create a new contract.
+	/// 2. Terminate the contract.
+	/// 3. Immediately recreate the contract with the same address.
+	///
+	/// This is bad because the contents of a trie are deleted lazily and there might be
+	/// storage of the old instantiation still in it when the new contract is created. Please
+	/// note that we can't replace the counter by the block number because the sequence above
+	/// can happen in the same block. We also can't keep the account counter in memory only
+	/// because storage is the only way to communicate across different extrinsics in the
+	/// same block.
+	///
+	/// # Note
+	///
+	/// Do not use it to determine the number of contracts. It won't be decremented if
+	/// a contract is destroyed.
+	#[pallet::storage]
+	pub(crate) type Nonce<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// The code associated with a given address.
+	#[pallet::storage]
+	pub(crate) type ContractInfoOf<T: Config> = StorageMap<_, Identity, H160, ContractInfo<T>>;
+
+	/// Evicted contracts that await child trie deletion.
+	///
+	/// Child trie deletion is a heavy operation depending on the amount of storage items
+	/// stored in said trie. Therefore this operation is performed lazily in `on_idle`.
+	#[pallet::storage]
+	pub(crate) type DeletionQueue<T: Config> = StorageMap<_, Twox64Concat, u32, TrieId>;
+
+	/// A pair of monotonic counters used to track the latest contract marked for deletion
+	/// and the latest deleted contract in queue.
+	#[pallet::storage]
+	pub(crate) type DeletionQueueCounter<T: Config> =
+		StorageValue<_, DeletionQueueManager<T>, ValueQuery>;
+
+	/// Map a Ethereum address to its original `AccountId32`.
+	///
+	/// Stores the last 12 byte for addresses that were originally an `AccountId32` instead
+	/// of an `H160`. Register your `AccountId32` using [`Pallet::map_account`] in order to
+	/// use it with this pallet.
+	#[pallet::storage]
+	pub(crate) type OriginalAccount<T: Config> = StorageMap<_, Identity, H160, T::AccountId>;
+}
+
+/// The type of origins supported by the revive pallet.
+#[derive(Clone, Encode, Decode, PartialEq, TypeInfo, RuntimeDebugNoBound)]
+pub enum Origin<T: Config> {
+	Root,
+	Signed(T::AccountId),
+}
+
+impl<T: Config> Origin<T> {
+	/// Creates a new Signed Caller from an AccountId.
+	pub fn from_account_id(account_id: T::AccountId) -> Self {
+		Origin::Signed(account_id)
+	}
+	/// Creates a new Origin from a `RuntimeOrigin`.
+	pub fn from_runtime_origin(o: OriginFor<T>) -> Result<Self, DispatchError> {
+		match o.into() {
+			Ok(RawOrigin::Root) => Ok(Self::Root),
+			Ok(RawOrigin::Signed(t)) => Ok(Self::Signed(t)),
+			_ => Err(BadOrigin.into()),
+		}
+	}
+	/// Returns the AccountId of a Signed Origin or an error if the origin is Root.
+	pub fn account_id(&self) -> Result<&T::AccountId, DispatchError> {
+		match self {
+			Origin::Signed(id) => Ok(id),
+			Origin::Root => Err(DispatchError::RootNotAllowed),
+		}
+	}
+}
+
+/// Determines whether events should be collected during execution.
+#[derive(
+	Copy, Clone, PartialEq, Eq, RuntimeDebug, Decode, Encode, MaxEncodedLen, scale_info::TypeInfo,
+)]
+pub enum CollectEvents {
+	/// Collect events.
+	///
+	/// # Note
+	///
+	/// Events should only be collected when called off-chain, as this would otherwise
+	/// collect all the Events emitted in the block so far and put them into the PoV.
+	///
+	/// **Never** use this mode for on-chain execution.
+	UnsafeCollect,
+	/// Skip event collection.
+	Skip,
+}
+
+/// Determines whether debug messages will be collected.
+#[derive(
+	Copy, Clone, PartialEq, Eq, RuntimeDebug, Decode, Encode, MaxEncodedLen, scale_info::TypeInfo,
+)]
+pub enum DebugInfo {
+	/// Collect debug messages.
+	/// # Note
+	///
+	/// This should only ever be set to `UnsafeDebug` when executing as an RPC because
+	/// it adds allocations and could be abused to drive the runtime into an OOM panic.
+	UnsafeDebug,
+	/// Skip collection of debug messages.
+	Skip,
+}
+
+/// A transaction submitted through [`Pallet::eth_transact`] whose signature was verified.
+struct CheckedEthTransact<T: Config> {
+	/// The decoded transaction.
+	tx: TransactionSigned,
+	/// The recovered signer of the transaction.
+	from: H160,
+	/// The account `from` is mapped to.
+	account_id: T::AccountId,
+	/// The value of the transaction converted to a balance.
+	value: BalanceOf<T>,
+	/// The current nonce of `account_id`.
+	account_nonce: U256,
+	/// The maximum amount the signer pays for the fee and the storage deposit together.
+	max_cost: BalanceOf<T>,
+	/// The gas limit derived from `max_cost`.
+	gas_limit: Weight,
+}
+
+/// Return type of private helper functions.
+struct InternalOutput<T: Config, O> {
+	/// The gas meter that was used to execute the call.
+	gas_meter: GasMeter<T>,
+	/// The storage deposit used by the call.
+	storage_deposit: StorageDeposit<BalanceOf<T>>,
+	/// The result of the call.
+	result: Result<O, ExecError>,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Perform a call to a specified contract.
+	///
+	/// This function is similar to [`Self::call`], but doesn't perform any address lookups
+	/// and better suitable for calling directly from Rust.
+	///
+	/// # Note
+	///
+	/// If `debug` is set to `DebugInfo::UnsafeDebug` it returns additional human readable debugging
+	/// information.
+	///
+	/// If `collect_events` is set to `CollectEvents::UnsafeCollect` it collects all the Events
+	/// emitted in the block so far and the ones emitted during the execution of this contract.
+	pub fn bare_call(
+		origin: T::AccountId,
+		dest: H160,
+		value: BalanceOf<T>,
+		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
+		data: Vec<u8>,
+		debug: DebugInfo,
+		collect_events: CollectEvents,
+	) -> ContractExecResult<BalanceOf<T>, EventRecordOf<T>> {
+		let mut debug_message = if matches!(debug, DebugInfo::UnsafeDebug) {
+			Some(DebugBuffer::default())
+		} else {
+			None
+		};
+		let output = Self::internal_call(
+			Origin::from_account_id(origin),
+			dest,
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			data,
+			debug_message.as_mut(),
+		);
+		let events = if matches!(collect_events, CollectEvents::UnsafeCollect) {
+			Some(System::<T>::read_events_no_consensus().map(|e| *e).collect())
+		} else {
+			None
+		};
+
+		ContractExecResult {
+			result: output.result.map_err(|r| r.error),
+			gas_consumed: output.gas_meter.gas_consumed(),
+			gas_required: output.gas_meter.gas_required(),
+			storage_deposit: output.storage_deposit,
+			debug_message: debug_message.unwrap_or_default().to_vec(),
+			events,
+		}
+	}
+
+	/// Instantiate a new contract.
+	///
+	/// This function is similar to [`Self::instantiate`], but doesn't perform any address lookups
+	/// and better suitable for calling directly from Rust.
+	///
+	/// It returns the execution result, address and the amount of used weight.
+	///
+	/// # Note
+	///
+	/// If `debug` is set to `DebugInfo::UnsafeDebug` it returns additional human readable debugging
+	/// information.
+	///
+	/// If `collect_events` is set to `CollectEvents::UnsafeCollect` it collects all the Events
+	/// emitted in the block so far.
+	pub fn bare_instantiate(
+		origin: T::AccountId,
+		value: BalanceOf<T>,
+		gas_limit: Weight,
+		mut storage_deposit_limit: Option<BalanceOf<T>>,
+		code: Code,
+		data: Vec<u8>,
+		salt: Option<[u8; 32]>,
+		debug: DebugInfo,
+		collect_events: CollectEvents,
+	) -> ContractInstantiateResult<BalanceOf<T>, EventRecordOf<T>> {
+		let mut debug_message =
+			if debug == DebugInfo::UnsafeDebug { Some(DebugBuffer::default()) } else { None };
+		// collect events if CollectEvents is UnsafeCollect
+		let events = || {
+			if collect_events == CollectEvents::UnsafeCollect {
+				Some(System::<T>::read_events_no_consensus().map(|e| *e).collect())
+			} else {
+				None
+			}
+		};
+
+		let (code, upload_deposit): (Result<PolkaVmBlob<T>, H256>, BalanceOf<T>) = match code {
+			Code::Upload(code) => {
+				let result = Self::try_upload_code(origin.clone(), code, storage_deposit_limit);
+				let (module, deposit) = match result {
+					Ok(result) => result,
+					Err(error) =>
+						return ContractResult {
+							gas_consumed: Zero::zero(),
+							gas_required: Zero::zero(),
+							storage_deposit: Default::default(),
+							debug_message: debug_message.unwrap_or_default().into(),
+							result: Err(error),
+							events: events(),
+						},
+				};
+
+				storage_deposit_limit = storage_deposit_limit.map(|l| l.saturating_sub(deposit));
+				(Ok(module), deposit)
+			},
+			Code::Existing(hash) => (Err(hash), Default::default()),
+		};
+
+		let output = Self::internal_instantiate(
+			origin,
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			code,
+			data,
+			salt,
+			debug_message.as_mut(),
+		);
+		ContractInstantiateResult {
+			result: output
+				.result
+				.map(|(addr, result)| InstantiateReturnValue { result, addr })
+				.map_err(|e| e.error),
+			gas_consumed: output.gas_meter.gas_consumed(),
+			gas_required: output.gas_meter.gas_required(),
+			storage_deposit: output
+				.storage_deposit
+				.saturating_add(&StorageDeposit::Charge(upload_deposit)),
+			debug_message: debug_message.unwrap_or_default().to_vec(),
+			events: events(),
+		}
+	}
+
+	/// Upload new code without instantiating a contract from it.
+	///
+	/// This function is similar to [`Self::upload_code`], but doesn't perform any address lookups
+	/// and better suitable for calling directly from Rust.
+	pub fn bare_upload_code(
+		origin: T::AccountId,
+		code: Vec<u8>,
+		storage_deposit_limit: Option<BalanceOf<T>>,
+	) -> CodeUploadResult<BalanceOf<T>> {
+		let (module, deposit) = Self::try_upload_code(origin, code, storage_deposit_limit)?;
+		Ok(CodeUploadReturnValue { code_hash: *module.code_hash(), deposit })
+	}
+
+	/// Query storage of a specified contract under a specified key.
+	pub fn get_storage(address: H160, key: [u8; 32]) -> GetStorageResult {
+		let contract_info =
+			ContractInfoOf::<T>::get(&address).ok_or(ContractAccessError::DoesntExist)?;
+
+		let maybe_value = contract_info.read(&Key::Fix(key));
+		Ok(maybe_value)
+	}
+
+	/// Returns the code hash of the contract specified by `address`.
+	pub fn code_hash(address: &H160) -> Option<H256> {
+		ContractInfo::<T>::load_code_hash(address)
+	}
+
+	/// Returns the balance of `address` as it is exposed to Ethereum tooling.
+	pub fn evm_balance(address: &H160) -> U256 {
+		let account_id = T::AddressMapper::to_account_id(address);
+		balance_to_u256::<T>(T::Currency::balance(&account_id))
+	}
+
+	/// Returns the nonce of `address` as it is exposed to Ethereum tooling.
+	pub fn evm_nonce(address: &H160) -> U256 {
+		let account_id = T::AddressMapper::to_account_id(address);
+		System::<T>::account_nonce(&account_id).saturated_into::<u64>().into()
+	}
+
+	/// Dry run an Ethereum style call or contract creation.
+	///
+	/// This is used to implement `eth_call` and `eth_estimateGas`. If `to` is `None` the `input`
+	/// is instantiated as a new contract just like [`Self::eth_transact`] would do. The returned
+	/// fee is an upper bound of what [`Self::eth_transact`] burns for the execution. Together with
+	/// the storage deposit it is the amount a transaction needs to authorize via its gas.
+	pub fn bare_eth_dry_run(
+		from: H160,
+		to: Option<H160>,
+		value: U256,
+		input: Vec<u8>,
+		gas_limit: Weight,
+	) -> EthContractResult<BalanceOf<T>> {
+		let origin = T::AddressMapper::to_account_id(&from);
+		let payload_len = input.len().saturating_add(evm::TRANSACTION_MAX_OVERHEAD);
+		let value = match u256_to_balance::<T>(value) {
+			Ok(value) => value,
+			Err(err) =>
+				return EthContractResult {
+					fee: Zero::zero(),
+					gas_required: Zero::zero(),
+					storage_deposit: Zero::zero(),
+					result: Err(err),
+				},
+		};
+
+		let (gas_required, storage_deposit, result) = match to {
+			Some(dest) => {
+				let output = Self::bare_call(
+					origin,
+					dest,
+					value,
+					gas_limit,
+					None,
+					input,
+					DebugInfo::Skip,
+					CollectEvents::Skip,
+				);
+				(output.gas_required, output.storage_deposit, output.result)
+			},
+			None => {
+				// `eth_transact` increments the nonce before the contract address is derived.
+				System::<T>::inc_account_nonce(&origin);
+				let upload_weight = T::WeightInfo::upload_code(input.len() as u32);
+				let output = Self::bare_instantiate(
+					origin,
+					value,
+					gas_limit.saturating_sub(upload_weight),
+					None,
+					Code::Upload(input),
+					vec![],
+					None,
+					DebugInfo::Skip,
+					CollectEvents::Skip,
+				);
+				(
+					output.gas_required.saturating_add(upload_weight),
+					output.storage_deposit,
+					output.result.map(|output| output.result),
+				)
+			},
+		};
+
+		let fee = T::WeightPrice::convert(
+			T::WeightInfo::eth_transact(payload_len as u32).saturating_add(gas_required),
+		);
+		EthContractResult {
+			fee,
+			gas_required,
+			storage_deposit: storage_deposit.charge_or_zero(),
+			result,
+		}
+	}
+
+	/// Returns the receipts of all transactions submitted through [`Self::eth_transact`] in the
+	/// current block.
+	///
+	/// # Note
+	///
+	/// This reads all the events of the block and hence should only be called off-chain.
+	pub fn eth_receipts() -> Vec<EthReceipt<BalanceOf<T>>> {
+		let mut receipts = Vec::new();
+		let mut logs = Vec::new();
+		let mut current_extrinsic = None;
+		for record in System::<T>::read_events_no_consensus() {
+			let EventRecord { phase, event, .. } = *record;
+			let Phase::ApplyExtrinsic(extrinsic_index) = phase else { continue };
+			if current_extrinsic != Some(extrinsic_index) {
+				logs.clear();
+				current_extrinsic = Some(extrinsic_index);
+			}
+			let event: Result<Event<T>, _> = <T as Config>::RuntimeEvent::from(event).try_into();
+			match event {
+				Ok(Event::ContractEmitted { contract, data, topics }) =>
+					logs.push(EthLog { address: contract, topics, data }),
+				Ok(Event::EthTransactionExecuted {
+					transaction_hash,
+					from,
+					to,
+					contract_address,
+					fee,
+					success,
+					transaction_type,
+				}) => receipts.push(EthReceipt {
+					transaction_hash,
+					extrinsic_index,
+					from,
+					to,
+					contract_address,
+					fee,
+					success,
+					transaction_type,
+					logs: core::mem::take(&mut logs),
+				}),
+				_ => (),
+			}
+		}
+		receipts
+	}
+
+	/// Uploads new code and returns the blob and deposit amount collected.
+	fn try_upload_code(
+		origin: T::AccountId,
+		code: Vec<u8>,
+		storage_deposit_limit: Option<BalanceOf<T>>,
+	) -> Result<(PolkaVmBlob<T>, BalanceOf<T>), DispatchError> {
+		let mut module = PolkaVmBlob::from_code(code, origin)?;
+		let deposit = module.store_code()?;
+		if let Some(storage_deposit_limit) = storage_deposit_limit {
+			ensure!(storage_deposit_limit >= deposit, <Error<T>>::StorageDepositLimitExhausted);
+		}
+
+		Ok((module, deposit))
+	}
+
+	/// Executes a call to the contract at `dest`.
+	fn internal_call(
+		origin: Origin<T>,
+		dest: H160,
+		value: BalanceOf<T>,
+		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
+		data: Vec<u8>,
+		debug_message: Option<&mut DebugBuffer>,
+	) -> InternalOutput<T, ExecReturnValue> {
+		let mut gas_meter = GasMeter::new(gas_limit);
+		if let Ok(account_id) = origin.account_id() {
+			if let Err(err) = Self::ensure_is_mapped(account_id) {
+				return InternalOutput {
+					gas_meter,
+					storage_deposit: Default::default(),
+					result: Err(err.into()),
+				}
+			}
+		}
+		let mut storage_meter = match StorageMeter::new(&origin, storage_deposit_limit, value) {
+			Ok(meter) => meter,
+			Err(err) =>
+				return InternalOutput {
+					gas_meter,
+					storage_deposit: Default::default(),
+					result: Err(err.into()),
+				},
+		};
+		let result = ExecStack::<T, PolkaVmBlob<T>>::run_call(
+			origin.clone(),
+			dest,
+			&mut gas_meter,
+			&mut storage_meter,
+			value,
+			data,
+			debug_message,
+		);
+
+		match storage_meter.try_into_deposit(&origin) {
+			Ok(storage_deposit) => InternalOutput { gas_meter, storage_deposit, result },
+			Err(err) => InternalOutput {
+				gas_meter,
+				storage_deposit: Default::default(),
+				result: Err(err.into()),
+			},
+		}
+	}
+
+	/// Instantiates either the passed `module` or the code stored under the passed code hash.
+	fn internal_instantiate(
+		origin: T::AccountId,
+		value: BalanceOf<T>,
+		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
+		code: Result<PolkaVmBlob<T>, H256>,
+		data: Vec<u8>,
+		salt: Option<[u8; 32]>,
+		debug_message: Option<&mut DebugBuffer>,
+	) -> InternalOutput<T, (H160, ExecReturnValue)> {
+		let mut gas_meter = GasMeter::new(gas_limit);
+		let mut storage_deposit = Default::default();
+		let try_exec = || {
+			let executable = match code {
+				Ok(module) => module,
+				Err(code_hash) => PolkaVmBlob::from_storage(code_hash, &mut gas_meter)?,
+			};
+			let contract_origin = Origin::from_account_id(origin.clone());
+			let mut storage_meter =
+				StorageMeter::new(&contract_origin, storage_deposit_limit, value)?;
+			let result = ExecStack::<T, PolkaVmBlob<T>>::run_instantiate(
+				origin,
+				executable,
+				&mut gas_meter,
+				&mut storage_meter,
+				value,
+				data,
+				salt.as_ref(),
+				debug_message,
+			);
+			storage_deposit = storage_meter.try_into_deposit(&contract_origin)?;
+			result
+		};
+		let result = try_exec();
+		InternalOutput { result, gas_meter, storage_deposit }
+	}
+
+	/// Decodes the `payload` of [`Self::eth_transact`] and checks that its signer can pay for it.
+	///
+	/// Transactions with a nonce higher than the signer's account nonce are accepted. It is up to
+	/// the caller to decide whether this is acceptable.
+	fn check_eth_transact(
+		payload: &[u8],
+	) -> Result<CheckedEthTransact<T>, TransactionValidityError> {
+		let tx = TransactionSigned::decode(payload).map_err(|_| InvalidTransaction::Call)?;
+		let from = tx.recover_eth_address().ok_or(InvalidTransaction::BadProof)?;
+		ensure!(tx.chain_id() == Some(T::ChainId::get().into()), InvalidTransaction::BadProof);
+
+		let account_id = T::AddressMapper::to_account_id(&from);
+		let account_nonce: U256 =
+			System::<T>::account_nonce(&account_id).saturated_into::<u64>().into();
+		ensure!(tx.nonce() >= account_nonce, InvalidTransaction::Stale);
+
+		// The signer needs to authorize at least the fee of an empty execution and needs to be
+		// able to pay for everything it authorized.
+		let value = u256_to_balance::<T>(tx.value()).map_err(|_| InvalidTransaction::Call)?;
+		let max_cost = Self::eth_max_cost(&tx).ok_or(InvalidTransaction::Payment)?;
+		let base_weight = T::WeightInfo::eth_transact(payload.len() as u32);
+		ensure!(T::WeightPrice::convert(base_weight) <= max_cost, InvalidTransaction::Payment);
+		let reducible =
+			T::Currency::reducible_balance(&account_id, Preservation::Preserve, Fortitude::Polite);
+		ensure!(reducible >= max_cost.saturating_add(value), InvalidTransaction::Payment);
+
+		let gas_limit = Self::eth_gas_limit(base_weight, max_cost);
+		Ok(CheckedEthTransact { tx, from, account_id, value, account_nonce, max_cost, gas_limit })
+	}
+
+	/// The weight of [`Self::eth_transact`] including the gas limit authorized by `payload`.
+	fn eth_transact_weight(payload: &[u8]) -> Weight {
+		let base_weight = T::WeightInfo::eth_transact(payload.len() as u32);
+		let gas_limit = TransactionSigned::decode(payload)
+			.ok()
+			.and_then(|tx| Self::eth_max_cost(&tx))
+			.map_or(Weight::zero(), |max_cost| Self::eth_gas_limit(base_weight, max_cost));
+		base_weight.saturating_add(gas_limit)
+	}
+
+	/// The maximum amount the signer of `tx` pays for the fee and the storage deposit together.
+	///
+	/// Returns `None` if the amount doesn't fit into a balance.
+	fn eth_max_cost(tx: &TransactionSigned) -> Option<BalanceOf<T>> {
+		u256_to_balance::<T>(tx.gas().saturating_mul(tx.max_fee_per_gas())).ok()
+	}
+
+	/// The largest gas limit whose fee, on top of the fee for `base_weight`, fits into
+	/// `max_cost`.
+	///
+	/// The gas limit can't exceed the maximum weight of an extrinsic.
+	fn eth_gas_limit(base_weight: Weight, max_cost: BalanceOf<T>) -> Weight {
+		let block_weights = <T as frame_system::Config>::BlockWeights::get();
+		let max_gas_limit = block_weights
+			.get(DispatchClass::Normal)
+			.max_extrinsic
+			.unwrap_or(block_weights.max_block)
+			.saturating_sub(base_weight);
+		let fits = |gas_limit: Weight| {
+			T::WeightPrice::convert(base_weight.saturating_add(gas_limit)) <= max_cost
+		};
+		if fits(max_gas_limit) {
+			return max_gas_limit
+		}
+
+		// Binary search for the largest share of `max_gas_limit` that fits.
+		let (mut low, mut high) = (0, Perbill::ACCURACY);
+		while low < high {
+			let mid = low + (high - low + 1) / 2;
+			if fits(Perbill::from_parts(mid) * max_gas_limit) {
+				low = mid;
+			} else {
+				high = mid - 1;
+			}
+		}
+		Perbill::from_parts(low) * max_gas_limit
+	}
+
+	/// Executes a transaction which was verified by [`Self::check_eth_transact`].
+	///
+	/// Returns whether the execution succeeded, the address of the created contract if any, the
+	/// consumed gas and the charged storage deposit.
+	fn execute_eth_transact(
+		account_id: T::AccountId,
+		tx: TransactionSigned,
+		value: BalanceOf<T>,
+		gas_limit: Weight,
+		storage_deposit_limit: BalanceOf<T>,
+	) -> (bool, Option<H160>, Weight, BalanceOf<T>) {
+		let Some(dest) = tx.to() else {
+			let code = tx.into_input();
+			// The upload is paid from the gas limit as it isn't covered by the extrinsic weight.
+			let upload_weight = T::WeightInfo::upload_code(code.len() as u32);
+			if upload_weight.any_gt(gas_limit) {
+				return (false, None, gas_limit, Zero::zero())
+			}
+			let Ok((module, upload_deposit)) =
+				Self::try_upload_code(account_id.clone(), code, Some(storage_deposit_limit))
+			else {
+				return (false, None, upload_weight, Zero::zero())
+			};
+			let output = Self::internal_instantiate(
+				account_id,
+				value,
+				gas_limit.saturating_sub(upload_weight),
+				Some(storage_deposit_limit.saturating_sub(upload_deposit)),
+				Ok(module),
+				vec![],
+				None,
+				None,
+			);
+			let gas_consumed = output.gas_meter.gas_consumed().saturating_add(upload_weight);
+			let storage_deposit =
+				output.storage_deposit.charge_or_zero().saturating_add(upload_deposit);
+			return match output.result {
+				Ok((address, retval)) if !retval.did_revert() =>
+					(true, Some(address), gas_consumed, storage_deposit),
+				_ => (false, None, gas_consumed, storage_deposit),
+			}
+		};
+
+		let output = Self::internal_call(
+			Origin::from_account_id(account_id),
+			dest,
+			value,
+			gas_limit,
+			Some(storage_deposit_limit),
+			tx.into_input(),
+			None,
+		);
+		let success = output.result.map_or(false, |retval| !retval.did_revert());
+		(success, None, output.gas_meter.gas_consumed(), output.storage_deposit.charge_or_zero())
+	}
+
+	/// Fails with [`Error::AccountUnmapped`] if `account_id` can't be used as a caller.
+	fn ensure_is_mapped(account_id: &T::AccountId) -> DispatchResult {
+		ensure!(T::AddressMapper::is_mapped(account_id), <Error<T>>::AccountUnmapped);
+		Ok(())
+	}
+
+	/// Return the existential deposit of [`Config::Currency`].
+	fn min_balance() -> BalanceOf<T> {
+		<T::Currency as Inspect<AccountIdOf<T>>>::minimum_balance()
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API used to dry-run contract interactions and to serve Ethereum RPC requests.
+	#[api_version(1)]
+	pub trait ReviveApi<AccountId, Balance, EventRecord> where
+		AccountId: Codec,
+		Balance: Codec,
+		EventRecord: Codec,
+	{
+		/// Perform a call from a specified account to a given contract.
+		///
+		/// See [`crate::Pallet::bare_call`].
+		fn call(
+			origin: AccountId,
+			dest: H160,
+			value: Balance,
+			gas_limit: Option<Weight>,
+			storage_deposit_limit: Option<Balance>,
+			input_data: Vec<u8>,
+		) -> ContractExecResult<Balance, EventRecord>;
+
+		/// Instantiate a new contract.
+		///
+		/// See `[crate::Pallet::bare_instantiate]`.
+		fn instantiate(
+			origin: AccountId,
+			value: Balance,
+			gas_limit: Option<Weight>,
+			storage_deposit_limit: Option<Balance>,
+			code: Code,
+			data: Vec<u8>,
+			salt: Option<[u8; 32]>,
+		) -> ContractInstantiateResult<Balance, EventRecord>;
+
+		/// Upload new code without instantiating a contract from it.
+		///
+		/// See [`crate::Pallet::bare_upload_code`].
+		fn upload_code(
+			origin: AccountId,
+			code: Vec<u8>,
+			storage_deposit_limit: Option<Balance>,
+		) -> CodeUploadResult<Balance>;
+
+		/// Query a given storage key in a given contract.
+		///
+		/// Returns `Ok(Some(Vec<u8>))` if the storage value exists under the given key in the
+		/// specified account and `Ok(None)` if it doesn't. If the account specified by the address
+		/// doesn't exist, or doesn't have a contract then `Err` is returned.
+		fn get_storage(
+			address: H160,
+			key: [u8; 32],
+		) -> GetStorageResult;
+
+		/// Returns the EIP-155 chain id of this chain.
+		///
+		/// See [`crate::Config::ChainId`].
+		fn chain_id() -> u64;
+
+		/// Returns the balance of the given address.
+		///
+		/// See [`crate::Pallet::evm_balance`].
+		fn balance(address: H160) -> U256;
+
+		/// Returns the nonce of the given address.
+		///
+		/// See [`crate::Pallet::evm_nonce`].
+		fn nonce(address: H160) -> U256;
+
+		/// Dry run an Ethereum style call or contract creation.
+		///
+		/// See [`crate::Pallet::bare_eth_dry_run`].
+		fn eth_dry_run(
+			from: H160,
+			to: Option<H160>,
+			value: U256,
+			input: Vec<u8>,
+			gas_limit: Option<Weight>,
+		) -> EthContractResult<Balance>;
+
+		/// Wrap a signed Ethereum transaction into an unsigned [`crate::Call::eth_transact`]
+		/// extrinsic which can be submitted to the transaction pool.
+		fn eth_transact(payload: Vec<u8>) -> <Block as BlockT>::Extrinsic;
+
+		/// Returns the receipts of all Ethereum transactions of the current block.
+		///
+		/// See [`crate::Pallet::eth_receipts`].
+		fn eth_receipts() -> Vec<EthReceipt<Balance>>;
+	}
+}
@@ -0,0 +1,81 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Limits that are observable by contract code.
+//!
+//! It is important to never change these limits without supporting the old limits
+//! for already deployed contracts. This is true for either increasing or decreasing the limit.
+//!
+//! Limits in this file are different from the limits configured on the [`crate::Config`] trait
+//! which generally only affect actions that cannot be performed by a contract: For example
+//! uploading new code.
+//!
+//! Exceptions to this rule apply: Limits in the [`code`] module can be increased
+//! without emulating the old values for existing contracts. Reason is that those limits are only
+//! applied **once** at code upload time. Since this action cannot be performed by contracts we
+//! can change those limits without breaking existing contracts. Please keep in mind that we should
+//! only ever **increase** those values but never decrease.
+
+/// The maximum depth of the call stack.
+///
+/// A 0 means that no callings of other contracts are possible. In other words only the origin
+/// called "root contract" is allowed to execute then.
+pub const CALL_STACK_DEPTH: u32 = 5;
+
+/// The maximum number of topics a call to `deposit_event` can emit.
+///
+/// We set it to the same limit that ethereum has. It is unlikely to change.
+pub const NUM_EVENT_TOPICS: u32 = 4;
+
+/// Maximum size of events (excluding topics) and storage values.
+pub const PAYLOAD_BYTES: u32 = 416;
+
+/// The maximum length of a storage key in bytes.
+pub const STORAGE_KEY_BYTES: u32 = 128;
+
+/// The maximum size of the debug buffer contracts can write messages to.
+///
+/// The buffer will always be at least this size and one call to `debug_message` is truncated
+/// to this value.
+pub const DEBUG_BUFFER_BYTES: u32 = 2 * 1024 * 1024;
+
+/// The page size in which PolkaVM should allocate memory chunks.
+pub const PAGE_SIZE: u32 = 4 * 1024;
+
+/// Limits that are only enforced on code upload.
+///
+/// # Note
+///
+/// This limit can be increased later without breaking existing contracts
+/// as it is only enforced at code upload time. Code already uploaded
+/// will not be affected by this limit.
+pub mod code {
+	/// The maximum length of a code blob in bytes.
+	///
+	/// This mostly exist to prevent parsing too big blobs and to
+	/// have a maximum encoded length.
+	pub const BLOB_BYTES: u32 = 256 * 1024;
+
+	/// The maximum amount of memory a contract can have in bytes.
+	///
+	/// The sum of RO data, RW data and the stack is capped by this limit. Keeping the contract
+	/// memory small keeps the total memory a call stack can use bounded.
+	pub const MEMORY_BYTES: u32 = 1024 * 1024;
+
+	/// The maximum amount of imports a contract can declare.
+	pub const IMPORTS: u32 = 64;
+}
@@ -0,0 +1,113 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A crate that hosts a common definitions that are relevant for the pallet-revive.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::weights::Weight;
+use scale_info::TypeInfo;
+use sp_core::{H160, H256};
+use sp_runtime::{DispatchError, RuntimeDebug};
+use sp_std::prelude::*;
+
+pub use pallet_contracts_common::{
+	ContractExecResult, ContractResult, ExecReturnValue, StorageDeposit,
+};
+
+/// Result type of a `bare_instantiate` call as well as `ReviveApi::instantiate`.
+pub type ContractInstantiateResult<Balance, EventRecord> =
+	ContractResult<Result<InstantiateReturnValue, DispatchError>, Balance, EventRecord>;
+
+/// Result type of a `bare_code_upload` call.
+pub type CodeUploadResult<Balance> = Result<CodeUploadReturnValue<Balance>, DispatchError>;
+
+/// Result type of a `get_storage` call.
+pub type GetStorageResult = Result<Option<Vec<u8>>, ContractAccessError>;
+
+/// The possible errors that can happen querying the storage of a contract.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum ContractAccessError {
+	/// The given address doesn't point to a contract.
+	DoesntExist,
+	/// Storage key cannot be decoded from the provided input data.
+	KeyDecodingFailed,
+}
+
+/// The result of a successful contract instantiation.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct InstantiateReturnValue {
+	/// The output of the called constructor.
+	pub result: ExecReturnValue,
+	/// The address of the new contract.
+	pub addr: H160,
+}
+
+/// The result of successfully uploading a contract.
+pub type CodeUploadReturnValue<Balance> =
+	pallet_contracts_common::CodeUploadReturnValue<H256, Balance>;
+
+/// Reference to an existing code hash or a new PolkaVM blob.
+pub type Code = pallet_contracts_common::Code<H256>;
+
+/// Result type of `bare_eth_dry_run` as well as `ReviveApi::eth_dry_run`.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct EthContractResult<Balance> {
+	/// The fee that would be burned from the signer when submitting this transaction.
+	pub fee: Balance,
+	/// How much weight is required as gas limit in order to execute this transaction.
+	pub gas_required: Weight,
+	/// The storage deposit that would be charged from the signer.
+	///
+	/// Refunds are reported as zero because they can't be used to pay for anything else.
+	pub storage_deposit: Balance,
+	/// The execution result of the code.
+	pub result: Result<ExecReturnValue, DispatchError>,
+}
+
+/// A log emitted by a contract during the execution of an Ethereum transaction.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct EthLog {
+	/// The contract that emitted the log.
+	pub address: H160,
+	/// The topics used to index the log.
+	pub topics: Vec<H256>,
+	/// The data supplied by the contract.
+	pub data: Vec<u8>,
+}
+
+/// The outcome of an Ethereum transaction which was included in a block.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct EthReceipt<Balance> {
+	/// The hash under which Ethereum tooling refers to the transaction.
+	pub transaction_hash: H256,
+	/// The index of the extrinsic within the block.
+	pub extrinsic_index: u32,
+	/// The signer of the transaction.
+	pub from: H160,
+	/// The called contract or `None` if a contract was created.
+	pub to: Option<H160>,
+	/// The address of the created contract if any.
+	pub contract_address: Option<H160>,
+	/// The fee that was burned from the signer.
+	pub fee: Balance,
+	/// Whether the execution succeeded.
+	pub success: bool,
+	/// The type of the transaction.
+	pub transaction_type: u8,
+	/// The logs emitted during execution in the order they were emitted.
+	pub logs: Vec<EthLog>,
+}
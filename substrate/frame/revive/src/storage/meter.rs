@@ -0,0 +1,217 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains functions to meter the storage deposit.
+//!
+//! The metering itself is shared with `pallet-contracts` and lives in
+//! [`pallet_contracts_common::storage_meter`]. This module connects it to the storage and the
+//! currency of this pallet.
+
+use crate::{
+	storage::ContractInfo, vm::CodeInfo, AccountIdOf, BalanceOf, Config, Error, HoldReason,
+	Inspect, Origin, Pallet, StorageDeposit as Deposit, System, LOG_TARGET,
+};
+
+use frame_support::{
+	ensure,
+	traits::{
+		fungible::{Mutate, MutateHold},
+		tokens::{
+			Fortitude, Fortitude::Polite, Precision, Preservation, Restriction, WithdrawConsequence,
+		},
+		Get,
+	},
+};
+use pallet_contracts_common::storage_meter::{self, StorageUsage};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	DispatchError,
+};
+
+pub use storage_meter::{ContractState, Diff, Ext, Nested, Root, State};
+
+/// Deposit that uses the native fungible's balance type.
+pub type DepositOf<T> = Deposit<BalanceOf<T>>;
+
+/// A storage meter of this pallet which charges using `E`.
+pub type RawMeter<T, E, S> = storage_meter::RawMeter<Pallet<T>, E, S>;
+
+/// A production root storage meter that actually charges from its origin.
+pub type Meter<T> = RawMeter<T, ReservingExt, Root>;
+
+/// A production nested storage meter that actually charges from its origin.
+pub type NestedMeter<T> = RawMeter<T, ReservingExt, Nested>;
+
+/// A production storage meter that actually charges from its origin.
+///
+/// This can be used where we want to be generic over the state (Root vs. Nested).
+pub type GenericMeter<T, S> = RawMeter<T, ReservingExt, S>;
+
+/// This [`Ext`] is used for actual on-chain execution when balance needs to be charged.
+///
+/// It uses [`frame_support::traits::fungible::Mutate`] in order to do accomplish the reserves.
+pub enum ReservingExt {}
+
+impl<T: Config> storage_meter::Config for Pallet<T> {
+	type AccountId = AccountIdOf<T>;
+	type Balance = BalanceOf<T>;
+	type Origin = Origin<T>;
+	type ContractInfo = ContractInfo<T>;
+	type CodeInfo = CodeInfo<T>;
+
+	fn origin_account(origin: &Origin<T>) -> Option<&AccountIdOf<T>> {
+		match origin {
+			Origin::Root => None,
+			Origin::Signed(account_id) => Some(account_id),
+		}
+	}
+
+	fn default_deposit_limit() -> BalanceOf<T> {
+		T::DefaultDepositLimit::get()
+	}
+
+	fn deposit_per_byte() -> BalanceOf<T> {
+		T::DepositPerByte::get()
+	}
+
+	fn deposit_per_item() -> BalanceOf<T> {
+		T::DepositPerItem::get()
+	}
+
+	fn min_balance() -> BalanceOf<T> {
+		Pallet::<T>::min_balance()
+	}
+
+	fn deposit_limit_exhausted() -> DispatchError {
+		<Error<T>>::StorageDepositLimitExhausted.into()
+	}
+
+	fn storage_usage(info: &mut ContractInfo<T>) -> StorageUsage<'_, BalanceOf<T>> {
+		StorageUsage {
+			bytes: &mut info.storage_bytes,
+			items: &mut info.storage_items,
+			byte_deposit: &mut info.storage_byte_deposit,
+			item_deposit: &mut info.storage_item_deposit,
+		}
+	}
+
+	fn total_deposit(info: &ContractInfo<T>) -> BalanceOf<T> {
+		info.total_deposit()
+	}
+
+	fn update_base_deposit(info: &mut ContractInfo<T>, code_info: &CodeInfo<T>) -> BalanceOf<T> {
+		info.update_base_deposit(code_info)
+	}
+
+	fn create_contract_account(
+		origin: &AccountIdOf<T>,
+		contract: &AccountIdOf<T>,
+	) -> Result<(), DispatchError> {
+		T::Currency::transfer(
+			origin,
+			contract,
+			Pallet::<T>::min_balance(),
+			Preservation::Preserve,
+		)?;
+
+		// A consumer is added at account creation and removed it on termination, otherwise the
+		// runtime could remove the account. As long as a contract exists its account must exist.
+		// With the consumer, a correct runtime cannot remove the account.
+		System::<T>::inc_consumers(contract)?;
+		Ok(())
+	}
+}
+
+impl<T: Config> Ext<Pallet<T>> for ReservingExt {
+	fn check_limit(
+		origin: &T::AccountId,
+		limit: Option<BalanceOf<T>>,
+		min_leftover: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		// We are sending the `min_leftover` and the `min_balance` from the origin
+		// account as part of a contract call. Hence origin needs to have those left over
+		// as free balance after accounting for all deposits.
+		let max = T::Currency::reducible_balance(origin, Preservation::Preserve, Polite)
+			.saturating_sub(min_leftover)
+			.saturating_sub(Pallet::<T>::min_balance());
+		let default = max.min(T::DefaultDepositLimit::get());
+		let limit = limit.unwrap_or(default);
+		ensure!(
+			limit <= max &&
+				matches!(T::Currency::can_withdraw(origin, limit), WithdrawConsequence::Success),
+			<Error<T>>::StorageDepositNotEnoughFunds,
+		);
+		Ok(limit)
+	}
+
+	fn charge(
+		origin: &T::AccountId,
+		contract: &T::AccountId,
+		amount: &DepositOf<T>,
+		state: &ContractState<Pallet<T>>,
+	) -> Result<(), DispatchError> {
+		match amount {
+			Deposit::Charge(amount) | Deposit::Refund(amount) if amount.is_zero() => return Ok(()),
+			Deposit::Charge(amount) => {
+				// This could fail if the `origin` does not have enough liquidity. Ideally, though,
+				// this should have been checked before with `check_limit`.
+				T::Currency::transfer_and_hold(
+					&HoldReason::StorageDepositReserve.into(),
+					origin,
+					contract,
+					*amount,
+					Precision::Exact,
+					Preservation::Preserve,
+					Fortitude::Polite,
+				)?;
+			},
+			Deposit::Refund(amount) => {
+				let transferred = T::Currency::transfer_on_hold(
+					&HoldReason::StorageDepositReserve.into(),
+					contract,
+					origin,
+					*amount,
+					Precision::BestEffort,
+					Restriction::Free,
+					Fortitude::Polite,
+				)?;
+
+				if transferred < *amount {
+					// This should never happen, if it does it means that there is a bug in the
+					// runtime logic. In the rare case this happens we try to refund as much as we
+					// can, thus the `Precision::BestEffort`.
+					log::error!(
+						target: LOG_TARGET,
+						"Failed to repatriate full storage deposit {:?} from contract {:?} to origin {:?}. Transferred {:?}.",
+						amount, contract, origin, transferred,
+					);
+				}
+			},
+		}
+		if let ContractState::Terminated { beneficiary } = state {
+			System::<T>::dec_consumers(&contract);
+			// Whatever is left in the contract is sent to the termination beneficiary.
+			T::Currency::transfer(
+				&contract,
+				&beneficiary,
+				T::Currency::reducible_balance(&contract, Preservation::Expendable, Polite),
+				Preservation::Expendable,
+			)?;
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,874 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use self::test_utils::{get_balance, place_contract, set_balance};
+use crate::{
+	self as pallet_revive, address,
+	evm::{self, Transaction1559Unsigned, TransactionLegacyUnsigned},
+	AccountId32Mapper, AddressMapper, BalanceOf, Code, CodeInfoOf, CollectEvents, Config,
+	ContractAccessError, ContractInfoOf, DebugInfo, DeletionQueueCounter, Error, EthContractResult,
+	EthReceipt, ExecReturnValue, HoldReason, Origin, StorageDeposit,
+};
+use codec::Encode;
+use frame_support::{
+	assert_err, assert_err_ignore_postinfo, assert_noop, assert_ok, derive_impl,
+	dispatch::GetDispatchInfo,
+	parameter_types,
+	storage::{with_transaction, TransactionOutcome},
+	traits::{fungible::InspectHold, ConstU32, ConstU64, Get, OnIdle},
+	weights::{constants::WEIGHT_REF_TIME_PER_SECOND, Weight},
+};
+use frame_system::EnsureSigned;
+use pallet_contracts_uapi::ReturnErrorCode;
+use pallet_revive_fixtures::compile_module;
+use pretty_assertions::assert_eq;
+use sp_core::{ecdsa, Pair, H160, H256, U256};
+use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
+use sp_runtime::{
+	traits::{BlakeTwo256, Convert, IdentityLookup, ValidateUnsigned},
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	AccountId32, BuildStorage, DispatchError, Perbill,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Timestamp: pallet_timestamp,
+		Revive: pallet_revive,
+	}
+);
+
+pub mod test_utils {
+	use super::{Revive, Test};
+	use crate::{
+		address::AddressMapper, vm::CodeInfo, AccountIdOf, CodeInfoOf, Config, ContractInfo,
+		ContractInfoOf, Nonce,
+	};
+	use frame_support::traits::fungible::{Inspect, Mutate};
+	use sp_core::{H160, H256};
+
+	pub fn place_contract(address: &H160, code_hash: H256) {
+		let nonce = <Nonce<Test>>::mutate(|counter| {
+			*counter += 1;
+			*counter
+		});
+		let account_id = <Test as Config>::AddressMapper::to_account_id(address);
+		set_balance(&account_id, Revive::min_balance() * 10);
+		<CodeInfoOf<Test>>::insert(code_hash, CodeInfo::new(account_id));
+		let contract = <ContractInfo<Test>>::new(address, nonce, code_hash).unwrap();
+		<ContractInfoOf<Test>>::insert(address, contract);
+	}
+	pub fn set_balance(who: &AccountIdOf<Test>, amount: u64) {
+		let _ = <Test as Config>::Currency::set_balance(who, amount);
+	}
+	pub fn get_balance(who: &AccountIdOf<Test>) -> u64 {
+		<Test as Config>::Currency::balance(who)
+	}
+}
+
+parameter_types! {
+	pub BlockWeights: frame_system::limits::BlockWeights =
+		frame_system::limits::BlockWeights::simple_max(
+			Weight::from_parts(2u64 * WEIGHT_REF_TIME_PER_SECOND, u64::MAX),
+		);
+	pub static ExistentialDeposit: u64 = 1;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+	type BlockWeights = BlockWeights;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId32;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type BlockHashCount = ConstU64<250>;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub static DepositPerByte: BalanceOf<Test> = 1;
+	pub const DepositPerItem: BalanceOf<Test> = 2;
+	pub static CodeHashLockupDepositPercent: Perbill = Perbill::from_percent(0);
+	pub static DefaultDepositLimit: BalanceOf<Test> = 10_000_000;
+}
+
+/// Charges one unit of balance per microsecond of execution time.
+pub struct WeightToBalance;
+
+impl Convert<Weight, BalanceOf<Test>> for WeightToBalance {
+	fn convert(weight: Weight) -> BalanceOf<Test> {
+		weight.ref_time() / 1_000_000
+	}
+}
+
+impl Config for Test {
+	type Time = Timestamp;
+	type Currency = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type WeightInfo = ();
+	type DepositPerByte = DepositPerByte;
+	type DefaultDepositLimit = DefaultDepositLimit;
+	type DepositPerItem = DepositPerItem;
+	type CodeHashLockupDepositPercent = CodeHashLockupDepositPercent;
+	type AddressMapper = AccountId32Mapper<Self>;
+	type UploadOrigin = EnsureSigned<AccountId32>;
+	type InstantiateOrigin = EnsureSigned<AccountId32>;
+	type ChainId = ConstU64<420_420_420>;
+	type WeightPrice = WeightToBalance;
+}
+
+pub const ALICE: AccountId32 = eth_account(1);
+pub const BOB: AccountId32 = eth_account(2);
+pub const ALICE_ADDR: H160 = H160([1u8; 20]);
+pub const BOB_ADDR: H160 = H160([2u8; 20]);
+pub const CHARLIE_ADDR: H160 = H160([3u8; 20]);
+
+/// An account which is derived from an ethereum key and hence doesn't need a stateful mapping.
+const fn eth_account(byte: u8) -> AccountId32 {
+	let mut account = [0xEE; 32];
+	let mut i = 0;
+	while i < 20 {
+		account[i] = byte;
+		i += 1;
+	}
+	AccountId32::new(account)
+}
+
+/// An `ed25519` or `sr25519` style account which needs to be mapped before it can be used.
+pub const DJANGO: AccountId32 = AccountId32::new([4u8; 32]);
+
+pub const GAS_LIMIT: Weight = Weight::from_parts(100_000_000_000, 3 * 1024 * 1024);
+
+/// The amount the signer of [`eth_transaction`] authorizes for the fee and the storage deposit.
+pub const ETH_MAX_COST: u64 = 10_000;
+
+/// A transaction calling `to` which authorizes [`ETH_MAX_COST`].
+fn eth_transaction(nonce: u64, to: Option<H160>, value: u64) -> TransactionLegacyUnsigned {
+	TransactionLegacyUnsigned {
+		nonce: nonce.into(),
+		gas_price: evm::GAS_PRICE.into(),
+		gas: ETH_MAX_COST.into(),
+		to,
+		value: value.into(),
+		input: vec![],
+		chain_id: Some(<<Test as Config>::ChainId as Get<u64>>::get().into()),
+	}
+}
+
+/// Signs `tx` with a key derived from `seed` and returns the payload and the signer.
+fn sign_eth_transaction(tx: TransactionLegacyUnsigned, seed: u8) -> (Vec<u8>, H160) {
+	let pair = ecdsa::Pair::from_seed(&[seed; 32]);
+	let signature = pair.sign_prehashed(&tx.signing_hash().0);
+	let signed = tx.with_signature(signature.0);
+	(signed.rlp_bytes(), signed.recover_eth_address().unwrap())
+}
+
+/// An EIP-1559 transaction deploying `code` which authorizes `gas` at [`evm::GAS_PRICE`].
+fn eth_deployment(code: Vec<u8>, gas: u64) -> Transaction1559Unsigned {
+	Transaction1559Unsigned {
+		chain_id: <<Test as Config>::ChainId as Get<u64>>::get().into(),
+		nonce: 0.into(),
+		max_priority_fee_per_gas: 0.into(),
+		max_fee_per_gas: evm::GAS_PRICE.into(),
+		gas: gas.into(),
+		to: None,
+		value: 0.into(),
+		input: code,
+		access_list: vec![],
+	}
+}
+
+/// Signs `tx` with a key derived from `seed` and returns the payload.
+fn sign_eth_deployment(tx: Transaction1559Unsigned, seed: u8) -> Vec<u8> {
+	let pair = ecdsa::Pair::from_seed(&[seed; 32]);
+	let signature = pair.sign_prehashed(&tx.signing_hash().0);
+	tx.with_signature(signature.0).rlp_bytes()
+}
+
+/// Dry runs the deployment of `code` by `from` without keeping any changes.
+fn eth_dry_run_deployment(from: H160, code: Vec<u8>) -> EthContractResult<u64> {
+	with_transaction(|| {
+		TransactionOutcome::Rollback(Ok::<_, DispatchError>(Revive::bare_eth_dry_run(
+			from,
+			None,
+			U256::zero(),
+			code,
+			GAS_LIMIT,
+		)))
+	})
+	.unwrap()
+}
+
+pub struct ExtBuilder {
+	existential_deposit: u64,
+	code_hashes: Vec<H256>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self { existential_deposit: ExistentialDeposit::get(), code_hashes: vec![] }
+	}
+}
+
+impl ExtBuilder {
+	pub fn existential_deposit(mut self, existential_deposit: u64) -> Self {
+		self.existential_deposit = existential_deposit;
+		self
+	}
+	pub fn with_code_hashes(mut self, code_hashes: Vec<H256>) -> Self {
+		self.code_hashes = code_hashes;
+		self
+	}
+	pub fn set_associated_consts(&self) {
+		EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = self.existential_deposit);
+	}
+	pub fn build(self) -> sp_io::TestExternalities {
+		sp_tracing::try_init_simple();
+		self.set_associated_consts();
+		let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		pallet_balances::GenesisConfig::<Test> { balances: vec![] }
+			.assimilate_storage(&mut t)
+			.unwrap();
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.register_extension(KeystoreExt::new(MemoryKeystore::new()));
+		ext.execute_with(|| System::set_block_number(1));
+		ext.execute_with(|| {
+			for code_hash in self.code_hashes {
+				CodeInfoOf::<Test>::insert(code_hash, crate::vm::CodeInfo::new(ALICE));
+			}
+		});
+		ext
+	}
+}
+
+#[test]
+fn calling_plain_account_fails() {
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		set_balance(&ALICE, 1_000_000);
+
+		assert_err_ignore_postinfo!(
+			Revive::call(RuntimeOrigin::signed(ALICE), BOB_ADDR, 1_000, GAS_LIMIT, None, vec![]),
+			<Error<Test>>::ContractNotFound,
+		);
+		assert_eq!(get_balance(&BOB), 0);
+		assert_eq!(get_balance(&ALICE), 1_000_000);
+	});
+}
+
+#[test]
+fn unmapped_account_cannot_call() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_balance(&DJANGO, 1_000_000);
+
+		assert_err!(
+			Revive::bare_call(
+				DJANGO,
+				BOB_ADDR,
+				100,
+				GAS_LIMIT,
+				None,
+				vec![],
+				DebugInfo::Skip,
+				CollectEvents::Skip,
+			)
+			.result,
+			<Error<Test>>::AccountUnmapped,
+		);
+
+		// once mapped the call reaches the executor
+		assert_ok!(Revive::map_account(RuntimeOrigin::signed(DJANGO)));
+		assert_err!(
+			Revive::bare_call(
+				DJANGO,
+				BOB_ADDR,
+				100,
+				GAS_LIMIT,
+				None,
+				vec![],
+				DebugInfo::Skip,
+				CollectEvents::Skip,
+			)
+			.result,
+			<Error<Test>>::ContractNotFound,
+		);
+	});
+}
+
+#[test]
+fn map_and_unmap_account_hold_deposit() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_balance(&DJANGO, 1_000_000);
+		let hold_reason = HoldReason::AddressMapping.into();
+
+		assert_ok!(Revive::map_account(RuntimeOrigin::signed(DJANGO)));
+		assert!(Balances::balance_on_hold(&hold_reason, &DJANGO) > 0);
+		assert_noop!(
+			Revive::map_account(RuntimeOrigin::signed(DJANGO)),
+			<Error<Test>>::AccountAlreadyMapped,
+		);
+
+		assert_ok!(Revive::unmap_account(RuntimeOrigin::signed(DJANGO)));
+		assert_eq!(Balances::balance_on_hold(&hold_reason, &DJANGO), 0);
+		assert_eq!(get_balance(&DJANGO), 1_000_000);
+	});
+}
+
+#[test]
+fn upload_rejects_invalid_code() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_balance(&ALICE, 1_000_000);
+
+		assert_noop!(
+			Revive::upload_code(RuntimeOrigin::signed(ALICE), vec![1, 2, 3, 4], None),
+			<Error<Test>>::CodeRejected,
+		);
+		assert_noop!(
+			Revive::bare_instantiate(
+				ALICE,
+				0,
+				GAS_LIMIT,
+				None,
+				Code::Upload(vec![0; 300 * 1024]),
+				vec![],
+				None,
+				DebugInfo::Skip,
+				CollectEvents::Skip,
+			)
+			.result,
+			<Error<Test>>::CodeTooLarge,
+		);
+	});
+}
+
+#[test]
+fn instantiate_unknown_code_hash_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_balance(&ALICE, 1_000_000);
+
+		assert_err!(
+			Revive::bare_instantiate(
+				ALICE,
+				0,
+				GAS_LIMIT,
+				None,
+				Code::Existing(H256::repeat_byte(0x42)),
+				vec![],
+				None,
+				DebugInfo::Skip,
+				CollectEvents::Skip,
+			)
+			.result,
+			<Error<Test>>::CodeNotFound,
+		);
+	});
+}
+
+#[test]
+fn remove_code_checks_owner_and_refcount() {
+	let code_hash = H256::repeat_byte(0x11);
+	ExtBuilder::default()
+		.with_code_hashes(vec![code_hash])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Revive::remove_code(RuntimeOrigin::signed(ALICE), H256::repeat_byte(0x22)),
+				<Error<Test>>::CodeNotFound,
+			);
+			assert_noop!(
+				Revive::remove_code(RuntimeOrigin::signed(BOB), code_hash),
+				DispatchError::BadOrigin,
+			);
+
+			CodeInfoOf::<Test>::mutate(code_hash, |info| {
+				*info.as_mut().unwrap().refcount_mut() = 1
+			});
+			assert_noop!(
+				Revive::remove_code(RuntimeOrigin::signed(ALICE), code_hash),
+				<Error<Test>>::CodeInUse,
+			);
+
+			CodeInfoOf::<Test>::mutate(code_hash, |info| {
+				*info.as_mut().unwrap().refcount_mut() = 0
+			});
+			assert_ok!(Revive::remove_code(RuntimeOrigin::signed(ALICE), code_hash));
+			assert!(!CodeInfoOf::<Test>::contains_key(code_hash));
+			assert_eq!(revive_events(), vec![crate::Event::CodeRemoved { code_hash }]);
+		});
+}
+
+#[test]
+fn get_storage_of_unknown_contract_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			Revive::get_storage(CHARLIE_ADDR, [0u8; 32]),
+			Err(ContractAccessError::DoesntExist),
+		);
+
+		place_contract(&CHARLIE_ADDR, H256::repeat_byte(0x33));
+		assert_eq!(Revive::get_storage(CHARLIE_ADDR, [0u8; 32]), Ok(None));
+		assert_eq!(Revive::code_hash(&CHARLIE_ADDR), Some(H256::repeat_byte(0x33)));
+	});
+}
+
+#[test]
+fn deletion_queue_is_processed_on_idle() {
+	ExtBuilder::default().build().execute_with(|| {
+		place_contract(&CHARLIE_ADDR, H256::repeat_byte(0x33));
+		let info = ContractInfoOf::<Test>::take(CHARLIE_ADDR).unwrap();
+		info.queue_trie_for_deletion();
+		assert_eq!(<DeletionQueueCounter<Test>>::get().as_test_tuple(), (1, 0));
+
+		Revive::on_idle(System::block_number(), Weight::MAX);
+		assert_eq!(<DeletionQueueCounter<Test>>::get().as_test_tuple(), (1, 1));
+	});
+}
+
+#[test]
+fn root_cannot_instantiate() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Revive::instantiate(
+				RuntimeOrigin::root(),
+				0,
+				GAS_LIMIT,
+				None,
+				H256::zero(),
+				vec![],
+				None,
+			),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			Revive::upload_code(RuntimeOrigin::root(), vec![], None),
+			DispatchError::BadOrigin,
+		);
+	});
+}
+
+#[test]
+fn eth_transact_is_validated() {
+	ExtBuilder::default().build().execute_with(|| {
+		let validate = |payload: Vec<u8>| {
+			let call = crate::Call::<Test>::eth_transact { payload };
+			Revive::validate_unsigned(TransactionSource::External, &call)
+				.and_then(|valid| Revive::pre_dispatch(&call).map(|_| valid))
+		};
+		let (payload, from) = sign_eth_transaction(eth_transaction(0, Some(BOB_ADDR), 100), 1);
+		let account_id = <Test as Config>::AddressMapper::to_account_id(&from);
+
+		// the signer can't pay for the transaction
+		assert_eq!(validate(payload.clone()), Err(InvalidTransaction::Payment.into()));
+
+		set_balance(&account_id, 1_000_000);
+		let valid = validate(payload).unwrap();
+		assert_eq!(valid.provides, vec![(evm::TAG_PREFIX, (from, U256::zero())).encode()]);
+		assert!(valid.requires.is_empty());
+
+		// a transaction with a future nonce is accepted by the pool but not in a block
+		let (payload, _) = sign_eth_transaction(eth_transaction(1, Some(BOB_ADDR), 100), 1);
+		let call = crate::Call::<Test>::eth_transact { payload };
+		let valid = Revive::validate_unsigned(TransactionSource::External, &call).unwrap();
+		assert_eq!(valid.requires, vec![(evm::TAG_PREFIX, (from, U256::zero())).encode()]);
+		assert_eq!(Revive::pre_dispatch(&call), Err(InvalidTransaction::Future.into()));
+
+		// the transaction is signed for another chain
+		let mut tx = eth_transaction(0, Some(BOB_ADDR), 100);
+		tx.chain_id = Some(1.into());
+		let (payload, _) = sign_eth_transaction(tx, 1);
+		assert_eq!(validate(payload), Err(InvalidTransaction::BadProof.into()));
+
+		// the signer doesn't authorize enough gas
+		let mut tx = eth_transaction(0, Some(BOB_ADDR), 100);
+		tx.gas = 1.into();
+		let (payload, _) = sign_eth_transaction(tx, 1);
+		assert_eq!(validate(payload), Err(InvalidTransaction::Payment.into()));
+
+		assert_eq!(validate(vec![1, 2, 3]), Err(InvalidTransaction::Call.into()));
+	});
+}
+
+#[test]
+fn failed_eth_transact_pays_fee_and_increments_nonce() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (payload, from) = sign_eth_transaction(eth_transaction(0, Some(BOB_ADDR), 100), 1);
+		let account_id = <Test as Config>::AddressMapper::to_account_id(&from);
+		set_balance(&account_id, 1_000_000);
+		System::note_finished_initialize();
+
+		assert_noop!(
+			Revive::eth_transact(RuntimeOrigin::signed(ALICE), payload.clone()),
+			DispatchError::BadOrigin,
+		);
+		assert_ok!(Revive::eth_transact(RuntimeOrigin::none(), payload.clone()));
+
+		// calling a plain account fails but the nonce and the fee are kept
+		let fee = 1_000_000 - get_balance(&account_id);
+		assert!(fee > 0 && fee <= ETH_MAX_COST);
+		assert_eq!(get_balance(&BOB), 0);
+		assert_eq!(Revive::evm_nonce(&from), U256::one());
+		assert_eq!(Revive::evm_balance(&from), U256::from(1_000_000 - fee));
+		assert_eq!(
+			Revive::eth_receipts(),
+			vec![EthReceipt {
+				transaction_hash: evm::transaction_hash(&payload),
+				extrinsic_index: 0,
+				from,
+				to: Some(BOB_ADDR),
+				contract_address: None,
+				fee,
+				success: false,
+				transaction_type: evm::TYPE_LEGACY,
+				logs: vec![],
+			}],
+		);
+
+		// the transaction can't be replayed
+		assert_err_ignore_postinfo!(
+			Revive::eth_transact(RuntimeOrigin::none(), payload),
+			<Error<Test>>::EthTransactionInvalid,
+		);
+		assert_eq!(Revive::evm_nonce(&from), U256::one());
+	});
+}
+
+#[test]
+fn eth_transact_gas_limit_is_derived_from_transaction() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (payload, _) = sign_eth_transaction(eth_transaction(0, Some(BOB_ADDR), 0), 1);
+		let call = crate::Call::<Test>::eth_transact { payload };
+
+		// the dispatch weight is the largest weight whose fee fits into what the signer authorized
+		let weight = call.get_dispatch_info().weight;
+		assert_eq!(WeightToBalance::convert(weight), ETH_MAX_COST);
+		assert!(WeightToBalance::convert(weight.add_ref_time(1_000_000)) > ETH_MAX_COST);
+	});
+}
+
+#[test]
+fn eth_transact_deploys_eip1559_transaction() {
+	let code = compile_module("dummy").unwrap();
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let from = evm::TransactionSigned::decode(&sign_eth_deployment(
+			eth_deployment(code.clone(), 0),
+			1,
+		))
+		.unwrap()
+		.recover_eth_address()
+		.unwrap();
+		let account_id = <Test as Config>::AddressMapper::to_account_id(&from);
+		set_balance(&account_id, 1_000_000);
+		System::note_finished_initialize();
+
+		// the estimate of the dry run is enough to pay for the fee and the storage deposit
+		let dry_run = eth_dry_run_deployment(from, code.clone());
+		assert_ok!(dry_run.result);
+		let gas = dry_run.fee + dry_run.storage_deposit;
+		let payload = sign_eth_deployment(eth_deployment(code.clone(), gas), 1);
+		assert_ok!(Revive::eth_transact(RuntimeOrigin::none(), payload.clone()));
+
+		let addr = address::create1(&from, 0);
+		assert_eq!(Revive::code_hash(&addr), Some(address::code_hash(&code)));
+		assert_eq!(Revive::evm_nonce(&from), U256::one());
+		assert!(1_000_000 - get_balance(&account_id) <= gas);
+		let receipts = Revive::eth_receipts();
+		assert_eq!(receipts.len(), 1);
+		assert_eq!(receipts[0].transaction_hash, evm::transaction_hash(&payload));
+		assert_eq!(receipts[0].contract_address, Some(addr));
+		assert_eq!(receipts[0].transaction_type, evm::TYPE_EIP1559);
+		assert!(receipts[0].success);
+	});
+}
+
+#[test]
+fn eth_transact_fails_if_storage_deposit_exceeds_authorized_gas() {
+	let code = compile_module("dummy").unwrap();
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let from = evm::TransactionSigned::decode(&sign_eth_deployment(
+			eth_deployment(code.clone(), 0),
+			1,
+		))
+		.unwrap()
+		.recover_eth_address()
+		.unwrap();
+		let account_id = <Test as Config>::AddressMapper::to_account_id(&from);
+		set_balance(&account_id, 1_000_000);
+		System::note_finished_initialize();
+
+		// the gas covers the fee of the execution but not the storage deposit on top
+		let dry_run = eth_dry_run_deployment(from, code.clone());
+		assert!(dry_run.storage_deposit > 0);
+		let payload = sign_eth_deployment(eth_deployment(code.clone(), dry_run.fee), 1);
+		assert_ok!(Revive::eth_transact(RuntimeOrigin::none(), payload));
+
+		// the execution is rolled back but the nonce and the fee are kept
+		let addr = address::create1(&from, 0);
+		assert_eq!(Revive::code_hash(&addr), None);
+		assert!(!CodeInfoOf::<Test>::contains_key(address::code_hash(&code)));
+		assert_eq!(Revive::evm_nonce(&from), U256::one());
+		let fee = 1_000_000 - get_balance(&account_id);
+		assert!(fee > 0 && fee <= dry_run.fee);
+		let receipts = Revive::eth_receipts();
+		assert_eq!(receipts.len(), 1);
+		assert_eq!(receipts[0].contract_address, None);
+		assert!(!receipts[0].success);
+	});
+}
+
+/// The events deposited by the pallet.
+fn revive_events() -> Vec<crate::Event<Test>> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			RuntimeEvent::Revive(event) => Some(event),
+			_ => None,
+		})
+		.collect()
+}
+
+/// Instantiates `code` as ALICE transferring `value` and returns the contract address.
+fn instantiate(code: Code, value: u64, data: Vec<u8>) -> H160 {
+	// The address is derived from the nonce which is incremented by `CheckNonce` for extrinsics.
+	System::inc_account_nonce(&ALICE);
+	Revive::bare_instantiate(
+		ALICE,
+		value,
+		GAS_LIMIT,
+		None,
+		code,
+		data,
+		None,
+		DebugInfo::Skip,
+		CollectEvents::Skip,
+	)
+	.result
+	.unwrap()
+	.addr
+}
+
+/// Calls `dest` as ALICE and returns the result of the execution.
+fn call(dest: H160, data: Vec<u8>) -> ExecReturnValue {
+	Revive::bare_call(ALICE, dest, 0, GAS_LIMIT, None, data, DebugInfo::Skip, CollectEvents::Skip)
+		.result
+		.unwrap()
+}
+
+#[test]
+fn instantiate_and_call_work() {
+	let code = compile_module("dummy").unwrap();
+	let code_hash = address::code_hash(&code);
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		set_balance(&ALICE, 1_000_000);
+
+		let addr = instantiate(Code::Upload(code), 1_000, vec![]);
+		assert_eq!(addr, address::create1(&ALICE_ADDR, 0));
+		assert_eq!(Revive::code_hash(&addr), Some(code_hash));
+		assert!(CodeInfoOf::<Test>::contains_key(code_hash));
+		// the contract receives the transferred value on top of the existential deposit
+		let account_id = <Test as Config>::AddressMapper::to_account_id(&addr);
+		assert_eq!(get_balance(&account_id), 1_100);
+
+		assert_ok!(Revive::call(RuntimeOrigin::signed(ALICE), addr, 10, GAS_LIMIT, None, vec![]));
+		assert_eq!(get_balance(&account_id), 1_110);
+
+		// a second instance can be deployed from the stored code
+		let other = instantiate(Code::Existing(code_hash), 0, vec![]);
+		assert_eq!(other, address::create1(&ALICE_ADDR, 1));
+		assert_eq!(
+			revive_events(),
+			vec![
+				crate::Event::CodeStored { code_hash },
+				crate::Event::Instantiated { deployer: ALICE_ADDR, contract: addr },
+				crate::Event::Called { caller: Origin::from_account_id(ALICE), contract: addr },
+				crate::Event::Instantiated { deployer: ALICE_ADDR, contract: other },
+			],
+		);
+	});
+}
+
+#[test]
+fn storage_is_written_and_read() {
+	let code = compile_module("storage").unwrap();
+	ExtBuilder::default().build().execute_with(|| {
+		set_balance(&ALICE, 1_000_000);
+
+		// the constructor stores its input
+		let addr = instantiate(Code::Upload(code), 0, vec![1, 2, 3]);
+		assert_eq!(Revive::get_storage(addr, [0u8; 32]), Ok(Some(vec![1, 2, 3])));
+
+		let result = call(addr, vec![4, 5, 6, 7]);
+		assert!(!result.did_revert());
+		assert_eq!(result.data, vec![4, 5, 6, 7]);
+		assert_eq!(Revive::get_storage(addr, [0u8; 32]), Ok(Some(vec![4, 5, 6, 7])));
+		assert_eq!(Revive::get_storage(addr, [1u8; 32]), Ok(None));
+	});
+}
+
+#[test]
+fn storage_deposit_is_charged() {
+	let code = compile_module("storage").unwrap();
+	ExtBuilder::default().build().execute_with(|| {
+		set_balance(&ALICE, 1_000_000);
+		let addr = instantiate(Code::Upload(code), 0, vec![1]);
+		let account_id = <Test as Config>::AddressMapper::to_account_id(&addr);
+		let deposit = || {
+			<Test as Config>::Currency::balance_on_hold(
+				&HoldReason::StorageDepositReserve.into(),
+				&account_id,
+			)
+		};
+		let before = deposit();
+
+		// growing the value by three bytes charges three bytes worth of deposit
+		let result = Revive::bare_call(
+			ALICE,
+			addr,
+			0,
+			GAS_LIMIT,
+			None,
+			vec![1, 2, 3, 4],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		);
+		assert_eq!(result.storage_deposit, StorageDeposit::Charge(3 * DepositPerByte::get()));
+		assert_eq!(deposit(), before + 3 * DepositPerByte::get());
+
+		// shrinking it refunds the deposit
+		let result = Revive::bare_call(
+			ALICE,
+			addr,
+			0,
+			GAS_LIMIT,
+			None,
+			vec![1],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		);
+		assert_eq!(result.storage_deposit, StorageDeposit::Refund(3 * DepositPerByte::get()));
+		assert_eq!(deposit(), before);
+	});
+}
+
+#[test]
+fn transfer_from_contract_works() {
+	let code = compile_module("transfer").unwrap();
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		set_balance(&ALICE, 1_000_000);
+		let addr = instantiate(Code::Upload(code), 1_000, vec![]);
+		let account_id = <Test as Config>::AddressMapper::to_account_id(&addr);
+
+		let transfer = |value: u64| {
+			let mut input = BOB_ADDR.as_bytes().to_vec();
+			let mut value_bytes = [0u8; 32];
+			U256::from(value).to_little_endian(&mut value_bytes);
+			input.extend_from_slice(&value_bytes);
+			let result = call(addr, input);
+			u32::from_le_bytes(result.data.try_into().unwrap())
+		};
+
+		assert_eq!(transfer(100), u32::from(ReturnErrorCode::Success));
+		assert_eq!(get_balance(&BOB), 100);
+		assert_eq!(get_balance(&account_id), 950);
+
+		// the contract can't transfer more than it owns
+		assert_eq!(transfer(10_000), u32::from(ReturnErrorCode::TransferFailed));
+		assert_eq!(get_balance(&BOB), 100);
+		assert_eq!(get_balance(&account_id), 950);
+	});
+}
+
+#[test]
+fn revert_discards_changes() {
+	let code = compile_module("revert").unwrap();
+	ExtBuilder::default().build().execute_with(|| {
+		set_balance(&ALICE, 1_000_000);
+		let addr = instantiate(Code::Upload(code), 0, vec![]);
+
+		let result = call(addr, vec![1, 2, 3]);
+		assert!(result.did_revert());
+		assert_eq!(result.data, vec![1, 2, 3]);
+		assert_eq!(Revive::get_storage(addr, [0u8; 32]), Ok(None));
+
+		// a reverted transaction is rolled back as a whole
+		assert_err_ignore_postinfo!(
+			Revive::call(RuntimeOrigin::signed(ALICE), addr, 100, GAS_LIMIT, None, vec![1]),
+			<Error<Test>>::ContractReverted,
+		);
+		let account_id = <Test as Config>::AddressMapper::to_account_id(&addr);
+		assert_eq!(get_balance(&account_id), Revive::min_balance());
+	});
+}
+
+#[test]
+fn contract_events_are_deposited() {
+	let code = compile_module("event").unwrap();
+	ExtBuilder::default().build().execute_with(|| {
+		set_balance(&ALICE, 1_000_000);
+		let addr = instantiate(Code::Upload(code), 0, vec![]);
+		System::reset_events();
+
+		assert_ok!(Revive::call(
+			RuntimeOrigin::signed(ALICE),
+			addr,
+			0,
+			GAS_LIMIT,
+			None,
+			vec![42, 43]
+		));
+		assert_eq!(
+			revive_events(),
+			vec![
+				crate::Event::ContractEmitted {
+					contract: addr,
+					data: vec![42, 43],
+					topics: vec![H256::repeat_byte(0x11)],
+				},
+				crate::Event::Called { caller: Origin::from_account_id(ALICE), contract: addr },
+			],
+		);
+	});
+}
@@ -0,0 +1,365 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides a means for executing contracts
+//! represented in PolkaVM.
+
+mod runtime;
+
+pub use crate::vm::runtime::Runtime;
+#[cfg(feature = "runtime-benchmarks")]
+pub(crate) use crate::vm::runtime::TrapReason;
+pub(crate) use crate::vm::runtime::{balance_to_u256, u256_to_balance};
+
+use crate::{
+	address::code_hash,
+	exec::{ExecResult, Executable, ExportedFunction, Ext},
+	gas::{GasMeter, Token},
+	limits,
+	weights::WeightInfo,
+	AccountIdOf, BadOrigin, BalanceOf, CodeInfoOf, CodeVec, Config, Error, Event, HoldReason,
+	Pallet, PristineCode, Weight, LOG_TARGET,
+};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	traits::{fungible::MutateHold, tokens::Precision::BestEffort, Get},
+};
+use sp_core::H256;
+use sp_runtime::{traits::Saturating, DispatchError};
+use sp_std::prelude::*;
+
+/// Sentinel value used by contracts to signal that an optional pointer was not supplied
+/// or that a storage item does not exist.
+pub const SENTINEL: u32 = u32::MAX;
+
+/// Validated PolkaVM program blob ready for execution.
+/// This data structure is immutable once created and stored.
+#[derive(Encode, Decode, scale_info::TypeInfo)]
+#[codec(mel_bound())]
+#[scale_info(skip_type_params(T))]
+pub struct PolkaVmBlob<T: Config> {
+	code: CodeVec,
+	// This isn't needed for contract execution and is not stored alongside it.
+	#[codec(skip)]
+	code_info: CodeInfo<T>,
+	// This is for not calculating the hash every time we need it.
+	#[codec(skip)]
+	code_hash: H256,
+}
+
+/// Contract code related data, such as:
+///
+/// - owner of the contract, i.e. account uploaded its code,
+/// - storage deposit amount,
+/// - reference count,
+/// - length of the code.
+///
+/// It is stored in a separate storage entry to avoid loading the code when not necessary.
+#[derive(Clone, Encode, Decode, scale_info::TypeInfo, MaxEncodedLen)]
+#[codec(mel_bound())]
+#[scale_info(skip_type_params(T))]
+pub struct CodeInfo<T: Config> {
+	/// The account that has uploaded the contract code and hence is allowed to remove it.
+	owner: AccountIdOf<T>,
+	/// The amount of balance that was deposited by the owner in order to store it on-chain.
+	#[codec(compact)]
+	deposit: BalanceOf<T>,
+	/// The number of instantiated contracts that use this as their code.
+	#[codec(compact)]
+	refcount: u64,
+	/// Length of the code in bytes.
+	code_len: u32,
+}
+
+/// Cost of code loading from storage.
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[derive(Clone, Copy)]
+struct CodeLoadToken(u32);
+
+impl<T: Config> Token<T> for CodeLoadToken {
+	fn weight(&self) -> Weight {
+		// When loading the contract, we already covered the general costs of
+		// calling the storage but still need to account for the actual size of the
+		// contract code. This is why we subtract `T::*::(0)`. We need to do this at this
+		// point because when charging the general weight for calling the contract we don't know the
+		// size of the contract.
+		T::WeightInfo::call_with_code_per_byte(self.0)
+			.saturating_sub(T::WeightInfo::call_with_code_per_byte(0))
+	}
+}
+
+impl<T: Config> PolkaVmBlob<T> {
+	/// Create the blob by checking the `code`.
+	///
+	/// The code is rejected if it is not a valid PolkaVM program, does not export the
+	/// `call` and `deploy` entry points, imports unknown syscalls or exceeds the
+	/// limits defined in [`limits::code`].
+	pub fn from_code(code: Vec<u8>, owner: AccountIdOf<T>) -> Result<Self, DispatchError> {
+		let code: CodeVec = code.try_into().map_err(|_| <Error<T>>::CodeTooLarge)?;
+		Self::validate(&code)?;
+
+		let code_len = code.len() as u32;
+		let bytes_added = code_len.saturating_add(<CodeInfo<T>>::max_encoded_len() as u32);
+		let deposit = T::DepositPerByte::get()
+			.saturating_mul(bytes_added.into())
+			.saturating_add(T::DepositPerItem::get().saturating_mul(2u32.into()));
+		let code_info = CodeInfo { owner, deposit, refcount: 0, code_len };
+		let code_hash = code_hash(&code);
+
+		Ok(Self { code, code_info, code_hash })
+	}
+
+	/// Check that `code` can be loaded and executed by PolkaVM.
+	fn validate(code: &[u8]) -> DispatchResult {
+		let program = polkavm::ProgramBlob::parse(code.into()).map_err(|err| {
+			log::debug!(target: LOG_TARGET, "failed to parse polkavm blob: {err:?}");
+			Error::<T>::CodeRejected
+		})?;
+
+		for entry in [ExportedFunction::Call, ExportedFunction::Constructor] {
+			ensure!(
+				program
+					.exports()
+					.any(|export| export.symbol().as_bytes() == entry.identifier().as_bytes()),
+				{
+					log::debug!(
+						target: LOG_TARGET,
+						"missing export `{}` in polkavm blob",
+						entry.identifier(),
+					);
+					Error::<T>::CodeRejected
+				}
+			);
+		}
+
+		let imports = program.imports();
+		ensure!(imports.len() <= limits::code::IMPORTS, Error::<T>::CodeRejected);
+		for import in imports.iter() {
+			let Some(import) = import else { return Err(Error::<T>::CodeRejected.into()) };
+			ensure!(runtime::is_known_syscall(import.as_bytes()), {
+				log::debug!(
+					target: LOG_TARGET,
+					"polkavm blob imports unknown syscall: {:?}",
+					core::str::from_utf8(import.as_bytes()),
+				);
+				Error::<T>::CodeRejected
+			});
+		}
+
+		let memory_size = program
+			.ro_data_size()
+			.saturating_add(program.rw_data_size())
+			.saturating_add(program.stack_size());
+		ensure!(memory_size <= limits::code::MEMORY_BYTES, {
+			log::debug!(target: LOG_TARGET, "polkavm blob uses too much memory: {memory_size}");
+			Error::<T>::CodeRejected
+		});
+
+		Ok(())
+	}
+
+	/// Remove the code from storage and refund the deposit to its owner.
+	///
+	/// Applies all necessary checks before removing the code.
+	pub fn remove(origin: &T::AccountId, code_hash: H256) -> DispatchResult {
+		<CodeInfoOf<T>>::try_mutate_exists(&code_hash, |existing| {
+			if let Some(code_info) = existing {
+				ensure!(code_info.refcount == 0, <Error<T>>::CodeInUse);
+				ensure!(&code_info.owner == origin, BadOrigin);
+				let _ = T::Currency::release(
+					&HoldReason::CodeUploadDepositReserve.into(),
+					&code_info.owner,
+					code_info.deposit,
+					BestEffort,
+				);
+
+				*existing = None;
+				<PristineCode<T>>::remove(&code_hash);
+				<Pallet<T>>::deposit_event(Event::CodeRemoved { code_hash });
+				Ok(())
+			} else {
+				Err(<Error<T>>::CodeNotFound.into())
+			}
+		})
+	}
+
+	/// Puts the blob into storage, and returns the deposit collected for the storage.
+	pub fn store_code(&mut self) -> Result<BalanceOf<T>, Error<T>> {
+		let code_hash = *self.code_hash();
+		<CodeInfoOf<T>>::mutate(code_hash, |stored_code_info| {
+			match stored_code_info {
+				// Contract code is already stored in storage. Nothing to be done here.
+				Some(_) => Ok(Default::default()),
+				// Upload a new contract code.
+				// We need to store the code and its code_info, and collect the deposit.
+				// This `None` case happens only with freshly uploaded modules. This means that
+				// the `owner` is always the origin of the current transaction.
+				None => {
+					let deposit = self.code_info.deposit;
+					T::Currency::hold(
+						&HoldReason::CodeUploadDepositReserve.into(),
+						&self.code_info.owner,
+						deposit,
+					)
+					.map_err(|_| <Error<T>>::StorageDepositNotEnoughFunds)?;
+
+					self.code_info.refcount = 0;
+					<PristineCode<T>>::insert(code_hash, &self.code);
+					*stored_code_info = Some(self.code_info.clone());
+					<Pallet<T>>::deposit_event(Event::CodeStored { code_hash });
+					Ok(deposit)
+				},
+			}
+		})
+	}
+}
+
+impl<T: Config> CodeInfo<T> {
+	#[cfg(test)]
+	pub fn new(owner: T::AccountId) -> Self {
+		CodeInfo { owner, deposit: Default::default(), refcount: 0, code_len: 0 }
+	}
+
+	/// Returns reference count of the module.
+	pub fn refcount(&self) -> u64 {
+		self.refcount
+	}
+
+	/// Return mutable reference to the refcount of the module.
+	pub fn refcount_mut(&mut self) -> &mut u64 {
+		&mut self.refcount
+	}
+
+	/// Returns the deposit of the module.
+	pub fn deposit(&self) -> BalanceOf<T> {
+		self.deposit
+	}
+}
+
+impl<T: Config> Executable<T> for PolkaVmBlob<T> {
+	fn from_storage(code_hash: H256, gas_meter: &mut GasMeter<T>) -> Result<Self, DispatchError> {
+		let code_info = <CodeInfoOf<T>>::get(code_hash).ok_or(Error::<T>::CodeNotFound)?;
+		gas_meter.charge(CodeLoadToken(code_info.code_len))?;
+		let code = <PristineCode<T>>::get(code_hash).ok_or(Error::<T>::CodeNotFound)?;
+		Ok(Self { code, code_info, code_hash })
+	}
+
+	fn execute<E: Ext<T = T>>(
+		self,
+		ext: &mut E,
+		function: &ExportedFunction,
+		input_data: Vec<u8>,
+	) -> ExecResult {
+		let mut config = polkavm::Config::default();
+		config.set_backend(Some(polkavm::BackendKind::Interpreter));
+		let engine = polkavm::Engine::new(&config).expect(
+			"on-chain (no_std) use of interpreter is hard coded.
+				interpreter is available on all plattforms; qed",
+		);
+
+		let mut module_config = polkavm::ModuleConfig::new();
+		module_config.set_page_size(limits::PAGE_SIZE);
+		module_config.set_gas_metering(Some(polkavm::GasMeteringKind::Sync));
+		let module = polkavm::Module::new(&engine, &module_config, self.code.into_inner().into())
+			.map_err(|err| {
+			log::debug!(target: LOG_TARGET, "failed to create polkavm module: {err:?}");
+			Error::<T>::CodeRejected
+		})?;
+
+		let entry_program_counter = module
+			.exports()
+			.find(|export| export.symbol().as_bytes() == function.identifier().as_bytes())
+			.ok_or_else(|| {
+				log::error!(target: LOG_TARGET, "failed to find entry point");
+				Error::<T>::CodeRejected
+			})?
+			.program_counter();
+
+		let mut instance = module.instantiate().map_err(|err| {
+			log::debug!(target: LOG_TARGET, "failed to instantiate polkavm module: {err:?}");
+			Error::<T>::CodeRejected
+		})?;
+
+		// Start function should already see the correct refcount in case it will be ever
+		// inspected.
+		if let &ExportedFunction::Constructor = function {
+			E::increment_refcount(self.code_hash)?;
+		}
+
+		// Sync this frame's gas meter with the engine's one.
+		let gas_limit_polkavm = ext.gas_meter_mut().sync_to_executor();
+		instance.set_gas(gas_limit_polkavm);
+		instance.prepare_call_untyped(entry_program_counter, &[]);
+
+		let mut runtime = Runtime::new(ext, input_data);
+		let exec_result = loop {
+			let interrupt = instance.run();
+			if let Some(exec_result) = runtime.handle_interrupt(interrupt, &module, &mut instance) {
+				break exec_result
+			}
+		};
+		let gas_left = instance.gas();
+		runtime.ext().gas_meter_mut().sync_from_executor(gas_left)?;
+		exec_result
+	}
+
+	fn code(&self) -> &[u8] {
+		self.code.as_ref()
+	}
+
+	fn code_hash(&self) -> &H256 {
+		&self.code_hash
+	}
+
+	fn code_info(&self) -> &CodeInfo<T> {
+		&self.code_info
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{Test, ALICE};
+	use frame_support::assert_err;
+
+	#[test]
+	fn invalid_blob_is_rejected() {
+		assert_err!(
+			PolkaVmBlob::<Test>::from_code(vec![0, 1, 2, 3], ALICE).map(|_| ()),
+			Error::<Test>::CodeRejected,
+		);
+	}
+
+	#[test]
+	fn too_large_blob_is_rejected() {
+		assert_err!(
+			PolkaVmBlob::<Test>::from_code(vec![0; limits::code::BLOB_BYTES as usize + 1], ALICE)
+				.map(|_| ()),
+			Error::<Test>::CodeTooLarge,
+		);
+	}
+
+	#[test]
+	fn known_syscalls_are_accepted() {
+		assert!(runtime::is_known_syscall(b"seal_return"));
+		assert!(runtime::is_known_syscall(b"call"));
+		assert!(!runtime::is_known_syscall(b"call_runtime"));
+		assert!(!runtime::is_known_syscall(b""));
+	}
+}
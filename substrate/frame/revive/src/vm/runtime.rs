@@ -0,0 +1,1226 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Environment definition of the PolkaVM smart-contract runtime.
+//!
+//! Contracts call into the runtime through `ecalli` instructions. Each syscall is identified by
+//! the symbol of its import and receives its arguments in the registers `a0` to `a5`. Syscalls
+//! which need more arguments than registers are available receive a pointer to a SCALE encoded
+//! tuple of their arguments instead. A result, if any, is returned in `a0`.
+//!
+//! Balances, block numbers and timestamps are exchanged with contracts as 32 byte little endian
+//! encoded unsigned integers. Accounts are exchanged as 20 byte Ethereum addresses.
+
+use crate::{
+	address::AddressMapper,
+	exec::{ExecError, ExecResult, Ext, Key},
+	gas::{ChargedAmount, Token},
+	limits,
+	primitives::ExecReturnValue,
+	vm::SENTINEL,
+	weights::WeightInfo,
+	BalanceOf, Config, Error, LOG_TARGET,
+};
+use codec::{Decode, DecodeLimit, Encode, MaxEncodedLen};
+use frame_support::{ensure, traits::Get, weights::Weight};
+use pallet_contracts_uapi::{CallFlags, ReturnErrorCode, ReturnFlags};
+use sp_core::{H160, H256, U256};
+use sp_io::hashing::{blake2_128, blake2_256, keccak_256, sha2_256};
+use sp_runtime::{
+	traits::{SaturatedConversion, Zero},
+	DispatchError, RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+/// The maximum nesting depth a contract can use when encoding types.
+const MAX_DECODE_NESTING: u32 = 256;
+
+/// All syscalls a contract can import.
+///
+/// Code which imports a symbol not contained in this list is rejected on upload.
+const SYSCALLS: &[&[u8]] = &[
+	b"input",
+	b"seal_return",
+	b"caller",
+	b"address",
+	b"balance",
+	b"value_transferred",
+	b"minimum_balance",
+	b"now",
+	b"block_number",
+	b"chain_id",
+	b"gas_left",
+	b"set_storage",
+	b"clear_storage",
+	b"get_storage",
+	b"contains_storage",
+	b"take_storage",
+	b"transfer",
+	b"call",
+	b"delegate_call",
+	b"instantiate",
+	b"terminate",
+	b"deposit_event",
+	b"hash_sha2_256",
+	b"hash_keccak_256",
+	b"hash_blake2_256",
+	b"hash_blake2_128",
+	b"debug_message",
+	b"ecdsa_recover",
+	b"ecdsa_to_eth_address",
+	b"caller_is_origin",
+	b"caller_is_root",
+	b"is_contract",
+	b"code_hash",
+	b"own_code_hash",
+	b"reentrance_count",
+	b"account_reentrance_count",
+];
+
+/// Returns `true` if a contract is allowed to import the syscall with the given `name`.
+pub fn is_known_syscall(name: &[u8]) -> bool {
+	SYSCALLS.contains(&name)
+}
+
+/// Abstraction over the memory of a contract.
+pub trait Memory<T: Config> {
+	/// Read designated chunk from the sandbox memory into the supplied buffer.
+	///
+	/// Returns `Err` if one of the following conditions occurs:
+	///
+	/// - requested buffer is not within the bounds of the sandbox memory.
+	fn read_into_buf(&self, ptr: u32, buf: &mut [u8]) -> Result<(), DispatchError>;
+
+	/// Write the given buffer to the designated location in the sandbox memory.
+	///
+	/// Returns `Err` if one of the following conditions occurs:
+	///
+	/// - designated area is not within the bounds of the sandbox memory.
+	fn write(&mut self, ptr: u32, buf: &[u8]) -> Result<(), DispatchError>;
+
+	/// Read designated chunk from the sandbox memory.
+	///
+	/// Returns `Err` if one of the following conditions occurs:
+	///
+	/// - requested buffer is not within the bounds of the sandbox memory.
+	fn read(&self, ptr: u32, len: u32) -> Result<Vec<u8>, DispatchError> {
+		ensure!(len <= limits::code::MEMORY_BYTES, Error::<T>::OutOfBounds);
+		let mut buf = vec![0u8; len as usize];
+		self.read_into_buf(ptr, buf.as_mut_slice())?;
+		Ok(buf)
+	}
+
+	/// Same as [`Self::read`] but reads into a fixed size buffer.
+	fn read_array<const N: usize>(&self, ptr: u32) -> Result<[u8; N], DispatchError> {
+		let mut buf = [0u8; N];
+		self.read_into_buf(ptr, &mut buf)?;
+		Ok(buf)
+	}
+
+	/// Read a 32 byte little endian encoded unsigned integer.
+	fn read_u256(&self, ptr: u32) -> Result<U256, DispatchError> {
+		let buf: [u8; 32] = self.read_array(ptr)?;
+		Ok(U256::from_little_endian(&buf))
+	}
+
+	/// Read a 20 byte Ethereum address.
+	fn read_h160(&self, ptr: u32) -> Result<H160, DispatchError> {
+		self.read_array(ptr).map(H160)
+	}
+
+	/// Read a 32 byte hash.
+	fn read_h256(&self, ptr: u32) -> Result<H256, DispatchError> {
+		self.read_array(ptr).map(H256)
+	}
+
+	/// Reads and decodes a type with a size fixed at compile time from contract memory.
+	///
+	/// # Note
+	///
+	/// The weight of reading a fixed value is included in the overall weight of any
+	/// contract callable function.
+	fn read_as<D: Decode + MaxEncodedLen>(&self, ptr: u32) -> Result<D, DispatchError> {
+		let buf = self.read(ptr, D::max_encoded_len() as u32)?;
+		let decoded = D::decode_with_depth_limit(MAX_DECODE_NESTING, &mut buf.as_ref())
+			.map_err(|_| DispatchError::from(Error::<T>::DecodingFailed))?;
+		Ok(decoded)
+	}
+}
+
+impl<T: Config> Memory<T> for polkavm::RawInstance {
+	fn read_into_buf(&self, ptr: u32, buf: &mut [u8]) -> Result<(), DispatchError> {
+		self.read_memory_into(ptr, buf)
+			.map(|_| ())
+			.map_err(|_| Error::<T>::OutOfBounds.into())
+	}
+
+	fn write(&mut self, ptr: u32, buf: &[u8]) -> Result<(), DispatchError> {
+		self.write_memory(ptr, buf).map_err(|_| Error::<T>::OutOfBounds.into())
+	}
+}
+
+/// Allows the benchmarks to invoke syscalls with a plain buffer as contract memory.
+#[cfg(feature = "runtime-benchmarks")]
+impl<T: Config> Memory<T> for [u8] {
+	fn read_into_buf(&self, ptr: u32, buf: &mut [u8]) -> Result<(), DispatchError> {
+		let ptr = ptr as usize;
+		let bound_checked =
+			self.get(ptr..ptr.saturating_add(buf.len())).ok_or(Error::<T>::OutOfBounds)?;
+		buf.copy_from_slice(bound_checked);
+		Ok(())
+	}
+
+	fn write(&mut self, ptr: u32, buf: &[u8]) -> Result<(), DispatchError> {
+		let ptr = ptr as usize;
+		let bound_checked = self
+			.get_mut(ptr..ptr.saturating_add(buf.len()))
+			.ok_or(Error::<T>::OutOfBounds)?;
+		bound_checked.copy_from_slice(buf);
+		Ok(())
+	}
+}
+
+/// The data passed through when a contract uses `seal_return`.
+#[derive(RuntimeDebug)]
+pub struct ReturnData {
+	/// The flags as passed through by the contract. They are still unchecked and
+	/// will later be parsed into a `ReturnFlags` bitflags struct.
+	flags: u32,
+	/// The output buffer passed by the contract as return data.
+	data: Vec<u8>,
+}
+
+/// Enumerates all possible reasons why a trap was generated.
+///
+/// This is either used to supply the caller with more information about why an error
+/// occurred (the SupervisorError variant).
+/// The other case is where the trap does not constitute an error but rather was invoked
+/// as a quick way to terminate the application (all other variants).
+#[derive(RuntimeDebug)]
+pub enum TrapReason {
+	/// The supervisor trapped the contract because of an error condition occurred during
+	/// execution in privileged code.
+	SupervisorError(DispatchError),
+	/// Signals that trap was generated in response to call `seal_return` host function.
+	Return(ReturnData),
+	/// Signals that a trap was generated in response to a successful call to the
+	/// `terminate` host function.
+	Termination,
+}
+
+impl<T: Into<DispatchError>> From<T> for TrapReason {
+	fn from(from: T) -> Self {
+		Self::SupervisorError(from.into())
+	}
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[derive(Copy, Clone)]
+pub enum RuntimeCosts {
+	/// Weight charged for copying data from the sandbox.
+	CopyFromContract(u32),
+	/// Weight charged for copying data to the sandbox.
+	CopyToContract(u32),
+	/// Weight of calling `caller`.
+	Caller,
+	/// Weight of calling `is_contract`.
+	IsContract,
+	/// Weight of calling `code_hash`.
+	CodeHash,
+	/// Weight of calling `own_code_hash`.
+	OwnCodeHash,
+	/// Weight of calling `caller_is_origin`.
+	CallerIsOrigin,
+	/// Weight of calling `caller_is_root`.
+	CallerIsRoot,
+	/// Weight of calling `address`.
+	Address,
+	/// Weight of calling `gas_left`.
+	GasLeft,
+	/// Weight of calling `balance`.
+	Balance,
+	/// Weight of calling `value_transferred`.
+	ValueTransferred,
+	/// Weight of calling `minimum_balance`.
+	MinimumBalance,
+	/// Weight of calling `block_number`.
+	BlockNumber,
+	/// Weight of calling `now`.
+	Now,
+	/// Weight of calling `chain_id`.
+	ChainId,
+	/// Weight of calling `input` without the weight of copying the input.
+	InputBase,
+	/// Weight of calling `seal_return` for the given output size.
+	Return(u32),
+	/// Weight of calling `terminate`.
+	Terminate,
+	/// Weight of calling `deposit_event` with the given number of topics and event size.
+	DepositEvent { num_topic: u32, len: u32 },
+	/// Weight of calling `debug_message` per byte of passed message.
+	DebugMessage(u32),
+	/// Weight of calling `set_storage` for the given storage item sizes.
+	SetStorage { old_bytes: u32, new_bytes: u32 },
+	/// Weight of calling `clear_storage` per cleared byte.
+	ClearStorage(u32),
+	/// Weight of calling `contains_storage` per byte of the checked item.
+	ContainsStorage(u32),
+	/// Weight of calling `get_storage` with the specified size in storage.
+	GetStorage(u32),
+	/// Weight of calling `take_storage` for the given size.
+	TakeStorage(u32),
+	/// Weight of calling `transfer`.
+	Transfer,
+	/// Base weight of calling `call`.
+	CallBase,
+	/// Weight of calling `delegate_call`.
+	DelegateCallBase,
+	/// Weight of the transfer performed during a call.
+	CallTransferSurcharge,
+	/// Weight per byte that is cloned by supplying the `CLONE_INPUT` flag.
+	CallInputCloned(u32),
+	/// Weight of calling `instantiate` for the given input length.
+	Instantiate { input_data_len: u32 },
+	/// Weight of calling `hash_sha_256` for the given input size.
+	HashSha256(u32),
+	/// Weight of calling `hash_keccak_256` for the given input size.
+	HashKeccak256(u32),
+	/// Weight of calling `hash_blake2_256` for the given input size.
+	HashBlake256(u32),
+	/// Weight of calling `hash_blake2_128` for the given input size.
+	HashBlake128(u32),
+	/// Weight of calling `ecdsa_recover`.
+	EcdsaRecovery,
+	/// Weight of calling `ecdsa_to_eth_address`
+	EcdsaToEthAddress,
+	/// Weight of calling `reentrance_count`
+	ReentrantCount,
+	/// Weight of calling `account_reentrance_count`
+	AccountEntranceCount,
+}
+
+impl<T: Config> Token<T> for RuntimeCosts {
+	fn weight(&self) -> Weight {
+		use self::RuntimeCosts::*;
+		match *self {
+			CopyFromContract(len) => T::WeightInfo::seal_return(len),
+			CopyToContract(len) => T::WeightInfo::seal_input(len),
+			Caller => T::WeightInfo::seal_caller(),
+			IsContract => T::WeightInfo::seal_is_contract(),
+			CodeHash => T::WeightInfo::seal_code_hash(),
+			OwnCodeHash => T::WeightInfo::seal_own_code_hash(),
+			CallerIsOrigin => T::WeightInfo::seal_caller_is_origin(),
+			CallerIsRoot => T::WeightInfo::seal_caller_is_root(),
+			Address => T::WeightInfo::seal_address(),
+			GasLeft => T::WeightInfo::seal_gas_left(),
+			Balance => T::WeightInfo::seal_balance(),
+			ValueTransferred => T::WeightInfo::seal_value_transferred(),
+			MinimumBalance => T::WeightInfo::seal_minimum_balance(),
+			BlockNumber => T::WeightInfo::seal_block_number(),
+			Now => T::WeightInfo::seal_now(),
+			ChainId => T::WeightInfo::seal_chain_id(),
+			InputBase => T::WeightInfo::seal_input(0),
+			Return(len) => T::WeightInfo::seal_return(len),
+			Terminate => T::WeightInfo::seal_terminate(),
+			DepositEvent { num_topic, len } => T::WeightInfo::seal_deposit_event(num_topic, len),
+			DebugMessage(len) => T::WeightInfo::seal_debug_message(len),
+			SetStorage { new_bytes, old_bytes } =>
+				T::WeightInfo::seal_set_storage(new_bytes, old_bytes),
+			ClearStorage(len) => T::WeightInfo::seal_clear_storage(len),
+			ContainsStorage(len) => T::WeightInfo::seal_contains_storage(len),
+			GetStorage(len) => T::WeightInfo::seal_get_storage(len),
+			TakeStorage(len) => T::WeightInfo::seal_take_storage(len),
+			Transfer => T::WeightInfo::seal_transfer(),
+			CallBase => T::WeightInfo::seal_call(0, 0),
+			DelegateCallBase => T::WeightInfo::seal_delegate_call(),
+			CallTransferSurcharge =>
+				T::WeightInfo::seal_call(1, 0).saturating_sub(T::WeightInfo::seal_call(0, 0)),
+			CallInputCloned(len) =>
+				T::WeightInfo::seal_call(0, len).saturating_sub(T::WeightInfo::seal_call(0, 0)),
+			Instantiate { input_data_len } => T::WeightInfo::seal_instantiate(0, input_data_len),
+			HashSha256(len) => T::WeightInfo::seal_hash_sha2_256(len),
+			HashKeccak256(len) => T::WeightInfo::seal_hash_keccak_256(len),
+			HashBlake256(len) => T::WeightInfo::seal_hash_blake2_256(len),
+			HashBlake128(len) => T::WeightInfo::seal_hash_blake2_128(len),
+			EcdsaRecovery => T::WeightInfo::seal_ecdsa_recover(),
+			EcdsaToEthAddress => T::WeightInfo::seal_ecdsa_to_eth_address(),
+			ReentrantCount => T::WeightInfo::seal_reentrance_count(),
+			AccountEntranceCount => T::WeightInfo::seal_account_reentrance_count(),
+		}
+	}
+}
+
+/// This is only appropriate when writing out data of constant size that does not depend on user
+/// input. In this case the costs for this copy was already charged as part of the token at
+/// the beginning of the API entry point.
+fn already_charged(_: u32) -> Option<RuntimeCosts> {
+	None
+}
+
+/// The kind of call that should be performed.
+enum CallType {
+	/// Execute another instantiated contract
+	Call { callee_ptr: u32, value_ptr: u32, deposit_ptr: u32, weight: Weight },
+	/// Execute deployed code in the context (storage, account ID, value) of the caller contract
+	DelegateCall { code_hash_ptr: u32 },
+}
+
+impl CallType {
+	fn cost(&self) -> RuntimeCosts {
+		match self {
+			CallType::Call { .. } => RuntimeCosts::CallBase,
+			CallType::DelegateCall { .. } => RuntimeCosts::DelegateCallBase,
+		}
+	}
+}
+
+/// Can only be used for one call.
+pub struct Runtime<'a, E: Ext + 'a> {
+	ext: &'a mut E,
+	input_data: Option<Vec<u8>>,
+}
+
+impl<'a, E: Ext + 'a> Runtime<'a, E> {
+	pub fn new(ext: &'a mut E, input_data: Vec<u8>) -> Self {
+		Runtime { ext, input_data: Some(input_data) }
+	}
+
+	/// Get a mutable reference to the inner `Ext`.
+	pub fn ext(&mut self) -> &mut E {
+		self.ext
+	}
+
+	/// Handles an interrupt of the PolkaVM instance.
+	///
+	/// Returns `None` if the execution should be resumed and the result of the execution
+	/// otherwise.
+	pub fn handle_interrupt(
+		&mut self,
+		interrupt: Result<polkavm::InterruptKind, polkavm::Error>,
+		module: &polkavm::Module,
+		instance: &mut polkavm::RawInstance,
+	) -> Option<ExecResult> {
+		use polkavm::InterruptKind::*;
+
+		match interrupt {
+			Err(error) => {
+				// in contrast to the other returns this "should" not happen: log level error
+				log::error!(target: LOG_TARGET, "polkavm execution error: {error}");
+				Some(Err(Error::<E::T>::ContractTrapped.into()))
+			},
+			Ok(Finished) =>
+				Some(Ok(ExecReturnValue { flags: ReturnFlags::empty(), data: Vec::new() })),
+			Ok(Trap) | Ok(Segfault(_)) => Some(Err(Error::<E::T>::ContractTrapped.into())),
+			Ok(NotEnoughGas) => Some(Err(Error::<E::T>::OutOfGas.into())),
+			Ok(Step) => None,
+			Ok(Ecalli(idx)) => {
+				let Some(syscall_symbol) = module.imports().get(idx) else {
+					return Some(Err(<Error<E::T>>::InvalidSyscall.into()))
+				};
+				// Charge the instructions executed since the last syscall.
+				if let Err(error) = self.ext.gas_meter_mut().sync_from_executor(instance.gas()) {
+					return Some(Err(error.into()))
+				}
+				let args = [
+					polkavm::Reg::A0,
+					polkavm::Reg::A1,
+					polkavm::Reg::A2,
+					polkavm::Reg::A3,
+					polkavm::Reg::A4,
+					polkavm::Reg::A5,
+				]
+				.map(|reg| instance.reg(reg) as u32);
+				let outcome = self.handle_ecall(instance, syscall_symbol.as_bytes(), args);
+				instance.set_gas(self.ext.gas_meter_mut().sync_to_executor());
+				match outcome {
+					Ok(None) => None,
+					Ok(Some(return_value)) => {
+						instance.set_reg(polkavm::Reg::A0, return_value.into());
+						None
+					},
+					Err(TrapReason::Return(ReturnData { flags, data })) =>
+						match ReturnFlags::from_bits(flags) {
+							None => Some(Err(Error::<E::T>::InvalidCallFlags.into())),
+							Some(flags) => Some(Ok(ExecReturnValue { flags, data })),
+						},
+					Err(TrapReason::Termination) =>
+						Some(Ok(ExecReturnValue { flags: ReturnFlags::empty(), data: Vec::new() })),
+					Err(TrapReason::SupervisorError(error)) => Some(Err(error.into())),
+				}
+			},
+		}
+	}
+
+	/// Dispatches the syscall `name` with the register values `args`.
+	///
+	/// Returns the value which should be written to `a0` if any.
+	pub(crate) fn handle_ecall<M: Memory<E::T> + ?Sized>(
+		&mut self,
+		memory: &mut M,
+		name: &[u8],
+		args: [u32; 6],
+	) -> Result<Option<u32>, TrapReason> {
+		let [a0, a1, a2, a3, _a4, _a5] = args;
+		let code =
+			|code: ReturnErrorCode| -> Result<Option<u32>, TrapReason> { Ok(Some(code.into())) };
+		match name {
+			b"input" => self.input(memory, a0, a1).map(|_| None),
+			b"seal_return" => self.seal_return(memory, a0, a1, a2).map(|_| None),
+			b"caller" => {
+				self.charge_gas(RuntimeCosts::Caller)?;
+				let caller =
+					<E::T as Config>::AddressMapper::to_address(self.ext.caller().account_id()?);
+				memory.write(a0, caller.as_bytes())?;
+				Ok(None)
+			},
+			b"address" => {
+				self.charge_gas(RuntimeCosts::Address)?;
+				memory.write(a0, self.ext.address().as_bytes())?;
+				Ok(None)
+			},
+			b"balance" => {
+				self.charge_gas(RuntimeCosts::Balance)?;
+				let balance = self.ext.balance();
+				Self::write_u256(memory, a0, balance_to_u256::<E::T>(balance))?;
+				Ok(None)
+			},
+			b"value_transferred" => {
+				self.charge_gas(RuntimeCosts::ValueTransferred)?;
+				let value = self.ext.value_transferred();
+				Self::write_u256(memory, a0, balance_to_u256::<E::T>(value))?;
+				Ok(None)
+			},
+			b"minimum_balance" => {
+				self.charge_gas(RuntimeCosts::MinimumBalance)?;
+				let minimum_balance = self.ext.minimum_balance();
+				Self::write_u256(memory, a0, balance_to_u256::<E::T>(minimum_balance))?;
+				Ok(None)
+			},
+			b"now" => {
+				self.charge_gas(RuntimeCosts::Now)?;
+				let now: u128 = (*self.ext.now()).saturated_into();
+				Self::write_u256(memory, a0, now.into())?;
+				Ok(None)
+			},
+			b"block_number" => {
+				self.charge_gas(RuntimeCosts::BlockNumber)?;
+				let block_number: u128 = self.ext.block_number().saturated_into();
+				Self::write_u256(memory, a0, block_number.into())?;
+				Ok(None)
+			},
+			b"chain_id" => {
+				self.charge_gas(RuntimeCosts::ChainId)?;
+				Self::write_u256(memory, a0, <E::T as Config>::ChainId::get().into())?;
+				Ok(None)
+			},
+			b"gas_left" => {
+				self.charge_gas(RuntimeCosts::GasLeft)?;
+				let gas_left = self.ext.gas_meter().gas_left().encode();
+				self.write_sandbox_output(memory, a0, a1, &gas_left, false, already_charged)?;
+				Ok(None)
+			},
+			b"set_storage" => self.set_storage(memory, a0, a1, a2, a3).map(Some),
+			b"clear_storage" => self.clear_storage(memory, a0, a1).map(Some),
+			b"get_storage" => self.get_storage(memory, a0, a1, a2, a3).map(|c| Some(c.into())),
+			b"contains_storage" => self.contains_storage(memory, a0, a1).map(Some),
+			b"take_storage" => self.take_storage(memory, a0, a1, a2, a3).map(|c| Some(c.into())),
+			b"transfer" => {
+				self.charge_gas(RuntimeCosts::Transfer)?;
+				let to = memory.read_h160(a0)?;
+				let value = u256_to_balance::<E::T>(memory.read_u256(a1)?)?;
+				let result = self.ext.transfer(&to, value);
+				match result {
+					Ok(()) => code(ReturnErrorCode::Success),
+					Err(err) => code(Self::err_into_return_code(err)?),
+				}
+			},
+			b"call" => {
+				let (
+					flags,
+					callee_ptr,
+					ref_time_limit,
+					proof_size_limit,
+					deposit_ptr,
+					value_ptr,
+					input_data_ptr,
+					input_data_len,
+					output_ptr,
+					output_len_ptr,
+				): (u32, u32, u64, u64, u32, u32, u32, u32, u32, u32) = memory.read_as(a0)?;
+				let flags = CallFlags::from_bits(flags).ok_or(Error::<E::T>::InvalidCallFlags)?;
+				self.call(
+					memory,
+					flags,
+					CallType::Call {
+						callee_ptr,
+						value_ptr,
+						deposit_ptr,
+						weight: Weight::from_parts(ref_time_limit, proof_size_limit),
+					},
+					input_data_ptr,
+					input_data_len,
+					output_ptr,
+					output_len_ptr,
+				)
+				.map(|c| Some(c.into()))
+			},
+			b"delegate_call" => {
+				let (
+					flags,
+					code_hash_ptr,
+					input_data_ptr,
+					input_data_len,
+					output_ptr,
+					output_len_ptr,
+				): (u32, u32, u32, u32, u32, u32) = memory.read_as(a0)?;
+				let flags = CallFlags::from_bits(flags).ok_or(Error::<E::T>::InvalidCallFlags)?;
+				self.call(
+					memory,
+					flags,
+					CallType::DelegateCall { code_hash_ptr },
+					input_data_ptr,
+					input_data_len,
+					output_ptr,
+					output_len_ptr,
+				)
+				.map(|c| Some(c.into()))
+			},
+			b"instantiate" => {
+				let (
+					code_hash_ptr,
+					ref_time_limit,
+					proof_size_limit,
+					deposit_ptr,
+					value_ptr,
+					input_data_ptr,
+					input_data_len,
+					address_ptr,
+					output_ptr,
+					output_len_ptr,
+					salt_ptr,
+				): (u32, u64, u64, u32, u32, u32, u32, u32, u32, u32, u32) = memory.read_as(a0)?;
+				self.instantiate(
+					memory,
+					code_hash_ptr,
+					Weight::from_parts(ref_time_limit, proof_size_limit),
+					deposit_ptr,
+					value_ptr,
+					input_data_ptr,
+					input_data_len,
+					address_ptr,
+					output_ptr,
+					output_len_ptr,
+					salt_ptr,
+				)
+				.map(|c| Some(c.into()))
+			},
+			b"terminate" => {
+				self.charge_gas(RuntimeCosts::Terminate)?;
+				let beneficiary = memory.read_h160(a0)?;
+				self.ext.terminate(&beneficiary)?;
+				Err(TrapReason::Termination)
+			},
+			b"deposit_event" => self.deposit_event(memory, a0, a1, a2, a3).map(|_| None),
+			b"hash_sha2_256" => {
+				self.charge_gas(RuntimeCosts::HashSha256(a1))?;
+				Self::compute_hash_on_intermediate_buffer(memory, sha2_256, a0, a1, a2)?;
+				Ok(None)
+			},
+			b"hash_keccak_256" => {
+				self.charge_gas(RuntimeCosts::HashKeccak256(a1))?;
+				Self::compute_hash_on_intermediate_buffer(memory, keccak_256, a0, a1, a2)?;
+				Ok(None)
+			},
+			b"hash_blake2_256" => {
+				self.charge_gas(RuntimeCosts::HashBlake256(a1))?;
+				Self::compute_hash_on_intermediate_buffer(memory, blake2_256, a0, a1, a2)?;
+				Ok(None)
+			},
+			b"hash_blake2_128" => {
+				self.charge_gas(RuntimeCosts::HashBlake128(a1))?;
+				Self::compute_hash_on_intermediate_buffer(memory, blake2_128, a0, a1, a2)?;
+				Ok(None)
+			},
+			b"debug_message" => {
+				let str_len = a1.min(limits::DEBUG_BUFFER_BYTES);
+				self.charge_gas(RuntimeCosts::DebugMessage(str_len))?;
+				if self.ext.append_debug_buffer("") {
+					let data = memory.read(a0, str_len)?;
+					if let Some(msg) = core::str::from_utf8(&data).ok() {
+						self.ext.append_debug_buffer(msg);
+					}
+					code(ReturnErrorCode::Success)
+				} else {
+					code(ReturnErrorCode::LoggingDisabled)
+				}
+			},
+			b"ecdsa_recover" => {
+				self.charge_gas(RuntimeCosts::EcdsaRecovery)?;
+				let signature: [u8; 65] = memory.read_array(a0)?;
+				let message_hash: [u8; 32] = memory.read_array(a1)?;
+				match self.ext.ecdsa_recover(&signature, &message_hash) {
+					Ok(pub_key) => {
+						// Write the recovered compressed ecdsa public key back into the sandboxed
+						// output buffer.
+						memory.write(a2, pub_key.as_ref())?;
+						code(ReturnErrorCode::Success)
+					},
+					Err(_) => code(ReturnErrorCode::EcdsaRecoveryFailed),
+				}
+			},
+			b"ecdsa_to_eth_address" => {
+				self.charge_gas(RuntimeCosts::EcdsaToEthAddress)?;
+				let compressed_key: [u8; 33] = memory.read_array(a0)?;
+				match self.ext.ecdsa_to_eth_address(&compressed_key) {
+					Ok(eth_address) => {
+						memory.write(a1, eth_address.as_ref())?;
+						code(ReturnErrorCode::Success)
+					},
+					Err(_) => code(ReturnErrorCode::EcdsaRecoveryFailed),
+				}
+			},
+			b"caller_is_origin" => {
+				self.charge_gas(RuntimeCosts::CallerIsOrigin)?;
+				Ok(Some(self.ext.caller_is_origin() as u32))
+			},
+			b"caller_is_root" => {
+				self.charge_gas(RuntimeCosts::CallerIsRoot)?;
+				Ok(Some(self.ext.caller_is_root() as u32))
+			},
+			b"is_contract" => {
+				self.charge_gas(RuntimeCosts::IsContract)?;
+				let address = memory.read_h160(a0)?;
+				Ok(Some(self.ext.is_contract(&address) as u32))
+			},
+			b"code_hash" => {
+				self.charge_gas(RuntimeCosts::CodeHash)?;
+				let address = memory.read_h160(a0)?;
+				if let Some(value) = self.ext.code_hash(&address) {
+					memory.write(a1, value.as_bytes())?;
+					code(ReturnErrorCode::Success)
+				} else {
+					code(ReturnErrorCode::KeyNotFound)
+				}
+			},
+			b"own_code_hash" => {
+				self.charge_gas(RuntimeCosts::OwnCodeHash)?;
+				let code_hash = *self.ext.own_code_hash();
+				memory.write(a0, code_hash.as_bytes())?;
+				Ok(None)
+			},
+			b"reentrance_count" => {
+				self.charge_gas(RuntimeCosts::ReentrantCount)?;
+				Ok(Some(self.ext.reentrance_count()))
+			},
+			b"account_reentrance_count" => {
+				self.charge_gas(RuntimeCosts::AccountEntranceCount)?;
+				let address = memory.read_h160(a0)?;
+				Ok(Some(self.ext.account_reentrance_count(&address)))
+			},
+			_ => {
+				log::debug!(
+					target: LOG_TARGET,
+					"contract called unknown syscall: {:?}",
+					core::str::from_utf8(name),
+				);
+				Err(Error::<E::T>::InvalidSyscall.into())
+			},
+		}
+	}
+
+	/// Charge the gas meter with the specified token.
+	///
+	/// Returns `Err(HostError)` if there is not enough gas.
+	pub fn charge_gas(&mut self, costs: RuntimeCosts) -> Result<ChargedAmount, DispatchError> {
+		self.ext.gas_meter_mut().charge(costs)
+	}
+
+	/// Adjust a previously charged amount down to its actual amount.
+	///
+	/// This is when a maximum a priori amount was charged and then should be partially
+	/// refunded to match the actual amount.
+	pub fn adjust_gas(&mut self, charged: ChargedAmount, actual_costs: RuntimeCosts) {
+		self.ext.gas_meter_mut().adjust_gas(charged, actual_costs);
+	}
+
+	/// Write the given buffer and its length to the designated locations in sandbox memory and
+	/// charge gas according to the token returned by `create_token`.
+	///
+	/// `out_ptr` is the location in sandbox memory where `buf` should be written to.
+	/// `out_len_ptr` is an in-out location in sandbox memory. It is read to determine the
+	/// length of the buffer located at `out_ptr`. If that buffer is large enough the actual
+	/// `buf.len()` is written to this location.
+	///
+	/// If `out_ptr` is set to the sentinel value of `SENTINEL` and `allow_skip` is true the
+	/// operation is skipped and `Ok` is returned. This is supposed to help callers to make copying
+	/// output optional. For example to skip copying back the output buffer of an `call`
+	/// when the caller is not interested in the result.
+	///
+	/// `create_token` can optionally instruct this function to charge the gas meter with the token
+	/// it returns. `create_token` receives the variable amount of bytes that are about to be copied
+	/// by this function.
+	///
+	/// In addition to the error conditions of `Memory::write` this functions returns
+	/// `Err` if the size of the buffer located at `out_ptr` is too small to fit `buf`.
+	pub fn write_sandbox_output<M: Memory<E::T> + ?Sized>(
+		&mut self,
+		memory: &mut M,
+		out_ptr: u32,
+		out_len_ptr: u32,
+		buf: &[u8],
+		allow_skip: bool,
+		create_token: impl FnOnce(u32) -> Option<RuntimeCosts>,
+	) -> Result<(), DispatchError> {
+		if allow_skip && out_ptr == SENTINEL {
+			return Ok(())
+		}
+
+		let buf_len = buf.len() as u32;
+		let len: u32 = memory.read_as(out_len_ptr)?;
+
+		if len < buf_len {
+			return Err(Error::<E::T>::OutputBufferTooSmall.into())
+		}
+
+		if let Some(costs) = create_token(buf_len) {
+			self.charge_gas(costs)?;
+		}
+
+		memory.write(out_ptr, buf)?;
+		memory.write(out_len_ptr, &buf_len.encode())
+	}
+
+	/// Write a 32 byte little endian encoded unsigned integer to `ptr`.
+	fn write_u256<M: Memory<E::T> + ?Sized>(
+		memory: &mut M,
+		ptr: u32,
+		value: U256,
+	) -> Result<(), DispatchError> {
+		let mut buf = [0u8; 32];
+		value.to_little_endian(&mut buf);
+		memory.write(ptr, &buf)
+	}
+
+	/// Computes the given hash function on the supplied input.
+	///
+	/// Reads from the sandboxed input buffer into an intermediate buffer.
+	/// Returns the result directly to the output buffer of the sandboxed memory.
+	///
+	/// It is the callers responsibility to provide an output buffer that
+	/// is large enough to hold the expected amount of bytes returned by the
+	/// chosen hash function.
+	///
+	/// # Note
+	///
+	/// The `input` and `output` buffers may overlap.
+	fn compute_hash_on_intermediate_buffer<M: Memory<E::T> + ?Sized, F, R>(
+		memory: &mut M,
+		hash_fn: F,
+		input_ptr: u32,
+		input_len: u32,
+		output_ptr: u32,
+	) -> Result<(), DispatchError>
+	where
+		F: FnOnce(&[u8]) -> R,
+		R: AsRef<[u8]>,
+	{
+		// Copy input into supervisor memory.
+		let input = memory.read(input_ptr, input_len)?;
+		// Compute the hash on the input buffer using the given hash function.
+		let hash = hash_fn(&input);
+		// Write the resulting hash back into the sandboxed output buffer.
+		memory.write(output_ptr, hash.as_ref())?;
+		Ok(())
+	}
+
+	/// Fallible conversion of `DispatchError` to `ReturnErrorCode`.
+	fn err_into_return_code(from: DispatchError) -> Result<ReturnErrorCode, DispatchError> {
+		use ReturnErrorCode::*;
+
+		let transfer_failed = Error::<E::T>::TransferFailed.into();
+		let no_code = Error::<E::T>::CodeNotFound.into();
+		let not_found = Error::<E::T>::ContractNotFound.into();
+
+		match from {
+			x if x == transfer_failed => Ok(TransferFailed),
+			x if x == no_code => Ok(CodeNotFound),
+			x if x == not_found => Ok(NotCallable),
+			err => Err(err),
+		}
+	}
+
+	/// Fallible conversion of a `ExecResult` to `ReturnErrorCode`.
+	fn exec_into_return_code(from: ExecResult) -> Result<ReturnErrorCode, DispatchError> {
+		use crate::exec::ErrorOrigin::Callee;
+
+		let ExecError { error, origin } = match from {
+			Ok(retval) if retval.did_revert() => return Ok(ReturnErrorCode::CalleeReverted),
+			Ok(_) => return Ok(ReturnErrorCode::Success),
+			Err(err) => err,
+		};
+
+		match (error, origin) {
+			(_, Callee) => Ok(ReturnErrorCode::CalleeTrapped),
+			(err, _) => Self::err_into_return_code(err),
+		}
+	}
+
+	/// Reads a storage key from contract memory.
+	///
+	/// Keys of exactly 32 bytes are fixed sized keys. All other keys are variable sized keys which
+	/// are transparently hashed.
+	fn decode_key<M: Memory<E::T> + ?Sized>(
+		memory: &M,
+		key_ptr: u32,
+		key_len: u32,
+	) -> Result<Key, TrapReason> {
+		ensure!(key_len <= limits::STORAGE_KEY_BYTES, Error::<E::T>::DecodingFailed);
+		let key = memory.read(key_ptr, key_len)?;
+		let res = if key_len == 32 { Key::try_from_fix(key) } else { Key::try_from_var(key) };
+		res.map_err(|_| Error::<E::T>::DecodingFailed.into())
+	}
+
+	fn input<M: Memory<E::T> + ?Sized>(
+		&mut self,
+		memory: &mut M,
+		out_ptr: u32,
+		out_len_ptr: u32,
+	) -> Result<(), TrapReason> {
+		self.charge_gas(RuntimeCosts::InputBase)?;
+		if let Some(input) = self.input_data.take() {
+			self.write_sandbox_output(memory, out_ptr, out_len_ptr, &input, false, |len| {
+				Some(RuntimeCosts::CopyToContract(len))
+			})?;
+			self.input_data = Some(input);
+			Ok(())
+		} else {
+			Err(Error::<E::T>::InputForwarded.into())
+		}
+	}
+
+	fn seal_return<M: Memory<E::T> + ?Sized>(
+		&mut self,
+		memory: &mut M,
+		flags: u32,
+		data_ptr: u32,
+		data_len: u32,
+	) -> Result<(), TrapReason> {
+		self.charge_gas(RuntimeCosts::Return(data_len))?;
+		Err(TrapReason::Return(ReturnData { flags, data: memory.read(data_ptr, data_len)? }))
+	}
+
+	fn set_storage<M: Memory<E::T> + ?Sized>(
+		&mut self,
+		memory: &mut M,
+		key_ptr: u32,
+		key_len: u32,
+		value_ptr: u32,
+		value_len: u32,
+	) -> Result<u32, TrapReason> {
+		let max_size = self.ext.max_value_size();
+		let charged = self
+			.charge_gas(RuntimeCosts::SetStorage { new_bytes: value_len, old_bytes: max_size })?;
+		if value_len > max_size {
+			return Err(Error::<E::T>::ValueTooLarge.into())
+		}
+		let key = Self::decode_key(memory, key_ptr, key_len)?;
+		let value = Some(memory.read(value_ptr, value_len)?);
+		let write_outcome = self.ext.set_storage(&key, value, false)?;
+
+		self.adjust_gas(
+			charged,
+			RuntimeCosts::SetStorage { new_bytes: value_len, old_bytes: write_outcome.old_len() },
+		);
+		Ok(write_outcome.old_len_with_sentinel())
+	}
+
+	fn clear_storage<M: Memory<E::T> + ?Sized>(
+		&mut self,
+		memory: &mut M,
+		key_ptr: u32,
+		key_len: u32,
+	) -> Result<u32, TrapReason> {
+		let charged = self.charge_gas(RuntimeCosts::ClearStorage(self.ext.max_value_size()))?;
+		let key = Self::decode_key(memory, key_ptr, key_len)?;
+		let outcome = self.ext.set_storage(&key, None, false)?;
+
+		self.adjust_gas(charged, RuntimeCosts::ClearStorage(outcome.old_len()));
+		Ok(outcome.old_len_with_sentinel())
+	}
+
+	fn get_storage<M: Memory<E::T> + ?Sized>(
+		&mut self,
+		memory: &mut M,
+		key_ptr: u32,
+		key_len: u32,
+		out_ptr: u32,
+		out_len_ptr: u32,
+	) -> Result<ReturnErrorCode, TrapReason> {
+		let charged = self.charge_gas(RuntimeCosts::GetStorage(self.ext.max_value_size()))?;
+		let key = Self::decode_key(memory, key_ptr, key_len)?;
+		let outcome = self.ext.get_storage(&key);
+
+		if let Some(value) = outcome {
+			self.adjust_gas(charged, RuntimeCosts::GetStorage(value.len() as u32));
+			self.write_sandbox_output(
+				memory,
+				out_ptr,
+				out_len_ptr,
+				&value,
+				false,
+				already_charged,
+			)?;
+			Ok(ReturnErrorCode::Success)
+		} else {
+			self.adjust_gas(charged, RuntimeCosts::GetStorage(0));
+			Ok(ReturnErrorCode::KeyNotFound)
+		}
+	}
+
+	fn contains_storage<M: Memory<E::T> + ?Sized>(
+		&mut self,
+		memory: &mut M,
+		key_ptr: u32,
+		key_len: u32,
+	) -> Result<u32, TrapReason> {
+		let charged = self.charge_gas(RuntimeCosts::ContainsStorage(self.ext.max_value_size()))?;
+		let key = Self::decode_key(memory, key_ptr, key_len)?;
+		let outcome = self.ext.get_storage_size(&key);
+
+		self.adjust_gas(charged, RuntimeCosts::ContainsStorage(outcome.unwrap_or(0)));
+		Ok(outcome.unwrap_or(SENTINEL))
+	}
+
+	fn take_storage<M: Memory<E::T> + ?Sized>(
+		&mut self,
+		memory: &mut M,
+		key_ptr: u32,
+		key_len: u32,
+		out_ptr: u32,
+		out_len_ptr: u32,
+	) -> Result<ReturnErrorCode, TrapReason> {
+		let charged = self.charge_gas(RuntimeCosts::TakeStorage(self.ext.max_value_size()))?;
+		let key = Self::decode_key(memory, key_ptr, key_len)?;
+		if let crate::storage::WriteOutcome::Taken(value) =
+			self.ext.set_storage(&key, None, true)?
+		{
+			self.adjust_gas(charged, RuntimeCosts::TakeStorage(value.len() as u32));
+			self.write_sandbox_output(
+				memory,
+				out_ptr,
+				out_len_ptr,
+				&value,
+				false,
+				already_charged,
+			)?;
+			Ok(ReturnErrorCode::Success)
+		} else {
+			self.adjust_gas(charged, RuntimeCosts::TakeStorage(0));
+			Ok(ReturnErrorCode::KeyNotFound)
+		}
+	}
+
+	fn deposit_event<M: Memory<E::T> + ?Sized>(
+		&mut self,
+		memory: &mut M,
+		topics_ptr: u32,
+		num_topic: u32,
+		data_ptr: u32,
+		data_len: u32,
+	) -> Result<(), TrapReason> {
+		self.charge_gas(RuntimeCosts::DepositEvent { num_topic, len: data_len })?;
+		if num_topic > limits::NUM_EVENT_TOPICS {
+			return Err(Error::<E::T>::TooManyTopics.into())
+		}
+		if data_len > self.ext.max_value_size() {
+			return Err(Error::<E::T>::ValueTooLarge.into())
+		}
+
+		let topics: Vec<H256> = (0..num_topic)
+			.map(|i| memory.read_h256(topics_ptr.saturating_add(i.saturating_mul(32))))
+			.collect::<Result<_, _>>()?;
+		let event_data = memory.read(data_ptr, data_len)?;
+		self.ext.deposit_event(topics, event_data);
+		Ok(())
+	}
+
+	fn call<M: Memory<E::T> + ?Sized>(
+		&mut self,
+		memory: &mut M,
+		flags: CallFlags,
+		call_type: CallType,
+		input_data_ptr: u32,
+		input_data_len: u32,
+		output_ptr: u32,
+		output_len_ptr: u32,
+	) -> Result<ReturnErrorCode, TrapReason> {
+		self.charge_gas(call_type.cost())?;
+		let input_data = if flags.contains(CallFlags::CLONE_INPUT) {
+			let input = self.input_data.as_ref().ok_or(Error::<E::T>::InputForwarded)?;
+			let len = input.len() as u32;
+			self.charge_gas(RuntimeCosts::CallInputCloned(len))?;
+			self.input_data.clone().ok_or(Error::<E::T>::InputForwarded)?
+		} else if flags.contains(CallFlags::FORWARD_INPUT) {
+			self.input_data.take().ok_or(Error::<E::T>::InputForwarded)?
+		} else {
+			self.charge_gas(RuntimeCosts::CopyFromContract(input_data_len))?;
+			memory.read(input_data_ptr, input_data_len)?
+		};
+
+		let call_outcome = match call_type {
+			CallType::Call { callee_ptr, value_ptr, deposit_ptr, weight } => {
+				let callee = memory.read_h160(callee_ptr)?;
+				let deposit_limit = if deposit_ptr == SENTINEL {
+					BalanceOf::<E::T>::zero()
+				} else {
+					u256_to_balance::<E::T>(memory.read_u256(deposit_ptr)?)?
+				};
+				let value = u256_to_balance::<E::T>(memory.read_u256(value_ptr)?)?;
+				if !value.is_zero() {
+					self.charge_gas(RuntimeCosts::CallTransferSurcharge)?;
+				}
+				self.ext.call(
+					weight,
+					deposit_limit,
+					&callee,
+					value,
+					input_data,
+					flags.contains(CallFlags::ALLOW_REENTRY),
+				)
+			},
+			CallType::DelegateCall { code_hash_ptr } => {
+				if flags.contains(CallFlags::ALLOW_REENTRY) {
+					return Err(Error::<E::T>::InvalidCallFlags.into())
+				}
+				let code_hash = memory.read_h256(code_hash_ptr)?;
+				self.ext.delegate_call(code_hash, input_data)
+			},
+		};
+
+		// `TAIL_CALL` only matters on an `OK` result. Otherwise the call stack comes to
+		// a halt anyways without anymore code being executed.
+		if flags.contains(CallFlags::TAIL_CALL) {
+			if let Ok(return_value) = call_outcome {
+				return Err(TrapReason::Return(ReturnData {
+					flags: return_value.flags.bits(),
+					data: return_value.data,
+				}))
+			}
+		}
+
+		if let Ok(output) = &call_outcome {
+			self.write_sandbox_output(
+				memory,
+				output_ptr,
+				output_len_ptr,
+				&output.data,
+				true,
+				|len| Some(RuntimeCosts::CopyToContract(len)),
+			)?;
+		}
+		Ok(Self::exec_into_return_code(call_outcome)?)
+	}
+
+	fn instantiate<M: Memory<E::T> + ?Sized>(
+		&mut self,
+		memory: &mut M,
+		code_hash_ptr: u32,
+		weight: Weight,
+		deposit_ptr: u32,
+		value_ptr: u32,
+		input_data_ptr: u32,
+		input_data_len: u32,
+		address_ptr: u32,
+		output_ptr: u32,
+		output_len_ptr: u32,
+		salt_ptr: u32,
+	) -> Result<ReturnErrorCode, TrapReason> {
+		self.charge_gas(RuntimeCosts::Instantiate { input_data_len })?;
+		let deposit_limit = if deposit_ptr == SENTINEL {
+			BalanceOf::<E::T>::zero()
+		} else {
+			u256_to_balance::<E::T>(memory.read_u256(deposit_ptr)?)?
+		};
+		let value = u256_to_balance::<E::T>(memory.read_u256(value_ptr)?)?;
+		let code_hash = memory.read_h256(code_hash_ptr)?;
+		let input_data = memory.read(input_data_ptr, input_data_len)?;
+		let salt: Option<[u8; 32]> =
+			if salt_ptr == SENTINEL { None } else { Some(memory.read_array(salt_ptr)?) };
+		let instantiate_outcome = self.ext.instantiate(
+			weight,
+			deposit_limit,
+			code_hash,
+			value,
+			input_data,
+			salt.as_ref(),
+		);
+		if let Ok((address, output)) = &instantiate_outcome {
+			if !output.did_revert() && address_ptr != SENTINEL {
+				memory.write(address_ptr, address.as_bytes())?;
+			}
+			self.write_sandbox_output(
+				memory,
+				output_ptr,
+				output_len_ptr,
+				&output.data,
+				true,
+				|len| Some(RuntimeCosts::CopyToContract(len)),
+			)?;
+		}
+		Ok(Self::exec_into_return_code(instantiate_outcome.map(|(_, retval)| retval))?)
+	}
+}
+
+/// Converts a balance into the 256 bit representation used by contracts.
+pub(crate) fn balance_to_u256<T: Config>(balance: BalanceOf<T>) -> U256 {
+	balance.saturated_into::<u128>().into()
+}
+
+/// Converts a 256 bit value supplied by a contract into a balance.
+///
+/// Fails with [`Error::BalanceConversionFailed`] if the value does not fit.
+pub(crate) fn u256_to_balance<T: Config>(value: U256) -> Result<BalanceOf<T>, DispatchError> {
+	u128::try_from(value)
+		.ok()
+		.and_then(|value| value.try_into().ok())
+		.ok_or_else(|| Error::<T>::BalanceConversionFailed.into())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::Test;
+
+	#[test]
+	fn balance_conversion_works() {
+		assert_eq!(balance_to_u256::<Test>(42), U256::from(42));
+		assert_eq!(u256_to_balance::<Test>(U256::from(42)), Ok(42));
+		assert_eq!(u256_to_balance::<Test>(U256::from(u64::MAX)), Ok(u64::MAX));
+		assert_eq!(
+			u256_to_balance::<Test>(U256::from(u64::MAX) + 1),
+			Err(Error::<Test>::BalanceConversionFailed.into())
+		);
+	}
+
+	#[test]
+	fn syscall_list_has_no_duplicates() {
+		let mut syscalls = SYSCALLS.to_vec();
+		syscalls.sort();
+		syscalls.dedup();
+		assert_eq!(syscalls.len(), SYSCALLS.len());
+	}
+}
@@ -0,0 +1,665 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for `pallet_revive`.
+//!
+//! The values below are derived from the `pallet_contracts` benchmarks of the equivalent
+//! extrinsics and host functions. They must be replaced by weights generated from the benchmarks
+//! of this pallet on reference hardware before the pallet is used in production.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_revive`.
+pub trait WeightInfo {
+	fn on_process_deletion_queue_batch() -> Weight;
+	fn on_initialize_per_trie_key(k: u32, ) -> Weight;
+	fn call_with_code_per_byte(c: u32, ) -> Weight;
+	fn instantiate_with_code(c: u32, i: u32, ) -> Weight;
+	fn instantiate(i: u32, ) -> Weight;
+	fn call() -> Weight;
+	fn upload_code(c: u32, ) -> Weight;
+	fn remove_code() -> Weight;
+	fn map_account() -> Weight;
+	fn unmap_account() -> Weight;
+	fn eth_transact(p: u32, ) -> Weight;
+	fn seal_caller() -> Weight;
+	fn seal_is_contract() -> Weight;
+	fn seal_code_hash() -> Weight;
+	fn seal_own_code_hash() -> Weight;
+	fn seal_caller_is_origin() -> Weight;
+	fn seal_caller_is_root() -> Weight;
+	fn seal_address() -> Weight;
+	fn seal_gas_left() -> Weight;
+	fn seal_balance() -> Weight;
+	fn seal_value_transferred() -> Weight;
+	fn seal_minimum_balance() -> Weight;
+	fn seal_block_number() -> Weight;
+	fn seal_now() -> Weight;
+	fn seal_chain_id() -> Weight;
+	fn seal_input(n: u32, ) -> Weight;
+	fn seal_return(n: u32, ) -> Weight;
+	fn seal_terminate() -> Weight;
+	fn seal_deposit_event(t: u32, n: u32, ) -> Weight;
+	fn seal_debug_message(i: u32, ) -> Weight;
+	fn seal_set_storage(n: u32, o: u32, ) -> Weight;
+	fn seal_clear_storage(n: u32, ) -> Weight;
+	fn seal_get_storage(n: u32, ) -> Weight;
+	fn seal_contains_storage(n: u32, ) -> Weight;
+	fn seal_take_storage(n: u32, ) -> Weight;
+	fn seal_transfer() -> Weight;
+	fn seal_call(t: u32, i: u32, ) -> Weight;
+	fn seal_delegate_call() -> Weight;
+	fn seal_instantiate(t: u32, i: u32, ) -> Weight;
+	fn seal_hash_sha2_256(n: u32, ) -> Weight;
+	fn seal_hash_keccak_256(n: u32, ) -> Weight;
+	fn seal_hash_blake2_256(n: u32, ) -> Weight;
+	fn seal_hash_blake2_128(n: u32, ) -> Weight;
+	fn seal_ecdsa_recover() -> Weight;
+	fn seal_ecdsa_to_eth_address() -> Weight;
+	fn seal_reentrance_count() -> Weight;
+	fn seal_account_reentrance_count() -> Weight;
+	fn instr(r: u32, ) -> Weight;
+}
+
+/// Weights for `pallet_revive` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn on_process_deletion_queue_batch() -> Weight {
+		// Minimum execution time: 2_130_000 picoseconds.
+		Weight::from_parts(2_130_000, 1627)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	/// The range of component `k` is `[0, 1024]`.
+	fn on_initialize_per_trie_key(k: u32, ) -> Weight {
+		// Minimum execution time: 1_593_881 picoseconds.
+		Weight::from_parts(1_593_881, 442)
+			.saturating_add(Weight::from_parts(1_109_302, 0).saturating_mul(k.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// The range of component `c` is `[0, 262144]`.
+	fn call_with_code_per_byte(c: u32, ) -> Weight {
+		// Minimum execution time: 266_148_573 picoseconds.
+		Weight::from_parts(266_148_573, 6739)
+			.saturating_add(Weight::from_parts(34_592, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// The range of component `c` is `[0, 262144]`.
+	/// The range of component `i` is `[0, 262144]`.
+	fn instantiate_with_code(c: u32, i: u32, ) -> Weight {
+		// Minimum execution time: 794_812_431 picoseconds.
+		Weight::from_parts(794_812_431, 8737)
+			.saturating_add(Weight::from_parts(101_881, 0).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(1_404, 0).saturating_mul(i.into()))
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// The range of component `i` is `[0, 262144]`.
+	fn instantiate(i: u32, ) -> Weight {
+		// Minimum execution time: 374_252_840 picoseconds.
+		Weight::from_parts(374_252_840, 6504)
+			.saturating_add(Weight::from_parts(1_630, 0).saturating_mul(i.into()))
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	fn call() -> Weight {
+		// Minimum execution time: 195_510_000 picoseconds.
+		Weight::from_parts(195_510_000, 6766)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// The range of component `c` is `[0, 262144]`.
+	fn upload_code(c: u32, ) -> Weight {
+		// Minimum execution time: 285_603_050 picoseconds.
+		Weight::from_parts(285_603_050, 3607)
+			.saturating_add(Weight::from_parts(66_212, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn remove_code() -> Weight {
+		// Minimum execution time: 45_036_000 picoseconds.
+		Weight::from_parts(45_036_000, 3780)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn map_account() -> Weight {
+		// Minimum execution time: 39_840_000 picoseconds.
+		Weight::from_parts(39_840_000, 3604)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn unmap_account() -> Weight {
+		// Minimum execution time: 34_150_000 picoseconds.
+		Weight::from_parts(34_150_000, 3604)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// The range of component `p` is `[0, 262144]`.
+	fn eth_transact(p: u32, ) -> Weight {
+		// Minimum execution time: 88_410_000 picoseconds.
+		Weight::from_parts(88_410_000, 3604)
+			.saturating_add(Weight::from_parts(1_512, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn seal_caller() -> Weight {
+		// Minimum execution time: 322_000 picoseconds.
+		Weight::from_parts(322_000, 0)
+	}
+	fn seal_is_contract() -> Weight {
+		// Minimum execution time: 4_104_000 picoseconds.
+		Weight::from_parts(4_104_000, 3604)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	fn seal_code_hash() -> Weight {
+		// Minimum execution time: 4_235_000 picoseconds.
+		Weight::from_parts(4_235_000, 3604)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	fn seal_own_code_hash() -> Weight {
+		// Minimum execution time: 360_000 picoseconds.
+		Weight::from_parts(360_000, 0)
+	}
+	fn seal_caller_is_origin() -> Weight {
+		// Minimum execution time: 157_000 picoseconds.
+		Weight::from_parts(157_000, 0)
+	}
+	fn seal_caller_is_root() -> Weight {
+		// Minimum execution time: 146_000 picoseconds.
+		Weight::from_parts(146_000, 0)
+	}
+	fn seal_address() -> Weight {
+		// Minimum execution time: 330_000 picoseconds.
+		Weight::from_parts(330_000, 0)
+	}
+	fn seal_gas_left() -> Weight {
+		// Minimum execution time: 305_000 picoseconds.
+		Weight::from_parts(305_000, 0)
+	}
+	fn seal_balance() -> Weight {
+		// Minimum execution time: 4_580_000 picoseconds.
+		Weight::from_parts(4_580_000, 3599)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	fn seal_value_transferred() -> Weight {
+		// Minimum execution time: 318_000 picoseconds.
+		Weight::from_parts(318_000, 0)
+	}
+	fn seal_minimum_balance() -> Weight {
+		// Minimum execution time: 315_000 picoseconds.
+		Weight::from_parts(315_000, 0)
+	}
+	fn seal_block_number() -> Weight {
+		// Minimum execution time: 310_000 picoseconds.
+		Weight::from_parts(310_000, 0)
+	}
+	fn seal_now() -> Weight {
+		// Minimum execution time: 308_000 picoseconds.
+		Weight::from_parts(308_000, 0)
+	}
+	fn seal_chain_id() -> Weight {
+		// Minimum execution time: 152_000 picoseconds.
+		Weight::from_parts(152_000, 0)
+	}
+	/// The range of component `n` is `[0, 262140]`.
+	fn seal_input(n: u32, ) -> Weight {
+		// Minimum execution time: 432_000 picoseconds.
+		Weight::from_parts(432_000, 0)
+			.saturating_add(Weight::from_parts(1, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[0, 262140]`.
+	fn seal_return(n: u32, ) -> Weight {
+		// Minimum execution time: 420_000 picoseconds.
+		Weight::from_parts(420_000, 0)
+			.saturating_add(Weight::from_parts(1, 0).saturating_mul(n.into()))
+	}
+	fn seal_terminate() -> Weight {
+		// Minimum execution time: 18_432_000 picoseconds.
+		Weight::from_parts(18_432_000, 3937)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// The range of component `t` is `[0, 4]`.
+	/// The range of component `n` is `[0, 416]`.
+	fn seal_deposit_event(t: u32, n: u32, ) -> Weight {
+		// Minimum execution time: 3_912_000 picoseconds.
+		Weight::from_parts(3_912_000, 0)
+			.saturating_add(Weight::from_parts(1_258_000, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(62, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `i` is `[0, 262144]`.
+	fn seal_debug_message(i: u32, ) -> Weight {
+		// Minimum execution time: 292_000 picoseconds.
+		Weight::from_parts(292_000, 0)
+			.saturating_add(Weight::from_parts(280, 0).saturating_mul(i.into()))
+	}
+	/// The range of component `n` is `[0, 416]`.
+	/// The range of component `o` is `[0, 416]`.
+	fn seal_set_storage(n: u32, o: u32, ) -> Weight {
+		// Minimum execution time: 10_233_000 picoseconds.
+		Weight::from_parts(10_233_000, 1430)
+			.saturating_add(Weight::from_parts(576, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(21, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// The range of component `n` is `[0, 416]`.
+	fn seal_clear_storage(n: u32, ) -> Weight {
+		// Minimum execution time: 9_712_000 picoseconds.
+		Weight::from_parts(9_712_000, 1430)
+			.saturating_add(Weight::from_parts(218, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// The range of component `n` is `[0, 416]`.
+	fn seal_get_storage(n: u32, ) -> Weight {
+		// Minimum execution time: 8_910_000 picoseconds.
+		Weight::from_parts(8_910_000, 1430)
+			.saturating_add(Weight::from_parts(805, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	/// The range of component `n` is `[0, 416]`.
+	fn seal_contains_storage(n: u32, ) -> Weight {
+		// Minimum execution time: 8_230_000 picoseconds.
+		Weight::from_parts(8_230_000, 1430)
+			.saturating_add(Weight::from_parts(186, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	/// The range of component `n` is `[0, 416]`.
+	fn seal_take_storage(n: u32, ) -> Weight {
+		// Minimum execution time: 10_112_000 picoseconds.
+		Weight::from_parts(10_112_000, 1430)
+			.saturating_add(Weight::from_parts(830, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn seal_transfer() -> Weight {
+		// Minimum execution time: 12_880_000 picoseconds.
+		Weight::from_parts(12_880_000, 3604)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// The range of component `t` is `[0, 1]`.
+	/// The range of component `i` is `[0, 262144]`.
+	fn seal_call(t: u32, i: u32, ) -> Weight {
+		// Minimum execution time: 22_920_000 picoseconds.
+		Weight::from_parts(22_920_000, 4101)
+			.saturating_add(Weight::from_parts(6_238_000, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(1, 0).saturating_mul(i.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn seal_delegate_call() -> Weight {
+		// Minimum execution time: 18_370_000 picoseconds.
+		Weight::from_parts(18_370_000, 3937)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+	}
+	/// The range of component `t` is `[0, 1]`.
+	/// The range of component `i` is `[0, 261888]`.
+	fn seal_instantiate(t: u32, i: u32, ) -> Weight {
+		// Minimum execution time: 34_460_000 picoseconds.
+		Weight::from_parts(34_460_000, 4162)
+			.saturating_add(Weight::from_parts(6_012_000, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(2, 0).saturating_mul(i.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// The range of component `n` is `[0, 262144]`.
+	fn seal_hash_sha2_256(n: u32, ) -> Weight {
+		// Minimum execution time: 1_014_000 picoseconds.
+		Weight::from_parts(1_014_000, 0)
+			.saturating_add(Weight::from_parts(1_072, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[0, 262144]`.
+	fn seal_hash_keccak_256(n: u32, ) -> Weight {
+		// Minimum execution time: 1_170_000 picoseconds.
+		Weight::from_parts(1_170_000, 0)
+			.saturating_add(Weight::from_parts(3_328, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[0, 262144]`.
+	fn seal_hash_blake2_256(n: u32, ) -> Weight {
+		// Minimum execution time: 872_000 picoseconds.
+		Weight::from_parts(872_000, 0)
+			.saturating_add(Weight::from_parts(1_221, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[0, 262144]`.
+	fn seal_hash_blake2_128(n: u32, ) -> Weight {
+		// Minimum execution time: 830_000 picoseconds.
+		Weight::from_parts(830_000, 0)
+			.saturating_add(Weight::from_parts(1_225, 0).saturating_mul(n.into()))
+	}
+	fn seal_ecdsa_recover() -> Weight {
+		// Minimum execution time: 46_132_000 picoseconds.
+		Weight::from_parts(46_132_000, 0)
+	}
+	fn seal_ecdsa_to_eth_address() -> Weight {
+		// Minimum execution time: 12_914_000 picoseconds.
+		Weight::from_parts(12_914_000, 0)
+	}
+	fn seal_reentrance_count() -> Weight {
+		// Minimum execution time: 167_000 picoseconds.
+		Weight::from_parts(167_000, 0)
+	}
+	fn seal_account_reentrance_count() -> Weight {
+		// Minimum execution time: 162_000 picoseconds.
+		Weight::from_parts(162_000, 0)
+	}
+	/// The range of component `r` is `[0, 5000]`.
+	fn instr(r: u32, ) -> Weight {
+		// Minimum execution time: 2_310_000 picoseconds.
+		Weight::from_parts(2_310_000, 0)
+			.saturating_add(Weight::from_parts(4_856, 0).saturating_mul(r.into()))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn on_process_deletion_queue_batch() -> Weight {
+		// Minimum execution time: 2_130_000 picoseconds.
+		Weight::from_parts(2_130_000, 1627)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	/// The range of component `k` is `[0, 1024]`.
+	fn on_initialize_per_trie_key(k: u32, ) -> Weight {
+		// Minimum execution time: 1_593_881 picoseconds.
+		Weight::from_parts(1_593_881, 442)
+			.saturating_add(Weight::from_parts(1_109_302, 0).saturating_mul(k.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// The range of component `c` is `[0, 262144]`.
+	fn call_with_code_per_byte(c: u32, ) -> Weight {
+		// Minimum execution time: 266_148_573 picoseconds.
+		Weight::from_parts(266_148_573, 6739)
+			.saturating_add(Weight::from_parts(34_592, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// The range of component `c` is `[0, 262144]`.
+	/// The range of component `i` is `[0, 262144]`.
+	fn instantiate_with_code(c: u32, i: u32, ) -> Weight {
+		// Minimum execution time: 794_812_431 picoseconds.
+		Weight::from_parts(794_812_431, 8737)
+			.saturating_add(Weight::from_parts(101_881, 0).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(1_404, 0).saturating_mul(i.into()))
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// The range of component `i` is `[0, 262144]`.
+	fn instantiate(i: u32, ) -> Weight {
+		// Minimum execution time: 374_252_840 picoseconds.
+		Weight::from_parts(374_252_840, 6504)
+			.saturating_add(Weight::from_parts(1_630, 0).saturating_mul(i.into()))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	fn call() -> Weight {
+		// Minimum execution time: 195_510_000 picoseconds.
+		Weight::from_parts(195_510_000, 6766)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// The range of component `c` is `[0, 262144]`.
+	fn upload_code(c: u32, ) -> Weight {
+		// Minimum execution time: 285_603_050 picoseconds.
+		Weight::from_parts(285_603_050, 3607)
+			.saturating_add(Weight::from_parts(66_212, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn remove_code() -> Weight {
+		// Minimum execution time: 45_036_000 picoseconds.
+		Weight::from_parts(45_036_000, 3780)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn map_account() -> Weight {
+		// Minimum execution time: 39_840_000 picoseconds.
+		Weight::from_parts(39_840_000, 3604)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn unmap_account() -> Weight {
+		// Minimum execution time: 34_150_000 picoseconds.
+		Weight::from_parts(34_150_000, 3604)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// The range of component `p` is `[0, 262144]`.
+	fn eth_transact(p: u32, ) -> Weight {
+		// Minimum execution time: 88_410_000 picoseconds.
+		Weight::from_parts(88_410_000, 3604)
+			.saturating_add(Weight::from_parts(1_512, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn seal_caller() -> Weight {
+		// Minimum execution time: 322_000 picoseconds.
+		Weight::from_parts(322_000, 0)
+	}
+	fn seal_is_contract() -> Weight {
+		// Minimum execution time: 4_104_000 picoseconds.
+		Weight::from_parts(4_104_000, 3604)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	fn seal_code_hash() -> Weight {
+		// Minimum execution time: 4_235_000 picoseconds.
+		Weight::from_parts(4_235_000, 3604)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	fn seal_own_code_hash() -> Weight {
+		// Minimum execution time: 360_000 picoseconds.
+		Weight::from_parts(360_000, 0)
+	}
+	fn seal_caller_is_origin() -> Weight {
+		// Minimum execution time: 157_000 picoseconds.
+		Weight::from_parts(157_000, 0)
+	}
+	fn seal_caller_is_root() -> Weight {
+		// Minimum execution time: 146_000 picoseconds.
+		Weight::from_parts(146_000, 0)
+	}
+	fn seal_address() -> Weight {
+		// Minimum execution time: 330_000 picoseconds.
+		Weight::from_parts(330_000, 0)
+	}
+	fn seal_gas_left() -> Weight {
+		// Minimum execution time: 305_000 picoseconds.
+		Weight::from_parts(305_000, 0)
+	}
+	fn seal_balance() -> Weight {
+		// Minimum execution time: 4_580_000 picoseconds.
+		Weight::from_parts(4_580_000, 3599)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	fn seal_value_transferred() -> Weight {
+		// Minimum execution time: 318_000 picoseconds.
+		Weight::from_parts(318_000, 0)
+	}
+	fn seal_minimum_balance() -> Weight {
+		// Minimum execution time: 315_000 picoseconds.
+		Weight::from_parts(315_000, 0)
+	}
+	fn seal_block_number() -> Weight {
+		// Minimum execution time: 310_000 picoseconds.
+		Weight::from_parts(310_000, 0)
+	}
+	fn seal_now() -> Weight {
+		// Minimum execution time: 308_000 picoseconds.
+		Weight::from_parts(308_000, 0)
+	}
+	fn seal_chain_id() -> Weight {
+		// Minimum execution time: 152_000 picoseconds.
+		Weight::from_parts(152_000, 0)
+	}
+	/// The range of component `n` is `[0, 262140]`.
+	fn seal_input(n: u32, ) -> Weight {
+		// Minimum execution time: 432_000 picoseconds.
+		Weight::from_parts(432_000, 0)
+			.saturating_add(Weight::from_parts(1, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[0, 262140]`.
+	fn seal_return(n: u32, ) -> Weight {
+		// Minimum execution time: 420_000 picoseconds.
+		Weight::from_parts(420_000, 0)
+			.saturating_add(Weight::from_parts(1, 0).saturating_mul(n.into()))
+	}
+	fn seal_terminate() -> Weight {
+		// Minimum execution time: 18_432_000 picoseconds.
+		Weight::from_parts(18_432_000, 3937)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// The range of component `t` is `[0, 4]`.
+	/// The range of component `n` is `[0, 416]`.
+	fn seal_deposit_event(t: u32, n: u32, ) -> Weight {
+		// Minimum execution time: 3_912_000 picoseconds.
+		Weight::from_parts(3_912_000, 0)
+			.saturating_add(Weight::from_parts(1_258_000, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(62, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `i` is `[0, 262144]`.
+	fn seal_debug_message(i: u32, ) -> Weight {
+		// Minimum execution time: 292_000 picoseconds.
+		Weight::from_parts(292_000, 0)
+			.saturating_add(Weight::from_parts(280, 0).saturating_mul(i.into()))
+	}
+	/// The range of component `n` is `[0, 416]`.
+	/// The range of component `o` is `[0, 416]`.
+	fn seal_set_storage(n: u32, o: u32, ) -> Weight {
+		// Minimum execution time: 10_233_000 picoseconds.
+		Weight::from_parts(10_233_000, 1430)
+			.saturating_add(Weight::from_parts(576, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(21, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// The range of component `n` is `[0, 416]`.
+	fn seal_clear_storage(n: u32, ) -> Weight {
+		// Minimum execution time: 9_712_000 picoseconds.
+		Weight::from_parts(9_712_000, 1430)
+			.saturating_add(Weight::from_parts(218, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// The range of component `n` is `[0, 416]`.
+	fn seal_get_storage(n: u32, ) -> Weight {
+		// Minimum execution time: 8_910_000 picoseconds.
+		Weight::from_parts(8_910_000, 1430)
+			.saturating_add(Weight::from_parts(805, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	/// The range of component `n` is `[0, 416]`.
+	fn seal_contains_storage(n: u32, ) -> Weight {
+		// Minimum execution time: 8_230_000 picoseconds.
+		Weight::from_parts(8_230_000, 1430)
+			.saturating_add(Weight::from_parts(186, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	/// The range of component `n` is `[0, 416]`.
+	fn seal_take_storage(n: u32, ) -> Weight {
+		// Minimum execution time: 10_112_000 picoseconds.
+		Weight::from_parts(10_112_000, 1430)
+			.saturating_add(Weight::from_parts(830, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn seal_transfer() -> Weight {
+		// Minimum execution time: 12_880_000 picoseconds.
+		Weight::from_parts(12_880_000, 3604)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// The range of component `t` is `[0, 1]`.
+	/// The range of component `i` is `[0, 262144]`.
+	fn seal_call(t: u32, i: u32, ) -> Weight {
+		// Minimum execution time: 22_920_000 picoseconds.
+		Weight::from_parts(22_920_000, 4101)
+			.saturating_add(Weight::from_parts(6_238_000, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(1, 0).saturating_mul(i.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn seal_delegate_call() -> Weight {
+		// Minimum execution time: 18_370_000 picoseconds.
+		Weight::from_parts(18_370_000, 3937)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+	}
+	/// The range of component `t` is `[0, 1]`.
+	/// The range of component `i` is `[0, 261888]`.
+	fn seal_instantiate(t: u32, i: u32, ) -> Weight {
+		// Minimum execution time: 34_460_000 picoseconds.
+		Weight::from_parts(34_460_000, 4162)
+			.saturating_add(Weight::from_parts(6_012_000, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(2, 0).saturating_mul(i.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// The range of component `n` is `[0, 262144]`.
+	fn seal_hash_sha2_256(n: u32, ) -> Weight {
+		// Minimum execution time: 1_014_000 picoseconds.
+		Weight::from_parts(1_014_000, 0)
+			.saturating_add(Weight::from_parts(1_072, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[0, 262144]`.
+	fn seal_hash_keccak_256(n: u32, ) -> Weight {
+		// Minimum execution time: 1_170_000 picoseconds.
+		Weight::from_parts(1_170_000, 0)
+			.saturating_add(Weight::from_parts(3_328, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[0, 262144]`.
+	fn seal_hash_blake2_256(n: u32, ) -> Weight {
+		// Minimum execution time: 872_000 picoseconds.
+		Weight::from_parts(872_000, 0)
+			.saturating_add(Weight::from_parts(1_221, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[0, 262144]`.
+	fn seal_hash_blake2_128(n: u32, ) -> Weight {
+		// Minimum execution time: 830_000 picoseconds.
+		Weight::from_parts(830_000, 0)
+			.saturating_add(Weight::from_parts(1_225, 0).saturating_mul(n.into()))
+	}
+	fn seal_ecdsa_recover() -> Weight {
+		// Minimum execution time: 46_132_000 picoseconds.
+		Weight::from_parts(46_132_000, 0)
+	}
+	fn seal_ecdsa_to_eth_address() -> Weight {
+		// Minimum execution time: 12_914_000 picoseconds.
+		Weight::from_parts(12_914_000, 0)
+	}
+	fn seal_reentrance_count() -> Weight {
+		// Minimum execution time: 167_000 picoseconds.
+		Weight::from_parts(167_000, 0)
+	}
+	fn seal_account_reentrance_count() -> Weight {
+		// Minimum execution time: 162_000 picoseconds.
+		Weight::from_parts(162_000, 0)
+	}
+	/// The range of component `r` is `[0, 5000]`.
+	fn instr(r: u32, ) -> Weight {
+		// Minimum execution time: 2_310_000 picoseconds.
+		Weight::from_parts(2_310_000, 0)
+			.saturating_add(Weight::from_parts(4_856, 0).saturating_mul(r.into()))
+	}
+}
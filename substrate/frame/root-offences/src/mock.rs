@@ -123,7 +123,7 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
-	type RuntimeHoldReason = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type RuntimeFreezeReason = ();
 }
 
@@ -164,7 +164,9 @@ parameter_types! {
 }
 
 impl pallet_staking::Config for Test {
+	type OldCurrency = Balances;
 	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type CurrencyBalance = <Self as pallet_balances::Config>::Balance;
 	type UnixTime = Timestamp;
 	type CurrencyToVote = ();
@@ -257,11 +259,11 @@ impl ExtBuilder {
 				(20, self.balance_factor * 50),
 				(30, self.balance_factor * 50),
 				(40, self.balance_factor * 50),
-				// stashes
-				(11, self.balance_factor * 1000),
-				(21, self.balance_factor * 1000),
-				(31, self.balance_factor * 500),
-				(41, self.balance_factor * 1000),
+				// stashes, with an extra existential deposit since staked funds are held.
+				(11, self.balance_factor * 1000 + 1),
+				(21, self.balance_factor * 1000 + 1),
+				(31, self.balance_factor * 500 + 1),
+				(41, self.balance_factor * 1000 + 1),
 			],
 		}
 		.assimilate_storage(&mut storage)
@@ -0,0 +1,155 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Contains all the interactions with [`Config::Currency`] to manipulate the underlying staking
+//! asset.
+//!
+//! Staked funds are held in the stash account with [`HoldReason::Staking`]. All the other pieces
+//! of the pallet should go through the helpers in this module instead of calling into
+//! [`Config::Currency`] directly.
+
+use frame_support::traits::{
+	fungible::{
+		hold::{Balanced as FunHoldBalanced, Inspect as FunHoldInspect, Mutate as FunHoldMutate},
+		Balanced, Inspect as FunInspect,
+	},
+	tokens::{Fortitude, Precision, Preservation},
+	Imbalance,
+};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	DispatchResult,
+};
+
+use crate::{BalanceOf, Config, HoldReason, NegativeImbalanceOf, PositiveImbalanceOf};
+
+/// Existential deposit for the chain.
+pub fn existential_deposit<T: Config>() -> BalanceOf<T> {
+	T::Currency::minimum_balance()
+}
+
+/// Total issuance of the chain.
+pub fn total_issuance<T: Config>() -> BalanceOf<T> {
+	T::Currency::total_issuance()
+}
+
+/// Total balance of `who`. Includes both free and held balance.
+pub fn total_balance<T: Config>(who: &T::AccountId) -> BalanceOf<T> {
+	T::Currency::total_balance(who)
+}
+
+/// Balance of `who` that is currently held for staking.
+pub fn staked<T: Config>(who: &T::AccountId) -> BalanceOf<T> {
+	T::Currency::balance_on_hold(&HoldReason::Staking.into(), who)
+}
+
+/// Balance of `who` that is free to be added to its stake.
+///
+/// Funds that are frozen (e.g. locked for governance) can still be staked, but unless the account
+/// is kept alive by another provider, it must keep an existential deposit that is not held.
+pub fn free_to_stake<T: Config>(who: &T::AccountId) -> BalanceOf<T> {
+	T::Currency::reducible_balance(who, Preservation::Protect, Fortitude::Force)
+}
+
+/// The total balance of `who` that can be at stake: the funds already staked plus
+/// [`free_to_stake`].
+pub fn stakeable_balance<T: Config>(who: &T::AccountId) -> BalanceOf<T> {
+	free_to_stake::<T>(who).saturating_add(staked::<T>(who))
+}
+
+/// Set the balance of `who` that is held for staking to exactly `amount`.
+///
+/// Fails if `who` does not have enough funds to hold `amount`.
+pub fn update_stake<T: Config>(who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+	T::Currency::set_on_hold(&HoldReason::Staking.into(), who, amount)
+}
+
+/// Release all the staked funds of `who`.
+pub fn kill_stake<T: Config>(who: &T::AccountId) -> DispatchResult {
+	T::Currency::release_all(&HoldReason::Staking.into(), who, Precision::BestEffort).map(|_| ())
+}
+
+/// Slash up to `value` from the staked funds of `who`.
+///
+/// Returns the slashed imbalance and the part of `value` that could not be slashed.
+pub fn slash<T: Config>(
+	who: &T::AccountId,
+	value: BalanceOf<T>,
+) -> (NegativeImbalanceOf<T>, BalanceOf<T>) {
+	T::Currency::slash(&HoldReason::Staking.into(), who, value)
+}
+
+/// Mint `value` into the existing account `who`.
+///
+/// Returns `None` if `who` does not exist.
+pub fn mint_into_existing<T: Config>(
+	who: &T::AccountId,
+	value: BalanceOf<T>,
+) -> Option<PositiveImbalanceOf<T>> {
+	if total_balance::<T>(who).is_zero() {
+		return None
+	}
+
+	T::Currency::deposit(who, value, Precision::Exact).ok()
+}
+
+/// Mint `value` into `who`, creating the account if needed.
+///
+/// Returns a zero imbalance if the account does not exist and `value` is below the existential
+/// deposit.
+pub fn mint_creating<T: Config>(who: &T::AccountId, value: BalanceOf<T>) -> PositiveImbalanceOf<T> {
+	T::Currency::deposit(who, value, Precision::BestEffort).unwrap_or_else(|_| Imbalance::zero())
+}
+
+/// Deposit the slashed imbalance `value` into `who`.
+///
+/// The imbalance is dropped if it cannot be deposited.
+pub fn deposit_slashed<T: Config>(who: &T::AccountId, value: NegativeImbalanceOf<T>) {
+	let _ = T::Currency::resolve(who, value);
+}
+
+/// Issue `value` new funds, increasing the total issuance.
+///
+/// The returned imbalance must be dealt with, e.g. by depositing it somewhere.
+pub fn issue<T: Config>(value: BalanceOf<T>) -> NegativeImbalanceOf<T> {
+	T::Currency::issue(value)
+}
+
+/// Burn `value` from the total issuance.
+#[cfg(feature = "runtime-benchmarks")]
+pub fn burn<T: Config>(value: BalanceOf<T>) -> PositiveImbalanceOf<T> {
+	T::Currency::rescind(value)
+}
+
+/// Set the balance of `who` so that its [`stakeable_balance`] is exactly `value`.
+///
+/// If `value` is lower than the funds currently staked, the stake is reduced to `value`. The
+/// account always keeps an existential deposit of free balance on top.
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+pub fn set_stakeable_balance<T: Config>(who: &T::AccountId, value: BalanceOf<T>) {
+	use frame_support::traits::fungible::Mutate;
+
+	let ed = existential_deposit::<T>();
+	let staked = staked::<T>(who);
+
+	if value > staked {
+		let _ = T::Currency::set_balance(who, value - staked + ed);
+	} else {
+		update_stake::<T>(who, value).expect("can always release staked funds; qed");
+		let _ = T::Currency::set_balance(who, ed);
+	}
+}
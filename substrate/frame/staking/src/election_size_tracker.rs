@@ -25,6 +25,9 @@
 //! [`codec::Encode::size_hint`] to estimate the SCALE encoded size of the snapshot voters struct
 //! as it is being constructed without requiring extra stack allocations.
 //!
+//! In a multi-page election, a new tracker is used for every page that is requested, i.e. the
+//! `DataProviderBounds` apply to each page of the snapshot individually.
+//!
 //! The [`StaticTracker::try_register_voter`] is called to update the static tracker internal
 //! state, if It will return an error if the resulting SCALE encoded size (in bytes) is larger than
 //! the provided `DataProviderBounds`.
@@ -25,26 +25,30 @@
 //!   instances of [`StakingLedger`] keyed by the staker's controller account and should be mutated
 //!   and read through the [`StakingLedger`] API;
 //! * [`Payee`]: mutates and reads the reward destination preferences for a bonded stash.
-//! * Staking locks: mutates the locks for staking.
+//! * Staking holds: mutates the funds held for staking with [`crate::HoldReason::Staking`].
+//!
+//! Ledgers bonded before the pallet used holds are backed by a staking lock instead. These are
+//! migrated lazily: the lock is replaced by a hold the next time the ledger is updated, or when
+//! anyone calls [`crate::Call::migrate_currency`] for the stash.
 //!
 //! NOTE: All the storage operations related to the staking ledger (both reads and writes) *MUST* be
 //! performed through the methods exposed by the [`StakingLedger`] implementation in order to ensure
 //! state consistency.
 
+use frame_election_provider_support::SortedListProvider;
 use frame_support::{
 	defensive,
-	traits::{LockableCurrency, WithdrawReasons},
+	traits::{Defensive, InspectLockableCurrency, LockableCurrency},
 };
+use sp_runtime::{traits::Zero, Saturating};
 use sp_staking::StakingAccount;
 use sp_std::prelude::*;
 
 use crate::{
-	BalanceOf, Bonded, Config, Error, Ledger, Payee, RewardDestination, StakingLedger, STAKING_ID,
+	asset, BalanceOf, Bonded, Config, Error, Event, Ledger, Pallet, Payee, RewardDestination,
+	StakingLedger, VirtualStakers, STAKING_ID,
 };
 
-#[cfg(any(feature = "runtime-benchmarks", test))]
-use sp_runtime::traits::Zero;
-
 impl<T: Config> StakingLedger<T> {
 	#[cfg(any(feature = "runtime-benchmarks", test))]
 	pub fn default_from(stash: T::AccountId) -> Self {
@@ -154,25 +158,106 @@ impl<T: Config> StakingLedger<T> {
 		})
 	}
 
+	/// Returns whether the stake of `stash` is still backed by a legacy staking lock instead of a
+	/// hold.
+	pub(crate) fn has_legacy_lock(stash: &T::AccountId) -> bool {
+		!T::OldCurrency::balance_locked(STAKING_ID, stash).is_zero()
+	}
+
+	/// Reduces the stake of the ledger so that the stash can hold all of it, returning the
+	/// amount that was withdrawn from the stake.
+	///
+	/// Only needed when migrating a legacy staking lock: a lock could cover the whole balance of
+	/// the stash, while held funds must leave at least the existential deposit free. The funds
+	/// that are already unlocking are withdrawn first, then the active stake.
+	pub(crate) fn fit_to_stakeable(mut self) -> (Self, BalanceOf<T>) {
+		let force_withdraw = self.total.saturating_sub(asset::stakeable_balance::<T>(&self.stash));
+		let mut remaining = force_withdraw;
+
+		while let Some(last) = self.unlocking.last_mut() {
+			if remaining.is_zero() {
+				break
+			}
+			let withdrawn = last.value.min(remaining);
+			last.value.saturating_reduce(withdrawn);
+			remaining.saturating_reduce(withdrawn);
+			if last.value.is_zero() {
+				self.unlocking.pop();
+			}
+		}
+
+		let withdrawn = self.active.min(remaining);
+		self.active.saturating_reduce(withdrawn);
+		self.total.saturating_reduce(force_withdraw);
+
+		(self, force_withdraw)
+	}
+
 	/// Inserts/updates a staking ledger account.
 	///
-	/// Bonds the ledger if it is not bonded yet, signalling that this is a new ledger. The staking
-	/// locks of the stash account are updated accordingly.
+	/// Bonds the ledger if it is not bonded yet, signalling that this is a new ledger. The funds
+	/// held for staking in the stash account are updated accordingly. If the stash still has a
+	/// legacy staking lock, it is migrated to a hold (see [`Self::migrate_legacy_lock`]).
 	///
-	/// Note: To ensure lock consistency, all the [`Ledger`] storage updates should be made through
+	/// Virtual stakers are an exception: their funds are managed by another pallet, so neither
+	/// holds nor locks are touched for them.
+	///
+	/// Note: To ensure hold consistency, all the [`Ledger`] storage updates should be made through
 	/// this helper function.
 	pub(crate) fn update(self) -> Result<(), Error<T>> {
 		if !<Bonded<T>>::contains_key(&self.stash) {
 			return Err(Error::<T>::NotStash)
 		}
 
-		T::Currency::set_lock(STAKING_ID, &self.stash, self.total, WithdrawReasons::all());
+		// We skip holding the funds of virtual stakers since they are managed by another pallet.
+		if !Pallet::<T>::is_virtual_staker(&self.stash) {
+			if Self::has_legacy_lock(&self.stash) {
+				return self.migrate_legacy_lock().map(|_| ())
+			}
+
+			asset::update_stake::<T>(&self.stash, self.total)
+				.map_err(|_| Error::<T>::NotEnoughFunds)?;
+		}
+
+		self.insert()
+	}
+
+	/// Migrates the legacy staking lock of the stash to a hold and stores the ledger, returning
+	/// the migrated ledger.
+	///
+	/// The stake is reduced if the stash cannot hold all of it (see [`Self::fit_to_stakeable`]),
+	/// in which case the stash is also updated in the voter list.
+	///
+	/// Emits `CurrencyMigrated`.
+	pub(crate) fn migrate_legacy_lock(self) -> Result<Self, Error<T>> {
+		let (ledger, force_withdraw) = self.fit_to_stakeable();
+
+		asset::update_stake::<T>(&ledger.stash, ledger.total)
+			.map_err(|_| Error::<T>::NotEnoughFunds)?;
+		T::OldCurrency::remove_lock(STAKING_ID, &ledger.stash);
+		ledger.insert()?;
+
+		if !force_withdraw.is_zero() && T::VoterList::contains(&ledger.stash) {
+			let _ = T::VoterList::on_update(&ledger.stash, Pallet::<T>::weight_of(&ledger.stash))
+				.defensive();
+		}
+
+		Pallet::<T>::deposit_event(Event::<T>::CurrencyMigrated {
+			stash: ledger.stash.clone(),
+			force_withdraw,
+		});
+
+		Ok(ledger)
+	}
+
+	/// Stores the ledger in [`Ledger`], keyed by its controller.
+	fn insert(&self) -> Result<(), Error<T>> {
 		Ledger::<T>::insert(
 			&self.controller().ok_or_else(|| {
 				defensive!("update called on a ledger that is not bonded.");
 				Error::<T>::NotController
 			})?,
-			&self,
+			self,
 		);
 
 		Ok(())
@@ -202,17 +287,28 @@ impl<T: Config> StakingLedger<T> {
 	}
 
 	/// Clears all data related to a staking ledger and its bond in both [`Ledger`] and [`Bonded`]
-	/// storage items and updates the stash staking lock.
+	/// storage items and releases the funds held for staking by the stash.
 	pub(crate) fn kill(stash: &T::AccountId) -> Result<(), Error<T>> {
 		let controller = <Bonded<T>>::get(stash).ok_or(Error::<T>::NotStash)?;
 
 		<Ledger<T>>::get(&controller).ok_or(Error::<T>::NotController).map(|ledger| {
-			T::Currency::remove_lock(STAKING_ID, &ledger.stash);
+			// virtual stakers have neither holds nor locks to release.
+			if !Pallet::<T>::is_virtual_staker(&ledger.stash) {
+				if Self::has_legacy_lock(&ledger.stash) {
+					T::OldCurrency::remove_lock(STAKING_ID, &ledger.stash);
+				}
+				asset::kill_stake::<T>(&ledger.stash).map_err(|_| Error::<T>::BadState)?;
+			}
 			Ledger::<T>::remove(controller);
 
 			<Bonded<T>>::remove(&stash);
 			<Payee<T>>::remove(&stash);
 
+			// kill virtual staker if it exists.
+			if <VirtualStakers<T>>::contains_key(&ledger.stash) {
+				<VirtualStakers<T>>::remove(&ledger.stash);
+			}
+
 			Ok(())
 		})?
 	}
@@ -295,6 +295,7 @@ pub(crate) mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod asset;
 pub mod election_size_tracker;
 pub mod inflation;
 pub mod ledger;
@@ -308,7 +309,8 @@ use codec::{Decode, Encode, HasCompact, MaxEncodedLen};
 use frame_support::{
 	defensive, defensive_assert,
 	traits::{
-		ConstU32, Currency, Defensive, DefensiveMax, DefensiveSaturating, Get, LockIdentifier,
+		tokens::fungible::{Credit, Debt},
+		ConstU32, Defensive, DefensiveMax, DefensiveSaturating, Get, LockIdentifier,
 	},
 	weights::Weight,
 	BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
@@ -330,6 +332,8 @@ pub use weights::WeightInfo;
 
 pub use pallet::{pallet::*, UseNominatorsAndValidatorsMap, UseValidatorsMap};
 
+/// The identifier of the legacy staking lock. Staked funds are now held with
+/// [`HoldReason::Staking`]; the lock is only read and removed when a ledger is migrated.
 pub(crate) const STAKING_ID: LockIdentifier = *b"staking ";
 pub(crate) const LOG_TARGET: &str = "runtime::staking";
 
@@ -358,12 +362,12 @@ pub type RewardPoint = u32;
 /// The balance type of this pallet.
 pub type BalanceOf<T> = <T as Config>::CurrencyBalance;
 
-type PositiveImbalanceOf<T> = <<T as Config>::Currency as Currency<
-	<T as frame_system::Config>::AccountId,
->>::PositiveImbalance;
-type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
-	<T as frame_system::Config>::AccountId,
->>::NegativeImbalance;
+/// The positive imbalance of the staking currency, i.e. newly minted funds.
+pub type PositiveImbalanceOf<T> =
+	Debt<<T as frame_system::Config>::AccountId, <T as Config>::Currency>;
+/// The negative imbalance of the staking currency, e.g. slashed or newly issued funds.
+pub type NegativeImbalanceOf<T> =
+	Credit<<T as frame_system::Config>::AccountId, <T as Config>::Currency>;
 
 type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
 
@@ -376,7 +380,7 @@ pub struct ActiveEraInfo {
 	///
 	/// Start can be none if start hasn't been set for the era yet,
 	/// Start is set on the first on_finalize of the era to guarantee usage of `Time`.
-	start: Option<u64>,
+	pub start: Option<u64>,
 }
 
 /// Reward points of an era. Used to split era total payout between validators.
@@ -441,7 +445,7 @@ pub struct UnlockChunk<Balance: HasCompact + MaxEncodedLen> {
 ///
 /// Note: All the reads and mutations to the [`Ledger`], [`Bonded`] and [`Payee`] storage items
 /// *MUST* be performed through the methods exposed by this struct, to ensure the consistency of
-/// ledger's data and corresponding staking lock
+/// ledger's data and corresponding staking hold.
 ///
 /// TODO: move struct definition and full implementation into `/src/ledger.rs`. Currently
 /// leaving here to enforce a clean PR diff, given how critical this logic is. Tracking issue
@@ -458,7 +462,7 @@ pub struct UnlockChunk<Balance: HasCompact + MaxEncodedLen> {
 )]
 #[scale_info(skip_type_params(T))]
 pub struct StakingLedger<T: Config> {
-	/// The stash account whose balance is actually locked and at stake.
+	/// The stash account whose balance is actually held and at stake.
 	pub stash: T::AccountId,
 
 	/// The total amount of the stash's balance that we are currently accounting for.
@@ -958,6 +962,19 @@ impl Default for Forcing {
 	}
 }
 
+/// Status of the voters snapshot that is being handed out to the election provider, page by page.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+pub enum SnapshotStatus<AccountId> {
+	/// Paged snapshot is in progress, the `AccountId` is the next voter to be handed out.
+	Ongoing(AccountId),
+	/// All the voters have been handed out, the remaining pages of the current election are
+	/// empty.
+	Consumed,
+	/// Waiting for a new snapshot to be requested.
+	#[default]
+	Waiting,
+}
+
 /// A `Convert` implementation that finds the stash of the given controller account,
 /// if any.
 pub struct StashOf<T>(sp_std::marker::PhantomData<T>);
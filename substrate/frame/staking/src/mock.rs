@@ -20,7 +20,8 @@
 use crate::{self as pallet_staking, *};
 use frame_election_provider_support::{
 	bounds::{ElectionBounds, ElectionBoundsBuilder},
-	onchain, SequentialPhragmen, VoteWeight,
+	onchain, BoundedSupportsOf, ElectionProvider, ElectionProviderBase, PageIndex,
+	SequentialPhragmen, VoteWeight,
 };
 use frame_support::{
 	assert_ok, derive_impl, ord_parameter_types, parameter_types,
@@ -163,7 +164,7 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
-	type RuntimeHoldReason = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type RuntimeFreezeReason = ();
 }
 
@@ -265,6 +266,40 @@ impl onchain::Config for OnChainSeqPhragmen {
 	type Bounds = ElectionsBounds;
 }
 
+parameter_types! {
+	pub static ElectionPages: PageIndex = 1;
+	pub static FetchedElectionPages: Vec<(BlockNumber, PageIndex)> = vec![];
+}
+
+/// An election provider with [`ElectionPages`] pages, which records the pages that are fetched.
+///
+/// The whole on-chain election result is handed out in the most significant page, and the other
+/// pages are empty.
+pub struct PagedOnChainElection;
+impl ElectionProviderBase for PagedOnChainElection {
+	type AccountId = AccountId;
+	type BlockNumber = BlockNumber;
+	type Error = onchain::Error;
+	type MaxWinners = MaxWinners;
+	type DataProvider = Staking;
+	type Pages = ElectionPages;
+}
+
+impl ElectionProvider for PagedOnChainElection {
+	fn ongoing() -> bool {
+		false
+	}
+
+	fn elect(page: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		FetchedElectionPages::mutate(|pages| pages.push((System::block_number(), page)));
+		if page == Self::msp() {
+			onchain::OnChainExecution::<OnChainSeqPhragmen>::elect(0)
+		} else {
+			Ok(Default::default())
+		}
+	}
+}
+
 pub struct MockReward {}
 impl OnUnbalanced<PositiveImbalanceOf<Test>> for MockReward {
 	fn on_unbalanced(_: PositiveImbalanceOf<Test>) {
@@ -291,7 +326,9 @@ impl OnStakingUpdate<AccountId, Balance> for EventListenerMock {
 }
 
 impl crate::pallet::pallet::Config for Test {
+	type OldCurrency = Balances;
 	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type CurrencyBalance = <Self as pallet_balances::Config>::Balance;
 	type UnixTime = Timestamp;
 	type CurrencyToVote = ();
@@ -308,8 +345,8 @@ impl crate::pallet::pallet::Config for Test {
 	type NextNewSession = Session;
 	type MaxExposurePageSize = MaxExposurePageSize;
 	type OffendingValidatorsThreshold = OffendingValidatorsThreshold;
-	type ElectionProvider = onchain::OnChainExecution<OnChainSeqPhragmen>;
-	type GenesisElectionProvider = Self::ElectionProvider;
+	type ElectionProvider = PagedOnChainElection;
+	type GenesisElectionProvider = onchain::OnChainExecution<OnChainSeqPhragmen>;
 	// NOTE: consider a macro and use `UseNominatorsAndValidatorsMap<Self>` as well.
 	type VoterList = VoterBagsList;
 	type TargetList = UseValidatorsMap<Self>;
@@ -479,21 +516,24 @@ impl ExtBuilder {
 				(40, self.balance_factor),
 				(50, self.balance_factor),
 				// stashes
-				(11, self.balance_factor * 1000),
-				(21, self.balance_factor * 2000),
-				(31, self.balance_factor * 2000),
-				(41, self.balance_factor * 2000),
-				(51, self.balance_factor * 2000),
+				// Note: held funds cannot cover the existential deposit of an account, unlike the
+				// staking lock used before. Stakers get an extra existential deposit so that they
+				// can still bond their full balance.
+				(11, self.balance_factor * 1000 + ExistentialDeposit::get()),
+				(21, self.balance_factor * 2000 + ExistentialDeposit::get()),
+				(31, self.balance_factor * 2000 + ExistentialDeposit::get()),
+				(41, self.balance_factor * 2000 + ExistentialDeposit::get()),
+				(51, self.balance_factor * 2000 + ExistentialDeposit::get()),
 				// optional nominator
-				(100, self.balance_factor * 2000),
-				(101, self.balance_factor * 2000),
+				(100, self.balance_factor * 2000 + ExistentialDeposit::get()),
+				(101, self.balance_factor * 2000 + ExistentialDeposit::get()),
 				// aux accounts
 				(60, self.balance_factor),
-				(61, self.balance_factor * 2000),
+				(61, self.balance_factor * 2000 + ExistentialDeposit::get()),
 				(70, self.balance_factor),
-				(71, self.balance_factor * 2000),
+				(71, self.balance_factor * 2000 + ExistentialDeposit::get()),
 				(80, self.balance_factor),
-				(81, self.balance_factor * 2000),
+				(81, self.balance_factor * 2000 + ExistentialDeposit::get()),
 				// This allows us to have a total_payout different from 0.
 				(999, 1_000_000_000_000),
 			],
@@ -606,7 +646,7 @@ pub(crate) fn current_era() -> EraIndex {
 }
 
 pub(crate) fn bond(who: AccountId, val: Balance) {
-	let _ = Balances::make_free_balance_be(&who, val);
+	asset::set_stakeable_balance::<Test>(&who, val);
 	assert_ok!(Staking::bond(RuntimeOrigin::signed(who), val, RewardDestination::Stash));
 }
 
@@ -861,5 +901,5 @@ pub(crate) fn staking_events_since_last_call() -> Vec<crate::Event<Test>> {
 }
 
 pub(crate) fn balances(who: &AccountId) -> (Balance, Balance) {
-	(Balances::free_balance(who), Balances::reserved_balance(who))
+	(asset::stakeable_balance::<Test>(who), Balances::reserved_balance(who))
 }
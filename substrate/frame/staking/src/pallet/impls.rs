@@ -20,38 +20,42 @@
 use frame_election_provider_support::{
 	bounds::{CountBound, SizeBound},
 	data_provider, BoundedSupportsOf, DataProviderBounds, ElectionDataProvider, ElectionProvider,
-	ScoreProvider, SortedListProvider, VoteWeight, VoterOf,
+	ElectionProviderBase, PageIndex, ScoreProvider, SortedListProvider, Support, VoteWeight,
+	VoterOf,
 };
 use frame_support::{
 	defensive,
 	dispatch::WithPostDispatchInfo,
 	pallet_prelude::*,
 	traits::{
-		Currency, Defensive, DefensiveSaturating, EstimateNextNewSession, Get, Imbalance, Len,
-		OnUnbalanced, TryCollect, UnixTime,
+		Defensive, DefensiveSaturating, DefensiveTruncateFrom, EstimateNextNewSession, Get,
+		Imbalance, Len, LockableCurrency, OnUnbalanced, TryCollect, UnixTime,
 	},
 	weights::Weight,
 };
 use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
 use pallet_session::historical;
 use sp_runtime::{
-	traits::{Bounded, Convert, One, SaturatedConversion, Saturating, StaticLookup, Zero},
-	Perbill, Percent,
+	traits::{
+		Bounded, CheckedAdd, CheckedSub, Convert, One, SaturatedConversion, Saturating,
+		StaticLookup, Zero,
+	},
+	ArithmeticError, Perbill, Percent,
 };
 use sp_staking::{
 	currency_to_vote::CurrencyToVote,
 	offence::{DisableStrategy, OffenceDetails, OnOffenceHandler},
 	EraIndex, OnStakingUpdate, Page, SessionIndex, Stake,
 	StakingAccount::{self, Controller, Stash},
-	StakingInterface,
+	StakingInterface, StakingUnchecked,
 };
-use sp_std::prelude::*;
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 use crate::{
-	election_size_tracker::StaticTracker, log, slashing, weights::WeightInfo, ActiveEraInfo,
+	asset, election_size_tracker::StaticTracker, log, slashing, weights::WeightInfo, ActiveEraInfo,
 	BalanceOf, EraInfo, EraPayout, Exposure, ExposureOf, Forcing, IndividualExposure,
 	MaxNominationsOf, MaxWinnersOf, Nominations, NominationsQuota, PositiveImbalanceOf,
-	RewardDestination, SessionInterface, StakingLedger, ValidatorPrefs,
+	RewardDestination, SessionInterface, SnapshotStatus, StakingLedger, ValidatorPrefs, STAKING_ID,
 };
 
 use super::pallet::*;
@@ -105,7 +109,7 @@ impl<T: Config> Pallet<T> {
 	pub fn weight_of_fn() -> Box<dyn Fn(&T::AccountId) -> VoteWeight> {
 		// NOTE: changing this to unboxed `impl Fn(..)` return type and the pallet will still
 		// compile, while some types in mock fail to resolve.
-		let issuance = T::Currency::total_issuance();
+		let issuance = asset::total_issuance::<T>();
 		Box::new(move |who: &T::AccountId| -> VoteWeight {
 			Self::slashable_balance_of_vote_weight(who, issuance)
 		})
@@ -113,10 +117,48 @@ impl<T: Config> Pallet<T> {
 
 	/// Same as `weight_of_fn`, but made for one time use.
 	pub fn weight_of(who: &T::AccountId) -> VoteWeight {
-		let issuance = T::Currency::total_issuance();
+		let issuance = asset::total_issuance::<T>();
 		Self::slashable_balance_of_vote_weight(who, issuance)
 	}
 
+	/// Whether `who` is a virtual staker whose funds are managed by another pallet.
+	pub(crate) fn is_virtual_staker(who: &T::AccountId) -> bool {
+		VirtualStakers::<T>::contains_key(who)
+	}
+
+	pub(super) fn do_bond_extra(stash: &T::AccountId, additional: BalanceOf<T>) -> DispatchResult {
+		let mut ledger = Self::ledger(StakingAccount::Stash(stash.clone()))?;
+
+		// for virtual stakers, we don't need to check the balance. Since they are only accessed
+		// via low level apis, we can assume that the caller has done the due diligence.
+		let extra = if Self::is_virtual_staker(stash) {
+			additional
+		} else {
+			// additional amount or actual balance of stash whichever is lower.
+			additional.min(
+				asset::stakeable_balance::<T>(stash)
+					.checked_sub(&ledger.total)
+					.ok_or(ArithmeticError::Overflow)?,
+			)
+		};
+
+		ledger.total = ledger.total.checked_add(&extra).ok_or(ArithmeticError::Overflow)?;
+		ledger.active = ledger.active.checked_add(&extra).ok_or(ArithmeticError::Overflow)?;
+		// Last check: the new active amount of ledger must be more than ED.
+		ensure!(ledger.active >= asset::existential_deposit::<T>(), Error::<T>::InsufficientBond);
+
+		// NOTE: ledger must be updated prior to calling `Self::weight_of`.
+		ledger.update()?;
+		// update this staker in the sorted list, if they exist in it.
+		if T::VoterList::contains(stash) {
+			let _ = T::VoterList::on_update(stash, Self::weight_of(stash)).defensive();
+		}
+
+		Self::deposit_event(Event::<T>::Bonded { stash: stash.clone(), amount: extra });
+
+		Ok(())
+	}
+
 	pub(super) fn do_withdraw_unbonded(
 		controller: &T::AccountId,
 		num_slashing_spans: u32,
@@ -129,7 +171,7 @@ impl<T: Config> Pallet<T> {
 		let new_total = ledger.total;
 
 		let used_weight =
-			if ledger.unlocking.is_empty() && ledger.active < T::Currency::minimum_balance() {
+			if ledger.unlocking.is_empty() && ledger.active < asset::existential_deposit::<T>() {
 				// This account must have called `unbond()` with some value that caused the active
 				// portion to fall below existential deposit + will have no more unlocking chunks
 				// left. We can now safely remove all staking-related information.
@@ -342,22 +384,25 @@ impl<T: Config> Pallet<T> {
 		let dest = Self::payee(StakingAccount::Stash(stash.clone()))?;
 
 		let maybe_imbalance = match dest {
-			RewardDestination::Stash => T::Currency::deposit_into_existing(stash, amount).ok(),
+			RewardDestination::Stash => asset::mint_into_existing::<T>(stash, amount),
 			RewardDestination::Staked => Self::ledger(Stash(stash.clone()))
 				.and_then(|mut ledger| {
 					ledger.active += amount;
 					ledger.total += amount;
-					let r = T::Currency::deposit_into_existing(stash, amount).ok();
+					let r = asset::mint_into_existing::<T>(stash, amount);
 
-					let _ = ledger
-						.update()
-						.defensive_proof("ledger fetched from storage, so it exists; qed.");
+					// the reward can only be held if it was minted into the stash.
+					if r.is_some() {
+						let _ = ledger
+							.update()
+							.defensive_proof("ledger fetched from storage, so it exists; qed.");
+					}
 
 					Ok(r)
 				})
 				.unwrap_or_default(),
 			RewardDestination::Account(ref dest_account) =>
-				Some(T::Currency::deposit_creating(&dest_account, amount)),
+				Some(asset::mint_creating::<T>(&dest_account, amount)),
 			RewardDestination::None => None,
 			#[allow(deprecated)]
 			RewardDestination::Controller => Self::bonded(stash)
@@ -365,7 +410,7 @@ impl<T: Config> Pallet<T> {
 						defensive!("Paying out controller as reward destination which is deprecated and should be migrated.");
 						// This should never happen once payees with a `Controller` variant have been migrated.
 						// But if it does, just pay the controller account.
-						T::Currency::deposit_creating(&controller, amount)
+						asset::mint_creating::<T>(&controller, amount)
 		}),
 		};
 		maybe_imbalance.map(|imbalance| (imbalance, dest))
@@ -506,7 +551,7 @@ impl<T: Config> Pallet<T> {
 			let era_duration = (now_as_millis_u64.defensive_saturating_sub(active_era_start))
 				.saturated_into::<u64>();
 			let staked = Self::eras_total_stake(&active_era.index);
-			let issuance = T::Currency::total_issuance();
+			let issuance = asset::total_issuance::<T>();
 
 			let (validator_payout, remainder) =
 				T::EraPayout::era_payout(staked, issuance, era_duration);
@@ -527,7 +572,7 @@ impl<T: Config> Pallet<T> {
 
 			// Set ending era reward.
 			<ErasValidatorReward<T>>::insert(&active_era.index, validator_payout);
-			T::RewardRemainder::on_unbalanced(T::Currency::issue(remainder));
+			T::RewardRemainder::on_unbalanced(asset::issue::<T>(remainder));
 
 			// Clear offending validators.
 			<OffendingValidators<T>>::kill();
@@ -576,7 +621,10 @@ impl<T: Config> Pallet<T> {
 		is_genesis: bool,
 	) -> Option<BoundedVec<T::AccountId, MaxWinnersOf<T>>> {
 		let election_result: BoundedVec<_, MaxWinnersOf<T>> = if is_genesis {
-			let result = <T::GenesisElectionProvider>::elect().map_err(|e| {
+			let result = <T::GenesisElectionProvider>::elect(
+				<T::GenesisElectionProvider as ElectionProviderBase>::lsp(),
+			)
+			.map_err(|e| {
 				log!(warn, "genesis election provider failed due to {:?}", e);
 				Self::deposit_event(Event::StakingElectionFailed);
 			});
@@ -588,7 +636,7 @@ impl<T: Config> Pallet<T> {
 				// both bounds checked in integrity test to be equal
 				.defensive_unwrap_or_default()
 		} else {
-			let result = <T::ElectionProvider>::elect().map_err(|e| {
+			let result = Self::elect_all_pages().map_err(|e| {
 				log!(warn, "election provider failed due to {:?}", e);
 				Self::deposit_event(Event::StakingElectionFailed);
 			});
@@ -626,6 +674,107 @@ impl<T: Config> Pallet<T> {
 		Some(Self::trigger_new_era(start_session_index, exposures))
 	}
 
+	/// Fetch the next page of a multi-page election result, if the election is close enough.
+	///
+	/// All but the least significant page are fetched one page per block, in the blocks right
+	/// before [`ElectionDataProvider::next_election_prediction`]. The least significant page is
+	/// fetched by [`Self::elect_all_pages`] when the new era is planned.
+	///
+	/// Returns the weight consumed, on top of the weight registered by the election provider.
+	pub(crate) fn maybe_fetch_election_page(now: BlockNumberFor<T>) -> Weight {
+		let msp = <T::ElectionProvider as ElectionProviderBase>::msp();
+		let lsp = <T::ElectionProvider as ElectionProviderBase>::lsp();
+		if msp == lsp {
+			return Weight::zero()
+		}
+
+		let remaining =
+			<Self as ElectionDataProvider>::next_election_prediction(now).saturating_sub(now);
+		// `next_election_prediction` reads the current era, its start session, the planned
+		// session and the forcing mode.
+		let prediction_weight = T::DbWeight::get().reads(4);
+		if remaining.is_zero() || remaining > msp.into() {
+			return prediction_weight
+		}
+
+		let page = NextElectionPage::<T>::get().unwrap_or(msp);
+		if page == lsp {
+			return prediction_weight.saturating_add(T::DbWeight::get().reads(1))
+		}
+
+		match <T::ElectionProvider>::elect(page) {
+			Ok(supports) => {
+				log!(
+					debug,
+					"fetched page {} of the election result, {} winners",
+					page,
+					supports.len()
+				);
+				let mut merged: BTreeMap<_, _> =
+					ElectionPartialSupports::<T>::get().into_iter().collect();
+				Self::merge_supports(&mut merged, supports);
+				// the winners of all pages are a subset of the winners of the most significant
+				// page, thus the merged supports are bounded by `MaxWinners` as well.
+				ElectionPartialSupports::<T>::put(
+					BoundedSupportsOf::<T::ElectionProvider>::defensive_truncate_from(
+						merged.into_iter().collect::<Vec<_>>(),
+					),
+				);
+				NextElectionPage::<T>::put(page - 1);
+			},
+			Err(e) => {
+				// start over when the new era is planned.
+				log!(warn, "failed to fetch page {} of the election result due to {:?}", page, e);
+				ElectionPartialSupports::<T>::kill();
+				NextElectionPage::<T>::kill();
+			},
+		}
+
+		prediction_weight.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+
+	/// Fetch the remaining pages of the election result from `T::ElectionProvider` and merge them
+	/// with the pages fetched so far into a single set of supports.
+	///
+	/// Pages are requested from the most significant page down to the least significant one. A
+	/// validator may be backed by voters of several pages, in which case its supports are summed
+	/// up. With a single page, this is the same as a single call to `elect`.
+	pub(crate) fn elect_all_pages() -> Result<
+		BoundedSupportsOf<T::ElectionProvider>,
+		<T::ElectionProvider as ElectionProviderBase>::Error,
+	> {
+		let msp = <T::ElectionProvider as ElectionProviderBase>::msp();
+		let lsp = <T::ElectionProvider as ElectionProviderBase>::lsp();
+		if msp == lsp {
+			return <T::ElectionProvider>::elect(lsp)
+		}
+
+		let mut merged: BTreeMap<T::AccountId, Support<T::AccountId>> =
+			ElectionPartialSupports::<T>::take().into_iter().collect();
+		let next_page = NextElectionPage::<T>::take().unwrap_or(msp);
+		for page in (lsp..=next_page).rev() {
+			let supports = <T::ElectionProvider>::elect(page)?;
+			log!(debug, "fetched page {} of the election result, {} winners", page, supports.len());
+			Self::merge_supports(&mut merged, supports);
+		}
+
+		// the winners of all pages are a subset of the winners of the most significant page, thus
+		// the merged result is bounded by `MaxWinners` as well.
+		Ok(merged.into_iter().collect::<Vec<_>>().try_into().defensive_unwrap_or_default())
+	}
+
+	/// Add the supports of a page of the election result to the `merged` supports.
+	fn merge_supports(
+		merged: &mut BTreeMap<T::AccountId, Support<T::AccountId>>,
+		supports: BoundedSupportsOf<T::ElectionProvider>,
+	) {
+		for (validator, support) in supports.into_iter() {
+			let merged_support = merged.entry(validator).or_default();
+			merged_support.total = merged_support.total.saturating_add(support.total);
+			merged_support.voters.extend(support.voters);
+		}
+	}
+
 	/// Process the output of the election.
 	///
 	/// Store staking information for the new planned era
@@ -678,7 +827,7 @@ impl<T: Config> Pallet<T> {
 	fn collect_exposures(
 		supports: BoundedSupportsOf<T::ElectionProvider>,
 	) -> BoundedVec<(T::AccountId, Exposure<T::AccountId, BalanceOf<T>>), MaxWinnersOf<T>> {
-		let total_issuance = T::Currency::total_issuance();
+		let total_issuance = asset::total_issuance::<T>();
 		let to_currency = |e: frame_election_provider_support::ExtendedBalance| {
 			T::CurrencyToVote::to_currency(e, total_issuance)
 		};
@@ -720,6 +869,9 @@ impl<T: Config> Pallet<T> {
 	pub(crate) fn kill_stash(stash: &T::AccountId, num_slashing_spans: u32) -> DispatchResult {
 		slashing::clear_stash_metadata::<T>(&stash, num_slashing_spans)?;
 
+		// virtual stakers never increment the consumer reference of the stash.
+		let is_virtual_staker = Self::is_virtual_staker(stash);
+
 		// removes controller from `Bonded` and staking ledger from `Ledger`, as well as reward
 		// setting of the stash in `Payee`.
 		StakingLedger::<T>::kill(&stash)?;
@@ -727,7 +879,9 @@ impl<T: Config> Pallet<T> {
 		Self::do_remove_validator(&stash);
 		Self::do_remove_nominator(&stash);
 
-		frame_system::Pallet::<T>::dec_consumers(&stash);
+		if !is_virtual_staker {
+			frame_system::Pallet::<T>::dec_consumers(&stash);
+		}
 
 		Ok(())
 	}
@@ -821,15 +975,20 @@ impl<T: Config> Pallet<T> {
 		SlashRewardFraction::<T>::put(fraction);
 	}
 
-	/// Get all of the voters that are eligible for the npos election.
+	/// Get the voters of the given `page` that are eligible for the npos election.
 	///
-	/// `maybe_max_len` can imposes a cap on the number of voters returned;
+	/// `bounds` can impose a cap on the number and size of the voters returned in this page.
+	///
+	/// The voters are handed out in the order of `T::VoterList`: the most significant page starts
+	/// from the top of the list, and every following page continues from the first voter that was
+	/// not handed out yet, as tracked in [`VoterSnapshotStatus`]. The status is reset once the
+	/// least significant page has been requested.
 	///
 	/// Sets `MinimumActiveStake` to the minimum active nominator stake in the returned set of
-	/// nominators.
+	/// nominators of all the pages so far.
 	///
 	/// This function is self-weighing as [`DispatchClass::Mandatory`].
-	pub fn get_npos_voters(bounds: DataProviderBounds) -> Vec<VoterOf<Self>> {
+	pub fn get_npos_voters(bounds: DataProviderBounds, page: PageIndex) -> Vec<VoterOf<Self>> {
 		let mut voters_size_tracker: StaticTracker<Self> = StaticTracker::default();
 
 		let final_predicted_len = {
@@ -847,7 +1006,26 @@ impl<T: Config> Pallet<T> {
 		let mut nominators_taken = 0u32;
 		let mut min_active_stake = u64::MAX;
 
-		let mut sorted_voters = T::VoterList::iter();
+		// the most significant page always starts a new snapshot.
+		let status = if page == <T::ElectionProvider as ElectionProviderBase>::msp() {
+			SnapshotStatus::Waiting
+		} else {
+			VoterSnapshotStatus::<T>::get()
+		};
+		let mut sorted_voters: Box<dyn Iterator<Item = T::AccountId>> = match status {
+			// start the snapshot from the beginning of the list.
+			SnapshotStatus::Waiting => T::VoterList::iter(),
+			// continue from the first voter that was not handed out yet.
+			SnapshotStatus::Ongoing(ref next) => match T::VoterList::iter_from(next).defensive() {
+				Ok(iter) => Box::new(sp_std::iter::once(next.clone()).chain(iter)),
+				Err(_) => Box::new(Vec::<T::AccountId>::new().into_iter()),
+			},
+			// all voters have been handed out already.
+			SnapshotStatus::Consumed => Box::new(Vec::<T::AccountId>::new().into_iter()),
+		};
+		// the voter that did not fit into this page, if any.
+		let mut next_voter = None;
+
 		while all_voters.len() < final_predicted_len as usize &&
 			voters_seen < (NPOS_MAX_ITERATIONS_COEFFICIENT * final_predicted_len as u32)
 		{
@@ -865,6 +1043,7 @@ impl<T: Config> Pallet<T> {
 				log!(debug, "voter's active balance is 0. skip this voter.");
 				continue
 			}
+			let cursor = voter.clone();
 
 			if let Some(Nominations { targets, .. }) = <Nominators<T>>::get(&voter) {
 				if !targets.is_empty() {
@@ -878,6 +1057,7 @@ impl<T: Config> Pallet<T> {
 						Self::deposit_event(Event::<T>::SnapshotVotersSizeExceeded {
 							size: voters_size_tracker.size as u32,
 						});
+						next_voter = Some(cursor);
 						break
 					}
 
@@ -903,6 +1083,7 @@ impl<T: Config> Pallet<T> {
 					Self::deposit_event(Event::<T>::SnapshotVotersSizeExceeded {
 						size: voters_size_tracker.size as u32,
 					});
+					next_voter = Some(cursor);
 					break
 				}
 				all_voters.push(self_vote);
@@ -923,17 +1104,38 @@ impl<T: Config> Pallet<T> {
 		// all_voters should have not re-allocated.
 		debug_assert!(all_voters.capacity() == final_predicted_len as usize);
 
-		Self::register_weight(T::WeightInfo::get_npos_voters(validators_taken, nominators_taken));
+		// the first voter of the next page.
+		let next_voter = next_voter.or_else(|| sorted_voters.next());
 
-		let min_active_stake: T::CurrencyBalance =
-			if all_voters.is_empty() { Zero::zero() } else { min_active_stake.into() };
+		Self::register_weight(
+			T::WeightInfo::get_npos_voters(validators_taken, nominators_taken)
+				.saturating_add(T::DbWeight::get().reads_writes(2, 1)),
+		);
 
-		MinimumActiveStake::<T>::put(min_active_stake);
+		if matches!(status, SnapshotStatus::Waiting) {
+			let min_active_stake: T::CurrencyBalance =
+				if all_voters.is_empty() { Zero::zero() } else { min_active_stake.into() };
+			MinimumActiveStake::<T>::put(min_active_stake);
+		} else if !all_voters.is_empty() {
+			// keep the minimum of all the pages of this snapshot.
+			MinimumActiveStake::<T>::mutate(|current| {
+				*current = (*current).min(min_active_stake.into())
+			});
+		}
+
+		// update the status of the voter snapshot for the next page.
+		match (page == <T::ElectionProvider as ElectionProviderBase>::lsp(), next_voter) {
+			// the last page of the snapshot, wait for the next one.
+			(true, _) => VoterSnapshotStatus::<T>::kill(),
+			(false, Some(next)) => VoterSnapshotStatus::<T>::put(SnapshotStatus::Ongoing(next)),
+			(false, None) => VoterSnapshotStatus::<T>::put(SnapshotStatus::Consumed),
+		}
 
 		log!(
 			info,
-			"generated {} npos voters, {} from validators and {} nominators",
+			"generated {} npos voters for page {}, {} from validators and {} nominators",
 			all_voters.len(),
+			page,
 			validators_taken,
 			nominators_taken
 		);
@@ -1019,6 +1221,7 @@ impl<T: Config> Pallet<T> {
 	pub fn do_remove_nominator(who: &T::AccountId) -> bool {
 		let outcome = if Nominators::<T>::contains_key(who) {
 			Nominators::<T>::remove(who);
+			Self::skip_removed_voter_in_snapshot(who);
 			let _ = T::VoterList::on_remove(who).defensive();
 			true
 		} else {
@@ -1064,6 +1267,7 @@ impl<T: Config> Pallet<T> {
 	pub fn do_remove_validator(who: &T::AccountId) -> bool {
 		let outcome = if Validators::<T>::contains_key(who) {
 			Validators::<T>::remove(who);
+			Self::skip_removed_voter_in_snapshot(who);
 			let _ = T::VoterList::on_remove(who).defensive();
 			true
 		} else {
@@ -1078,6 +1282,23 @@ impl<T: Config> Pallet<T> {
 		outcome
 	}
 
+	/// Move the cursor of an ongoing voter snapshot past `who`, which is about to be removed from
+	/// `T::VoterList`.
+	///
+	/// The cursor always points to a voter in the list, so that the next page of the snapshot can
+	/// continue from it.
+	fn skip_removed_voter_in_snapshot(who: &T::AccountId) {
+		if let SnapshotStatus::Ongoing(next) = VoterSnapshotStatus::<T>::get() {
+			if &next == who {
+				let status = T::VoterList::iter_from(who)
+					.ok()
+					.and_then(|mut voters| voters.next())
+					.map_or(SnapshotStatus::Consumed, SnapshotStatus::Ongoing);
+				VoterSnapshotStatus::<T>::put(status);
+			}
+		}
+	}
+
 	/// Register some amount of weight directly with the system pallet.
 	///
 	/// This is always mandatory weight.
@@ -1131,9 +1352,12 @@ impl<T: Config> ElectionDataProvider for Pallet<T> {
 		Ok(Self::validator_count())
 	}
 
-	fn electing_voters(bounds: DataProviderBounds) -> data_provider::Result<Vec<VoterOf<Self>>> {
+	fn electing_voters(
+		bounds: DataProviderBounds,
+		page: PageIndex,
+	) -> data_provider::Result<Vec<VoterOf<Self>>> {
 		// This can never fail -- if `maybe_max_len` is `Some(_)` we handle it.
-		let voters = Self::get_npos_voters(bounds);
+		let voters = Self::get_npos_voters(bounds, page);
 
 		debug_assert!(!bounds.exhausted(
 			SizeBound(voters.encoded_size() as u32).into(),
@@ -1143,7 +1367,11 @@ impl<T: Config> ElectionDataProvider for Pallet<T> {
 		Ok(voters)
 	}
 
-	fn electable_targets(bounds: DataProviderBounds) -> data_provider::Result<Vec<T::AccountId>> {
+	fn electable_targets(
+		bounds: DataProviderBounds,
+		_page: PageIndex,
+	) -> data_provider::Result<Vec<T::AccountId>> {
+		// all the targets are returned in every page, since they are not paged.
 		let targets = Self::get_npos_targets(bounds);
 
 		// We can't handle this case yet -- return an error. WIP to improve handling this case in
@@ -1517,7 +1745,7 @@ impl<T: Config> ScoreProvider<T::AccountId> for Pallet<T> {
 		// also, we play a trick to make sure that a issuance based-`CurrencyToVote` behaves well:
 		// This will make sure that total issuance is zero, thus the currency to vote will be a 1-1
 		// conversion.
-		let imbalance = T::Currency::burn(T::Currency::total_issuance());
+		let imbalance = asset::burn::<T>(asset::total_issuance::<T>());
 		// kinda ugly, but gets the job done. The fact that this works here is a HUGE exception.
 		// Don't try this pattern in other places.
 		sp_std::mem::forget(imbalance);
@@ -1705,7 +1933,7 @@ impl<T: Config> StakingInterface for Pallet<T> {
 	}
 
 	fn bond_extra(who: &Self::AccountId, extra: Self::Balance) -> DispatchResult {
-		Self::bond_extra(RawOrigin::Signed(who.clone()).into(), extra)
+		Self::do_bond_extra(who, extra)
 	}
 
 	fn unbond(who: &Self::AccountId, value: Self::Balance) -> DispatchResult {
@@ -1799,6 +2027,31 @@ impl<T: Config> StakingInterface for Pallet<T> {
 		}
 	}
 
+	fn update_payee(stash: &Self::AccountId, reward_acc: &Self::AccountId) -> DispatchResult {
+		// Since virtual stakers are not allowed to compound their rewards as this pallet does not
+		// manage their holds, we do not allow the reward account to be the stash. External
+		// pallets that manage the virtual bond can claim the rewards and re-bond them.
+		ensure!(
+			!Self::is_virtual_staker(stash) || stash != reward_acc,
+			Error::<T>::RewardDestinationRestricted
+		);
+
+		let ledger = Self::ledger(Stash(stash.clone()))?;
+		ledger
+			.set_payee(RewardDestination::Account(reward_acc.clone()))
+			.defensive_proof("ledger was retrieved from storage, thus it is bonded; qed.")?;
+
+		Ok(())
+	}
+
+	fn is_virtual_staker(who: &Self::AccountId) -> bool {
+		Self::is_virtual_staker(who)
+	}
+
+	fn slash_reward_fraction() -> Perbill {
+		SlashRewardFraction::<T>::get()
+	}
+
 	sp_staking::runtime_benchmarks_enabled! {
 		fn nominations(who: &Self::AccountId) -> Option<Vec<T::AccountId>> {
 			Nominators::<T>::get(who).map(|n| n.targets.into_inner())
@@ -1827,6 +2080,59 @@ impl<T: Config> StakingInterface for Pallet<T> {
 	}
 }
 
+impl<T: Config> StakingUnchecked for Pallet<T> {
+	fn migrate_to_virtual_staker(who: &Self::AccountId) -> DispatchResult {
+		ensure!(!Self::is_virtual_staker(who), Error::<T>::VirtualStakerNotAllowed);
+		ensure!(
+			StakingLedger::<T>::is_bonded(StakingAccount::Stash(who.clone())),
+			Error::<T>::NotStash
+		);
+
+		// release all the funds backing the stake, be it a hold or a legacy lock.
+		if StakingLedger::<T>::has_legacy_lock(who) {
+			T::OldCurrency::remove_lock(STAKING_ID, who);
+		}
+		asset::kill_stake::<T>(who)?;
+		frame_system::Pallet::<T>::dec_consumers(who);
+
+		VirtualStakers::<T>::insert(who, ());
+
+		Ok(())
+	}
+
+	fn virtual_bond(
+		keyless_who: &Self::AccountId,
+		value: Self::Balance,
+		payee: &Self::AccountId,
+	) -> DispatchResult {
+		if StakingLedger::<T>::is_bonded(StakingAccount::Stash(keyless_who.clone())) {
+			return Err(Error::<T>::AlreadyBonded.into())
+		}
+
+		// check if payee not same as who.
+		ensure!(keyless_who != payee, Error::<T>::RewardDestinationRestricted);
+
+		// mark who as a virtual staker.
+		VirtualStakers::<T>::insert(keyless_who, ());
+
+		Self::deposit_event(Event::<T>::Bonded { stash: keyless_who.clone(), amount: value });
+		let ledger = StakingLedger::<T>::new(keyless_who.clone(), value);
+
+		ledger.bond(RewardDestination::Account(payee.clone()))?;
+
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn migrate_to_direct_staker(who: &Self::AccountId) {
+		assert!(VirtualStakers::<T>::contains_key(who));
+		let ledger = StakingLedger::<T>::get(Stash(who.clone())).unwrap();
+		asset::update_stake::<T>(who, ledger.total).expect("funds must be transferred to stash");
+		frame_system::Pallet::<T>::inc_consumers(who).expect("stash must exist");
+		VirtualStakers::<T>::remove(who);
+	}
+}
+
 #[cfg(any(test, feature = "try-runtime"))]
 impl<T: Config> Pallet<T> {
 	pub(crate) fn do_try_state(_: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
@@ -19,13 +19,18 @@
 
 use codec::Codec;
 use frame_election_provider_support::{
-	ElectionProvider, ElectionProviderBase, SortedListProvider, VoteWeight,
+	BoundedSupportsOf, ElectionProvider, ElectionProviderBase, PageIndex, SortedListProvider,
+	VoteWeight,
 };
 use frame_support::{
 	pallet_prelude::*,
 	traits::{
-		Currency, Defensive, DefensiveSaturating, EnsureOrigin, EstimateNextNewSession, Get,
-		LockableCurrency, OnUnbalanced, UnixTime,
+		fungible::{
+			hold::{Balanced as FunHoldBalanced, Mutate as FunHoldMutate},
+			Mutate as FunMutate,
+		},
+		Defensive, DefensiveSaturating, EnsureOrigin, EstimateNextNewSession, Get,
+		InspectLockableCurrency, OnUnbalanced, UnixTime,
 	},
 	weights::Weight,
 	BoundedVec,
@@ -47,10 +52,10 @@ mod impls;
 pub use impls::*;
 
 use crate::{
-	slashing, weights::WeightInfo, AccountIdLookupOf, ActiveEraInfo, BalanceOf, EraPayout,
+	asset, slashing, weights::WeightInfo, AccountIdLookupOf, ActiveEraInfo, BalanceOf, EraPayout,
 	EraRewardPoints, Exposure, ExposurePage, Forcing, MaxNominationsOf, NegativeImbalanceOf,
 	Nominations, NominationsQuota, PositiveImbalanceOf, RewardDestination, SessionInterface,
-	StakingLedger, UnappliedSlash, UnlockChunk, ValidatorPrefs,
+	SnapshotStatus, StakingLedger, UnappliedSlash, UnlockChunk, ValidatorPrefs,
 };
 
 // The speculative number of spans are used as an input of the weight annotation of
@@ -86,12 +91,25 @@ pub mod pallet {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
-		/// The staking balance.
-		type Currency: LockableCurrency<
+		/// The old trait for staking balance. Deprecated and only used for migrating the legacy
+		/// staking locks of existing ledgers to holds.
+		type OldCurrency: InspectLockableCurrency<
 			Self::AccountId,
 			Moment = BlockNumberFor<Self>,
 			Balance = Self::CurrencyBalance,
 		>;
+
+		/// The staking balance. Staked funds are held with [`HoldReason::Staking`].
+		type Currency: FunHoldMutate<
+				Self::AccountId,
+				Reason = Self::RuntimeHoldReason,
+				Balance = Self::CurrencyBalance,
+			> + FunMutate<Self::AccountId, Balance = Self::CurrencyBalance>
+			+ FunHoldBalanced<Self::AccountId, Balance = Self::CurrencyBalance>;
+
+		/// Overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
 		/// Just the `Currency::Balance` type; we have this item to allow us to constrain it to
 		/// `From<u64>`.
 		type CurrencyBalance: sp_runtime::traits::AtLeast32BitUnsigned
@@ -285,6 +303,14 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 	}
 
+	/// A reason for placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Funds on stake by a nominator or a validator.
+		#[codec(index = 0)]
+		Staking,
+	}
+
 	/// The ideal number of active validators.
 	#[pallet::storage]
 	#[pallet::getter(fn validator_count)]
@@ -303,7 +329,7 @@ pub mod pallet {
 	#[pallet::unbounded]
 	pub type Invulnerables<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
 
-	/// Map from all locked "stash" accounts to the controller account.
+	/// Map from all bonded "stash" accounts to the controller account.
 	///
 	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
 	#[pallet::storage]
@@ -379,6 +405,15 @@ pub mod pallet {
 	pub type Nominators<T: Config> =
 		CountedStorageMap<_, Twox64Concat, T::AccountId, Nominations<T>>;
 
+	/// Stakers whose funds are managed by other pallets.
+	///
+	/// This pallet does not apply any locks on them, therefore they are only virtually bonded. They
+	/// are expected to be keyless accounts and hence should not be allowed to mutate their ledger
+	/// directly via this pallet. Instead, these accounts are managed by other pallets and accessed
+	/// via low level apis. We keep track of them to do minimal integrity checks.
+	#[pallet::storage]
+	pub type VirtualStakers<T: Config> = CountedStorageMap<_, Twox64Concat, T::AccountId, ()>;
+
 	/// The maximum nominator count before we stop allowing new validators to join.
 	///
 	/// When this value is not set, no limits are enforced.
@@ -665,6 +700,33 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type ChillThreshold<T: Config> = StorageValue<_, Percent, OptionQuery>;
 
+	/// Voter snapshot progress status.
+	///
+	/// If the status is `Ongoing`, it keeps a cursor of the last voter retrieved to proceed when
+	/// creating the next page of the voters snapshot of a multi-page election.
+	#[pallet::storage]
+	pub(crate) type VoterSnapshotStatus<T: Config> =
+		StorageValue<_, SnapshotStatus<T::AccountId>, ValueQuery>;
+
+	/// The next page of the election result to fetch from [`Config::ElectionProvider`].
+	///
+	/// Only used if the election provider has more than one page. All but the least significant
+	/// page are fetched one page per block, right before the election. `None` if no page of the
+	/// upcoming election was fetched yet.
+	#[pallet::storage]
+	pub(crate) type NextElectionPage<T: Config> = StorageValue<_, PageIndex, OptionQuery>;
+
+	/// The merged supports of the pages of the election result that were fetched so far.
+	///
+	/// The remaining pages are merged into these supports when the new era is planned. The winners
+	/// of all pages are a subset of the winners of the most significant page, hence these are
+	/// bounded by the `MaxWinners` of [`Config::ElectionProvider`]. The voters of each support are
+	/// bounded by the voters of the election snapshot.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub(crate) type ElectionPartialSupports<T: Config> =
+		StorageValue<_, BoundedSupportsOf<T::ElectionProvider>, ValueQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -709,7 +771,7 @@ pub mod pallet {
 					status
 				);
 				assert!(
-					T::Currency::free_balance(stash) >= balance,
+					asset::free_to_stake::<T>(stash) >= balance,
 					"Stash does not have enough balance to bond."
 				);
 				frame_support::assert_ok!(<Pallet<T>>::bond(
@@ -791,6 +853,9 @@ pub mod pallet {
 		SnapshotTargetsSizeExceeded { size: u32 },
 		/// A new force era mode was set.
 		ForceEra { mode: Forcing },
+		/// The legacy staking lock of a stash was migrated to a hold. `force_withdraw` is the
+		/// amount that could not be held and was withdrawn from the stake.
+		CurrencyMigrated { stash: T::AccountId, force_withdraw: BalanceOf<T> },
 	}
 
 	#[pallet::error]
@@ -853,13 +918,34 @@ pub mod pallet {
 		BoundNotMet,
 		/// Used when attempting to use deprecated controller account logic.
 		ControllerDeprecated,
+		/// The stash does not have enough free funds to be held for staking.
+		NotEnoughFunds,
+		/// The stake of this stash is already held and has no legacy staking lock to migrate.
+		AlreadyMigrated,
+		/// Operation not allowed for virtual stakers.
+		VirtualStakerNotAllowed,
+		/// Provided reward destination is not allowed.
+		RewardDestinationRestricted,
+	}
+
+	#[pallet::view_functions]
+	impl<T: Config> Pallet<T> {
+		/// Returns the nominations quota for a nominator with a given balance.
+		pub fn nominations_quota(balance: BalanceOf<T>) -> u32 {
+			Self::api_nominations_quota(balance)
+		}
+
+		/// Returns the page count of exposures for a validator `account` in a given era.
+		pub fn eras_stakers_page_count(era: EraIndex, account: T::AccountId) -> Page {
+			Self::api_eras_stakers_page_count(era, account)
+		}
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
-			// just return the weight of the on_finalize.
-			T::DbWeight::get().reads(1)
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			// the weight of the on_finalize, and of fetching the next page of the election result.
+			T::DbWeight::get().reads(1).saturating_add(Self::maybe_fetch_election_page(now))
 		}
 
 		fn on_finalize(_n: BlockNumberFor<T>) {
@@ -891,6 +977,11 @@ pub mod pallet {
 					<T::GenesisElectionProvider as ElectionProviderBase>::MaxWinners::get()
 			);
 
+			// the genesis election must happen within a single page, and the election provider
+			// must have at least one page.
+			assert_eq!(<T::GenesisElectionProvider as ElectionProviderBase>::Pages::get(), 1);
+			assert!(!<T::ElectionProvider as ElectionProviderBase>::Pages::get().is_zero());
+
 			assert!(
 				T::SlashDeferDuration::get() < T::BondingDuration::get() || T::BondingDuration::get() == 0,
 				"As per documentation, slash defer duration ({}) should be less than bonding duration ({}).",
@@ -936,13 +1027,13 @@ pub mod pallet {
 			}
 
 			// Reject a bond which is considered to be _dust_.
-			if value < T::Currency::minimum_balance() {
+			if value < asset::existential_deposit::<T>() {
 				return Err(Error::<T>::InsufficientBond.into())
 			}
 
 			frame_system::Pallet::<T>::inc_consumers(&stash).map_err(|_| Error::<T>::BadState)?;
 
-			let stash_balance = T::Currency::free_balance(&stash);
+			let stash_balance = asset::free_to_stake::<T>(&stash);
 			let value = value.min(stash_balance);
 			Self::deposit_event(Event::<T>::Bonded { stash: stash.clone(), amount: value });
 			let ledger = StakingLedger::<T>::new(stash.clone(), value);
@@ -975,30 +1066,9 @@ pub mod pallet {
 			#[pallet::compact] max_additional: BalanceOf<T>,
 		) -> DispatchResult {
 			let stash = ensure_signed(origin)?;
+			ensure!(!Self::is_virtual_staker(&stash), Error::<T>::VirtualStakerNotAllowed);
 
-			let mut ledger = Self::ledger(StakingAccount::Stash(stash.clone()))?;
-
-			let stash_balance = T::Currency::free_balance(&stash);
-			if let Some(extra) = stash_balance.checked_sub(&ledger.total) {
-				let extra = extra.min(max_additional);
-				ledger.total += extra;
-				ledger.active += extra;
-				// Last check: the new active amount of ledger must be more than ED.
-				ensure!(
-					ledger.active >= T::Currency::minimum_balance(),
-					Error::<T>::InsufficientBond
-				);
-
-				// NOTE: ledger must be updated prior to calling `Self::weight_of`.
-				ledger.update()?;
-				// update this staker in the sorted list, if they exist in it.
-				if T::VoterList::contains(&stash) {
-					let _ = T::VoterList::on_update(&stash, Self::weight_of(&stash)).defensive();
-				}
-
-				Self::deposit_event(Event::<T>::Bonded { stash, amount: extra });
-			}
-			Ok(())
+			Self::do_bond_extra(&stash, max_additional)
 		}
 
 		/// Schedule a portion of the stash to be unlocked ready for transfer out after the bond
@@ -1059,7 +1129,7 @@ pub mod pallet {
 				ledger.active -= value;
 
 				// Avoid there being a dust balance left in the staking system.
-				if ledger.active < T::Currency::minimum_balance() {
+				if ledger.active < asset::existential_deposit::<T>() {
 					value += ledger.active;
 					ledger.active = Zero::zero();
 				}
@@ -1495,7 +1565,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
-			// Remove all staking-related information and lock.
+			// Remove all staking-related information and release the held funds.
 			Self::kill_stash(&stash, num_slashing_spans)?;
 
 			Ok(())
@@ -1592,7 +1662,10 @@ pub mod pallet {
 			let initial_unlocking = ledger.unlocking.len() as u32;
 			let (ledger, rebonded_value) = ledger.rebond(value);
 			// Last check: the new active amount of ledger must be more than ED.
-			ensure!(ledger.active >= T::Currency::minimum_balance(), Error::<T>::InsufficientBond);
+			ensure!(
+				ledger.active >= asset::existential_deposit::<T>(),
+				Error::<T>::InsufficientBond
+			);
 
 			Self::deposit_event(Event::<T>::Bonded {
 				stash: ledger.stash.clone(),
@@ -1640,12 +1713,15 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let _ = ensure_signed(origin)?;
 
-			let ed = T::Currency::minimum_balance();
-			let reapable = T::Currency::total_balance(&stash) < ed ||
+			// virtual stakers should not be allowed to be reaped.
+			ensure!(!Self::is_virtual_staker(&stash), Error::<T>::VirtualStakerNotAllowed);
+
+			let ed = asset::existential_deposit::<T>();
+			let reapable = asset::total_balance::<T>(&stash) < ed ||
 				Self::ledger(Stash(stash.clone())).map(|l| l.total).unwrap_or_default() < ed;
 			ensure!(reapable, Error::<T>::FundedTarget);
 
-			// Remove all staking-related information and lock.
+			// Remove all staking-related information and release the held funds.
 			Self::kill_stash(&stash, num_slashing_spans)?;
 
 			Ok(Pays::No.into())
@@ -1978,6 +2054,37 @@ pub mod pallet {
 			}
 			Ok(Some(T::WeightInfo::deprecate_controller_batch(controllers.len() as u32)).into())
 		}
+
+		/// Migrates the legacy staking lock of a `stash` to a hold.
+		///
+		/// Ledgers bonded before staking used holds are migrated lazily the next time they are
+		/// updated. This call allows anyone to migrate a ledger that is not touched, so that the
+		/// whole staking state can be migrated over multiple blocks.
+		///
+		/// If the stash cannot hold its whole stake, the stake is reduced by the amount that cannot
+		/// be held, e.g. the existential deposit that was previously covered by the lock.
+		///
+		/// The dispatch origin for this call must be _Signed_ by any account.
+		///
+		/// Refunds the transaction fees upon successful execution.
+		///
+		/// Emits `CurrencyMigrated`.
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::migrate_currency())]
+		pub fn migrate_currency(
+			origin: OriginFor<T>,
+			stash: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let _ = ensure_signed(origin)?;
+
+			let ledger = Self::ledger(Stash(stash.clone()))?;
+			ensure!(!Self::is_virtual_staker(&stash), Error::<T>::VirtualStakerNotAllowed);
+			ensure!(StakingLedger::<T>::has_legacy_lock(&stash), Error::<T>::AlreadyMigrated);
+
+			ledger.migrate_legacy_lock()?;
+
+			Ok(Pays::No.into())
+		}
 	}
 }
 
@@ -50,14 +50,14 @@
 //! Based on research at <https://research.web3.foundation/en/latest/polkadot/slashing/npos.html>
 
 use crate::{
-	BalanceOf, Config, Error, Exposure, NegativeImbalanceOf, NominatorSlashInEra,
-	OffendingValidators, Pallet, Perbill, SessionInterface, SpanSlash, UnappliedSlash,
-	ValidatorSlashInEra,
+	asset, BalanceOf, Config, Error, Exposure, NegativeImbalanceOf, NominatorSlashInEra,
+	OffendingValidators, Pallet, Perbill, SessionInterface, SpanSlash, StakingLedger,
+	UnappliedSlash, ValidatorSlashInEra,
 };
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	ensure,
-	traits::{Currency, Defensive, DefensiveSaturating, Get, Imbalance, OnUnbalanced},
+	traits::{Defensive, DefensiveSaturating, Get, Imbalance, OnUnbalanced},
 };
 use scale_info::TypeInfo;
 use sp_runtime::{
@@ -594,7 +594,7 @@ pub(crate) fn clear_stash_metadata<T: Config>(
 
 // apply the slash to a stash account, deducting any missing funds from the reward
 // payout, saturating at 0. this is mildly unfair but also an edge-case that
-// can only occur when overlapping locked funds have been slashed.
+// can only occur when overlapping held funds have been slashed.
 pub fn do_slash<T: Config>(
 	stash: &T::AccountId,
 	value: BalanceOf<T>,
@@ -608,15 +608,27 @@ pub fn do_slash<T: Config>(
 			Err(_) => return, // nothing to do.
 		};
 
-	let value = ledger.slash(value, T::Currency::minimum_balance(), slash_era);
+	// a stake that is still backed by a legacy staking lock has nothing on hold yet, so migrate it
+	// before slashing the held funds.
+	if !Pallet::<T>::is_virtual_staker(stash) && StakingLedger::<T>::has_legacy_lock(stash) {
+		ledger = match ledger.migrate_legacy_lock().defensive() {
+			Ok(ledger) => ledger,
+			Err(_) => return,
+		};
+	}
 
-	if !value.is_zero() {
-		let (imbalance, missing) = T::Currency::slash(stash, value);
-		slashed_imbalance.subsume(imbalance);
+	let value = ledger.slash(value, asset::existential_deposit::<T>(), slash_era);
 
-		if !missing.is_zero() {
-			// deduct overslash from the reward payout
-			*reward_payout = reward_payout.saturating_sub(missing);
+	if !value.is_zero() {
+		// Skip slashing for virtual stakers. The pallets managing them should handle the slashing.
+		if !Pallet::<T>::is_virtual_staker(stash) {
+			let (imbalance, missing) = asset::slash::<T>(stash, value);
+			slashed_imbalance.subsume(imbalance);
+
+			if !missing.is_zero() {
+				// deduct overslash from the reward payout
+				*reward_payout = reward_payout.saturating_sub(missing);
+			}
 		}
 
 		let _ = ledger
@@ -684,7 +696,7 @@ fn pay_reporters<T: Config>(
 
 		// this cancels out the reporter reward imbalance internally, leading
 		// to no change in total issuance.
-		T::Currency::resolve_creating(reporter, reporter_reward);
+		asset::deposit_slashed::<T>(reporter, reporter_reward);
 	}
 
 	// the rest goes to the on-slash imbalance handler (e.g. treasury)
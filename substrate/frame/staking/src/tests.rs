@@ -109,7 +109,7 @@ fn force_unstake_works() {
 		// Cant transfer
 		assert_noop!(
 			Balances::transfer_allow_death(RuntimeOrigin::signed(11), 1, 10),
-			TokenError::Frozen,
+			TokenError::FundsUnavailable,
 		);
 		// Force unstake requires root.
 		assert_noop!(Staking::force_unstake(RuntimeOrigin::signed(11), 11, 2), BadOrigin);
@@ -479,7 +479,7 @@ fn staking_should_work() {
 		start_session(9);
 		assert_eq_uvec!(validator_controllers(), vec![21, 11]);
 
-		// Note: the stashed value of 4 is still lock
+		// Note: the stashed value of 4 is still held
 		assert_eq!(
 			Staking::ledger(3.into()).unwrap(),
 			StakingLedgerInspect {
@@ -491,7 +491,7 @@ fn staking_should_work() {
 			}
 		);
 		// e.g. it cannot reserve more than 500 that it has free from the total 2000
-		assert_noop!(Balances::reserve(&3, 501), BalancesError::<Test, _>::LiquidityRestrictions);
+		assert_noop!(Balances::reserve(&3, 501), BalancesError::<Test, _>::InsufficientBalance);
 		assert_ok!(Balances::reserve(&3, 409));
 	});
 }
@@ -612,7 +612,7 @@ fn nominating_and_rewards_should_work() {
 			// give the man some money
 			let initial_balance = 1000;
 			for i in [1, 3, 5, 11, 21].iter() {
-				let _ = Balances::make_free_balance_be(i, initial_balance);
+				asset::set_stakeable_balance::<Test>(i, initial_balance);
 			}
 
 			// bond two account pairs and state interest in nomination.
@@ -688,21 +688,21 @@ fn nominating_and_rewards_should_work() {
 			// Nominator 2: has [400/1800 ~ 2/9 from 10] + [600/2200 ~ 3/11 from 21]'s reward. ==>
 			// 2/9 + 3/11
 			assert_eq_error_rate!(
-				Balances::total_balance(&1),
+				asset::stakeable_balance::<Test>(&1),
 				initial_balance + (2 * payout_for_11 / 9 + 3 * payout_for_21 / 11),
 				2,
 			);
 			// Nominator 3: has [400/1800 ~ 2/9 from 10] + [600/2200 ~ 3/11 from 21]'s reward. ==>
 			// 2/9 + 3/11
 			assert_eq_error_rate!(
-				Balances::total_balance(&3),
+				asset::stakeable_balance::<Test>(&3),
 				initial_balance + (2 * payout_for_11 / 9 + 3 * payout_for_21 / 11),
 				2,
 			);
 
 			// Validator 11: got 800 / 1800 external stake => 8/18 =? 4/9 => Validator's share = 5/9
 			assert_eq_error_rate!(
-				Balances::total_balance(&11),
+				asset::stakeable_balance::<Test>(&11),
 				initial_balance + 5 * payout_for_11 / 9,
 				2,
 			);
@@ -987,7 +987,7 @@ fn cannot_transfer_staked_balance() {
 		// Confirm account 11 is stashed
 		assert_eq!(Staking::bonded(&11), Some(11));
 		// Confirm account 11 has some free balance
-		assert_eq!(Balances::free_balance(11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
 		// Confirm account 11 (via controller) is totally staked
 		assert_eq!(Staking::eras_stakers(active_era(), &11).total, 1000);
 		// Confirm account 11 cannot transfer as a result
@@ -997,7 +997,7 @@ fn cannot_transfer_staked_balance() {
 		);
 
 		// Give account 11 extra free balance
-		let _ = Balances::make_free_balance_be(&11, 10000);
+		asset::set_stakeable_balance::<Test>(&11, 10000);
 		// Confirm that account 11 can now transfer some balance
 		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(11), 21, 1));
 	});
@@ -1012,7 +1012,7 @@ fn cannot_transfer_staked_balance_2() {
 		// Confirm account 21 is stashed
 		assert_eq!(Staking::bonded(&21), Some(21));
 		// Confirm account 21 has some free balance
-		assert_eq!(Balances::free_balance(21), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&21), 2000);
 		// Confirm account 21 (via controller) is totally staked
 		assert_eq!(Staking::eras_stakers(active_era(), &21).total, 1000);
 		// Confirm account 21 can transfer at most 1000
@@ -1031,14 +1031,14 @@ fn cannot_reserve_staked_balance() {
 		// Confirm account 11 is stashed
 		assert_eq!(Staking::bonded(&11), Some(11));
 		// Confirm account 11 has some free balance
-		assert_eq!(Balances::free_balance(11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
 		// Confirm account 11 (via controller 10) is totally staked
 		assert_eq!(Staking::eras_stakers(active_era(), &11).own, 1000);
 		// Confirm account 11 cannot reserve as a result
-		assert_noop!(Balances::reserve(&11, 1), BalancesError::<Test, _>::LiquidityRestrictions);
+		assert_noop!(Balances::reserve(&11, 2), BalancesError::<Test, _>::InsufficientBalance);
 
 		// Give account 11 extra free balance
-		let _ = Balances::make_free_balance_be(&11, 10000);
+		asset::set_stakeable_balance::<Test>(&11, 10000);
 		// Confirm account 11 can now reserve balance
 		assert_ok!(Balances::reserve(&11, 1));
 	});
@@ -1053,7 +1053,7 @@ fn reward_destination_works() {
 		// Check the balance of the validator account
 		assert_eq!(Balances::free_balance(10), 1);
 		// Check the balance of the stash account
-		assert_eq!(Balances::free_balance(11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
 		// Check how much is at stake
 		assert_eq!(
 			Staking::ledger(11.into()).unwrap(),
@@ -1076,7 +1076,7 @@ fn reward_destination_works() {
 		// Check that RewardDestination is Staked
 		assert_eq!(Staking::payee(11.into()), Some(RewardDestination::Staked));
 		// Check that reward went to the stash account of validator
-		assert_eq!(Balances::free_balance(11), 1000 + total_payout_0);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000 + total_payout_0);
 		// Check that amount at stake increased accordingly
 		assert_eq!(
 			Staking::ledger(11.into()).unwrap(),
@@ -1105,7 +1105,7 @@ fn reward_destination_works() {
 		// Check that RewardDestination is Stash
 		assert_eq!(Staking::payee(11.into()), Some(RewardDestination::Stash));
 		// Check that reward went to the stash account
-		assert_eq!(Balances::free_balance(11), 1000 + total_payout_0 + total_payout_1);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000 + total_payout_0 + total_payout_1);
 		// Record this value
 		let recorded_stash_balance = 1000 + total_payout_0 + total_payout_1;
 		// Check that amount at stake is NOT increased
@@ -1127,7 +1127,7 @@ fn reward_destination_works() {
 		<Payee<Test>>::insert(&11, RewardDestination::Account(11));
 
 		// Check controller balance
-		assert_eq!(Balances::free_balance(11), 23150);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 23150);
 
 		// Compute total payout now for whole duration as other parameter won't change
 		let total_payout_2 = current_total_payout_for_duration(reward_time_per_era());
@@ -1139,7 +1139,7 @@ fn reward_destination_works() {
 		// Check that RewardDestination is Account(11)
 		assert_eq!(Staking::payee(11.into()), Some(RewardDestination::Account(11)));
 		// Check that reward went to the controller account
-		assert_eq!(Balances::free_balance(11), recorded_stash_balance + total_payout_2);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), recorded_stash_balance + total_payout_2);
 		// Check that amount at stake is NOT increased
 		assert_eq!(
 			Staking::ledger(11.into()).unwrap(),
@@ -1216,7 +1216,7 @@ fn bond_extra_works() {
 		);
 
 		// Give account 11 some large free balance greater than total
-		let _ = Balances::make_free_balance_be(&11, 1000000);
+		asset::set_stakeable_balance::<Test>(&11, 1000000);
 
 		// Call the bond_extra function from controller, add only 100
 		assert_ok!(Staking::bond_extra(RuntimeOrigin::signed(11), 100));
@@ -1261,13 +1261,13 @@ fn bond_extra_and_withdraw_unbonded_works() {
 		assert_ok!(Staking::set_payee(RuntimeOrigin::signed(11), RewardDestination::Stash));
 
 		// Give account 11 some large free balance greater than total
-		let _ = Balances::make_free_balance_be(&11, 1000000);
+		asset::set_stakeable_balance::<Test>(&11, 1000000);
 
 		// Initial config should be correct
 		assert_eq!(active_era(), 0);
 
 		// check the balance of a validator accounts.
-		assert_eq!(Balances::total_balance(&11), 1000000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000000);
 
 		// confirm that 10 is a normal validator and gets paid at the end of the era.
 		mock::start_active_era(1);
@@ -1472,7 +1472,7 @@ fn rebond_works() {
 		assert_ok!(Staking::set_payee(RuntimeOrigin::signed(11), RewardDestination::Stash));
 
 		// Give account 11 some large free balance greater than total
-		let _ = Balances::make_free_balance_be(&11, 1000000);
+		asset::set_stakeable_balance::<Test>(&11, 1000000);
 
 		// confirm that 10 is a normal validator and gets paid at the end of the era.
 		mock::start_active_era(1);
@@ -1598,7 +1598,7 @@ fn rebond_is_fifo() {
 		assert_ok!(Staking::set_payee(RuntimeOrigin::signed(11), RewardDestination::Stash));
 
 		// Give account 11 some large free balance greater than total
-		let _ = Balances::make_free_balance_be(&11, 1000000);
+		asset::set_stakeable_balance::<Test>(&11, 1000000);
 
 		// confirm that 10 is a normal validator and gets paid at the end of the era.
 		mock::start_active_era(1);
@@ -1694,7 +1694,7 @@ fn rebond_emits_right_value_in_event() {
 		assert_ok!(Staking::set_payee(RuntimeOrigin::signed(11), RewardDestination::Stash));
 
 		// Give account 11 some large free balance greater than total
-		let _ = Balances::make_free_balance_be(&11, 1000000);
+		asset::set_stakeable_balance::<Test>(&11, 1000000);
 
 		// confirm that 10 is a normal validator and gets paid at the end of the era.
 		mock::start_active_era(1);
@@ -1857,7 +1857,7 @@ fn reward_to_stake_works() {
 			assert_eq!(Staking::eras_stakers(active_era(), &11).total, 1000);
 			assert_eq!(Staking::eras_stakers(active_era(), &21).total, 2000);
 
-			let _11_balance = Balances::free_balance(&11);
+			let _11_balance = asset::stakeable_balance::<Test>(&11);
 			assert_eq!(_11_balance, 1000 + total_payout_0 / 2);
 
 			// Trigger another new era as the info are frozen before the era start.
@@ -1876,7 +1876,7 @@ fn reap_stash_works() {
 		.balance_factor(10)
 		.build_and_execute(|| {
 			// given
-			assert_eq!(Balances::free_balance(11), 10 * 1000);
+			assert_eq!(asset::stakeable_balance::<Test>(&11), 10 * 1000);
 			assert_eq!(Staking::bonded(&11), Some(11));
 
 			assert!(<Ledger<Test>>::contains_key(&11));
@@ -2006,7 +2006,7 @@ fn bond_with_no_staked_value() {
 			);
 			// bonded with absolute minimum value possible.
 			assert_ok!(Staking::bond(RuntimeOrigin::signed(1), 5, RewardDestination::Account(1)));
-			assert_eq!(Balances::locks(&1)[0].amount, 5);
+			assert_eq!(asset::staked::<Test>(&1), 5);
 
 			// unbonding even 1 will cause all to be unbonded.
 			assert_ok!(Staking::unbond(RuntimeOrigin::signed(1), 1));
@@ -2027,14 +2027,14 @@ fn bond_with_no_staked_value() {
 			// not yet removed.
 			assert_ok!(Staking::withdraw_unbonded(RuntimeOrigin::signed(1), 0));
 			assert!(Staking::ledger(1.into()).is_ok());
-			assert_eq!(Balances::locks(&1)[0].amount, 5);
+			assert_eq!(asset::staked::<Test>(&1), 5);
 
 			mock::start_active_era(3);
 
 			// poof. Account 1 is removed from the staking system.
 			assert_ok!(Staking::withdraw_unbonded(RuntimeOrigin::signed(1), 0));
 			assert!(Staking::ledger(1.into()).is_err());
-			assert_eq!(Balances::locks(&1).len(), 0);
+			assert_eq!(asset::staked::<Test>(&1), 0);
 		});
 }
 
@@ -2048,8 +2048,8 @@ fn bond_with_little_staked_value_bounded() {
 			// setup
 			assert_ok!(Staking::chill(RuntimeOrigin::signed(31)));
 			assert_ok!(Staking::set_payee(RuntimeOrigin::signed(11), RewardDestination::Stash));
-			let init_balance_1 = Balances::free_balance(&1);
-			let init_balance_11 = Balances::free_balance(&11);
+			let init_balance_1 = asset::stakeable_balance::<Test>(&1);
+			let init_balance_11 = asset::stakeable_balance::<Test>(&11);
 
 			// Stingy validator.
 			assert_ok!(Staking::bond(RuntimeOrigin::signed(1), 1, RewardDestination::Account(1)));
@@ -2074,12 +2074,12 @@ fn bond_with_little_staked_value_bounded() {
 
 			// Old ones are rewarded.
 			assert_eq_error_rate!(
-				Balances::free_balance(11),
+				asset::stakeable_balance::<Test>(&11),
 				init_balance_11 + total_payout_0 / 3,
 				1
 			);
 			// no rewards paid to 2. This was initial election.
-			assert_eq!(Balances::free_balance(1), init_balance_1);
+			assert_eq!(asset::stakeable_balance::<Test>(&1), init_balance_1);
 
 			// reward era 2
 			let total_payout_1 = current_total_payout_for_duration(reward_time_per_era());
@@ -2092,12 +2092,12 @@ fn bond_with_little_staked_value_bounded() {
 
 			// 2 is now rewarded.
 			assert_eq_error_rate!(
-				Balances::free_balance(1),
+				asset::stakeable_balance::<Test>(&1),
 				init_balance_1 + total_payout_1 / 3,
 				1
 			);
 			assert_eq_error_rate!(
-				Balances::free_balance(&11),
+				asset::stakeable_balance::<Test>(&11),
 				init_balance_11 + total_payout_0 / 3 + total_payout_1 / 3,
 				2,
 			);
@@ -2125,7 +2125,7 @@ fn bond_with_duplicate_vote_should_be_ignored_by_election_provider() {
 			// give the man some money.
 			let initial_balance = 1000;
 			for i in [1, 2, 3, 4].iter() {
-				let _ = Balances::make_free_balance_be(i, initial_balance);
+				asset::set_stakeable_balance::<Test>(i, initial_balance);
 			}
 
 			assert_ok!(Staking::bond(
@@ -2144,7 +2144,7 @@ fn bond_with_duplicate_vote_should_be_ignored_by_election_provider() {
 
 			// winners should be 21 and 31. Otherwise this election is taking duplicates into
 			// account.
-			let supports = <Test as Config>::ElectionProvider::elect().unwrap();
+			let supports = <Test as Config>::ElectionProvider::elect(0).unwrap();
 			assert_eq!(
 				supports,
 				vec![
@@ -2178,7 +2178,7 @@ fn bond_with_duplicate_vote_should_be_ignored_by_election_provider_elected() {
 			// give the man some money.
 			let initial_balance = 1000;
 			for i in [1, 2, 3, 4].iter() {
-				let _ = Balances::make_free_balance_be(i, initial_balance);
+				asset::set_stakeable_balance::<Test>(i, initial_balance);
 			}
 
 			assert_ok!(Staking::bond(
@@ -2196,7 +2196,7 @@ fn bond_with_duplicate_vote_should_be_ignored_by_election_provider_elected() {
 			assert_ok!(Staking::nominate(RuntimeOrigin::signed(3), vec![21]));
 
 			// winners should be 21 and 11.
-			let supports = <Test as Config>::ElectionProvider::elect().unwrap();
+			let supports = <Test as Config>::ElectionProvider::elect(0).unwrap();
 			assert_eq!(
 				supports,
 				vec![
@@ -2254,7 +2254,7 @@ fn reward_validator_slashing_validator_does_not_overflow() {
 		assert!(stake.checked_mul(reward_slash).is_none());
 
 		// Set staker
-		let _ = Balances::make_free_balance_be(&11, stake);
+		asset::set_stakeable_balance::<Test>(&11, stake);
 
 		let exposure = Exposure::<AccountId, Balance> { total: stake, own: stake, others: vec![] };
 		let reward = EraRewardPoints::<AccountId> {
@@ -2267,10 +2267,9 @@ fn reward_validator_slashing_validator_does_not_overflow() {
 		EraInfo::<Test>::set_exposure(0, &11, exposure);
 		ErasValidatorReward::<Test>::insert(0, stake);
 		assert_ok!(Staking::payout_stakers_by_page(RuntimeOrigin::signed(1337), 11, 0, 0));
-		assert_eq!(Balances::total_balance(&11), stake * 2);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), stake * 2);
 
 		// Set staker
-		let _ = Balances::make_free_balance_be(&11, stake);
 		let _ = Balances::make_free_balance_be(&2, stake);
 
 		// only slashes out of bonded stake are applied. without this line, it is 0.
@@ -2295,7 +2294,7 @@ fn reward_validator_slashing_validator_does_not_overflow() {
 			&[Perbill::from_percent(100)],
 		);
 
-		assert_eq!(Balances::total_balance(&11), stake - 1);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), stake * 2 - 1);
 		assert_eq!(Balances::total_balance(&2), 1);
 	})
 }
@@ -2457,7 +2456,7 @@ fn slashing_performed_according_exposure() {
 		);
 
 		// The stash account should be slashed for 250 (50% of 500).
-		assert_eq!(Balances::free_balance(11), 1000 - 250);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000 - 250);
 	});
 }
 
@@ -2600,14 +2599,17 @@ fn subsequent_reports_in_same_span_pay_out_less() {
 fn invulnerables_are_not_slashed() {
 	// For invulnerable validators no slashing is performed.
 	ExtBuilder::default().invulnerables(vec![11]).build_and_execute(|| {
-		assert_eq!(Balances::free_balance(11), 1000);
-		assert_eq!(Balances::free_balance(21), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&21), 2000);
 
 		let exposure = Staking::eras_stakers(active_era(), &21);
 		let initial_balance = Staking::slashable_balance_of(&21);
 
-		let nominator_balances: Vec<_> =
-			exposure.others.iter().map(|o| Balances::free_balance(&o.who)).collect();
+		let nominator_balances: Vec<_> = exposure
+			.others
+			.iter()
+			.map(|o| asset::stakeable_balance::<Test>(&o.who))
+			.collect();
 
 		on_offence_now(
 			&[
@@ -2624,14 +2626,14 @@ fn invulnerables_are_not_slashed() {
 		);
 
 		// The validator 11 hasn't been slashed, but 21 has been.
-		assert_eq!(Balances::free_balance(11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
 		// 2000 - (0.2 * initial_balance)
-		assert_eq!(Balances::free_balance(21), 2000 - (2 * initial_balance / 10));
+		assert_eq!(asset::stakeable_balance::<Test>(&21), 2000 - (2 * initial_balance / 10));
 
 		// ensure that nominators were slashed as well.
 		for (initial_balance, other) in nominator_balances.into_iter().zip(exposure.others) {
 			assert_eq!(
-				Balances::free_balance(&other.who),
+				asset::stakeable_balance::<Test>(&other.who),
 				initial_balance - (2 * other.value / 10),
 			);
 		}
@@ -2642,7 +2644,7 @@ fn invulnerables_are_not_slashed() {
 fn dont_slash_if_fraction_is_zero() {
 	// Don't slash if the fraction is zero.
 	ExtBuilder::default().build_and_execute(|| {
-		assert_eq!(Balances::free_balance(11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
 
 		on_offence_now(
 			&[OffenceDetails {
@@ -2653,7 +2655,7 @@ fn dont_slash_if_fraction_is_zero() {
 		);
 
 		// The validator hasn't been slashed. The new era is not forced.
-		assert_eq!(Balances::free_balance(11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
 		assert_eq!(Staking::force_era(), Forcing::ForceNew);
 	});
 }
@@ -2663,7 +2665,7 @@ fn only_slash_for_max_in_era() {
 	// multiple slashes within one era are only applied if it is more than any previous slash in the
 	// same era.
 	ExtBuilder::default().build_and_execute(|| {
-		assert_eq!(Balances::free_balance(11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
 
 		on_offence_now(
 			&[OffenceDetails {
@@ -2674,7 +2676,7 @@ fn only_slash_for_max_in_era() {
 		);
 
 		// The validator has been slashed and has been force-chilled.
-		assert_eq!(Balances::free_balance(11), 500);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 500);
 		assert_eq!(Staking::force_era(), Forcing::ForceNew);
 
 		on_offence_now(
@@ -2686,7 +2688,7 @@ fn only_slash_for_max_in_era() {
 		);
 
 		// The validator has not been slashed additionally.
-		assert_eq!(Balances::free_balance(11), 500);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 500);
 
 		on_offence_now(
 			&[OffenceDetails {
@@ -2697,7 +2699,7 @@ fn only_slash_for_max_in_era() {
 		);
 
 		// The validator got slashed 10% more.
-		assert_eq!(Balances::free_balance(11), 400);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 400);
 	})
 }
 
@@ -2708,7 +2710,7 @@ fn garbage_collection_after_slashing() {
 		.existential_deposit(2)
 		.balance_factor(2)
 		.build_and_execute(|| {
-			assert_eq!(Balances::free_balance(11), 2000);
+			assert_eq!(asset::stakeable_balance::<Test>(&11), 2000);
 
 			on_offence_now(
 				&[OffenceDetails {
@@ -2718,7 +2720,7 @@ fn garbage_collection_after_slashing() {
 				&[Perbill::from_percent(10)],
 			);
 
-			assert_eq!(Balances::free_balance(11), 2000 - 200);
+			assert_eq!(asset::stakeable_balance::<Test>(&11), 2000 - 200);
 			assert!(SlashingSpans::<Test>::get(&11).is_some());
 			assert_eq!(SpanSlash::<Test>::get(&(11, 0)).amount(), &200);
 
@@ -2733,7 +2735,7 @@ fn garbage_collection_after_slashing() {
 			// validator and nominator slash in era are garbage-collected by era change,
 			// so we don't test those here.
 
-			assert_eq!(Balances::free_balance(11), 2);
+			assert_eq!(asset::stakeable_balance::<Test>(&11), 0);
 			assert_eq!(Balances::total_balance(&11), 2);
 
 			let slashing_spans = SlashingSpans::<Test>::get(&11).unwrap();
@@ -2758,11 +2760,11 @@ fn garbage_collection_on_window_pruning() {
 	ExtBuilder::default().build_and_execute(|| {
 		mock::start_active_era(1);
 
-		assert_eq!(Balances::free_balance(11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
 		let now = active_era();
 
 		let exposure = Staking::eras_stakers(now, &11);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 		let nominated_value = exposure.others.iter().find(|o| o.who == 101).unwrap().value;
 
 		on_offence_now(
@@ -2773,8 +2775,8 @@ fn garbage_collection_on_window_pruning() {
 			&[Perbill::from_percent(10)],
 		);
 
-		assert_eq!(Balances::free_balance(11), 900);
-		assert_eq!(Balances::free_balance(101), 2000 - (nominated_value / 10));
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 900);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000 - (nominated_value / 10));
 
 		assert!(ValidatorSlashInEra::<Test>::get(&now, &11).is_some());
 		assert!(NominatorSlashInEra::<Test>::get(&now, &101).is_some());
@@ -2799,9 +2801,9 @@ fn slashing_nominators_by_span_max() {
 		mock::start_active_era(2);
 		mock::start_active_era(3);
 
-		assert_eq!(Balances::free_balance(11), 1000);
-		assert_eq!(Balances::free_balance(21), 2000);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&21), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 		assert_eq!(Staking::slashable_balance_of(&21), 1000);
 
 		let exposure_11 = Staking::eras_stakers(active_era(), &11);
@@ -2819,10 +2821,10 @@ fn slashing_nominators_by_span_max() {
 			DisableStrategy::WhenSlashed,
 		);
 
-		assert_eq!(Balances::free_balance(11), 900);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 900);
 
 		let slash_1_amount = Perbill::from_percent(10) * nominated_value_11;
-		assert_eq!(Balances::free_balance(101), 2000 - slash_1_amount);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000 - slash_1_amount);
 
 		let expected_spans = vec![
 			slashing::SlashingSpan { index: 1, start: 4, length: None },
@@ -2847,14 +2849,14 @@ fn slashing_nominators_by_span_max() {
 		);
 
 		// 11 was not further slashed, but 21 and 101 were.
-		assert_eq!(Balances::free_balance(11), 900);
-		assert_eq!(Balances::free_balance(21), 1700);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 900);
+		assert_eq!(asset::stakeable_balance::<Test>(&21), 1700);
 
 		let slash_2_amount = Perbill::from_percent(30) * nominated_value_21;
 		assert!(slash_2_amount > slash_1_amount);
 
 		// only the maximum slash in a single span is taken.
-		assert_eq!(Balances::free_balance(101), 2000 - slash_2_amount);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000 - slash_2_amount);
 
 		// third slash: in same era and on same validator as first, higher
 		// in-era value, but lower slash value than slash 2.
@@ -2869,15 +2871,15 @@ fn slashing_nominators_by_span_max() {
 		);
 
 		// 11 was further slashed, but 21 and 101 were not.
-		assert_eq!(Balances::free_balance(11), 800);
-		assert_eq!(Balances::free_balance(21), 1700);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 800);
+		assert_eq!(asset::stakeable_balance::<Test>(&21), 1700);
 
 		let slash_3_amount = Perbill::from_percent(20) * nominated_value_21;
 		assert!(slash_3_amount < slash_2_amount);
 		assert!(slash_3_amount > slash_1_amount);
 
 		// only the maximum slash in a single span is taken.
-		assert_eq!(Balances::free_balance(101), 2000 - slash_2_amount);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000 - slash_2_amount);
 	});
 }
 
@@ -2888,7 +2890,7 @@ fn slashes_are_summed_across_spans() {
 		mock::start_active_era(2);
 		mock::start_active_era(3);
 
-		assert_eq!(Balances::free_balance(21), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&21), 2000);
 		assert_eq!(Staking::slashable_balance_of(&21), 1000);
 
 		let get_span = |account| SlashingSpans::<Test>::get(&account).unwrap();
@@ -2907,7 +2909,7 @@ fn slashes_are_summed_across_spans() {
 		];
 
 		assert_eq!(get_span(21).iter().collect::<Vec<_>>(), expected_spans);
-		assert_eq!(Balances::free_balance(21), 1900);
+		assert_eq!(asset::stakeable_balance::<Test>(&21), 1900);
 
 		// 21 has been force-chilled. re-signal intent to validate.
 		Staking::validate(RuntimeOrigin::signed(21), Default::default()).unwrap();
@@ -2931,7 +2933,7 @@ fn slashes_are_summed_across_spans() {
 		];
 
 		assert_eq!(get_span(21).iter().collect::<Vec<_>>(), expected_spans);
-		assert_eq!(Balances::free_balance(21), 1810);
+		assert_eq!(asset::stakeable_balance::<Test>(&21), 1810);
 	});
 }
 
@@ -2940,10 +2942,10 @@ fn deferred_slashes_are_deferred() {
 	ExtBuilder::default().slash_defer_duration(2).build_and_execute(|| {
 		mock::start_active_era(1);
 
-		assert_eq!(Balances::free_balance(11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
 
 		let exposure = Staking::eras_stakers(active_era(), &11);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 		let nominated_value = exposure.others.iter().find(|o| o.who == 101).unwrap().value;
 
 		System::reset_events();
@@ -2959,25 +2961,25 @@ fn deferred_slashes_are_deferred() {
 		// nominations are not removed regardless of the deferring.
 		assert_eq!(Staking::nominators(101).unwrap().targets, vec![11, 21]);
 
-		assert_eq!(Balances::free_balance(11), 1000);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 
 		mock::start_active_era(2);
 
-		assert_eq!(Balances::free_balance(11), 1000);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 
 		mock::start_active_era(3);
 
-		assert_eq!(Balances::free_balance(11), 1000);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 
 		// at the start of era 4, slashes from era 1 are processed,
 		// after being deferred for at least 2 full eras.
 		mock::start_active_era(4);
 
-		assert_eq!(Balances::free_balance(11), 900);
-		assert_eq!(Balances::free_balance(101), 2000 - (nominated_value / 10));
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 900);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000 - (nominated_value / 10));
 
 		assert!(matches!(
 			staking_events_since_last_call().as_slice(),
@@ -3082,8 +3084,8 @@ fn staker_cannot_bail_deferred_slash() {
 	ExtBuilder::default().slash_defer_duration(2).build_and_execute(|| {
 		mock::start_active_era(1);
 
-		assert_eq!(Balances::free_balance(11), 1000);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 
 		let exposure = Staking::eras_stakers(active_era(), &11);
 		let nominated_value = exposure.others.iter().find(|o| o.who == 101).unwrap().value;
@@ -3115,20 +3117,20 @@ fn staker_cannot_bail_deferred_slash() {
 		);
 
 		// no slash yet.
-		assert_eq!(Balances::free_balance(11), 1000);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 
 		// no slash yet.
 		mock::start_active_era(2);
-		assert_eq!(Balances::free_balance(11), 1000);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 		assert_eq!(Staking::current_era().unwrap(), 2);
 		assert_eq!(active_era(), 2);
 
 		// no slash yet.
 		mock::start_active_era(3);
-		assert_eq!(Balances::free_balance(11), 1000);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 		assert_eq!(Staking::current_era().unwrap(), 3);
 		assert_eq!(active_era(), 3);
 
@@ -3145,8 +3147,8 @@ fn staker_cannot_bail_deferred_slash() {
 		// after being deferred for at least 2 full eras.
 		mock::start_active_era(4);
 
-		assert_eq!(Balances::free_balance(11), 900);
-		assert_eq!(Balances::free_balance(101), 2000 - (nominated_value / 10));
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 900);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000 - (nominated_value / 10));
 
 		// and the leftover of the funds can now be unbonded.
 	})
@@ -3157,10 +3159,10 @@ fn remove_deferred() {
 	ExtBuilder::default().slash_defer_duration(2).build_and_execute(|| {
 		mock::start_active_era(1);
 
-		assert_eq!(Balances::free_balance(11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
 
 		let exposure = Staking::eras_stakers(active_era(), &11);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 		let nominated_value = exposure.others.iter().find(|o| o.who == 101).unwrap().value;
 
 		// deferred to start of era 4.
@@ -3169,8 +3171,8 @@ fn remove_deferred() {
 			&[Perbill::from_percent(10)],
 		);
 
-		assert_eq!(Balances::free_balance(11), 1000);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 
 		mock::start_active_era(2);
 
@@ -3192,13 +3194,13 @@ fn remove_deferred() {
 		// cancel one of them.
 		assert_ok!(Staking::cancel_deferred_slash(RuntimeOrigin::root(), 4, vec![0]));
 
-		assert_eq!(Balances::free_balance(11), 1000);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 
 		mock::start_active_era(3);
 
-		assert_eq!(Balances::free_balance(11), 1000);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 
 		// at the start of era 4, slashes from era 1 are processed,
 		// after being deferred for at least 2 full eras.
@@ -3223,8 +3225,8 @@ fn remove_deferred() {
 		let actual_slash = total_slash - initial_slash;
 
 		// 5% slash (15 - 10) processed now.
-		assert_eq!(Balances::free_balance(11), 950);
-		assert_eq!(Balances::free_balance(101), 2000 - actual_slash);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 950);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000 - actual_slash);
 	})
 }
 
@@ -3233,10 +3235,10 @@ fn remove_multi_deferred() {
 	ExtBuilder::default().slash_defer_duration(2).build_and_execute(|| {
 		mock::start_active_era(1);
 
-		assert_eq!(Balances::free_balance(11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
 
 		let exposure = Staking::eras_stakers(active_era(), &11);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 
 		on_offence_now(
 			&[OffenceDetails { offender: (11, exposure.clone()), reporters: vec![] }],
@@ -3300,8 +3302,8 @@ fn slash_kicks_validators_not_nominators_and_disables_nominator_for_kicked_valid
 		assert_eq_uvec!(Session::validators(), vec![11, 21]);
 
 		// pre-slash balance
-		assert_eq!(Balances::free_balance(11), 1000);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 
 		// 100 has approval for 11 as of now
 		assert!(Staking::nominators(101).unwrap().targets.contains(&11));
@@ -3337,8 +3339,8 @@ fn slash_kicks_validators_not_nominators_and_disables_nominator_for_kicked_valid
 
 		// post-slash balance
 		let nominator_slash_amount_11 = 125 / 10;
-		assert_eq!(Balances::free_balance(11), 900);
-		assert_eq!(Balances::free_balance(101), 2000 - nominator_slash_amount_11);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 900);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000 - nominator_slash_amount_11);
 
 		// check that validator was chilled.
 		assert!(Validators::<Test>::iter().all(|(stash, _)| stash != 11));
@@ -3673,18 +3675,18 @@ fn zero_slash_keeps_nominators() {
 	ExtBuilder::default().build_and_execute(|| {
 		mock::start_active_era(1);
 
-		assert_eq!(Balances::free_balance(11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
 
 		let exposure = Staking::eras_stakers(active_era(), &11);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 
 		on_offence_now(
 			&[OffenceDetails { offender: (11, exposure.clone()), reporters: vec![] }],
 			&[Perbill::from_percent(0)],
 		);
 
-		assert_eq!(Balances::free_balance(11), 1000);
-		assert_eq!(Balances::free_balance(101), 2000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
 
 		// 11 is still removed..
 		assert!(Validators::<Test>::iter().all(|(stash, _)| stash != 11));
@@ -3760,7 +3762,7 @@ fn test_nominators_over_max_exposure_page_size_are_rewarded() {
 		for i in 0..=MaxExposurePageSize::get() {
 			let stash = 10_000 + i as AccountId;
 			let balance = 10_000 + i as Balance;
-			Balances::make_free_balance_be(&stash, balance);
+			asset::set_stakeable_balance::<Test>(&stash, balance);
 			assert_ok!(Staking::bond(
 				RuntimeOrigin::signed(stash),
 				balance,
@@ -3782,13 +3784,13 @@ fn test_nominators_over_max_exposure_page_size_are_rewarded() {
 		while i < MaxExposurePageSize::get() {
 			let stash = 10_000 + i as AccountId;
 			let balance = 10_000 + i as Balance;
-			assert!(Balances::free_balance(&stash) > balance);
+			assert!(asset::stakeable_balance::<Test>(&stash) > balance);
 			i += 1;
 		}
 
 		// Assert overflowing nominators from page 1 are also rewarded
 		let stash = 10_000 + i as AccountId;
-		assert!(Balances::free_balance(&stash) > (10_000 + i) as Balance);
+		assert!(asset::stakeable_balance::<Test>(&stash) > (10_000 + i) as Balance);
 	});
 }
 
@@ -3801,7 +3803,7 @@ fn test_nominators_are_rewarded_for_all_exposure_page() {
 		for i in 0..nominator_count {
 			let stash = 10_000 + i as AccountId;
 			let balance = 10_000 + i as Balance;
-			Balances::make_free_balance_be(&stash, balance);
+			asset::set_stakeable_balance::<Test>(&stash, balance);
 			assert_ok!(Staking::bond(
 				RuntimeOrigin::signed(stash),
 				balance,
@@ -3823,9 +3825,10 @@ fn test_nominators_are_rewarded_for_all_exposure_page() {
 		// Assert all nominators are rewarded according to their stake
 		for i in 0..nominator_count {
 			// balance of the nominator after the reward payout.
-			let current_balance = Balances::free_balance(&((10000 + i) as AccountId));
+			let current_balance = asset::stakeable_balance::<Test>(&((10000 + i) as AccountId));
 			// balance of the nominator in the previous iteration.
-			let previous_balance = Balances::free_balance(&((10000 + i - 1) as AccountId));
+			let previous_balance =
+				asset::stakeable_balance::<Test>(&((10000 + i - 1) as AccountId));
 			// balance before the reward.
 			let original_balance = 10_000 + i as Balance;
 
@@ -3881,7 +3884,7 @@ fn test_multi_page_payout_stakers_by_page() {
 		RewardOnUnbalanceWasCalled::set(false);
 		System::reset_events();
 
-		let controller_balance_before_p0_payout = Balances::free_balance(&11);
+		let controller_balance_before_p0_payout = asset::stakeable_balance::<Test>(&11);
 		// Payout rewards for first exposure page
 		assert_ok!(Staking::payout_stakers_by_page(RuntimeOrigin::signed(1337), 11, 1, 0));
 
@@ -3895,7 +3898,7 @@ fn test_multi_page_payout_stakers_by_page() {
 			]
 		));
 
-		let controller_balance_after_p0_payout = Balances::free_balance(&11);
+		let controller_balance_after_p0_payout = asset::stakeable_balance::<Test>(&11);
 
 		// verify rewards have been paid out but still some left
 		assert!(Balances::total_issuance() > pre_payout_total_issuance);
@@ -3919,16 +3922,16 @@ fn test_multi_page_payout_stakers_by_page() {
 			]
 		));
 		// verify the validator was not rewarded the second time
-		assert_eq!(Balances::free_balance(&11), controller_balance_after_p0_payout);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), controller_balance_after_p0_payout);
 
 		// verify all rewards have been paid out
 		assert_eq_error_rate!(Balances::total_issuance(), pre_payout_total_issuance + payout, 2);
 		assert!(RewardOnUnbalanceWasCalled::get());
 
 		// Top 64 nominators of validator 11 automatically paid out, including the validator
-		assert!(Balances::free_balance(&11) > balance);
+		assert!(asset::stakeable_balance::<Test>(&11) > balance);
 		for i in 0..100 {
-			assert!(Balances::free_balance(&(1000 + i)) > balance + i as Balance);
+			assert!(asset::stakeable_balance::<Test>(&(1000 + i)) > balance + i as Balance);
 		}
 
 		// verify we no longer track rewards in `legacy_claimed_rewards` vec
@@ -4097,7 +4100,7 @@ fn test_multi_page_payout_stakers_backward_compatible() {
 		let pre_payout_total_issuance = Balances::total_issuance();
 		RewardOnUnbalanceWasCalled::set(false);
 
-		let controller_balance_before_p0_payout = Balances::free_balance(&11);
+		let controller_balance_before_p0_payout = asset::stakeable_balance::<Test>(&11);
 		// Payout rewards for first exposure page
 		assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 1));
 		// page 0 is claimed
@@ -4106,7 +4109,7 @@ fn test_multi_page_payout_stakers_backward_compatible() {
 			Error::<Test>::AlreadyClaimed.with_weight(err_weight)
 		);
 
-		let controller_balance_after_p0_payout = Balances::free_balance(&11);
+		let controller_balance_after_p0_payout = asset::stakeable_balance::<Test>(&11);
 
 		// verify rewards have been paid out but still some left
 		assert!(Balances::total_issuance() > pre_payout_total_issuance);
@@ -4125,7 +4128,7 @@ fn test_multi_page_payout_stakers_backward_compatible() {
 		);
 
 		// verify the validator was not rewarded the second time
-		assert_eq!(Balances::free_balance(&11), controller_balance_after_p0_payout);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), controller_balance_after_p0_payout);
 
 		// verify all rewards have been paid out
 		assert_eq_error_rate!(Balances::total_issuance(), pre_payout_total_issuance + payout, 2);
@@ -4133,9 +4136,9 @@ fn test_multi_page_payout_stakers_backward_compatible() {
 
 		// verify all nominators of validator 11 are paid out, including the validator
 		// Validator payout goes to controller.
-		assert!(Balances::free_balance(&11) > balance);
+		assert!(asset::stakeable_balance::<Test>(&11) > balance);
 		for i in 0..100 {
-			assert!(Balances::free_balance(&(1000 + i)) > balance + i as Balance);
+			assert!(asset::stakeable_balance::<Test>(&(1000 + i)) > balance + i as Balance);
 		}
 
 		// verify we no longer track rewards in `legacy_claimed_rewards` vec
@@ -4493,25 +4496,29 @@ fn test_commission_paid_across_pages() {
 		let payout = current_total_payout_for_duration(reward_time_per_era());
 		mock::start_active_era(2);
 
-		let initial_balance = Balances::free_balance(&11);
+		let initial_balance = asset::stakeable_balance::<Test>(&11);
 		// Payout rewards for first exposure page
 		assert_ok!(Staking::payout_stakers_by_page(RuntimeOrigin::signed(1337), 11, 1, 0));
 
-		let controller_balance_after_p0_payout = Balances::free_balance(&11);
+		let controller_balance_after_p0_payout = asset::stakeable_balance::<Test>(&11);
 
 		// some commission is paid
 		assert!(initial_balance < controller_balance_after_p0_payout);
 
 		// payout all pages
 		for i in 1..4 {
-			let before_balance = Balances::free_balance(&11);
+			let before_balance = asset::stakeable_balance::<Test>(&11);
 			assert_ok!(Staking::payout_stakers_by_page(RuntimeOrigin::signed(1337), 11, 1, i));
-			let after_balance = Balances::free_balance(&11);
+			let after_balance = asset::stakeable_balance::<Test>(&11);
 			// some commission is paid for every page
 			assert!(before_balance < after_balance);
 		}
 
-		assert_eq_error_rate!(Balances::free_balance(&11), initial_balance + payout / 2, 1,);
+		assert_eq_error_rate!(
+			asset::stakeable_balance::<Test>(&11),
+			initial_balance + payout / 2,
+			1,
+		);
 	});
 }
 
@@ -5060,14 +5067,15 @@ mod election_data_provider {
 			.build_and_execute(|| {
 				// default bounds are unbounded.
 				assert_ok!(<Staking as ElectionDataProvider>::electing_voters(
-					DataProviderBounds::default()
+					DataProviderBounds::default(),
+					0
 				));
 				assert_eq!(MinimumActiveStake::<Test>::get(), 10);
 
 				// remove staker with lower bond by limiting the number of voters and check
 				// `MinimumActiveStake` again after electing voters.
 				let bounds = ElectionBoundsBuilder::default().voters_count(5.into()).build();
-				assert_ok!(<Staking as ElectionDataProvider>::electing_voters(bounds.voters));
+				assert_ok!(<Staking as ElectionDataProvider>::electing_voters(bounds.voters, 0));
 				assert_eq!(MinimumActiveStake::<Test>::get(), 50);
 			});
 	}
@@ -5078,7 +5086,8 @@ mod election_data_provider {
 		ExtBuilder::default().has_stakers(false).build_and_execute(|| {
 			// default bounds are unbounded.
 			assert_ok!(<Staking as ElectionDataProvider>::electing_voters(
-				DataProviderBounds::default()
+				DataProviderBounds::default(),
+				0
 			));
 			assert_eq!(<Test as Config>::VoterList::count(), 0);
 			assert_eq!(MinimumActiveStake::<Test>::get(), 0);
@@ -5094,9 +5103,11 @@ mod election_data_provider {
 			assert_ok!(Staking::nominate(RuntimeOrigin::signed(4), vec![1]));
 			assert_eq!(<Test as Config>::VoterList::count(), 5);
 
-			let voters_before =
-				<Staking as ElectionDataProvider>::electing_voters(DataProviderBounds::default())
-					.unwrap();
+			let voters_before = <Staking as ElectionDataProvider>::electing_voters(
+				DataProviderBounds::default(),
+				0,
+			)
+			.unwrap();
 			assert_eq!(MinimumActiveStake::<Test>::get(), 5);
 
 			// update minimum nominator bond.
@@ -5106,9 +5117,11 @@ mod election_data_provider {
 			// lower than `MinNominatorBond`.
 			assert_eq!(<Test as Config>::VoterList::count(), 5);
 
-			let voters =
-				<Staking as ElectionDataProvider>::electing_voters(DataProviderBounds::default())
-					.unwrap();
+			let voters = <Staking as ElectionDataProvider>::electing_voters(
+				DataProviderBounds::default(),
+				0,
+			)
+			.unwrap();
 			assert_eq!(voters_before, voters);
 
 			// minimum active stake is lower than `MinNominatorBond`.
@@ -5126,6 +5139,7 @@ mod election_data_provider {
 				assert_eq!(Staking::weight_of(&101), 500);
 				let voters = <Staking as ElectionDataProvider>::electing_voters(
 					DataProviderBounds::default(),
+					0,
 				)
 				.unwrap();
 				assert_eq!(voters.len(), 5);
@@ -5141,6 +5155,7 @@ mod election_data_provider {
 
 				let voters = <Staking as ElectionDataProvider>::electing_voters(
 					DataProviderBounds::default(),
+					0,
 				)
 				.unwrap();
 				// number of returned voters decreases since ledger entry of stash 101 is now
@@ -5162,7 +5177,8 @@ mod election_data_provider {
 		ExtBuilder::default().nominate(false).build_and_execute(|| {
 			// default bounds are unbounded.
 			assert!(<Validators<Test>>::iter().map(|(x, _)| x).all(|v| Staking::electing_voters(
-				DataProviderBounds::default()
+				DataProviderBounds::default(),
+				0
 			)
 			.unwrap()
 			.into_iter()
@@ -5170,6 +5186,76 @@ mod election_data_provider {
 		})
 	}
 
+	#[test]
+	fn paged_voters_continue_from_last_voter() {
+		ExtBuilder::default().build_and_execute(|| {
+			let all_voters = Staking::electing_voters(DataProviderBounds::default(), 0).unwrap();
+			assert_eq!(all_voters.len(), 4);
+			assert_eq!(VoterSnapshotStatus::<Test>::get(), SnapshotStatus::Waiting);
+
+			// pages that are not the most significant one continue from the first voter that was
+			// not handed out yet.
+			let bounds = ElectionBoundsBuilder::default().voters_count(3.into()).build().voters;
+			let first_page = Staking::electing_voters(bounds, 3).unwrap();
+			assert_eq!(first_page.len(), 3);
+			assert_eq!(
+				VoterSnapshotStatus::<Test>::get(),
+				SnapshotStatus::Ongoing(all_voters[3].0)
+			);
+
+			let second_page = Staking::electing_voters(bounds, 2).unwrap();
+			assert_eq!(second_page.len(), 1);
+			assert_eq!(VoterSnapshotStatus::<Test>::get(), SnapshotStatus::Consumed);
+			assert_eq!([first_page, second_page].concat(), all_voters);
+
+			// once all voters are consumed, the remaining pages are empty.
+			assert!(Staking::electing_voters(bounds, 1).unwrap().is_empty());
+			assert_eq!(VoterSnapshotStatus::<Test>::get(), SnapshotStatus::Consumed);
+
+			// the most significant page starts a new snapshot, and the least significant page
+			// resets the status.
+			assert_eq!(
+				Staking::electing_voters(DataProviderBounds::default(), 0).unwrap(),
+				all_voters
+			);
+			assert_eq!(VoterSnapshotStatus::<Test>::get(), SnapshotStatus::Waiting);
+		})
+	}
+
+	#[test]
+	fn paged_voters_skip_removed_voter() {
+		ExtBuilder::default().build_and_execute(|| {
+			let all_voters = Staking::electing_voters(DataProviderBounds::default(), 0).unwrap();
+			assert_eq!(all_voters.len(), 4);
+
+			let bounds = ElectionBoundsBuilder::default().voters_count(2.into()).build().voters;
+			let first_page = Staking::electing_voters(bounds, 3).unwrap();
+			assert_eq!(first_page, all_voters[..2].to_vec());
+			let removed = all_voters[2].0;
+			assert_eq!(VoterSnapshotStatus::<Test>::get(), SnapshotStatus::Ongoing(removed));
+
+			// the voter the next page starts from is removed from the list.
+			assert_ok!(Staking::chill(RuntimeOrigin::signed(removed)));
+			let last = all_voters[3].0;
+			assert_eq!(VoterSnapshotStatus::<Test>::get(), SnapshotStatus::Ongoing(last));
+
+			// the snapshot continues with the following voter.
+			let second_page = Staking::electing_voters(bounds, 2).unwrap();
+			assert_eq!(second_page, all_voters[3..].to_vec());
+			assert_eq!(VoterSnapshotStatus::<Test>::get(), SnapshotStatus::Consumed);
+
+			// start a new snapshot, whose remaining voter is removed from the list.
+			let _ = Staking::electing_voters(DataProviderBounds::default(), 0).unwrap();
+			assert_eq!(Staking::electing_voters(bounds, 3).unwrap(), all_voters[..2].to_vec());
+			assert_eq!(VoterSnapshotStatus::<Test>::get(), SnapshotStatus::Ongoing(last));
+			assert_ok!(Staking::chill(RuntimeOrigin::signed(last)));
+
+			// the snapshot is consumed.
+			assert_eq!(VoterSnapshotStatus::<Test>::get(), SnapshotStatus::Consumed);
+			assert!(Staking::electing_voters(bounds, 2).unwrap().is_empty());
+		})
+	}
+
 	// Tests the criteria that in `ElectionDataProvider::voters` function, we try to get at most
 	// `maybe_max_len` voters, and if some of them end up being skipped, we iterate at most `2 *
 	// maybe_max_len`.
@@ -5215,12 +5301,15 @@ mod election_data_provider {
 				// 11 is taken;
 				// we finish since the 2x limit is reached.
 				assert_eq!(
-					Staking::electing_voters(bounds_builder.voters_count(2.into()).build().voters)
-						.unwrap()
-						.iter()
-						.map(|(stash, _, _)| stash)
-						.copied()
-						.collect::<Vec<_>>(),
+					Staking::electing_voters(
+						bounds_builder.voters_count(2.into()).build().voters,
+						0
+					)
+					.unwrap()
+					.iter()
+					.map(|(stash, _, _)| stash)
+					.copied()
+					.collect::<Vec<_>>(),
 					vec![11],
 				);
 			});
@@ -5238,32 +5327,42 @@ mod election_data_provider {
 
 				// if voter count limit is less..
 				assert_eq!(
-					Staking::electing_voters(bounds_builder.voters_count(1.into()).build().voters)
-						.unwrap()
-						.len(),
+					Staking::electing_voters(
+						bounds_builder.voters_count(1.into()).build().voters,
+						0
+					)
+					.unwrap()
+					.len(),
 					1
 				);
 
 				// if voter count limit is equal..
 				assert_eq!(
-					Staking::electing_voters(bounds_builder.voters_count(5.into()).build().voters)
-						.unwrap()
-						.len(),
+					Staking::electing_voters(
+						bounds_builder.voters_count(5.into()).build().voters,
+						0
+					)
+					.unwrap()
+					.len(),
 					5
 				);
 
 				// if voter count limit is more.
 				assert_eq!(
-					Staking::electing_voters(bounds_builder.voters_count(55.into()).build().voters)
-						.unwrap()
-						.len(),
+					Staking::electing_voters(
+						bounds_builder.voters_count(55.into()).build().voters,
+						0
+					)
+					.unwrap()
+					.len(),
 					5
 				);
 
 				// if target count limit is more..
 				assert_eq!(
 					Staking::electable_targets(
-						bounds_builder.targets_count(6.into()).build().targets
+						bounds_builder.targets_count(6.into()).build().targets,
+						0
 					)
 					.unwrap()
 					.len(),
@@ -5273,7 +5372,8 @@ mod election_data_provider {
 				// if target count limit is equal..
 				assert_eq!(
 					Staking::electable_targets(
-						bounds_builder.targets_count(4.into()).build().targets
+						bounds_builder.targets_count(4.into()).build().targets,
+						0
 					)
 					.unwrap()
 					.len(),
@@ -5283,7 +5383,8 @@ mod election_data_provider {
 				// if target limit count is less, then we return an error.
 				assert_eq!(
 					Staking::electable_targets(
-						bounds_builder.targets_count(1.into()).build().targets
+						bounds_builder.targets_count(1.into()).build().targets,
+						0
 					)
 					.unwrap_err(),
 					"Target snapshot too big"
@@ -5296,25 +5397,25 @@ mod election_data_provider {
 		ExtBuilder::default().build_and_execute(|| {
 			// voters: set size bounds that allows only for 1 voter.
 			let bounds = ElectionBoundsBuilder::default().voters_size(26.into()).build();
-			let elected = Staking::electing_voters(bounds.voters).unwrap();
+			let elected = Staking::electing_voters(bounds.voters, 0).unwrap();
 			assert!(elected.encoded_size() == 26 as usize);
 			let prev_len = elected.len();
 
 			// larger size bounds means more quota for voters.
 			let bounds = ElectionBoundsBuilder::default().voters_size(100.into()).build();
-			let elected = Staking::electing_voters(bounds.voters).unwrap();
+			let elected = Staking::electing_voters(bounds.voters, 0).unwrap();
 			assert!(elected.encoded_size() <= 100 as usize);
 			assert!(elected.len() > 1 && elected.len() > prev_len);
 
 			// targets: set size bounds that allows for only one target to fit in the snapshot.
 			let bounds = ElectionBoundsBuilder::default().targets_size(10.into()).build();
-			let elected = Staking::electable_targets(bounds.targets).unwrap();
+			let elected = Staking::electable_targets(bounds.targets, 0).unwrap();
 			assert!(elected.encoded_size() == 9 as usize);
 			let prev_len = elected.len();
 
 			// larger size bounds means more space for targets.
 			let bounds = ElectionBoundsBuilder::default().targets_size(100.into()).build();
-			let elected = Staking::electable_targets(bounds.targets).unwrap();
+			let elected = Staking::electable_targets(bounds.targets, 0).unwrap();
 			assert!(elected.encoded_size() <= 100 as usize);
 			assert!(elected.len() > 1 && elected.len() > prev_len);
 		});
@@ -5358,7 +5459,7 @@ mod election_data_provider {
 				// even through 61 has nomination quota of 2 at the time of the election, all the
 				// nominations (5) will be used.
 				assert_eq!(
-					Staking::electing_voters(DataProviderBounds::default())
+					Staking::electing_voters(DataProviderBounds::default(), 0)
 						.unwrap()
 						.iter()
 						.map(|(stash, _, targets)| (*stash, targets.len()))
@@ -5382,7 +5483,7 @@ mod election_data_provider {
 				// nominations of controller 70 won't be added due to voter size limit exceeded.
 				let bounds = ElectionBoundsBuilder::default().voters_size(100.into()).build();
 				assert_eq!(
-					Staking::electing_voters(bounds.voters)
+					Staking::electing_voters(bounds.voters, 0)
 						.unwrap()
 						.iter()
 						.map(|(stash, _, targets)| (*stash, targets.len()))
@@ -5399,7 +5500,7 @@ mod election_data_provider {
 				// include the electing voters of 70.
 				let bounds = ElectionBoundsBuilder::default().voters_size(1_000.into()).build();
 				assert_eq!(
-					Staking::electing_voters(bounds.voters)
+					Staking::electing_voters(bounds.voters, 0)
 						.unwrap()
 						.iter()
 						.map(|(stash, _, targets)| (*stash, targets.len()))
@@ -5409,6 +5510,59 @@ mod election_data_provider {
 			});
 	}
 
+	#[test]
+	fn paged_election_is_fetched_one_page_per_block() {
+		ExtBuilder::default().session_per_era(5).period(5).build_and_execute(|| {
+			ElectionPages::set(3);
+			assert_eq!(Staking::next_election_prediction(System::block_number()), 20);
+
+			// all but the least significant page are fetched in the blocks right before the
+			// election, and their supports are kept until the new era is planned.
+			run_to_block(17);
+			assert!(FetchedElectionPages::get().is_empty());
+			run_to_block(18);
+			assert_eq!(FetchedElectionPages::get(), vec![(18, 2)]);
+			assert_eq!(NextElectionPage::<Test>::get(), Some(1));
+			assert!(!ElectionPartialSupports::<Test>::get().is_empty());
+			run_to_block(19);
+			assert_eq!(FetchedElectionPages::get(), vec![(18, 2), (19, 1)]);
+			assert_eq!(NextElectionPage::<Test>::get(), Some(0));
+
+			// the least significant page is fetched when the new era is planned.
+			run_to_block(20);
+			assert_eq!(FetchedElectionPages::get(), vec![(18, 2), (19, 1), (20, 0)]);
+			assert_eq!(NextElectionPage::<Test>::get(), None);
+			assert!(ElectionPartialSupports::<Test>::get().is_empty());
+			assert_eq!(*staking_events().last().unwrap(), Event::StakersElected);
+			assert_eq!(current_era(), 1);
+			for validator in [11, 21] {
+				assert_eq!(
+					Staking::eras_stakers(1, &validator),
+					Staking::eras_stakers(0, &validator)
+				);
+			}
+		})
+	}
+
+	#[test]
+	fn paged_election_fetches_remaining_pages_when_era_is_planned() {
+		ExtBuilder::default().session_per_era(5).period(5).build_and_execute(|| {
+			ElectionPages::set(3);
+			run_to_block(14);
+			assert_eq!(Staking::next_election_prediction(System::block_number()), 20);
+
+			// the new era is forced at the end of the current session, leaving no time to fetch
+			// the pages ahead.
+			Staking::force_new_era(RuntimeOrigin::root()).unwrap();
+			assert_eq!(Staking::next_election_prediction(System::block_number()), 15);
+			run_to_block(15);
+
+			// all the pages are fetched when the new era is planned.
+			assert_eq!(FetchedElectionPages::get(), vec![(15, 2), (15, 1), (15, 0)]);
+			assert_eq!(current_era(), 1);
+		})
+	}
+
 	#[test]
 	fn estimate_next_election_works() {
 		ExtBuilder::default().session_per_era(5).period(5).build_and_execute(|| {
@@ -5884,7 +6038,7 @@ fn change_of_absolute_max_nominations() {
 			let bounds = DataProviderBounds::default();
 
 			// 3 validators and 3 nominators
-			assert_eq!(Staking::electing_voters(bounds).unwrap().len(), 3 + 3);
+			assert_eq!(Staking::electing_voters(bounds, 0).unwrap().len(), 3 + 3);
 
 			// abrupt change from 16 to 4, everyone should be fine.
 			AbsoluteMaxNominations::set(4);
@@ -5895,7 +6049,7 @@ fn change_of_absolute_max_nominations() {
 					.collect::<Vec<_>>(),
 				vec![(101, 2), (71, 3), (61, 1)]
 			);
-			assert_eq!(Staking::electing_voters(bounds).unwrap().len(), 3 + 3);
+			assert_eq!(Staking::electing_voters(bounds, 0).unwrap().len(), 3 + 3);
 
 			// abrupt change from 4 to 3, everyone should be fine.
 			AbsoluteMaxNominations::set(3);
@@ -5906,7 +6060,7 @@ fn change_of_absolute_max_nominations() {
 					.collect::<Vec<_>>(),
 				vec![(101, 2), (71, 3), (61, 1)]
 			);
-			assert_eq!(Staking::electing_voters(bounds).unwrap().len(), 3 + 3);
+			assert_eq!(Staking::electing_voters(bounds, 0).unwrap().len(), 3 + 3);
 
 			// abrupt change from 3 to 2, this should cause some nominators to be non-decodable, and
 			// thus non-existent unless if they update.
@@ -5923,7 +6077,7 @@ fn change_of_absolute_max_nominations() {
 			// but its value cannot be decoded and default is returned.
 			assert!(Nominators::<Test>::get(71).is_none());
 
-			assert_eq!(Staking::electing_voters(bounds).unwrap().len(), 3 + 2);
+			assert_eq!(Staking::electing_voters(bounds, 0).unwrap().len(), 3 + 2);
 			assert!(Nominators::<Test>::contains_key(101));
 
 			// abrupt change from 2 to 1, this should cause some nominators to be non-decodable, and
@@ -5940,7 +6094,7 @@ fn change_of_absolute_max_nominations() {
 			assert!(Nominators::<Test>::contains_key(61));
 			assert!(Nominators::<Test>::get(71).is_none());
 			assert!(Nominators::<Test>::get(61).is_some());
-			assert_eq!(Staking::electing_voters(bounds).unwrap().len(), 3 + 1);
+			assert_eq!(Staking::electing_voters(bounds, 0).unwrap().len(), 3 + 1);
 
 			// now one of them can revive themselves by re-nominating to a proper value.
 			assert_ok!(Staking::nominate(RuntimeOrigin::signed(71), vec![1]));
@@ -5964,10 +6118,10 @@ fn change_of_absolute_max_nominations() {
 fn nomination_quota_max_changes_decoding() {
 	use frame_election_provider_support::ElectionDataProvider;
 	ExtBuilder::default()
-		.add_staker(60, 61, 10, StakerStatus::Nominator(vec![1]))
-		.add_staker(70, 71, 10, StakerStatus::Nominator(vec![1, 2, 3]))
-		.add_staker(30, 330, 10, StakerStatus::Nominator(vec![1, 2, 3, 4]))
-		.add_staker(50, 550, 10, StakerStatus::Nominator(vec![1, 2, 3, 4]))
+		.add_staker(60, 61, 9, StakerStatus::Nominator(vec![1]))
+		.add_staker(70, 71, 9, StakerStatus::Nominator(vec![1, 2, 3]))
+		.add_staker(30, 330, 9, StakerStatus::Nominator(vec![1, 2, 3, 4]))
+		.add_staker(50, 550, 9, StakerStatus::Nominator(vec![1, 2, 3, 4]))
 		.balance_factor(10)
 		.build_and_execute(|| {
 			// pre-condition.
@@ -5982,7 +6136,7 @@ fn nomination_quota_max_changes_decoding() {
 				vec![(70, 3), (101, 2), (50, 4), (30, 4), (60, 1)]
 			);
 			// 4 validators and 4 nominators
-			assert_eq!(Staking::electing_voters(unbonded_election).unwrap().len(), 4 + 4);
+			assert_eq!(Staking::electing_voters(unbonded_election, 0).unwrap().len(), 4 + 4);
 		});
 }
 
@@ -6760,7 +6914,7 @@ fn test_validator_exposure_is_backward_compatible_with_non_paged_rewards_payout(
 
 mod staking_interface {
 	use frame_support::storage::with_storage_layer;
-	use sp_staking::StakingInterface;
+	use sp_staking::{StakingInterface, StakingUnchecked};
 
 	use super::*;
 
@@ -6830,6 +6984,117 @@ mod staking_interface {
 			assert!(Staking::status(&42).is_err());
 		})
 	}
+
+	#[test]
+	fn virtual_bond_does_not_hold() {
+		ExtBuilder::default().build_and_execute(|| {
+			// 200 is a keyless account without any funds.
+			assert_eq!(Balances::free_balance(&200), 0);
+			assert_ok!(<Staking as StakingUnchecked>::virtual_bond(&200, 1000, &201));
+
+			assert!(<Staking as StakingInterface>::is_virtual_staker(&200));
+			assert_eq!(Staking::ledger(200.into()).unwrap().total, 1000);
+			assert_eq!(Payee::<Test>::get(&200), Some(RewardDestination::Account(201)));
+			// nothing is held.
+			assert_eq!(asset::staked::<Test>(&200), 0);
+
+			// bonding extra does not require any funds either.
+			assert_ok!(<Staking as StakingInterface>::bond_extra(&200, 500));
+			assert_eq!(Staking::ledger(200.into()).unwrap().total, 1500);
+			assert_eq!(asset::staked::<Test>(&200), 0);
+		});
+	}
+
+	#[test]
+	fn virtual_staker_reward_destination_is_restricted() {
+		ExtBuilder::default().build_and_execute(|| {
+			// the stash cannot be its own payee.
+			assert_noop!(
+				<Staking as StakingUnchecked>::virtual_bond(&200, 1000, &200),
+				Error::<Test>::RewardDestinationRestricted
+			);
+
+			assert_ok!(<Staking as StakingUnchecked>::virtual_bond(&200, 1000, &201));
+			assert_noop!(
+				<Staking as StakingInterface>::update_payee(&200, &200),
+				Error::<Test>::RewardDestinationRestricted
+			);
+			assert_ok!(<Staking as StakingInterface>::update_payee(&200, &202));
+			assert_eq!(Payee::<Test>::get(&200), Some(RewardDestination::Account(202)));
+
+			// direct stakers can still compound.
+			assert_ok!(<Staking as StakingInterface>::update_payee(&11, &11));
+		});
+	}
+
+	#[test]
+	fn virtual_staker_cannot_be_managed_directly() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_ok!(<Staking as StakingUnchecked>::virtual_bond(&200, 1000, &201));
+
+			assert_noop!(
+				Staking::bond_extra(RuntimeOrigin::signed(200), 100),
+				Error::<Test>::VirtualStakerNotAllowed
+			);
+			assert_noop!(
+				Staking::reap_stash(RuntimeOrigin::signed(10), 200, 0),
+				Error::<Test>::VirtualStakerNotAllowed
+			);
+			assert_noop!(
+				Staking::migrate_currency(RuntimeOrigin::signed(10), 200),
+				Error::<Test>::VirtualStakerNotAllowed
+			);
+		});
+	}
+
+	#[test]
+	fn migrate_to_virtual_staker_releases_stake() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_eq!(asset::staked::<Test>(&101), 500);
+
+			assert_ok!(<Staking as StakingUnchecked>::migrate_to_virtual_staker(&101));
+
+			// the ledger is untouched but nothing is held anymore.
+			assert!(<Staking as StakingInterface>::is_virtual_staker(&101));
+			assert_eq!(asset::staked::<Test>(&101), 0);
+			assert_eq!(Staking::ledger(101.into()).unwrap().total, 500);
+
+			// can't migrate twice.
+			assert_noop!(
+				<Staking as StakingUnchecked>::migrate_to_virtual_staker(&101),
+				Error::<Test>::VirtualStakerNotAllowed
+			);
+		});
+	}
+
+	#[test]
+	fn virtual_staker_slash_is_only_book_kept() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_ok!(<Staking as StakingUnchecked>::migrate_to_virtual_staker(&101));
+			let total_balance = Balances::total_balance(&101);
+			let exposed = Staking::eras_stakers(active_era(), &11)
+				.others
+				.iter()
+				.find(|e| e.who == 101)
+				.unwrap()
+				.value;
+
+			// slash 10% of the exposure of validator 11, backed by nominator 101.
+			add_slash(&11);
+
+			// the ledger is slashed..
+			assert_eq!(
+				Staking::ledger(101.into()).unwrap().active,
+				500 - Perbill::from_percent(10) * exposed
+			);
+			// .. but not the funds, those are managed by another pallet.
+			assert_eq!(Balances::total_balance(&101), total_balance);
+
+			// the ledger can still be killed.
+			assert_ok!(<Staking as StakingInterface>::force_unstake(101));
+			assert!(!<Staking as StakingInterface>::is_virtual_staker(&101));
+		});
+	}
 }
 
 mod ledger {
@@ -7162,3 +7427,121 @@ mod ledger {
 		})
 	}
 }
+
+mod hold_migration {
+	use super::*;
+	use frame_support::traits::{InspectLockableCurrency, LockableCurrency, WithdrawReasons};
+
+	/// Replaces the stake of `stash` with a legacy staking lock of the same amount.
+	fn lock_instead_of_hold(stash: AccountId) {
+		let stake = asset::staked::<Test>(&stash);
+		assert_ok!(asset::kill_stake::<Test>(&stash));
+		Balances::set_lock(crate::STAKING_ID, &stash, stake, WithdrawReasons::all());
+		assert!(StakingLedger::<Test>::has_legacy_lock(&stash));
+	}
+
+	#[test]
+	fn migrate_currency_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			lock_instead_of_hold(11);
+			assert_eq!(asset::staked::<Test>(&11), 0);
+			let _ = staking_events_since_last_call();
+
+			// anyone can migrate the stash, for free.
+			let result = Staking::migrate_currency(RuntimeOrigin::signed(1337), 11);
+			assert_ok!(result);
+			assert_eq!(result.unwrap().pays_fee, Pays::No);
+
+			assert_eq!(
+				staking_events_since_last_call(),
+				vec![Event::CurrencyMigrated { stash: 11, force_withdraw: 0 }]
+			);
+			assert_eq!(Balances::balance_locked(crate::STAKING_ID, &11), 0);
+			assert_eq!(asset::staked::<Test>(&11), 1000);
+			assert_eq!(Staking::ledger(11.into()).unwrap().active, 1000);
+
+			// can only be migrated once.
+			assert_noop!(
+				Staking::migrate_currency(RuntimeOrigin::signed(1337), 11),
+				Error::<Test>::AlreadyMigrated
+			);
+		});
+	}
+
+	#[test]
+	fn migrate_currency_withdraws_stake_that_cannot_be_held() {
+		ExtBuilder::default()
+			.existential_deposit(10)
+			.balance_factor(10)
+			.build_and_execute(|| {
+				lock_instead_of_hold(11);
+				// the lock covers the whole balance, including the existential deposit.
+				let _ = Balances::make_free_balance_be(&11, 10000);
+				let _ = staking_events_since_last_call();
+
+				assert_ok!(Staking::migrate_currency(RuntimeOrigin::signed(1337), 11));
+
+				assert_eq!(
+					staking_events_since_last_call(),
+					vec![Event::CurrencyMigrated { stash: 11, force_withdraw: 10 }]
+				);
+				assert_eq!(asset::staked::<Test>(&11), 9990);
+				// the voter list follows the withdrawn stake.
+				assert_eq!(<Test as Config>::VoterList::get_score(&11), Ok(9990));
+				assert_eq!(
+					Staking::ledger(11.into()).unwrap(),
+					StakingLedgerInspect {
+						stash: 11,
+						total: 9990,
+						active: 9990,
+						unlocking: Default::default(),
+						legacy_claimed_rewards: bounded_vec![],
+					}
+				);
+			});
+	}
+
+	#[test]
+	fn ledger_update_migrates_lazily() {
+		ExtBuilder::default().build_and_execute(|| {
+			lock_instead_of_hold(11);
+
+			assert_ok!(Staking::chill(RuntimeOrigin::signed(11)));
+			assert_ok!(Staking::unbond(RuntimeOrigin::signed(11), 100));
+
+			// the lock is gone and the unbonding funds are still held.
+			assert!(!StakingLedger::<Test>::has_legacy_lock(&11));
+			assert_eq!(asset::staked::<Test>(&11), 1000);
+			assert_noop!(
+				Staking::migrate_currency(RuntimeOrigin::signed(1337), 11),
+				Error::<Test>::AlreadyMigrated
+			);
+		});
+	}
+	#[test]
+	fn ledger_update_withdraws_stake_that_cannot_be_held() {
+		ExtBuilder::default()
+			.existential_deposit(10)
+			.balance_factor(10)
+			.build_and_execute(|| {
+				lock_instead_of_hold(101);
+				// the lock covers the whole balance, including the existential deposit.
+				let _ = Balances::make_free_balance_be(&101, 5000);
+				let _ = staking_events_since_last_call();
+
+				assert_ok!(Staking::unbond(RuntimeOrigin::signed(101), 100));
+
+				assert_eq!(
+					staking_events_since_last_call(),
+					vec![
+						Event::CurrencyMigrated { stash: 101, force_withdraw: 10 },
+						Event::Unbonded { stash: 101, amount: 100 }
+					]
+				);
+				// the stake that cannot be held is withdrawn from the unlocking funds first.
+				assert_eq!(asset::staked::<Test>(&101), 4990);
+				let ledger = Staking::ledger(101.into()).unwrap();
+				assert_eq!((ledger.active, ledger.unlocking[0].value), (4900, 90));
+			});
+	}
+}
@@ -122,8 +122,8 @@ pub fn expand_outer_inherent(
 
 				for xt in block.extrinsics() {
 					// Inherents are before any other extrinsics.
-					// And signed extrinsics are not inherents.
-					if #scrate::sp_runtime::traits::Extrinsic::is_signed(xt).unwrap_or(false) {
+					// And non-bare extrinsics are not inherents.
+					if !#scrate::sp_runtime::traits::Extrinsic::is_bare(xt) {
 						break
 					}
 
@@ -161,10 +161,9 @@ pub fn expand_outer_inherent(
 					match #pallet_names::is_inherent_required(self) {
 						Ok(Some(e)) => {
 							let found = block.extrinsics().iter().any(|xt| {
-								let is_signed = #scrate::sp_runtime::traits::Extrinsic::is_signed(xt)
-									.unwrap_or(false);
+								let is_bare = #scrate::sp_runtime::traits::Extrinsic::is_bare(xt);
 
-								if !is_signed {
+								if is_bare {
 									let call = <
 										#unchecked_extrinsic as ExtrinsicCall
 									>::call(xt);
@@ -213,11 +212,10 @@ pub fn expand_outer_inherent(
 				let mut first_signed_observed = false;
 
 				for (i, xt) in block.extrinsics().iter().enumerate() {
-					let is_signed = #scrate::sp_runtime::traits::Extrinsic::is_signed(xt)
-						.unwrap_or(false);
+					let is_bare = #scrate::sp_runtime::traits::Extrinsic::is_bare(xt);
 
-					let is_inherent = if is_signed {
-						// Signed extrinsics are not inherents.
+					let is_inherent = if !is_bare {
+						// Non-bare extrinsics are not inherents.
 						false
 					} else {
 						let mut is_inherent = false;
@@ -50,6 +50,8 @@ pub fn expand_runtime_metadata(
 			let constants = expand_pallet_metadata_constants(runtime, decl);
 			let errors = expand_pallet_metadata_errors(runtime, decl);
 			let docs = expand_pallet_metadata_docs(runtime, decl);
+			let associated_types = expand_pallet_metadata_associated_types(runtime, decl);
+			let view_functions = expand_pallet_metadata_view_functions(runtime, decl);
 			let attr = decl.cfg_pattern.iter().fold(TokenStream::new(), |acc, pattern| {
 				let attr = TokenStream::from_str(&format!("#[cfg({})]", pattern.original()))
 					.expect("was successfully parsed before; qed");
@@ -69,6 +71,8 @@ pub fn expand_runtime_metadata(
 					event: #event,
 					constants: #constants,
 					error: #errors,
+					associated_types: #associated_types,
+					view_functions: #view_functions,
 					docs: #docs,
 				}
 			}
@@ -114,7 +118,7 @@ pub fn expand_runtime_metadata(
 					pallets: #scrate::__private::sp_std::vec![ #(#pallets),* ],
 					extrinsic: #scrate::__private::metadata_ir::ExtrinsicMetadataIR {
 						ty,
-						version: <#extrinsic as #scrate::sp_runtime::traits::ExtrinsicMetadata>::VERSION,
+						versions: <#extrinsic as #scrate::sp_runtime::traits::ExtrinsicMetadata>::VERSIONS.to_vec(),
 						address_ty,
 						call_ty,
 						signature_ty,
@@ -122,13 +126,13 @@ pub fn expand_runtime_metadata(
 						signed_extensions: <
 								<
 									#extrinsic as #scrate::sp_runtime::traits::ExtrinsicMetadata
-								>::SignedExtensions as #scrate::sp_runtime::traits::SignedExtension
+								>::TransactionExtensions as #scrate::sp_runtime::traits::TransactionExtensionBase
 							>::metadata()
 								.into_iter()
 								.map(|meta| #scrate::__private::metadata_ir::SignedExtensionMetadataIR {
 									identifier: meta.identifier,
 									ty: meta.ty,
-									additional_signed: meta.additional_signed,
+									additional_signed: meta.implicit,
 								})
 								.collect(),
 					},
@@ -221,7 +225,8 @@ fn expand_pallet_metadata_events(
 		quote! {
 			Some(
 				#scrate::__private::metadata_ir::PalletEventMetadataIR {
-					ty: #scrate::__private::scale_info::meta_type::<#pallet_event>()
+					ty: #scrate::__private::scale_info::meta_type::<#pallet_event>(),
+					deprecation_info: #pallet_event::deprecation_info(),
 				}
 			)
 		}
@@ -256,3 +261,21 @@ fn expand_pallet_metadata_docs(runtime: &Ident, decl: &Pallet) -> TokenStream {
 		#path::Pallet::<#runtime #(, #path::#instance)*>::pallet_documentation_metadata()
 	}
 }
+
+fn expand_pallet_metadata_associated_types(runtime: &Ident, decl: &Pallet) -> TokenStream {
+	let path = &decl.path;
+	let instance = decl.instance.as_ref().into_iter();
+
+	quote! {
+		#path::Pallet::<#runtime #(, #path::#instance)*>::pallet_associated_types_metadata()
+	}
+}
+
+fn expand_pallet_metadata_view_functions(runtime: &Ident, decl: &Pallet) -> TokenStream {
+	let path = &decl.path;
+	let instance = decl.instance.as_ref().into_iter();
+
+	quote! {
+		#path::Pallet::<#runtime #(, #path::#instance)*>::pallet_view_functions_metadata()
+	}
+}
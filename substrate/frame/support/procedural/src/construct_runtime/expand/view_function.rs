@@ -0,0 +1,96 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::construct_runtime::Pallet;
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::quote;
+use std::str::FromStr;
+
+/// Expands implementation of runtime level `DispatchViewFunction`.
+pub fn expand_outer_view_function(
+	runtime_name: &Ident,
+	pallet_decls: &[Pallet],
+	scrate: &TokenStream2,
+) -> TokenStream2 {
+	let runtime_view_function = syn::Ident::new("RuntimeViewFunction", runtime_name.span());
+
+	let prefix_conditionals = pallet_decls
+		.iter()
+		.filter(|decl| decl.find_part("Pallet").is_some())
+		.map(|decl| {
+			let path = &decl.path;
+			let instance = decl.instance.as_ref().into_iter();
+			let pallet = quote!(#path::Pallet::<#runtime_name #(, #path::#instance)*>);
+			let attr = decl.cfg_pattern.iter().fold(TokenStream2::new(), |acc, pattern| {
+				let attr = TokenStream2::from_str(&format!("#[cfg({})]", pattern.original()))
+					.expect("was successfully parsed before; qed");
+				quote! {
+					#acc
+					#attr
+				}
+			});
+
+			quote! {
+				#attr
+				if id.prefix == <#pallet as #scrate::view_functions::ViewFunctionIdPrefix>::prefix() {
+					return <#pallet as #scrate::view_functions::DispatchViewFunction>::dispatch_view_function(
+						id, input, output
+					)
+				}
+			}
+		});
+
+	quote! {
+		/// Runtime view function dispatch logic.
+		#[derive(Clone, Copy, PartialEq, Eq, #scrate::__private::RuntimeDebug)]
+		pub struct #runtime_view_function;
+
+		const _: () = {
+			impl #scrate::view_functions::DispatchViewFunction for #runtime_view_function {
+				fn dispatch_view_function<O: #scrate::__private::codec::Output>(
+					id: &#scrate::view_functions::ViewFunctionId,
+					input: &mut &[u8],
+					output: &mut O
+				) -> Result<(), #scrate::view_functions::ViewFunctionDispatchError>
+				{
+					#( #prefix_conditionals )*
+					Err(#scrate::view_functions::ViewFunctionDispatchError::NotFound(id.clone()))
+				}
+			}
+
+			impl #runtime_name {
+				/// Convenience function for dispatching a view function by id. Used when
+				/// implementing the `RuntimeViewFunction` runtime API.
+				pub fn execute_view_function(
+					id: #scrate::view_functions::ViewFunctionId,
+					input: #scrate::__private::sp_std::vec::Vec<::core::primitive::u8>,
+				) -> Result<
+					#scrate::__private::sp_std::vec::Vec<::core::primitive::u8>,
+					#scrate::view_functions::ViewFunctionDispatchError
+				> {
+					let mut output = #scrate::__private::sp_std::vec::Vec::new();
+					<#runtime_view_function as #scrate::view_functions::DispatchViewFunction>::dispatch_view_function(
+						&id,
+						&mut &input[..],
+						&mut output,
+					)?;
+					Ok(output)
+				}
+			}
+		};
+	}
+}
@@ -387,6 +387,7 @@ fn construct_runtime_final_expansion(
 
 	let dispatch = expand::expand_outer_dispatch(&name, system_pallet, &pallets, &scrate);
 	let tasks = expand::expand_outer_task(&name, &pallets, &scrate);
+	let view_function = expand::expand_outer_view_function(&name, &pallets, &scrate);
 	let metadata = expand::expand_runtime_metadata(
 		&name,
 		&pallets,
@@ -478,6 +479,8 @@ fn construct_runtime_final_expansion(
 
 		#tasks
 
+		#view_function
+
 		#metadata
 
 		#outer_config
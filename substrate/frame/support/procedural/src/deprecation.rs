@@ -0,0 +1,130 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing of the `#[deprecated]` attribute into the deprecation info of the metadata.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+	punctuated::Punctuated, spanned::Spanned, Attribute, Error, Expr, ExprLit, Lit, LitStr, Meta,
+	MetaNameValue, Token,
+};
+
+/// The deprecation status of an item, as declared by its `#[deprecated]` attribute.
+#[derive(Clone)]
+pub enum Deprecation {
+	/// `#[deprecated]` or `#[deprecated(since = "..")]`.
+	WithoutNote,
+	/// `#[deprecated = ".."]` or `#[deprecated(note = "..", since = "..")]`.
+	WithNote { note: LitStr, since: Option<LitStr> },
+}
+
+fn string_literal(expr: &Expr) -> syn::Result<&LitStr> {
+	match expr {
+		Expr::Lit(ExprLit { lit: Lit::Str(lit), .. }) => Ok(lit),
+		_ => Err(Error::new(expr.span(), "Invalid deprecation attribute: expected string literal")),
+	}
+}
+
+fn parse_deprecation(attr: &Attribute) -> syn::Result<Deprecation> {
+	match &attr.meta {
+		Meta::Path(_) => Ok(Deprecation::WithoutNote),
+		Meta::NameValue(MetaNameValue { value, .. }) =>
+			Ok(Deprecation::WithNote { note: string_literal(value)?.clone(), since: None }),
+		Meta::List(list) => {
+			let items =
+				list.parse_args_with(Punctuated::<MetaNameValue, Token![,]>::parse_terminated)?;
+
+			let mut note = None;
+			let mut since = None;
+			for item in items {
+				if item.path.is_ident("note") {
+					note = Some(string_literal(&item.value)?.clone());
+				} else if item.path.is_ident("since") {
+					since = Some(string_literal(&item.value)?.clone());
+				} else {
+					let msg = "Invalid deprecation attribute: expected `note` or `since`";
+					return Err(Error::new(item.path.span(), msg))
+				}
+			}
+
+			Ok(match note {
+				Some(note) => Deprecation::WithNote { note, since },
+				None => Deprecation::WithoutNote,
+			})
+		},
+	}
+}
+
+/// Parse the `#[deprecated]` attribute among `attrs`, if any.
+pub fn get_deprecation(attrs: &[Attribute]) -> syn::Result<Option<Deprecation>> {
+	attrs
+		.iter()
+		.find(|attr| attr.path().is_ident("deprecated"))
+		.map(parse_deprecation)
+		.transpose()
+}
+
+/// Expand into a `ItemDeprecationInfoIR` for the given deprecation status.
+pub fn expand_item_deprecation(
+	frame_support: &syn::Path,
+	deprecation: &Option<Deprecation>,
+) -> TokenStream {
+	let metadata_ir = quote!(#frame_support::__private::metadata_ir);
+
+	match deprecation {
+		None => quote!(#metadata_ir::ItemDeprecationInfoIR::NotDeprecated),
+		Some(Deprecation::WithoutNote) =>
+			quote!(#metadata_ir::ItemDeprecationInfoIR::DeprecatedWithoutNote),
+		Some(Deprecation::WithNote { note, since }) => {
+			let since = since.as_ref().map_or_else(|| quote!(None), |since| quote!(Some(#since)));
+			quote!(#metadata_ir::ItemDeprecationInfoIR::Deprecated { note: #note, since: #since })
+		},
+	}
+}
+
+/// Expand into a `EnumDeprecationInfoIR` given the deprecation status of each variant, keyed by
+/// the variant index.
+pub fn expand_enum_deprecation<'a>(
+	frame_support: &syn::Path,
+	variants: impl Iterator<Item = (u8, &'a Option<Deprecation>)>,
+) -> TokenStream {
+	let metadata_ir = quote!(#frame_support::__private::metadata_ir);
+
+	let entries = variants.filter_map(|(index, deprecation)| {
+		let info = match deprecation.as_ref()? {
+			Deprecation::WithoutNote =>
+				quote!(#metadata_ir::VariantDeprecationInfoIR::DeprecatedWithoutNote),
+			Deprecation::WithNote { note, since } => {
+				let since =
+					since.as_ref().map_or_else(|| quote!(None), |since| quote!(Some(#since)));
+				quote!(
+					#metadata_ir::VariantDeprecationInfoIR::Deprecated { note: #note, since: #since }
+				)
+			},
+		};
+		Some(quote!((#index, #info)))
+	});
+
+	quote!(
+		#metadata_ir::EnumDeprecationInfoIR(
+			#frame_support::__private::sp_std::collections::btree_map::BTreeMap::from([
+				#( #entries, )*
+			])
+		)
+	)
+}
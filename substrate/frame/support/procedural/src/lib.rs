@@ -23,6 +23,7 @@
 mod benchmark;
 mod construct_runtime;
 mod crate_version;
+mod deprecation;
 mod derive_impl;
 mod dummy_part_checker;
 mod dynamic_params;
@@ -1468,7 +1469,7 @@ pub fn inherent(_: TokenStream, _: TokenStream) -> TokenStream {
 /// I.e. a trait implementation with bound `T: Config`, of trait `ValidateUnsigned` for type
 /// `Pallet<T>`, and some optional where clause.
 ///
-/// NOTE: There is also the `sp_runtime::traits::SignedExtension` trait that can be used to add
+/// NOTE: There is also the `sp_runtime::traits::TransactionExtension` trait that can be used to add
 /// some specific logic for transaction validation.
 ///
 /// ## Macro expansion
@@ -1569,6 +1570,16 @@ pub fn task_weight(_: TokenStream, _: TokenStream) -> TokenStream {
 	pallet_macro_stub()
 }
 
+///
+/// ---
+///
+/// **Rust-Analyzer users**: See the documentation of the Rust item in
+/// `frame_support::pallet_macros::view_functions`.
+#[proc_macro_attribute]
+pub fn view_functions(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
 ///
 /// ---
 ///
@@ -53,6 +53,10 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 
 	let fn_name = methods.iter().map(|method| &method.name).collect::<Vec<_>>();
 	let call_index = methods.iter().map(|method| method.call_index).collect::<Vec<_>>();
+	let deprecation = crate::deprecation::expand_enum_deprecation(
+		frame_support,
+		methods.iter().map(|method| (method.call_index, &method.deprecation)),
+	);
 	let new_call_variant_fn_name = fn_name
 		.iter()
 		.map(|fn_name| quote::format_ident!("new_call_variant_{}", fn_name))
@@ -424,6 +428,7 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 			#where_clause
 		{
 			type RuntimeOrigin = #frame_system::pallet_prelude::OriginFor<T>;
+			#[allow(deprecated)]
 			fn dispatch_bypass_filter(
 				self,
 				origin: Self::RuntimeOrigin
@@ -460,7 +465,10 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 			#[allow(dead_code)]
 			#[doc(hidden)]
 			pub fn call_functions() -> #frame_support::__private::metadata_ir::PalletCallMetadataIR {
-				#frame_support::__private::scale_info::meta_type::<#call_ident<#type_use_gen>>().into()
+				#frame_support::__private::metadata_ir::PalletCallMetadataIR {
+					ty: #frame_support::__private::scale_info::meta_type::<#call_ident<#type_use_gen>>(),
+					deprecation_info: #deprecation,
+				}
 			}
 		}
 	)
@@ -22,7 +22,9 @@ use syn::{parse_quote, Item};
 
 ///
 /// * Generate default rust doc
+/// * Impl fn pallet_associated_types_metadata for pallet.
 pub fn expand_config(def: &mut Def) -> TokenStream {
+	let associated_types_metadata = expand_associated_types_metadata(def);
 	let config = &def.config;
 	let config_item = {
 		let item = &mut def.item.content.as_mut().expect("Checked by def parser").1[config.index];
@@ -50,7 +52,7 @@ Consequently, a runtime that wants to include this pallet must implement this tr
 
 	// we only emit `DefaultConfig` if there are trait items, so an empty `DefaultConfig` is
 	// impossible consequently.
-	match &config.default_sub_trait {
+	let default_config = match &config.default_sub_trait {
 		Some(default_sub_trait) if default_sub_trait.items.len() > 0 => {
 			let trait_items = &default_sub_trait
 				.items
@@ -93,5 +95,49 @@ Consequently, a runtime that wants to include this pallet must implement this tr
 			)
 		},
 		_ => Default::default(),
-	}
+	};
+
+	quote!(
+		#default_config
+		#associated_types_metadata
+	)
+}
+
+/// Generate the metadata of the associated types of the config which implement `TypeInfo`.
+fn expand_associated_types_metadata(def: &Def) -> TokenStream {
+	let frame_support = &def.frame_support;
+	let type_impl_gen = &def.type_impl_generics(proc_macro2::Span::call_site());
+	let type_use_gen = &def.type_use_generics(proc_macro2::Span::call_site());
+	let trait_use_gen = &def.trait_use_generics(proc_macro2::Span::call_site());
+	let pallet_ident = &def.pallet_struct.pallet;
+	let where_clause = &def.config.where_clause;
+
+	let types = def.config.associated_types_metadata.iter().map(|type_| {
+		let ident = &type_.ident;
+		let ident_str = ident.to_string();
+
+		let no_docs = vec![];
+		let doc = if cfg!(feature = "no-metadata-docs") { &no_docs } else { &type_.doc };
+
+		quote!({
+			#frame_support::__private::metadata_ir::PalletAssociatedTypeMetadataIR {
+				name: #ident_str,
+				ty: #frame_support::__private::scale_info::meta_type::<
+					<T as Config #trait_use_gen>::#ident
+				>(),
+				docs: #frame_support::__private::sp_std::vec![ #( #doc ),* ],
+			}
+		})
+	});
+
+	quote!(
+		impl<#type_impl_gen> #pallet_ident<#type_use_gen> #where_clause {
+			#[doc(hidden)]
+			pub fn pallet_associated_types_metadata()
+				-> #frame_support::__private::sp_std::vec::Vec<#frame_support::__private::metadata_ir::PalletAssociatedTypeMetadataIR>
+			{
+				#frame_support::__private::sp_std::vec![ #( #types ),* ]
+			}
+		}
+	)
 }
@@ -69,6 +69,13 @@ pub fn expand_event(def: &mut Def) -> proc_macro2::TokenStream {
 	let event_ident = &event.event;
 	let frame_system = &def.frame_system;
 	let frame_support = &def.frame_support;
+	let deprecation = crate::deprecation::expand_enum_deprecation(
+		frame_support,
+		event
+			.variants_deprecation
+			.iter()
+			.map(|(index, deprecation)| (*index, deprecation)),
+	);
 	let event_use_gen = &event.gen_kind.type_use_gen(event.attr_span);
 	let event_impl_gen = &event.gen_kind.type_impl_gen(event.attr_span);
 
@@ -170,5 +177,13 @@ pub fn expand_event(def: &mut Def) -> proc_macro2::TokenStream {
 		impl<#event_impl_gen> From<#event_ident<#event_use_gen>> for () #event_where_clause {
 			fn from(_: #event_ident<#event_use_gen>) {}
 		}
+
+		impl<#event_impl_gen> #event_ident<#event_use_gen> #event_where_clause {
+			#[allow(dead_code)]
+			#[doc(hidden)]
+			pub fn deprecation_info() -> #frame_support::__private::metadata_ir::EnumDeprecationInfoIR {
+				#deprecation
+			}
+		}
 	)
 }
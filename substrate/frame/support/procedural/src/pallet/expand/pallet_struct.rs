@@ -138,6 +138,7 @@ pub fn expand_pallet_struct(def: &mut Def) -> proc_macro2::TokenStream {
 			for #pallet_ident<#type_use_gen>
 			#storages_where_clauses
 		{
+			#[allow(deprecated)]
 			fn storage_info()
 				-> #frame_support::__private::sp_std::vec::Vec<#frame_support::traits::StorageInfo>
 			{
@@ -179,6 +180,7 @@ pub fn expand_pallet_struct(def: &mut Def) -> proc_macro2::TokenStream {
 	let whitelisted_storage_keys_impl = quote::quote![
 		use #frame_support::traits::{StorageInfoTrait, TrackedStorageKey, WhitelistedStorageKeys};
 		impl<#type_impl_gen> WhitelistedStorageKeys for #pallet_ident<#type_use_gen> #storages_where_clauses {
+			#[allow(deprecated)]
 			fn whitelisted_storage_keys() -> #frame_support::__private::sp_std::vec::Vec<TrackedStorageKey> {
 				use #frame_support::__private::sp_std::vec;
 				vec![#(
@@ -0,0 +1,128 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	deprecation::expand_item_deprecation,
+	pallet::{parse::helper::two128_str, Def},
+};
+use syn::spanned::Spanned;
+
+/// * Impl `ViewFunctionIdPrefix` and `DispatchViewFunction` for the pallet.
+/// * Impl fn `pallet_view_functions_metadata` for the pallet.
+pub fn expand_view_functions(def: &Def) -> proc_macro2::TokenStream {
+	let frame_support = &def.frame_support;
+	let span = def.view_functions.as_ref().map_or(def.item.span(), |v| v.attr_span);
+	let type_impl_gen = &def.type_impl_generics(span);
+	let type_use_gen = &def.type_use_generics(span);
+	let pallet_ident = &def.pallet_struct.pallet;
+
+	let mut where_clauses = vec![&def.config.where_clause];
+	where_clauses.extend(def.view_functions.iter().map(|d| &d.where_clause));
+	let completed_where_clause = super::merge_where_clauses(&where_clauses);
+
+	let view_functions = def.view_functions.iter().flat_map(|d| &d.view_functions);
+
+	let dispatch_arms = view_functions.clone().map(|view_fn| {
+		let name = &view_fn.name;
+		let suffix = two128_str(&view_fn.signature());
+		let arg_names = view_fn.args.iter().map(|(name, _)| name).collect::<Vec<_>>();
+		let arg_types = view_fn.args.iter().map(|(_, type_)| type_);
+
+		quote::quote_spanned!(span =>
+			#suffix => {
+				let ( #( #arg_names, )* ): ( #( #arg_types, )* ) =
+					#frame_support::__private::codec::DecodeAll::decode_all(input)?;
+				let result = Self::#name( #( #arg_names, )* );
+				#frame_support::__private::codec::Encode::encode_to(&result, output);
+				Ok(())
+			}
+		)
+	});
+
+	let metadata = view_functions.map(|view_fn| {
+		let name_str = view_fn.name.to_string();
+		let suffix = two128_str(&view_fn.signature());
+		let return_type = &view_fn.return_type;
+		let inputs = view_fn.args.iter().map(|(name, type_)| {
+			let name_str = name.to_string();
+			quote::quote!(
+				#frame_support::__private::metadata_ir::PalletViewFunctionParamMetadataIR {
+					name: #name_str,
+					ty: #frame_support::__private::scale_info::meta_type::<#type_>(),
+				}
+			)
+		});
+		let no_docs = vec![];
+		let docs = if cfg!(feature = "no-metadata-docs") { &no_docs } else { &view_fn.docs };
+		let deprecation_info = expand_item_deprecation(frame_support, &view_fn.deprecation);
+
+		quote::quote!(
+			#frame_support::__private::metadata_ir::PalletViewFunctionMetadataIR {
+				name: #name_str,
+				id: #frame_support::view_functions::ViewFunctionId {
+					prefix: <Self as #frame_support::view_functions::ViewFunctionIdPrefix>::prefix(),
+					suffix: #suffix,
+				}.into(),
+				inputs: #frame_support::__private::sp_std::vec![ #( #inputs ),* ],
+				output: #frame_support::__private::scale_info::meta_type::<#return_type>(),
+				docs: #frame_support::__private::sp_std::vec![ #( #docs ),* ],
+				deprecation_info: #deprecation_info,
+			}
+		)
+	});
+
+	quote::quote_spanned!(span =>
+		impl<#type_impl_gen> #frame_support::view_functions::ViewFunctionIdPrefix
+			for #pallet_ident<#type_use_gen> #completed_where_clause
+		{
+			fn prefix() -> [::core::primitive::u8; 16] {
+				<Self as #frame_support::traits::PalletInfoAccess>::name_hash()
+			}
+		}
+
+		impl<#type_impl_gen> #frame_support::view_functions::DispatchViewFunction
+			for #pallet_ident<#type_use_gen> #completed_where_clause
+		{
+			#[allow(deprecated)]
+			#[deny(unreachable_patterns)]
+			fn dispatch_view_function<O: #frame_support::__private::codec::Output>(
+				id: &#frame_support::view_functions::ViewFunctionId,
+				input: &mut &[::core::primitive::u8],
+				output: &mut O,
+			) -> Result<(), #frame_support::view_functions::ViewFunctionDispatchError> {
+				match id.suffix {
+					#( #dispatch_arms )*
+					_ => {
+						let _ = (input, output);
+						Err(#frame_support::view_functions::ViewFunctionDispatchError::NotFound(
+							id.clone(),
+						))
+					},
+				}
+			}
+		}
+
+		impl<#type_impl_gen> #pallet_ident<#type_use_gen> #completed_where_clause {
+			#[doc(hidden)]
+			pub fn pallet_view_functions_metadata() -> #frame_support::__private::sp_std::vec::Vec<
+				#frame_support::__private::metadata_ir::PalletViewFunctionMetadataIR
+			> {
+				#frame_support::__private::sp_std::vec![ #( #metadata ),* ]
+			}
+		}
+	)
+}
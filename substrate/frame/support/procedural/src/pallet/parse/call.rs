@@ -16,6 +16,7 @@
 // limitations under the License.
 
 use super::{helper, InheritedCallWeightAttr};
+use crate::deprecation::{get_deprecation, Deprecation};
 use frame_support_procedural_tools::get_doc_literals;
 use proc_macro2::Span;
 use quote::ToTokens;
@@ -83,6 +84,8 @@ pub struct CallVariantDef {
 	pub explicit_call_index: bool,
 	/// Docs, used for metadata.
 	pub docs: Vec<syn::Expr>,
+	/// The deprecation status of the dispatchable, from its `#[deprecated]` attribute.
+	pub deprecation: Option<Deprecation>,
 	/// Attributes annotated at the top of the dispatchable function.
 	pub attrs: Vec<syn::Attribute>,
 	/// The `cfg` attributes.
@@ -369,6 +372,7 @@ impl CallDef {
 				}
 
 				let docs = get_doc_literals(&method.attrs);
+				let deprecation = get_deprecation(&method.attrs)?;
 
 				if feeless_attrs.len() > 1 {
 					let msg = "Invalid pallet::call, there can only be one feeless_if attribute";
@@ -444,6 +448,7 @@ impl CallDef {
 					explicit_call_index,
 					args,
 					docs,
+					deprecation,
 					attrs: method.attrs.clone(),
 					cfg_attrs,
 					feeless_check,
@@ -55,6 +55,8 @@ pub struct ConfigDef {
 	pub has_instance: bool,
 	/// Const associated type.
 	pub consts_metadata: Vec<ConstMetadataDef>,
+	/// Associated types which implement `TypeInfo`, exposed in the metadata.
+	pub associated_types_metadata: Vec<AssociatedTypeMetadataDef>,
 	/// Whether the trait has the associated type `Event`, note that those bounds are
 	/// checked:
 	/// * `IsType<Self as frame_system::Config>::RuntimeEvent`
@@ -125,6 +127,30 @@ impl TryFrom<&syn::TraitItemType> for ConstMetadataDef {
 	}
 }
 
+/// Input definition for an associated type in pallet config, exposed in the metadata.
+pub struct AssociatedTypeMetadataDef {
+	/// Name of the associated type.
+	pub ident: syn::Ident,
+	/// The doc associated.
+	pub doc: Vec<syn::Expr>,
+}
+
+impl AssociatedTypeMetadataDef {
+	/// Returns the metadata definition of `trait_ty` if it is bounded by `TypeInfo`.
+	fn from_trait_type(trait_ty: &syn::TraitItemType) -> Option<Self> {
+		let implements_type_info = trait_ty.bounds.iter().any(|b| {
+			if let syn::TypeParamBound::Trait(tb) = b {
+				tb.path.segments.last().map_or(false, |s| s.ident == "TypeInfo")
+			} else {
+				false
+			}
+		});
+
+		implements_type_info
+			.then(|| Self { ident: trait_ty.ident.clone(), doc: get_doc_literals(&trait_ty.attrs) })
+	}
+}
+
 /// Parse for `#[pallet::disable_frame_system_supertrait_check]`
 pub struct DisableFrameSystemSupertraitCheck;
 
@@ -374,6 +400,7 @@ impl ConfigDef {
 
 		let mut has_event_type = false;
 		let mut consts_metadata = vec![];
+		let mut associated_types_metadata = vec![];
 		let mut default_sub_trait = if enable_default {
 			Some(DefaultTrait {
 				items: Default::default(),
@@ -445,6 +472,10 @@ impl ConfigDef {
 				}
 			}
 
+			if let syn::TraitItem::Type(typ) = &*trait_item {
+				associated_types_metadata.extend(AssociatedTypeMetadataDef::from_trait_type(typ));
+			}
+
 			if !already_no_default && enable_default {
 				default_sub_trait
 					.as_mut()
@@ -487,6 +518,7 @@ impl ConfigDef {
 			index,
 			has_instance,
 			consts_metadata,
+			associated_types_metadata,
 			has_event_type,
 			where_clause,
 			attr_span,
@@ -16,6 +16,7 @@
 // limitations under the License.
 
 use super::helper;
+use crate::deprecation::{get_deprecation, Deprecation};
 use quote::ToTokens;
 use syn::spanned::Spanned;
 
@@ -41,6 +42,8 @@ pub struct EventDef {
 	pub deposit_event: Option<PalletEventDepositAttr>,
 	/// Where clause used in event definition.
 	pub where_clause: Option<syn::WhereClause>,
+	/// The index and the deprecation status of each variant, used for metadata.
+	pub variants_deprecation: Vec<(u8, Option<Deprecation>)>,
 	/// The span of the pallet::event attribute.
 	pub attr_span: proc_macro2::Span,
 }
@@ -95,6 +98,28 @@ impl PalletEventAttrInfo {
 	}
 }
 
+/// Get the index of `variant` when encoded: either its `#[codec(index = ..)]` or its position.
+fn variant_index(variant: &syn::Variant, position: usize) -> syn::Result<u8> {
+	let mut index = None;
+	for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("codec")) {
+		attr.parse_nested_meta(|meta| {
+			if meta.path.is_ident("index") {
+				index = Some(meta.value()?.parse::<syn::LitInt>()?.base10_parse::<u8>()?);
+			} else if meta.input.peek(syn::Token![=]) {
+				meta.value()?.parse::<syn::Expr>()?;
+			}
+			Ok(())
+		})?;
+	}
+
+	match index {
+		Some(index) => Ok(index),
+		None => u8::try_from(position).map_err(|_| {
+			syn::Error::new(variant.span(), "Invalid pallet::event, too many variants")
+		}),
+	}
+}
+
 impl EventDef {
 	pub fn try_from(
 		attr_span: proc_macro2::Span,
@@ -136,6 +161,24 @@ impl EventDef {
 
 		let event = syn::parse2::<keyword::Event>(item.ident.to_token_stream())?;
 
-		Ok(EventDef { attr_span, index, instances, deposit_event, event, gen_kind, where_clause })
+		let variants_deprecation = item
+			.variants
+			.iter()
+			.enumerate()
+			.map(|(position, variant)| {
+				Ok((variant_index(variant, position)?, get_deprecation(&variant.attrs)?))
+			})
+			.collect::<syn::Result<Vec<_>>>()?;
+
+		Ok(EventDef {
+			attr_span,
+			index,
+			instances,
+			deposit_event,
+			event,
+			gen_kind,
+			where_clause,
+			variants_deprecation,
+		})
 	}
 }
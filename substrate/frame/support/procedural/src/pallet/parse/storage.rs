@@ -16,6 +16,7 @@
 // limitations under the License.
 
 use super::helper;
+use crate::deprecation::{get_deprecation, Deprecation};
 use frame_support_procedural_tools::get_doc_literals;
 use quote::ToTokens;
 use std::collections::HashMap;
@@ -161,6 +162,8 @@ pub struct StorageDef {
 	pub metadata: Metadata,
 	/// The doc associated to the storage.
 	pub docs: Vec<syn::Expr>,
+	/// The deprecation status of the storage, from its `#[deprecated]` attribute.
+	pub deprecation: Option<Deprecation>,
 	/// A set of usage of instance, must be check for consistency with config.
 	pub instances: Vec<helper::InstanceUsage>,
 	/// Optional getter to generate. If some then query_kind is ensured to be some as well.
@@ -786,6 +789,7 @@ impl StorageDef {
 
 		let where_clause = item.generics.where_clause.clone();
 		let docs = get_doc_literals(&item.attrs);
+		let deprecation = get_deprecation(&item.attrs)?;
 
 		let typ = if let syn::Type::Path(typ) = &*item.ty {
 			typ
@@ -913,6 +917,7 @@ impl StorageDef {
 			instances,
 			metadata,
 			docs,
+			deprecation,
 			getter,
 			rename_as,
 			query_kind,
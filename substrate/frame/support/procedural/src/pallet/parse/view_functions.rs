@@ -0,0 +1,151 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::helper;
+use crate::deprecation::{get_deprecation, Deprecation};
+use frame_support_procedural_tools::get_doc_literals;
+use quote::ToTokens;
+use syn::spanned::Spanned;
+
+/// Definition of view functions typically `impl<T: Config> Pallet<T> { ... }`
+pub struct ViewFunctionsImplDef {
+	/// The where_clause used.
+	pub where_clause: Option<syn::WhereClause>,
+	/// A set of usage of instance, must be check for consistency with trait.
+	pub instances: Vec<helper::InstanceUsage>,
+	/// The span of the pallet::view_functions attribute.
+	pub attr_span: proc_macro2::Span,
+	/// The view functions defined.
+	pub view_functions: Vec<ViewFunctionDef>,
+}
+
+/// Definition of a view function typically: `fn foo(param1: ...) -> ..`
+pub struct ViewFunctionDef {
+	/// Function name.
+	pub name: syn::Ident,
+	/// Information on args: `(name, type)`
+	pub args: Vec<(syn::Ident, Box<syn::Type>)>,
+	/// The type returned by the function.
+	pub return_type: syn::Type,
+	/// Docs, used for metadata.
+	pub docs: Vec<syn::Expr>,
+	/// The optional `deprecated` attribute of the function.
+	pub deprecation: Option<Deprecation>,
+}
+
+impl ViewFunctionDef {
+	/// The signature of the function, e.g. `foo(u32, T::AccountId) -> u64`, from which the query
+	/// id suffix of the view function is derived.
+	pub fn signature(&self) -> String {
+		let arg_types = self
+			.args
+			.iter()
+			.map(|(_, type_)| type_.to_token_stream().to_string())
+			.collect::<Vec<_>>()
+			.join(", ");
+		format!("{}({}) -> {}", self.name, arg_types, self.return_type.to_token_stream())
+	}
+}
+
+impl ViewFunctionsImplDef {
+	pub fn try_from(attr_span: proc_macro2::Span, item: &mut syn::Item) -> syn::Result<Self> {
+		let item = if let syn::Item::Impl(item) = item {
+			item
+		} else {
+			return Err(syn::Error::new(
+				item.span(),
+				"Invalid pallet::view_functions, expected item impl",
+			))
+		};
+
+		let instances = vec![
+			helper::check_impl_gen(&item.generics, item.impl_token.span())?,
+			helper::check_pallet_struct_usage(&item.self_ty)?,
+		];
+
+		if let Some((_, _, for_)) = item.trait_ {
+			let msg = "Invalid pallet::view_functions, expected no trait ident as in \
+				`impl<..> Pallet<..> { .. }`";
+			return Err(syn::Error::new(for_.span(), msg))
+		}
+
+		let mut view_functions = vec![];
+		for impl_item in &mut item.items {
+			let method = if let syn::ImplItem::Fn(method) = impl_item {
+				method
+			} else {
+				let msg = "Invalid pallet::view_functions, only method accepted";
+				return Err(syn::Error::new(impl_item.span(), msg))
+			};
+
+			if !matches!(method.vis, syn::Visibility::Public(_)) {
+				let msg = "Invalid pallet::view_functions, view function must be public: \
+					`pub fn`";
+				return Err(syn::Error::new(method.sig.span(), msg))
+			}
+
+			if !method.sig.generics.params.is_empty() {
+				let msg = "Invalid pallet::view_functions, view function must have 0 generics";
+				return Err(syn::Error::new(method.sig.generics.params[0].span(), msg))
+			}
+
+			let mut args = vec![];
+			for arg in method.sig.inputs.iter() {
+				let arg = if let syn::FnArg::Typed(arg) = arg {
+					arg
+				} else {
+					let msg = "Invalid pallet::view_functions, view function must not have a \
+						receiver";
+					return Err(syn::Error::new(arg.span(), msg))
+				};
+
+				let arg_ident = if let syn::Pat::Ident(pat) = &*arg.pat {
+					pat.ident.clone()
+				} else {
+					let msg = "Invalid pallet::view_functions, argument must be ident";
+					return Err(syn::Error::new(arg.pat.span(), msg))
+				};
+
+				args.push((arg_ident, arg.ty.clone()));
+			}
+
+			let return_type = match &method.sig.output {
+				syn::ReturnType::Default => {
+					let msg = "Invalid pallet::view_functions, view function must have a return \
+						type";
+					return Err(syn::Error::new(method.sig.span(), msg))
+				},
+				syn::ReturnType::Type(_, type_) => *type_.clone(),
+			};
+
+			view_functions.push(ViewFunctionDef {
+				name: method.sig.ident.clone(),
+				args,
+				return_type,
+				docs: get_doc_literals(&method.attrs),
+				deprecation: get_deprecation(&method.attrs)?,
+			});
+		}
+
+		Ok(Self {
+			where_clause: item.generics.where_clause.clone(),
+			instances,
+			attr_span,
+			view_functions,
+		})
+	}
+}
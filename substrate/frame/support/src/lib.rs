@@ -75,6 +75,7 @@ pub mod storage;
 #[cfg(test)]
 mod tests;
 pub mod traits;
+pub mod view_functions;
 pub mod weights;
 #[doc(hidden)]
 pub mod unsigned {
@@ -973,6 +974,7 @@ pub mod pallet_prelude {
 /// * [`pallet::compact`](#palletcompact-some_arg-some_type)
 /// * [`pallet::call_index($idx)`](#palletcall_indexidx)
 /// * [`pallet::extra_constants`](#extra-constants-palletextra_constants-optional)
+/// * [`pallet::view_functions`](#view-functions-palletview_functions-optional)
 /// * [`pallet::error`](#error-palleterror-optional)
 /// * [`pallet::event`](#event-palletevent-optional)
 /// * [`pallet::generate_deposit($visibility fn
@@ -1153,6 +1155,9 @@ pub mod pallet_prelude {
 /// The macro expands pallet constant metadata with the information given by
 /// `#[pallet::constant]`.
 ///
+/// The associated types which are directly bounded by `TypeInfo` are added into the metadata
+/// of the pallet, along with their documentation.
+///
 /// # `pallet::generate_store($vis trait Store)`
 ///
 /// To generate a `Store` trait associating all storages, annotate your `Pallet` struct with
@@ -1303,6 +1308,35 @@ pub mod pallet_prelude {
 ///
 /// Also see: [`pallet::extra_constants`](`frame_support::pallet_macros::extra_constants`)
 ///
+/// # View functions: `#[pallet::view_functions]` (optional)
+///
+/// Allows you to define some read-only functions which can be executed by external clients
+/// through the [`RuntimeViewFunction`](frame_support::view_functions::runtime_api) runtime
+/// API.
+///
+/// Item must be defined as:
+///
+/// ```ignore
+/// #[pallet::view_functions]
+/// impl<T: Config> Pallet<T> where $optional_where_clause {
+/// 	/// $some_doc
+/// 	pub fn $fn_name($some_arg: $some_type, ...) -> $some_return_type {
+/// 		...
+/// 	}
+/// 	...
+/// }
+/// ```
+/// I.e. a regular rust `impl` block with some optional where clause and public functions with
+/// 0 generics, no receiver and some return type.
+///
+/// ## Macro expansion
+///
+/// The macro implements
+/// [`DispatchViewFunction`](frame_support::view_functions::DispatchViewFunction) for the
+/// pallet and adds the view functions to the pallet metadata.
+///
+/// Also see: [`pallet::view_functions`](`frame_support::pallet_macros::view_functions`)
+///
 /// # Error: `#[pallet::error]` (optional)
 ///
 /// The `#[pallet::error]` attribute allows you to define an error enum that will be returned
@@ -1557,6 +1591,9 @@ pub mod pallet_prelude {
 /// * for a storage double map, the type of the values, and the types of `key1` and `key2` are
 ///   copied into the metadata.
 ///
+/// A `#[deprecated]` attribute on the storage item is copied into the metadata as well. The
+/// same goes for dispatchables of `#[pallet::call]` and variants of `#[pallet::event]`.
+///
 /// # Type value: `#[pallet::type_value]` (optional)
 ///
 /// The `#[pallet::type_value]` attribute lets you define a struct implementing the
@@ -1677,8 +1714,8 @@ pub mod pallet_prelude {
 /// [`ValidateUnsigned`](`pallet_prelude::ValidateUnsigned`) for type `Pallet<T>`, and some
 /// optional where clause.
 ///
-/// NOTE: There is also the [`sp_runtime::traits::SignedExtension`] trait that can be used to
-/// add some specific logic for transaction validation.
+/// NOTE: There is also the [`sp_runtime::traits::TransactionExtension`] trait that can be used
+/// to add some specific logic for transaction validation.
 ///
 /// Also see [`pallet::validate_unsigned`](`frame_support::pallet_macros::validate_unsigned`)
 ///
@@ -2773,6 +2810,43 @@ pub mod pallet_macros {
 	/// Now, this can be executed as follows:
 	#[doc = docify::embed!("src/tests/tasks.rs", tasks_work)]
 	pub use frame_support_procedural::tasks_experimental;
+
+	/// Allows a pallet to declare a set of read-only *view functions*, which can be executed
+	/// by external clients without a bespoke runtime API per pallet.
+	///
+	/// The attribute is attached to an `impl` block of the pallet. All its functions must be
+	/// public, have no receiver and no generics, and return a value. Their arguments and
+	/// return type must implement `Encode`, `Decode` and `TypeInfo`.
+	///
+	/// Each view function is identified by a
+	/// [`ViewFunctionId`](frame_support::view_functions::ViewFunctionId), derived from the
+	/// pallet name and from the signature of the function. The arguments and the return type
+	/// of each view function are exposed in the pallet metadata.
+	///
+	/// All view functions of the runtime are aggregated by
+	/// [`construct_runtime`](frame_support::construct_runtime) into a `RuntimeViewFunction`
+	/// type and can be executed through `Runtime::execute_view_function`, which is meant to
+	/// implement
+	/// the [`RuntimeViewFunction`](frame_support::view_functions::runtime_api::RuntimeViewFunction)
+	/// runtime API.
+	///
+	/// ## Example
+	///
+	/// ```ignore
+	/// #[pallet::view_functions]
+	/// impl<T: Config> Pallet<T> {
+	/// 	/// Get a value.
+	/// 	pub fn get_value() -> Option<u32> {
+	/// 		SomeValue::<T>::get()
+	/// 	}
+	///
+	/// 	/// Get a value from a map.
+	/// 	pub fn get_value_with_arg(key: u32) -> Option<u32> {
+	/// 		SomeMap::<T>::get(key)
+	/// 	}
+	/// }
+	/// ```
+	pub use frame_support_procedural::view_functions;
 }
 
 #[deprecated(note = "Will be removed after July 2023; Use `sp_runtime::traits` directly instead.")]
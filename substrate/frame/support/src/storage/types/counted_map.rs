@@ -31,7 +31,7 @@ use crate::{
 };
 use codec::{Decode, Encode, EncodeLike, FullCodec, MaxEncodedLen, Ref};
 use sp_io::MultiRemovalResults;
-use sp_metadata_ir::StorageEntryMetadataIR;
+use sp_metadata_ir::{ItemDeprecationInfoIR, StorageEntryMetadataIR};
 use sp_runtime::traits::Saturating;
 use sp_std::prelude::*;
 
@@ -508,9 +508,14 @@ where
 	OnEmpty: Get<QueryKind::Query> + 'static,
 	MaxValues: Get<Option<u32>>,
 {
-	fn build_metadata(docs: Vec<&'static str>, entries: &mut Vec<StorageEntryMetadataIR>) {
-		<Self as MapWrapper>::Map::build_metadata(docs, entries);
+	fn build_metadata(
+		deprecation_status: ItemDeprecationInfoIR,
+		docs: Vec<&'static str>,
+		entries: &mut Vec<StorageEntryMetadataIR>,
+	) {
+		<Self as MapWrapper>::Map::build_metadata(deprecation_status.clone(), docs, entries);
 		CounterFor::<Prefix>::build_metadata(
+			deprecation_status,
 			if cfg!(feature = "no-metadata-docs") {
 				vec![]
 			} else {
@@ -1193,7 +1198,7 @@ mod test {
 	fn test_metadata() {
 		type A = CountedStorageMap<Prefix, Twox64Concat, u16, u32, ValueQuery, ADefault>;
 		let mut entries = vec![];
-		A::build_metadata(vec![], &mut entries);
+		A::build_metadata(ItemDeprecationInfoIR::NotDeprecated, vec![], &mut entries);
 		assert_eq!(
 			entries,
 			vec![
@@ -1207,6 +1212,7 @@ mod test {
 					},
 					default: 97u32.encode(),
 					docs: vec![],
+					deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 				},
 				StorageEntryMetadataIR {
 					name: "counter_for_foo",
@@ -1218,6 +1224,7 @@ mod test {
 					} else {
 						vec!["Counter for the related counted storage map"]
 					},
+					deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 				},
 			]
 		);
@@ -29,7 +29,7 @@ use crate::{
 	Never,
 };
 use codec::{Decode, Encode, EncodeLike, FullCodec, MaxEncodedLen, Ref};
-use sp_metadata_ir::StorageEntryMetadataIR;
+use sp_metadata_ir::{ItemDeprecationInfoIR, StorageEntryMetadataIR};
 use sp_runtime::traits::Saturating;
 use sp_std::prelude::*;
 
@@ -632,9 +632,14 @@ where
 	OnEmpty: Get<QueryKind::Query> + 'static,
 	MaxValues: Get<Option<u32>>,
 {
-	fn build_metadata(docs: Vec<&'static str>, entries: &mut Vec<StorageEntryMetadataIR>) {
-		<Self as MapWrapper>::Map::build_metadata(docs, entries);
+	fn build_metadata(
+		deprecation_status: ItemDeprecationInfoIR,
+		docs: Vec<&'static str>,
+		entries: &mut Vec<StorageEntryMetadataIR>,
+	) {
+		<Self as MapWrapper>::Map::build_metadata(deprecation_status.clone(), docs, entries);
 		CounterFor::<Prefix>::build_metadata(
+			deprecation_status,
 			vec![&"Counter for the related counted storage map"],
 			entries,
 		);
@@ -858,8 +863,12 @@ mod test {
 			assert_eq!(A::count(), 2);
 
 			let mut entries = vec![];
-			A::build_metadata(vec![], &mut entries);
-			AValueQueryWithAnOnEmpty::build_metadata(vec![], &mut entries);
+			A::build_metadata(ItemDeprecationInfoIR::NotDeprecated, vec![], &mut entries);
+			AValueQueryWithAnOnEmpty::build_metadata(
+				ItemDeprecationInfoIR::NotDeprecated,
+				vec![],
+				&mut entries,
+			);
 			assert_eq!(
 				entries,
 				vec![
@@ -873,6 +882,7 @@ mod test {
 						},
 						default: Option::<u32>::None.encode(),
 						docs: vec![],
+						deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 					},
 					StorageEntryMetadataIR {
 						name: "Foo",
@@ -884,6 +894,7 @@ mod test {
 						} else {
 							vec!["Counter for the related counted storage map"]
 						},
+						deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 					},
 					StorageEntryMetadataIR {
 						name: "Foo",
@@ -895,6 +906,7 @@ mod test {
 						},
 						default: 98u32.encode(),
 						docs: vec![],
+						deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 					},
 					StorageEntryMetadataIR {
 						name: "Foo",
@@ -906,6 +918,7 @@ mod test {
 						} else {
 							vec!["Counter for the related counted storage map"]
 						},
+						deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 					},
 				]
 			);
@@ -1107,8 +1120,12 @@ mod test {
 			assert_eq!(A::count(), 2);
 
 			let mut entries = vec![];
-			A::build_metadata(vec![], &mut entries);
-			AValueQueryWithAnOnEmpty::build_metadata(vec![], &mut entries);
+			A::build_metadata(ItemDeprecationInfoIR::NotDeprecated, vec![], &mut entries);
+			AValueQueryWithAnOnEmpty::build_metadata(
+				ItemDeprecationInfoIR::NotDeprecated,
+				vec![],
+				&mut entries,
+			);
 			assert_eq!(
 				entries,
 				vec![
@@ -1125,6 +1142,7 @@ mod test {
 						},
 						default: Option::<u32>::None.encode(),
 						docs: vec![],
+						deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 					},
 					StorageEntryMetadataIR {
 						name: "Foo",
@@ -1136,6 +1154,7 @@ mod test {
 						} else {
 							vec!["Counter for the related counted storage map"]
 						},
+						deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 					},
 					StorageEntryMetadataIR {
 						name: "Foo",
@@ -1150,6 +1169,7 @@ mod test {
 						},
 						default: 98u32.encode(),
 						docs: vec![],
+						deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 					},
 					StorageEntryMetadataIR {
 						name: "Foo",
@@ -1161,6 +1181,7 @@ mod test {
 						} else {
 							vec!["Counter for the related counted storage map"]
 						},
+						deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 					},
 				]
 			);
@@ -1393,8 +1414,12 @@ mod test {
 			assert_eq!(A::count(), 2);
 
 			let mut entries = vec![];
-			A::build_metadata(vec![], &mut entries);
-			AValueQueryWithAnOnEmpty::build_metadata(vec![], &mut entries);
+			A::build_metadata(ItemDeprecationInfoIR::NotDeprecated, vec![], &mut entries);
+			AValueQueryWithAnOnEmpty::build_metadata(
+				ItemDeprecationInfoIR::NotDeprecated,
+				vec![],
+				&mut entries,
+			);
 			assert_eq!(
 				entries,
 				vec![
@@ -1412,6 +1437,7 @@ mod test {
 						},
 						default: Option::<u32>::None.encode(),
 						docs: vec![],
+						deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 					},
 					StorageEntryMetadataIR {
 						name: "Foo",
@@ -1423,6 +1449,7 @@ mod test {
 						} else {
 							vec!["Counter for the related counted storage map"]
 						},
+						deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 					},
 					StorageEntryMetadataIR {
 						name: "Foo",
@@ -1438,6 +1465,7 @@ mod test {
 						},
 						default: 98u32.encode(),
 						docs: vec![],
+						deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 					},
 					StorageEntryMetadataIR {
 						name: "Foo",
@@ -1449,6 +1477,7 @@ mod test {
 						} else {
 							vec!["Counter for the related counted storage map"]
 						},
+						deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 					},
 				]
 			);
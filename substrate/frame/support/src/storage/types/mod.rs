@@ -19,7 +19,7 @@
 //! StorageMap and others.
 
 use codec::FullCodec;
-use sp_metadata_ir::{StorageEntryMetadataIR, StorageEntryModifierIR};
+use sp_metadata_ir::{ItemDeprecationInfoIR, StorageEntryMetadataIR, StorageEntryModifierIR};
 use sp_std::prelude::*;
 
 mod counted_map;
@@ -140,8 +140,13 @@ where
 ///
 /// Implemented by each of the storage types: value, map, countedmap, doublemap and nmap.
 pub trait StorageEntryMetadataBuilder {
-	/// Build into `entries` the storage metadata entries of a storage given some `docs`.
-	fn build_metadata(doc: Vec<&'static str>, entries: &mut Vec<StorageEntryMetadataIR>);
+	/// Build into `entries` the storage metadata entries of a storage given some `docs` and its
+	/// `deprecation_status`.
+	fn build_metadata(
+		deprecation_status: ItemDeprecationInfoIR,
+		doc: Vec<&'static str>,
+		entries: &mut Vec<StorageEntryMetadataIR>,
+	);
 }
 
 #[cfg(test)]
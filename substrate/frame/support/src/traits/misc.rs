@@ -925,7 +925,7 @@ where
 	Address: TypeInfo,
 	Call: TypeInfo,
 	Signature: TypeInfo,
-	Extra: sp_runtime::traits::SignedExtension + TypeInfo,
+	Extra: sp_runtime::traits::TransactionExtensionBase + TypeInfo,
 {
 	fn call(&self) -> &Self::Call {
 		&self.function
@@ -18,10 +18,13 @@
 //! Traits and associated data structures concerned with voting, and moving between tokens and
 //! votes.
 
-use crate::dispatch::Parameter;
+use crate::{dispatch::Parameter, weights::Weight};
 use codec::{HasCompact, MaxEncodedLen};
 use sp_arithmetic::Perbill;
-use sp_runtime::{traits::Member, DispatchError};
+use sp_runtime::{
+	traits::{Member, Saturating, Zero},
+	DispatchError,
+};
 use sp_std::prelude::*;
 
 pub trait VoteTally<Votes, Class> {
@@ -126,3 +129,52 @@ pub trait Polling<Tally> {
 		(Self::classes().into_iter().next().expect("Always one class"), u32::max_value())
 	}
 }
+
+/// Provides the voting power of an account that is not part of the balance of the account
+/// itself.
+///
+/// For example, the stake of a nomination pool member is kept in the account of the pool, but it
+/// is owned by the member and can back their votes.
+///
+/// Since the voting pallet cannot lock these funds itself, the provider must lock them on its
+/// behalf, see [`Self::set_lock`].
+pub trait VotingPowerProvider<AccountId, Balance> {
+	/// The balance of `who` that is held outside of their account and can be used to vote.
+	fn voting_power(who: &AccountId) -> Balance;
+
+	/// Lock `amount` of the voting power of `who`, replacing any previous lock.
+	///
+	/// Until the lock is changed, `who` must not be able to reduce their voting power below the
+	/// locked amount, other than by being slashed. A zero `amount` removes the lock.
+	fn set_lock(who: &AccountId, amount: Balance);
+
+	/// The weight of a call to [`Self::voting_power`] followed by a call to [`Self::set_lock`].
+	fn weight() -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+impl<AccountId, Balance: Saturating + Zero + Ord + Copy> VotingPowerProvider<AccountId, Balance>
+	for Tuple
+{
+	fn voting_power(who: &AccountId) -> Balance {
+		let mut power = Balance::zero();
+		for_tuples!( #( power = power.saturating_add(Tuple::voting_power(who)); )* );
+		power
+	}
+
+	/// The lock is spread over the providers in order, each locking up to its voting power.
+	fn set_lock(who: &AccountId, amount: Balance) {
+		let mut remaining = amount;
+		for_tuples!( #(
+			let lock = remaining.min(Tuple::voting_power(who));
+			Tuple::set_lock(who, lock);
+			remaining = remaining.saturating_sub(lock);
+		)* );
+	}
+
+	fn weight() -> Weight {
+		let mut weight = Weight::zero();
+		for_tuples!( #( weight = weight.saturating_add(Tuple::weight()); )* );
+		weight
+	}
+}
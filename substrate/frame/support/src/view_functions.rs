@@ -0,0 +1,115 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traits and types for pallet view functions.
+//!
+//! View functions are read-only functions declared in a `#[pallet::view_functions]` section.
+//! They are executed by query id through the generic [`runtime_api::RuntimeViewFunction`]
+//! runtime API, instead of a bespoke runtime API per pallet.
+
+use codec::{Decode, Encode, Output};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// The unique identifier of a view function.
+///
+/// The `prefix` identifies the pallet, the `suffix` identifies the view function within the
+/// pallet.
+#[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo, PartialEq, Eq)]
+pub struct ViewFunctionId {
+	/// The part of the id for dispatching view functions from the top level of the runtime.
+	///
+	/// Derived from the pallet name.
+	pub prefix: [u8; 16],
+	/// The part of the id for dispatching to a view function within a pallet.
+	///
+	/// Derived from the signature of the view function.
+	pub suffix: [u8; 16],
+}
+
+impl From<ViewFunctionId> for [u8; 32] {
+	fn from(value: ViewFunctionId) -> Self {
+		let mut output = [0u8; 32];
+		output[..16].copy_from_slice(&value.prefix);
+		output[16..].copy_from_slice(&value.suffix);
+		output
+	}
+}
+
+/// Error returned when dispatching a view function.
+#[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo, PartialEq, Eq)]
+pub enum ViewFunctionDispatchError {
+	/// View functions are not implemented for this runtime.
+	NotImplemented,
+	/// A view function with the given id was not found.
+	NotFound(ViewFunctionId),
+	/// Failed to decode the view function input or to encode its output.
+	Codec,
+}
+
+impl From<codec::Error> for ViewFunctionDispatchError {
+	fn from(_: codec::Error) -> Self {
+		ViewFunctionDispatchError::Codec
+	}
+}
+
+/// Implemented by pallets to provide the [`ViewFunctionId::prefix`] of their view functions.
+pub trait ViewFunctionIdPrefix {
+	/// The prefix shared by all view functions of the pallet.
+	fn prefix() -> [u8; 16];
+}
+
+/// Dispatch a view function by its id.
+///
+/// Implemented by pallets for their own view functions and by the runtime for the view functions
+/// of all its pallets.
+pub trait DispatchViewFunction {
+	/// Decode the arguments of the view function `id` from `input`, execute it and encode its
+	/// result into `output`.
+	fn dispatch_view_function<O: Output>(
+		id: &ViewFunctionId,
+		input: &mut &[u8],
+		output: &mut O,
+	) -> Result<(), ViewFunctionDispatchError>;
+}
+
+impl DispatchViewFunction for () {
+	fn dispatch_view_function<O: Output>(
+		id: &ViewFunctionId,
+		_input: &mut &[u8],
+		_output: &mut O,
+	) -> Result<(), ViewFunctionDispatchError> {
+		Err(ViewFunctionDispatchError::NotFound(id.clone()))
+	}
+}
+
+/// The runtime API for executing view functions.
+pub mod runtime_api {
+	use super::*;
+
+	sp_api::decl_runtime_apis! {
+		/// Runtime API for executing view functions.
+		pub trait RuntimeViewFunction {
+			/// Execute the view function with the given `query_id` and the SCALE encoded
+			/// arguments in `input`, returning the SCALE encoded result.
+			fn execute_view_function(
+				query_id: ViewFunctionId,
+				input: sp_std::vec::Vec<u8>,
+			) -> Result<sp_std::vec::Vec<u8>, ViewFunctionDispatchError>;
+		}
+	}
+}
@@ -25,8 +25,8 @@ use frame_support::{
 use frame_system::pallet_prelude::BlockNumberFor;
 use sp_core::sr25519;
 use sp_metadata_ir::{
-	PalletStorageMetadataIR, StorageEntryMetadataIR, StorageEntryModifierIR, StorageEntryTypeIR,
-	StorageHasherIR,
+	ItemDeprecationInfoIR, PalletStorageMetadataIR, StorageEntryMetadataIR, StorageEntryModifierIR,
+	StorageEntryTypeIR, StorageHasherIR,
 };
 use sp_runtime::{
 	generic,
@@ -442,6 +442,7 @@ fn expected_metadata() -> PalletStorageMetadataIR {
 				ty: StorageEntryTypeIR::Plain(scale_info::meta_type::<u32>()),
 				default: vec![0, 0, 0, 0],
 				docs: vec![],
+				deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 			},
 			StorageEntryMetadataIR {
 				name: "Map",
@@ -453,6 +454,7 @@ fn expected_metadata() -> PalletStorageMetadataIR {
 				},
 				default: [0u8; 8].to_vec(),
 				docs: vec![],
+				deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 			},
 			StorageEntryMetadataIR {
 				name: "DoubleMap",
@@ -464,6 +466,7 @@ fn expected_metadata() -> PalletStorageMetadataIR {
 				},
 				default: [0u8; 8].to_vec(),
 				docs: vec![],
+				deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
 			},
 		],
 	}
@@ -0,0 +1,219 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the pallet information which is only exposed by the metadata V16: deprecation info
+//! and associated types.
+
+#![allow(deprecated)]
+
+use codec::Decode;
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use frame_support::derive_impl;
+use scale_info::meta_type;
+use sp_metadata_ir::{
+	EnumDeprecationInfoIR, ItemDeprecationInfoIR, PalletAssociatedTypeMetadataIR, PalletMetadataIR,
+	VariantDeprecationInfoIR,
+};
+use sp_runtime::traits::{BlakeTwo256, ExtrinsicMetadata};
+use std::collections::BTreeMap;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Some associated type.
+		type SomeType: TypeInfo;
+
+		/// Not part of the metadata, as it is not bounded by `TypeInfo`.
+		type OtherType: Parameter;
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::call_index(0)]
+		#[pallet::weight(Weight::zero())]
+		pub fn not_deprecated(_origin: OriginFor<T>) -> DispatchResult {
+			Ok(())
+		}
+
+		#[pallet::call_index(3)]
+		#[pallet::weight(Weight::zero())]
+		#[deprecated = "use `not_deprecated` instead"]
+		pub fn deprecated(_origin: OriginFor<T>) -> DispatchResult {
+			Ok(())
+		}
+	}
+
+	#[pallet::event]
+	pub enum Event {
+		NotDeprecated,
+		#[deprecated]
+		DeprecatedWithoutNote,
+		#[deprecated(note = "not emitted anymore", since = "1.0.0")]
+		#[codec(index = 5)]
+		Deprecated,
+	}
+
+	#[pallet::storage]
+	pub type NotDeprecated<T> = StorageValue<_, u32>;
+
+	#[pallet::storage]
+	#[deprecated]
+	pub type DeprecatedWithoutNote<T> = StorageValue<_, u32>;
+
+	#[pallet::storage]
+	#[deprecated(note = "use `NotDeprecated` instead", since = "1.0.0")]
+	pub type Deprecated<T> = StorageMap<_, Blake2_128Concat, u32, u32>;
+}
+
+pub type Header = sp_runtime::generic::Header<u64, BlakeTwo256>;
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, RuntimeCall, (), ()>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Example: pallet,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type Block = Block;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type PalletInfo = PalletInfo;
+	type OnSetCode = ();
+}
+
+impl pallet::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type SomeType = u64;
+	type OtherType = u32;
+}
+
+fn maybe_docs(doc: Vec<&'static str>) -> Vec<&'static str> {
+	if cfg!(feature = "no-metadata-docs") {
+		vec![]
+	} else {
+		doc
+	}
+}
+
+fn example_pallet_metadata() -> PalletMetadataIR {
+	Runtime::metadata_ir()
+		.pallets
+		.into_iter()
+		.find(|pallet| pallet.name == "Example")
+		.expect("Pallet should be present")
+}
+
+#[test]
+fn storage_deprecation_info() {
+	let storage = example_pallet_metadata().storage.expect("Pallet has storage");
+	let deprecation_info = storage
+		.entries
+		.into_iter()
+		.map(|entry| (entry.name, entry.deprecation_info))
+		.collect::<Vec<_>>();
+
+	assert_eq!(
+		deprecation_info,
+		vec![
+			("NotDeprecated", ItemDeprecationInfoIR::NotDeprecated),
+			("DeprecatedWithoutNote", ItemDeprecationInfoIR::DeprecatedWithoutNote),
+			(
+				"Deprecated",
+				ItemDeprecationInfoIR::Deprecated {
+					note: "use `NotDeprecated` instead",
+					since: Some("1.0.0"),
+				}
+			),
+		]
+	);
+}
+
+#[test]
+fn call_deprecation_info() {
+	let calls = example_pallet_metadata().calls.expect("Pallet has calls");
+
+	assert_eq!(
+		calls.deprecation_info,
+		EnumDeprecationInfoIR(BTreeMap::from([(
+			3,
+			VariantDeprecationInfoIR::Deprecated {
+				note: "use `not_deprecated` instead",
+				since: None
+			}
+		)]))
+	);
+}
+
+#[test]
+fn event_deprecation_info() {
+	let event = example_pallet_metadata().event.expect("Pallet has events");
+
+	assert_eq!(
+		event.deprecation_info,
+		EnumDeprecationInfoIR(BTreeMap::from([
+			(1, VariantDeprecationInfoIR::DeprecatedWithoutNote),
+			(
+				5,
+				VariantDeprecationInfoIR::Deprecated {
+					note: "not emitted anymore",
+					since: Some("1.0.0")
+				}
+			),
+		]))
+	);
+}
+
+#[test]
+fn associated_types_metadata() {
+	assert_eq!(
+		example_pallet_metadata().associated_types,
+		vec![PalletAssociatedTypeMetadataIR {
+			name: "SomeType",
+			ty: meta_type::<u64>(),
+			docs: maybe_docs(vec![" Some associated type."]),
+		}]
+	);
+}
+
+#[test]
+fn metadata_at_unstable_version() {
+	let bytes =
+		Runtime::metadata_at_version(u32::MAX).expect("Unstable metadata must be present; qed");
+	let metadata: RuntimeMetadataPrefixed =
+		Decode::decode(&mut &bytes[..]).expect("Metadata encoded properly; qed");
+
+	let RuntimeMetadata::V16(metadata) = metadata.1 else { panic!("Metadata V16 expected") };
+	assert_eq!(
+		metadata.extrinsic.versions,
+		<UncheckedExtrinsic as ExtrinsicMetadata>::VERSIONS.to_vec()
+	);
+}
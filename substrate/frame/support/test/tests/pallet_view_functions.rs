@@ -0,0 +1,299 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for `#[pallet::view_functions]`.
+
+#![allow(deprecated)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	derive_impl,
+	traits::PalletInfoAccess,
+	view_functions::{ViewFunctionDispatchError, ViewFunctionId, ViewFunctionIdPrefix},
+};
+use scale_info::meta_type;
+use sp_io::TestExternalities;
+use sp_metadata_ir::{
+	ItemDeprecationInfoIR, PalletViewFunctionMetadataIR, PalletViewFunctionParamMetadataIR,
+};
+use sp_runtime::traits::BlakeTwo256;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	#[pallet::storage]
+	pub type SomeValue<T: Config> = StorageValue<_, u32>;
+
+	#[pallet::storage]
+	pub type SomeMap<T: Config> = StorageMap<_, Twox64Concat, u32, u32, OptionQuery>;
+
+	#[pallet::view_functions]
+	impl<T: Config> Pallet<T> {
+		/// Get a value.
+		pub fn get_value() -> Option<u32> {
+			SomeValue::<T>::get()
+		}
+
+		/// Get a value from a map.
+		pub fn get_value_with_arg(key: u32) -> Option<u32> {
+			SomeMap::<T>::get(key)
+		}
+
+		/// Add two values.
+		#[deprecated = "use `get_value` instead"]
+		pub fn add(a: u32, b: u32) -> u64 {
+			a as u64 + b as u64
+		}
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet2 {
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {}
+
+	#[pallet::storage]
+	pub type SomeValue<T: Config<I>, I: 'static = ()> = StorageValue<_, u32>;
+
+	#[pallet::view_functions]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Get a value.
+		pub fn get_value() -> Option<u32> {
+			SomeValue::<T, I>::get()
+		}
+	}
+}
+
+pub type Header = sp_runtime::generic::Header<u64, BlakeTwo256>;
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, RuntimeCall, (), ()>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		ViewFunctionsExample: pallet,
+		ViewFunctionsInstance: pallet2,
+		ViewFunctionsInstance1: pallet2::<Instance1>,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type Block = Block;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type PalletInfo = PalletInfo;
+	type OnSetCode = ();
+}
+
+impl pallet::Config for Runtime {}
+impl pallet2::Config for Runtime {}
+impl pallet2::Config<pallet2::Instance1> for Runtime {}
+
+fn maybe_docs(doc: Vec<&'static str>) -> Vec<&'static str> {
+	if cfg!(feature = "no-metadata-docs") {
+		vec![]
+	} else {
+		doc
+	}
+}
+
+/// The id of the view function `name` of `pallet`, as exposed in the metadata.
+fn view_function_id(pallet: &str, name: &str) -> ViewFunctionId {
+	let id = Runtime::metadata_ir()
+		.pallets
+		.into_iter()
+		.find(|p| p.name == pallet)
+		.expect("Pallet should be present")
+		.view_functions
+		.into_iter()
+		.find(|view_fn| view_fn.name == name)
+		.expect("View function should be present")
+		.id;
+
+	ViewFunctionId {
+		prefix: id[..16].try_into().expect("16 bytes; qed"),
+		suffix: id[16..].try_into().expect("16 bytes; qed"),
+	}
+}
+
+fn execute<R: Decode>(
+	id: ViewFunctionId,
+	input: impl Encode,
+) -> Result<R, ViewFunctionDispatchError> {
+	Runtime::execute_view_function(id, input.encode())
+		.map(|output| R::decode(&mut &output[..]).expect("Output must be decodable; qed"))
+}
+
+#[test]
+fn pallet_view_functions_work() {
+	TestExternalities::default().execute_with(|| {
+		pallet::SomeValue::<Runtime>::set(Some(42));
+		pallet::SomeMap::<Runtime>::insert(1, 2);
+
+		let get_value = view_function_id("ViewFunctionsExample", "get_value");
+		assert_eq!(execute::<Option<u32>>(get_value, ()), Ok(Some(42)));
+
+		let get_value_with_arg = view_function_id("ViewFunctionsExample", "get_value_with_arg");
+		assert_eq!(execute::<Option<u32>>(get_value_with_arg.clone(), 1u32), Ok(Some(2)));
+		assert_eq!(execute::<Option<u32>>(get_value_with_arg, 2u32), Ok(None));
+
+		let add = view_function_id("ViewFunctionsExample", "add");
+		assert_eq!(execute::<u64>(add, (u32::MAX, 1u32)), Ok(u32::MAX as u64 + 1));
+	});
+}
+
+#[test]
+fn pallet_instance_view_functions_work() {
+	TestExternalities::default().execute_with(|| {
+		pallet2::SomeValue::<Runtime>::set(Some(1));
+		pallet2::SomeValue::<Runtime, pallet2::Instance1>::set(Some(2));
+
+		let get_value = view_function_id("ViewFunctionsInstance", "get_value");
+		assert_eq!(execute::<Option<u32>>(get_value, ()), Ok(Some(1)));
+
+		let get_value = view_function_id("ViewFunctionsInstance1", "get_value");
+		assert_eq!(execute::<Option<u32>>(get_value, ()), Ok(Some(2)));
+	});
+}
+
+#[test]
+fn view_function_id_prefix_is_the_pallet_name_hash() {
+	TestExternalities::default().execute_with(|| {
+		assert_eq!(
+			<ViewFunctionsExample as ViewFunctionIdPrefix>::prefix(),
+			<ViewFunctionsExample as PalletInfoAccess>::name_hash(),
+		);
+		assert_eq!(view_function_id("ViewFunctionsExample", "get_value").prefix, {
+			<ViewFunctionsExample as PalletInfoAccess>::name_hash()
+		});
+		assert_ne!(
+			view_function_id("ViewFunctionsInstance", "get_value").prefix,
+			view_function_id("ViewFunctionsInstance1", "get_value").prefix,
+		);
+	});
+}
+
+#[test]
+fn unknown_view_function_is_not_found() {
+	TestExternalities::default().execute_with(|| {
+		let unknown_suffix = ViewFunctionId {
+			prefix: <ViewFunctionsExample as ViewFunctionIdPrefix>::prefix(),
+			suffix: [0; 16],
+		};
+		assert_eq!(
+			execute::<()>(unknown_suffix.clone(), ()),
+			Err(ViewFunctionDispatchError::NotFound(unknown_suffix))
+		);
+
+		let unknown_prefix = ViewFunctionId {
+			prefix: [0; 16],
+			suffix: view_function_id("ViewFunctionsExample", "get_value").suffix,
+		};
+		assert_eq!(
+			execute::<()>(unknown_prefix.clone(), ()),
+			Err(ViewFunctionDispatchError::NotFound(unknown_prefix))
+		);
+	});
+}
+
+#[test]
+fn invalid_input_is_a_codec_error() {
+	TestExternalities::default().execute_with(|| {
+		let get_value_with_arg = view_function_id("ViewFunctionsExample", "get_value_with_arg");
+		assert_eq!(
+			execute::<Option<u32>>(get_value_with_arg.clone(), 1u8),
+			Err(ViewFunctionDispatchError::Codec)
+		);
+		// Trailing bytes are rejected as well.
+		assert_eq!(
+			execute::<Option<u32>>(get_value_with_arg, (1u32, 1u8)),
+			Err(ViewFunctionDispatchError::Codec)
+		);
+	});
+}
+
+#[test]
+fn metadata_ir_definitions() {
+	let metadata = Runtime::metadata_ir();
+	let view_functions = |pallet: &str| {
+		metadata
+			.pallets
+			.iter()
+			.find(|p| p.name == pallet)
+			.expect("Pallet should be present")
+			.view_functions
+			.clone()
+	};
+	let id = |pallet: &str, name: &str| -> [u8; 32] { view_function_id(pallet, name).into() };
+
+	assert_eq!(
+		view_functions("ViewFunctionsExample"),
+		vec![
+			PalletViewFunctionMetadataIR {
+				name: "get_value",
+				id: id("ViewFunctionsExample", "get_value"),
+				inputs: vec![],
+				output: meta_type::<Option<u32>>(),
+				docs: maybe_docs(vec![" Get a value."]),
+				deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
+			},
+			PalletViewFunctionMetadataIR {
+				name: "get_value_with_arg",
+				id: id("ViewFunctionsExample", "get_value_with_arg"),
+				inputs: vec![PalletViewFunctionParamMetadataIR {
+					name: "key",
+					ty: meta_type::<u32>(),
+				}],
+				output: meta_type::<Option<u32>>(),
+				docs: maybe_docs(vec![" Get a value from a map."]),
+				deprecation_info: ItemDeprecationInfoIR::NotDeprecated,
+			},
+			PalletViewFunctionMetadataIR {
+				name: "add",
+				id: id("ViewFunctionsExample", "add"),
+				inputs: vec![
+					PalletViewFunctionParamMetadataIR { name: "a", ty: meta_type::<u32>() },
+					PalletViewFunctionParamMetadataIR { name: "b", ty: meta_type::<u32>() },
+				],
+				output: meta_type::<u64>(),
+				docs: maybe_docs(vec![" Add two values."]),
+				deprecation_info: ItemDeprecationInfoIR::Deprecated {
+					note: "use `get_value` instead",
+					since: None,
+				},
+			},
+		]
+	);
+
+	assert!(view_functions("System").is_empty());
+	assert_eq!(view_functions("ViewFunctionsInstance1").len(), 1);
+}
@@ -175,6 +175,7 @@ fn runtime_metadata() {
 				"",
 				" Documentation on multiline.",
 			]),
+			version: 1u32.into(),
 		},
 		RuntimeApiMetadataIR {
 			name: "Core",
@@ -207,6 +208,7 @@ fn runtime_metadata() {
 			docs: maybe_docs(vec![
 				" The `Core` runtime api that every Substrate runtime needs to implement.",
 			]),
+			version: 4u32.into(),
 		},
 	];
 
@@ -20,10 +20,12 @@ use codec::{Decode, Encode};
 use frame_support::dispatch::DispatchInfo;
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{DispatchInfoOf, Dispatchable, One, SignedExtension, Zero},
+	traits::{
+		AsSystemOriginSigner, DispatchInfoOf, DispatchOriginOf, Dispatchable, One,
+		TransactionExtension, TransactionExtensionBase, ValidateResult, Zero,
+	},
 	transaction_validity::{
-		InvalidTransaction, TransactionLongevity, TransactionValidity, TransactionValidityError,
-		ValidTransaction,
+		InvalidTransaction, TransactionLongevity, TransactionValidityError, ValidTransaction,
 	},
 };
 use sp_std::vec;
@@ -33,8 +35,10 @@ use sp_std::vec;
 /// # Transaction Validity
 ///
 /// This extension affects `requires` and `provides` tags of validity, but DOES NOT
-/// set the `priority` field. Make sure that AT LEAST one of the signed extension sets
+/// set the `priority` field. Make sure that AT LEAST one of the transaction extension sets
 /// some kind of priority upon validating transactions.
+///
+/// Transactions which do not have a signed origin when reaching this extension are not checked.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 pub struct CheckNonce<T: Config>(#[codec(compact)] pub T::Nonce);
@@ -58,59 +62,41 @@ impl<T: Config> sp_std::fmt::Debug for CheckNonce<T> {
 	}
 }
 
-impl<T: Config> SignedExtension for CheckNonce<T>
-where
-	T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
-{
-	type AccountId = T::AccountId;
-	type Call = T::RuntimeCall;
-	type AdditionalSigned = ();
-	type Pre = ();
+impl<T: Config> TransactionExtensionBase for CheckNonce<T> {
 	const IDENTIFIER: &'static str = "CheckNonce";
+	type Implicit = ();
 
-	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
+	fn implicit(&self) -> sp_std::result::Result<(), TransactionValidityError> {
 		Ok(())
 	}
+}
 
-	fn pre_dispatch(
-		self,
-		who: &Self::AccountId,
-		_call: &Self::Call,
-		_info: &DispatchInfoOf<Self::Call>,
-		_len: usize,
-	) -> Result<(), TransactionValidityError> {
-		let mut account = crate::Account::<T>::get(who);
-		if account.providers.is_zero() && account.sufficients.is_zero() {
-			// Nonce storage not paid for
-			return Err(InvalidTransaction::Payment.into())
-		}
-		if self.0 != account.nonce {
-			return Err(if self.0 < account.nonce {
-				InvalidTransaction::Stale
-			} else {
-				InvalidTransaction::Future
-			}
-			.into())
-		}
-		account.nonce += T::Nonce::one();
-		crate::Account::<T>::insert(who, account);
-		Ok(())
-	}
+impl<T: Config> TransactionExtension<T::RuntimeCall> for CheckNonce<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
+	T::RuntimeOrigin: AsSystemOriginSigner<T::AccountId>,
+{
+	type Val = Option<T::AccountId>;
+	type Pre = ();
 
 	fn validate(
 		&self,
-		who: &Self::AccountId,
-		_call: &Self::Call,
-		_info: &DispatchInfoOf<Self::Call>,
+		origin: DispatchOriginOf<T::RuntimeCall>,
+		_call: &T::RuntimeCall,
+		_info: &DispatchInfoOf<T::RuntimeCall>,
 		_len: usize,
-	) -> TransactionValidity {
+		_inherited_implication: &impl Encode,
+	) -> ValidateResult<Self::Val, T::RuntimeCall> {
+		let Some(who) = origin.as_system_origin_signer() else {
+			return Ok((Default::default(), None, origin))
+		};
 		let account = crate::Account::<T>::get(who);
 		if account.providers.is_zero() && account.sufficients.is_zero() {
 			// Nonce storage not paid for
-			return InvalidTransaction::Payment.into()
+			return Err(InvalidTransaction::Payment.into())
 		}
 		if self.0 < account.nonce {
-			return InvalidTransaction::Stale.into()
+			return Err(InvalidTransaction::Stale.into())
 		}
 
 		let provides = vec![Encode::encode(&(who, self.0))];
@@ -120,13 +106,41 @@ where
 			vec![]
 		};
 
-		Ok(ValidTransaction {
+		let validity = ValidTransaction {
 			priority: 0,
 			requires,
 			provides,
 			longevity: TransactionLongevity::max_value(),
 			propagate: true,
-		})
+		};
+		Ok((validity, Some(who.clone()), origin))
+	}
+
+	fn prepare(
+		self,
+		val: Self::Val,
+		_origin: &DispatchOriginOf<T::RuntimeCall>,
+		_call: &T::RuntimeCall,
+		_info: &DispatchInfoOf<T::RuntimeCall>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		let Some(who) = val else { return Ok(()) };
+		let mut account = crate::Account::<T>::get(&who);
+		if account.providers.is_zero() && account.sufficients.is_zero() {
+			// Nonce storage not paid for
+			return Err(InvalidTransaction::Payment.into())
+		}
+		if self.0 != account.nonce {
+			return Err(if self.0 < account.nonce {
+				InvalidTransaction::Stale
+			} else {
+				InvalidTransaction::Future
+			}
+			.into())
+		}
+		account.nonce += T::Nonce::one();
+		crate::Account::<T>::insert(&who, account);
+		Ok(())
 	}
 }
 
@@ -135,6 +149,7 @@ mod tests {
 	use super::*;
 	use crate::mock::{new_test_ext, Test, CALL};
 	use frame_support::{assert_noop, assert_ok};
+	use sp_runtime::traits::DispatchTransaction;
 
 	#[test]
 	fn signed_ext_check_nonce_works() {
@@ -153,20 +168,31 @@ mod tests {
 			let len = 0_usize;
 			// stale
 			assert_noop!(
-				CheckNonce::<Test>(0).validate(&1, CALL, &info, len),
+				CheckNonce::<Test>(0)
+					.validate_only(Some(1).into(), CALL, &info, len)
+					.map(|_| ()),
 				InvalidTransaction::Stale
 			);
 			assert_noop!(
-				CheckNonce::<Test>(0).pre_dispatch(&1, CALL, &info, len),
+				CheckNonce::<Test>(0)
+					.validate_and_prepare(Some(1).into(), CALL, &info, len)
+					.map(|_| ()),
 				InvalidTransaction::Stale
 			);
 			// correct
-			assert_ok!(CheckNonce::<Test>(1).validate(&1, CALL, &info, len));
-			assert_ok!(CheckNonce::<Test>(1).pre_dispatch(&1, CALL, &info, len));
+			assert_ok!(CheckNonce::<Test>(1).validate_only(Some(1).into(), CALL, &info, len));
+			assert_ok!(CheckNonce::<Test>(1).validate_and_prepare(
+				Some(1).into(),
+				CALL,
+				&info,
+				len
+			));
 			// future
-			assert_ok!(CheckNonce::<Test>(5).validate(&1, CALL, &info, len));
+			assert_ok!(CheckNonce::<Test>(5).validate_only(Some(1).into(), CALL, &info, len));
 			assert_noop!(
-				CheckNonce::<Test>(5).pre_dispatch(&1, CALL, &info, len),
+				CheckNonce::<Test>(5)
+					.validate_and_prepare(Some(1).into(), CALL, &info, len)
+					.map(|_| ()),
 				InvalidTransaction::Future
 			);
 		})
@@ -199,19 +225,49 @@ mod tests {
 			let len = 0_usize;
 			// Both providers and sufficients zero
 			assert_noop!(
-				CheckNonce::<Test>(1).validate(&1, CALL, &info, len),
+				CheckNonce::<Test>(1)
+					.validate_only(Some(1).into(), CALL, &info, len)
+					.map(|_| ()),
 				InvalidTransaction::Payment
 			);
 			assert_noop!(
-				CheckNonce::<Test>(1).pre_dispatch(&1, CALL, &info, len),
+				CheckNonce::<Test>(1)
+					.validate_and_prepare(Some(1).into(), CALL, &info, len)
+					.map(|_| ()),
 				InvalidTransaction::Payment
 			);
 			// Non-zero providers
-			assert_ok!(CheckNonce::<Test>(1).validate(&2, CALL, &info, len));
-			assert_ok!(CheckNonce::<Test>(1).pre_dispatch(&2, CALL, &info, len));
+			assert_ok!(CheckNonce::<Test>(1).validate_only(Some(2).into(), CALL, &info, len));
+			assert_ok!(CheckNonce::<Test>(1).validate_and_prepare(
+				Some(2).into(),
+				CALL,
+				&info,
+				len
+			));
 			// Non-zero sufficients
-			assert_ok!(CheckNonce::<Test>(1).validate(&3, CALL, &info, len));
-			assert_ok!(CheckNonce::<Test>(1).pre_dispatch(&3, CALL, &info, len));
+			assert_ok!(CheckNonce::<Test>(1).validate_only(Some(3).into(), CALL, &info, len));
+			assert_ok!(CheckNonce::<Test>(1).validate_and_prepare(
+				Some(3).into(),
+				CALL,
+				&info,
+				len
+			));
+		})
+	}
+
+	#[test]
+	fn unsigned_check_nonce_works() {
+		new_test_ext().execute_with(|| {
+			let info = DispatchInfo::default();
+			let len = 0_usize;
+			// Non-signed origins are not checked, nor is any nonce incremented.
+			assert_ok!(CheckNonce::<Test>(0).validate_and_prepare(
+				crate::RawOrigin::Root.into(),
+				CALL,
+				&info,
+				len
+			));
+			assert_eq!(crate::Account::<Test>::get(0).nonce, 0);
 		})
 	}
 }
@@ -23,7 +23,10 @@ use frame_support::{
 };
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension},
+	traits::{
+		DispatchInfoOf, DispatchOriginOf, Dispatchable, PostDispatchInfoOf, TransactionExtension,
+		TransactionExtensionBase, ValidateResult,
+	},
 	transaction_validity::{InvalidTransaction, TransactionValidity, TransactionValidityError},
 	DispatchResult,
 };
@@ -100,7 +103,7 @@ where
 		}
 	}
 
-	/// Creates new `SignedExtension` to check weight of the extrinsic.
+	/// Creates new `TransactionExtension` to check weight of the extrinsic.
 	pub fn new() -> Self {
 		Self(Default::default())
 	}
@@ -134,6 +137,35 @@ where
 
 		Ok(Default::default())
 	}
+
+	/// Do the post-dispatch bookkeeping. This can be applied to both signed and unsigned.
+	///
+	/// It refunds the unspent weight of the extrinsic to the block weight.
+	pub fn do_post_dispatch(
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		post_info: &PostDispatchInfoOf<T::RuntimeCall>,
+	) -> Result<(), TransactionValidityError> {
+		let unspent = post_info.calc_unspent(info);
+		if unspent.any_gt(Weight::zero()) {
+			crate::BlockWeight::<T>::mutate(|current_weight| {
+				current_weight.reduce(unspent, info.class);
+			})
+		}
+
+		log::trace!(
+			target: LOG_TARGET,
+			"Used block weight: {:?}",
+			crate::BlockWeight::<T>::get(),
+		);
+
+		log::trace!(
+			target: LOG_TARGET,
+			"Used block length: {:?}",
+			Pallet::<T>::all_extrinsics_len(),
+		);
+
+		Ok(())
+	}
 }
 
 pub fn calculate_consumed_weight<Call>(
@@ -201,83 +233,77 @@ where
 	Ok(all_weight)
 }
 
-impl<T: Config + Send + Sync> SignedExtension for CheckWeight<T>
+impl<T: Config + Send + Sync> TransactionExtensionBase for CheckWeight<T> {
+	const IDENTIFIER: &'static str = "CheckWeight";
+	type Implicit = ();
+
+	fn implicit(&self) -> sp_std::result::Result<(), TransactionValidityError> {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> TransactionExtension<T::RuntimeCall> for CheckWeight<T>
 where
 	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
 {
-	type AccountId = T::AccountId;
-	type Call = T::RuntimeCall;
-	type AdditionalSigned = ();
+	type Val = ();
 	type Pre = ();
-	const IDENTIFIER: &'static str = "CheckWeight";
 
-	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
-		Ok(())
+	fn validate(
+		&self,
+		origin: DispatchOriginOf<T::RuntimeCall>,
+		_call: &T::RuntimeCall,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		len: usize,
+		_inherited_implication: &impl Encode,
+	) -> ValidateResult<Self::Val, T::RuntimeCall> {
+		Ok((Self::do_validate(info, len)?, (), origin))
 	}
 
-	fn pre_dispatch(
+	fn prepare(
 		self,
-		_who: &Self::AccountId,
-		_call: &Self::Call,
-		info: &DispatchInfoOf<Self::Call>,
+		_val: Self::Val,
+		_origin: &DispatchOriginOf<T::RuntimeCall>,
+		_call: &T::RuntimeCall,
+		info: &DispatchInfoOf<T::RuntimeCall>,
 		len: usize,
-	) -> Result<(), TransactionValidityError> {
+	) -> Result<Self::Pre, TransactionValidityError> {
 		Self::do_pre_dispatch(info, len)
 	}
 
-	fn validate(
-		&self,
-		_who: &Self::AccountId,
-		_call: &Self::Call,
-		info: &DispatchInfoOf<Self::Call>,
+	fn post_dispatch(
+		_pre: Self::Pre,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		post_info: &PostDispatchInfoOf<T::RuntimeCall>,
+		_len: usize,
+		_result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		Self::do_post_dispatch(info, post_info)
+	}
+
+	fn bare_validate(
+		_call: &T::RuntimeCall,
+		info: &DispatchInfoOf<T::RuntimeCall>,
 		len: usize,
 	) -> TransactionValidity {
 		Self::do_validate(info, len)
 	}
 
-	fn pre_dispatch_unsigned(
-		_call: &Self::Call,
-		info: &DispatchInfoOf<Self::Call>,
+	fn bare_validate_and_prepare(
+		_call: &T::RuntimeCall,
+		info: &DispatchInfoOf<T::RuntimeCall>,
 		len: usize,
 	) -> Result<(), TransactionValidityError> {
 		Self::do_pre_dispatch(info, len)
 	}
 
-	fn validate_unsigned(
-		_call: &Self::Call,
-		info: &DispatchInfoOf<Self::Call>,
-		len: usize,
-	) -> TransactionValidity {
-		Self::do_validate(info, len)
-	}
-
-	fn post_dispatch(
-		_pre: Option<Self::Pre>,
-		info: &DispatchInfoOf<Self::Call>,
-		post_info: &PostDispatchInfoOf<Self::Call>,
+	fn bare_post_dispatch(
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		post_info: &PostDispatchInfoOf<T::RuntimeCall>,
 		_len: usize,
 		_result: &DispatchResult,
 	) -> Result<(), TransactionValidityError> {
-		let unspent = post_info.calc_unspent(info);
-		if unspent.any_gt(Weight::zero()) {
-			crate::BlockWeight::<T>::mutate(|current_weight| {
-				current_weight.reduce(unspent, info.class);
-			})
-		}
-
-		log::trace!(
-			target: LOG_TARGET,
-			"Used block weight: {:?}",
-			crate::BlockWeight::<T>::get(),
-		);
-
-		log::trace!(
-			target: LOG_TARGET,
-			"Used block length: {:?}",
-			Pallet::<T>::all_extrinsics_len(),
-		);
-
-		Ok(())
+		Self::do_post_dispatch(info, post_info)
 	}
 }
 
@@ -301,6 +327,7 @@ mod tests {
 		AllExtrinsicsLen, BlockWeight, DispatchClass,
 	};
 	use frame_support::{assert_err, assert_ok, dispatch::Pays, weights::Weight};
+	use sp_runtime::traits::DispatchTransaction;
 	use sp_std::marker::PhantomData;
 
 	fn block_weights() -> crate::limits::BlockWeights {
@@ -504,20 +531,34 @@ mod tests {
 			});
 			// will not fit.
 			assert_err!(
-				CheckWeight::<Test>(PhantomData).pre_dispatch(&1, CALL, &normal, len),
+				CheckWeight::<Test>(PhantomData)
+					.validate_and_prepare(Some(1).into(), CALL, &normal, len)
+					.map(|_| ()),
 				InvalidTransaction::ExhaustsResources
 			);
 			// will fit.
-			assert_ok!(CheckWeight::<Test>(PhantomData).pre_dispatch(&1, CALL, &op, len));
+			assert_ok!(CheckWeight::<Test>(PhantomData).validate_and_prepare(
+				Some(1).into(),
+				CALL,
+				&op,
+				len
+			));
 
 			// likewise for length limit.
 			let len = 100_usize;
 			AllExtrinsicsLen::<Test>::put(normal_length_limit());
 			assert_err!(
-				CheckWeight::<Test>(PhantomData).pre_dispatch(&1, CALL, &normal, len),
+				CheckWeight::<Test>(PhantomData)
+					.validate_and_prepare(Some(1).into(), CALL, &normal, len)
+					.map(|_| ()),
 				InvalidTransaction::ExhaustsResources
 			);
-			assert_ok!(CheckWeight::<Test>(PhantomData).pre_dispatch(&1, CALL, &op, len));
+			assert_ok!(CheckWeight::<Test>(PhantomData).validate_and_prepare(
+				Some(1).into(),
+				CALL,
+				&op,
+				len
+			));
 		})
 	}
 
@@ -528,7 +569,12 @@ mod tests {
 			let normal_limit = normal_weight_limit().ref_time() as usize;
 			let reset_check_weight = |tx, s, f| {
 				AllExtrinsicsLen::<Test>::put(0);
-				let r = CheckWeight::<Test>(PhantomData).pre_dispatch(&1, CALL, tx, s);
+				let r = CheckWeight::<Test>(PhantomData).validate_and_prepare(
+					Some(1).into(),
+					CALL,
+					tx,
+					s,
+				);
 				if f {
 					assert!(r.is_err())
 				} else {
@@ -571,7 +617,12 @@ mod tests {
 				BlockWeight::<Test>::mutate(|current_weight| {
 					current_weight.set(s, DispatchClass::Normal)
 				});
-				let r = CheckWeight::<Test>(PhantomData).pre_dispatch(&1, CALL, i, len);
+				let r = CheckWeight::<Test>(PhantomData).validate_and_prepare(
+					Some(1).into(),
+					CALL,
+					i,
+					len,
+				);
 				if f {
 					assert!(r.is_err())
 				} else {
@@ -604,19 +655,15 @@ mod tests {
 					.set(Weight::from_parts(256, 0) - base_extrinsic, DispatchClass::Normal);
 			});
 
-			let pre = CheckWeight::<Test>(PhantomData).pre_dispatch(&1, CALL, &info, len).unwrap();
+			let (pre, _) = CheckWeight::<Test>(PhantomData)
+				.validate_and_prepare(Some(1).into(), CALL, &info, len)
+				.unwrap();
 			assert_eq!(
 				BlockWeight::<Test>::get().total(),
 				info.weight + Weight::from_parts(256, 0)
 			);
 
-			assert_ok!(CheckWeight::<Test>::post_dispatch(
-				Some(pre),
-				&info,
-				&post_info,
-				len,
-				&Ok(())
-			));
+			assert_ok!(CheckWeight::<Test>::post_dispatch(pre, &info, &post_info, len, &Ok(())));
 			assert_eq!(
 				BlockWeight::<Test>::get().total(),
 				post_info.actual_weight.unwrap() + Weight::from_parts(256, 0)
@@ -639,7 +686,9 @@ mod tests {
 				current_weight.set(Weight::from_parts(128, 0), DispatchClass::Normal);
 			});
 
-			let pre = CheckWeight::<Test>(PhantomData).pre_dispatch(&1, CALL, &info, len).unwrap();
+			let (pre, _) = CheckWeight::<Test>(PhantomData)
+				.validate_and_prepare(Some(1).into(), CALL, &info, len)
+				.unwrap();
 			assert_eq!(
 				BlockWeight::<Test>::get().total(),
 				info.weight +
@@ -647,13 +696,7 @@ mod tests {
 					block_weights().get(DispatchClass::Normal).base_extrinsic,
 			);
 
-			assert_ok!(CheckWeight::<Test>::post_dispatch(
-				Some(pre),
-				&info,
-				&post_info,
-				len,
-				&Ok(())
-			));
+			assert_ok!(CheckWeight::<Test>::post_dispatch(pre, &info, &post_info, len, &Ok(())));
 			assert_eq!(
 				BlockWeight::<Test>::get().total(),
 				info.weight +
@@ -672,7 +715,12 @@ mod tests {
 
 			// Initial weight from `weights.base_block`
 			assert_eq!(System::block_weight().total(), weights.base_block);
-			assert_ok!(CheckWeight::<Test>(PhantomData).pre_dispatch(&1, CALL, &free, len));
+			assert_ok!(CheckWeight::<Test>(PhantomData).validate_and_prepare(
+				Some(1).into(),
+				CALL,
+				&free,
+				len
+			));
 			assert_eq!(
 				System::block_weight().total(),
 				weights.get(DispatchClass::Normal).base_extrinsic + weights.base_block
@@ -76,9 +76,9 @@
 //!
 //! See the [`Pallet`] struct for details of publicly available functions.
 //!
-//! ### Signed Extensions
+//! ### Transaction Extensions
 //!
-//! The System pallet defines the following extensions:
+//! The System pallet defines the following transaction extensions:
 //!
 //!   - [`CheckWeight`]: Checks the weight and length of the block and ensure that it does not
 //!     exceed the limits.
@@ -92,7 +92,7 @@
 //!   - [`CheckTxVersion`]: Checks that the transaction version is the same as the one used to sign
 //!     the transaction.
 //!
-//! Look up the runtime aggregator file (e.g. `node/runtime`) to see the full list of signed
+//! Look up the runtime aggregator file (e.g. `node/runtime`) to see the full list of transaction
 //! extensions included in a chain.
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -56,7 +56,10 @@ use frame_support::{
 use pallet_transaction_payment::OnChargeTransaction;
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension, Zero},
+	traits::{
+		AsSystemOriginSigner, DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension,
+		TransactionExtension, Zero,
+	},
 	transaction_validity::{
 		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
 	},
@@ -236,6 +239,7 @@ where
 		+ Into<ChargeAssetLiquidityOf<T>>
 		+ From<ChargeAssetLiquidityOf<T>>,
 	ChargeAssetIdOf<T>: Send + Sync,
+	T::RuntimeOrigin: AsSystemOriginSigner<T::AccountId>,
 {
 	const IDENTIFIER: &'static str = "ChargeAssetTxPayment";
 	type AccountId = T::AccountId;
@@ -294,8 +298,10 @@ where
 						asset_id.is_none(),
 						"For that payment type the `asset_id` should be None"
 					);
-					pallet_transaction_payment::ChargeTransactionPayment::<T>::post_dispatch(
-						Some((tip, who, already_withdrawn)),
+					<pallet_transaction_payment::ChargeTransactionPayment<T> as TransactionExtension<
+						T::RuntimeCall,
+					>>::post_dispatch(
+						(tip, who, already_withdrawn),
 						info,
 						post_info,
 						len,
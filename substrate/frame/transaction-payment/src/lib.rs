@@ -61,11 +61,12 @@ pub use pallet::*;
 pub use payment::*;
 use sp_runtime::{
 	traits::{
-		Convert, DispatchInfoOf, Dispatchable, One, PostDispatchInfoOf, SaturatedConversion,
-		Saturating, SignedExtension, Zero,
+		AsSystemOriginSigner, Convert, DispatchInfoOf, DispatchOriginOf, Dispatchable, One,
+		PostDispatchInfoOf, SaturatedConversion, Saturating, TransactionExtension,
+		TransactionExtensionBase, ValidateResult, Zero,
 	},
 	transaction_validity::{
-		TransactionPriority, TransactionValidity, TransactionValidityError, ValidTransaction,
+		InvalidTransaction, TransactionPriority, TransactionValidityError, ValidTransaction,
 	},
 	FixedPointNumber, FixedU128, Perbill, Perquintill, RuntimeDebug,
 };
@@ -507,10 +508,10 @@ impl<T: Config> Pallet<T> {
 		// a very very little potential gain in the future.
 		let dispatch_info = <Extrinsic as GetDispatchInfo>::get_dispatch_info(&unchecked_extrinsic);
 
-		let partial_fee = if unchecked_extrinsic.is_signed().unwrap_or(false) {
+		let partial_fee = if !unchecked_extrinsic.is_bare() {
 			Self::compute_fee(len, &dispatch_info, 0u32.into())
 		} else {
-			// Unsigned extrinsics have no partial fee.
+			// Bare extrinsics have no partial fee.
 			0u32.into()
 		};
 
@@ -531,10 +532,10 @@ impl<T: Config> Pallet<T> {
 
 		let tip = 0u32.into();
 
-		if unchecked_extrinsic.is_signed().unwrap_or(false) {
+		if !unchecked_extrinsic.is_bare() {
 			Self::compute_fee_details(len, &dispatch_info, tip)
 		} else {
-			// Unsigned extrinsics have no inclusion fee.
+			// Bare extrinsics have no inclusion fee.
 			FeeDetails { inclusion_fee: None, tip }
 		}
 	}
@@ -714,21 +715,30 @@ where
 		who: &T::AccountId,
 		call: &T::RuntimeCall,
 		info: &DispatchInfoOf<T::RuntimeCall>,
-		len: usize,
+		fee: BalanceOf<T>,
 	) -> Result<
-		(
-			BalanceOf<T>,
-			<<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::LiquidityInfo,
-		),
+		<<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::LiquidityInfo,
 		TransactionValidityError,
 	> {
+		<<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::withdraw_fee(
+			who, call, info, fee, self.0,
+		)
+	}
+
+	fn can_withdraw_fee(
+		&self,
+		who: &T::AccountId,
+		call: &T::RuntimeCall,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		len: usize,
+	) -> Result<BalanceOf<T>, TransactionValidityError> {
 		let tip = self.0;
 		let fee = Pallet::<T>::compute_fee(len as u32, info, tip);
 
-		<<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::withdraw_fee(
+		<<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::can_withdraw_fee(
 			who, call, info, fee, tip,
-		)
-		.map(|i| (fee, i))
+		)?;
+		Ok(fee)
 	}
 
 	/// Get an appropriate priority for a transaction with the given `DispatchInfo`, encoded length
@@ -817,67 +827,84 @@ impl<T: Config> sp_std::fmt::Debug for ChargeTransactionPayment<T> {
 	}
 }
 
-impl<T: Config> SignedExtension for ChargeTransactionPayment<T>
+impl<T: Config> TransactionExtensionBase for ChargeTransactionPayment<T> {
+	const IDENTIFIER: &'static str = "ChargeTransactionPayment";
+	type Implicit = ();
+
+	fn implicit(&self) -> sp_std::result::Result<(), TransactionValidityError> {
+		Ok(())
+	}
+}
+
+impl<T: Config> TransactionExtension<T::RuntimeCall> for ChargeTransactionPayment<T>
 where
 	BalanceOf<T>: Send + Sync + From<u64>,
 	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	T::RuntimeOrigin: AsSystemOriginSigner<T::AccountId>,
 {
-	const IDENTIFIER: &'static str = "ChargeTransactionPayment";
-	type AccountId = T::AccountId;
-	type Call = T::RuntimeCall;
-	type AdditionalSigned = ();
+	type Val = (
+		// who pays the fee
+		T::AccountId,
+		// the computed fee, including the tip
+		BalanceOf<T>,
+	);
 	type Pre = (
 		// tip
 		BalanceOf<T>,
-		// who paid the fee - this is an option to allow for a Default impl.
-		Self::AccountId,
+		// who paid the fee
+		T::AccountId,
 		// imbalance resulting from withdrawing the fee
 		<<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::LiquidityInfo,
 	);
-	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
-		Ok(())
-	}
 
 	fn validate(
 		&self,
-		who: &Self::AccountId,
-		call: &Self::Call,
-		info: &DispatchInfoOf<Self::Call>,
+		origin: DispatchOriginOf<T::RuntimeCall>,
+		call: &T::RuntimeCall,
+		info: &DispatchInfoOf<T::RuntimeCall>,
 		len: usize,
-	) -> TransactionValidity {
-		let (final_fee, _) = self.withdraw_fee(who, call, info, len)?;
+		_inherited_implication: &impl Encode,
+	) -> ValidateResult<Self::Val, T::RuntimeCall> {
+		// The fee of a general transaction is paid by the account it was authorized as by the
+		// preceding extensions, a transaction nobody pays for is rejected.
+		let Some(who) = origin.as_system_origin_signer() else {
+			return Err(InvalidTransaction::Payment.into())
+		};
+		let final_fee = self.can_withdraw_fee(who, call, info, len)?;
 		let tip = self.0;
-		Ok(ValidTransaction {
+		let validity = ValidTransaction {
 			priority: Self::get_priority(info, len, tip, final_fee),
 			..Default::default()
-		})
+		};
+		Ok((validity, (who.clone(), final_fee), origin))
 	}
 
-	fn pre_dispatch(
+	fn prepare(
 		self,
-		who: &Self::AccountId,
-		call: &Self::Call,
-		info: &DispatchInfoOf<Self::Call>,
-		len: usize,
+		val: Self::Val,
+		_origin: &DispatchOriginOf<T::RuntimeCall>,
+		call: &T::RuntimeCall,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		_len: usize,
 	) -> Result<Self::Pre, TransactionValidityError> {
-		let (_fee, imbalance) = self.withdraw_fee(who, call, info, len)?;
-		Ok((self.0, who.clone(), imbalance))
+		let (who, fee) = val;
+		let imbalance = self.withdraw_fee(&who, call, info, fee)?;
+		Ok((self.0, who, imbalance))
 	}
 
 	fn post_dispatch(
-		maybe_pre: Option<Self::Pre>,
-		info: &DispatchInfoOf<Self::Call>,
-		post_info: &PostDispatchInfoOf<Self::Call>,
+		pre: Self::Pre,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		post_info: &PostDispatchInfoOf<T::RuntimeCall>,
 		len: usize,
 		_result: &DispatchResult,
 	) -> Result<(), TransactionValidityError> {
-		if let Some((tip, who, imbalance)) = maybe_pre {
-			let actual_fee = Pallet::<T>::compute_actual_fee(len as u32, info, post_info, tip);
-			T::OnChargeTransaction::correct_and_deposit_fee(
-				&who, info, post_info, actual_fee, tip, imbalance,
-			)?;
-			Pallet::<T>::deposit_event(Event::<T>::TransactionFeePaid { who, actual_fee, tip });
-		}
+		let (tip, who, imbalance) = pre;
+		let actual_fee = Pallet::<T>::compute_actual_fee(len as u32, info, post_info, tip);
+		T::OnChargeTransaction::correct_and_deposit_fee(
+			&who, info, post_info, actual_fee, tip, imbalance,
+		)?;
+		Pallet::<T>::deposit_event(Event::<T>::TransactionFeePaid { who, actual_fee, tip });
 		Ok(())
 	}
 }
@@ -21,7 +21,10 @@ use crate as pallet_transaction_payment;
 use codec::Encode;
 
 use sp_runtime::{
-	testing::TestXt, traits::One, transaction_validity::InvalidTransaction, BuildStorage,
+	testing::TestXt,
+	traits::{DispatchTransaction, One},
+	transaction_validity::InvalidTransaction,
+	BuildStorage,
 };
 
 use frame_support::{
@@ -136,13 +139,18 @@ fn signed_extension_transaction_payment_work() {
 		.build()
 		.execute_with(|| {
 			let len = 10;
-			let pre = ChargeTransactionPayment::<Runtime>::from(0)
-				.pre_dispatch(&1, CALL, &info_from_weight(Weight::from_parts(5, 0)), len)
+			let (pre, _) = ChargeTransactionPayment::<Runtime>::from(0)
+				.validate_and_prepare(
+					Some(1).into(),
+					CALL,
+					&info_from_weight(Weight::from_parts(5, 0)),
+					len,
+				)
 				.unwrap();
 			assert_eq!(Balances::free_balance(1), 100 - 5 - 5 - 10);
 
 			assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
-				Some(pre),
+				pre,
 				&info_from_weight(Weight::from_parts(5, 0)),
 				&default_post_info(),
 				len,
@@ -154,13 +162,18 @@ fn signed_extension_transaction_payment_work() {
 
 			FeeUnbalancedAmount::mutate(|a| *a = 0);
 
-			let pre = ChargeTransactionPayment::<Runtime>::from(5 /* tipped */)
-				.pre_dispatch(&2, CALL, &info_from_weight(Weight::from_parts(100, 0)), len)
+			let (pre, _) = ChargeTransactionPayment::<Runtime>::from(5 /* tipped */)
+				.validate_and_prepare(
+					Some(2).into(),
+					CALL,
+					&info_from_weight(Weight::from_parts(100, 0)),
+					len,
+				)
 				.unwrap();
 			assert_eq!(Balances::free_balance(2), 200 - 5 - 10 - 100 - 5);
 
 			assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
-				Some(pre),
+				pre,
 				&info_from_weight(Weight::from_parts(100, 0)),
 				&post_info_from_weight(Weight::from_parts(50, 0)),
 				len,
@@ -182,14 +195,19 @@ fn signed_extension_transaction_payment_multiplied_refund_works() {
 			let len = 10;
 			<NextFeeMultiplier<Runtime>>::put(Multiplier::saturating_from_rational(3, 2));
 
-			let pre = ChargeTransactionPayment::<Runtime>::from(5 /* tipped */)
-				.pre_dispatch(&2, CALL, &info_from_weight(Weight::from_parts(100, 0)), len)
+			let (pre, _) = ChargeTransactionPayment::<Runtime>::from(5 /* tipped */)
+				.validate_and_prepare(
+					Some(2).into(),
+					CALL,
+					&info_from_weight(Weight::from_parts(100, 0)),
+					len,
+				)
 				.unwrap();
 			// 5 base fee, 10 byte fee, 3/2 * 100 weight fee, 5 tip
 			assert_eq!(Balances::free_balance(2), 200 - 5 - 10 - 150 - 5);
 
 			assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
-				Some(pre),
+				pre,
 				&info_from_weight(Weight::from_parts(100, 0)),
 				&post_info_from_weight(Weight::from_parts(50, 0)),
 				len,
@@ -204,8 +222,8 @@ fn signed_extension_transaction_payment_multiplied_refund_works() {
 fn signed_extension_transaction_payment_is_bounded() {
 	ExtBuilder::default().balance_factor(1000).byte_fee(0).build().execute_with(|| {
 		// maximum weight possible
-		assert_ok!(ChargeTransactionPayment::<Runtime>::from(0).pre_dispatch(
-			&1,
+		assert_ok!(ChargeTransactionPayment::<Runtime>::from(0).validate_and_prepare(
+			Some(1).into(),
 			CALL,
 			&info_from_weight(Weight::MAX),
 			10
@@ -237,8 +255,8 @@ fn signed_extension_allows_free_transactions() {
 				class: DispatchClass::Operational,
 				pays_fee: Pays::No,
 			};
-			assert_ok!(ChargeTransactionPayment::<Runtime>::from(0).validate(
-				&1,
+			assert_ok!(ChargeTransactionPayment::<Runtime>::from(0).validate_only(
+				Some(1).into(),
 				CALL,
 				&operational_transaction,
 				len
@@ -251,12 +269,9 @@ fn signed_extension_allows_free_transactions() {
 				pays_fee: Pays::Yes,
 			};
 			assert_noop!(
-				ChargeTransactionPayment::<Runtime>::from(0).validate(
-					&1,
-					CALL,
-					&free_transaction,
-					len
-				),
+				ChargeTransactionPayment::<Runtime>::from(0)
+					.validate_only(Some(1).into(), CALL, &free_transaction, len)
+					.map(|_| ()),
 				TransactionValidityError::Invalid(InvalidTransaction::Payment),
 			);
 		});
@@ -274,7 +289,12 @@ fn signed_ext_length_fee_is_also_updated_per_congestion() {
 			let len = 10;
 
 			assert_ok!(ChargeTransactionPayment::<Runtime>::from(10) // tipped
-				.pre_dispatch(&1, CALL, &info_from_weight(Weight::from_parts(3, 0)), len));
+				.validate_and_prepare(
+					Some(1).into(),
+					CALL,
+					&info_from_weight(Weight::from_parts(3, 0)),
+					len
+				));
 			assert_eq!(
 				Balances::free_balance(1),
 				100 // original
@@ -286,6 +306,47 @@ fn signed_ext_length_fee_is_also_updated_per_congestion() {
 		})
 }
 
+#[test]
+fn validate_does_not_withdraw_fee() {
+	ExtBuilder::default()
+		.balance_factor(10)
+		.base_weight(Weight::from_parts(5, 0))
+		.build()
+		.execute_with(|| {
+			let len = 10;
+			assert_ok!(ChargeTransactionPayment::<Runtime>::from(5 /* tipped */).validate_only(
+				Some(2).into(),
+				CALL,
+				&info_from_weight(Weight::from_parts(100, 0)),
+				len
+			));
+			assert_eq!(Balances::free_balance(2), 200);
+		});
+}
+
+#[test]
+fn transaction_without_payer_is_rejected() {
+	ExtBuilder::default()
+		.balance_factor(10)
+		.base_weight(Weight::from_parts(5, 0))
+		.build()
+		.execute_with(|| {
+			let len = 10;
+			let info = info_from_weight(Weight::from_parts(100, 0));
+			// A general transaction that was not authorized as an account, or with another origin.
+			for origin in [system::RawOrigin::None, system::RawOrigin::Root] {
+				assert_eq!(
+					ChargeTransactionPayment::<Runtime>::from(5 /* tipped */)
+						.validate_and_prepare(origin.into(), CALL, &info, len)
+						.map(|_| ()),
+					Err(InvalidTransaction::Payment.into())
+				);
+			}
+			assert_eq!(FeeUnbalancedAmount::get(), 0);
+			assert_eq!(TipUnbalancedAmount::get(), 0);
+		});
+}
+
 #[test]
 fn query_info_and_fee_details_works() {
 	let call = RuntimeCall::Balances(BalancesCall::transfer_allow_death { dest: 2, value: 69 });
@@ -527,8 +588,13 @@ fn refund_does_not_recreate_account() {
 			// So events are emitted
 			System::set_block_number(10);
 			let len = 10;
-			let pre = ChargeTransactionPayment::<Runtime>::from(5 /* tipped */)
-				.pre_dispatch(&2, CALL, &info_from_weight(Weight::from_parts(100, 0)), len)
+			let (pre, _) = ChargeTransactionPayment::<Runtime>::from(5 /* tipped */)
+				.validate_and_prepare(
+					Some(2).into(),
+					CALL,
+					&info_from_weight(Weight::from_parts(100, 0)),
+					len,
+				)
 				.unwrap();
 			assert_eq!(Balances::free_balance(2), 200 - 5 - 10 - 100 - 5);
 
@@ -541,7 +607,7 @@ fn refund_does_not_recreate_account() {
 			assert_eq!(Balances::free_balance(2), 0);
 
 			assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
-				Some(pre),
+				pre,
 				&info_from_weight(Weight::from_parts(100, 0)),
 				&post_info_from_weight(Weight::from_parts(50, 0)),
 				len,
@@ -569,13 +635,18 @@ fn actual_weight_higher_than_max_refunds_nothing() {
 		.build()
 		.execute_with(|| {
 			let len = 10;
-			let pre = ChargeTransactionPayment::<Runtime>::from(5 /* tipped */)
-				.pre_dispatch(&2, CALL, &info_from_weight(Weight::from_parts(100, 0)), len)
+			let (pre, _) = ChargeTransactionPayment::<Runtime>::from(5 /* tipped */)
+				.validate_and_prepare(
+					Some(2).into(),
+					CALL,
+					&info_from_weight(Weight::from_parts(100, 0)),
+					len,
+				)
 				.unwrap();
 			assert_eq!(Balances::free_balance(2), 200 - 5 - 10 - 100 - 5);
 
 			assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
-				Some(pre),
+				pre,
 				&info_from_weight(Weight::from_parts(100, 0)),
 				&post_info_from_weight(Weight::from_parts(101, 0)),
 				len,
@@ -601,12 +672,12 @@ fn zero_transfer_on_free_transaction() {
 				class: DispatchClass::Normal,
 			};
 			let user = 69;
-			let pre = ChargeTransactionPayment::<Runtime>::from(0)
-				.pre_dispatch(&user, CALL, &dispatch_info, len)
+			let (pre, _) = ChargeTransactionPayment::<Runtime>::from(0)
+				.validate_and_prepare(Some(user).into(), CALL, &dispatch_info, len)
 				.unwrap();
 			assert_eq!(Balances::total_balance(&user), 0);
 			assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
-				Some(pre),
+				pre,
 				&dispatch_info,
 				&default_post_info(),
 				len,
@@ -639,12 +710,12 @@ fn refund_consistent_with_actual_weight() {
 
 			<NextFeeMultiplier<Runtime>>::put(Multiplier::saturating_from_rational(5, 4));
 
-			let pre = ChargeTransactionPayment::<Runtime>::from(tip)
-				.pre_dispatch(&2, CALL, &info, len)
+			let (pre, _) = ChargeTransactionPayment::<Runtime>::from(tip)
+				.validate_and_prepare(Some(2).into(), CALL, &info, len)
 				.unwrap();
 
 			ChargeTransactionPayment::<Runtime>::post_dispatch(
-				Some(pre),
+				pre,
 				&info,
 				&post_info,
 				len,
@@ -674,15 +745,17 @@ fn should_alter_operational_priority() {
 			pays_fee: Pays::Yes,
 		};
 		let priority = ChargeTransactionPayment::<Runtime>(tip)
-			.validate(&2, CALL, &normal, len)
+			.validate_only(Some(2).into(), CALL, &normal, len)
 			.unwrap()
+			.0
 			.priority;
 
 		assert_eq!(priority, 60);
 
 		let priority = ChargeTransactionPayment::<Runtime>(2 * tip)
-			.validate(&2, CALL, &normal, len)
+			.validate_only(Some(2).into(), CALL, &normal, len)
 			.unwrap()
+			.0
 			.priority;
 
 		assert_eq!(priority, 110);
@@ -695,14 +768,16 @@ fn should_alter_operational_priority() {
 			pays_fee: Pays::Yes,
 		};
 		let priority = ChargeTransactionPayment::<Runtime>(tip)
-			.validate(&2, CALL, &op, len)
+			.validate_only(Some(2).into(), CALL, &op, len)
 			.unwrap()
+			.0
 			.priority;
 		assert_eq!(priority, 5810);
 
 		let priority = ChargeTransactionPayment::<Runtime>(2 * tip)
-			.validate(&2, CALL, &op, len)
+			.validate_only(Some(2).into(), CALL, &op, len)
 			.unwrap()
+			.0
 			.priority;
 		assert_eq!(priority, 6110);
 	});
@@ -720,8 +795,9 @@ fn no_tip_has_some_priority() {
 			pays_fee: Pays::Yes,
 		};
 		let priority = ChargeTransactionPayment::<Runtime>(tip)
-			.validate(&2, CALL, &normal, len)
+			.validate_only(Some(2).into(), CALL, &normal, len)
 			.unwrap()
+			.0
 			.priority;
 
 		assert_eq!(priority, 10);
@@ -734,8 +810,9 @@ fn no_tip_has_some_priority() {
 			pays_fee: Pays::Yes,
 		};
 		let priority = ChargeTransactionPayment::<Runtime>(tip)
-			.validate(&2, CALL, &op, len)
+			.validate_only(Some(2).into(), CALL, &op, len)
 			.unwrap()
+			.0
 			.priority;
 		assert_eq!(priority, 5510);
 	});
@@ -754,8 +831,9 @@ fn higher_tip_have_higher_priority() {
 				pays_fee: Pays::Yes,
 			};
 			priority1 = ChargeTransactionPayment::<Runtime>(tip)
-				.validate(&2, CALL, &normal, len)
+				.validate_only(Some(2).into(), CALL, &normal, len)
 				.unwrap()
+				.0
 				.priority;
 		});
 
@@ -766,8 +844,9 @@ fn higher_tip_have_higher_priority() {
 				pays_fee: Pays::Yes,
 			};
 			priority2 = ChargeTransactionPayment::<Runtime>(tip)
-				.validate(&2, CALL, &op, len)
+				.validate_only(Some(2).into(), CALL, &op, len)
 				.unwrap()
+				.0
 				.priority;
 		});
 
@@ -799,12 +878,12 @@ fn post_info_can_change_pays_fee() {
 
 			<NextFeeMultiplier<Runtime>>::put(Multiplier::saturating_from_rational(5, 4));
 
-			let pre = ChargeTransactionPayment::<Runtime>::from(tip)
-				.pre_dispatch(&2, CALL, &info, len)
+			let (pre, _) = ChargeTransactionPayment::<Runtime>::from(tip)
+				.validate_and_prepare(Some(2).into(), CALL, &info, len)
 				.unwrap();
 
 			ChargeTransactionPayment::<Runtime>::post_dispatch(
-				Some(pre),
+				pre,
 				&info,
 				&post_info,
 				len,
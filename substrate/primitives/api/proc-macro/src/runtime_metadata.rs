@@ -173,6 +173,8 @@ pub fn generate_decl_runtime_metadata(decl: &ItemTrait) -> TokenStream2 {
 				name: #trait_name,
 				methods: #crate_::vec![ #( #methods, )* ],
 				docs: #docs,
+				// `VERSION` is declared next to this function by `decl_runtime_apis!`.
+				version: VERSION.into(),
 			}
 		}
 	)
@@ -20,7 +20,10 @@ use ::kvdb::{DBTransaction, KeyValueDB};
 
 use crate::{error, Change, ColumnId, Database, Transaction};
 
-struct DbAdapter<D: KeyValueDB + 'static>(D);
+/// Compacts a column of the database.
+pub type Compaction<D> = fn(&D, ColumnId) -> std::io::Result<()>;
+
+struct DbAdapter<D: KeyValueDB + 'static>(D, Option<Compaction<D>>);
 
 fn handle_err<T>(result: std::io::Result<T>) -> T {
 	match result {
@@ -37,7 +40,20 @@ where
 	D: KeyValueDB + 'static,
 	H: Clone + AsRef<[u8]>,
 {
-	std::sync::Arc::new(DbAdapter(db))
+	std::sync::Arc::new(DbAdapter(db, None))
+}
+
+/// Wrap RocksDb database into a trait object that implements `sp_database::Database`, using
+/// `compaction` to compact its columns.
+pub fn as_database_with_compaction<D, H>(
+	db: D,
+	compaction: Compaction<D>,
+) -> std::sync::Arc<dyn Database<H>>
+where
+	D: KeyValueDB + 'static,
+	H: Clone + AsRef<[u8]>,
+{
+	std::sync::Arc::new(DbAdapter(db, Some(compaction)))
 }
 
 impl<D: KeyValueDB> DbAdapter<D> {
@@ -115,4 +131,12 @@ impl<D: KeyValueDB, H: Clone + AsRef<[u8]>> Database<H> for DbAdapter<D> {
 	fn contains(&self, col: ColumnId, key: &[u8]) -> bool {
 		handle_err(self.0.has_key(col, key))
 	}
+
+	fn compact(&self, col: ColumnId) -> error::Result<()> {
+		match self.1 {
+			Some(compaction) =>
+				compaction(&self.0, col).map_err(|e| error::DatabaseError(Box::new(e))),
+			None => Ok(()),
+		}
+	}
 }
@@ -21,7 +21,7 @@ pub mod error;
 mod kvdb;
 mod mem;
 
-pub use crate::kvdb::as_database;
+pub use crate::kvdb::{as_database, as_database_with_compaction, Compaction};
 pub use mem::MemDb;
 
 /// An identifier for a column.
@@ -117,6 +117,14 @@ pub trait Database<H: Clone + AsRef<[u8]>>: Send + Sync {
 	///
 	/// Not all database implementations use a prefix for keys, so this function may be a noop.
 	fn sanitize_key(&self, _key: &mut Vec<u8>) {}
+
+	/// Compact the given column, reclaiming the space of removed values.
+	///
+	/// Not all database implementations support manual compaction, so this function may be a
+	/// noop.
+	fn compact(&self, _col: ColumnId) -> error::Result<()> {
+		Ok(())
+	}
 }
 
 impl<H> std::fmt::Debug for dyn Database<H> {
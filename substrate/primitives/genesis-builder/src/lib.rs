@@ -29,10 +29,23 @@
 //!
 //! Providing externalities with empty storage and putting `GenesisConfig` into storage allows to
 //! catch and build the raw storage of `GenesisConfig` which is the foundation for genesis block.
+//!
+//! Since version 2 of the API, the runtime can also provide a set of named presets, e.g. the
+//! `GenesisConfig` for the development chain or the local testnet. The preset is the JSON patch
+//! of the default `GenesisConfig`, so node binaries do not need the runtime-specific genesis code.
 
 /// The result type alias, used in build methods. `Err` contains formatted error message.
 pub type Result = core::result::Result<(), sp_runtime::RuntimeString>;
 
+/// The type representing the identifier of the `GenesisConfig` preset.
+pub type PresetId = sp_runtime::RuntimeString;
+
+/// The identifier of the preset used for the development chain.
+pub const DEV_RUNTIME_PRESET: &str = "development";
+
+/// The identifier of the preset used for the local testnet chain.
+pub const LOCAL_TESTNET_RUNTIME_PRESET: &str = "local_testnet";
+
 sp_api::decl_runtime_apis! {
 	/// API to interact with GenesisConfig for the runtime
 	pub trait GenesisBuilder {
@@ -50,5 +63,19 @@ sp_api::decl_runtime_apis! {
 		///
 		/// Please note that provided json blob must contain all `GenesisConfig` fields, no defaults will be used.
 		fn build_config(json: sp_std::vec::Vec<u8>) -> Result;
+
+		/// Returns a JSON blob representation of the named `GenesisConfig` preset.
+		///
+		/// The returned blob is a patch of the default `GenesisConfig`, i.e. it only contains the
+		/// keys which differ from the default values. If the preset with the given `id` does not
+		/// exist, `None` is returned.
+		#[api_version(2)]
+		fn get_preset(id: &PresetId) -> Option<sp_std::vec::Vec<u8>>;
+
+		/// Returns a list of identifiers of the available `GenesisConfig` presets.
+		///
+		/// The presets from the list can be retrieved with the `get_preset` function.
+		#[api_version(2)]
+		fn preset_names() -> sp_std::vec::Vec<PresetId>;
 	}
 }
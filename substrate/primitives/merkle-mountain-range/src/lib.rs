@@ -359,6 +359,23 @@ pub struct Proof<Hash> {
 	pub items: Vec<Hash>,
 }
 
+/// An MMR ancestry proof for a prior MMR root.
+///
+/// Proves that the MMR with `prev_leaf_count` leaves, whose peaks are `prev_peaks`, is a prefix of
+/// the MMR with `leaf_count` leaves.
+#[derive(codec::Encode, codec::Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct AncestryProof<Hash> {
+	/// Peaks of the ancestor's MMR.
+	pub prev_peaks: Vec<Hash>,
+	/// Number of leaves in the ancestor's MMR.
+	pub prev_leaf_count: LeafIndex,
+	/// Number of leaves in MMR, when the proof was generated.
+	pub leaf_count: LeafIndex,
+	/// Proof elements (positions and hashes of siblings of inner nodes on the path to the previous
+	/// peaks).
+	pub items: Vec<(NodeIndex, Hash)>,
+}
+
 /// Merkle Mountain Range operation error.
 #[cfg_attr(feature = "std", derive(thiserror::Error))]
 #[derive(RuntimeDebug, codec::Encode, codec::Decode, PartialEq, Eq, TypeInfo)]
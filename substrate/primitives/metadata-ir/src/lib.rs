@@ -30,6 +30,7 @@ pub use types::*;
 
 mod v14;
 mod v15;
+mod v16;
 
 /// Metadata V14.
 const V14: u32 = 14;
@@ -37,6 +38,9 @@ const V14: u32 = 14;
 /// Metadata V15.
 const V15: u32 = 15;
 
+/// Unstable metadata V16.
+const UNSTABLE_V16: u32 = u32::MAX;
+
 /// Transform the IR to the specified version.
 ///
 /// Use [`supported_versions`] to find supported versions.
@@ -47,13 +51,15 @@ pub fn into_version(metadata: MetadataIR, version: u32) -> Option<RuntimeMetadat
 		V14 => Some(into_v14(metadata)),
 		// Unstable metadata.
 		V15 => Some(into_latest(metadata)),
+		// Unstable metadata V16.
+		UNSTABLE_V16 => Some(into_unstable(metadata)),
 		_ => None,
 	}
 }
 
 /// Returns the supported metadata versions.
 pub fn supported_versions() -> sp_std::vec::Vec<u32> {
-	sp_std::vec![V14, V15]
+	sp_std::vec![V14, V15, UNSTABLE_V16]
 }
 
 /// Transform the IR to the latest stable metadata version.
@@ -68,6 +74,12 @@ pub fn into_v14(metadata: MetadataIR) -> RuntimeMetadataPrefixed {
 	latest.into()
 }
 
+/// Transform the IR to the unstable metadata version 16.
+pub fn into_unstable(metadata: MetadataIR) -> RuntimeMetadataPrefixed {
+	let unstable: frame_metadata::v16::RuntimeMetadataV16 = metadata.into();
+	unstable.into()
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -79,7 +91,7 @@ mod test {
 			pallets: vec![],
 			extrinsic: ExtrinsicMetadataIR {
 				ty: meta_type::<()>(),
-				version: 0,
+				versions: vec![0],
 				address_ty: meta_type::<()>(),
 				call_ty: meta_type::<()>(),
 				signature_ty: meta_type::<()>(),
@@ -115,4 +127,14 @@ mod test {
 
 		assert!(matches!(metadata.1, RuntimeMetadata::V15(_)));
 	}
+
+	#[test]
+	fn into_version_unstable_16() {
+		let ir = ir_metadata();
+		let metadata = into_version(ir, UNSTABLE_V16).expect("Should return prefixed metadata");
+
+		assert_eq!(metadata.0, META_RESERVED);
+
+		assert!(matches!(metadata.1, RuntimeMetadata::V16(_)));
+	}
 }
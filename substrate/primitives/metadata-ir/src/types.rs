@@ -15,10 +15,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use codec::Encode;
+use codec::{Compact, Encode};
 use scale_info::{
 	form::{Form, MetaForm, PortableForm},
-	prelude::vec::Vec,
+	prelude::{collections::BTreeMap, vec::Vec},
 	IntoPortable, MetaType, Registry,
 };
 
@@ -52,6 +52,8 @@ pub struct RuntimeApiMetadataIR<T: Form = MetaForm> {
 	pub methods: Vec<RuntimeApiMethodMetadataIR<T>>,
 	/// Trait documentation.
 	pub docs: Vec<T::String>,
+	/// Runtime API version.
+	pub version: Compact<u32>,
 }
 
 impl IntoPortable for RuntimeApiMetadataIR {
@@ -62,6 +64,7 @@ impl IntoPortable for RuntimeApiMetadataIR {
 			name: self.name.into_portable(registry),
 			methods: registry.map_into_portable(self.methods),
 			docs: registry.map_into_portable(self.docs),
+			version: self.version,
 		}
 	}
 }
@@ -127,6 +130,10 @@ pub struct PalletMetadataIR<T: Form = MetaForm> {
 	pub constants: Vec<PalletConstantMetadataIR<T>>,
 	/// Pallet error metadata.
 	pub error: Option<PalletErrorMetadataIR<T>>,
+	/// Pallet associated types metadata.
+	pub associated_types: Vec<PalletAssociatedTypeMetadataIR<T>>,
+	/// Pallet view functions metadata.
+	pub view_functions: Vec<PalletViewFunctionMetadataIR<T>>,
 	/// Define the index of the pallet, this index will be used for the encoding of pallet event,
 	/// call and origin variants.
 	pub index: u8,
@@ -145,6 +152,8 @@ impl IntoPortable for PalletMetadataIR {
 			event: self.event.map(|event| event.into_portable(registry)),
 			constants: registry.map_into_portable(self.constants),
 			error: self.error.map(|error| error.into_portable(registry)),
+			associated_types: registry.map_into_portable(self.associated_types),
+			view_functions: registry.map_into_portable(self.view_functions),
 			index: self.index,
 			docs: registry.map_into_portable(self.docs),
 		}
@@ -158,8 +167,8 @@ pub struct ExtrinsicMetadataIR<T: Form = MetaForm> {
 	///
 	/// Note: Field used for metadata V14 only.
 	pub ty: T::Type,
-	/// Extrinsic version.
-	pub version: u8,
+	/// Extrinsic versions.
+	pub versions: Vec<u8>,
 	/// The type of the address that signes the extrinsic
 	pub address_ty: T::Type,
 	/// The type of the outermost Call enum.
@@ -178,7 +187,7 @@ impl IntoPortable for ExtrinsicMetadataIR {
 	fn into_portable(self, registry: &mut Registry) -> Self::Output {
 		ExtrinsicMetadataIR {
 			ty: registry.register_type(&self.ty),
-			version: self.version,
+			versions: self.versions,
 			address_ty: registry.register_type(&self.address_ty),
 			call_ty: registry.register_type(&self.call_ty),
 			signature_ty: registry.register_type(&self.signature_ty),
@@ -245,6 +254,8 @@ pub struct StorageEntryMetadataIR<T: Form = MetaForm> {
 	pub default: Vec<u8>,
 	/// Storage entry documentation.
 	pub docs: Vec<T::String>,
+	/// Deprecation info of the storage entry.
+	pub deprecation_info: ItemDeprecationInfoIR<T>,
 }
 
 impl IntoPortable for StorageEntryMetadataIR {
@@ -257,6 +268,7 @@ impl IntoPortable for StorageEntryMetadataIR {
 			ty: self.ty.into_portable(registry),
 			default: self.default,
 			docs: registry.map_into_portable(self.docs),
+			deprecation_info: self.deprecation_info.into_portable(registry),
 		}
 	}
 }
@@ -331,19 +343,24 @@ impl IntoPortable for StorageEntryTypeIR {
 pub struct PalletCallMetadataIR<T: Form = MetaForm> {
 	/// The corresponding enum type for the pallet call.
 	pub ty: T::Type,
+	/// Deprecation info of the calls, indexed by call index.
+	pub deprecation_info: EnumDeprecationInfoIR<T>,
 }
 
 impl IntoPortable for PalletCallMetadataIR {
 	type Output = PalletCallMetadataIR<PortableForm>;
 
 	fn into_portable(self, registry: &mut Registry) -> Self::Output {
-		PalletCallMetadataIR { ty: registry.register_type(&self.ty) }
+		PalletCallMetadataIR {
+			ty: registry.register_type(&self.ty),
+			deprecation_info: self.deprecation_info.into_portable(registry),
+		}
 	}
 }
 
 impl From<MetaType> for PalletCallMetadataIR {
 	fn from(ty: MetaType) -> Self {
-		Self { ty }
+		Self { ty, deprecation_info: EnumDeprecationInfoIR::nothing_deprecated() }
 	}
 }
 
@@ -352,19 +369,24 @@ impl From<MetaType> for PalletCallMetadataIR {
 pub struct PalletEventMetadataIR<T: Form = MetaForm> {
 	/// The Event type.
 	pub ty: T::Type,
+	/// Deprecation info of the events, indexed by variant index.
+	pub deprecation_info: EnumDeprecationInfoIR<T>,
 }
 
 impl IntoPortable for PalletEventMetadataIR {
 	type Output = PalletEventMetadataIR<PortableForm>;
 
 	fn into_portable(self, registry: &mut Registry) -> Self::Output {
-		PalletEventMetadataIR { ty: registry.register_type(&self.ty) }
+		PalletEventMetadataIR {
+			ty: registry.register_type(&self.ty),
+			deprecation_info: self.deprecation_info.into_portable(registry),
+		}
 	}
 }
 
 impl From<MetaType> for PalletEventMetadataIR {
 	fn from(ty: MetaType) -> Self {
-		Self { ty }
+		Self { ty, deprecation_info: EnumDeprecationInfoIR::nothing_deprecated() }
 	}
 }
 
@@ -415,6 +437,171 @@ impl From<MetaType> for PalletErrorMetadataIR {
 	}
 }
 
+/// Metadata about a pallet associated type, i.e. a `type` item of the pallet `Config` trait.
+#[derive(Clone, PartialEq, Eq, Encode, Debug)]
+pub struct PalletAssociatedTypeMetadataIR<T: Form = MetaForm> {
+	/// The name of the associated type.
+	pub name: T::String,
+	/// The type of the associated type.
+	pub ty: T::Type,
+	/// The documentation of the associated type.
+	pub docs: Vec<T::String>,
+}
+
+impl IntoPortable for PalletAssociatedTypeMetadataIR {
+	type Output = PalletAssociatedTypeMetadataIR<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		PalletAssociatedTypeMetadataIR {
+			name: self.name.into_portable(registry),
+			ty: registry.register_type(&self.ty),
+			docs: registry.map_into_portable(self.docs),
+		}
+	}
+}
+
+/// Metadata about a pallet view function.
+#[derive(Clone, PartialEq, Eq, Encode, Debug)]
+pub struct PalletViewFunctionMetadataIR<T: Form = MetaForm> {
+	/// Method name.
+	pub name: T::String,
+	/// Method id.
+	pub id: [u8; 32],
+	/// Method parameters.
+	pub inputs: Vec<PalletViewFunctionParamMetadataIR<T>>,
+	/// Method output.
+	pub output: T::Type,
+	/// Method documentation.
+	pub docs: Vec<T::String>,
+	/// Deprecation info of the view function.
+	pub deprecation_info: ItemDeprecationInfoIR<T>,
+}
+
+impl IntoPortable for PalletViewFunctionMetadataIR {
+	type Output = PalletViewFunctionMetadataIR<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		PalletViewFunctionMetadataIR {
+			name: self.name.into_portable(registry),
+			id: self.id,
+			inputs: registry.map_into_portable(self.inputs),
+			output: registry.register_type(&self.output),
+			docs: registry.map_into_portable(self.docs),
+			deprecation_info: self.deprecation_info.into_portable(registry),
+		}
+	}
+}
+
+/// Metadata of a pallet view function parameter.
+#[derive(Clone, PartialEq, Eq, Encode, Debug)]
+pub struct PalletViewFunctionParamMetadataIR<T: Form = MetaForm> {
+	/// Parameter name.
+	pub name: T::String,
+	/// Parameter type.
+	pub ty: T::Type,
+}
+
+impl IntoPortable for PalletViewFunctionParamMetadataIR {
+	type Output = PalletViewFunctionParamMetadataIR<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		PalletViewFunctionParamMetadataIR {
+			name: self.name.into_portable(registry),
+			ty: registry.register_type(&self.ty),
+		}
+	}
+}
+
+/// Deprecation information of an item, as declared with the `#[deprecated]` attribute.
+#[derive(Clone, PartialEq, Eq, Encode, Debug)]
+pub enum ItemDeprecationInfoIR<T: Form = MetaForm> {
+	/// The item is not deprecated.
+	NotDeprecated,
+	/// The item is deprecated without a note.
+	DeprecatedWithoutNote,
+	/// The item is deprecated with a note and an optional `since` field.
+	Deprecated {
+		/// Note explaining the deprecation.
+		note: T::String,
+		/// Optional value for denoting the version when the deprecation occurred.
+		since: Option<T::String>,
+	},
+}
+
+impl IntoPortable for ItemDeprecationInfoIR {
+	type Output = ItemDeprecationInfoIR<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		match self {
+			Self::NotDeprecated => ItemDeprecationInfoIR::NotDeprecated,
+			Self::DeprecatedWithoutNote => ItemDeprecationInfoIR::DeprecatedWithoutNote,
+			Self::Deprecated { note, since } => ItemDeprecationInfoIR::Deprecated {
+				note: note.into_portable(registry),
+				since: since.map(|since| since.into_portable(registry)),
+			},
+		}
+	}
+}
+
+/// Deprecation information of an enum variant, as declared with the `#[deprecated]` attribute.
+#[derive(Clone, PartialEq, Eq, Encode, Debug)]
+pub enum VariantDeprecationInfoIR<T: Form = MetaForm> {
+	/// The variant is deprecated without a note.
+	DeprecatedWithoutNote,
+	/// The variant is deprecated with a note and an optional `since` field.
+	Deprecated {
+		/// Note explaining the deprecation.
+		note: T::String,
+		/// Optional value for denoting the version when the deprecation occurred.
+		since: Option<T::String>,
+	},
+}
+
+impl IntoPortable for VariantDeprecationInfoIR {
+	type Output = VariantDeprecationInfoIR<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		match self {
+			Self::DeprecatedWithoutNote => VariantDeprecationInfoIR::DeprecatedWithoutNote,
+			Self::Deprecated { note, since } => VariantDeprecationInfoIR::Deprecated {
+				note: note.into_portable(registry),
+				since: since.map(|since| since.into_portable(registry)),
+			},
+		}
+	}
+}
+
+/// Deprecation information of the variants of an enum, indexed by the variant index.
+///
+/// Variants that are not deprecated are not part of the map.
+#[derive(Clone, PartialEq, Eq, Encode, Debug)]
+pub struct EnumDeprecationInfoIR<T: Form = MetaForm>(pub BTreeMap<u8, VariantDeprecationInfoIR<T>>);
+
+impl<T: Form> EnumDeprecationInfoIR<T> {
+	/// Returns the deprecation info of an enum without any deprecated variant.
+	pub fn nothing_deprecated() -> Self {
+		Self(BTreeMap::new())
+	}
+
+	/// Returns whether any variant of the enum is deprecated.
+	pub fn has_deprecated_variants(&self) -> bool {
+		!self.0.is_empty()
+	}
+}
+
+impl IntoPortable for EnumDeprecationInfoIR {
+	type Output = EnumDeprecationInfoIR<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		EnumDeprecationInfoIR(
+			self.0
+				.into_iter()
+				.map(|(index, info)| (index, info.into_portable(registry)))
+				.collect(),
+		)
+	}
+}
+
 /// The type of the outer enums.
 #[derive(Clone, PartialEq, Eq, Encode, Debug)]
 pub struct OuterEnumsIR<T: Form = MetaForm> {
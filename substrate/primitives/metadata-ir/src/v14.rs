@@ -149,9 +149,15 @@ impl From<SignedExtensionMetadataIR> for SignedExtensionMetadata {
 
 impl From<ExtrinsicMetadataIR> for ExtrinsicMetadata {
 	fn from(ir: ExtrinsicMetadataIR) -> Self {
+		let lowest_supported_version = ir
+			.versions
+			.iter()
+			.min()
+			.expect("The extrinsic supports at least one version; qed");
+
 		ExtrinsicMetadata {
 			ty: ir.ty,
-			version: ir.version,
+			version: *lowest_supported_version,
 			signed_extensions: ir.signed_extensions.into_iter().map(Into::into).collect(),
 		}
 	}
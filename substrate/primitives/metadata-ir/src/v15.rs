@@ -99,8 +99,14 @@ impl From<SignedExtensionMetadataIR> for SignedExtensionMetadata {
 
 impl From<ExtrinsicMetadataIR> for ExtrinsicMetadata {
 	fn from(ir: ExtrinsicMetadataIR) -> Self {
+		let lowest_supported_version = ir
+			.versions
+			.iter()
+			.min()
+			.expect("The extrinsic supports at least one version; qed");
+
 		ExtrinsicMetadata {
-			version: ir.version,
+			version: *lowest_supported_version,
 			address_ty: ir.address_ty,
 			call_ty: ir.call_ty,
 			signature_ty: ir.signature_ty,
@@ -0,0 +1,244 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Convert the IR to V16 metadata.
+
+use super::types::{
+	EnumDeprecationInfoIR, ExtrinsicMetadataIR, ItemDeprecationInfoIR, MetadataIR,
+	PalletAssociatedTypeMetadataIR, PalletCallMetadataIR, PalletConstantMetadataIR,
+	PalletErrorMetadataIR, PalletEventMetadataIR, PalletMetadataIR, PalletStorageMetadataIR,
+	PalletViewFunctionMetadataIR, PalletViewFunctionParamMetadataIR, RuntimeApiMetadataIR,
+	RuntimeApiMethodMetadataIR, RuntimeApiMethodParamMetadataIR, SignedExtensionMetadataIR,
+	StorageEntryMetadataIR, VariantDeprecationInfoIR,
+};
+
+use frame_metadata::v16::{
+	CustomMetadata, DeprecationInfo, DeprecationStatus, ExtrinsicMetadata, OuterEnums,
+	PalletAssociatedTypeMetadata, PalletCallMetadata, PalletConstantMetadata, PalletErrorMetadata,
+	PalletEventMetadata, PalletMetadata, PalletStorageMetadata, PalletViewFunctionMetadata,
+	PalletViewFunctionParamMetadata, RuntimeApiMetadata, RuntimeApiMethodMetadata,
+	RuntimeApiMethodParamMetadata, RuntimeMetadataV16, StorageEntryMetadata,
+	TransactionExtensionMetadata,
+};
+use scale_info::prelude::{collections::BTreeMap, vec::Vec};
+
+impl From<MetadataIR> for RuntimeMetadataV16 {
+	fn from(ir: MetadataIR) -> Self {
+		RuntimeMetadataV16::new(
+			ir.pallets.into_iter().map(Into::into).collect(),
+			ir.extrinsic.into(),
+			ir.apis.into_iter().map(Into::into).collect(),
+			OuterEnums {
+				call_enum_ty: ir.outer_enums.call_enum_ty,
+				event_enum_ty: ir.outer_enums.event_enum_ty,
+				error_enum_ty: ir.outer_enums.error_enum_ty,
+			},
+			// Substrate does not collect yet the custom metadata fields.
+			// This allows us to extend the V16 easily.
+			CustomMetadata { map: Default::default() },
+		)
+	}
+}
+
+impl From<RuntimeApiMetadataIR> for RuntimeApiMetadata {
+	fn from(ir: RuntimeApiMetadataIR) -> Self {
+		RuntimeApiMetadata {
+			name: ir.name,
+			methods: ir.methods.into_iter().map(Into::into).collect(),
+			docs: ir.docs,
+			version: ir.version.0,
+			// Note: deprecation of runtime APIs is not collected yet.
+			deprecation_info: DeprecationStatus::NotDeprecated,
+		}
+	}
+}
+
+impl From<RuntimeApiMethodMetadataIR> for RuntimeApiMethodMetadata {
+	fn from(ir: RuntimeApiMethodMetadataIR) -> Self {
+		RuntimeApiMethodMetadata {
+			name: ir.name,
+			inputs: ir.inputs.into_iter().map(Into::into).collect(),
+			output: ir.output,
+			docs: ir.docs,
+			deprecation_info: DeprecationStatus::NotDeprecated,
+		}
+	}
+}
+
+impl From<RuntimeApiMethodParamMetadataIR> for RuntimeApiMethodParamMetadata {
+	fn from(ir: RuntimeApiMethodParamMetadataIR) -> Self {
+		RuntimeApiMethodParamMetadata { name: ir.name, ty: ir.ty }
+	}
+}
+
+impl From<PalletMetadataIR> for PalletMetadata {
+	fn from(ir: PalletMetadataIR) -> Self {
+		PalletMetadata {
+			name: ir.name,
+			storage: ir.storage.map(Into::into),
+			calls: ir.calls.map(Into::into),
+			event: ir.event.map(Into::into),
+			constants: ir.constants.into_iter().map(Into::into).collect(),
+			error: ir.error.map(Into::into),
+			associated_types: ir.associated_types.into_iter().map(Into::into).collect(),
+			view_functions: ir.view_functions.into_iter().map(Into::into).collect(),
+			index: ir.index,
+			docs: ir.docs,
+			// Note: deprecation of whole pallets is not collected yet.
+			deprecation_info: DeprecationStatus::NotDeprecated,
+		}
+	}
+}
+
+impl From<PalletStorageMetadataIR> for PalletStorageMetadata {
+	fn from(ir: PalletStorageMetadataIR) -> Self {
+		PalletStorageMetadata {
+			prefix: ir.prefix,
+			entries: ir.entries.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+impl From<StorageEntryMetadataIR> for StorageEntryMetadata {
+	fn from(ir: StorageEntryMetadataIR) -> Self {
+		StorageEntryMetadata {
+			name: ir.name,
+			modifier: ir.modifier.into(),
+			ty: ir.ty.into(),
+			default: ir.default,
+			docs: ir.docs,
+			deprecation_info: ir.deprecation_info.into(),
+		}
+	}
+}
+
+impl From<PalletCallMetadataIR> for PalletCallMetadata {
+	fn from(ir: PalletCallMetadataIR) -> Self {
+		PalletCallMetadata { ty: ir.ty, deprecation_info: ir.deprecation_info.into() }
+	}
+}
+
+impl From<PalletEventMetadataIR> for PalletEventMetadata {
+	fn from(ir: PalletEventMetadataIR) -> Self {
+		PalletEventMetadata { ty: ir.ty, deprecation_info: ir.deprecation_info.into() }
+	}
+}
+
+impl From<PalletConstantMetadataIR> for PalletConstantMetadata {
+	fn from(ir: PalletConstantMetadataIR) -> Self {
+		PalletConstantMetadata {
+			name: ir.name,
+			ty: ir.ty,
+			value: ir.value,
+			docs: ir.docs,
+			deprecation_info: DeprecationStatus::NotDeprecated,
+		}
+	}
+}
+
+impl From<PalletErrorMetadataIR> for PalletErrorMetadata {
+	fn from(ir: PalletErrorMetadataIR) -> Self {
+		PalletErrorMetadata { ty: ir.ty, deprecation_info: DeprecationInfo::NotDeprecated }
+	}
+}
+
+impl From<PalletAssociatedTypeMetadataIR> for PalletAssociatedTypeMetadata {
+	fn from(ir: PalletAssociatedTypeMetadataIR) -> Self {
+		PalletAssociatedTypeMetadata { name: ir.name, ty: ir.ty, docs: ir.docs }
+	}
+}
+
+impl From<PalletViewFunctionMetadataIR> for PalletViewFunctionMetadata {
+	fn from(ir: PalletViewFunctionMetadataIR) -> Self {
+		PalletViewFunctionMetadata {
+			name: ir.name,
+			id: ir.id,
+			inputs: ir.inputs.into_iter().map(Into::into).collect(),
+			output: ir.output,
+			docs: ir.docs,
+			deprecation_info: ir.deprecation_info.into(),
+		}
+	}
+}
+
+impl From<PalletViewFunctionParamMetadataIR> for PalletViewFunctionParamMetadata {
+	fn from(ir: PalletViewFunctionParamMetadataIR) -> Self {
+		PalletViewFunctionParamMetadata { name: ir.name, ty: ir.ty }
+	}
+}
+
+impl From<ItemDeprecationInfoIR> for DeprecationStatus {
+	fn from(ir: ItemDeprecationInfoIR) -> Self {
+		match ir {
+			ItemDeprecationInfoIR::NotDeprecated => DeprecationStatus::NotDeprecated,
+			ItemDeprecationInfoIR::DeprecatedWithoutNote =>
+				DeprecationStatus::DeprecatedWithoutNote,
+			ItemDeprecationInfoIR::Deprecated { note, since } =>
+				DeprecationStatus::Deprecated { note, since },
+		}
+	}
+}
+
+impl From<VariantDeprecationInfoIR> for DeprecationStatus {
+	fn from(ir: VariantDeprecationInfoIR) -> Self {
+		match ir {
+			VariantDeprecationInfoIR::DeprecatedWithoutNote =>
+				DeprecationStatus::DeprecatedWithoutNote,
+			VariantDeprecationInfoIR::Deprecated { note, since } =>
+				DeprecationStatus::Deprecated { note, since },
+		}
+	}
+}
+
+impl From<EnumDeprecationInfoIR> for DeprecationInfo {
+	fn from(ir: EnumDeprecationInfoIR) -> Self {
+		if ir.has_deprecated_variants() {
+			DeprecationInfo::VariantsDeprecated(
+				ir.0.into_iter().map(|(index, info)| (index, info.into())).collect(),
+			)
+		} else {
+			DeprecationInfo::NotDeprecated
+		}
+	}
+}
+
+impl From<SignedExtensionMetadataIR> for TransactionExtensionMetadata {
+	fn from(ir: SignedExtensionMetadataIR) -> Self {
+		TransactionExtensionMetadata {
+			identifier: ir.identifier,
+			ty: ir.ty,
+			implicit: ir.additional_signed,
+		}
+	}
+}
+
+impl From<ExtrinsicMetadataIR> for ExtrinsicMetadata {
+	fn from(ir: ExtrinsicMetadataIR) -> Self {
+		// All transaction extensions belong to the only supported extension version `0`.
+		let indexes = (0..ir.signed_extensions.len() as u32).map(Into::into).collect::<Vec<_>>();
+		let mut transaction_extensions_by_version = BTreeMap::new();
+		transaction_extensions_by_version.insert(0, indexes);
+
+		ExtrinsicMetadata {
+			versions: ir.versions,
+			address_ty: ir.address_ty,
+			signature_ty: ir.signature_ty,
+			transaction_extensions_by_version,
+			transaction_extensions: ir.signed_extensions.into_iter().map(Into::into).collect(),
+		}
+	}
+}
@@ -18,10 +18,10 @@
 //! Generic implementation of an unchecked (pre-verification) extrinsic.
 
 use crate::{
-	generic::CheckedExtrinsic,
+	generic::{CheckedExtrinsic, ExtrinsicFormat},
 	traits::{
 		self, Checkable, Extrinsic, ExtrinsicMetadata, IdentifyAccount, MaybeDisplay, Member,
-		SignaturePayload, SignedExtension,
+		SignaturePayload, TransactionExtensionBase,
 	},
 	transaction_validity::{InvalidTransaction, TransactionValidityError},
 	OpaqueExtrinsic,
@@ -33,15 +33,146 @@ use sp_io::hashing::blake2_256;
 use sp_std::alloc::format;
 use sp_std::{fmt, prelude::*};
 
-/// Current version of the [`UncheckedExtrinsic`] encoded format.
+/// Current version of the [`UncheckedExtrinsic`] encoded format of bare and signed extrinsics.
 ///
 /// This version needs to be bumped if the encoded representation changes.
 /// It ensures that if the representation is changed and the format is not known,
 /// the decoding fails.
 const EXTRINSIC_FORMAT_VERSION: u8 = 4;
 
+/// Current version of the [`UncheckedExtrinsic`] encoded format of general extrinsics.
+const GENERAL_EXTRINSIC_FORMAT_VERSION: u8 = 5;
+
+/// Current version of the transaction extensions of general extrinsics.
+///
+/// This version is encoded right after the extrinsic format version of a general extrinsic, so
+/// that the set of transaction extensions can change without changing the extrinsic format.
+const EXTENSION_VERSION: u8 = 0;
+
+/// The bits of the first byte of an encoded extrinsic which hold its type.
+const TYPE_MASK: u8 = 0b1100_0000;
+/// The bits of the first byte of an encoded extrinsic which hold its format version.
+const VERSION_MASK: u8 = 0b0011_1111;
+/// The type bits of a bare extrinsic.
+const BARE_EXTRINSIC: u8 = 0b0000_0000;
+/// The type bits of a signed extrinsic.
+const SIGNED_EXTRINSIC: u8 = 0b1000_0000;
+/// The type bits of a general extrinsic.
+const GENERAL_EXTRINSIC: u8 = 0b0100_0000;
+
 /// The `SingaturePayload` of `UncheckedExtrinsic`.
-type UncheckedSignaturePayload<Address, Signature, Extra> = (Address, Signature, Extra);
+type UncheckedSignaturePayload<Address, Signature, Extension> = (Address, Signature, Extension);
+
+impl<Address: TypeInfo, Signature: TypeInfo, Extension: TypeInfo> SignaturePayload
+	for UncheckedSignaturePayload<Address, Signature, Extension>
+{
+	type SignatureAddress = Address;
+	type Signature = Signature;
+	type SignatureExtra = Extension;
+}
+
+/// A "header" for extrinsics leading up to the call itself. Determines the type of extrinsic and
+/// holds any necessary specialized data.
+#[derive(Eq, PartialEq, Clone)]
+pub enum Preamble<Address, Signature, Extension> {
+	/// An extrinsic without a signature or any extension. This means it's either an inherent or
+	/// an old-school "Unsigned" (we don't use that terminology any more since it's confusable with
+	/// the general transaction which is without a signature but does have extensions).
+	///
+	/// NOTE: In the future, once we remove `ValidateUnsigned`, this will only serve Inherent
+	/// extrinsics and thus can be renamed to `Inherent`.
+	Bare,
+	/// A transaction signed by the given address, together with the transaction extensions which
+	/// were signed together with the call.
+	Signed(Address, Signature, Extension),
+	/// A transaction without a signature but with transaction extensions. The extensions are
+	/// responsible for authorizing the origin of the transaction.
+	General(Extension),
+}
+
+impl<Address, Signature, Extension> Preamble<Address, Signature, Extension> {
+	/// Returns `Some` if this is a signed extrinsic, together with the relevant inner fields.
+	pub fn to_signed(self) -> Option<(Address, Signature, Extension)> {
+		match self {
+			Self::Signed(address, signature, extension) => Some((address, signature, extension)),
+			_ => None,
+		}
+	}
+}
+
+impl<Address, Signature, Extension> Decode for Preamble<Address, Signature, Extension>
+where
+	Address: Decode,
+	Signature: Decode,
+	Extension: Decode,
+{
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let version = input.read_byte()?;
+
+		Ok(match (version & TYPE_MASK, version & VERSION_MASK) {
+			(BARE_EXTRINSIC, EXTRINSIC_FORMAT_VERSION) => Self::Bare,
+			(SIGNED_EXTRINSIC, EXTRINSIC_FORMAT_VERSION) => {
+				let (address, signature, extension) = Decode::decode(input)?;
+				Self::Signed(address, signature, extension)
+			},
+			(GENERAL_EXTRINSIC, GENERAL_EXTRINSIC_FORMAT_VERSION) => {
+				if input.read_byte()? != EXTENSION_VERSION {
+					return Err("Invalid transaction extension version".into())
+				}
+				Self::General(Decode::decode(input)?)
+			},
+			_ => return Err("Invalid transaction version".into()),
+		})
+	}
+}
+
+impl<Address, Signature, Extension> Encode for Preamble<Address, Signature, Extension>
+where
+	Address: Encode,
+	Signature: Encode,
+	Extension: Encode,
+{
+	fn size_hint(&self) -> usize {
+		match self {
+			Self::Bare => 1,
+			Self::Signed(address, signature, extension) =>
+				1 + address.size_hint() + signature.size_hint() + extension.size_hint(),
+			Self::General(extension) => 2 + extension.size_hint(),
+		}
+	}
+
+	fn encode_to<T: codec::Output + ?Sized>(&self, dest: &mut T) {
+		match self {
+			Self::Bare => dest.push_byte(BARE_EXTRINSIC | EXTRINSIC_FORMAT_VERSION),
+			Self::Signed(address, signature, extension) => {
+				dest.push_byte(SIGNED_EXTRINSIC | EXTRINSIC_FORMAT_VERSION);
+				address.encode_to(dest);
+				signature.encode_to(dest);
+				extension.encode_to(dest);
+			},
+			Self::General(extension) => {
+				dest.push_byte(GENERAL_EXTRINSIC | GENERAL_EXTRINSIC_FORMAT_VERSION);
+				dest.push_byte(EXTENSION_VERSION);
+				extension.encode_to(dest);
+			},
+		}
+	}
+}
+
+impl<Address, Signature, Extension> fmt::Debug for Preamble<Address, Signature, Extension>
+where
+	Address: fmt::Debug,
+	Extension: fmt::Debug,
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Bare => write!(f, "Bare"),
+			Self::Signed(address, _, extension) =>
+				write!(f, "Signed({:?}, {:?})", address, extension),
+			Self::General(extension) => write!(f, "General({:?})", extension),
+		}
+	}
+}
 
 /// An extrinsic right from the external world. This is unchecked and so can contain a signature.
 ///
@@ -54,9 +185,10 @@ type UncheckedSignaturePayload<Address, Signature, Extra> = (Address, Signature,
 ///
 /// Transactions are all other statements provided by external entities that the chain deems values
 /// and decided to include in the block. This value is typically in the form of fee payment, but it
-/// could in principle be any other interaction. Transactions are either signed or unsigned. A
-/// sensible transaction pool should ensure that only transactions that are worthwhile are
-/// considered for block-building.
+/// could in principle be any other interaction. Transactions are either signed, general or
+/// unsigned: general transactions carry transaction extensions but no signature, their extensions
+/// are responsible for authorizing them. A sensible transaction pool should ensure that only
+/// transactions that are worthwhile are considered for block-building.
 #[cfg_attr(feature = "std", doc = simple_mermaid::mermaid!("../../docs/mermaid/extrinsics.mmd"))]
 /// This type is by no means enforced within Substrate, but given its genericness, it is highly
 /// likely that for most use-cases it will suffice. Thus, the encoding of this type will dictate
@@ -66,40 +198,27 @@ type UncheckedSignaturePayload<Address, Signature, Extra> = (Address, Signature,
 /// counterpart of this type after its signature (and other non-negotiable validity checks) have
 /// passed.
 #[derive(PartialEq, Eq, Clone)]
-pub struct UncheckedExtrinsic<Address, Call, Signature, Extra>
-where
-	Extra: SignedExtension,
-{
-	/// The signature, address, number of extrinsics have come before from the same signer and an
-	/// era describing the longevity of this transaction, if this is a signed extrinsic.
-	///
-	/// `None` if it is unsigned or an inherent.
-	pub signature: Option<UncheckedSignaturePayload<Address, Signature, Extra>>,
+pub struct UncheckedExtrinsic<Address, Call, Signature, Extension> {
+	/// Information regarding the type of extrinsic this is (inherent or transaction) as well as
+	/// associated extension (`Extension`) data if it's a transaction and a possible signature.
+	pub preamble: Preamble<Address, Signature, Extension>,
 	/// The function that should be called.
 	pub function: Call,
 }
 
-impl<Address: TypeInfo, Signature: TypeInfo, Extra: TypeInfo> SignaturePayload
-	for UncheckedSignaturePayload<Address, Signature, Extra>
-{
-	type SignatureAddress = Address;
-	type Signature = Signature;
-	type SignatureExtra = Extra;
-}
-
 /// Manual [`TypeInfo`] implementation because of custom encoding. The data is a valid encoded
 /// `Vec<u8>`, but requires some logic to extract the signature and payload.
 ///
 /// See [`UncheckedExtrinsic::encode`] and [`UncheckedExtrinsic::decode`].
-impl<Address, Call, Signature, Extra> TypeInfo
-	for UncheckedExtrinsic<Address, Call, Signature, Extra>
+impl<Address, Call, Signature, Extension> TypeInfo
+	for UncheckedExtrinsic<Address, Call, Signature, Extension>
 where
 	Address: StaticTypeInfo,
 	Call: StaticTypeInfo,
 	Signature: StaticTypeInfo,
-	Extra: SignedExtension + StaticTypeInfo,
+	Extension: StaticTypeInfo,
 {
-	type Identity = UncheckedExtrinsic<Address, Call, Signature, Extra>;
+	type Identity = UncheckedExtrinsic<Address, Call, Signature, Extension>;
 
 	fn type_info() -> Type {
 		Type::builder()
@@ -111,7 +230,7 @@ where
 				TypeParameter::new("Address", Some(meta_type::<Address>())),
 				TypeParameter::new("Call", Some(meta_type::<Call>())),
 				TypeParameter::new("Signature", Some(meta_type::<Signature>())),
-				TypeParameter::new("Extra", Some(meta_type::<Extra>())),
+				TypeParameter::new("Extra", Some(meta_type::<Extension>())),
 			])
 			.docs(&["UncheckedExtrinsic raw bytes, requires custom decoding routine"])
 			// Because of the custom encoding, we can only accurately describe the encoding as an
@@ -121,66 +240,84 @@ where
 	}
 }
 
-impl<Address, Call, Signature, Extra: SignedExtension>
-	UncheckedExtrinsic<Address, Call, Signature, Extra>
-{
+impl<Address, Call, Signature, Extension> UncheckedExtrinsic<Address, Call, Signature, Extension> {
 	/// New instance of a signed extrinsic aka "transaction".
-	pub fn new_signed(function: Call, signed: Address, signature: Signature, extra: Extra) -> Self {
-		Self { signature: Some((signed, signature, extra)), function }
+	pub fn new_signed(
+		function: Call,
+		signed: Address,
+		signature: Signature,
+		extension: Extension,
+	) -> Self {
+		Self { preamble: Preamble::Signed(signed, signature, extension), function }
+	}
+
+	/// New instance of a general transaction, which carries transaction extensions but no
+	/// signature.
+	pub fn new_transaction(function: Call, extension: Extension) -> Self {
+		Self { preamble: Preamble::General(extension), function }
 	}
 
 	/// New instance of an unsigned extrinsic aka "inherent".
 	pub fn new_unsigned(function: Call) -> Self {
-		Self { signature: None, function }
+		Self { preamble: Preamble::Bare, function }
 	}
 }
 
-impl<Address: TypeInfo, Call: TypeInfo, Signature: TypeInfo, Extra: SignedExtension + TypeInfo>
-	Extrinsic for UncheckedExtrinsic<Address, Call, Signature, Extra>
+impl<Address: TypeInfo, Call: TypeInfo, Signature: TypeInfo, Extension: TypeInfo> Extrinsic
+	for UncheckedExtrinsic<Address, Call, Signature, Extension>
 {
 	type Call = Call;
 
-	type SignaturePayload = UncheckedSignaturePayload<Address, Signature, Extra>;
+	type SignaturePayload = UncheckedSignaturePayload<Address, Signature, Extension>;
 
 	fn is_signed(&self) -> Option<bool> {
-		Some(self.signature.is_some())
+		Some(matches!(self.preamble, Preamble::Signed(..)))
+	}
+
+	fn is_bare(&self) -> bool {
+		matches!(self.preamble, Preamble::Bare)
 	}
 
 	fn new(function: Call, signed_data: Option<Self::SignaturePayload>) -> Option<Self> {
-		Some(if let Some((address, signature, extra)) = signed_data {
-			Self::new_signed(function, address, signature, extra)
+		Some(if let Some((address, signature, extension)) = signed_data {
+			Self::new_signed(function, address, signature, extension)
 		} else {
 			Self::new_unsigned(function)
 		})
 	}
 }
 
-impl<LookupSource, AccountId, Call, Signature, Extra, Lookup> Checkable<Lookup>
-	for UncheckedExtrinsic<LookupSource, Call, Signature, Extra>
+impl<LookupSource, AccountId, Call, Signature, Extension, Lookup> Checkable<Lookup>
+	for UncheckedExtrinsic<LookupSource, Call, Signature, Extension>
 where
 	LookupSource: Member + MaybeDisplay,
 	Call: Encode + Member,
 	Signature: Member + traits::Verify,
 	<Signature as traits::Verify>::Signer: IdentifyAccount<AccountId = AccountId>,
-	Extra: SignedExtension<AccountId = AccountId>,
+	Extension: TransactionExtensionBase,
 	AccountId: Member + MaybeDisplay,
 	Lookup: traits::Lookup<Source = LookupSource, Target = AccountId>,
 {
-	type Checked = CheckedExtrinsic<AccountId, Call, Extra>;
+	type Checked = CheckedExtrinsic<AccountId, Call, Extension>;
 
 	fn check(self, lookup: &Lookup) -> Result<Self::Checked, TransactionValidityError> {
-		Ok(match self.signature {
-			Some((signed, signature, extra)) => {
+		Ok(match self.preamble {
+			Preamble::Signed(signed, signature, extension) => {
 				let signed = lookup.lookup(signed)?;
-				let raw_payload = SignedPayload::new(self.function, extra)?;
+				let raw_payload = SignedPayload::new(self.function, extension)?;
 				if !raw_payload.using_encoded(|payload| signature.verify(payload, &signed)) {
 					return Err(InvalidTransaction::BadProof.into())
 				}
 
-				let (function, extra, _) = raw_payload.deconstruct();
-				CheckedExtrinsic { signed: Some((signed, extra)), function }
+				let (function, extension, _) = raw_payload.deconstruct();
+				CheckedExtrinsic { format: ExtrinsicFormat::Signed(signed, extension), function }
 			},
-			None => CheckedExtrinsic { signed: None, function: self.function },
+			Preamble::General(extension) => CheckedExtrinsic {
+				format: ExtrinsicFormat::General(extension),
+				function: self.function,
+			},
+			Preamble::Bare =>
+				CheckedExtrinsic { format: ExtrinsicFormat::Bare, function: self.function },
 		})
 	}
 
@@ -189,25 +326,31 @@ where
 		self,
 		lookup: &Lookup,
 	) -> Result<Self::Checked, TransactionValidityError> {
-		Ok(match self.signature {
-			Some((signed, _, extra)) => {
+		Ok(match self.preamble {
+			Preamble::Signed(signed, _, extension) => {
 				let signed = lookup.lookup(signed)?;
-				let raw_payload = SignedPayload::new(self.function, extra)?;
-				let (function, extra, _) = raw_payload.deconstruct();
-				CheckedExtrinsic { signed: Some((signed, extra)), function }
+				CheckedExtrinsic {
+					format: ExtrinsicFormat::Signed(signed, extension),
+					function: self.function,
+				}
+			},
+			Preamble::General(extension) => CheckedExtrinsic {
+				format: ExtrinsicFormat::General(extension),
+				function: self.function,
 			},
-			None => CheckedExtrinsic { signed: None, function: self.function },
+			Preamble::Bare =>
+				CheckedExtrinsic { format: ExtrinsicFormat::Bare, function: self.function },
 		})
 	}
 }
 
-impl<Address, Call, Signature, Extra> ExtrinsicMetadata
-	for UncheckedExtrinsic<Address, Call, Signature, Extra>
+impl<Address, Call, Signature, Extension> ExtrinsicMetadata
+	for UncheckedExtrinsic<Address, Call, Signature, Extension>
 where
-	Extra: SignedExtension,
+	Extension: TransactionExtensionBase,
 {
-	const VERSION: u8 = EXTRINSIC_FORMAT_VERSION;
-	type SignedExtensions = Extra;
+	const VERSIONS: &'static [u8] = &[EXTRINSIC_FORMAT_VERSION, GENERAL_EXTRINSIC_FORMAT_VERSION];
+	type TransactionExtensions = Extension;
 }
 
 /// A payload that has been signed for an unchecked extrinsics.
@@ -215,37 +358,39 @@ where
 /// Note that the payload that we sign to produce unchecked extrinsic signature
 /// is going to be different than the `SignaturePayload` - so the thing the extrinsic
 /// actually contains.
-pub struct SignedPayload<Call, Extra: SignedExtension>((Call, Extra, Extra::AdditionalSigned));
+pub struct SignedPayload<Call, Extension: TransactionExtensionBase>(
+	(Call, Extension, Extension::Implicit),
+);
 
-impl<Call, Extra> SignedPayload<Call, Extra>
+impl<Call, Extension> SignedPayload<Call, Extension>
 where
 	Call: Encode,
-	Extra: SignedExtension,
+	Extension: TransactionExtensionBase,
 {
 	/// Create new `SignedPayload`.
 	///
-	/// This function may fail if `additional_signed` of `Extra` is not available.
-	pub fn new(call: Call, extra: Extra) -> Result<Self, TransactionValidityError> {
-		let additional_signed = extra.additional_signed()?;
-		let raw_payload = (call, extra, additional_signed);
+	/// This function may fail if `implicit` of `Extension` is not available.
+	pub fn new(call: Call, extension: Extension) -> Result<Self, TransactionValidityError> {
+		let implicit = extension.implicit()?;
+		let raw_payload = (call, extension, implicit);
 		Ok(Self(raw_payload))
 	}
 
 	/// Create new `SignedPayload` from raw components.
-	pub fn from_raw(call: Call, extra: Extra, additional_signed: Extra::AdditionalSigned) -> Self {
-		Self((call, extra, additional_signed))
+	pub fn from_raw(call: Call, extension: Extension, implicit: Extension::Implicit) -> Self {
+		Self((call, extension, implicit))
 	}
 
 	/// Deconstruct the payload into it's components.
-	pub fn deconstruct(self) -> (Call, Extra, Extra::AdditionalSigned) {
+	pub fn deconstruct(self) -> (Call, Extension, Extension::Implicit) {
 		self.0
 	}
 }
 
-impl<Call, Extra> Encode for SignedPayload<Call, Extra>
+impl<Call, Extension> Encode for SignedPayload<Call, Extension>
 where
 	Call: Encode,
-	Extra: SignedExtension,
+	Extension: TransactionExtensionBase,
 {
 	/// Get an encoded version of this payload.
 	///
@@ -261,19 +406,20 @@ where
 	}
 }
 
-impl<Call, Extra> EncodeLike for SignedPayload<Call, Extra>
+impl<Call, Extension> EncodeLike for SignedPayload<Call, Extension>
 where
 	Call: Encode,
-	Extra: SignedExtension,
+	Extension: TransactionExtensionBase,
 {
 }
 
-impl<Address, Call, Signature, Extra> Decode for UncheckedExtrinsic<Address, Call, Signature, Extra>
+impl<Address, Call, Signature, Extension> Decode
+	for UncheckedExtrinsic<Address, Call, Signature, Extension>
 where
 	Address: Decode,
 	Signature: Decode,
 	Call: Decode,
-	Extra: SignedExtension,
+	Extension: Decode,
 {
 	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
 		// This is a little more complicated than usual since the binary format must be compatible
@@ -282,15 +428,7 @@ where
 		let expected_length: Compact<u32> = Decode::decode(input)?;
 		let before_length = input.remaining_len()?;
 
-		let version = input.read_byte()?;
-
-		let is_signed = version & 0b1000_0000 != 0;
-		let version = version & 0b0111_1111;
-		if version != EXTRINSIC_FORMAT_VERSION {
-			return Err("Invalid transaction version".into())
-		}
-
-		let signature = is_signed.then(|| Decode::decode(input)).transpose()?;
+		let preamble = Decode::decode(input)?;
 		let function = Decode::decode(input)?;
 
 		if let Some((before_length, after_length)) =
@@ -303,31 +441,24 @@ where
 			}
 		}
 
-		Ok(Self { signature, function })
+		Ok(Self { preamble, function })
 	}
 }
 
 #[docify::export(unchecked_extrinsic_encode_impl)]
-impl<Address, Call, Signature, Extra> Encode for UncheckedExtrinsic<Address, Call, Signature, Extra>
+impl<Address, Call, Signature, Extension> Encode
+	for UncheckedExtrinsic<Address, Call, Signature, Extension>
 where
 	Address: Encode,
 	Signature: Encode,
 	Call: Encode,
-	Extra: SignedExtension,
+	Extension: Encode,
 {
 	fn encode(&self) -> Vec<u8> {
 		let mut tmp = Vec::with_capacity(sp_std::mem::size_of::<Self>());
 
-		// 1 byte version id.
-		match self.signature.as_ref() {
-			Some(s) => {
-				tmp.push(EXTRINSIC_FORMAT_VERSION | 0b1000_0000);
-				s.encode_to(&mut tmp);
-			},
-			None => {
-				tmp.push(EXTRINSIC_FORMAT_VERSION & 0b0111_1111);
-			},
-		}
+		// 1 byte version id, followed by the data of the preamble.
+		self.preamble.encode_to(&mut tmp);
 		self.function.encode_to(&mut tmp);
 
 		let compact_len = codec::Compact::<u32>(tmp.len() as u32);
@@ -342,19 +473,19 @@ where
 	}
 }
 
-impl<Address, Call, Signature, Extra> EncodeLike
-	for UncheckedExtrinsic<Address, Call, Signature, Extra>
+impl<Address, Call, Signature, Extension> EncodeLike
+	for UncheckedExtrinsic<Address, Call, Signature, Extension>
 where
 	Address: Encode,
 	Signature: Encode,
 	Call: Encode,
-	Extra: SignedExtension,
+	Extension: Encode,
 {
 }
 
 #[cfg(feature = "serde")]
-impl<Address: Encode, Signature: Encode, Call: Encode, Extra: SignedExtension> serde::Serialize
-	for UncheckedExtrinsic<Address, Call, Signature, Extra>
+impl<Address: Encode, Signature: Encode, Call: Encode, Extension: Encode> serde::Serialize
+	for UncheckedExtrinsic<Address, Call, Signature, Extension>
 {
 	fn serialize<S>(&self, seq: S) -> Result<S::Ok, S::Error>
 	where
@@ -365,8 +496,8 @@ impl<Address: Encode, Signature: Encode, Call: Encode, Extra: SignedExtension> s
 }
 
 #[cfg(feature = "serde")]
-impl<'a, Address: Decode, Signature: Decode, Call: Decode, Extra: SignedExtension>
-	serde::Deserialize<'a> for UncheckedExtrinsic<Address, Call, Signature, Extra>
+impl<'a, Address: Decode, Signature: Decode, Call: Decode, Extension: Decode> serde::Deserialize<'a>
+	for UncheckedExtrinsic<Address, Call, Signature, Extension>
 {
 	fn deserialize<D>(de: D) -> Result<Self, D::Error>
 	where
@@ -378,32 +509,27 @@ impl<'a, Address: Decode, Signature: Decode, Call: Decode, Extra: SignedExtensio
 	}
 }
 
-impl<Address, Call, Signature, Extra> fmt::Debug
-	for UncheckedExtrinsic<Address, Call, Signature, Extra>
+impl<Address, Call, Signature, Extension> fmt::Debug
+	for UncheckedExtrinsic<Address, Call, Signature, Extension>
 where
 	Address: fmt::Debug,
 	Call: fmt::Debug,
-	Extra: SignedExtension,
+	Extension: fmt::Debug,
 {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(
-			f,
-			"UncheckedExtrinsic({:?}, {:?})",
-			self.signature.as_ref().map(|x| (&x.0, &x.2)),
-			self.function,
-		)
+		write!(f, "UncheckedExtrinsic({:?}, {:?})", self.preamble, self.function)
 	}
 }
 
-impl<Address, Call, Signature, Extra> From<UncheckedExtrinsic<Address, Call, Signature, Extra>>
-	for OpaqueExtrinsic
+impl<Address, Call, Signature, Extension>
+	From<UncheckedExtrinsic<Address, Call, Signature, Extension>> for OpaqueExtrinsic
 where
 	Address: Encode,
 	Signature: Encode,
 	Call: Encode,
-	Extra: SignedExtension,
+	Extension: Encode,
 {
-	fn from(extrinsic: UncheckedExtrinsic<Address, Call, Signature, Extra>) -> Self {
+	fn from(extrinsic: UncheckedExtrinsic<Address, Call, Signature, Extension>) -> Self {
 		Self::from_bytes(extrinsic.encode().as_slice()).expect(
 			"both OpaqueExtrinsic and UncheckedExtrinsic have encoding that is compatible with \
 				raw Vec<u8> encoding; qed",
@@ -417,7 +543,7 @@ mod tests {
 	use crate::{
 		codec::{Decode, Encode},
 		testing::TestSignature as TestSig,
-		traits::{DispatchInfoOf, IdentityLookup, SignedExtension},
+		traits::IdentityLookup,
 	};
 	use sp_io::hashing::blake2_256;
 
@@ -430,26 +556,13 @@ mod tests {
 	// NOTE: this is demonstration. One can simply use `()` for testing.
 	#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, Ord, PartialOrd, TypeInfo)]
 	struct TestExtra;
-	impl SignedExtension for TestExtra {
+	impl TransactionExtensionBase for TestExtra {
 		const IDENTIFIER: &'static str = "TestExtra";
-		type AccountId = u64;
-		type Call = ();
-		type AdditionalSigned = ();
-		type Pre = ();
+		type Implicit = ();
 
-		fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
+		fn implicit(&self) -> sp_std::result::Result<(), TransactionValidityError> {
 			Ok(())
 		}
-
-		fn pre_dispatch(
-			self,
-			who: &Self::AccountId,
-			call: &Self::Call,
-			info: &DispatchInfoOf<Self::Call>,
-			len: usize,
-		) -> Result<Self::Pre, TransactionValidityError> {
-			self.validate(who, call, info, len).map(|_| ())
-		}
 	}
 
 	type Ex = UncheckedExtrinsic<TestAccountId, TestCall, TestSig, TestExtra>;
@@ -500,10 +613,50 @@ mod tests {
 		assert_eq!(Ex::decode(&mut &encoded[..]), Ok(ux));
 	}
 
+	#[test]
+	fn general_codec_should_work() {
+		let ux = Ex::new_transaction(vec![0u8; 0], TestExtra);
+		let encoded = ux.encode();
+		assert_eq!(&encoded[1..3], &[0b0100_0101, 0]);
+		assert_eq!(Ex::decode(&mut &encoded[..]), Ok(ux));
+	}
+
+	#[test]
+	fn unknown_extension_version_is_rejected() {
+		let ux = Ex::new_transaction(vec![0u8; 0], TestExtra);
+		let mut encoded = ux.encode();
+		encoded[2] = 1;
+		assert_eq!(
+			Ex::decode(&mut &encoded[..]),
+			Err("Invalid transaction extension version".into())
+		);
+	}
+
+	#[test]
+	fn legacy_versions_are_kept() {
+		let bare = Ex::new_unsigned(vec![0u8; 0]).encode();
+		assert_eq!(bare[1], 0b0000_0100);
+
+		let signed = Ex::new_signed(
+			vec![0u8; 0],
+			TEST_ACCOUNT,
+			TestSig(TEST_ACCOUNT, (vec![0u8; 0], TestExtra).encode()),
+			TestExtra,
+		)
+		.encode();
+		assert_eq!(signed[1], 0b1000_0100);
+
+		// A general extrinsic needs the general format version.
+		let mut general = Ex::new_transaction(vec![0u8; 0], TestExtra).encode();
+		general[1] = 0b0100_0100;
+		assert_eq!(Ex::decode(&mut &general[..]), Err("Invalid transaction version".into()));
+	}
+
 	#[test]
 	fn unsigned_check_should_work() {
 		let ux = Ex::new_unsigned(vec![0u8; 0]);
 		assert!(!ux.is_signed().unwrap_or(false));
+		assert!(ux.is_bare());
 		assert!(<Ex as Checkable<TestContext>>::check(ux, &Default::default()).is_ok());
 	}
 
@@ -533,7 +686,21 @@ mod tests {
 		assert!(ux.is_signed().unwrap_or(false));
 		assert_eq!(
 			<Ex as Checkable<TestContext>>::check(ux, &Default::default()),
-			Ok(CEx { signed: Some((TEST_ACCOUNT, TestExtra)), function: vec![0u8; 0] }),
+			Ok(CEx {
+				format: ExtrinsicFormat::Signed(TEST_ACCOUNT, TestExtra),
+				function: vec![0u8; 0]
+			}),
+		);
+	}
+
+	#[test]
+	fn general_check_should_work() {
+		let ux = Ex::new_transaction(vec![0u8; 0], TestExtra);
+		assert!(!ux.is_signed().unwrap_or(false));
+		assert!(!ux.is_bare());
+		assert_eq!(
+			<Ex as Checkable<TestContext>>::check(ux, &Default::default()),
+			Ok(CEx { format: ExtrinsicFormat::General(TestExtra), function: vec![0u8; 0] }),
 		);
 	}
 
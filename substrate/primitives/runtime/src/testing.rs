@@ -22,8 +22,9 @@ use crate::{
 	generic,
 	scale_info::TypeInfo,
 	traits::{
-		self, Applyable, BlakeTwo256, Checkable, DispatchInfoOf, Dispatchable, OpaqueKeys,
-		PostDispatchInfoOf, SignaturePayload, SignedExtension, ValidateUnsigned,
+		self, Applyable, AsTransactionAuthorizedOrigin, BlakeTwo256, Checkable, DispatchInfoOf,
+		DispatchTransaction, Dispatchable, OpaqueKeys, PostDispatchInfoOf, SignaturePayload,
+		TransactionExtension, TransactionExtensionBase, ValidateUnsigned,
 	},
 	transaction_validity::{TransactionSource, TransactionValidity, TransactionValidityError},
 	ApplyExtrinsicResultWithInfo, KeyTypeId,
@@ -209,6 +210,10 @@ impl<Xt> traits::Extrinsic for ExtrinsicWrapper<Xt> {
 	fn is_signed(&self) -> Option<bool> {
 		None
 	}
+
+	fn is_bare(&self) -> bool {
+		false
+	}
 }
 
 impl<Xt: Encode> serde::Serialize for ExtrinsicWrapper<Xt> {
@@ -354,6 +359,10 @@ impl<Call: Codec + Sync + Send + TypeInfo, Extra: TypeInfo> traits::Extrinsic
 		Some(self.signature.is_some())
 	}
 
+	fn is_bare(&self) -> bool {
+		self.signature.is_none()
+	}
+
 	fn new(c: Call, sig: Option<Self::SignaturePayload>) -> Option<Self> {
 		Some(TestXt { signature: sig, call: c })
 	}
@@ -362,10 +371,10 @@ impl<Call: Codec + Sync + Send + TypeInfo, Extra: TypeInfo> traits::Extrinsic
 impl<Call, Extra> traits::ExtrinsicMetadata for TestXt<Call, Extra>
 where
 	Call: Codec + Sync + Send,
-	Extra: SignedExtension<AccountId = u64, Call = Call>,
+	Extra: TransactionExtensionBase,
 {
-	type SignedExtensions = Extra;
-	const VERSION: u8 = 0u8;
+	type TransactionExtensions = Extra;
+	const VERSIONS: &'static [u8] = &[0u8];
 }
 
 impl<Origin, Call, Extra> Applyable for TestXt<Call, Extra>
@@ -379,8 +388,8 @@ where
 		+ Codec
 		+ Debug
 		+ Dispatchable<RuntimeOrigin = Origin>,
-	Extra: SignedExtension<AccountId = u64, Call = Call>,
-	Origin: From<Option<u64>>,
+	Extra: TransactionExtension<Call>,
+	Origin: From<Option<u64>> + AsTransactionAuthorizedOrigin,
 {
 	type Call = Call;
 
@@ -392,9 +401,9 @@ where
 		len: usize,
 	) -> TransactionValidity {
 		if let Some((ref id, ref extra)) = self.signature {
-			Extra::validate(extra, id, &self.call, info, len)
+			extra.validate_only(Some(*id).into(), &self.call, info, len).map(|x| x.0)
 		} else {
-			let valid = Extra::validate_unsigned(&self.call, info, len)?;
+			let valid = Extra::bare_validate(&self.call, info, len)?;
 			let unsigned_validation = U::validate_unsigned(source, &self.call)?;
 			Ok(valid.combine_with(unsigned_validation))
 		}
@@ -407,15 +416,12 @@ where
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> ApplyExtrinsicResultWithInfo<PostDispatchInfoOf<Self::Call>> {
-		let maybe_who = if let Some((who, extra)) = self.signature {
-			Extra::pre_dispatch(extra, &who, &self.call, info, len)?;
-			Some(who)
+		if let Some((who, extra)) = self.signature {
+			extra.dispatch_transaction(Some(who).into(), self.call, info, len)
 		} else {
-			Extra::pre_dispatch_unsigned(&self.call, info, len)?;
+			Extra::bare_validate_and_prepare(&self.call, info, len)?;
 			U::pre_dispatch(&self.call)?;
-			None
-		};
-
-		Ok(self.call.dispatch(maybe_who.into()))
+			Ok(self.call.dispatch(None.into()))
+		}
 	}
 }
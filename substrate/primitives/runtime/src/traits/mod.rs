@@ -52,6 +52,12 @@ use std::fmt::Display;
 #[cfg(feature = "std")]
 use std::str::FromStr;
 
+mod transaction_extension;
+pub use transaction_extension::{
+	AsSystemOriginSigner, AsTransactionAuthorizedOrigin, DispatchOriginOf, DispatchTransaction,
+	TransactionExtension, TransactionExtensionBase, TransactionExtensionMetadata, ValidateResult,
+};
+
 /// A lazy value.
 pub trait Lazy<T: ?Sized> {
 	/// Get a reference to the underlying value.
@@ -1327,6 +1333,12 @@ pub trait Extrinsic: Sized {
 		None
 	}
 
+	/// Is this `Extrinsic` bare, i.e. neither signed nor carrying transaction extensions?
+	///
+	/// Inherents and unsigned transactions are bare. Implementors which cannot tell should return
+	/// `false`, so that the extrinsic is not mistaken for an inherent.
+	fn is_bare(&self) -> bool;
+
 	/// Create new instance of the extrinsic.
 	///
 	/// Extrinsics can be split into:
@@ -1365,13 +1377,13 @@ impl SignaturePayload for () {
 
 /// Implementor is an [`Extrinsic`] and provides metadata about this extrinsic.
 pub trait ExtrinsicMetadata {
-	/// The format version of the `Extrinsic`.
+	/// The format versions of the `Extrinsic`.
 	///
 	/// By format is meant the encoded representation of the `Extrinsic`.
-	const VERSION: u8;
+	const VERSIONS: &'static [u8];
 
-	/// Signed extensions attached to this `Extrinsic`.
-	type SignedExtensions: SignedExtension;
+	/// Transaction extensions attached to this `Extrinsic`.
+	type TransactionExtensions: TransactionExtensionBase;
 }
 
 /// Extract the hashing type for a block.
@@ -1476,6 +1488,10 @@ impl Dispatchable for () {
 
 /// Means by which a transaction may be extended. This type embodies both the data and the logic
 /// that should be additionally associated with the transaction. It should be plain old data.
+///
+/// Every signed extension is also a [`TransactionExtension`], whose signed logic is used when the
+/// origin of the transaction is signed. New extensions should implement [`TransactionExtension`]
+/// directly.
 pub trait SignedExtension:
 	Codec + Debug + Sync + Send + Clone + Eq + PartialEq + StaticTypeInfo
 {
@@ -1617,105 +1633,6 @@ pub struct SignedExtensionMetadata {
 	pub additional_signed: MetaType,
 }
 
-#[impl_for_tuples(1, 12)]
-impl<AccountId, Call: Dispatchable> SignedExtension for Tuple {
-	for_tuples!( where #( Tuple: SignedExtension<AccountId=AccountId, Call=Call,> )* );
-	type AccountId = AccountId;
-	type Call = Call;
-	const IDENTIFIER: &'static str = "You should call `identifier()`!";
-	for_tuples!( type AdditionalSigned = ( #( Tuple::AdditionalSigned ),* ); );
-	for_tuples!( type Pre = ( #( Tuple::Pre ),* ); );
-
-	fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
-		Ok(for_tuples!( ( #( Tuple.additional_signed()? ),* ) ))
-	}
-
-	fn validate(
-		&self,
-		who: &Self::AccountId,
-		call: &Self::Call,
-		info: &DispatchInfoOf<Self::Call>,
-		len: usize,
-	) -> TransactionValidity {
-		let valid = ValidTransaction::default();
-		for_tuples!( #( let valid = valid.combine_with(Tuple.validate(who, call, info, len)?); )* );
-		Ok(valid)
-	}
-
-	fn pre_dispatch(
-		self,
-		who: &Self::AccountId,
-		call: &Self::Call,
-		info: &DispatchInfoOf<Self::Call>,
-		len: usize,
-	) -> Result<Self::Pre, TransactionValidityError> {
-		Ok(for_tuples!( ( #( Tuple.pre_dispatch(who, call, info, len)? ),* ) ))
-	}
-
-	fn validate_unsigned(
-		call: &Self::Call,
-		info: &DispatchInfoOf<Self::Call>,
-		len: usize,
-	) -> TransactionValidity {
-		let valid = ValidTransaction::default();
-		for_tuples!( #( let valid = valid.combine_with(Tuple::validate_unsigned(call, info, len)?); )* );
-		Ok(valid)
-	}
-
-	fn pre_dispatch_unsigned(
-		call: &Self::Call,
-		info: &DispatchInfoOf<Self::Call>,
-		len: usize,
-	) -> Result<(), TransactionValidityError> {
-		for_tuples!( #( Tuple::pre_dispatch_unsigned(call, info, len)?; )* );
-		Ok(())
-	}
-
-	fn post_dispatch(
-		pre: Option<Self::Pre>,
-		info: &DispatchInfoOf<Self::Call>,
-		post_info: &PostDispatchInfoOf<Self::Call>,
-		len: usize,
-		result: &DispatchResult,
-	) -> Result<(), TransactionValidityError> {
-		match pre {
-			Some(x) => {
-				for_tuples!( #( Tuple::post_dispatch(Some(x.Tuple), info, post_info, len, result)?; )* );
-			},
-			None => {
-				for_tuples!( #( Tuple::post_dispatch(None, info, post_info, len, result)?; )* );
-			},
-		}
-		Ok(())
-	}
-
-	fn metadata() -> Vec<SignedExtensionMetadata> {
-		let mut ids = Vec::new();
-		for_tuples!( #( ids.extend(Tuple::metadata()); )* );
-		ids
-	}
-}
-
-impl SignedExtension for () {
-	type AccountId = u64;
-	type AdditionalSigned = ();
-	type Call = ();
-	type Pre = ();
-	const IDENTIFIER: &'static str = "UnitSignedExtension";
-	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
-		Ok(())
-	}
-	fn pre_dispatch(
-		self,
-		who: &Self::AccountId,
-		call: &Self::Call,
-		info: &DispatchInfoOf<Self::Call>,
-		len: usize,
-	) -> Result<Self::Pre, TransactionValidityError> {
-		self.validate(who, call, info, len).map(|_| ())
-	}
-}
-
 /// An "executable" piece of information, used by the standard Substrate Executive in order to
 /// enact a piece of extrinsic information by marshalling and dispatching to a named function
 /// call.
@@ -0,0 +1,589 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The transaction extension trait.
+
+use crate::{
+	scale_info::{MetaType, StaticTypeInfo, TypeInfo},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+	DispatchResult,
+};
+use codec::{Codec, Encode, Output};
+use impl_trait_for_tuples::impl_for_tuples;
+use sp_std::{fmt::Debug, prelude::*};
+
+use super::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension};
+
+/// Shortcut to reference the `RuntimeOrigin` type of a `Dispatchable`.
+pub type DispatchOriginOf<T> = <T as Dispatchable>::RuntimeOrigin;
+
+/// The result of [`TransactionExtension::validate`]: the validity of the transaction, the value
+/// to pass on to [`TransactionExtension::prepare`] and the (possibly transformed) origin.
+pub type ValidateResult<Val, Call> =
+	Result<(ValidTransaction, Val, DispatchOriginOf<Call>), TransactionValidityError>;
+
+/// An origin which may be the origin of a plain signed transaction.
+pub trait AsSystemOriginSigner<AccountId> {
+	/// Return a reference to the account of the signer, if this is a signed origin.
+	fn as_system_origin_signer(&self) -> Option<&AccountId>;
+}
+
+/// An origin which tells whether a transaction was authorized to be dispatched with it.
+pub trait AsTransactionAuthorizedOrigin {
+	/// Whether the origin is authorized to dispatch a transaction.
+	///
+	/// The origin of a general transaction must be authorized by one of its transaction
+	/// extensions, typically by replacing the initial "none" origin, otherwise the transaction is
+	/// invalid.
+	fn is_transaction_authorized(&self) -> bool;
+}
+
+/// Information about a [`TransactionExtensionBase`] for the runtime metadata.
+pub struct TransactionExtensionMetadata {
+	/// The unique identifier of the transaction extension.
+	pub identifier: &'static str,
+	/// The type of the transaction extension.
+	pub ty: MetaType,
+	/// The type of the implicit data of the transaction extension.
+	pub implicit: MetaType,
+}
+
+/// The part of a transaction extension which does not depend on the call type.
+///
+/// This type embodies the data of the extension which is part of the transaction, together with
+/// the data which is implied by the extension but not part of the transaction.
+pub trait TransactionExtensionBase:
+	Codec + Debug + Sync + Send + Clone + Eq + PartialEq + StaticTypeInfo
+{
+	/// Unique identifier of this transaction extension.
+	///
+	/// This will be exposed in the metadata to identify the transaction extension used in an
+	/// extrinsic.
+	const IDENTIFIER: &'static str;
+
+	/// Any additional data which was known at the time of transaction construction and can be
+	/// useful in authenticating the transaction. This is part of the signed payload of a signed
+	/// transaction and is passed on to the extensions preceding this one in a pipeline.
+	type Implicit: Encode + TypeInfo;
+
+	/// Determine the implicit data of this transaction extension. Can also perform any pre-checks
+	/// and return an error if needed.
+	fn implicit(&self) -> Result<Self::Implicit, TransactionValidityError>;
+
+	/// Returns the metadata for this transaction extension.
+	///
+	/// As a [`TransactionExtensionBase`] can be a tuple of [`TransactionExtensionBase`]s we need
+	/// to return a `Vec` that holds the metadata of each one. Each individual extension must
+	/// return *exactly* one [`TransactionExtensionMetadata`].
+	///
+	/// This method provides a default implementation that returns a vec containing a single
+	/// [`TransactionExtensionMetadata`].
+	fn metadata() -> Vec<TransactionExtensionMetadata> {
+		sp_std::vec![TransactionExtensionMetadata {
+			identifier: Self::IDENTIFIER,
+			ty: scale_info::meta_type::<Self>(),
+			implicit: scale_info::meta_type::<Self::Implicit>()
+		}]
+	}
+}
+
+/// Means by which a transaction may be extended. This type embodies both the data and the logic
+/// that should be additionally associated with the transaction. It should be plain old data.
+///
+/// Unlike a [`SignedExtension`], a transaction extension is not tied to a signed origin: it
+/// receives the origin of the transaction and may transform it, e.g. to authorize a general
+/// transaction from a proof carried by the extension. Extensions are executed in a pipeline: each
+/// one receives the origin returned by the previous one.
+///
+/// The lifecycle of a transaction is:
+/// - [`Self::validate`], when the transaction is validated for the transaction pool and again
+///   before it is applied;
+/// - [`Self::prepare`], right before dispatch, with the value returned by [`Self::validate`];
+/// - [`Self::post_dispatch`], after dispatch, with the value returned by [`Self::prepare`].
+///
+/// Bare extrinsics (inherents and unsigned transactions) go through [`Self::bare_validate`],
+/// [`Self::bare_validate_and_prepare`] and [`Self::bare_post_dispatch`] instead.
+pub trait TransactionExtension<Call: Dispatchable>: TransactionExtensionBase {
+	/// The type that encodes information that can be passed from `validate` to `prepare`.
+	type Val;
+
+	/// The type that encodes information that can be passed from `prepare` to `post_dispatch`.
+	type Pre;
+
+	/// Validate a transaction for the transaction queue.
+	///
+	/// This function can be called frequently by the transaction queue to obtain transaction
+	/// validity against current state. It should perform all checks that determine a valid
+	/// transaction, that can pay for its execution and quickly eliminate ones that are stale or
+	/// incorrect.
+	///
+	/// The `inherited_implication` is the data which the transaction implies and which is not
+	/// already covered by this extension: the call, followed by the explicit and implicit data of
+	/// the extensions which come after this one in the pipeline. An extension which authorizes a
+	/// transaction with a proof should have this proof commit to `inherited_implication`, as well
+	/// as to its own explicit and implicit data.
+	///
+	/// Returns the validity of the transaction, a value to be passed on to [`Self::prepare`] and
+	/// the origin to be passed on to the next extension in the pipeline.
+	fn validate(
+		&self,
+		origin: DispatchOriginOf<Call>,
+		call: &Call,
+		info: &DispatchInfoOf<Call>,
+		len: usize,
+		inherited_implication: &impl Encode,
+	) -> ValidateResult<Self::Val, Call>;
+
+	/// Do any pre-flight stuff for a transaction after validation.
+	///
+	/// This is for actions which do not happen in the transaction queue but only immediately
+	/// prior to dispatch. The `origin` is the one resulting from the whole validation pipeline.
+	fn prepare(
+		self,
+		val: Self::Val,
+		origin: &DispatchOriginOf<Call>,
+		call: &Call,
+		info: &DispatchInfoOf<Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError>;
+
+	/// Do any post-flight stuff for a transaction.
+	///
+	/// This gets given the `DispatchResult` `_result` from the extrinsic and can, if desired,
+	/// introduce a `TransactionValidityError`, causing the block to become invalid for including
+	/// it.
+	///
+	/// WARNING: It is dangerous to return an error here. To do so will fundamentally invalidate
+	/// the transaction and any block that it is included in, causing the block author to not be
+	/// compensated for their work in validating the transaction or producing the block so far.
+	fn post_dispatch(
+		_pre: Self::Pre,
+		_info: &DispatchInfoOf<Call>,
+		_post_info: &PostDispatchInfoOf<Call>,
+		_len: usize,
+		_result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	/// Validate a bare extrinsic (an inherent or an unsigned transaction) for the transaction
+	/// queue.
+	///
+	/// Make sure to perform the same checks in [`Self::bare_validate_and_prepare`].
+	fn bare_validate(
+		_call: &Call,
+		_info: &DispatchInfoOf<Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		Ok(ValidTransaction::default())
+	}
+
+	/// Do any pre-flight stuff for a bare extrinsic.
+	///
+	/// Note this function by default delegates to [`Self::bare_validate`], so that all checks
+	/// performed for the transaction queue are also performed during the dispatch phase (applying
+	/// the extrinsic).
+	fn bare_validate_and_prepare(
+		call: &Call,
+		info: &DispatchInfoOf<Call>,
+		len: usize,
+	) -> Result<(), TransactionValidityError> {
+		Self::bare_validate(call, info, len).map(|_| ())
+	}
+
+	/// Do any post-flight stuff for a bare extrinsic.
+	///
+	/// The same warning as for [`Self::post_dispatch`] applies.
+	fn bare_post_dispatch(
+		_info: &DispatchInfoOf<Call>,
+		_post_info: &PostDispatchInfoOf<Call>,
+		_len: usize,
+		_result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+}
+
+/// The implication inherited by an extension of a tuple: the implication of the tuple itself,
+/// followed by the explicit and implicit data of the subsequent extensions of the tuple.
+struct ImplicationParts<'a, Base> {
+	base: &'a Base,
+	explicit: &'a [Vec<u8>],
+	implicit: &'a [Vec<u8>],
+}
+
+impl<Base: Encode> Encode for ImplicationParts<'_, Base> {
+	fn size_hint(&self) -> usize {
+		self.base.size_hint() +
+			self.explicit.iter().chain(self.implicit).map(|part| part.len()).sum::<usize>()
+	}
+
+	fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+		self.base.encode_to(dest);
+		self.explicit.iter().chain(self.implicit).for_each(|part| dest.write(part));
+	}
+}
+
+#[impl_for_tuples(1, 12)]
+impl TransactionExtensionBase for Tuple {
+	for_tuples!( where #( Tuple: TransactionExtensionBase )* );
+	const IDENTIFIER: &'static str = "Use `metadata()`!";
+	for_tuples!( type Implicit = ( #( Tuple::Implicit ),* ); );
+
+	fn implicit(&self) -> Result<Self::Implicit, TransactionValidityError> {
+		Ok(for_tuples!( ( #( Tuple.implicit()? ),* ) ))
+	}
+
+	fn metadata() -> Vec<TransactionExtensionMetadata> {
+		let mut ids = Vec::new();
+		for_tuples!( #( ids.extend(Tuple::metadata()); )* );
+		ids
+	}
+}
+
+#[impl_for_tuples(1, 12)]
+impl<Call: Dispatchable> TransactionExtension<Call> for Tuple {
+	for_tuples!( where #( Tuple: TransactionExtension<Call> )* );
+	for_tuples!( type Val = ( #( Tuple::Val ),* ); );
+	for_tuples!( type Pre = ( #( Tuple::Pre ),* ); );
+
+	fn validate(
+		&self,
+		origin: DispatchOriginOf<Call>,
+		call: &Call,
+		info: &DispatchInfoOf<Call>,
+		len: usize,
+		inherited_implication: &impl Encode,
+	) -> ValidateResult<Self::Val, Call> {
+		let mut explicit = Vec::new();
+		for_tuples!( #( explicit.push(Tuple.encode()); )* );
+		let mut implicit = Vec::new();
+		for_tuples!( #( implicit.push(Tuple.implicit()?.encode()); )* );
+
+		let mut valid = ValidTransaction::default();
+		let mut origin = origin;
+		let mut index = 0;
+		let val = for_tuples!( ( #( {
+			index += 1;
+			let implication = ImplicationParts {
+				base: inherited_implication,
+				explicit: &explicit[index..],
+				implicit: &implicit[index..],
+			};
+			let (item_valid, item_val, item_origin) =
+				Tuple.validate(origin, call, info, len, &implication)?;
+			valid = valid.combine_with(item_valid);
+			origin = item_origin;
+			item_val
+		} ),* ) );
+
+		Ok((valid, val, origin))
+	}
+
+	fn prepare(
+		self,
+		val: Self::Val,
+		origin: &DispatchOriginOf<Call>,
+		call: &Call,
+		info: &DispatchInfoOf<Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		Ok(
+			for_tuples!( ( #( Tuple::prepare(self.Tuple, val.Tuple, origin, call, info, len)? ),* ) ),
+		)
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		info: &DispatchInfoOf<Call>,
+		post_info: &PostDispatchInfoOf<Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		for_tuples!( #( Tuple::post_dispatch(pre.Tuple, info, post_info, len, result)?; )* );
+		Ok(())
+	}
+
+	fn bare_validate(call: &Call, info: &DispatchInfoOf<Call>, len: usize) -> TransactionValidity {
+		let valid = ValidTransaction::default();
+		for_tuples!( #( let valid = valid.combine_with(Tuple::bare_validate(call, info, len)?); )* );
+		Ok(valid)
+	}
+
+	fn bare_validate_and_prepare(
+		call: &Call,
+		info: &DispatchInfoOf<Call>,
+		len: usize,
+	) -> Result<(), TransactionValidityError> {
+		for_tuples!( #( Tuple::bare_validate_and_prepare(call, info, len)?; )* );
+		Ok(())
+	}
+
+	fn bare_post_dispatch(
+		info: &DispatchInfoOf<Call>,
+		post_info: &PostDispatchInfoOf<Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		for_tuples!( #( Tuple::bare_post_dispatch(info, post_info, len, result)?; )* );
+		Ok(())
+	}
+}
+
+impl TransactionExtensionBase for () {
+	const IDENTIFIER: &'static str = "UnitTransactionExtension";
+	type Implicit = ();
+
+	fn implicit(&self) -> Result<Self::Implicit, TransactionValidityError> {
+		Ok(())
+	}
+}
+
+impl<Call: Dispatchable> TransactionExtension<Call> for () {
+	type Val = ();
+	type Pre = ();
+
+	fn validate(
+		&self,
+		origin: DispatchOriginOf<Call>,
+		_call: &Call,
+		_info: &DispatchInfoOf<Call>,
+		_len: usize,
+		_inherited_implication: &impl Encode,
+	) -> ValidateResult<Self::Val, Call> {
+		Ok((ValidTransaction::default(), (), origin))
+	}
+
+	fn prepare(
+		self,
+		_val: Self::Val,
+		_origin: &DispatchOriginOf<Call>,
+		_call: &Call,
+		_info: &DispatchInfoOf<Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		Ok(())
+	}
+}
+
+/// Every [`SignedExtension`] is a [`TransactionExtensionBase`], with its additional signed data
+/// as implicit data.
+impl<SE: SignedExtension> TransactionExtensionBase for SE {
+	const IDENTIFIER: &'static str = SE::IDENTIFIER;
+	type Implicit = SE::AdditionalSigned;
+
+	fn implicit(&self) -> Result<Self::Implicit, TransactionValidityError> {
+		self.additional_signed()
+	}
+
+	fn metadata() -> Vec<TransactionExtensionMetadata> {
+		SE::metadata()
+			.into_iter()
+			.map(|metadata| TransactionExtensionMetadata {
+				identifier: metadata.identifier,
+				ty: metadata.ty,
+				implicit: metadata.additional_signed,
+			})
+			.collect()
+	}
+}
+
+/// Every [`SignedExtension`] is a [`TransactionExtension`].
+///
+/// The signed logic of the extension is used for transactions whose origin is signed when it
+/// reaches the extension, the unsigned logic of the extension is used otherwise.
+///
+/// A [`SignedExtension`] does not expect its validation logic to be executed right before its
+/// pre-dispatch logic, so any storage changes made while validating are discarded.
+impl<SE: SignedExtension> TransactionExtension<SE::Call> for SE
+where
+	DispatchOriginOf<SE::Call>: AsSystemOriginSigner<SE::AccountId>,
+{
+	type Val = ();
+	type Pre = Option<SE::Pre>;
+
+	fn validate(
+		&self,
+		origin: DispatchOriginOf<SE::Call>,
+		call: &SE::Call,
+		info: &DispatchInfoOf<SE::Call>,
+		len: usize,
+		_inherited_implication: &impl Encode,
+	) -> ValidateResult<Self::Val, SE::Call> {
+		sp_io::storage::start_transaction();
+		let valid = match origin.as_system_origin_signer() {
+			Some(who) => SignedExtension::validate(self, who, call, info, len),
+			None => SE::validate_unsigned(call, info, len),
+		};
+		sp_io::storage::rollback_transaction();
+		Ok((valid?, (), origin))
+	}
+
+	fn prepare(
+		self,
+		_val: Self::Val,
+		origin: &DispatchOriginOf<SE::Call>,
+		call: &SE::Call,
+		info: &DispatchInfoOf<SE::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		match origin.as_system_origin_signer() {
+			Some(who) => SignedExtension::pre_dispatch(self, who, call, info, len).map(Some),
+			None => SE::pre_dispatch_unsigned(call, info, len).map(|_| None),
+		}
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		info: &DispatchInfoOf<SE::Call>,
+		post_info: &PostDispatchInfoOf<SE::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		SE::post_dispatch(pre, info, post_info, len, result)
+	}
+
+	fn bare_validate(
+		call: &SE::Call,
+		info: &DispatchInfoOf<SE::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		SE::validate_unsigned(call, info, len)
+	}
+
+	fn bare_validate_and_prepare(
+		call: &SE::Call,
+		info: &DispatchInfoOf<SE::Call>,
+		len: usize,
+	) -> Result<(), TransactionValidityError> {
+		SE::pre_dispatch_unsigned(call, info, len)
+	}
+
+	fn bare_post_dispatch(
+		info: &DispatchInfoOf<SE::Call>,
+		post_info: &PostDispatchInfoOf<SE::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		SE::post_dispatch(None, info, post_info, len, result)
+	}
+}
+
+/// Single-shot transaction validation and dispatch through a [`TransactionExtension`] pipeline.
+///
+/// This is implemented for every [`TransactionExtension`]; the implication of the pipeline is the
+/// call itself.
+pub trait DispatchTransaction<Call: Dispatchable> {
+	/// The origin type of the transaction.
+	type Origin;
+	/// The info type.
+	type Info;
+	/// The resulting type.
+	type Result;
+	/// The `Val` of the extension.
+	type Val;
+	/// The `Pre` of the extension.
+	type Pre;
+
+	/// Just validate a transaction.
+	///
+	/// This is basically the same as [`validate`](TransactionExtension::validate), except that
+	/// there is no need to supply the implication. The resulting origin must be authorized.
+	fn validate_only(
+		&self,
+		origin: Self::Origin,
+		call: &Call,
+		info: &Self::Info,
+		len: usize,
+	) -> Result<(ValidTransaction, Self::Val, Self::Origin), TransactionValidityError>;
+
+	/// Validate and prepare a transaction, ready for dispatch.
+	fn validate_and_prepare(
+		self,
+		origin: Self::Origin,
+		call: &Call,
+		info: &Self::Info,
+		len: usize,
+	) -> Result<(Self::Pre, Self::Origin), TransactionValidityError>;
+
+	/// Validate, prepare, dispatch and post-dispatch a transaction.
+	fn dispatch_transaction(
+		self,
+		origin: Self::Origin,
+		call: Call,
+		info: &Self::Info,
+		len: usize,
+	) -> Self::Result;
+}
+
+impl<T, Call> DispatchTransaction<Call> for T
+where
+	T: TransactionExtension<Call>,
+	Call: Dispatchable + Encode,
+	DispatchOriginOf<Call>: AsTransactionAuthorizedOrigin,
+{
+	type Origin = DispatchOriginOf<Call>;
+	type Info = DispatchInfoOf<Call>;
+	type Result = crate::ApplyExtrinsicResultWithInfo<PostDispatchInfoOf<Call>>;
+	type Val = T::Val;
+	type Pre = T::Pre;
+
+	fn validate_only(
+		&self,
+		origin: Self::Origin,
+		call: &Call,
+		info: &Self::Info,
+		len: usize,
+	) -> Result<(ValidTransaction, T::Val, Self::Origin), TransactionValidityError> {
+		let (valid, val, origin) = self.validate(origin, call, info, len, call)?;
+		if !origin.is_transaction_authorized() {
+			return Err(InvalidTransaction::BadSigner.into())
+		}
+		Ok((valid, val, origin))
+	}
+
+	fn validate_and_prepare(
+		self,
+		origin: Self::Origin,
+		call: &Call,
+		info: &Self::Info,
+		len: usize,
+	) -> Result<(T::Pre, Self::Origin), TransactionValidityError> {
+		let (_, val, origin) = self.validate_only(origin, call, info, len)?;
+		let pre = self.prepare(val, &origin, call, info, len)?;
+		Ok((pre, origin))
+	}
+
+	fn dispatch_transaction(
+		self,
+		origin: Self::Origin,
+		call: Call,
+		info: &Self::Info,
+		len: usize,
+	) -> Self::Result {
+		let (pre, origin) = self.validate_and_prepare(origin, &call, info, len)?;
+		let res = call.dispatch(origin);
+		let post_info = match res {
+			Ok(info) => info,
+			Err(err) => err.post_info,
+		};
+		T::post_dispatch(pre, info, &post_info, len, &res.map(|_| ()).map_err(|e| e.error))?;
+		Ok(res)
+	}
+}
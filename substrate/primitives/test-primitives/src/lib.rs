@@ -57,6 +57,10 @@ impl ExtrinsicT for Extrinsic {
 		}
 	}
 
+	fn is_bare(&self) -> bool {
+		matches!(self, Extrinsic::IncludeData(_))
+	}
+
 	fn new(call: Self::Call, _signature_payload: Option<Self::SignaturePayload>) -> Option<Self> {
 		Some(call)
 	}
@@ -721,6 +721,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl sp_genesis_builder::GenesisBuilder<Block> for Runtime {
 		fn create_default_config() -> Vec<u8> {
 			create_default_config::<RuntimeGenesisConfig>()
@@ -729,9 +730,48 @@ impl_runtime_apis! {
 		fn build_config(config: Vec<u8>) -> sp_genesis_builder::Result {
 			build_config::<RuntimeGenesisConfig>(config)
 		}
+
+		fn get_preset(id: &sp_genesis_builder::PresetId) -> Option<Vec<u8>> {
+			genesis_config_preset(id).map(|patch| {
+				serde_json::to_vec(&patch).expect("serialization to json is expected to work. qed.")
+			})
+		}
+
+		fn preset_names() -> Vec<sp_genesis_builder::PresetId> {
+			vec![
+				sp_genesis_builder::PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET),
+				sp_genesis_builder::PresetId::from(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET),
+			]
+		}
 	}
 }
 
+/// Returns the patch of the default `RuntimeGenesisConfig` for the preset with the given `id`.
+fn genesis_config_preset(id: &sp_genesis_builder::PresetId) -> Option<serde_json::Value> {
+	// Alice and Bob from the well-known development keyring.
+	const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+	const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+
+	let authorities = match core::str::from_utf8(id.as_ref()).ok()? {
+		sp_genesis_builder::DEV_RUNTIME_PRESET => vec![ALICE],
+		sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET => vec![ALICE, BOB],
+		_ => return None,
+	};
+
+	Some(serde_json::json!({
+		"babe": {
+			"authorities": authorities.iter().map(|a| (a, 1)).collect::<Vec<_>>(),
+			"epochConfig": {
+				"c": [3, 10],
+				"allowed_slots": "PrimaryAndSecondaryPlainSlots"
+			}
+		},
+		"substrateTest": {
+			"authorities": authorities,
+		}
+	}))
+}
+
 fn test_ed25519_crypto() -> (ed25519::AppSignature, ed25519::AppPublic) {
 	let public0 = ed25519::AppPublic::generate_pair(None);
 	let public1 = ed25519::AppPublic::generate_pair(None);
@@ -1315,6 +1355,36 @@ mod tests {
 			assert_eq!(keys, storage_key_generator::get_expected_storage_hashed_keys(false));
 		}
 
+		#[test]
+		fn preset_names_works() {
+			sp_tracing::try_init_simple();
+			let mut t = BasicExternalities::new_empty();
+			let r = executor_call(&mut t, "GenesisBuilder_preset_names", &vec![]).unwrap();
+			let r = Vec::<String>::decode(&mut &r[..]).unwrap();
+			assert_eq!(r, vec!["development".to_string(), "local_testnet".to_string()]);
+		}
+
+		#[test]
+		fn get_preset_works() {
+			sp_tracing::try_init_simple();
+			let mut t = BasicExternalities::new_empty();
+			let r = executor_call(&mut t, "GenesisBuilder_get_preset", &"local_testnet".encode())
+				.unwrap();
+			let r = Option::<Vec<u8>>::decode(&mut &r[..]).unwrap().unwrap();
+			let patch = serde_json::from_slice::<serde_json::Value>(&r).unwrap();
+			assert_eq!(
+				patch["substrateTest"]["authorities"],
+				json!([
+					AccountKeyring::Alice.public().to_ss58check(),
+					AccountKeyring::Bob.public().to_ss58check()
+				])
+			);
+
+			let r =
+				executor_call(&mut t, "GenesisBuilder_get_preset", &"unknown".encode()).unwrap();
+			assert_eq!(Option::<Vec<u8>>::decode(&mut &r[..]).unwrap(), None);
+		}
+
 		#[test]
 		fn build_config_from_invalid_json_fails() {
 			sp_tracing::try_init_simple();